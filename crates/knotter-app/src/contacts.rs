@@ -0,0 +1,238 @@
+use crate::error::{AppError, Result};
+use chrono::FixedOffset;
+use knotter_core::domain::ContactId;
+use knotter_core::dto::{
+    AttachmentDto, ContactDateDto, ContactDetailDto, ContactListItemDto, ContactOriginDto,
+    FieldProvenanceDto, InteractionDto, SyncHistoryDto,
+};
+use knotter_core::filter::{parse_filter, ArchivedSelector, ReplySelector};
+use knotter_store::query::{ContactListQuery, ContactQuery, ContactSort, ListPage};
+use knotter_store::repo::InteractionRange;
+use knotter_store::Store;
+
+/// Which contacts a listing covers when the filter has no `archived:` term.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ArchivedScope {
+    #[default]
+    Active,
+    Include,
+    Only,
+}
+
+/// Options for [`list`]; `ListOptions::new` matches a bare `knotter list`.
+#[derive(Debug, Clone)]
+pub struct ListOptions {
+    /// Filter text in the `#tag due:soon archived:true ...` syntax; may be empty.
+    pub filter: String,
+    pub archived: ArchivedScope,
+    pub sort: ContactSort,
+    /// Only contacts whose latest imported message is inbound and unanswered.
+    pub awaiting_reply: bool,
+    /// Minimum age in days of the unanswered inbound message.
+    pub awaiting_reply_days: Option<i64>,
+    /// Width of the "soon" window after today, in days.
+    pub soon_days: i64,
+    /// One page of the sorted results; `None` returns them all.
+    pub page: Option<ListPage>,
+}
+
+impl ListOptions {
+    pub fn new(soon_days: i64) -> Self {
+        Self {
+            filter: String::new(),
+            archived: ArchivedScope::default(),
+            sort: ContactSort::default(),
+            awaiting_reply: false,
+            awaiting_reply_days: None,
+            soon_days,
+            page: None,
+        }
+    }
+
+    /// Resolves the options into the store's list query.
+    pub fn to_query(&self) -> Result<ContactListQuery> {
+        let parsed = parse_filter(&self.filter)?;
+        let mut query = ContactListQuery::new(self.soon_days);
+        query.archived = match self.archived {
+            ArchivedScope::Active => Some(ArchivedSelector::Active),
+            ArchivedScope::Include => None,
+            ArchivedScope::Only => {
+                if ContactQuery::from_filter(&parsed)?.archived == Some(ArchivedSelector::Active) {
+                    return Err(AppError::InvalidInput(
+                        "archived:false conflicts with listing only archived contacts".to_string(),
+                    ));
+                }
+                Some(ArchivedSelector::Archived)
+            }
+        };
+        query.filter = Some(parsed);
+        query.sort = self.sort;
+        query.page = self.page;
+        if self.awaiting_reply {
+            query.reply = Some(ReplySelector::Awaiting);
+        }
+        if let Some(days) = self.awaiting_reply_days {
+            if days < 0 {
+                return Err(AppError::InvalidInput(
+                    "awaiting reply days must be zero or greater".to_string(),
+                ));
+            }
+            query.awaiting_reply_days = Some(days);
+        }
+        Ok(query)
+    }
+}
+
+/// Contacts matching `options`, in the same order and with the same due
+/// buckets as `knotter list`.
+pub fn list(
+    store: &Store,
+    options: &ListOptions,
+    now_utc: i64,
+    local_offset: FixedOffset,
+) -> Result<Vec<ContactListItemDto>> {
+    let query = options.to_query()?;
+    Ok(store.contacts().list_view(&query, now_utc, local_offset)?)
+}
+
+/// Options for [`detail`]; the default lists every interaction and leaves out
+/// provenance and sync history.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DetailOptions {
+    /// Which interactions to page through, by `occurred_at`.
+    pub range: InteractionRange,
+    /// Interactions to include, newest first; `None` includes all of them.
+    pub limit: Option<i64>,
+    /// Newer interactions to skip.
+    pub offset: i64,
+    /// Include which source last set each field.
+    pub provenance: bool,
+    /// Include imports that changed the contact, newest first.
+    pub history: bool,
+}
+
+/// The contact as `knotter show` reports it, or `None` when there is no
+/// contact with this id.
+pub fn detail(
+    store: &Store,
+    id: ContactId,
+    options: &DetailOptions,
+    now_utc: i64,
+) -> Result<Option<ContactDetailDto>> {
+    if options.limit.is_some_and(|limit| limit < 0) || options.offset < 0 {
+        return Err(AppError::InvalidInput(
+            "interaction limit and offset must not be negative".to_string(),
+        ));
+    }
+    let Some(contact) = store.contacts().get(id)? else {
+        return Ok(None);
+    };
+
+    let interactions_repo = store.interactions();
+    let mut attachments = store.interaction_attachments().list_for_contact(id)?;
+    let recent_interactions = interactions_repo
+        .list_for_contact_in_range(id, options.range, options.limit, options.offset)?
+        .into_iter()
+        .map(|interaction| InteractionDto {
+            id: interaction.id,
+            occurred_at: interaction.occurred_at,
+            kind: interaction.kind.label(),
+            note: interaction.note,
+            follow_up_at: interaction.follow_up_at,
+            direction: interaction.direction,
+            attachments: attachments
+                .remove(&interaction.id)
+                .unwrap_or_default()
+                .into_iter()
+                .map(AttachmentDto::from)
+                .collect(),
+        })
+        .collect();
+    let interactions_total = interactions_repo.count_for_contact_in_range(id, options.range)?;
+
+    let dates = store
+        .contact_dates()
+        .list_for_contact(id)?
+        .into_iter()
+        .map(|date| ContactDateDto {
+            id: date.id,
+            kind: date.kind,
+            label: date.label,
+            month: date.month,
+            day: date.day,
+            year: date.year,
+            remind_days_before: date.remind_days_before,
+        })
+        .collect();
+    let provenance = if options.provenance {
+        store
+            .field_provenance()
+            .list_for_contact(id)?
+            .into_iter()
+            .map(|entry| FieldProvenanceDto {
+                field: entry.field,
+                source: entry.source,
+                updated_at: entry.updated_at,
+            })
+            .collect()
+    } else {
+        Vec::new()
+    };
+    let sync_history = if options.history {
+        store
+            .sync_log()
+            .list_for_contact(id)?
+            .into_iter()
+            .map(|entry| SyncHistoryDto {
+                source: entry.source,
+                fields: entry.fields,
+                synced_at: entry.synced_at,
+            })
+            .collect()
+    } else {
+        Vec::new()
+    };
+    let origin = match store.contact_origins().get(id)? {
+        Some(origin) => {
+            let met_via_name = match origin.met_via {
+                Some(via) => store.contacts().get(via)?.map(|via| via.display_name),
+                None => None,
+            };
+            Some(ContactOriginDto::from_origin(origin, met_via_name))
+        }
+        None => None,
+    };
+
+    Ok(Some(ContactDetailDto {
+        id: contact.id,
+        display_name: contact.display_name,
+        email: contact.email,
+        emails: store.emails().list_emails_for_contact(&id)?,
+        phone: contact.phone,
+        handle: contact.handle,
+        timezone: contact.timezone,
+        organization: contact.organization,
+        next_touchpoint_at: contact.next_touchpoint_at,
+        cadence_days: contact.cadence_days,
+        created_at: contact.created_at,
+        updated_at: contact.updated_at,
+        archived_at: contact.archived_at,
+        archive_reason: store.contacts().archive_reason(id)?,
+        muted_until: store.contacts().active_mute(id, now_utc)?,
+        priority: store.contacts().priority(id)?,
+        tags: store
+            .tags()
+            .list_for_contact(&id.to_string())?
+            .into_iter()
+            .map(|tag| tag.name.as_str().to_string())
+            .collect(),
+        aliases: store.contact_aliases().list_for_contact(id)?,
+        origin,
+        dates,
+        recent_interactions,
+        interactions_total,
+        provenance,
+        sync_history,
+        last_touch_context: store.last_touch().list_for_contact(id)?,
+    }))
+}
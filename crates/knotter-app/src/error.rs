@@ -0,0 +1,18 @@
+use knotter_core::filter::FilterParseError;
+use knotter_core::CoreError;
+use knotter_store::error::StoreError;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum AppError {
+    #[error(transparent)]
+    Store(#[from] StoreError),
+    #[error(transparent)]
+    Core(#[from] CoreError),
+    #[error(transparent)]
+    Filter(#[from] FilterParseError),
+    #[error("invalid input: {0}")]
+    InvalidInput(String),
+}
+
+pub type Result<T> = std::result::Result<T, AppError>;
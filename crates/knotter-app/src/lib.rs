@@ -0,0 +1,58 @@
+//! Command-layer orchestration shared by the CLI, for embedding knotter's
+//! data in other front ends without going through clap or the `knotter`
+//! binary.
+//!
+//! ```
+//! use knotter_app::{contacts, reminders};
+//! use knotter_config::AppConfig;
+//! use knotter_core::domain::TagName;
+//! use knotter_core::time::Clock;
+//! use knotter_store::repo::ContactNew;
+//! use knotter_store::Store;
+//!
+//! let store = Store::open_in_memory()?;
+//! store.migrate()?;
+//! let config = AppConfig::default();
+//! let clock = Clock::new(config.timezone, None);
+//! let now = clock.now_utc();
+//!
+//! let ada = store.contacts().create(
+//!     now,
+//!     ContactNew {
+//!         display_name: "Ada Lovelace".to_string(),
+//!         email: None,
+//!         phone: None,
+//!         handle: None,
+//!         timezone: None,
+//!         next_touchpoint_at: Some(now - 3 * 86_400),
+//!         cadence_days: None,
+//!         archived_at: None,
+//!         organization: None,
+//!     },
+//! )?;
+//! store
+//!     .tags()
+//!     .add_tag_to_contact(&ada.id.to_string(), TagName::new("friends")?)?;
+//!
+//! let options = reminders::ReminderOptions::from_config(&config);
+//! let output = reminders::compute(&store, &options, now, clock.local_offset())?;
+//! assert_eq!(output.overdue.len(), 1);
+//!
+//! let friends = contacts::list(
+//!     &store,
+//!     &contacts::ListOptions {
+//!         filter: "#friends due:overdue".to_string(),
+//!         ..contacts::ListOptions::new(config.due_soon_days)
+//!     },
+//!     now,
+//!     clock.local_offset(),
+//! )?;
+//! assert_eq!(friends[0].display_name, "Ada Lovelace");
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+
+pub mod contacts;
+pub mod error;
+pub mod reminders;
+
+pub use error::{AppError, Result};
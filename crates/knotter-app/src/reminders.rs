@@ -0,0 +1,166 @@
+use crate::error::Result;
+use chrono::{Duration, FixedOffset};
+use knotter_config::AppConfig;
+use knotter_core::dto::{DateReminderItemDto, FollowUpItemDto, ReminderDayDto, ReminderOutputDto};
+use knotter_core::filter::{ArchivedSelector, MutedSelector};
+use knotter_core::rules::{local_today, validate_soon_days, DueSelector};
+use knotter_store::query::{due_bounds, ContactListQuery};
+use knotter_store::repo::{ContactDateOccurrence, FollowUp};
+use knotter_store::Store;
+
+const WEEKLY_DIGEST_DAYS: i64 = 7;
+
+/// Options for [`compute`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReminderOptions {
+    /// Width of the "soon" window after today, in days.
+    pub soon_days: i64,
+    /// Also fill `week` with a per-day look-ahead for the next 7 days.
+    pub weekly_digest: bool,
+}
+
+impl ReminderOptions {
+    pub fn from_config(config: &AppConfig) -> Self {
+        Self {
+            soon_days: config.due_soon_days,
+            weekly_digest: false,
+        }
+    }
+}
+
+/// Everything `knotter remind` reports: due contacts, today's and upcoming
+/// contact dates, pending follow-ups, and optionally the weekly digest.
+/// Notification delivery and random picks are left to the caller.
+pub fn compute(
+    store: &Store,
+    options: &ReminderOptions,
+    now_utc: i64,
+    local_offset: FixedOffset,
+) -> Result<ReminderOutputDto> {
+    let soon_days = validate_soon_days(options.soon_days)?;
+
+    let mut items = Vec::new();
+    for due in [DueSelector::Overdue, DueSelector::Today, DueSelector::Soon] {
+        let query = ContactListQuery {
+            due: Some(due),
+            ..reminder_query(soon_days)
+        };
+        items.extend(store.contacts().list_view(&query, now_utc, local_offset)?);
+    }
+
+    let mut output = ReminderOutputDto::from_items(items);
+    output.dates_today = store
+        .contact_dates()
+        .list_today(now_utc, local_offset)?
+        .into_iter()
+        .map(date_item)
+        .collect();
+    output.dates_upcoming = store
+        .contact_dates()
+        .list_upcoming(now_utc, local_offset)?
+        .into_iter()
+        .map(|upcoming| DateReminderItemDto {
+            days_until: Some(upcoming.days_until),
+            ..date_item(upcoming.occurrence)
+        })
+        .collect();
+    output.follow_ups = follow_ups(store, soon_days, now_utc, local_offset)?;
+
+    if options.weekly_digest {
+        output.week = weekly_digest(store, now_utc, soon_days, local_offset)?;
+    }
+    Ok(output)
+}
+
+/// Follow-ups due before the end of the soon window, overdue ones included.
+pub fn follow_ups(
+    store: &Store,
+    soon_days: i64,
+    now_utc: i64,
+    local_offset: FixedOffset,
+) -> Result<Vec<FollowUpItemDto>> {
+    let soon_days = validate_soon_days(soon_days)?;
+    let until = due_bounds(now_utc, soon_days, local_offset).soon_end - 1;
+    Ok(store
+        .interactions()
+        .list_follow_ups(until)?
+        .into_iter()
+        .map(follow_up_item)
+        .collect())
+}
+
+/// Active, unmuted contacts in the shared list view; the same rows `list` shows.
+fn reminder_query(soon_days: i64) -> ContactListQuery {
+    ContactListQuery {
+        archived: Some(ArchivedSelector::Active),
+        muted: Some(MutedSelector::Unmuted),
+        ..ContactListQuery::new(soon_days)
+    }
+}
+
+fn date_item(item: ContactDateOccurrence) -> DateReminderItemDto {
+    DateReminderItemDto {
+        contact_id: item.contact_id,
+        display_name: item.display_name,
+        kind: item.kind,
+        label: item.label,
+        month: item.month,
+        day: item.day,
+        year: item.year,
+        days_until: None,
+        also_due: None,
+    }
+}
+
+fn follow_up_item(follow_up: FollowUp) -> FollowUpItemDto {
+    let interaction = follow_up.interaction;
+    FollowUpItemDto {
+        interaction_id: interaction.id,
+        contact_id: interaction.contact_id,
+        display_name: follow_up.display_name,
+        follow_up_at: interaction.follow_up_at.unwrap_or_default(),
+        kind: interaction.kind.label(),
+        note: interaction.note,
+    }
+}
+
+/// Groups touchpoints and contact dates by local day, starting today.
+fn weekly_digest(
+    store: &Store,
+    now_utc: i64,
+    soon_days: i64,
+    local_offset: FixedOffset,
+) -> Result<Vec<ReminderDayDto>> {
+    let today = local_today(now_utc, local_offset)?;
+    let query = ContactListQuery {
+        due: Some(DueSelector::Any),
+        ..reminder_query(soon_days)
+    };
+    let items = store.contacts().list_view(&query, now_utc, local_offset)?;
+
+    let mut week = Vec::with_capacity(WEEKLY_DIGEST_DAYS as usize);
+    for day_offset in 0..WEEKLY_DIGEST_DAYS {
+        let date = today + Duration::days(day_offset);
+        let contacts = items
+            .iter()
+            .filter(|item| {
+                item.next_touchpoint_at
+                    .and_then(|ts| local_today(ts, local_offset).ok())
+                    == Some(date)
+            })
+            .cloned()
+            .collect();
+        let dates = store
+            .contact_dates()
+            .list_for_day(date)?
+            .into_iter()
+            .map(date_item)
+            .collect();
+        week.push(ReminderDayDto {
+            date: date.format("%Y-%m-%d").to_string(),
+            contacts,
+            dates,
+        });
+    }
+    Ok(week)
+}
@@ -0,0 +1,115 @@
+use crate::commands::{print_json, resolve_contact_arg, Context};
+use crate::error::{invalid_input, not_found};
+use anyhow::Result;
+use clap::{Args, Subcommand};
+use knotter_core::domain::{normalize_alias, ContactId};
+use serde::Serialize;
+
+#[derive(Debug, Subcommand)]
+pub enum AliasCommand {
+    /// Add a nickname or alternate name used for matching and filters
+    Add(AliasAddArgs),
+    /// Remove an alias (matched ignoring case)
+    Rm(AliasRemoveArgs),
+    /// List a contact's aliases
+    Ls(AliasListArgs),
+}
+
+#[derive(Debug, Args)]
+pub struct AliasAddArgs {
+    /// Contact id, or a name, alias, email, or @handle
+    pub id: String,
+    pub alias: String,
+}
+
+#[derive(Debug, Args)]
+pub struct AliasRemoveArgs {
+    /// Contact id, or a name, alias, email, or @handle
+    pub id: String,
+    pub alias: String,
+}
+
+#[derive(Debug, Args)]
+pub struct AliasListArgs {
+    /// Contact id, or a name, alias, email, or @handle
+    pub id: String,
+}
+
+#[derive(Debug, Serialize)]
+struct ContactAliasesDto {
+    contact_id: ContactId,
+    aliases: Vec<String>,
+}
+
+pub fn add_alias(ctx: &Context<'_>, args: AliasAddArgs) -> Result<()> {
+    let id = resolve_contact_arg(ctx, &args.id)?;
+    let alias = parse_alias(&args.alias)?;
+    ensure_contact(ctx, id)?;
+    let added = ctx
+        .store
+        .contact_aliases()
+        .add(ctx.clock.now_utc(), id, &alias)?;
+
+    if ctx.json {
+        return print_json(&contact_aliases(ctx, id)?);
+    }
+    if added {
+        println!("alias {} added to {}", alias, id);
+    } else {
+        println!("{} already has alias {}", id, alias);
+    }
+    Ok(())
+}
+
+pub fn remove_alias(ctx: &Context<'_>, args: AliasRemoveArgs) -> Result<()> {
+    let id = resolve_contact_arg(ctx, &args.id)?;
+    let alias = parse_alias(&args.alias)?;
+    ensure_contact(ctx, id)?;
+    if !ctx.store.contact_aliases().remove(id, &alias)? {
+        return Err(not_found(format!(
+            "alias {alias} not found on contact {id}"
+        )));
+    }
+
+    if ctx.json {
+        return print_json(&contact_aliases(ctx, id)?);
+    }
+    println!("alias {} removed from {}", alias, id);
+    Ok(())
+}
+
+pub fn list_aliases(ctx: &Context<'_>, args: AliasListArgs) -> Result<()> {
+    let id = resolve_contact_arg(ctx, &args.id)?;
+    ensure_contact(ctx, id)?;
+    let aliases = contact_aliases(ctx, id)?;
+
+    if ctx.json {
+        return print_json(&aliases);
+    }
+    if aliases.aliases.is_empty() {
+        println!("no aliases");
+        return Ok(());
+    }
+    for alias in &aliases.aliases {
+        println!("{}", alias);
+    }
+    Ok(())
+}
+
+fn parse_alias(raw: &str) -> Result<String> {
+    normalize_alias(raw).ok_or_else(|| invalid_input("alias cannot be empty"))
+}
+
+fn ensure_contact(ctx: &Context<'_>, id: ContactId) -> Result<()> {
+    if ctx.store.contacts().get(id)?.is_none() {
+        return Err(not_found("contact not found"));
+    }
+    Ok(())
+}
+
+fn contact_aliases(ctx: &Context<'_>, id: ContactId) -> Result<ContactAliasesDto> {
+    Ok(ContactAliasesDto {
+        contact_id: id,
+        aliases: ctx.store.contact_aliases().list_for_contact(id)?,
+    })
+}
@@ -0,0 +1,85 @@
+use crate::commands::{print_json, resolve_contact_arg, Context};
+use anyhow::Result;
+use clap::Args;
+use knotter_core::dto::ContactAuditDto;
+use knotter_core::time::Clock;
+use knotter_store::repo::AuditEntry;
+use serde_json::Value;
+
+/// Fields holding unix timestamps, shown as local date-times.
+const TIMESTAMP_FIELDS: [&str; 2] = ["archived_at", "next_touchpoint_at"];
+
+#[derive(Debug, Args)]
+pub struct LogArgs {
+    /// Contact id, name, alias, email, or handle
+    pub id: String,
+    #[arg(long, value_name = "N", help = "Show at most N entries")]
+    pub limit: Option<usize>,
+}
+
+pub fn contact_log(ctx: &Context<'_>, args: LogArgs) -> Result<()> {
+    let id = resolve_contact_arg(ctx, &args.id)?;
+    let entries: Vec<ContactAuditDto> = ctx
+        .store
+        .contact_audit()
+        .list_for_contact(id, args.limit)?
+        .into_iter()
+        .map(audit_dto)
+        .collect();
+
+    if ctx.json {
+        return print_json(&entries);
+    }
+    if entries.is_empty() {
+        println!("no changes recorded for {id}");
+        return Ok(());
+    }
+    for entry in &entries {
+        let detail = entry
+            .detail
+            .as_deref()
+            .map(|detail| format!("  {detail}"))
+            .unwrap_or_default();
+        println!(
+            "{}  {}  {}{}",
+            ctx.clock.format_timestamp_datetime(entry.occurred_at),
+            entry.action,
+            entry.origin,
+            detail
+        );
+        for (field, (old, new)) in &entry.changes {
+            println!(
+                "  {field}: {} -> {}",
+                format_value(field, old, &ctx.clock),
+                format_value(field, new, &ctx.clock)
+            );
+        }
+    }
+    Ok(())
+}
+
+pub fn audit_dto(entry: AuditEntry) -> ContactAuditDto {
+    ContactAuditDto {
+        occurred_at: entry.occurred_at,
+        action: entry.action,
+        origin: entry.origin,
+        changes: entry
+            .changes
+            .into_iter()
+            .map(|change| (change.field, (change.old, change.new)))
+            .collect(),
+        detail: entry.detail,
+    }
+}
+
+fn format_value(field: &str, value: &Value, clock: &Clock) -> String {
+    match value {
+        Value::Null => "-".to_string(),
+        Value::String(text) => text.clone(),
+        Value::Number(number) if TIMESTAMP_FIELDS.contains(&field) => number
+            .as_i64()
+            .map(|ts| clock.format_timestamp_datetime(ts))
+            .unwrap_or_else(|| number.to_string()),
+        other => other.to_string(),
+    }
+}
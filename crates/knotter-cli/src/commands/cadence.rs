@@ -0,0 +1,131 @@
+use crate::commands::{print_json, resolve_contact_arg, Context};
+use crate::error::not_found;
+use anyhow::Result;
+use clap::Args;
+use knotter_core::domain::{Contact, ContactId};
+use knotter_core::rules::cadence::SUGGESTION_WINDOW_DAYS;
+use knotter_core::rules::suggest_cadence;
+use knotter_store::repo::ContactUpdate;
+use serde::Serialize;
+
+#[derive(Debug, Args)]
+pub struct SuggestCadenceArgs {
+    /// Contact id, or a name, alias, email, or @handle
+    #[arg(required_unless_present = "all", conflicts_with = "all")]
+    pub id: Option<String>,
+    #[arg(long, help = "Suggest cadences for every active contact")]
+    pub all: bool,
+    #[arg(long, help = "Set the suggested cadence on contacts without one")]
+    pub apply: bool,
+    #[arg(
+        long = "override",
+        requires = "apply",
+        help = "With --apply, also replace cadences that are already set"
+    )]
+    pub override_existing: bool,
+}
+
+#[derive(Debug, Serialize)]
+struct CadenceSuggestionDto {
+    id: ContactId,
+    display_name: String,
+    current_cadence_days: Option<i32>,
+    suggested_cadence_days: Option<i32>,
+    median_gap_days: Option<f64>,
+    interactions: usize,
+    applied: bool,
+}
+
+pub fn suggest_contact_cadence(ctx: &Context<'_>, args: SuggestCadenceArgs) -> Result<()> {
+    let now = ctx.clock.now_utc();
+    let contacts: Vec<Contact> = match args.id.as_deref() {
+        Some(raw) => {
+            let id = resolve_contact_arg(ctx, raw)?;
+            let contact = ctx
+                .store
+                .contacts()
+                .get(id)?
+                .ok_or_else(|| not_found("contact not found"))?;
+            vec![contact]
+        }
+        None => ctx
+            .store
+            .contacts()
+            .list_all()?
+            .into_iter()
+            .filter(|contact| contact.archived_at.is_none())
+            .collect(),
+    };
+
+    let ids: Vec<ContactId> = contacts.iter().map(|contact| contact.id).collect();
+    let since = now - SUGGESTION_WINDOW_DAYS * 86_400;
+    let mut history = ctx
+        .store
+        .interactions()
+        .occurred_at_since_for_contacts(&ids, since)?;
+
+    let mut results = Vec::with_capacity(contacts.len());
+    for contact in contacts {
+        let occurred = history.remove(&contact.id).unwrap_or_default();
+        let suggestion = suggest_cadence(now, &occurred);
+        // Bulk runs only report contacts that have something to suggest.
+        if args.all && suggestion.is_none() {
+            continue;
+        }
+
+        let mut applied = false;
+        if let Some(suggestion) = suggestion {
+            let replace = contact.cadence_days.is_none() || args.override_existing;
+            if args.apply && replace && contact.cadence_days != Some(suggestion.cadence_days) {
+                ctx.store.contacts().update(
+                    now,
+                    contact.id,
+                    ContactUpdate {
+                        cadence_days: Some(Some(suggestion.cadence_days)),
+                        ..Default::default()
+                    },
+                )?;
+                applied = true;
+            }
+        }
+
+        results.push(CadenceSuggestionDto {
+            id: contact.id,
+            display_name: contact.display_name,
+            current_cadence_days: contact.cadence_days,
+            suggested_cadence_days: suggestion.map(|value| value.cadence_days),
+            median_gap_days: suggestion.map(|value| value.median_gap_days),
+            interactions: occurred.len(),
+            applied,
+        });
+    }
+
+    if ctx.json {
+        return print_json(&results);
+    }
+
+    if results.is_empty() {
+        println!("no cadence suggestions");
+        return Ok(());
+    }
+    for result in &results {
+        let (Some(days), Some(median)) = (result.suggested_cadence_days, result.median_gap_days)
+        else {
+            println!(
+                "{}  {}  no suggestion (needs interactions on 3+ days in the last year)",
+                result.id, result.display_name
+            );
+            continue;
+        };
+        let current = result
+            .current_cadence_days
+            .map(|value| format!("{value} days"))
+            .unwrap_or_else(|| "none".to_string());
+        let status = if result.applied { "  [applied]" } else { "" };
+        println!(
+            "{}  {}  suggest {} days (median gap {:.1} days, {} interactions; current {}){}",
+            result.id, result.display_name, days, median, result.interactions, current, status
+        );
+    }
+    Ok(())
+}
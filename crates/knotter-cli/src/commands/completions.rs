@@ -1,7 +1,15 @@
 use anyhow::Result;
 use clap::CommandFactory;
 use clap_complete::generate;
-use std::io;
+use knotter_config as config;
+use knotter_store::{paths, Store};
+use std::collections::BTreeSet;
+use std::fmt::Write as _;
+use std::io::{self, Write};
+use std::path::PathBuf;
+
+/// Upper bound on candidates printed by `__complete`.
+const COMPLETE_LIMIT: usize = 50;
 
 #[derive(Debug, clap::Args)]
 pub struct CompletionsArgs {
@@ -31,11 +39,504 @@ impl From<CompletionShell> for clap_complete::Shell {
     }
 }
 
+#[derive(Debug, clap::Args)]
+pub struct CompleteArgs {
+    #[arg(value_enum)]
+    pub kind: CompleteKind,
+    pub prefix: Option<String>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, clap::ValueEnum)]
+pub enum CompleteKind {
+    Contacts,
+    Tags,
+    Sources,
+    #[value(name = "email-accounts")]
+    EmailAccounts,
+}
+
+impl CompleteKind {
+    fn as_str(self) -> &'static str {
+        match self {
+            CompleteKind::Contacts => "contacts",
+            CompleteKind::Tags => "tags",
+            CompleteKind::Sources => "sources",
+            CompleteKind::EmailAccounts => "email-accounts",
+        }
+    }
+}
+
+enum SlotPosition {
+    /// The completed word is the Nth positional word after the binary.
+    Positional(usize),
+    /// The completed word is the value of this option.
+    OptionValue(&'static str),
+}
+
+/// Argument positions whose values come from the store or config.
+struct DynamicSlot {
+    path: &'static [&'static str],
+    position: SlotPosition,
+    kind: CompleteKind,
+}
+
+const CONTACT_ID_COMMANDS: &[&str] = &[
+    "show",
+    "edit-contact",
+    "archive-contact",
+    "unarchive-contact",
+    "mute",
+    "unmute",
+    "delete",
+    "add-note",
+    "touch",
+    "schedule",
+    "clear-schedule",
+    "suggest-cadence",
+];
+
+fn dynamic_slots() -> Vec<DynamicSlot> {
+    let mut slots: Vec<DynamicSlot> = CONTACT_ID_COMMANDS
+        .iter()
+        .map(|command| DynamicSlot {
+            path: std::slice::from_ref(command),
+            position: SlotPosition::Positional(2),
+            kind: CompleteKind::Contacts,
+        })
+        .collect();
+    for path in [&["tag", "add"], &["tag", "rm"]] {
+        slots.push(DynamicSlot {
+            path,
+            position: SlotPosition::Positional(3),
+            kind: CompleteKind::Contacts,
+        });
+        slots.push(DynamicSlot {
+            path,
+            position: SlotPosition::Positional(4),
+            kind: CompleteKind::Tags,
+        });
+    }
+    for path in [&["tag", "rename"], &["tag", "merge"], &["tag", "delete"]] {
+        slots.push(DynamicSlot {
+            path,
+            position: SlotPosition::Positional(3),
+            kind: CompleteKind::Tags,
+        });
+    }
+    slots.push(DynamicSlot {
+        path: &["tag", "merge"],
+        position: SlotPosition::OptionValue("--into"),
+        kind: CompleteKind::Tags,
+    });
+    for path in [
+        &["email", "add"],
+        &["email", "rm"],
+        &["email", "ls"],
+        &["email", "set-primary"],
+        &["alias", "add"],
+        &["alias", "rm"],
+        &["alias", "ls"],
+    ] {
+        slots.push(DynamicSlot {
+            path,
+            position: SlotPosition::Positional(3),
+            kind: CompleteKind::Contacts,
+        });
+    }
+    for path in [&["add-contact"], &["edit-contact"]] {
+        slots.push(DynamicSlot {
+            path,
+            position: SlotPosition::OptionValue("--met-via"),
+            kind: CompleteKind::Contacts,
+        });
+    }
+    slots.push(DynamicSlot {
+        path: &["import", "source"],
+        position: SlotPosition::Positional(3),
+        kind: CompleteKind::Sources,
+    });
+    slots.push(DynamicSlot {
+        path: &["import", "email"],
+        position: SlotPosition::OptionValue("--account"),
+        kind: CompleteKind::EmailAccounts,
+    });
+    slots
+}
+
 pub fn emit(args: CompletionsArgs) -> Result<()> {
-    let mut cmd = crate::Cli::command();
+    let mut cmd = public_command();
     let name = cmd.get_name().to_string();
-    let mut stdout = io::stdout().lock();
     let shell: clap_complete::Shell = args.shell.into();
-    generate(shell, &mut cmd, name, &mut stdout);
+    let mut buffer = Vec::new();
+    generate(shell, &mut cmd, name.clone(), &mut buffer);
+    let script = String::from_utf8(buffer)?;
+
+    let mut value_options = BTreeSet::new();
+    collect_value_options(&cmd, &mut value_options);
+    let value_options: Vec<String> = value_options.into_iter().collect();
+
+    let script = match args.shell {
+        CompletionShell::Bash => bash_script(script, &name, &value_options),
+        CompletionShell::Zsh => zsh_script(script, &name, &value_options),
+        CompletionShell::Fish => fish_script(script, &name, &value_options),
+        CompletionShell::PowerShell | CompletionShell::Elvish => script,
+    };
+    io::stdout().lock().write_all(script.as_bytes())?;
+    Ok(())
+}
+
+/// The CLI without hidden subcommands: clap_complete would otherwise offer
+/// `__complete` (and its bash generator cannot encode that name).
+fn public_command() -> clap::Command {
+    let full = crate::Cli::command();
+    let mut cmd = clap::Command::new("knotter")
+        .version(env!("CARGO_PKG_VERSION"))
+        .subcommand_required(full.is_subcommand_required_set());
+    if let Some(about) = full.get_about() {
+        cmd = cmd.about(about.clone());
+    }
+    for arg in full.get_arguments() {
+        cmd = cmd.arg(arg.clone());
+    }
+    for sub in full.get_subcommands().filter(|sub| !sub.is_hide_set()) {
+        cmd = cmd.subcommand(sub.clone());
+    }
+    cmd
+}
+
+/// Prints completion candidates one per line (`value` or `value<TAB>description`).
+/// Any failure (missing database, bad config) prints nothing so shells stay quiet.
+pub fn complete(
+    db_path: Option<PathBuf>,
+    config_path: Option<PathBuf>,
+    args: CompleteArgs,
+) -> Result<()> {
+    let prefix = args.prefix.unwrap_or_default();
+    let candidates = match args.kind {
+        CompleteKind::Contacts => open_existing_store(db_path).and_then(|store| {
+            store
+                .contacts()
+                .list_id_names_with_prefix(&prefix, COMPLETE_LIMIT)
+                .ok()
+                .map(|items| {
+                    items
+                        .into_iter()
+                        .map(|(id, name)| format!("{id}\t{name}"))
+                        .collect()
+                })
+        }),
+        CompleteKind::Tags => open_existing_store(db_path).and_then(|store| {
+            store
+                .tags()
+                .list_names_with_prefix(&prefix, COMPLETE_LIMIT)
+                .ok()
+        }),
+        CompleteKind::Sources => config::load(config_path).ok().map(|cfg| {
+            filter_names(
+                cfg.contacts.sources.iter().map(|s| s.name.as_str()),
+                &prefix,
+            )
+        }),
+        CompleteKind::EmailAccounts => config::load(config_path).ok().map(|cfg| {
+            filter_names(
+                cfg.contacts
+                    .email_accounts
+                    .iter()
+                    .map(|account| account.name.as_str()),
+                &prefix,
+            )
+        }),
+    };
+
+    let mut stdout = io::stdout().lock();
+    for line in candidates.unwrap_or_default() {
+        if writeln!(stdout, "{line}").is_err() {
+            break;
+        }
+    }
     Ok(())
 }
+
+fn open_existing_store(db_path: Option<PathBuf>) -> Option<Store> {
+    let path = match db_path {
+        Some(path) => path,
+        None => paths::db_path_in(&paths::data_dir().ok()?),
+    };
+    // Never create a database just to complete a word.
+    if !path.is_file() {
+        return None;
+    }
+    Store::open(&path).ok()
+}
+
+fn filter_names<'a>(names: impl Iterator<Item = &'a str>, prefix: &str) -> Vec<String> {
+    let prefix = prefix.to_lowercase();
+    names
+        .filter(|name| name.to_lowercase().starts_with(&prefix))
+        .take(COMPLETE_LIMIT)
+        .map(str::to_string)
+        .collect()
+}
+
+fn collect_value_options(cmd: &clap::Command, out: &mut BTreeSet<String>) {
+    for arg in cmd.get_arguments() {
+        if arg.is_positional() || !arg.get_action().takes_values() {
+            continue;
+        }
+        if let Some(long) = arg.get_long() {
+            if long != "db-path" && long != "config" {
+                out.insert(format!("--{long}"));
+            }
+        }
+        if let Some(short) = arg.get_short() {
+            out.insert(format!("-{short}"));
+        }
+    }
+    for sub in cmd.get_subcommands() {
+        collect_value_options(sub, out);
+    }
+}
+
+/// Renders `if`/`elif` branches that set `kind` for each dynamic slot.
+/// `count`, `word(i)` and `prev` adapt the test to the shell's array syntax.
+fn slot_conditions(
+    count: &str,
+    word: impl Fn(usize) -> String,
+    prev: &str,
+    and: &str,
+) -> Vec<(String, &'static str)> {
+    dynamic_slots()
+        .into_iter()
+        .map(|slot| {
+            let mut tests = Vec::new();
+            match slot.position {
+                SlotPosition::Positional(position) => {
+                    tests.push(format!("{count} -eq {}", position - 1));
+                }
+                SlotPosition::OptionValue(option) => {
+                    tests.push(format!("\"{prev}\" = \"{option}\""));
+                    tests.push(format!("{count} -ge {}", slot.path.len()));
+                }
+            }
+            for (index, part) in slot.path.iter().enumerate() {
+                tests.push(format!("\"{}\" = \"{part}\"", word(index)));
+            }
+            (tests.join(and), slot.kind.as_str())
+        })
+        .collect()
+}
+
+fn bash_script(mut script: String, name: &str, value_options: &[String]) -> String {
+    let mut branches = String::new();
+    for (index, (test, kind)) in slot_conditions(
+        "${#words[@]}",
+        |i| format!("${{words[{i}]}}"),
+        "$prev",
+        " && ",
+    )
+    .into_iter()
+    .enumerate()
+    {
+        let keyword = if index == 0 { "if" } else { "elif" };
+        let test = test.replace(" = ", " == ");
+        let _ = writeln!(
+            branches,
+            "        {keyword} [[ {test} ]]; then\n            kind=\"{kind}\""
+        );
+    }
+    branches.push_str("        fi\n");
+
+    let _ = write!(
+        script,
+        r#"
+# Dynamic values (contact ids, tags, source names) via `{name} __complete`.
+_{name}_dynamic() {{
+    local cur="${{COMP_WORDS[COMP_CWORD]}}"
+    local prev=""
+    (( COMP_CWORD > 0 )) && prev="${{COMP_WORDS[COMP_CWORD-1]}}"
+    local -a words=() globals=()
+    local i word skip=0
+    for (( i = 1; i < COMP_CWORD; i++ )); do
+        word="${{COMP_WORDS[i]}}"
+        if (( skip )); then
+            skip=0
+            continue
+        fi
+        case "$word" in
+            --db-path|--config)
+                globals+=("$word" "${{COMP_WORDS[i+1]}}")
+                skip=1
+                ;;
+            {options})
+                skip=1
+                ;;
+            -*)
+                ;;
+            *)
+                words+=("$word")
+                ;;
+        esac
+    done
+
+    local kind=""
+    if [[ "$cur" != -* ]]; then
+{branches}    fi
+    if [[ -n "$kind" ]]; then
+        local line
+        COMPREPLY=()
+        while IFS= read -r line; do
+            COMPREPLY+=("${{line%%$'\t'*}}")
+        done < <("${{COMP_WORDS[0]}}" "${{globals[@]}}" __complete "$kind" "$cur" 2>/dev/null)
+        return 0
+    fi
+    _{name} "$@"
+}}
+
+complete -F _{name}_dynamic -o bashdefault -o default {name}
+"#,
+        options = value_options.join("|"),
+    );
+    script
+}
+
+fn zsh_script(script: String, name: &str, value_options: &[String]) -> String {
+    let mut branches = String::new();
+    for (index, (test, kind)) in slot_conditions(
+        "${#positional}",
+        |i| format!("${{positional[{}]}}", i + 1),
+        "$prev",
+        " && ",
+    )
+    .into_iter()
+    .enumerate()
+    {
+        let keyword = if index == 0 { "if" } else { "elif" };
+        let _ = writeln!(
+            branches,
+            "        {keyword} [[ {test} ]]; then\n            kind=\"{kind}\""
+        );
+    }
+    branches.push_str("        fi\n");
+
+    let dynamic = format!(
+        r#"# Dynamic values (contact ids, tags, source names) via `{name} __complete`.
+_{name}() {{
+    local -a positional globals
+    local i word skip=0
+    for (( i = 2; i < CURRENT; i++ )); do
+        word="${{words[i]}}"
+        if (( skip )); then
+            skip=0
+            continue
+        fi
+        case "$word" in
+            --db-path|--config)
+                globals+=("$word" "${{words[i+1]}}")
+                skip=1
+                ;;
+            {options})
+                skip=1
+                ;;
+            -*)
+                ;;
+            *)
+                positional+=("$word")
+                ;;
+        esac
+    done
+
+    local cur="${{words[CURRENT]}}" prev="${{words[CURRENT-1]}}" kind=""
+    if [[ "$cur" != -* ]]; then
+{branches}    fi
+    if [[ -n "$kind" ]]; then
+        local -a candidates
+        local line
+        for line in ${{(f)"$(${{words[1]}} "${{globals[@]}}" __complete "$kind" "$cur" 2>/dev/null)"}}; do
+            candidates+=("${{${{line%%$'\t'*}}//:/\\:}}:${{line#*$'\t'}}")
+        done
+        _describe -t "$kind" "$kind" candidates -U
+        return
+    fi
+    _{name}_static "$@"
+}}
+
+"#,
+        options = value_options.join("|"),
+    );
+
+    // Keep clap's completer as `_<name>_static` and dispatch through the dynamic one.
+    let script = script.replacen(
+        &format!("\n_{name}() {{\n"),
+        &format!("\n_{name}_static() {{\n"),
+        1,
+    );
+    let dispatch = format!("if [ \"$funcstack[1]\" = \"_{name}\" ]; then");
+    match script.rfind(&dispatch) {
+        Some(index) => {
+            let mut script = script;
+            script.insert_str(index, &dynamic);
+            script
+        }
+        None => script + "\n" + &dynamic,
+    }
+}
+
+fn fish_script(mut script: String, name: &str, value_options: &[String]) -> String {
+    let mut branches = String::new();
+    for (index, (test, kind)) in slot_conditions(
+        "$count",
+        |i| format!("$positional[{}]", i + 1),
+        "$tokens[-1]",
+        " -a ",
+    )
+    .into_iter()
+    .enumerate()
+    {
+        let keyword = if index == 0 { "if" } else { "else if" };
+        let _ = writeln!(branches, "    {keyword} test {test}\n        echo {kind}");
+    }
+    branches.push_str("    else\n        return 1\n    end\n");
+
+    let _ = write!(
+        script,
+        r#"
+# Dynamic values (contact ids, tags, source names) via `{name} __complete`.
+function __{name}_dynamic_kind
+    set -l tokens (commandline -opc)
+    string match -q -- '-*' (commandline -ct); and return 1
+    set -l positional
+    set -l skip 0
+    for token in $tokens[2..-1]
+        if test $skip -eq 1
+            set skip 0
+            continue
+        end
+        switch $token
+            case --db-path --config {options}
+                set skip 1
+            case '-*'
+            case '*'
+                set -a positional $token
+        end
+    end
+    set -l count (count $positional)
+{branches}end
+
+function __{name}_dynamic
+    set -l tokens (commandline -opc)
+    set -l globals
+    for i in (seq 2 (count $tokens))
+        switch $tokens[$i]
+            case --db-path --config
+                set -a globals $tokens[$i] $tokens[(math $i + 1)]
+        end
+    end
+    $tokens[1] $globals __complete (__{name}_dynamic_kind) (commandline -ct) 2>/dev/null
+end
+
+complete -c {name} -n '__{name}_dynamic_kind >/dev/null' -f -a '(__{name}_dynamic)'
+"#,
+        options = value_options.join(" "),
+    );
+    script
+}
@@ -0,0 +1,91 @@
+use crate::commands::print_json;
+use crate::error::invalid_input;
+use anyhow::{Context as _, Result};
+use clap::{Args, Subcommand};
+use serde::Serialize;
+use std::path::PathBuf;
+
+#[derive(Debug, Subcommand)]
+pub enum ConfigCommand {
+    /// Validate the config file and report every problem found
+    Check(CheckArgs),
+    /// Write a commented config template
+    Init(InitArgs),
+}
+
+#[derive(Debug, Args)]
+pub struct CheckArgs {}
+
+#[derive(Debug, Args)]
+pub struct InitArgs {
+    #[arg(long)]
+    pub force: bool,
+}
+
+#[derive(Debug, Serialize)]
+struct ConfigCheckReport {
+    path: String,
+    exists: bool,
+    valid: bool,
+    errors: Vec<String>,
+    warnings: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct ConfigInitReport {
+    path: String,
+}
+
+pub fn check(config_path: Option<PathBuf>, json: bool, _args: CheckArgs) -> Result<()> {
+    let report = knotter_config::check(config_path).with_context(|| "resolve config path")?;
+    let errors: Vec<String> = report.errors.iter().map(|err| err.to_string()).collect();
+
+    if json {
+        print_json(&ConfigCheckReport {
+            path: report.path.display().to_string(),
+            exists: report.exists,
+            valid: errors.is_empty(),
+            errors: errors.clone(),
+            warnings: report.warnings.clone(),
+        })?;
+    } else {
+        println!("config: {}", report.path.display());
+        for error in &errors {
+            println!("error: {error}");
+        }
+        for warning in &report.warnings {
+            println!("warning: {warning}");
+        }
+        if errors.is_empty() {
+            println!("ok");
+        }
+    }
+
+    if !errors.is_empty() {
+        return Err(invalid_input(format!(
+            "config check found {} error(s)",
+            errors.len()
+        )));
+    }
+    Ok(())
+}
+
+pub fn init(config_path: Option<PathBuf>, json: bool, args: InitArgs) -> Result<()> {
+    let path =
+        knotter_config::resolve_config_path(config_path).with_context(|| "resolve config path")?;
+    if path.exists() && !args.force {
+        return Err(invalid_input(format!(
+            "config file already exists: {} (use --force to overwrite)",
+            path.display()
+        )));
+    }
+    knotter_config::write_template(&path, args.force)?;
+
+    if json {
+        return print_json(&ConfigInitReport {
+            path: path.display().to_string(),
+        });
+    }
+    println!("Wrote config template to {}", path.display());
+    Ok(())
+}
@@ -1,44 +1,147 @@
-use crate::commands::{print_json, Context, DEFAULT_INTERACTION_LIMIT};
-use crate::error::{invalid_input, not_found};
-use crate::util::{
-    due_state_label, format_date_parts, format_interaction_kind, format_timestamp_date,
-    format_timestamp_datetime, local_offset, now_utc, parse_contact_id,
-    parse_local_timestamp_with_precision,
+use crate::commands::dry_run::{print_dry_run, run_contact_change};
+use crate::commands::{
+    print_json, read_confirmation, resolve_contact_arg, Context, DEFAULT_INTERACTION_LIMIT,
 };
+use crate::error::{invalid_input, not_found};
+use crate::util::{due_state_label, format_date_parts};
 use anyhow::Result;
-use clap::{ArgAction, Args};
-use knotter_config::LoopAnchor;
-use knotter_core::domain::{normalize_email, TagName};
-use knotter_core::dto::{ContactDateDto, ContactDetailDto, ContactListItemDto, InteractionDto};
-use knotter_core::filter::parse_filter;
-use knotter_core::rules::compute_due_state;
+use chrono::NaiveDate;
+use clap::{ArgAction, Args, ValueEnum};
+use knotter_app::contacts::{self, ArchivedScope, DetailOptions, ListOptions};
+use knotter_config::{ContactDefaultsConfig, LoopAnchor};
+use knotter_core::domain::{
+    canonical_phone, is_starred, normalize_email, validate_contact_priority, Contact, ContactId,
+    TagName,
+};
+use knotter_core::dto::ContactListItemDto;
 use knotter_core::rules::{ensure_future_timestamp_with_precision, schedule_next};
-use knotter_store::query::ContactQuery;
-use knotter_store::repo::{ContactNew, ContactUpdate, EmailOps};
+use knotter_core::time::{Clock, TimePrecision};
+use knotter_store::query::ContactSort;
+use knotter_store::repo::{
+    ContactNew, ContactOriginUpdate, ContactOriginsRepo, ContactUpdate, ContactsRepo, EmailOps,
+    InteractionRange,
+};
+use serde::Serialize;
+use std::path::PathBuf;
+
+mod batch_edit;
+mod bulk_add;
 
 #[derive(Debug, Args)]
 pub struct AddContactArgs {
-    #[arg(long)]
-    pub name: String,
+    #[arg(long, required_unless_present = "from_tsv")]
+    pub name: Option<String>,
     #[arg(long, value_name = "EMAIL", action = ArgAction::Append)]
     pub email: Vec<String>,
     #[arg(long)]
     pub phone: Option<String>,
+    #[arg(
+        long,
+        requires = "phone",
+        help = "Reject a phone that is not digits with an optional leading + and extension"
+    )]
+    pub strict_phone: bool,
     #[arg(long)]
     pub handle: Option<String>,
     #[arg(long)]
     pub timezone: Option<String>,
+    #[arg(long, value_name = "NAME", help = "Company or organization")]
+    pub org: Option<String>,
+    #[arg(
+        long,
+        value_name = "N",
+        help = "How much the contact matters, 0-10; 5+ is starred"
+    )]
+    pub priority: Option<i32>,
     #[arg(long)]
     pub cadence_days: Option<i32>,
     #[arg(long)]
     pub next_touchpoint_at: Option<String>,
     #[arg(long, value_name = "TAG")]
     pub tag: Vec<String>,
+    #[arg(
+        long,
+        value_name = "TEXT",
+        help = "Where or how you met (e.g. \"RustConf 2023\")"
+    )]
+    pub met: Option<String>,
+    #[arg(long, value_name = "DATE", help = "When you met (YYYY-MM-DD)")]
+    pub met_date: Option<String>,
+    #[arg(
+        long,
+        value_name = "CONTACT",
+        help = "Contact who introduced you (id, name, alias, email, or @handle)"
+    )]
+    pub met_via: Option<String>,
+    #[arg(long, help = "Ignore [contacts.defaults] for this contact")]
+    pub no_defaults: bool,
+    #[arg(
+        long,
+        value_name = "PATH",
+        conflicts_with_all = [
+            "name",
+            "email",
+            "phone",
+            "handle",
+            "timezone",
+            "org",
+            "priority",
+            "cadence_days",
+            "next_touchpoint_at",
+            "tag",
+            "met",
+            "met_date",
+            "met_via",
+        ],
+        help = "Add one contact per row of a TSV or CSV file with a header row (- reads stdin)"
+    )]
+    pub from_tsv: Option<PathBuf>,
+    #[arg(
+        long,
+        value_enum,
+        default_value_t = OnDuplicate::Fail,
+        conflicts_with = "name",
+        help = "What to do with a row whose email belongs to another contact"
+    )]
+    pub on_duplicate: OnDuplicate,
+    #[arg(
+        long,
+        conflicts_with = "name",
+        help = "Validate the rows and report what would happen without adding anything"
+    )]
+    pub dry_run: bool,
+}
+
+/// `add-contact --from-tsv` handling of a row whose email is already taken,
+/// by a stored contact or an earlier row.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum OnDuplicate {
+    /// Leave the row out
+    Skip,
+    /// Add the contact without the taken email and stage a merge candidate
+    Stage,
+    /// Treat the row as invalid, so nothing is added
+    Fail,
 }
 
 #[derive(Debug, Args)]
 pub struct EditContactArgs {
-    pub id: String,
+    /// Contact id, or a name, alias, email, or @handle
+    #[arg(required_unless_present = "batch")]
+    pub id: Option<String>,
+    #[arg(
+        long,
+        value_name = "PATH",
+        conflicts_with_all = [
+            "id", "name", "email", "add_email", "remove_email", "clear_emails", "phone",
+            "handle", "timezone", "org", "priority", "cadence_days", "next_touchpoint_at",
+            "met", "met_date", "met_via"
+        ],
+        help = "Apply JSON-lines edits from a file (or - for stdin) in one transaction"
+    )]
+    pub batch: Option<PathBuf>,
+    #[arg(long, help = "Report changes without applying them")]
+    pub dry_run: bool,
     #[arg(long)]
     pub name: Option<String>,
     #[arg(long)]
@@ -51,19 +154,89 @@ pub struct EditContactArgs {
     pub clear_emails: bool,
     #[arg(long)]
     pub phone: Option<String>,
+    #[arg(
+        long,
+        requires = "phone",
+        help = "Reject a phone that is not digits with an optional leading + and extension"
+    )]
+    pub strict_phone: bool,
     #[arg(long)]
     pub handle: Option<String>,
     #[arg(long)]
     pub timezone: Option<String>,
+    #[arg(
+        long,
+        value_name = "NAME",
+        help = "Company or organization; empty clears"
+    )]
+    pub org: Option<String>,
+    #[arg(
+        long,
+        value_name = "N",
+        help = "How much the contact matters, 0-10; 5+ is starred"
+    )]
+    pub priority: Option<i32>,
     #[arg(long)]
     pub cadence_days: Option<i32>,
     #[arg(long)]
     pub next_touchpoint_at: Option<String>,
+    #[arg(long, value_name = "TEXT", help = "Where or how you met; empty clears")]
+    pub met: Option<String>,
+    #[arg(
+        long,
+        value_name = "DATE",
+        help = "When you met (YYYY-MM-DD); empty clears"
+    )]
+    pub met_date: Option<String>,
+    #[arg(
+        long,
+        value_name = "CONTACT",
+        help = "Contact who introduced you (id, name, alias, email, or @handle); empty clears"
+    )]
+    pub met_via: Option<String>,
 }
 
 #[derive(Debug, Args)]
 pub struct ShowArgs {
-    pub id: String,
+    /// Contact id, or a name, alias, email, or @handle
+    #[arg(required_unless_present = "handle")]
+    pub id: Option<String>,
+    #[arg(
+        long,
+        conflicts_with = "id",
+        help = "Find the contact by handle (with or without a leading @)"
+    )]
+    pub handle: Option<String>,
+    #[arg(long, help = "Show which source last set each field")]
+    pub provenance: bool,
+    #[arg(long, help = "Show imports that changed this contact, newest first")]
+    pub history: bool,
+    #[arg(
+        long,
+        default_value_t = DEFAULT_INTERACTION_LIMIT,
+        help = "Interactions to show, newest first"
+    )]
+    pub limit: i64,
+    #[arg(long, default_value_t = 0, help = "Skip this many newer interactions")]
+    pub offset: i64,
+    #[arg(
+        long,
+        conflicts_with = "limit",
+        help = "Show every matching interaction"
+    )]
+    pub all: bool,
+    #[arg(
+        long,
+        value_name = "DATE",
+        help = "Only interactions on or after this local date (YYYY-MM-DD[ HH:MM])"
+    )]
+    pub since: Option<String>,
+    #[arg(
+        long,
+        value_name = "DATE",
+        help = "Only interactions on or before this local date (YYYY-MM-DD[ HH:MM])"
+    )]
+    pub until: Option<String>,
 }
 
 #[derive(Debug, Args)]
@@ -72,44 +245,251 @@ pub struct ListArgs {
     pub filter: Option<String>,
     #[arg(long, action = ArgAction::SetTrue)]
     pub include_archived: bool,
-    #[arg(long, action = ArgAction::SetTrue, conflicts_with = "include_archived")]
+    #[arg(
+        long,
+        alias = "archived-only",
+        action = ArgAction::SetTrue,
+        conflicts_with = "include_archived"
+    )]
     pub only_archived: bool,
+    #[arg(
+        long,
+        value_enum,
+        default_value_t = ListSortArg::Due,
+        help = "Sort by due state (default) or by archive time, newest first"
+    )]
+    pub sort: ListSortArg,
+    #[arg(
+        long,
+        action = ArgAction::SetTrue,
+        help = "Only contacts whose latest imported message is inbound and unanswered (same as reply:awaiting)"
+    )]
+    pub awaiting_reply: bool,
+    #[arg(
+        long,
+        value_name = "DAYS",
+        help = "Minimum age in days of the unanswered inbound message (default 3)"
+    )]
+    pub reply_days: Option<i64>,
+    #[arg(
+        long,
+        value_enum,
+        value_name = "FIELD",
+        help = "Print contacts under headings; contacts without a value come last"
+    )]
+    pub group_by: Option<ListGroupByArg>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ListSortArg {
+    Due,
+    Archived,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ListGroupByArg {
+    Org,
+}
+
+#[derive(Debug, Serialize)]
+struct ContactGroupDto {
+    org: Option<String>,
+    contacts: Vec<ContactListItemDto>,
+}
+
+#[derive(Debug, Serialize)]
+struct ContactGroupsDto {
+    groups: Vec<ContactGroupDto>,
 }
 
 #[derive(Debug, Args)]
 pub struct DeleteArgs {
+    /// Contact id, or a name, alias, email, or @handle
     pub id: String,
+    #[arg(long, help = "Delete permanently instead of moving to the trash")]
+    pub hard: bool,
+    #[arg(
+        long,
+        requires = "hard",
+        help = "Hard-delete even if the contact is not archived"
+    )]
+    pub force: bool,
+    #[arg(
+        long,
+        requires = "hard",
+        help = "Skip the display-name confirmation prompt"
+    )]
+    pub yes: bool,
+}
+
+#[derive(Debug, Serialize)]
+struct TrashedContactDto {
+    id: ContactId,
+    display_name: String,
+    deleted_at: i64,
+    merge_candidates_dismissed: usize,
+}
+
+#[derive(Debug, Serialize)]
+struct DeletedContactDto {
+    id: ContactId,
+    display_name: String,
+    interactions: usize,
+    emails: usize,
+    dates: usize,
+    tags: usize,
+    merge_candidates_dismissed: usize,
 }
 
 #[derive(Debug, Args)]
 pub struct ArchiveArgs {
+    /// Contact id, or a name, alias, email, or @handle
     pub id: String,
+    #[arg(long, help = "Why the contact is archived (shown by show and exports)")]
+    pub reason: Option<String>,
+    #[arg(long, help = "Show the change without saving it")]
+    pub dry_run: bool,
+}
+
+#[derive(Debug, Serialize)]
+struct ArchivedContactDto<'a> {
+    #[serde(flatten)]
+    contact: &'a Contact,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    archive_reason: Option<String>,
 }
 
 #[derive(Debug, Args)]
 pub struct UnarchiveArgs {
+    /// Contact id, or a name, alias, email, or @handle
     pub id: String,
+    #[arg(long, help = "Show the change without saving it")]
+    pub dry_run: bool,
 }
 
 pub fn add_contact(ctx: &Context<'_>, args: AddContactArgs) -> Result<()> {
-    let now = now_utc();
-    let next_touchpoint_at = match args.next_touchpoint_at {
-        Some(value) => {
-            let (timestamp, precision) = parse_local_timestamp_with_precision(&value)?;
+    if let Some(source) = args.from_tsv.as_deref() {
+        return bulk_add::add_contacts_from_table(
+            ctx,
+            source,
+            args.on_duplicate,
+            args.dry_run,
+            args.no_defaults,
+        );
+    }
+    let name = args
+        .name
+        .ok_or_else(|| invalid_input("--name is required"))?;
+    let now = ctx.clock.now_utc();
+    let priority = args.priority.map(validate_contact_priority).transpose()?;
+    let defaults = new_contact_defaults(
+        ctx,
+        now,
+        args.no_defaults,
+        &args.tag,
+        args.cadence_days,
+        args.next_touchpoint_at,
+        args.timezone,
+        priority.unwrap_or(0),
+    )?;
+
+    let phone = prepare_phone(
+        args.phone,
+        ctx.config.contacts.normalize_phones,
+        args.strict_phone,
+    )?;
+    let origin = origin_update(ctx, args.met, args.met_date, args.met_via)?;
+    let emails = normalize_emails(&args.email);
+    let primary_email = emails.first().cloned();
+    let tx = ctx.store.connection().unchecked_transaction()?;
+    let contact = ContactsRepo::new(&tx).create_with_emails_and_tags(
+        now,
+        ContactNew {
+            display_name: name,
+            email: primary_email.clone(),
+            phone,
+            handle: args.handle,
+            timezone: defaults.timezone,
+            next_touchpoint_at: defaults.next_touchpoint_at,
+            cadence_days: defaults.cadence_days,
+            archived_at: None,
+            organization: args.org.and_then(normalize_optional_value),
+        },
+        defaults.tags,
+        emails,
+        Some("cli"),
+    )?;
+    if !origin.is_empty() {
+        ContactOriginsRepo::new(&tx).update(now, contact.id, origin)?;
+    }
+    let contact = match priority {
+        Some(priority) if priority != 0 => {
+            ContactsRepo::new(&tx).set_priority(now, contact.id, priority)?
+        }
+        _ => contact,
+    };
+    tx.commit()?;
+
+    if ctx.json {
+        print_json(&contact)?;
+    } else {
+        println!("created {} {}", contact.id, contact.display_name);
+    }
+    Ok(())
+}
+
+/// What a new contact gets once `[contacts.defaults]` (unless `no_defaults`)
+/// and the loop policy fill in the values that were not given.
+struct NewContactDefaults {
+    tags: Vec<TagName>,
+    cadence_days: Option<i32>,
+    next_touchpoint_at: Option<i64>,
+    timezone: Option<String>,
+}
+
+#[allow(clippy::too_many_arguments)]
+fn new_contact_defaults(
+    ctx: &Context<'_>,
+    now: i64,
+    no_defaults: bool,
+    tags: &[String],
+    cadence_days: Option<i32>,
+    next_touchpoint_at: Option<String>,
+    timezone: Option<String>,
+    priority: i32,
+) -> Result<NewContactDefaults> {
+    let unset = ContactDefaultsConfig::default();
+    let defaults = if no_defaults {
+        &unset
+    } else {
+        &ctx.config.contacts.defaults
+    };
+    let next_touchpoint_at = match (next_touchpoint_at, &defaults.next_touchpoint) {
+        (Some(value), _) => {
+            let (timestamp, precision) = ctx.clock.parse_local_timestamp_with_precision(&value)?;
             Some(ensure_future_timestamp_with_precision(
-                now, timestamp, precision,
+                now, timestamp, precision, &ctx.clock,
             )?)
         }
-        None => None,
+        (None, Some(offset)) => Some(
+            ctx.clock
+                .parse_local_date_time_relative(offset, None, now)?
+                .0,
+        ),
+        (None, None) => None,
+    };
+    let tags = if tags.is_empty() {
+        parse_tags(&defaults.tags)?
+    } else {
+        parse_tags(tags)?
     };
-    let tags = parse_tags(&args.tag)?;
     let loop_cadence = ctx
         .config
         .loops
         .policy
-        .resolve_cadence(tags.iter().map(|tag| tag.as_str()));
-    let cadence_days = args
-        .cadence_days
+        .resolve_cadence(tags.iter().map(|tag| tag.as_str()), priority);
+    let cadence_days = cadence_days
+        .or(defaults.cadence_days)
         .or(loop_cadence)
         .or(ctx.config.default_cadence_days);
     let next_touchpoint_at = if next_touchpoint_at.is_none()
@@ -124,37 +504,25 @@ pub fn add_contact(ctx: &Context<'_>, args: AddContactArgs) -> Result<()> {
     } else {
         next_touchpoint_at
     };
-
-    let emails = normalize_emails(&args.email);
-    let primary_email = emails.first().cloned();
-    let contact = ctx.store.contacts().create_with_emails_and_tags(
-        now,
-        ContactNew {
-            display_name: args.name,
-            email: primary_email.clone(),
-            phone: args.phone,
-            handle: args.handle,
-            timezone: args.timezone,
-            next_touchpoint_at,
-            cadence_days,
-            archived_at: None,
-        },
+    Ok(NewContactDefaults {
         tags,
-        emails,
-        Some("cli"),
-    )?;
-
-    if ctx.json {
-        print_json(&contact)?;
-    } else {
-        println!("created {} {}", contact.id, contact.display_name);
-    }
-    Ok(())
+        cadence_days,
+        next_touchpoint_at,
+        timezone: timezone.or_else(|| defaults.timezone.clone()),
+    })
 }
 
 pub fn edit_contact(ctx: &Context<'_>, args: EditContactArgs) -> Result<()> {
-    let now = now_utc();
-    let id = parse_contact_id(&args.id)?;
+    if let Some(batch) = args.batch.as_deref() {
+        return batch_edit::edit_contacts_batch(ctx, batch, args.dry_run);
+    }
+    let now = ctx.clock.now_utc();
+    let id = resolve_contact_arg(
+        ctx,
+        args.id
+            .as_deref()
+            .ok_or_else(|| invalid_input("contact id is required"))?,
+    )?;
 
     if args.email.is_some() && (!args.add_email.is_empty() || !args.remove_email.is_empty()) {
         return Err(invalid_input(
@@ -173,8 +541,12 @@ pub fn edit_contact(ctx: &Context<'_>, args: EditContactArgs) -> Result<()> {
     if args.clear_emails {
         update.email = Some(None);
     }
-    if let Some(phone) = args.phone {
-        update.phone = Some(normalize_optional_value(phone));
+    if args.phone.is_some() {
+        update.phone = Some(prepare_phone(
+            args.phone,
+            ctx.config.contacts.normalize_phones,
+            args.strict_phone,
+        )?);
     }
     if let Some(handle) = args.handle {
         update.handle = Some(normalize_optional_value(handle));
@@ -182,15 +554,20 @@ pub fn edit_contact(ctx: &Context<'_>, args: EditContactArgs) -> Result<()> {
     if let Some(timezone) = args.timezone {
         update.timezone = Some(normalize_optional_value(timezone));
     }
+    if let Some(org) = args.org {
+        update.organization = Some(normalize_optional_value(org));
+    }
     if let Some(cadence) = args.cadence_days {
         update.cadence_days = Some(Some(cadence));
     }
     if let Some(value) = args.next_touchpoint_at {
-        let (timestamp, precision) = parse_local_timestamp_with_precision(&value)?;
-        let parsed = ensure_future_timestamp_with_precision(now, timestamp, precision)?;
+        let (timestamp, precision) = ctx.clock.parse_local_timestamp_with_precision(&value)?;
+        let parsed = ensure_future_timestamp_with_precision(now, timestamp, precision, &ctx.clock)?;
         update.next_touchpoint_at = Some(Some(parsed));
     }
 
+    let origin = origin_update(ctx, args.met, args.met_date, args.met_via)?;
+    let priority = args.priority.map(validate_contact_priority).transpose()?;
     let add_emails = normalize_emails(&args.add_email);
     let remove_emails = normalize_emails(&args.remove_email);
     let has_email_ops = args.clear_emails || !add_emails.is_empty() || !remove_emails.is_empty();
@@ -203,7 +580,7 @@ pub fn edit_contact(ctx: &Context<'_>, args: EditContactArgs) -> Result<()> {
             }
         }
     }
-    if update_is_empty(&update) && !has_email_ops {
+    if update_is_empty(&update) && !has_email_ops && origin.is_empty() && priority.is_none() {
         return Err(invalid_input("no updates provided"));
     }
 
@@ -218,77 +595,59 @@ pub fn edit_contact(ctx: &Context<'_>, args: EditContactArgs) -> Result<()> {
         EmailOps::None
     };
 
-    let contact = ctx
-        .store
-        .contacts()
-        .update_with_email_ops(now, id, update.clone(), email_ops)?;
-    if ctx.json {
+    let (contact, changes) = run_contact_change(ctx, id, args.dry_run, || {
+        let contacts = ctx.store.contacts();
+        let contact = if update_is_empty(&update) && !has_email_ops {
+            contacts
+                .get(id)?
+                .ok_or_else(|| not_found("contact not found"))?
+        } else {
+            contacts.update_with_email_ops(now, id, update.clone(), email_ops)?
+        };
+        if !origin.is_empty() {
+            ContactOriginsRepo::new(ctx.store.connection()).update(now, contact.id, origin)?;
+        }
+        match priority {
+            Some(priority) => Ok(contacts.set_priority(now, contact.id, priority)?),
+            None => Ok(contact),
+        }
+    })?;
+    let summary = format!("updated {} {}", contact.id, contact.display_name);
+    if args.dry_run {
+        print_dry_run(ctx, &contact, &summary, &changes)?;
+    } else if ctx.json {
         print_json(&contact)?;
     } else {
-        println!("updated {} {}", contact.id, contact.display_name);
+        println!("{summary}");
     }
     Ok(())
 }
 
 pub fn show_contact(ctx: &Context<'_>, args: ShowArgs) -> Result<()> {
-    let id = parse_contact_id(&args.id)?;
-    let contact = ctx
-        .store
-        .contacts()
-        .get(id)?
-        .ok_or_else(|| not_found("contact not found"))?;
+    let contact = match (&args.id, &args.handle) {
+        (_, Some(handle)) => find_by_handle(ctx, handle)?,
+        (Some(id), None) => {
+            let id = resolve_contact_arg(ctx, id)?;
+            ctx.store
+                .contacts()
+                .get(id)?
+                .ok_or_else(|| not_found("contact not found"))?
+        }
+        (None, None) => return Err(invalid_input("pass a contact id or --handle")),
+    };
 
-    let tags = ctx.store.tags().list_for_contact(&contact.id.to_string())?;
-    let tag_names: Vec<String> = tags
-        .iter()
-        .map(|tag| tag.name.as_str().to_string())
-        .collect();
-
-    let interactions =
-        ctx.store
-            .interactions()
-            .list_for_contact(contact.id, DEFAULT_INTERACTION_LIMIT, 0)?;
-    let interaction_dtos: Vec<InteractionDto> = interactions
-        .iter()
-        .map(|interaction| InteractionDto {
-            id: interaction.id,
-            occurred_at: interaction.occurred_at,
-            kind: format_interaction_kind(&interaction.kind),
-            note: interaction.note.clone(),
-            follow_up_at: interaction.follow_up_at,
-        })
-        .collect();
-
-    let emails = ctx.store.emails().list_emails_for_contact(&contact.id)?;
-    let dates = ctx.store.contact_dates().list_for_contact(contact.id)?;
-    let date_dtos: Vec<ContactDateDto> = dates
-        .iter()
-        .map(|date| ContactDateDto {
-            id: date.id,
-            kind: date.kind,
-            label: date.label.clone(),
-            month: date.month,
-            day: date.day,
-            year: date.year,
-        })
-        .collect();
-    let detail = ContactDetailDto {
-        id: contact.id,
-        display_name: contact.display_name.clone(),
-        email: contact.email.clone(),
-        emails,
-        phone: contact.phone.clone(),
-        handle: contact.handle.clone(),
-        timezone: contact.timezone.clone(),
-        next_touchpoint_at: contact.next_touchpoint_at,
-        cadence_days: contact.cadence_days,
-        created_at: contact.created_at,
-        updated_at: contact.updated_at,
-        archived_at: contact.archived_at,
-        tags: tag_names.clone(),
-        dates: date_dtos,
-        recent_interactions: interaction_dtos,
+    if args.limit < 0 || args.offset < 0 {
+        return Err(invalid_input("--limit and --offset must not be negative"));
+    }
+    let options = DetailOptions {
+        range: interaction_range(args.since.as_deref(), args.until.as_deref(), &ctx.clock)?,
+        limit: (!args.all).then_some(args.limit),
+        offset: args.offset,
+        provenance: args.provenance,
+        history: args.history,
     };
+    let detail = contacts::detail(ctx.store, contact.id, &options, ctx.clock.now_utc())?
+        .ok_or_else(|| not_found("contact not found"))?;
 
     if ctx.json {
         print_json(&detail)?;
@@ -297,6 +656,12 @@ pub fn show_contact(ctx: &Context<'_>, args: ShowArgs) -> Result<()> {
 
     println!("id: {}", detail.id);
     println!("name: {}", detail.display_name);
+    if !detail.aliases.is_empty() {
+        println!("aliases: {}", detail.aliases.join(", "));
+    }
+    if let Some(organization) = detail.organization.as_deref() {
+        println!("organization: {}", organization);
+    }
     if !detail.emails.is_empty() {
         println!("emails:");
         for email in &detail.emails {
@@ -319,25 +684,61 @@ pub fn show_contact(ctx: &Context<'_>, args: ShowArgs) -> Result<()> {
         println!("timezone: {}", timezone);
     }
     if let Some(next) = detail.next_touchpoint_at {
-        println!("next_touchpoint_at: {}", format_timestamp_datetime(next));
+        println!(
+            "next_touchpoint_at: {}",
+            ctx.clock.format_timestamp_datetime(next)
+        );
     }
     if let Some(cadence) = detail.cadence_days {
         println!("cadence_days: {}", cadence);
     }
     println!(
         "created_at: {}",
-        format_timestamp_datetime(detail.created_at)
+        ctx.clock.format_timestamp_datetime(detail.created_at)
     );
     println!(
         "updated_at: {}",
-        format_timestamp_datetime(detail.updated_at)
+        ctx.clock.format_timestamp_datetime(detail.updated_at)
     );
     if let Some(archived) = detail.archived_at {
-        println!("archived_at: {}", format_timestamp_datetime(archived));
+        println!(
+            "archived_at: {}",
+            ctx.clock.format_timestamp_datetime(archived)
+        );
+    }
+    if let Some(reason) = detail.archive_reason.as_deref() {
+        println!("archive_reason: {}", reason);
+    }
+    if let Some(muted_until) = detail.muted_until {
+        println!(
+            "muted_until: {}",
+            ctx.clock.format_timestamp_datetime(muted_until)
+        );
+    }
+    if detail.priority != 0 {
+        let starred = if is_starred(detail.priority) {
+            " (starred)"
+        } else {
+            ""
+        };
+        println!("priority: {}{}", detail.priority, starred);
+    }
+    if let Some(origin) = &detail.origin {
+        if let Some(line) = origin.summary() {
+            println!("met: {}", line);
+        }
+    }
+    for touch in &detail.last_touch_context {
+        println!(
+            "last {}: {}",
+            touch.channel.label().to_ascii_lowercase(),
+            touch.summary(&ctx.clock)
+        );
     }
 
-    if !tag_names.is_empty() {
-        let tag_line = tag_names
+    if !detail.tags.is_empty() {
+        let tag_line = detail
+            .tags
             .iter()
             .map(|tag| format!("#{}", tag))
             .collect::<Vec<_>>()
@@ -350,64 +751,203 @@ pub fn show_contact(ctx: &Context<'_>, args: ShowArgs) -> Result<()> {
         for date in &detail.dates {
             let label = format_contact_date_label(date.kind, date.label.as_deref());
             let date_str = format_date_parts(date.month, date.day, date.year);
-            println!("  {}  {}", label, date_str);
+            let lead = match date.remind_days_before {
+                Some(days) if days > 0 => format!("  (remind {days}d before)"),
+                _ => String::new(),
+            };
+            println!("  {}  {}{}", label, date_str, lead);
+        }
+    }
+
+    if args.provenance {
+        if detail.provenance.is_empty() {
+            println!("provenance: none recorded");
+        } else {
+            println!("provenance:");
+            for entry in &detail.provenance {
+                println!(
+                    "  {}  {}  {}",
+                    entry.field,
+                    entry.source,
+                    ctx.clock.format_timestamp_datetime(entry.updated_at)
+                );
+            }
+        }
+    }
+
+    if args.history {
+        if detail.sync_history.is_empty() {
+            println!("sync history: none recorded");
+        } else {
+            println!("sync history:");
+            for entry in &detail.sync_history {
+                println!(
+                    "  {}  {}  {}",
+                    ctx.clock.format_timestamp_datetime(entry.synced_at),
+                    entry.source,
+                    entry.fields.join(", ")
+                );
+            }
         }
     }
 
-    if detail.recent_interactions.is_empty() {
-        println!("interactions: none");
+    let shown = detail.recent_interactions.len();
+    if shown == 0 {
+        if detail.interactions_total == 0 {
+            println!("interactions: none");
+        } else {
+            println!(
+                "interactions: none in this page ({} total)",
+                detail.interactions_total
+            );
+        }
     } else {
-        println!("interactions:");
+        let first = args.offset as usize + 1;
+        println!(
+            "interactions ({}-{} of {}):",
+            first,
+            first + shown - 1,
+            detail.interactions_total
+        );
         for interaction in detail.recent_interactions {
-            let when = format_timestamp_datetime(interaction.occurred_at);
+            let when = ctx.clock.format_timestamp_datetime(interaction.occurred_at);
             let kind = interaction.kind;
             let note = if interaction.note.trim().is_empty() {
                 "(no note)"
             } else {
                 &interaction.note
             };
-            println!("  {} [{}] {}", when, kind, note);
+            let attached = match interaction.attachments.len() {
+                0 => String::new(),
+                1 => " (1 attachment)".to_string(),
+                count => format!(" ({count} attachments)"),
+            };
+            println!("  {} [{}] {}{}", when, kind, note, attached);
+            for attachment in &interaction.attachments {
+                println!(
+                    "      {:<4}  {}",
+                    attachment.kind.as_str(),
+                    attachment.value
+                );
+            }
+        }
+        let next_offset = args.offset as usize + shown;
+        if next_offset < detail.interactions_total {
+            println!("  ... older: --offset {next_offset} (or --all)");
         }
     }
 
     Ok(())
 }
 
+/// `--since` starts at the given local date/time; `--until` includes the whole
+/// day (or the given minute), so the store sees an exclusive upper bound.
+/// Resolves a handle the way the `@handle` filter does: case-insensitive, and
+/// matching stored handles with or without a leading `@`.
+fn find_by_handle(ctx: &Context<'_>, raw: &str) -> Result<Contact> {
+    let handle = raw.trim().trim_start_matches('@');
+    if handle.is_empty() {
+        return Err(invalid_input("handle must not be empty"));
+    }
+    let contacts = ctx.store.contacts();
+    let mut matches = contacts.list_by_handle(handle)?;
+    for contact in contacts.list_by_handle(&format!("@{handle}"))? {
+        if !matches.iter().any(|existing| existing.id == contact.id) {
+            matches.push(contact);
+        }
+    }
+    match matches.len() {
+        0 => Err(not_found(format!("no contact with handle @{handle}"))),
+        1 => Ok(matches.remove(0)),
+        _ => {
+            let ids = matches
+                .iter()
+                .map(|contact| format!("{} ({})", contact.id, contact.display_name))
+                .collect::<Vec<_>>()
+                .join(", ");
+            Err(invalid_input(format!(
+                "handle @{handle} matches {} contacts: {ids}; pass an id instead",
+                matches.len()
+            )))
+        }
+    }
+}
+
+fn interaction_range(
+    since: Option<&str>,
+    until: Option<&str>,
+    clock: &Clock,
+) -> Result<InteractionRange> {
+    let since = since
+        .map(|raw| clock.parse_local_timestamp(raw))
+        .transpose()?;
+    let until = match until {
+        Some(raw) => {
+            let (timestamp, precision) = clock.parse_local_timestamp_with_precision(raw)?;
+            Some(match precision {
+                TimePrecision::Date => {
+                    let next_day = chrono::NaiveDate::parse_from_str(raw.trim(), "%Y-%m-%d")?
+                        .succ_opt()
+                        .ok_or_else(|| invalid_input("--until date is out of range"))?;
+                    clock.parse_local_timestamp(&next_day.format("%Y-%m-%d").to_string())?
+                }
+                TimePrecision::Minute => timestamp + 60,
+                TimePrecision::Second => timestamp + 1,
+            })
+        }
+        None => None,
+    };
+    if let (Some(since), Some(until)) = (since, until) {
+        if since >= until {
+            return Err(invalid_input("--since must be before --until"));
+        }
+    }
+    Ok(InteractionRange { since, until })
+}
+
 pub fn list_contacts(ctx: &Context<'_>, args: ListArgs) -> Result<()> {
-    let filter_text = args.filter.as_deref().unwrap_or_default();
-    let parsed = parse_filter(filter_text)?;
-    let mut query = ContactQuery::from_filter(&parsed)?;
-    apply_archived_filter(&mut query, &args)?;
-
-    let now = now_utc();
-    let offset = local_offset();
-    let soon_days = ctx.config.due_soon_days;
-    let contacts = ctx
-        .store
-        .contacts()
-        .list_contacts(&query, now, soon_days, offset)?;
-
-    let contact_ids = contacts
-        .iter()
-        .map(|contact| contact.id)
-        .collect::<Vec<_>>();
-    let tags_by_contact = ctx.store.tags().list_names_for_contacts(&contact_ids)?;
-
-    let mut items = Vec::with_capacity(contacts.len());
-    for contact in contacts {
-        let tag_names = tags_by_contact
-            .get(&contact.id)
-            .cloned()
-            .unwrap_or_default();
-        let due_state = compute_due_state(now, contact.next_touchpoint_at, soon_days, offset)?;
-        items.push(ContactListItemDto {
-            id: contact.id,
-            display_name: contact.display_name,
-            due_state,
-            next_touchpoint_at: contact.next_touchpoint_at,
-            archived_at: contact.archived_at,
-            tags: tag_names,
-        });
+    let options = ListOptions {
+        filter: args.filter.unwrap_or_default(),
+        archived: if args.only_archived {
+            ArchivedScope::Only
+        } else if args.include_archived {
+            ArchivedScope::Include
+        } else {
+            ArchivedScope::Active
+        },
+        sort: match args.sort {
+            ListSortArg::Due => ContactSort::Due,
+            ListSortArg::Archived => ContactSort::Archived,
+        },
+        awaiting_reply: args.awaiting_reply,
+        awaiting_reply_days: args.reply_days,
+        ..ListOptions::new(ctx.config.due_soon_days)
+    };
+    let now = ctx.clock.now_utc();
+    let items = contacts::list(ctx.store, &options, now, ctx.clock.local_offset())?;
+
+    if let Some(ListGroupByArg::Org) = args.group_by {
+        let groups = group_by_org(items);
+        if ctx.json {
+            return print_json(&ContactGroupsDto { groups });
+        }
+        if groups.is_empty() {
+            println!("no contacts");
+        }
+        for (index, group) in groups.iter().enumerate() {
+            if index > 0 {
+                println!();
+            }
+            println!(
+                "{} ({})",
+                group.org.as_deref().unwrap_or("(no organization)"),
+                group.contacts.len()
+            );
+            for item in &group.contacts {
+                println!("  {}", format_list_row(item, now, &ctx.clock));
+            }
+        }
+        return Ok(());
     }
 
     if ctx.json {
@@ -420,65 +960,270 @@ pub fn list_contacts(ctx: &Context<'_>, args: ListArgs) -> Result<()> {
         return Ok(());
     }
 
+    for item in &items {
+        println!("{}", format_list_row(item, now, &ctx.clock));
+    }
+
+    Ok(())
+}
+
+/// Splits a sorted listing into per-organization groups, keeping the listing
+/// order inside each group. Organizations are ordered by name, ignoring case;
+/// contacts without one come last.
+fn group_by_org(items: Vec<ContactListItemDto>) -> Vec<ContactGroupDto> {
+    let mut groups: Vec<ContactGroupDto> = Vec::new();
+    let mut ungrouped = Vec::new();
     for item in items {
-        let due = due_state_label(item.due_state);
-        let date = item
-            .next_touchpoint_at
-            .map(format_timestamp_date)
-            .unwrap_or_else(|| "-".to_string());
-        let tag_suffix = if item.tags.is_empty() {
-            String::new()
-        } else {
-            let tags = item
-                .tags
-                .iter()
-                .map(|tag| format!("#{}", tag))
-                .collect::<Vec<_>>()
-                .join(" ");
-            format!(" {}", tags)
+        let Some(org) = item.organization.clone() else {
+            ungrouped.push(item);
+            continue;
         };
-        println!(
-            "{}  {}  [{}]  {}{}",
-            item.id, item.display_name, due, date, tag_suffix
-        );
+        match groups
+            .iter_mut()
+            .find(|group| group.org.as_deref() == Some(org.as_str()))
+        {
+            Some(group) => group.contacts.push(item),
+            None => groups.push(ContactGroupDto {
+                org: Some(org),
+                contacts: vec![item],
+            }),
+        }
     }
+    groups.sort_by_cached_key(|group| group.org.as_deref().map(str::to_lowercase));
+    if !ungrouped.is_empty() {
+        groups.push(ContactGroupDto {
+            org: None,
+            contacts: ungrouped,
+        });
+    }
+    groups
+}
 
-    Ok(())
+fn format_list_row(item: &ContactListItemDto, now: i64, clock: &Clock) -> String {
+    let due = due_state_label(item.due_state);
+    let date = item
+        .next_touchpoint_at
+        .map(|ts| clock.format_timestamp_date(ts))
+        .unwrap_or_else(|| "-".to_string());
+    let last = format_last_interaction(item, now, clock);
+    let tag_suffix = if item.tags.is_empty() {
+        String::new()
+    } else {
+        let tags = item
+            .tags
+            .iter()
+            .map(|tag| format!("#{}", tag))
+            .collect::<Vec<_>>()
+            .join(" ");
+        format!(" {}", tags)
+    };
+    let archived_suffix = match (item.archived_at, item.archive_reason.as_deref()) {
+        (Some(archived_at), Some(reason)) => format!(
+            "  (archived {}: {})",
+            clock.format_timestamp_date(archived_at),
+            reason
+        ),
+        (Some(archived_at), None) => {
+            format!("  (archived {})", clock.format_timestamp_date(archived_at))
+        }
+        (None, _) => String::new(),
+    };
+    let muted_suffix = item
+        .muted_until
+        .map(|until| format!("  (muted until {})", clock.format_timestamp_date(until)))
+        .unwrap_or_default();
+    let star = if is_starred(item.priority) {
+        "★ "
+    } else {
+        ""
+    };
+    format!(
+        "{}  {}{}  [{}]  {}  {}{}{}{}",
+        item.id,
+        star,
+        item.display_name,
+        due,
+        date,
+        last,
+        tag_suffix,
+        archived_suffix,
+        muted_suffix
+    )
+}
+
+/// "3w ago (call)" for the list's last-interaction column, "-" when there is none.
+fn format_last_interaction(item: &ContactListItemDto, now: i64, clock: &Clock) -> String {
+    match (
+        item.last_interaction_at,
+        item.last_interaction_kind.as_deref(),
+    ) {
+        (Some(at), Some(kind)) => format!("{} ({kind})", clock.format_relative_past(at, now)),
+        (Some(at), None) => clock.format_relative_past(at, now),
+        (None, _) => "-".to_string(),
+    }
 }
 
 pub fn delete_contact(ctx: &Context<'_>, args: DeleteArgs) -> Result<()> {
-    let id = parse_contact_id(&args.id)?;
-    ctx.store.contacts().delete(now_utc(), id)?;
+    let id = resolve_contact_arg(ctx, &args.id)?;
+    let contact = ctx
+        .store
+        .contacts()
+        .get(id)?
+        .ok_or_else(|| not_found("contact not found"))?;
+    if !args.hard {
+        return trash_contact(ctx, contact);
+    }
+    if contact.archived_at.is_none() && !args.force {
+        return Err(invalid_input(format!(
+            "{} is not archived; use `knotter archive-contact {}` instead, or pass --force to delete permanently",
+            contact.display_name, id
+        )));
+    }
+
+    let impact = ctx.store.contacts().delete_impact(id)?;
+    let summary = format!(
+        "{} interactions, {} emails, {} dates, {} tags, {} open merge candidates",
+        impact.interactions, impact.emails, impact.dates, impact.tags, impact.open_merge_candidates
+    );
+    if !args.yes {
+        confirm_delete(&contact, &summary)?;
+    }
+
+    ctx.store.contacts().delete(ctx.clock.now_utc(), id)?;
     if ctx.json {
-        print_json(&serde_json::json!({ "id": id }))?;
+        print_json(&DeletedContactDto {
+            id,
+            display_name: contact.display_name,
+            interactions: impact.interactions,
+            emails: impact.emails,
+            dates: impact.dates,
+            tags: impact.tags,
+            merge_candidates_dismissed: impact.open_merge_candidates,
+        })?;
     } else {
-        println!("deleted {}", id);
+        println!("deleted {} {} ({})", id, contact.display_name, summary);
     }
     Ok(())
 }
 
-pub fn archive_contact(ctx: &Context<'_>, args: ArchiveArgs) -> Result<()> {
-    let id = parse_contact_id(&args.id)?;
-    let contact = ctx.store.contacts().archive(now_utc(), id)?;
+fn trash_contact(ctx: &Context<'_>, contact: Contact) -> Result<()> {
+    let now = ctx.clock.now_utc();
+    let impact = ctx.store.contacts().delete_impact(contact.id)?;
+    ctx.store.contacts().trash(now, contact.id)?;
     if ctx.json {
-        print_json(&contact)?;
+        print_json(&TrashedContactDto {
+            id: contact.id,
+            display_name: contact.display_name,
+            deleted_at: now,
+            merge_candidates_dismissed: impact.open_merge_candidates,
+        })?;
+    } else {
+        println!(
+            "moved {} {} to the trash (undo with `knotter trash restore {}`)",
+            contact.id, contact.display_name, contact.id
+        );
+    }
+    Ok(())
+}
+
+fn confirm_delete(contact: &Contact, summary: &str) -> Result<()> {
+    eprintln!(
+        "Deleting {} ({}) also removes {}.",
+        contact.display_name, contact.id, summary
+    );
+    let answer = read_confirmation("Type the contact's display name to confirm: ", "delete")?;
+    if answer != contact.display_name {
+        return Err(invalid_input("confirmation did not match; nothing deleted"));
+    }
+    Ok(())
+}
+
+pub fn archive_contact(ctx: &Context<'_>, args: ArchiveArgs) -> Result<()> {
+    let id = resolve_contact_arg(ctx, &args.id)?;
+    let reason = args.reason.and_then(normalize_optional_value);
+    let (contact, changes) = run_contact_change(ctx, id, args.dry_run, || {
+        Ok(ctx
+            .store
+            .contacts()
+            .archive_with_reason(ctx.clock.now_utc(), id, reason.as_deref())?)
+    })?;
+    let summary = match reason.as_deref() {
+        Some(reason) => format!(
+            "archived {} {} ({})",
+            contact.id, contact.display_name, reason
+        ),
+        None => format!("archived {} {}", contact.id, contact.display_name),
+    };
+    let output = ArchivedContactDto {
+        contact: &contact,
+        archive_reason: reason,
+    };
+    if args.dry_run {
+        print_dry_run(ctx, &output, &summary, &changes)?;
+    } else if ctx.json {
+        print_json(&output)?;
     } else {
-        println!("archived {} {}", contact.id, contact.display_name);
+        println!("{summary}");
     }
     Ok(())
 }
 
 pub fn unarchive_contact(ctx: &Context<'_>, args: UnarchiveArgs) -> Result<()> {
-    let id = parse_contact_id(&args.id)?;
-    let contact = ctx.store.contacts().unarchive(now_utc(), id)?;
-    if ctx.json {
+    let id = resolve_contact_arg(ctx, &args.id)?;
+    let (contact, changes) = run_contact_change(ctx, id, args.dry_run, || {
+        Ok(ctx.store.contacts().unarchive(ctx.clock.now_utc(), id)?)
+    })?;
+    let summary = format!("unarchived {} {}", contact.id, contact.display_name);
+    if args.dry_run {
+        print_dry_run(ctx, &contact, &summary, &changes)?;
+    } else if ctx.json {
         print_json(&contact)?;
     } else {
-        println!("unarchived {} {}", contact.id, contact.display_name);
+        println!("{summary}");
     }
     Ok(())
 }
 
+fn origin_update(
+    ctx: &Context<'_>,
+    met: Option<String>,
+    met_date: Option<String>,
+    met_via: Option<String>,
+) -> Result<ContactOriginUpdate> {
+    let met_on = match met_date.as_deref().map(str::trim) {
+        Some("") => Some(None),
+        Some(raw) => Some(Some(NaiveDate::parse_from_str(raw, "%Y-%m-%d").map_err(
+            |_| invalid_input(format!("--met-date must be YYYY-MM-DD: {raw}")),
+        )?)),
+        None => None,
+    };
+    let met_via = match met_via.as_deref().map(str::trim) {
+        Some("") => Some(None),
+        Some(raw) => Some(Some(resolve_contact_arg(ctx, raw)?)),
+        None => None,
+    };
+    Ok(ContactOriginUpdate {
+        met_at: met.map(normalize_optional_value),
+        met_on,
+        met_via,
+    })
+}
+
+/// Trims a phone from the command line and, with `normalize`
+/// (`contacts.normalize_phones`), stores it in canonical form. Values that do
+/// not parse are kept as typed unless `strict` (`--strict-phone`) is set.
+fn prepare_phone(phone: Option<String>, normalize: bool, strict: bool) -> Result<Option<String>> {
+    let Some(phone) = phone.and_then(normalize_optional_value) else {
+        return Ok(None);
+    };
+    match canonical_phone(&phone) {
+        Ok(canonical) if normalize => Ok(Some(canonical)),
+        Ok(_) => Ok(Some(phone)),
+        Err(err) if strict => Err(err.into()),
+        Err(_) => Ok(Some(phone)),
+    }
+}
+
 fn normalize_optional_value(value: String) -> Option<String> {
     let trimmed = value.trim();
     if trimmed.is_empty() {
@@ -512,29 +1257,11 @@ fn update_is_empty(update: &ContactUpdate) -> bool {
         && update.next_touchpoint_at.is_none()
         && update.cadence_days.is_none()
         && update.archived_at.is_none()
+        && update.organization.is_none()
 }
 
-fn apply_archived_filter(query: &mut ContactQuery, args: &ListArgs) -> Result<()> {
-    if args.only_archived {
-        if let Some(selector) = query.archived {
-            if selector != knotter_core::filter::ArchivedSelector::Archived {
-                return Err(invalid_input(
-                    "archived:false conflicts with --only-archived",
-                ));
-            }
-            return Ok(());
-        }
-        query.archived = Some(knotter_core::filter::ArchivedSelector::Archived);
-        return Ok(());
-    }
-
-    if query.archived.is_none() && !args.include_archived {
-        query.archived = Some(knotter_core::filter::ArchivedSelector::Active);
-    }
-
-    Ok(())
-}
-
+/// Archived selector for `list`; an `archived:` term in the filter wins unless it
+/// contradicts `--only-archived`.
 fn parse_tags(tags: &[String]) -> Result<Vec<TagName>> {
     let mut parsed = Vec::with_capacity(tags.len());
     for raw in tags {
@@ -0,0 +1,352 @@
+use super::{normalize_emails, normalize_optional_value, prepare_phone, update_is_empty};
+use crate::commands::dry_run::{diff_snapshots, print_changes, snapshot, FieldChange};
+use crate::commands::{print_json, Context};
+use crate::error::invalid_input;
+use crate::util::parse_contact_id;
+use anyhow::{Context as _, Result};
+use knotter_core::domain::{ContactId, TagName};
+use knotter_core::rules::ensure_future_timestamp_with_precision;
+use knotter_core::time::Clock;
+use knotter_store::repo::{ContactUpdate, ContactsRepo, EmailOps, TagsRepo};
+use serde::{Deserialize, Deserializer, Serialize};
+use std::fs;
+use std::io::{self, Read};
+use std::path::Path;
+
+/// One JSONL line of `edit-contact --batch`. Omitted fields are left unchanged;
+/// `null` clears optional fields.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct BatchEditLine {
+    id: String,
+    #[serde(default)]
+    name: Option<String>,
+    #[serde(default, deserialize_with = "present")]
+    email: Option<Option<String>>,
+    #[serde(default)]
+    add_emails: Vec<String>,
+    #[serde(default)]
+    remove_emails: Vec<String>,
+    #[serde(default)]
+    clear_emails: bool,
+    #[serde(default, deserialize_with = "present")]
+    phone: Option<Option<String>>,
+    #[serde(default, deserialize_with = "present")]
+    handle: Option<Option<String>>,
+    #[serde(default, deserialize_with = "present")]
+    timezone: Option<Option<String>>,
+    #[serde(default, deserialize_with = "present")]
+    organization: Option<Option<String>>,
+    #[serde(default, deserialize_with = "present")]
+    cadence_days: Option<Option<i32>>,
+    #[serde(default, deserialize_with = "present")]
+    next_touchpoint_at: Option<Option<String>>,
+    #[serde(default)]
+    add_tags: Vec<String>,
+    #[serde(default)]
+    remove_tags: Vec<String>,
+}
+
+/// Distinguishes an explicit `null` (Some(None)) from an omitted field (None).
+fn present<'de, T, D>(deserializer: D) -> std::result::Result<Option<Option<T>>, D::Error>
+where
+    T: Deserialize<'de>,
+    D: Deserializer<'de>,
+{
+    Option::<T>::deserialize(deserializer).map(Some)
+}
+
+struct PlannedEdit {
+    id: ContactId,
+    update: ContactUpdate,
+    email_ops: EmailOps,
+    add_tags: Vec<TagName>,
+    remove_tags: Vec<TagName>,
+}
+
+#[derive(Debug, Serialize)]
+struct BatchEditResult {
+    line: usize,
+    id: Option<ContactId>,
+    status: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+    changes: Vec<FieldChange>,
+}
+
+pub(super) fn edit_contacts_batch(ctx: &Context<'_>, source: &Path, dry_run: bool) -> Result<()> {
+    let now = ctx.clock.now_utc();
+    let input = read_batch_input(source)?;
+    let contacts = ctx.store.contacts();
+
+    let mut results = Vec::new();
+    let mut planned = Vec::new();
+    for (index, raw) in input.lines().enumerate() {
+        let line = index + 1;
+        if raw.trim().is_empty() {
+            continue;
+        }
+        match plan_edit(
+            &contacts,
+            now,
+            &ctx.clock,
+            ctx.config.contacts.normalize_phones,
+            raw,
+        ) {
+            Ok(plan) => {
+                results.push(BatchEditResult {
+                    line,
+                    id: Some(plan.id),
+                    status: "pending",
+                    error: None,
+                    changes: Vec::new(),
+                });
+                planned.push((results.len() - 1, plan));
+            }
+            Err((id, err)) => results.push(BatchEditResult {
+                line,
+                id,
+                status: "failed",
+                error: Some(format!("{err:#}")),
+                changes: Vec::new(),
+            }),
+        }
+    }
+    if results.is_empty() {
+        return Err(invalid_input("batch input contains no edits"));
+    }
+
+    let mut failed = results.iter().filter(|r| r.status == "failed").count();
+    if failed == 0 {
+        let tx = ctx.store.connection().unchecked_transaction()?;
+        let contacts = ContactsRepo::new(&tx);
+        let tags = TagsRepo::new(&tx);
+        for (result_index, plan) in planned {
+            let result = &mut results[result_index];
+            match apply_edit(ctx, &contacts, &tags, now, plan) {
+                Ok(changes) => {
+                    result.status = match (dry_run, changes.is_empty()) {
+                        (_, true) => "unchanged",
+                        (true, false) => "would_update",
+                        (false, false) => "updated",
+                    };
+                    result.changes = changes;
+                }
+                Err(err) => {
+                    result.status = "failed";
+                    result.error = Some(format!("{err:#}"));
+                    failed += 1;
+                    break;
+                }
+            }
+        }
+        if failed == 0 && !dry_run {
+            tx.commit()?;
+        }
+    }
+    for result in results.iter_mut() {
+        if result.status == "pending" || (failed > 0 && result.status == "updated") {
+            result.status = "skipped";
+        }
+    }
+
+    if ctx.json {
+        print_json(&results)?;
+    } else {
+        print_results(&results, dry_run, &ctx.clock);
+    }
+
+    if failed > 0 {
+        return Err(invalid_input(format!(
+            "batch edit failed on {failed} line(s); no changes applied"
+        )));
+    }
+    Ok(())
+}
+
+fn read_batch_input(source: &Path) -> Result<String> {
+    if source.as_os_str() == "-" {
+        let mut input = String::new();
+        io::stdin()
+            .read_to_string(&mut input)
+            .with_context(|| "read batch edits from stdin")?;
+        return Ok(input);
+    }
+    fs::read_to_string(source).with_context(|| format!("read batch file {}", source.display()))
+}
+
+fn plan_edit(
+    contacts: &ContactsRepo<'_>,
+    now: i64,
+    clock: &Clock,
+    normalize_phones: bool,
+    raw: &str,
+) -> std::result::Result<PlannedEdit, (Option<ContactId>, anyhow::Error)> {
+    let line: BatchEditLine = serde_json::from_str(raw)
+        .map_err(|err| (None, invalid_input(format!("invalid JSON: {err}"))))?;
+    let id = parse_contact_id(&line.id).map_err(|err| (None, err))?;
+    build_plan(contacts, now, clock, normalize_phones, id, line).map_err(|err| (Some(id), err))
+}
+
+fn build_plan(
+    contacts: &ContactsRepo<'_>,
+    now: i64,
+    clock: &Clock,
+    normalize_phones: bool,
+    id: ContactId,
+    line: BatchEditLine,
+) -> Result<PlannedEdit> {
+    if contacts.get(id)?.is_none() {
+        return Err(invalid_input(format!("contact not found: {id}")));
+    }
+    if line.email.is_some() && (!line.add_emails.is_empty() || !line.remove_emails.is_empty()) {
+        return Err(invalid_input(
+            "email cannot be combined with add_emails or remove_emails",
+        ));
+    }
+    if line.clear_emails
+        && (line.email.is_some() || !line.add_emails.is_empty() || !line.remove_emails.is_empty())
+    {
+        return Err(invalid_input(
+            "clear_emails cannot be combined with other email fields",
+        ));
+    }
+
+    let mut update = ContactUpdate::default();
+    if let Some(name) = line.name {
+        update.display_name = Some(name);
+    }
+    if let Some(email) = line.email {
+        update.email = Some(email.and_then(normalize_optional_value));
+        update.email_source = Some("cli".to_string());
+    }
+    if line.clear_emails {
+        update.email = Some(None);
+    }
+    if let Some(phone) = line.phone {
+        update.phone = Some(prepare_phone(phone, normalize_phones, false)?);
+    }
+    if let Some(handle) = line.handle {
+        update.handle = Some(handle.and_then(normalize_optional_value));
+    }
+    if let Some(timezone) = line.timezone {
+        update.timezone = Some(timezone.and_then(normalize_optional_value));
+    }
+    if let Some(organization) = line.organization {
+        update.organization = Some(organization.and_then(normalize_optional_value));
+    }
+    if let Some(cadence) = line.cadence_days {
+        update.cadence_days = Some(cadence);
+    }
+    if let Some(value) = line.next_touchpoint_at {
+        update.next_touchpoint_at = Some(match value {
+            Some(value) => {
+                let (timestamp, precision) = clock.parse_local_timestamp_with_precision(&value)?;
+                Some(ensure_future_timestamp_with_precision(
+                    now, timestamp, precision, clock,
+                )?)
+            }
+            None => None,
+        });
+    }
+
+    let add_emails = normalize_emails(&line.add_emails);
+    let remove_emails = normalize_emails(&line.remove_emails);
+    if let Some(email) = add_emails
+        .iter()
+        .find(|email| remove_emails.contains(email))
+    {
+        return Err(invalid_input(format!(
+            "email {email} cannot be both added and removed"
+        )));
+    }
+    let has_email_ops = line.clear_emails || !add_emails.is_empty() || !remove_emails.is_empty();
+
+    let add_tags = parse_tags(&line.add_tags)?;
+    let remove_tags = parse_tags(&line.remove_tags)?;
+    if let Some(tag) = add_tags.iter().find(|tag| remove_tags.contains(tag)) {
+        return Err(invalid_input(format!(
+            "tag {} cannot be both added and removed",
+            tag.as_str()
+        )));
+    }
+
+    if update_is_empty(&update) && !has_email_ops && add_tags.is_empty() && remove_tags.is_empty() {
+        return Err(invalid_input("no updates provided"));
+    }
+
+    let email_ops = if has_email_ops {
+        EmailOps::Mutate {
+            clear: line.clear_emails,
+            add: add_emails,
+            remove: remove_emails,
+            source: Some("cli".to_string()),
+        }
+    } else {
+        EmailOps::None
+    };
+
+    Ok(PlannedEdit {
+        id,
+        update,
+        email_ops,
+        add_tags,
+        remove_tags,
+    })
+}
+
+fn parse_tags(values: &[String]) -> Result<Vec<TagName>> {
+    values
+        .iter()
+        .map(|value| TagName::new(value).map_err(Into::into))
+        .collect()
+}
+
+fn apply_edit(
+    ctx: &Context<'_>,
+    contacts: &ContactsRepo<'_>,
+    tags: &TagsRepo<'_>,
+    now: i64,
+    plan: PlannedEdit,
+) -> Result<Vec<FieldChange>> {
+    let before = snapshot(ctx, plan.id)?;
+    if !update_is_empty(&plan.update) || !matches!(plan.email_ops, EmailOps::None) {
+        contacts.update_with_email_ops(now, plan.id, plan.update, plan.email_ops)?;
+    }
+    let contact_id = plan.id.to_string();
+    for tag in plan.add_tags {
+        tags.add_tag_to_contact(&contact_id, tag)?;
+    }
+    for tag in plan.remove_tags {
+        tags.remove_tag_from_contact(&contact_id, tag)?;
+    }
+    let after = snapshot(ctx, plan.id)?;
+    Ok(diff_snapshots(&before, &after))
+}
+
+fn print_results(results: &[BatchEditResult], dry_run: bool, clock: &Clock) {
+    for result in results {
+        let id = result
+            .id
+            .map(|id| id.to_string())
+            .unwrap_or_else(|| "-".to_string());
+        match &result.error {
+            Some(error) => println!("line {}: {} {}: {}", result.line, result.status, id, error),
+            None => println!("line {}: {} {}", result.line, result.status, id),
+        }
+        print_changes(&result.changes, clock);
+    }
+    let count = |status: &str| results.iter().filter(|r| r.status == status).count();
+    let applied = if dry_run {
+        count("would_update")
+    } else {
+        count("updated")
+    };
+    let label = if dry_run { "would update" } else { "updated" };
+    println!(
+        "{label} {applied}, unchanged {}, failed {}, skipped {}",
+        count("unchanged"),
+        count("failed"),
+        count("skipped")
+    );
+}
@@ -0,0 +1,412 @@
+use super::{new_contact_defaults, normalize_optional_value, prepare_phone, OnDuplicate};
+use crate::commands::{print_json, Context};
+use crate::error::invalid_input;
+use anyhow::{Context as _, Result};
+use knotter_core::domain::{
+    normalize_email, Contact, ContactId, MergeCandidateId, MergeCandidateReason, TagName,
+};
+use knotter_store::repo::{
+    ContactNew, ContactsRepo, EmailsRepo, MergeCandidateCreate, MergeCandidatesRepo,
+};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fs;
+use std::io::{self, Read};
+use std::path::Path;
+
+/// Provenance source for contacts and emails added from a table.
+const BULK_ADD_SOURCE: &str = "cli";
+
+struct Columns {
+    name: usize,
+    email: Option<usize>,
+    phone: Option<usize>,
+    handle: Option<usize>,
+    timezone: Option<usize>,
+    cadence_days: Option<usize>,
+    tags: Option<usize>,
+}
+
+impl Columns {
+    fn from_headers(headers: &csv::StringRecord) -> Result<Self> {
+        let find = |name: &str| {
+            headers
+                .iter()
+                .position(|header| header.trim().eq_ignore_ascii_case(name))
+        };
+        Ok(Self {
+            name: find("name")
+                .ok_or_else(|| invalid_input("header row is missing a `name` column"))?,
+            email: find("email"),
+            phone: find("phone"),
+            handle: find("handle"),
+            timezone: find("timezone"),
+            cadence_days: find("cadence_days"),
+            tags: find("tags"),
+        })
+    }
+}
+
+/// Who already has one of a row's emails.
+#[derive(Debug, Clone, Copy)]
+enum EmailOwner {
+    Stored(ContactId),
+    /// An earlier row, by its index in the results.
+    Row(usize),
+}
+
+struct PlannedRow {
+    contact: ContactNew,
+    tags: Vec<TagName>,
+    emails: Vec<String>,
+    /// Taken emails left off the contact, with the first owner found.
+    duplicate: Option<(Vec<String>, EmailOwner)>,
+}
+
+#[derive(Debug, Serialize)]
+struct BulkAddResult {
+    line: usize,
+    name: String,
+    id: Option<ContactId>,
+    status: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    duplicate_emails: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    merge_candidate_id: Option<MergeCandidateId>,
+}
+
+/// Validates every row first; only when all of them pass are the contacts
+/// added, in one transaction.
+pub(super) fn add_contacts_from_table(
+    ctx: &Context<'_>,
+    source: &Path,
+    on_duplicate: OnDuplicate,
+    dry_run: bool,
+    no_defaults: bool,
+) -> Result<()> {
+    let now = ctx.clock.now_utc();
+    let input = read_table_input(source)?;
+    let delimiter = if input.lines().next().unwrap_or_default().contains('\t') {
+        b'\t'
+    } else {
+        b','
+    };
+    let mut reader = csv::ReaderBuilder::new()
+        .delimiter(delimiter)
+        .flexible(true)
+        .from_reader(input.as_bytes());
+    let columns = Columns::from_headers(reader.headers()?)?;
+
+    let emails = EmailsRepo::new(ctx.store.connection());
+    let mut claimed: HashMap<String, usize> = HashMap::new();
+    let mut results = Vec::new();
+    let mut planned = Vec::new();
+    for record in reader.records() {
+        let record = record.map_err(|err| invalid_input(format!("invalid table: {err}")))?;
+        if record.iter().all(|field| field.trim().is_empty()) {
+            continue;
+        }
+        let line = record
+            .position()
+            .map(|pos| line_number(&input, pos.byte() as usize))
+            .unwrap_or_default();
+        let name = field(&record, Some(columns.name)).to_string();
+        let index = results.len();
+        let mut result = BulkAddResult {
+            line,
+            name,
+            id: None,
+            status: "pending",
+            error: None,
+            duplicate_emails: Vec::new(),
+            merge_candidate_id: None,
+        };
+        let plan = plan_row(ctx, now, no_defaults, &columns, &record).and_then(|mut plan| {
+            let mut taken = Vec::new();
+            let mut owner = None;
+            for email in &plan.emails {
+                let found = match claimed.get(email) {
+                    Some(&row) => Some(EmailOwner::Row(row)),
+                    None => emails
+                        .find_contact_id_by_email(email)?
+                        .map(EmailOwner::Stored),
+                };
+                if let Some(found) = found {
+                    taken.push(email.clone());
+                    owner.get_or_insert(found);
+                }
+            }
+            if let Some(owner) = owner {
+                if on_duplicate == OnDuplicate::Fail {
+                    let owner = match owner {
+                        EmailOwner::Stored(id) => id.to_string(),
+                        EmailOwner::Row(row) => format!("line {}", results_line(&results, row)),
+                    };
+                    return Err(invalid_input(format!(
+                        "email {} already belongs to {owner} (use --on-duplicate skip or stage)",
+                        taken.join(", ")
+                    )));
+                }
+                plan.emails.retain(|email| !taken.contains(email));
+                plan.duplicate = Some((taken, owner));
+            }
+            Ok(plan)
+        });
+        match plan {
+            Ok(plan) => {
+                if let Some((taken, _)) = &plan.duplicate {
+                    result.duplicate_emails = taken.clone();
+                }
+                if plan.duplicate.is_some() && on_duplicate == OnDuplicate::Skip {
+                    result.status = "duplicate";
+                } else {
+                    for email in &plan.emails {
+                        claimed.insert(email.clone(), index);
+                    }
+                    planned.push((index, plan));
+                }
+            }
+            Err(err) => {
+                result.status = "failed";
+                result.error = Some(format!("{err:#}"));
+            }
+        }
+        results.push(result);
+    }
+    if results.is_empty() {
+        return Err(invalid_input("table contains no contacts"));
+    }
+
+    let failed = results.iter().filter(|r| r.status == "failed").count();
+    if failed == 0 {
+        let tx = ctx.store.connection().unchecked_transaction()?;
+        let contacts = ContactsRepo::new(&tx);
+        let candidates = MergeCandidatesRepo::new(&tx);
+        for (index, plan) in planned {
+            let staged = plan.duplicate.is_some();
+            if dry_run {
+                results[index].status = if staged {
+                    "would_stage"
+                } else {
+                    "would_create"
+                };
+                continue;
+            }
+            let contact = contacts.create_with_emails_and_tags(
+                now,
+                plan.contact,
+                plan.tags,
+                plan.emails,
+                Some(BULK_ADD_SOURCE),
+            )?;
+            results[index].id = Some(contact.id);
+            results[index].status = "created";
+            if let Some((_, owner)) = plan.duplicate {
+                let owner_id = match owner {
+                    EmailOwner::Stored(id) => id,
+                    EmailOwner::Row(row) => results[row]
+                        .id
+                        .expect("earlier rows are added before later ones"),
+                };
+                let candidate = candidates.create(
+                    now,
+                    owner_id,
+                    contact.id,
+                    MergeCandidateCreate {
+                        reason: MergeCandidateReason::EmailDuplicate.as_str().to_string(),
+                        source: Some(BULK_ADD_SOURCE.to_string()),
+                        preferred_contact_id: Some(owner_id),
+                    },
+                )?;
+                results[index].status = "staged";
+                results[index].merge_candidate_id = Some(candidate.candidate.id);
+            }
+        }
+        if !dry_run {
+            tx.commit()?;
+        }
+    }
+    for result in results.iter_mut() {
+        if result.status == "pending" {
+            result.status = "skipped";
+        }
+    }
+
+    if ctx.json {
+        print_json(&results)?;
+    } else {
+        print_results(&results, dry_run);
+    }
+
+    if failed > 0 {
+        return Err(invalid_input(format!(
+            "bulk add failed on {failed} line(s); no contacts added"
+        )));
+    }
+    Ok(())
+}
+
+fn results_line(results: &[BulkAddResult], index: usize) -> usize {
+    results.get(index).map(|result| result.line).unwrap_or(0)
+}
+
+/// The reader neither counts blank lines nor moves a record's position past
+/// the blank lines before it, so work the line out from the byte offset.
+fn line_number(input: &str, offset: usize) -> usize {
+    let rest = input.get(offset..).unwrap_or_default();
+    let blank = rest.len() - rest.trim_start_matches(['\r', '\n']).len();
+    input
+        .get(..offset + blank)
+        .unwrap_or(input)
+        .matches('\n')
+        .count()
+        + 1
+}
+
+fn read_table_input(source: &Path) -> Result<String> {
+    if source.as_os_str() == "-" {
+        let mut input = String::new();
+        io::stdin()
+            .read_to_string(&mut input)
+            .with_context(|| "read contacts from stdin")?;
+        return Ok(input);
+    }
+    fs::read_to_string(source).with_context(|| format!("read {}", source.display()))
+}
+
+fn field(record: &csv::StringRecord, column: Option<usize>) -> &str {
+    column
+        .and_then(|index| record.get(index))
+        .unwrap_or_default()
+        .trim()
+}
+
+/// Cells that hold several values separate them with commas or semicolons.
+fn split_list(raw: &str) -> impl Iterator<Item = &str> {
+    raw.split([',', ';'])
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+}
+
+fn plan_row(
+    ctx: &Context<'_>,
+    now: i64,
+    no_defaults: bool,
+    columns: &Columns,
+    record: &csv::StringRecord,
+) -> Result<PlannedRow> {
+    let name = field(record, Some(columns.name));
+    if name.is_empty() {
+        return Err(invalid_input("name is empty"));
+    }
+    let cadence_days = match field(record, columns.cadence_days) {
+        "" => None,
+        raw => Some(
+            raw.parse::<i32>()
+                .map_err(|_| invalid_input(format!("cadence_days is not a number: {raw}")))?,
+        ),
+    };
+    let tags: Vec<String> = split_list(field(record, columns.tags))
+        .map(str::to_string)
+        .collect();
+    let defaults = new_contact_defaults(
+        ctx,
+        now,
+        no_defaults,
+        &tags,
+        cadence_days,
+        None,
+        normalize_optional_value(field(record, columns.timezone).to_string()),
+        0,
+    )?;
+    let mut emails: Vec<String> = Vec::new();
+    for email in split_list(field(record, columns.email)).filter_map(normalize_email) {
+        if !emails.contains(&email) {
+            emails.push(email);
+        }
+    }
+    let phone = prepare_phone(
+        Some(field(record, columns.phone).to_string()),
+        ctx.config.contacts.normalize_phones,
+        false,
+    )?;
+
+    let contact = ContactNew {
+        display_name: name.to_string(),
+        email: None,
+        phone,
+        handle: normalize_optional_value(field(record, columns.handle).to_string()),
+        timezone: defaults.timezone,
+        next_touchpoint_at: defaults.next_touchpoint_at,
+        cadence_days: defaults.cadence_days,
+        archived_at: None,
+        organization: None,
+    };
+    // Catch what the insert would reject before anything is written.
+    Contact {
+        id: ContactId::new(),
+        display_name: contact.display_name.clone(),
+        email: None,
+        phone: contact.phone.clone(),
+        handle: contact.handle.clone(),
+        timezone: contact.timezone.clone(),
+        next_touchpoint_at: contact.next_touchpoint_at,
+        cadence_days: contact.cadence_days,
+        created_at: now,
+        updated_at: now,
+        archived_at: None,
+        organization: None,
+    }
+    .validate()?;
+
+    Ok(PlannedRow {
+        contact,
+        tags: defaults.tags,
+        emails,
+        duplicate: None,
+    })
+}
+
+fn print_results(results: &[BulkAddResult], dry_run: bool) {
+    for result in results {
+        let id = result
+            .id
+            .map(|id| id.to_string())
+            .unwrap_or_else(|| "-".to_string());
+        let mut detail = String::new();
+        if !result.duplicate_emails.is_empty() {
+            detail.push_str(&format!(
+                " (email taken: {})",
+                result.duplicate_emails.join(", ")
+            ));
+        }
+        if let Some(candidate) = result.merge_candidate_id {
+            detail.push_str(&format!(" merge candidate {candidate}"));
+        }
+        if let Some(error) = &result.error {
+            detail.push_str(&format!(": {error}"));
+        }
+        println!(
+            "line {}: {} {} {}{}",
+            result.line, result.status, id, result.name, detail
+        );
+    }
+    let count = |status: &str| results.iter().filter(|r| r.status == status).count();
+    let (created, staged) = if dry_run {
+        (count("would_create"), count("would_stage"))
+    } else {
+        (count("created"), count("staged"))
+    };
+    let label = if dry_run { "would add" } else { "added" };
+    println!(
+        "{label} {created}, staged {staged}, duplicates {}, failed {}, skipped {}",
+        count("duplicate"),
+        count("failed"),
+        count("skipped")
+    );
+    if dry_run {
+        println!("Dry run: no contacts were added.");
+    }
+}
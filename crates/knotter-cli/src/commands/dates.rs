@@ -1,8 +1,6 @@
-use crate::commands::{print_json, Context};
+use crate::commands::{print_json, resolve_contact_arg, Context};
 use crate::error::{invalid_input, not_found};
-use crate::util::{
-    format_date_parts, now_utc, parse_contact_date_id, parse_contact_id, parse_date_parts,
-};
+use crate::util::{format_date_parts, parse_contact_date_id, parse_date_parts};
 use anyhow::Result;
 use clap::{Args, Subcommand};
 use knotter_core::domain::{ContactDateKind, ContactId};
@@ -19,6 +17,7 @@ pub enum DateCommand {
 
 #[derive(Debug, Args)]
 pub struct AddDateArgs {
+    /// Contact id, or a name, alias, email, or @handle
     pub contact_id: String,
     #[arg(long, value_name = "KIND")]
     pub kind: String,
@@ -26,10 +25,17 @@ pub struct AddDateArgs {
     pub label: Option<String>,
     #[arg(long, value_name = "DATE")]
     pub on: String,
+    #[arg(
+        long = "remind-before",
+        value_name = "DAYS",
+        help = "Also remind this many days ahead (e.g. 7 to mail a gift)"
+    )]
+    pub remind_before: Option<u16>,
 }
 
 #[derive(Debug, Args)]
 pub struct ListDatesArgs {
+    /// Contact id, or a name, alias, email, or @handle
     pub contact_id: String,
 }
 
@@ -39,7 +45,7 @@ pub struct RemoveDateArgs {
 }
 
 pub fn add_date(ctx: &Context<'_>, args: AddDateArgs) -> Result<()> {
-    let contact_id = parse_contact_id(&args.contact_id)?;
+    let contact_id = resolve_contact_arg(ctx, &args.contact_id)?;
     ensure_contact_exists(ctx, contact_id)?;
     let kind = parse_contact_date_kind(&args.kind)?;
     let (month, day, year) =
@@ -48,7 +54,7 @@ pub fn add_date(ctx: &Context<'_>, args: AddDateArgs) -> Result<()> {
         return Err(invalid_input("custom dates require --label"));
     }
 
-    let now = now_utc();
+    let now = ctx.clock.now_utc();
     let created = ctx.store.contact_dates().upsert(
         now,
         ContactDateNew {
@@ -59,6 +65,7 @@ pub fn add_date(ctx: &Context<'_>, args: AddDateArgs) -> Result<()> {
             day,
             year,
             source: Some("cli".to_string()),
+            remind_days_before: args.remind_before,
         },
     )?;
 
@@ -68,13 +75,19 @@ pub fn add_date(ctx: &Context<'_>, args: AddDateArgs) -> Result<()> {
     } else {
         let label = format_date_label(&dto);
         let date = format_date_parts(dto.month, dto.day, dto.year);
-        println!("added {} {} {}", dto.id, label, date);
+        println!(
+            "added {} {} {}{}",
+            dto.id,
+            label,
+            date,
+            format_lead_suffix(&dto)
+        );
     }
     Ok(())
 }
 
 pub fn list_dates(ctx: &Context<'_>, args: ListDatesArgs) -> Result<()> {
-    let contact_id = parse_contact_id(&args.contact_id)?;
+    let contact_id = resolve_contact_arg(ctx, &args.contact_id)?;
     ensure_contact_exists(ctx, contact_id)?;
     let dates = ctx.store.contact_dates().list_for_contact(contact_id)?;
     let dtos: Vec<ContactDateDto> = dates.iter().map(contact_date_to_dto).collect();
@@ -92,7 +105,13 @@ pub fn list_dates(ctx: &Context<'_>, args: ListDatesArgs) -> Result<()> {
     for date in dtos {
         let label = format_date_label(&date);
         let date_str = format_date_parts(date.month, date.day, date.year);
-        println!("{}  {}  {}", date.id, label, date_str);
+        println!(
+            "{}  {}  {}{}",
+            date.id,
+            label,
+            date_str,
+            format_lead_suffix(&date)
+        );
     }
     Ok(())
 }
@@ -121,6 +140,14 @@ fn contact_date_to_dto(date: &knotter_core::domain::ContactDate) -> ContactDateD
         month: date.month,
         day: date.day,
         year: date.year,
+        remind_days_before: date.remind_days_before,
+    }
+}
+
+fn format_lead_suffix(date: &ContactDateDto) -> String {
+    match date.remind_days_before {
+        Some(days) if days > 0 => format!("  (remind {days}d before)"),
+        _ => String::new(),
     }
 }
 
@@ -0,0 +1,114 @@
+use crate::commands::{print_json, Context};
+use anyhow::{Context as _, Result};
+use clap::Subcommand;
+use knotter_store::maintenance::DbStats;
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+#[derive(Debug, Subcommand)]
+pub enum DbCommand {
+    /// Show file size, page usage, and row counts
+    Stats,
+    /// Compact the database file and refresh query planner statistics
+    Vacuum,
+}
+
+#[derive(Debug, Serialize)]
+struct DbStatsDto {
+    path: Option<String>,
+    size_bytes: u64,
+    wal_size_bytes: Option<u64>,
+    page_count: u64,
+    page_size: u64,
+    free_pages: u64,
+    rows: BTreeMap<&'static str, u64>,
+}
+
+impl From<&DbStats> for DbStatsDto {
+    fn from(stats: &DbStats) -> Self {
+        Self {
+            path: stats.path.as_ref().map(|path| path.display().to_string()),
+            size_bytes: stats.file_size_bytes,
+            wal_size_bytes: stats.wal_size_bytes,
+            page_count: stats.page_count,
+            page_size: stats.page_size,
+            free_pages: stats.freelist_count,
+            rows: stats
+                .tables
+                .iter()
+                .map(|entry| (entry.table, entry.rows))
+                .collect(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct VacuumDto {
+    before_bytes: u64,
+    after_bytes: u64,
+    reclaimed_bytes: u64,
+}
+
+pub fn db_stats(ctx: &Context<'_>) -> Result<()> {
+    let stats = ctx.store.stats().with_context(|| "read database stats")?;
+    if ctx.json {
+        return print_json(&DbStatsDto::from(&stats));
+    }
+
+    match &stats.path {
+        Some(path) => println!("path: {}", path.display()),
+        None => println!("path: (in memory)"),
+    }
+    println!("size: {}", format_bytes(stats.file_size_bytes));
+    if let Some(wal) = stats.wal_size_bytes {
+        println!("wal: {}", format_bytes(wal));
+    }
+    println!(
+        "pages: {} x {} bytes ({} free)",
+        stats.page_count, stats.page_size, stats.freelist_count
+    );
+    let width = stats
+        .tables
+        .iter()
+        .map(|entry| entry.table.len())
+        .max()
+        .unwrap_or(0);
+    println!("rows:");
+    for entry in &stats.tables {
+        println!("  {:<width$}  {}", entry.table, entry.rows);
+    }
+    Ok(())
+}
+
+pub fn db_vacuum(ctx: &Context<'_>) -> Result<()> {
+    let report = ctx.store.vacuum().with_context(|| "vacuum database")?;
+    let dto = VacuumDto {
+        before_bytes: report.before.total_bytes(),
+        after_bytes: report.after.total_bytes(),
+        reclaimed_bytes: report.reclaimed_bytes(),
+    };
+    if ctx.json {
+        return print_json(&dto);
+    }
+    println!(
+        "Vacuumed database: {} -> {} (reclaimed {})",
+        format_bytes(dto.before_bytes),
+        format_bytes(dto.after_bytes),
+        format_bytes(dto.reclaimed_bytes)
+    );
+    Ok(())
+}
+
+fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    format!("{value:.1} {}", UNITS[unit])
+}
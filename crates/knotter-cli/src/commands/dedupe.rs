@@ -0,0 +1,130 @@
+use crate::commands::{print_json, Context};
+use crate::error::invalid_input;
+use anyhow::Result;
+use clap::{Args, Subcommand};
+use knotter_core::domain::ContactId;
+use knotter_core::time::parse_duration;
+use knotter_store::repo::DuplicateInteraction;
+use serde::Serialize;
+use std::collections::HashMap;
+
+#[derive(Debug, Subcommand)]
+pub enum DedupeCommand {
+    /// Remove interactions logged twice for the same event
+    #[command(after_help = "Examples:
+  knotter dedupe interactions --dry-run
+  knotter dedupe interactions --window 10m")]
+    Interactions(DedupeInteractionsArgs),
+}
+
+#[derive(Debug, Args)]
+pub struct DedupeInteractionsArgs {
+    #[arg(
+        long,
+        value_name = "DURATION",
+        default_value = "2m",
+        help = "Treat a repeat (same contact, kind, and note) at most this long after the previous one as a duplicate, e.g. 90s, 2m, 1h"
+    )]
+    pub window: String,
+    #[arg(long, help = "List the duplicates without deleting them")]
+    pub dry_run: bool,
+}
+
+#[derive(Debug, Serialize)]
+struct ContactDuplicatesDto {
+    id: ContactId,
+    display_name: String,
+    removed: usize,
+}
+
+#[derive(Debug, Serialize)]
+struct DedupeInteractionsReport {
+    window_seconds: i64,
+    dry_run: bool,
+    removed: usize,
+    /// Sync rows moved onto a kept interaction; `None` on a dry run.
+    relinked_messages: Option<usize>,
+    contacts: Vec<ContactDuplicatesDto>,
+}
+
+pub fn dedupe_interactions(ctx: &Context<'_>, args: DedupeInteractionsArgs) -> Result<()> {
+    let window_seconds =
+        parse_duration(&args.window).map_err(|err| invalid_input(err.to_string()))?;
+
+    let repo = ctx.store.interactions();
+    let (duplicates, relinked_messages) = if args.dry_run {
+        (repo.find_duplicates(window_seconds)?, None)
+    } else {
+        let dedupe = repo.delete_duplicates(window_seconds)?;
+        (dedupe.removed, Some(dedupe.relinked_messages))
+    };
+
+    let report = DedupeInteractionsReport {
+        window_seconds,
+        dry_run: args.dry_run,
+        removed: duplicates.len(),
+        relinked_messages,
+        contacts: per_contact(ctx, &duplicates)?,
+    };
+
+    if ctx.json {
+        return print_json(&report);
+    }
+    if report.contacts.is_empty() {
+        println!("no duplicate interactions");
+        return Ok(());
+    }
+    let verb = if report.dry_run {
+        "would remove"
+    } else {
+        "removed"
+    };
+    for contact in &report.contacts {
+        println!(
+            "{verb} {} from {} {}",
+            contact.removed, contact.id, contact.display_name
+        );
+    }
+    let mut summary = format!(
+        "{verb} {} duplicate interaction(s) across {} contact(s)",
+        report.removed,
+        report.contacts.len()
+    );
+    if let Some(relinked) = report.relinked_messages.filter(|count| *count > 0) {
+        summary.push_str(&format!("; relinked {relinked} synced message(s)"));
+    }
+    println!("{summary}");
+    Ok(())
+}
+
+/// Counts per contact, most duplicates first.
+fn per_contact(
+    ctx: &Context<'_>,
+    duplicates: &[DuplicateInteraction],
+) -> Result<Vec<ContactDuplicatesDto>> {
+    let mut counts: HashMap<ContactId, usize> = HashMap::new();
+    for duplicate in duplicates {
+        *counts.entry(duplicate.interaction.contact_id).or_default() += 1;
+    }
+    let mut contacts = Vec::with_capacity(counts.len());
+    for (id, removed) in counts {
+        let display_name = ctx
+            .store
+            .contacts()
+            .get(id)?
+            .map(|contact| contact.display_name)
+            .unwrap_or_default();
+        contacts.push(ContactDuplicatesDto {
+            id,
+            display_name,
+            removed,
+        });
+    }
+    contacts.sort_by(|a, b| {
+        b.removed
+            .cmp(&a.removed)
+            .then_with(|| a.display_name.cmp(&b.display_name))
+            .then_with(|| a.id.to_string().cmp(&b.id.to_string()))
+    });
+    Ok(contacts)
+}
@@ -0,0 +1,228 @@
+use crate::commands::{print_json, Context};
+use anyhow::Result;
+use clap::Args;
+use knotter_core::rules::MIN_INTERACTION_TIMESTAMP;
+use knotter_store::doctor;
+use serde::Serialize;
+
+#[derive(Debug, Args)]
+pub struct DoctorArgs {
+    #[arg(
+        long,
+        help = "Delete orphaned rows and dismiss dangling merge candidates"
+    )]
+    pub fix: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+enum Severity {
+    Error,
+    Warning,
+    Info,
+}
+
+impl Severity {
+    fn as_str(self) -> &'static str {
+        match self {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+            Severity::Info => "info",
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct Finding {
+    check: &'static str,
+    severity: Severity,
+    message: String,
+    count: usize,
+    fixed: bool,
+}
+
+#[derive(Debug, Serialize)]
+struct DoctorReport {
+    ok: bool,
+    fix: bool,
+    findings: Vec<Finding>,
+}
+
+pub fn doctor(ctx: &Context<'_>, args: DoctorArgs) -> Result<()> {
+    let now = ctx.clock.now_utc();
+    let conn = ctx.store.connection();
+    let mut findings = Vec::new();
+
+    for problem in doctor::integrity_check(conn)? {
+        findings.push(Finding {
+            check: "integrity",
+            severity: Severity::Error,
+            message: problem,
+            count: 1,
+            fixed: false,
+        });
+    }
+
+    // The safe repairs share one transaction so a failure leaves nothing half-fixed.
+    let tx = conn.unchecked_transaction()?;
+    let orphans = if args.fix {
+        doctor::delete_orphan_rows(&tx)?
+    } else {
+        doctor::orphan_rows(&tx)?
+    };
+    for orphan in orphans {
+        findings.push(Finding {
+            check: "orphans",
+            severity: Severity::Warning,
+            message: format!(
+                "{} {} row(s) reference a missing contact or tag",
+                orphan.rows, orphan.table
+            ),
+            count: orphan.rows,
+            fixed: args.fix,
+        });
+    }
+    let dangling = if args.fix {
+        doctor::dismiss_dangling_merge_candidates(&tx, now)?
+    } else {
+        doctor::dangling_merge_candidates(&tx)?
+    };
+    if dangling > 0 {
+        findings.push(Finding {
+            check: "merge_candidates",
+            severity: Severity::Warning,
+            message: format!("{dangling} open merge candidate(s) reference a missing contact"),
+            count: dangling,
+            fixed: args.fix,
+        });
+    }
+    tx.commit()?;
+
+    let future = doctor::archived_in_future(conn, now)?;
+    if !future.is_empty() {
+        let ids = future
+            .iter()
+            .map(|id| id.to_string())
+            .collect::<Vec<_>>()
+            .join(", ");
+        findings.push(Finding {
+            check: "archived_at",
+            severity: Severity::Warning,
+            message: format!("{} contact(s) archived in the future: {ids}", future.len()),
+            count: future.len(),
+            fixed: false,
+        });
+    }
+
+    for duplicate in doctor::duplicate_emails_without_candidate(conn)? {
+        let ids = duplicate
+            .contact_ids
+            .iter()
+            .map(|id| id.to_string())
+            .collect::<Vec<_>>()
+            .join(", ");
+        findings.push(Finding {
+            check: "duplicate_emails",
+            severity: Severity::Warning,
+            message: format!(
+                "{} is shared by contacts {ids} without an open merge candidate",
+                duplicate.email
+            ),
+            count: duplicate.contact_ids.len(),
+            fixed: false,
+        });
+    }
+
+    let accounts = doctor::sync_state_accounts(conn)?;
+    for account in accounts.email {
+        if ctx.config.contacts.email_account(&account).is_none() {
+            findings.push(stale_sync_state("email", account));
+        }
+    }
+    for account in accounts.telegram {
+        if ctx.config.contacts.telegram_account(&account).is_none() {
+            findings.push(stale_sync_state("telegram", account));
+        }
+    }
+
+    let max_skew_days = ctx.config.sync.max_future_skew_days;
+    let skewed = doctor::interactions_out_of_range(
+        conn,
+        MIN_INTERACTION_TIMESTAMP,
+        now.saturating_add(max_skew_days.saturating_mul(86_400)),
+    )?;
+    if !skewed.is_empty() {
+        let ids = skewed
+            .iter()
+            .map(|id| id.to_string())
+            .collect::<Vec<_>>()
+            .join(", ");
+        findings.push(Finding {
+            check: "interaction_dates",
+            severity: Severity::Warning,
+            message: format!(
+                "{} interaction(s) dated before 1990 or more than {max_skew_days} day(s) ahead (fix with `knotter note edit` or `note rm`): {ids}",
+                skewed.len()
+            ),
+            count: skewed.len(),
+            fixed: false,
+        });
+    }
+
+    let overdue = doctor::overdue_active_contacts(conn, now)?;
+    if overdue > 0 {
+        findings.push(Finding {
+            check: "overdue",
+            severity: Severity::Info,
+            message: format!("{overdue} active contact(s) have a next touchpoint in the past"),
+            count: overdue,
+            fixed: false,
+        });
+    }
+
+    let ok = findings
+        .iter()
+        .all(|finding| finding.fixed || finding.severity == Severity::Info);
+    let report = DoctorReport {
+        ok,
+        fix: args.fix,
+        findings,
+    };
+
+    if ctx.json {
+        return print_json(&report);
+    }
+
+    if report.findings.is_empty() {
+        println!("doctor: no problems found");
+        return Ok(());
+    }
+    for finding in &report.findings {
+        let suffix = if finding.fixed { " (fixed)" } else { "" };
+        println!(
+            "[{}] {}: {}{}",
+            finding.severity.as_str(),
+            finding.check,
+            finding.message,
+            suffix
+        );
+    }
+    let fixable = report
+        .findings
+        .iter()
+        .any(|finding| !finding.fixed && matches!(finding.check, "orphans" | "merge_candidates"));
+    if fixable {
+        println!("run `knotter doctor --fix` to repair orphans and dangling merge candidates");
+    }
+    Ok(())
+}
+
+fn stale_sync_state(kind: &'static str, account: String) -> Finding {
+    Finding {
+        check: "sync_state",
+        severity: Severity::Warning,
+        message: format!("{kind} sync state exists for account {account}, which is not in config"),
+        count: 1,
+        fixed: false,
+    }
+}
@@ -0,0 +1,281 @@
+//! `--dry-run` for commands that change one contact: the real change runs in a
+//! transaction, the contact is diffed before and after, and the transaction is
+//! rolled back. Validation and computed values (such as the reschedule a touch
+//! triggers) therefore match the real command exactly.
+
+use crate::commands::{print_json, Context};
+use crate::error::not_found;
+use crate::util::format_date_parts;
+use anyhow::Result;
+use knotter_core::domain::ContactId;
+use knotter_core::time::Clock;
+use knotter_store::repo::ContactOriginsRepo;
+use serde::Serialize;
+use serde_json::Value;
+
+#[derive(Debug, Serialize)]
+pub(crate) struct FieldChange {
+    pub field: &'static str,
+    pub old: Value,
+    pub new: Value,
+}
+
+#[derive(Debug)]
+pub(crate) struct ContactSnapshot {
+    display_name: String,
+    email: Option<String>,
+    emails: Vec<String>,
+    phone: Option<String>,
+    handle: Option<String>,
+    timezone: Option<String>,
+    organization: Option<String>,
+    cadence_days: Option<i32>,
+    next_touchpoint_at: Option<i64>,
+    archived_at: Option<i64>,
+    archive_reason: Option<String>,
+    priority: i32,
+    met_at: Option<String>,
+    met_date: Option<String>,
+    met_via: Option<String>,
+    tags: Vec<String>,
+    aliases: Vec<String>,
+    dates: Vec<String>,
+}
+
+#[derive(Serialize)]
+struct DryRunDto<'a, T: Serialize> {
+    dry_run: bool,
+    #[serde(flatten)]
+    output: &'a T,
+    changes: &'a [FieldChange],
+}
+
+/// Runs `apply` in a transaction. When `dry_run` is set, contact `id` is
+/// diffed around the change and everything is rolled back; otherwise the
+/// transaction commits and no diff is taken. `apply` must reach the database
+/// through `ctx.store`, whose repositories join the open transaction.
+pub(crate) fn run_contact_change<T>(
+    ctx: &Context<'_>,
+    id: ContactId,
+    dry_run: bool,
+    apply: impl FnOnce() -> Result<T>,
+) -> Result<(T, Vec<FieldChange>)> {
+    let tx = ctx.store.connection().unchecked_transaction()?;
+    if !dry_run {
+        let value = apply()?;
+        tx.commit()?;
+        return Ok((value, Vec::new()));
+    }
+    let before = snapshot(ctx, id)?;
+    let value = apply()?;
+    let after = snapshot(ctx, id)?;
+    drop(tx);
+    Ok((value, diff_snapshots(&before, &after)))
+}
+
+/// Prints a dry run in the real command's format: its JSON output plus
+/// `"dry_run": true` and `changes`, or its summary line marked "(dry run)"
+/// followed by one line per changed field.
+pub(crate) fn print_dry_run<T: Serialize>(
+    ctx: &Context<'_>,
+    output: &T,
+    summary: &str,
+    changes: &[FieldChange],
+) -> Result<()> {
+    if ctx.json {
+        return print_json(&DryRunDto {
+            dry_run: true,
+            output,
+            changes,
+        });
+    }
+    println!("{summary} (dry run)");
+    print_changes(changes, &ctx.clock);
+    if changes.is_empty() {
+        println!("  no changes");
+    }
+    Ok(())
+}
+
+pub(crate) fn print_changes(changes: &[FieldChange], clock: &Clock) {
+    for change in changes {
+        println!(
+            "  {}: {} -> {}",
+            change.field,
+            display_value(change.field, &change.old, clock),
+            display_value(change.field, &change.new, clock)
+        );
+    }
+}
+
+fn display_value(field: &str, value: &Value, clock: &Clock) -> String {
+    match value {
+        Value::Null => "-".to_string(),
+        Value::Number(number) if field.ends_with("_at") => number
+            .as_i64()
+            .map(|ts| clock.format_timestamp_datetime(ts))
+            .unwrap_or_else(|| number.to_string()),
+        Value::String(text) => text.clone(),
+        other => other.to_string(),
+    }
+}
+
+pub(crate) fn snapshot(ctx: &Context<'_>, id: ContactId) -> Result<ContactSnapshot> {
+    let contacts = ctx.store.contacts();
+    let contact = contacts
+        .get(id)?
+        .ok_or_else(|| not_found(format!("contact not found: {id}")))?;
+    let origin = ContactOriginsRepo::new(ctx.store.connection())
+        .get(id)?
+        .unwrap_or_default();
+    let mut tags: Vec<String> = ctx
+        .store
+        .tags()
+        .list_for_contact(&id.to_string())?
+        .into_iter()
+        .map(|tag| tag.name.as_str().to_string())
+        .collect();
+    tags.sort();
+    let mut dates: Vec<String> = ctx
+        .store
+        .contact_dates()
+        .list_for_contact(id)?
+        .into_iter()
+        .map(|date| {
+            let kind = match date.label {
+                Some(label) => format!("{} {label}", date.kind.as_str()),
+                None => date.kind.as_str().to_string(),
+            };
+            format!(
+                "{kind} {}",
+                format_date_parts(date.month, date.day, date.year)
+            )
+        })
+        .collect();
+    dates.sort();
+    Ok(ContactSnapshot {
+        display_name: contact.display_name,
+        email: contact.email,
+        emails: ctx.store.emails().list_emails_for_contact(&id)?,
+        phone: contact.phone,
+        handle: contact.handle,
+        timezone: contact.timezone,
+        organization: contact.organization,
+        cadence_days: contact.cadence_days,
+        next_touchpoint_at: contact.next_touchpoint_at,
+        archived_at: contact.archived_at,
+        archive_reason: contacts.archive_reason(id)?,
+        priority: contacts.priority(id)?,
+        met_at: origin.met_at,
+        met_date: origin
+            .met_on
+            .map(|date| date.format("%Y-%m-%d").to_string()),
+        met_via: origin.met_via.map(|via| via.to_string()),
+        tags,
+        aliases: ctx.store.contact_aliases().list_for_contact(id)?,
+        dates,
+    })
+}
+
+pub(crate) fn diff_snapshots(
+    before: &ContactSnapshot,
+    after: &ContactSnapshot,
+) -> Vec<FieldChange> {
+    let mut changes = Vec::new();
+    let mut push = |field: &'static str, old: Value, new: Value| {
+        if old != new {
+            changes.push(FieldChange { field, old, new });
+        }
+    };
+    push(
+        "name",
+        Value::from(before.display_name.as_str()),
+        Value::from(after.display_name.as_str()),
+    );
+    push(
+        "email",
+        Value::from(before.email.clone()),
+        Value::from(after.email.clone()),
+    );
+    push(
+        "emails",
+        Value::from(before.emails.clone()),
+        Value::from(after.emails.clone()),
+    );
+    push(
+        "phone",
+        Value::from(before.phone.clone()),
+        Value::from(after.phone.clone()),
+    );
+    push(
+        "handle",
+        Value::from(before.handle.clone()),
+        Value::from(after.handle.clone()),
+    );
+    push(
+        "timezone",
+        Value::from(before.timezone.clone()),
+        Value::from(after.timezone.clone()),
+    );
+    push(
+        "organization",
+        Value::from(before.organization.clone()),
+        Value::from(after.organization.clone()),
+    );
+    push(
+        "cadence_days",
+        Value::from(before.cadence_days),
+        Value::from(after.cadence_days),
+    );
+    push(
+        "next_touchpoint_at",
+        Value::from(before.next_touchpoint_at),
+        Value::from(after.next_touchpoint_at),
+    );
+    push(
+        "archived_at",
+        Value::from(before.archived_at),
+        Value::from(after.archived_at),
+    );
+    push(
+        "archive_reason",
+        Value::from(before.archive_reason.clone()),
+        Value::from(after.archive_reason.clone()),
+    );
+    push(
+        "priority",
+        Value::from(before.priority),
+        Value::from(after.priority),
+    );
+    push(
+        "met_at",
+        Value::from(before.met_at.clone()),
+        Value::from(after.met_at.clone()),
+    );
+    push(
+        "met_date",
+        Value::from(before.met_date.clone()),
+        Value::from(after.met_date.clone()),
+    );
+    push(
+        "met_via",
+        Value::from(before.met_via.clone()),
+        Value::from(after.met_via.clone()),
+    );
+    push(
+        "tags",
+        Value::from(before.tags.clone()),
+        Value::from(after.tags.clone()),
+    );
+    push(
+        "aliases",
+        Value::from(before.aliases.clone()),
+        Value::from(after.aliases.clone()),
+    );
+    push(
+        "dates",
+        Value::from(before.dates.clone()),
+        Value::from(after.dates.clone()),
+    );
+    changes
+}
@@ -0,0 +1,263 @@
+use crate::commands::{print_json, resolve_contact_arg, Context};
+use crate::error::{invalid_input, not_found};
+use anyhow::Result;
+use clap::{Args, Subcommand};
+use knotter_core::domain::{normalize_email, ContactId, MergeCandidateId, MergeCandidateReason};
+use knotter_store::repo::{ContactUpdate, EmailOps, MergeCandidateCreate};
+use serde::Serialize;
+
+const EMAIL_SOURCE: &str = "cli";
+
+#[derive(Debug, Subcommand)]
+pub enum EmailCommand {
+    /// Add an email address to a contact
+    Add(EmailAddArgs),
+    /// Remove an email address (the next address is promoted if it was primary)
+    Rm(EmailRemoveArgs),
+    /// List a contact's email addresses
+    Ls(EmailListArgs),
+    /// Make an existing address the contact's primary email
+    #[command(name = "set-primary")]
+    SetPrimary(EmailSetPrimaryArgs),
+}
+
+#[derive(Debug, Args)]
+pub struct EmailAddArgs {
+    /// Contact id, or a name, alias, email, or @handle
+    pub id: String,
+    pub email: String,
+    #[arg(
+        long,
+        help = "If another contact owns the address, stage a merge candidate instead of failing"
+    )]
+    pub stage_merge: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct EmailRemoveArgs {
+    /// Contact id, or a name, alias, email, or @handle
+    pub id: String,
+    pub email: String,
+}
+
+#[derive(Debug, Args)]
+pub struct EmailListArgs {
+    /// Contact id, or a name, alias, email, or @handle
+    pub id: String,
+}
+
+#[derive(Debug, Args)]
+pub struct EmailSetPrimaryArgs {
+    /// Contact id, or a name, alias, email, or @handle
+    pub id: String,
+    pub email: String,
+}
+
+#[derive(Debug, Serialize)]
+struct ContactEmailsDto {
+    contact_id: ContactId,
+    primary: Option<String>,
+    emails: Vec<ContactEmailDto>,
+}
+
+#[derive(Debug, Serialize)]
+struct ContactEmailDto {
+    email: String,
+    is_primary: bool,
+    source: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct EmailMergeStagedDto {
+    contact_id: ContactId,
+    email: String,
+    owner_id: ContactId,
+    merge_candidate_id: MergeCandidateId,
+    created: bool,
+}
+
+pub fn add_email(ctx: &Context<'_>, args: EmailAddArgs) -> Result<()> {
+    let now = ctx.clock.now_utc();
+    let id = resolve_contact_arg(ctx, &args.id)?;
+    let email = parse_email(&args.email)?;
+    let contact = ctx
+        .store
+        .contacts()
+        .get(id)?
+        .ok_or_else(|| not_found("contact not found"))?;
+
+    if let Some(owner_id) = ctx.store.emails().find_contact_id_by_email(&email)? {
+        if owner_id != id {
+            let owner_name = ctx
+                .store
+                .contacts()
+                .get(owner_id)?
+                .map(|owner| owner.display_name)
+                .unwrap_or_default();
+            if !args.stage_merge {
+                return Err(invalid_input(format!(
+                    "email {email} already belongs to {owner_name} ({owner_id}); use --stage-merge to create a merge candidate"
+                )));
+            }
+            let result = ctx.store.merge_candidates().create(
+                now,
+                owner_id,
+                id,
+                MergeCandidateCreate {
+                    reason: MergeCandidateReason::EmailDuplicate.as_str().to_string(),
+                    source: Some(EMAIL_SOURCE.to_string()),
+                    preferred_contact_id: Some(owner_id),
+                },
+            )?;
+            let staged = EmailMergeStagedDto {
+                contact_id: id,
+                email,
+                owner_id,
+                merge_candidate_id: result.candidate.id,
+                created: result.created,
+            };
+            if ctx.json {
+                return print_json(&staged);
+            }
+            let verb = if staged.created {
+                "staged"
+            } else {
+                "already open"
+            };
+            println!(
+                "email {} belongs to {} ({}); merge candidate {} {}",
+                staged.email, owner_name, owner_id, staged.merge_candidate_id, verb
+            );
+            return Ok(());
+        }
+    }
+
+    ctx.store.contacts().update_with_email_ops(
+        now,
+        contact.id,
+        ContactUpdate::default(),
+        EmailOps::Mutate {
+            clear: false,
+            add: vec![email.clone()],
+            remove: Vec::new(),
+            source: Some(EMAIL_SOURCE.to_string()),
+        },
+    )?;
+
+    if ctx.json {
+        return print_json(&contact_emails(ctx, id)?);
+    }
+    println!("email {} added to {}", email, id);
+    Ok(())
+}
+
+pub fn remove_email(ctx: &Context<'_>, args: EmailRemoveArgs) -> Result<()> {
+    let now = ctx.clock.now_utc();
+    let id = resolve_contact_arg(ctx, &args.id)?;
+    let email = parse_email(&args.email)?;
+    ensure_contact_has_email(ctx, id, &email)?;
+
+    ctx.store.contacts().update_with_email_ops(
+        now,
+        id,
+        ContactUpdate::default(),
+        EmailOps::Mutate {
+            clear: false,
+            add: Vec::new(),
+            remove: vec![email.clone()],
+            source: None,
+        },
+    )?;
+
+    if ctx.json {
+        return print_json(&contact_emails(ctx, id)?);
+    }
+    println!("email {} removed from {}", email, id);
+    Ok(())
+}
+
+pub fn list_emails(ctx: &Context<'_>, args: EmailListArgs) -> Result<()> {
+    let id = resolve_contact_arg(ctx, &args.id)?;
+    if ctx.store.contacts().get(id)?.is_none() {
+        return Err(not_found("contact not found"));
+    }
+    let emails = contact_emails(ctx, id)?;
+
+    if ctx.json {
+        return print_json(&emails);
+    }
+    if emails.emails.is_empty() {
+        println!("no emails");
+        return Ok(());
+    }
+    for email in &emails.emails {
+        let primary = if email.is_primary { " (primary)" } else { "" };
+        match email.source.as_deref() {
+            Some(source) => println!("{}{}  [{}]", email.email, primary, source),
+            None => println!("{}{}", email.email, primary),
+        }
+    }
+    Ok(())
+}
+
+pub fn set_primary_email(ctx: &Context<'_>, args: EmailSetPrimaryArgs) -> Result<()> {
+    let now = ctx.clock.now_utc();
+    let id = resolve_contact_arg(ctx, &args.id)?;
+    let email = parse_email(&args.email)?;
+    ensure_contact_has_email(ctx, id, &email)?;
+
+    // Updating the legacy `email` column keeps exports and older readers in sync.
+    ctx.store.contacts().update_with_email_ops(
+        now,
+        id,
+        ContactUpdate {
+            email: Some(Some(email.clone())),
+            email_source: Some(EMAIL_SOURCE.to_string()),
+            ..Default::default()
+        },
+        EmailOps::None,
+    )?;
+
+    if ctx.json {
+        return print_json(&contact_emails(ctx, id)?);
+    }
+    println!("primary email for {} set to {}", id, email);
+    Ok(())
+}
+
+fn parse_email(raw: &str) -> Result<String> {
+    normalize_email(raw).ok_or_else(|| invalid_input(format!("invalid email: {raw}")))
+}
+
+fn ensure_contact_has_email(ctx: &Context<'_>, id: ContactId, email: &str) -> Result<()> {
+    if ctx.store.contacts().get(id)?.is_none() {
+        return Err(not_found("contact not found"));
+    }
+    let emails = ctx.store.emails().list_emails_for_contact(&id)?;
+    if !emails.iter().any(|existing| existing == email) {
+        return Err(not_found(format!(
+            "email {email} not found on contact {id}"
+        )));
+    }
+    Ok(())
+}
+
+fn contact_emails(ctx: &Context<'_>, id: ContactId) -> Result<ContactEmailsDto> {
+    let emails = ctx.store.emails().list_for_contact(&id)?;
+    let primary = emails
+        .iter()
+        .find(|email| email.is_primary)
+        .map(|email| email.email.clone());
+    Ok(ContactEmailsDto {
+        contact_id: id,
+        primary,
+        emails: emails
+            .into_iter()
+            .map(|email| ContactEmailDto {
+                email: email.email,
+                is_primary: email.is_primary,
+                source: email.source,
+            })
+            .collect(),
+    })
+}
@@ -0,0 +1,131 @@
+use crate::commands::{print_json, Context};
+use anyhow::Result;
+use clap::{Args, Subcommand};
+use knotter_core::domain::{phone_region, ContactId};
+use knotter_store::repo::ContactUpdate;
+use serde::Serialize;
+
+/// Provenance source recorded for fields filled by `enrich`.
+const ENRICH_TIMEZONES_SOURCE: &str = "enrich-timezones";
+
+#[derive(Debug, Subcommand)]
+pub enum EnrichCommand {
+    /// Fill empty timezones from phone number country codes
+    Timezones(EnrichTimezonesArgs),
+}
+
+#[derive(Debug, Args)]
+pub struct EnrichTimezonesArgs {
+    #[arg(long, help = "List the proposed timezones without saving them")]
+    pub dry_run: bool,
+}
+
+#[derive(Debug, Serialize)]
+struct TimezoneChangeDto {
+    id: ContactId,
+    display_name: String,
+    phone: String,
+    region: &'static str,
+    timezone: &'static str,
+}
+
+#[derive(Debug, Serialize)]
+struct TimezoneSkipDto {
+    id: ContactId,
+    display_name: String,
+    phone: String,
+    region: &'static str,
+}
+
+#[derive(Debug, Serialize)]
+struct EnrichTimezonesReport {
+    dry_run: bool,
+    changes: Vec<TimezoneChangeDto>,
+    ambiguous: Vec<TimezoneSkipDto>,
+    /// Contacts whose phone has no recognizable country code.
+    unrecognized: usize,
+}
+
+pub fn enrich_timezones(ctx: &Context<'_>, args: EnrichTimezonesArgs) -> Result<()> {
+    let now = ctx.clock.now_utc();
+    let mut report = EnrichTimezonesReport {
+        dry_run: args.dry_run,
+        changes: Vec::new(),
+        ambiguous: Vec::new(),
+        unrecognized: 0,
+    };
+
+    let contacts = ctx.store.contacts().list_all()?;
+    for contact in contacts {
+        if contact.archived_at.is_some() || contact.timezone.is_some() {
+            continue;
+        }
+        let Some(phone) = contact.phone else {
+            continue;
+        };
+        let Some(region) = phone_region(&phone) else {
+            report.unrecognized += 1;
+            continue;
+        };
+        let Some(timezone) = region.timezone else {
+            report.ambiguous.push(TimezoneSkipDto {
+                id: contact.id,
+                display_name: contact.display_name,
+                phone,
+                region: region.region,
+            });
+            continue;
+        };
+
+        if !args.dry_run {
+            ctx.store.contacts().update(
+                now,
+                contact.id,
+                ContactUpdate {
+                    timezone: Some(Some(timezone.to_string())),
+                    source: Some(ENRICH_TIMEZONES_SOURCE.to_string()),
+                    ..Default::default()
+                },
+            )?;
+        }
+        report.changes.push(TimezoneChangeDto {
+            id: contact.id,
+            display_name: contact.display_name,
+            phone,
+            region: region.region,
+            timezone,
+        });
+    }
+
+    if ctx.json {
+        return print_json(&report);
+    }
+
+    let (verb, summary) = if args.dry_run {
+        ("would set", "would update")
+    } else {
+        ("set", "updated")
+    };
+    for change in &report.changes {
+        println!(
+            "{}  {}  {} {} ({} {})",
+            change.id, change.display_name, verb, change.timezone, change.region, change.phone
+        );
+    }
+    for skipped in &report.ambiguous {
+        println!(
+            "{}  {}  skipped: {} spans several timezones ({})",
+            skipped.id, skipped.display_name, skipped.region, skipped.phone
+        );
+    }
+    println!(
+        "{summary} {} | ambiguous {} | unrecognized {}",
+        report.changes.len(),
+        report.ambiguous.len(),
+        report.unrecognized
+    );
+    if args.dry_run {
+        println!("Dry run: no changes were applied.");
+    }
+    Ok(())
+}
@@ -0,0 +1,120 @@
+use crate::commands::remind_fmt::note_summary;
+use crate::commands::{print_json, Context};
+use crate::util::{parse_interaction_id, parse_interaction_kind};
+use anyhow::Result;
+use clap::{ArgAction, Args, Subcommand};
+use knotter_app::reminders;
+
+#[derive(Debug, Args)]
+#[command(args_conflicts_with_subcommands = true)]
+pub struct FollowupsArgs {
+    #[command(subcommand)]
+    pub command: Option<FollowupsCommand>,
+    #[arg(
+        long,
+        help = "Include follow-ups due in the next N days (default: due_soon_days)"
+    )]
+    pub days: Option<i64>,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum FollowupsCommand {
+    /// Mark a follow-up as done
+    Done(FollowupDoneArgs),
+}
+
+#[derive(Debug, Args)]
+pub struct FollowupDoneArgs {
+    /// Interaction id that carries the follow-up
+    pub id: String,
+    #[arg(
+        long,
+        help = "Also log a new interaction with this note for the same contact"
+    )]
+    pub note: Option<String>,
+    #[arg(long, default_value = "other:follow-up", requires = "note")]
+    pub kind: String,
+    #[arg(
+        long,
+        action = ArgAction::SetTrue,
+        conflicts_with = "no_reschedule",
+        requires = "note"
+    )]
+    pub reschedule: bool,
+    #[arg(long, action = ArgAction::SetTrue, requires = "note")]
+    pub no_reschedule: bool,
+}
+
+pub fn followups(ctx: &Context<'_>, args: FollowupsArgs) -> Result<()> {
+    match args.command {
+        Some(FollowupsCommand::Done(done)) => complete_follow_up(ctx, done),
+        None => list_follow_ups(ctx, args.days),
+    }
+}
+
+fn list_follow_ups(ctx: &Context<'_>, days: Option<i64>) -> Result<()> {
+    let days = days.unwrap_or(ctx.config.due_soon_days);
+    let now = ctx.clock.now_utc();
+    let items = reminders::follow_ups(ctx.store, days, now, ctx.clock.local_offset())?;
+
+    if ctx.json {
+        return print_json(&items);
+    }
+    if items.is_empty() {
+        println!("no pending follow-ups");
+        return Ok(());
+    }
+    for item in &items {
+        let marker = if item.follow_up_at < now {
+            "  (overdue)"
+        } else {
+            ""
+        };
+        println!(
+            "{}  {}  {}  {}{}",
+            item.interaction_id,
+            item.display_name,
+            ctx.clock.format_timestamp_date(item.follow_up_at),
+            note_summary(&item.note),
+            marker
+        );
+    }
+    Ok(())
+}
+
+fn complete_follow_up(ctx: &Context<'_>, args: FollowupDoneArgs) -> Result<()> {
+    let id = parse_interaction_id(&args.id)?;
+    let interactions = ctx.store.interactions();
+    let (cleared, logged) = match args.note {
+        Some(note) => {
+            let kind = parse_interaction_kind(&args.kind)?;
+            let reschedule = if args.reschedule {
+                true
+            } else if args.no_reschedule {
+                false
+            } else {
+                ctx.config.interactions.auto_reschedule
+            };
+            let (cleared, logged) =
+                interactions.complete_follow_up(ctx.clock.now_utc(), id, kind, note, reschedule)?;
+            (cleared, Some(logged))
+        }
+        None => (interactions.clear_follow_up(id)?, None),
+    };
+
+    if ctx.json {
+        print_json(&serde_json::json!({
+            "id": cleared.id,
+            "contact_id": cleared.contact_id,
+            "logged_interaction_id": logged.as_ref().map(|interaction| interaction.id),
+        }))?;
+    } else if let Some(logged) = logged {
+        println!(
+            "completed follow-up {} (logged interaction {})",
+            cleared.id, logged.id
+        );
+    } else {
+        println!("completed follow-up {}", cleared.id);
+    }
+    Ok(())
+}
@@ -0,0 +1,266 @@
+use crate::commands::sync::print_warnings;
+use crate::commands::{print_json, Context};
+use crate::error::invalid_input;
+use crate::util::parse_interaction_kind;
+use anyhow::{Context as _, Result};
+use clap::Args;
+use knotter_core::domain::{ContactId, InteractionKind};
+use knotter_core::time::Clock;
+use knotter_store::repo::{ContactsRepo, InteractionNew, InteractionsRepo};
+use serde::Serialize;
+use std::collections::HashSet;
+use std::io;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+const DEFAULT_KIND: &str = "other:note";
+
+#[derive(Debug, Args)]
+pub struct ImportInteractionsArgs {
+    /// CSV file with a header row: contact,date,kind,note[,follow_up]
+    pub path: PathBuf,
+    #[arg(long)]
+    pub dry_run: bool,
+    #[arg(long, help = "Accept rows dated after the current time")]
+    pub allow_future: bool,
+}
+
+#[derive(Debug, Default, Serialize)]
+struct InteractionImportReport {
+    rows: usize,
+    created: usize,
+    skipped_duplicates: usize,
+    skipped_unresolved: usize,
+    skipped_ambiguous: usize,
+    rejected: usize,
+    warnings: Vec<String>,
+    dry_run: bool,
+}
+
+struct Columns {
+    contact: usize,
+    date: usize,
+    kind: usize,
+    note: usize,
+    follow_up: Option<usize>,
+}
+
+impl Columns {
+    fn from_headers(headers: &csv::StringRecord) -> Result<Self> {
+        let find = |names: &[&str]| {
+            headers
+                .iter()
+                .position(|header| names.contains(&header.trim().to_ascii_lowercase().as_str()))
+        };
+        let required = |name: &str| {
+            find(&[name]).ok_or_else(|| invalid_input(format!("CSV is missing a `{name}` column")))
+        };
+        Ok(Self {
+            contact: required("contact")?,
+            date: required("date")?,
+            kind: required("kind")?,
+            note: required("note")?,
+            follow_up: find(&["follow_up", "follow_up_at"]),
+        })
+    }
+}
+
+/// A row that passed validation and resolved to exactly one contact.
+struct ParsedRow {
+    contact_id: ContactId,
+    occurred_at: i64,
+    kind: InteractionKind,
+    note: String,
+    follow_up_at: Option<i64>,
+}
+
+enum RowOutcome {
+    Ready(ParsedRow),
+    Unresolved(String),
+    Ambiguous(String),
+    Rejected(String),
+}
+
+pub fn import_interactions(ctx: &Context<'_>, args: ImportInteractionsArgs) -> Result<()> {
+    let mut reader = csv::ReaderBuilder::new()
+        .flexible(true)
+        .from_path(&args.path)
+        .with_context(|| format!("open {}", args.path.display()))?;
+    let columns = Columns::from_headers(reader.headers()?)?;
+    let now = ctx.clock.now_utc();
+    let reschedule = ctx.config.interactions.auto_reschedule;
+    let mut report = InteractionImportReport {
+        dry_run: args.dry_run,
+        ..Default::default()
+    };
+
+    let tx = ctx.store.connection().unchecked_transaction()?;
+    let contacts = ContactsRepo::new(&tx);
+    let interactions = InteractionsRepo::new(&tx);
+    let mut seen = HashSet::new();
+    for record in reader.records() {
+        let record = record.map_err(|err| invalid_input(format!("invalid CSV: {err}")))?;
+        let line = record.position().map(|pos| pos.line()).unwrap_or_default();
+        report.rows += 1;
+
+        let row = match parse_row(
+            &contacts,
+            &columns,
+            &record,
+            now,
+            &ctx.clock,
+            args.allow_future,
+        )? {
+            RowOutcome::Ready(row) => row,
+            RowOutcome::Unresolved(reason) => {
+                report.skipped_unresolved += 1;
+                report.warnings.push(format!("line {line}: {reason}"));
+                continue;
+            }
+            RowOutcome::Ambiguous(reason) => {
+                report.skipped_ambiguous += 1;
+                report.warnings.push(format!("line {line}: {reason}"));
+                continue;
+            }
+            RowOutcome::Rejected(reason) => {
+                report.rejected += 1;
+                report.warnings.push(format!("line {line}: {reason}"));
+                continue;
+            }
+        };
+
+        let key = (
+            row.contact_id,
+            row.occurred_at,
+            row.kind.label(),
+            row.note.clone(),
+        );
+        if !seen.insert(key)
+            || interactions.exists_at(row.contact_id, row.occurred_at, &row.kind, &row.note)?
+        {
+            report.skipped_duplicates += 1;
+            continue;
+        }
+        report.created += 1;
+        if args.dry_run {
+            continue;
+        }
+        interactions.add_with_reschedule_in_tx(
+            now,
+            InteractionNew {
+                contact_id: row.contact_id,
+                occurred_at: row.occurred_at,
+                created_at: now,
+                kind: row.kind,
+                note: row.note,
+                follow_up_at: row.follow_up_at,
+                direction: None,
+            },
+            reschedule,
+        )?;
+    }
+    if !args.dry_run {
+        tx.commit()?;
+    }
+
+    if ctx.json {
+        return print_json(&report);
+    }
+    let suffix = if report.dry_run { " (dry run)" } else { "" };
+    println!(
+        "Imported interactions{}: {} row(s), created {}, duplicates {}, unresolved {}, ambiguous {}, rejected {}",
+        suffix,
+        report.rows,
+        report.created,
+        report.skipped_duplicates,
+        report.skipped_unresolved,
+        report.skipped_ambiguous,
+        report.rejected
+    );
+    if report.dry_run {
+        println!("Dry run: no changes were applied.");
+    }
+    print_warnings(&mut io::stderr(), "interactions import", &report.warnings)
+}
+
+fn parse_row(
+    contacts: &ContactsRepo<'_>,
+    columns: &Columns,
+    record: &csv::StringRecord,
+    now: i64,
+    clock: &Clock,
+    allow_future: bool,
+) -> Result<RowOutcome> {
+    let field = |index: usize| record.get(index).unwrap_or_default().trim();
+
+    let date = field(columns.date);
+    let occurred_at = match clock.parse_local_timestamp(date) {
+        Ok(value) => value,
+        Err(err) => return Ok(RowOutcome::Rejected(format!("date `{date}`: {err}"))),
+    };
+    if occurred_at > now && !allow_future {
+        return Ok(RowOutcome::Rejected(format!(
+            "date `{date}` is in the future (pass --allow-future to import it)"
+        )));
+    }
+    let raw_kind = match field(columns.kind) {
+        "" => DEFAULT_KIND,
+        value => value,
+    };
+    let kind = match parse_interaction_kind(raw_kind) {
+        Ok(kind) => kind,
+        Err(err) => return Ok(RowOutcome::Rejected(err.to_string())),
+    };
+    let follow_up_at = match columns.follow_up.map(field).filter(|raw| !raw.is_empty()) {
+        Some(raw) => match clock.parse_local_timestamp(raw) {
+            Ok(value) => Some(value),
+            Err(err) => return Ok(RowOutcome::Rejected(format!("follow_up `{raw}`: {err}"))),
+        },
+        None => None,
+    };
+
+    let contact = field(columns.contact);
+    if contact.is_empty() {
+        return Ok(RowOutcome::Unresolved("contact is empty".to_string()));
+    }
+    let matches = resolve_contact(contacts, contact)?;
+    let contact_id = match matches.as_slice() {
+        [id] => *id,
+        [] => {
+            return Ok(RowOutcome::Unresolved(format!(
+                "no contact matches `{contact}`"
+            )))
+        }
+        _ => {
+            return Ok(RowOutcome::Ambiguous(format!(
+                "`{contact}` matches {} contacts; use an id or email",
+                matches.len()
+            )))
+        }
+    };
+
+    Ok(RowOutcome::Ready(ParsedRow {
+        contact_id,
+        occurred_at,
+        kind,
+        note: record.get(columns.note).unwrap_or_default().to_string(),
+        follow_up_at,
+    }))
+}
+
+/// Resolves a contact cell as an id, then an email, then a display name.
+fn resolve_contact(contacts: &ContactsRepo<'_>, raw: &str) -> Result<Vec<ContactId>> {
+    if let Ok(id) = ContactId::from_str(raw) {
+        return Ok(contacts
+            .get(id)?
+            .map(|contact| contact.id)
+            .into_iter()
+            .collect());
+    }
+    let found = if raw.contains('@') {
+        contacts.list_by_email(raw)?
+    } else {
+        contacts.list_by_display_name(raw)?
+    };
+    Ok(found.into_iter().map(|contact| contact.id).collect())
+}
@@ -0,0 +1,343 @@
+use crate::commands::print_json;
+use crate::error::invalid_input;
+use anyhow::{Context as _, Result};
+use clap::Args;
+use knotter_config::{InitConfig, InitEmail, InitSource, DEFAULT_SOON_DAYS};
+use knotter_core::rules::cadence::MAX_CADENCE_DAYS;
+use knotter_core::rules::validate_soon_days;
+use knotter_store::{paths, Store};
+use serde::Serialize;
+use std::io::{self, IsTerminal, Write};
+use std::path::PathBuf;
+
+const DEFAULT_SMTP_PASSWORD_ENV: &str = "KNOTTER_SMTP_PASSWORD";
+const DEFAULT_CARDDAV_PASSWORD_ENV: &str = "KNOTTER_CARDDAV_PASSWORD";
+
+/// Every prompt has a flag; prompts are only shown on a terminal, and anything
+/// left unanswered off a terminal takes its default (no cadence, no
+/// notifications, no contact source).
+#[derive(Debug, Args)]
+pub struct InitArgs {
+    #[arg(long, help = "Overwrite an existing config file")]
+    pub force: bool,
+    #[arg(long, value_name = "DAYS")]
+    pub due_soon_days: Option<i64>,
+    #[arg(long, value_name = "DAYS", conflicts_with = "no_default_cadence")]
+    pub default_cadence_days: Option<i32>,
+    #[arg(long, help = "Do not set a default cadence")]
+    pub no_default_cadence: bool,
+    #[arg(
+        long,
+        help = "Do not configure email notifications",
+        conflicts_with_all = [
+            "email_smtp_host",
+            "email_from",
+            "email_to",
+            "email_username",
+            "email_password_env",
+        ]
+    )]
+    pub no_notifications: bool,
+    #[arg(long, value_name = "HOST")]
+    pub email_smtp_host: Option<String>,
+    #[arg(long, value_name = "ADDRESS")]
+    pub email_from: Option<String>,
+    #[arg(long, value_name = "ADDRESS", help = "Recipient (repeatable)")]
+    pub email_to: Vec<String>,
+    #[arg(long, value_name = "USER")]
+    pub email_username: Option<String>,
+    #[arg(
+        long,
+        value_name = "VAR",
+        requires = "email_username",
+        help = "Env var that will hold the SMTP password"
+    )]
+    pub email_password_env: Option<String>,
+    #[arg(
+        long,
+        help = "Do not add a contact source",
+        conflicts_with_all = [
+            "carddav_url",
+            "carddav_username",
+            "carddav_password_env",
+            "macos_source",
+            "source_name",
+        ]
+    )]
+    pub no_source: bool,
+    #[arg(long, value_name = "URL", conflicts_with = "macos_source")]
+    pub carddav_url: Option<String>,
+    #[arg(long, value_name = "USER", requires = "carddav_url")]
+    pub carddav_username: Option<String>,
+    #[arg(
+        long,
+        value_name = "VAR",
+        requires = "carddav_url",
+        help = "Env var that will hold the CardDAV password"
+    )]
+    pub carddav_password_env: Option<String>,
+    #[arg(long, help = "Add the macOS Contacts app as a source")]
+    pub macos_source: bool,
+    #[arg(long, value_name = "NAME", help = "Name for the contact source")]
+    pub source_name: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct InitReport {
+    config_path: String,
+    db_path: String,
+    due_soon_days: i64,
+    default_cadence_days: Option<i32>,
+    email_notifications: bool,
+    contact_source: Option<String>,
+}
+
+pub fn init(
+    db_path: Option<PathBuf>,
+    config_path: Option<PathBuf>,
+    json: bool,
+    args: InitArgs,
+) -> Result<()> {
+    let path =
+        knotter_config::resolve_config_path(config_path).with_context(|| "resolve config path")?;
+    if path.exists() && !args.force {
+        return Err(invalid_input(format!(
+            "config file already exists: {} (use --force to overwrite)",
+            path.display()
+        )));
+    }
+
+    let prompt = Prompter {
+        interactive: io::stdin().is_terminal(),
+    };
+    let answers = InitConfig {
+        due_soon_days: due_soon_days(&prompt, args.due_soon_days)?,
+        default_cadence_days: default_cadence_days(&prompt, &args)?,
+        email: email(&prompt, &args)?,
+        source: source(&prompt, &args)?,
+    };
+
+    knotter_config::write_config(&path, &answers.render(), args.force)?;
+    let db_path = paths::resolve_db_path(db_path).with_context(|| "resolve database path")?;
+    let store =
+        Store::open(&db_path).with_context(|| format!("open database {}", db_path.display()))?;
+    store.migrate().with_context(|| "run migrations")?;
+
+    let report = InitReport {
+        config_path: path.display().to_string(),
+        db_path: db_path.display().to_string(),
+        due_soon_days: answers.due_soon_days,
+        default_cadence_days: answers.default_cadence_days,
+        email_notifications: answers.email.is_some(),
+        contact_source: answers.source.as_ref().map(|source| match source {
+            InitSource::Carddav { name, .. } | InitSource::Macos { name } => name.clone(),
+        }),
+    };
+    if json {
+        return print_json(&report);
+    }
+    println!("Wrote config to {}", report.config_path);
+    println!("Initialized database at {}", report.db_path);
+    if let Some(email) = &answers.email {
+        if let Some(var) = &email.password_env {
+            println!("Set {var} to the SMTP password before running `knotter remind`.");
+        }
+    }
+    if let Some(name) = &report.contact_source {
+        println!("Import contacts with `knotter import source {name}`.");
+    }
+    Ok(())
+}
+
+fn due_soon_days(prompt: &Prompter, flag: Option<i64>) -> Result<i64> {
+    let days = match flag {
+        Some(days) => days,
+        None => match prompt.ask(
+            "Days ahead that count as due soon",
+            Some(&DEFAULT_SOON_DAYS.to_string()),
+        )? {
+            Some(answer) => answer
+                .parse()
+                .map_err(|_| invalid_input(format!("not a number of days: {answer}")))?,
+            None => DEFAULT_SOON_DAYS,
+        },
+    };
+    Ok(validate_soon_days(days)?)
+}
+
+fn default_cadence_days(prompt: &Prompter, args: &InitArgs) -> Result<Option<i32>> {
+    let days = match args.default_cadence_days {
+        Some(days) => days,
+        None if args.no_default_cadence => return Ok(None),
+        None => match prompt.ask(
+            "Default cadence in days for new contacts (blank for none)",
+            None,
+        )? {
+            Some(answer) => answer
+                .parse()
+                .map_err(|_| invalid_input(format!("not a number of days: {answer}")))?,
+            None => return Ok(None),
+        },
+    };
+    if !(1..=MAX_CADENCE_DAYS).contains(&days) {
+        return Err(invalid_input(format!(
+            "default cadence must be between 1 and {MAX_CADENCE_DAYS} days"
+        )));
+    }
+    Ok(Some(days))
+}
+
+fn email(prompt: &Prompter, args: &InitArgs) -> Result<Option<InitEmail>> {
+    let from_flags = args.email_smtp_host.is_some()
+        || args.email_from.is_some()
+        || !args.email_to.is_empty()
+        || args.email_username.is_some();
+    if args.no_notifications || (!from_flags && !prompt.confirm("Send reminders by email?")?) {
+        return Ok(None);
+    }
+
+    let smtp_host = prompt.require(
+        "SMTP host",
+        args.email_smtp_host.clone(),
+        "--email-smtp-host",
+    )?;
+    let from = prompt.require("From address", args.email_from.clone(), "--email-from")?;
+    let to = if args.email_to.is_empty() {
+        vec![prompt.require("Send reminders to", None, "--email-to")?]
+    } else {
+        args.email_to.clone()
+    };
+    let username = match &args.email_username {
+        Some(username) => Some(username.clone()),
+        None if from_flags => None,
+        None => prompt.ask("SMTP username (blank for none)", None)?,
+    };
+    let password_env = match (&username, &args.email_password_env) {
+        (None, _) => None,
+        (Some(_), Some(var)) => Some(var.clone()),
+        (Some(_), None) => Some(
+            prompt
+                .ask(
+                    "Env var that will hold the SMTP password",
+                    Some(DEFAULT_SMTP_PASSWORD_ENV),
+                )?
+                .unwrap_or_else(|| DEFAULT_SMTP_PASSWORD_ENV.to_string()),
+        ),
+    };
+    Ok(Some(InitEmail {
+        smtp_host,
+        from,
+        to,
+        username,
+        password_env,
+    }))
+}
+
+fn source(prompt: &Prompter, args: &InitArgs) -> Result<Option<InitSource>> {
+    if args.no_source {
+        return Ok(None);
+    }
+    let kind = if args.carddav_url.is_some() {
+        "carddav".to_string()
+    } else if args.macos_source {
+        "macos".to_string()
+    } else {
+        match prompt.ask(
+            "Add a contact source (carddav, macos, or none)",
+            Some("none"),
+        )? {
+            Some(answer) => answer.to_ascii_lowercase(),
+            None => return Ok(None),
+        }
+    };
+    let name = |default: &str| -> Result<String> {
+        match &args.source_name {
+            Some(name) => Ok(name.clone()),
+            None => Ok(prompt
+                .ask("Source name", Some(default))?
+                .unwrap_or_else(|| default.to_string())),
+        }
+    };
+
+    match kind.as_str() {
+        "none" => Ok(None),
+        "macos" => Ok(Some(InitSource::Macos {
+            name: name("macos")?,
+        })),
+        "carddav" => {
+            let url = prompt.require(
+                "CardDAV address book URL",
+                args.carddav_url.clone(),
+                "--carddav-url",
+            )?;
+            let username = prompt.require(
+                "CardDAV username",
+                args.carddav_username.clone(),
+                "--carddav-username",
+            )?;
+            let password_env = match &args.carddav_password_env {
+                Some(var) => Some(var.clone()),
+                None if args.carddav_url.is_some() => None,
+                None => Some(
+                    prompt
+                        .ask(
+                            "Env var that will hold the CardDAV password",
+                            Some(DEFAULT_CARDDAV_PASSWORD_ENV),
+                        )?
+                        .unwrap_or_else(|| DEFAULT_CARDDAV_PASSWORD_ENV.to_string()),
+                ),
+            };
+            Ok(Some(InitSource::Carddav {
+                name: name("carddav")?,
+                url,
+                username,
+                password_env,
+            }))
+        }
+        other => Err(invalid_input(format!(
+            "unknown contact source type: {other} (expected carddav, macos, or none)"
+        ))),
+    }
+}
+
+/// Asks on stderr so `--json` output stays clean; off a terminal every
+/// question gets its default.
+struct Prompter {
+    interactive: bool,
+}
+
+impl Prompter {
+    /// The trimmed answer, or `default` when it is blank; `None` when both are.
+    fn ask(&self, question: &str, default: Option<&str>) -> Result<Option<String>> {
+        if !self.interactive {
+            return Ok(default.map(str::to_string));
+        }
+        match default {
+            Some(default) => eprint!("{question} [{default}]: "),
+            None => eprint!("{question}: "),
+        }
+        io::stderr().flush()?;
+        let mut answer = String::new();
+        io::stdin().read_line(&mut answer)?;
+        let answer = answer.trim();
+        if answer.is_empty() {
+            return Ok(default.map(str::to_string));
+        }
+        Ok(Some(answer.to_string()))
+    }
+
+    fn confirm(&self, question: &str) -> Result<bool> {
+        let answer = self.ask(&format!("{question} (y/N)"), None)?;
+        Ok(answer.is_some_and(|answer| answer.eq_ignore_ascii_case("y")))
+    }
+
+    /// `flag` if given, otherwise a non-blank answer; a blank answer or a
+    /// missing flag off a terminal is an error naming the flag.
+    fn require(&self, question: &str, flag: Option<String>, flag_name: &str) -> Result<String> {
+        if let Some(value) = flag {
+            return Ok(value);
+        }
+        self.ask(question, None)?
+            .ok_or_else(|| invalid_input(format!("{flag_name} is required")))
+    }
+}
@@ -1,17 +1,41 @@
-use crate::commands::{print_json, Context};
+use crate::commands::dry_run::{print_dry_run, run_contact_change, FieldChange};
+use crate::commands::{print_json, resolve_contact_arg, Context};
 use crate::error::{invalid_input, not_found};
-use crate::util::{
-    format_interaction_kind, now_utc, parse_contact_id, parse_interaction_kind,
-    parse_local_timestamp,
-};
+use crate::util::{parse_interaction_id, parse_interaction_kind};
 use anyhow::Result;
-use clap::{ArgAction, Args};
-use knotter_core::dto::InteractionDto;
-use knotter_store::repo::InteractionNew;
+use clap::{ArgAction, Args, Subcommand, ValueEnum};
+use knotter_core::domain::{
+    validate_attachment, AttachmentKind, Interaction, InteractionId, InteractionKind,
+};
+use knotter_core::dto::{AttachmentDto, InteractionDto};
+use knotter_core::rules::ensure_sane_interaction_timestamp;
+use knotter_store::repo::{InteractionAdd, InteractionNew, InteractionUpdate};
+use serde::Serialize;
+use std::collections::BTreeMap;
 use std::io::{self, Read};
 
+// `--template`/`--var`, shared by `touch` and `add-note`. A plain comment:
+// clap would take a doc comment on a flattened struct as the command about.
+#[derive(Debug, Default, Args)]
+pub struct NoteTemplateArgs {
+    #[arg(
+        long,
+        value_name = "NAME",
+        help = "Add a note from [interactions.templates], after --note if both are given"
+    )]
+    pub template: Option<String>,
+    #[arg(
+        long = "var",
+        value_name = "NAME=VALUE",
+        requires = "template",
+        help = "Fill a template variable (repeatable)"
+    )]
+    pub vars: Vec<String>,
+}
+
 #[derive(Debug, Args)]
 pub struct AddNoteArgs {
+    /// Contact id, or a name, alias, email, or @handle
     pub id: String,
     #[arg(long, default_value = "other:note")]
     pub kind: String,
@@ -19,48 +43,163 @@ pub struct AddNoteArgs {
     pub when: Option<String>,
     #[arg(long)]
     pub note: Option<String>,
+    #[command(flatten)]
+    pub template: NoteTemplateArgs,
     #[arg(long)]
     pub follow_up_at: Option<String>,
     #[arg(long, action = ArgAction::SetTrue, conflicts_with = "no_reschedule")]
     pub reschedule: bool,
     #[arg(long, action = ArgAction::SetTrue)]
     pub no_reschedule: bool,
+    #[arg(long, help = "Add the note even if the same one was just logged")]
+    pub force: bool,
 }
 
 #[derive(Debug, Args)]
 pub struct TouchArgs {
+    /// Contact id, or a name, alias, email, or @handle
     pub id: String,
-    #[arg(long, default_value = "other:touch")]
-    pub kind: String,
+    #[arg(
+        long,
+        help = "Interaction kind [default: interactions.default_touch_kind, or other:touch]"
+    )]
+    pub kind: Option<String>,
     #[arg(long)]
     pub when: Option<String>,
     #[arg(long)]
     pub note: Option<String>,
+    #[command(flatten)]
+    pub template: NoteTemplateArgs,
     #[arg(long)]
     pub follow_up_at: Option<String>,
     #[arg(long, action = ArgAction::SetTrue, conflicts_with = "no_reschedule")]
     pub reschedule: bool,
     #[arg(long, action = ArgAction::SetTrue)]
     pub no_reschedule: bool,
+    #[arg(long, help = "Log the touch even if the same one was just logged")]
+    pub force: bool,
+    #[arg(long, help = "Show the touch and any reschedule without saving them")]
+    pub dry_run: bool,
+}
+
+/// Interaction output plus whether the dedupe window matched an existing one.
+#[derive(Debug, Serialize)]
+pub(crate) struct RecordedInteractionDto {
+    #[serde(flatten)]
+    interaction: InteractionDto,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    already_recorded: bool,
+}
+
+/// Edit or remove logged interactions
+#[derive(Debug, Subcommand)]
+pub enum NoteCommand {
+    /// Change an interaction's note, kind, or time
+    Edit(EditNoteArgs),
+    /// Delete an interaction
+    Rm(RemoveNoteArgs),
+    /// Link a file path or URL to an interaction
+    #[command(
+        after_help = "Files are linked by path, as given; knotter does not copy them.
+
+Examples:
+  knotter note attach <interaction-id> ~/notes/2024-05-02-ada.md
+  knotter note attach <interaction-id> https://photos.example/album/42
+  knotter note attach <interaction-id> --kind url mailto:ada@example.com"
+    )]
+    Attach(AttachArgs),
+    /// List the files and URLs linked to an interaction
+    Attachments(AttachmentsArgs),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum AttachmentKindArg {
+    File,
+    Url,
+}
+
+impl From<AttachmentKindArg> for AttachmentKind {
+    fn from(kind: AttachmentKindArg) -> Self {
+        match kind {
+            AttachmentKindArg::File => AttachmentKind::File,
+            AttachmentKindArg::Url => AttachmentKind::Url,
+        }
+    }
+}
+
+#[derive(Debug, Args)]
+pub struct AttachArgs {
+    /// Interaction id (see `show <contact>`)
+    pub id: String,
+    /// File path or URL
+    pub target: String,
+    #[arg(
+        long,
+        value_enum,
+        help = "Attachment kind; by default anything like scheme://... is a URL and the rest are file paths"
+    )]
+    pub kind: Option<AttachmentKindArg>,
+}
+
+#[derive(Debug, Args)]
+pub struct AttachmentsArgs {
+    /// Interaction id (see `show <contact>`)
+    pub id: String,
+}
+
+#[derive(Debug, Serialize)]
+struct AttachReport {
+    interaction_id: InteractionId,
+    /// `false` when the interaction already had this attachment.
+    added: bool,
+    attachment: AttachmentDto,
+}
+
+#[derive(Debug, Args)]
+pub struct EditNoteArgs {
+    pub id: String,
+    #[arg(long)]
+    pub kind: Option<String>,
+    #[arg(long)]
+    pub when: Option<String>,
+    #[arg(long)]
+    pub note: Option<String>,
+    #[arg(long, action = ArgAction::SetTrue, conflicts_with = "no_reschedule")]
+    pub reschedule: bool,
+    #[arg(long, action = ArgAction::SetTrue)]
+    pub no_reschedule: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct RemoveNoteArgs {
+    pub id: String,
+    #[arg(long, action = ArgAction::SetTrue, conflicts_with = "no_reschedule")]
+    pub reschedule: bool,
+    #[arg(long, action = ArgAction::SetTrue)]
+    pub no_reschedule: bool,
 }
 
 pub fn add_note(ctx: &Context<'_>, args: AddNoteArgs) -> Result<()> {
-    let contact_id = parse_contact_id(&args.id)?;
+    let contact_id = resolve_contact_arg(ctx, &args.id)?;
     if ctx.store.contacts().get(contact_id)?.is_none() {
         return Err(not_found("contact not found"));
     }
-    let now = now_utc();
+    let now = ctx.clock.now_utc();
     let kind = parse_interaction_kind(&args.kind)?;
     let occurred_at = match args.when {
-        Some(value) => parse_local_timestamp(&value)?,
+        Some(value) => ensure_sane_interaction_timestamp(
+            now,
+            ctx.clock.parse_local_timestamp(&value)?,
+            ctx.config.sync.max_future_skew_days,
+        )?,
         None => now,
     };
     let follow_up_at = match args.follow_up_at {
-        Some(value) => Some(parse_local_timestamp(&value)?),
+        Some(value) => Some(ctx.clock.parse_local_timestamp(&value)?),
         None => None,
     };
 
-    let note = match args.note {
+    let note = match compose_note(ctx, args.note, &args.template)? {
         Some(value) => value,
         None => read_note_from_stdin()?,
     };
@@ -80,46 +219,84 @@ pub fn add_note(ctx: &Context<'_>, args: AddNoteArgs) -> Result<()> {
         kind,
         note,
         follow_up_at,
+        direction: None,
     };
-    let interaction = if reschedule {
-        ctx.store
-            .interactions()
-            .add_with_reschedule(now, input, true)?
-    } else {
-        ctx.store.interactions().add(input)?
-    };
+    let outcome = ctx.store.interactions().add_unless_recent(
+        now,
+        input,
+        reschedule,
+        dedupe_window_seconds(ctx, args.force),
+    )?;
+    let already_recorded = matches!(outcome, InteractionAdd::AlreadyRecorded(_));
+    let interaction = outcome.into_interaction();
 
     if ctx.json {
-        let dto = InteractionDto {
-            id: interaction.id,
-            occurred_at: interaction.occurred_at,
-            kind: format_interaction_kind(&interaction.kind),
-            note: interaction.note,
-            follow_up_at: interaction.follow_up_at,
-        };
-        print_json(&dto)?;
+        print_json(&RecordedInteractionDto {
+            interaction: interaction_to_dto(ctx, interaction)?,
+            already_recorded,
+        })?;
+    } else if already_recorded {
+        println!(
+            "already recorded interaction {} (use --force to add another)",
+            interaction.id
+        );
     } else {
         println!("added interaction {}", interaction.id);
     }
     Ok(())
 }
 
+/// What `record_touch` did, ready for `touch_contact` to print.
+pub(crate) struct RecordedTouch {
+    pub dto: RecordedInteractionDto,
+    /// "touched <id>", or why nothing new was logged.
+    pub summary: String,
+    changes: Vec<FieldChange>,
+}
+
 pub fn touch_contact(ctx: &Context<'_>, args: TouchArgs) -> Result<()> {
-    let contact_id = parse_contact_id(&args.id)?;
+    let dry_run = args.dry_run;
+    let touch = record_touch(ctx, args)?;
+    if dry_run {
+        print_dry_run(ctx, &touch.dto, &touch.summary, &touch.changes)?;
+    } else if ctx.json {
+        print_json(&touch.dto)?;
+    } else {
+        println!("{}", touch.summary);
+    }
+    Ok(())
+}
+
+/// The `touch` command without its output, for callers that report the
+/// result their own way.
+pub(crate) fn record_touch(ctx: &Context<'_>, args: TouchArgs) -> Result<RecordedTouch> {
+    let contact_id = resolve_contact_arg(ctx, &args.id)?;
     if ctx.store.contacts().get(contact_id)?.is_none() {
         return Err(not_found("contact not found"));
     }
-    let now = now_utc();
-    let kind = parse_interaction_kind(&args.kind)?;
+    let now = ctx.clock.now_utc();
+    let kind = match args.kind {
+        Some(raw) => parse_interaction_kind(&raw)?,
+        None => ctx
+            .config
+            .interactions
+            .default_touch_kind
+            .clone()
+            .unwrap_or_else(|| InteractionKind::Other("touch".to_string())),
+    };
     let occurred_at = match args.when {
-        Some(value) => parse_local_timestamp(&value)?,
+        Some(value) => ensure_sane_interaction_timestamp(
+            now,
+            ctx.clock.parse_local_timestamp(&value)?,
+            ctx.config.sync.max_future_skew_days,
+        )?,
         None => now,
     };
     let follow_up_at = match args.follow_up_at {
-        Some(value) => Some(parse_local_timestamp(&value)?),
+        Some(value) => Some(ctx.clock.parse_local_timestamp(&value)?),
         None => None,
     };
-    let note = args.note.unwrap_or_default();
+    let note = compose_note(ctx, args.note, &args.template)?.unwrap_or_default();
     let reschedule = if args.reschedule {
         true
     } else if args.no_reschedule {
@@ -134,30 +311,241 @@ pub fn touch_contact(ctx: &Context<'_>, args: TouchArgs) -> Result<()> {
         kind,
         note,
         follow_up_at,
+        direction: None,
+    };
+    let window_seconds = dedupe_window_seconds(ctx, args.force);
+    let (outcome, changes) = run_contact_change(ctx, contact_id, args.dry_run, || {
+        Ok(ctx.store.interactions().add_unless_recent_in_tx(
+            now,
+            input,
+            reschedule,
+            window_seconds,
+        )?)
+    })?;
+    let already_recorded = matches!(outcome, InteractionAdd::AlreadyRecorded(_));
+
+    let dto = RecordedInteractionDto {
+        interaction: interaction_to_dto(ctx, outcome.into_interaction())?,
+        already_recorded,
     };
-    let interaction = if reschedule {
-        ctx.store
-            .interactions()
-            .add_with_reschedule(now, input, true)?
+    let summary = if already_recorded {
+        format!("already touched {contact_id} (use --force to log another)")
     } else {
-        ctx.store.interactions().add(input)?
+        format!("touched {contact_id}")
     };
+    Ok(RecordedTouch {
+        dto,
+        summary,
+        changes,
+    })
+}
 
-    if ctx.json {
-        let dto = InteractionDto {
-            id: interaction.id,
-            occurred_at: interaction.occurred_at,
-            kind: format_interaction_kind(&interaction.kind),
-            note: interaction.note,
-            follow_up_at: interaction.follow_up_at,
+/// `--note`, then the rendered `--template` on its own line; `None` when
+/// neither was given.
+fn compose_note(
+    ctx: &Context<'_>,
+    note: Option<String>,
+    args: &NoteTemplateArgs,
+) -> Result<Option<String>> {
+    let Some(name) = args.template.as_deref() else {
+        return Ok(note);
+    };
+    let templates = &ctx.config.interactions.templates;
+    let Some(template) = templates.get(name) else {
+        let known = if templates.is_empty() {
+            "none defined; add them under [interactions.templates]".to_string()
+        } else {
+            format!(
+                "defined: {}",
+                templates.keys().cloned().collect::<Vec<_>>().join(", ")
+            )
         };
-        print_json(&dto)?;
+        return Err(invalid_input(format!("unknown template {name} ({known})")));
+    };
+    let mut vars = BTreeMap::new();
+    for raw in &args.vars {
+        let Some((key, value)) = raw.split_once('=') else {
+            return Err(invalid_input(format!(
+                "--var expects NAME=VALUE, got {raw:?}"
+            )));
+        };
+        let key = key.trim();
+        if vars.insert(key.to_string(), value.to_string()).is_some() {
+            return Err(invalid_input(format!("--var {key} given more than once")));
+        }
+    }
+    let rendered = template
+        .render(&vars)
+        .map_err(|err| invalid_input(format!("template {name}: {err}")))?;
+    Ok(Some(match note {
+        Some(note) if !note.is_empty() => format!("{note}\n{rendered}"),
+        _ => rendered,
+    }))
+}
+
+pub fn edit_note(ctx: &Context<'_>, args: EditNoteArgs) -> Result<()> {
+    let id = parse_interaction_id(&args.id)?;
+    if args.kind.is_none() && args.when.is_none() && args.note.is_none() {
+        return Err(invalid_input(
+            "nothing to change (use --note, --kind, or --when)",
+        ));
+    }
+    let now = ctx.clock.now_utc();
+    let kind = args
+        .kind
+        .as_deref()
+        .map(parse_interaction_kind)
+        .transpose()?;
+    let occurred_at = args
+        .when
+        .as_deref()
+        .map(|raw| ctx.clock.parse_local_timestamp(raw))
+        .transpose()?;
+    if occurred_at.is_some_and(|value| value > now) {
+        return Err(invalid_input("--when cannot be in the future"));
+    }
+    if let Some(value) = occurred_at {
+        ensure_sane_interaction_timestamp(now, value, 0)?;
+    }
+    let reschedule = resolve_reschedule(ctx, args.reschedule, args.no_reschedule);
+
+    let interaction = ctx.store.interactions().update(
+        now,
+        id,
+        InteractionUpdate {
+            occurred_at,
+            kind,
+            note: args.note,
+        },
+        reschedule,
+    )?;
+
+    if ctx.json {
+        print_json(&interaction_to_dto(ctx, interaction)?)?;
+    } else {
+        println!("updated interaction {}", interaction.id);
+    }
+    Ok(())
+}
+
+pub fn remove_note(ctx: &Context<'_>, args: RemoveNoteArgs) -> Result<()> {
+    let id = parse_interaction_id(&args.id)?;
+    let reschedule = resolve_reschedule(ctx, args.reschedule, args.no_reschedule);
+    let removed = ctx
+        .store
+        .interactions()
+        .delete(ctx.clock.now_utc(), id, reschedule)?;
+
+    if ctx.json {
+        print_json(&serde_json::json!({
+            "id": removed.id,
+            "contact_id": removed.contact_id,
+        }))?;
+    } else {
+        println!("removed interaction {}", removed.id);
+    }
+    Ok(())
+}
+
+pub fn attach(ctx: &Context<'_>, args: AttachArgs) -> Result<()> {
+    let id = parse_interaction_id(&args.id)?;
+    let kind = args
+        .kind
+        .map(AttachmentKind::from)
+        .unwrap_or_else(|| AttachmentKind::detect(&args.target));
+    let value =
+        validate_attachment(kind, &args.target).map_err(|err| invalid_input(err.to_string()))?;
+    if ctx.store.interactions().get(id)?.is_none() {
+        return Err(not_found("interaction not found"));
+    }
+
+    let repo = ctx.store.interaction_attachments();
+    let added = repo.add(ctx.clock.now_utc(), id, kind, &value)?;
+    let attachment = repo
+        .list_for_interaction(id)?
+        .into_iter()
+        .find(|attachment| attachment.value == value)
+        .map(AttachmentDto::from)
+        .ok_or_else(|| not_found("attachment not found"))?;
+
+    if ctx.json {
+        print_json(&AttachReport {
+            interaction_id: id,
+            added,
+            attachment,
+        })?;
+    } else if added {
+        println!(
+            "attached {} {} to interaction {id}",
+            attachment.kind.as_str(),
+            attachment.value
+        );
     } else {
-        println!("touched {}", contact_id);
+        println!("interaction {id} already has {}", attachment.value);
+    }
+    Ok(())
+}
+
+pub fn list_attachments(ctx: &Context<'_>, args: AttachmentsArgs) -> Result<()> {
+    let id = parse_interaction_id(&args.id)?;
+    if ctx.store.interactions().get(id)?.is_none() {
+        return Err(not_found("interaction not found"));
+    }
+    let attachments: Vec<AttachmentDto> = ctx
+        .store
+        .interaction_attachments()
+        .list_for_interaction(id)?
+        .into_iter()
+        .map(AttachmentDto::from)
+        .collect();
+
+    if ctx.json {
+        return print_json(&attachments);
+    }
+    if attachments.is_empty() {
+        println!("no attachments");
+    }
+    for attachment in attachments {
+        println!("{:<4}  {}", attachment.kind.as_str(), attachment.value);
     }
     Ok(())
 }
 
+fn resolve_reschedule(ctx: &Context<'_>, reschedule: bool, no_reschedule: bool) -> bool {
+    if reschedule {
+        true
+    } else if no_reschedule {
+        false
+    } else {
+        ctx.config.interactions.auto_reschedule
+    }
+}
+
+/// 0 (no guard) with `--force`, otherwise `interactions.dedupe_window_minutes`.
+fn dedupe_window_seconds(ctx: &Context<'_>, force: bool) -> i64 {
+    if force {
+        0
+    } else {
+        ctx.config.interactions.dedupe_window_minutes * 60
+    }
+}
+
+fn interaction_to_dto(ctx: &Context<'_>, interaction: Interaction) -> Result<InteractionDto> {
+    let attachments = ctx
+        .store
+        .interaction_attachments()
+        .list_for_interaction(interaction.id)?;
+    Ok(InteractionDto {
+        id: interaction.id,
+        occurred_at: interaction.occurred_at,
+        kind: interaction.kind.label(),
+        note: interaction.note,
+        follow_up_at: interaction.follow_up_at,
+        direction: interaction.direction,
+        attachments: attachments.into_iter().map(AttachmentDto::from).collect(),
+    })
+}
+
 fn read_note_from_stdin() -> Result<String> {
     let mut buffer = String::new();
     io::stdin().read_to_string(&mut buffer)?;
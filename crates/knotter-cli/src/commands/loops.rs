@@ -1,12 +1,11 @@
 use crate::commands::{print_json, Context};
 use crate::error::invalid_input;
-use crate::util::{format_timestamp_date, local_offset, now_utc};
 use anyhow::Result;
 use clap::{ArgAction, Args, Subcommand};
 use knotter_config::{AppConfig, LoopAnchor};
-use knotter_core::domain::ContactId;
-use knotter_core::filter::parse_filter;
-use knotter_core::rules::schedule_next;
+use knotter_core::domain::{ContactId, TagName};
+use knotter_core::filter::{parse_filter, MutedSelector};
+use knotter_core::rules::{jitter_touchpoint, schedule_next, LoopPolicy, MAX_JITTER_DAYS};
 use knotter_store::query::ContactQuery;
 use knotter_store::repo::{ContactUpdate, ContactsRepo, InteractionsRepo, TagsRepo};
 use serde::Serialize;
@@ -18,6 +17,12 @@ pub enum LoopCommand {
 }
 
 #[derive(Debug, Args)]
+#[command(after_help = "Examples:
+  knotter loops apply --dry-run
+  knotter loops apply --filter \"#friends\" --schedule-missing
+  knotter loops apply --only-tag family --anchor last-interaction
+  knotter loops apply --force --quiet
+  knotter loops apply --schedule-missing --jitter 5")]
 pub struct LoopApplyArgs {
     #[arg(long)]
     pub filter: Option<String>,
@@ -31,6 +36,20 @@ pub struct LoopApplyArgs {
     pub no_schedule_missing: bool,
     #[arg(long)]
     pub anchor: Option<String>,
+    #[arg(long, help = "Only print warnings and errors")]
+    pub quiet: bool,
+    #[arg(
+        long = "only-tag",
+        value_name = "TAG",
+        help = "Only apply the loop rules for this tag (repeatable)"
+    )]
+    pub only_tag: Vec<String>,
+    #[arg(
+        long,
+        value_name = "DAYS",
+        help = "Spread new touchpoints over ±DAYS per contact (default: loops.jitter_days)"
+    )]
+    pub jitter: Option<i32>,
 }
 
 #[derive(Debug, Serialize)]
@@ -44,28 +63,43 @@ struct LoopApplyChange {
     scheduled: bool,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Default, Serialize)]
 struct LoopApplyReport {
     matched: usize,
     updated: usize,
     scheduled: usize,
+    /// Total of the `skipped_*` buckets below.
     skipped: usize,
+    skipped_archived: usize,
+    /// Kept an existing cadence because `override_existing` is off.
+    skipped_existing: usize,
+    /// No rule (or, without `--only-tag`, no default cadence) applies, or the
+    /// contact's priority is below every matching rule's `min_priority`.
+    skipped_no_loop: usize,
+    skipped_unchanged: usize,
     dry_run: bool,
     changes: Vec<LoopApplyChange>,
 }
 
 pub fn apply_loops(ctx: &Context<'_>, args: LoopApplyArgs) -> Result<()> {
-    let policy = &ctx.config.loops.policy;
-    if policy.rules.is_empty() && policy.default_cadence_days.is_none() {
+    if !loops_configured(ctx.config) {
         return Err(invalid_input("no loops configured"));
     }
+    let only_tags = restricted_policy(&ctx.config.loops.policy, &args.only_tag)?;
+    let policy = only_tags.as_ref().unwrap_or(&ctx.config.loops.policy);
 
     let filter_text = args.filter.unwrap_or_default();
     let parsed = parse_filter(&filter_text)?;
-    let query = ContactQuery::from_filter(&parsed)?;
+    let mut query = ContactQuery::from_filter(&parsed)?;
+    // Muted contacts keep their schedule untouched unless the filter asks for them.
+    if query.muted.is_none() {
+        query.muted = Some(MutedSelector::Unmuted);
+    }
+    // Archived contacts are never rescheduled, even when the filter selects
+    // them; they are listed only so the report can count them.
 
-    let now = now_utc();
-    let offset = local_offset();
+    let now = ctx.clock.now_utc();
+    let offset = ctx.clock.local_offset();
     let soon_days = ctx.config.due_soon_days;
     let contacts = ctx
         .store
@@ -75,12 +109,8 @@ pub fn apply_loops(ctx: &Context<'_>, args: LoopApplyArgs) -> Result<()> {
     if contacts.is_empty() {
         if ctx.json {
             print_json(&LoopApplyReport {
-                matched: 0,
-                updated: 0,
-                scheduled: 0,
-                skipped: 0,
                 dry_run: args.dry_run,
-                changes: Vec::new(),
+                ..Default::default()
             })?;
         } else {
             println!("no contacts matched");
@@ -100,12 +130,14 @@ pub fn apply_loops(ctx: &Context<'_>, args: LoopApplyArgs) -> Result<()> {
         ctx.config.loops.schedule_missing
     };
     let override_existing = args.force || ctx.config.loops.override_existing;
+    let jitter_days = resolve_jitter(args.jitter, ctx.config)?;
 
     let contact_ids = contacts
         .iter()
         .map(|contact| contact.id)
         .collect::<Vec<_>>();
     let tags_by_contact = ctx.store.tags().list_names_for_contacts(&contact_ids)?;
+    let priorities = ctx.store.contacts().list_priorities()?;
     let latest_interactions = if schedule_missing && anchor == LoopAnchor::LastInteraction {
         ctx.store
             .interactions()
@@ -114,16 +146,15 @@ pub fn apply_loops(ctx: &Context<'_>, args: LoopApplyArgs) -> Result<()> {
         HashMap::new()
     };
 
-    let mut matched = 0;
-    let mut updated = 0;
-    let mut scheduled = 0;
-    let mut skipped = 0;
-    let mut changes = Vec::new();
+    let mut report = LoopApplyReport {
+        dry_run: args.dry_run,
+        ..Default::default()
+    };
     let mut planned_updates = Vec::new();
 
     for contact in contacts {
         if contact.archived_at.is_some() {
-            skipped += 1;
+            report.skipped_archived += 1;
             continue;
         }
 
@@ -131,14 +162,15 @@ pub fn apply_loops(ctx: &Context<'_>, args: LoopApplyArgs) -> Result<()> {
             .get(&contact.id)
             .cloned()
             .unwrap_or_default();
-        let desired = match policy.resolve_cadence(tags.iter().map(|tag| tag.as_str())) {
+        let priority = priorities.get(&contact.id).copied().unwrap_or(0);
+        let desired = match policy.resolve_cadence(tags.iter().map(|tag| tag.as_str()), priority) {
             Some(value) => value,
             None => {
-                skipped += 1;
+                report.skipped_no_loop += 1;
                 continue;
             }
         };
-        matched += 1;
+        report.matched += 1;
 
         let cadence_before = contact.cadence_days;
         let cadence_after = if cadence_before.is_some() && !override_existing {
@@ -154,14 +186,20 @@ pub fn apply_loops(ctx: &Context<'_>, args: LoopApplyArgs) -> Result<()> {
             if let Some(cadence_days) = cadence_after {
                 if let Some(anchor_ts) = resolve_anchor(&contact, anchor, now, &latest_interactions)
                 {
-                    next_touchpoint_after = Some(schedule_next(anchor_ts, cadence_days)?);
+                    let scheduled = schedule_next(anchor_ts, cadence_days)?;
+                    next_touchpoint_after =
+                        Some(jitter_touchpoint(scheduled, jitter_days, &contact.id)?);
                     scheduled_now = true;
                 }
             }
         }
 
         if !cadence_changed && !scheduled_now {
-            skipped += 1;
+            if cadence_before.is_some_and(|before| before != desired) {
+                report.skipped_existing += 1;
+            } else {
+                report.skipped_unchanged += 1;
+            }
             continue;
         }
 
@@ -176,12 +214,12 @@ pub fn apply_loops(ctx: &Context<'_>, args: LoopApplyArgs) -> Result<()> {
             planned_updates.push((contact.id, update));
         }
 
-        updated += 1;
+        report.updated += 1;
         if scheduled_now {
-            scheduled += 1;
+            report.scheduled += 1;
         }
 
-        changes.push(LoopApplyChange {
+        report.changes.push(LoopApplyChange {
             id: contact.id,
             display_name: contact.display_name,
             cadence_before,
@@ -201,26 +239,22 @@ pub fn apply_loops(ctx: &Context<'_>, args: LoopApplyArgs) -> Result<()> {
         tx.commit()?;
     }
 
-    let report = LoopApplyReport {
-        matched,
-        updated,
-        scheduled,
-        skipped,
-        dry_run: args.dry_run,
-        changes,
-    };
+    report.skipped = report.skipped_archived
+        + report.skipped_existing
+        + report.skipped_no_loop
+        + report.skipped_unchanged;
 
     if ctx.json {
         print_json(&report)?;
         return Ok(());
     }
+    if args.quiet {
+        return Ok(());
+    }
 
     if report.changes.is_empty() {
         println!("no changes needed");
-        println!(
-            "matched {} | updated {} | scheduled {} | skipped {}",
-            report.matched, report.updated, report.scheduled, report.skipped
-        );
+        print_summary(&report);
         return Ok(());
     }
 
@@ -233,7 +267,7 @@ pub fn apply_loops(ctx: &Context<'_>, args: LoopApplyArgs) -> Result<()> {
             _ => "cadence unchanged".to_string(),
         };
         let schedule_label = match (change.next_touchpoint_before, change.next_touchpoint_after) {
-            (None, Some(after)) => format!("scheduled {}", format_timestamp_date(after)),
+            (None, Some(after)) => format!("scheduled {}", ctx.clock.format_timestamp_date(after)),
             _ => "schedule unchanged".to_string(),
         };
         let prefix = if args.dry_run {
@@ -247,12 +281,62 @@ pub fn apply_loops(ctx: &Context<'_>, args: LoopApplyArgs) -> Result<()> {
         );
     }
 
+    print_summary(&report);
+
+    Ok(())
+}
+
+fn print_summary(report: &LoopApplyReport) {
     println!(
-        "matched {} | updated {} | scheduled {} | skipped {}",
-        report.matched, report.updated, report.scheduled, report.skipped
+        "matched {} | updated {} | scheduled {} | skipped {} (archived {}, kept existing cadence {}, no loop {}, unchanged {})",
+        report.matched,
+        report.updated,
+        report.scheduled,
+        report.skipped,
+        report.skipped_archived,
+        report.skipped_existing,
+        report.skipped_no_loop,
+        report.skipped_unchanged
     );
+}
 
-    Ok(())
+/// Narrows the policy to the rules for `only_tag`, dropping the default
+/// cadence so untagged contacts are left alone. Every tag must have a rule.
+fn restricted_policy(policy: &LoopPolicy, only_tag: &[String]) -> Result<Option<LoopPolicy>> {
+    if only_tag.is_empty() {
+        return Ok(None);
+    }
+    let mut tags = Vec::with_capacity(only_tag.len());
+    for raw in only_tag {
+        let tag = TagName::new(raw)?;
+        if !policy.rules.iter().any(|rule| rule.tag == tag) {
+            let configured = policy
+                .rules
+                .iter()
+                .map(|rule| rule.tag.as_str())
+                .collect::<Vec<_>>();
+            let configured = if configured.is_empty() {
+                "none".to_string()
+            } else {
+                configured.join(", ")
+            };
+            return Err(invalid_input(format!(
+                "no loop rule for tag {}; configured loop tags: {configured}",
+                tag.as_str()
+            )));
+        }
+        tags.push(tag);
+    }
+    Ok(Some(LoopPolicy {
+        default_cadence_days: None,
+        strategy: policy.strategy,
+        rules: policy
+            .rules
+            .iter()
+            .filter(|rule| tags.contains(&rule.tag))
+            .cloned()
+            .collect(),
+    }))
 }
 
 pub(crate) fn apply_loops_for_contact_with_repos(
@@ -260,6 +344,7 @@ pub(crate) fn apply_loops_for_contact_with_repos(
     tags: &TagsRepo<'_>,
     interactions: &InteractionsRepo<'_>,
     config: &AppConfig,
+    now_utc: i64,
     contact_id: ContactId,
 ) -> Result<()> {
     if !loops_configured(config) {
@@ -280,7 +365,8 @@ pub(crate) fn apply_loops_for_contact_with_repos(
         .map(|tag| tag.name.as_str().to_string())
         .collect::<Vec<_>>();
 
-    let desired = match policy.resolve_cadence(tags.iter().map(|tag| tag.as_str())) {
+    let priority = contacts.priority(contact.id)?;
+    let desired = match policy.resolve_cadence(tags.iter().map(|tag| tag.as_str()), priority) {
         Some(value) => value,
         None => return Ok(()),
     };
@@ -302,10 +388,14 @@ pub(crate) fn apply_loops_for_contact_with_repos(
             } else {
                 HashMap::new()
             };
-            if let Some(anchor_ts) =
-                resolve_anchor(&contact, config.loops.anchor, now_utc(), &latest)
+            if let Some(anchor_ts) = resolve_anchor(&contact, config.loops.anchor, now_utc, &latest)
             {
-                next_touchpoint_after = Some(schedule_next(anchor_ts, cadence_days)?);
+                let scheduled = schedule_next(anchor_ts, cadence_days)?;
+                next_touchpoint_after = Some(jitter_touchpoint(
+                    scheduled,
+                    config.loops.jitter_days,
+                    &contact.id,
+                )?);
                 scheduled_now = true;
             }
         }
@@ -322,11 +412,22 @@ pub(crate) fn apply_loops_for_contact_with_repos(
     if scheduled_now {
         update.next_touchpoint_at = Some(next_touchpoint_after);
     }
-    contacts.update(now_utc(), contact.id, update)?;
+    contacts.update(now_utc, contact.id, update)?;
 
     Ok(())
 }
 
+/// The `--jitter` value, falling back to `loops.jitter_days`.
+pub(crate) fn resolve_jitter(flag: Option<i32>, config: &AppConfig) -> Result<i32> {
+    let jitter_days = flag.unwrap_or(config.loops.jitter_days);
+    if !(0..=MAX_JITTER_DAYS).contains(&jitter_days) {
+        return Err(invalid_input(format!(
+            "--jitter must be between 0 and {MAX_JITTER_DAYS}"
+        )));
+    }
+    Ok(jitter_days)
+}
+
 pub(crate) fn loops_configured(config: &AppConfig) -> bool {
     let policy = &config.loops.policy;
     !(policy.rules.is_empty() && policy.default_cadence_days.is_none())
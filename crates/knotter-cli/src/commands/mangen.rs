@@ -0,0 +1,57 @@
+use anyhow::{Context as _, Result};
+use clap::CommandFactory;
+use clap_mangen::Man;
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::commands::print_json;
+
+#[derive(Debug, clap::Args)]
+pub struct MangenArgs {
+    /// Directory for `knotter.1` and one page per subcommand (created if missing)
+    #[arg(long, value_name = "DIR")]
+    pub out: PathBuf,
+}
+
+#[derive(Debug, Serialize)]
+struct MangenReport {
+    out: String,
+    pages: Vec<String>,
+}
+
+/// Writes roff man pages for `knotter` and every visible subcommand, named
+/// after the command path (`knotter-import-email.1`).
+pub fn generate(args: MangenArgs, json: bool) -> Result<()> {
+    fs::create_dir_all(&args.out)
+        .with_context(|| format!("create man page directory {}", args.out.display()))?;
+
+    let mut cmd = crate::Cli::command().disable_help_subcommand(true);
+    cmd.build();
+    let mut pages = Vec::new();
+    write_pages(cmd, &args.out, &mut pages)
+        .with_context(|| format!("write man pages to {}", args.out.display()))?;
+    pages.sort();
+
+    let report = MangenReport {
+        out: args.out.display().to_string(),
+        pages: pages
+            .iter()
+            .filter_map(|page| page.file_name())
+            .map(|name| name.to_string_lossy().into_owned())
+            .collect(),
+    };
+    if json {
+        return print_json(&report);
+    }
+    println!("wrote {} man page(s) to {}", report.pages.len(), report.out);
+    Ok(())
+}
+
+fn write_pages(cmd: clap::Command, out: &Path, pages: &mut Vec<PathBuf>) -> std::io::Result<()> {
+    for sub in cmd.get_subcommands().filter(|sub| !sub.is_hide_set()) {
+        write_pages(sub.clone(), out, pages)?;
+    }
+    pages.push(Man::new(cmd).generate_to(out)?);
+    Ok(())
+}
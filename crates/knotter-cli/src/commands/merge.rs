@@ -1,16 +1,23 @@
-use crate::commands::{print_json, Context};
+use crate::commands::trash::{age_cutoff, parse_age_days};
+use crate::commands::{print_json, resolve_contact_arg, Context};
 use crate::error::{invalid_input, not_found};
 use anyhow::Result;
-use clap::{ArgAction, Args, Subcommand, ValueEnum};
+use clap::{ArgAction, ArgGroup, Args, Subcommand, ValueEnum};
 use knotter_core::domain::{Contact, ContactId, MergeCandidateId, MergeCandidateReason};
+use knotter_core::time::Clock;
+use knotter_store::query::ListPage;
 use knotter_store::repo::{
-    ContactMergeOptions, MergeArchivedPreference, MergeCandidate, MergeCandidateStatus,
-    MergePreference, MergeTouchpointPreference,
+    ContactMergeOptions, ContactsRepo, MergeArchivedPreference, MergeCadencePreference,
+    MergeCandidate, MergeCandidateQuery, MergeCandidateStatus, MergePreference, MergePruneAction,
+    MergeTouchpointPreference,
 };
 use serde::Serialize;
+use std::collections::HashMap;
 use std::str::FromStr;
 
+mod import_decisions;
 mod scan_same_name;
+pub use import_decisions::{import_merges, MergeImportArgs};
 pub use scan_same_name::{scan_same_name, MergeScanSameNameArgs};
 
 #[derive(Debug, Subcommand)]
@@ -22,17 +29,65 @@ pub enum MergeCommand {
     Dismiss(MergeDismissArgs),
     Contacts(MergeContactsArgs),
     ScanSameName(MergeScanSameNameArgs),
+    /// Dismiss or delete open candidates older than an age
+    Prune(MergePruneArgs),
+    /// Replay open and dismissed merge decisions from another database's JSON export
+    Import(MergeImportArgs),
 }
 
 #[derive(Debug, Args)]
 pub struct MergeListArgs {
     #[arg(long, value_enum)]
     pub status: Option<MergeStatusArg>,
+    #[arg(long, value_enum, action = ArgAction::Append)]
+    pub reason: Vec<MergeReasonArg>,
+    #[arg(long)]
+    pub source: Option<String>,
+    #[arg(
+        long,
+        value_name = "AGE",
+        value_parser = parse_age_days,
+        help = "Only candidates created at least this long ago (e.g. 90d)"
+    )]
+    pub older_than: Option<i64>,
+    #[arg(long)]
+    pub limit: Option<usize>,
+    #[arg(long, default_value_t = 0)]
+    pub offset: usize,
+}
+
+#[derive(Debug, Args)]
+#[command(group(ArgGroup::new("prune_action").args(["dismiss", "delete"])))]
+pub struct MergePruneArgs {
+    #[arg(
+        long,
+        value_name = "AGE",
+        value_parser = parse_age_days,
+        help = "Prune open candidates created at least this long ago (e.g. 90d)"
+    )]
+    pub older_than: i64,
+    /// Mark matching candidates dismissed (the default)
+    #[arg(long)]
+    pub dismiss: bool,
+    /// Remove matching candidates instead of dismissing them
+    #[arg(long)]
+    pub delete: bool,
+    #[arg(long)]
+    pub dry_run: bool,
 }
 
 #[derive(Debug, Args)]
 pub struct MergeShowArgs {
     pub id: String,
+    /// Also show the contact that `merge apply` would produce, without merging
+    #[arg(long)]
+    pub preview: bool,
+    #[arg(long, value_enum, requires = "preview")]
+    pub prefer: Option<MergePreferArg>,
+    #[arg(long, value_enum, requires = "preview")]
+    pub touchpoint: Option<MergeTouchpointArg>,
+    #[arg(long, value_enum, requires = "preview")]
+    pub archived: Option<MergeArchivedArg>,
 }
 
 #[derive(Debug, Args)]
@@ -44,6 +99,9 @@ pub struct MergeApplyArgs {
     pub touchpoint: Option<MergeTouchpointArg>,
     #[arg(long, value_enum)]
     pub archived: Option<MergeArchivedArg>,
+    /// Print the merged contact without merging
+    #[arg(long)]
+    pub dry_run: bool,
 }
 
 #[derive(Debug, Args)]
@@ -62,6 +120,17 @@ pub struct MergeApplyAllArgs {
     pub include_unsafe: bool,
     #[arg(long)]
     pub limit: Option<usize>,
+    #[arg(
+        long,
+        value_name = "N",
+        help = "Allow applying up to N candidates without --yes; larger batches still require --yes"
+    )]
+    pub max: Option<usize>,
+    #[arg(
+        long,
+        help = "Apply candidates even if a contact was modified after the candidate was created"
+    )]
+    pub force: bool,
     #[arg(long)]
     pub dry_run: bool,
     #[arg(long, help = "Skip confirmation for bulk apply")]
@@ -75,12 +144,17 @@ pub struct MergeDismissArgs {
 
 #[derive(Debug, Args)]
 pub struct MergeContactsArgs {
+    /// Contact to keep: an id, or a name, alias, email, or @handle
     pub primary_id: String,
+    /// Contact merged into it, given the same way
     pub secondary_id: String,
     #[arg(long, value_enum)]
     pub prefer: Option<MergePreferArg>,
     #[arg(long, value_enum)]
     pub touchpoint: Option<MergeTouchpointArg>,
+    /// Which cadence the merged contact keeps (default: follows --prefer)
+    #[arg(long, value_enum)]
+    pub cadence: Option<MergeCadenceArg>,
     #[arg(long, value_enum)]
     pub archived: Option<MergeArchivedArg>,
 }
@@ -106,6 +180,15 @@ pub enum MergeTouchpointArg {
     Secondary,
     Earliest,
     Latest,
+    /// Keep the touchpoint of the contact whose cadence is kept
+    Cadence,
+}
+
+#[derive(Debug, Clone, ValueEnum)]
+pub enum MergeCadenceArg {
+    Preferred,
+    Shortest,
+    Longest,
 }
 
 #[derive(Debug, Clone, ValueEnum)]
@@ -150,6 +233,42 @@ struct ContactSummaryDto {
     updated_at: i64,
 }
 
+#[derive(Debug, Serialize)]
+struct MergeShowDto {
+    #[serde(flatten)]
+    candidate: MergeCandidateDto,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    preview: Option<MergePreviewDto>,
+}
+
+#[derive(Debug, Serialize)]
+struct MergePreviewDto {
+    primary_id: String,
+    secondary_id: String,
+    contact: Contact,
+    emails: Vec<String>,
+    tags: Vec<String>,
+    new_aliases: Vec<String>,
+    interactions_moved: usize,
+    dates_moved: usize,
+}
+
+#[derive(Debug, Serialize)]
+struct MergeApplyDryRunDto {
+    dry_run: bool,
+    #[serde(flatten)]
+    preview: MergePreviewDto,
+}
+
+#[derive(Debug, Serialize)]
+struct MergePruneReport {
+    action: &'static str,
+    older_than_days: i64,
+    created_before: i64,
+    pruned: usize,
+    dry_run: bool,
+}
+
 #[derive(Debug, Serialize)]
 struct MergeApplyAllReport {
     considered: usize,
@@ -174,8 +293,26 @@ struct MergeApplyAllResult {
 }
 
 pub fn list_merges(ctx: &Context<'_>, args: MergeListArgs) -> Result<()> {
-    let status = args.status.map(status_from_arg);
-    let candidates = ctx.store.merge_candidates().list(status)?;
+    let now = ctx.clock.now_utc();
+    let paged = args.limit.is_some() || args.offset > 0;
+    let query = MergeCandidateQuery {
+        status: args.status.map(status_from_arg),
+        reasons: args
+            .reason
+            .iter()
+            .map(|reason| reason.as_reason().as_str().to_string())
+            .collect(),
+        source: args.source,
+        created_before: args
+            .older_than
+            .map(|days| age_cutoff(now, days))
+            .transpose()?,
+        page: paged.then(|| ListPage {
+            limit: args.limit.unwrap_or(usize::MAX),
+            offset: args.offset,
+        }),
+    };
+    let candidates = ctx.store.merge_candidates().list(&query)?;
     if ctx.json {
         let dtos = build_candidate_dtos(ctx, &candidates)?;
         return print_json(&dtos);
@@ -201,6 +338,59 @@ pub fn list_merges(ctx: &Context<'_>, args: MergeListArgs) -> Result<()> {
                 .unwrap_or_default()
         );
     }
+    if paged {
+        let total = ctx.store.merge_candidates().count(&query)?;
+        println!(
+            "showing {}-{} of {}",
+            args.offset + 1,
+            args.offset + candidates.len(),
+            total
+        );
+    }
+    Ok(())
+}
+
+pub fn prune_merges(ctx: &Context<'_>, args: MergePruneArgs) -> Result<()> {
+    let now = ctx.clock.now_utc();
+    let created_before = age_cutoff(now, args.older_than)?;
+    let action = if args.delete {
+        MergePruneAction::Delete
+    } else {
+        MergePruneAction::Dismiss
+    };
+    let pruned = if args.dry_run {
+        ctx.store.merge_candidates().count(&MergeCandidateQuery {
+            created_before: Some(created_before),
+            ..MergeCandidateQuery::status(MergeCandidateStatus::Open)
+        })?
+    } else {
+        ctx.store
+            .merge_candidates()
+            .prune_open(now, created_before, action)?
+    };
+    let report = MergePruneReport {
+        action: match action {
+            MergePruneAction::Dismiss => "dismiss",
+            MergePruneAction::Delete => "delete",
+        },
+        older_than_days: args.older_than,
+        created_before,
+        pruned,
+        dry_run: args.dry_run,
+    };
+    if ctx.json {
+        return print_json(&report);
+    }
+    let verb = match (action, args.dry_run) {
+        (MergePruneAction::Dismiss, false) => "Dismissed",
+        (MergePruneAction::Dismiss, true) => "Would dismiss",
+        (MergePruneAction::Delete, false) => "Deleted",
+        (MergePruneAction::Delete, true) => "Would delete",
+    };
+    println!(
+        "{verb} {pruned} open merge candidate(s) created before {}",
+        ctx.clock.format_timestamp_datetime(created_before)
+    );
     Ok(())
 }
 
@@ -211,18 +401,31 @@ pub fn show_merge(ctx: &Context<'_>, args: MergeShowArgs) -> Result<()> {
         .merge_candidates()
         .get(id)?
         .ok_or_else(|| not_found("merge candidate not found"))?;
+    let preview = if args.preview {
+        let options = build_merge_options_for_apply(args.touchpoint, args.archived)?;
+        let (primary_id, secondary_id) = select_primary_secondary(&candidate, args.prefer)?;
+        Some(preview_dto(ctx, primary_id, secondary_id, options)?)
+    } else {
+        None
+    };
     let dto = build_candidate_dto(ctx, &candidate)?;
     if ctx.json {
-        return print_json(&dto);
+        return print_json(&MergeShowDto {
+            candidate: dto,
+            preview,
+        });
     }
     print_candidate_human(&dto);
+    if let Some(preview) = &preview {
+        print_preview_human(preview, &ctx.clock);
+    }
     Ok(())
 }
 
 pub fn apply_merge(ctx: &Context<'_>, args: MergeApplyArgs) -> Result<()> {
     let id = parse_merge_candidate_id(&args.id)?;
     let options = build_merge_options_for_apply(args.touchpoint, args.archived)?;
-    let now = crate::util::now_utc();
+    let now = ctx.clock.now_utc();
     let tx = ctx.store.connection().unchecked_transaction()?;
     let candidate = knotter_store::repo::MergeCandidatesRepo::new(&tx)
         .get(id)?
@@ -233,6 +436,18 @@ pub fn apply_merge(ctx: &Context<'_>, args: MergeApplyArgs) -> Result<()> {
     }
 
     let (primary_id, secondary_id) = select_primary_secondary(&candidate, args.prefer)?;
+    if args.dry_run {
+        drop(tx);
+        let preview = preview_dto(ctx, primary_id, secondary_id, options)?;
+        if ctx.json {
+            return print_json(&MergeApplyDryRunDto {
+                dry_run: true,
+                preview,
+            });
+        }
+        print_preview_human(&preview, &ctx.clock);
+        return Ok(());
+    }
     let merged = knotter_store::repo::ContactsRepo::new(&tx).merge_contacts(
         now,
         primary_id,
@@ -303,9 +518,19 @@ pub fn apply_all_merges(ctx: &Context<'_>, args: MergeApplyAllArgs) -> Result<()
     }
 
     if !args.dry_run && !args.yes {
-        return Err(invalid_input(
-            "merge apply-all requires --yes unless --dry-run is set",
-        ));
+        match args.max {
+            Some(max) if selected > max => {
+                return Err(invalid_input(format!(
+                    "merge apply-all would apply {selected} candidates (more than --max {max}); pass --yes to confirm"
+                )));
+            }
+            Some(_) => {}
+            None => {
+                return Err(invalid_input(
+                    "merge apply-all requires --yes unless --dry-run or --max is set",
+                ));
+            }
+        }
     }
 
     let options = build_merge_options_for_apply(args.touchpoint, args.archived)?;
@@ -319,8 +544,24 @@ pub fn apply_all_merges(ctx: &Context<'_>, args: MergeApplyAllArgs) -> Result<()
         results: Vec::new(),
     };
 
+    let updated_before = contacts_updated_at(&ctx.store.contacts(), &candidates)?;
     if args.dry_run {
         for candidate in candidates {
+            if !args.force && contacts_modified_since(&updated_before, &candidate) {
+                warn_stale_candidate(&candidate);
+                report.skipped += 1;
+                report.results.push(MergeApplyAllResult {
+                    id: candidate.id.to_string(),
+                    status: "skipped".to_string(),
+                    reason: candidate.reason,
+                    source: candidate.source,
+                    primary_id: None,
+                    secondary_id: None,
+                    merged_contact_id: None,
+                    error: Some(STALE_CANDIDATE_ERROR.to_string()),
+                });
+                continue;
+            }
             match select_primary_secondary(&candidate, args.prefer.clone()) {
                 Ok((primary_id, secondary_id)) => {
                     report.results.push(MergeApplyAllResult {
@@ -354,18 +595,21 @@ pub fn apply_all_merges(ctx: &Context<'_>, args: MergeApplyAllArgs) -> Result<()
             return print_json(&report);
         }
         println!(
-            "Dry-run: {} candidate(s) selected ({} considered).",
-            report.selected, report.considered
+            "Dry-run: {} candidate(s) selected ({} considered); {} would be skipped.",
+            report.selected, report.considered, report.skipped
         );
         for result in &report.results {
             let primary = result.primary_id.as_deref().unwrap_or("?");
             let secondary = result.secondary_id.as_deref().unwrap_or("?");
-            println!("{}  {} -> {}", result.id, secondary, primary);
+            println!(
+                "{}  {}  {} -> {}",
+                result.id, result.status, secondary, primary
+            );
         }
         return Ok(());
     }
 
-    let now = crate::util::now_utc();
+    let now = ctx.clock.now_utc();
     for candidate in candidates {
         let tx = ctx.store.connection().unchecked_transaction()?;
         let current = knotter_store::repo::MergeCandidatesRepo::new(&tx).get(candidate.id)?;
@@ -414,6 +658,22 @@ pub fn apply_all_merges(ctx: &Context<'_>, args: MergeApplyAllArgs) -> Result<()
             continue;
         }
 
+        if !args.force && contacts_modified_since(&updated_before, &current) {
+            warn_stale_candidate(&current);
+            report.skipped += 1;
+            report.results.push(MergeApplyAllResult {
+                id: current.id.to_string(),
+                status: "skipped".to_string(),
+                reason: current.reason,
+                source: current.source,
+                primary_id: None,
+                secondary_id: None,
+                merged_contact_id: None,
+                error: Some(STALE_CANDIDATE_ERROR.to_string()),
+            });
+            continue;
+        }
+
         let (primary_id, secondary_id) =
             match select_primary_secondary(&current, args.prefer.clone()) {
                 Ok(value) => value,
@@ -488,6 +748,46 @@ pub fn apply_all_merges(ctx: &Context<'_>, args: MergeApplyAllArgs) -> Result<()
     Ok(())
 }
 
+const STALE_CANDIDATE_ERROR: &str = "contact modified since merge candidate was created";
+
+/// `updated_at` of every contact in `candidates`, read before any of them is
+/// applied: each merge bumps its primary, which must not make later
+/// candidates sharing that contact look stale.
+fn contacts_updated_at(
+    contacts: &ContactsRepo<'_>,
+    candidates: &[MergeCandidate],
+) -> Result<HashMap<ContactId, i64>> {
+    let mut updated_at = HashMap::new();
+    for candidate in candidates {
+        for id in [candidate.contact_a_id, candidate.contact_b_id] {
+            if updated_at.contains_key(&id) {
+                continue;
+            }
+            if let Some(contact) = contacts.get(id)? {
+                updated_at.insert(id, contact.updated_at);
+            }
+        }
+    }
+    Ok(updated_at)
+}
+
+fn contacts_modified_since(
+    updated_before: &HashMap<ContactId, i64>,
+    candidate: &MergeCandidate,
+) -> bool {
+    [candidate.contact_a_id, candidate.contact_b_id]
+        .iter()
+        .filter_map(|id| updated_before.get(id))
+        .any(|updated_at| *updated_at > candidate.created_at)
+}
+
+fn warn_stale_candidate(candidate: &MergeCandidate) {
+    eprintln!(
+        "warning: skipping merge candidate {}: {} (use --force to apply anyway)",
+        candidate.id, STALE_CANDIDATE_ERROR
+    );
+}
+
 pub fn dismiss_merge(ctx: &Context<'_>, args: MergeDismissArgs) -> Result<()> {
     let id = parse_merge_candidate_id(&args.id)?;
     let candidate = ctx
@@ -501,7 +801,7 @@ pub fn dismiss_merge(ctx: &Context<'_>, args: MergeDismissArgs) -> Result<()> {
     let candidate = ctx
         .store
         .merge_candidates()
-        .dismiss(crate::util::now_utc(), id)?;
+        .dismiss(ctx.clock.now_utc(), id)?;
     if ctx.json {
         return print_json(&candidate_to_dto(ctx, candidate)?);
     }
@@ -510,11 +810,11 @@ pub fn dismiss_merge(ctx: &Context<'_>, args: MergeDismissArgs) -> Result<()> {
 }
 
 pub fn merge_contacts(ctx: &Context<'_>, args: MergeContactsArgs) -> Result<()> {
-    let primary_id = parse_contact_id(&args.primary_id)?;
-    let secondary_id = parse_contact_id(&args.secondary_id)?;
-    let options = build_merge_options(args.prefer, args.touchpoint, args.archived)?;
+    let primary_id = resolve_contact_arg(ctx, &args.primary_id)?;
+    let secondary_id = resolve_contact_arg(ctx, &args.secondary_id)?;
+    let options = build_merge_options(args.prefer, args.touchpoint, args.cadence, args.archived)?;
     let merged = ctx.store.contacts().merge_contacts(
-        crate::util::now_utc(),
+        ctx.clock.now_utc(),
         primary_id,
         secondary_id,
         options,
@@ -609,6 +909,7 @@ fn status_from_arg(status: MergeStatusArg) -> MergeCandidateStatus {
 fn build_merge_options(
     prefer: Option<MergePreferArg>,
     touchpoint: Option<MergeTouchpointArg>,
+    cadence: Option<MergeCadenceArg>,
     archived: Option<MergeArchivedArg>,
 ) -> Result<ContactMergeOptions> {
     let mut options = ContactMergeOptions::default();
@@ -628,6 +929,14 @@ fn build_merge_options(
             MergeTouchpointArg::Secondary => MergeTouchpointPreference::Secondary,
             MergeTouchpointArg::Earliest => MergeTouchpointPreference::Earliest,
             MergeTouchpointArg::Latest => MergeTouchpointPreference::Latest,
+            MergeTouchpointArg::Cadence => MergeTouchpointPreference::Cadence,
+        };
+    }
+    if let Some(cadence) = cadence {
+        options.cadence = match cadence {
+            MergeCadenceArg::Preferred => MergeCadencePreference::Preferred,
+            MergeCadenceArg::Shortest => MergeCadencePreference::Shortest,
+            MergeCadenceArg::Longest => MergeCadencePreference::Longest,
         };
     }
     if let Some(archived) = archived {
@@ -651,6 +960,7 @@ fn build_merge_options_for_apply(
             MergeTouchpointArg::Secondary => MergeTouchpointPreference::Secondary,
             MergeTouchpointArg::Earliest => MergeTouchpointPreference::Earliest,
             MergeTouchpointArg::Latest => MergeTouchpointPreference::Latest,
+            MergeTouchpointArg::Cadence => MergeTouchpointPreference::Cadence,
         };
     }
     if let Some(archived) = archived {
@@ -713,8 +1023,74 @@ impl MergeReasonArg {
     }
 }
 
-fn parse_contact_id(value: &str) -> Result<ContactId> {
-    ContactId::from_str(value).map_err(|_| invalid_input("invalid contact id"))
+fn preview_dto(
+    ctx: &Context<'_>,
+    primary_id: ContactId,
+    secondary_id: ContactId,
+    options: ContactMergeOptions,
+) -> Result<MergePreviewDto> {
+    let now = ctx.clock.now_utc();
+    let preview = ctx
+        .store
+        .contacts()
+        .preview_merge(now, primary_id, secondary_id, options)?;
+    Ok(MergePreviewDto {
+        primary_id: primary_id.to_string(),
+        secondary_id: secondary_id.to_string(),
+        contact: preview.contact,
+        emails: preview.emails,
+        tags: preview.tags,
+        new_aliases: preview.new_aliases,
+        interactions_moved: preview.interactions_moved,
+        dates_moved: preview.dates_moved,
+    })
+}
+
+fn print_preview_human(preview: &MergePreviewDto, clock: &Clock) {
+    let or_dash = |value: Option<&str>| value.unwrap_or("-").to_string();
+    let contact = &preview.contact;
+    let joined = |values: &[String]| {
+        if values.is_empty() {
+            "-".to_string()
+        } else {
+            values.join(", ")
+        }
+    };
+    println!(
+        "preview: merge {} into {}",
+        preview.secondary_id, preview.primary_id
+    );
+    println!("  name: {}", contact.display_name);
+    println!("  email: {}", or_dash(contact.email.as_deref()));
+    println!("  emails: {}", joined(&preview.emails));
+    println!("  phone: {}", or_dash(contact.phone.as_deref()));
+    println!("  handle: {}", or_dash(contact.handle.as_deref()));
+    println!("  timezone: {}", or_dash(contact.timezone.as_deref()));
+    println!(
+        "  cadence_days: {}",
+        contact
+            .cadence_days
+            .map(|days| days.to_string())
+            .unwrap_or_else(|| "-".to_string())
+    );
+    println!(
+        "  next_touchpoint_at: {}",
+        contact
+            .next_touchpoint_at
+            .map(|ts| clock.format_timestamp_datetime(ts))
+            .unwrap_or_else(|| "-".to_string())
+    );
+    println!(
+        "  archived_at: {}",
+        contact
+            .archived_at
+            .map(|ts| clock.format_timestamp_datetime(ts))
+            .unwrap_or_else(|| "-".to_string())
+    );
+    println!("  tags: {}", joined(&preview.tags));
+    println!("  new aliases: {}", joined(&preview.new_aliases));
+    println!("  interactions moved: {}", preview.interactions_moved);
+    println!("  dates moved: {}", preview.dates_moved);
 }
 
 fn print_candidate_human(dto: &MergeCandidateDto) {
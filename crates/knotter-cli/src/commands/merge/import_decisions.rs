@@ -0,0 +1,149 @@
+use crate::commands::{print_json, Context};
+use crate::error::invalid_input;
+use anyhow::{Context as _, Result};
+use clap::Args;
+use knotter_core::domain::{normalize_email, ContactId};
+use knotter_core::dto::ExportMergeCandidateDto;
+use knotter_core::export_format::parse_export_snapshot;
+use knotter_store::repo::{
+    ContactsRepo, MergeCandidateCreate, MergeCandidateStatus, MergeCandidatesRepo,
+    MergeDecisionOutcome,
+};
+use serde::Serialize;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+#[derive(Debug, Args)]
+pub struct MergeImportArgs {
+    /// JSON snapshot written by `knotter export json --include-merges`
+    pub file: PathBuf,
+    #[arg(long)]
+    pub dry_run: bool,
+}
+
+#[derive(Debug, Default, Serialize)]
+struct MergeImportReport {
+    dry_run: bool,
+    created: usize,
+    dismissed: usize,
+    unchanged: usize,
+    skipped_merged: usize,
+    warnings: Vec<String>,
+}
+
+pub fn import_merges(ctx: &Context<'_>, args: MergeImportArgs) -> Result<()> {
+    let raw = std::fs::read_to_string(&args.file)
+        .with_context(|| format!("read {}", args.file.display()))?;
+    let snapshot = parse_export_snapshot(&raw)?;
+    let Some(candidates) = snapshot.merge_candidates else {
+        return Err(invalid_input(
+            "export has no merge candidates; re-export with `knotter export json --include-merges`",
+        ));
+    };
+
+    let now = ctx.clock.now_utc();
+    let mut report = MergeImportReport {
+        dry_run: args.dry_run,
+        ..Default::default()
+    };
+    let tx = ctx.store.connection().unchecked_transaction()?;
+    {
+        let contacts = ContactsRepo::new(&tx);
+        let merges = MergeCandidatesRepo::new(&tx);
+        for candidate in &candidates {
+            let status = MergeCandidateStatus::from_str(&candidate.status)?;
+            if status == MergeCandidateStatus::Merged {
+                report.skipped_merged += 1;
+                continue;
+            }
+            let pair = match match_pair(&contacts, candidate) {
+                Ok(pair) => pair,
+                Err(warning) => {
+                    report.warnings.push(warning);
+                    continue;
+                }
+            };
+            let preferred = candidate.preferred_contact_id.and_then(|id| {
+                if id == candidate.contact_a_id {
+                    Some(pair.0)
+                } else if id == candidate.contact_b_id {
+                    Some(pair.1)
+                } else {
+                    None
+                }
+            });
+            let outcome = merges.apply_decision(
+                now,
+                pair.0,
+                pair.1,
+                status,
+                MergeCandidateCreate {
+                    reason: candidate.reason.clone(),
+                    source: candidate.source.clone(),
+                    preferred_contact_id: preferred,
+                },
+            )?;
+            match outcome {
+                MergeDecisionOutcome::Created => report.created += 1,
+                MergeDecisionOutcome::Dismissed => report.dismissed += 1,
+                MergeDecisionOutcome::Unchanged => report.unchanged += 1,
+            }
+        }
+    }
+    if !args.dry_run {
+        tx.commit()?;
+    }
+
+    if ctx.json {
+        return print_json(&report);
+    }
+    for warning in &report.warnings {
+        eprintln!("warning: {warning}");
+    }
+    println!(
+        "merge import: created {} | dismissed {} | unchanged {} | skipped merged {} | unmatched {}",
+        report.created,
+        report.dismissed,
+        report.unchanged,
+        report.skipped_merged,
+        report.warnings.len()
+    );
+    if args.dry_run {
+        println!("Dry run: no changes were applied.");
+    }
+    Ok(())
+}
+
+/// Resolves both sides of an exported candidate to local contacts by primary email.
+fn match_pair(
+    contacts: &ContactsRepo<'_>,
+    candidate: &ExportMergeCandidateDto,
+) -> std::result::Result<(ContactId, ContactId), String> {
+    let resolve = |id: ContactId, email: Option<&str>| -> std::result::Result<ContactId, String> {
+        let Some(email) = email.and_then(normalize_email) else {
+            return Err(format!(
+                "merge candidate {}: contact {id} has no primary email to match on",
+                candidate.id
+            ));
+        };
+        let matched = contacts
+            .list_by_email(&email)
+            .map_err(|err| format!("merge candidate {}: {err}", candidate.id))?;
+        matched.first().map(|contact| contact.id).ok_or_else(|| {
+            format!(
+                "merge candidate {}: no local contact with email {email}",
+                candidate.id
+            )
+        })
+    };
+
+    let a = resolve(candidate.contact_a_id, candidate.contact_a_email.as_deref())?;
+    let b = resolve(candidate.contact_b_id, candidate.contact_b_email.as_deref())?;
+    if a == b {
+        return Err(format!(
+            "merge candidate {}: both contacts match local contact {a}",
+            candidate.id
+        ));
+    }
+    Ok((a, b))
+}
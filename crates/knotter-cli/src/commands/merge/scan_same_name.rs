@@ -2,7 +2,7 @@ use crate::commands::{print_json, Context};
 use crate::error::invalid_input;
 use anyhow::Result;
 use clap::Args;
-use knotter_core::domain::{Contact, ContactId};
+use knotter_core::domain::{name_match_key, Contact, ContactId};
 use knotter_store::repo::MergeCandidateCreate;
 use serde::Serialize;
 use std::collections::{HashMap, HashSet};
@@ -88,7 +88,7 @@ pub fn scan_same_name(ctx: &Context<'_>, args: MergeScanSameNameArgs) -> Result<
 
     let mut groups: HashMap<String, Vec<Contact>> = HashMap::new();
     for contact in contacts {
-        let key = normalize_display_name(&contact.display_name);
+        let key = name_match_key(&contact.display_name);
         if key.is_empty() {
             skipped_empty_name_contacts += 1;
             continue;
@@ -132,7 +132,7 @@ pub fn scan_same_name(ctx: &Context<'_>, args: MergeScanSameNameArgs) -> Result<
         results: Vec::new(),
     };
 
-    let now = crate::util::now_utc();
+    let now = ctx.clock.now_utc();
 
     if args.dry_run {
         for (normalized_name, items) in dupe_groups {
@@ -216,7 +216,7 @@ fn build_group_result_dry_run(
     open_pairs: &mut HashSet<(String, String)>,
     report: &mut MergeScanSameNameReport,
 ) -> MergeScanSameNameGroupResult {
-    items.sort_by(|a, b| a.id.to_string().cmp(&b.id.to_string()));
+    items.sort_by_key(|a| a.id.to_string());
     let preferred = choose_preferred_contact(&items);
     let display_name = items
         .iter()
@@ -268,7 +268,7 @@ fn build_group_result_apply(
     open_pairs: &mut HashSet<(String, String)>,
     report: &mut MergeScanSameNameReport,
 ) -> Result<MergeScanSameNameGroupResult> {
-    items.sort_by(|a, b| a.id.to_string().cmp(&b.id.to_string()));
+    items.sort_by_key(|a| a.id.to_string());
     let preferred = choose_preferred_contact(&items);
     let display_name = items
         .iter()
@@ -330,18 +330,6 @@ fn build_group_result_apply(
     Ok(group)
 }
 
-fn normalize_display_name(value: &str) -> String {
-    // Normalize whitespace and lowercase for grouping. (We only treat empty/whitespace as invalid.)
-    let mut out = String::new();
-    for part in value.split_whitespace() {
-        if !out.is_empty() {
-            out.push(' ');
-        }
-        out.push_str(part);
-    }
-    out.to_lowercase()
-}
-
 fn pair_key(a: &str, b: &str) -> (String, String) {
     if a <= b {
         (a.to_string(), b.to_string())
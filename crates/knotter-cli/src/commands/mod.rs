@@ -1,22 +1,50 @@
+use crate::error::{invalid_input, not_found};
 use anyhow::Result;
 use knotter_config::AppConfig;
+use knotter_core::domain::ContactId;
+use knotter_core::time::Clock;
 use knotter_store::Store;
 use serde::Serialize;
 use std::io::{self, Write};
+use std::str::FromStr;
 
+pub mod alias;
+pub mod audit;
 pub mod backup;
+pub mod cadence;
 pub mod completions;
+pub mod config;
 pub mod contacts;
 pub mod dates;
+pub mod db;
+pub mod dedupe;
+pub mod doctor;
+mod dry_run;
+pub mod email;
+pub mod enrich;
+pub mod followups;
+pub mod import_interactions;
+pub mod init;
 pub mod interactions;
 pub mod loops;
+pub mod mangen;
 pub mod merge;
+pub mod mute;
+pub mod normalize;
+pub mod rebalance;
 pub mod remind;
 mod remind_fmt;
+mod remind_interactive;
+pub mod roulette;
 pub mod schedule;
+pub mod serve;
 pub mod sync;
+pub mod sync_state;
 pub mod tags;
+pub mod templates;
+pub mod trash;
 pub mod tui;
+pub mod vacation;
 
 pub const DEFAULT_INTERACTION_LIMIT: i64 = 20;
 
@@ -24,11 +52,80 @@ pub struct Context<'a> {
     pub store: &'a Store,
     pub json: bool,
     pub config: &'a AppConfig,
+    pub clock: Clock,
 }
 
 pub fn print_json<T: Serialize>(value: &T) -> Result<()> {
-    let mut stdout = io::stdout().lock();
-    serde_json::to_writer_pretty(&mut stdout, value)?;
-    writeln!(stdout)?;
+    write_json(&mut io::stdout().lock(), value)
+}
+
+pub fn write_json<T: Serialize>(writer: &mut impl Write, value: &T) -> Result<()> {
+    serde_json::to_writer_pretty(&mut *writer, value)?;
+    writeln!(writer)?;
     Ok(())
 }
+
+/// Asks `prompt` on stderr, so `--json` output stays clean, and returns the
+/// answer read from stdin. A closed stdin is an error: non-interactive runs
+/// confirm `action` with `--yes` instead.
+pub fn read_confirmation(prompt: &str, action: &str) -> Result<String> {
+    eprint!("{prompt}");
+    io::stderr().flush()?;
+    let mut answer = String::new();
+    if io::stdin().read_line(&mut answer)? == 0 {
+        return Err(invalid_input(format!(
+            "{action} needs confirmation; pass --yes when running non-interactively"
+        )));
+    }
+    Ok(answer.trim().to_string())
+}
+
+/// A `[y/N]` question through [`read_confirmation`]; only y or yes agrees.
+pub fn confirm(prompt: &str, action: &str) -> Result<bool> {
+    let answer = read_confirmation(&format!("{prompt} [y/N] "), action)?;
+    Ok(matches!(answer.as_str(), "y" | "Y" | "yes"))
+}
+
+/// Resolves a positional contact argument: an id when it parses as one,
+/// otherwise a display name, alias, email, or handle (case-insensitive). The
+/// first kind with any match decides, so an exact name beats a nickname.
+pub fn resolve_contact_arg(ctx: &Context<'_>, raw: &str) -> Result<ContactId> {
+    let trimmed = raw.trim();
+    if trimmed.is_empty() {
+        return Err(invalid_input("contact id cannot be empty"));
+    }
+    if let Ok(id) = ContactId::from_str(trimmed) {
+        return Ok(id);
+    }
+
+    let contacts = ctx.store.contacts();
+    let mut matches = contacts.list_by_name_or_alias(trimmed)?;
+    if matches.is_empty() && trimmed.contains('@') && !trimmed.starts_with('@') {
+        matches = contacts.list_by_email(trimmed)?;
+    }
+    if matches.is_empty() {
+        let handle = trimmed.trim_start_matches('@');
+        matches = contacts.list_by_handle(handle)?;
+        for contact in contacts.list_by_handle(&format!("@{handle}"))? {
+            if !matches.iter().any(|existing| existing.id == contact.id) {
+                matches.push(contact);
+            }
+        }
+    }
+
+    match matches.as_slice() {
+        [] => Err(not_found(format!("no contact matches {trimmed:?}"))),
+        [contact] => Ok(contact.id),
+        _ => {
+            let candidates = matches
+                .iter()
+                .map(|contact| format!("{} ({})", contact.id, contact.display_name))
+                .collect::<Vec<_>>()
+                .join(", ");
+            Err(invalid_input(format!(
+                "{trimmed:?} matches {} contacts: {candidates}; pass an id instead",
+                matches.len()
+            )))
+        }
+    }
+}
@@ -0,0 +1,84 @@
+use crate::commands::{print_json, resolve_contact_arg, Context};
+use crate::error::invalid_input;
+use anyhow::Result;
+use clap::{ArgGroup, Args};
+use knotter_core::domain::ContactId;
+use serde::Serialize;
+
+#[derive(Debug, Args)]
+#[command(group(ArgGroup::new("length").required(true).args(["until", "for_"])))]
+pub struct MuteArgs {
+    /// Contact id, or a name, alias, email, or @handle
+    pub id: String,
+    #[arg(
+        long,
+        value_name = "DATE",
+        help = "Resume reminders on this local date (YYYY-MM-DD, +2w, next-mon)"
+    )]
+    pub until: Option<String>,
+    #[arg(
+        long = "for",
+        value_name = "DURATION",
+        help = "Mute for a span such as 10d, 2w, 3m, or 1y"
+    )]
+    pub for_: Option<String>,
+}
+
+#[derive(Debug, Args)]
+pub struct UnmuteArgs {
+    /// Contact id, or a name, alias, email, or @handle
+    pub id: String,
+}
+
+#[derive(Debug, Serialize)]
+struct MuteDto {
+    id: ContactId,
+    display_name: String,
+    muted_until: Option<i64>,
+}
+
+pub fn mute_contact(ctx: &Context<'_>, args: MuteArgs) -> Result<()> {
+    let id = resolve_contact_arg(ctx, &args.id)?;
+    let now = ctx.clock.now_utc();
+    let date = match (args.until.as_deref(), args.for_.as_deref()) {
+        (Some(until), _) => until.to_string(),
+        (None, Some(span)) => format!("+{}", span.trim().trim_start_matches('+')),
+        (None, None) => unreachable!("clap requires --until or --for"),
+    };
+    let (until, _, resolved) = ctx.clock.parse_local_date_time_relative(&date, None, now)?;
+    if until <= now {
+        return Err(invalid_input(format!(
+            "mute must end after today (parsed as {resolved})"
+        )));
+    }
+
+    let contact = ctx.store.contacts().mute(now, id, until)?;
+    if ctx.json {
+        return print_json(&MuteDto {
+            id: contact.id,
+            display_name: contact.display_name,
+            muted_until: Some(until),
+        });
+    }
+    println!(
+        "muted {} {} until {}",
+        contact.id,
+        contact.display_name,
+        ctx.clock.format_timestamp_date(until)
+    );
+    Ok(())
+}
+
+pub fn unmute_contact(ctx: &Context<'_>, args: UnmuteArgs) -> Result<()> {
+    let id = resolve_contact_arg(ctx, &args.id)?;
+    let contact = ctx.store.contacts().unmute(ctx.clock.now_utc(), id)?;
+    if ctx.json {
+        return print_json(&MuteDto {
+            id: contact.id,
+            display_name: contact.display_name,
+            muted_until: None,
+        });
+    }
+    println!("unmuted {} {}", contact.id, contact.display_name);
+    Ok(())
+}
@@ -0,0 +1,142 @@
+use crate::commands::{print_json, Context};
+use anyhow::Result;
+use clap::{Args, Subcommand};
+use knotter_core::domain::{canonical_phone, ContactId};
+use knotter_store::repo::{ContactField, ContactUpdate};
+use serde::Serialize;
+
+/// Provenance source recorded for phones reformatted by `normalize phones`
+/// when nothing else had claimed the field.
+const NORMALIZE_PHONES_SOURCE: &str = "normalize-phones";
+
+#[derive(Debug, Subcommand)]
+pub enum NormalizeCommand {
+    /// Rewrite stored phone numbers into their canonical form
+    Phones(NormalizePhonesArgs),
+}
+
+#[derive(Debug, Args)]
+pub struct NormalizePhonesArgs {
+    #[arg(
+        long,
+        conflicts_with = "apply",
+        help = "List the proposed values without saving them (the default)"
+    )]
+    pub dry_run: bool,
+    #[arg(long, help = "Save the canonical values")]
+    pub apply: bool,
+}
+
+#[derive(Debug, Serialize)]
+struct PhoneChangeDto {
+    id: ContactId,
+    display_name: String,
+    phone: String,
+    normalized: String,
+}
+
+#[derive(Debug, Serialize)]
+struct PhoneSkipDto {
+    id: ContactId,
+    display_name: String,
+    phone: String,
+}
+
+#[derive(Debug, Serialize)]
+struct NormalizePhonesReport {
+    dry_run: bool,
+    changes: Vec<PhoneChangeDto>,
+    unparseable: Vec<PhoneSkipDto>,
+    /// Contacts whose phone is already canonical.
+    unchanged: usize,
+}
+
+pub fn normalize_phones(ctx: &Context<'_>, args: NormalizePhonesArgs) -> Result<()> {
+    let now = ctx.clock.now_utc();
+    let dry_run = !args.apply;
+    let mut report = NormalizePhonesReport {
+        dry_run,
+        changes: Vec::new(),
+        unparseable: Vec::new(),
+        unchanged: 0,
+    };
+
+    let tx = ctx.store.connection().unchecked_transaction()?;
+    for contact in ctx.store.contacts().list_all()? {
+        let Some(phone) = contact.phone else {
+            continue;
+        };
+        let Ok(normalized) = canonical_phone(&phone) else {
+            report.unparseable.push(PhoneSkipDto {
+                id: contact.id,
+                display_name: contact.display_name,
+                phone,
+            });
+            continue;
+        };
+        if normalized == phone {
+            report.unchanged += 1;
+            continue;
+        }
+
+        if !dry_run {
+            // Reformatting keeps the number, so keep whoever last wrote it.
+            let source = ctx
+                .store
+                .field_provenance()
+                .list_for_contact(contact.id)?
+                .into_iter()
+                .find(|entry| entry.field == ContactField::Phone.as_str())
+                .map(|entry| entry.source)
+                .unwrap_or_else(|| NORMALIZE_PHONES_SOURCE.to_string());
+            ctx.store.contacts().update(
+                now,
+                contact.id,
+                ContactUpdate {
+                    phone: Some(Some(normalized.clone())),
+                    source: Some(source),
+                    ..Default::default()
+                },
+            )?;
+        }
+        report.changes.push(PhoneChangeDto {
+            id: contact.id,
+            display_name: contact.display_name,
+            phone,
+            normalized,
+        });
+    }
+    tx.commit()?;
+
+    if ctx.json {
+        return print_json(&report);
+    }
+
+    let (verb, summary) = if dry_run {
+        ("would change", "would update")
+    } else {
+        ("changed", "updated")
+    };
+    for change in &report.changes {
+        println!(
+            "{}  {}  {} {:?} -> {}",
+            change.id, change.display_name, verb, change.phone, change.normalized
+        );
+    }
+    for skipped in &report.unparseable {
+        println!(
+            "{}  {}  skipped: cannot parse {:?}",
+            skipped.id, skipped.display_name, skipped.phone
+        );
+    }
+    println!(
+        "{summary} {} | unchanged {} | unparseable {}",
+        report.changes.len(),
+        report.unchanged,
+        report.unparseable.len()
+    );
+    if dry_run {
+        println!("Dry run: no changes were applied. Pass --apply to save them.");
+    }
+    Ok(())
+}
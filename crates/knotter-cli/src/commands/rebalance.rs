@@ -0,0 +1,179 @@
+use crate::commands::{print_json, Context};
+use crate::error::invalid_input;
+use anyhow::Result;
+use clap::Args;
+use knotter_core::domain::ContactId;
+use knotter_core::filter::{ArchivedSelector, MutedSelector};
+use knotter_core::rules::cadence::MAX_CADENCE_DAYS;
+use knotter_core::rules::{rebalance_days, ScheduledDay};
+use knotter_store::query::ContactQuery;
+use knotter_store::repo::{ContactUpdate, ContactsRepo};
+use serde::Serialize;
+use std::collections::HashMap;
+
+const SECONDS_PER_DAY: i64 = 86_400;
+
+#[derive(Debug, Args)]
+#[command(after_help = "Examples:
+  knotter rebalance --dry-run
+  knotter rebalance --window 14 --max-per-day 5")]
+pub struct RebalanceArgs {
+    #[arg(
+        long,
+        value_name = "DAYS",
+        default_value_t = 30,
+        help = "Look at touchpoints from tomorrow through the next DAYS days"
+    )]
+    pub window: i64,
+    #[arg(
+        long,
+        value_name = "N",
+        default_value_t = 8,
+        help = "Most touchpoints to leave on any one day"
+    )]
+    pub max_per_day: usize,
+    #[arg(long, help = "Show the moves without saving them")]
+    pub dry_run: bool,
+}
+
+#[derive(Debug, Serialize)]
+struct RebalanceMove {
+    id: ContactId,
+    display_name: String,
+    from: i64,
+    to: i64,
+}
+
+#[derive(Debug, Serialize)]
+struct RebalanceReport {
+    window_days: i64,
+    max_per_day: usize,
+    dry_run: bool,
+    /// Touchpoints inside the window.
+    considered: usize,
+    moved: usize,
+    /// Days still over the cap because the window had no room left.
+    overfull_days: usize,
+    moves: Vec<RebalanceMove>,
+}
+
+pub fn rebalance(ctx: &Context<'_>, args: RebalanceArgs) -> Result<()> {
+    if args.window < 1 || args.window > i64::from(MAX_CADENCE_DAYS) {
+        return Err(invalid_input(format!(
+            "--window must be between 1 and {MAX_CADENCE_DAYS}"
+        )));
+    }
+    if args.max_per_day == 0 {
+        return Err(invalid_input("--max-per-day must be at least 1"));
+    }
+
+    let now = ctx.clock.now_utc();
+    let offset = ctx.clock.local_offset();
+    let offset_seconds = i64::from(offset.local_minus_utc());
+    let local_day = |timestamp: i64| (timestamp + offset_seconds).div_euclid(SECONDS_PER_DAY);
+    // Today's touchpoints are already due and stay put; moving one to an
+    // earlier hour today could put it in the past.
+    let first_day = local_day(now) + 1;
+    let last_day = first_day + args.window - 1;
+
+    let query = ContactQuery {
+        archived: Some(ArchivedSelector::Active),
+        muted: Some(MutedSelector::Unmuted),
+        ..Default::default()
+    };
+    let contacts: Vec<_> = ctx
+        .store
+        .contacts()
+        .list_contacts(&query, now, ctx.config.due_soon_days, offset)?
+        .into_iter()
+        .filter(|contact| {
+            contact
+                .next_touchpoint_at
+                .is_some_and(|at| (first_day..=last_day).contains(&local_day(at)))
+        })
+        .collect();
+
+    let scheduled: Vec<ScheduledDay> = contacts
+        .iter()
+        .map(|contact| ScheduledDay {
+            day: contact
+                .next_touchpoint_at
+                .map(local_day)
+                .unwrap_or_default(),
+            cadence_days: contact.cadence_days,
+        })
+        .collect();
+    let planned = rebalance_days(&scheduled, first_day, args.window, args.max_per_day);
+
+    let mut load: HashMap<i64, usize> = HashMap::new();
+    for item in &scheduled {
+        *load.entry(item.day).or_default() += 1;
+    }
+    let mut moves = Vec::with_capacity(planned.len());
+    for (index, target_day) in planned {
+        let contact = &contacts[index];
+        let from = contact.next_touchpoint_at.unwrap_or_default();
+        *load.entry(scheduled[index].day).or_default() -= 1;
+        *load.entry(target_day).or_default() += 1;
+        moves.push(RebalanceMove {
+            id: contact.id,
+            display_name: contact.display_name.clone(),
+            from,
+            // Whole days keep the time of day.
+            to: from + (target_day - scheduled[index].day) * SECONDS_PER_DAY,
+        });
+    }
+    moves.sort_by(|a, b| (a.from, &a.display_name).cmp(&(b.from, &b.display_name)));
+
+    if !args.dry_run && !moves.is_empty() {
+        let tx = ctx.store.connection().unchecked_transaction()?;
+        let repo = ContactsRepo::new(&tx);
+        for planned in &moves {
+            let update = ContactUpdate {
+                next_touchpoint_at: Some(Some(planned.to)),
+                ..Default::default()
+            };
+            repo.update(now, planned.id, update)?;
+        }
+        tx.commit()?;
+    }
+
+    let report = RebalanceReport {
+        window_days: args.window,
+        max_per_day: args.max_per_day,
+        dry_run: args.dry_run,
+        considered: contacts.len(),
+        moved: moves.len(),
+        overfull_days: load
+            .values()
+            .filter(|count| **count > args.max_per_day)
+            .count(),
+        moves,
+    };
+
+    if ctx.json {
+        return print_json(&report);
+    }
+    if report.moves.is_empty() && report.overfull_days == 0 {
+        println!("no days over {} touchpoints", report.max_per_day);
+    }
+    let verb = if report.dry_run {
+        "would move"
+    } else {
+        "moved"
+    };
+    for planned in &report.moves {
+        println!(
+            "{verb} {} {} ({} -> {})",
+            planned.id,
+            planned.display_name,
+            ctx.clock.format_timestamp_date(planned.from),
+            ctx.clock.format_timestamp_date(planned.to)
+        );
+    }
+    println!(
+        "considered {} | moved {} | days still over the cap {}",
+        report.considered, report.moved, report.overfull_days
+    );
+    Ok(())
+}
@@ -1,13 +1,26 @@
-use crate::commands::remind_fmt::{notification_body, print_human, RandomContactPick};
-use crate::commands::{print_json, Context};
+use crate::commands::interactions::{record_touch, TouchArgs};
+use crate::commands::remind_fmt::{
+    contact_notifications, dedupe, listed_contact_ids, notification_body, print_human,
+    sort_by_urgency, RandomContactPick,
+};
+use crate::commands::{print_json, remind_interactive, Context};
 use crate::error::invalid_input;
-use crate::notify::{Notifier, StdoutNotifier};
-use crate::util::{local_offset, now_utc};
+#[cfg(any(feature = "email-notify", test))]
+use crate::error::notify_failed;
+use crate::notify::{Notification, Notifier, StdoutNotifier};
 use anyhow::Result;
-use clap::Args;
-use knotter_config::{NotificationBackend, NotificationsEmailConfig};
-use knotter_core::dto::{ContactListItemDto, DateReminderItemDto, ReminderOutputDto};
-use knotter_core::rules::{compute_due_state, validate_soon_days};
+use chrono::NaiveTime;
+use clap::{Args, ValueEnum};
+use knotter_app::reminders::{self, ReminderOptions};
+#[cfg(feature = "email-notify")]
+use knotter_config::NotificationFallback;
+use knotter_config::{NotificationBackend, NotificationsConfig};
+use knotter_core::domain::ContactId;
+use knotter_core::dto::ReminderOutputDto;
+use knotter_core::rules::{validate_soon_days, QuietHours};
+use knotter_core::time::Clock;
+use std::collections::HashMap;
+use std::io::{self, IsTerminal};
 
 #[cfg(feature = "desktop-notify")]
 use crate::notify::DesktopNotifier;
@@ -17,11 +30,23 @@ use anyhow::Context as _;
 use tracing::warn;
 
 #[cfg(feature = "email-notify")]
-use crate::commands::remind_fmt::{email_body, email_subject};
+use crate::commands::remind_fmt::{email_body, email_html, email_subject};
 #[cfg(feature = "email-notify")]
 use crate::notify::EmailNotifier;
 
+#[cfg(feature = "webhook-notify")]
+use crate::notify::WebhookNotifier;
+
 #[derive(Debug, Args)]
+#[command(after_help = "Examples:
+  knotter remind                     overdue, today, and the next due_soon_days
+  knotter remind --soon-days 14 --json
+  knotter remind --notify            send through the configured backend
+  knotter remind --notify --force-notify  send even during quiet hours or vacation
+  knotter remind --max-per-bucket 10 list the 10 most overdue, then a count
+  knotter remind --interactive       touch, snooze, or skip each due contact
+  knotter remind --mark-contacted <id>
+  knotter remind --digest weekly --digest-only")]
 pub struct RemindArgs {
     #[arg(long)]
     pub soon_days: Option<i64>,
@@ -29,11 +54,68 @@ pub struct RemindArgs {
     pub notify: bool,
     #[arg(long, conflicts_with = "notify")]
     pub no_notify: bool,
+    #[arg(
+        long,
+        conflicts_with = "no_notify",
+        help = "Send notifications even during quiet hours or vacation mode"
+    )]
+    pub force_notify: bool,
+    #[arg(
+        long,
+        value_enum,
+        help = "Add a per-day look-ahead for the next 7 days"
+    )]
+    pub digest: Option<DigestMode>,
+    #[arg(
+        long,
+        requires = "digest",
+        help = "Only output the digest, not the due buckets"
+    )]
+    pub digest_only: bool,
+    #[arg(
+        long,
+        value_name = "N",
+        help = "Show at most N contacts per due bucket, most urgent first (overrides notifications.max_per_bucket; JSON stays complete)"
+    )]
+    pub max_per_bucket: Option<usize>,
+    #[arg(
+        long,
+        value_name = "ID",
+        help = "Record a touch for ID and move it to its next cadence date before listing reminders (repeatable)"
+    )]
+    pub mark_contacted: Vec<String>,
+    #[arg(
+        long,
+        conflicts_with_all = ["notify", "digest_only"],
+        help = "After listing, step through overdue and today's contacts to touch, snooze, or skip each"
+    )]
+    pub interactive: bool,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum DigestMode {
+    Weekly,
 }
 
 pub fn remind(ctx: &Context<'_>, args: RemindArgs) -> Result<()> {
     let soon_days = validate_soon_days(args.soon_days.unwrap_or(ctx.config.due_soon_days))?;
-    let notify_requested = if args.no_notify {
+    if args.max_per_bucket == Some(0) {
+        return Err(invalid_input("--max-per-bucket must be at least 1"));
+    }
+    let max_per_bucket = args
+        .max_per_bucket
+        .or(ctx.config.notifications.max_per_bucket);
+    if args.interactive {
+        if ctx.json {
+            return Err(invalid_input("--interactive cannot be used with --json"));
+        }
+        if !io::stdin().is_terminal() || !io::stdout().is_terminal() {
+            return Err(invalid_input("--interactive needs a terminal"));
+        }
+    }
+    // The interactive session works from the printed buckets, so nothing is
+    // sent in their place.
+    let notify_requested = if args.no_notify || args.interactive {
         false
     } else if args.notify {
         true
@@ -42,66 +124,69 @@ pub fn remind(ctx: &Context<'_>, args: RemindArgs) -> Result<()> {
     } else {
         ctx.config.notifications.enabled
     };
-    let backend = ctx.config.notifications.backend;
-    let email_config = ctx.config.notifications.email.as_ref();
-
-    let now = now_utc();
-    let offset = local_offset();
-
-    let contacts = ctx
-        .store
-        .contacts()
-        .list_due_contacts(now, soon_days, offset)?;
-    let contact_ids = contacts
-        .iter()
-        .map(|contact| contact.id)
-        .collect::<Vec<_>>();
-    let tags_by_contact = ctx.store.tags().list_names_for_contacts(&contact_ids)?;
-
-    let mut items = Vec::with_capacity(contacts.len());
-    for contact in contacts {
-        let tag_names = tags_by_contact
-            .get(&contact.id)
-            .cloned()
-            .unwrap_or_default();
-        let due_state = compute_due_state(now, contact.next_touchpoint_at, soon_days, offset)?;
-        items.push(ContactListItemDto {
-            id: contact.id,
-            display_name: contact.display_name,
-            due_state,
-            next_touchpoint_at: contact.next_touchpoint_at,
-            archived_at: contact.archived_at,
-            tags: tag_names,
-        });
+
+    for id in args.mark_contacted {
+        let touched = record_touch(
+            ctx,
+            TouchArgs {
+                id,
+                kind: None,
+                when: None,
+                note: None,
+                template: Default::default(),
+                follow_up_at: None,
+                // Same as touching from `--interactive`: the reminder is handled.
+                reschedule: true,
+                no_reschedule: false,
+                force: false,
+                dry_run: false,
+            },
+        )?;
+        if !ctx.json {
+            println!("{}", touched.summary);
+        }
     }
 
-    let mut output = ReminderOutputDto::from_items(items);
-    let dates_today = ctx
-        .store
-        .contact_dates()
-        .list_today(now, offset)?
-        .into_iter()
-        .map(|item| DateReminderItemDto {
-            contact_id: item.contact_id,
-            display_name: item.display_name,
-            kind: item.kind,
-            label: item.label,
-            month: item.month,
-            day: item.day,
-            year: item.year,
-        })
-        .collect();
-    output.dates_today = dates_today;
+    let now = ctx.clock.now_utc();
+    let vacation_until = ctx.store.settings().active_vacation_until(now)?;
+    let held_back = if notify_requested && !args.force_notify {
+        suppression_reason(
+            vacation_until,
+            ctx.config.notifications.quiet_hours,
+            ctx.clock.local_naive(now).time(),
+            &ctx.clock,
+        )
+    } else {
+        None
+    };
+    let deliver = notify_requested && held_back.is_none();
+
+    let options = ReminderOptions {
+        soon_days,
+        weekly_digest: args.digest == Some(DigestMode::Weekly),
+    };
+    let mut output = reminders::compute(ctx.store, &options, now, ctx.clock.local_offset())?;
+    sort_by_urgency(&mut output);
+    if args.digest_only {
+        output.overdue.clear();
+        output.today.clear();
+        output.soon.clear();
+        output.dates_today.clear();
+        output.dates_upcoming.clear();
+        output.follow_ups.clear();
+    }
 
-    let random_picks = if notify_requested
+    let mut random_picks = if deliver
         && output.is_empty()
         && ctx.config.notifications.random_contacts_if_no_reminders > 0
     {
+        let listed: Vec<_> = listed_contact_ids(&output).into_iter().collect();
         ctx.store
             .contacts()
             .list_random_active(
+                now,
                 ctx.config.notifications.random_contacts_if_no_reminders,
-                &[],
+                &listed,
             )?
             .into_iter()
             .map(|contact| RandomContactPick {
@@ -112,36 +197,125 @@ pub fn remind(ctx: &Context<'_>, args: RemindArgs) -> Result<()> {
     } else {
         Vec::new()
     };
+    dedupe(&mut output, &mut random_picks);
 
     if ctx.json {
         print_json(&output)?;
-    } else if !notify_requested {
-        print_human(&output, &random_picks);
+    } else {
+        if let Some(until) = vacation_until {
+            println!(
+                "Vacation mode until {}: notifications are paused.",
+                ctx.clock.format_timestamp_datetime(until)
+            );
+        }
+        if !deliver {
+            print_human(&output, &random_picks, max_per_bucket, &ctx.clock);
+        }
+    }
+    if let Some(reason) = held_back {
+        eprintln!("notifications skipped: {reason} (use --force-notify to send anyway)");
+    }
+
+    if deliver {
+        let last_interactions = if ctx.config.notifications.per_contact {
+            let ids: Vec<_> = output
+                .overdue
+                .iter()
+                .chain(&output.today)
+                .map(|item| item.id)
+                .collect();
+            ctx.store
+                .interactions()
+                .latest_occurred_at_for_contacts(&ids)?
+        } else {
+            HashMap::new()
+        };
+        notify(
+            &output,
+            &random_picks,
+            &last_interactions,
+            ctx.json,
+            max_per_bucket,
+            &ctx.config.notifications,
+            &ctx.clock,
+        )?;
     }
 
-    if notify_requested {
-        notify(&output, &random_picks, ctx.json, backend, email_config)?;
+    if args.interactive {
+        let due: Vec<_> = output
+            .overdue
+            .iter()
+            .chain(&output.today)
+            .cloned()
+            .collect();
+        if due.is_empty() {
+            return Ok(());
+        }
+        println!();
+        let summary =
+            remind_interactive::run(ctx, &due, &mut io::stdin().lock(), &mut io::stderr())?;
+        println!(
+            "touched {} | snoozed {} | skipped {}",
+            summary.touched, summary.snoozed, summary.skipped
+        );
     }
 
     Ok(())
 }
 
+/// Why notifications are held back right now, if they are: vacation mode
+/// first, then quiet hours at `local_time`.
+fn suppression_reason(
+    vacation_until: Option<i64>,
+    quiet_hours: Option<QuietHours>,
+    local_time: NaiveTime,
+    clock: &Clock,
+) -> Option<String> {
+    if let Some(until) = vacation_until {
+        return Some(format!(
+            "vacation mode until {}",
+            clock.format_timestamp_datetime(until)
+        ));
+    }
+    let quiet_hours = quiet_hours.filter(|quiet_hours| quiet_hours.contains(local_time))?;
+    Some(format!(
+        "quiet hours ({}-{})",
+        quiet_hours.start.format("%H:%M"),
+        quiet_hours.end.format("%H:%M")
+    ))
+}
+
 fn notify(
     output: &ReminderOutputDto,
     random_picks: &[RandomContactPick],
+    last_interactions: &HashMap<ContactId, i64>,
     json_mode: bool,
-    backend: NotificationBackend,
-    email_config: Option<&NotificationsEmailConfig>,
+    max_per_bucket: Option<usize>,
+    config: &NotificationsConfig,
+    clock: &Clock,
 ) -> Result<()> {
-    #[cfg(not(feature = "email-notify"))]
-    let _ = email_config;
+    let backend = config.backend;
 
     if output.is_empty() && random_picks.is_empty() {
         return Ok(());
     }
 
     let title = "knotter reminders";
-    let body = notification_body(output, random_picks, 5);
+    let body = notification_body(output, random_picks, 5, max_per_bucket);
+    // Email and webhooks always get the aggregate; per-contact only splits
+    // desktop popups and their stdout rendering.
+    let notifications = if config.per_contact {
+        contact_notifications(
+            output,
+            random_picks,
+            last_interactions,
+            config.max_per_run,
+            max_per_bucket,
+            clock,
+        )
+    } else {
+        vec![Notification::new(title, body.clone())]
+    };
 
     if backend == NotificationBackend::Stdout {
         if json_mode {
@@ -149,21 +323,39 @@ fn notify(
                 "stdout notifications are unavailable in --json mode; drop --json or use desktop backend",
             ));
         }
-        print_human(output, random_picks);
+        if config.per_contact {
+            return StdoutNotifier.send_all(&notifications);
+        }
+        print_human(output, random_picks, max_per_bucket, clock);
         return Ok(());
     }
 
     if backend == NotificationBackend::Email {
         #[cfg(feature = "email-notify")]
         {
-            let email_config = email_config.ok_or_else(|| {
+            let email_config = config.email.as_ref().ok_or_else(|| {
                 invalid_input("notifications.email config is required for email backend")
             })?;
             let subject = email_subject(output, random_picks, &email_config.subject_prefix);
-            let body = email_body(output, random_picks);
+            let mut notification = Notification::new(
+                subject,
+                email_body(output, random_picks, max_per_bucket, clock),
+            );
+            if email_config.html {
+                notification =
+                    notification.with_html(email_html(output, random_picks, max_per_bucket, clock));
+            }
             let notifier = EmailNotifier::new(email_config)?;
-            notifier.send(&subject, &body)?;
-            return Ok(());
+            return send_with_fallback(&notifier, &notification, || {
+                run_fallback(
+                    config.fallback,
+                    output,
+                    random_picks,
+                    json_mode,
+                    max_per_bucket,
+                    clock,
+                )
+            });
         }
 
         #[cfg(not(feature = "email-notify"))]
@@ -174,10 +366,42 @@ fn notify(
         }
     }
 
+    if backend == NotificationBackend::Webhook {
+        #[cfg(feature = "webhook-notify")]
+        {
+            let webhook_config = config.webhook.as_ref().ok_or_else(|| {
+                invalid_input("notifications.webhook config is required for webhook backend")
+            })?;
+            // The webhook goes to another device, so keep the local summary too.
+            if !json_mode {
+                print_human(output, random_picks, max_per_bucket, clock);
+            }
+            let random_contacts: Vec<_> = random_picks
+                .iter()
+                .map(|pick| serde_json::json!({ "id": pick.id, "display_name": pick.display_name }))
+                .collect();
+            let data = serde_json::json!({
+                "reminders": output,
+                "random_contacts": random_contacts,
+            });
+            let notifier = WebhookNotifier::new(webhook_config)?;
+            return notifier
+                .send_with_data(&Notification::new(title, body), &data)
+                .map_err(|err| anyhow::anyhow!("webhook notification failed: {err}"));
+        }
+
+        #[cfg(not(feature = "webhook-notify"))]
+        {
+            return Err(invalid_input(
+                "webhook notifications unavailable (build with webhook-notify feature)",
+            ));
+        }
+    }
+
     #[cfg(feature = "desktop-notify")]
     {
         let desktop = DesktopNotifier;
-        match desktop.send(title, &body) {
+        match desktop.send_all(&notifications) {
             Ok(()) => return Ok(()),
             Err(err) => {
                 if json_mode {
@@ -202,5 +426,133 @@ fn notify(
     }
 
     let stdout = StdoutNotifier;
-    stdout.send(title, &body)
+    stdout.send_all(&notifications)
+}
+
+/// Sends through `primary`; if that fails the reminders still reach the user
+/// through `fallback`, and the command exits with `EXIT_NOTIFY_FAILED`.
+#[cfg(any(feature = "email-notify", test))]
+fn send_with_fallback(
+    primary: &dyn Notifier,
+    notification: &Notification,
+    fallback: impl FnOnce() -> Result<()>,
+) -> Result<()> {
+    let Err(err) = primary.send(notification) else {
+        return Ok(());
+    };
+    eprintln!("warning: email notification failed: {err:#}");
+    if let Err(fallback_err) = fallback() {
+        eprintln!("warning: notification fallback failed: {fallback_err:#}");
+    }
+    Err(notify_failed(format!("email notification failed: {err}")))
+}
+
+#[cfg(feature = "email-notify")]
+fn run_fallback(
+    fallback: NotificationFallback,
+    output: &ReminderOutputDto,
+    random_picks: &[RandomContactPick],
+    json_mode: bool,
+    max_per_bucket: Option<usize>,
+    clock: &Clock,
+) -> Result<()> {
+    if fallback == NotificationFallback::None {
+        return Ok(());
+    }
+
+    #[cfg(feature = "desktop-notify")]
+    if fallback == NotificationFallback::Desktop {
+        let body = notification_body(output, random_picks, 5, max_per_bucket);
+        match DesktopNotifier.send(&Notification::new("knotter reminders", body)) {
+            Ok(()) => return Ok(()),
+            Err(err) => {
+                warn!(error = %err, "desktop fallback failed, falling back to stdout");
+            }
+        }
+    }
+
+    // In --json mode the reminders are already on stdout.
+    if !json_mode {
+        print_human(output, random_picks, max_per_bucket, clock);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::{exit_status_for, EXIT_NOTIFY_FAILED};
+    use std::cell::Cell;
+
+    struct FailingNotifier;
+
+    fn notification() -> Notification {
+        Notification::new("subject", "body")
+    }
+
+    impl Notifier for FailingNotifier {
+        fn send(&self, _notification: &Notification) -> Result<()> {
+            Err(anyhow::anyhow!("smtp unreachable"))
+        }
+    }
+
+    #[test]
+    fn failed_send_runs_fallback_and_maps_to_notify_exit_code() {
+        let fell_back = Cell::new(false);
+        let err = send_with_fallback(&FailingNotifier, &notification(), || {
+            fell_back.set(true);
+            Ok(())
+        })
+        .expect_err("send should fail");
+
+        assert!(fell_back.get());
+        assert_eq!(exit_status_for(&err), EXIT_NOTIFY_FAILED);
+        assert!(err.to_string().contains("smtp unreachable"));
+    }
+
+    #[test]
+    fn failed_fallback_keeps_notify_exit_code() {
+        let err = send_with_fallback(&FailingNotifier, &notification(), || {
+            Err(anyhow::anyhow!("no display"))
+        })
+        .expect_err("send should fail");
+
+        assert_eq!(exit_status_for(&err), EXIT_NOTIFY_FAILED);
+    }
+
+    #[test]
+    fn suppression_prefers_vacation_then_quiet_hours() {
+        let at = |hour, minute| NaiveTime::from_hms_opt(hour, minute, 0).expect("time");
+        let clock = Clock::default();
+        let quiet = Some(QuietHours {
+            start: at(22, 0),
+            end: at(8, 0),
+        });
+
+        assert_eq!(suppression_reason(None, None, at(23, 0), &clock), None);
+        assert_eq!(suppression_reason(None, quiet, at(12, 0), &clock), None);
+        assert_eq!(suppression_reason(None, quiet, at(8, 0), &clock), None);
+        assert_eq!(
+            suppression_reason(None, quiet, at(23, 30), &clock).as_deref(),
+            Some("quiet hours (22:00-08:00)")
+        );
+        assert_eq!(
+            suppression_reason(None, quiet, at(0, 15), &clock).as_deref(),
+            Some("quiet hours (22:00-08:00)")
+        );
+        let vacation = suppression_reason(Some(1_700_000_000), quiet, at(12, 0), &clock);
+        assert!(vacation.is_some_and(|reason| reason.starts_with("vacation mode until ")));
+    }
+
+    #[test]
+    fn successful_send_skips_fallback() {
+        let fell_back = Cell::new(false);
+        send_with_fallback(&StdoutNotifier, &notification(), || {
+            fell_back.set(true);
+            Ok(())
+        })
+        .expect("stdout send");
+
+        assert!(!fell_back.get());
+    }
 }
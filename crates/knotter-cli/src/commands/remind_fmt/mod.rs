@@ -1,6 +1,12 @@
-use crate::util::{format_date_parts, format_timestamp_date};
+use crate::notify::Notification;
+use crate::util::{due_state_label, format_date_parts};
+use chrono::NaiveDate;
 use knotter_core::domain::ContactId;
-use knotter_core::dto::{ContactListItemDto, DateReminderItemDto, ReminderOutputDto};
+use knotter_core::dto::{
+    ContactListItemDto, DateReminderItemDto, FollowUpItemDto, ReminderDayDto, ReminderOutputDto,
+};
+use knotter_core::time::Clock;
+use std::collections::{HashMap, HashSet};
 
 #[derive(Debug, Clone)]
 pub(crate) struct RandomContactPick {
@@ -8,97 +14,278 @@ pub(crate) struct RandomContactPick {
     pub(crate) display_name: String,
 }
 
-pub(crate) fn print_human(output: &ReminderOutputDto, random_picks: &[RandomContactPick]) {
+/// Keeps each contact in at most one due bucket (overdue, then today, then
+/// soon), marks date reminders whose contact is also due, and drops random
+/// picks already listed elsewhere. Run before formatting so counts match.
+pub(crate) fn dedupe(output: &mut ReminderOutputDto, random_picks: &mut Vec<RandomContactPick>) {
+    let mut due = HashMap::new();
+    for bucket in [&mut output.overdue, &mut output.today, &mut output.soon] {
+        bucket.retain(|item| {
+            if due.contains_key(&item.id) {
+                return false;
+            }
+            due.insert(item.id, item.due_state);
+            true
+        });
+    }
+    for item in output
+        .dates_today
+        .iter_mut()
+        .chain(output.dates_upcoming.iter_mut())
+    {
+        item.also_due = due.get(&item.contact_id).copied();
+    }
+
+    let listed = listed_contact_ids(output);
+    let mut picked = HashSet::new();
+    random_picks.retain(|pick| !listed.contains(&pick.id) && picked.insert(pick.id));
+}
+
+/// Orders each due bucket most important, then most urgent, first: highest
+/// priority, earliest `next_touchpoint_at`, then name (ignoring case), then
+/// id, so capped output is deterministic.
+pub(crate) fn sort_by_urgency(output: &mut ReminderOutputDto) {
+    for bucket in [&mut output.overdue, &mut output.today, &mut output.soon] {
+        bucket.sort_by(|a, b| {
+            b.priority
+                .cmp(&a.priority)
+                .then_with(|| {
+                    (a.next_touchpoint_at.is_none(), a.next_touchpoint_at)
+                        .cmp(&(b.next_touchpoint_at.is_none(), b.next_touchpoint_at))
+                })
+                .then_with(|| {
+                    a.display_name
+                        .to_lowercase()
+                        .cmp(&b.display_name.to_lowercase())
+                })
+                .then_with(|| a.id.to_string().cmp(&b.id.to_string()))
+        });
+    }
+}
+
+/// The first `max_per_bucket` entries of a due bucket and how many were left
+/// out; `None` shows everything.
+fn capped<T>(items: &[T], max_per_bucket: Option<usize>) -> (&[T], usize) {
+    match max_per_bucket {
+        Some(max) if items.len() > max => (&items[..max], items.len() - max),
+        _ => (items, 0),
+    }
+}
+
+/// Trailing line for a capped due bucket, pointing at the full list.
+fn overflow_line(remaining: usize, selector: &str) -> String {
+    format!("… and {remaining} more (run `knotter list --filter due:{selector}`)")
+}
+
+/// Every contact mentioned anywhere in the reminder output.
+pub(crate) fn listed_contact_ids(output: &ReminderOutputDto) -> HashSet<ContactId> {
+    let buckets = output
+        .overdue
+        .iter()
+        .chain(&output.today)
+        .chain(&output.soon);
+    let week_contacts = output.week.iter().flat_map(|day| &day.contacts);
+    let week_dates = output.week.iter().flat_map(|day| &day.dates);
+    buckets
+        .chain(week_contacts)
+        .map(|item| item.id)
+        .chain(
+            output
+                .dates_today
+                .iter()
+                .chain(&output.dates_upcoming)
+                .chain(week_dates)
+                .map(|item| item.contact_id),
+        )
+        .chain(output.follow_ups.iter().map(|item| item.contact_id))
+        .collect()
+}
+
+/// `max_per_bucket` caps the overdue, today, and soon buckets.
+pub(crate) fn print_human(
+    output: &ReminderOutputDto,
+    random_picks: &[RandomContactPick],
+    max_per_bucket: Option<usize>,
+    clock: &Clock,
+) {
+    for line in human_lines(output, random_picks, max_per_bucket, clock) {
+        println!("{line}");
+    }
+}
+
+fn human_lines(
+    output: &ReminderOutputDto,
+    random_picks: &[RandomContactPick],
+    max_per_bucket: Option<usize>,
+    clock: &Clock,
+) -> Vec<String> {
     if output.is_empty() && random_picks.is_empty() {
-        println!("no reminders");
+        return vec!["no reminders".to_string()];
+    }
+
+    let mut lines = Vec::new();
+    push_human_bucket(
+        &mut lines,
+        "overdue",
+        &output.overdue,
+        max_per_bucket,
+        clock,
+    );
+    push_human_bucket(&mut lines, "today", &output.today, max_per_bucket, clock);
+    push_human_bucket(&mut lines, "soon", &output.soon, max_per_bucket, clock);
+    push_human_date_bucket(&mut lines, "dates today", &output.dates_today);
+    push_human_date_bucket(&mut lines, "dates upcoming", &output.dates_upcoming);
+    push_human_follow_up_bucket(&mut lines, "follow-ups", &output.follow_ups, clock);
+    push_human_random_bucket(&mut lines, "random contacts", random_picks);
+    push_human_week(&mut lines, &output.week);
+    lines
+}
+
+fn push_human_week(lines: &mut Vec<String>, week: &[ReminderDayDto]) {
+    if week.is_empty() {
         return;
     }
 
-    print_bucket("overdue", &output.overdue);
-    print_bucket("today", &output.today);
-    print_bucket("soon", &output.soon);
-    print_date_bucket("dates today", &output.dates_today);
-    print_random_bucket("random contacts", random_picks);
+    lines.push("week:".to_string());
+    for day in week {
+        lines.push(format!(
+            "  {}: {}",
+            day_label(day),
+            join_day_names(day, usize::MAX)
+        ));
+    }
 }
 
-fn print_bucket(label: &str, items: &[ContactListItemDto]) {
+/// `label` doubles as the `due:` selector in the overflow hint.
+fn push_human_bucket(
+    lines: &mut Vec<String>,
+    label: &str,
+    items: &[ContactListItemDto],
+    max_per_bucket: Option<usize>,
+    clock: &Clock,
+) {
     if items.is_empty() {
         return;
     }
 
-    println!("{label}:");
-    for item in items {
+    lines.push(format!("{label}:"));
+    let (shown, remaining) = capped(items, max_per_bucket);
+    for item in shown {
         let date = item
             .next_touchpoint_at
-            .map(format_timestamp_date)
+            .map(|ts| clock.format_timestamp_date(ts))
             .unwrap_or_else(|| "-".to_string());
         let tag_suffix = format_tag_suffix(&item.tags);
-        println!(
+        lines.push(format!(
             "  {}  {}  {}{}",
             item.id, item.display_name, date, tag_suffix
-        );
+        ));
+    }
+    if remaining > 0 {
+        lines.push(format!("  {}", overflow_line(remaining, label)));
     }
 }
 
-fn print_date_bucket(label: &str, items: &[DateReminderItemDto]) {
+fn push_human_date_bucket(lines: &mut Vec<String>, label: &str, items: &[DateReminderItemDto]) {
     if items.is_empty() {
         return;
     }
 
-    println!("{label}:");
+    lines.push(format!("{label}:"));
     for item in items {
         let date = format_date_parts(item.month, item.day, item.year);
         let label = format_date_label(item);
-        println!(
+        lines.push(format!(
+            "  {}  {}  {}  {}{}{}",
+            item.contact_id,
+            item.display_name,
+            label,
+            date,
+            format_days_until(item),
+            format_also_due(item)
+        ));
+    }
+}
+
+fn push_human_follow_up_bucket(
+    lines: &mut Vec<String>,
+    label: &str,
+    items: &[FollowUpItemDto],
+    clock: &Clock,
+) {
+    if items.is_empty() {
+        return;
+    }
+
+    lines.push(format!("{label}:"));
+    for item in items {
+        lines.push(format!(
             "  {}  {}  {}  {}",
-            item.contact_id, item.display_name, label, date
-        );
+            item.interaction_id,
+            item.display_name,
+            clock.format_timestamp_date(item.follow_up_at),
+            note_summary(&item.note)
+        ));
     }
 }
 
-fn print_random_bucket(label: &str, items: &[RandomContactPick]) {
+fn push_human_random_bucket(lines: &mut Vec<String>, label: &str, items: &[RandomContactPick]) {
     if items.is_empty() {
         return;
     }
 
-    println!("{label}:");
+    lines.push(format!("{label}:"));
     for item in items {
-        println!("  {}  {}", item.id, item.display_name);
+        lines.push(format!("  {}  {}", item.id, item.display_name));
     }
 }
 
+/// `max_names` shortens each line; `max_per_bucket` caps the due buckets
+/// first, like the other formats.
 pub(crate) fn notification_body(
     output: &ReminderOutputDto,
     random_picks: &[RandomContactPick],
     max_names: usize,
+    max_per_bucket: Option<usize>,
 ) -> String {
     let mut lines = Vec::new();
-    if !output.overdue.is_empty() {
+    for (label, selector, items) in [
+        ("Overdue", "overdue", &output.overdue),
+        ("Today", "today", &output.today),
+        ("Soon", "soon", &output.soon),
+    ] {
+        if items.is_empty() {
+            continue;
+        }
+        let (shown, remaining) = capped(items, max_per_bucket);
         lines.push(format!(
-            "Overdue ({}): {}",
-            output.overdue.len(),
-            join_names(&output.overdue, max_names)
+            "{label} ({}): {}",
+            items.len(),
+            join_names(shown, max_names)
         ));
+        if remaining > 0 {
+            lines.push(overflow_line(remaining, selector));
+        }
     }
-    if !output.today.is_empty() {
+    if !output.dates_today.is_empty() {
         lines.push(format!(
-            "Today ({}): {}",
-            output.today.len(),
-            join_names(&output.today, max_names)
+            "Dates today ({}): {}",
+            output.dates_today.len(),
+            join_date_names(&output.dates_today, max_names)
         ));
     }
-    if !output.soon.is_empty() {
+    if !output.dates_upcoming.is_empty() {
         lines.push(format!(
-            "Soon ({}): {}",
-            output.soon.len(),
-            join_names(&output.soon, max_names)
+            "Dates upcoming ({}): {}",
+            output.dates_upcoming.len(),
+            join_date_names(&output.dates_upcoming, max_names)
         ));
     }
-    if !output.dates_today.is_empty() {
+    if !output.follow_ups.is_empty() {
         lines.push(format!(
-            "Dates today ({}): {}",
-            output.dates_today.len(),
-            join_date_names(&output.dates_today, max_names)
+            "Follow-ups ({}): {}",
+            output.follow_ups.len(),
+            join_follow_up_names(&output.follow_ups, max_names)
         ));
     }
     if !random_picks.is_empty() {
@@ -108,9 +295,73 @@ pub(crate) fn notification_body(
             join_random_names(random_picks, max_names)
         ));
     }
+    let busy_days = output
+        .week
+        .iter()
+        .filter(|day| !day.is_empty())
+        .collect::<Vec<_>>();
+    if !busy_days.is_empty() {
+        lines.push("This week:".to_string());
+        for day in busy_days {
+            lines.push(format!(
+                "{}: {}",
+                weekday_label(day),
+                join_day_names(day, max_names)
+            ));
+        }
+    }
     lines.join("\n")
 }
 
+/// One notification per overdue or due-today contact (at most `max_per_run`),
+/// each naming the contact and their last interaction, then a single summary
+/// of the contacts past the cap and every other reminder.
+pub(crate) fn contact_notifications(
+    output: &ReminderOutputDto,
+    random_picks: &[RandomContactPick],
+    last_interactions: &HashMap<ContactId, i64>,
+    max_per_run: usize,
+    max_per_bucket: Option<usize>,
+    clock: &Clock,
+) -> Vec<Notification> {
+    let due = output
+        .overdue
+        .iter()
+        .map(|item| ("Overdue", item))
+        .chain(output.today.iter().map(|item| ("Due today", item)))
+        .collect::<Vec<_>>();
+    let mut notifications = due
+        .iter()
+        .take(max_per_run)
+        .map(|(label, item)| {
+            let last = match last_interactions.get(&item.id) {
+                Some(at) => format!("Last interaction: {}", clock.format_timestamp_date(*at)),
+                None => "No interactions logged yet".to_string(),
+            };
+            Notification::new(format!("{label}: {}", item.display_name), last)
+        })
+        .collect::<Vec<_>>();
+
+    let mut lines = Vec::new();
+    let remaining = due.len().saturating_sub(max_per_run);
+    if remaining > 0 {
+        lines.push(format!("And {remaining} more overdue or due today"));
+    }
+    let rest = ReminderOutputDto {
+        overdue: Vec::new(),
+        today: Vec::new(),
+        ..output.clone()
+    };
+    let rest_body = notification_body(&rest, random_picks, max_per_run, max_per_bucket);
+    if !rest_body.is_empty() {
+        lines.push(rest_body);
+    }
+    if !lines.is_empty() {
+        notifications.push(Notification::new("knotter reminders", lines.join("\n")));
+    }
+    notifications
+}
+
 #[cfg(feature = "email-notify")]
 pub(crate) fn email_subject(
     output: &ReminderOutputDto,
@@ -121,6 +372,8 @@ pub(crate) fn email_subject(
         + output.today.len()
         + output.soon.len()
         + output.dates_today.len()
+        + output.dates_upcoming.len()
+        + output.follow_ups.len()
         + random_picks.len();
     let trimmed = prefix.trim();
     if total == 0 {
@@ -131,47 +384,99 @@ pub(crate) fn email_subject(
         }
     } else if trimmed.is_empty() {
         format!(
-            "knotter reminders (overdue {}, today {}, soon {}, dates {}, random {})",
+            "knotter reminders (overdue {}, today {}, soon {}, dates {}, follow-ups {}, random {})",
             output.overdue.len(),
             output.today.len(),
             output.soon.len(),
-            output.dates_today.len(),
+            output.dates_today.len() + output.dates_upcoming.len(),
+            output.follow_ups.len(),
             random_picks.len()
         )
     } else {
         format!(
-            "{} (overdue {}, today {}, soon {}, dates {}, random {})",
+            "{} (overdue {}, today {}, soon {}, dates {}, follow-ups {}, random {})",
             trimmed,
             output.overdue.len(),
             output.today.len(),
             output.soon.len(),
-            output.dates_today.len(),
+            output.dates_today.len() + output.dates_upcoming.len(),
+            output.follow_ups.len(),
             random_picks.len()
         )
     }
 }
 
 #[cfg(feature = "email-notify")]
-pub(crate) fn email_body(output: &ReminderOutputDto, random_picks: &[RandomContactPick]) -> String {
+pub(crate) fn email_body(
+    output: &ReminderOutputDto,
+    random_picks: &[RandomContactPick],
+    max_per_bucket: Option<usize>,
+    clock: &Clock,
+) -> String {
     let mut lines = Vec::new();
-    push_email_bucket(&mut lines, "Overdue", &output.overdue);
-    push_email_bucket(&mut lines, "Today", &output.today);
-    push_email_bucket(&mut lines, "Soon", &output.soon);
+    push_email_bucket(
+        &mut lines,
+        ("Overdue", "overdue"),
+        &output.overdue,
+        max_per_bucket,
+        clock,
+    );
+    push_email_bucket(
+        &mut lines,
+        ("Today", "today"),
+        &output.today,
+        max_per_bucket,
+        clock,
+    );
+    push_email_bucket(
+        &mut lines,
+        ("Soon", "soon"),
+        &output.soon,
+        max_per_bucket,
+        clock,
+    );
     push_email_date_bucket(&mut lines, "Dates today", &output.dates_today);
+    push_email_date_bucket(&mut lines, "Dates upcoming", &output.dates_upcoming);
+    push_email_follow_up_bucket(&mut lines, "Follow-ups", &output.follow_ups, clock);
     push_email_random_bucket(&mut lines, "Random contacts", random_picks);
+    push_email_week(&mut lines, &output.week);
     lines.join("\n")
 }
 
 #[cfg(feature = "email-notify")]
-fn push_email_bucket(lines: &mut Vec<String>, label: &str, items: &[ContactListItemDto]) {
+fn push_email_week(lines: &mut Vec<String>, week: &[ReminderDayDto]) {
+    if week.is_empty() {
+        return;
+    }
+    lines.push("Week ahead".to_string());
+    for day in week {
+        lines.push(format!(
+            "  {}: {}",
+            day_label(day),
+            join_day_names(day, usize::MAX)
+        ));
+    }
+    lines.push(String::new());
+}
+
+/// `label` is the heading and `due:` selector pair.
+#[cfg(feature = "email-notify")]
+fn push_email_bucket(
+    lines: &mut Vec<String>,
+    (label, selector): (&str, &str),
+    items: &[ContactListItemDto],
+    max_per_bucket: Option<usize>,
+    clock: &Clock,
+) {
     if items.is_empty() {
         return;
     }
     lines.push(format!("{label} ({})", items.len()));
-    for item in items {
+    let (shown, remaining) = capped(items, max_per_bucket);
+    for item in shown {
         let date = item
             .next_touchpoint_at
-            .map(format_timestamp_date)
+            .map(|ts| clock.format_timestamp_date(ts))
             .unwrap_or_else(|| "-".to_string());
         let tag_suffix = format_tag_suffix(&item.tags);
         lines.push(format!(
@@ -179,6 +484,9 @@ fn push_email_bucket(lines: &mut Vec<String>, label: &str, items: &[ContactListI
             item.id, item.display_name, date, tag_suffix
         ));
     }
+    if remaining > 0 {
+        lines.push(format!("  {}", overflow_line(remaining, selector)));
+    }
     lines.push(String::new());
 }
 
@@ -191,7 +499,36 @@ fn push_email_date_bucket(lines: &mut Vec<String>, label: &str, items: &[DateRem
     for item in items {
         let date = format_date_parts(item.month, item.day, item.year);
         let label = format_date_label(item);
-        lines.push(format!("  {}  {}  {}", item.display_name, label, date));
+        lines.push(format!(
+            "  {}  {}  {}{}{}",
+            item.display_name,
+            label,
+            date,
+            format_days_until(item),
+            format_also_due(item)
+        ));
+    }
+    lines.push(String::new());
+}
+
+#[cfg(feature = "email-notify")]
+fn push_email_follow_up_bucket(
+    lines: &mut Vec<String>,
+    label: &str,
+    items: &[FollowUpItemDto],
+    clock: &Clock,
+) {
+    if items.is_empty() {
+        return;
+    }
+    lines.push(format!("{label} ({})", items.len()));
+    for item in items {
+        lines.push(format!(
+            "  {}  {}  {}",
+            item.display_name,
+            clock.format_timestamp_date(item.follow_up_at),
+            note_summary(&item.note)
+        ));
     }
     lines.push(String::new());
 }
@@ -208,6 +545,215 @@ fn push_email_random_bucket(lines: &mut Vec<String>, label: &str, items: &[Rando
     lines.push(String::new());
 }
 
+/// HTML alternative to [`email_body`] with the same sections: overdue in red,
+/// today in bold, dates marked with a cake, and each contact name linking to
+/// `knotter://contact/<id>`.
+#[cfg(feature = "email-notify")]
+pub(crate) fn email_html(
+    output: &ReminderOutputDto,
+    random_picks: &[RandomContactPick],
+    max_per_bucket: Option<usize>,
+    clock: &Clock,
+) -> String {
+    let mut lines = vec![
+        "<html>".to_string(),
+        "<body style=\"font-family:sans-serif\">".to_string(),
+    ];
+    push_html_bucket(
+        &mut lines,
+        ("Overdue", "overdue"),
+        Some("color:#c62828"),
+        &output.overdue,
+        max_per_bucket,
+        clock,
+    );
+    push_html_bucket(
+        &mut lines,
+        ("Today", "today"),
+        Some("font-weight:bold"),
+        &output.today,
+        max_per_bucket,
+        clock,
+    );
+    push_html_bucket(
+        &mut lines,
+        ("Soon", "soon"),
+        None,
+        &output.soon,
+        max_per_bucket,
+        clock,
+    );
+    push_html_date_bucket(&mut lines, "Dates today", &output.dates_today);
+    push_html_date_bucket(&mut lines, "Dates upcoming", &output.dates_upcoming);
+    push_html_follow_up_bucket(&mut lines, "Follow-ups", &output.follow_ups, clock);
+    push_html_random_bucket(&mut lines, "Random contacts", random_picks);
+    push_html_week(&mut lines, &output.week);
+    lines.push("</body>".to_string());
+    lines.push("</html>".to_string());
+    lines.join("\n")
+}
+
+/// One `<section>` with a heading and a list; `rows` are already escaped.
+#[cfg(feature = "email-notify")]
+fn push_html_section(
+    lines: &mut Vec<String>,
+    heading: &str,
+    style: Option<&str>,
+    rows: Vec<String>,
+) {
+    if rows.is_empty() {
+        return;
+    }
+    match style {
+        Some(style) => lines.push(format!("<section style=\"{style}\">")),
+        None => lines.push("<section>".to_string()),
+    }
+    lines.push(format!("<h3>{}</h3>", escape_html(heading)));
+    lines.push("<ul>".to_string());
+    lines.extend(rows.into_iter().map(|row| format!("<li>{row}</li>")));
+    lines.push("</ul>".to_string());
+    lines.push("</section>".to_string());
+}
+
+#[cfg(feature = "email-notify")]
+fn push_html_bucket(
+    lines: &mut Vec<String>,
+    (label, selector): (&str, &str),
+    style: Option<&str>,
+    items: &[ContactListItemDto],
+    max_per_bucket: Option<usize>,
+    clock: &Clock,
+) {
+    let (shown, remaining) = capped(items, max_per_bucket);
+    let mut rows: Vec<String> = shown
+        .iter()
+        .map(|item| {
+            let date = item
+                .next_touchpoint_at
+                .map(|ts| clock.format_timestamp_date(ts))
+                .unwrap_or_else(|| "-".to_string());
+            format!(
+                "{} {}{}",
+                html_contact_link(item.id, &item.display_name),
+                date,
+                escape_html(&format_tag_suffix(&item.tags))
+            )
+        })
+        .collect();
+    if remaining > 0 {
+        rows.push(escape_html(&overflow_line(remaining, selector)));
+    }
+    push_html_section(lines, &format!("{label} ({})", items.len()), style, rows);
+}
+
+#[cfg(feature = "email-notify")]
+fn push_html_date_bucket(lines: &mut Vec<String>, label: &str, items: &[DateReminderItemDto]) {
+    let rows = items
+        .iter()
+        .map(|item| format!("\u{1f382} {}", html_date_entry(item)))
+        .collect();
+    push_html_section(lines, &format!("{label} ({})", items.len()), None, rows);
+}
+
+#[cfg(feature = "email-notify")]
+fn html_date_entry(item: &DateReminderItemDto) -> String {
+    let mut details = vec![
+        format_date_label(item),
+        format_date_parts(item.month, item.day, item.year),
+    ];
+    if let Some(days) = item.days_until {
+        details.push(in_days_label(days));
+    }
+    if let Some(state) = item.also_due {
+        details.push(format!("(also {})", due_state_label(state)));
+    }
+    format!(
+        "{} {}",
+        html_contact_link(item.contact_id, &item.display_name),
+        escape_html(&details.join(" "))
+    )
+}
+
+#[cfg(feature = "email-notify")]
+fn push_html_follow_up_bucket(
+    lines: &mut Vec<String>,
+    label: &str,
+    items: &[FollowUpItemDto],
+    clock: &Clock,
+) {
+    let rows = items
+        .iter()
+        .map(|item| {
+            format!(
+                "{} {} {}",
+                html_contact_link(item.contact_id, &item.display_name),
+                clock.format_timestamp_date(item.follow_up_at),
+                escape_html(note_summary(&item.note))
+            )
+        })
+        .collect();
+    push_html_section(lines, &format!("{label} ({})", items.len()), None, rows);
+}
+
+#[cfg(feature = "email-notify")]
+fn push_html_random_bucket(lines: &mut Vec<String>, label: &str, items: &[RandomContactPick]) {
+    let rows = items
+        .iter()
+        .map(|item| html_contact_link(item.id, &item.display_name))
+        .collect();
+    push_html_section(lines, &format!("{label} ({})", items.len()), None, rows);
+}
+
+#[cfg(feature = "email-notify")]
+fn push_html_week(lines: &mut Vec<String>, week: &[ReminderDayDto]) {
+    let rows = week
+        .iter()
+        .map(|day| {
+            let entries = day
+                .contacts
+                .iter()
+                .map(|item| html_contact_link(item.id, &item.display_name))
+                .chain(
+                    day.dates
+                        .iter()
+                        .map(|item| format!("\u{1f382} {}", html_date_entry(item))),
+                )
+                .collect::<Vec<_>>();
+            let entries = if entries.is_empty() {
+                "—".to_string()
+            } else {
+                entries.join(", ")
+            };
+            format!("{}: {entries}", day_label(day))
+        })
+        .collect();
+    push_html_section(lines, "Week ahead", None, rows);
+}
+
+#[cfg(feature = "email-notify")]
+fn html_contact_link(id: ContactId, name: &str) -> String {
+    format!(
+        "<a href=\"knotter://contact/{id}\" style=\"color:inherit\">{}</a>",
+        escape_html(name)
+    )
+}
+
+#[cfg(feature = "email-notify")]
+fn escape_html(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    for ch in value.chars() {
+        match ch {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            _ => out.push(ch),
+        }
+    }
+    out
+}
+
 fn join_names(items: &[ContactListItemDto], max_names: usize) -> String {
     let mut names = items
         .iter()
@@ -225,7 +771,16 @@ fn join_date_names(items: &[DateReminderItemDto], max_names: usize) -> String {
     let mut names = items
         .iter()
         .take(max_names)
-        .map(|item| format!("{} ({})", item.display_name, format_date_label(item)))
+        .map(|item| {
+            let mut details = vec![format_date_label(item)];
+            if let Some(days) = item.days_until {
+                details.push(in_days_label(days));
+            }
+            if let Some(state) = item.also_due {
+                details.push(format!("also {}", due_state_label(state)));
+            }
+            format!("{} ({})", item.display_name, details.join(", "))
+        })
         .collect::<Vec<_>>();
     let remaining = items.len().saturating_sub(max_names);
     if remaining > 0 {
@@ -234,6 +789,24 @@ fn join_date_names(items: &[DateReminderItemDto], max_names: usize) -> String {
     names.join(", ")
 }
 
+fn join_follow_up_names(items: &[FollowUpItemDto], max_names: usize) -> String {
+    let mut names = items
+        .iter()
+        .take(max_names)
+        .map(|item| item.display_name.clone())
+        .collect::<Vec<_>>();
+    let remaining = items.len().saturating_sub(max_names);
+    if remaining > 0 {
+        names.push(format!("+{} more", remaining));
+    }
+    names.join(", ")
+}
+
+/// First line of a note, for one-line listings.
+pub(crate) fn note_summary(note: &str) -> &str {
+    note.lines().next().unwrap_or("").trim()
+}
+
 fn join_random_names(items: &[RandomContactPick], max_names: usize) -> String {
     let mut names = items
         .iter()
@@ -247,6 +820,41 @@ fn join_random_names(items: &[RandomContactPick], max_names: usize) -> String {
     names.join(", ")
 }
 
+fn join_day_names(day: &ReminderDayDto, max_names: usize) -> String {
+    if day.is_empty() {
+        return "—".to_string();
+    }
+    let all = day
+        .contacts
+        .iter()
+        .map(|item| item.display_name.clone())
+        .chain(
+            day.dates
+                .iter()
+                .map(|item| format!("{} ({})", item.display_name, format_date_label(item))),
+        )
+        .collect::<Vec<_>>();
+    let remaining = all.len().saturating_sub(max_names);
+    let mut names = all.into_iter().take(max_names).collect::<Vec<_>>();
+    if remaining > 0 {
+        names.push(format!("+{} more", remaining));
+    }
+    names.join(", ")
+}
+
+fn weekday_label(day: &ReminderDayDto) -> String {
+    NaiveDate::parse_from_str(&day.date, "%Y-%m-%d")
+        .map(|date| date.format("%a").to_string())
+        .unwrap_or_else(|_| day.date.clone())
+}
+
+fn day_label(day: &ReminderDayDto) -> String {
+    match NaiveDate::parse_from_str(&day.date, "%Y-%m-%d") {
+        Ok(date) => date.format("%a %Y-%m-%d").to_string(),
+        Err(_) => day.date.clone(),
+    }
+}
+
 fn format_tag_suffix(tags: &[String]) -> String {
     if tags.is_empty() {
         return String::new();
@@ -259,6 +867,26 @@ fn format_tag_suffix(tags: &[String]) -> String {
     format!(" {}", tags)
 }
 
+fn format_days_until(item: &DateReminderItemDto) -> String {
+    item.days_until
+        .map(|days| format!("  {}", in_days_label(days)))
+        .unwrap_or_default()
+}
+
+fn format_also_due(item: &DateReminderItemDto) -> String {
+    item.also_due
+        .map(|state| format!("  (also {})", due_state_label(state)))
+        .unwrap_or_default()
+}
+
+fn in_days_label(days: i64) -> String {
+    if days == 1 {
+        "in 1 day".to_string()
+    } else {
+        format!("in {days} days")
+    }
+}
+
 fn format_date_label(item: &DateReminderItemDto) -> String {
     use knotter_core::domain::ContactDateKind;
     match item.kind {
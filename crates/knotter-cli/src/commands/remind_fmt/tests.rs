@@ -1,7 +1,13 @@
-use super::{notification_body, RandomContactPick};
+use super::{
+    contact_notifications, dedupe, human_lines, join_day_names, notification_body, sort_by_urgency,
+    RandomContactPick,
+};
 use knotter_core::domain::{ContactDateKind, ContactId};
-use knotter_core::dto::{ContactListItemDto, DateReminderItemDto, ReminderOutputDto};
+use knotter_core::dto::{
+    ContactListItemDto, DateReminderItemDto, ReminderDayDto, ReminderOutputDto,
+};
 use knotter_core::rules::DueState;
+use knotter_core::time::Clock;
 
 fn item(name: &str, due_state: DueState, next: Option<i64>) -> ContactListItemDto {
     ContactListItemDto {
@@ -10,7 +16,13 @@ fn item(name: &str, due_state: DueState, next: Option<i64>) -> ContactListItemDt
         due_state,
         next_touchpoint_at: next,
         archived_at: None,
+        archive_reason: None,
+        muted_until: None,
         tags: vec![],
+        last_interaction_at: None,
+        last_interaction_kind: None,
+        organization: None,
+        priority: 0,
     }
 }
 
@@ -28,14 +40,61 @@ fn notification_body_includes_dates_today() {
             month: 3,
             day: 5,
             year: None,
+            days_until: None,
+            also_due: None,
         }],
+        dates_upcoming: vec![],
+        follow_ups: vec![],
+        week: vec![],
     };
 
-    let body = notification_body(&output, &[], 5);
+    let body = notification_body(&output, &[], 5, None);
     assert!(body.contains("Dates today (1)"));
     assert!(body.contains("Grace (Birthday)"));
 }
 
+#[test]
+fn contact_notifications_name_each_due_contact_and_summarize_the_rest() {
+    let ada = item("Ada", DueState::Overdue, Some(1));
+    let grace = item("Grace", DueState::Today, Some(2));
+    let linus = item("Linus", DueState::Today, Some(3));
+    let output = ReminderOutputDto {
+        overdue: vec![ada.clone()],
+        today: vec![grace, linus],
+        soon: vec![item("Hedy", DueState::Soon, Some(4))],
+        dates_today: vec![],
+        dates_upcoming: vec![],
+        follow_ups: vec![],
+        week: vec![],
+    };
+    let last = std::collections::HashMap::from([(ada.id, 1_704_067_200)]);
+
+    let notifications = contact_notifications(&output, &[], &last, 2, None, &Clock::default());
+    assert_eq!(notifications.len(), 3);
+    assert_eq!(notifications[0].title, "Overdue: Ada");
+    assert!(notifications[0].body.starts_with("Last interaction: "));
+    assert_eq!(notifications[1].title, "Due today: Grace");
+    assert_eq!(notifications[1].body, "No interactions logged yet");
+    assert_eq!(notifications[2].title, "knotter reminders");
+    assert_eq!(
+        notifications[2].body,
+        "And 1 more overdue or due today\nSoon (1): Hedy"
+    );
+
+    let all = contact_notifications(&output, &[], &last, 5, None, &Clock::default());
+    assert_eq!(all.len(), 4);
+    assert_eq!(all[3].body, "Soon (1): Hedy");
+
+    let only_due = ReminderOutputDto {
+        soon: vec![],
+        ..output
+    };
+    assert_eq!(
+        contact_notifications(&only_due, &[], &last, 5, None, &Clock::default()).len(),
+        3
+    );
+}
+
 #[test]
 fn notification_body_includes_random_contacts() {
     let output = ReminderOutputDto {
@@ -43,6 +102,9 @@ fn notification_body_includes_random_contacts() {
         today: vec![],
         soon: vec![],
         dates_today: vec![],
+        dates_upcoming: vec![],
+        follow_ups: vec![],
+        week: vec![],
     };
     let picks = vec![
         RandomContactPick {
@@ -55,16 +117,209 @@ fn notification_body_includes_random_contacts() {
         },
     ];
 
-    let body = notification_body(&output, &picks, 5);
+    let body = notification_body(&output, &picks, 5, None);
     assert!(body.contains("Random contacts (2)"));
     assert!(body.contains("Ada"));
     assert!(body.contains("Grace"));
 }
 
+#[test]
+fn dedupe_lists_a_contact_once_across_buckets_dates_and_random_picks() {
+    let ada = item("Ada", DueState::Overdue, Some(1));
+    let ada_id = ada.id;
+    let mut ada_today = ada.clone();
+    ada_today.due_state = DueState::Today;
+    let mut ada_soon = ada.clone();
+    ada_soon.due_state = DueState::Soon;
+    let grace = item("Grace", DueState::Soon, Some(3));
+    let mut output = ReminderOutputDto {
+        overdue: vec![ada],
+        today: vec![ada_today],
+        soon: vec![ada_soon, grace],
+        dates_today: vec![DateReminderItemDto {
+            contact_id: ada_id,
+            display_name: "Ada".to_string(),
+            kind: ContactDateKind::Birthday,
+            label: None,
+            month: 3,
+            day: 5,
+            year: None,
+            days_until: None,
+            also_due: None,
+        }],
+        dates_upcoming: vec![],
+        follow_ups: vec![],
+        week: vec![],
+    };
+    let tim = ContactId::new();
+    let mut picks = vec![
+        RandomContactPick {
+            id: ada_id,
+            display_name: "Ada".to_string(),
+        },
+        RandomContactPick {
+            id: tim,
+            display_name: "Tim".to_string(),
+        },
+    ];
+
+    dedupe(&mut output, &mut picks);
+
+    assert_eq!(output.overdue.len(), 1);
+    assert!(output.today.is_empty());
+    assert_eq!(output.soon.len(), 1);
+    assert_eq!(output.soon[0].display_name, "Grace");
+    assert_eq!(output.dates_today[0].also_due, Some(DueState::Overdue));
+    assert_eq!(picks.len(), 1);
+    assert_eq!(picks[0].id, tim);
+
+    let body = notification_body(&output, &picks, 5, None);
+    assert!(body.contains("Overdue (1): Ada"), "{body}");
+    assert!(!body.contains("Today ("), "{body}");
+    assert!(body.contains("Soon (1): Grace"), "{body}");
+    assert!(
+        body.contains("Dates today (1): Ada (Birthday, also overdue)"),
+        "{body}"
+    );
+    assert!(body.contains("Random contacts (1): Tim"), "{body}");
+}
+
+fn week_fixture() -> Vec<ReminderDayDto> {
+    let mut week = (15..=21)
+        .map(|day| ReminderDayDto {
+            date: format!("2024-01-{day}"),
+            contacts: vec![],
+            dates: vec![],
+        })
+        .collect::<Vec<_>>();
+    week[0].contacts = vec![
+        item("Alice", DueState::Today, Some(1)),
+        item("Bob", DueState::Today, Some(2)),
+    ];
+    week[2].dates = vec![DateReminderItemDto {
+        contact_id: ContactId::new(),
+        display_name: "Grace".to_string(),
+        kind: ContactDateKind::Birthday,
+        label: None,
+        month: 1,
+        day: 17,
+        year: None,
+        days_until: None,
+        also_due: None,
+    }];
+    week
+}
+
+#[test]
+fn join_day_names_lists_contacts_and_dates_or_dash() {
+    let week = week_fixture();
+    assert_eq!(join_day_names(&week[0], usize::MAX), "Alice, Bob");
+    assert_eq!(join_day_names(&week[0], 1), "Alice, +1 more");
+    assert_eq!(join_day_names(&week[1], usize::MAX), "—");
+    assert_eq!(join_day_names(&week[2], usize::MAX), "Grace (Birthday)");
+}
+
+#[test]
+fn notification_body_lists_busy_week_days() {
+    let output = ReminderOutputDto {
+        overdue: vec![],
+        today: vec![],
+        soon: vec![],
+        dates_today: vec![],
+        dates_upcoming: vec![],
+        follow_ups: vec![],
+        week: week_fixture(),
+    };
+
+    let body = notification_body(&output, &[], 5, None);
+    assert_eq!(body, "This week:\nMon: Alice, Bob\nWed: Grace (Birthday)");
+}
+
+fn overdue_output(names: &[(&str, i64)]) -> ReminderOutputDto {
+    ReminderOutputDto {
+        overdue: names
+            .iter()
+            .map(|(name, next)| item(name, DueState::Overdue, Some(*next)))
+            .collect(),
+        today: vec![],
+        soon: vec![],
+        dates_today: vec![],
+        dates_upcoming: vec![],
+        follow_ups: vec![],
+        week: vec![],
+    }
+}
+
+#[test]
+fn sort_by_urgency_puts_longest_overdue_first_and_breaks_ties_by_name() {
+    let mut output = overdue_output(&[("grace", 20), ("Ada", 20), ("Linus", 10), ("ada", 20)]);
+    sort_by_urgency(&mut output);
+    let names = output
+        .overdue
+        .iter()
+        .map(|item| item.display_name.to_lowercase())
+        .collect::<Vec<_>>();
+    assert_eq!(names, ["linus", "ada", "ada", "grace"]);
+
+    // Same time and name: the id decides, so reruns keep the same order.
+    let ids = output.overdue[1..3]
+        .iter()
+        .map(|item| item.id.to_string())
+        .collect::<Vec<_>>();
+    let mut sorted = ids.clone();
+    sorted.sort();
+    assert_eq!(ids, sorted);
+}
+
+#[test]
+fn sort_by_urgency_puts_higher_priority_first_within_a_bucket() {
+    let mut output = overdue_output(&[("Grace", 10), ("Ada", 30), ("Hedy", 20), ("Linus", 5)]);
+    output.overdue[1].priority = 10;
+    output.overdue[2].priority = 3;
+    sort_by_urgency(&mut output);
+    let names = output
+        .overdue
+        .iter()
+        .map(|item| item.display_name.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(names, ["Ada", "Hedy", "Linus", "Grace"]);
+}
+
+#[test]
+fn max_per_bucket_caps_human_and_notification_output() {
+    let mut output = overdue_output(&[("Grace", 30), ("Ada", 10), ("Hedy", 20), ("Linus", 40)]);
+    sort_by_urgency(&mut output);
+
+    let lines = human_lines(&output, &[], Some(2), &Clock::default());
+    assert_eq!(lines.len(), 4);
+    assert_eq!(lines[0], "overdue:");
+    assert!(lines[1].contains("  Ada  "));
+    assert!(lines[2].contains("  Hedy  "));
+    assert_eq!(
+        lines[3],
+        "  … and 2 more (run `knotter list --filter due:overdue`)"
+    );
+    assert_eq!(
+        human_lines(&output, &[], Some(4), &Clock::default()).len(),
+        5
+    );
+
+    let body = notification_body(&output, &[], 5, Some(2));
+    assert_eq!(
+        body,
+        "Overdue (4): Ada, Hedy\n… and 2 more (run `knotter list --filter due:overdue`)"
+    );
+    assert_eq!(
+        notification_body(&output, &[], 5, None),
+        "Overdue (4): Ada, Hedy, Grace, Linus"
+    );
+}
+
 #[cfg(feature = "email-notify")]
 mod email {
     use super::*;
-    use crate::commands::remind_fmt::{email_body, email_subject};
+    use crate::commands::remind_fmt::{email_body, email_html, email_subject};
+    use std::str::FromStr;
 
     fn tagged_item(name: &str, due_state: DueState, next: Option<i64>) -> ContactListItemDto {
         ContactListItemDto {
@@ -73,7 +328,13 @@ mod email {
             due_state,
             next_touchpoint_at: next,
             archived_at: None,
+            archive_reason: None,
+            muted_until: None,
             tags: vec!["friends".to_string()],
+            last_interaction_at: None,
+            last_interaction_kind: None,
+            organization: None,
+            priority: 0,
         }
     }
 
@@ -91,7 +352,12 @@ mod email {
                 month: 1,
                 day: 2,
                 year: None,
+                days_until: None,
+                also_due: None,
             }],
+            dates_upcoming: vec![],
+            follow_ups: vec![],
+            week: vec![],
         };
 
         let subject = email_subject(&output, &[], "Knotter");
@@ -102,6 +368,50 @@ mod email {
         assert!(subject.contains("dates 1"));
     }
 
+    #[test]
+    fn email_subject_counts_deduped_contacts() {
+        let ada = tagged_item("Ada", DueState::Overdue, Some(1));
+        let mut ada_soon = ada.clone();
+        ada_soon.due_state = DueState::Soon;
+        let mut output = ReminderOutputDto {
+            overdue: vec![ada.clone()],
+            today: vec![],
+            soon: vec![ada_soon],
+            dates_today: vec![DateReminderItemDto {
+                contact_id: ada.id,
+                display_name: "Ada".to_string(),
+                kind: ContactDateKind::Birthday,
+                label: None,
+                month: 1,
+                day: 2,
+                year: None,
+                days_until: None,
+                also_due: None,
+            }],
+            dates_upcoming: vec![],
+            follow_ups: vec![],
+            week: vec![],
+        };
+        let mut picks = vec![RandomContactPick {
+            id: ada.id,
+            display_name: "Ada".to_string(),
+        }];
+
+        dedupe(&mut output, &mut picks);
+
+        let subject = email_subject(&output, &picks, "");
+        assert!(
+            subject.contains("overdue 1, today 0, soon 0, dates 1"),
+            "{subject}"
+        );
+        assert!(subject.contains("random 0"), "{subject}");
+        let body = email_body(&output, &picks, None, &Clock::default());
+        assert!(
+            body.contains("  Ada  Birthday  01-02  (also overdue)"),
+            "{body}"
+        );
+    }
+
     #[test]
     fn email_body_formats_buckets() {
         let output = ReminderOutputDto {
@@ -116,10 +426,15 @@ mod email {
                 month: 2,
                 day: 14,
                 year: None,
+                days_until: None,
+                also_due: None,
             }],
+            dates_upcoming: vec![],
+            follow_ups: vec![],
+            week: vec![],
         };
 
-        let body = email_body(&output, &[]);
+        let body = email_body(&output, &[], None, &Clock::default());
         assert!(body.contains("Overdue (1)"));
         assert!(body.contains("Soon (1)"));
         assert!(body.contains("Dates today (1)"));
@@ -127,4 +442,134 @@ mod email {
         assert!(body.contains("Grace"));
         assert!(body.contains("Anniversary"));
     }
+
+    #[test]
+    fn email_body_includes_week_ahead() {
+        let output = ReminderOutputDto {
+            overdue: vec![],
+            today: vec![],
+            soon: vec![],
+            dates_today: vec![],
+            dates_upcoming: vec![],
+            follow_ups: vec![],
+            week: week_fixture(),
+        };
+
+        let body = email_body(&output, &[], None, &Clock::default());
+        assert!(body.contains("Week ahead"));
+        assert!(body.contains("  Mon 2024-01-15: Alice, Bob"));
+        assert!(body.contains("  Tue 2024-01-16: —"));
+        assert!(body.contains("  Wed 2024-01-17: Grace (Birthday)"));
+    }
+
+    /// Fixed ids and noon-UTC timestamps so the rendered dates hold in any
+    /// local timezone.
+    fn snapshot_output() -> (ReminderOutputDto, Vec<RandomContactPick>) {
+        let id = |n: u8| {
+            ContactId::from_str(&format!("00000000-0000-4000-8000-0000000000{n:02}")).unwrap()
+        };
+        let mut ada = tagged_item("Ada <Lovelace>", DueState::Overdue, Some(1_699_963_200));
+        ada.id = id(1);
+        let mut grace = tagged_item("Grace & Co", DueState::Today, Some(1_700_049_600));
+        grace.id = id(2);
+        grace.tags = vec![];
+        let mut alan = tagged_item("Alan", DueState::Soon, Some(1_700_222_400));
+        alan.id = id(3);
+        let output = ReminderOutputDto {
+            overdue: vec![ada.clone()],
+            today: vec![grace],
+            soon: vec![alan],
+            dates_today: vec![DateReminderItemDto {
+                contact_id: ada.id,
+                display_name: ada.display_name.clone(),
+                kind: ContactDateKind::Birthday,
+                label: None,
+                month: 11,
+                day: 15,
+                year: None,
+                days_until: None,
+                also_due: Some(DueState::Overdue),
+            }],
+            dates_upcoming: vec![DateReminderItemDto {
+                contact_id: id(4),
+                display_name: "Tim".to_string(),
+                kind: ContactDateKind::Custom,
+                label: Some("Anniversary".to_string()),
+                month: 11,
+                day: 18,
+                year: None,
+                days_until: Some(3),
+                also_due: None,
+            }],
+            follow_ups: vec![],
+            week: vec![],
+        };
+        let picks = vec![RandomContactPick {
+            id: id(5),
+            display_name: "Margaret".to_string(),
+        }];
+        (output, picks)
+    }
+
+    #[test]
+    fn email_html_matches_snapshot() {
+        let (output, picks) = snapshot_output();
+        assert_eq!(
+            email_html(&output, &picks, None, &Clock::default()),
+            include_str!("snapshots/email.html").trim_end()
+        );
+    }
+
+    #[test]
+    fn email_body_matches_snapshot() {
+        let (output, picks) = snapshot_output();
+        assert_eq!(
+            email_body(&output, &picks, None, &Clock::default()),
+            include_str!("snapshots/email.txt")
+        );
+    }
+
+    #[test]
+    fn max_per_bucket_caps_email_text_and_html() {
+        let mut output = overdue_output(&[("Grace", 30), ("Ada", 10), ("Hedy", 20)]);
+        sort_by_urgency(&mut output);
+
+        let body = email_body(&output, &[], Some(1), &Clock::default());
+        assert!(body.starts_with("Overdue (3)\n"));
+        assert!(body.contains("  Ada  "));
+        assert!(!body.contains("Hedy"));
+        assert!(body.contains("\n  … and 2 more (run `knotter list --filter due:overdue`)\n"));
+
+        let html = email_html(&output, &[], Some(1), &Clock::default());
+        assert!(html.contains("<h3>Overdue (3)</h3>"));
+        assert!(!html.contains("Hedy"));
+        assert!(html.contains("<li>… and 2 more (run `knotter list --filter due:overdue`)</li>"));
+        assert!(!email_html(&output, &[], None, &Clock::default()).contains("more (run"));
+    }
+}
+
+#[test]
+fn notification_body_counts_down_upcoming_dates() {
+    let output = ReminderOutputDto {
+        overdue: vec![],
+        today: vec![],
+        soon: vec![],
+        dates_today: vec![],
+        dates_upcoming: vec![DateReminderItemDto {
+            contact_id: ContactId::new(),
+            display_name: "Grace".to_string(),
+            kind: ContactDateKind::Birthday,
+            label: None,
+            month: 1,
+            day: 3,
+            year: None,
+            days_until: Some(9),
+            also_due: None,
+        }],
+        follow_ups: vec![],
+        week: vec![],
+    };
+
+    let body = notification_body(&output, &[], 5, None);
+    assert!(body.contains("Dates upcoming (1): Grace (Birthday, in 9 days)"));
 }
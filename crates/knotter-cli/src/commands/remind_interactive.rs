@@ -0,0 +1,291 @@
+//! `remind --interactive`: after the buckets are printed, step through the due
+//! contacts and touch, snooze, or skip each one. Every action goes through the
+//! `touch` and `schedule` commands and is saved as soon as it is chosen, so
+//! quitting early (`q`, end of input, or Ctrl+C at a prompt) keeps whatever was
+//! already applied.
+
+use crate::commands::interactions::{record_touch, TouchArgs};
+use crate::commands::schedule::{apply_schedule, ScheduleArgs};
+use crate::commands::Context;
+use anyhow::Result;
+use knotter_core::domain::ContactId;
+use knotter_core::dto::ContactListItemDto;
+use std::io::{self, BufRead, Write};
+
+/// Used when the snooze prompt is left blank.
+const DEFAULT_SNOOZE: &str = "+1w";
+
+#[derive(Debug, Default, PartialEq, Eq)]
+pub(crate) struct SessionSummary {
+    pub touched: usize,
+    pub snoozed: usize,
+    pub skipped: usize,
+}
+
+/// Prompts on `prompt` (stderr in the real command) and reads answers from
+/// `input`; results are printed to stdout like the commands they come from.
+pub(crate) fn run(
+    ctx: &Context<'_>,
+    due: &[ContactListItemDto],
+    input: &mut impl BufRead,
+    prompt: &mut impl Write,
+) -> Result<SessionSummary> {
+    let mut summary = SessionSummary::default();
+    for (index, item) in due.iter().enumerate() {
+        loop {
+            let due_at = item
+                .next_touchpoint_at
+                .map(|ts| ctx.clock.format_timestamp_datetime(ts))
+                .unwrap_or_else(|| "unscheduled".to_string());
+            let question = format!(
+                "[{}/{}] {} (due {due_at}) - [t]ouch, [s]nooze, [x] skip, [q]uit",
+                index + 1,
+                due.len(),
+                item.display_name
+            );
+            let Some(answer) = ask(input, prompt, &question)? else {
+                return Ok(summary);
+            };
+            match answer.to_ascii_lowercase().as_str() {
+                "t" | "touch" => {
+                    let Some(note) = ask(input, prompt, "note (optional)")? else {
+                        return Ok(summary);
+                    };
+                    if apply(prompt, || touch(ctx, item.id, note))? {
+                        summary.touched += 1;
+                        break;
+                    }
+                }
+                "s" | "snooze" => {
+                    let question =
+                        format!("snooze for, e.g. 3d, 2w, next-monday [{DEFAULT_SNOOZE}]");
+                    let Some(duration) = ask(input, prompt, &question)? else {
+                        return Ok(summary);
+                    };
+                    if apply(prompt, || snooze(ctx, item.id, &duration))? {
+                        summary.snoozed += 1;
+                        break;
+                    }
+                }
+                "x" | "skip" => {
+                    summary.skipped += 1;
+                    break;
+                }
+                "q" | "quit" => return Ok(summary),
+                _ => writeln!(prompt, "choose t, s, x, or q")?,
+            }
+        }
+    }
+    Ok(summary)
+}
+
+/// The trimmed answer, or `None` at end of input or when the read was
+/// interrupted.
+fn ask(
+    input: &mut impl BufRead,
+    prompt: &mut impl Write,
+    question: &str,
+) -> Result<Option<String>> {
+    write!(prompt, "{question}: ")?;
+    prompt.flush()?;
+    let mut answer = String::new();
+    match input.read_line(&mut answer) {
+        Ok(0) => {
+            writeln!(prompt)?;
+            Ok(None)
+        }
+        Ok(_) => Ok(Some(answer.trim().to_string())),
+        Err(err) if err.kind() == io::ErrorKind::Interrupted => {
+            writeln!(prompt)?;
+            Ok(None)
+        }
+        Err(err) => Err(err.into()),
+    }
+}
+
+/// Runs one action; a failure (a bad snooze date, say) is reported and the
+/// same contact is asked about again.
+fn apply(prompt: &mut impl Write, action: impl FnOnce() -> Result<String>) -> Result<bool> {
+    match action() {
+        Ok(line) => {
+            println!("{line}");
+            Ok(true)
+        }
+        Err(err) => {
+            writeln!(prompt, "error: {err}")?;
+            Ok(false)
+        }
+    }
+}
+
+fn touch(ctx: &Context<'_>, id: ContactId, note: String) -> Result<String> {
+    let touched = record_touch(
+        ctx,
+        TouchArgs {
+            id: id.to_string(),
+            kind: None,
+            when: None,
+            note: (!note.is_empty()).then_some(note),
+            template: Default::default(),
+            follow_up_at: None,
+            // Handling a reminder means it is done for this cycle, whatever
+            // interactions.auto_reschedule says.
+            reschedule: true,
+            no_reschedule: false,
+            force: false,
+            dry_run: false,
+        },
+    )?;
+    let next = ctx
+        .store
+        .contacts()
+        .get(id)?
+        .and_then(|contact| contact.next_touchpoint_at)
+        .map(|at| format!("next {}", ctx.clock.format_timestamp_datetime(at)))
+        .unwrap_or_else(|| "no next touchpoint".to_string());
+    Ok(format!("{} ({next})", touched.summary))
+}
+
+fn snooze(ctx: &Context<'_>, id: ContactId, duration: &str) -> Result<String> {
+    let scheduled = apply_schedule(
+        ctx,
+        ScheduleArgs {
+            id: id.to_string(),
+            date: snooze_date(duration),
+            time: None,
+            jitter: None,
+            dry_run: false,
+        },
+    )?;
+    Ok(scheduled.summary)
+}
+
+/// `schedule --at` input for a snooze answer: a bare `3d` or `2w` means from
+/// today (`+3d`), and anything else (`next-monday`, a date) is passed through.
+pub(crate) fn snooze_date(answer: &str) -> String {
+    let answer = answer.trim();
+    if answer.is_empty() {
+        return DEFAULT_SNOOZE.to_string();
+    }
+    let bare_offset = answer
+        .strip_suffix(['d', 'w', 'm', 'y'])
+        .is_some_and(|count| !count.is_empty() && count.chars().all(|ch| ch.is_ascii_digit()));
+    if bare_offset {
+        format!("+{answer}")
+    } else {
+        answer.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use knotter_config::AppConfig;
+    use knotter_core::rules::DueState;
+    use knotter_core::time::Clock;
+    use knotter_store::repo::ContactNew;
+    use knotter_store::Store;
+
+    fn due_item(id: ContactId, name: &str, at: i64) -> ContactListItemDto {
+        ContactListItemDto {
+            id,
+            display_name: name.to_string(),
+            due_state: DueState::Overdue,
+            next_touchpoint_at: Some(at),
+            archived_at: None,
+            archive_reason: None,
+            muted_until: None,
+            tags: Vec::new(),
+            last_interaction_at: None,
+            last_interaction_kind: None,
+            organization: None,
+            priority: 0,
+        }
+    }
+
+    #[test]
+    fn snooze_answers_become_schedule_dates() {
+        assert_eq!(snooze_date(""), "+1w");
+        assert_eq!(snooze_date("3d"), "+3d");
+        assert_eq!(snooze_date(" 2w "), "+2w");
+        assert_eq!(snooze_date("+10d"), "+10d");
+        assert_eq!(snooze_date("next-monday"), "next-monday");
+        assert_eq!(snooze_date("2030-01-01"), "2030-01-01");
+        assert_eq!(snooze_date("d"), "d");
+    }
+
+    #[test]
+    fn session_applies_each_choice_and_stops_at_quit() {
+        let store = Store::open_in_memory().expect("open store");
+        store.migrate().expect("migrate");
+        let config = AppConfig::default();
+        let ctx = Context {
+            store: &store,
+            json: false,
+            config: &config,
+            clock: Clock::default(),
+        };
+        let past = 1_600_000_000;
+        let mut due = Vec::new();
+        for name in ["Ada", "Grace", "Linus", "Ken"] {
+            let contact = store
+                .contacts()
+                .create(
+                    past,
+                    ContactNew {
+                        display_name: name.to_string(),
+                        email: None,
+                        phone: None,
+                        handle: None,
+                        timezone: None,
+                        next_touchpoint_at: Some(past),
+                        cadence_days: Some(30),
+                        archived_at: None,
+                        organization: None,
+                    },
+                )
+                .expect("create contact");
+            due.push(due_item(contact.id, name, past));
+        }
+
+        // Touch Ada with a note, fumble then snooze Grace, skip Linus, quit at Ken.
+        let mut input = io::Cursor::new("t\ncalled\n?\ns\nnot a date\ns\n3d\nx\nq\n");
+        let mut prompts = Vec::new();
+        let summary = run(&ctx, &due, &mut input, &mut prompts).expect("session");
+        assert_eq!(
+            summary,
+            SessionSummary {
+                touched: 1,
+                snoozed: 1,
+                skipped: 1,
+            }
+        );
+        let prompts = String::from_utf8(prompts).expect("utf8");
+        assert!(prompts.contains("choose t, s, x, or q"));
+        assert!(prompts.contains("error: "));
+        assert_eq!(prompts.matches("[4/4]").count(), 1);
+
+        let contact = |index: usize| {
+            store
+                .contacts()
+                .get(due[index].id)
+                .expect("get")
+                .expect("contact")
+        };
+        let notes = store
+            .interactions()
+            .list_for_contact(due[0].id, 10, 0)
+            .expect("interactions");
+        assert_eq!(notes.len(), 1);
+        assert_eq!(notes[0].note, "called");
+        assert!(contact(0).next_touchpoint_at.expect("rescheduled") > past);
+        assert!(contact(1).next_touchpoint_at.expect("snoozed") > past);
+        assert_eq!(contact(2).next_touchpoint_at, Some(past));
+        assert_eq!(contact(3).next_touchpoint_at, Some(past));
+
+        // End of input quits quietly, too.
+        let mut input = io::Cursor::new("");
+        let summary = run(&ctx, &due[3..], &mut input, &mut Vec::new()).expect("eof");
+        assert_eq!(summary, SessionSummary::default());
+    }
+}
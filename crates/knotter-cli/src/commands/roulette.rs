@@ -0,0 +1,155 @@
+use crate::commands::{print_json, Context};
+use crate::error::invalid_input;
+use crate::util::snippet_from_text;
+use anyhow::Result;
+use clap::Args;
+use knotter_core::domain::{ContactId, InteractionKind};
+use knotter_core::filter::{parse_filter, ArchivedSelector, MutedSelector};
+use knotter_core::rules::{pick_weight, weighted_sample, PickRng};
+use knotter_store::query::ContactQuery;
+use knotter_store::repo::InteractionNew;
+use serde::Serialize;
+
+const NOTE_SNIPPET_LEN: usize = 80;
+const SECONDS_PER_DAY: i64 = 86_400;
+
+#[derive(Debug, Args)]
+pub struct RouletteArgs {
+    #[arg(
+        short = 'n',
+        long,
+        default_value_t = 3,
+        help = "How many contacts to pick"
+    )]
+    pub count: usize,
+    #[arg(
+        long,
+        default_value_t = 30,
+        help = "Skip contacts with an interaction in the last N days"
+    )]
+    pub min_gap_days: i64,
+    #[arg(long, help = "Only pick from contacts matching this filter")]
+    pub filter: Option<String>,
+    #[arg(long, help = "Log a \"reached-out\" interaction for each pick")]
+    pub touch: bool,
+}
+
+#[derive(Debug, Serialize)]
+struct RoulettePickDto {
+    id: ContactId,
+    display_name: String,
+    last_interaction_at: Option<i64>,
+    latest_note: Option<String>,
+    touched: bool,
+}
+
+pub fn roulette(ctx: &Context<'_>, args: RouletteArgs) -> Result<()> {
+    if args.min_gap_days < 0 {
+        return Err(invalid_input("--min-gap-days must be zero or more"));
+    }
+
+    let filter_text = args.filter.unwrap_or_default();
+    let parsed = parse_filter(&filter_text)?;
+    let mut query = ContactQuery::from_filter(&parsed)?;
+    if query.archived == Some(ArchivedSelector::Archived) {
+        return Err(invalid_input("roulette only picks active contacts"));
+    }
+    query.archived = Some(ArchivedSelector::Active);
+    if query.muted.is_none() {
+        query.muted = Some(MutedSelector::Unmuted);
+    }
+
+    let now = ctx.clock.now_utc();
+    let contacts = ctx.store.contacts().list_contacts(
+        &query,
+        now,
+        ctx.config.due_soon_days,
+        ctx.clock.local_offset(),
+    )?;
+    let ids: Vec<ContactId> = contacts.iter().map(|contact| contact.id).collect();
+    let last_interactions = ctx
+        .store
+        .interactions()
+        .latest_occurred_at_for_contacts(&ids)?;
+
+    let cutoff = now - args.min_gap_days * SECONDS_PER_DAY;
+    let candidates = contacts
+        .into_iter()
+        .filter_map(|contact| {
+            let last = last_interactions.get(&contact.id).copied();
+            if last.is_some_and(|ts| ts > cutoff) {
+                return None;
+            }
+            Some(((contact, last), pick_weight(last, now)))
+        })
+        .collect();
+    let picks = weighted_sample(candidates, args.count, &mut PickRng::from_entropy());
+
+    let pick_ids: Vec<ContactId> = picks.iter().map(|(contact, _)| contact.id).collect();
+    let mut interactions = ctx.store.interactions().list_for_contacts(&pick_ids)?;
+
+    if args.touch && !picks.is_empty() {
+        let reschedule = ctx.config.interactions.auto_reschedule;
+        let kind = InteractionKind::other("reached-out")?;
+        let tx = ctx.store.connection().unchecked_transaction()?;
+        let repo = knotter_store::repo::InteractionsRepo::new(&tx);
+        for id in &pick_ids {
+            repo.add_with_reschedule_in_tx(
+                now,
+                InteractionNew {
+                    contact_id: *id,
+                    occurred_at: now,
+                    created_at: now,
+                    kind: kind.clone(),
+                    note: String::new(),
+                    follow_up_at: None,
+                    direction: None,
+                },
+                reschedule,
+            )?;
+        }
+        tx.commit()?;
+    }
+
+    let items: Vec<RoulettePickDto> = picks
+        .into_iter()
+        .map(|(contact, last)| {
+            let latest_note = interactions
+                .remove(&contact.id)
+                .unwrap_or_default()
+                .into_iter()
+                .find_map(|interaction| {
+                    snippet_from_text(Some(&interaction.note), NOTE_SNIPPET_LEN)
+                });
+            RoulettePickDto {
+                id: contact.id,
+                display_name: contact.display_name,
+                last_interaction_at: last,
+                latest_note,
+                touched: args.touch,
+            }
+        })
+        .collect();
+
+    if ctx.json {
+        return print_json(&items);
+    }
+    if items.is_empty() {
+        println!("no contacts to pick");
+        return Ok(());
+    }
+    for item in &items {
+        let last = item
+            .last_interaction_at
+            .map(|ts| ctx.clock.format_timestamp_date(ts))
+            .unwrap_or_else(|| "never".to_string());
+        println!("{}  {}  last: {}", item.id, item.display_name, last);
+        if let Some(note) = &item.latest_note {
+            println!("  {}", note);
+        }
+    }
+    if args.touch {
+        println!("logged \"reached-out\" for {} contact(s)", items.len());
+    }
+    Ok(())
+}
@@ -1,65 +1,141 @@
-use crate::commands::{print_json, Context};
-use crate::util::{
-    format_timestamp_datetime, now_utc, parse_contact_id, parse_local_date_time_with_precision,
-};
+use crate::commands::dry_run::{print_dry_run, run_contact_change, FieldChange};
+use crate::commands::loops::resolve_jitter;
+use crate::commands::{print_json, resolve_contact_arg, Context};
+use crate::error::invalid_input;
 use anyhow::Result;
 use clap::Args;
-use knotter_core::rules::ensure_future_timestamp_with_precision;
+use knotter_core::domain::Contact;
+use knotter_core::rules::{ensure_future_timestamp_with_precision, jitter_touchpoint};
 use knotter_store::repo::ContactUpdate;
 
 #[derive(Debug, Args)]
 pub struct ScheduleArgs {
+    /// Contact id, or a name, alias, email, or @handle
     pub id: String,
-    #[arg(long = "at")]
+    #[arg(
+        long = "at",
+        help = "Date as YYYY-MM-DD, or relative: today, tomorrow, +10d, +2w, +3m, in 2 weeks, next-monday"
+    )]
     pub date: String,
-    #[arg(long)]
+    #[arg(long, help = "Local time as HH:MM (default: end of day)")]
     pub time: Option<String>,
+    #[arg(
+        long,
+        value_name = "DAYS",
+        help = "Shift the date by up to ±DAYS, the same way each time for this contact"
+    )]
+    pub jitter: Option<i32>,
+    #[arg(long, help = "Show the new schedule without saving it")]
+    pub dry_run: bool,
 }
 
 #[derive(Debug, Args)]
 pub struct ClearScheduleArgs {
+    /// Contact id, or a name, alias, email, or @handle
     pub id: String,
+    #[arg(long, help = "Show what would be cleared without saving it")]
+    pub dry_run: bool,
+}
+
+/// What `apply_schedule` did, ready for `schedule_contact` to print.
+pub(crate) struct ScheduledContact {
+    pub contact: Contact,
+    /// "scheduled <id> at <date time>".
+    pub summary: String,
+    changes: Vec<FieldChange>,
 }
 
 pub fn schedule_contact(ctx: &Context<'_>, args: ScheduleArgs) -> Result<()> {
-    let contact_id = parse_contact_id(&args.id)?;
-    let now = now_utc();
-    let (timestamp, precision) =
-        parse_local_date_time_with_precision(&args.date, args.time.as_deref())?;
-    let timestamp = ensure_future_timestamp_with_precision(now, timestamp, precision)?;
+    let dry_run = args.dry_run;
+    let scheduled = apply_schedule(ctx, args)?;
+    if dry_run {
+        print_dry_run(
+            ctx,
+            &scheduled.contact,
+            &scheduled.summary,
+            &scheduled.changes,
+        )?;
+    } else if ctx.json {
+        print_json(&scheduled.contact)?;
+    } else {
+        println!("{}", scheduled.summary);
+    }
+    Ok(())
+}
+
+/// The `schedule` command without its output, for callers that report the
+/// result their own way.
+pub(crate) fn apply_schedule(ctx: &Context<'_>, args: ScheduleArgs) -> Result<ScheduledContact> {
+    let contact_id = resolve_contact_arg(ctx, &args.id)?;
+    let now = ctx.clock.now_utc();
+    let (timestamp, precision, resolved) =
+        ctx.clock
+            .parse_local_date_time_relative(&args.date, args.time.as_deref(), now)?;
+    let timestamp = ensure_future_timestamp_with_precision(now, timestamp, precision, &ctx.clock)
+        .map_err(|err| {
+        let mut understood = resolved.format("%Y-%m-%d").to_string();
+        if let Some(time) = args.time.as_deref() {
+            understood = format!("{understood} {}", time.trim());
+        }
+        invalid_input(format!("{err} (parsed as {understood})"))
+    })?;
+    let timestamp = match args.jitter {
+        Some(flag) => {
+            let jitter_days = resolve_jitter(Some(flag), ctx.config)?;
+            // A date pulled into the past would be overdue on arrival; keep the
+            // requested one instead.
+            let jittered = jitter_touchpoint(timestamp, jitter_days, &contact_id)?;
+            if jittered > now {
+                jittered
+            } else {
+                timestamp
+            }
+        }
+        None => timestamp,
+    };
 
     let update = ContactUpdate {
         next_touchpoint_at: Some(Some(timestamp)),
         ..Default::default()
     };
 
-    let contact = ctx.store.contacts().update(now, contact_id, update)?;
+    let (contact, changes) = run_contact_change(ctx, contact_id, args.dry_run, || {
+        Ok(ctx.store.contacts().update(now, contact_id, update)?)
+    })?;
 
-    if ctx.json {
-        print_json(&contact)?;
-    } else {
-        println!(
-            "scheduled {} at {}",
-            contact.id,
-            format_timestamp_datetime(timestamp)
-        );
-    }
-    Ok(())
+    let summary = format!(
+        "scheduled {} at {}",
+        contact.id,
+        ctx.clock.format_timestamp_datetime(timestamp)
+    );
+    Ok(ScheduledContact {
+        contact,
+        summary,
+        changes,
+    })
 }
 
 pub fn clear_schedule(ctx: &Context<'_>, args: ClearScheduleArgs) -> Result<()> {
-    let contact_id = parse_contact_id(&args.id)?;
+    let contact_id = resolve_contact_arg(ctx, &args.id)?;
     let update = ContactUpdate {
         next_touchpoint_at: Some(None),
         ..Default::default()
     };
 
-    let contact = ctx.store.contacts().update(now_utc(), contact_id, update)?;
+    let (contact, changes) = run_contact_change(ctx, contact_id, args.dry_run, || {
+        Ok(ctx
+            .store
+            .contacts()
+            .update(ctx.clock.now_utc(), contact_id, update)?)
+    })?;
 
-    if ctx.json {
+    let summary = format!("cleared schedule for {}", contact.id);
+    if args.dry_run {
+        print_dry_run(ctx, &contact, &summary, &changes)?;
+    } else if ctx.json {
         print_json(&contact)?;
     } else {
-        println!("cleared schedule for {}", contact.id);
+        println!("{summary}");
     }
     Ok(())
 }
@@ -0,0 +1,591 @@
+//! `knotter serve`: a small JSON API over the store, for checking who is due
+//! from another device. Requests are handled one at a time on the calling
+//! thread, every route needs the bearer token, and each handler goes through
+//! the same functions as the matching command (`list`, `show`, `remind`,
+//! `touch`, `schedule`).
+
+use crate::commands::Context;
+use crate::error::invalid_input;
+use anyhow::Result;
+use clap::Args;
+
+const DEFAULT_BIND: &str = "127.0.0.1:7111";
+
+#[derive(Debug, Args)]
+#[command(after_help = "Routes (all need `Authorization: Bearer <token>`):
+  GET  /contacts              ?filter= &archived=active|include|only &sort=due|archived &limit= &offset=
+  GET  /contacts/<id>         ?limit= &offset= &all=true
+  GET  /remind                ?soon_days=
+  POST /contacts/<id>/touch   {\"kind\", \"note\", \"when\", \"reschedule\", \"force\"}
+  POST /contacts/<id>/snooze  {\"until\": \"3d\" | \"next-monday\" | \"2030-01-01\"}
+
+Examples:
+  KNOTTER_API_TOKEN=$(openssl rand -hex 16) knotter serve
+  knotter serve --bind 127.0.0.1:8080 --token-env MY_TOKEN")]
+pub struct ServeArgs {
+    #[arg(
+        long,
+        value_name = "ADDR",
+        default_value = DEFAULT_BIND,
+        help = "Address and port to listen on"
+    )]
+    pub bind: String,
+    #[arg(
+        long,
+        value_name = "VAR",
+        default_value = "KNOTTER_API_TOKEN",
+        help = "Environment variable holding the bearer token clients must send"
+    )]
+    pub token_env: String,
+}
+
+#[cfg(not(feature = "serve"))]
+pub fn serve(_ctx: &Context<'_>, _args: ServeArgs) -> Result<()> {
+    Err(invalid_input(
+        "HTTP server unavailable (build with serve feature)",
+    ))
+}
+
+#[cfg(feature = "serve")]
+pub fn serve(ctx: &Context<'_>, args: ServeArgs) -> Result<()> {
+    let token = std::env::var(&args.token_env)
+        .ok()
+        .map(|token| token.trim().to_string())
+        .filter(|token| !token.is_empty())
+        .ok_or_else(|| {
+            invalid_input(format!(
+                "set {} to the bearer token clients must send",
+                args.token_env
+            ))
+        })?;
+    let server = tiny_http::Server::http(args.bind.as_str())
+        .map_err(|err| anyhow::anyhow!("listen on {}: {err}", args.bind))?;
+    let addr = server.server_addr();
+    if let Some(ip) = addr.clone().to_ip() {
+        if !ip.ip().is_loopback() {
+            eprintln!("warning: {ip} is reachable from other machines and the API is plain HTTP");
+        }
+    }
+    eprintln!("listening on http://{addr}");
+    api::run(ctx, &server, &token);
+    Ok(())
+}
+
+#[cfg(feature = "serve")]
+mod api {
+    use crate::commands::interactions::{record_touch, NoteTemplateArgs, TouchArgs};
+    use crate::commands::remind_interactive::snooze_date;
+    use crate::commands::schedule::{apply_schedule, ScheduleArgs};
+    use crate::commands::{resolve_contact_arg, Context, DEFAULT_INTERACTION_LIMIT};
+    use crate::error::{
+        exit_status_for, invalid_input, not_found, EXIT_INVALID_INPUT, EXIT_NOT_FOUND,
+    };
+    use anyhow::Result;
+    use knotter_app::contacts::{self, ArchivedScope, DetailOptions, ListOptions};
+    use knotter_app::reminders::{self, ReminderOptions};
+    use knotter_store::query::{ContactSort, ListPage};
+    use serde::de::DeserializeOwned;
+    use serde::Deserialize;
+    use serde_json::{json, Value};
+    use std::collections::BTreeMap;
+    use std::io::Read;
+    use tiny_http::{Header, Method, Request, Response, Server};
+
+    /// Larger request bodies are refused; touch and snooze bodies are tiny.
+    const MAX_BODY_BYTES: u64 = 64 * 1024;
+
+    /// Serves until the server is unblocked or its listener fails.
+    pub(super) fn run(ctx: &Context<'_>, server: &Server, token: &str) {
+        for mut request in server.incoming_requests() {
+            let (status, body) = handle(ctx, token, &mut request);
+            tracing::debug!("{} {} -> {status}", request.method(), request.url());
+            let body = serde_json::to_vec_pretty(&body).unwrap_or_default();
+            let response = Response::from_data(body)
+                .with_status_code(status)
+                .with_header(
+                    Header::from_bytes("Content-Type", "application/json").expect("static header"),
+                );
+            if let Err(err) = request.respond(response) {
+                tracing::warn!("could not send response: {err}");
+            }
+        }
+    }
+
+    fn handle(ctx: &Context<'_>, token: &str, request: &mut Request) -> (u16, Value) {
+        if !authorized(request, token) {
+            return error(401, "missing or wrong bearer token");
+        }
+        let url = request.url().to_string();
+        let (path, query) = url.split_once('?').unwrap_or((&url, ""));
+        let segments: Vec<&str> = path.split('/').filter(|part| !part.is_empty()).collect();
+        let method = request.method().clone();
+        let result = match (&method, segments.as_slice()) {
+            (Method::Get, ["contacts"]) => list(ctx, Query::parse(query)),
+            (Method::Get, ["contacts", id]) => show(ctx, id, Query::parse(query)),
+            (Method::Get, ["remind"]) => remind(ctx, Query::parse(query)),
+            (Method::Post, ["contacts", id, action @ ("touch" | "snooze")]) => {
+                if ctx.store.is_readonly() {
+                    return error(403, "the server was started with --readonly");
+                }
+                match *action {
+                    "touch" => read_body(request).and_then(|body| touch(ctx, id, body)),
+                    _ => read_body(request).and_then(|body| snooze(ctx, id, body)),
+                }
+            }
+            (
+                _,
+                ["contacts"] | ["contacts", _] | ["remind"] | ["contacts", _, "touch" | "snooze"],
+            ) => {
+                return error(405, &format!("{method} is not allowed on {path}"));
+            }
+            _ => return error(404, &format!("no route for {path}")),
+        };
+        match result {
+            Ok(body) => (200, body),
+            Err(err) => {
+                let status = match exit_status_for(&err) {
+                    EXIT_INVALID_INPUT => 400,
+                    EXIT_NOT_FOUND => 404,
+                    _ => 500,
+                };
+                error(status, &err.to_string())
+            }
+        }
+    }
+
+    fn error(status: u16, message: &str) -> (u16, Value) {
+        (status, json!({ "error": message }))
+    }
+
+    /// Compares the whole token even after a mismatch, so response times do
+    /// not give away how much of a guess was right.
+    fn authorized(request: &Request, token: &str) -> bool {
+        let Some(header) = request
+            .headers()
+            .iter()
+            .find(|header| header.field.equiv("Authorization"))
+        else {
+            return false;
+        };
+        let Some(sent) = header.value.as_str().strip_prefix("Bearer ") else {
+            return false;
+        };
+        let (sent, token) = (sent.trim().as_bytes(), token.as_bytes());
+        sent.len() == token.len()
+            && sent
+                .iter()
+                .zip(token)
+                .fold(0u8, |diff, (a, b)| diff | (a ^ b))
+                == 0
+    }
+
+    /// Query parameters, taken one by one so leftovers can be reported as
+    /// unknown instead of silently ignored.
+    struct Query(BTreeMap<String, String>);
+
+    impl Query {
+        fn parse(raw: &str) -> Self {
+            Self(
+                url::form_urlencoded::parse(raw.as_bytes())
+                    .into_owned()
+                    .collect(),
+            )
+        }
+
+        fn take(&mut self, name: &str) -> Option<String> {
+            self.0.remove(name)
+        }
+
+        fn take_parsed<T: std::str::FromStr>(&mut self, name: &str) -> Result<Option<T>> {
+            self.take(name)
+                .map(|raw| {
+                    raw.parse()
+                        .map_err(|_| invalid_input(format!("invalid {name}: {raw:?}")))
+                })
+                .transpose()
+        }
+
+        fn finish(self) -> Result<()> {
+            match self.0.keys().next() {
+                Some(name) => Err(invalid_input(format!("unknown query parameter {name}"))),
+                None => Ok(()),
+            }
+        }
+    }
+
+    fn read_body<T: DeserializeOwned + Default>(request: &mut Request) -> Result<T> {
+        let mut body = Vec::new();
+        request
+            .as_reader()
+            .take(MAX_BODY_BYTES + 1)
+            .read_to_end(&mut body)?;
+        if body.len() as u64 > MAX_BODY_BYTES {
+            return Err(invalid_input("request body is too large"));
+        }
+        if body.iter().all(u8::is_ascii_whitespace) {
+            return Ok(T::default());
+        }
+        serde_json::from_slice(&body)
+            .map_err(|err| invalid_input(format!("invalid JSON body: {err}")))
+    }
+
+    fn list(ctx: &Context<'_>, mut query: Query) -> Result<Value> {
+        let mut options = ListOptions::new(ctx.config.due_soon_days);
+        options.filter = query.take("filter").unwrap_or_default();
+        options.archived = match query.take("archived").as_deref() {
+            None | Some("active") => ArchivedScope::Active,
+            Some("include") => ArchivedScope::Include,
+            Some("only") => ArchivedScope::Only,
+            Some(other) => {
+                return Err(invalid_input(format!(
+                    "invalid archived: {other:?} (expected active, include, or only)"
+                )))
+            }
+        };
+        options.sort = match query.take("sort").as_deref() {
+            None | Some("due") => ContactSort::Due,
+            Some("archived") => ContactSort::Archived,
+            Some(other) => {
+                return Err(invalid_input(format!(
+                    "invalid sort: {other:?} (expected due or archived)"
+                )))
+            }
+        };
+        let limit = query.take_parsed::<usize>("limit")?;
+        let offset = query.take_parsed::<usize>("offset")?.unwrap_or(0);
+        options.page = match limit {
+            Some(limit) => Some(ListPage { limit, offset }),
+            None if offset > 0 => return Err(invalid_input("offset needs a limit")),
+            None => None,
+        };
+        query.finish()?;
+        let items = contacts::list(
+            ctx.store,
+            &options,
+            ctx.clock.now_utc(),
+            ctx.clock.local_offset(),
+        )?;
+        Ok(serde_json::to_value(items)?)
+    }
+
+    fn show(ctx: &Context<'_>, id: &str, mut query: Query) -> Result<Value> {
+        let all = query.take_parsed::<bool>("all")?.unwrap_or(false);
+        let limit = query
+            .take_parsed::<i64>("limit")?
+            .unwrap_or(DEFAULT_INTERACTION_LIMIT);
+        let offset = query.take_parsed::<i64>("offset")?.unwrap_or(0);
+        query.finish()?;
+        let id = resolve_contact_arg(ctx, id)?;
+        let options = DetailOptions {
+            limit: (!all).then_some(limit),
+            offset,
+            ..Default::default()
+        };
+        let detail = contacts::detail(ctx.store, id, &options, ctx.clock.now_utc())?
+            .ok_or_else(|| not_found("contact not found"))?;
+        Ok(serde_json::to_value(detail)?)
+    }
+
+    fn remind(ctx: &Context<'_>, mut query: Query) -> Result<Value> {
+        let mut options = ReminderOptions::from_config(ctx.config);
+        if let Some(soon_days) = query.take_parsed::<i64>("soon_days")? {
+            options.soon_days = soon_days;
+        }
+        query.finish()?;
+        let output = reminders::compute(
+            ctx.store,
+            &options,
+            ctx.clock.now_utc(),
+            ctx.clock.local_offset(),
+        )?;
+        Ok(serde_json::to_value(output)?)
+    }
+
+    #[derive(Debug, Default, Deserialize)]
+    #[serde(default, deny_unknown_fields)]
+    struct TouchBody {
+        kind: Option<String>,
+        note: Option<String>,
+        /// Local date/time, like `touch --when`.
+        when: Option<String>,
+        /// `None` follows `interactions.auto_reschedule`.
+        reschedule: Option<bool>,
+        force: bool,
+    }
+
+    fn touch(ctx: &Context<'_>, id: &str, body: TouchBody) -> Result<Value> {
+        let touched = record_touch(
+            ctx,
+            TouchArgs {
+                id: id.to_string(),
+                kind: body.kind,
+                when: body.when,
+                note: body.note,
+                template: NoteTemplateArgs::default(),
+                follow_up_at: None,
+                reschedule: body.reschedule == Some(true),
+                no_reschedule: body.reschedule == Some(false),
+                force: body.force,
+                dry_run: false,
+            },
+        )?;
+        Ok(serde_json::to_value(touched.dto)?)
+    }
+
+    #[derive(Debug, Default, Deserialize)]
+    #[serde(default, deny_unknown_fields)]
+    struct SnoozeBody {
+        /// What the `remind --interactive` snooze prompt takes; empty means a week.
+        until: String,
+    }
+
+    fn snooze(ctx: &Context<'_>, id: &str, body: SnoozeBody) -> Result<Value> {
+        let scheduled = apply_schedule(
+            ctx,
+            ScheduleArgs {
+                id: id.to_string(),
+                date: snooze_date(&body.until),
+                time: None,
+                jitter: None,
+                dry_run: false,
+            },
+        )?;
+        Ok(serde_json::to_value(scheduled.contact)?)
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use knotter_config::AppConfig;
+        use knotter_core::time::{now_utc, Clock};
+        use knotter_store::repo::ContactNew;
+        use knotter_store::Store;
+        use std::io::Write;
+        use std::net::{SocketAddr, TcpStream};
+        use std::sync::{mpsc, Arc};
+
+        const TOKEN: &str = "s3cret";
+
+        /// Sends one request and returns the status and parsed JSON body.
+        fn call(
+            addr: SocketAddr,
+            method: &str,
+            path: &str,
+            token: Option<&str>,
+            body: &str,
+        ) -> (u16, Value) {
+            let mut stream = TcpStream::connect(addr).expect("connect");
+            let auth = token
+                .map(|token| format!("Authorization: Bearer {token}\r\n"))
+                .unwrap_or_default();
+            write!(
+                stream,
+                "{method} {path} HTTP/1.1\r\nHost: localhost\r\n{auth}Content-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            )
+            .expect("write request");
+            let mut response = String::new();
+            stream.read_to_string(&mut response).expect("read response");
+            let status = response
+                .split_whitespace()
+                .nth(1)
+                .and_then(|code| code.parse().ok())
+                .expect("status code");
+            let (_, body) = response.split_once("\r\n\r\n").expect("body");
+            (status, serde_json::from_str(body).expect("json body"))
+        }
+
+        #[test]
+        fn routes_need_the_token_and_reuse_the_commands() {
+            let server = Arc::new(Server::http("127.0.0.1:0").expect("bind"));
+            let addr = server.server_addr().to_ip().expect("ip address");
+            let (ids_tx, ids_rx) = mpsc::channel();
+            let serving = {
+                let server = Arc::clone(&server);
+                std::thread::spawn(move || {
+                    let store = Store::open_in_memory().expect("open store");
+                    store.migrate().expect("migrate");
+                    let now = now_utc();
+                    let mut ids = Vec::new();
+                    for (name, due) in [("Ada", now - 86_400), ("Grace", now + 30 * 86_400)] {
+                        let contact = store
+                            .contacts()
+                            .create(
+                                now - 10 * 86_400,
+                                ContactNew {
+                                    display_name: name.to_string(),
+                                    email: None,
+                                    phone: None,
+                                    handle: None,
+                                    timezone: None,
+                                    next_touchpoint_at: Some(due),
+                                    cadence_days: Some(14),
+                                    archived_at: None,
+                                    organization: None,
+                                },
+                            )
+                            .expect("create contact");
+                        ids.push(contact.id.to_string());
+                    }
+                    ids_tx.send(ids).expect("send ids");
+                    let config = AppConfig::default();
+                    let ctx = Context {
+                        store: &store,
+                        json: true,
+                        config: &config,
+                        clock: Clock::default(),
+                    };
+                    run(&ctx, &server, TOKEN);
+                })
+            };
+            let ids = ids_rx.recv().expect("ids");
+            let (ada, grace) = (&ids[0], &ids[1]);
+
+            assert_eq!(call(addr, "GET", "/contacts", None, "").0, 401);
+            assert_eq!(call(addr, "GET", "/contacts", Some("s3cre"), "").0, 401);
+
+            let (status, body) = call(addr, "GET", "/contacts?limit=1&offset=1", Some(TOKEN), "");
+            assert_eq!(status, 200);
+            assert_eq!(body[0]["display_name"], "Grace");
+            let (_, body) = call(
+                addr,
+                "GET",
+                "/contacts?filter=due%3Aoverdue",
+                Some(TOKEN),
+                "",
+            );
+            assert_eq!(body.as_array().map(Vec::len), Some(1));
+            assert_eq!(body[0]["id"], ada.as_str());
+            let (status, body) = call(addr, "GET", "/contacts?sort=name", Some(TOKEN), "");
+            assert_eq!(status, 400);
+            assert!(body["error"].as_str().expect("error").contains("sort"));
+            assert_eq!(
+                call(addr, "GET", "/contacts?limt=1", Some(TOKEN), "").0,
+                400
+            );
+
+            let (status, body) = call(addr, "GET", "/remind", Some(TOKEN), "");
+            assert_eq!(status, 200);
+            assert_eq!(body["overdue"][0]["id"], ada.as_str());
+
+            let (status, body) = call(
+                addr,
+                "POST",
+                &format!("/contacts/{ada}/touch"),
+                Some(TOKEN),
+                r#"{"kind": "call", "note": "caught up", "reschedule": true}"#,
+            );
+            assert_eq!(status, 200, "{body}");
+            assert_eq!(body["note"], "caught up");
+            let (_, body) = call(addr, "GET", &format!("/contacts/{ada}"), Some(TOKEN), "");
+            assert_eq!(body["recent_interactions"][0]["kind"], "call");
+            assert!(body["next_touchpoint_at"].as_i64().expect("rescheduled") > now_utc());
+
+            let (status, body) = call(
+                addr,
+                "POST",
+                &format!("/contacts/{grace}/snooze"),
+                Some(TOKEN),
+                r#"{"until": "3d"}"#,
+            );
+            assert_eq!(status, 200, "{body}");
+            assert!(body["next_touchpoint_at"].as_i64().expect("snoozed") < now_utc() + 4 * 86_400);
+
+            let (status, _) = call(
+                addr,
+                "POST",
+                &format!("/contacts/{grace}/snooze"),
+                Some(TOKEN),
+                "{\"for\": 1}",
+            );
+            assert_eq!(status, 400);
+            let missing = "00000000-0000-0000-0000-000000000000";
+            assert_eq!(
+                call(
+                    addr,
+                    "GET",
+                    &format!("/contacts/{missing}"),
+                    Some(TOKEN),
+                    ""
+                )
+                .0,
+                404
+            );
+            assert_eq!(call(addr, "DELETE", "/contacts", Some(TOKEN), "").0, 405);
+            assert_eq!(call(addr, "GET", "/nope", Some(TOKEN), "").0, 404);
+
+            server.unblock();
+            serving.join().expect("server thread");
+        }
+
+        #[test]
+        fn readonly_server_serves_reads_and_refuses_writes() {
+            let dir = tempfile::TempDir::new().expect("temp dir");
+            let db_path = dir.path().join("knotter.sqlite3");
+            let id = {
+                let store = Store::open(&db_path).expect("open store");
+                store.migrate().expect("migrate");
+                let contact = store
+                    .contacts()
+                    .create(
+                        now_utc(),
+                        ContactNew {
+                            display_name: "Ada".to_string(),
+                            email: None,
+                            phone: None,
+                            handle: None,
+                            timezone: None,
+                            next_touchpoint_at: None,
+                            cadence_days: None,
+                            archived_at: None,
+                            organization: None,
+                        },
+                    )
+                    .expect("create contact");
+                contact.id.to_string()
+            };
+
+            let server = Arc::new(Server::http("127.0.0.1:0").expect("bind"));
+            let addr = server.server_addr().to_ip().expect("ip address");
+            let serving = {
+                let server = Arc::clone(&server);
+                std::thread::spawn(move || {
+                    let store = Store::open_readonly(&db_path).expect("open read-only");
+                    let config = AppConfig::default();
+                    let ctx = Context {
+                        store: &store,
+                        json: true,
+                        config: &config,
+                        clock: Clock::default(),
+                    };
+                    run(&ctx, &server, TOKEN);
+                })
+            };
+
+            let (status, body) = call(addr, "GET", &format!("/contacts/{id}"), Some(TOKEN), "");
+            assert_eq!(status, 200, "{body}");
+            assert_eq!(body["display_name"], "Ada");
+            for action in ["touch", "snooze"] {
+                let (status, body) = call(
+                    addr,
+                    "POST",
+                    &format!("/contacts/{id}/{action}"),
+                    Some(TOKEN),
+                    "{}",
+                );
+                assert_eq!(status, 403, "{body}");
+                assert!(body["error"]
+                    .as_str()
+                    .expect("error")
+                    .contains("--readonly"));
+            }
+            let (_, body) = call(addr, "GET", &format!("/contacts/{id}"), Some(TOKEN), "");
+            assert!(body["recent_interactions"]
+                .as_array()
+                .is_none_or(Vec::is_empty));
+
+            server.unblock();
+            serving.join().expect("server thread");
+        }
+    }
+}
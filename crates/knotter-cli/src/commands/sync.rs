@@ -1,47 +1,89 @@
-use crate::commands::{print_json, Context};
-use crate::error::{invalid_input, not_found};
-use crate::util::{format_interaction_kind, now_utc};
+use crate::commands::audit::audit_dto;
+use crate::commands::dry_run::{diff_snapshots, snapshot};
+use crate::commands::import_interactions::ImportInteractionsArgs;
+use crate::commands::sync_state::SyncCommand;
+use crate::commands::{print_json, write_json, Context};
+use crate::error::{invalid_input, not_found, partial_failure};
+use crate::profile;
+use crate::progress::{ImportProgress, ProgressSink, StderrProgress};
+use crate::secret::resolve_secret;
+use crate::util::snippet_from_text;
 use anyhow::{Context as _, Result};
 use clap::{ArgAction, Args, Subcommand};
 use knotter_config::{
-    ContactSourceKind, EmailAccountTls, EmailMergePolicy, MacosSourceConfig, TelegramMergePolicy,
+    AppConfig, ContactSourceConfig, ContactSourceKind, EmailAccountConfig, EmailAccountTls,
+    EmailLabelTag, EmailMergePolicy, IgnoredIdentities, MacosSourceConfig, SecretSource,
+    TelegramMergePolicy,
 };
 use knotter_core::domain::{
-    normalize_email, normalize_phone_for_match, Contact, ContactId, InteractionKind,
-    MergeCandidateReason, TagName,
+    canonical_phone, is_email_placeholder_name, normalize_email, phone_match_key, Contact,
+    ContactId, InteractionDirection, InteractionKind, MergeCandidateReason, TagName,
+    STAGED_MERGE_ARCHIVE_REASON,
 };
 use knotter_core::dto::{
-    ContactDateDto, ExportContactDto, ExportInteractionDto, ExportMetadataDto, ExportSnapshotDto,
+    AttachmentDto, ContactDateDto, ContactOriginDto, ExportContactDto, ExportInteractionDto,
+    ExportMergeCandidateDto, ExportMetadataDto,
 };
+use knotter_core::export_format::{
+    contact_for_format_version, ensure_known_format_version, FORMAT_VERSION,
+    MERGE_CANDIDATES_FORMAT_VERSION,
+};
+use knotter_core::filter::{parse_filter, ArchivedSelector};
+use knotter_core::rules::{ensure_sane_interaction_timestamp, schedule_next};
+use knotter_core::time::parse_duration;
 use knotter_store::error::StoreErrorKind;
+use knotter_store::query::ContactQuery;
 use knotter_store::repo::contacts::{ContactNew, ContactUpdate};
 use knotter_store::repo::ContactDateNew;
+use knotter_store::repo::ContactField;
 use knotter_store::repo::ContactSource;
 use knotter_store::repo::EmailMessageRecord;
-use knotter_store::repo::{EmailOps, TelegramAccountNew, TelegramMessageRecord, TelegramSyncState};
+use knotter_store::repo::{
+    EmailOps, MergeCandidateQuery, TelegramAccountNew, TelegramMessageRecord, TelegramSyncState,
+};
 use knotter_sync::carddav::CardDavSource;
-use knotter_sync::email::{fetch_mailbox_headers, EmailAccount, EmailHeader, EmailTls};
+use knotter_sync::email::{
+    fetch_mailbox_headers, fetch_mailbox_headers_with_progress, has_mailbox_wildcard,
+    list_mailboxes, select_mailboxes, EmailAccount, EmailHeader, EmailTls, MailboxSyncResult,
+};
+use knotter_sync::google_csv;
 use knotter_sync::ics::{self, IcsExportOptions};
 use knotter_sync::macos::MacosContactsSource;
+use knotter_sync::parallel::map_bounded;
 use knotter_sync::source::VcfSource;
 use knotter_sync::telegram::{self, TelegramAccount as SyncTelegramAccount, TelegramUser};
 use knotter_sync::vcf;
 use serde::Serialize;
-use std::collections::HashSet;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::fs;
-use std::io::Read;
+use std::io::{self, Read, Write};
 use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use tracing::Span;
 use url::Url;
 
+/// Interaction kind used for notes imported from macOS Contacts.
+const CONTACT_NOTE_KIND: &str = "contacts-note";
+
+/// Source name reported by `import google-csv`.
+const GOOGLE_CSV_SOURCE: &str = "google-csv";
+
 #[derive(Debug, Subcommand)]
 pub enum ImportCommand {
     Vcf(ImportVcfArgs),
+    /// Import contacts.csv from Google Takeout or Google Contacts
+    #[command(after_help = "Examples:
+  knotter import google-csv ~/Downloads/contacts.csv --dry-run
+  knotter import google-csv contacts.csv --groups-as-tags --tag google")]
+    GoogleCsv(ImportGoogleCsvArgs),
     Macos(ImportMacosArgs),
     #[command(name = "carddav", alias = "gmail")]
     Carddav(ImportCarddavArgs),
     Email(ImportEmailArgs),
     Telegram(ImportTelegramArgs),
     Source(ImportSourceArgs),
+    /// Log interactions in bulk from a CSV file
+    Interactions(ImportInteractionsArgs),
 }
 
 #[derive(Debug, Args, Clone)]
@@ -57,16 +99,45 @@ pub struct ImportCommonArgs {
     pub retry_skipped: bool,
     #[arg(long, value_name = "TAG")]
     pub tag: Vec<String>,
+    #[arg(long, help = "Let contact imports replace fields last edited manually")]
+    pub overwrite_manual: bool,
+    #[arg(long, help = "Only print warnings and errors")]
+    pub quiet: bool,
 }
 
 #[derive(Debug, Args)]
 pub struct ImportVcfArgs {
-    pub file: PathBuf,
+    #[arg(
+        value_name = "PATH",
+        required = true,
+        help = "vCard files or directories (searched recursively for *.vcf)"
+    )]
+    pub paths: Vec<PathBuf>,
+    #[arg(
+        long,
+        help = "Match existing contacts by display name + phone when no email match is found"
+    )]
+    pub match_phone_name: bool,
+    #[arg(long, help = "Fail when any file cannot be read or parsed")]
+    pub strict: bool,
+    #[command(flatten)]
+    pub common: ImportCommonArgs,
+}
+
+#[derive(Debug, Args)]
+pub struct ImportGoogleCsvArgs {
+    #[arg(value_name = "PATH", help = "CSV exported in the Google CSV format")]
+    pub path: PathBuf,
     #[arg(
         long,
         help = "Match existing contacts by display name + phone when no email match is found"
     )]
     pub match_phone_name: bool,
+    #[arg(
+        long,
+        help = "Tag contacts with their Google groups (labels), except \"* myContacts\""
+    )]
+    pub groups_as_tags: bool,
     #[command(flatten)]
     pub common: ImportCommonArgs,
 }
@@ -75,6 +146,8 @@ pub struct ImportVcfArgs {
 pub struct ImportMacosArgs {
     #[arg(long)]
     pub group: Option<String>,
+    #[arg(long, help = "Tag contacts with every Contacts group they belong to")]
+    pub groups_as_tags: bool,
     #[command(flatten)]
     pub common: ImportCommonArgs,
 }
@@ -107,6 +180,11 @@ pub struct ImportSourceArgs {
 }
 
 #[derive(Debug, Args)]
+#[command(after_help = "Examples:
+  knotter import email                            every configured account
+  knotter import email --account gmail --limit 200
+  knotter import email --account work --include-cc --dry-run
+  knotter import email --force-uidvalidity-resync --retry-skipped")]
 pub struct ImportEmailArgs {
     #[arg(long, value_name = "ACCOUNT", action = ArgAction::Append)]
     pub account: Vec<String>,
@@ -115,6 +193,17 @@ pub struct ImportEmailArgs {
         help = "Force a full resync on UIDVALIDITY changes (may duplicate touches when Message-ID is missing)"
     )]
     pub force_uidvalidity_resync: bool,
+    #[arg(
+        long,
+        help = "Record \"Email (cc)\" touches for existing contacts on the Cc line (same as include_cc = true)"
+    )]
+    pub include_cc: bool,
+    #[arg(
+        long,
+        default_value_t = DEFAULT_FETCH_JOBS,
+        help = "Fetch up to N mailboxes at once (1 fetches them one by one)"
+    )]
+    pub jobs: usize,
     #[command(flatten)]
     pub common: ImportCommonArgs,
 }
@@ -132,7 +221,17 @@ pub struct ImportTelegramArgs {
 }
 
 #[derive(Debug, Args)]
+#[command(
+    args_conflicts_with_subcommands = true,
+    after_help = "Examples:
+  knotter sync                        import every source and mailbox, apply loops, remind
+  knotter sync --dry-run --no-remind  preview what the imports would change
+  knotter sync --jobs 1 --no-telegram fetch one source at a time, skip Telegram
+  knotter sync email-state ls         show stored IMAP positions"
+)]
 pub struct SyncArgs {
+    #[command(subcommand)]
+    pub command: Option<SyncCommand>,
     #[command(flatten)]
     pub common: ImportCommonArgs,
     #[arg(
@@ -140,19 +239,68 @@ pub struct SyncArgs {
         help = "Force a full resync on UIDVALIDITY changes (may duplicate touches when Message-ID is missing)"
     )]
     pub force_uidvalidity_resync: bool,
+    #[arg(
+        long,
+        default_value_t = DEFAULT_FETCH_JOBS,
+        help = "Fetch up to N contact sources or mailboxes at once (1 fetches them one by one)"
+    )]
+    pub jobs: usize,
     #[arg(long, action = ArgAction::SetTrue)]
     pub no_telegram: bool,
     #[arg(long, action = ArgAction::SetTrue)]
     pub no_loops: bool,
     #[arg(long, action = ArgAction::SetTrue)]
     pub no_remind: bool,
+    #[arg(
+        long,
+        value_name = "PATH",
+        help = "After syncing, export an ICS calendar to PATH"
+    )]
+    pub export_ics: Option<PathBuf>,
+    #[arg(
+        long,
+        value_name = "PATH",
+        help = "After syncing, export a JSON snapshot to PATH"
+    )]
+    pub export_json: Option<PathBuf>,
+    #[arg(
+        long,
+        value_name = "PATH",
+        help = "After syncing, export a vCard file to PATH"
+    )]
+    pub export_vcf: Option<PathBuf>,
+}
+
+/// Exports `sync` can run once its import, loop, and remind steps are done.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SyncExport {
+    Ics,
+    Json,
+    Vcf,
 }
 
-trait SyncRunner {
-    fn import_source(
+impl SyncExport {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Ics => "ics",
+            Self::Json => "json",
+            Self::Vcf => "vcf",
+        }
+    }
+}
+
+/// Network fetches that may run at once during `sync` and `import email`.
+const DEFAULT_FETCH_JOBS: usize = 4;
+
+/// `fetch_source` runs on worker threads and must not touch the store; the
+/// fetched data is handed to `import_source_data` on the calling thread.
+trait SyncRunner: Sync {
+    fn fetch_source(&self, config: &AppConfig, source_name: &str) -> Result<String>;
+    fn import_source_data(
         &self,
         ctx: &Context<'_>,
         source_name: &str,
+        data: String,
         common: &ImportCommonArgs,
     ) -> Result<()>;
     fn import_email(
@@ -160,28 +308,30 @@ trait SyncRunner {
         ctx: &Context<'_>,
         common: &ImportCommonArgs,
         force_uidvalidity_resync: bool,
+        jobs: usize,
     ) -> Result<()>;
     fn import_telegram(&self, ctx: &Context<'_>, common: &ImportCommonArgs) -> Result<()>;
-    fn apply_loops(&self, ctx: &Context<'_>, dry_run: bool) -> Result<()>;
+    fn apply_loops(&self, ctx: &Context<'_>, dry_run: bool, quiet: bool) -> Result<()>;
     fn remind(&self, ctx: &Context<'_>, dry_run: bool) -> Result<()>;
+    fn export(&self, ctx: &Context<'_>, export: SyncExport, out: &Path) -> Result<()>;
 }
 
 struct DefaultSyncRunner;
 
 impl SyncRunner for DefaultSyncRunner {
-    fn import_source(
+    fn fetch_source(&self, config: &AppConfig, source_name: &str) -> Result<String> {
+        fetch_source_vcf(config, source_name, None, false)
+    }
+
+    fn import_source_data(
         &self,
         ctx: &Context<'_>,
         source_name: &str,
+        data: String,
         common: &ImportCommonArgs,
     ) -> Result<()> {
-        let args = ImportSourceArgs {
-            name: source_name.to_string(),
-            password_env: None,
-            password_stdin: false,
-            common: common.clone(),
-        };
-        import_source(ctx, args)
+        let options = source_import_options(ctx.config, source_name, common)?;
+        import_from_vcf_data(ctx, source_name, data, options)
     }
 
     fn import_email(
@@ -189,10 +339,13 @@ impl SyncRunner for DefaultSyncRunner {
         ctx: &Context<'_>,
         common: &ImportCommonArgs,
         force_uidvalidity_resync: bool,
+        jobs: usize,
     ) -> Result<()> {
         let args = ImportEmailArgs {
             account: Vec::new(),
             force_uidvalidity_resync,
+            include_cc: false,
+            jobs,
             common: common.clone(),
         };
         import_email(ctx, args)
@@ -208,14 +361,17 @@ impl SyncRunner for DefaultSyncRunner {
         import_telegram(ctx, args)
     }
 
-    fn apply_loops(&self, ctx: &Context<'_>, dry_run: bool) -> Result<()> {
+    fn apply_loops(&self, ctx: &Context<'_>, dry_run: bool, quiet: bool) -> Result<()> {
         let args = crate::commands::loops::LoopApplyArgs {
             filter: None,
             dry_run,
+            quiet,
             force: false,
             schedule_missing: false,
             no_schedule_missing: false,
             anchor: None,
+            only_tag: Vec::new(),
+            jitter: None,
         };
         crate::commands::loops::apply_loops(ctx, args)
     }
@@ -225,9 +381,51 @@ impl SyncRunner for DefaultSyncRunner {
             soon_days: None,
             notify: false,
             no_notify: dry_run,
+            digest: None,
+            digest_only: false,
+            max_per_bucket: None,
+            force_notify: false,
+            mark_contacted: Vec::new(),
+            interactive: false,
         };
         crate::commands::remind::remind(ctx, args)
     }
+
+    fn export(&self, ctx: &Context<'_>, export: SyncExport, out: &Path) -> Result<()> {
+        let out = Some(out.to_path_buf());
+        match export {
+            SyncExport::Ics => export_ics(
+                ctx,
+                ExportIcsArgs {
+                    out,
+                    include_archived: false,
+                    window_days: None,
+                    expand_cadence: false,
+                    alarm: None,
+                },
+            ),
+            SyncExport::Json => export_json(
+                ctx,
+                ExportJsonArgs {
+                    out,
+                    exclude_archived: false,
+                    include_merges: false,
+                    include_audit: false,
+                    format_version: None,
+                },
+            ),
+            SyncExport::Vcf => export_vcf(
+                ctx,
+                ExportVcfArgs {
+                    out,
+                    filter: None,
+                    include_archived: false,
+                    split_by_tag: false,
+                    out_dir: None,
+                },
+            ),
+        }
+    }
 }
 
 #[derive(Debug, Subcommand)]
@@ -239,24 +437,78 @@ pub enum ExportCommand {
 
 #[derive(Debug, Args)]
 pub struct ExportVcfArgs {
-    #[arg(long)]
+    #[arg(long, conflicts_with = "split_by_tag")]
     pub out: Option<PathBuf>,
+    #[arg(
+        long,
+        help = "Only export contacts matching this filter (same syntax as list)"
+    )]
+    pub filter: Option<String>,
+    #[arg(
+        long,
+        help = "Also export archived contacts (excluded by default unless --filter selects archived:true)"
+    )]
+    pub include_archived: bool,
+    #[arg(
+        long,
+        requires = "out_dir",
+        help = "Write one .vcf per tag into --out-dir, plus untagged.vcf"
+    )]
+    pub split_by_tag: bool,
+    #[arg(long, value_name = "DIR", requires = "split_by_tag")]
+    pub out_dir: Option<PathBuf>,
 }
 
 #[derive(Debug, Args)]
 pub struct ExportIcsArgs {
     #[arg(long)]
     pub out: Option<PathBuf>,
+    #[arg(
+        long,
+        help = "Add archived contacts' birthdays (excluded by default; their touchpoints are never exported)"
+    )]
+    pub include_archived: bool,
     #[arg(long)]
     pub window_days: Option<i64>,
+    #[arg(
+        long,
+        help = "Emit an event for every cadence step in the window, not just the next touchpoint"
+    )]
+    pub expand_cadence: bool,
+    #[arg(
+        long,
+        value_name = "DURATION",
+        help = "Add a reminder this long before each event, e.g. 9h, 1d, or PT9H (overrides exports.ics.alarm)"
+    )]
+    pub alarm: Option<String>,
 }
 
 #[derive(Debug, Args)]
 pub struct ExportJsonArgs {
     #[arg(long)]
     pub out: Option<PathBuf>,
-    #[arg(long)]
+    #[arg(long, help = "Omit archived contacts (included by default)")]
     pub exclude_archived: bool,
+    #[arg(long, help = "Include merge candidates and their decisions")]
+    pub include_merges: bool,
+    #[arg(long, help = "Include each contact's audit trail (see `knotter log`)")]
+    pub include_audit: bool,
+    #[arg(
+        long,
+        value_name = "N",
+        help = "Write an older snapshot format for older knotter installs"
+    )]
+    pub format_version: Option<u32>,
+}
+
+/// Which contacts an export covers. Every format resolves its flags into one
+/// of these so contact selection lives in `load_export_contacts`.
+#[derive(Debug, Clone, Default)]
+struct ExportScope {
+    include_archived: bool,
+    /// A `list`-style filter; an `archived:` selector in it overrides
+    /// `include_archived`.
+    filter: Option<String>,
 }
 
 #[derive(Debug, Serialize)]
@@ -266,6 +518,23 @@ struct ExportReport {
     output: Option<String>,
 }
 
+/// `export vcf --split-by-tag`: `count` is distinct contacts across all files.
+#[derive(Debug, Serialize)]
+struct SplitExportReport {
+    format: String,
+    count: usize,
+    output_dir: String,
+    files: Vec<SplitExportFile>,
+}
+
+#[derive(Debug, Serialize)]
+struct SplitExportFile {
+    /// `None` for `untagged.vcf`.
+    tag: Option<String>,
+    path: String,
+    count: usize,
+}
+
 #[derive(Debug, Clone)]
 struct ImportOptions {
     dry_run: bool,
@@ -273,6 +542,10 @@ struct ImportOptions {
     retry_skipped: bool,
     extra_tags: Vec<TagName>,
     match_phone_name: bool,
+    overwrite_manual: bool,
+    /// Store each contact's vCard `NOTE` as an interaction.
+    import_notes: bool,
+    quiet: bool,
 }
 
 #[derive(Debug, Serialize)]
@@ -282,39 +555,243 @@ struct EmailImportReport {
     messages_seen: usize,
     messages_imported: usize,
     contacts_created: usize,
+    /// New contacts given `default_cadence_days` (`apply_default_cadence_on_import`).
+    default_cadence_applied: usize,
     contacts_merged: usize,
     contacts_matched: usize,
     merge_candidates_created: usize,
     touches_recorded: usize,
+    /// "Email (cc)" touches for contacts on a message's Cc line.
+    cc_touches_recorded: usize,
+    /// Address-only display names replaced by a header name (`update_names`).
+    names_updated: usize,
+    /// Messages skipped because their date is before 1990 or too far ahead.
+    messages_out_of_range: usize,
+    /// Messages only between the user's own addresses (account identities and
+    /// `contacts.ignore_identities`).
+    identities_skipped: usize,
+    /// Accounts whose `mailboxes` contain a wildcard, with what it matched.
+    mailbox_expansions: Vec<MailboxExpansionReport>,
     warnings: Vec<String>,
     dry_run: bool,
 }
 
+#[derive(Debug, Serialize)]
+struct MailboxExpansionReport {
+    account: String,
+    /// Server mailboxes a wildcard matched and that are imported.
+    expanded: Vec<String>,
+    /// Server mailboxes a wildcard matched but `exclude_mailboxes` dropped.
+    excluded: Vec<String>,
+}
+
 #[derive(Debug, Serialize)]
 struct TelegramImportReport {
     accounts: usize,
     users_seen: usize,
     contacts_created: usize,
+    /// New contacts given `default_cadence_days` (`apply_default_cadence_on_import`).
+    default_cadence_applied: usize,
     contacts_matched: usize,
     contacts_merged: usize,
     merge_candidates_created: usize,
+    /// Contacts given at least one `tg-*` tag (`metadata_tags = true`).
+    metadata_tagged: usize,
     messages_seen: usize,
     messages_imported: usize,
     touches_recorded: usize,
+    /// Messages skipped because their date is before 1990 or too far ahead.
+    messages_out_of_range: usize,
+    /// Users whose username or phone is in `contacts.ignore_identities`.
+    identities_skipped: usize,
     warnings: Vec<String>,
     dry_run: bool,
 }
 
 pub fn import_vcf(ctx: &Context<'_>, args: ImportVcfArgs) -> Result<()> {
-    let data = fs::read_to_string(&args.file)
-        .with_context(|| format!("read vcf file {}", args.file.display()))?;
     let options = build_import_options(&args.common, None, args.match_phone_name)?;
-    import_from_vcf_data(ctx, "vcard", data, options)
+    let files = collect_vcf_files(&args.paths)?;
+    if files.is_empty() {
+        return Err(invalid_input("no .vcf files found"));
+    }
+    let batch = files.len() > 1;
+    // Skipping a bad file only makes sense among others; a single named
+    // file that fails to parse is the whole import failing.
+    let single_file = args.paths.len() == 1 && !args.paths[0].is_dir();
+
+    let mut report = vcf::ImportReport {
+        created: 0,
+        updated: 0,
+        unchanged: 0,
+        skipped: 0,
+        merge_candidates_created: 0,
+        group_tags_attached: 0,
+        notes_imported: 0,
+        default_cadence_applied: 0,
+        identities_skipped: 0,
+        warnings: Vec::new(),
+        dry_run: options.dry_run,
+        files: Vec::new(),
+    };
+    // Parse everything first so `--strict` fails before anything is written.
+    let parsed_files = files
+        .iter()
+        .map(|path| (path.display().to_string(), read_vcf_file(path)))
+        .collect::<Vec<_>>();
+    if args.strict || single_file {
+        if let Some((label, Err(err))) = parsed_files.iter().find(|(_, parsed)| parsed.is_err()) {
+            return Err(invalid_input(format!("{label}: {err}")));
+        }
+    }
+
+    let total = parsed_files.len();
+    let mut remaining = options.limit;
+    for (index, (label, parsed)) in parsed_files.into_iter().enumerate() {
+        if remaining == Some(0) {
+            report.warnings.push(format!(
+                "limit reached; skipped {} remaining files",
+                total - index
+            ));
+            break;
+        }
+
+        let parsed = match parsed {
+            Ok(parsed) => parsed,
+            Err(err) => {
+                report.warnings.push(format!("{label}: {err}"));
+                report.files.push(vcf::ImportFileReport {
+                    path: label,
+                    created: 0,
+                    updated: 0,
+                    unchanged: 0,
+                    skipped: 0,
+                    merge_candidates_created: 0,
+                    error: Some(err.to_string()),
+                });
+                continue;
+            }
+        };
+
+        // The limit spans the whole batch, so each file only gets what is left.
+        let processed = remaining.map_or(parsed.contacts.len(), |limit| {
+            parsed.contacts.len().min(limit)
+        });
+        let file_options = ImportOptions {
+            limit: remaining,
+            ..options.clone()
+        };
+        let file_report = import_contacts(ctx, "vcard", parsed, file_options)?;
+        remaining = remaining.map(|limit| limit - processed);
+
+        report.created += file_report.created;
+        report.updated += file_report.updated;
+        report.unchanged += file_report.unchanged;
+        report.skipped += file_report.skipped;
+        report.merge_candidates_created += file_report.merge_candidates_created;
+        report.default_cadence_applied += file_report.default_cadence_applied;
+        report.identities_skipped += file_report.identities_skipped;
+        if batch {
+            report.warnings.extend(
+                file_report
+                    .warnings
+                    .into_iter()
+                    .map(|warning| format!("{label}: {warning}")),
+            );
+        } else {
+            report.warnings.extend(file_report.warnings);
+        }
+        report.files.push(vcf::ImportFileReport {
+            path: label,
+            created: file_report.created,
+            updated: file_report.updated,
+            unchanged: file_report.unchanged,
+            skipped: file_report.skipped,
+            merge_candidates_created: file_report.merge_candidates_created,
+            error: None,
+        });
+    }
+
+    emit_import_report(
+        ctx,
+        "vcard",
+        report,
+        args.common.quiet,
+        &mut io::stdout(),
+        &mut io::stderr(),
+    )
+}
+
+fn collect_vcf_files(paths: &[PathBuf]) -> Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    for path in paths {
+        let metadata = fs::metadata(path)
+            .map_err(|_| not_found(format!("vcf path {} not found", path.display())))?;
+        if metadata.is_dir() {
+            collect_vcf_dir(path, &mut files)?;
+        } else {
+            files.push(path.clone());
+        }
+    }
+    Ok(files)
+}
+
+fn collect_vcf_dir(dir: &Path, files: &mut Vec<PathBuf>) -> Result<()> {
+    let mut entries = fs::read_dir(dir)
+        .with_context(|| format!("read vcf directory {}", dir.display()))?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<std::io::Result<Vec<_>>>()
+        .with_context(|| format!("read vcf directory {}", dir.display()))?;
+    entries.sort();
+    for path in entries {
+        if path.is_dir() {
+            collect_vcf_dir(&path, files)?;
+        } else if path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("vcf"))
+        {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+fn read_vcf_file(path: &Path) -> Result<vcf::ParsedVcf> {
+    let bytes = fs::read(path).context("read failed")?;
+    let data = String::from_utf8(bytes).map_err(|_| anyhow::anyhow!("not valid UTF-8"))?;
+    let parsed = vcf::parse_vcf(&data)?;
+    if parsed.contacts.is_empty() && parsed.skipped == 0 {
+        anyhow::bail!("no vCard entries found");
+    }
+    Ok(parsed)
+}
+
+pub fn import_google_csv(ctx: &Context<'_>, args: ImportGoogleCsvArgs) -> Result<()> {
+    let options = build_import_options(&args.common, None, args.match_phone_name)?;
+    let bytes = fs::read(&args.path)
+        .map_err(|_| not_found(format!("csv file {} not found", args.path.display())))?;
+    let data = String::from_utf8(bytes)
+        .map_err(|_| invalid_input(format!("{} is not valid UTF-8", args.path.display())))?;
+    let parsed = google_csv::parse_google_csv(&data, args.groups_as_tags)
+        .map_err(|err| invalid_input(format!("{}: {err}", args.path.display())))?;
+    let quiet = options.quiet;
+    let report = import_contacts(ctx, GOOGLE_CSV_SOURCE, parsed, options)?;
+    emit_import_report(
+        ctx,
+        GOOGLE_CSV_SOURCE,
+        report,
+        quiet,
+        &mut io::stdout(),
+        &mut io::stderr(),
+    )
 }
 
 pub fn import_macos(ctx: &Context<'_>, args: ImportMacosArgs) -> Result<()> {
-    let options = build_import_options(&args.common, None, true)?;
-    let source = MacosContactsSource::new(args.group);
+    let options = ImportOptions {
+        import_notes: true,
+        ..build_import_options(&args.common, None, true)?
+    };
+    let source = MacosContactsSource::new(args.group).with_groups_as_tags(args.groups_as_tags);
     import_from_source(ctx, &source, source.source_name(), options)
 }
 
@@ -331,38 +808,74 @@ pub fn import_carddav(ctx: &Context<'_>, args: ImportCarddavArgs) -> Result<()>
 }
 
 pub fn import_source(ctx: &Context<'_>, args: ImportSourceArgs) -> Result<()> {
-    let source = ctx
-        .config
+    let source_label = configured_source(ctx.config, &args.name)?.name.clone();
+    let options = source_import_options(ctx.config, &source_label, &args.common)?;
+    let data = fetch_source_vcf(
+        ctx.config,
+        &source_label,
+        args.password_env.as_deref(),
+        args.password_stdin,
+    )?;
+    import_from_vcf_data(ctx, &source_label, data, options)
+}
+
+fn configured_source<'a>(config: &'a AppConfig, name: &str) -> Result<&'a ContactSourceConfig> {
+    config
         .contacts
-        .source(&args.name)
-        .ok_or_else(|| not_found(format!("contact source {} not found", args.name)))?;
-    let source_label = source.name.clone();
+        .source(name)
+        .ok_or_else(|| not_found(format!("contact source {} not found", name)))
+}
+
+fn source_import_options(
+    config: &AppConfig,
+    name: &str,
+    common: &ImportCommonArgs,
+) -> Result<ImportOptions> {
+    match &configured_source(config, name)?.kind {
+        ContactSourceKind::Carddav(cfg) => build_import_options(common, cfg.tag.as_deref(), false),
+        ContactSourceKind::Macos(MacosSourceConfig { tag, .. }) => Ok(ImportOptions {
+            import_notes: true,
+            ..build_import_options(common, tag.as_deref(), true)?
+        }),
+    }
+}
 
+/// Reads a configured source's vCard data. Never touches the store, so `sync`
+/// can run it on worker threads.
+fn fetch_source_vcf(
+    config: &AppConfig,
+    name: &str,
+    password_env: Option<&str>,
+    password_stdin: bool,
+) -> Result<String> {
+    let source = configured_source(config, name)?;
     match &source.kind {
         ContactSourceKind::Carddav(cfg) => {
             let username = cfg.username.as_ref().ok_or_else(|| {
-                invalid_input(format!("carddav source {source_label} missing username"))
+                invalid_input(format!("carddav source {} missing username", source.name))
             })?;
-            let password = resolve_password(
-                args.password_env.as_deref(),
-                args.password_stdin,
-                cfg.password_env.as_deref(),
-            )?;
+            let password = resolve_password(password_env, password_stdin, cfg.password.as_ref())?;
             let user_agent = Some(default_user_agent());
             let source =
                 CardDavSource::new(cfg.url.clone(), username.to_string(), password, user_agent);
-            let options = build_import_options(&args.common, cfg.tag.as_deref(), false)?;
-            import_from_source(ctx, &source, &source_label, options)
+            Ok(source.fetch_vcf()?)
         }
-        ContactSourceKind::Macos(MacosSourceConfig { group, tag }) => {
-            let source = MacosContactsSource::new(group.clone());
-            let options = build_import_options(&args.common, tag.as_deref(), true)?;
-            import_from_source(ctx, &source, &source_label, options)
+        ContactSourceKind::Macos(MacosSourceConfig {
+            group,
+            groups_as_tags,
+            ..
+        }) => {
+            let source =
+                MacosContactsSource::new(group.clone()).with_groups_as_tags(*groups_as_tags);
+            Ok(source.fetch_vcf()?)
         }
     }
 }
 
 pub fn import_email(ctx: &Context<'_>, args: ImportEmailArgs) -> Result<()> {
+    if args.jobs == 0 {
+        return Err(invalid_input("--jobs must be at least 1"));
+    }
     let accounts = if args.account.is_empty() {
         ctx.config.contacts.email_accounts.clone()
     } else {
@@ -388,40 +901,53 @@ pub fn import_email(ctx: &Context<'_>, args: ImportEmailArgs) -> Result<()> {
         messages_seen: 0,
         messages_imported: 0,
         contacts_created: 0,
+        default_cadence_applied: 0,
         contacts_merged: 0,
         contacts_matched: 0,
         merge_candidates_created: 0,
         touches_recorded: 0,
+        cc_touches_recorded: 0,
+        names_updated: 0,
+        messages_out_of_range: 0,
+        identities_skipped: 0,
+        mailbox_expansions: Vec::new(),
         warnings: Vec::new(),
         dry_run: args.common.dry_run,
     };
 
     let mut remaining = args.common.limit;
+    // Under `--limit` each mailbox only gets what earlier ones left over,
+    // which is not known until they are imported, so fetch one by one.
+    let mut prefetched = if args.jobs > 1 && remaining.is_none() {
+        let _phase = profile::phase("mailbox prefetch");
+        prefetch_mail(ctx, &accounts, args.jobs)?
+    } else {
+        PrefetchedMail::default()
+    };
 
+    let several_accounts = accounts.len() > 1;
+    let mut sink = StderrProgress::stderr(args.common.quiet);
     let mut stop_all = false;
     for account_cfg in accounts {
         report.accounts += 1;
-        let password =
-            resolve_password(Some(&account_cfg.password_env), false, None).map_err(|err| {
-                invalid_input(format!(
-                    "email account {} password error: {err}",
+        let (account, expansion) = match prefetched.accounts.remove(&account_cfg.name) {
+            Some(account) => account?,
+            None => resolve_email_account(&account_cfg)?,
+        };
+        if let Some(expansion) = expansion {
+            if account.mailboxes.is_empty() {
+                report.warnings.push(format!(
+                    "email account {} has no mailboxes left after wildcard expansion",
                     account_cfg.name
-                ))
-            })?;
-        let tls = match account_cfg.tls {
-            EmailAccountTls::Tls => EmailTls::Tls,
-            EmailAccountTls::StartTls => EmailTls::StartTls,
-            EmailAccountTls::None => EmailTls::None,
-        };
-        let account = EmailAccount {
-            host: account_cfg.host.clone(),
-            port: account_cfg.port,
-            username: account_cfg.username.clone(),
-            password,
-            tls,
-            mailboxes: account_cfg.mailboxes.clone(),
-        };
-        let identities = normalize_identities(&account_cfg.identities, &account_cfg.username);
+                ));
+            }
+            report.mailbox_expansions.push(expansion);
+        }
+        let identities = normalize_identities(
+            &account_cfg.identities,
+            &account_cfg.username,
+            &ctx.config.contacts.ignore_identities,
+        );
         if identities.is_empty() {
             return Err(invalid_input(format!(
                 "email account {} identities are empty; set identities or a valid username email",
@@ -438,6 +964,7 @@ pub fn import_email(ctx: &Context<'_>, args: ImportEmailArgs) -> Result<()> {
                 break;
             }
             report.mailboxes += 1;
+            let _phase = profile::phase(format_args!("mailbox {}/{mailbox}", account_cfg.name));
             let state = ctx
                 .store
                 .email_sync()
@@ -448,7 +975,31 @@ pub fn import_email(ctx: &Context<'_>, args: ImportEmailArgs) -> Result<()> {
                 Some(value) => Some(value),
                 None => None,
             };
-            let mut result = fetch_mailbox_headers(&account, mailbox, last_uid, fetch_limit)?;
+            let label = if several_accounts {
+                format!("{}/{mailbox}", account_cfg.name)
+            } else {
+                mailbox.clone()
+            };
+            let mut progress = ImportProgress::new(label, "headers");
+            let mut result = match prefetched
+                .mailboxes
+                .remove(&(account_cfg.name.clone(), mailbox.clone()))
+            {
+                Some(result) => {
+                    let result = result?;
+                    progress.fetched = result.headers.len();
+                    progress.total = result.headers.len();
+                    result
+                }
+                None => fetch_with_progress(
+                    &account,
+                    mailbox,
+                    last_uid,
+                    fetch_limit,
+                    &mut progress,
+                    &mut sink,
+                )?,
+            };
             let mut skip_mailbox = false;
             if let Some(prev) = state.as_ref().and_then(|s| s.uidvalidity) {
                 if let Some(current) = result.uidvalidity {
@@ -463,11 +1014,13 @@ pub fn import_email(ctx: &Context<'_>, args: ImportEmailArgs) -> Result<()> {
                                     "mailbox {mailbox} uidvalidity changed; forcing resync (missing Message-ID may duplicate touches)"
                                 ));
                                 last_uid = 0;
-                                result = fetch_mailbox_headers(
+                                result = fetch_with_progress(
                                     &account,
                                     mailbox,
                                     last_uid,
                                     fetch_limit,
+                                    &mut progress,
+                                    &mut sink,
                                 )?;
                             } else {
                                 report.warnings.push(format!(
@@ -477,8 +1030,14 @@ pub fn import_email(ctx: &Context<'_>, args: ImportEmailArgs) -> Result<()> {
                             }
                         } else {
                             last_uid = 0;
-                            result =
-                                fetch_mailbox_headers(&account, mailbox, last_uid, fetch_limit)?;
+                            result = fetch_with_progress(
+                                &account,
+                                mailbox,
+                                last_uid,
+                                fetch_limit,
+                                &mut progress,
+                                &mut sink,
+                            )?;
                         }
                     }
                 }
@@ -493,86 +1052,33 @@ pub fn import_email(ctx: &Context<'_>, args: ImportEmailArgs) -> Result<()> {
                 merge_policy: &account_cfg.merge_policy,
                 options: &options,
                 identities: &identities,
-                now_utc: now_utc(),
+                label_tags: &account_cfg.label_tags,
+                include_cc: args.include_cc || account_cfg.include_cc,
+                cc_creates_contacts: account_cfg.cc_creates_contacts,
+                update_names: account_cfg.update_names,
+                now_utc: ctx.clock.now_utc(),
             };
-            let mut headers = result.headers;
-            headers.sort_by_key(|header| header.uid);
-            let mut new_last_uid = last_uid;
-            let mut processed_all = true;
-            for header in headers {
-                if let Some(limit) = remaining.as_mut() {
-                    if *limit == 0 {
-                        processed_all = false;
-                        break;
-                    }
-                    *limit -= 1;
-                }
-                report.messages_seen += 1;
-                if let Some(contact_id) = handle_email_header(&email_ctx, &header, &mut report)? {
-                    if options.dry_run {
-                        continue;
-                    }
-                    let record = EmailMessageRecord {
-                        account: account_cfg.name.clone(),
-                        mailbox: mailbox.to_string(),
-                        uidvalidity: result.uidvalidity.unwrap_or(0),
-                        uid: header.uid as i64,
-                        message_id: header.message_id.clone(),
-                        contact_id,
-                        occurred_at: header.occurred_at,
-                        direction: direction_for_header(&identities, &header),
-                        subject: header.subject.clone(),
-                        created_at: now_utc(),
-                    };
-                    let tx = ctx.store.connection().unchecked_transaction()?;
-                    let email_sync = knotter_store::repo::EmailSyncRepo::new(&tx);
-                    let interactions = knotter_store::repo::InteractionsRepo::new(&tx);
-                    let mut inserted = false;
-                    if email_sync.record_message(&record)? {
-                        let note = format_email_note(&record.direction, record.subject.as_deref());
-                        let interaction = knotter_store::repo::InteractionNew {
-                            contact_id,
-                            occurred_at: record.occurred_at,
-                            created_at: record.created_at,
-                            kind: InteractionKind::Email,
-                            note,
-                            follow_up_at: None,
-                        };
-                        interactions.add_with_reschedule_in_tx(
-                            record.created_at,
-                            interaction,
-                            ctx.config.interactions.auto_reschedule,
-                        )?;
-                        inserted = true;
-                    }
-                    tx.commit()?;
-                    if inserted {
-                        report.messages_imported += 1;
-                        report.touches_recorded += 1;
-                    }
-                } else if options.retry_skipped {
-                    report.warnings.push(format!(
-                        "email {} skipped; stopping due to --retry-skipped",
-                        header.uid
-                    ));
-                    processed_all = false;
-                    stop_all = true;
-                    break;
-                }
-                new_last_uid = header.uid as i64;
-            }
-            if processed_all {
-                new_last_uid = new_last_uid.max(result.last_uid);
-            }
+            let uidvalidity = result.uidvalidity;
+            let outcome = import_mailbox_headers(
+                &email_ctx,
+                mailbox,
+                result,
+                last_uid,
+                &mut remaining,
+                &mut report,
+                &mut progress,
+                &mut sink,
+            )?;
+            stop_all = outcome.stop_all;
+            sink.finish(&progress);
 
             if !options.dry_run && !stop_all {
-                let uidvalidity = result.uidvalidity;
                 let state = knotter_store::repo::EmailSyncState {
                     account: account_cfg.name.clone(),
                     mailbox: mailbox.to_string(),
                     uidvalidity,
-                    last_uid: new_last_uid,
-                    last_seen_at: Some(now_utc()),
+                    last_uid: outcome.last_uid,
+                    last_seen_at: Some(ctx.clock.now_utc()),
                 };
                 ctx.store.email_sync().upsert_state(&state)?;
             }
@@ -595,96 +1101,427 @@ pub fn import_email(ctx: &Context<'_>, args: ImportEmailArgs) -> Result<()> {
 
     if ctx.json {
         print_json(&report)?;
+    } else if args.common.quiet {
+        print_warnings(&mut io::stderr(), "email import", &report.warnings)?;
     } else {
         println!(
-            "email import: {} account(s), {} mailbox(es), {} message(s), {} touch(es), {} merge candidate(s)",
+            "email import: {} account(s), {} mailbox(es), {} message(s), {} touch(es), {} cc touch(es), {} merge candidate(s), {} out of range",
             report.accounts,
             report.mailboxes,
             report.messages_seen,
             report.touches_recorded,
-            report.merge_candidates_created
+            report.cc_touches_recorded,
+            report.merge_candidates_created,
+            report.messages_out_of_range
         );
-        if !report.warnings.is_empty() {
-            println!("warnings:");
-            for warning in report.warnings {
-                println!("  - {}", warning);
-            }
+        if report.default_cadence_applied > 0 {
+            println!(
+                "default cadence applied to {} new contact(s)",
+                report.default_cadence_applied
+            );
+        }
+        if report.names_updated > 0 {
+            println!(
+                "display name updated from headers for {} contact(s)",
+                report.names_updated
+            );
+        }
+        if report.identities_skipped > 0 {
+            println!(
+                "{} message(s) only between your own addresses",
+                report.identities_skipped
+            );
         }
+        for expansion in &report.mailbox_expansions {
+            println!(
+                "{}: expanded to {}; excluded {}",
+                expansion.account,
+                format_mailbox_list(&expansion.expanded),
+                format_mailbox_list(&expansion.excluded)
+            );
+        }
+        print_warnings(&mut io::stderr(), "email import", &report.warnings)?;
     }
 
     Ok(())
 }
 
-fn import_telegram_account(
-    ctx: &Context<'_>,
-    account_cfg: &knotter_config::TelegramAccountConfig,
-    options: &ImportOptions,
-    contacts_only: bool,
-    messages_only: bool,
-    report: &mut TelegramImportReport,
-) -> Result<bool> {
-    let now_utc = now_utc();
-    let api_hash = resolve_required_env(&account_cfg.api_hash_env, "telegram api hash")?;
-    let session_path = match &account_cfg.session_path {
-        Some(path) => path.clone(),
-        None => default_telegram_session_path(&account_cfg.name)?,
-    };
-    let account = SyncTelegramAccount {
-        name: account_cfg.name.clone(),
-        api_id: account_cfg.api_id,
-        api_hash,
-        phone: account_cfg.phone.clone(),
-        session_path,
-    };
-
-    let mut client = telegram::connect(account)?;
-    client.ensure_authorized()?;
-
-    import_telegram_account_with_client(
-        ctx,
-        account_cfg,
-        options,
-        contacts_only,
-        messages_only,
-        report,
-        &mut *client,
-        now_utc,
-    )
+/// Where [`import_mailbox_headers`] left a mailbox.
+struct MailboxImport {
+    /// UID to store as the mailbox's sync state.
+    last_uid: i64,
+    /// `--retry-skipped` stopped on a message; no later mailbox runs.
+    stop_all: bool,
 }
 
+/// Records touches for one mailbox's headers in UID order, reporting the
+/// mailbox's counters to `sink` as it goes.
 #[allow(clippy::too_many_arguments)]
-fn import_telegram_account_with_client(
-    ctx: &Context<'_>,
-    account_cfg: &knotter_config::TelegramAccountConfig,
-    options: &ImportOptions,
-    contacts_only: bool,
-    messages_only: bool,
-    report: &mut TelegramImportReport,
-    client: &mut dyn telegram::TelegramClient,
-    now_utc: i64,
-) -> Result<bool> {
-    let ctx = TelegramImportContext {
-        ctx,
-        options,
-        now_utc,
-        account_name: &account_cfg.name,
-        merge_policy: account_cfg.merge_policy,
-        allowlist_user_ids: &account_cfg.allowlist_user_ids,
-        snippet_len: account_cfg.snippet_len,
-        messages_only,
-    };
-
+fn import_mailbox_headers(
+    email_ctx: &EmailImportContext<'_>,
+    mailbox: &str,
+    result: MailboxSyncResult,
+    last_uid: i64,
+    remaining: &mut Option<usize>,
+    report: &mut EmailImportReport,
+    progress: &mut ImportProgress,
+    sink: &mut dyn ProgressSink,
+) -> Result<MailboxImport> {
+    let ctx = email_ctx.ctx;
+    let options = email_ctx.options;
+    let mut headers = result.headers;
+    headers.sort_by_key(|header| header.uid);
+    let mut new_last_uid = last_uid;
+    let mut processed_all = true;
+    let mut stop_all = false;
+    let (seen_before, matched_before, created_before) = (
+        report.messages_seen,
+        report.contacts_matched,
+        report.contacts_created,
+    );
+    let refresh = |progress: &mut ImportProgress, report: &EmailImportReport| {
+        progress.processed = report.messages_seen - seen_before;
+        progress.matched = report.contacts_matched - matched_before;
+        progress.contacts_created = report.contacts_created - created_before;
+    };
+    for header in headers {
+        refresh(progress, report);
+        sink.update(progress);
+        if let Some(limit) = remaining.as_mut() {
+            if *limit == 0 {
+                processed_all = false;
+                break;
+            }
+            *limit -= 1;
+        }
+        report.messages_seen += 1;
+        if let Err(err) = ensure_sane_interaction_timestamp(
+            email_ctx.now_utc,
+            header.occurred_at,
+            ctx.config.sync.max_future_skew_days,
+        ) {
+            report.messages_out_of_range += 1;
+            report
+                .warnings
+                .push(format!("email {} in {mailbox} skipped: {err}", header.uid));
+            new_last_uid = header.uid as i64;
+            continue;
+        }
+        if let Some(contact_id) = handle_email_header(email_ctx, &header, report)? {
+            let cc_contact_ids = cc_contacts(email_ctx, &header, contact_id, report)?;
+            if options.dry_run {
+                continue;
+            }
+            let record = EmailMessageRecord {
+                account: email_ctx.account_name.to_string(),
+                mailbox: mailbox.to_string(),
+                uidvalidity: result.uidvalidity.unwrap_or(0),
+                uid: header.uid as i64,
+                message_id: header.message_id.clone(),
+                contact_id,
+                occurred_at: header.occurred_at,
+                direction: direction_for_header(email_ctx.identities, &header),
+                subject: header.subject.clone(),
+                created_at: ctx.clock.now_utc(),
+            };
+            let tx = ctx.store.connection().unchecked_transaction()?;
+            let email_sync = knotter_store::repo::EmailSyncRepo::new(&tx);
+            let interactions = knotter_store::repo::InteractionsRepo::new(&tx);
+            let mut inserted = false;
+            if email_sync.record_message(&record)? {
+                let note = format_email_note(&record.direction, record.subject.as_deref());
+                let interaction = knotter_store::repo::InteractionNew {
+                    contact_id,
+                    occurred_at: record.occurred_at,
+                    created_at: record.created_at,
+                    kind: InteractionKind::Email,
+                    note,
+                    follow_up_at: None,
+                    direction: InteractionDirection::from_str(&record.direction).ok(),
+                };
+                interactions.add_with_reschedule_in_tx(
+                    record.created_at,
+                    interaction,
+                    ctx.config.interactions.auto_reschedule,
+                )?;
+                // No direction, so Cc touches never mark a reply as awaited.
+                for cc_contact_id in &cc_contact_ids {
+                    let interaction = knotter_store::repo::InteractionNew {
+                        contact_id: *cc_contact_id,
+                        occurred_at: record.occurred_at,
+                        created_at: record.created_at,
+                        kind: InteractionKind::Email,
+                        note: format_cc_email_note(record.subject.as_deref()),
+                        follow_up_at: None,
+                        direction: None,
+                    };
+                    interactions.add_with_reschedule_in_tx(
+                        record.created_at,
+                        interaction,
+                        ctx.config.interactions.auto_reschedule,
+                    )?;
+                }
+                inserted = true;
+            }
+            tx.commit()?;
+            if inserted {
+                report.messages_imported += 1;
+                report.touches_recorded += 1;
+                report.cc_touches_recorded += cc_contact_ids.len();
+            }
+        } else if options.retry_skipped {
+            report.warnings.push(format!(
+                "email {} skipped; stopping due to --retry-skipped",
+                header.uid
+            ));
+            processed_all = false;
+            stop_all = true;
+            break;
+        }
+        new_last_uid = header.uid as i64;
+    }
+    refresh(progress, report);
+    sink.update(progress);
+    if processed_all {
+        new_last_uid = new_last_uid.max(result.last_uid);
+    }
+    Ok(MailboxImport {
+        last_uid: new_last_uid,
+        stop_all,
+    })
+}
+
+/// Fetches one mailbox's new headers, passing batch progress to `sink`.
+fn fetch_with_progress(
+    account: &EmailAccount,
+    mailbox: &str,
+    last_uid: i64,
+    limit: Option<usize>,
+    progress: &mut ImportProgress,
+    sink: &mut dyn ProgressSink,
+) -> Result<MailboxSyncResult> {
+    Ok(fetch_mailbox_headers_with_progress(
+        account,
+        mailbox,
+        last_uid,
+        limit,
+        &mut |fetched, total| {
+            progress.fetched = fetched;
+            progress.total = total;
+            sink.update(progress);
+        },
+    )?)
+}
+
+fn format_mailbox_list(mailboxes: &[String]) -> String {
+    if mailboxes.is_empty() {
+        "-".to_string()
+    } else {
+        mailboxes.join(", ")
+    }
+}
+
+/// [`email_account`] plus, when `mailboxes` has a wildcard, the server's
+/// mailbox list expanded into it (minus `exclude_mailboxes`).
+fn resolve_email_account(
+    account_cfg: &EmailAccountConfig,
+) -> Result<(EmailAccount, Option<MailboxExpansionReport>)> {
+    let mut account = email_account(account_cfg)?;
+    if !has_mailbox_wildcard(&account.mailboxes) {
+        return Ok((account, None));
+    }
+    let available = list_mailboxes(&account)
+        .with_context(|| format!("list mailboxes for email account {}", account_cfg.name))?;
+    let selection = select_mailboxes(
+        &account.mailboxes,
+        &available,
+        &account_cfg.exclude_mailboxes,
+    );
+    account.mailboxes = selection.mailboxes;
+    Ok((
+        account,
+        Some(MailboxExpansionReport {
+            account: account_cfg.name.clone(),
+            expanded: selection.expanded,
+            excluded: selection.excluded,
+        }),
+    ))
+}
+
+fn email_account(account_cfg: &EmailAccountConfig) -> Result<EmailAccount> {
+    let password = resolve_password(None, false, Some(&account_cfg.password)).map_err(|err| {
+        invalid_input(format!(
+            "email account {} password error: {err}",
+            account_cfg.name
+        ))
+    })?;
+    let tls = match account_cfg.tls {
+        EmailAccountTls::Tls => EmailTls::Tls,
+        EmailAccountTls::StartTls => EmailTls::StartTls,
+        EmailAccountTls::None => EmailTls::None,
+    };
+    Ok(EmailAccount {
+        host: account_cfg.host.clone(),
+        port: account_cfg.port,
+        username: account_cfg.username.clone(),
+        password,
+        tls,
+        mailboxes: account_cfg.mailboxes.clone(),
+        gmail_labels: account_cfg.gmail_labels,
+    })
+}
+
+/// Accounts and mailbox headers fetched up front when `--jobs` allows several
+/// IMAP connections at once and no `--limit` is set. The import loop takes
+/// entries in account order, so errors surface where the one-by-one import
+/// would raise them.
+#[derive(Default)]
+struct PrefetchedMail {
+    accounts: HashMap<String, Result<(EmailAccount, Option<MailboxExpansionReport>)>>,
+    mailboxes: HashMap<(String, String), Result<MailboxSyncResult>>,
+}
+
+fn prefetch_mail(
+    ctx: &Context<'_>,
+    accounts: &[EmailAccountConfig],
+    jobs: usize,
+) -> Result<PrefetchedMail> {
+    let mut prefetched = PrefetchedMail::default();
+    let mut work = Vec::new();
+    for account_cfg in accounts {
+        let account = resolve_email_account(account_cfg);
+        if let Ok((account, _)) = &account {
+            for mailbox in &account.mailboxes {
+                let last_uid = ctx
+                    .store
+                    .email_sync()
+                    .load_state(&account_cfg.name, mailbox)?
+                    .map(|state| state.last_uid)
+                    .unwrap_or(0);
+                work.push((
+                    account_cfg.name.clone(),
+                    account.clone(),
+                    mailbox.clone(),
+                    last_uid,
+                ));
+            }
+        }
+        prefetched
+            .accounts
+            .insert(account_cfg.name.clone(), account);
+    }
+
+    let parent = Span::current();
+    let fetched = map_bounded(
+        jobs,
+        work.iter().collect(),
+        |(account_name, account, mailbox, last_uid)| {
+            let _phase =
+                profile::phase_within(&parent, format_args!("mailbox {account_name}/{mailbox}"));
+            fetch_mailbox_headers(account, mailbox, *last_uid, None)
+        },
+    );
+    for ((account_name, _, mailbox, _), result) in work.into_iter().zip(fetched) {
+        prefetched
+            .mailboxes
+            .insert((account_name, mailbox), result.map_err(Into::into));
+    }
+    Ok(prefetched)
+}
+
+fn import_telegram_account(
+    ctx: &Context<'_>,
+    account_cfg: &knotter_config::TelegramAccountConfig,
+    options: &ImportOptions,
+    contacts_only: bool,
+    messages_only: bool,
+    report: &mut TelegramImportReport,
+    sink: &mut dyn ProgressSink,
+) -> Result<bool> {
+    let now_utc = ctx.clock.now_utc();
+    let api_hash = resolve_secret(&account_cfg.api_hash, "telegram api hash")?;
+    let session_path = match &account_cfg.session_path {
+        Some(path) => path.clone(),
+        None => default_telegram_session_path(&account_cfg.name)?,
+    };
+    let account = SyncTelegramAccount {
+        name: account_cfg.name.clone(),
+        api_id: account_cfg.api_id,
+        api_hash,
+        phone: account_cfg.phone.clone(),
+        session_path,
+    };
+
+    let mut client = telegram::connect(account)?;
+    client.ensure_authorized()?;
+
+    import_telegram_account_with_client(
+        ctx,
+        account_cfg,
+        options,
+        contacts_only,
+        messages_only,
+        report,
+        &mut *client,
+        sink,
+        now_utc,
+    )
+}
+
+#[allow(clippy::too_many_arguments)]
+fn import_telegram_account_with_client(
+    ctx: &Context<'_>,
+    account_cfg: &knotter_config::TelegramAccountConfig,
+    options: &ImportOptions,
+    contacts_only: bool,
+    messages_only: bool,
+    report: &mut TelegramImportReport,
+    client: &mut dyn telegram::TelegramClient,
+    sink: &mut dyn ProgressSink,
+    now_utc: i64,
+) -> Result<bool> {
+    let ctx = TelegramImportContext {
+        ctx,
+        options,
+        now_utc,
+        account_name: &account_cfg.name,
+        merge_policy: account_cfg.merge_policy,
+        allowlist_user_ids: &account_cfg.allowlist_user_ids,
+        snippet_len: account_cfg.snippet_len,
+        metadata_tags: account_cfg.metadata_tags,
+        messages_only,
+    };
+
     let mut stop_all = false;
     let users = client.list_users()?;
     report.users_seen += users.len();
 
+    let mut progress = ImportProgress::new(format!("telegram {}", account_cfg.name), "chats");
+    progress.total = users.len();
+    let (seen_before, matched_before, created_before) = (
+        report.messages_seen,
+        report.contacts_matched,
+        report.contacts_created,
+    );
+    let refresh = |progress: &mut ImportProgress, report: &TelegramImportReport| {
+        progress.processed = report.messages_seen - seen_before;
+        progress.matched = report.contacts_matched - matched_before;
+        progress.contacts_created = report.contacts_created - created_before;
+    };
     for user in users {
+        refresh(&mut progress, report);
+        sink.update(&progress);
+        progress.fetched += 1;
         if user.is_bot {
             continue;
         }
         if !ctx.allowlist_user_ids.is_empty() && !ctx.allowlist_user_ids.contains(&user.id) {
             continue;
         }
+        if is_ignored_telegram_user(ctx.ctx, &user) {
+            report.identities_skipped += 1;
+            continue;
+        }
 
         let contact_id = resolve_telegram_contact(&ctx, &user, report)?;
         let Some(contact_id) = contact_id else {
@@ -705,6 +1542,9 @@ fn import_telegram_account_with_client(
             break;
         }
     }
+    refresh(&mut progress, report);
+    sink.update(&progress);
+    sink.finish(&progress);
 
     Ok(stop_all)
 }
@@ -739,16 +1579,21 @@ pub fn import_telegram(ctx: &Context<'_>, args: ImportTelegramArgs) -> Result<()
         accounts: 0,
         users_seen: 0,
         contacts_created: 0,
+        default_cadence_applied: 0,
         contacts_matched: 0,
         contacts_merged: 0,
         merge_candidates_created: 0,
+        metadata_tagged: 0,
         messages_seen: 0,
         messages_imported: 0,
         touches_recorded: 0,
+        messages_out_of_range: 0,
+        identities_skipped: 0,
         warnings: Vec::new(),
         dry_run: args.common.dry_run,
     };
 
+    let mut sink = StderrProgress::stderr(args.common.quiet);
     let mut stop_all = false;
     let mut first_error: Option<anyhow::Error> = None;
     for account_cfg in &accounts {
@@ -764,6 +1609,7 @@ pub fn import_telegram(ctx: &Context<'_>, args: ImportTelegramArgs) -> Result<()
             args.contacts_only,
             args.messages_only,
             &mut report,
+            &mut sink,
         );
         match result {
             Ok(stop) => stop_all = stop,
@@ -782,21 +1628,37 @@ pub fn import_telegram(ctx: &Context<'_>, args: ImportTelegramArgs) -> Result<()
 
     if ctx.json {
         print_json(&report)?;
+    } else if args.common.quiet {
+        print_warnings(&mut io::stderr(), "telegram import", &report.warnings)?;
     } else {
         println!(
-            "telegram import: {} account(s), {} user(s), {} message(s), {} touch(es), {} merge candidate(s)",
+            "telegram import: {} account(s), {} user(s), {} message(s), {} touch(es), {} merge candidate(s), {} out of range",
             report.accounts,
             report.users_seen,
             report.messages_seen,
             report.touches_recorded,
-            report.merge_candidates_created
+            report.merge_candidates_created,
+            report.messages_out_of_range
         );
-        if !report.warnings.is_empty() {
-            println!("warnings:");
-            for warning in report.warnings {
-                println!("  - {}", warning);
-            }
+        if report.default_cadence_applied > 0 {
+            println!(
+                "default cadence applied to {} new contact(s)",
+                report.default_cadence_applied
+            );
+        }
+        if report.identities_skipped > 0 {
+            println!(
+                "{} user(s) skipped as your own accounts",
+                report.identities_skipped
+            );
         }
+        if report.metadata_tagged > 0 {
+            println!(
+                "metadata tags added to {} contact(s)",
+                report.metadata_tagged
+            );
+        }
+        print_warnings(&mut io::stderr(), "telegram import", &report.warnings)?;
     }
 
     if let Some(err) = first_error {
@@ -817,38 +1679,69 @@ fn sync_all_with_runner(ctx: &Context<'_>, args: SyncArgs, runner: &dyn SyncRunn
         ));
     }
 
+    if args.jobs == 0 {
+        return Err(invalid_input("--jobs must be at least 1"));
+    }
+
+    let quiet = args.common.quiet;
     let mut ran_any = false;
+    let mut steps = 0;
     let mut errors: Vec<String> = Vec::new();
 
     if ctx.config.contacts.sources.is_empty() {
-        println!("no contact sources configured; skipping contact import");
+        if !quiet {
+            println!("no contact sources configured; skipping contact import");
+        }
     } else {
-        for source in &ctx.config.contacts.sources {
+        // Fetch concurrently, then import one source at a time in config order.
+        let config = ctx.config;
+        let sources: Vec<&str> = config
+            .contacts
+            .sources
+            .iter()
+            .map(|source| source.name.as_str())
+            .collect();
+        let parent = Span::current();
+        let fetched = map_bounded(args.jobs, sources.clone(), |name| {
+            let _phase = profile::phase_within(&parent, format_args!("source {name} fetch"));
+            runner.fetch_source(config, name)
+        });
+        for (name, data) in sources.into_iter().zip(fetched) {
             ran_any = true;
+            steps += 1;
+            let _phase = profile::phase(format_args!("source {name} import"));
             record_sync_result(
-                format!("contact source {}", source.name),
-                runner.import_source(ctx, &source.name, &args.common),
+                format!("contact source {name}"),
+                data.and_then(|data| runner.import_source_data(ctx, name, data, &args.common)),
                 &mut errors,
             );
         }
     }
 
     if ctx.config.contacts.email_accounts.is_empty() {
-        println!("no email accounts configured; skipping email import");
+        if !quiet {
+            println!("no email accounts configured; skipping email import");
+        }
     } else {
         ran_any = true;
+        steps += 1;
+        let _phase = profile::phase("email import");
         record_sync_result(
             "email import".to_string(),
-            runner.import_email(ctx, &args.common, args.force_uidvalidity_resync),
+            runner.import_email(ctx, &args.common, args.force_uidvalidity_resync, args.jobs),
             &mut errors,
         );
     }
 
     if !args.no_telegram {
         if ctx.config.contacts.telegram_accounts.is_empty() {
-            println!("no telegram accounts configured; skipping telegram import");
+            if !quiet {
+                println!("no telegram accounts configured; skipping telegram import");
+            }
         } else {
             ran_any = true;
+            steps += 1;
+            let _phase = profile::phase("telegram import");
             record_sync_result(
                 "telegram import".to_string(),
                 runner.import_telegram(ctx, &args.common),
@@ -865,17 +1758,21 @@ fn sync_all_with_runner(ctx: &Context<'_>, args: SyncArgs, runner: &dyn SyncRunn
 
     if !args.no_loops {
         if crate::commands::loops::loops_configured(ctx.config) {
+            steps += 1;
+            let _phase = profile::phase("loops apply");
             record_sync_result(
                 "loops apply".to_string(),
-                runner.apply_loops(ctx, args.common.dry_run),
+                runner.apply_loops(ctx, args.common.dry_run, quiet),
                 &mut errors,
             );
-        } else {
+        } else if !quiet {
             println!("no loops configured; skipping loop apply");
         }
     }
 
     if !args.no_remind {
+        steps += 1;
+        let _phase = profile::phase("remind");
         record_sync_result(
             "remind".to_string(),
             runner.remind(ctx, args.common.dry_run),
@@ -883,10 +1780,54 @@ fn sync_all_with_runner(ctx: &Context<'_>, args: SyncArgs, runner: &dyn SyncRunn
         );
     }
 
+    // Exports run last, even after failed steps, and one failing export does
+    // not stop the others.
+    let exports: Vec<(SyncExport, &Path)> = [
+        (SyncExport::Ics, args.export_ics.as_deref()),
+        (SyncExport::Json, args.export_json.as_deref()),
+        (SyncExport::Vcf, args.export_vcf.as_deref()),
+    ]
+    .into_iter()
+    .filter_map(|(export, path)| path.map(|path| (export, path)))
+    .collect();
+    if !exports.is_empty() && args.common.dry_run {
+        if !quiet {
+            println!("dry run; skipping exports");
+        }
+    } else if !exports.is_empty() {
+        let mut summary = Vec::new();
+        for (export, path) in exports {
+            steps += 1;
+            let failed_before = errors.len();
+            let phase = profile::phase(format_args!("export {}", export.as_str()));
+            record_sync_result(
+                format!("export {}", export.as_str()),
+                runner.export(ctx, export, path),
+                &mut errors,
+            );
+            drop(phase);
+            let outcome = if errors.len() > failed_before {
+                "failed"
+            } else {
+                "ok"
+            };
+            summary.push(format!(
+                "{} {outcome} ({})",
+                export.as_str(),
+                path.display()
+            ));
+        }
+        if !quiet {
+            println!("sync exports: {}", summary.join(", "));
+        }
+    }
+
     if errors.is_empty() {
         Ok(())
+    } else if errors.len() == steps {
+        Err(anyhow::anyhow!("sync failed: all {steps} step(s) failed"))
     } else {
-        Err(anyhow::anyhow!(format!(
+        Err(partial_failure(format!(
             "sync completed with {} error(s)",
             errors.len()
         )))
@@ -902,11 +1843,23 @@ fn record_sync_result(label: String, result: Result<()>, errors: &mut Vec<String
 }
 
 pub fn export_vcf(ctx: &Context<'_>, args: ExportVcfArgs) -> Result<()> {
-    let contacts = load_export_contacts(ctx, false)?;
+    let contacts = load_export_contacts(
+        ctx,
+        &ExportScope {
+            include_archived: args.include_archived,
+            filter: args.filter.clone(),
+        },
+    )?;
+    if let Some(out_dir) = args.out_dir.as_deref() {
+        return export_vcf_split_by_tag(ctx, &contacts, out_dir);
+    }
     let tags = load_tags(ctx, &contacts)?;
     let emails = load_emails(ctx, &contacts)?;
     let dates = load_contact_dates(ctx, &contacts)?;
-    let data = vcf::export_vcf(&contacts, &tags, &emails, &dates)?;
+    let ids: Vec<ContactId> = contacts.iter().map(|contact| contact.id).collect();
+    let aliases = ctx.store.contact_aliases().list_for_contacts(&ids)?;
+    let origins = ctx.store.contact_origins().list_for_contacts(&ids)?;
+    let data = vcf::export_vcf(&contacts, &tags, &emails, &dates, &aliases, &origins)?;
     write_export(
         ctx,
         ExportReport {
@@ -916,24 +1869,144 @@ pub fn export_vcf(ctx: &Context<'_>, args: ExportVcfArgs) -> Result<()> {
         },
         args.out.as_deref(),
         &data,
+        &mut io::stdout(),
     )
 }
 
+fn export_vcf_split_by_tag(
+    ctx: &Context<'_>,
+    contacts: &[knotter_core::domain::Contact],
+    out_dir: &Path,
+) -> Result<()> {
+    let tags = load_tags(ctx, contacts)?;
+    let emails = load_emails(ctx, contacts)?;
+    let dates = load_contact_dates(ctx, contacts)?;
+    let ids: Vec<ContactId> = contacts.iter().map(|contact| contact.id).collect();
+    let aliases = ctx.store.contact_aliases().list_for_contacts(&ids)?;
+    let origins = ctx.store.contact_origins().list_for_contacts(&ids)?;
+
+    let mut groups: BTreeMap<String, Vec<knotter_core::domain::Contact>> = BTreeMap::new();
+    let mut untagged = Vec::new();
+    for contact in contacts {
+        match tags.get(&contact.id).filter(|names| !names.is_empty()) {
+            Some(names) => {
+                for name in names {
+                    groups
+                        .entry(name.clone())
+                        .or_default()
+                        .push(contact.clone());
+                }
+            }
+            None => untagged.push(contact.clone()),
+        }
+    }
+
+    fs::create_dir_all(out_dir)
+        .with_context(|| format!("create export directory {}", out_dir.display()))?;
+    // `untagged` is reserved up front so a tag with that name cannot overwrite it.
+    let mut used_stems = HashSet::from([UNTAGGED_VCF_STEM.to_string()]);
+    let files = groups
+        .into_iter()
+        .map(|(tag, members)| {
+            let stem = unique_file_stem(&tag_file_stem(&tag), &mut used_stems);
+            (Some(tag), stem, members)
+        })
+        .chain((!untagged.is_empty()).then(|| (None, UNTAGGED_VCF_STEM.to_string(), untagged)));
+
+    let mut report = SplitExportReport {
+        format: "vcf".to_string(),
+        count: contacts.len(),
+        output_dir: out_dir.display().to_string(),
+        files: Vec::new(),
+    };
+    for (tag, stem, members) in files {
+        let path = out_dir.join(format!("{stem}.vcf"));
+        let data = vcf::export_vcf(&members, &tags, &emails, &dates, &aliases, &origins)?;
+        fs::write(&path, data).with_context(|| format!("write export file {}", path.display()))?;
+        report.files.push(SplitExportFile {
+            tag,
+            path: path.display().to_string(),
+            count: members.len(),
+        });
+    }
+
+    if ctx.json {
+        return print_json(&report);
+    }
+    println!(
+        "Exported {} contacts to {} files in {}",
+        report.count,
+        report.files.len(),
+        report.output_dir
+    );
+    for file in &report.files {
+        println!("  {}  {}", file.path, file.count);
+    }
+    Ok(())
+}
+
+const UNTAGGED_VCF_STEM: &str = "untagged";
+
+/// File name for a tag's export: characters that are not letters, digits,
+/// `-`, `_`, or `.` become `_`, and a leading `.` is replaced so the file is
+/// not hidden.
+fn tag_file_stem(tag: &str) -> String {
+    let mut stem: String = tag
+        .chars()
+        .map(|ch| {
+            if ch.is_alphanumeric() || matches!(ch, '-' | '_' | '.') {
+                ch
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    if stem.starts_with('.') {
+        stem.replace_range(..1, "_");
+    }
+    stem
+}
+
+/// `stem`, or `stem-2`, `stem-3`, … when two tags sanitize to the same name.
+fn unique_file_stem(stem: &str, used: &mut HashSet<String>) -> String {
+    let mut candidate = stem.to_string();
+    let mut suffix = 2;
+    while !used.insert(candidate.clone()) {
+        candidate = format!("{stem}-{suffix}");
+        suffix += 1;
+    }
+    candidate
+}
+
 pub fn export_ics(ctx: &Context<'_>, args: ExportIcsArgs) -> Result<()> {
     if let Some(days) = args.window_days {
         if days <= 0 {
             return Err(invalid_input("--window-days must be positive"));
         }
     }
+    let alarm_seconds = match args.alarm.as_deref() {
+        Some(raw) => Some(parse_duration(raw).map_err(|err| invalid_input(err.to_string()))?),
+        None => ctx.config.exports.ics.alarm_seconds,
+    };
 
-    let contacts = load_export_contacts(ctx, false)?;
+    let contacts = load_export_contacts(
+        ctx,
+        &ExportScope {
+            include_archived: args.include_archived,
+            filter: None,
+        },
+    )?;
     let tags = load_tags(ctx, &contacts)?;
+    let dates = load_contact_dates(ctx, &contacts)?;
     let export = ics::export_ics(
         &contacts,
         &tags,
+        &dates,
         IcsExportOptions {
-            now_utc: now_utc(),
+            now_utc: ctx.clock.now_utc(),
             window_days: args.window_days,
+            expand_cadence: args.expand_cadence,
+            alarm_seconds,
         },
     )?;
 
@@ -946,129 +2019,318 @@ pub fn export_ics(ctx: &Context<'_>, args: ExportIcsArgs) -> Result<()> {
         },
         args.out.as_deref(),
         &export.data,
+        &mut io::stdout(),
     )
 }
 
 pub fn export_json(ctx: &Context<'_>, args: ExportJsonArgs) -> Result<()> {
-    let include_archived = !args.exclude_archived;
-    let contacts = load_export_contacts(ctx, include_archived)?;
-    let ids: Vec<ContactId> = contacts.iter().map(|contact| contact.id).collect();
-    let mut tags = load_tags(ctx, &contacts)?;
-    let mut emails = load_emails(ctx, &contacts)?;
-    let mut dates = load_contact_dates(ctx, &contacts)?;
-    let mut interactions = ctx.store.interactions().list_for_contacts(&ids)?;
-
-    let export_contacts: Vec<ExportContactDto> = contacts
-        .into_iter()
-        .map(|contact| {
-            let tags = tags.remove(&contact.id).unwrap_or_default();
-            let emails = emails.remove(&contact.id).unwrap_or_default();
-            let dates = dates.remove(&contact.id).unwrap_or_default();
-            let dates = dates
-                .into_iter()
-                .map(|date| ContactDateDto {
-                    id: date.id,
-                    kind: date.kind,
-                    label: date.label,
-                    month: date.month,
-                    day: date.day,
-                    year: date.year,
-                })
-                .collect();
-            let interactions = interactions.remove(&contact.id).unwrap_or_default();
-            let interactions = interactions
-                .into_iter()
-                .map(|interaction| ExportInteractionDto {
-                    id: interaction.id,
-                    occurred_at: interaction.occurred_at,
-                    created_at: interaction.created_at,
-                    kind: format_interaction_kind(&interaction.kind),
-                    note: interaction.note,
-                    follow_up_at: interaction.follow_up_at,
-                })
-                .collect();
+    let format_version = args.format_version.unwrap_or(FORMAT_VERSION);
+    ensure_known_format_version(format_version)?;
+    if args.include_merges && format_version < MERGE_CANDIDATES_FORMAT_VERSION {
+        return Err(invalid_input(format!(
+            "--include-merges needs --format-version {MERGE_CANDIDATES_FORMAT_VERSION} or later"
+        )));
+    }
 
-            ExportContactDto {
-                id: contact.id,
-                display_name: contact.display_name,
-                email: contact.email,
-                emails,
-                phone: contact.phone,
-                handle: contact.handle,
-                timezone: contact.timezone,
-                next_touchpoint_at: contact.next_touchpoint_at,
-                cadence_days: contact.cadence_days,
-                created_at: contact.created_at,
-                updated_at: contact.updated_at,
-                archived_at: contact.archived_at,
-                tags,
-                dates,
-                interactions,
-            }
-        })
-        .collect();
+    let contacts = load_export_contacts(
+        ctx,
+        &ExportScope {
+            include_archived: !args.exclude_archived,
+            filter: None,
+        },
+    )?;
+    let archive_reasons = ctx.store.contacts().list_archive_reasons()?;
+    let muted_until = ctx.store.contacts().list_muted_until()?;
+    let priorities = ctx.store.contacts().list_priorities()?;
 
     let metadata = ExportMetadataDto {
-        exported_at: now_utc(),
+        exported_at: ctx.clock.now_utc(),
         app_version: env!("CARGO_PKG_VERSION").to_string(),
         schema_version: ctx.store.schema_version()?,
-        format_version: 1,
+        format_version,
     };
 
-    let snapshot = ExportSnapshotDto {
-        metadata,
-        contacts: export_contacts,
+    let merge_candidates = if args.include_merges {
+        Some(load_export_merge_candidates(ctx)?)
+    } else {
+        None
     };
 
-    let data = serde_json::to_string_pretty(&snapshot)?;
+    let snapshot = StreamedSnapshot {
+        metadata: &metadata,
+        contacts: StreamedContacts {
+            ctx,
+            contacts: &contacts,
+            archive_reasons: &archive_reasons,
+            muted_until: &muted_until,
+            priorities: &priorities,
+            format_version,
+            include_audit: args.include_audit,
+        },
+        merge_candidates: merge_candidates.as_deref(),
+    };
     write_json_export(
         ctx,
         ExportReport {
             format: "json".to_string(),
-            count: snapshot.contacts.len(),
+            count: contacts.len(),
             output: args.out.as_ref().map(|path| path.display().to_string()),
         },
         args.out.as_deref(),
-        &data,
+        &snapshot,
+        &mut io::stdout(),
     )
 }
 
-fn load_export_contacts(
-    ctx: &Context<'_>,
-    include_archived: bool,
-) -> Result<Vec<knotter_core::domain::Contact>> {
-    let mut contacts = ctx.store.contacts().list_all()?;
-    if !include_archived {
-        contacts.retain(|contact| contact.archived_at.is_none());
-    }
-    Ok(contacts)
+/// Contacts whose children are fetched per batch while serializing.
+const EXPORT_BATCH_SIZE: usize = 200;
+
+/// Serializes like `ExportSnapshotDto`, but streams the contacts.
+struct StreamedSnapshot<'a, 'c> {
+    metadata: &'a ExportMetadataDto,
+    contacts: StreamedContacts<'a, 'c>,
+    merge_candidates: Option<&'a [ExportMergeCandidateDto]>,
 }
 
-fn load_tags(
-    ctx: &Context<'_>,
-    contacts: &[knotter_core::domain::Contact],
-) -> Result<std::collections::HashMap<knotter_core::domain::ContactId, Vec<String>>> {
-    let ids: Vec<knotter_core::domain::ContactId> =
-        contacts.iter().map(|contact| contact.id).collect();
-    ctx.store
-        .tags()
-        .list_names_for_contacts(&ids)
-        .map_err(Into::into)
+impl Serialize for StreamedSnapshot<'_, '_> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+
+        let fields = if self.merge_candidates.is_some() {
+            3
+        } else {
+            2
+        };
+        let mut state = serializer.serialize_struct("ExportSnapshotDto", fields)?;
+        state.serialize_field("metadata", self.metadata)?;
+        state.serialize_field("contacts", &self.contacts)?;
+        if let Some(candidates) = self.merge_candidates {
+            state.serialize_field("merge_candidates", candidates)?;
+        }
+        state.end()
+    }
 }
 
-fn load_emails(
-    ctx: &Context<'_>,
-    contacts: &[knotter_core::domain::Contact],
-) -> Result<std::collections::HashMap<knotter_core::domain::ContactId, Vec<String>>> {
-    let ids: Vec<knotter_core::domain::ContactId> =
-        contacts.iter().map(|contact| contact.id).collect();
-    ctx.store
-        .emails()
-        .list_emails_for_contacts(&ids)
-        .map_err(Into::into)
+/// Loads tags, emails, dates, aliases, and interactions one batch of contacts
+/// at a time, so memory tracks the batch rather than the whole database.
+struct StreamedContacts<'a, 'c> {
+    ctx: &'a Context<'c>,
+    contacts: &'a [knotter_core::domain::Contact],
+    archive_reasons: &'a HashMap<ContactId, String>,
+    muted_until: &'a HashMap<ContactId, i64>,
+    priorities: &'a HashMap<ContactId, i32>,
+    format_version: u32,
+    include_audit: bool,
 }
 
-fn load_contact_dates(
+impl Serialize for StreamedContacts<'_, '_> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::{Error as _, SerializeSeq};
+
+        let mut seq = serializer.serialize_seq(Some(self.contacts.len()))?;
+        for batch in self.contacts.chunks(EXPORT_BATCH_SIZE) {
+            let items = self
+                .export_batch(batch)
+                .map_err(|err| S::Error::custom(format!("{err:#}")))?;
+            for item in &items {
+                if self.format_version == FORMAT_VERSION {
+                    seq.serialize_element(item)?;
+                } else {
+                    let value = contact_for_format_version(item, self.format_version)
+                        .map_err(S::Error::custom)?;
+                    seq.serialize_element(&value)?;
+                }
+            }
+        }
+        seq.end()
+    }
+}
+
+impl StreamedContacts<'_, '_> {
+    fn export_batch(
+        &self,
+        batch: &[knotter_core::domain::Contact],
+    ) -> Result<Vec<ExportContactDto>> {
+        let ctx = self.ctx;
+        let ids: Vec<ContactId> = batch.iter().map(|contact| contact.id).collect();
+        let mut tags = load_tags(ctx, batch)?;
+        let mut emails = load_emails(ctx, batch)?;
+        let mut dates = load_contact_dates(ctx, batch)?;
+        let mut aliases = ctx.store.contact_aliases().list_for_contacts(&ids)?;
+        let mut origins = ctx.store.contact_origins().list_for_contacts(&ids)?;
+        let mut interactions = ctx.store.interactions().list_for_contacts(&ids)?;
+        let mut attachments = ctx
+            .store
+            .interaction_attachments()
+            .list_for_contacts(&ids)?;
+        let mut audit = if self.include_audit {
+            ctx.store.contact_audit().list_for_contacts(&ids)?
+        } else {
+            HashMap::new()
+        };
+
+        Ok(batch
+            .iter()
+            .cloned()
+            .map(|contact| {
+                let tags = tags.remove(&contact.id).unwrap_or_default();
+                let emails = emails.remove(&contact.id).unwrap_or_default();
+                let dates = dates.remove(&contact.id).unwrap_or_default();
+                let dates = dates
+                    .into_iter()
+                    .map(|date| ContactDateDto {
+                        id: date.id,
+                        kind: date.kind,
+                        label: date.label,
+                        month: date.month,
+                        day: date.day,
+                        year: date.year,
+                        remind_days_before: date.remind_days_before,
+                    })
+                    .collect();
+                let interactions = interactions.remove(&contact.id).unwrap_or_default();
+                let interactions = interactions
+                    .into_iter()
+                    .map(|interaction| ExportInteractionDto {
+                        id: interaction.id,
+                        occurred_at: interaction.occurred_at,
+                        created_at: interaction.created_at,
+                        kind: interaction.kind.label(),
+                        note: interaction.note,
+                        follow_up_at: interaction.follow_up_at,
+                        direction: interaction.direction,
+                        attachments: attachments
+                            .remove(&interaction.id)
+                            .unwrap_or_default()
+                            .into_iter()
+                            .map(AttachmentDto::from)
+                            .collect(),
+                    })
+                    .collect();
+
+                ExportContactDto {
+                    id: contact.id,
+                    display_name: contact.display_name,
+                    email: contact.email,
+                    emails,
+                    phone: contact.phone,
+                    handle: contact.handle,
+                    timezone: contact.timezone,
+                    next_touchpoint_at: contact.next_touchpoint_at,
+                    cadence_days: contact.cadence_days,
+                    created_at: contact.created_at,
+                    updated_at: contact.updated_at,
+                    archived_at: contact.archived_at,
+                    archive_reason: self.archive_reasons.get(&contact.id).cloned(),
+                    muted_until: self.muted_until.get(&contact.id).copied(),
+                    tags,
+                    aliases: aliases.remove(&contact.id).unwrap_or_default(),
+                    origin: origins
+                        .remove(&contact.id)
+                        .map(|origin| ContactOriginDto::from_origin(origin, None)),
+                    dates,
+                    interactions,
+                    organization: contact.organization,
+                    priority: self.priorities.get(&contact.id).copied().unwrap_or(0),
+                    audit: audit
+                        .remove(&contact.id)
+                        .unwrap_or_default()
+                        .into_iter()
+                        .map(audit_dto)
+                        .collect(),
+                }
+            })
+            .collect())
+    }
+}
+
+fn load_export_merge_candidates(ctx: &Context<'_>) -> Result<Vec<ExportMergeCandidateDto>> {
+    let mut emails: HashMap<ContactId, Option<String>> = HashMap::new();
+    let mut primary_email = |id: ContactId| -> Result<Option<String>> {
+        if let Some(email) = emails.get(&id) {
+            return Ok(email.clone());
+        }
+        let email = ctx
+            .store
+            .contacts()
+            .get(id)?
+            .and_then(|contact| contact.email);
+        emails.insert(id, email.clone());
+        Ok(email)
+    };
+
+    let mut items = Vec::new();
+    for candidate in ctx
+        .store
+        .merge_candidates()
+        .list(&MergeCandidateQuery::default())?
+    {
+        items.push(ExportMergeCandidateDto {
+            id: candidate.id,
+            created_at: candidate.created_at,
+            status: candidate.status.as_str().to_string(),
+            reason: candidate.reason,
+            source: candidate.source,
+            contact_a_id: candidate.contact_a_id,
+            contact_a_email: primary_email(candidate.contact_a_id)?,
+            contact_b_id: candidate.contact_b_id,
+            contact_b_email: primary_email(candidate.contact_b_id)?,
+            preferred_contact_id: candidate.preferred_contact_id,
+            resolved_at: candidate.resolved_at,
+        });
+    }
+    Ok(items)
+}
+
+fn load_export_contacts(
+    ctx: &Context<'_>,
+    scope: &ExportScope,
+) -> Result<Vec<knotter_core::domain::Contact>> {
+    let Some(filter) = scope.filter.as_deref() else {
+        let mut contacts = ctx.store.contacts().list_all()?;
+        if !scope.include_archived {
+            contacts.retain(|contact| contact.archived_at.is_none());
+        }
+        return Ok(contacts);
+    };
+    let parsed = parse_filter(filter)?;
+    let mut query = ContactQuery::from_filter(&parsed)?;
+    if query.archived.is_none() && !scope.include_archived {
+        query.archived = Some(ArchivedSelector::Active);
+    }
+    Ok(ctx.store.contacts().list_contacts(
+        &query,
+        ctx.clock.now_utc(),
+        ctx.config.due_soon_days,
+        ctx.clock.local_offset(),
+    )?)
+}
+
+fn load_tags(
+    ctx: &Context<'_>,
+    contacts: &[knotter_core::domain::Contact],
+) -> Result<std::collections::HashMap<knotter_core::domain::ContactId, Vec<String>>> {
+    let ids: Vec<knotter_core::domain::ContactId> =
+        contacts.iter().map(|contact| contact.id).collect();
+    ctx.store
+        .tags()
+        .list_names_for_contacts(&ids)
+        .map_err(Into::into)
+}
+
+fn load_emails(
+    ctx: &Context<'_>,
+    contacts: &[knotter_core::domain::Contact],
+) -> Result<std::collections::HashMap<knotter_core::domain::ContactId, Vec<String>>> {
+    let ids: Vec<knotter_core::domain::ContactId> =
+        contacts.iter().map(|contact| contact.id).collect();
+    ctx.store
+        .emails()
+        .list_emails_for_contacts(&ids)
+        .map_err(Into::into)
+}
+
+fn load_contact_dates(
     ctx: &Context<'_>,
     contacts: &[knotter_core::domain::Contact],
 ) -> Result<
@@ -1085,11 +2347,15 @@ fn load_contact_dates(
         .map_err(Into::into)
 }
 
+/// Writes `data` to `out`, or to `stdout` when no file is given. In that case
+/// the data is the only thing written to `stdout`; the report is printed only
+/// after a file export.
 fn write_export(
     ctx: &Context<'_>,
     report: ExportReport,
     out: Option<&Path>,
     data: &str,
+    stdout: &mut impl Write,
 ) -> Result<()> {
     if ctx.json && out.is_none() {
         return Err(invalid_input("--json requires --out for export commands"));
@@ -1105,25 +2371,23 @@ fn write_export(
             }
             fs::write(path, data)
                 .with_context(|| format!("write export file {}", path.display()))?;
-            if ctx.json {
-                print_json(&report)?;
-            } else {
-                println!("Exported {} contacts to {}", report.count, path.display());
-            }
-            Ok(())
+            write_export_report(ctx, &report, path, stdout)
         }
         None => {
-            print!("{}", data);
+            stdout.write_all(data.as_bytes())?;
+            stdout.flush()?;
             Ok(())
         }
     }
 }
 
+/// Like `write_export`, but streams the snapshot instead of building it in memory.
 fn write_json_export(
     ctx: &Context<'_>,
     report: ExportReport,
     out: Option<&Path>,
-    data: &str,
+    snapshot: &impl Serialize,
+    stdout: &mut impl Write,
 ) -> Result<()> {
     match out {
         Some(path) => {
@@ -1133,22 +2397,44 @@ fn write_json_export(
                         .with_context(|| format!("create export directory {}", parent.display()))?;
                 }
             }
-            fs::write(path, data)
+            let file = fs::File::create(path)
                 .with_context(|| format!("write export file {}", path.display()))?;
-            if ctx.json {
-                print_json(&report)?;
-            } else {
-                println!("Exported {} contacts to {}", report.count, path.display());
+            if let Err(err) = write_pretty_json(std::io::BufWriter::new(file), snapshot) {
+                // Don't leave a truncated export behind.
+                let _ = fs::remove_file(path);
+                return Err(err.context(format!("write export file {}", path.display())));
             }
-            Ok(())
-        }
-        None => {
-            print!("{}", data);
-            Ok(())
+            write_export_report(ctx, &report, path, stdout)
         }
+        None => write_pretty_json(stdout, snapshot),
+    }
+}
+
+fn write_export_report(
+    ctx: &Context<'_>,
+    report: &ExportReport,
+    path: &Path,
+    stdout: &mut impl Write,
+) -> Result<()> {
+    if ctx.json {
+        write_json(stdout, report)
+    } else {
+        writeln!(
+            stdout,
+            "Exported {} contacts to {}",
+            report.count,
+            path.display()
+        )?;
+        Ok(())
     }
 }
 
+fn write_pretty_json(mut writer: impl Write, value: &impl Serialize) -> Result<()> {
+    serde_json::to_writer_pretty(&mut writer, value)?;
+    writer.flush()?;
+    Ok(())
+}
+
 fn import_from_source(
     ctx: &Context<'_>,
     source: &impl VcfSource,
@@ -1166,8 +2452,16 @@ fn import_from_vcf_data(
     options: ImportOptions,
 ) -> Result<()> {
     let parsed = vcf::parse_vcf(&data)?;
+    let quiet = options.quiet;
     let report = import_contacts(ctx, source_name, parsed, options)?;
-    emit_import_report(ctx, source_name, report)
+    emit_import_report(
+        ctx,
+        source_name,
+        report,
+        quiet,
+        &mut io::stdout(),
+        &mut io::stderr(),
+    )
 }
 
 fn import_contacts(
@@ -1179,12 +2473,18 @@ fn import_contacts(
     let mut report = vcf::ImportReport {
         created: 0,
         updated: 0,
+        unchanged: 0,
         skipped: parsed.skipped,
         merge_candidates_created: 0,
+        group_tags_attached: 0,
+        notes_imported: 0,
+        default_cadence_applied: 0,
+        identities_skipped: 0,
         warnings: parsed.warnings,
         dry_run: options.dry_run,
+        files: Vec::new(),
     };
-    let now = now_utc();
+    let now = ctx.clock.now_utc();
 
     let mut contacts = parsed.contacts;
     if let Some(limit) = options.limit {
@@ -1204,8 +2504,23 @@ fn import_contacts(
         ImportMode::Apply
     };
 
-    for contact in contacts {
-        let contact = apply_extra_tags(contact, &options.extra_tags);
+    let ignored = &ctx.config.contacts.ignore_identities;
+    for mut contact in contacts {
+        let is_own_card = contact
+            .emails
+            .iter()
+            .any(|email| ignored.contains_email(email))
+            || contact
+                .phone
+                .as_deref()
+                .is_some_and(|phone| ignored.contains_phone(phone));
+        if is_own_card {
+            report.identities_skipped += 1;
+            continue;
+        }
+        let group_tags = std::mem::take(&mut contact.group_tags);
+        let group_tag_count = group_tags.len();
+        let contact = apply_extra_tags(apply_extra_tags(contact, &group_tags), &options.extra_tags);
         match apply_vcf_contact(
             ctx,
             source_name,
@@ -1215,8 +2530,27 @@ fn import_contacts(
             &options,
             &mut report.warnings,
         ) {
-            Ok(ImportOutcome::Created) => report.created += 1,
-            Ok(ImportOutcome::Updated) => report.updated += 1,
+            Ok(ImportOutcome::Created {
+                note_imported,
+                default_cadence_applied,
+            }) => {
+                report.created += 1;
+                report.default_cadence_applied += usize::from(default_cadence_applied);
+                report.group_tags_attached += group_tag_count;
+                report.notes_imported += usize::from(note_imported);
+            }
+            Ok(ImportOutcome::Updated {
+                note_imported,
+                changed,
+            }) => {
+                if changed {
+                    report.updated += 1;
+                } else {
+                    report.unchanged += 1;
+                }
+                report.group_tags_attached += group_tag_count;
+                report.notes_imported += usize::from(note_imported);
+            }
             Ok(ImportOutcome::Staged {
                 candidates_created,
                 warning,
@@ -1260,30 +2594,84 @@ fn emit_import_report(
     ctx: &Context<'_>,
     source_name: &str,
     report: vcf::ImportReport,
+    quiet: bool,
+    stdout: &mut impl Write,
+    stderr: &mut impl Write,
 ) -> Result<()> {
     if ctx.json {
-        return print_json(&report);
+        return write_json(stdout, &report);
+    }
+    if quiet {
+        return print_warnings(stderr, source_name, &report.warnings);
     }
 
     let suffix = if report.dry_run { " (dry run)" } else { "" };
-    println!(
-        "Imported {} contacts{}: created {}, updated {}, skipped {}, merge candidates {}",
+    writeln!(
+        stdout,
+        "Imported {} contacts{}: created {}, updated {}, unchanged {}, skipped {}, merge candidates {}",
         source_name,
         suffix,
         report.created,
         report.updated,
+        report.unchanged,
         report.skipped,
         report.merge_candidates_created
-    );
-    if report.dry_run {
-        println!("Dry run: no changes were applied.");
+    )?;
+    if report.group_tags_attached > 0 || report.notes_imported > 0 {
+        writeln!(
+            stdout,
+            "Contacts groups: {} tags attached; notes imported: {}",
+            report.group_tags_attached, report.notes_imported
+        )?;
+    }
+    if report.default_cadence_applied > 0 {
+        writeln!(
+            stdout,
+            "Default cadence applied to {} new contact(s)",
+            report.default_cadence_applied
+        )?;
     }
-    if !report.warnings.is_empty() {
-        println!("Warnings:");
-        for warning in report.warnings {
-            println!("- {}", warning);
+    if report.identities_skipped > 0 {
+        writeln!(
+            stdout,
+            "Skipped {} card(s) with your own email or phone",
+            report.identities_skipped
+        )?;
+    }
+    if report.dry_run {
+        writeln!(stdout, "Dry run: no changes were applied.")?;
+    }
+    if report.files.len() > 1 {
+        writeln!(stdout, "Files:")?;
+        for file in &report.files {
+            match &file.error {
+                Some(error) => writeln!(stdout, "- {}: failed: {}", file.path, error)?,
+                None => writeln!(
+                    stdout,
+                    "- {}: created {}, updated {}, unchanged {}, skipped {}, merge candidates {}",
+                    file.path,
+                    file.created,
+                    file.updated,
+                    file.unchanged,
+                    file.skipped,
+                    file.merge_candidates_created
+                )?,
+            }
         }
     }
+    print_warnings(stderr, source_name, &report.warnings)
+}
+
+/// One line per warning, prefixed with the import label. Warnings go to
+/// stderr with or without `--quiet`, so stdout holds only the report.
+pub(crate) fn print_warnings(
+    stderr: &mut impl Write,
+    label: &str,
+    warnings: &[String],
+) -> Result<()> {
+    for warning in warnings {
+        writeln!(stderr, "warning: {label}: {warning}")?;
+    }
     Ok(())
 }
 
@@ -1311,9 +2699,25 @@ fn build_import_options(
         retry_skipped: common.retry_skipped,
         extra_tags,
         match_phone_name,
+        overwrite_manual: common.overwrite_manual,
+        import_notes: false,
+        quiet: common.quiet,
     })
 }
 
+/// Cadence and first touchpoint for a contact an import is about to create,
+/// when `contacts.apply_default_cadence_on_import` is set and
+/// `default_cadence_days` is configured. Staged merge contacts never get one.
+fn import_default_schedule(config: &AppConfig, now_utc: i64) -> Result<Option<(i32, i64)>> {
+    if !config.contacts.apply_default_cadence_on_import {
+        return Ok(None);
+    }
+    let Some(cadence_days) = config.default_cadence_days else {
+        return Ok(None);
+    };
+    Ok(Some((cadence_days, schedule_next(now_utc, cadence_days)?)))
+}
+
 fn parse_tags(values: &[String]) -> Result<Vec<TagName>> {
     let mut tags = Vec::with_capacity(values.len());
     for value in values {
@@ -1345,11 +2749,26 @@ fn handle_email_header(
     let direction = direction_for_header(email_ctx.identities, header);
     let counterparty = select_counterparty(email_ctx.identities, header, &direction);
     let Some(counterparty) = counterparty else {
-        report
-            .warnings
-            .push(format!("email {} missing counterparty", header.uid));
+        if addressed_only_to_identities(email_ctx.identities, header, &direction) {
+            report.identities_skipped += 1;
+        } else {
+            report
+                .warnings
+                .push(format!("email {} missing counterparty", header.uid));
+        }
         return Ok(None);
     };
+    resolve_email_address(email_ctx, header, &counterparty, report)
+}
+
+/// Matches `address` to a contact by email, then (per merge policy) by name,
+/// creating or staging a contact when nothing matches.
+fn resolve_email_address(
+    email_ctx: &EmailImportContext<'_>,
+    header: &EmailHeader,
+    counterparty: &knotter_sync::email::EmailAddress,
+    report: &mut EmailImportReport,
+) -> Result<Option<ContactId>> {
     let Some(email) = normalize_email(&counterparty.email) else {
         report
             .warnings
@@ -1363,6 +2782,7 @@ fn handle_email_header(
         .filter(|value| !value.is_empty())
         .map(|value| value.to_string())
         .unwrap_or_else(|| email.clone());
+    let tags = header_tags(email_ctx, header);
 
     if let Some(contact_id) = email_ctx
         .ctx
@@ -1389,12 +2809,11 @@ fn handle_email_header(
             return Ok(None);
         }
         report.contacts_matched += 1;
+        if update_placeholder_name(email_ctx, &contact, counterparty.name.as_deref())? {
+            report.names_updated += 1;
+        }
         if !email_ctx.options.dry_run {
-            merge_tags(
-                email_ctx.ctx,
-                &contact_id,
-                email_ctx.options.extra_tags.clone(),
-            )?;
+            merge_tags(email_ctx.ctx, &contact_id, tags.clone())?;
         }
         return Ok(Some(contact_id));
     }
@@ -1406,7 +2825,7 @@ fn handle_email_header(
             .ctx
             .store
             .contacts()
-            .list_by_display_name(&display_name)?;
+            .list_by_name_or_alias(&display_name)?;
         let active_matches: Vec<Contact> = matches
             .iter()
             .filter(|contact| contact.archived_at.is_none())
@@ -1437,11 +2856,7 @@ fn handle_email_header(
                         {
                             report.contacts_matched += 1;
                             if !email_ctx.options.dry_run {
-                                merge_tags(
-                                    email_ctx.ctx,
-                                    &owner_id,
-                                    email_ctx.options.extra_tags.clone(),
-                                )?;
+                                merge_tags(email_ctx.ctx, &owner_id, tags.clone())?;
                             }
                             return Ok(Some(owner_id));
                         }
@@ -1450,11 +2865,7 @@ fn handle_email_header(
                     return Err(err.into());
                 }
             }
-            merge_tags(
-                email_ctx.ctx,
-                &contact.id,
-                email_ctx.options.extra_tags.clone(),
-            )?;
+            merge_tags(email_ctx.ctx, &contact.id, tags.clone())?;
             report.contacts_merged += 1;
             return Ok(Some(contact.id));
         }
@@ -1475,7 +2886,9 @@ fn handle_email_header(
         }
     }
 
+    let default_schedule = import_default_schedule(email_ctx.ctx.config, email_ctx.now_utc)?;
     report.contacts_created += 1;
+    report.default_cadence_applied += usize::from(default_schedule.is_some());
     if email_ctx.options.dry_run {
         return Ok(None);
     }
@@ -1486,15 +2899,17 @@ fn handle_email_header(
         phone: None,
         handle: None,
         timezone: None,
-        next_touchpoint_at: None,
-        cadence_days: None,
+        next_touchpoint_at: default_schedule.map(|(_, next)| next),
+        cadence_days: default_schedule.map(|(cadence_days, _)| cadence_days),
         archived_at: None,
+        organization: None,
     };
-    let created = email_ctx.ctx.store.contacts().create_with_tags(
-        email_ctx.now_utc,
-        new_contact,
-        email_ctx.options.extra_tags.clone(),
-    )?;
+    let created =
+        email_ctx
+            .ctx
+            .store
+            .contacts()
+            .create_with_tags(email_ctx.now_utc, new_contact, tags)?;
     email_ctx.ctx.store.emails().add_email(
         email_ctx.now_utc,
         &created.id,
@@ -1505,6 +2920,66 @@ fn handle_email_header(
     Ok(Some(created.id))
 }
 
+/// Under `update_names`, replaces a display name that only stands in for the
+/// contact's address with the header's personal name, recording provenance
+/// and sync history. Returns whether the name changed (or would, on a dry run).
+fn update_placeholder_name(
+    email_ctx: &EmailImportContext<'_>,
+    contact: &Contact,
+    header_name: Option<&str>,
+) -> Result<bool> {
+    if !email_ctx.update_names {
+        return Ok(false);
+    }
+    let Some(name) = header_name
+        .map(str::trim)
+        .filter(|name| !name.is_empty() && !name.contains('@'))
+    else {
+        return Ok(false);
+    };
+    let store = email_ctx.ctx.store;
+    let emails = store.emails().list_emails_for_contact(&contact.id)?;
+    if contact.display_name == name || !is_email_placeholder_name(&contact.display_name, &emails) {
+        return Ok(false);
+    }
+    if email_ctx.options.dry_run {
+        return Ok(true);
+    }
+    let tx = store.connection().unchecked_transaction()?;
+    store.contacts().update(
+        email_ctx.now_utc,
+        contact.id,
+        ContactUpdate {
+            display_name: Some(name.to_string()),
+            source: Some(email_ctx.account_name.to_string()),
+            ..Default::default()
+        },
+    )?;
+    store.sync_log().record(
+        email_ctx.now_utc,
+        contact.id,
+        email_ctx.account_name,
+        &["name"],
+    )?;
+    tx.commit()?;
+    Ok(true)
+}
+
+/// Account tags plus any tags mapped from the message's Gmail labels.
+fn header_tags(email_ctx: &EmailImportContext<'_>, header: &EmailHeader) -> Vec<TagName> {
+    let mut tags = email_ctx.options.extra_tags.clone();
+    for label in &header.labels {
+        let mapped = email_ctx
+            .label_tags
+            .iter()
+            .find(|mapping| mapping.label.eq_ignore_ascii_case(label.trim()));
+        if let Some(tag) = mapped.and_then(|mapping| TagName::new(&mapping.tag).ok()) {
+            tags.push(tag);
+        }
+    }
+    dedupe_tags(tags)
+}
+
 fn stage_email_merge_candidates(
     email_ctx: &EmailImportContext<'_>,
     report: &mut EmailImportReport,
@@ -1530,6 +3005,7 @@ fn stage_email_merge_candidates(
         next_touchpoint_at: None,
         cadence_days: None,
         archived_at: Some(email_ctx.now_utc),
+        organization: None,
     };
     let tx = email_ctx.ctx.store.connection().unchecked_transaction()?;
     let created = knotter_store::repo::ContactsRepo::new(&tx).create_with_emails_and_tags(
@@ -1539,6 +3015,11 @@ fn stage_email_merge_candidates(
         vec![email.clone()],
         Some(email_ctx.account_name),
     )?;
+    knotter_store::repo::ContactsRepo::new(&tx).set_archive_reason(
+        email_ctx.now_utc,
+        created.id,
+        Some(STAGED_MERGE_ARCHIVE_REASON),
+    )?;
 
     let mut candidates_created = 0;
     for existing in matches {
@@ -1591,27 +3072,100 @@ fn select_counterparty(
     header: &EmailHeader,
     direction: &str,
 ) -> Option<knotter_sync::email::EmailAddress> {
-    let mut candidates = if direction == "outbound" {
-        header.to.clone()
+    if direction == "outbound" {
+        // Mail sent with only Cc recipients still has a counterparty.
+        first_non_identity(identities, &header.to)
+            .or_else(|| first_non_identity(identities, &header.cc))
     } else {
-        header.from.clone()
-    };
-    if candidates.is_empty() {
-        return None;
+        first_non_identity(identities, &header.from)
     }
+}
+
+/// Whether a message without a counterparty had one of the user's own addresses
+/// where the counterparty would be, rather than no usable address at all.
+fn addressed_only_to_identities(
+    identities: &std::collections::HashSet<String>,
+    header: &EmailHeader,
+    direction: &str,
+) -> bool {
+    let mut candidates: Box<dyn Iterator<Item = &knotter_sync::email::EmailAddress>> =
+        if direction == "outbound" {
+            Box::new(header.to.iter().chain(header.cc.iter()))
+        } else {
+            Box::new(header.from.iter())
+        };
+    candidates.any(|address| {
+        normalize_email(&address.email).is_some_and(|email| identities.contains(&email))
+    })
+}
+
+fn first_non_identity(
+    identities: &std::collections::HashSet<String>,
+    addresses: &[knotter_sync::email::EmailAddress],
+) -> Option<knotter_sync::email::EmailAddress> {
+    let mut candidates = addresses.to_vec();
     candidates.sort_by_key(|addr| addr.email.to_ascii_lowercase());
-    for candidate in candidates {
-        let Some(normalized) = normalize_email(&candidate.email) else {
+    candidates.into_iter().find(|candidate| {
+        normalize_email(&candidate.email).is_some_and(|email| !identities.contains(&email))
+    })
+}
+
+/// Contacts on the Cc line of a message already matched to `primary`. Unknown
+/// addresses are ignored unless the account sets `cc_creates_contacts`.
+fn cc_contacts(
+    email_ctx: &EmailImportContext<'_>,
+    header: &EmailHeader,
+    primary: ContactId,
+    report: &mut EmailImportReport,
+) -> Result<Vec<ContactId>> {
+    let mut contact_ids = Vec::new();
+    if !email_ctx.include_cc {
+        return Ok(contact_ids);
+    }
+    for address in &header.cc {
+        let Some(email) = normalize_email(&address.email) else {
+            continue;
+        };
+        if email_ctx.identities.contains(&email) {
             continue;
+        }
+        let contact_id = match email_ctx
+            .ctx
+            .store
+            .emails()
+            .find_contact_id_by_email(&email)?
+        {
+            Some(contact_id) => {
+                let archived = email_ctx
+                    .ctx
+                    .store
+                    .contacts()
+                    .get(contact_id)?
+                    .is_none_or(|contact| contact.archived_at.is_some());
+                (!archived).then_some(contact_id)
+            }
+            None if email_ctx.cc_creates_contacts => {
+                resolve_email_address(email_ctx, header, address, report)?
+            }
+            None => None,
         };
-        if !identities.contains(&normalized) {
-            return Some(candidate);
+        if let Some(contact_id) = contact_id {
+            if contact_id != primary && !contact_ids.contains(&contact_id) {
+                contact_ids.push(contact_id);
+            }
         }
     }
-    None
+    Ok(contact_ids)
 }
 
-fn normalize_identities(values: &[String], username: &str) -> std::collections::HashSet<String> {
+/// The account's own addresses plus the user's other ones from
+/// `contacts.ignore_identities`, so mail between them never names a
+/// counterparty. Empty when the account itself has none.
+fn normalize_identities(
+    values: &[String],
+    username: &str,
+    ignored: &IgnoredIdentities,
+) -> std::collections::HashSet<String> {
     let mut out = std::collections::HashSet::new();
     for value in values {
         if let Some(email) = normalize_email(value) {
@@ -1623,6 +3177,9 @@ fn normalize_identities(values: &[String], username: &str) -> std::collections::
             out.insert(email);
         }
     }
+    if !out.is_empty() {
+        out.extend(ignored.emails.iter().cloned());
+    }
     out
 }
 
@@ -1638,6 +3195,13 @@ fn format_email_note(direction: &str, subject: Option<&str>) -> String {
     }
 }
 
+fn format_cc_email_note(subject: Option<&str>) -> String {
+    match subject {
+        Some(value) if !value.trim().is_empty() => format!("Email (cc): {}", value.trim()),
+        _ => "Email (cc)".to_string(),
+    }
+}
+
 struct TelegramImportContext<'a> {
     ctx: &'a Context<'a>,
     options: &'a ImportOptions,
@@ -1646,16 +3210,57 @@ struct TelegramImportContext<'a> {
     merge_policy: TelegramMergePolicy,
     allowlist_user_ids: &'a [i64],
     snippet_len: usize,
+    metadata_tags: bool,
     messages_only: bool,
 }
 
+/// Tags for the user's profile flags, in `metadata_tags` order.
+fn telegram_metadata_tags(user: &TelegramUser) -> Vec<TagName> {
+    [
+        (user.verified, "tg-verified"),
+        (user.premium, "tg-premium"),
+        (user.contact, "tg-mutual"),
+    ]
+    .into_iter()
+    .filter(|(flag, _)| *flag)
+    .map(|(_, name)| TagName::new(name).expect("fixed telegram metadata tag"))
+    .collect()
+}
+
+/// Finds or creates the user's contact, then adds `tg-*` metadata tags when
+/// the account enables them. Tags are only ever added, so turning the option
+/// off later keeps the ones already applied.
 fn resolve_telegram_contact(
     telegram_ctx: &TelegramImportContext<'_>,
     user: &TelegramUser,
     report: &mut TelegramImportReport,
+) -> Result<Option<ContactId>> {
+    let contact_id = match_telegram_contact(telegram_ctx, user, report)?;
+    let Some(contact_id) = contact_id else {
+        return Ok(None);
+    };
+    if telegram_ctx.metadata_tags {
+        let tags = telegram_metadata_tags(user);
+        if !tags.is_empty() {
+            if !telegram_ctx.options.dry_run {
+                merge_tags(telegram_ctx.ctx, &contact_id, tags)?;
+            }
+            report.metadata_tagged += 1;
+        }
+    }
+    Ok(Some(contact_id))
+}
+
+fn match_telegram_contact(
+    telegram_ctx: &TelegramImportContext<'_>,
+    user: &TelegramUser,
+    report: &mut TelegramImportReport,
 ) -> Result<Option<ContactId>> {
     let username = normalize_telegram_username(user.username.as_deref());
-    let phone = normalize_optional_string(user.phone.as_deref());
+    let phone = import_phone(
+        telegram_ctx.ctx,
+        normalize_optional_string(user.phone.as_deref()),
+    );
     let display_name = user.display_name();
     let warn_messages_only_ambiguous = |report: &mut TelegramImportReport, label: &str| {
         report.warnings.push(format!(
@@ -1810,7 +3415,7 @@ fn resolve_telegram_contact(
             .ctx
             .store
             .contacts()
-            .list_by_display_name(&display_name)?;
+            .list_by_name_or_alias(&display_name)?;
         let active_matches: Vec<Contact> = matches
             .iter()
             .filter(|contact| contact.archived_at.is_none())
@@ -1870,7 +3475,9 @@ fn resolve_telegram_contact(
         return Ok(None);
     }
 
+    let default_schedule = import_default_schedule(telegram_ctx.ctx.config, telegram_ctx.now_utc)?;
     report.contacts_created += 1;
+    report.default_cadence_applied += usize::from(default_schedule.is_some());
     if telegram_ctx.options.dry_run {
         return Ok(None);
     }
@@ -1881,9 +3488,10 @@ fn resolve_telegram_contact(
         phone: None,
         handle: None,
         timezone: None,
-        next_touchpoint_at: None,
-        cadence_days: None,
+        next_touchpoint_at: default_schedule.map(|(_, next)| next),
+        cadence_days: default_schedule.map(|(cadence_days, _)| cadence_days),
         archived_at: None,
+        organization: None,
     };
     let created = telegram_ctx.ctx.store.contacts().create_with_tags(
         telegram_ctx.now_utc,
@@ -2004,6 +3612,7 @@ fn stage_telegram_merge_candidates(
         next_touchpoint_at: None,
         cadence_days: None,
         archived_at: Some(telegram_ctx.now_utc),
+        organization: None,
     };
     let tx = telegram_ctx
         .ctx
@@ -2015,6 +3624,11 @@ fn stage_telegram_merge_candidates(
         new_contact,
         telegram_ctx.options.extra_tags.clone(),
     )?;
+    knotter_store::repo::ContactsRepo::new(&tx).set_archive_reason(
+        telegram_ctx.now_utc,
+        created.id,
+        Some(STAGED_MERGE_ARCHIVE_REASON),
+    )?;
     knotter_store::repo::TelegramAccountsRepo::new(&tx).upsert(
         telegram_ctx.now_utc,
         TelegramAccountNew {
@@ -2077,6 +3691,18 @@ fn import_telegram_messages(
     for message in messages {
         report.messages_seen += 1;
         new_last_message_id = new_last_message_id.max(message.id);
+        if let Err(err) = ensure_sane_interaction_timestamp(
+            telegram_ctx.now_utc,
+            message.occurred_at,
+            telegram_ctx.ctx.config.sync.max_future_skew_days,
+        ) {
+            report.messages_out_of_range += 1;
+            report.warnings.push(format!(
+                "telegram message {} from user {} skipped: {err}",
+                message.id, user.id
+            ));
+            continue;
+        }
 
         if telegram_ctx.options.dry_run {
             continue;
@@ -2116,6 +3742,7 @@ fn import_telegram_messages(
                 kind: InteractionKind::Telegram,
                 note,
                 follow_up_at: None,
+                direction: InteractionDirection::from_str(&direction).ok(),
             };
             interactions.add_with_reschedule_in_tx(
                 record.created_at,
@@ -2155,6 +3782,18 @@ fn import_telegram_messages(
     Ok(false)
 }
 
+/// One of the user's own accounts, by username or phone.
+fn is_ignored_telegram_user(ctx: &Context<'_>, user: &TelegramUser) -> bool {
+    let ignored = &ctx.config.contacts.ignore_identities;
+    user.username
+        .as_deref()
+        .is_some_and(|username| ignored.contains_handle(username))
+        || user
+            .phone
+            .as_deref()
+            .is_some_and(|phone| ignored.contains_phone(phone))
+}
+
 fn normalize_telegram_username(raw: Option<&str>) -> Option<String> {
     let value = raw?;
     let trimmed = value.trim().trim_start_matches('@');
@@ -2174,48 +3813,6 @@ fn normalize_optional_string(raw: Option<&str>) -> Option<String> {
     }
 }
 
-fn snippet_from_text(text: Option<&str>, max_len: usize) -> Option<String> {
-    let raw = text?;
-    let collapsed = collapse_whitespace(raw);
-    if collapsed.is_empty() {
-        return None;
-    }
-    Some(truncate_with_ellipsis(&collapsed, max_len))
-}
-
-fn collapse_whitespace(value: &str) -> String {
-    let mut out = String::with_capacity(value.len());
-    let mut last_was_space = false;
-    for ch in value.chars() {
-        if ch.is_whitespace() {
-            if !last_was_space {
-                out.push(' ');
-                last_was_space = true;
-            }
-        } else {
-            out.push(ch);
-            last_was_space = false;
-        }
-    }
-    out.trim().to_string()
-}
-
-fn truncate_with_ellipsis(value: &str, max_len: usize) -> String {
-    if max_len == 0 {
-        return String::new();
-    }
-    let total_len = value.chars().count();
-    if total_len <= max_len {
-        return value.to_string();
-    }
-    if max_len <= 3 {
-        return value.chars().take(max_len).collect();
-    }
-    let mut out: String = value.chars().take(max_len - 3).collect();
-    out.push_str("...");
-    out
-}
-
 fn format_telegram_note(direction: &str, snippet: Option<&str>) -> String {
     let base = if direction == "outbound" {
         "Sent Telegram message"
@@ -2247,6 +3844,20 @@ fn apply_contact_dates(
     apply_contact_dates_repo(ctx.store.contact_dates(), now_utc, contact_id, dates)
 }
 
+/// Adds imported nicknames as aliases; existing aliases are never removed.
+fn apply_contact_aliases(
+    ctx: &Context<'_>,
+    now_utc: i64,
+    contact_id: ContactId,
+    aliases: &[String],
+) -> Result<()> {
+    let repo = ctx.store.contact_aliases();
+    for alias in aliases {
+        repo.add(now_utc, contact_id, alias)?;
+    }
+    Ok(())
+}
+
 fn apply_contact_dates_repo(
     repo: knotter_store::repo::ContactDatesRepo<'_>,
     now_utc: i64,
@@ -2267,6 +3878,7 @@ fn apply_contact_dates_repo(
                 day: date.day,
                 year: date.year,
                 source: Some("vcf".to_string()),
+                remind_days_before: None,
             },
         )?;
     }
@@ -2276,7 +3888,7 @@ fn apply_contact_dates_repo(
 fn resolve_password(
     password_env: Option<&str>,
     password_stdin: bool,
-    fallback_env: Option<&str>,
+    fallback: Option<&SecretSource>,
 ) -> Result<String> {
     if password_stdin {
         let mut buffer = String::new();
@@ -2290,33 +3902,13 @@ fn resolve_password(
         return Ok(password);
     }
 
-    let var = password_env
-        .or(fallback_env)
-        .ok_or_else(|| invalid_input("missing password; use --password-env or --password-stdin"))?;
-    let password = std::env::var(var)
-        .map_err(|_| invalid_input(format!("environment variable {var} is not set")))?;
-    let trimmed = password.trim();
-    if trimmed.is_empty() {
-        return Err(invalid_input(format!(
-            "environment variable {var} is empty"
-        )));
-    }
-    Ok(trimmed.to_string())
-}
-
-fn resolve_required_env(var: &str, label: &str) -> Result<String> {
-    let value = std::env::var(var).map_err(|_| {
-        invalid_input(format!(
-            "environment variable {var} is not set (required for {label})"
-        ))
-    })?;
-    let trimmed = value.trim();
-    if trimmed.is_empty() {
-        return Err(invalid_input(format!(
-            "environment variable {var} is empty (required for {label})"
-        )));
-    }
-    Ok(trimmed.to_string())
+    let source = match password_env {
+        Some(var) => SecretSource::Env(var.to_string()),
+        None => fallback.cloned().ok_or_else(|| {
+            invalid_input("missing password; use --password-env or --password-stdin")
+        })?,
+    };
+    resolve_secret(&source, "password")
 }
 
 fn default_telegram_session_path(account_name: &str) -> Result<PathBuf> {
@@ -2360,8 +3952,16 @@ fn carddav_source_label(addressbook_url: &str, username: &str) -> String {
 
 #[derive(Debug)]
 enum ImportOutcome {
-    Created,
-    Updated,
+    Created {
+        note_imported: bool,
+        default_cadence_applied: bool,
+    },
+    /// A matched contact; `changed` is false when the import matched its
+    /// current values and nothing was written.
+    Updated {
+        note_imported: bool,
+        changed: bool,
+    },
     Skipped(String),
     Staged {
         candidates_created: usize,
@@ -2382,18 +3982,33 @@ struct EmailImportContext<'a> {
     merge_policy: &'a EmailMergePolicy,
     options: &'a ImportOptions,
     identities: &'a HashSet<String>,
+    label_tags: &'a [EmailLabelTag],
+    include_cc: bool,
+    cc_creates_contacts: bool,
+    update_names: bool,
     now_utc: i64,
 }
 
+/// Canonical form of an imported phone under `contacts.normalize_phones`;
+/// values that do not parse are imported as they are.
+fn import_phone(ctx: &Context<'_>, phone: Option<String>) -> Option<String> {
+    let phone = phone?;
+    if !ctx.config.contacts.normalize_phones {
+        return Some(phone);
+    }
+    Some(canonical_phone(&phone).unwrap_or(phone))
+}
+
 fn apply_vcf_contact(
     ctx: &Context<'_>,
     source_name: &str,
     now_utc: i64,
-    contact: vcf::VcfContact,
+    mut contact: vcf::VcfContact,
     mode: ImportMode,
     options: &ImportOptions,
     warnings: &mut Vec<String>,
 ) -> Result<ImportOutcome> {
+    contact.phone = import_phone(ctx, contact.phone.take());
     let mut external_id = contact.external_id.clone();
     let mut matched_contact_id: Option<ContactId> = None;
     let mut pending_collapse: Option<(ContactId, String, usize)> = None;
@@ -2470,7 +4085,16 @@ fn apply_vcf_contact(
                     "case-insensitive external id match for {source_name}: matches {group_len} stored ids for one contact; duplicates would be collapsed"
                 ));
             }
-            return Ok(ImportOutcome::Updated);
+            return apply_vcf_update(
+                ctx,
+                source_name,
+                now_utc,
+                existing.id,
+                contact,
+                mode,
+                options,
+                warnings,
+            );
         }
         if let Some((contact_id, keep_external_id, _group_len)) = pending_collapse {
             let removed = ctx
@@ -2488,9 +4112,18 @@ fn apply_vcf_contact(
                 ));
             }
         }
-        apply_vcf_update(ctx, now_utc, existing.id, contact)?;
+        let outcome = apply_vcf_update(
+            ctx,
+            source_name,
+            now_utc,
+            existing.id,
+            contact,
+            mode,
+            options,
+            warnings,
+        )?;
         upsert_contact_source(ctx, now_utc, source_name, existing.id, external_id)?;
-        return Ok(ImportOutcome::Updated);
+        return Ok(outcome);
     }
 
     let mut matched_contacts: Vec<Contact> = Vec::new();
@@ -2530,12 +4163,20 @@ fn apply_vcf_contact(
     }
 
     if let Some(existing) = active_matches.first().cloned() {
-        if matches!(mode, ImportMode::DryRun) {
-            return Ok(ImportOutcome::Updated);
+        let outcome = apply_vcf_update(
+            ctx,
+            source_name,
+            now_utc,
+            existing.id,
+            contact,
+            mode,
+            options,
+            warnings,
+        )?;
+        if matches!(mode, ImportMode::Apply) {
+            upsert_contact_source(ctx, now_utc, source_name, existing.id, external_id)?;
         }
-        apply_vcf_update(ctx, now_utc, existing.id, contact)?;
-        upsert_contact_source(ctx, now_utc, source_name, existing.id, external_id)?;
-        return Ok(ImportOutcome::Updated);
+        return Ok(outcome);
     }
 
     if options.match_phone_name {
@@ -2558,12 +4199,20 @@ fn apply_vcf_contact(
                 );
             }
             if let Some(existing) = matches.active_matches.first().cloned() {
-                if matches!(mode, ImportMode::DryRun) {
-                    return Ok(ImportOutcome::Updated);
+                let outcome = apply_vcf_update(
+                    ctx,
+                    source_name,
+                    now_utc,
+                    existing.id,
+                    contact,
+                    mode,
+                    options,
+                    warnings,
+                )?;
+                if matches!(mode, ImportMode::Apply) {
+                    upsert_contact_source(ctx, now_utc, source_name, existing.id, external_id)?;
                 }
-                apply_vcf_update(ctx, now_utc, existing.id, contact)?;
-                upsert_contact_source(ctx, now_utc, source_name, existing.id, external_id)?;
-                return Ok(ImportOutcome::Updated);
+                return Ok(outcome);
             }
         }
     }
@@ -2574,8 +4223,17 @@ fn apply_vcf_contact(
         ));
     }
 
+    // A cadence carried in the vCard wins over the configured default.
+    let default_schedule = if contact.cadence_days.is_none() {
+        import_default_schedule(ctx.config, now_utc)?
+    } else {
+        None
+    };
     if matches!(mode, ImportMode::DryRun) {
-        return Ok(ImportOutcome::Created);
+        return Ok(ImportOutcome::Created {
+            note_imported: false,
+            default_cadence_applied: default_schedule.is_some(),
+        });
     }
 
     let vcf::VcfContact {
@@ -2587,6 +4245,10 @@ fn apply_vcf_contact(
         cadence_days,
         dates,
         external_id: _,
+        group_tags: _,
+        note,
+        aliases,
+        organization,
     } = contact;
     let primary = emails.first().cloned();
     let new_contact = ContactNew {
@@ -2595,9 +4257,10 @@ fn apply_vcf_contact(
         phone,
         handle: None,
         timezone: None,
-        next_touchpoint_at,
-        cadence_days,
+        next_touchpoint_at: next_touchpoint_at.or(default_schedule.map(|(_, next)| next)),
+        cadence_days: cadence_days.or(default_schedule.map(|(cadence_days, _)| cadence_days)),
         archived_at: None,
+        organization,
     };
     let created = ctx.store.contacts().create_with_emails_and_tags(
         now_utc,
@@ -2608,7 +4271,41 @@ fn apply_vcf_contact(
     )?;
     upsert_contact_source(ctx, now_utc, source_name, created.id, external_id)?;
     apply_contact_dates(ctx, now_utc, created.id, dates)?;
-    Ok(ImportOutcome::Created)
+    apply_contact_aliases(ctx, now_utc, created.id, &aliases)?;
+    let note_imported = import_contact_note(ctx, now_utc, created.id, note, options)?;
+    Ok(ImportOutcome::Created {
+        note_imported,
+        default_cadence_applied: default_schedule.is_some(),
+    })
+}
+
+/// Stores a contact's vCard note as an interaction unless the same note was
+/// already imported. Returns whether an interaction was added.
+fn import_contact_note(
+    ctx: &Context<'_>,
+    now_utc: i64,
+    contact_id: ContactId,
+    note: Option<String>,
+    options: &ImportOptions,
+) -> Result<bool> {
+    let Some(note) = note.filter(|_| options.import_notes) else {
+        return Ok(false);
+    };
+    let kind = InteractionKind::other(CONTACT_NOTE_KIND)?;
+    let interactions = ctx.store.interactions();
+    if interactions.exists_with_note(contact_id, &kind, &note)? {
+        return Ok(false);
+    }
+    interactions.add(knotter_store::repo::InteractionNew {
+        contact_id,
+        occurred_at: now_utc,
+        created_at: now_utc,
+        kind,
+        note,
+        follow_up_at: None,
+        direction: None,
+    })?;
+    Ok(true)
 }
 
 struct PhoneNameMatches {
@@ -2622,7 +4319,7 @@ fn match_contacts_by_phone_name(
     display_name: &str,
     phone: &str,
 ) -> Result<PhoneNameMatches> {
-    let Some(normalized_phone) = normalize_phone_for_match(phone) else {
+    let Some(normalized_phone) = phone_match_key(phone) else {
         return Ok(PhoneNameMatches {
             matched_contacts: Vec::new(),
             active_matches: Vec::new(),
@@ -2630,28 +4327,38 @@ fn match_contacts_by_phone_name(
         });
     };
 
-    let candidates = ctx.store.contacts().list_by_display_name(display_name)?;
     let mut matched_contacts = Vec::new();
     let mut active_matches = Vec::new();
     let mut archived_found = false;
 
-    for contact in candidates {
-        let Some(contact_phone) = contact.phone.as_deref() else {
-            continue;
-        };
-        let Some(contact_normalized) = normalize_phone_for_match(contact_phone) else {
-            continue;
-        };
-        if !phones_equivalent(&contact_normalized, &normalized_phone) {
-            continue;
+    // Aliases are only consulted when no display name matches the phone.
+    for by_alias in [false, true] {
+        if !matched_contacts.is_empty() {
+            break;
         }
-
-        if contact.archived_at.is_some() {
-            archived_found = true;
+        let candidates = if by_alias {
+            ctx.store.contacts().list_by_alias(display_name)?
         } else {
-            active_matches.push(contact.clone());
+            ctx.store.contacts().list_by_display_name(display_name)?
+        };
+        for contact in candidates {
+            let Some(contact_phone) = contact.phone.as_deref() else {
+                continue;
+            };
+            let Some(contact_normalized) = phone_match_key(contact_phone) else {
+                continue;
+            };
+            if !phones_equivalent(&contact_normalized, &normalized_phone) {
+                continue;
+            }
+
+            if contact.archived_at.is_some() {
+                archived_found = true;
+            } else {
+                active_matches.push(contact.clone());
+            }
+            matched_contacts.push(contact);
         }
-        matched_contacts.push(contact);
     }
 
     Ok(PhoneNameMatches {
@@ -2661,46 +4368,75 @@ fn match_contacts_by_phone_name(
     })
 }
 
+/// Match keys already carry the `+1` an 11-digit NANP number implies, so the
+/// only remaining gap is a 10-digit national number against its `+1` form.
 fn phones_equivalent(left: &str, right: &str) -> bool {
-    if left == right {
-        return true;
-    }
-    let left_stripped = strip_us_country_code(left);
-    let right_stripped = strip_us_country_code(right);
-    if let (Some(left_value), Some(right_value)) = (left_stripped, right_stripped) {
-        if left_value == right_value {
-            return true;
-        }
-    }
-    if let Some(stripped) = left_stripped {
-        if stripped == right {
-            return true;
-        }
-    }
-    if let Some(stripped) = right_stripped {
-        if stripped == left {
-            return true;
-        }
-    }
-    false
+    left == right
+        || left.strip_prefix("+1") == Some(right)
+        || right.strip_prefix("+1") == Some(left)
 }
 
-fn strip_us_country_code(value: &str) -> Option<&str> {
-    if let Some(stripped) = value.strip_prefix("+1") {
-        return Some(stripped);
-    }
-    if value.len() == 11 && value.starts_with('1') {
-        return Some(&value[1..]);
+/// Applies an import to a matched contact in a transaction, diffing the
+/// contact around the write. Real changes are committed and logged to the
+/// contact's sync history; a dry run, or an import that changed nothing, is
+/// rolled back so no-op updates leave no trace.
+#[allow(clippy::too_many_arguments)]
+fn apply_vcf_update(
+    ctx: &Context<'_>,
+    source_name: &str,
+    now_utc: i64,
+    existing_id: ContactId,
+    contact: vcf::VcfContact,
+    mode: ImportMode,
+    options: &ImportOptions,
+    warnings: &mut Vec<String>,
+) -> Result<ImportOutcome> {
+    let tx = ctx.store.connection().unchecked_transaction()?;
+    let before = snapshot(ctx, existing_id)?;
+    let note_imported = write_vcf_update(
+        ctx,
+        source_name,
+        now_utc,
+        existing_id,
+        contact,
+        options,
+        warnings,
+    )?;
+    let after = snapshot(ctx, existing_id)?;
+    let mut fields: Vec<&str> = diff_snapshots(&before, &after)
+        .iter()
+        .map(|change| change.field)
+        .collect();
+    if note_imported {
+        fields.push("note");
+    }
+    let changed = !fields.is_empty();
+    if matches!(mode, ImportMode::DryRun) || !changed {
+        drop(tx);
+        return Ok(ImportOutcome::Updated {
+            note_imported: false,
+            changed,
+        });
     }
-    None
+    ctx.store
+        .sync_log()
+        .record(now_utc, existing_id, source_name, &fields)?;
+    tx.commit()?;
+    Ok(ImportOutcome::Updated {
+        note_imported,
+        changed,
+    })
 }
 
-fn apply_vcf_update(
+fn write_vcf_update(
     ctx: &Context<'_>,
+    source_name: &str,
     now_utc: i64,
     existing_id: ContactId,
     contact: vcf::VcfContact,
-) -> Result<()> {
+    options: &ImportOptions,
+    warnings: &mut Vec<String>,
+) -> Result<bool> {
     let vcf::VcfContact {
         display_name,
         emails,
@@ -2710,6 +4446,10 @@ fn apply_vcf_update(
         cadence_days,
         dates,
         external_id: _,
+        group_tags: _,
+        note,
+        aliases,
+        organization,
     } = contact;
 
     let mut filtered_emails = Vec::new();
@@ -2725,7 +4465,7 @@ fn apply_vcf_update(
         filtered_emails.push(email.clone());
     }
     let primary = filtered_emails.first().cloned();
-    let update = ContactUpdate {
+    let mut update = ContactUpdate {
         display_name: Some(display_name),
         email: primary.clone().map(Some),
         email_source: Some("vcf".to_string()),
@@ -2735,7 +4475,12 @@ fn apply_vcf_update(
         next_touchpoint_at: next_touchpoint_at.map(Some),
         cadence_days: cadence_days.map(Some),
         archived_at: None,
+        organization: organization.map(Some),
+        source: Some(source_name.to_string()),
     };
+    if !options.overwrite_manual {
+        keep_manual_fields(ctx, existing_id, &mut update, warnings)?;
+    }
     let email_ops = if filtered_emails.is_empty() {
         EmailOps::None
     } else {
@@ -2752,6 +4497,83 @@ fn apply_vcf_update(
             .update_with_email_ops(now_utc, existing_id, update, email_ops)?;
     merge_tags(ctx, &updated.id, tags)?;
     apply_contact_dates(ctx, now_utc, updated.id, dates)?;
+    apply_contact_aliases(ctx, now_utc, updated.id, &aliases)?;
+    import_contact_note(ctx, now_utc, updated.id, note, options)
+}
+
+/// Drops imported values for fields whose last edit was manual, warning when
+/// the import would have changed them.
+fn keep_manual_fields(
+    ctx: &Context<'_>,
+    contact_id: ContactId,
+    update: &mut ContactUpdate,
+    warnings: &mut Vec<String>,
+) -> Result<()> {
+    let provenance = ctx.store.field_provenance().list_for_contact(contact_id)?;
+    let is_manual = |field: ContactField| {
+        provenance
+            .iter()
+            .any(|entry| entry.field == field.as_str() && entry.is_manual())
+    };
+    let Some(current) = ctx.store.contacts().get(contact_id)? else {
+        return Ok(());
+    };
+
+    let mut kept = Vec::new();
+    if is_manual(ContactField::DisplayName) {
+        if let Some(value) = update.display_name.take() {
+            if value != current.display_name {
+                kept.push(ContactField::DisplayName);
+            }
+        }
+    }
+    if is_manual(ContactField::Email) {
+        if let Some(value) = update.email.take() {
+            if value != current.email {
+                kept.push(ContactField::Email);
+            }
+        }
+    }
+    if is_manual(ContactField::Phone) {
+        if let Some(value) = update.phone.take() {
+            if value != current.phone {
+                kept.push(ContactField::Phone);
+            }
+        }
+    }
+    if is_manual(ContactField::Organization) {
+        if let Some(value) = update.organization.take() {
+            if value != current.organization {
+                kept.push(ContactField::Organization);
+            }
+        }
+    }
+    if is_manual(ContactField::NextTouchpointAt) {
+        if let Some(value) = update.next_touchpoint_at.take() {
+            if value != current.next_touchpoint_at {
+                kept.push(ContactField::NextTouchpointAt);
+            }
+        }
+    }
+    if is_manual(ContactField::CadenceDays) {
+        if let Some(value) = update.cadence_days.take() {
+            if value != current.cadence_days {
+                kept.push(ContactField::CadenceDays);
+            }
+        }
+    }
+
+    if !kept.is_empty() {
+        let fields = kept
+            .iter()
+            .map(|field| field.as_str())
+            .collect::<Vec<_>>()
+            .join(", ");
+        warnings.push(format!(
+            "kept manual {fields} for {}; use --overwrite-manual to replace",
+            current.display_name
+        ));
+    }
     Ok(())
 }
 
@@ -2863,6 +4685,10 @@ fn stage_merge_candidate(
         cadence_days,
         dates,
         external_id: _,
+        group_tags: _,
+        note: _,
+        aliases,
+        organization,
     } = contact;
 
     let emails_repo = knotter_store::repo::EmailsRepo::new(ctx.store.connection());
@@ -2956,6 +4782,7 @@ fn stage_merge_candidate(
         next_touchpoint_at,
         cadence_days,
         archived_at: Some(now_utc),
+        organization,
     };
     let created = knotter_store::repo::ContactsRepo::new(&tx).create_with_emails_and_tags(
         now_utc,
@@ -2964,12 +4791,21 @@ fn stage_merge_candidate(
         staged_emails,
         Some("vcf"),
     )?;
+    knotter_store::repo::ContactsRepo::new(&tx).set_archive_reason(
+        now_utc,
+        created.id,
+        Some(STAGED_MERGE_ARCHIVE_REASON),
+    )?;
     apply_contact_dates_repo(
         knotter_store::repo::ContactDatesRepo::new(&tx),
         now_utc,
         created.id,
         dates,
     )?;
+    let aliases_repo = knotter_store::repo::ContactAliasesRepo::new(&tx);
+    for alias in &aliases {
+        aliases_repo.add(now_utc, created.id, alias)?;
+    }
 
     let mut candidates_created = 0;
     for existing in matches {
@@ -3064,18 +4900,23 @@ fn handle_duplicate_email_match(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::error::{exit_status_for, EXIT_FAILURE, EXIT_PARTIAL_FAILURE};
+    use crate::progress::RecordedProgress;
     use knotter_config::{
         AppConfig, ContactSourceConfig, ContactSourceKind, EmailAccountConfig, EmailAccountTls,
         EmailMergePolicy, MacosSourceConfig, TelegramAccountConfig, TelegramMergePolicy,
         DEFAULT_TELEGRAM_SNIPPET_LEN,
     };
+    use knotter_core::time::Clock;
     use knotter_store::repo::{ContactNew, ContactSourceNew};
     use knotter_store::Store;
     use knotter_sync::email::{EmailAddress, EmailHeader};
     use knotter_sync::telegram::{TelegramMessage, TelegramMessageBatch};
     use knotter_sync::vcf;
-    use std::cell::{Cell, RefCell};
     use std::collections::{HashMap, HashSet};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Mutex;
+    use std::time::Duration;
     use tempfile::TempDir;
 
     type TelegramResult<T> = std::result::Result<T, knotter_sync::SyncError>;
@@ -3084,13 +4925,14 @@ mod tests {
         TelegramAccountConfig {
             name: name.to_string(),
             api_id: 123,
-            api_hash_env: "KNOTTER_TELEGRAM_HASH".to_string(),
+            api_hash: SecretSource::Env("KNOTTER_TELEGRAM_HASH".to_string()),
             phone: "+15551234567".to_string(),
             session_path: None,
             tag: None,
             merge_policy: TelegramMergePolicy::NameOrUsername,
             allowlist_user_ids: Vec::new(),
             snippet_len: DEFAULT_TELEGRAM_SNIPPET_LEN,
+            metadata_tags: false,
         }
     }
 
@@ -3099,12 +4941,16 @@ mod tests {
             accounts: 0,
             users_seen: 0,
             contacts_created: 0,
+            default_cadence_applied: 0,
             contacts_matched: 0,
             contacts_merged: 0,
             merge_candidates_created: 0,
+            metadata_tagged: 0,
             messages_seen: 0,
             messages_imported: 0,
             touches_recorded: 0,
+            messages_out_of_range: 0,
+            identities_skipped: 0,
             warnings: Vec::new(),
             dry_run,
         }
@@ -3118,6 +4964,9 @@ mod tests {
             first_name: first_name.map(|value| value.to_string()),
             last_name: None,
             is_bot: false,
+            verified: false,
+            premium: false,
+            contact: false,
         }
     }
 
@@ -3168,55 +5017,540 @@ mod tests {
                 }))
         }
 
-        fn ensure_authorized(&mut self) -> TelegramResult<()> {
-            Ok(())
-        }
+        fn ensure_authorized(&mut self) -> TelegramResult<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn tag_file_stems_are_filesystem_safe_and_unique() {
+        assert_eq!(tag_file_stem("family"), "family");
+        assert_eq!(tag_file_stem("work/acme:eu"), "work_acme_eu");
+        assert_eq!(tag_file_stem(".hidden"), "_hidden");
+        assert_eq!(tag_file_stem("café-friends"), "café-friends");
+
+        let mut used = HashSet::from([UNTAGGED_VCF_STEM.to_string()]);
+        assert_eq!(unique_file_stem("untagged", &mut used), "untagged-2");
+        assert_eq!(unique_file_stem("a_b", &mut used), "a_b");
+        assert_eq!(unique_file_stem("a_b", &mut used), "a_b-2");
+        assert_eq!(unique_file_stem("a_b", &mut used), "a_b-3");
+    }
+
+    #[test]
+    fn email_import_stages_ambiguous_name_matches() {
+        let store = Store::open_in_memory().expect("open store");
+        store.migrate().expect("migrate");
+        let now = 1_700_000_000;
+        for idx in 0..2 {
+            store
+                .contacts()
+                .create(
+                    now,
+                    ContactNew {
+                        display_name: "Ada".to_string(),
+                        email: Some(format!("ada{idx}@example.com")),
+                        phone: None,
+                        handle: None,
+                        timezone: None,
+                        next_touchpoint_at: None,
+                        cadence_days: None,
+                        archived_at: None,
+                        organization: None,
+                    },
+                )
+                .expect("create contact");
+        }
+
+        let config = AppConfig::default();
+        let ctx = Context {
+            store: &store,
+            json: false,
+            config: &config,
+            clock: Clock::default(),
+        };
+        let identities = std::collections::HashSet::from(["me@example.com".to_string()]);
+        let options = ImportOptions {
+            dry_run: false,
+            limit: None,
+            retry_skipped: false,
+            extra_tags: Vec::new(),
+            match_phone_name: false,
+            overwrite_manual: false,
+            import_notes: false,
+            quiet: false,
+        };
+        let email_ctx = EmailImportContext {
+            ctx: &ctx,
+            account_name: "test",
+            merge_policy: &EmailMergePolicy::NameOrEmail,
+            options: &options,
+            identities: &identities,
+            label_tags: &[],
+            include_cc: false,
+            cc_creates_contacts: false,
+            update_names: false,
+            now_utc: now,
+        };
+        let header = EmailHeader {
+            mailbox: "INBOX".to_string(),
+            uid: 1,
+            message_id: None,
+            occurred_at: now,
+            from: vec![EmailAddress {
+                name: Some("Ada".to_string()),
+                email: "ada@example.com".to_string(),
+            }],
+            to: vec![EmailAddress {
+                name: None,
+                email: "me@example.com".to_string(),
+            }],
+            cc: Vec::new(),
+            subject: None,
+            labels: Vec::new(),
+        };
+        let mut report = EmailImportReport {
+            accounts: 0,
+            mailboxes: 0,
+            messages_seen: 0,
+            messages_imported: 0,
+            contacts_created: 0,
+            default_cadence_applied: 0,
+            contacts_merged: 0,
+            contacts_matched: 0,
+            merge_candidates_created: 0,
+            touches_recorded: 0,
+            cc_touches_recorded: 0,
+            names_updated: 0,
+            messages_out_of_range: 0,
+            identities_skipped: 0,
+            mailbox_expansions: Vec::new(),
+            warnings: Vec::new(),
+            dry_run: false,
+        };
+
+        let result = handle_email_header(&email_ctx, &header, &mut report).expect("handle header");
+        let staged_id = result.expect("staged contact");
+        assert_eq!(report.contacts_created, 1);
+        assert_eq!(report.contacts_merged, 0);
+        assert_eq!(report.merge_candidates_created, 2);
+        assert!(report
+            .warnings
+            .iter()
+            .any(|warning| warning.contains("staged contact")));
+        let staged = store.contacts().get(staged_id).expect("fetch staged");
+        assert!(staged.expect("contact").archived_at.is_some());
+    }
+
+    #[test]
+    fn email_import_applies_default_cadence_to_new_contacts_only() {
+        let store = Store::open_in_memory().expect("open store");
+        store.migrate().expect("migrate");
+        let now = 1_700_000_000;
+        for idx in 0..2 {
+            store
+                .contacts()
+                .create(
+                    now,
+                    ContactNew {
+                        display_name: "Ada".to_string(),
+                        email: Some(format!("ada{idx}@example.com")),
+                        phone: None,
+                        handle: None,
+                        timezone: None,
+                        next_touchpoint_at: None,
+                        cadence_days: None,
+                        archived_at: None,
+                        organization: None,
+                    },
+                )
+                .expect("create contact");
+        }
+
+        let mut config = AppConfig {
+            default_cadence_days: Some(45),
+            ..AppConfig::default()
+        };
+        config.contacts.apply_default_cadence_on_import = true;
+        let ctx = Context {
+            store: &store,
+            json: false,
+            config: &config,
+            clock: Clock::default(),
+        };
+        let identities = std::collections::HashSet::from(["me@example.com".to_string()]);
+        let options = ImportOptions {
+            dry_run: false,
+            limit: None,
+            retry_skipped: false,
+            extra_tags: Vec::new(),
+            match_phone_name: false,
+            overwrite_manual: false,
+            import_notes: false,
+            quiet: false,
+        };
+        let email_ctx = EmailImportContext {
+            ctx: &ctx,
+            account_name: "test",
+            merge_policy: &EmailMergePolicy::NameOrEmail,
+            options: &options,
+            identities: &identities,
+            label_tags: &[],
+            include_cc: false,
+            cc_creates_contacts: false,
+            update_names: false,
+            now_utc: now,
+        };
+        let header_from = |uid: u32, name: &str, email: &str| EmailHeader {
+            mailbox: "INBOX".to_string(),
+            uid,
+            message_id: None,
+            occurred_at: now,
+            from: vec![EmailAddress {
+                name: Some(name.to_string()),
+                email: email.to_string(),
+            }],
+            to: vec![EmailAddress {
+                name: None,
+                email: "me@example.com".to_string(),
+            }],
+            cc: Vec::new(),
+            subject: None,
+            labels: Vec::new(),
+        };
+        let mut report = EmailImportReport {
+            accounts: 0,
+            mailboxes: 0,
+            messages_seen: 0,
+            messages_imported: 0,
+            contacts_created: 0,
+            default_cadence_applied: 0,
+            contacts_merged: 0,
+            contacts_matched: 0,
+            merge_candidates_created: 0,
+            touches_recorded: 0,
+            cc_touches_recorded: 0,
+            names_updated: 0,
+            messages_out_of_range: 0,
+            identities_skipped: 0,
+            mailbox_expansions: Vec::new(),
+            warnings: Vec::new(),
+            dry_run: false,
+        };
+
+        let created_id = handle_email_header(
+            &email_ctx,
+            &header_from(1, "Grace", "grace@example.com"),
+            &mut report,
+        )
+        .expect("handle header")
+        .expect("created contact");
+        let staged_id = handle_email_header(
+            &email_ctx,
+            &header_from(2, "Ada", "ada@example.com"),
+            &mut report,
+        )
+        .expect("handle header")
+        .expect("staged contact");
+
+        assert_eq!(report.contacts_created, 2);
+        assert_eq!(report.default_cadence_applied, 1);
+        let created = store
+            .contacts()
+            .get(created_id)
+            .expect("fetch created")
+            .expect("contact");
+        assert_eq!(created.cadence_days, Some(45));
+        assert_eq!(created.next_touchpoint_at, Some(now + 45 * 86_400));
+        let staged = store
+            .contacts()
+            .get(staged_id)
+            .expect("fetch staged")
+            .expect("contact");
+        assert!(staged.archived_at.is_some());
+        assert_eq!(staged.cadence_days, None);
+        assert_eq!(staged.next_touchpoint_at, None);
+    }
+
+    #[test]
+    fn email_import_update_names_replaces_only_address_names() {
+        let store = Store::open_in_memory().expect("open store");
+        store.migrate().expect("migrate");
+        let now = 1_700_000_000;
+        let real = store
+            .contacts()
+            .create(
+                now,
+                ContactNew {
+                    display_name: "Bob".to_string(),
+                    email: Some("bob@example.com".to_string()),
+                    phone: None,
+                    handle: None,
+                    timezone: None,
+                    next_touchpoint_at: None,
+                    cadence_days: None,
+                    archived_at: None,
+                    organization: None,
+                },
+            )
+            .expect("create contact");
+
+        let config = AppConfig::default();
+        let ctx = Context {
+            store: &store,
+            json: false,
+            config: &config,
+            clock: Clock::default(),
+        };
+        let identities = std::collections::HashSet::from(["me@example.com".to_string()]);
+        let options = ImportOptions {
+            dry_run: false,
+            limit: None,
+            retry_skipped: false,
+            extra_tags: Vec::new(),
+            match_phone_name: false,
+            overwrite_manual: false,
+            import_notes: false,
+            quiet: false,
+        };
+        let email_ctx = EmailImportContext {
+            ctx: &ctx,
+            account_name: "work",
+            merge_policy: &EmailMergePolicy::EmailOnly,
+            options: &options,
+            identities: &identities,
+            label_tags: &[],
+            include_cc: false,
+            cc_creates_contacts: false,
+            update_names: true,
+            now_utc: now,
+        };
+        let header_from = |uid: u32, name: Option<&str>, email: &str| EmailHeader {
+            mailbox: "INBOX".to_string(),
+            uid,
+            message_id: None,
+            occurred_at: now,
+            from: vec![EmailAddress {
+                name: name.map(str::to_string),
+                email: email.to_string(),
+            }],
+            to: vec![EmailAddress {
+                name: None,
+                email: "me@example.com".to_string(),
+            }],
+            cc: Vec::new(),
+            subject: None,
+            labels: Vec::new(),
+        };
+        let mut report = EmailImportReport {
+            accounts: 0,
+            mailboxes: 0,
+            messages_seen: 0,
+            messages_imported: 0,
+            contacts_created: 0,
+            default_cadence_applied: 0,
+            contacts_merged: 0,
+            contacts_matched: 0,
+            merge_candidates_created: 0,
+            touches_recorded: 0,
+            cc_touches_recorded: 0,
+            names_updated: 0,
+            messages_out_of_range: 0,
+            identities_skipped: 0,
+            mailbox_expansions: Vec::new(),
+            warnings: Vec::new(),
+            dry_run: false,
+        };
+
+        let jane_id = handle_email_header(
+            &email_ctx,
+            &header_from(1, None, "jsmith@corp.com"),
+            &mut report,
+        )
+        .expect("handle header")
+        .expect("created contact");
+        let name_of = |id| {
+            store
+                .contacts()
+                .get(id)
+                .expect("fetch contact")
+                .expect("contact")
+                .display_name
+        };
+        assert_eq!(name_of(jane_id), "jsmith@corp.com");
+
+        for (uid, name) in [(2, "jsmith@corp.com"), (3, "Jane Smith"), (4, "J. Smith")] {
+            handle_email_header(
+                &email_ctx,
+                &header_from(uid, Some(name), "JSmith@corp.com"),
+                &mut report,
+            )
+            .expect("handle header");
+        }
+        handle_email_header(
+            &email_ctx,
+            &header_from(5, Some("Robert Jones"), "bob@example.com"),
+            &mut report,
+        )
+        .expect("handle header");
+
+        assert_eq!(report.names_updated, 1);
+        assert_eq!(name_of(jane_id), "Jane Smith");
+        assert_eq!(name_of(real.id), "Bob");
+        let provenance = store
+            .field_provenance()
+            .list_for_contact(jane_id)
+            .expect("provenance");
+        assert!(provenance
+            .iter()
+            .any(|entry| entry.field == "display_name" && entry.source == "work"));
+        let history = store
+            .sync_log()
+            .list_for_contact(jane_id)
+            .expect("sync log");
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].fields, vec!["name"]);
+    }
+
+    #[test]
+    fn email_import_dry_run_reports_staged_counts() {
+        let store = Store::open_in_memory().expect("open store");
+        store.migrate().expect("migrate");
+        let now = 1_700_000_000;
+        for idx in 0..2 {
+            store
+                .contacts()
+                .create(
+                    now,
+                    ContactNew {
+                        display_name: "Ada".to_string(),
+                        email: Some(format!("ada{idx}@example.com")),
+                        phone: None,
+                        handle: None,
+                        timezone: None,
+                        next_touchpoint_at: None,
+                        cadence_days: None,
+                        archived_at: None,
+                        organization: None,
+                    },
+                )
+                .expect("create contact");
+        }
+
+        let config = AppConfig::default();
+        let ctx = Context {
+            store: &store,
+            json: false,
+            config: &config,
+            clock: Clock::default(),
+        };
+        let identities = std::collections::HashSet::from(["me@example.com".to_string()]);
+        let options = ImportOptions {
+            dry_run: true,
+            limit: None,
+            retry_skipped: false,
+            extra_tags: Vec::new(),
+            match_phone_name: false,
+            overwrite_manual: false,
+            import_notes: false,
+            quiet: false,
+        };
+        let email_ctx = EmailImportContext {
+            ctx: &ctx,
+            account_name: "test",
+            merge_policy: &EmailMergePolicy::NameOrEmail,
+            options: &options,
+            identities: &identities,
+            label_tags: &[],
+            include_cc: false,
+            cc_creates_contacts: false,
+            update_names: false,
+            now_utc: now,
+        };
+        let header = EmailHeader {
+            mailbox: "INBOX".to_string(),
+            uid: 1,
+            message_id: None,
+            occurred_at: now,
+            from: vec![EmailAddress {
+                name: Some("Ada".to_string()),
+                email: "ada@example.com".to_string(),
+            }],
+            to: vec![EmailAddress {
+                name: None,
+                email: "me@example.com".to_string(),
+            }],
+            cc: Vec::new(),
+            subject: None,
+            labels: Vec::new(),
+        };
+        let mut report = EmailImportReport {
+            accounts: 0,
+            mailboxes: 0,
+            messages_seen: 0,
+            messages_imported: 0,
+            contacts_created: 0,
+            default_cadence_applied: 0,
+            contacts_merged: 0,
+            contacts_matched: 0,
+            merge_candidates_created: 0,
+            touches_recorded: 0,
+            cc_touches_recorded: 0,
+            names_updated: 0,
+            messages_out_of_range: 0,
+            identities_skipped: 0,
+            mailbox_expansions: Vec::new(),
+            warnings: Vec::new(),
+            dry_run: true,
+        };
+
+        let result = handle_email_header(&email_ctx, &header, &mut report).expect("handle header");
+        assert!(result.is_none());
+        assert_eq!(report.contacts_created, 1);
+        assert_eq!(report.merge_candidates_created, 2);
+        assert!(report
+            .warnings
+            .iter()
+            .any(|warning| warning.contains("dry-run")));
     }
 
     #[test]
-    fn email_import_stages_ambiguous_name_matches() {
+    fn email_import_maps_gmail_labels_to_tags() {
         let store = Store::open_in_memory().expect("open store");
         store.migrate().expect("migrate");
         let now = 1_700_000_000;
-        for idx in 0..2 {
-            store
-                .contacts()
-                .create(
-                    now,
-                    ContactNew {
-                        display_name: "Ada".to_string(),
-                        email: Some(format!("ada{idx}@example.com")),
-                        phone: None,
-                        handle: None,
-                        timezone: None,
-                        next_touchpoint_at: None,
-                        cadence_days: None,
-                        archived_at: None,
-                    },
-                )
-                .expect("create contact");
-        }
 
         let config = AppConfig::default();
         let ctx = Context {
             store: &store,
             json: false,
             config: &config,
+            clock: Clock::default(),
         };
         let identities = std::collections::HashSet::from(["me@example.com".to_string()]);
         let options = ImportOptions {
             dry_run: false,
             limit: None,
             retry_skipped: false,
-            extra_tags: Vec::new(),
+            extra_tags: vec![TagName::new("gmail").expect("tag")],
             match_phone_name: false,
+            overwrite_manual: false,
+            import_notes: false,
+            quiet: false,
         };
+        let label_tags = vec![EmailLabelTag {
+            label: "Clients".to_string(),
+            tag: "clients".to_string(),
+        }];
         let email_ctx = EmailImportContext {
             ctx: &ctx,
             account_name: "test",
             merge_policy: &EmailMergePolicy::NameOrEmail,
             options: &options,
             identities: &identities,
+            label_tags: &label_tags,
+            include_cc: false,
+            cc_creates_contacts: false,
+            update_names: false,
             now_utc: now,
         };
         let header = EmailHeader {
@@ -3232,7 +5566,9 @@ mod tests {
                 name: None,
                 email: "me@example.com".to_string(),
             }],
+            cc: Vec::new(),
             subject: None,
+            labels: vec!["\\Important".to_string(), "clients".to_string()],
         };
         let mut report = EmailImportReport {
             accounts: 0,
@@ -3240,87 +5576,249 @@ mod tests {
             messages_seen: 0,
             messages_imported: 0,
             contacts_created: 0,
+            default_cadence_applied: 0,
             contacts_merged: 0,
             contacts_matched: 0,
             merge_candidates_created: 0,
             touches_recorded: 0,
+            cc_touches_recorded: 0,
+            names_updated: 0,
+            messages_out_of_range: 0,
+            identities_skipped: 0,
+            mailbox_expansions: Vec::new(),
             warnings: Vec::new(),
             dry_run: false,
         };
 
-        let result = handle_email_header(&email_ctx, &header, &mut report).expect("handle header");
-        let staged_id = result.expect("staged contact");
-        assert_eq!(report.contacts_created, 1);
-        assert_eq!(report.contacts_merged, 0);
-        assert_eq!(report.merge_candidates_created, 2);
-        assert!(report
-            .warnings
+        let contact_id = handle_email_header(&email_ctx, &header, &mut report)
+            .expect("handle header")
+            .expect("created contact");
+        let tags = store
+            .tags()
+            .list_for_contact(&contact_id.to_string())
+            .expect("tags");
+        let names: Vec<&str> = tags.iter().map(|tag| tag.name.as_str()).collect();
+        assert_eq!(names, vec!["clients", "gmail"]);
+    }
+
+    #[test]
+    fn email_import_reports_mailbox_progress_per_message() {
+        let store = Store::open_in_memory().expect("open store");
+        store.migrate().expect("migrate");
+        let now = 1_700_000_000;
+        store
+            .contacts()
+            .create(
+                now,
+                ContactNew {
+                    display_name: "Grace".to_string(),
+                    email: Some("grace@example.com".to_string()),
+                    phone: None,
+                    handle: None,
+                    timezone: None,
+                    next_touchpoint_at: None,
+                    cadence_days: None,
+                    archived_at: None,
+                    organization: None,
+                },
+            )
+            .expect("create contact");
+
+        let config = AppConfig::default();
+        let ctx = Context {
+            store: &store,
+            json: false,
+            config: &config,
+            clock: Clock::default(),
+        };
+        let identities = std::collections::HashSet::from(["me@example.com".to_string()]);
+        let options = ImportOptions {
+            dry_run: false,
+            limit: None,
+            retry_skipped: false,
+            extra_tags: Vec::new(),
+            match_phone_name: false,
+            overwrite_manual: false,
+            import_notes: false,
+            quiet: false,
+        };
+        let email_ctx = EmailImportContext {
+            ctx: &ctx,
+            account_name: "test",
+            merge_policy: &EmailMergePolicy::EmailOnly,
+            options: &options,
+            identities: &identities,
+            label_tags: &[],
+            include_cc: false,
+            cc_creates_contacts: false,
+            update_names: false,
+            now_utc: now,
+        };
+        let header = |uid: u32, from: &str| EmailHeader {
+            mailbox: "INBOX".to_string(),
+            uid,
+            message_id: Some(format!("<{uid}@example.com>")),
+            occurred_at: now - i64::from(uid),
+            from: vec![EmailAddress {
+                name: None,
+                email: from.to_string(),
+            }],
+            to: vec![EmailAddress {
+                name: None,
+                email: "me@example.com".to_string(),
+            }],
+            cc: Vec::new(),
+            subject: None,
+            labels: Vec::new(),
+        };
+        let result = MailboxSyncResult {
+            mailbox: "INBOX".to_string(),
+            uidvalidity: Some(1),
+            last_uid: 3,
+            headers: vec![
+                header(3, "grace@example.com"),
+                header(1, "grace@example.com"),
+                header(2, "linus@example.com"),
+            ],
+        };
+        let mut report = EmailImportReport {
+            accounts: 1,
+            mailboxes: 1,
+            messages_seen: 0,
+            messages_imported: 0,
+            contacts_created: 0,
+            default_cadence_applied: 0,
+            contacts_merged: 0,
+            contacts_matched: 0,
+            merge_candidates_created: 0,
+            touches_recorded: 0,
+            cc_touches_recorded: 0,
+            names_updated: 0,
+            messages_out_of_range: 0,
+            identities_skipped: 0,
+            mailbox_expansions: Vec::new(),
+            warnings: Vec::new(),
+            dry_run: false,
+        };
+        let mut progress = ImportProgress {
+            fetched: 3,
+            total: 3,
+            ..ImportProgress::new("INBOX", "headers")
+        };
+        let mut sink = RecordedProgress::default();
+
+        let outcome = import_mailbox_headers(
+            &email_ctx,
+            "INBOX",
+            result,
+            0,
+            &mut None,
+            &mut report,
+            &mut progress,
+            &mut sink,
+        )
+        .expect("import mailbox");
+
+        assert_eq!(outcome.last_uid, 3);
+        assert!(!outcome.stop_all);
+        let counters: Vec<(usize, usize, usize)> = sink
+            .updates
             .iter()
-            .any(|warning| warning.contains("staged contact")));
-        let staged = store.contacts().get(staged_id).expect("fetch staged");
-        assert!(staged.expect("contact").archived_at.is_some());
+            .map(|update| (update.processed, update.matched, update.contacts_created))
+            .collect();
+        assert_eq!(
+            counters,
+            vec![(0, 0, 0), (1, 1, 0), (2, 1, 1), (3, 2, 1)],
+            "one update before each message and one after the last"
+        );
+        assert_eq!(
+            sink.updates.last().map(ToString::to_string).as_deref(),
+            Some("INBOX: fetched 3/3 headers, 3 processed, 2 matched, 1 created")
+        );
+        assert_eq!(report.touches_recorded, 3);
     }
 
     #[test]
-    fn email_import_dry_run_reports_staged_counts() {
+    fn email_import_cc_matches_existing_contacts_only() {
         let store = Store::open_in_memory().expect("open store");
         store.migrate().expect("migrate");
         let now = 1_700_000_000;
-        for idx in 0..2 {
+        let create = |name: &str, email: &str, archived_at: Option<i64>| {
             store
                 .contacts()
                 .create(
                     now,
                     ContactNew {
-                        display_name: "Ada".to_string(),
-                        email: Some(format!("ada{idx}@example.com")),
+                        display_name: name.to_string(),
+                        email: Some(email.to_string()),
                         phone: None,
                         handle: None,
                         timezone: None,
                         next_touchpoint_at: None,
                         cadence_days: None,
-                        archived_at: None,
+                        archived_at,
+                        organization: None,
                     },
                 )
-                .expect("create contact");
-        }
+                .expect("create contact")
+                .id
+        };
+        let ada = create("Ada", "ada@example.com", None);
+        let bob = create("Bob", "bob@example.com", None);
+        create("Old", "old@example.com", Some(now));
 
         let config = AppConfig::default();
         let ctx = Context {
             store: &store,
             json: false,
             config: &config,
+            clock: Clock::default(),
         };
         let identities = std::collections::HashSet::from(["me@example.com".to_string()]);
         let options = ImportOptions {
-            dry_run: true,
+            dry_run: false,
             limit: None,
             retry_skipped: false,
             extra_tags: Vec::new(),
             match_phone_name: false,
+            overwrite_manual: false,
+            import_notes: false,
+            quiet: false,
         };
-        let email_ctx = EmailImportContext {
+        let mut email_ctx = EmailImportContext {
             ctx: &ctx,
             account_name: "test",
-            merge_policy: &EmailMergePolicy::NameOrEmail,
+            merge_policy: &EmailMergePolicy::EmailOnly,
             options: &options,
             identities: &identities,
+            label_tags: &[],
+            include_cc: true,
+            cc_creates_contacts: false,
+            update_names: false,
             now_utc: now,
         };
+        let address = |email: &str| EmailAddress {
+            name: None,
+            email: email.to_string(),
+        };
+        // Sent to Ada, with Bob, an archived contact, a stranger, Ada again,
+        // and one of our own addresses on Cc.
         let header = EmailHeader {
-            mailbox: "INBOX".to_string(),
-            uid: 1,
+            mailbox: "Sent".to_string(),
+            uid: 7,
             message_id: None,
             occurred_at: now,
-            from: vec![EmailAddress {
-                name: Some("Ada".to_string()),
-                email: "ada@example.com".to_string(),
-            }],
-            to: vec![EmailAddress {
-                name: None,
-                email: "me@example.com".to_string(),
-            }],
-            subject: None,
+            from: vec![address("me@example.com")],
+            to: vec![address("ada@example.com")],
+            cc: vec![
+                address("stranger@example.com"),
+                address("Bob@Example.com"),
+                address("old@example.com"),
+                address("ada@example.com"),
+                address("me@example.com"),
+            ],
+            subject: Some("plans".to_string()),
+            labels: Vec::new(),
         };
         let mut report = EmailImportReport {
             accounts: 0,
@@ -3328,22 +5826,47 @@ mod tests {
             messages_seen: 0,
             messages_imported: 0,
             contacts_created: 0,
+            default_cadence_applied: 0,
             contacts_merged: 0,
             contacts_matched: 0,
             merge_candidates_created: 0,
             touches_recorded: 0,
+            cc_touches_recorded: 0,
+            names_updated: 0,
+            messages_out_of_range: 0,
+            identities_skipped: 0,
+            mailbox_expansions: Vec::new(),
             warnings: Vec::new(),
-            dry_run: true,
+            dry_run: false,
         };
 
-        let result = handle_email_header(&email_ctx, &header, &mut report).expect("handle header");
-        assert!(result.is_none());
+        let primary = handle_email_header(&email_ctx, &header, &mut report)
+            .expect("handle header")
+            .expect("primary contact");
+        assert_eq!(primary, ada);
+        let cc = cc_contacts(&email_ctx, &header, primary, &mut report).expect("cc contacts");
+        assert_eq!(cc, vec![bob]);
+        assert_eq!(report.contacts_created, 0);
+        assert_eq!(format_cc_email_note(Some(" plans ")), "Email (cc): plans");
+
+        email_ctx.cc_creates_contacts = true;
+        let cc = cc_contacts(&email_ctx, &header, primary, &mut report).expect("cc contacts");
+        assert_eq!(cc.len(), 2);
+        assert_eq!(cc[1], bob);
         assert_eq!(report.contacts_created, 1);
-        assert_eq!(report.merge_candidates_created, 2);
-        assert!(report
-            .warnings
-            .iter()
-            .any(|warning| warning.contains("dry-run")));
+
+        email_ctx.include_cc = false;
+        let cc = cc_contacts(&email_ctx, &header, primary, &mut report).expect("cc contacts");
+        assert!(cc.is_empty());
+
+        // Outbound mail with only Cc recipients still picks a counterparty.
+        let cc_only = EmailHeader {
+            to: Vec::new(),
+            ..header
+        };
+        let counterparty =
+            select_counterparty(&identities, &cc_only, "outbound").expect("counterparty from cc");
+        assert_eq!(counterparty.email, "ada@example.com");
     }
 
     #[test]
@@ -3365,6 +5888,7 @@ mod tests {
                     next_touchpoint_at: None,
                     cadence_days: None,
                     archived_at: None,
+                    organization: None,
                 },
             )
             .expect("create active");
@@ -3381,6 +5905,7 @@ mod tests {
                     next_touchpoint_at: None,
                     cadence_days: None,
                     archived_at: Some(now),
+                    organization: None,
                 },
             )
             .expect("create archived");
@@ -3390,6 +5915,7 @@ mod tests {
             store: &store,
             json: false,
             config: &config,
+            clock: Clock::default(),
         };
         let options = ImportOptions {
             dry_run: false,
@@ -3397,6 +5923,9 @@ mod tests {
             retry_skipped: false,
             extra_tags: Vec::new(),
             match_phone_name: false,
+            overwrite_manual: false,
+            import_notes: false,
+            quiet: false,
         };
         let contact = vcf::VcfContact {
             display_name: "Updated".to_string(),
@@ -3410,6 +5939,10 @@ mod tests {
             cadence_days: None,
             dates: Vec::new(),
             external_id: None,
+            group_tags: Vec::new(),
+            note: None,
+            aliases: Vec::new(),
+            organization: None,
         };
 
         let mut warnings = Vec::new();
@@ -3423,7 +5956,7 @@ mod tests {
             &mut warnings,
         )
         .expect("apply vcf");
-        assert!(matches!(outcome, ImportOutcome::Updated));
+        assert!(matches!(outcome, ImportOutcome::Updated { .. }));
 
         let updated = store
             .contacts()
@@ -3433,7 +5966,7 @@ mod tests {
         assert_eq!(updated.display_name, "Updated");
         let candidates = store
             .merge_candidates()
-            .list(None)
+            .list(&MergeCandidateQuery::default())
             .expect("list candidates");
         assert!(candidates.is_empty());
     }
@@ -3457,6 +5990,7 @@ mod tests {
                     next_touchpoint_at: None,
                     cadence_days: None,
                     archived_at: None,
+                    organization: None,
                 },
             )
             .expect("create contact");
@@ -3478,6 +6012,7 @@ mod tests {
             store: &store,
             json: false,
             config: &config,
+            clock: Clock::default(),
         };
         let options = ImportOptions {
             dry_run: false,
@@ -3485,6 +6020,9 @@ mod tests {
             retry_skipped: false,
             extra_tags: Vec::new(),
             match_phone_name: false,
+            overwrite_manual: false,
+            import_notes: false,
+            quiet: false,
         };
         let contact = vcf::VcfContact {
             display_name: "Updated".to_string(),
@@ -3495,6 +6033,10 @@ mod tests {
             cadence_days: None,
             dates: Vec::new(),
             external_id: Some("UID-ABC".to_string()),
+            group_tags: Vec::new(),
+            note: None,
+            aliases: Vec::new(),
+            organization: None,
         };
 
         let mut warnings = Vec::new();
@@ -3508,7 +6050,7 @@ mod tests {
             &mut warnings,
         )
         .expect("apply vcf");
-        assert!(matches!(outcome, ImportOutcome::Updated));
+        assert!(matches!(outcome, ImportOutcome::Updated { .. }));
         assert!(warnings
             .iter()
             .any(|warning| warning.contains("case-insensitive external id match")));
@@ -3555,6 +6097,7 @@ mod tests {
                     next_touchpoint_at: None,
                     cadence_days: None,
                     archived_at: None,
+                    organization: None,
                 },
             )
             .expect("create primary");
@@ -3571,6 +6114,7 @@ mod tests {
                     next_touchpoint_at: None,
                     cadence_days: None,
                     archived_at: None,
+                    organization: None,
                 },
             )
             .expect("create secondary");
@@ -3602,6 +6146,7 @@ mod tests {
             store: &store,
             json: false,
             config: &config,
+            clock: Clock::default(),
         };
         let options = ImportOptions {
             dry_run: false,
@@ -3609,6 +6154,9 @@ mod tests {
             retry_skipped: false,
             extra_tags: Vec::new(),
             match_phone_name: false,
+            overwrite_manual: false,
+            import_notes: false,
+            quiet: false,
         };
         let contact = vcf::VcfContact {
             display_name: "Updated".to_string(),
@@ -3619,6 +6167,10 @@ mod tests {
             cadence_days: None,
             dates: Vec::new(),
             external_id: Some("uid-abc".to_string()),
+            group_tags: Vec::new(),
+            note: None,
+            aliases: Vec::new(),
+            organization: None,
         };
 
         let mut warnings = Vec::new();
@@ -3632,7 +6184,7 @@ mod tests {
             &mut warnings,
         )
         .expect("apply vcf");
-        assert!(matches!(outcome, ImportOutcome::Updated));
+        assert!(matches!(outcome, ImportOutcome::Updated { .. }));
         assert!(warnings
             .iter()
             .any(|warning| warning.contains("ambiguous case-insensitive")));
@@ -3680,6 +6232,7 @@ mod tests {
                     next_touchpoint_at: None,
                     cadence_days: None,
                     archived_at: None,
+                    organization: None,
                 },
             )
             .expect("create contact");
@@ -3711,6 +6264,7 @@ mod tests {
             store: &store,
             json: false,
             config: &config,
+            clock: Clock::default(),
         };
         let options = ImportOptions {
             dry_run: false,
@@ -3718,6 +6272,9 @@ mod tests {
             retry_skipped: false,
             extra_tags: Vec::new(),
             match_phone_name: false,
+            overwrite_manual: false,
+            import_notes: false,
+            quiet: false,
         };
         let incoming = vcf::VcfContact {
             display_name: "Updated".to_string(),
@@ -3728,6 +6285,10 @@ mod tests {
             cadence_days: None,
             dates: Vec::new(),
             external_id: Some("uid-abc".to_string()),
+            group_tags: Vec::new(),
+            note: None,
+            aliases: Vec::new(),
+            organization: None,
         };
 
         let mut warnings = Vec::new();
@@ -3741,7 +6302,7 @@ mod tests {
             &mut warnings,
         )
         .expect("apply vcf");
-        assert!(matches!(outcome, ImportOutcome::Updated));
+        assert!(matches!(outcome, ImportOutcome::Updated { .. }));
         assert!(warnings
             .iter()
             .any(|warning| warning.contains("case-insensitive external id match")));
@@ -3815,6 +6376,7 @@ mod tests {
                     next_touchpoint_at: None,
                     cadence_days: None,
                     archived_at: Some(now),
+                    organization: None,
                 },
             )
             .expect("create archived one");
@@ -3831,6 +6393,7 @@ mod tests {
                     next_touchpoint_at: None,
                     cadence_days: None,
                     archived_at: Some(now),
+                    organization: None,
                 },
             )
             .expect("create archived two");
@@ -3853,6 +6416,7 @@ mod tests {
             store: &store,
             json: false,
             config: &config,
+            clock: Clock::default(),
         };
         let options = ImportOptions {
             dry_run: false,
@@ -3860,6 +6424,9 @@ mod tests {
             retry_skipped: false,
             extra_tags: Vec::new(),
             match_phone_name: false,
+            overwrite_manual: false,
+            import_notes: false,
+            quiet: false,
         };
         let contact = vcf::VcfContact {
             display_name: "Incoming".to_string(),
@@ -3870,6 +6437,10 @@ mod tests {
             cadence_days: None,
             dates: Vec::new(),
             external_id: None,
+            group_tags: Vec::new(),
+            note: None,
+            aliases: Vec::new(),
+            organization: None,
         };
 
         let mut warnings = Vec::new();
@@ -3892,7 +6463,7 @@ mod tests {
 
         let candidates = store
             .merge_candidates()
-            .list(None)
+            .list(&MergeCandidateQuery::default())
             .expect("list candidates");
         assert!(candidates.is_empty());
         let contacts = store.contacts().list_all().expect("list contacts");
@@ -3918,6 +6489,7 @@ mod tests {
                     next_touchpoint_at: None,
                     cadence_days: None,
                     archived_at: None,
+                    organization: None,
                 },
             )
             .expect("create contact");
@@ -3927,6 +6499,7 @@ mod tests {
             store: &store,
             json: false,
             config: &config,
+            clock: Clock::default(),
         };
         let options = ImportOptions {
             dry_run: false,
@@ -3934,6 +6507,9 @@ mod tests {
             retry_skipped: false,
             extra_tags: Vec::new(),
             match_phone_name: true,
+            overwrite_manual: false,
+            import_notes: false,
+            quiet: false,
         };
         let contact = vcf::VcfContact {
             display_name: "Ada Lovelace".to_string(),
@@ -3944,6 +6520,10 @@ mod tests {
             cadence_days: None,
             dates: Vec::new(),
             external_id: None,
+            group_tags: Vec::new(),
+            note: None,
+            aliases: Vec::new(),
+            organization: None,
         };
 
         let mut warnings = Vec::new();
@@ -3957,7 +6537,7 @@ mod tests {
             &mut warnings,
         )
         .expect("apply vcf");
-        assert!(matches!(outcome, ImportOutcome::Updated));
+        assert!(matches!(outcome, ImportOutcome::Updated { .. }));
 
         let updated = store
             .contacts()
@@ -3988,6 +6568,7 @@ mod tests {
                     next_touchpoint_at: None,
                     cadence_days: None,
                     archived_at: None,
+                    organization: None,
                 },
             )
             .expect("create contact");
@@ -3997,6 +6578,7 @@ mod tests {
             store: &store,
             json: false,
             config: &config,
+            clock: Clock::default(),
         };
         let options = ImportOptions {
             dry_run: false,
@@ -4004,6 +6586,9 @@ mod tests {
             retry_skipped: false,
             extra_tags: Vec::new(),
             match_phone_name: true,
+            overwrite_manual: false,
+            import_notes: false,
+            quiet: false,
         };
         let contact = vcf::VcfContact {
             display_name: "Grace Hopper".to_string(),
@@ -4014,6 +6599,10 @@ mod tests {
             cadence_days: None,
             dates: Vec::new(),
             external_id: None,
+            group_tags: Vec::new(),
+            note: None,
+            aliases: Vec::new(),
+            organization: None,
         };
 
         let mut warnings = Vec::new();
@@ -4027,7 +6616,7 @@ mod tests {
             &mut warnings,
         )
         .expect("apply vcf");
-        assert!(matches!(outcome, ImportOutcome::Updated));
+        assert!(matches!(outcome, ImportOutcome::Updated { .. }));
 
         let updated = store
             .contacts()
@@ -4039,6 +6628,186 @@ mod tests {
         assert_eq!(contacts.len(), 1);
     }
 
+    #[test]
+    fn email_and_vcf_imports_skip_globally_ignored_identities() {
+        let store = Store::open_in_memory().expect("open store");
+        store.migrate().expect("migrate");
+        let now = 1_700_000_000;
+        let mut config = AppConfig::default();
+        assert!(config.contacts.ignore_identities.insert("Me@Alt.com"));
+        assert!(config
+            .contacts
+            .ignore_identities
+            .insert("+49 151 2345 6789"));
+        let ctx = Context {
+            store: &store,
+            json: false,
+            config: &config,
+            clock: Clock::default(),
+        };
+        let identities = normalize_identities(
+            &["me@example.com".to_string()],
+            "",
+            &config.contacts.ignore_identities,
+        );
+        let options = ImportOptions {
+            dry_run: false,
+            limit: None,
+            retry_skipped: false,
+            extra_tags: Vec::new(),
+            match_phone_name: false,
+            overwrite_manual: false,
+            import_notes: false,
+            quiet: false,
+        };
+        let email_ctx = EmailImportContext {
+            ctx: &ctx,
+            account_name: "test",
+            merge_policy: &EmailMergePolicy::EmailOnly,
+            options: &options,
+            identities: &identities,
+            label_tags: &[],
+            include_cc: false,
+            cc_creates_contacts: false,
+            update_names: false,
+            now_utc: now,
+        };
+        let address = |email: &str| EmailAddress {
+            name: None,
+            email: email.to_string(),
+        };
+        let header = |uid: u32, from: &str, to: &str| EmailHeader {
+            mailbox: "INBOX".to_string(),
+            uid,
+            message_id: None,
+            occurred_at: now,
+            from: vec![address(from)],
+            to: vec![address(to)],
+            cc: Vec::new(),
+            subject: None,
+            labels: Vec::new(),
+        };
+        let mut report = EmailImportReport {
+            accounts: 0,
+            mailboxes: 0,
+            messages_seen: 0,
+            messages_imported: 0,
+            contacts_created: 0,
+            default_cadence_applied: 0,
+            contacts_merged: 0,
+            contacts_matched: 0,
+            merge_candidates_created: 0,
+            touches_recorded: 0,
+            cc_touches_recorded: 0,
+            names_updated: 0,
+            messages_out_of_range: 0,
+            identities_skipped: 0,
+            mailbox_expansions: Vec::new(),
+            warnings: Vec::new(),
+            dry_run: false,
+        };
+
+        // To myself, in both directions: no contact either way.
+        let sent = handle_email_header(
+            &email_ctx,
+            &header(1, "me@example.com", "me@alt.com"),
+            &mut report,
+        )
+        .expect("sent to self");
+        let received = handle_email_header(
+            &email_ctx,
+            &header(2, "ME@alt.com", "me@example.com"),
+            &mut report,
+        )
+        .expect("received from self");
+        assert!(sent.is_none() && received.is_none());
+        assert_eq!(report.identities_skipped, 2);
+        assert!(report.warnings.is_empty());
+
+        // From the alternate address to someone else is outbound mail to them.
+        let friend = handle_email_header(
+            &email_ctx,
+            &header(3, "me@alt.com", "friend@example.com"),
+            &mut report,
+        )
+        .expect("sent from alternate")
+        .expect("friend contact");
+        let contacts = store.contacts().list_all().expect("list contacts");
+        assert_eq!(contacts.len(), 1);
+        assert_eq!(contacts[0].id, friend);
+        assert_eq!(contacts[0].email.as_deref(), Some("friend@example.com"));
+
+        let data = "BEGIN:VCARD\nFN:Me\nEMAIL:me@alt.com\nEND:VCARD\nBEGIN:VCARD\nFN:Me Mobile\nTEL:004915123456789\nEND:VCARD\nBEGIN:VCARD\nFN:Ada Lovelace\nEMAIL:ada@example.com\nEND:VCARD\n";
+        let imported =
+            import_contacts(&ctx, "vcard", vcf::parse_vcf(data).expect("parse"), options)
+                .expect("vcf import");
+        assert_eq!(imported.created, 1);
+        assert_eq!(imported.identities_skipped, 2);
+        assert!(imported.warnings.is_empty());
+    }
+
+    #[test]
+    fn vcf_import_attaches_group_tags_and_imports_notes_once() {
+        let store = Store::open_in_memory().expect("open store");
+        store.migrate().expect("migrate");
+        let config = AppConfig::default();
+        let ctx = Context {
+            store: &store,
+            json: false,
+            config: &config,
+            clock: Clock::default(),
+        };
+        let options = ImportOptions {
+            dry_run: false,
+            limit: None,
+            retry_skipped: false,
+            extra_tags: Vec::new(),
+            match_phone_name: false,
+            overwrite_manual: false,
+            import_notes: true,
+            quiet: false,
+        };
+        let data = "BEGIN:VCARD\nFN:Ada Lovelace\nEMAIL:ada@example.com\nNOTE:Met at the salon\nX-KNOTTER-GROUP:Friends\nEND:VCARD\n";
+
+        let first = import_contacts(
+            &ctx,
+            "macos",
+            vcf::parse_vcf(data).expect("parse"),
+            options.clone(),
+        )
+        .expect("first import");
+        assert_eq!(first.created, 1);
+        assert_eq!(first.group_tags_attached, 1);
+        assert_eq!(first.notes_imported, 1);
+
+        let second = import_contacts(&ctx, "macos", vcf::parse_vcf(data).expect("parse"), options)
+            .expect("second import");
+        assert_eq!(second.updated, 0);
+        assert_eq!(second.unchanged, 1);
+        assert_eq!(second.group_tags_attached, 1);
+        assert_eq!(second.notes_imported, 0);
+
+        let contact = store
+            .contacts()
+            .list_by_email("ada@example.com")
+            .expect("list")
+            .pop()
+            .expect("contact");
+        let tags = store
+            .tags()
+            .list_for_contact(&contact.id.to_string())
+            .expect("tags");
+        assert_eq!(tags.len(), 1);
+        assert_eq!(tags[0].name.as_str(), "friends");
+        let interactions = store
+            .interactions()
+            .list_for_contact(contact.id, 10, 0)
+            .expect("interactions");
+        assert_eq!(interactions.len(), 1);
+        assert_eq!(interactions[0].note, "Met at the salon");
+        assert_eq!(interactions[0].kind.label(), "other:contacts-note");
+    }
+
     #[test]
     fn vcf_dry_run_reports_staged_counts() {
         let store = Store::open_in_memory().expect("open store");
@@ -4058,6 +6827,7 @@ mod tests {
                     next_touchpoint_at: None,
                     cadence_days: None,
                     archived_at: None,
+                    organization: None,
                 },
             )
             .expect("create a");
@@ -4074,6 +6844,7 @@ mod tests {
                     next_touchpoint_at: None,
                     cadence_days: None,
                     archived_at: None,
+                    organization: None,
                 },
             )
             .expect("create b");
@@ -4083,6 +6854,7 @@ mod tests {
             store: &store,
             json: false,
             config: &config,
+            clock: Clock::default(),
         };
         let options = ImportOptions {
             dry_run: true,
@@ -4090,6 +6862,9 @@ mod tests {
             retry_skipped: false,
             extra_tags: Vec::new(),
             match_phone_name: false,
+            overwrite_manual: false,
+            import_notes: false,
+            quiet: false,
         };
         let contact = vcf::VcfContact {
             display_name: "Ada".to_string(),
@@ -4103,6 +6878,10 @@ mod tests {
             cadence_days: None,
             dates: Vec::new(),
             external_id: None,
+            group_tags: Vec::new(),
+            note: None,
+            aliases: Vec::new(),
+            organization: None,
         };
 
         let mut warnings = Vec::new();
@@ -4149,6 +6928,7 @@ mod tests {
                     next_touchpoint_at: None,
                     cadence_days: None,
                     archived_at: None,
+                    organization: None,
                 },
             )
             .expect("create contact");
@@ -4165,6 +6945,7 @@ mod tests {
                     next_touchpoint_at: None,
                     cadence_days: None,
                     archived_at: None,
+                    organization: None,
                 },
             )
             .expect("create owner");
@@ -4174,6 +6955,7 @@ mod tests {
             store: &store,
             json: false,
             config: &config,
+            clock: Clock::default(),
         };
         let identities = std::collections::HashSet::from(["me@example.com".to_string()]);
         let options = ImportOptions {
@@ -4182,6 +6964,9 @@ mod tests {
             retry_skipped: false,
             extra_tags: Vec::new(),
             match_phone_name: false,
+            overwrite_manual: false,
+            import_notes: false,
+            quiet: false,
         };
         let email_ctx = EmailImportContext {
             ctx: &ctx,
@@ -4189,6 +6974,10 @@ mod tests {
             merge_policy: &EmailMergePolicy::NameOrEmail,
             options: &options,
             identities: &identities,
+            label_tags: &[],
+            include_cc: false,
+            cc_creates_contacts: false,
+            update_names: false,
             now_utc: now,
         };
         let mut report = EmailImportReport {
@@ -4197,10 +6986,16 @@ mod tests {
             messages_seen: 0,
             messages_imported: 0,
             contacts_created: 0,
+            default_cadence_applied: 0,
             contacts_merged: 0,
             contacts_matched: 0,
             merge_candidates_created: 0,
             touches_recorded: 0,
+            cc_touches_recorded: 0,
+            names_updated: 0,
+            messages_out_of_range: 0,
+            identities_skipped: 0,
+            mailbox_expansions: Vec::new(),
             warnings: Vec::new(),
             dry_run: false,
         };
@@ -4215,7 +7010,7 @@ mod tests {
             .any(|warning| warning.contains("merge candidate created")));
         let candidates = store
             .merge_candidates()
-            .list(None)
+            .list(&MergeCandidateQuery::default())
             .expect("list candidates");
         assert_eq!(candidates.len(), 1);
         let candidate = &candidates[0];
@@ -4243,6 +7038,7 @@ mod tests {
                     next_touchpoint_at: None,
                     cadence_days: None,
                     archived_at: Some(now),
+                    organization: None,
                 },
             )
             .expect("create staged");
@@ -4259,6 +7055,7 @@ mod tests {
                     next_touchpoint_at: None,
                     cadence_days: None,
                     archived_at: None,
+                    organization: None,
                 },
             )
             .expect("create other");
@@ -4283,6 +7080,7 @@ mod tests {
             store: &store,
             json: false,
             config: &config,
+            clock: Clock::default(),
         };
         let identities = std::collections::HashSet::from(["me@example.com".to_string()]);
         let options = ImportOptions {
@@ -4291,6 +7089,9 @@ mod tests {
             retry_skipped: false,
             extra_tags: Vec::new(),
             match_phone_name: false,
+            overwrite_manual: false,
+            import_notes: false,
+            quiet: false,
         };
         let email_ctx = EmailImportContext {
             ctx: &ctx,
@@ -4298,6 +7099,10 @@ mod tests {
             merge_policy: &EmailMergePolicy::NameOrEmail,
             options: &options,
             identities: &identities,
+            label_tags: &[],
+            include_cc: false,
+            cc_creates_contacts: false,
+            update_names: false,
             now_utc: now,
         };
         let header = EmailHeader {
@@ -4313,7 +7118,9 @@ mod tests {
                 name: None,
                 email: "me@example.com".to_string(),
             }],
+            cc: Vec::new(),
             subject: None,
+            labels: Vec::new(),
         };
         let mut report = EmailImportReport {
             accounts: 0,
@@ -4321,10 +7128,16 @@ mod tests {
             messages_seen: 0,
             messages_imported: 0,
             contacts_created: 0,
+            default_cadence_applied: 0,
             contacts_merged: 0,
             contacts_matched: 0,
             merge_candidates_created: 0,
             touches_recorded: 0,
+            cc_touches_recorded: 0,
+            names_updated: 0,
+            messages_out_of_range: 0,
+            identities_skipped: 0,
+            mailbox_expansions: Vec::new(),
             warnings: Vec::new(),
             dry_run: false,
         };
@@ -4356,6 +7169,7 @@ mod tests {
                     next_touchpoint_at: None,
                     cadence_days: None,
                     archived_at: None,
+                    organization: None,
                 },
             )
             .expect("create contact");
@@ -4365,6 +7179,7 @@ mod tests {
             store: &store,
             json: false,
             config: &config,
+            clock: Clock::default(),
         };
         let options = ImportOptions {
             dry_run: false,
@@ -4372,6 +7187,9 @@ mod tests {
             retry_skipped: false,
             extra_tags: Vec::new(),
             match_phone_name: false,
+            overwrite_manual: false,
+            import_notes: false,
+            quiet: false,
         };
         let account_cfg = telegram_account_config("primary");
         let mut report = empty_telegram_report(false);
@@ -4388,6 +7206,7 @@ mod tests {
             false,
             &mut report,
             &mut client,
+            &mut RecordedProgress::default(),
             now,
         )
         .expect("import");
@@ -4401,6 +7220,67 @@ mod tests {
         assert_eq!(linked.len(), 1);
     }
 
+    #[test]
+    fn telegram_import_skips_ignored_usernames_with_or_without_at() {
+        let store = Store::open_in_memory().expect("open store");
+        store.migrate().expect("migrate");
+        let now = 1_700_000_000;
+        let mut config = AppConfig::default();
+        assert!(config.contacts.ignore_identities.insert("@MyBizHandle"));
+        assert!(config.contacts.ignore_identities.insert("sidekick"));
+        let ctx = Context {
+            store: &store,
+            json: false,
+            config: &config,
+            clock: Clock::default(),
+        };
+        let options = ImportOptions {
+            dry_run: false,
+            limit: None,
+            retry_skipped: false,
+            extra_tags: Vec::new(),
+            match_phone_name: false,
+            overwrite_manual: false,
+            import_notes: false,
+            quiet: false,
+        };
+        let account_cfg = telegram_account_config("primary");
+        let mut report = empty_telegram_report(false);
+        let mut client = FakeTelegramClient::new(
+            "primary",
+            vec![
+                telegram_user(7, Some("mybizhandle"), Some("Me Business")),
+                telegram_user(8, Some("@Sidekick"), Some("Me Again")),
+                telegram_user(9, Some("alice"), Some("Alice")),
+            ],
+        );
+
+        let mut progress = RecordedProgress::default();
+        import_telegram_account_with_client(
+            &ctx,
+            &account_cfg,
+            &options,
+            true,
+            false,
+            &mut report,
+            &mut client,
+            &mut progress,
+            now,
+        )
+        .expect("import");
+
+        assert_eq!(report.identities_skipped, 2);
+        assert_eq!(report.contacts_created, 1);
+        assert!(report.warnings.is_empty());
+        assert_eq!(
+            progress.finished.last().map(ToString::to_string).as_deref(),
+            Some("telegram primary: fetched 3/3 chats, 0 matched, 1 created")
+        );
+        let contacts = store.contacts().list_all().expect("list contacts");
+        assert_eq!(contacts.len(), 1);
+        assert_eq!(contacts[0].display_name, "Alice");
+    }
+
     #[test]
     fn telegram_messages_only_skips_contact_creation() {
         let store = Store::open_in_memory().expect("open store");
@@ -4412,6 +7292,7 @@ mod tests {
             store: &store,
             json: false,
             config: &config,
+            clock: Clock::default(),
         };
         let options = ImportOptions {
             dry_run: false,
@@ -4419,6 +7300,9 @@ mod tests {
             retry_skipped: false,
             extra_tags: Vec::new(),
             match_phone_name: false,
+            overwrite_manual: false,
+            import_notes: false,
+            quiet: false,
         };
         let account_cfg = telegram_account_config("primary");
         let mut report = empty_telegram_report(false);
@@ -4435,6 +7319,7 @@ mod tests {
             true,
             &mut report,
             &mut client,
+            &mut RecordedProgress::default(),
             now,
         )
         .expect("import");
@@ -4471,6 +7356,7 @@ mod tests {
                     next_touchpoint_at: None,
                     cadence_days: None,
                     archived_at: None,
+                    organization: None,
                 },
             )
             .expect("create contact");
@@ -4480,6 +7366,7 @@ mod tests {
             store: &store,
             json: false,
             config: &config,
+            clock: Clock::default(),
         };
         let options = ImportOptions {
             dry_run: false,
@@ -4487,6 +7374,9 @@ mod tests {
             retry_skipped: false,
             extra_tags: Vec::new(),
             match_phone_name: false,
+            overwrite_manual: false,
+            import_notes: false,
+            quiet: false,
         };
         let account_cfg = telegram_account_config("primary");
         let mut report = empty_telegram_report(false);
@@ -4513,6 +7403,7 @@ mod tests {
             true,
             &mut report,
             &mut client,
+            &mut RecordedProgress::default(),
             now,
         )
         .expect("import");
@@ -4530,6 +7421,107 @@ mod tests {
         assert!(state.is_some());
     }
 
+    #[test]
+    fn telegram_skips_messages_with_skewed_dates() {
+        let store = Store::open_in_memory().expect("open store");
+        store.migrate().expect("migrate");
+        let now = 1_700_000_000;
+
+        let contact = store
+            .contacts()
+            .create(
+                now,
+                ContactNew {
+                    display_name: "Dora".to_string(),
+                    email: None,
+                    phone: None,
+                    handle: Some("dora".to_string()),
+                    timezone: None,
+                    next_touchpoint_at: None,
+                    cadence_days: None,
+                    archived_at: None,
+                    organization: None,
+                },
+            )
+            .expect("create contact");
+
+        let config = AppConfig::default();
+        let ctx = Context {
+            store: &store,
+            json: false,
+            config: &config,
+            clock: Clock::default(),
+        };
+        let options = ImportOptions {
+            dry_run: false,
+            limit: None,
+            retry_skipped: false,
+            extra_tags: Vec::new(),
+            match_phone_name: false,
+            overwrite_manual: false,
+            import_notes: false,
+            quiet: false,
+        };
+        let account_cfg = telegram_account_config("primary");
+        let mut report = empty_telegram_report(false);
+        let user = telegram_user(55, Some("dora"), Some("Dora"));
+        let message = |id: i64, occurred_at: i64| TelegramMessage {
+            id,
+            peer_id: user.id,
+            sender_id: Some(user.id),
+            occurred_at,
+            outgoing: false,
+            text: Some("hey".to_string()),
+        };
+        let batch = TelegramMessageBatch {
+            messages: vec![
+                message(1, 0),
+                message(2, now - 5),
+                message(3, now + 30 * 86_400),
+            ],
+            complete: true,
+        };
+        let mut client =
+            FakeTelegramClient::new("primary", vec![user.clone()]).with_batch(user.id, batch);
+
+        import_telegram_account_with_client(
+            &ctx,
+            &account_cfg,
+            &options,
+            false,
+            true,
+            &mut report,
+            &mut client,
+            &mut RecordedProgress::default(),
+            now,
+        )
+        .expect("import");
+
+        assert_eq!(report.messages_seen, 3);
+        assert_eq!(report.messages_imported, 1);
+        assert_eq!(report.messages_out_of_range, 2);
+        assert_eq!(
+            report
+                .warnings
+                .iter()
+                .filter(|warning| warning.contains("skipped: interaction time"))
+                .count(),
+            2
+        );
+        let interactions = store
+            .interactions()
+            .list_for_contact(contact.id, 10, 0)
+            .expect("list interactions");
+        assert_eq!(interactions.len(), 1);
+        assert_eq!(interactions[0].occurred_at, now - 5);
+        let state = store
+            .telegram_sync()
+            .load_state("primary", user.id)
+            .expect("load state")
+            .expect("state");
+        assert_eq!(state.last_message_id, 3);
+    }
+
     #[test]
     fn telegram_contacts_only_skips_messages_and_state() {
         let store = Store::open_in_memory().expect("open store");
@@ -4541,6 +7533,7 @@ mod tests {
             store: &store,
             json: false,
             config: &config,
+            clock: Clock::default(),
         };
         let options = ImportOptions {
             dry_run: false,
@@ -4548,6 +7541,9 @@ mod tests {
             retry_skipped: false,
             extra_tags: Vec::new(),
             match_phone_name: false,
+            overwrite_manual: false,
+            import_notes: false,
+            quiet: false,
         };
         let account_cfg = telegram_account_config("primary");
         let mut report = empty_telegram_report(false);
@@ -4575,6 +7571,7 @@ mod tests {
             false,
             &mut report,
             &mut client,
+            &mut RecordedProgress::default(),
             now,
         )
         .expect("import");
@@ -4597,6 +7594,84 @@ mod tests {
         assert!(state.is_none());
     }
 
+    #[test]
+    fn telegram_metadata_tags_are_added_and_kept_when_disabled() {
+        let store = Store::open_in_memory().expect("open store");
+        store.migrate().expect("migrate");
+        let now = 1_700_000_000;
+
+        let config = AppConfig::default();
+        let ctx = Context {
+            store: &store,
+            json: false,
+            config: &config,
+            clock: Clock::default(),
+        };
+        let options = ImportOptions {
+            dry_run: false,
+            limit: None,
+            retry_skipped: false,
+            extra_tags: Vec::new(),
+            match_phone_name: false,
+            overwrite_manual: false,
+            import_notes: false,
+            quiet: false,
+        };
+        let mut account_cfg = telegram_account_config("primary");
+        account_cfg.metadata_tags = true;
+        let dana = TelegramUser {
+            verified: true,
+            contact: true,
+            ..telegram_user(31, Some("dana"), Some("Dana"))
+        };
+        let plain = telegram_user(32, Some("eve"), Some("Eve"));
+        let import = |account_cfg: &TelegramAccountConfig, users: Vec<TelegramUser>| {
+            let mut report = empty_telegram_report(false);
+            let mut client = FakeTelegramClient::new("primary", users);
+            import_telegram_account_with_client(
+                &ctx,
+                account_cfg,
+                &options,
+                true,
+                false,
+                &mut report,
+                &mut client,
+                &mut RecordedProgress::default(),
+                now,
+            )
+            .expect("import");
+            report
+        };
+        let tag_names = |name: &str| {
+            let contacts = store
+                .contacts()
+                .list_by_display_name(name)
+                .expect("list contacts");
+            store
+                .tags()
+                .list_names_for_contacts(&[contacts[0].id])
+                .expect("list tags")
+                .remove(&contacts[0].id)
+                .unwrap_or_default()
+        };
+
+        let report = import(&account_cfg, vec![dana.clone(), plain.clone()]);
+        assert_eq!(report.metadata_tagged, 1);
+        assert_eq!(tag_names("Dana"), ["tg-mutual", "tg-verified"]);
+        assert!(tag_names("Eve").is_empty());
+
+        // Turning the option off neither adds nor strips tags.
+        account_cfg.metadata_tags = false;
+        let premium = TelegramUser {
+            premium: true,
+            ..plain
+        };
+        let report = import(&account_cfg, vec![dana, premium]);
+        assert_eq!(report.metadata_tagged, 0);
+        assert_eq!(tag_names("Dana"), ["tg-mutual", "tg-verified"]);
+        assert!(tag_names("Eve").is_empty());
+    }
+
     #[test]
     fn telegram_limit_incomplete_does_not_advance_state() {
         let store = Store::open_in_memory().expect("open store");
@@ -4616,6 +7691,7 @@ mod tests {
                     next_touchpoint_at: None,
                     cadence_days: None,
                     archived_at: None,
+                    organization: None,
                 },
             )
             .expect("create contact");
@@ -4625,6 +7701,7 @@ mod tests {
             store: &store,
             json: false,
             config: &config,
+            clock: Clock::default(),
         };
         let options = ImportOptions {
             dry_run: false,
@@ -4632,6 +7709,9 @@ mod tests {
             retry_skipped: false,
             extra_tags: Vec::new(),
             match_phone_name: false,
+            overwrite_manual: false,
+            import_notes: false,
+            quiet: false,
         };
         let telegram_ctx = TelegramImportContext {
             ctx: &ctx,
@@ -4641,6 +7721,7 @@ mod tests {
             merge_policy: TelegramMergePolicy::NameOrUsername,
             allowlist_user_ids: &[],
             snippet_len: DEFAULT_TELEGRAM_SNIPPET_LEN,
+            metadata_tags: false,
             messages_only: false,
         };
         let user = telegram_user(42, Some("cara"), Some("Cara"));
@@ -4675,19 +7756,22 @@ mod tests {
 
     #[derive(Default)]
     struct TestRunner {
-        calls: RefCell<Vec<String>>,
-        fail_on: RefCell<HashSet<String>>,
-        last_force_uidvalidity: Cell<Option<bool>>,
+        calls: Mutex<Vec<String>>,
+        fail_on: Mutex<HashSet<String>>,
+        last_force_uidvalidity: Mutex<Option<bool>>,
+        fetch_delay: Option<Duration>,
+        fetches_in_flight: AtomicUsize,
+        max_fetches_in_flight: AtomicUsize,
     }
 
     impl TestRunner {
         fn fail_step(&self, label: &str) {
-            self.fail_on.borrow_mut().insert(label.to_string());
+            self.fail_on.lock().unwrap().insert(label.to_string());
         }
 
         fn record(&self, label: &str) -> Result<()> {
-            self.calls.borrow_mut().push(label.to_string());
-            if self.fail_on.borrow().contains(label) {
+            self.calls.lock().unwrap().push(label.to_string());
+            if self.fail_on.lock().unwrap().contains(label) {
                 return Err(anyhow::anyhow!("boom"));
             }
             Ok(())
@@ -4695,12 +7779,25 @@ mod tests {
     }
 
     impl SyncRunner for TestRunner {
-        fn import_source(
+        fn fetch_source(&self, _config: &AppConfig, source_name: &str) -> Result<String> {
+            let in_flight = self.fetches_in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+            self.max_fetches_in_flight
+                .fetch_max(in_flight, Ordering::SeqCst);
+            if let Some(delay) = self.fetch_delay {
+                std::thread::sleep(delay);
+            }
+            self.fetches_in_flight.fetch_sub(1, Ordering::SeqCst);
+            Ok(source_name.to_string())
+        }
+
+        fn import_source_data(
             &self,
             _ctx: &Context<'_>,
             source_name: &str,
+            data: String,
             _common: &ImportCommonArgs,
         ) -> Result<()> {
+            assert_eq!(data, source_name);
             self.record(&format!("source:{source_name}"))
         }
 
@@ -4709,9 +7806,9 @@ mod tests {
             _ctx: &Context<'_>,
             _common: &ImportCommonArgs,
             force_uidvalidity_resync: bool,
+            _jobs: usize,
         ) -> Result<()> {
-            self.last_force_uidvalidity
-                .set(Some(force_uidvalidity_resync));
+            *self.last_force_uidvalidity.lock().unwrap() = Some(force_uidvalidity_resync);
             self.record("email")
         }
 
@@ -4719,27 +7816,38 @@ mod tests {
             self.record("telegram")
         }
 
-        fn apply_loops(&self, _ctx: &Context<'_>, _dry_run: bool) -> Result<()> {
+        fn apply_loops(&self, _ctx: &Context<'_>, _dry_run: bool, _quiet: bool) -> Result<()> {
             self.record("loops")
         }
 
         fn remind(&self, _ctx: &Context<'_>, _dry_run: bool) -> Result<()> {
             self.record("remind")
         }
+
+        fn export(&self, _ctx: &Context<'_>, export: SyncExport, _out: &Path) -> Result<()> {
+            self.record(&format!("export:{}", export.as_str()))
+        }
     }
 
     fn base_sync_args() -> SyncArgs {
         SyncArgs {
+            command: None,
             common: ImportCommonArgs {
                 dry_run: false,
                 limit: None,
                 retry_skipped: false,
                 tag: Vec::new(),
+                overwrite_manual: false,
+                quiet: false,
             },
             force_uidvalidity_resync: false,
+            jobs: DEFAULT_FETCH_JOBS,
             no_telegram: false,
             no_loops: false,
             no_remind: false,
+            export_ics: None,
+            export_json: None,
+            export_vcf: None,
         }
     }
 
@@ -4752,6 +7860,7 @@ mod tests {
                 kind: ContactSourceKind::Macos(MacosSourceConfig {
                     group: None,
                     tag: None,
+                    groups_as_tags: false,
                 }),
             },
             ContactSourceConfig {
@@ -4759,6 +7868,7 @@ mod tests {
                 kind: ContactSourceKind::Macos(MacosSourceConfig {
                     group: None,
                     tag: None,
+                    groups_as_tags: false,
                 }),
             },
         ];
@@ -4767,12 +7877,18 @@ mod tests {
             host: "example.test".to_string(),
             port: 993,
             username: "user@example.test".to_string(),
-            password_env: "KNOTTER_EMAIL_PASSWORD".to_string(),
+            password: SecretSource::Env("KNOTTER_EMAIL_PASSWORD".to_string()),
             mailboxes: vec!["INBOX".to_string()],
+            exclude_mailboxes: Vec::new(),
             identities: vec!["user@example.test".to_string()],
             tag: None,
             merge_policy: EmailMergePolicy::EmailOnly,
             tls: EmailAccountTls::Tls,
+            gmail_labels: false,
+            label_tags: Vec::new(),
+            include_cc: false,
+            cc_creates_contacts: false,
+            update_names: false,
         }];
         config.loops.policy.default_cadence_days = Some(14);
 
@@ -4784,14 +7900,15 @@ mod tests {
             store: &store,
             json: false,
             config: &config,
+            clock: Clock::default(),
         };
         let runner = TestRunner::default();
         runner.fail_step("source:alpha");
 
-        let result = sync_all_with_runner(&ctx, base_sync_args(), &runner);
-        assert!(result.is_err());
+        let err = sync_all_with_runner(&ctx, base_sync_args(), &runner).unwrap_err();
+        assert_eq!(exit_status_for(&err), EXIT_PARTIAL_FAILURE);
 
-        let calls = runner.calls.borrow();
+        let calls = runner.calls.lock().unwrap();
         assert!(calls.contains(&"source:alpha".to_string()));
         assert!(calls.contains(&"source:beta".to_string()));
         assert!(calls.contains(&"email".to_string()));
@@ -4799,6 +7916,155 @@ mod tests {
         assert!(calls.contains(&"remind".to_string()));
     }
 
+    #[test]
+    fn sync_exits_with_failure_when_every_step_fails() {
+        let mut config = AppConfig::default();
+        config.contacts.sources = vec![ContactSourceConfig {
+            name: "alpha".to_string(),
+            kind: ContactSourceKind::Macos(MacosSourceConfig {
+                group: None,
+                tag: None,
+                groups_as_tags: false,
+            }),
+        }];
+
+        let temp = TempDir::new().expect("temp dir");
+        let db_path = temp.path().join("knotter.sqlite3");
+        let store = Store::open(&db_path).expect("open store");
+        store.migrate().expect("migrate");
+        let ctx = Context {
+            store: &store,
+            json: false,
+            config: &config,
+            clock: Clock::default(),
+        };
+        let runner = TestRunner::default();
+        runner.fail_step("source:alpha");
+        runner.fail_step("remind");
+        let mut args = base_sync_args();
+        args.common.quiet = true;
+
+        let err = sync_all_with_runner(&ctx, args, &runner).unwrap_err();
+        assert_eq!(exit_status_for(&err), EXIT_FAILURE);
+        assert!(err.to_string().contains("all 2 step(s) failed"));
+
+        runner.fail_on.lock().unwrap().clear();
+        let result = sync_all_with_runner(&ctx, base_sync_args(), &runner);
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn sync_runs_requested_exports_last_even_after_failures() {
+        let mut config = AppConfig::default();
+        config.contacts.sources = vec![ContactSourceConfig {
+            name: "alpha".to_string(),
+            kind: ContactSourceKind::Macos(MacosSourceConfig {
+                group: None,
+                tag: None,
+                groups_as_tags: false,
+            }),
+        }];
+
+        let temp = TempDir::new().expect("temp dir");
+        let db_path = temp.path().join("knotter.sqlite3");
+        let store = Store::open(&db_path).expect("open store");
+        store.migrate().expect("migrate");
+        let ctx = Context {
+            store: &store,
+            json: false,
+            config: &config,
+            clock: Clock::default(),
+        };
+        let runner = TestRunner::default();
+        runner.fail_step("source:alpha");
+        runner.fail_step("export:ics");
+        let mut args = base_sync_args();
+        args.export_ics = Some(temp.path().join("calendar.ics"));
+        args.export_vcf = Some(temp.path().join("contacts.vcf"));
+
+        let err = sync_all_with_runner(&ctx, args, &runner).unwrap_err();
+        assert_eq!(exit_status_for(&err), EXIT_PARTIAL_FAILURE);
+        assert!(err.to_string().contains("2 error(s)"));
+        assert_eq!(
+            *runner.calls.lock().unwrap(),
+            ["source:alpha", "remind", "export:ics", "export:vcf"]
+        );
+
+        runner.calls.lock().unwrap().clear();
+        let mut args = base_sync_args();
+        args.common.dry_run = true;
+        args.export_json = Some(temp.path().join("snapshot.json"));
+        assert!(sync_all_with_runner(&ctx, args, &runner).is_err());
+        assert!(!runner
+            .calls
+            .lock()
+            .unwrap()
+            .iter()
+            .any(|call| call.starts_with("export:")));
+    }
+
+    #[test]
+    fn sync_profile_records_a_phase_per_source() {
+        use tracing_subscriber::layer::SubscriberExt;
+
+        let mut config = AppConfig::default();
+        config.contacts.sources = ["alpha", "beta"]
+            .into_iter()
+            .map(|name| ContactSourceConfig {
+                name: name.to_string(),
+                kind: ContactSourceKind::Macos(MacosSourceConfig {
+                    group: None,
+                    tag: None,
+                    groups_as_tags: false,
+                }),
+            })
+            .collect();
+
+        let temp = TempDir::new().expect("temp dir");
+        let db_path = temp.path().join("knotter.sqlite3");
+        let store = Store::open(&db_path).expect("open store");
+        store.migrate().expect("migrate");
+        let ctx = Context {
+            store: &store,
+            json: false,
+            config: &config,
+            clock: Clock::default(),
+        };
+        let runner = TestRunner::default();
+        // One job keeps the fetches on this thread, where the test subscriber is.
+        let mut args = base_sync_args();
+        args.jobs = 1;
+        args.export_vcf = Some(temp.path().join("contacts.vcf"));
+
+        let profiler = crate::profile::Profiler::default();
+        let subscriber = tracing_subscriber::registry().with(profiler.layer());
+        tracing::subscriber::with_default(subscriber, || {
+            sync_all_with_runner(&ctx, args, &runner).expect("sync");
+        });
+
+        let phases: Vec<String> = profiler
+            .phases()
+            .into_iter()
+            .map(|timing| timing.phase)
+            .collect();
+        assert_eq!(
+            phases,
+            [
+                "source alpha fetch",
+                "source beta fetch",
+                "source alpha import",
+                "source beta import",
+                "remind",
+                "export vcf",
+            ]
+        );
+        let summary = profiler.summary();
+        for source in ["alpha", "beta"] {
+            assert!(summary.contains(&format!("source {source} fetch")));
+            assert!(summary.contains(&format!("source {source} import")));
+        }
+    }
+
     #[test]
     fn sync_respects_no_loops_and_no_remind() {
         let mut config = AppConfig::default();
@@ -4807,6 +8073,7 @@ mod tests {
             kind: ContactSourceKind::Macos(MacosSourceConfig {
                 group: None,
                 tag: None,
+                groups_as_tags: false,
             }),
         }];
         config.contacts.email_accounts = vec![EmailAccountConfig {
@@ -4814,12 +8081,18 @@ mod tests {
             host: "example.test".to_string(),
             port: 993,
             username: "user@example.test".to_string(),
-            password_env: "KNOTTER_EMAIL_PASSWORD".to_string(),
+            password: SecretSource::Env("KNOTTER_EMAIL_PASSWORD".to_string()),
             mailboxes: vec!["INBOX".to_string()],
+            exclude_mailboxes: Vec::new(),
             identities: vec!["user@example.test".to_string()],
             tag: None,
             merge_policy: EmailMergePolicy::EmailOnly,
             tls: EmailAccountTls::Tls,
+            gmail_labels: false,
+            label_tags: Vec::new(),
+            include_cc: false,
+            cc_creates_contacts: false,
+            update_names: false,
         }];
         config.loops.policy.default_cadence_days = Some(14);
 
@@ -4831,6 +8104,7 @@ mod tests {
             store: &store,
             json: false,
             config: &config,
+            clock: Clock::default(),
         };
         let runner = TestRunner::default();
         let mut args = base_sync_args();
@@ -4840,7 +8114,7 @@ mod tests {
         let result = sync_all_with_runner(&ctx, args, &runner);
         assert!(result.is_ok());
 
-        let calls = runner.calls.borrow();
+        let calls = runner.calls.lock().unwrap();
         assert!(calls.contains(&"source:alpha".to_string()));
         assert!(calls.contains(&"email".to_string()));
         assert!(!calls.contains(&"loops".to_string()));
@@ -4855,6 +8129,7 @@ mod tests {
             kind: ContactSourceKind::Macos(MacosSourceConfig {
                 group: None,
                 tag: None,
+                groups_as_tags: false,
             }),
         }];
         config.contacts.email_accounts = vec![EmailAccountConfig {
@@ -4862,12 +8137,18 @@ mod tests {
             host: "example.test".to_string(),
             port: 993,
             username: "user@example.test".to_string(),
-            password_env: "KNOTTER_EMAIL_PASSWORD".to_string(),
+            password: SecretSource::Env("KNOTTER_EMAIL_PASSWORD".to_string()),
             mailboxes: vec!["INBOX".to_string()],
+            exclude_mailboxes: Vec::new(),
             identities: vec!["user@example.test".to_string()],
             tag: None,
             merge_policy: EmailMergePolicy::EmailOnly,
             tls: EmailAccountTls::Tls,
+            gmail_labels: false,
+            label_tags: Vec::new(),
+            include_cc: false,
+            cc_creates_contacts: false,
+            update_names: false,
         }];
 
         let temp = TempDir::new().expect("temp dir");
@@ -4878,6 +8159,7 @@ mod tests {
             store: &store,
             json: false,
             config: &config,
+            clock: Clock::default(),
         };
         let runner = TestRunner::default();
         let mut args = base_sync_args();
@@ -4885,6 +8167,55 @@ mod tests {
 
         let result = sync_all_with_runner(&ctx, args, &runner);
         assert!(result.is_ok());
-        assert_eq!(runner.last_force_uidvalidity.get(), Some(true));
+        assert_eq!(*runner.last_force_uidvalidity.lock().unwrap(), Some(true));
+    }
+
+    #[test]
+    fn sync_fetches_sources_concurrently_and_imports_in_config_order() {
+        let mut config = AppConfig::default();
+        config.contacts.sources = ["alpha", "beta"]
+            .into_iter()
+            .map(|name| ContactSourceConfig {
+                name: name.to_string(),
+                kind: ContactSourceKind::Macos(MacosSourceConfig {
+                    group: None,
+                    tag: None,
+                    groups_as_tags: false,
+                }),
+            })
+            .collect();
+
+        let temp = TempDir::new().expect("temp dir");
+        let db_path = temp.path().join("knotter.sqlite3");
+        let store = Store::open(&db_path).expect("open store");
+        store.migrate().expect("migrate");
+        let ctx = Context {
+            store: &store,
+            json: false,
+            config: &config,
+            clock: Clock::default(),
+        };
+
+        for (jobs, expected_overlap) in [(2, 2), (1, 1)] {
+            let runner = TestRunner {
+                fetch_delay: Some(Duration::from_millis(200)),
+                ..TestRunner::default()
+            };
+            let mut args = base_sync_args();
+            args.jobs = jobs;
+            args.no_loops = true;
+            args.no_remind = true;
+            args.common.quiet = true;
+
+            sync_all_with_runner(&ctx, args, &runner).expect("sync");
+            assert_eq!(
+                runner.max_fetches_in_flight.load(Ordering::SeqCst),
+                expected_overlap
+            );
+            assert_eq!(
+                *runner.calls.lock().unwrap(),
+                vec!["source:alpha".to_string(), "source:beta".to_string()]
+            );
+        }
     }
 }
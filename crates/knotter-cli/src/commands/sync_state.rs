@@ -0,0 +1,298 @@
+use crate::commands::{confirm, print_json, Context};
+use crate::error::{invalid_input, not_found};
+use anyhow::Result;
+use clap::{Args, Subcommand};
+use knotter_core::time::Clock;
+use knotter_store::repo::{EmailSyncRepo, TelegramSyncRepo};
+use serde::Serialize;
+
+#[derive(Debug, Subcommand)]
+pub enum SyncCommand {
+    /// Email import positions per account and mailbox
+    #[command(name = "email-state", subcommand)]
+    EmailState(EmailStateCommand),
+    /// Telegram import positions per account and peer
+    #[command(name = "telegram-state", subcommand)]
+    TelegramState(TelegramStateCommand),
+}
+
+impl SyncCommand {
+    pub fn is_reset(&self) -> bool {
+        matches!(
+            self,
+            SyncCommand::EmailState(EmailStateCommand::Reset(_))
+                | SyncCommand::TelegramState(TelegramStateCommand::Reset(_))
+        )
+    }
+}
+
+#[derive(Debug, Subcommand)]
+pub enum EmailStateCommand {
+    /// List every account/mailbox with its UIDVALIDITY and last UID
+    Ls,
+    /// Zero an account's (or one mailbox's) position so the next import refetches
+    Reset(EmailStateResetArgs),
+}
+
+#[derive(Debug, Subcommand)]
+pub enum TelegramStateCommand {
+    /// List every account/peer with its last message id
+    Ls,
+    /// Zero an account's (or one peer's) position so the next import refetches
+    Reset(TelegramStateResetArgs),
+}
+
+#[derive(Debug, Args)]
+pub struct EmailStateResetArgs {
+    pub account: String,
+    pub mailbox: Option<String>,
+    #[arg(long, help = "Skip the confirmation prompt")]
+    pub yes: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct TelegramStateResetArgs {
+    pub account: String,
+    pub peer_id: Option<i64>,
+    #[arg(long, help = "Skip the confirmation prompt")]
+    pub yes: bool,
+}
+
+#[derive(Debug, Serialize)]
+struct EmailStateDto {
+    account: String,
+    mailbox: String,
+    uidvalidity: Option<i64>,
+    last_uid: i64,
+    last_seen_at: Option<i64>,
+}
+
+#[derive(Debug, Serialize)]
+struct TelegramStateDto {
+    account: String,
+    peer_id: i64,
+    last_message_id: i64,
+    last_seen_at: Option<i64>,
+}
+
+#[derive(Debug, Serialize)]
+struct EmailStateResetReport {
+    account: String,
+    mailboxes: Vec<String>,
+    /// Reset mailboxes that already hold messages without a Message-ID.
+    missing_message_id: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct TelegramStateResetReport {
+    account: String,
+    peers: Vec<i64>,
+}
+
+pub fn sync_command(ctx: &Context<'_>, command: SyncCommand) -> Result<()> {
+    match command {
+        SyncCommand::EmailState(EmailStateCommand::Ls) => list_email_state(ctx),
+        SyncCommand::EmailState(EmailStateCommand::Reset(args)) => reset_email_state(ctx, args),
+        SyncCommand::TelegramState(TelegramStateCommand::Ls) => list_telegram_state(ctx),
+        SyncCommand::TelegramState(TelegramStateCommand::Reset(args)) => {
+            reset_telegram_state(ctx, args)
+        }
+    }
+}
+
+fn list_email_state(ctx: &Context<'_>) -> Result<()> {
+    let states: Vec<EmailStateDto> = ctx
+        .store
+        .email_sync()
+        .list_states()?
+        .into_iter()
+        .map(|state| EmailStateDto {
+            account: state.account,
+            mailbox: state.mailbox,
+            uidvalidity: state.uidvalidity,
+            last_uid: state.last_uid,
+            last_seen_at: state.last_seen_at,
+        })
+        .collect();
+    if ctx.json {
+        return print_json(&states);
+    }
+    if states.is_empty() {
+        println!("no email sync state");
+        return Ok(());
+    }
+    for state in states {
+        println!(
+            "{}  {}  uidvalidity {}  last uid {}  last seen {}",
+            state.account,
+            state.mailbox,
+            state
+                .uidvalidity
+                .map(|value| value.to_string())
+                .unwrap_or_else(|| "-".to_string()),
+            state.last_uid,
+            format_last_seen(state.last_seen_at, &ctx.clock)
+        );
+    }
+    Ok(())
+}
+
+fn list_telegram_state(ctx: &Context<'_>) -> Result<()> {
+    let states: Vec<TelegramStateDto> = ctx
+        .store
+        .telegram_sync()
+        .list_states()?
+        .into_iter()
+        .map(|state| TelegramStateDto {
+            account: state.account,
+            peer_id: state.peer_id,
+            last_message_id: state.last_message_id,
+            last_seen_at: state.last_seen_at,
+        })
+        .collect();
+    if ctx.json {
+        return print_json(&states);
+    }
+    if states.is_empty() {
+        println!("no telegram sync state");
+        return Ok(());
+    }
+    for state in states {
+        println!(
+            "{}  peer {}  last message {}  last seen {}",
+            state.account,
+            state.peer_id,
+            state.last_message_id,
+            format_last_seen(state.last_seen_at, &ctx.clock)
+        );
+    }
+    Ok(())
+}
+
+fn reset_email_state(ctx: &Context<'_>, args: EmailStateResetArgs) -> Result<()> {
+    let repo = ctx.store.email_sync();
+    let mailboxes: Vec<String> = repo
+        .list_states()?
+        .into_iter()
+        .filter(|state| {
+            state.account == args.account
+                && args
+                    .mailbox
+                    .as_deref()
+                    .is_none_or(|mailbox| state.mailbox == mailbox)
+        })
+        .map(|state| state.mailbox)
+        .collect();
+    if mailboxes.is_empty() {
+        return Err(not_found(match &args.mailbox {
+            Some(mailbox) => format!(
+                "no email sync state for account {} mailbox {mailbox}",
+                args.account
+            ),
+            None => format!("no email sync state for account {}", args.account),
+        }));
+    }
+    let mut missing_message_id = Vec::new();
+    for mailbox in &mailboxes {
+        if repo.has_null_message_id(&args.account, mailbox)? {
+            missing_message_id.push(mailbox.clone());
+        }
+    }
+
+    eprintln!(
+        "Resetting email sync state for {} ({}); the next import refetches every message.",
+        args.account,
+        mailboxes.join(", ")
+    );
+    if !missing_message_id.is_empty() {
+        eprintln!(
+            "warning: {} already hold messages without a Message-ID; if the server renumbered UIDs, refetching them logs duplicate touches.",
+            missing_message_id.join(", ")
+        );
+    }
+    if !args.yes {
+        confirm_reset()?;
+    }
+
+    let tx = ctx.store.connection().unchecked_transaction()?;
+    EmailSyncRepo::new(&tx).reset_state(&args.account, args.mailbox.as_deref())?;
+    tx.commit()?;
+
+    let report = EmailStateResetReport {
+        account: args.account,
+        mailboxes,
+        missing_message_id,
+    };
+    if ctx.json {
+        return print_json(&report);
+    }
+    println!(
+        "reset email sync state for {}: {}",
+        report.account,
+        report.mailboxes.join(", ")
+    );
+    Ok(())
+}
+
+fn reset_telegram_state(ctx: &Context<'_>, args: TelegramStateResetArgs) -> Result<()> {
+    let peers: Vec<i64> = ctx
+        .store
+        .telegram_sync()
+        .list_states()?
+        .into_iter()
+        .filter(|state| {
+            state.account == args.account
+                && args.peer_id.is_none_or(|peer_id| state.peer_id == peer_id)
+        })
+        .map(|state| state.peer_id)
+        .collect();
+    if peers.is_empty() {
+        return Err(not_found(match args.peer_id {
+            Some(peer_id) => format!(
+                "no telegram sync state for account {} peer {peer_id}",
+                args.account
+            ),
+            None => format!("no telegram sync state for account {}", args.account),
+        }));
+    }
+
+    eprintln!(
+        "Resetting telegram sync state for {} ({} peer(s)); the next import refetches their history.",
+        args.account,
+        peers.len()
+    );
+    if !args.yes {
+        confirm_reset()?;
+    }
+
+    let tx = ctx.store.connection().unchecked_transaction()?;
+    TelegramSyncRepo::new(&tx).reset_state(&args.account, args.peer_id)?;
+    tx.commit()?;
+
+    let report = TelegramStateResetReport {
+        account: args.account,
+        peers,
+    };
+    if ctx.json {
+        return print_json(&report);
+    }
+    println!(
+        "reset telegram sync state for {}: {} peer(s)",
+        report.account,
+        report.peers.len()
+    );
+    Ok(())
+}
+
+fn confirm_reset() -> Result<()> {
+    if !confirm("Reset?", "reset")? {
+        return Err(invalid_input("reset cancelled; nothing changed"));
+    }
+    Ok(())
+}
+
+fn format_last_seen(value: Option<i64>, clock: &Clock) -> String {
+    value
+        .map(|ts| clock.format_timestamp_datetime(ts))
+        .unwrap_or_else(|| "-".to_string())
+}
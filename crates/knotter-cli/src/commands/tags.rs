@@ -1,8 +1,8 @@
-use crate::commands::{loops, print_json, Context};
-use crate::error::invalid_input;
-use crate::util::parse_contact_id;
+use crate::commands::dry_run::{print_dry_run, run_contact_change};
+use crate::commands::{confirm, loops, print_json, resolve_contact_arg, Context};
+use crate::error::{invalid_input, not_found};
 use anyhow::Result;
-use clap::{Args, Subcommand};
+use clap::{Args, Subcommand, ValueEnum};
 use knotter_core::domain::TagName;
 use serde::Serialize;
 
@@ -10,27 +10,100 @@ use serde::Serialize;
 pub enum TagCommand {
     Add(TagAddArgs),
     Rm(TagRemoveArgs),
+    /// List tags with how many contacts carry each
     Ls(TagListArgs),
+    /// Rename a tag on every contact, merging into the new name if it exists
+    Rename(TagRenameArgs),
+    /// Fold several tags into one target tag
+    Merge(TagMergeArgs),
+    /// Remove a tag from every contact and delete it
+    Delete(TagDeleteArgs),
 }
 
 #[derive(Debug, Args)]
 pub struct TagAddArgs {
+    /// Contact id, or a name, alias, email, or @handle
     pub id: String,
     pub tag: String,
     #[arg(long)]
     pub apply_loop: bool,
+    #[arg(long, help = "Show the change without saving it")]
+    pub dry_run: bool,
 }
 
 #[derive(Debug, Args)]
 pub struct TagRemoveArgs {
+    /// Contact id, or a name, alias, email, or @handle
     pub id: String,
     pub tag: String,
     #[arg(long)]
     pub apply_loop: bool,
+    #[arg(long, help = "Show the change without saving it")]
+    pub dry_run: bool,
 }
 
 #[derive(Debug, Args)]
-pub struct TagListArgs {}
+#[command(after_help = "Examples:
+  knotter tag ls --counts --sort count
+  knotter tag ls --unused --active-only
+  knotter tag ls --prune-unused")]
+pub struct TagListArgs {
+    #[arg(long, help = "Print each count in a column before the name")]
+    pub counts: bool,
+    #[arg(
+        long,
+        conflicts_with = "min_count",
+        help = "Only list tags with no contacts"
+    )]
+    pub unused: bool,
+    #[arg(long, value_name = "N", help = "Only list tags on at least N contacts")]
+    pub min_count: Option<i64>,
+    #[arg(long, value_enum, default_value_t = TagSortArg::Name)]
+    pub sort: TagSortArg,
+    #[arg(long, help = "Do not count archived contacts")]
+    pub active_only: bool,
+    #[arg(
+        long,
+        conflicts_with_all = ["unused", "min_count", "active_only", "sort", "counts"],
+        help = "Delete every tag no contact carries (archived and trashed contacts count)"
+    )]
+    pub prune_unused: bool,
+    #[arg(long, requires = "prune_unused", help = "Skip the confirmation prompt")]
+    pub yes: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum TagSortArg {
+    /// Alphabetically
+    Name,
+    /// Most contacts first, then alphabetically
+    Count,
+}
+
+#[derive(Debug, Args)]
+pub struct TagRenameArgs {
+    pub old: String,
+    pub new: String,
+    #[arg(long)]
+    pub dry_run: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct TagMergeArgs {
+    #[arg(required = true, value_name = "TAG")]
+    pub sources: Vec<String>,
+    #[arg(long, value_name = "TAG")]
+    pub into: String,
+    #[arg(long)]
+    pub dry_run: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct TagDeleteArgs {
+    pub name: String,
+    #[arg(long)]
+    pub dry_run: bool,
+}
 
 #[derive(Debug, Serialize)]
 struct TagCountDto {
@@ -38,73 +111,122 @@ struct TagCountDto {
     count: i64,
 }
 
+#[derive(Debug, Serialize)]
+struct TagBulkDto {
+    sources: Vec<String>,
+    target: Option<String>,
+    affected_contacts: usize,
+    dry_run: bool,
+}
+
 pub fn add_tag(ctx: &Context<'_>, args: TagAddArgs) -> Result<()> {
-    let id = parse_contact_id(&args.id)?;
+    let id = resolve_contact_arg(ctx, &args.id)?;
     let tag = TagName::new(&args.tag)?;
     let normalized = tag.as_str().to_string();
     let apply_loop = args.apply_loop || ctx.config.loops.apply_on_tag_change;
     if apply_loop && !loops::loops_configured(ctx.config) {
         return Err(invalid_input("no loops configured"));
     }
-    if apply_loop {
-        let tx = ctx.store.connection().unchecked_transaction()?;
-        let tags = knotter_store::repo::TagsRepo::new(&tx);
-        let contacts = knotter_store::repo::ContactsRepo::new(&tx);
-        let interactions = knotter_store::repo::InteractionsRepo::new(&tx);
+    let ((), changes) = run_contact_change(ctx, id, args.dry_run, || {
+        let tags = ctx.store.tags();
         tags.add_tag_to_contact(&id.to_string(), tag)?;
-        loops::apply_loops_for_contact_with_repos(&contacts, &tags, &interactions, ctx.config, id)?;
-        tx.commit()?;
-    } else {
-        ctx.store.tags().add_tag_to_contact(&id.to_string(), tag)?;
-    }
+        if apply_loop {
+            loops::apply_loops_for_contact_with_repos(
+                &ctx.store.contacts(),
+                &tags,
+                &ctx.store.interactions(),
+                ctx.config,
+                ctx.clock.now_utc(),
+                id,
+            )?;
+        }
+        Ok(())
+    })?;
 
-    if ctx.json {
-        print_json(&serde_json::json!({ "id": id, "tag": normalized }))?;
+    let output = serde_json::json!({ "id": id, "tag": normalized });
+    let summary = format!("tag added to {}", id);
+    if args.dry_run {
+        print_dry_run(ctx, &output, &summary, &changes)?;
+    } else if ctx.json {
+        print_json(&output)?;
     } else {
-        println!("tag added to {}", id);
+        println!("{summary}");
     }
     Ok(())
 }
 
 pub fn remove_tag(ctx: &Context<'_>, args: TagRemoveArgs) -> Result<()> {
-    let id = parse_contact_id(&args.id)?;
+    let id = resolve_contact_arg(ctx, &args.id)?;
     let tag = TagName::new(&args.tag)?;
     let normalized = tag.as_str().to_string();
     let apply_loop = args.apply_loop || ctx.config.loops.apply_on_tag_change;
     if apply_loop && !loops::loops_configured(ctx.config) {
         return Err(invalid_input("no loops configured"));
     }
-    if apply_loop {
-        let tx = ctx.store.connection().unchecked_transaction()?;
-        let tags = knotter_store::repo::TagsRepo::new(&tx);
-        let contacts = knotter_store::repo::ContactsRepo::new(&tx);
-        let interactions = knotter_store::repo::InteractionsRepo::new(&tx);
+    let ((), changes) = run_contact_change(ctx, id, args.dry_run, || {
+        let tags = ctx.store.tags();
         tags.remove_tag_from_contact(&id.to_string(), tag)?;
-        loops::apply_loops_for_contact_with_repos(&contacts, &tags, &interactions, ctx.config, id)?;
-        tx.commit()?;
-    } else {
-        ctx.store
-            .tags()
-            .remove_tag_from_contact(&id.to_string(), tag)?;
-    }
+        if apply_loop {
+            loops::apply_loops_for_contact_with_repos(
+                &ctx.store.contacts(),
+                &tags,
+                &ctx.store.interactions(),
+                ctx.config,
+                ctx.clock.now_utc(),
+                id,
+            )?;
+        }
+        Ok(())
+    })?;
 
-    if ctx.json {
-        print_json(&serde_json::json!({ "id": id, "tag": normalized }))?;
+    let output = serde_json::json!({ "id": id, "tag": normalized });
+    let summary = format!("tag removed from {}", id);
+    if args.dry_run {
+        print_dry_run(ctx, &output, &summary, &changes)?;
+    } else if ctx.json {
+        print_json(&output)?;
     } else {
-        println!("tag removed from {}", id);
+        println!("{summary}");
     }
     Ok(())
 }
 
-pub fn list_tags(ctx: &Context<'_>, _args: TagListArgs) -> Result<()> {
-    let tags = ctx.store.tags().list_with_counts()?;
-    let items: Vec<TagCountDto> = tags
+#[derive(Debug, Serialize)]
+struct TagPruneDto {
+    pruned: Vec<String>,
+}
+
+pub fn list_tags(ctx: &Context<'_>, args: TagListArgs) -> Result<()> {
+    if args.prune_unused {
+        return prune_unused(ctx, args.yes);
+    }
+    if args.min_count.is_some_and(|min| min < 0) {
+        return Err(invalid_input("--min-count must be 0 or more"));
+    }
+
+    let tags = if args.active_only {
+        ctx.store.tags().list_with_active_counts()?
+    } else {
+        ctx.store.tags().list_with_counts()?
+    };
+    let mut items: Vec<TagCountDto> = tags
         .into_iter()
+        .filter(|(_, count)| {
+            if args.unused {
+                *count == 0
+            } else {
+                args.min_count.is_none_or(|min| *count >= min)
+            }
+        })
         .map(|(tag, count)| TagCountDto {
             name: tag.name.as_str().to_string(),
             count,
         })
         .collect();
+    if args.sort == TagSortArg::Count {
+        // Stable, so equal counts stay in name order.
+        items.sort_by_key(|item| std::cmp::Reverse(item.count));
+    }
 
     if ctx.json {
         print_json(&items)?;
@@ -116,8 +238,168 @@ pub fn list_tags(ctx: &Context<'_>, _args: TagListArgs) -> Result<()> {
         return Ok(());
     }
 
-    for item in items {
-        println!("{} ({})", item.name, item.count);
+    if args.counts {
+        let width = items
+            .iter()
+            .map(|item| item.count.to_string().len())
+            .max()
+            .unwrap_or(1);
+        for item in items {
+            println!("{:>width$}  {}", item.count, item.name);
+        }
+    } else {
+        for item in items {
+            println!("{} ({})", item.name, item.count);
+        }
+    }
+    Ok(())
+}
+
+fn prune_unused(ctx: &Context<'_>, yes: bool) -> Result<()> {
+    let tags = ctx.store.tags();
+    let unused = tags.list_unused()?;
+    let pruned = if unused.is_empty() {
+        Vec::new()
+    } else {
+        if !yes {
+            let names: Vec<&str> = unused.iter().map(|tag| tag.name.as_str()).collect();
+            eprintln!(
+                "Deleting {} unused tag(s): {}",
+                names.len(),
+                names.join(", ")
+            );
+            confirm_prune()?;
+        }
+        tags.delete_unused()?
+    };
+
+    let dto = TagPruneDto { pruned };
+    if ctx.json {
+        return print_json(&dto);
+    }
+    if dto.pruned.is_empty() {
+        println!("no unused tags");
+    } else {
+        println!(
+            "deleted {} unused tag(s): {}",
+            dto.pruned.len(),
+            dto.pruned.join(", ")
+        );
+    }
+    Ok(())
+}
+
+fn confirm_prune() -> Result<()> {
+    if !confirm("Delete?", "pruning")? {
+        return Err(invalid_input("prune cancelled; nothing deleted"));
     }
     Ok(())
 }
+
+pub fn rename_tag(ctx: &Context<'_>, args: TagRenameArgs) -> Result<()> {
+    let old = TagName::new(&args.old)?;
+    let new = TagName::new(&args.new)?;
+    if old == new {
+        return Err(invalid_input(format!(
+            "tag {} is already named {}",
+            args.old,
+            new.as_str()
+        )));
+    }
+    bulk_update(ctx, vec![old], Some(new), args.dry_run)
+}
+
+pub fn merge_tags(ctx: &Context<'_>, args: TagMergeArgs) -> Result<()> {
+    let target = TagName::new(&args.into)?;
+    let mut sources = Vec::new();
+    for raw in &args.sources {
+        let tag = TagName::new(raw)?;
+        if tag != target && !sources.contains(&tag) {
+            sources.push(tag);
+        }
+    }
+    if sources.is_empty() {
+        return Err(invalid_input(
+            "nothing to merge: every source is the target",
+        ));
+    }
+    bulk_update(ctx, sources, Some(target), args.dry_run)
+}
+
+pub fn delete_tag(ctx: &Context<'_>, args: TagDeleteArgs) -> Result<()> {
+    let name = TagName::new(&args.name)?;
+    bulk_update(ctx, vec![name], None, args.dry_run)
+}
+
+fn bulk_update(
+    ctx: &Context<'_>,
+    sources: Vec<TagName>,
+    target: Option<TagName>,
+    dry_run: bool,
+) -> Result<()> {
+    let tags = ctx.store.tags();
+    for source in &sources {
+        if tags.get_by_name(source)?.is_none() {
+            return Err(not_found(format!("tag {} not found", source.as_str())));
+        }
+    }
+
+    let affected = if dry_run {
+        tags.count_contacts_with_any(&sources)?
+    } else {
+        match &target {
+            Some(target) => tags.merge_into(&sources, target.clone())?,
+            None => tags.delete(&sources[0])?,
+        }
+    };
+    warn_loop_rules(ctx, &sources, target.as_ref());
+
+    let dto = TagBulkDto {
+        sources: sources.iter().map(|tag| tag.as_str().to_string()).collect(),
+        target: target.map(|tag| tag.as_str().to_string()),
+        affected_contacts: affected,
+        dry_run,
+    };
+    if ctx.json {
+        return print_json(&dto);
+    }
+
+    let prefix = if dry_run { "dry run: would " } else { "" };
+    let verb = match (&dto.target, dry_run) {
+        (Some(_), true) => "move",
+        (Some(_), false) => "moved",
+        (None, true) => "remove",
+        (None, false) => "removed",
+    };
+    let sources = dto.sources.join(", ");
+    match &dto.target {
+        Some(target) => println!(
+            "{prefix}{verb} {sources} -> {target} on {} contact(s)",
+            dto.affected_contacts
+        ),
+        None => println!(
+            "{prefix}{verb} {sources} from {} contact(s)",
+            dto.affected_contacts
+        ),
+    }
+    Ok(())
+}
+
+/// Loop rules match tags by name, so they silently stop applying once their tag
+/// is renamed or deleted; point at the rules that need a config edit.
+fn warn_loop_rules(ctx: &Context<'_>, sources: &[TagName], target: Option<&TagName>) {
+    for rule in &ctx.config.loops.policy.rules {
+        if !sources.contains(&rule.tag) {
+            continue;
+        }
+        let hint = match target {
+            Some(target) => format!("update it to \"{}\"", target.as_str()),
+            None => "remove it or pick another tag".to_string(),
+        };
+        eprintln!(
+            "warning: loops rule for tag \"{}\" (cadence {}d) references a tag that no longer exists; {hint}",
+            rule.tag.as_str(),
+            rule.cadence_days
+        );
+    }
+}
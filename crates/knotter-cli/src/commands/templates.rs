@@ -0,0 +1,54 @@
+use crate::commands::print_json;
+use anyhow::{Context as _, Result};
+use clap::Subcommand;
+use serde::Serialize;
+use std::path::PathBuf;
+
+/// Note templates from [interactions.templates]
+#[derive(Debug, Subcommand)]
+pub enum TemplatesCommand {
+    /// List the defined templates and the variables each one needs
+    Ls,
+}
+
+#[derive(Debug, Serialize)]
+struct TemplateDto {
+    name: String,
+    template: String,
+    variables: Vec<String>,
+}
+
+pub fn list_templates(config_path: Option<PathBuf>, json: bool) -> Result<()> {
+    let config = knotter_config::load(config_path).with_context(|| "load config")?;
+    let templates: Vec<TemplateDto> = config
+        .interactions
+        .templates
+        .iter()
+        .map(|(name, template)| TemplateDto {
+            name: name.clone(),
+            template: template.source().to_string(),
+            variables: template
+                .variables()
+                .into_iter()
+                .map(str::to_string)
+                .collect(),
+        })
+        .collect();
+
+    if json {
+        return print_json(&templates);
+    }
+    if templates.is_empty() {
+        println!("no templates defined (add them under [interactions.templates])");
+        return Ok(());
+    }
+    for template in &templates {
+        let variables = if template.variables.is_empty() {
+            "-".to_string()
+        } else {
+            template.variables.join(", ")
+        };
+        println!("{}  [{}]  {}", template.name, variables, template.template);
+    }
+    Ok(())
+}
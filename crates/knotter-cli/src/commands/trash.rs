@@ -0,0 +1,197 @@
+use crate::commands::{print_json, Context};
+use crate::error::{invalid_input, not_found};
+use anyhow::Result;
+use clap::{Args, Subcommand};
+use knotter_core::domain::ContactId;
+use serde::Serialize;
+use std::str::FromStr;
+
+const SECONDS_PER_DAY: i64 = 86_400;
+
+#[derive(Debug, Subcommand)]
+pub enum TrashCommand {
+    /// List trashed contacts, most recently deleted first
+    Ls,
+    /// Move a trashed contact back into the address book
+    Restore(TrashRestoreArgs),
+    /// Permanently delete trashed contacts
+    Empty(TrashEmptyArgs),
+}
+
+#[derive(Debug, Args)]
+pub struct TrashRestoreArgs {
+    /// Trashed contact's id, or its name
+    pub id: String,
+}
+
+#[derive(Debug, Args)]
+pub struct TrashEmptyArgs {
+    #[arg(
+        long,
+        value_name = "AGE",
+        value_parser = parse_age_days,
+        help = "Only purge contacts trashed at least this long ago (e.g. 30d)"
+    )]
+    pub older_than: Option<i64>,
+}
+
+#[derive(Debug, Serialize)]
+struct TrashedContactDto {
+    id: ContactId,
+    display_name: String,
+    deleted_at: i64,
+    archived: bool,
+}
+
+#[derive(Debug, Serialize)]
+struct RestoredContactDto {
+    id: ContactId,
+    display_name: String,
+    dropped_emails: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct EmptyTrashDto {
+    purged: usize,
+}
+
+pub fn list_trash(ctx: &Context<'_>) -> Result<()> {
+    let trashed = ctx.store.contacts().list_trashed()?;
+    if ctx.json {
+        let items: Vec<TrashedContactDto> = trashed
+            .into_iter()
+            .map(|item| TrashedContactDto {
+                id: item.contact.id,
+                display_name: item.contact.display_name,
+                deleted_at: item.deleted_at,
+                archived: item.contact.archived_at.is_some(),
+            })
+            .collect();
+        return print_json(&items);
+    }
+
+    if trashed.is_empty() {
+        println!("trash is empty");
+        return Ok(());
+    }
+    for item in trashed {
+        println!(
+            "{}  {}  deleted {}",
+            item.contact.id,
+            item.contact.display_name,
+            ctx.clock.format_timestamp_datetime(item.deleted_at)
+        );
+    }
+    Ok(())
+}
+
+pub fn restore(ctx: &Context<'_>, args: TrashRestoreArgs) -> Result<()> {
+    let id = resolve_trashed_arg(ctx, &args.id)?;
+    let restored = ctx.store.contacts().restore(ctx.clock.now_utc(), id)?;
+    if ctx.json {
+        return print_json(&RestoredContactDto {
+            id: restored.contact.id,
+            display_name: restored.contact.display_name,
+            dropped_emails: restored.dropped_emails,
+        });
+    }
+
+    println!(
+        "restored {} {}",
+        restored.contact.id, restored.contact.display_name
+    );
+    for email in &restored.dropped_emails {
+        eprintln!("warning: {email} now belongs to another contact and was not restored");
+    }
+    Ok(())
+}
+
+/// Trashed contacts are not found by [`crate::commands::resolve_contact_arg`],
+/// so names are matched against the trash itself.
+fn resolve_trashed_arg(ctx: &Context<'_>, raw: &str) -> Result<ContactId> {
+    let trimmed = raw.trim();
+    if trimmed.is_empty() {
+        return Err(invalid_input("contact id cannot be empty"));
+    }
+    if let Ok(id) = ContactId::from_str(trimmed) {
+        return Ok(id);
+    }
+    let matches: Vec<ContactId> = ctx
+        .store
+        .contacts()
+        .list_trashed()?
+        .into_iter()
+        .filter(|item| {
+            item.contact
+                .display_name
+                .trim()
+                .eq_ignore_ascii_case(trimmed)
+        })
+        .map(|item| item.contact.id)
+        .collect();
+    match matches.as_slice() {
+        [] => Err(not_found(format!("no trashed contact matches {trimmed:?}"))),
+        [id] => Ok(*id),
+        _ => Err(invalid_input(format!(
+            "{trimmed:?} matches {} trashed contacts; pass an id instead",
+            matches.len()
+        ))),
+    }
+}
+
+pub fn empty(ctx: &Context<'_>, args: TrashEmptyArgs) -> Result<()> {
+    let now = ctx.clock.now_utc();
+    let cutoff = args
+        .older_than
+        .map(|days| age_cutoff(now, days))
+        .transpose()?;
+    let purged = ctx.store.contacts().empty_trash(now, cutoff)?;
+    if ctx.json {
+        return print_json(&EmptyTrashDto { purged });
+    }
+    println!("purged {purged} contact(s) from the trash");
+    Ok(())
+}
+
+/// Parses an age such as `30d` (or a bare `30`) into whole days.
+pub(crate) fn parse_age_days(raw: &str) -> Result<i64> {
+    let trimmed = raw.trim();
+    let digits = trimmed.strip_suffix('d').unwrap_or(trimmed);
+    match digits.parse::<i64>() {
+        Ok(days) if days >= 0 => Ok(days),
+        _ => Err(invalid_input(format!(
+            "invalid age {raw:?}; expected days such as 30d"
+        ))),
+    }
+}
+
+/// The timestamp `days` before `now`; ages too large to represent are
+/// rejected rather than wrapped into a cutoff that matches the wrong rows.
+pub(crate) fn age_cutoff(now: i64, days: i64) -> Result<i64> {
+    days.checked_mul(SECONDS_PER_DAY)
+        .and_then(|seconds| now.checked_sub(seconds))
+        .ok_or_else(|| invalid_input(format!("age of {days} days is too large")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{age_cutoff, parse_age_days};
+
+    #[test]
+    fn parse_age_days_accepts_day_suffix_and_bare_numbers() {
+        assert_eq!(parse_age_days("30d").unwrap(), 30);
+        assert_eq!(parse_age_days("7").unwrap(), 7);
+        assert_eq!(parse_age_days("0d").unwrap(), 0);
+        assert!(parse_age_days("-1d").is_err());
+        assert!(parse_age_days("2w").is_err());
+        assert!(parse_age_days("").is_err());
+    }
+
+    #[test]
+    fn age_cutoff_rejects_ages_that_overflow() {
+        assert_eq!(age_cutoff(1_700_000_000, 1).unwrap(), 1_699_913_600);
+        assert_eq!(age_cutoff(1_700_000_000, 0).unwrap(), 1_700_000_000);
+        let days = parse_age_days(&format!("{}d", i64::MAX / 2)).unwrap();
+        assert!(age_cutoff(1_700_000_000, days).is_err());
+    }
+}
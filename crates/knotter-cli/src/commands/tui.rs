@@ -2,6 +2,7 @@ use crate::error::not_found;
 use anyhow::{Context as _, Result};
 use clap::Args;
 use knotter_core::rules::validate_soon_days;
+use knotter_core::time::TODAY_ENV;
 use knotter_store::paths;
 use std::env;
 use std::path::{Path, PathBuf};
@@ -16,15 +17,26 @@ pub struct TuiArgs {
 
 pub fn launch(
     db_path: Option<PathBuf>,
+    data_dir: Option<PathBuf>,
     config_path: Option<PathBuf>,
+    readonly: bool,
+    today_is: Option<String>,
     args: TuiArgs,
     verbose: bool,
 ) -> Result<()> {
-    let db_path = paths::resolve_db_path(db_path).with_context(|| "resolve database path")?;
+    let db_path = if readonly {
+        paths::resolve_db_path_readonly(db_path)
+    } else {
+        paths::resolve_db_path(db_path)
+    }
+    .with_context(|| "resolve database path")?;
     if verbose {
         debug!(path = %db_path.display(), "database path resolved");
     }
-    let mut command = build_command(&db_path, config_path, args.soon_days)?;
+    let mut command = build_command(&db_path, data_dir, config_path, readonly, args.soon_days)?;
+    if let Some(date) = today_is {
+        command.env(TODAY_ENV, date);
+    }
 
     #[cfg(unix)]
     {
@@ -42,15 +54,23 @@ pub fn launch(
 
 fn build_command(
     db_path: &Path,
+    data_dir: Option<PathBuf>,
     config_path: Option<PathBuf>,
+    readonly: bool,
     soon_days: Option<i64>,
 ) -> Result<Command> {
     let binary = find_tui_binary();
     let mut command = Command::new(binary);
     command.arg("--db-path").arg(db_path);
+    if let Some(dir) = data_dir {
+        command.arg("--data-dir").arg(dir);
+    }
     if let Some(path) = config_path {
         command.arg("--config").arg(path);
     }
+    if readonly {
+        command.arg("--readonly");
+    }
     if let Some(value) = soon_days {
         let soon_days = validate_soon_days(value)?;
         command.arg("--soon-days").arg(soon_days.to_string());
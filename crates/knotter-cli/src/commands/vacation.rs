@@ -0,0 +1,80 @@
+use crate::commands::{print_json, Context};
+use crate::error::invalid_input;
+use anyhow::Result;
+use clap::Args;
+use serde::Serialize;
+
+#[derive(Debug, Args)]
+#[command(after_help = "Examples:
+  knotter vacation --until 2025-08-20   notifications resume on Aug 20
+  knotter vacation --for 2w
+  knotter vacation --clear
+  knotter vacation                      show whether vacation mode is on")]
+pub struct VacationArgs {
+    #[arg(
+        long,
+        value_name = "DATE",
+        conflicts_with_all = ["for_", "clear"],
+        help = "Resume notifications on this local date (YYYY-MM-DD, +2w, next-mon)"
+    )]
+    pub until: Option<String>,
+    #[arg(
+        long = "for",
+        value_name = "DURATION",
+        conflicts_with = "clear",
+        help = "Pause notifications for a span such as 10d, 2w, or 1m"
+    )]
+    pub for_: Option<String>,
+    #[arg(long, help = "End vacation mode now")]
+    pub clear: bool,
+}
+
+impl VacationArgs {
+    pub fn changes_state(&self) -> bool {
+        self.until.is_some() || self.for_.is_some() || self.clear
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct VacationDto {
+    active: bool,
+    until: Option<i64>,
+}
+
+pub fn vacation(ctx: &Context<'_>, args: VacationArgs) -> Result<()> {
+    let now = ctx.clock.now_utc();
+    let settings = ctx.store.settings();
+    let date = match (args.until.as_deref(), args.for_.as_deref()) {
+        (Some(until), _) => Some(until.to_string()),
+        (None, Some(span)) => Some(format!("+{}", span.trim().trim_start_matches('+'))),
+        (None, None) => None,
+    };
+
+    if let Some(date) = date {
+        let (until, _, resolved) = ctx.clock.parse_local_date_time_relative(&date, None, now)?;
+        if until <= now {
+            return Err(invalid_input(format!(
+                "vacation must end after now (parsed as {resolved})"
+            )));
+        }
+        settings.set_vacation_until(now, until)?;
+    } else if args.clear {
+        settings.clear_vacation()?;
+    }
+
+    let until = settings.active_vacation_until(now)?;
+    if ctx.json {
+        return print_json(&VacationDto {
+            active: until.is_some(),
+            until,
+        });
+    }
+    match until {
+        Some(until) => println!(
+            "Vacation mode until {}: remind --notify will not send notifications.",
+            ctx.clock.format_timestamp_datetime(until)
+        ),
+        None => println!("Vacation mode is off."),
+    }
+    Ok(())
+}
@@ -1,4 +1,5 @@
 use anyhow::Error;
+use knotter_app::AppError;
 use knotter_config::ConfigError;
 use knotter_core::filter::FilterParseError;
 use knotter_core::time::TimeParseError;
@@ -11,6 +12,13 @@ use thiserror::Error as ThisError;
 pub const EXIT_FAILURE: u8 = 1;
 pub const EXIT_NOT_FOUND: u8 = 2;
 pub const EXIT_INVALID_INPUT: u8 = 3;
+/// `knotter sync` ran but some of its steps failed. This reuses
+/// [`EXIT_NOT_FOUND`]: sync turns every step error, not-found ones included,
+/// into a warning, so for sync a `2` can only mean partial failure.
+pub const EXIT_PARTIAL_FAILURE: u8 = EXIT_NOT_FOUND;
+/// `knotter remind --notify` could not deliver through the configured backend;
+/// the reminders went to the fallback instead.
+pub const EXIT_NOTIFY_FAILED: u8 = 4;
 
 #[derive(Debug, ThisError)]
 pub enum CliError {
@@ -18,6 +26,11 @@ pub enum CliError {
     InvalidInput(String),
     #[error("not found: {0}")]
     NotFound(String),
+    #[error("{0}")]
+    PartialFailure(String),
+    #[cfg_attr(not(feature = "email-notify"), allow(dead_code))]
+    #[error("{0}")]
+    NotifyFailed(String),
 }
 
 pub fn invalid_input(message: impl Into<String>) -> Error {
@@ -36,34 +49,57 @@ pub fn report_error(err: &Error, verbose: bool) {
     }
 }
 
+pub fn partial_failure(message: impl Into<String>) -> Error {
+    CliError::PartialFailure(message.into()).into()
+}
+
+#[cfg_attr(not(feature = "email-notify"), allow(dead_code))]
+pub fn notify_failed(message: impl Into<String>) -> Error {
+    CliError::NotifyFailed(message.into()).into()
+}
+
 pub fn exit_code_for(err: &Error) -> ExitCode {
+    ExitCode::from(exit_status_for(err))
+}
+
+pub fn exit_status_for(err: &Error) -> u8 {
     for cause in err.chain() {
         if let Some(cli_err) = cause.downcast_ref::<CliError>() {
-            return ExitCode::from(match cli_err {
+            return match cli_err {
                 CliError::InvalidInput(_) => EXIT_INVALID_INPUT,
                 CliError::NotFound(_) => EXIT_NOT_FOUND,
-            });
+                CliError::PartialFailure(_) => EXIT_PARTIAL_FAILURE,
+                CliError::NotifyFailed(_) => EXIT_NOTIFY_FAILED,
+            };
+        }
+        if let Some(app_err) = cause.downcast_ref::<AppError>() {
+            return match app_err {
+                AppError::Store(store_err) => store_exit_code(store_err),
+                AppError::Core(_) | AppError::Filter(_) | AppError::InvalidInput(_) => {
+                    EXIT_INVALID_INPUT
+                }
+            };
         }
         if let Some(store_err) = cause.downcast_ref::<StoreError>() {
-            return ExitCode::from(store_exit_code(store_err));
+            return store_exit_code(store_err);
         }
         if let Some(config_err) = cause.downcast_ref::<ConfigError>() {
-            return ExitCode::from(config_exit_code(config_err));
+            return config_exit_code(config_err);
         }
         if let Some(sync_err) = cause.downcast_ref::<SyncError>() {
-            return ExitCode::from(sync_exit_code(sync_err));
+            return sync_exit_code(sync_err);
         }
         if let Some(_core_err) = cause.downcast_ref::<CoreError>() {
-            return ExitCode::from(EXIT_INVALID_INPUT);
+            return EXIT_INVALID_INPUT;
         }
         if let Some(_parse_err) = cause.downcast_ref::<FilterParseError>() {
-            return ExitCode::from(EXIT_INVALID_INPUT);
+            return EXIT_INVALID_INPUT;
         }
         if let Some(_parse_err) = cause.downcast_ref::<TimeParseError>() {
-            return ExitCode::from(EXIT_INVALID_INPUT);
+            return EXIT_INVALID_INPUT;
         }
     }
-    ExitCode::from(EXIT_FAILURE)
+    EXIT_FAILURE
 }
 
 fn store_exit_code(err: &StoreError) -> u8 {
@@ -73,6 +109,7 @@ fn store_exit_code(err: &StoreError) -> u8 {
         | StoreErrorKind::InvalidFilter
         | StoreErrorKind::InvalidBackupPath
         | StoreErrorKind::InvalidInteractionKind
+        | StoreErrorKind::InvalidInteraction
         | StoreErrorKind::InvalidDataPath
         | StoreErrorKind::DuplicateEmail
         | StoreErrorKind::DuplicateTelegramUser
@@ -82,6 +119,7 @@ fn store_exit_code(err: &StoreError) -> u8 {
         StoreErrorKind::MissingHomeDir
         | StoreErrorKind::Migration
         | StoreErrorKind::Sql
+        | StoreErrorKind::InsufficientSpace
         | StoreErrorKind::Io => EXIT_FAILURE,
     }
 }
@@ -94,23 +132,39 @@ fn config_exit_code(err: &ConfigError) -> u8 {
         | ConfigError::InsecurePermissions(_)
         | ConfigError::InvalidSoonDays(_)
         | ConfigError::InvalidCadenceDays(_)
+        | ConfigError::InvalidTimezone(_)
         | ConfigError::InvalidLoopDefaultCadence(_)
         | ConfigError::InvalidLoopCadenceDays(_)
+        | ConfigError::InvalidLoopJitterDays(_)
         | ConfigError::InvalidLoopTag(_)
         | ConfigError::DuplicateLoopTag(_)
+        | ConfigError::InvalidLoopMinPriority(_)
         | ConfigError::InvalidContactSourceName(_)
         | ConfigError::DuplicateContactSourceName(_)
         | ConfigError::InvalidContactSourceField { .. }
         | ConfigError::InvalidEmailAccountName(_)
+        | ConfigError::InvalidIgnoreIdentity(_)
         | ConfigError::DuplicateEmailAccountName(_)
         | ConfigError::InvalidEmailAccountField { .. }
         | ConfigError::InvalidTelegramAccountName(_)
         | ConfigError::DuplicateTelegramAccountName(_)
         | ConfigError::InvalidTelegramAccountField { .. }
+        | ConfigError::InvalidContactDefaultsField { .. }
         | ConfigError::InvalidNotificationsEmailField { .. }
+        | ConfigError::InvalidNotificationsWebhookField { .. }
         | ConfigError::InvalidNotificationsRandomContacts { .. }
+        | ConfigError::InvalidNotificationsMaxPerRun { .. }
+        | ConfigError::InvalidNotificationsMaxPerBucket { .. }
+        | ConfigError::InvalidNotificationsQuietHours(_)
+        | ConfigError::InvalidInteractionsDedupeWindowMinutes { .. }
+        | ConfigError::InvalidInteractionsDefaultTouchKind(_)
+        | ConfigError::InvalidInteractionsTemplate { .. }
+        | ConfigError::InvalidSyncMaxFutureSkewDays { .. }
+        | ConfigError::InvalidExportsIcsAlarm(_)
+        | ConfigError::ConfigFileExists(_)
         | ConfigError::Read { .. }
         | ConfigError::Parse { .. } => EXIT_INVALID_INPUT,
+        ConfigError::Write { .. } => EXIT_FAILURE,
     }
 }
 
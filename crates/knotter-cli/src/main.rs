@@ -1,6 +1,9 @@
 mod commands;
 mod error;
 mod notify;
+mod profile;
+mod progress;
+mod secret;
 mod util;
 
 use anyhow::{Context as _, Result};
@@ -10,11 +13,15 @@ use std::process::ExitCode;
 use tracing::debug;
 
 use crate::commands::{
-    backup, completions, contacts, dates, interactions, loops, merge, remind, schedule, sync, tags,
-    tui, Context,
+    alias, audit, backup, cadence, completions, contacts, dates, db, dedupe, doctor, email, enrich,
+    followups, import_interactions, init, interactions, loops, mangen, merge, mute, normalize,
+    rebalance, remind, roulette, schedule, serve, sync, sync_state, tags, templates, trash, tui,
+    vacation, Context,
 };
-use crate::error::{exit_code_for, report_error};
+use crate::error::{exit_code_for, invalid_input, report_error};
+use crate::profile::{phase, Profiler};
 use knotter_config as config;
+use knotter_core::time::{today_override, Clock};
 use knotter_store::{paths, Store};
 
 #[derive(Debug, Parser)]
@@ -22,12 +29,27 @@ use knotter_store::{paths, Store};
 struct Cli {
     #[arg(long, global = true)]
     db_path: Option<PathBuf>,
+    /// Base directory for the database and session files (overrides KNOTTER_DATA_DIR)
+    #[arg(long, global = true, value_name = "DIR")]
+    data_dir: Option<PathBuf>,
     #[arg(long, global = true)]
     config: Option<PathBuf>,
     #[arg(long, global = true)]
     json: bool,
+    /// Open the database read-only: no migrations and no mutating commands
+    #[arg(long, global = true)]
+    readonly: bool,
     #[arg(long, short, global = true)]
     verbose: bool,
+    /// Print wall time per phase to stderr when the command finishes
+    #[arg(long, global = true)]
+    profile: bool,
+    /// Also write the phase timings as JSON to PATH (implies --profile)
+    #[arg(long, global = true, value_name = "PATH")]
+    profile_json: Option<PathBuf>,
+    /// Treat DATE (YYYY-MM-DD) as today, for reproducible runs (overrides KNOTTER_TODAY)
+    #[arg(long, global = true, value_name = "DATE", hide = true)]
+    today_is: Option<String>,
     #[command(subcommand)]
     command: Command,
 }
@@ -35,8 +57,27 @@ struct Cli {
 #[derive(Debug, Subcommand)]
 enum Command {
     Backup(backup::BackupArgs),
+    /// Check the database for consistency problems
+    Doctor(doctor::DoctorArgs),
+    /// Report database size or compact it
+    #[command(subcommand)]
+    Db(db::DbCommand),
+    /// Find and remove repeated records
+    #[command(subcommand)]
+    Dedupe(dedupe::DedupeCommand),
+    /// Validate or initialize the config file
+    #[command(subcommand)]
+    Config(commands::config::ConfigCommand),
+    /// Set up a config file and database, asking for the essentials
+    Init(init::InitArgs),
     /// Generate shell completions
     Completions(completions::CompletionsArgs),
+    /// Print dynamic completion candidates (used by completion scripts)
+    #[command(name = "__complete", hide = true)]
+    Complete(completions::CompleteArgs),
+    /// Write roff man pages for every command (used for packaging)
+    #[command(hide = true)]
+    Mangen(mangen::MangenArgs),
     #[command(name = "add-contact")]
     AddContact(contacts::AddContactArgs),
     #[command(name = "edit-contact")]
@@ -44,26 +85,75 @@ enum Command {
     Show(contacts::ShowArgs),
     List(contacts::ListArgs),
     Delete(contacts::DeleteArgs),
+    /// List, restore, or purge deleted contacts
+    #[command(subcommand)]
+    Trash(trash::TrashCommand),
     #[command(name = "archive-contact")]
     ArchiveContact(contacts::ArchiveArgs),
     #[command(name = "unarchive-contact")]
     UnarchiveContact(contacts::UnarchiveArgs),
+    /// Pause reminders and loop scheduling for a contact without archiving it
+    Mute(mute::MuteArgs),
+    /// End a contact's mute early
+    Unmute(mute::UnmuteArgs),
+    /// Show the history of changes to a contact, newest first
+    Log(audit::LogArgs),
     #[command(subcommand)]
     Tag(tags::TagCommand),
     #[command(subcommand)]
     Date(dates::DateCommand),
+    /// Manage a contact's email addresses
     #[command(subcommand)]
-    Loops(loops::LoopCommand),
+    Email(email::EmailCommand),
+    /// Manage a contact's nicknames and alternate names
     #[command(subcommand)]
+    Alias(alias::AliasCommand),
+    #[command(subcommand)]
+    Loops(loops::LoopCommand),
+    #[command(
+        subcommand,
+        after_help = "Examples:
+  knotter merge list --status open
+  knotter merge show <candidate-id>
+  knotter merge apply <candidate-id> --prefer a --touchpoint earliest
+  knotter merge apply-all --reason email-duplicate --dry-run
+  knotter merge contacts <primary-id> <secondary-id> --cadence shortest
+  knotter merge scan-same-name"
+    )]
     Merge(merge::MergeCommand),
     #[command(name = "add-note")]
     AddNote(interactions::AddNoteArgs),
     Touch(interactions::TouchArgs),
+    #[command(subcommand)]
+    Templates(templates::TemplatesCommand),
+    #[command(subcommand)]
+    Note(interactions::NoteCommand),
     Schedule(schedule::ScheduleArgs),
     #[command(name = "clear-schedule")]
     ClearSchedule(schedule::ClearScheduleArgs),
+    /// Move touchpoints off crowded days to the nearest days with room
+    Rebalance(rebalance::RebalanceArgs),
+    /// Suggest a cadence from interaction history
+    #[command(name = "suggest-cadence")]
+    SuggestCadence(cadence::SuggestCadenceArgs),
+    /// Fill empty contact fields from data already in knotter
+    #[command(subcommand)]
+    Enrich(enrich::EnrichCommand),
+    /// Rewrite stored values into a canonical form
+    #[command(subcommand)]
+    Normalize(normalize::NormalizeCommand),
     Remind(remind::RemindArgs),
+    /// Pause reminder notifications until a date, or end the pause
+    Vacation(vacation::VacationArgs),
+    /// Pick random contacts to reach out to, favoring those not heard from in a while
+    #[command(alias = "pick")]
+    Roulette(roulette::RouletteArgs),
+    /// List pending follow-ups or mark one done
+    Followups(followups::FollowupsArgs),
+    /// Import every configured source, email and telegram account, then apply loops and remind
     Sync(sync::SyncArgs),
+    /// Serve a small JSON API for contacts and reminders over HTTP
+    Serve(serve::ServeArgs),
     Tui(tui::TuiArgs),
     #[command(subcommand)]
     Import(sync::ImportCommand),
@@ -74,8 +164,19 @@ enum Command {
 fn main() -> ExitCode {
     let cli = Cli::parse();
     let verbose = cli.verbose;
-    init_logging(verbose);
-    match run(cli) {
+    let profile_json = cli.profile_json.clone();
+    let profiler = (cli.profile || profile_json.is_some()).then(Profiler::default);
+    init_logging(verbose, profiler.as_ref());
+    let result = run(cli);
+    if let Some(profiler) = &profiler {
+        eprint!("{}", profiler.summary());
+        if let Some(path) = &profile_json {
+            if let Err(err) = profiler.write_json(path) {
+                eprintln!("warning: {err:#}");
+            }
+        }
+    }
+    match result {
         Ok(()) => ExitCode::SUCCESS,
         Err(err) => {
             report_error(&err, verbose);
@@ -87,17 +188,67 @@ fn main() -> ExitCode {
 fn run(cli: Cli) -> Result<()> {
     let Cli {
         db_path,
+        data_dir,
         config: config_path,
         json,
+        readonly,
         verbose,
+        today_is,
         command,
+        ..
     } = cli;
 
+    if let Some(dir) = data_dir.clone() {
+        paths::set_data_dir_override(dir);
+    }
+
     match command {
-        Command::Tui(args) => tui::launch(db_path, config_path, args, verbose),
+        Command::Tui(args) => tui::launch(
+            db_path,
+            data_dir,
+            config_path,
+            readonly,
+            today_is,
+            args,
+            verbose,
+        ),
         Command::Completions(args) => completions::emit(args),
+        Command::Complete(args) => completions::complete(db_path, config_path, args),
+        Command::Mangen(args) => mangen::generate(args, json),
+        Command::Config(cmd) => match cmd {
+            commands::config::ConfigCommand::Check(args) => {
+                commands::config::check(config_path, json, args)
+            }
+            commands::config::ConfigCommand::Init(args) => {
+                commands::config::init(config_path, json, args)
+            }
+        },
+        Command::Templates(cmd) => match cmd {
+            templates::TemplatesCommand::Ls => templates::list_templates(config_path, json),
+        },
+        Command::Init(args) => {
+            if readonly {
+                return Err(invalid_input(
+                    "read-only mode: `init` would modify the database",
+                ));
+            }
+            init::init(db_path, config_path, json, args)
+        }
         command => {
+            if readonly {
+                if let Some(name) = mutating_command(&command) {
+                    return Err(invalid_input(format!(
+                        "read-only mode: `{name}` would modify the database"
+                    )));
+                }
+            }
+            let config_phase = phase("config load");
             let app_config = config::load(config_path.clone()).with_context(|| "load config")?;
+            drop(config_phase);
+            let today = today_override(today_is.as_deref()).map_err(|_| {
+                invalid_input("invalid --today-is/KNOTTER_TODAY: expected YYYY-MM-DD")
+            })?;
+            let clock = Clock::new(app_config.timezone, today);
             if verbose {
                 match config::resolve_config_path(config_path.clone()) {
                     Ok(path) => {
@@ -112,42 +263,98 @@ fn run(cli: Cli) -> Result<()> {
                     }
                 }
             }
-            let db_path =
-                paths::resolve_db_path(db_path).with_context(|| "resolve database path")?;
+            let db_path = if readonly {
+                paths::resolve_db_path_readonly(db_path)
+            } else {
+                paths::resolve_db_path(db_path)
+            }
+            .with_context(|| "resolve database path")?;
 
             if verbose {
                 debug!(path = %db_path.display(), "database path resolved");
             }
 
-            let store = Store::open(&db_path)
-                .with_context(|| format!("open database {}", db_path.display()))?;
-            store.migrate().with_context(|| "run migrations")?;
+            let store_phase = phase("store open");
+            let store = if readonly {
+                let store = Store::open_readonly(&db_path)
+                    .with_context(|| format!("open database {} read-only", db_path.display()))?;
+                store
+                    .check_schema()
+                    .with_context(|| "check schema version")?;
+                if dry_run_rolls_back(&command) {
+                    store
+                        .copy_to_memory()
+                        .with_context(|| "copy database for dry run")?
+                } else {
+                    store
+                }
+            } else {
+                let store = Store::open(&db_path)
+                    .with_context(|| format!("open database {}", db_path.display()))?;
+                store.migrate().with_context(|| "run migrations")?;
+                store
+            };
+            drop(store_phase);
 
             let ctx = Context {
                 store: &store,
                 json,
                 config: &app_config,
+                clock,
             };
 
+            let _command_phase = phase("command");
             match command {
                 Command::AddContact(args) => contacts::add_contact(&ctx, args),
                 Command::Backup(args) => backup::backup(&ctx, args),
+                Command::Doctor(args) => doctor::doctor(&ctx, args),
+                Command::Db(cmd) => match cmd {
+                    db::DbCommand::Stats => db::db_stats(&ctx),
+                    db::DbCommand::Vacuum => db::db_vacuum(&ctx),
+                },
+                Command::Dedupe(cmd) => match cmd {
+                    dedupe::DedupeCommand::Interactions(args) => {
+                        dedupe::dedupe_interactions(&ctx, args)
+                    }
+                },
                 Command::EditContact(args) => contacts::edit_contact(&ctx, args),
                 Command::Show(args) => contacts::show_contact(&ctx, args),
                 Command::List(args) => contacts::list_contacts(&ctx, args),
                 Command::Delete(args) => contacts::delete_contact(&ctx, args),
+                Command::Trash(cmd) => match cmd {
+                    trash::TrashCommand::Ls => trash::list_trash(&ctx),
+                    trash::TrashCommand::Restore(args) => trash::restore(&ctx, args),
+                    trash::TrashCommand::Empty(args) => trash::empty(&ctx, args),
+                },
                 Command::ArchiveContact(args) => contacts::archive_contact(&ctx, args),
                 Command::UnarchiveContact(args) => contacts::unarchive_contact(&ctx, args),
+                Command::Mute(args) => mute::mute_contact(&ctx, args),
+                Command::Unmute(args) => mute::unmute_contact(&ctx, args),
+                Command::Log(args) => audit::contact_log(&ctx, args),
                 Command::Tag(cmd) => match cmd {
                     tags::TagCommand::Add(args) => tags::add_tag(&ctx, args),
                     tags::TagCommand::Rm(args) => tags::remove_tag(&ctx, args),
                     tags::TagCommand::Ls(args) => tags::list_tags(&ctx, args),
+                    tags::TagCommand::Rename(args) => tags::rename_tag(&ctx, args),
+                    tags::TagCommand::Merge(args) => tags::merge_tags(&ctx, args),
+                    tags::TagCommand::Delete(args) => tags::delete_tag(&ctx, args),
                 },
                 Command::Date(cmd) => match cmd {
                     dates::DateCommand::Add(args) => dates::add_date(&ctx, args),
                     dates::DateCommand::Ls(args) => dates::list_dates(&ctx, args),
                     dates::DateCommand::Rm(args) => dates::remove_date(&ctx, args),
                 },
+                Command::Email(cmd) => match cmd {
+                    email::EmailCommand::Add(args) => email::add_email(&ctx, args),
+                    email::EmailCommand::Rm(args) => email::remove_email(&ctx, args),
+                    email::EmailCommand::Ls(args) => email::list_emails(&ctx, args),
+                    email::EmailCommand::SetPrimary(args) => email::set_primary_email(&ctx, args),
+                },
+                Command::Alias(cmd) => match cmd {
+                    alias::AliasCommand::Add(args) => alias::add_alias(&ctx, args),
+                    alias::AliasCommand::Rm(args) => alias::remove_alias(&ctx, args),
+                    alias::AliasCommand::Ls(args) => alias::list_aliases(&ctx, args),
+                },
                 Command::Loops(cmd) => match cmd {
                     loops::LoopCommand::Apply(args) => loops::apply_loops(&ctx, args),
                 },
@@ -159,24 +366,58 @@ fn run(cli: Cli) -> Result<()> {
                     merge::MergeCommand::Dismiss(args) => merge::dismiss_merge(&ctx, args),
                     merge::MergeCommand::Contacts(args) => merge::merge_contacts(&ctx, args),
                     merge::MergeCommand::ScanSameName(args) => merge::scan_same_name(&ctx, args),
+                    merge::MergeCommand::Prune(args) => merge::prune_merges(&ctx, args),
+                    merge::MergeCommand::Import(args) => merge::import_merges(&ctx, args),
                 },
                 Command::AddNote(args) => interactions::add_note(&ctx, args),
                 Command::Touch(args) => interactions::touch_contact(&ctx, args),
+                Command::Note(cmd) => match cmd {
+                    interactions::NoteCommand::Edit(args) => interactions::edit_note(&ctx, args),
+                    interactions::NoteCommand::Rm(args) => interactions::remove_note(&ctx, args),
+                    interactions::NoteCommand::Attach(args) => interactions::attach(&ctx, args),
+                    interactions::NoteCommand::Attachments(args) => {
+                        interactions::list_attachments(&ctx, args)
+                    }
+                },
                 Command::Schedule(args) => schedule::schedule_contact(&ctx, args),
                 Command::ClearSchedule(args) => schedule::clear_schedule(&ctx, args),
+                Command::Rebalance(args) => rebalance::rebalance(&ctx, args),
+                Command::SuggestCadence(args) => cadence::suggest_contact_cadence(&ctx, args),
+                Command::Enrich(cmd) => match cmd {
+                    enrich::EnrichCommand::Timezones(args) => enrich::enrich_timezones(&ctx, args),
+                },
+                Command::Normalize(cmd) => match cmd {
+                    normalize::NormalizeCommand::Phones(args) => {
+                        normalize::normalize_phones(&ctx, args)
+                    }
+                },
                 Command::Remind(args) => remind::remind(&ctx, args),
-                Command::Sync(args) => sync::sync_all(&ctx, args),
+                Command::Vacation(args) => vacation::vacation(&ctx, args),
+                Command::Roulette(args) => roulette::roulette(&ctx, args),
+                Command::Followups(args) => followups::followups(&ctx, args),
+                Command::Sync(mut args) => match args.command.take() {
+                    Some(command) => sync_state::sync_command(&ctx, command),
+                    None => sync::sync_all(&ctx, args),
+                },
+                Command::Serve(args) => serve::serve(&ctx, args),
                 Command::Tui(_) => unreachable!("tui command handled before store initialization"),
-                Command::Completions(_) => {
+                Command::Completions(_) | Command::Complete(_) | Command::Mangen(_) => {
                     unreachable!("completions command handled before store initialization")
                 }
+                Command::Config(_) | Command::Init(_) | Command::Templates(_) => {
+                    unreachable!("config command handled before store initialization")
+                }
                 Command::Import(cmd) => match cmd {
                     sync::ImportCommand::Vcf(args) => sync::import_vcf(&ctx, args),
+                    sync::ImportCommand::GoogleCsv(args) => sync::import_google_csv(&ctx, args),
                     sync::ImportCommand::Macos(args) => sync::import_macos(&ctx, args),
                     sync::ImportCommand::Carddav(args) => sync::import_carddav(&ctx, args),
                     sync::ImportCommand::Email(args) => sync::import_email(&ctx, args),
                     sync::ImportCommand::Telegram(args) => sync::import_telegram(&ctx, args),
                     sync::ImportCommand::Source(args) => sync::import_source(&ctx, args),
+                    sync::ImportCommand::Interactions(args) => {
+                        import_interactions::import_interactions(&ctx, args)
+                    }
                 },
                 Command::Export(cmd) => match cmd {
                     sync::ExportCommand::Vcf(args) => sync::export_vcf(&ctx, args),
@@ -188,14 +429,157 @@ fn run(cli: Cli) -> Result<()> {
     }
 }
 
-fn init_logging(verbose: bool) {
-    use tracing_subscriber::{fmt, EnvFilter};
+/// Name of the command when it writes to the database, for `--readonly` checks.
+/// Every command is listed so a new one has to decide whether it writes.
+fn mutating_command(command: &Command) -> Option<&'static str> {
+    match command {
+        Command::Backup(_) => None,
+        Command::Doctor(args) if args.fix => Some("doctor --fix"),
+        Command::Doctor(_) => None,
+        Command::Db(db::DbCommand::Stats) => None,
+        Command::Db(db::DbCommand::Vacuum) => Some("db vacuum"),
+        Command::Dedupe(dedupe::DedupeCommand::Interactions(args)) if !args.dry_run => {
+            Some("dedupe interactions")
+        }
+        Command::Dedupe(dedupe::DedupeCommand::Interactions(_)) => None,
+        Command::Config(_) => None,
+        Command::Init(_) => Some("init"),
+        Command::Completions(_) => None,
+        Command::Complete(_) => None,
+        Command::Mangen(_) => None,
+        Command::AddContact(args) if !args.dry_run => Some("add-contact"),
+        Command::AddContact(_) => None,
+        Command::EditContact(args) if !args.dry_run => Some("edit-contact"),
+        Command::EditContact(_) => None,
+        Command::Show(_) => None,
+        Command::List(_) => None,
+        Command::Delete(_) => Some("delete"),
+        Command::Trash(trash::TrashCommand::Ls) => None,
+        Command::Trash(trash::TrashCommand::Restore(_)) => Some("trash restore"),
+        Command::Trash(trash::TrashCommand::Empty(_)) => Some("trash empty"),
+        Command::ArchiveContact(args) if !args.dry_run => Some("archive-contact"),
+        Command::ArchiveContact(_) => None,
+        Command::UnarchiveContact(args) if !args.dry_run => Some("unarchive-contact"),
+        Command::UnarchiveContact(_) => None,
+        Command::Mute(_) => Some("mute"),
+        Command::Unmute(_) => Some("unmute"),
+        Command::Log(_) => None,
+        Command::Tag(tags::TagCommand::Add(_)) => Some("tag add"),
+        Command::Tag(tags::TagCommand::Rm(_)) => Some("tag rm"),
+        Command::Tag(tags::TagCommand::Ls(args)) if args.prune_unused => {
+            Some("tag ls --prune-unused")
+        }
+        Command::Tag(tags::TagCommand::Ls(_)) => None,
+        Command::Tag(tags::TagCommand::Rename(args)) if !args.dry_run => Some("tag rename"),
+        Command::Tag(tags::TagCommand::Rename(_)) => None,
+        Command::Tag(tags::TagCommand::Merge(args)) if !args.dry_run => Some("tag merge"),
+        Command::Tag(tags::TagCommand::Merge(_)) => None,
+        Command::Tag(tags::TagCommand::Delete(args)) if !args.dry_run => Some("tag delete"),
+        Command::Tag(tags::TagCommand::Delete(_)) => None,
+        Command::Date(dates::DateCommand::Add(_)) => Some("date add"),
+        Command::Date(dates::DateCommand::Ls(_)) => None,
+        Command::Date(dates::DateCommand::Rm(_)) => Some("date rm"),
+        Command::Email(email::EmailCommand::Add(_)) => Some("email add"),
+        Command::Email(email::EmailCommand::Rm(_)) => Some("email rm"),
+        Command::Email(email::EmailCommand::Ls(_)) => None,
+        Command::Email(email::EmailCommand::SetPrimary(_)) => Some("email set-primary"),
+        Command::Alias(alias::AliasCommand::Add(_)) => Some("alias add"),
+        Command::Alias(alias::AliasCommand::Rm(_)) => Some("alias rm"),
+        Command::Alias(alias::AliasCommand::Ls(_)) => None,
+        Command::Loops(loops::LoopCommand::Apply(args)) if !args.dry_run => Some("loops apply"),
+        Command::Loops(loops::LoopCommand::Apply(_)) => None,
+        Command::Merge(merge::MergeCommand::List(_)) => None,
+        Command::Merge(merge::MergeCommand::Show(_)) => None,
+        Command::Merge(merge::MergeCommand::Apply(args)) if !args.dry_run => Some("merge apply"),
+        Command::Merge(merge::MergeCommand::Apply(_)) => None,
+        Command::Merge(merge::MergeCommand::ApplyAll(_)) => Some("merge apply-all"),
+        Command::Merge(merge::MergeCommand::Dismiss(_)) => Some("merge dismiss"),
+        Command::Merge(merge::MergeCommand::Contacts(_)) => Some("merge contacts"),
+        Command::Merge(merge::MergeCommand::Import(args)) if !args.dry_run => Some("merge import"),
+        Command::Merge(merge::MergeCommand::Import(_)) => None,
+        Command::Merge(merge::MergeCommand::Prune(args)) if !args.dry_run => Some("merge prune"),
+        Command::Merge(merge::MergeCommand::Prune(_)) => None,
+        Command::Merge(merge::MergeCommand::ScanSameName(args)) if !args.dry_run => {
+            Some("merge scan-same-name")
+        }
+        Command::Merge(merge::MergeCommand::ScanSameName(_)) => None,
+        Command::AddNote(_) => Some("add-note"),
+        Command::Touch(args) if !args.dry_run => Some("touch"),
+        Command::Touch(_) => None,
+        Command::Templates(_) => None,
+        Command::Note(interactions::NoteCommand::Edit(_)) => Some("note edit"),
+        Command::Note(interactions::NoteCommand::Rm(_)) => Some("note rm"),
+        Command::Note(interactions::NoteCommand::Attach(_)) => Some("note attach"),
+        Command::Note(interactions::NoteCommand::Attachments(_)) => None,
+        Command::Schedule(args) if !args.dry_run => Some("schedule"),
+        Command::Schedule(_) => None,
+        Command::ClearSchedule(args) if !args.dry_run => Some("clear-schedule"),
+        Command::ClearSchedule(_) => None,
+        Command::Rebalance(args) if !args.dry_run => Some("rebalance"),
+        Command::Rebalance(_) => None,
+        Command::SuggestCadence(args) if args.apply => Some("suggest-cadence --apply"),
+        Command::SuggestCadence(_) => None,
+        Command::Enrich(enrich::EnrichCommand::Timezones(args)) if !args.dry_run => {
+            Some("enrich timezones")
+        }
+        Command::Enrich(enrich::EnrichCommand::Timezones(_)) => None,
+        Command::Normalize(normalize::NormalizeCommand::Phones(args)) if args.apply => {
+            Some("normalize phones --apply")
+        }
+        Command::Normalize(normalize::NormalizeCommand::Phones(_)) => None,
+        Command::Remind(args) if args.interactive => Some("remind --interactive"),
+        Command::Remind(args) if !args.mark_contacted.is_empty() => Some("remind --mark-contacted"),
+        Command::Remind(_) => None,
+        Command::Vacation(args) if args.changes_state() => Some("vacation"),
+        Command::Vacation(_) => None,
+        Command::Roulette(args) if args.touch => Some("roulette --touch"),
+        Command::Roulette(_) => None,
+        Command::Followups(args) if args.command.is_some() => Some("followups done"),
+        Command::Followups(_) => None,
+        Command::Sync(args) => match &args.command {
+            None => Some("sync"),
+            Some(command) if command.is_reset() => Some("sync state reset"),
+            Some(_) => None,
+        },
+        // Serving reads is fine read-only; the write routes answer 403 instead.
+        Command::Serve(_) => None,
+        Command::Tui(_) => None,
+        Command::Import(_) => Some("import"),
+        Command::Export(_) => None,
+    }
+}
+
+/// Dry runs that apply their changes inside a transaction and roll it back.
+/// Under `--readonly` they run against an in-memory copy of the database.
+fn dry_run_rolls_back(command: &Command) -> bool {
+    match command {
+        Command::EditContact(args) => args.dry_run,
+        Command::ArchiveContact(args) => args.dry_run,
+        Command::UnarchiveContact(args) => args.dry_run,
+        Command::Touch(args) => args.dry_run,
+        Command::Schedule(args) => args.dry_run,
+        Command::ClearSchedule(args) => args.dry_run,
+        _ => false,
+    }
+}
+
+/// The profiler's layer has its own filter, so `--profile` does not change
+/// what gets logged. Logs go to stderr so they never end up in exported data.
+fn init_logging(verbose: bool, profiler: Option<&Profiler>) {
+    use tracing_subscriber::layer::SubscriberExt;
+    use tracing_subscriber::util::SubscriberInitExt;
+    use tracing_subscriber::{fmt, EnvFilter, Layer};
     let default_level = if verbose { "debug" } else { "warn" };
     let filter =
         EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new(default_level));
-    let _ = fmt()
-        .with_env_filter(filter)
-        .with_target(false)
-        .without_time()
+    let _ = tracing_subscriber::registry()
+        .with(
+            fmt::layer()
+                .with_writer(std::io::stderr)
+                .with_target(false)
+                .without_time()
+                .with_filter(filter),
+        )
+        .with(profiler.map(Profiler::layer))
         .try_init();
 }
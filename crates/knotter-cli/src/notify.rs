@@ -1,14 +1,47 @@
 use anyhow::Result;
 
+/// One notification payload: a short title and a plain-text body.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Notification {
+    pub title: String,
+    pub body: String,
+    /// Alternative HTML rendering of `body`; only the email backend uses it.
+    pub html: Option<String>,
+}
+
+impl Notification {
+    pub fn new(title: impl Into<String>, body: impl Into<String>) -> Self {
+        Self {
+            title: title.into(),
+            body: body.into(),
+            html: None,
+        }
+    }
+
+    #[cfg(feature = "email-notify")]
+    pub fn with_html(mut self, html: impl Into<String>) -> Self {
+        self.html = Some(html.into());
+        self
+    }
+}
+
 pub trait Notifier {
-    fn send(&self, title: &str, body: &str) -> Result<()>;
+    fn send(&self, notification: &Notification) -> Result<()>;
+
+    /// Sends each notification in order, stopping at the first failure.
+    fn send_all(&self, notifications: &[Notification]) -> Result<()> {
+        for notification in notifications {
+            self.send(notification)?;
+        }
+        Ok(())
+    }
 }
 
 pub struct StdoutNotifier;
 
 impl Notifier for StdoutNotifier {
-    fn send(&self, title: &str, body: &str) -> Result<()> {
-        println!("{title}: {body}");
+    fn send(&self, notification: &Notification) -> Result<()> {
+        println!("{}: {}", notification.title, notification.body);
         Ok(())
     }
 }
@@ -24,8 +57,8 @@ pub struct EmailNotifier {
 impl EmailNotifier {
     pub fn new(config: &knotter_config::NotificationsEmailConfig) -> Result<Self> {
         use crate::error::invalid_input;
+        use crate::secret::resolve_secret;
         use lettre::transport::smtp::authentication::Credentials;
-        use std::env;
         use std::time::Duration;
 
         let from = config
@@ -60,16 +93,11 @@ impl EmailNotifier {
             builder = builder.timeout(Some(Duration::from_secs(seconds)));
         }
 
-        if let (Some(username), Some(password_env)) =
-            (config.username.as_deref(), config.password_env.as_deref())
+        if let (Some(username), Some(password)) =
+            (config.username.as_deref(), config.password.as_ref())
         {
-            let password = env::var(password_env)
-                .map_err(|_| invalid_input(format!("missing env var {password_env}")))?;
-            let password = password.trim();
-            if password.is_empty() {
-                return Err(invalid_input(format!("env var {password_env} is empty")));
-            }
-            let credentials = Credentials::new(username.to_string(), password.to_string());
+            let password = resolve_secret(password, "notifications.email password")?;
+            let credentials = Credentials::new(username.to_string(), password);
             builder = builder.credentials(credentials);
         }
 
@@ -83,34 +111,193 @@ impl EmailNotifier {
 
 #[cfg(feature = "email-notify")]
 impl Notifier for EmailNotifier {
-    fn send(&self, title: &str, body: &str) -> Result<()> {
+    fn send(&self, notification: &Notification) -> Result<()> {
         use lettre::message::header::ContentType;
+        use lettre::message::MultiPart;
         use lettre::Message;
         use lettre::Transport as _;
 
         let mut builder = Message::builder()
             .from(self.from.clone())
-            .subject(title)
-            .header(ContentType::TEXT_PLAIN);
+            .subject(&notification.title);
         for mailbox in &self.to {
             builder = builder.to(mailbox.clone());
         }
 
-        let message = builder.body(body.to_string())?;
+        let message = match &notification.html {
+            Some(html) => builder.multipart(MultiPart::alternative_plain_html(
+                notification.body.clone(),
+                html.clone(),
+            ))?,
+            None => builder
+                .header(ContentType::TEXT_PLAIN)
+                .body(notification.body.clone())?,
+        };
         self.transport.send(&message)?;
         Ok(())
     }
 }
 
+#[cfg(feature = "webhook-notify")]
+const WEBHOOK_DEFAULT_TIMEOUT_SECONDS: u64 = 10;
+#[cfg(feature = "webhook-notify")]
+const DISCORD_CONTENT_LIMIT: usize = 2000;
+
+#[cfg(feature = "webhook-notify")]
+pub struct WebhookNotifier {
+    client: reqwest::blocking::Client,
+    url: String,
+    format: knotter_config::WebhookFormat,
+    token: Option<String>,
+}
+
+#[cfg(feature = "webhook-notify")]
+#[derive(Debug, PartialEq, Eq)]
+struct WebhookRequest {
+    content_type: &'static str,
+    title_header: Option<String>,
+    body: Vec<u8>,
+}
+
+#[cfg(feature = "webhook-notify")]
+impl WebhookNotifier {
+    pub fn new(config: &knotter_config::NotificationsWebhookConfig) -> Result<Self> {
+        use crate::secret::resolve_secret;
+        use std::time::Duration;
+
+        let token = config
+            .token
+            .as_ref()
+            .map(|token| resolve_secret(token, "notifications.webhook token"))
+            .transpose()?;
+
+        let timeout = config
+            .timeout_seconds
+            .unwrap_or(WEBHOOK_DEFAULT_TIMEOUT_SECONDS);
+        let client = reqwest::blocking::Client::builder()
+            .timeout(Duration::from_secs(timeout))
+            .build()?;
+
+        Ok(Self {
+            client,
+            url: config.url.clone(),
+            format: config.format,
+            token,
+        })
+    }
+
+    /// Posts the reminder summary. The `json` format sends `data` as the payload;
+    /// the chat formats only use the text summary.
+    pub fn send_with_data(
+        &self,
+        notification: &Notification,
+        data: &serde_json::Value,
+    ) -> Result<()> {
+        self.post(webhook_request(
+            self.format,
+            &notification.title,
+            &notification.body,
+            Some(data),
+        )?)
+    }
+
+    fn post(&self, request: WebhookRequest) -> Result<()> {
+        use reqwest::header::{AUTHORIZATION, CONTENT_TYPE};
+
+        let mut builder = self
+            .client
+            .post(&self.url)
+            .header(CONTENT_TYPE, request.content_type)
+            .body(request.body);
+        if let Some(title) = request.title_header {
+            builder = builder.header("Title", title);
+        }
+        if let Some(token) = &self.token {
+            builder = builder.header(AUTHORIZATION, format!("Bearer {token}"));
+        }
+
+        let response = builder.send()?;
+        let status = response.status();
+        if !status.is_success() {
+            let detail = response.text().unwrap_or_default();
+            let detail = detail.trim();
+            if detail.is_empty() {
+                anyhow::bail!("webhook returned HTTP {status}");
+            }
+            let detail: String = detail.chars().take(200).collect();
+            anyhow::bail!("webhook returned HTTP {status}: {detail}");
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "webhook-notify")]
+impl Notifier for WebhookNotifier {
+    fn send(&self, notification: &Notification) -> Result<()> {
+        self.post(webhook_request(
+            self.format,
+            &notification.title,
+            &notification.body,
+            None,
+        )?)
+    }
+}
+
+#[cfg(feature = "webhook-notify")]
+fn webhook_request(
+    format: knotter_config::WebhookFormat,
+    title: &str,
+    body: &str,
+    data: Option<&serde_json::Value>,
+) -> Result<WebhookRequest> {
+    use knotter_config::WebhookFormat;
+    use serde_json::json;
+
+    const JSON: &str = "application/json";
+    let text = format!("{title}\n{body}");
+    let request = match format {
+        WebhookFormat::Slack => WebhookRequest {
+            content_type: JSON,
+            title_header: None,
+            body: serde_json::to_vec(&json!({ "text": text }))?,
+        },
+        WebhookFormat::Discord => {
+            let content: String = text.chars().take(DISCORD_CONTENT_LIMIT).collect();
+            WebhookRequest {
+                content_type: JSON,
+                title_header: None,
+                body: serde_json::to_vec(&json!({ "content": content }))?,
+            }
+        }
+        WebhookFormat::Ntfy => WebhookRequest {
+            content_type: "text/plain; charset=utf-8",
+            title_header: Some(title.to_string()),
+            body: body.as_bytes().to_vec(),
+        },
+        WebhookFormat::Json => {
+            let payload = match data {
+                Some(data) => json!({ "title": title, "data": data }),
+                None => json!({ "title": title, "body": body }),
+            };
+            WebhookRequest {
+                content_type: JSON,
+                title_header: None,
+                body: serde_json::to_vec(&payload)?,
+            }
+        }
+    };
+    Ok(request)
+}
+
 #[cfg(feature = "desktop-notify")]
 pub struct DesktopNotifier;
 
 #[cfg(feature = "desktop-notify")]
 impl Notifier for DesktopNotifier {
-    fn send(&self, title: &str, body: &str) -> Result<()> {
+    fn send(&self, notification: &Notification) -> Result<()> {
         notify_rust::Notification::new()
-            .summary(title)
-            .body(body)
+            .summary(&notification.title)
+            .body(&notification.body)
             .show()?;
         Ok(())
     }
@@ -119,7 +306,7 @@ impl Notifier for DesktopNotifier {
 #[cfg(all(test, feature = "email-notify"))]
 mod tests {
     use super::EmailNotifier;
-    use knotter_config::{EmailTls, NotificationsEmailConfig};
+    use knotter_config::{EmailTls, NotificationsEmailConfig, SecretSource};
 
     fn base_config() -> NotificationsEmailConfig {
         NotificationsEmailConfig {
@@ -129,9 +316,10 @@ mod tests {
             smtp_host: "smtp.example.com".to_string(),
             smtp_port: Some(587),
             username: None,
-            password_env: None,
+            password: None,
             tls: EmailTls::StartTls,
             timeout_seconds: Some(5),
+            html: false,
         }
     }
 
@@ -139,12 +327,14 @@ mod tests {
     fn email_notifier_new_fails_when_password_env_missing() {
         let mut config = base_config();
         config.username = Some("user@example.com".to_string());
-        config.password_env = Some("KNOTTER_TEST_SMTP_PASSWORD".to_string());
+        config.password = Some(SecretSource::Env("KNOTTER_TEST_SMTP_PASSWORD".to_string()));
         std::env::remove_var("KNOTTER_TEST_SMTP_PASSWORD");
         match EmailNotifier::new(&config) {
             Ok(_) => panic!("expected error"),
             Err(err) => {
-                assert!(err.to_string().contains("missing env var"));
+                assert!(err
+                    .to_string()
+                    .contains("environment variable KNOTTER_TEST_SMTP_PASSWORD is not set"));
             }
         }
     }
@@ -153,19 +343,39 @@ mod tests {
     fn email_notifier_new_fails_when_password_env_empty() {
         let mut config = base_config();
         config.username = Some("user@example.com".to_string());
-        config.password_env = Some("KNOTTER_TEST_SMTP_PASSWORD_EMPTY".to_string());
+        config.password = Some(SecretSource::Env(
+            "KNOTTER_TEST_SMTP_PASSWORD_EMPTY".to_string(),
+        ));
         std::env::set_var("KNOTTER_TEST_SMTP_PASSWORD_EMPTY", "   ");
         match EmailNotifier::new(&config) {
             Ok(_) => panic!("expected error"),
             Err(err) => {
                 assert!(err
                     .to_string()
-                    .contains("env var KNOTTER_TEST_SMTP_PASSWORD_EMPTY is empty"));
+                    .contains("environment variable KNOTTER_TEST_SMTP_PASSWORD_EMPTY is empty"));
             }
         }
         std::env::remove_var("KNOTTER_TEST_SMTP_PASSWORD_EMPTY");
     }
 
+    #[cfg(unix)]
+    #[test]
+    fn email_notifier_new_reads_password_from_command() {
+        let mut config = base_config();
+        config.username = Some("user@example.com".to_string());
+        config.password = Some(SecretSource::Command(vec![
+            "/bin/echo".to_string(),
+            "hunter2".to_string(),
+        ]));
+        assert!(EmailNotifier::new(&config).is_ok());
+
+        config.password = Some(SecretSource::Command(vec!["/bin/echo".to_string()]));
+        match EmailNotifier::new(&config) {
+            Ok(_) => panic!("expected error"),
+            Err(err) => assert!(err.to_string().contains("printed nothing")),
+        }
+    }
+
     #[test]
     fn email_notifier_new_supports_tls_modes() {
         let mut config = base_config();
@@ -176,3 +386,65 @@ mod tests {
         }
     }
 }
+
+#[cfg(all(test, feature = "webhook-notify"))]
+mod webhook_tests {
+    use super::{webhook_request, WebhookNotifier};
+    use knotter_config::{NotificationsWebhookConfig, SecretSource, WebhookFormat};
+    use serde_json::{json, Value};
+
+    fn payload(format: WebhookFormat, data: Option<&Value>) -> Value {
+        let request = webhook_request(format, "knotter reminders", "Overdue: Ada", data)
+            .expect("build request");
+        serde_json::from_slice(&request.body).expect("json body")
+    }
+
+    #[test]
+    fn webhook_request_formats_chat_payloads() {
+        assert_eq!(
+            payload(WebhookFormat::Slack, None),
+            json!({ "text": "knotter reminders\nOverdue: Ada" })
+        );
+        assert_eq!(
+            payload(WebhookFormat::Discord, None),
+            json!({ "content": "knotter reminders\nOverdue: Ada" })
+        );
+
+        let request = webhook_request(
+            WebhookFormat::Ntfy,
+            "knotter reminders",
+            "Overdue: Ada",
+            None,
+        )
+        .expect("build request");
+        assert_eq!(request.title_header.as_deref(), Some("knotter reminders"));
+        assert_eq!(request.body, b"Overdue: Ada".to_vec());
+    }
+
+    #[test]
+    fn webhook_request_json_embeds_structured_data() {
+        let data = json!({ "overdue": [{ "display_name": "Ada" }] });
+        assert_eq!(
+            payload(WebhookFormat::Json, Some(&data)),
+            json!({ "title": "knotter reminders", "data": data })
+        );
+    }
+
+    #[test]
+    fn webhook_notifier_new_fails_when_token_env_missing() {
+        let config = NotificationsWebhookConfig {
+            url: "https://example.com/hook".to_string(),
+            format: WebhookFormat::Json,
+            token: Some(SecretSource::Env("KNOTTER_TEST_WEBHOOK_TOKEN".to_string())),
+            timeout_seconds: None,
+            allow_insecure: false,
+        };
+        std::env::remove_var("KNOTTER_TEST_WEBHOOK_TOKEN");
+        match WebhookNotifier::new(&config) {
+            Ok(_) => panic!("expected error"),
+            Err(err) => assert!(err
+                .to_string()
+                .contains("environment variable KNOTTER_TEST_WEBHOOK_TOKEN is not set")),
+        }
+    }
+}
@@ -0,0 +1,157 @@
+//! `--profile`: wall time per phase of a command.
+//!
+//! Phases are `tracing` spans with the [`TARGET`] target, opened at command and
+//! sync-runner boundaries through [`phase`]. The recording layer is only
+//! installed when profiling is on; otherwise the spans are disabled at their
+//! callsites and their labels are never formatted.
+
+use anyhow::{Context as _, Result};
+use serde::Serialize;
+use std::fmt;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, EnteredSpan, Id};
+use tracing::{Span, Subscriber};
+use tracing_subscriber::filter::filter_fn;
+use tracing_subscriber::layer::{Context, Layer};
+use tracing_subscriber::registry::LookupSpan;
+
+pub const TARGET: &str = "knotter::profile";
+
+/// Starts a phase nested under the current one; it ends when the guard drops.
+pub fn phase(label: impl fmt::Display) -> EnteredSpan {
+    tracing::info_span!(target: TARGET, "phase", label = %label).entered()
+}
+
+/// Starts a phase under `parent`, for work handed to another thread.
+pub fn phase_within(parent: &Span, label: impl fmt::Display) -> EnteredSpan {
+    tracing::info_span!(target: TARGET, parent: parent, "phase", label = %label).entered()
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PhaseTiming {
+    pub phase: String,
+    /// Number of enclosing phases.
+    pub depth: usize,
+    pub elapsed_ms: f64,
+}
+
+#[derive(Debug, Serialize)]
+struct ProfileReport<'a> {
+    phases: &'a [PhaseTiming],
+}
+
+/// Collects phases in the order they started.
+#[derive(Debug, Clone, Default)]
+pub struct Profiler {
+    phases: Arc<Mutex<Vec<PhaseTiming>>>,
+}
+
+impl Profiler {
+    pub fn layer<S>(&self) -> impl Layer<S>
+    where
+        S: Subscriber + for<'a> LookupSpan<'a>,
+    {
+        PhaseLayer {
+            phases: Arc::clone(&self.phases),
+        }
+        .with_filter(filter_fn(|metadata| metadata.target() == TARGET))
+    }
+
+    pub fn phases(&self) -> Vec<PhaseTiming> {
+        self.phases
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+            .clone()
+    }
+
+    /// Table of phases, indented by nesting, for stderr.
+    pub fn summary(&self) -> String {
+        let phases = self.phases();
+        let width = phases
+            .iter()
+            .map(|timing| timing.depth * 2 + timing.phase.len())
+            .max()
+            .unwrap_or(0)
+            .max("phase".len());
+        let mut out = format!("profile:\n  {:<width$}  {:>10}\n", "phase", "ms");
+        for timing in &phases {
+            let label = format!("{}{}", "  ".repeat(timing.depth), timing.phase);
+            out.push_str(&format!("  {label:<width$}  {:>10.1}\n", timing.elapsed_ms));
+        }
+        out
+    }
+
+    pub fn write_json(&self, path: &Path) -> Result<()> {
+        let phases = self.phases();
+        let json = serde_json::to_string_pretty(&ProfileReport { phases: &phases })?;
+        std::fs::write(path, format!("{json}\n"))
+            .with_context(|| format!("write profile {}", path.display()))
+    }
+}
+
+struct PhaseLayer {
+    phases: Arc<Mutex<Vec<PhaseTiming>>>,
+}
+
+/// Where a phase sits in the collected list, and when it started.
+struct Started {
+    index: usize,
+    at: Instant,
+}
+
+impl<S> Layer<S> for PhaseLayer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(id) else {
+            return;
+        };
+        let mut label = LabelVisitor(None);
+        attrs.record(&mut label);
+        let depth = span.scope().skip(1).count();
+        let mut phases = self.phases.lock().unwrap_or_else(|err| err.into_inner());
+        phases.push(PhaseTiming {
+            phase: label.0.unwrap_or_else(|| span.name().to_string()),
+            depth,
+            elapsed_ms: 0.0,
+        });
+        span.extensions_mut().insert(Started {
+            index: phases.len() - 1,
+            at: Instant::now(),
+        });
+    }
+
+    fn on_close(&self, id: Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(&id) else {
+            return;
+        };
+        let extensions = span.extensions();
+        let Some(started) = extensions.get::<Started>() else {
+            return;
+        };
+        let mut phases = self.phases.lock().unwrap_or_else(|err| err.into_inner());
+        if let Some(timing) = phases.get_mut(started.index) {
+            timing.elapsed_ms = (started.at.elapsed().as_secs_f64() * 1_000_000.0).round() / 1000.0;
+        }
+    }
+}
+
+struct LabelVisitor(Option<String>);
+
+impl Visit for LabelVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "label" {
+            self.0 = Some(value.to_string());
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        if field.name() == "label" {
+            self.0 = Some(format!("{value:?}"));
+        }
+    }
+}
@@ -0,0 +1,308 @@
+//! Progress for long imports (`import email`, `import telegram`).
+//!
+//! Import loops report their counters to a [`ProgressSink`] after every
+//! message. [`StderrProgress`] redraws one line per mailbox on a terminal,
+//! once a second after the first second, and prints a plain line every
+//! [`LOG_EVERY`] messages otherwise. Shown updates are also `debug` events with the [`TARGET`]
+//! target, so `--verbose` logs the same counters; the redrawn line is left off
+//! then, since the log lines would tear it.
+
+use std::fmt;
+use std::io::{self, IsTerminal, Write};
+use std::time::{Duration, Instant};
+
+pub const TARGET: &str = "knotter::progress";
+
+/// Messages between plain progress lines when stderr is not a terminal.
+pub const LOG_EVERY: usize = 1000;
+
+const REDRAW_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Counters for one mailbox or account of a running import.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ImportProgress {
+    /// Mailbox or account the counters belong to.
+    pub label: String,
+    /// What `fetched` and `total` count, e.g. `headers`.
+    pub unit: &'static str,
+    pub fetched: usize,
+    pub total: usize,
+    /// Messages handled so far.
+    pub processed: usize,
+    /// Messages matched to an existing contact.
+    pub matched: usize,
+    pub contacts_created: usize,
+}
+
+impl ImportProgress {
+    pub fn new(label: impl Into<String>, unit: &'static str) -> Self {
+        Self {
+            label: label.into(),
+            unit,
+            ..Self::default()
+        }
+    }
+
+    fn steps(&self) -> usize {
+        self.fetched + self.processed
+    }
+}
+
+impl fmt::Display for ImportProgress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}: fetched {}/{} {}",
+            self.label,
+            with_separators(self.fetched),
+            with_separators(self.total),
+            self.unit
+        )?;
+        if self.processed > 0 {
+            write!(f, ", {} processed", with_separators(self.processed))?;
+        }
+        write!(
+            f,
+            ", {} matched, {} created",
+            with_separators(self.matched),
+            with_separators(self.contacts_created)
+        )
+    }
+}
+
+/// Receives import counters as they change.
+pub trait ProgressSink {
+    /// Counters changed; sinks decide whether to show them.
+    fn update(&mut self, progress: &ImportProgress);
+    /// The mailbox or account is done.
+    fn finish(&mut self, progress: &ImportProgress);
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Style {
+    /// Rewrite one line in place.
+    Redraw,
+    /// A new line every [`LOG_EVERY`] messages.
+    Lines,
+    /// Only the `debug` events.
+    Hidden,
+}
+
+/// Progress on stderr, or any writer in tests.
+pub struct StderrProgress<W: Write = io::Stderr> {
+    out: W,
+    style: Style,
+    /// Last redraw, or when the current mailbox started, so mailboxes that
+    /// finish within a second are never drawn.
+    last_redraw: Instant,
+    next_line_at: usize,
+    shown: bool,
+}
+
+impl StderrProgress {
+    /// Hidden with `--quiet` or `--verbose`; otherwise redrawn on a terminal
+    /// and printed as plain lines when stderr is redirected.
+    pub fn stderr(quiet: bool) -> Self {
+        let style = if quiet || tracing::enabled!(target: TARGET, tracing::Level::DEBUG) {
+            Style::Hidden
+        } else if io::stderr().is_terminal() {
+            Style::Redraw
+        } else {
+            Style::Lines
+        };
+        Self::with_style(io::stderr(), style)
+    }
+}
+
+impl<W: Write> StderrProgress<W> {
+    fn with_style(out: W, style: Style) -> Self {
+        Self {
+            out,
+            style,
+            last_redraw: Instant::now(),
+            next_line_at: LOG_EVERY,
+            shown: false,
+        }
+    }
+
+    fn due(&mut self, progress: &ImportProgress) -> bool {
+        match self.style {
+            Style::Redraw | Style::Hidden => {
+                let now = Instant::now();
+                if now.duration_since(self.last_redraw) < REDRAW_INTERVAL {
+                    return false;
+                }
+                self.last_redraw = now;
+                true
+            }
+            Style::Lines => {
+                if progress.steps() < self.next_line_at {
+                    return false;
+                }
+                self.next_line_at = (progress.steps() / LOG_EVERY + 1) * LOG_EVERY;
+                true
+            }
+        }
+    }
+}
+
+impl<W: Write> ProgressSink for StderrProgress<W> {
+    fn update(&mut self, progress: &ImportProgress) {
+        if !self.due(progress) {
+            return;
+        }
+        tracing::debug!(target: TARGET, "{progress}");
+        self.shown = true;
+        // Progress is best effort; a closed stderr must not fail the import.
+        let _ = match self.style {
+            Style::Redraw => write!(self.out, "\r{progress}\x1b[K").and_then(|()| self.out.flush()),
+            Style::Lines => writeln!(self.out, "{progress}"),
+            Style::Hidden => Ok(()),
+        };
+    }
+
+    /// Prints the final counters, but only for work that showed progress
+    /// before, so short imports stay quiet.
+    fn finish(&mut self, progress: &ImportProgress) {
+        if self.shown {
+            tracing::debug!(target: TARGET, "{progress}");
+            let _ = match self.style {
+                Style::Redraw => writeln!(self.out, "\r{progress}\x1b[K"),
+                Style::Lines => writeln!(self.out, "{progress}"),
+                Style::Hidden => Ok(()),
+            };
+        }
+        self.last_redraw = Instant::now();
+        self.next_line_at = LOG_EVERY;
+        self.shown = false;
+    }
+}
+
+/// Ends a redrawn line left open by an import that failed part way, so the
+/// error does not land on it.
+impl<W: Write> Drop for StderrProgress<W> {
+    fn drop(&mut self) {
+        if self.shown && self.style == Style::Redraw {
+            let _ = writeln!(self.out);
+        }
+    }
+}
+
+/// Keeps every update, for tests of the import loops.
+#[cfg(test)]
+#[derive(Debug, Default)]
+pub struct RecordedProgress {
+    pub updates: Vec<ImportProgress>,
+    pub finished: Vec<ImportProgress>,
+}
+
+#[cfg(test)]
+impl ProgressSink for RecordedProgress {
+    fn update(&mut self, progress: &ImportProgress) {
+        self.updates.push(progress.clone());
+    }
+
+    fn finish(&mut self, progress: &ImportProgress) {
+        self.finished.push(progress.clone());
+    }
+}
+
+/// `18540` as `18,540`.
+fn with_separators(value: usize) -> String {
+    let digits = value.to_string();
+    let mut out = String::with_capacity(digits.len() + digits.len() / 3);
+    for (index, digit) in digits.chars().enumerate() {
+        if index > 0 && (digits.len() - index).is_multiple_of(3) {
+            out.push(',');
+        }
+        out.push(digit);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn inbox(fetched: usize, processed: usize) -> ImportProgress {
+        ImportProgress {
+            fetched,
+            total: 18_540,
+            processed,
+            matched: 210,
+            contacts_created: 12,
+            ..ImportProgress::new("INBOX", "headers")
+        }
+    }
+
+    #[test]
+    fn progress_line_groups_digits_and_shows_processed_once_matching_starts() {
+        assert_eq!(
+            inbox(3_200, 0).to_string(),
+            "INBOX: fetched 3,200/18,540 headers, 210 matched, 12 created"
+        );
+        assert_eq!(
+            inbox(18_540, 1_000_000).to_string(),
+            "INBOX: fetched 18,540/18,540 headers, 1,000,000 processed, 210 matched, 12 created"
+        );
+    }
+
+    #[test]
+    fn plain_lines_come_every_log_every_messages_and_short_runs_stay_silent() {
+        let mut sink = StderrProgress::with_style(Vec::new(), Style::Lines);
+        for fetched in [10, 999, 1_000, 1_500, 2_400] {
+            sink.update(&inbox(fetched, 0));
+        }
+        sink.finish(&inbox(2_400, 0));
+        let short = inbox(5, 0);
+        sink.update(&short);
+        sink.finish(&short);
+
+        let out = String::from_utf8(sink.out.clone()).expect("utf8");
+        assert_eq!(
+            out.lines().collect::<Vec<_>>(),
+            vec![
+                "INBOX: fetched 1,000/18,540 headers, 210 matched, 12 created",
+                "INBOX: fetched 2,400/18,540 headers, 210 matched, 12 created",
+                "INBOX: fetched 2,400/18,540 headers, 210 matched, 12 created",
+            ]
+        );
+    }
+
+    /// Pretends the current mailbox started over a second ago.
+    fn backdate(sink: &mut StderrProgress<Vec<u8>>) {
+        sink.last_redraw = Instant::now()
+            .checked_sub(REDRAW_INTERVAL)
+            .expect("clock older than a second");
+    }
+
+    #[test]
+    fn redraw_waits_a_second_so_quick_mailboxes_stay_silent() {
+        let mut sink = StderrProgress::with_style(Vec::new(), Style::Redraw);
+        sink.update(&inbox(500, 0));
+        sink.update(&inbox(1_000, 0));
+        sink.finish(&inbox(1_000, 0));
+        assert!(sink.out.is_empty());
+
+        backdate(&mut sink);
+        sink.finish(&inbox(18_540, 0));
+        sink.update(&inbox(10, 0));
+        assert!(sink.out.is_empty(), "finish restarts the wait");
+    }
+
+    #[test]
+    fn redraw_is_throttled_and_finish_ends_the_line() {
+        let mut sink = StderrProgress::with_style(Vec::new(), Style::Redraw);
+        backdate(&mut sink);
+        sink.update(&inbox(500, 0));
+        sink.update(&inbox(1_000, 0));
+        sink.finish(&inbox(18_540, 0));
+
+        let out = String::from_utf8(sink.out.clone()).expect("utf8");
+        assert_eq!(
+            out,
+            "\rINBOX: fetched 500/18,540 headers, 210 matched, 12 created\x1b[K\
+             \rINBOX: fetched 18,540/18,540 headers, 210 matched, 12 created\x1b[K\n"
+        );
+    }
+}
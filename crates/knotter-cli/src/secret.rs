@@ -0,0 +1,88 @@
+use crate::error::invalid_input;
+use anyhow::Result;
+use knotter_config::SecretSource;
+use std::process::{Command, Stdio};
+
+/// Reads a credential from its environment variable or by running its command.
+/// `label` names what the secret is for in error messages.
+pub fn resolve_secret(source: &SecretSource, label: &str) -> Result<String> {
+    match source {
+        SecretSource::Env(var) => {
+            let value = std::env::var(var).map_err(|_| {
+                invalid_input(format!(
+                    "environment variable {var} is not set (required for {label})"
+                ))
+            })?;
+            let trimmed = value.trim();
+            if trimmed.is_empty() {
+                return Err(invalid_input(format!(
+                    "environment variable {var} is empty (required for {label})"
+                )));
+            }
+            Ok(trimmed.to_string())
+        }
+        SecretSource::Command(argv) => run_secret_command(argv, label),
+    }
+}
+
+fn run_secret_command(argv: &[String], label: &str) -> Result<String> {
+    let (program, args) = argv
+        .split_first()
+        .ok_or_else(|| invalid_input(format!("empty secret command for {label}")))?;
+    let output = Command::new(program)
+        .args(args)
+        .stdin(Stdio::null())
+        .stderr(Stdio::inherit())
+        .output()
+        .map_err(|err| {
+            invalid_input(format!(
+                "failed to run secret command `{program}` for {label}: {err}"
+            ))
+        })?;
+    if !output.status.success() {
+        return Err(invalid_input(format!(
+            "secret command `{program}` for {label} exited with {}",
+            output.status
+        )));
+    }
+    let stdout = String::from_utf8(output.stdout).map_err(|_| {
+        invalid_input(format!(
+            "secret command `{program}` for {label} printed invalid UTF-8"
+        ))
+    })?;
+    let secret = stdout.trim_end_matches(['\n', '\r']);
+    if secret.trim().is_empty() {
+        return Err(invalid_input(format!(
+            "secret command `{program}` for {label} printed nothing"
+        )));
+    }
+    Ok(secret.to_string())
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::resolve_secret;
+    use knotter_config::SecretSource;
+
+    fn command(argv: &[&str]) -> SecretSource {
+        SecretSource::Command(argv.iter().map(|arg| arg.to_string()).collect())
+    }
+
+    #[test]
+    fn command_secret_trims_trailing_newline_only() {
+        let secret = resolve_secret(&command(&["/bin/echo", " hunter2"]), "test").expect("secret");
+        assert_eq!(secret, " hunter2");
+    }
+
+    #[test]
+    fn command_secret_rejects_failure_and_empty_output() {
+        let err = resolve_secret(&command(&["/bin/sh", "-c", "exit 3"]), "test").unwrap_err();
+        assert!(err.to_string().contains("exited with"));
+
+        let err = resolve_secret(&command(&["/bin/echo"]), "test").unwrap_err();
+        assert!(err.to_string().contains("printed nothing"));
+
+        let err = resolve_secret(&command(&["/nonexistent/knotter-secret"]), "test").unwrap_err();
+        assert!(err.to_string().contains("failed to run secret command"));
+    }
+}
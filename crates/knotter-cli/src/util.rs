@@ -1,12 +1,9 @@
 use crate::error::invalid_input;
 use anyhow::Result;
-use knotter_core::domain::{ContactDateId, ContactId, InteractionKind};
+use knotter_core::domain::{ContactDateId, ContactId, InteractionId, InteractionKind};
 use knotter_core::rules::DueState;
-pub use knotter_core::time::{
-    format_date_parts, format_timestamp_date, format_timestamp_datetime, local_offset, now_utc,
-    parse_date_parts, parse_local_date_time_with_precision, parse_local_timestamp,
-    parse_local_timestamp_with_precision,
-};
+pub use knotter_core::time::{format_date_parts, parse_date_parts};
+use knotter_core::CoreError;
 use std::str::FromStr;
 
 pub fn parse_interaction_kind(raw: &str) -> Result<InteractionKind> {
@@ -14,33 +11,12 @@ pub fn parse_interaction_kind(raw: &str) -> Result<InteractionKind> {
     if trimmed.is_empty() {
         return Err(invalid_input("interaction kind cannot be empty"));
     }
-    let lower = trimmed.to_ascii_lowercase();
-    match lower.as_str() {
-        "call" => Ok(InteractionKind::Call),
-        "text" => Ok(InteractionKind::Text),
-        "hangout" => Ok(InteractionKind::Hangout),
-        "email" => Ok(InteractionKind::Email),
-        "telegram" => Ok(InteractionKind::Telegram),
-        _ => {
-            if lower.starts_with("other:") {
-                let rest = &trimmed[6..];
-                return Ok(InteractionKind::other(rest)?);
-            }
-            Err(invalid_input(
-                "invalid interaction kind: expected call|text|hangout|email|telegram|other:<label>",
-            ))
-        }
-    }
-}
-
-pub fn format_interaction_kind(kind: &InteractionKind) -> String {
-    match kind {
-        InteractionKind::Call => "call".to_string(),
-        InteractionKind::Text => "text".to_string(),
-        InteractionKind::Hangout => "hangout".to_string(),
-        InteractionKind::Email => "email".to_string(),
-        InteractionKind::Telegram => "telegram".to_string(),
-        InteractionKind::Other(label) => format!("other:{}", label),
+    match trimmed.parse() {
+        Ok(kind) => Ok(kind),
+        Err(CoreError::InvalidInteractionKind(_)) => Err(invalid_input(
+            "invalid interaction kind: expected call|text|hangout|email|telegram|other:<label>",
+        )),
+        Err(err) => Err(err.into()),
     }
 }
 
@@ -69,3 +45,54 @@ pub fn parse_contact_date_id(raw: &str) -> Result<ContactDateId> {
     }
     ContactDateId::from_str(trimmed).map_err(|_| invalid_input("invalid contact date id"))
 }
+
+pub fn parse_interaction_id(raw: &str) -> Result<InteractionId> {
+    let trimmed = raw.trim();
+    if trimmed.is_empty() {
+        return Err(invalid_input("interaction id cannot be empty"));
+    }
+    InteractionId::from_str(trimmed).map_err(|_| invalid_input("invalid interaction id"))
+}
+
+/// Collapses whitespace and truncates to `max_len` characters; `None` when blank.
+pub fn snippet_from_text(text: Option<&str>, max_len: usize) -> Option<String> {
+    let raw = text?;
+    let collapsed = collapse_whitespace(raw);
+    if collapsed.is_empty() {
+        return None;
+    }
+    Some(truncate_with_ellipsis(&collapsed, max_len))
+}
+
+fn collapse_whitespace(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    let mut last_was_space = false;
+    for ch in value.chars() {
+        if ch.is_whitespace() {
+            if !last_was_space {
+                out.push(' ');
+                last_was_space = true;
+            }
+        } else {
+            out.push(ch);
+            last_was_space = false;
+        }
+    }
+    out.trim().to_string()
+}
+
+fn truncate_with_ellipsis(value: &str, max_len: usize) -> String {
+    if max_len == 0 {
+        return String::new();
+    }
+    let total_len = value.chars().count();
+    if total_len <= max_len {
+        return value.to_string();
+    }
+    if max_len <= 3 {
+        return value.chars().take(max_len).collect();
+    }
+    let mut out: String = value.chars().take(max_len - 3).collect();
+    out.push_str("...");
+    out
+}
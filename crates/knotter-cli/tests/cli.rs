@@ -4,7 +4,7 @@ use knotter_core::domain::ContactId;
 use knotter_core::domain::InteractionKind;
 use knotter_core::domain::MergeCandidateReason;
 use knotter_core::rules::{schedule_next, MAX_SOON_DAYS};
-use knotter_core::time::parse_local_timestamp;
+use knotter_core::time::Clock;
 use knotter_store::repo::ContactUpdate;
 use knotter_store::repo::MergeCandidateCreate;
 use knotter_store::Store;
@@ -115,6 +115,12 @@ fn run_cmd_json_with_env(db_path: &Path, args: &[&str], envs: &[(&str, &str)]) -
     serde_json::from_slice(&output.stdout).expect("parse json")
 }
 
+fn tomorrow_local_date() -> String {
+    (Local::now() + Duration::days(1))
+        .format("%Y-%m-%d")
+        .to_string()
+}
+
 #[test]
 fn cli_merge_contacts_merges_records() {
     let dir = TempDir::new().expect("temp dir");
@@ -136,6 +142,7 @@ fn cli_merge_contacts_merges_records() {
                 next_touchpoint_at: None,
                 cadence_days: None,
                 archived_at: None,
+                organization: None,
             },
         )
         .expect("create primary");
@@ -153,6 +160,7 @@ fn cli_merge_contacts_merges_records() {
                 next_touchpoint_at: None,
                 cadence_days: None,
                 archived_at: None,
+                organization: None,
             },
         )
         .expect("create secondary");
@@ -197,6 +205,7 @@ fn cli_merge_scan_same_name_creates_candidates() {
                 next_touchpoint_at: None,
                 cadence_days: None,
                 archived_at: None,
+                organization: None,
             },
         )
         .expect("create a");
@@ -213,6 +222,7 @@ fn cli_merge_scan_same_name_creates_candidates() {
                 next_touchpoint_at: None,
                 cadence_days: None,
                 archived_at: None,
+                organization: None,
             },
         )
         .expect("create b");
@@ -250,6 +260,172 @@ fn cli_merge_scan_same_name_creates_candidates() {
     );
 }
 
+#[test]
+fn cli_merge_scan_same_name_folds_diacritics() {
+    let dir = TempDir::new().expect("temp dir");
+    let db_path = dir.path().join("knotter.sqlite3");
+    for name in ["Zoë Müller", "Zoe  Muller", "Zoe Miller"] {
+        run_cmd(&db_path, &["add-contact", "--name", name]);
+    }
+
+    let report = run_cmd_json(&db_path, &["merge", "scan-same-name", "--dry-run"]);
+    assert_eq!(report["duplicate_groups"], 1);
+    let results = report["results"].as_array().expect("results");
+    assert_eq!(results[0]["normalized_name"], "zoe muller");
+    assert_eq!(results[0]["pairs"].as_array().expect("pairs").len(), 1);
+}
+
+#[test]
+fn cli_merge_import_replays_dismissals_from_another_database() {
+    let dir = TempDir::new().expect("temp dir");
+    let laptop = dir.path().join("laptop.sqlite3");
+    let desktop = dir.path().join("desktop.sqlite3");
+    for db_path in [&laptop, &desktop] {
+        for email in ["ada@example.com", "ada@work.example"] {
+            run_cmd(
+                db_path,
+                &["add-contact", "--name", "Ada Lovelace", "--email", email],
+            );
+        }
+        run_cmd(db_path, &["merge", "scan-same-name", "--yes"]);
+    }
+    // Only the laptop knows this pair, and it has no emails to match on.
+    run_cmd(&laptop, &["add-contact", "--name", "Grace"]);
+    run_cmd(&laptop, &["add-contact", "--name", "Grace"]);
+    run_cmd(&laptop, &["merge", "scan-same-name", "--yes"]);
+
+    let list = run_cmd_json(&laptop, &["merge", "list"]);
+    let ada_candidate = list
+        .as_array()
+        .expect("array")
+        .iter()
+        .find(|item| item["contact_a"]["display_name"] == "Ada Lovelace")
+        .expect("ada candidate");
+    let candidate_id = ada_candidate["id"].as_str().expect("id");
+    run_cmd(&laptop, &["merge", "dismiss", candidate_id]);
+
+    let plain = run_cmd_json(&laptop, &["export", "json"]);
+    assert!(plain.get("merge_candidates").is_none());
+
+    let export_path = dir.path().join("laptop.json");
+    run_cmd(
+        &laptop,
+        &[
+            "export",
+            "json",
+            "--include-merges",
+            "--out",
+            export_path.to_str().expect("path"),
+        ],
+    );
+    let export: Value =
+        serde_json::from_str(&std::fs::read_to_string(&export_path).expect("read export"))
+            .expect("parse export");
+    assert_eq!(
+        export["merge_candidates"].as_array().expect("array").len(),
+        2
+    );
+
+    let report = run_cmd_json(
+        &desktop,
+        &[
+            "merge",
+            "import",
+            export_path.to_str().expect("path"),
+            "--dry-run",
+        ],
+    );
+    assert_eq!(report["dismissed"], 1);
+    assert_eq!(report["warnings"].as_array().expect("warnings").len(), 1);
+    let open = run_cmd_json(&desktop, &["merge", "list", "--status", "open"]);
+    assert_eq!(open.as_array().expect("array").len(), 1);
+
+    let report = run_cmd_json(
+        &desktop,
+        &["merge", "import", export_path.to_str().expect("path")],
+    );
+    assert_eq!(report["dismissed"], 1);
+    assert_eq!(report["created"], 0);
+    let open = run_cmd_json(&desktop, &["merge", "list", "--status", "open"]);
+    assert!(open.as_array().expect("array").is_empty());
+
+    let report = run_cmd_json(
+        &desktop,
+        &["merge", "import", export_path.to_str().expect("path")],
+    );
+    assert_eq!(report["dismissed"], 0);
+    assert_eq!(report["unchanged"], 1);
+}
+
+#[test]
+fn cli_export_json_format_version_downgrades_and_import_rejects_newer() {
+    let dir = TempDir::new().expect("temp dir");
+    let db_path = dir.path().join("knotter.sqlite3");
+    let created = run_cmd_json(
+        &db_path,
+        &[
+            "add-contact",
+            "--name",
+            "Ada Lovelace",
+            "--email",
+            "ada@example.com",
+            "--org",
+            "Analytical Engines",
+            "--priority",
+            "7",
+        ],
+    );
+    let id = created["id"].as_str().expect("id");
+    run_cmd(&db_path, &["alias", "add", id, "Countess"]);
+
+    let current = run_cmd_json(&db_path, &["export", "json"]);
+    assert_eq!(current["metadata"]["format_version"], 5);
+    assert_eq!(current["contacts"][0]["priority"], 7);
+    assert_eq!(
+        current["contacts"][0]["aliases"],
+        serde_json::json!(["Countess"])
+    );
+    assert_eq!(current["contacts"][0]["organization"], "Analytical Engines");
+
+    let v4 = run_cmd_json(&db_path, &["export", "json", "--format-version", "4"]);
+    assert!(v4["contacts"][0].get("priority").is_none());
+
+    let v2 = run_cmd_json(&db_path, &["export", "json", "--format-version", "2"]);
+    assert_eq!(v2["metadata"]["format_version"], 2);
+    assert!(v2["contacts"][0].get("organization").is_none());
+
+    let v1 = run_cmd_json(&db_path, &["export", "json", "--format-version", "1"]);
+    assert_eq!(v1["metadata"]["format_version"], 1);
+    assert_eq!(v1["contacts"][0]["display_name"], "Ada Lovelace");
+    assert!(v1["contacts"][0].get("aliases").is_none());
+
+    for args in [
+        &["export", "json", "--format-version", "6"][..],
+        &[
+            "export",
+            "json",
+            "--format-version",
+            "1",
+            "--include-merges",
+        ][..],
+    ] {
+        let output = run_cmd_output(&db_path, args);
+        assert_eq!(output.status.code(), Some(3), "{args:?}");
+    }
+
+    let mut newer = run_cmd_json(&db_path, &["export", "json", "--include-merges"]);
+    newer["metadata"]["format_version"] = serde_json::json!(6);
+    let newer_path = dir.path().join("newer.json");
+    std::fs::write(&newer_path, newer.to_string()).expect("write export");
+    let output = run_cmd_output(
+        &db_path,
+        &["merge", "import", newer_path.to_str().expect("path")],
+    );
+    assert_eq!(output.status.code(), Some(3));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("produced by a newer knotter"), "{stderr}");
+}
+
 #[test]
 fn cli_merge_list_outputs_candidates() {
     let dir = TempDir::new().expect("temp dir");
@@ -271,6 +447,7 @@ fn cli_merge_list_outputs_candidates() {
                 next_touchpoint_at: None,
                 cadence_days: None,
                 archived_at: None,
+                organization: None,
             },
         )
         .expect("create contact a");
@@ -288,6 +465,7 @@ fn cli_merge_list_outputs_candidates() {
                 next_touchpoint_at: None,
                 cadence_days: None,
                 archived_at: None,
+                organization: None,
             },
         )
         .expect("create contact b");
@@ -332,6 +510,7 @@ fn cli_merge_apply_merges_candidate() {
                 next_touchpoint_at: None,
                 cadence_days: None,
                 archived_at: None,
+                organization: None,
             },
         )
         .expect("create primary");
@@ -349,6 +528,7 @@ fn cli_merge_apply_merges_candidate() {
                 next_touchpoint_at: None,
                 cadence_days: None,
                 archived_at: None,
+                organization: None,
             },
         )
         .expect("create secondary");
@@ -366,12 +546,37 @@ fn cli_merge_apply_merges_candidate() {
             },
         )
         .expect("create candidate");
+    let candidate_id = created.candidate.id.to_string();
 
-    let merged = run_cmd_json(
+    let shown = run_cmd_json(
         &db_path,
-        &["merge", "apply", &created.candidate.id.to_string()],
+        &[
+            "merge",
+            "show",
+            &candidate_id,
+            "--preview",
+            "--prefer",
+            "secondary",
+        ],
     );
+    assert_eq!(shown["status"], "open");
+    assert_eq!(shown["preview"]["contact"]["display_name"], "Ada L");
+    assert_eq!(shown["preview"]["new_aliases"][0], "Ada");
+
+    let dry_run = run_cmd_json(&db_path, &["merge", "apply", &candidate_id, "--dry-run"]);
+    assert_eq!(dry_run["dry_run"], true);
+    assert_eq!(dry_run["contact"]["display_name"], "Ada");
+    assert_eq!(dry_run["contact"]["email"], "ada@example.com");
+    assert_eq!(dry_run["emails"].as_array().map(Vec::len), Some(2));
+    assert!(store
+        .contacts()
+        .get(secondary.id)
+        .expect("get secondary")
+        .is_some());
+
+    let merged = run_cmd_json(&db_path, &["merge", "apply", &candidate_id]);
     assert_eq!(merged["id"], primary.id.to_string());
+    assert_eq!(merged["display_name"], dry_run["contact"]["display_name"]);
 
     let store = Store::open(&db_path).expect("open store");
     let candidate = store
@@ -390,6 +595,75 @@ fn cli_merge_apply_merges_candidate() {
         .is_none());
 }
 
+#[test]
+fn cli_merge_list_pages_and_prune_dismisses_stale_candidates() {
+    let dir = TempDir::new().expect("temp dir");
+    let db_path = dir.path().join("knotter.sqlite3");
+    let store = Store::open(&db_path).expect("open store");
+    store.migrate().expect("migrate");
+    let now = knotter_core::time::now_utc();
+
+    let ids: Vec<ContactId> = (0..4)
+        .map(|index| {
+            store
+                .contacts()
+                .create(
+                    now,
+                    knotter_store::repo::ContactNew {
+                        display_name: format!("Ada {index}"),
+                        email: None,
+                        phone: None,
+                        handle: None,
+                        timezone: None,
+                        next_touchpoint_at: None,
+                        cadence_days: None,
+                        archived_at: None,
+                        organization: None,
+                    },
+                )
+                .expect("create contact")
+                .id
+        })
+        .collect();
+    for (index, age_days) in [(1, 200), (2, 120), (3, 1)] {
+        store
+            .merge_candidates()
+            .create(
+                now - age_days * 86_400,
+                ids[0],
+                ids[index],
+                MergeCandidateCreate {
+                    reason: "name-duplicate".to_string(),
+                    source: Some("scan:same-name".to_string()),
+                    preferred_contact_id: None,
+                },
+            )
+            .expect("create candidate");
+    }
+
+    let page = run_cmd_json(&db_path, &["merge", "list", "--limit", "2"]);
+    assert_eq!(page.as_array().map(Vec::len), Some(2));
+    let stale = run_cmd_json(&db_path, &["merge", "list", "--older-than", "90d"]);
+    assert_eq!(stale.as_array().map(Vec::len), Some(2));
+
+    let report = run_cmd_json(
+        &db_path,
+        &["merge", "prune", "--older-than", "90d", "--dry-run"],
+    );
+    assert_eq!(report["pruned"], 2);
+    assert_eq!(report["dry_run"], true);
+    let open = run_cmd_json(&db_path, &["merge", "list", "--status", "open"]);
+    assert_eq!(open.as_array().map(Vec::len), Some(3));
+
+    let report = run_cmd_json(&db_path, &["merge", "prune", "--older-than", "90d"]);
+    assert_eq!(report["action"], "dismiss");
+    assert_eq!(report["pruned"], 2);
+    let open = run_cmd_json(&db_path, &["merge", "list", "--status", "open"]);
+    assert_eq!(open.as_array().map(Vec::len), Some(1));
+    let dismissed = run_cmd_json(&db_path, &["merge", "list", "--status", "dismissed"]);
+    assert_eq!(dismissed.as_array().map(Vec::len), Some(2));
+}
+
 #[test]
 fn cli_merge_apply_all_applies_safe_candidates_only() {
     let dir = TempDir::new().expect("temp dir");
@@ -411,6 +685,7 @@ fn cli_merge_apply_all_applies_safe_candidates_only() {
                 next_touchpoint_at: None,
                 cadence_days: None,
                 archived_at: None,
+                organization: None,
             },
         )
         .expect("create primary");
@@ -427,6 +702,7 @@ fn cli_merge_apply_all_applies_safe_candidates_only() {
                 next_touchpoint_at: None,
                 cadence_days: None,
                 archived_at: None,
+                organization: None,
             },
         )
         .expect("create secondary");
@@ -457,6 +733,7 @@ fn cli_merge_apply_all_applies_safe_candidates_only() {
                 next_touchpoint_at: None,
                 cadence_days: None,
                 archived_at: None,
+                organization: None,
             },
         )
         .expect("create other primary");
@@ -473,6 +750,7 @@ fn cli_merge_apply_all_applies_safe_candidates_only() {
                 next_touchpoint_at: None,
                 cadence_days: None,
                 archived_at: None,
+                organization: None,
             },
         )
         .expect("create other secondary");
@@ -531,6 +809,127 @@ fn cli_merge_apply_all_applies_safe_candidates_only() {
         .is_some());
 }
 
+#[test]
+fn cli_merge_apply_all_skips_modified_contacts_and_honors_max() {
+    let dir = TempDir::new().expect("temp dir");
+    let db_path = dir.path().join("knotter.sqlite3");
+    let store = Store::open(&db_path).expect("open store");
+    store.migrate().expect("migrate");
+    let now = 1_700_000_000;
+
+    let mut ids = Vec::new();
+    for (name, email) in [
+        ("Fresh Primary", "fresh@example.com"),
+        ("Fresh Secondary", "fresh-alt@example.com"),
+        ("Stale Primary", "stale@example.com"),
+        ("Stale Secondary", "stale-alt@example.com"),
+    ] {
+        let contact = store
+            .contacts()
+            .create(
+                now,
+                knotter_store::repo::ContactNew {
+                    display_name: name.to_string(),
+                    email: Some(email.to_string()),
+                    phone: None,
+                    handle: None,
+                    timezone: None,
+                    next_touchpoint_at: None,
+                    cadence_days: None,
+                    archived_at: None,
+                    organization: None,
+                },
+            )
+            .expect("create contact");
+        ids.push(contact.id);
+    }
+    for pair in ids.chunks(2) {
+        store
+            .merge_candidates()
+            .create(
+                now,
+                pair[0],
+                pair[1],
+                MergeCandidateCreate {
+                    reason: MergeCandidateReason::EmailDuplicate.as_str().to_string(),
+                    source: Some("gmail".to_string()),
+                    preferred_contact_id: Some(pair[0]),
+                },
+            )
+            .expect("create candidate");
+    }
+    store
+        .contacts()
+        .update(
+            now + 60,
+            ids[3],
+            ContactUpdate {
+                display_name: Some("Stale Secondary Renamed".to_string()),
+                ..Default::default()
+            },
+        )
+        .expect("update stale contact");
+    drop(store);
+
+    let output = run_cmd_output(&db_path, &["merge", "apply-all", "--max", "1"]);
+    assert_eq!(output.status.code(), Some(3));
+
+    let report = run_cmd_json(
+        &db_path,
+        &["merge", "apply-all", "--source", "gmail", "--max", "2"],
+    );
+    assert_eq!(report["selected"], 2);
+    assert_eq!(report["applied"], 1);
+    assert_eq!(report["skipped"], 1);
+    let results = report["results"].as_array().expect("results");
+    let skipped = results
+        .iter()
+        .find(|result| result["status"] == "skipped")
+        .expect("skipped result");
+    assert_eq!(skipped["primary_id"], Value::Null);
+    assert!(skipped["error"]
+        .as_str()
+        .expect("error")
+        .contains("modified"));
+
+    let report = run_cmd_json(&db_path, &["merge", "apply-all", "--yes", "--force"]);
+    assert_eq!(report["applied"], 1);
+    let store = Store::open(&db_path).expect("open store");
+    assert!(store.contacts().get(ids[3]).expect("get").is_none());
+}
+
+#[test]
+fn cli_merge_apply_all_applies_candidates_sharing_a_contact() {
+    let dir = TempDir::new().expect("temp dir");
+    let db_path = dir.path().join("knotter.sqlite3");
+    for _ in 0..3 {
+        run_cmd(&db_path, &["add-contact", "--name", "Ada"]);
+    }
+    run_cmd(&db_path, &["merge", "scan-same-name", "--yes"]);
+
+    // Merges stamp the primary a minute after the candidates were created.
+    let later = (chrono::Utc::now().timestamp() + 60).to_string();
+    let envs = [
+        ("KNOTTER_ALLOW_TEST_NOW_UTC", "1"),
+        ("KNOTTER_TEST_NOW_UTC", later.as_str()),
+    ];
+    let dry_run = run_cmd_json_with_env(
+        &db_path,
+        &["merge", "apply-all", "--include-unsafe", "--dry-run"],
+        &envs,
+    );
+    let report = run_cmd_json_with_env(
+        &db_path,
+        &["merge", "apply-all", "--include-unsafe", "--yes"],
+        &envs,
+    );
+    assert_eq!(report["selected"], dry_run["selected"]);
+    assert_eq!(report["applied"], 2, "{report}");
+    assert_eq!(report["skipped"], dry_run["skipped"]);
+    let contacts = run_cmd_json(&db_path, &["list"]);
+    assert_eq!(contacts.as_array().expect("list").len(), 1);
+}
+
 fn restrict_config_permissions(path: &Path) {
     #[cfg(unix)]
     {
@@ -555,6 +954,263 @@ fn cli_completions_bash_emits_output() {
     assert!(stdout.contains("knotter"));
 }
 
+#[test]
+fn cli_mangen_writes_a_page_per_subcommand() {
+    let temp = TempDir::new().expect("temp dir");
+    let out = temp.path().join("man");
+    let output = cargo_bin_cmd!("knotter")
+        .args(["--json", "mangen", "--out", out.to_str().expect("out path")])
+        .output()
+        .expect("run mangen");
+    assert!(output.status.success(), "command failed: {:?}", output);
+    let report: Value = serde_json::from_slice(&output.stdout).expect("parse json");
+    let pages: Vec<&str> = report["pages"]
+        .as_array()
+        .expect("pages array")
+        .iter()
+        .map(|page| page.as_str().expect("page name"))
+        .collect();
+
+    for page in [
+        "knotter.1",
+        "knotter-sync.1",
+        "knotter-import-email.1",
+        "knotter-merge-apply-all.1",
+        "knotter-loops-apply.1",
+    ] {
+        assert!(pages.contains(&page), "missing {page}");
+        let contents = std::fs::read_to_string(out.join(page)).expect("read page");
+        assert!(contents.contains(".TH "), "{page} has no title line");
+    }
+    assert_eq!(
+        std::fs::read_dir(&out).expect("read dir").count(),
+        pages.len()
+    );
+    for page in &pages {
+        let len = std::fs::metadata(out.join(page))
+            .expect("page metadata")
+            .len();
+        assert!(len > 0, "{page} is empty");
+    }
+    assert!(!pages
+        .iter()
+        .any(|page| page.contains("mangen") || page.contains("__complete")));
+
+    let sync = std::fs::read_to_string(out.join("knotter-sync.1")).expect("read sync page");
+    assert!(sync.contains("Examples:"));
+}
+
+#[test]
+fn cli_completions_call_dynamic_helper_for_ids_and_tags() {
+    for shell in ["bash", "zsh", "fish"] {
+        let output = cargo_bin_cmd!("knotter")
+            .args(["completions", shell])
+            .output()
+            .expect("run completions");
+        assert!(output.status.success(), "command failed: {:?}", output);
+        let stdout = String::from_utf8(output.stdout).expect("utf8");
+        assert!(
+            stdout.contains("knotter __complete"),
+            "{shell} script lacks dynamic helper"
+        );
+        assert!(stdout.contains("email-accounts"), "{shell}");
+        // The hidden helper itself is never offered as a subcommand.
+        assert!(!stdout.contains("dynamic-complete"), "{shell}");
+    }
+}
+
+#[test]
+fn cli_complete_helper_lists_contacts_tags_and_sources() {
+    let temp = TempDir::new().expect("temp dir");
+    let db_path = temp.path().join("knotter.sqlite3");
+
+    let ada = run_cmd_json(&db_path, &["add-contact", "--name", "Ada Lovelace"]);
+    let ada_id = ada["id"].as_str().expect("id");
+    run_cmd_json(&db_path, &["add-contact", "--name", "Grace Hopper"]);
+    run_cmd(&db_path, &["tag", "add", ada_id, "friend"]);
+    run_cmd(&db_path, &["tag", "add", ada_id, "family"]);
+
+    let contacts = run_cmd(&db_path, &["__complete", "contacts", "ad"]);
+    assert_eq!(contacts.trim_end(), format!("{ada_id}\tAda Lovelace"));
+    let by_id = run_cmd(&db_path, &["__complete", "contacts", &ada_id[..8]]);
+    assert!(by_id.starts_with(ada_id));
+    let all = run_cmd(&db_path, &["__complete", "contacts"]);
+    assert_eq!(all.lines().count(), 2);
+
+    let tags = run_cmd(&db_path, &["__complete", "tags", "FA"]);
+    assert_eq!(tags, "family\n");
+
+    let config_path = temp.path().join("config.toml");
+    std::fs::write(
+        &config_path,
+        "[[contacts.sources]]\nname = \"work\"\ntype = \"macos\"\n",
+    )
+    .expect("write config");
+    restrict_config_permissions(&config_path);
+    let sources = run_cmd_with_config(&db_path, &config_path, &["__complete", "sources", "w"]);
+    assert_eq!(sources, "work\n");
+
+    // A missing database prints nothing and is not created.
+    let missing = temp.path().join("missing.sqlite3");
+    let output = run_cmd_output(&missing, &["__complete", "contacts", "a"]);
+    assert!(output.status.success());
+    assert!(output.stdout.is_empty());
+    assert!(!missing.exists());
+}
+
+#[test]
+fn cli_config_init_writes_template_and_check_reports_all_errors() {
+    let config_home = TempDir::new().expect("temp config dir");
+    let config_path = config_home.path().join("knotter").join("config.toml");
+
+    let output = cargo_bin_cmd!("knotter")
+        .env("XDG_CONFIG_HOME", config_home.path())
+        .args(["config", "init"])
+        .output()
+        .expect("run config init");
+    assert!(output.status.success(), "command failed: {:?}", output);
+    assert!(config_path.exists());
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let mode = std::fs::metadata(&config_path)
+            .expect("config metadata")
+            .permissions()
+            .mode();
+        assert_eq!(mode & 0o777, 0o600);
+    }
+
+    let output = cargo_bin_cmd!("knotter")
+        .env("XDG_CONFIG_HOME", config_home.path())
+        .args(["config", "init"])
+        .output()
+        .expect("run config init again");
+    assert_eq!(output.status.code(), Some(3));
+
+    let output = cargo_bin_cmd!("knotter")
+        .env("XDG_CONFIG_HOME", config_home.path())
+        .args(["--json", "config", "check"])
+        .output()
+        .expect("run config check");
+    assert!(output.status.success(), "command failed: {:?}", output);
+    let report: Value = serde_json::from_slice(&output.stdout).expect("parse json");
+    assert_eq!(report["valid"], true);
+
+    std::fs::write(
+        &config_path,
+        "due_soon_days = -1\n\n[[contacts.email_accounts]]\nname = \"work\"\nhost = \"\"\nusername = \"me@example.com\"\npassword_env = \"KNOTTER_TEST_UNSET_PASSWORD\"\n",
+    )
+    .expect("write config");
+    let output = cargo_bin_cmd!("knotter")
+        .env("XDG_CONFIG_HOME", config_home.path())
+        .args(["--json", "config", "check"])
+        .output()
+        .expect("run config check");
+    assert_eq!(output.status.code(), Some(3));
+    let report: Value = serde_json::from_slice(&output.stdout).expect("parse json");
+    assert_eq!(report["valid"], false);
+    assert_eq!(
+        report["path"].as_str(),
+        Some(config_path.to_str().expect("config path"))
+    );
+    let errors = report["errors"].as_array().expect("errors array");
+    assert_eq!(errors.len(), 2, "{errors:?}");
+    assert!(errors[0].as_str().unwrap().contains("due_soon_days"));
+    assert!(errors[1].as_str().unwrap().contains("host"));
+}
+
+#[test]
+fn cli_init_writes_config_from_flags_and_migrates() {
+    let temp = TempDir::new().expect("temp dir");
+    let db_path = temp.path().join("data").join("knotter.sqlite3");
+    let config_path = temp.path().join("config").join("config.toml");
+    let init = |extra: &[&str]| {
+        cargo_bin_cmd!("knotter")
+            .env("XDG_CONFIG_HOME", temp.path())
+            .args([
+                "--db-path",
+                db_path.to_str().expect("db path"),
+                "--config",
+                config_path.to_str().expect("config path"),
+                "--json",
+                "init",
+            ])
+            .args(extra)
+            .output()
+            .expect("run init")
+    };
+
+    let output = init(&[
+        "--due-soon-days",
+        "10",
+        "--default-cadence-days",
+        "30",
+        "--email-smtp-host",
+        "smtp.example.com",
+        "--email-from",
+        "Knotter <knotter@example.com>",
+        "--email-to",
+        "you@example.com",
+        "--email-username",
+        "you@example.com",
+        "--email-password-env",
+        "MY_SMTP_PASSWORD",
+        "--carddav-url",
+        "https://example.test/carddav/",
+        "--carddav-username",
+        "you@example.com",
+        "--source-name",
+        "gmail",
+    ]);
+    assert!(output.status.success(), "command failed: {:?}", output);
+    let report: Value = serde_json::from_slice(&output.stdout).expect("parse json");
+    assert_eq!(report["due_soon_days"], 10);
+    assert_eq!(report["default_cadence_days"], 30);
+    assert_eq!(report["email_notifications"], true);
+    assert_eq!(report["contact_source"], "gmail");
+    assert!(db_path.exists());
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let mode = std::fs::metadata(&config_path)
+            .expect("config metadata")
+            .permissions()
+            .mode();
+        assert_eq!(mode & 0o777, 0o600);
+    }
+    let contents = std::fs::read_to_string(&config_path).expect("read config");
+    assert!(contents.contains("password_env = \"MY_SMTP_PASSWORD\""));
+    assert!(contents.contains("type = \"carddav\""));
+    let check = run_cmd_json_with_config(&db_path, &config_path, &["list"]);
+    assert_eq!(check, serde_json::json!([]));
+
+    let output = init(&["--no-notifications", "--no-source"]);
+    assert_eq!(output.status.code(), Some(3));
+    assert_eq!(
+        std::fs::read_to_string(&config_path).expect("read config"),
+        contents
+    );
+
+    let output = init(&[
+        "--force",
+        "--no-default-cadence",
+        "--email-smtp-host",
+        "smtp.example.com",
+    ]);
+    assert_eq!(output.status.code(), Some(3));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("--email-from is required"));
+
+    let output = init(&["--force"]);
+    assert!(output.status.success(), "command failed: {:?}", output);
+    let report: Value = serde_json::from_slice(&output.stdout).expect("parse json");
+    assert_eq!(report["due_soon_days"], 7);
+    assert!(report["default_cadence_days"].is_null());
+    assert_eq!(report["email_notifications"], false);
+    assert!(report["contact_source"].is_null());
+    let contents = std::fs::read_to_string(&config_path).expect("read config");
+    assert!(!contents.contains("[notifications]"));
+}
+
 #[test]
 fn cli_import_vcf_dry_run_skips_writes() {
     let temp = TempDir::new().expect("temp dir");
@@ -586,28 +1242,360 @@ fn cli_import_vcf_dry_run_skips_writes() {
 }
 
 #[test]
-#[cfg(not(feature = "dav-sync"))]
-fn cli_import_source_requires_dav_sync() {
+fn cli_import_google_csv_goes_through_the_contact_import_pipeline() {
     let temp = TempDir::new().expect("temp dir");
     let db_path = temp.path().join("knotter.sqlite3");
-    let config_path = temp.path().join("config.toml");
+    let vcf_path = temp.path().join("hans.vcf");
+    let csv_arg = concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/../knotter-sync/tests/fixtures/google-takeout.csv"
+    );
 
     std::fs::write(
-        &config_path,
-        r#"
-[contacts]
-[[contacts.sources]]
-name = "gmail"
-type = "carddav"
-url = "https://example.test/carddav/"
-username = "user@example.com"
-password_env = "KNOTTER_GMAIL_PASSWORD"
-"#,
+        &vcf_path,
+        "BEGIN:VCARD\nVERSION:3.0\nFN:Hans Müller\nEMAIL:hans.mueller@example.de\nEND:VCARD\n",
     )
-    .expect("write config");
-    restrict_config_permissions(&config_path);
-
-    let output = cargo_bin_cmd!("knotter")
+    .expect("write vcf");
+    run_cmd(
+        &db_path,
+        &["import", "vcf", vcf_path.to_str().expect("vcf path")],
+    );
+
+    let report = run_cmd_json(&db_path, &["import", "google-csv", "--dry-run", csv_arg]);
+    assert_eq!(report["created"], 2);
+    assert_eq!(report["updated"], 1);
+    assert_eq!(report["dry_run"], true);
+    assert_eq!(
+        run_cmd_json(&db_path, &["list"]).as_array().map(Vec::len),
+        Some(1)
+    );
+
+    let report = run_cmd_json(
+        &db_path,
+        &["import", "google-csv", "--groups-as-tags", csv_arg],
+    );
+    assert_eq!(report["created"], 2);
+    assert_eq!(report["updated"], 1);
+    assert_eq!(report["skipped"], 1);
+    assert_eq!(report["group_tags_attached"], 3);
+    assert_eq!(report["warnings"][0], "row 5: missing name; skipping");
+
+    let hans = run_cmd_json(&db_path, &["list", "--filter", "#freunde"]);
+    let hans_id = hans[0]["id"].as_str().expect("hans id").to_string();
+    let shown = run_cmd_json(&db_path, &["show", &hans_id]);
+    assert_eq!(shown["display_name"], "Hans Müller");
+    let emails: Vec<&str> = shown["emails"]
+        .as_array()
+        .expect("emails")
+        .iter()
+        .filter_map(Value::as_str)
+        .collect();
+    assert!(emails.contains(&"hans@example.org"), "{emails:?}");
+    assert!(emails.contains(&"hans.mueller@example.de"), "{emails:?}");
+    assert_eq!(shown["dates"][0]["kind"], "birthday");
+    assert_eq!(
+        run_cmd_json(&db_path, &["list", "--filter", "#mycontacts"])
+            .as_array()
+            .map(Vec::len),
+        Some(0)
+    );
+
+    // Rows carry no ids, so Grace (phone only) needs name + phone matching.
+    let report = run_cmd_json(
+        &db_path,
+        &["import", "google-csv", "--match-phone-name", csv_arg],
+    );
+    assert_eq!(report["created"], 0);
+    assert_eq!(report["unchanged"], 3);
+
+    let output = run_cmd_output(&db_path, &["import", "google-csv", "missing.csv"]);
+    assert_eq!(output.status.code(), Some(2));
+    let output = run_cmd_output(
+        &db_path,
+        &["import", "google-csv", vcf_path.to_str().expect("vcf path")],
+    );
+    assert_eq!(output.status.code(), Some(3));
+}
+
+#[test]
+fn cli_export_and_import_keep_warnings_and_logs_off_stdout() {
+    let temp = TempDir::new().expect("temp dir");
+    let db_path = temp.path().join("knotter.sqlite3");
+    let vcf_path = temp.path().join("contacts.vcf");
+    std::fs::write(
+        &vcf_path,
+        "BEGIN:VCARD\nVERSION:3.0\nFN:Ada Lovelace\nEND:VCARD\nBEGIN:VCARD\nVERSION:3.0\nEMAIL:x@example.com\nEND:VCARD\n",
+    )
+    .expect("write vcf");
+    let vcf_arg = vcf_path.to_str().expect("vcf path");
+
+    // Import warnings go to stderr; the report stays on stdout.
+    let output = run_cmd_output(&db_path, &["import", "vcf", "--dry-run", vcf_arg]);
+    assert!(output.status.success(), "{output:?}");
+    let stdout = String::from_utf8(output.stdout).expect("utf8");
+    let stderr = String::from_utf8(output.stderr).expect("utf8");
+    assert!(
+        stdout.starts_with("Imported vcard contacts (dry run)"),
+        "{stdout}"
+    );
+    assert!(!stdout.contains("missing FN"), "{stdout}");
+    assert!(
+        stderr.contains("warning: vcard: missing FN; skipping vCard"),
+        "{stderr}"
+    );
+    run_cmd(&db_path, &["import", "vcf", vcf_arg]);
+
+    // Without --out the data owns stdout, even with debug logging on.
+    for format in ["ics", "vcf", "json"] {
+        let output = run_cmd_output(&db_path, &["--verbose", "export", format]);
+        assert!(output.status.success(), "{output:?}");
+        let stdout = String::from_utf8(output.stdout).expect("utf8");
+        let stderr = String::from_utf8(output.stderr).expect("utf8");
+        let expected_start = match format {
+            "ics" => "BEGIN:VCALENDAR",
+            "vcf" => "BEGIN:VCARD",
+            _ => "{",
+        };
+        assert!(stdout.starts_with(expected_start), "{format}: {stdout}");
+        assert!(!stdout.contains("DEBUG"), "{format}: {stdout}");
+        assert!(
+            stderr.contains("database path resolved"),
+            "{format}: {stderr}"
+        );
+    }
+
+    // With --out the report may use stdout.
+    let out_path = temp.path().join("contacts.ics");
+    let output = run_cmd_output(
+        &db_path,
+        &[
+            "export",
+            "ics",
+            "--out",
+            out_path.to_str().expect("out path"),
+        ],
+    );
+    assert!(output.status.success(), "{output:?}");
+    let stdout = String::from_utf8(output.stdout).expect("utf8");
+    assert!(stdout.starts_with("Exported "), "{stdout}");
+    let data = std::fs::read_to_string(&out_path).expect("read export");
+    assert!(data.starts_with("BEGIN:VCALENDAR"));
+}
+
+#[test]
+fn cli_sync_state_lists_and_resets_email_and_telegram_positions() {
+    let dir = TempDir::new().expect("temp dir");
+    let db_path = dir.path().join("knotter.sqlite3");
+    let store = Store::open(&db_path).expect("open store");
+    store.migrate().expect("migrate");
+    let now = 1_700_000_000;
+    let contact = store
+        .contacts()
+        .create(
+            now,
+            knotter_store::repo::ContactNew {
+                display_name: "Ada".to_string(),
+                email: Some("ada@example.com".to_string()),
+                phone: None,
+                handle: None,
+                timezone: None,
+                next_touchpoint_at: None,
+                cadence_days: None,
+                archived_at: None,
+                organization: None,
+            },
+        )
+        .expect("create contact");
+    let email_sync = store.email_sync();
+    for mailbox in ["INBOX", "Sent"] {
+        email_sync
+            .upsert_state(&knotter_store::repo::EmailSyncState {
+                account: "work".to_string(),
+                mailbox: mailbox.to_string(),
+                uidvalidity: Some(7),
+                last_uid: 300,
+                last_seen_at: Some(now),
+            })
+            .expect("upsert email state");
+    }
+    email_sync
+        .record_message(&knotter_store::repo::EmailMessageRecord {
+            account: "work".to_string(),
+            mailbox: "INBOX".to_string(),
+            uidvalidity: 7,
+            uid: 12,
+            message_id: None,
+            contact_id: contact.id,
+            occurred_at: now,
+            direction: "inbound".to_string(),
+            subject: None,
+            created_at: now,
+        })
+        .expect("record message");
+    store
+        .telegram_sync()
+        .upsert_state(&knotter_store::repo::TelegramSyncState {
+            account: "primary".to_string(),
+            peer_id: 42,
+            last_message_id: 900,
+            last_seen_at: Some(now),
+        })
+        .expect("upsert telegram state");
+    drop(store);
+
+    let states = run_cmd_json(&db_path, &["sync", "email-state", "ls"]);
+    let states = states.as_array().expect("array");
+    assert_eq!(states.len(), 2);
+    assert_eq!(states[0]["mailbox"], "INBOX");
+    assert_eq!(states[0]["uidvalidity"], 7);
+    assert_eq!(states[0]["last_uid"], 300);
+
+    let unconfirmed = run_cmd_output(&db_path, &["sync", "email-state", "reset", "work"]);
+    assert_eq!(unconfirmed.status.code(), Some(3));
+    let stderr = String::from_utf8_lossy(&unconfirmed.stderr);
+    assert!(stderr.contains("without a Message-ID"), "stderr: {stderr}");
+    let states = run_cmd_json(&db_path, &["sync", "email-state", "ls"]);
+    assert_eq!(states[1]["last_uid"], 300);
+
+    let report = run_cmd_json(
+        &db_path,
+        &["sync", "email-state", "reset", "work", "Sent", "--yes"],
+    );
+    assert_eq!(report["mailboxes"], serde_json::json!(["Sent"]));
+    assert_eq!(report["missing_message_id"], serde_json::json!([]));
+    let report = run_cmd_json(&db_path, &["sync", "email-state", "reset", "work", "--yes"]);
+    assert_eq!(report["mailboxes"], serde_json::json!(["INBOX", "Sent"]));
+    assert_eq!(report["missing_message_id"], serde_json::json!(["INBOX"]));
+    let states = run_cmd_json(&db_path, &["sync", "email-state", "ls"]);
+    for state in states.as_array().expect("array") {
+        assert_eq!(state["last_uid"], 0);
+        assert!(state["uidvalidity"].is_null());
+    }
+
+    let telegram = run_cmd_json(&db_path, &["sync", "telegram-state", "ls"]);
+    assert_eq!(telegram[0]["peer_id"], 42);
+    assert_eq!(telegram[0]["last_message_id"], 900);
+    let report = run_cmd_json(
+        &db_path,
+        &["sync", "telegram-state", "reset", "primary", "42", "--yes"],
+    );
+    assert_eq!(report["peers"], serde_json::json!([42]));
+    let telegram = run_cmd_json(&db_path, &["sync", "telegram-state", "ls"]);
+    assert_eq!(telegram[0]["last_message_id"], 0);
+
+    let missing = run_cmd_output(
+        &db_path,
+        &["sync", "telegram-state", "reset", "other", "--yes"],
+    );
+    assert_eq!(missing.status.code(), Some(2));
+}
+
+#[test]
+fn cli_import_interactions_resolves_rows_and_is_idempotent() {
+    let temp = TempDir::new().expect("temp dir");
+    let db_path = temp.path().join("knotter.sqlite3");
+    let csv_path = temp.path().join("interactions.csv");
+    let ada = run_cmd_json(
+        &db_path,
+        &[
+            "add-contact",
+            "--name",
+            "Ada Lovelace",
+            "--email",
+            "ada@example.com",
+        ],
+    );
+    let ada_id = ada["id"].as_str().expect("id").to_string();
+    let grace = run_cmd_json(&db_path, &["add-contact", "--name", "Grace Hopper"]);
+    let grace_id = grace["id"].as_str().expect("id").to_string();
+    run_cmd(&db_path, &["add-contact", "--name", "Sam"]);
+    run_cmd(&db_path, &["add-contact", "--name", "Sam"]);
+
+    std::fs::write(
+        &csv_path,
+        format!(
+            "contact,date,kind,note,follow_up\n\
+             ada@example.com,2024-01-05,call,\"Caught up, long call\",\n\
+             grace hopper,2024-02-10 18:30,other:dinner,Dinner,2024-03-01\n\
+             {grace_id},2024-02-11,text,Thanks,\n\
+             Sam,2024-01-01,call,Hi,\n\
+             Nobody,2024-01-01,call,Hi,\n\
+             ada@example.com,2999-01-01,call,Later,\n\
+             ada@example.com,not-a-date,call,Oops,\n"
+        ),
+    )
+    .expect("write csv");
+    let csv_arg = csv_path.to_str().expect("csv path");
+
+    let dry_run = run_cmd_json(&db_path, &["import", "interactions", "--dry-run", csv_arg]);
+    assert_eq!(dry_run["dry_run"], true);
+    assert_eq!(dry_run["rows"], 7);
+    assert_eq!(dry_run["created"], 3);
+    assert_eq!(dry_run["skipped_ambiguous"], 1);
+    assert_eq!(dry_run["skipped_unresolved"], 1);
+    assert_eq!(dry_run["rejected"], 2);
+    let warnings = dry_run["warnings"].as_array().expect("warnings");
+    assert_eq!(warnings.len(), 4);
+    assert!(warnings
+        .iter()
+        .any(|warning| warning.as_str().unwrap().contains("--allow-future")));
+    let shown = run_cmd_json(&db_path, &["show", &ada_id]);
+    assert!(shown["recent_interactions"]
+        .as_array()
+        .expect("array")
+        .is_empty());
+
+    let first = run_cmd_json(&db_path, &["import", "interactions", csv_arg]);
+    assert_eq!(first["dry_run"], false);
+    assert_eq!(first["created"], 3);
+    let shown = run_cmd_json(&db_path, &["show", &grace_id]);
+    let interactions = shown["recent_interactions"].as_array().expect("array");
+    assert_eq!(interactions.len(), 2);
+    assert!(interactions
+        .iter()
+        .any(|item| item["kind"] == "other:dinner" && !item["follow_up_at"].is_null()));
+    let shown = run_cmd_json(&db_path, &["show", &ada_id]);
+    assert_eq!(
+        shown["recent_interactions"][0]["note"],
+        "Caught up, long call"
+    );
+
+    let second = run_cmd_json(
+        &db_path,
+        &["import", "interactions", "--allow-future", csv_arg],
+    );
+    assert_eq!(second["created"], 1);
+    assert_eq!(second["skipped_duplicates"], 3);
+    assert_eq!(second["rejected"], 1);
+
+    std::fs::write(&csv_path, "who,date,kind,note\n").expect("write csv");
+    let output = run_cmd_output(&db_path, &["import", "interactions", csv_arg]);
+    assert_eq!(output.status.code(), Some(3));
+}
+
+#[test]
+#[cfg(not(feature = "dav-sync"))]
+fn cli_import_source_requires_dav_sync() {
+    let temp = TempDir::new().expect("temp dir");
+    let db_path = temp.path().join("knotter.sqlite3");
+    let config_path = temp.path().join("config.toml");
+
+    std::fs::write(
+        &config_path,
+        r#"
+[contacts]
+[[contacts.sources]]
+name = "gmail"
+type = "carddav"
+url = "https://example.test/carddav/"
+username = "user@example.com"
+password_env = "KNOTTER_GMAIL_PASSWORD"
+"#,
+    )
+    .expect("write config");
+    restrict_config_permissions(&config_path);
+
+    let output = cargo_bin_cmd!("knotter")
         .args([
             "--db-path",
             db_path.to_str().expect("db path"),
@@ -629,619 +1617,2641 @@ password_env = "KNOTTER_GMAIL_PASSWORD"
 }
 
 #[test]
-fn cli_add_list_tag_schedule_flow() {
+fn cli_edit_contact_batch_validates_all_lines_then_applies() {
     let temp = TempDir::new().expect("temp dir");
     let db_path = temp.path().join("knotter.sqlite3");
 
-    run_cmd(&db_path, &["add-contact", "--name", "Ada Lovelace"]);
-
-    let list = run_cmd_json(&db_path, &["list"]);
-    let items = list.as_array().expect("array");
-    assert_eq!(items.len(), 1);
-    assert_eq!(items[0]["display_name"], "Ada Lovelace");
-    let id = items[0]["id"].as_str().expect("id").to_string();
+    let ada = run_cmd_json(&db_path, &["add-contact", "--name", "ada", "--phone", "1"]);
+    let grace = run_cmd_json(&db_path, &["add-contact", "--name", "Grace Hopper"]);
+    let ada_id = ada["id"].as_str().expect("id").to_string();
+    let grace_id = grace["id"].as_str().expect("id").to_string();
 
-    run_cmd(&db_path, &["tag", "add", &id, "friend"]);
+    let batch_path = temp.path().join("edits.jsonl");
+    std::fs::write(
+        &batch_path,
+        format!(
+            "{{\"id\": \"{ada_id}\", \"name\": \"Ada Lovelace\", \"phone\": null, \"add_tags\": [\"friends\"]}}\n\n{{\"id\": \"{grace_id}\", \"timezone\": \"America/New_York\"}}\n{{\"id\": \"{grace_id}\", \"nickname\": \"amazing\"}}\n"
+        ),
+    )
+    .expect("write batch");
 
-    let filtered = run_cmd_json(&db_path, &["list", "--filter", "#friend"]);
-    let filtered_items = filtered.as_array().expect("array");
-    assert_eq!(filtered_items.len(), 1);
+    let output = run_cmd_output(
+        &db_path,
+        &[
+            "--json",
+            "edit-contact",
+            "--batch",
+            batch_path.to_str().expect("batch path"),
+        ],
+    );
+    assert_eq!(output.status.code(), Some(3));
+    let report: Value = serde_json::from_slice(&output.stdout).expect("parse json");
+    let lines = report.as_array().expect("array");
+    assert_eq!(lines.len(), 3);
+    assert_eq!(lines[0]["status"], "skipped");
+    assert_eq!(lines[2]["line"], 4);
+    assert_eq!(lines[2]["status"], "failed");
+    let shown = run_cmd_json(&db_path, &["show", &ada_id]);
+    assert_eq!(shown["display_name"], "ada");
 
-    run_cmd(&db_path, &["schedule", &id, "--at", "2030-01-01"]);
+    std::fs::write(
+        &batch_path,
+        format!(
+            "{{\"id\": \"{ada_id}\", \"name\": \"Ada Lovelace\", \"phone\": null, \"add_tags\": [\"friends\"]}}\n{{\"id\": \"{grace_id}\", \"timezone\": \"America/New_York\"}}\n"
+        ),
+    )
+    .expect("write batch");
+    let dry_run = run_cmd_json(
+        &db_path,
+        &[
+            "edit-contact",
+            "--batch",
+            batch_path.to_str().expect("batch path"),
+            "--dry-run",
+        ],
+    );
+    let lines = dry_run.as_array().expect("array");
+    assert_eq!(lines[0]["status"], "would_update");
+    let changes = lines[0]["changes"].as_array().expect("changes");
+    let fields: Vec<&str> = changes
+        .iter()
+        .map(|change| change["field"].as_str().expect("field"))
+        .collect();
+    assert_eq!(fields, vec!["name", "phone", "tags"]);
+    assert_eq!(changes[0]["old"], "ada");
+    assert_eq!(changes[0]["new"], "Ada Lovelace");
+    assert_eq!(changes[1]["new"], Value::Null);
+    let shown = run_cmd_json(&db_path, &["show", &ada_id]);
+    assert_eq!(shown["display_name"], "ada");
 
-    let detail = run_cmd_json(&db_path, &["show", &id]);
-    assert!(detail["next_touchpoint_at"].is_number());
+    let config_dir = TempDir::new().expect("temp config dir");
+    let output = cargo_bin_cmd!("knotter")
+        .env("XDG_CONFIG_HOME", config_dir.path())
+        .args([
+            "--db-path",
+            db_path.to_str().expect("db path"),
+            "--json",
+            "edit-contact",
+            "--batch",
+            "-",
+        ])
+        .write_stdin(std::fs::read(&batch_path).expect("read batch"))
+        .output()
+        .expect("run batch edit");
+    assert!(output.status.success(), "command failed: {:?}", output);
+    let report: Value = serde_json::from_slice(&output.stdout).expect("parse json");
+    assert_eq!(report[0]["status"], "updated");
+    assert_eq!(report[1]["status"], "updated");
+
+    let shown = run_cmd_json(&db_path, &["show", &ada_id]);
+    assert_eq!(shown["display_name"], "Ada Lovelace");
+    assert_eq!(shown["phone"], Value::Null);
+    assert_eq!(shown["tags"][0], "friends");
+    let shown = run_cmd_json(&db_path, &["show", &grace_id]);
+    assert_eq!(shown["timezone"], "America/New_York");
 }
 
 #[test]
-fn cli_schedule_rejects_past_date() {
+fn cli_add_contact_from_tsv_validates_rows_then_adds_in_one_go() {
     let temp = TempDir::new().expect("temp dir");
     let db_path = temp.path().join("knotter.sqlite3");
+    let owner = run_cmd_json(
+        &db_path,
+        &[
+            "add-contact",
+            "--name",
+            "Owner",
+            "--email",
+            "taken@example.com",
+        ],
+    );
 
-    run_cmd(&db_path, &["add-contact", "--name", "Ada Lovelace"]);
-    let list = run_cmd_json(&db_path, &["list"]);
-    let items = list.as_array().expect("array");
-    let id = items[0]["id"].as_str().expect("id").to_string();
+    let table_path = temp.path().join("people.tsv");
+    std::fs::write(
+        &table_path,
+        "name\temail\tphone\tcadence_days\ttags\tnotes\n\
+         Ann\tann@example.com\t+1 555 0100\t14\tfriends, work\tignored\n\
+         Bob\ttaken@example.com\t\t\t\t\n\
+         \n\
+         Cat\tcat@example.com\t\tsoon\t\t\n",
+    )
+    .expect("write table");
+    let table = table_path.to_str().expect("table path");
 
-    let output = run_cmd_output(&db_path, &["schedule", &id, "--at", "2000-01-01"]);
+    let output = run_cmd_output(&db_path, &["--json", "add-contact", "--from-tsv", table]);
     assert_eq!(output.status.code(), Some(3));
-    let stderr = String::from_utf8_lossy(&output.stderr);
-    assert!(stderr.contains("timestamp must be now or later"));
+    let report: Value = serde_json::from_slice(&output.stdout).expect("parse json");
+    let rows = report.as_array().expect("array");
+    assert_eq!(rows.len(), 3);
+    assert_eq!(rows[0]["status"], "skipped");
+    assert_eq!(rows[1]["status"], "failed");
+    assert!(rows[1]["error"]
+        .as_str()
+        .expect("error")
+        .contains(owner["id"].as_str().expect("id")));
+    assert_eq!(rows[2]["line"], 5);
+    assert_eq!(rows[2]["status"], "failed");
+    let listed = run_cmd_json(&db_path, &["list"]);
+    assert_eq!(listed.as_array().expect("array").len(), 1);
+
+    std::fs::write(
+        &table_path,
+        "name,email,tags\nAnn,ann@example.com,friends;work\nBob,taken@example.com,\nAnnie,ann@example.com,\n",
+    )
+    .expect("write table");
+    let dry_run = run_cmd_json(
+        &db_path,
+        &[
+            "add-contact",
+            "--from-tsv",
+            table,
+            "--on-duplicate",
+            "stage",
+            "--dry-run",
+        ],
+    );
+    let statuses: Vec<&str> = dry_run
+        .as_array()
+        .expect("array")
+        .iter()
+        .map(|row| row["status"].as_str().expect("status"))
+        .collect();
+    assert_eq!(statuses, ["would_create", "would_stage", "would_stage"]);
+    let listed = run_cmd_json(&db_path, &["list"]);
+    assert_eq!(listed.as_array().expect("array").len(), 1);
+
+    let skipped = run_cmd_json(
+        &db_path,
+        &["add-contact", "--from-tsv", table, "--on-duplicate", "skip"],
+    );
+    let statuses: Vec<&str> = skipped
+        .as_array()
+        .expect("array")
+        .iter()
+        .map(|row| row["status"].as_str().expect("status"))
+        .collect();
+    assert_eq!(statuses, ["created", "duplicate", "duplicate"]);
+    let ann = run_cmd_json(&db_path, &["show", "ann@example.com"]);
+    assert_eq!(ann["tags"], serde_json::json!(["friends", "work"]));
+
+    let config_dir = TempDir::new().expect("temp config dir");
+    let output = cargo_bin_cmd!("knotter")
+        .env("XDG_CONFIG_HOME", config_dir.path())
+        .args([
+            "--db-path",
+            db_path.to_str().expect("db path"),
+            "--json",
+            "add-contact",
+            "--from-tsv",
+            "-",
+            "--on-duplicate",
+            "stage",
+        ])
+        .write_stdin("name\temail\nBobby\ttaken@example.com\n")
+        .output()
+        .expect("run bulk add");
+    assert!(output.status.success(), "command failed: {:?}", output);
+    let report: Value = serde_json::from_slice(&output.stdout).expect("parse json");
+    assert_eq!(report[0]["status"], "staged");
+    assert_eq!(
+        report[0]["duplicate_emails"],
+        serde_json::json!(["taken@example.com"])
+    );
+    let candidates = run_cmd_json(&db_path, &["merge", "list"]);
+    assert_eq!(candidates.as_array().expect("array").len(), 1);
+    let bobby = run_cmd_json(&db_path, &["show", "Bobby"]);
+    assert_eq!(bobby["emails"], serde_json::json!([]));
 }
 
 #[test]
-fn cli_add_contact_rejects_past_next_touchpoint() {
+fn cli_email_subcommands_manage_addresses_and_primary() {
     let temp = TempDir::new().expect("temp dir");
     let db_path = temp.path().join("knotter.sqlite3");
 
-    let output = run_cmd_output(
+    let ada = run_cmd_json(
+        &db_path,
+        &["add-contact", "--name", "Ada", "--email", "ada@example.com"],
+    );
+    let ada_id = ada["id"].as_str().expect("id").to_string();
+    let grace = run_cmd_json(
         &db_path,
         &[
             "add-contact",
             "--name",
-            "Ada Lovelace",
-            "--next-touchpoint-at",
-            "2000-01-01",
+            "Grace",
+            "--email",
+            "grace@example.com",
+        ],
+    );
+    let grace_id = grace["id"].as_str().expect("id").to_string();
+
+    let added = run_cmd_json(&db_path, &["email", "add", &ada_id, " ADA@work.test "]);
+    assert_eq!(added["primary"], "ada@example.com");
+    assert_eq!(added["emails"].as_array().expect("emails").len(), 2);
+
+    let primary = run_cmd_json(
+        &db_path,
+        &["email", "set-primary", &ada_id, "ada@work.test"],
+    );
+    assert_eq!(primary["primary"], "ada@work.test");
+    let shown = run_cmd_json(&db_path, &["show", &ada_id]);
+    assert_eq!(shown["email"], "ada@work.test");
+
+    let removed = run_cmd_json(&db_path, &["email", "rm", &ada_id, "ada@work.test"]);
+    assert_eq!(removed["primary"], "ada@example.com");
+    let shown = run_cmd_json(&db_path, &["show", &ada_id]);
+    assert_eq!(shown["email"], "ada@example.com");
+
+    let output = run_cmd_output(&db_path, &["email", "add", &ada_id, "grace@example.com"]);
+    assert_eq!(output.status.code(), Some(3));
+    let stderr = String::from_utf8(output.stderr).expect("utf8");
+    assert!(stderr.contains("Grace"), "{stderr}");
+
+    let staged = run_cmd_json(
+        &db_path,
+        &[
+            "email",
+            "add",
+            &ada_id,
+            "grace@example.com",
+            "--stage-merge",
+        ],
+    );
+    assert_eq!(staged["owner_id"], grace_id.as_str());
+    assert_eq!(staged["created"], true);
+    let merges = run_cmd_json(&db_path, &["merge", "list"]);
+    assert_eq!(merges.as_array().expect("merges").len(), 1);
+
+    let listed = run_cmd_json(&db_path, &["email", "ls", &ada_id]);
+    assert_eq!(listed["emails"].as_array().expect("emails").len(), 1);
+
+    let output = run_cmd_output(&db_path, &["email", "set-primary", &ada_id, "nope@x.test"]);
+    assert_eq!(output.status.code(), Some(2));
+}
+
+#[test]
+fn cli_alias_subcommands_feed_show_filters_and_export() {
+    let temp = TempDir::new().expect("temp dir");
+    let db_path = temp.path().join("knotter.sqlite3");
+
+    let robert = run_cmd_json(&db_path, &["add-contact", "--name", "Robert Smith"]);
+    let robert_id = robert["id"].as_str().expect("id").to_string();
+    run_cmd(&db_path, &["add-contact", "--name", "Ada"]);
+
+    let added = run_cmd_json(&db_path, &["alias", "add", &robert_id, "  Bob "]);
+    assert_eq!(added["aliases"], serde_json::json!(["Bob"]));
+    run_cmd(&db_path, &["alias", "add", &robert_id, "Rob"]);
+    let listed = run_cmd_json(&db_path, &["alias", "ls", &robert_id]);
+    assert_eq!(listed["aliases"], serde_json::json!(["Bob", "Rob"]));
+
+    let shown = run_cmd_json(&db_path, &["show", &robert_id]);
+    assert_eq!(shown["aliases"], serde_json::json!(["Bob", "Rob"]));
+    let text = run_cmd(&db_path, &["show", &robert_id]);
+    assert!(text.contains("aliases: Bob, Rob"), "{text}");
+
+    let filtered = run_cmd_json(&db_path, &["list", "--filter", "bob"]);
+    let items = filtered.as_array().expect("array");
+    assert_eq!(items.len(), 1);
+    assert_eq!(items[0]["id"], robert_id.as_str());
+
+    let export = run_cmd_json(&db_path, &["export", "json"]);
+    let contacts = export["contacts"].as_array().expect("contacts");
+    let exported = contacts
+        .iter()
+        .find(|contact| contact["id"] == robert_id.as_str())
+        .expect("exported");
+    assert_eq!(exported["aliases"], serde_json::json!(["Bob", "Rob"]));
+    let vcf = run_cmd(&db_path, &["export", "vcf"]);
+    assert!(vcf.contains("NICKNAME:Bob,Rob"), "{vcf}");
+
+    run_cmd(&db_path, &["alias", "rm", &robert_id, "rob"]);
+    let output = run_cmd_output(&db_path, &["alias", "rm", &robert_id, "rob"]);
+    assert_eq!(output.status.code(), Some(2));
+    let output = run_cmd_output(&db_path, &["alias", "add", &robert_id, "   "]);
+    assert_eq!(output.status.code(), Some(3));
+}
+
+#[test]
+fn cli_met_fields_show_filter_export_and_clear() {
+    let temp = TempDir::new().expect("temp dir");
+    let db_path = temp.path().join("knotter.sqlite3");
+
+    let grace = run_cmd_json(&db_path, &["add-contact", "--name", "Grace"]);
+    let grace_id = grace["id"].as_str().expect("id").to_string();
+    let ada = run_cmd_json(
+        &db_path,
+        &[
+            "add-contact",
+            "--name",
+            "Ada",
+            "--met",
+            "RustConf 2023",
+            "--met-date",
+            "2023-09-12",
+            "--met-via",
+            &grace_id,
+        ],
+    );
+    let ada_id = ada["id"].as_str().expect("id").to_string();
+
+    let shown = run_cmd_json(&db_path, &["show", &ada_id]);
+    assert_eq!(shown["origin"]["met_at"], "RustConf 2023");
+    assert_eq!(shown["origin"]["met_date"], "2023-09-12");
+    assert_eq!(shown["origin"]["met_via"], grace_id.as_str());
+    assert_eq!(shown["origin"]["met_via_name"], "Grace");
+    let text = run_cmd(&db_path, &["show", &ada_id]);
+    assert!(
+        text.contains("met: RustConf 2023 on 2023-09-12 via Grace"),
+        "{text}"
+    );
+
+    let filtered = run_cmd_json(&db_path, &["list", "--filter", "met:rustconf"]);
+    let items = filtered.as_array().expect("array");
+    assert_eq!(items.len(), 1);
+    assert_eq!(items[0]["id"], ada_id.as_str());
+
+    let export = run_cmd_json(&db_path, &["export", "json"]);
+    let exported = export["contacts"]
+        .as_array()
+        .expect("contacts")
+        .iter()
+        .find(|contact| contact["id"] == ada_id.as_str())
+        .expect("exported")
+        .clone();
+    assert_eq!(exported["origin"]["met_via"], grace_id.as_str());
+    let vcf = run_cmd(&db_path, &["export", "vcf"]);
+    assert!(vcf.contains("X-KNOTTER-MET:RustConf 2023"), "{vcf}");
+    assert!(vcf.contains("X-KNOTTER-MET-DATE:2023-09-12"), "{vcf}");
+
+    let output = run_cmd_output(&db_path, &["edit-contact", &ada_id, "--met-via", &ada_id]);
+    assert_eq!(output.status.code(), Some(3));
+    let output = run_cmd_output(&db_path, &["edit-contact", &ada_id, "--met-date", "Sept"]);
+    assert_eq!(output.status.code(), Some(3));
+
+    run_cmd(
+        &db_path,
+        &["edit-contact", &ada_id, "--met", "", "--met-date", ""],
+    );
+    let shown = run_cmd_json(&db_path, &["show", &ada_id]);
+    assert_eq!(shown["origin"]["met_via"], grace_id.as_str());
+    assert!(shown["origin"].get("met_at").is_none());
+
+    run_cmd(
+        &db_path,
+        &["delete", &grace_id, "--hard", "--force", "--yes"],
+    );
+    let shown = run_cmd_json(&db_path, &["show", &ada_id]);
+    assert!(shown.get("origin").is_none(), "{shown}");
+}
+
+#[test]
+fn cli_organization_show_filter_group_by_and_vcf() {
+    let temp = TempDir::new().expect("temp dir");
+    let db_path = temp.path().join("knotter.sqlite3");
+
+    let mut ids = Vec::new();
+    for (name, org) in [
+        ("Zed", Some("Acme")),
+        ("Ada", Some("Acme")),
+        ("Grace", Some("Bell Labs")),
+        ("Solo", None),
+    ] {
+        let mut args = vec!["add-contact", "--name", name];
+        if let Some(org) = org {
+            args.extend(["--org", org]);
+        }
+        // Scheduled contacts sort ahead of unscheduled ones, so Zed leads Acme.
+        if name == "Zed" {
+            args.extend(["--next-touchpoint-at", "2099-01-01"]);
+        }
+        let created = run_cmd_json(&db_path, &args);
+        ids.push(created["id"].as_str().expect("id").to_string());
+    }
+
+    let shown = run_cmd_json(&db_path, &["show", &ids[2]]);
+    assert_eq!(shown["organization"], "Bell Labs");
+    let text = run_cmd(&db_path, &["show", &ids[2]]);
+    assert!(text.contains("organization: Bell Labs"), "{text}");
+
+    let filtered = run_cmd_json(&db_path, &["list", "--filter", "org:ACM"]);
+    let names: Vec<_> = filtered
+        .as_array()
+        .expect("array")
+        .iter()
+        .map(|item| item["display_name"].as_str().expect("name"))
+        .collect();
+    assert_eq!(names, vec!["Zed", "Ada"]);
+
+    let grouped = run_cmd_json(&db_path, &["list", "--group-by", "org"]);
+    let groups = grouped["groups"].as_array().expect("groups");
+    let summary: Vec<_> = groups
+        .iter()
+        .map(|group| {
+            let names: Vec<_> = group["contacts"]
+                .as_array()
+                .expect("contacts")
+                .iter()
+                .map(|item| item["display_name"].as_str().expect("name").to_string())
+                .collect();
+            (group["org"].clone(), names)
+        })
+        .collect();
+    assert_eq!(
+        summary,
+        vec![
+            (
+                Value::from("Acme"),
+                vec!["Zed".to_string(), "Ada".to_string()]
+            ),
+            (Value::from("Bell Labs"), vec!["Grace".to_string()]),
+            (Value::Null, vec!["Solo".to_string()]),
+        ]
+    );
+    let text = run_cmd(&db_path, &["list", "--group-by", "org"]);
+    let headings: Vec<_> = text
+        .lines()
+        .filter(|line| !line.is_empty() && !line.starts_with(' '))
+        .collect();
+    assert_eq!(
+        headings,
+        vec!["Acme (2)", "Bell Labs (1)", "(no organization) (1)"],
+        "{text}"
+    );
+
+    let vcf = run_cmd(&db_path, &["export", "vcf"]);
+    assert!(vcf.contains("ORG:Bell Labs\r\n"), "{vcf}");
+
+    run_cmd(&db_path, &["edit-contact", &ids[2], "--org", ""]);
+    let shown = run_cmd_json(&db_path, &["show", &ids[2]]);
+    assert!(shown.get("organization").is_none(), "{shown}");
+}
+
+#[test]
+fn cli_mute_pauses_reminders_and_loops_until_it_lapses() {
+    let temp = TempDir::new().expect("temp dir");
+    let db_path = temp.path().join("knotter.sqlite3");
+    let config_path = temp.path().join("config.toml");
+    std::fs::write(&config_path, "[loops]\ndefault_cadence_days = 30\n").expect("write config");
+    restrict_config_permissions(&config_path);
+
+    let store = Store::open(&db_path).expect("open store");
+    store.migrate().expect("migrate");
+    let now = Utc::now().timestamp();
+    let mut ids = Vec::new();
+    for name in ["Ada", "Grace"] {
+        let contact = store
+            .contacts()
+            .create(
+                now,
+                knotter_store::repo::ContactNew {
+                    display_name: name.to_string(),
+                    email: None,
+                    phone: None,
+                    handle: None,
+                    timezone: None,
+                    next_touchpoint_at: Some(now - 3600),
+                    cadence_days: None,
+                    archived_at: None,
+                    organization: None,
+                },
+            )
+            .expect("create contact");
+        ids.push(contact.id.to_string());
+    }
+    drop(store);
+    let ada_id = ids[0].as_str();
+
+    let muted = run_cmd_json(&db_path, &["mute", ada_id, "--for", "2w"]);
+    let muted_until = muted["muted_until"].as_i64().expect("muted_until");
+    assert!(muted_until > now + 13 * 86_400);
+
+    let overdue_names = |remind: &Value| -> Vec<String> {
+        remind["overdue"]
+            .as_array()
+            .expect("overdue")
+            .iter()
+            .map(|item| item["display_name"].as_str().expect("name").to_string())
+            .collect()
+    };
+    let remind = run_cmd_json(&db_path, &["remind"]);
+    assert_eq!(overdue_names(&remind), vec!["Grace"]);
+
+    let listed = run_cmd_json(&db_path, &["list"]);
+    assert_eq!(listed.as_array().expect("list").len(), 2);
+    let filtered = run_cmd_json(&db_path, &["list", "--filter", "muted:true"]);
+    let filtered = filtered.as_array().expect("list");
+    assert_eq!(filtered.len(), 1);
+    assert_eq!(filtered[0]["muted_until"], muted_until);
+    let shown = run_cmd_json(&db_path, &["show", ada_id]);
+    assert_eq!(shown["muted_until"], muted_until);
+    let export = run_cmd_json(&db_path, &["export", "json"]);
+    let exported = export["contacts"]
+        .as_array()
+        .expect("contacts")
+        .iter()
+        .find(|contact| contact["id"] == ada_id)
+        .expect("exported")
+        .clone();
+    assert_eq!(exported["muted_until"], muted_until);
+
+    let loops = run_cmd_json_with_config(&db_path, &config_path, &["loops", "apply", "--dry-run"]);
+    assert_eq!(loops["matched"], 1);
+
+    let later = muted_until.to_string();
+    let remind = run_cmd_json_with_env(
+        &db_path,
+        &["remind"],
+        &[
+            ("KNOTTER_TEST_NOW_UTC", later.as_str()),
+            ("KNOTTER_ALLOW_TEST_NOW_UTC", "1"),
         ],
     );
+    assert_eq!(overdue_names(&remind), vec!["Ada", "Grace"]);
+
+    let unmuted = run_cmd_json(&db_path, &["unmute", ada_id]);
+    assert!(unmuted["muted_until"].is_null());
+    let remind = run_cmd_json(&db_path, &["remind"]);
+    assert_eq!(overdue_names(&remind), vec!["Ada", "Grace"]);
+
+    let output = run_cmd_output(&db_path, &["mute", ada_id, "--until", "2000-01-01"]);
+    assert_eq!(output.status.code(), Some(3));
+    let output = run_cmd_output(&db_path, &["mute", ada_id]);
+    assert!(!output.status.success());
+}
+
+#[test]
+fn cli_due_today_flips_at_midnight_in_the_configured_timezone() {
+    let temp = TempDir::new().expect("temp dir");
+    let db_path = temp.path().join("knotter.sqlite3");
+    let config_path = temp.path().join("config.toml");
+    std::fs::write(
+        &config_path,
+        "timezone = \"Asia/Tokyo\"\n[loops]\ndefault_cadence_days = 30\n",
+    )
+    .expect("write config");
+    restrict_config_permissions(&config_path);
+
+    // Due 2030-03-10 09:00 in Tokyo, which is midnight UTC.
+    let due = Utc
+        .with_ymd_and_hms(2030, 3, 10, 0, 0, 0)
+        .unwrap()
+        .timestamp();
+    let store = Store::open(&db_path).expect("open store");
+    store.migrate().expect("migrate");
+    store
+        .contacts()
+        .create(
+            due - 30 * 86_400,
+            knotter_store::repo::ContactNew {
+                display_name: "Ada".to_string(),
+                email: None,
+                phone: None,
+                handle: None,
+                timezone: None,
+                next_touchpoint_at: Some(due),
+                cadence_days: None,
+                archived_at: None,
+                organization: None,
+            },
+        )
+        .expect("create contact");
+    drop(store);
+
+    let run = |args: &[&str], envs: &[(&str, &str)]| -> std::process::Output {
+        let config_dir = TempDir::new().expect("temp config dir");
+        let mut cmd = cargo_bin_cmd!("knotter");
+        cmd.env("XDG_CONFIG_HOME", config_dir.path())
+            .env_remove("KNOTTER_TODAY")
+            .args([
+                "--db-path",
+                db_path.to_str().expect("db path"),
+                "--config",
+                config_path.to_str().expect("config path"),
+                "--json",
+            ])
+            .args(args);
+        for (key, value) in envs {
+            cmd.env(key, value);
+        }
+        cmd.output().expect("run command")
+    };
+    // Whether remind, `list --filter due:today`, and `loops apply` all see
+    // the contact as due today.
+    let due_today_everywhere = |extra: &[&str], envs: &[(&str, &str)]| -> [bool; 3] {
+        let json = |args: &[&str]| -> Value {
+            let args = [args, extra].concat();
+            let output = run(&args, envs);
+            assert!(output.status.success(), "command failed: {output:?}");
+            serde_json::from_slice(&output.stdout).expect("parse json")
+        };
+        let remind = json(&["remind"]);
+        let listed = json(&["list", "--filter", "due:today"]);
+        let loops = json(&["loops", "apply", "--filter", "due:today", "--dry-run"]);
+        [
+            remind["today"].as_array().expect("today").len() == 1,
+            listed.as_array().expect("list").len() == 1,
+            loops["matched"] == 1,
+        ]
+    };
+    let at = |hour: u32, minute: u32| {
+        Utc.with_ymd_and_hms(2030, 3, 9, hour, minute, 0)
+            .unwrap()
+            .timestamp()
+            .to_string()
+    };
+
+    // 23:59 on the 9th in Tokyo: still tomorrow's contact.
+    let before = at(14, 59);
+    let envs = [
+        ("KNOTTER_ALLOW_TEST_NOW_UTC", "1"),
+        ("KNOTTER_TEST_NOW_UTC", before.as_str()),
+    ];
+    assert_eq!(due_today_everywhere(&[], &envs), [false; 3]);
+    // 00:01 on the 10th in Tokyo, while UTC is still on the 9th.
+    let after = at(15, 1);
+    let envs = [
+        ("KNOTTER_ALLOW_TEST_NOW_UTC", "1"),
+        ("KNOTTER_TEST_NOW_UTC", after.as_str()),
+    ];
+    assert_eq!(due_today_everywhere(&[], &envs), [true; 3]);
+
+    assert_eq!(
+        due_today_everywhere(&["--today-is", "2030-03-10"], &[]),
+        [true; 3]
+    );
+    assert_eq!(
+        due_today_everywhere(&[], &[("KNOTTER_TODAY", "2030-03-09")]),
+        [false; 3]
+    );
+    // The flag wins over the environment.
+    assert_eq!(
+        due_today_everywhere(
+            &["--today-is", "2030-03-10"],
+            &[("KNOTTER_TODAY", "2030-03-09")]
+        ),
+        [true; 3]
+    );
+
+    let output = run(&["remind", "--today-is", "10/03/2030"], &[]);
+    assert_eq!(output.status.code(), Some(3));
+}
+
+#[test]
+fn cli_add_list_tag_schedule_flow() {
+    let temp = TempDir::new().expect("temp dir");
+    let db_path = temp.path().join("knotter.sqlite3");
+
+    run_cmd(&db_path, &["add-contact", "--name", "Ada Lovelace"]);
+
+    let list = run_cmd_json(&db_path, &["list"]);
+    let items = list.as_array().expect("array");
+    assert_eq!(items.len(), 1);
+    assert_eq!(items[0]["display_name"], "Ada Lovelace");
+    let id = items[0]["id"].as_str().expect("id").to_string();
+
+    run_cmd(&db_path, &["tag", "add", &id, "friend"]);
+
+    let filtered = run_cmd_json(&db_path, &["list", "--filter", "#friend"]);
+    let filtered_items = filtered.as_array().expect("array");
+    assert_eq!(filtered_items.len(), 1);
+
+    run_cmd(&db_path, &["schedule", &id, "--at", "2030-01-01"]);
+
+    let detail = run_cmd_json(&db_path, &["show", &id]);
+    assert!(detail["next_touchpoint_at"].is_number());
+}
+
+#[test]
+fn cli_schedule_rejects_past_date() {
+    let temp = TempDir::new().expect("temp dir");
+    let db_path = temp.path().join("knotter.sqlite3");
+
+    run_cmd(&db_path, &["add-contact", "--name", "Ada Lovelace"]);
+    let list = run_cmd_json(&db_path, &["list"]);
+    let items = list.as_array().expect("array");
+    let id = items[0]["id"].as_str().expect("id").to_string();
+
+    let output = run_cmd_output(&db_path, &["schedule", &id, "--at", "2000-01-01"]);
     assert_eq!(output.status.code(), Some(3));
     let stderr = String::from_utf8_lossy(&output.stderr);
     assert!(stderr.contains("timestamp must be now or later"));
+    assert!(stderr.contains("parsed as 2000-01-01"));
+
+    let output = run_cmd_output(&db_path, &["schedule", &id, "--at", "in two weeks"]);
+    assert_eq!(output.status.code(), Some(3));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("invalid relative date 'in two weeks'"));
+}
+
+#[test]
+fn cli_schedule_jitter_is_stable_and_rebalance_caps_days() {
+    let temp = TempDir::new().expect("temp dir");
+    let db_path = temp.path().join("knotter.sqlite3");
+
+    for index in 0..5 {
+        run_cmd(
+            &db_path,
+            &[
+                "add-contact",
+                "--name",
+                &format!("Contact {index}"),
+                "--cadence-days",
+                &format!("{}", 10 * (index + 1)),
+            ],
+        );
+    }
+    let list = run_cmd_json(&db_path, &["list"]);
+    let ids: Vec<String> = list
+        .as_array()
+        .expect("array")
+        .iter()
+        .map(|item| item["id"].as_str().expect("id").to_string())
+        .collect();
+
+    let base = run_cmd_json(&db_path, &["schedule", &ids[0], "--at", "+15d"])["next_touchpoint_at"]
+        .as_i64()
+        .expect("timestamp");
+    let jittered = run_cmd_json(
+        &db_path,
+        &["schedule", &ids[0], "--at", "+15d", "--jitter", "3"],
+    )["next_touchpoint_at"]
+        .as_i64()
+        .expect("timestamp");
+    let again = run_cmd_json(
+        &db_path,
+        &["schedule", &ids[0], "--at", "+15d", "--jitter", "3"],
+    )["next_touchpoint_at"]
+        .as_i64()
+        .expect("timestamp");
+    assert_eq!(jittered, again);
+    assert_eq!((jittered - base) % 86_400, 0);
+    assert!((jittered - base).abs() <= 3 * 86_400);
+
+    let output = run_cmd_output(
+        &db_path,
+        &["schedule", &ids[0], "--at", "+15d", "--jitter", "31"],
+    );
+    assert_eq!(output.status.code(), Some(3));
+
+    for id in &ids {
+        run_cmd(&db_path, &["schedule", id, "--at", "+10d"]);
+    }
+    let dry = run_cmd_json(&db_path, &["rebalance", "--max-per-day", "2", "--dry-run"]);
+    assert_eq!(dry["considered"], 5);
+    assert_eq!(dry["moved"], 3);
+    assert_eq!(dry["overfull_days"], 0);
+    let listed = run_cmd_json(&db_path, &["list"]);
+    assert!(listed
+        .as_array()
+        .expect("array")
+        .iter()
+        .all(|item| item["next_touchpoint_at"] == listed[0]["next_touchpoint_at"]));
+
+    let report = run_cmd_json(&db_path, &["rebalance", "--max-per-day", "2"]);
+    let moves = report["moves"].as_array().expect("moves");
+    assert_eq!(moves.len(), 3);
+    let moved: Vec<&str> = moves
+        .iter()
+        .map(|item| item["display_name"].as_str().expect("name"))
+        .collect();
+    assert!(!moved.contains(&"Contact 0"));
+    assert!(!moved.contains(&"Contact 1"));
+    for item in moves {
+        let shift = item["to"].as_i64().expect("to") - item["from"].as_i64().expect("from");
+        assert_eq!(shift.abs(), 86_400);
+    }
+
+    let again = run_cmd_json(&db_path, &["rebalance", "--max-per-day", "2"]);
+    assert_eq!(again["moved"], 0);
+    let log = run_cmd_json(&db_path, &["log", moves[0]["id"].as_str().expect("id")]);
+    assert_eq!(log[0]["action"], "update");
+}
+
+#[test]
+fn cli_schedule_accepts_relative_dates() {
+    let temp = TempDir::new().expect("temp dir");
+    let db_path = temp.path().join("knotter.sqlite3");
+
+    run_cmd(&db_path, &["add-contact", "--name", "Ada Lovelace"]);
+    let list = run_cmd_json(&db_path, &["list"]);
+    let id = list[0]["id"].as_str().expect("id").to_string();
+
+    // 2030-01-14 is a Monday.
+    let now = Local
+        .with_ymd_and_hms(2030, 1, 14, 9, 0, 0)
+        .single()
+        .expect("local time")
+        .with_timezone(&Utc)
+        .timestamp();
+    let now_env = now.to_string();
+    let envs = [
+        ("KNOTTER_TEST_NOW_UTC", now_env.as_str()),
+        ("KNOTTER_ALLOW_TEST_NOW_UTC", "1"),
+    ];
+
+    for (input, expected_date) in [
+        ("+2w", "2030-01-28"),
+        ("in 10 days", "2030-01-24"),
+        ("next-monday", "2030-01-21"),
+        ("tomorrow", "2030-01-15"),
+    ] {
+        let contact = run_cmd_json_with_env(&db_path, &["schedule", &id, "--at", input], &envs);
+        let (timestamp, precision) = Clock::default()
+            .parse_local_timestamp_with_precision(expected_date)
+            .expect("parse date");
+        let expected = knotter_core::rules::ensure_future_timestamp_with_precision(
+            now,
+            timestamp,
+            precision,
+            &Clock::default(),
+        )
+        .expect("expected schedule");
+        assert_eq!(contact["next_touchpoint_at"], expected, "{input}");
+    }
+}
+
+#[test]
+fn cli_add_contact_rejects_past_next_touchpoint() {
+    let temp = TempDir::new().expect("temp dir");
+    let db_path = temp.path().join("knotter.sqlite3");
+
+    let output = run_cmd_output(
+        &db_path,
+        &[
+            "add-contact",
+            "--name",
+            "Ada Lovelace",
+            "--next-touchpoint-at",
+            "2000-01-01",
+        ],
+    );
+    assert_eq!(output.status.code(), Some(3));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("timestamp must be now or later"));
+}
+
+#[test]
+fn cli_schedule_date_only_sets_end_of_day() {
+    let temp = TempDir::new().expect("temp dir");
+    let db_path = temp.path().join("knotter.sqlite3");
+
+    run_cmd(&db_path, &["add-contact", "--name", "Ada Lovelace"]);
+    let list = run_cmd_json(&db_path, &["list"]);
+    let items = list.as_array().expect("array");
+    let id = items[0]["id"].as_str().expect("id").to_string();
+
+    run_cmd(&db_path, &["schedule", &id, "--at", "2030-01-15"]);
+
+    let detail = run_cmd_json(&db_path, &["show", &id]);
+    let (timestamp, precision) = Clock::default()
+        .parse_local_timestamp_with_precision("2030-01-15")
+        .expect("parse date");
+    let expected = knotter_core::rules::ensure_future_timestamp_with_precision(
+        knotter_core::time::now_utc(),
+        timestamp,
+        precision,
+        &Clock::default(),
+    )
+    .expect("expected schedule");
+    assert_eq!(detail["next_touchpoint_at"], expected);
+}
+
+#[test]
+fn cli_remind_includes_soon_contact() {
+    let temp = TempDir::new().expect("temp dir");
+    let db_path = temp.path().join("knotter.sqlite3");
+
+    run_cmd(&db_path, &["add-contact", "--name", "Ada Lovelace"]);
+
+    let list = run_cmd_json(&db_path, &["list"]);
+    let items = list.as_array().expect("array");
+    let id = items[0]["id"].as_str().expect("id").to_string();
+
+    let scheduled = "2030-01-02";
+    run_cmd(&db_path, &["schedule", &id, "--at", scheduled]);
+
+    let remind = run_cmd_json(
+        &db_path,
+        &["remind", "--soon-days", &MAX_SOON_DAYS.to_string()],
+    );
+    let soon = remind["soon"].as_array().expect("soon array");
+    assert_eq!(soon.len(), 1);
+    assert_eq!(soon[0]["id"], id);
+}
+
+#[test]
+fn cli_remind_mark_contacted_and_interactive_needs_a_terminal() {
+    let temp = TempDir::new().expect("temp dir");
+    let db_path = temp.path().join("knotter.sqlite3");
+
+    let store = Store::open(&db_path).expect("open store");
+    store.migrate().expect("migrate");
+    let now = Utc::now().timestamp();
+    let mut ids = Vec::new();
+    for name in ["Ada Lovelace", "Grace Hopper"] {
+        let contact = store
+            .contacts()
+            .create(
+                now,
+                knotter_store::repo::ContactNew {
+                    display_name: name.to_string(),
+                    email: None,
+                    phone: None,
+                    handle: None,
+                    timezone: None,
+                    next_touchpoint_at: Some(now - 3600),
+                    cadence_days: Some(30),
+                    archived_at: None,
+                    organization: None,
+                },
+            )
+            .expect("create contact");
+        ids.push(contact.id.to_string());
+    }
+    drop(store);
+
+    let output = run_cmd_output(&db_path, &["remind", "--interactive"]);
+    assert_eq!(output.status.code(), Some(3));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("--interactive needs a terminal"),
+        "{stderr}"
+    );
+
+    let remind = run_cmd_json(&db_path, &["remind", "--mark-contacted", &ids[0]]);
+    let overdue = remind["overdue"].as_array().expect("overdue array");
+    assert_eq!(overdue.len(), 1);
+    assert_eq!(overdue[0]["id"], ids[1].as_str());
+    let interactions = run_cmd_json(&db_path, &["show", &ids[0]]);
+    assert_eq!(
+        interactions["recent_interactions"][0]["kind"],
+        "other:touch"
+    );
+
+    let output = run_cmd_output(
+        &db_path,
+        &[
+            "remind",
+            "--mark-contacted",
+            "00000000-0000-0000-0000-000000000000",
+        ],
+    );
+    assert_eq!(output.status.code(), Some(2));
+}
+
+#[test]
+fn cli_date_add_list_and_remind_includes_today() {
+    let temp = TempDir::new().expect("temp dir");
+    let db_path = temp.path().join("knotter.sqlite3");
+
+    run_cmd(&db_path, &["add-contact", "--name", "Ada Lovelace"]);
+    let list = run_cmd_json(&db_path, &["list"]);
+    let items = list.as_array().expect("array");
+    let id = items[0]["id"].as_str().expect("id").to_string();
+
+    let fixed_local = Local
+        .with_ymd_and_hms(2030, 1, 15, 12, 0, 0)
+        .single()
+        .expect("local time");
+    let date_str = fixed_local.format("%Y-%m-%d").to_string();
+    let now_env = fixed_local.with_timezone(&Utc).timestamp().to_string();
+
+    run_cmd(
+        &db_path,
+        &["date", "add", &id, "--kind", "birthday", "--on", &date_str],
+    );
+
+    let dates = run_cmd_json(&db_path, &["date", "ls", &id]);
+    let dates = dates.as_array().expect("dates array");
+    assert_eq!(dates.len(), 1);
+    assert_eq!(dates[0]["kind"], "birthday");
+
+    let remind = run_cmd_json_with_env(
+        &db_path,
+        &["remind"],
+        &[
+            ("KNOTTER_TEST_NOW_UTC", now_env.as_str()),
+            ("KNOTTER_ALLOW_TEST_NOW_UTC", "1"),
+        ],
+    );
+    let dates_today = remind["dates_today"].as_array().expect("dates_today array");
+    assert_eq!(dates_today.len(), 1);
+    assert_eq!(dates_today[0]["display_name"], "Ada Lovelace");
+}
+
+#[test]
+fn cli_remind_lists_dates_inside_their_lead_window() {
+    let temp = TempDir::new().expect("temp dir");
+    let db_path = temp.path().join("knotter.sqlite3");
+
+    let created = run_cmd_json(&db_path, &["add-contact", "--name", "Ada Lovelace"]);
+    let id = created["id"].as_str().expect("id").to_string();
+    let added = run_cmd_json(
+        &db_path,
+        &[
+            "date",
+            "add",
+            &id,
+            "--kind",
+            "birthday",
+            "--on",
+            "01-03",
+            "--remind-before",
+            "14",
+        ],
+    );
+    assert_eq!(added["remind_days_before"], 14);
+
+    let fixed_local = Local
+        .with_ymd_and_hms(2029, 12, 25, 12, 0, 0)
+        .single()
+        .expect("local time");
+    let now_env = fixed_local.with_timezone(&Utc).timestamp().to_string();
+    let remind = run_cmd_json_with_env(
+        &db_path,
+        &["remind"],
+        &[
+            ("KNOTTER_TEST_NOW_UTC", now_env.as_str()),
+            ("KNOTTER_ALLOW_TEST_NOW_UTC", "1"),
+        ],
+    );
+    assert_eq!(remind["dates_today"], serde_json::json!([]));
+    let upcoming = remind["dates_upcoming"].as_array().expect("upcoming");
+    assert_eq!(upcoming.len(), 1);
+    assert_eq!(upcoming[0]["display_name"], "Ada Lovelace");
+    assert_eq!(upcoming[0]["days_until"], 9);
+
+    let output = run_cmd_output(&db_path, &["date", "ls", &id]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("(remind 14d before)"), "{stdout}");
+}
+
+#[test]
+fn cli_remind_weekly_digest_groups_by_day() {
+    let temp = TempDir::new().expect("temp dir");
+    let db_path = temp.path().join("knotter.sqlite3");
+
+    run_cmd(
+        &db_path,
+        &[
+            "add-contact",
+            "--name",
+            "Ada",
+            "--next-touchpoint-at",
+            "2030-01-16 10:00",
+        ],
+    );
+    run_cmd(
+        &db_path,
+        &[
+            "add-contact",
+            "--name",
+            "Bob",
+            "--next-touchpoint-at",
+            "2030-01-25",
+        ],
+    );
+    let grace = run_cmd_json(&db_path, &["add-contact", "--name", "Grace"]);
+    let grace_id = grace["id"].as_str().expect("id").to_string();
+    run_cmd(
+        &db_path,
+        &[
+            "date",
+            "add",
+            &grace_id,
+            "--kind",
+            "birthday",
+            "--on",
+            "2000-01-18",
+        ],
+    );
+
+    let now_env = Local
+        .with_ymd_and_hms(2030, 1, 14, 9, 0, 0)
+        .single()
+        .expect("local time")
+        .with_timezone(&Utc)
+        .timestamp()
+        .to_string();
+    let envs = [
+        ("KNOTTER_TEST_NOW_UTC", now_env.as_str()),
+        ("KNOTTER_ALLOW_TEST_NOW_UTC", "1"),
+    ];
+
+    let plain = run_cmd_json_with_env(&db_path, &["remind"], &envs);
+    assert!(plain.get("week").is_none());
+
+    let remind = run_cmd_json_with_env(&db_path, &["remind", "--digest", "weekly"], &envs);
+    assert_eq!(remind["soon"].as_array().expect("soon").len(), 1);
+    let week = remind["week"].as_array().expect("week array");
+    assert_eq!(week.len(), 7);
+    assert_eq!(week[0]["date"], "2030-01-14");
+    assert_eq!(week[2]["date"], "2030-01-16");
+    assert_eq!(week[2]["contacts"][0]["display_name"], "Ada");
+    assert_eq!(week[4]["dates"][0]["display_name"], "Grace");
+    let scheduled: usize = week
+        .iter()
+        .map(|day| day["contacts"].as_array().expect("contacts").len())
+        .sum();
+    assert_eq!(scheduled, 1);
+
+    let digest_only = run_cmd_json_with_env(
+        &db_path,
+        &["remind", "--digest", "weekly", "--digest-only"],
+        &envs,
+    );
+    assert!(digest_only["soon"].as_array().expect("soon").is_empty());
+    assert_eq!(digest_only["week"].as_array().expect("week").len(), 7);
+}
+
+#[test]
+fn cli_remind_uses_config_due_soon_days() {
+    let temp = TempDir::new().expect("temp dir");
+    let db_path = temp.path().join("knotter.sqlite3");
+    let config_path = temp.path().join("config.toml");
+
+    std::fs::write(&config_path, "due_soon_days = 0\n").expect("write config");
+    restrict_config_permissions(&config_path);
+
+    run_cmd_with_config(
+        &db_path,
+        &config_path,
+        &["add-contact", "--name", "Ada Lovelace"],
+    );
+
+    let list = run_cmd_json_with_config(&db_path, &config_path, &["list"]);
+    let items = list.as_array().expect("array");
+    let id = items[0]["id"].as_str().expect("id").to_string();
+
+    let tomorrow = Local::now()
+        .date_naive()
+        .checked_add_signed(Duration::days(1))
+        .expect("tomorrow");
+    let scheduled = tomorrow.format("%Y-%m-%d").to_string();
+    run_cmd_with_config(
+        &db_path,
+        &config_path,
+        &["schedule", &id, "--at", &scheduled],
+    );
+
+    let remind = run_cmd_json_with_config(&db_path, &config_path, &["remind"]);
+    assert!(remind["overdue"].as_array().expect("overdue").is_empty());
+    assert!(remind["today"].as_array().expect("today").is_empty());
+    assert!(remind["soon"].as_array().expect("soon").is_empty());
+}
+
+#[test]
+fn cli_remind_notification_falls_back_to_random_contacts_when_no_reminders() {
+    let temp = TempDir::new().expect("temp dir");
+    let db_path = temp.path().join("knotter.sqlite3");
+    let config_path = temp.path().join("config.toml");
+
+    std::fs::write(
+        &config_path,
+        // Backwards-compat: old key name is still accepted.
+        "[notifications]\nenabled = true\nbackend = \"stdout\"\nrandom_contacts_if_no_dates_today = 10\n",
+    )
+    .expect("write config");
+    restrict_config_permissions(&config_path);
+
+    run_cmd_with_config(
+        &db_path,
+        &config_path,
+        &["add-contact", "--name", "Ada Lovelace"],
+    );
+    run_cmd_with_config(
+        &db_path,
+        &config_path,
+        &["add-contact", "--name", "Grace Hopper"],
+    );
+
+    let output = run_cmd_with_config(&db_path, &config_path, &["remind"]);
+    assert!(output.contains("random contacts:"), "output: {output}");
+    assert!(output.contains("Ada Lovelace"), "output: {output}");
+    assert!(output.contains("Grace Hopper"), "output: {output}");
+    assert!(!output.contains("no reminders"), "output: {output}");
+}
+
+#[test]
+fn cli_remind_notification_does_not_add_random_contacts_when_there_are_reminders() {
+    let temp = TempDir::new().expect("temp dir");
+    let db_path = temp.path().join("knotter.sqlite3");
+    let config_path = temp.path().join("config.toml");
+
+    std::fs::write(
+        &config_path,
+        "[notifications]\nenabled = true\nbackend = \"stdout\"\nrandom_contacts_if_no_reminders = 10\n",
+    )
+    .expect("write config");
+    restrict_config_permissions(&config_path);
+
+    run_cmd_with_config(
+        &db_path,
+        &config_path,
+        &["add-contact", "--name", "Ada Lovelace"],
+    );
+    let list = run_cmd_json_with_config(&db_path, &config_path, &["list"]);
+    let items = list.as_array().expect("array");
+    let id = items[0]["id"].as_str().expect("id").to_string();
+
+    let fixed_local = Local
+        .with_ymd_and_hms(2030, 1, 15, 12, 0, 0)
+        .single()
+        .expect("local time");
+    let date_str = fixed_local.format("%Y-%m-%d").to_string();
+    let now_env = fixed_local.with_timezone(&Utc).timestamp().to_string();
+
+    // Schedule a touchpoint for "today" relative to the fixed now, so reminders are non-empty.
+    run_cmd_with_config(
+        &db_path,
+        &config_path,
+        &["schedule", &id, "--at", &date_str],
+    );
+
+    let output = {
+        let config_dir = TempDir::new().expect("temp config dir");
+        let output = cargo_bin_cmd!("knotter")
+            .env("XDG_CONFIG_HOME", config_dir.path())
+            .env("KNOTTER_TEST_NOW_UTC", now_env.as_str())
+            .env("KNOTTER_ALLOW_TEST_NOW_UTC", "1")
+            .args([
+                "--db-path",
+                db_path.to_str().expect("db path"),
+                "--config",
+                config_path.to_str().expect("config path"),
+            ])
+            .args(["remind"])
+            .output()
+            .expect("run command");
+        assert!(output.status.success(), "command failed: {:?}", output);
+        String::from_utf8(output.stdout).expect("utf8")
+    };
+
+    assert!(
+        output.contains("today:") || output.contains("overdue:") || output.contains("soon:"),
+        "output: {output}"
+    );
+    assert!(!output.contains("random contacts:"), "output: {output}");
+}
+
+#[test]
+fn cli_remind_per_contact_notifications_cap_and_summarize() {
+    let temp = TempDir::new().expect("temp dir");
+    let db_path = temp.path().join("knotter.sqlite3");
+    let config_path = temp.path().join("config.toml");
+
+    std::fs::write(
+        &config_path,
+        "[notifications]\nenabled = true\nbackend = \"stdout\"\nper_contact = true\nmax_per_run = 2\n",
+    )
+    .expect("write config");
+    restrict_config_permissions(&config_path);
+
+    for (name, when) in [
+        ("Ada Lovelace", "2024-01-01"),
+        ("Grace Hopper", "2024-02-01"),
+        ("Linus Torvalds", "2024-03-01"),
+    ] {
+        let contact = run_cmd_json_with_config(
+            &db_path,
+            &config_path,
+            &["add-contact", "--name", name, "--cadence-days", "7"],
+        );
+        let id = contact["id"].as_str().expect("id");
+        run_cmd_with_config(
+            &db_path,
+            &config_path,
+            &[
+                "add-note",
+                id,
+                "--when",
+                when,
+                "--note",
+                "hi",
+                "--reschedule",
+            ],
+        );
+    }
+
+    // A month later every 7-day touchpoint is overdue.
+    let later = (Utc::now().timestamp() + 30 * 86_400).to_string();
+    let config_dir = TempDir::new().expect("temp config dir");
+    let output = cargo_bin_cmd!("knotter")
+        .env("XDG_CONFIG_HOME", config_dir.path())
+        .env("KNOTTER_TEST_NOW_UTC", later.as_str())
+        .env("KNOTTER_ALLOW_TEST_NOW_UTC", "1")
+        .args([
+            "--db-path",
+            db_path.to_str().expect("db path"),
+            "--config",
+            config_path.to_str().expect("config path"),
+            "remind",
+        ])
+        .output()
+        .expect("run command");
+    assert!(output.status.success(), "command failed: {:?}", output);
+    let output = String::from_utf8(output.stdout).expect("utf8");
+    let lines: Vec<&str> = output.lines().collect();
+    assert_eq!(
+        lines[0], "Overdue: Ada Lovelace: Last interaction: 2024-01-01",
+        "output: {output}"
+    );
+    assert_eq!(
+        lines[1], "Overdue: Grace Hopper: Last interaction: 2024-02-01",
+        "output: {output}"
+    );
+    assert_eq!(
+        lines[2], "knotter reminders: And 1 more overdue or due today",
+        "output: {output}"
+    );
+    assert!(!output.contains("Linus"), "output: {output}");
+}
+
+#[test]
+fn cli_vacation_holds_back_notifications_unless_forced() {
+    let temp = TempDir::new().expect("temp dir");
+    let db_path = temp.path().join("knotter.sqlite3");
+    let config_path = temp.path().join("config.toml");
+    std::fs::write(
+        &config_path,
+        "[notifications]\nenabled = true\nbackend = \"stdout\"\nper_contact = true\n",
+    )
+    .expect("write config");
+    restrict_config_permissions(&config_path);
+
+    let ada = run_cmd_json_with_config(
+        &db_path,
+        &config_path,
+        &[
+            "add-contact",
+            "--name",
+            "Ada Lovelace",
+            "--cadence-days",
+            "7",
+        ],
+    );
+    let ada_id = ada["id"].as_str().expect("id");
+    run_cmd_with_config(
+        &db_path,
+        &config_path,
+        &[
+            "add-note",
+            ada_id,
+            "--when",
+            "2024-01-01",
+            "--note",
+            "hi",
+            "--reschedule",
+        ],
+    );
+
+    let status = run_cmd_json_with_config(&db_path, &config_path, &["vacation"]);
+    assert_eq!(status["active"], false);
+    let status = run_cmd_json_with_config(&db_path, &config_path, &["vacation", "--for", "2w"]);
+    assert_eq!(status["active"], true);
+    assert!(status["until"].as_i64().expect("until") > Utc::now().timestamp());
+
+    let held = run_cmd_output_with_config(&db_path, &config_path, &["remind"]);
+    assert!(held.status.success(), "command failed: {:?}", held);
+    let stdout = String::from_utf8(held.stdout).expect("utf8");
+    let stderr = String::from_utf8(held.stderr).expect("utf8");
+    assert!(
+        stdout.starts_with("Vacation mode until "),
+        "stdout: {stdout}"
+    );
+    assert!(stdout.contains("Ada Lovelace"), "stdout: {stdout}");
+    assert!(
+        !stdout.contains("knotter reminders: Soon (1): Ada Lovelace"),
+        "stdout: {stdout}"
+    );
+    assert!(
+        stderr.contains("notifications skipped: vacation mode until"),
+        "stderr: {stderr}"
+    );
+
+    let forced = run_cmd_with_config(&db_path, &config_path, &["remind", "--force-notify"]);
+    assert!(
+        forced.contains("knotter reminders: Soon (1): Ada Lovelace"),
+        "output: {forced}"
+    );
+
+    let status = run_cmd_json_with_config(&db_path, &config_path, &["vacation", "--clear"]);
+    assert_eq!(status["active"], false);
+    let delivered = run_cmd_output_with_config(&db_path, &config_path, &["remind"]);
+    let stdout = String::from_utf8(delivered.stdout).expect("utf8");
+    assert!(
+        stdout.contains("knotter reminders: Soon (1): Ada Lovelace"),
+        "stdout: {stdout}"
+    );
+    assert!(!stdout.contains("Vacation mode"), "stdout: {stdout}");
+    assert!(delivered.stderr.is_empty());
+
+    let past = run_cmd_output_with_config(
+        &db_path,
+        &config_path,
+        &["vacation", "--until", "2020-01-01"],
+    );
+    assert_eq!(past.status.code(), Some(3));
+}
+
+#[test]
+fn cli_remind_max_per_bucket_caps_formatted_output_only() {
+    let temp = TempDir::new().expect("temp dir");
+    let db_path = temp.path().join("knotter.sqlite3");
+    let config_path = temp.path().join("config.toml");
+    std::fs::write(&config_path, "[notifications]\nmax_per_bucket = 1\n").expect("write config");
+    restrict_config_permissions(&config_path);
+
+    for (name, when) in [
+        ("Grace Hopper", "2030-01-03"),
+        ("Ada Lovelace", "2030-01-01"),
+        ("Linus Torvalds", "2030-01-02"),
+    ] {
+        let contact = run_cmd_json(&db_path, &["add-contact", "--name", name]);
+        let id = contact["id"].as_str().expect("id");
+        run_cmd(&db_path, &["schedule", id, "--at", when]);
+    }
+
+    // Run a month after every touchpoint so all three are overdue.
+    let remind = |args: &[&str]| {
+        let config_dir = TempDir::new().expect("temp config dir");
+        let output = cargo_bin_cmd!("knotter")
+            .env("XDG_CONFIG_HOME", config_dir.path())
+            .env("KNOTTER_TEST_NOW_UTC", "1896134400")
+            .env("KNOTTER_ALLOW_TEST_NOW_UTC", "1")
+            .args(["--db-path", db_path.to_str().expect("db path")])
+            .args(args)
+            .output()
+            .expect("run command");
+        assert!(output.status.success(), "command failed: {:?}", output);
+        String::from_utf8(output.stdout).expect("utf8")
+    };
+
+    let stdout = remind(&["remind", "--max-per-bucket", "2"]);
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines[0], "overdue:", "output: {stdout}");
+    assert!(lines[1].contains("Ada Lovelace"), "output: {stdout}");
+    assert!(lines[2].contains("Linus Torvalds"), "output: {stdout}");
+    assert_eq!(
+        lines[3], "  … and 1 more (run `knotter list --filter due:overdue`)",
+        "output: {stdout}"
+    );
+    assert!(!stdout.contains("Grace Hopper"), "output: {stdout}");
+
+    let config = config_path.to_str().expect("config path");
+    let stdout = remind(&["--config", config, "remind"]);
+    assert!(stdout.contains("… and 2 more"), "output: {stdout}");
+
+    let json: Value = serde_json::from_str(&remind(&["--config", config, "--json", "remind"]))
+        .expect("parse json");
+    let names: Vec<&str> = json["overdue"]
+        .as_array()
+        .expect("overdue")
+        .iter()
+        .map(|item| item["display_name"].as_str().expect("name"))
+        .collect();
+    assert_eq!(names, ["Ada Lovelace", "Linus Torvalds", "Grace Hopper"]);
+}
+
+#[test]
+fn cli_remind_truncation_hint_runs_as_printed() {
+    let temp = TempDir::new().expect("temp dir");
+    let db_path = temp.path().join("knotter.sqlite3");
+    for (name, when) in [
+        ("Ada Lovelace", "2030-01-01"),
+        ("Grace Hopper", "2030-01-02"),
+    ] {
+        let contact = run_cmd_json(&db_path, &["add-contact", "--name", name]);
+        let id = contact["id"].as_str().expect("id");
+        run_cmd(&db_path, &["schedule", id, "--at", when]);
+    }
+    let run = |args: &[&str]| {
+        let config_dir = TempDir::new().expect("temp config dir");
+        let output = cargo_bin_cmd!("knotter")
+            .env("XDG_CONFIG_HOME", config_dir.path())
+            .env("KNOTTER_TEST_NOW_UTC", "1896134400")
+            .env("KNOTTER_ALLOW_TEST_NOW_UTC", "1")
+            .args(["--db-path", db_path.to_str().expect("db path")])
+            .args(args)
+            .output()
+            .expect("run command");
+        assert!(output.status.success(), "command failed: {:?}", output);
+        String::from_utf8(output.stdout).expect("utf8")
+    };
+
+    let stdout = run(&["remind", "--max-per-bucket", "1"]);
+    let hint = stdout
+        .split('`')
+        .nth(1)
+        .unwrap_or_else(|| panic!("no hint in: {stdout}"));
+    let args: Vec<&str> = hint.split_whitespace().collect();
+    assert_eq!(args[0], "knotter");
+    let listed = run(&args[1..]);
+    assert!(listed.contains("Grace Hopper"), "{hint}: {listed}");
+}
+
+#[test]
+fn cli_remind_no_notify_overrides_config() {
+    let temp = TempDir::new().expect("temp dir");
+    let db_path = temp.path().join("knotter.sqlite3");
+    let config_path = temp.path().join("config.toml");
+
+    std::fs::write(
+        &config_path,
+        "due_soon_days = 3650\n[notifications]\nenabled = true\nbackend = \"desktop\"\n",
+    )
+    .expect("write config");
+    restrict_config_permissions(&config_path);
+
+    run_cmd_with_config(
+        &db_path,
+        &config_path,
+        &["add-contact", "--name", "Ada Lovelace"],
+    );
+
+    let list = run_cmd_json_with_config(&db_path, &config_path, &["list"]);
+    let items = list.as_array().expect("array");
+    let id = items[0]["id"].as_str().expect("id").to_string();
+    run_cmd_with_config(
+        &db_path,
+        &config_path,
+        &["schedule", &id, "--at", "2030-01-02"],
+    );
+
+    let output = run_cmd_with_config(&db_path, &config_path, &["remind", "--no-notify"]);
+    assert!(output.contains("soon:"));
+    assert!(output.contains("Ada Lovelace"));
+}
+
+#[test]
+fn cli_remind_config_stdout_backend_prints_full_list() {
+    let temp = TempDir::new().expect("temp dir");
+    let db_path = temp.path().join("knotter.sqlite3");
+    let config_path = temp.path().join("config.toml");
+
+    std::fs::write(
+        &config_path,
+        "due_soon_days = 3650\n[notifications]\nenabled = true\nbackend = \"stdout\"\n",
+    )
+    .expect("write config");
+    restrict_config_permissions(&config_path);
+
+    run_cmd_with_config(
+        &db_path,
+        &config_path,
+        &["add-contact", "--name", "Ada Lovelace"],
+    );
+
+    let list = run_cmd_json_with_config(&db_path, &config_path, &["list"]);
+    let items = list.as_array().expect("array");
+    let id = items[0]["id"].as_str().expect("id").to_string();
+    run_cmd_with_config(
+        &db_path,
+        &config_path,
+        &["schedule", &id, "--at", "2030-01-02"],
+    );
+
+    let output = run_cmd_with_config(&db_path, &config_path, &["remind"]);
+    assert!(output.contains("soon:"));
+    assert!(output.contains("Ada Lovelace"));
+}
+
+#[test]
+fn cli_remind_notify_json_fails_without_desktop_feature() {
+    if cfg!(feature = "desktop-notify") {
+        return;
+    }
+
+    let temp = TempDir::new().expect("temp dir");
+    let db_path = temp.path().join("knotter.sqlite3");
+
+    run_cmd(&db_path, &["add-contact", "--name", "Ada Lovelace"]);
+
+    let list = run_cmd_json(&db_path, &["list"]);
+    let items = list.as_array().expect("array");
+    let id = items[0]["id"].as_str().expect("id").to_string();
+
+    run_cmd(&db_path, &["schedule", &id, "--at", "2030-01-02"]);
+
+    let output = run_cmd_output(
+        &db_path,
+        &[
+            "--json",
+            "remind",
+            "--notify",
+            "--soon-days",
+            &MAX_SOON_DAYS.to_string(),
+        ],
+    );
+    assert!(!output.status.success());
+    let parsed: Value = serde_json::from_slice(&output.stdout).expect("parse json");
+    let soon = parsed["soon"].as_array().expect("soon array");
+    assert_eq!(soon.len(), 1);
+}
+
+#[test]
+fn cli_remind_email_backend_fails_without_feature() {
+    if cfg!(feature = "email-notify") {
+        return;
+    }
+
+    let temp = TempDir::new().expect("temp dir");
+    let db_path = temp.path().join("knotter.sqlite3");
+    let config_path = temp.path().join("config.toml");
+
+    std::fs::write(
+        &config_path,
+        "due_soon_days = 3650\n[notifications]\nenabled = true\nbackend = \"email\"\n\n[notifications.email]\nfrom = \"Knotter <knotter@example.com>\"\nto = [\"ada@example.com\"]\nsmtp_host = \"smtp.example.com\"\nsmtp_port = 587\nusername = \"user@example.com\"\npassword_env = \"KNOTTER_SMTP_PASSWORD\"\ntls = \"start-tls\"\ntimeout_seconds = 20\n",
+    )
+    .expect("write config");
+    restrict_config_permissions(&config_path);
+
+    run_cmd_with_config(
+        &db_path,
+        &config_path,
+        &["add-contact", "--name", "Ada Lovelace"],
+    );
+
+    let list = run_cmd_json_with_config(&db_path, &config_path, &["list"]);
+    let items = list.as_array().expect("array");
+    let id = items[0]["id"].as_str().expect("id").to_string();
+
+    run_cmd_with_config(
+        &db_path,
+        &config_path,
+        &["schedule", &id, "--at", "2030-01-02"],
+    );
+
+    let output = run_cmd_output_with_config(
+        &db_path,
+        &config_path,
+        &[
+            "--json",
+            "remind",
+            "--notify",
+            "--soon-days",
+            &MAX_SOON_DAYS.to_string(),
+        ],
+    );
+    assert!(!output.status.success());
+    let parsed: Value = serde_json::from_slice(&output.stdout).expect("parse json");
+    let soon = parsed["soon"].as_array().expect("soon array");
+    assert_eq!(soon.len(), 1);
+}
+
+#[test]
+fn cli_import_vcf_creates_contact() {
+    let temp = TempDir::new().expect("temp dir");
+    let db_path = temp.path().join("knotter.sqlite3");
+    let vcf_path = temp.path().join("contacts.vcf");
+
+    let vcf = "BEGIN:VCARD\nVERSION:3.0\nFN:Grace Hopper\nEMAIL:grace@example.com\nCATEGORIES:friends\nEND:VCARD\n";
+    std::fs::write(&vcf_path, vcf).expect("write vcf");
+
+    run_cmd(
+        &db_path,
+        &["import", "vcf", vcf_path.to_str().expect("path")],
+    );
+
+    let list = run_cmd_json(&db_path, &["list"]);
+    let items = list.as_array().expect("array");
+    assert_eq!(items.len(), 1);
+    assert_eq!(items[0]["display_name"], "Grace Hopper");
+}
+
+#[test]
+fn cli_import_vcf_applies_default_cadence_when_configured() {
+    let temp = TempDir::new().expect("temp dir");
+    let db_path = temp.path().join("knotter.sqlite3");
+    let vcf_path = temp.path().join("contacts.vcf");
+    let config_path = temp.path().join("config.toml");
+    std::fs::write(
+        &config_path,
+        "default_cadence_days = 45\n[contacts]\napply_default_cadence_on_import = true\n",
+    )
+    .expect("write config");
+    restrict_config_permissions(&config_path);
+
+    let vcf = "BEGIN:VCARD\nVERSION:3.0\nFN:Grace Hopper\nEMAIL:grace@example.com\nEND:VCARD\n";
+    std::fs::write(&vcf_path, vcf).expect("write vcf");
+
+    let output = run_cmd_with_config(
+        &db_path,
+        &config_path,
+        &["--json", "import", "vcf", vcf_path.to_str().expect("path")],
+    );
+    let report: Value = serde_json::from_str(&output).expect("parse json");
+    assert_eq!(report["created"], 1);
+    assert_eq!(report["default_cadence_applied"], 1);
+
+    let list = run_cmd_json(&db_path, &["list"]);
+    let items = list.as_array().expect("array");
+    let id = items[0]["id"].as_str().expect("id");
+    let contact = run_cmd_json(&db_path, &["show", id]);
+    assert_eq!(contact["cadence_days"], 45);
+    assert!(contact["next_touchpoint_at"].as_i64().is_some());
+}
+
+#[test]
+fn cli_import_vcf_directory_reports_per_file_and_limits_batch() {
+    let temp = TempDir::new().expect("temp dir");
+    let db_path = temp.path().join("knotter.sqlite3");
+    let dir = temp.path().join("cards");
+    std::fs::create_dir_all(dir.join("nested")).expect("create dirs");
+    std::fs::write(
+        dir.join("a.vcf"),
+        "BEGIN:VCARD\nVERSION:3.0\nFN:Ada Lovelace\nEMAIL:ada@example.com\nEND:VCARD\n\
+         BEGIN:VCARD\nVERSION:3.0\nEMAIL:nameless@example.com\nEND:VCARD\n",
+    )
+    .expect("write a");
+    std::fs::write(
+        dir.join("nested").join("b.VCF"),
+        "BEGIN:VCARD\nVERSION:3.0\nFN:Grace Hopper\nEND:VCARD\n",
+    )
+    .expect("write b");
+    std::fs::write(dir.join("broken.vcf"), [0xff, 0xfe, 0x00]).expect("write broken");
+    std::fs::write(dir.join("notes.txt"), "not a card").expect("write txt");
+    let dir_arg = dir.to_str().expect("dir path");
+
+    let report = run_cmd_json(&db_path, &["import", "vcf", "--dry-run", dir_arg]);
+    assert_eq!(report["created"], 2);
+    assert_eq!(report["skipped"], 1);
+    let files = report["files"].as_array().expect("files array");
+    assert_eq!(files.len(), 3);
+    assert!(files[0]["path"].as_str().unwrap().ends_with("a.vcf"));
+    assert_eq!(files[0]["created"], 1);
+    assert_eq!(files[0]["skipped"], 1);
+    assert!(files[1]["path"].as_str().unwrap().ends_with("broken.vcf"));
+    assert!(files[1]["error"].is_string());
+    assert!(files[2]["path"].as_str().unwrap().ends_with("b.VCF"));
+    let warnings = report["warnings"].as_array().expect("warnings");
+    assert!(warnings.iter().any(|warning| {
+        let warning = warning.as_str().unwrap();
+        warning.contains("a.vcf: ") && warning.contains("FN")
+    }));
+    assert!(warnings
+        .iter()
+        .any(|warning| warning.as_str().unwrap().contains("broken.vcf: ")));
+    assert!(run_cmd_json(&db_path, &["list"])
+        .as_array()
+        .expect("list")
+        .is_empty());
+
+    let report = run_cmd_json(&db_path, &["import", "vcf", "--limit", "1", dir_arg]);
+    assert_eq!(report["created"], 1);
+    let list = run_cmd_json(&db_path, &["list"]);
+    assert_eq!(list.as_array().expect("list").len(), 1);
+    assert_eq!(list[0]["display_name"], "Ada Lovelace");
+
+    let output = run_cmd_output(&db_path, &["import", "vcf", "--strict", dir_arg]);
+    assert!(!output.status.success());
+    let stderr = String::from_utf8(output.stderr).expect("utf8");
+    assert!(stderr.contains("broken.vcf"), "{stderr}");
+
+    // Named on its own, a broken file fails the import instead of being skipped.
+    let broken = dir.join("broken.vcf");
+    let output = run_cmd_output(
+        &db_path,
+        &["import", "vcf", broken.to_str().expect("broken path")],
+    );
+    assert_eq!(output.status.code(), Some(3));
+    let stderr = String::from_utf8(output.stderr).expect("utf8");
+    assert!(stderr.contains("broken.vcf"), "{stderr}");
+    let with_good = run_cmd_json(
+        &db_path,
+        &[
+            "import",
+            "vcf",
+            "--dry-run",
+            broken.to_str().expect("broken path"),
+            dir.join("a.vcf").to_str().expect("a path"),
+        ],
+    );
+    assert_eq!(with_good["files"].as_array().map(Vec::len), Some(2));
+    assert!(with_good["files"][0]["error"].is_string());
+    assert!(with_good["files"][1]["error"].is_null());
+}
+
+#[test]
+fn cli_import_vcf_dedupes_by_uid() {
+    let temp = TempDir::new().expect("temp dir");
+    let db_path = temp.path().join("knotter.sqlite3");
+    let vcf_path = temp.path().join("contacts.vcf");
+
+    let vcf =
+        "BEGIN:VCARD\nVERSION:3.0\nUID:abc-123\nFN:Grace Hopper\nORG:Navy;Computing\nEND:VCARD\n";
+    std::fs::write(&vcf_path, vcf).expect("write vcf");
+
+    run_cmd(
+        &db_path,
+        &["import", "vcf", vcf_path.to_str().expect("path")],
+    );
+
+    let list = run_cmd_json(&db_path, &["list"]);
+    let items = list.as_array().expect("array");
+    assert_eq!(items.len(), 1);
+    assert_eq!(items[0]["display_name"], "Grace Hopper");
+    assert_eq!(items[0]["organization"], "Navy");
+
+    let vcf = "BEGIN:VCARD\nVERSION:3.0\nUID:abc-123\nFN:Grace H.\nORG:Univac\nEND:VCARD\n";
+    std::fs::write(&vcf_path, vcf).expect("write vcf");
+
+    run_cmd(
+        &db_path,
+        &["import", "vcf", vcf_path.to_str().expect("path")],
+    );
+
+    let list = run_cmd_json(&db_path, &["list"]);
+    let items = list.as_array().expect("array");
+    assert_eq!(items.len(), 1);
+    assert_eq!(items[0]["display_name"], "Grace H.");
+    assert_eq!(items[0]["organization"], "Univac");
+}
+
+#[test]
+fn cli_import_vcf_updates_when_emails_match_active_and_archived() {
+    let temp = TempDir::new().expect("temp dir");
+    let db_path = temp.path().join("knotter.sqlite3");
+    let vcf_path = temp.path().join("contacts.vcf");
+    let store = Store::open(&db_path).expect("open store");
+    store.migrate().expect("migrate");
+    let now = 1_700_000_000;
+
+    store
+        .contacts()
+        .create(
+            now,
+            knotter_store::repo::ContactNew {
+                display_name: "Active".to_string(),
+                email: Some("active@example.com".to_string()),
+                phone: None,
+                handle: None,
+                timezone: None,
+                next_touchpoint_at: None,
+                cadence_days: None,
+                archived_at: None,
+                organization: None,
+            },
+        )
+        .expect("create active");
+    store
+        .contacts()
+        .create(
+            now,
+            knotter_store::repo::ContactNew {
+                display_name: "Archived".to_string(),
+                email: Some("archived@example.com".to_string()),
+                phone: None,
+                handle: None,
+                timezone: None,
+                next_touchpoint_at: None,
+                cadence_days: None,
+                archived_at: Some(now),
+                organization: None,
+            },
+        )
+        .expect("create archived");
+
+    let vcf = "BEGIN:VCARD\nVERSION:3.0\nFN:Mixed\nEMAIL:active@example.com\nEMAIL:archived@example.com\nEND:VCARD\n";
+    std::fs::write(&vcf_path, vcf).expect("write vcf");
+
+    let report = run_cmd_json(
+        &db_path,
+        &["import", "vcf", vcf_path.to_str().expect("path")],
+    );
+    assert_eq!(report["created"], 0);
+    assert_eq!(report["updated"], 1);
+    assert_eq!(report["skipped"], 0);
+    assert_eq!(report["merge_candidates_created"], 0);
+
+    let store = Store::open(&db_path).expect("open store");
+    let candidates = store
+        .merge_candidates()
+        .list(&knotter_store::repo::MergeCandidateQuery::default())
+        .expect("list candidates");
+    assert!(candidates.is_empty());
 }
 
 #[test]
-fn cli_schedule_date_only_sets_end_of_day() {
+fn cli_import_vcf_logs_sync_history_only_for_real_changes() {
     let temp = TempDir::new().expect("temp dir");
     let db_path = temp.path().join("knotter.sqlite3");
+    let vcf_path = temp.path().join("contacts.vcf");
+    let vcf_arg = vcf_path.to_str().expect("path");
 
-    run_cmd(&db_path, &["add-contact", "--name", "Ada Lovelace"]);
+    let vcf = "BEGIN:VCARD\nVERSION:3.0\nFN:Ada Lovelace\nEMAIL:ada@example.com\nEND:VCARD\n";
+    std::fs::write(&vcf_path, vcf).expect("write vcf");
+    run_cmd(&db_path, &["import", "vcf", vcf_arg]);
     let list = run_cmd_json(&db_path, &["list"]);
-    let items = list.as_array().expect("array");
-    let id = items[0]["id"].as_str().expect("id").to_string();
+    let id = list[0]["id"].as_str().expect("id").to_string();
 
-    run_cmd(&db_path, &["schedule", &id, "--at", "2030-01-15"]);
+    let report = run_cmd_json(&db_path, &["import", "vcf", vcf_arg]);
+    assert_eq!(report["updated"], 0);
+    assert_eq!(report["unchanged"], 1);
+    let detail = run_cmd_json(&db_path, &["show", &id, "--history"]);
+    assert!(detail.get("sync_history").is_none());
 
-    let detail = run_cmd_json(&db_path, &["show", &id]);
-    let (timestamp, precision) =
-        knotter_core::time::parse_local_timestamp_with_precision("2030-01-15").expect("parse date");
-    let expected = knotter_core::rules::ensure_future_timestamp_with_precision(
-        knotter_core::time::now_utc(),
-        timestamp,
-        precision,
-    )
-    .expect("expected schedule");
-    assert_eq!(detail["next_touchpoint_at"], expected);
+    let vcf = "BEGIN:VCARD\nVERSION:3.0\nFN:Ada King\nEMAIL:ada@example.com\nTEL:555\nEND:VCARD\n";
+    std::fs::write(&vcf_path, vcf).expect("write vcf");
+    let report = run_cmd_json(&db_path, &["import", "vcf", "--dry-run", vcf_arg]);
+    assert_eq!(report["updated"], 1);
+    assert_eq!(report["unchanged"], 0);
+    let detail = run_cmd_json(&db_path, &["show", &id, "--history"]);
+    assert_eq!(detail["display_name"], "Ada Lovelace");
+    assert!(detail.get("sync_history").is_none());
+
+    let report = run_cmd_json(&db_path, &["import", "vcf", vcf_arg]);
+    assert_eq!(report["updated"], 1);
+    let detail = run_cmd_json(&db_path, &["show", &id, "--history"]);
+    assert_eq!(detail["display_name"], "Ada King");
+    let history = detail["sync_history"].as_array().expect("history");
+    assert_eq!(history.len(), 1);
+    assert_eq!(history[0]["source"], "vcard");
+    assert_eq!(history[0]["fields"], serde_json::json!(["name", "phone"]));
+
+    let output = run_cmd(&db_path, &["show", &id, "--history"]);
+    assert!(output.contains("sync history:"));
+    assert!(output.contains("vcard  name, phone"));
 }
 
 #[test]
-fn cli_remind_includes_soon_contact() {
+fn cli_import_vcf_keeps_manual_edits_unless_overwrite_manual() {
     let temp = TempDir::new().expect("temp dir");
     let db_path = temp.path().join("knotter.sqlite3");
+    let vcf_path = temp.path().join("contacts.vcf");
 
-    run_cmd(&db_path, &["add-contact", "--name", "Ada Lovelace"]);
+    let vcf =
+        "BEGIN:VCARD\nVERSION:3.0\nFN:Grace Hopper\nEMAIL:grace@example.com\nTEL:111\nEND:VCARD\n";
+    std::fs::write(&vcf_path, vcf).expect("write vcf");
+    let vcf_arg = vcf_path.to_str().expect("path");
+    run_cmd(&db_path, &["import", "vcf", vcf_arg]);
 
     let list = run_cmd_json(&db_path, &["list"]);
-    let items = list.as_array().expect("array");
-    let id = items[0]["id"].as_str().expect("id").to_string();
-
-    let scheduled = "2030-01-02";
-    run_cmd(&db_path, &["schedule", &id, "--at", scheduled]);
+    let id = list[0]["id"].as_str().expect("id").to_string();
+    run_cmd(&db_path, &["edit-contact", &id, "--phone", "222"]);
 
-    let remind = run_cmd_json(
-        &db_path,
-        &["remind", "--soon-days", &MAX_SOON_DAYS.to_string()],
-    );
-    let soon = remind["soon"].as_array().expect("soon array");
-    assert_eq!(soon.len(), 1);
-    assert_eq!(soon[0]["id"], id);
+    let vcf = "BEGIN:VCARD\nVERSION:3.0\nFN:Grace B. Hopper\nEMAIL:grace@example.com\nTEL:333\nEND:VCARD\n";
+    std::fs::write(&vcf_path, vcf).expect("write vcf");
+    let report = run_cmd_json(&db_path, &["import", "vcf", vcf_arg]);
+    assert_eq!(report["updated"], 1);
+    let warnings = report["warnings"].as_array().expect("warnings");
+    assert!(warnings.iter().any(|warning| warning
+        .as_str()
+        .expect("warning")
+        .contains("kept manual phone")));
+
+    let detail = run_cmd_json(&db_path, &["show", &id, "--provenance"]);
+    assert_eq!(detail["display_name"], "Grace B. Hopper");
+    assert_eq!(detail["phone"], "222");
+    let provenance = detail["provenance"].as_array().expect("provenance");
+    let source_of = |field: &str| {
+        provenance
+            .iter()
+            .find(|entry| entry["field"] == field)
+            .map(|entry| entry["source"].as_str().expect("source").to_string())
+    };
+    assert_eq!(source_of("phone").as_deref(), Some("manual"));
+    assert_eq!(source_of("display_name").as_deref(), Some("vcard"));
+
+    let plain = run_cmd_json(&db_path, &["show", &id]);
+    assert!(plain.get("provenance").is_none());
+
+    run_cmd(&db_path, &["import", "vcf", vcf_arg, "--overwrite-manual"]);
+    let detail = run_cmd_json(&db_path, &["show", &id, "--provenance"]);
+    assert_eq!(detail["phone"], "333");
+    let human = run_cmd(&db_path, &["show", &id, "--provenance"]);
+    assert!(human.contains("provenance:"));
+    assert!(human.contains("  phone  vcard  "));
 }
 
 #[test]
-fn cli_date_add_list_and_remind_includes_today() {
+fn cli_rejects_and_reports_skewed_interaction_dates() {
     let temp = TempDir::new().expect("temp dir");
     let db_path = temp.path().join("knotter.sqlite3");
 
-    run_cmd(&db_path, &["add-contact", "--name", "Ada Lovelace"]);
-    let list = run_cmd_json(&db_path, &["list"]);
-    let items = list.as_array().expect("array");
-    let id = items[0]["id"].as_str().expect("id").to_string();
-
-    let fixed_local = Local
-        .with_ymd_and_hms(2030, 1, 15, 12, 0, 0)
-        .single()
-        .expect("local time");
-    let date_str = fixed_local.format("%Y-%m-%d").to_string();
-    let now_env = fixed_local.with_timezone(&Utc).timestamp().to_string();
+    let ada = run_cmd_json(&db_path, &["add-contact", "--name", "Ada"]);
+    let ada_id = ada["id"].as_str().expect("id").to_string();
 
-    run_cmd(
+    let output = run_cmd_output(
         &db_path,
-        &["date", "add", &id, "--kind", "birthday", "--on", &date_str],
+        &["add-note", &ada_id, "--when", "1970-01-02", "--note", "x"],
     );
+    assert_eq!(output.status.code(), Some(3));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("before 1990-01-01"));
 
-    let dates = run_cmd_json(&db_path, &["date", "ls", &id]);
-    let dates = dates.as_array().expect("dates array");
-    assert_eq!(dates.len(), 1);
-    assert_eq!(dates[0]["kind"], "birthday");
-
-    let remind = run_cmd_json_with_env(
+    let far = (Local::now() + Duration::days(10))
+        .format("%Y-%m-%d")
+        .to_string();
+    let output = run_cmd_output(&db_path, &["touch", &ada_id, "--when", &far]);
+    assert_eq!(output.status.code(), Some(3));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("2 day(s) in the future"));
+    run_cmd(
         &db_path,
-        &["remind"],
-        &[
-            ("KNOTTER_TEST_NOW_UTC", now_env.as_str()),
-            ("KNOTTER_ALLOW_TEST_NOW_UTC", "1"),
-        ],
+        &["touch", &ada_id, "--when", &tomorrow_local_date()],
     );
-    let dates_today = remind["dates_today"].as_array().expect("dates_today array");
-    assert_eq!(dates_today.len(), 1);
-    assert_eq!(dates_today[0]["display_name"], "Ada Lovelace");
+
+    {
+        let store = Store::open(&db_path).expect("open store");
+        store
+            .connection()
+            .execute(
+                "UPDATE interactions SET occurred_at = 2147483647 WHERE contact_id = ?1;",
+                [ada_id.as_str()],
+            )
+            .expect("skew interaction");
+    }
+    let report = run_cmd_json(&db_path, &["doctor"]);
+    let finding = report["findings"]
+        .as_array()
+        .expect("findings")
+        .iter()
+        .find(|finding| finding["check"] == "interaction_dates")
+        .expect("interaction_dates finding");
+    assert_eq!(finding["count"], 1);
+    assert_eq!(finding["severity"], "warning");
+    assert_eq!(report["ok"], false);
 }
 
 #[test]
-fn cli_remind_uses_config_due_soon_days() {
+fn cli_doctor_reports_and_fixes_orphans() {
     let temp = TempDir::new().expect("temp dir");
     let db_path = temp.path().join("knotter.sqlite3");
-    let config_path = temp.path().join("config.toml");
-
-    std::fs::write(&config_path, "due_soon_days = 0\n").expect("write config");
-    restrict_config_permissions(&config_path);
-
-    run_cmd_with_config(
-        &db_path,
-        &config_path,
-        &["add-contact", "--name", "Ada Lovelace"],
-    );
 
-    let list = run_cmd_json_with_config(&db_path, &config_path, &["list"]);
-    let items = list.as_array().expect("array");
-    let id = items[0]["id"].as_str().expect("id").to_string();
+    let clean = run_cmd(&db_path, &["doctor"]);
+    assert_eq!(clean, "doctor: no problems found\n");
 
-    let tomorrow = Local::now()
-        .date_naive()
-        .checked_add_signed(Duration::days(1))
-        .expect("tomorrow");
-    let scheduled = tomorrow.format("%Y-%m-%d").to_string();
-    run_cmd_with_config(
+    let ada = run_cmd_json(
         &db_path,
-        &config_path,
-        &["schedule", &id, "--at", &scheduled],
+        &["add-contact", "--name", "Ada", "--email", "ada@example.com"],
     );
+    let ada_id = ada["id"].as_str().expect("id").to_string();
+    run_cmd(&db_path, &["add-note", &ada_id, "--note", "hello"]);
+    {
+        let store = Store::open(&db_path).expect("open store");
+        let conn = store.connection();
+        conn.pragma_update(None, "foreign_keys", "OFF")
+            .expect("disable fks");
+        conn.execute("DELETE FROM contacts WHERE id = ?1;", [ada_id.as_str()])
+            .expect("delete contact");
+        conn.execute(
+            "INSERT INTO email_sync_state (account, mailbox, last_uid) VALUES ('old', 'INBOX', 5);",
+            [],
+        )
+        .expect("insert sync state");
+    }
 
-    let remind = run_cmd_json_with_config(&db_path, &config_path, &["remind"]);
-    assert!(remind["overdue"].as_array().expect("overdue").is_empty());
-    assert!(remind["today"].as_array().expect("today").is_empty());
-    assert!(remind["soon"].as_array().expect("soon").is_empty());
+    let report = run_cmd_json(&db_path, &["doctor"]);
+    assert_eq!(report["ok"], false);
+    let findings = report["findings"].as_array().expect("findings");
+    let orphans: Vec<&Value> = findings
+        .iter()
+        .filter(|finding| finding["check"] == "orphans")
+        .collect();
+    assert_eq!(orphans.len(), 2);
+    assert!(orphans
+        .iter()
+        .all(|finding| finding["severity"] == "warning" && finding["fixed"] == false));
+    assert!(findings
+        .iter()
+        .any(|finding| finding["check"] == "sync_state"
+            && finding["message"]
+                .as_str()
+                .expect("message")
+                .contains("old")));
+
+    let human = run_cmd(&db_path, &["doctor"]);
+    assert!(human.contains("[warning] orphans: 1 interactions row(s)"));
+    assert!(human.contains("knotter doctor --fix"));
+
+    let fixed = run_cmd_json(&db_path, &["doctor", "--fix"]);
+    let findings = fixed["findings"].as_array().expect("findings");
+    assert!(findings
+        .iter()
+        .filter(|finding| finding["check"] == "orphans")
+        .all(|finding| finding["fixed"] == true));
+    // Stale sync state is reported but never removed automatically.
+    assert_eq!(fixed["ok"], false);
+
+    let after = run_cmd_json(&db_path, &["doctor"]);
+    let findings = after["findings"].as_array().expect("findings");
+    assert!(findings
+        .iter()
+        .all(|finding| finding["check"] == "sync_state"));
 }
 
 #[test]
-fn cli_remind_notification_falls_back_to_random_contacts_when_no_reminders() {
+fn cli_export_vcf_writes_file() {
     let temp = TempDir::new().expect("temp dir");
     let db_path = temp.path().join("knotter.sqlite3");
-    let config_path = temp.path().join("config.toml");
+    let out_path = temp.path().join("export.vcf");
 
-    std::fs::write(
-        &config_path,
-        // Backwards-compat: old key name is still accepted.
-        "[notifications]\nenabled = true\nbackend = \"stdout\"\nrandom_contacts_if_no_dates_today = 10\n",
-    )
-    .expect("write config");
-    restrict_config_permissions(&config_path);
+    run_cmd(&db_path, &["add-contact", "--name", "Ada Lovelace"]);
 
-    run_cmd_with_config(
-        &db_path,
-        &config_path,
-        &["add-contact", "--name", "Ada Lovelace"],
-    );
-    run_cmd_with_config(
+    run_cmd(
         &db_path,
-        &config_path,
-        &["add-contact", "--name", "Grace Hopper"],
+        &["export", "vcf", "--out", out_path.to_str().expect("path")],
     );
 
-    let output = run_cmd_with_config(&db_path, &config_path, &["remind"]);
-    assert!(output.contains("random contacts:"), "output: {output}");
-    assert!(output.contains("Ada Lovelace"), "output: {output}");
-    assert!(output.contains("Grace Hopper"), "output: {output}");
-    assert!(!output.contains("no reminders"), "output: {output}");
+    let contents = std::fs::read_to_string(&out_path).expect("read vcf");
+    assert!(contents.contains("BEGIN:VCARD"));
+    assert!(contents.contains("FN:Ada Lovelace"));
 }
 
 #[test]
-fn cli_remind_notification_does_not_add_random_contacts_when_there_are_reminders() {
+fn cli_export_vcf_filter_and_split_by_tag() {
     let temp = TempDir::new().expect("temp dir");
     let db_path = temp.path().join("knotter.sqlite3");
-    let config_path = temp.path().join("config.toml");
-
-    std::fs::write(
-        &config_path,
-        "[notifications]\nenabled = true\nbackend = \"stdout\"\nrandom_contacts_if_no_reminders = 10\n",
-    )
-    .expect("write config");
-    restrict_config_permissions(&config_path);
+    let out_path = temp.path().join("family.vcf");
+    let out_dir = temp.path().join("by-tag");
 
-    run_cmd_with_config(
+    run_cmd(
         &db_path,
-        &config_path,
-        &["add-contact", "--name", "Ada Lovelace"],
+        &["add-contact", "--name", "Ada Lovelace", "--tag", "family"],
     );
-    let list = run_cmd_json_with_config(&db_path, &config_path, &["list"]);
-    let items = list.as_array().expect("array");
-    let id = items[0]["id"].as_str().expect("id").to_string();
-
-    let fixed_local = Local
-        .with_ymd_and_hms(2030, 1, 15, 12, 0, 0)
-        .single()
-        .expect("local time");
-    let date_str = fixed_local.format("%Y-%m-%d").to_string();
-    let now_env = fixed_local.with_timezone(&Utc).timestamp().to_string();
-
-    // Schedule a touchpoint for "today" relative to the fixed now, so reminders are non-empty.
-    run_cmd_with_config(
+    run_cmd(
         &db_path,
-        &config_path,
-        &["schedule", &id, "--at", &date_str],
+        &[
+            "add-contact",
+            "--name",
+            "Grace Hopper",
+            "--tag",
+            "family",
+            "--tag",
+            "work/navy",
+        ],
     );
+    run_cmd(&db_path, &["add-contact", "--name", "Alan Turing"]);
 
-    let output = {
-        let config_dir = TempDir::new().expect("temp config dir");
-        let output = cargo_bin_cmd!("knotter")
-            .env("XDG_CONFIG_HOME", config_dir.path())
-            .env("KNOTTER_TEST_NOW_UTC", now_env.as_str())
-            .env("KNOTTER_ALLOW_TEST_NOW_UTC", "1")
-            .args([
-                "--db-path",
-                db_path.to_str().expect("db path"),
-                "--config",
-                config_path.to_str().expect("config path"),
-            ])
-            .args(["remind"])
-            .output()
-            .expect("run command");
-        assert!(output.status.success(), "command failed: {:?}", output);
-        String::from_utf8(output.stdout).expect("utf8")
-    };
-
-    assert!(
-        output.contains("today:") || output.contains("overdue:") || output.contains("soon:"),
-        "output: {output}"
+    let report = run_cmd_json(
+        &db_path,
+        &[
+            "export",
+            "vcf",
+            "--filter",
+            "#family",
+            "--out",
+            out_path.to_str().expect("path"),
+        ],
     );
-    assert!(!output.contains("random contacts:"), "output: {output}");
-}
-
-#[test]
-fn cli_remind_no_notify_overrides_config() {
-    let temp = TempDir::new().expect("temp dir");
-    let db_path = temp.path().join("knotter.sqlite3");
-    let config_path = temp.path().join("config.toml");
-
-    std::fs::write(
-        &config_path,
-        "due_soon_days = 3650\n[notifications]\nenabled = true\nbackend = \"desktop\"\n",
-    )
-    .expect("write config");
-    restrict_config_permissions(&config_path);
+    assert_eq!(report["count"], 2);
+    let contents = std::fs::read_to_string(&out_path).expect("read vcf");
+    assert!(contents.contains("FN:Ada Lovelace"));
+    assert!(contents.contains("FN:Grace Hopper"));
+    assert!(!contents.contains("FN:Alan Turing"));
 
-    run_cmd_with_config(
+    let report = run_cmd_json(
         &db_path,
-        &config_path,
-        &["add-contact", "--name", "Ada Lovelace"],
+        &[
+            "export",
+            "vcf",
+            "--split-by-tag",
+            "--out-dir",
+            out_dir.to_str().expect("path"),
+        ],
+    );
+    assert_eq!(report["count"], 3);
+    let files: Vec<(String, u64)> = report["files"]
+        .as_array()
+        .expect("files")
+        .iter()
+        .map(|file| {
+            let path = std::path::PathBuf::from(file["path"].as_str().expect("path"));
+            (
+                path.file_name()
+                    .expect("file name")
+                    .to_string_lossy()
+                    .into_owned(),
+                file["count"].as_u64().expect("count"),
+            )
+        })
+        .collect();
+    assert_eq!(
+        files,
+        vec![
+            ("family.vcf".to_string(), 2),
+            ("work_navy.vcf".to_string(), 1),
+            ("untagged.vcf".to_string(), 1),
+        ]
     );
+    let navy = std::fs::read_to_string(out_dir.join("work_navy.vcf")).expect("read vcf");
+    assert!(navy.contains("FN:Grace Hopper"));
+    let untagged = std::fs::read_to_string(out_dir.join("untagged.vcf")).expect("read vcf");
+    assert!(untagged.contains("FN:Alan Turing"));
+    assert!(!untagged.contains("FN:Ada Lovelace"));
 
-    let list = run_cmd_json_with_config(&db_path, &config_path, &["list"]);
-    let items = list.as_array().expect("array");
-    let id = items[0]["id"].as_str().expect("id").to_string();
-    run_cmd_with_config(
+    let output = run_cmd(
         &db_path,
-        &config_path,
-        &["schedule", &id, "--at", "2030-01-02"],
+        &[
+            "export",
+            "vcf",
+            "--filter",
+            "#family",
+            "--split-by-tag",
+            "--out-dir",
+            out_dir.to_str().expect("path"),
+        ],
+    );
+    assert!(
+        output.starts_with("Exported 2 contacts to 2 files in "),
+        "{output}"
     );
-
-    let output = run_cmd_with_config(&db_path, &config_path, &["remind", "--no-notify"]);
-    assert!(output.contains("soon:"));
-    assert!(output.contains("Ada Lovelace"));
+    assert!(output.contains("family.vcf  2"), "{output}");
 }
 
 #[test]
-fn cli_remind_config_stdout_backend_prints_full_list() {
+fn cli_export_ics_writes_file() {
     let temp = TempDir::new().expect("temp dir");
     let db_path = temp.path().join("knotter.sqlite3");
-    let config_path = temp.path().join("config.toml");
-
-    std::fs::write(
-        &config_path,
-        "due_soon_days = 3650\n[notifications]\nenabled = true\nbackend = \"stdout\"\n",
-    )
-    .expect("write config");
-    restrict_config_permissions(&config_path);
-
-    run_cmd_with_config(
-        &db_path,
-        &config_path,
-        &["add-contact", "--name", "Ada Lovelace"],
-    );
+    let out_path = temp.path().join("export.ics");
 
-    let list = run_cmd_json_with_config(&db_path, &config_path, &["list"]);
+    run_cmd(&db_path, &["add-contact", "--name", "Ada Lovelace"]);
+    let list = run_cmd_json(&db_path, &["list"]);
     let items = list.as_array().expect("array");
     let id = items[0]["id"].as_str().expect("id").to_string();
-    run_cmd_with_config(
+    run_cmd(&db_path, &["schedule", &id, "--at", "2030-01-01"]);
+
+    run_cmd(
         &db_path,
-        &config_path,
-        &["schedule", &id, "--at", "2030-01-02"],
+        &["export", "ics", "--out", out_path.to_str().expect("path")],
     );
 
-    let output = run_cmd_with_config(&db_path, &config_path, &["remind"]);
-    assert!(output.contains("soon:"));
-    assert!(output.contains("Ada Lovelace"));
+    let contents = std::fs::read_to_string(&out_path).expect("read ics");
+    assert!(contents.contains("BEGIN:VEVENT"));
+    assert!(contents.contains("SUMMARY:Reach out to Ada Lovelace"));
 }
 
 #[test]
-fn cli_remind_notify_json_fails_without_desktop_feature() {
-    if cfg!(feature = "desktop-notify") {
-        return;
-    }
-
+fn cli_exports_respect_archived_state() {
     let temp = TempDir::new().expect("temp dir");
     let db_path = temp.path().join("knotter.sqlite3");
 
-    run_cmd(&db_path, &["add-contact", "--name", "Ada Lovelace"]);
-
-    let list = run_cmd_json(&db_path, &["list"]);
-    let items = list.as_array().expect("array");
-    let id = items[0]["id"].as_str().expect("id").to_string();
-
-    run_cmd(&db_path, &["schedule", &id, "--at", "2030-01-02"]);
-
-    let output = run_cmd_output(
+    let ada = run_cmd_json(&db_path, &["add-contact", "--name", "Ada Lovelace"]);
+    let ada_id = ada["id"].as_str().expect("id").to_string();
+    let grace = run_cmd_json(&db_path, &["add-contact", "--name", "Grace Hopper"]);
+    let grace_id = grace["id"].as_str().expect("id").to_string();
+    for id in [&ada_id, &grace_id] {
+        run_cmd(&db_path, &["schedule", id, "--at", "2030-01-01"]);
+    }
+    run_cmd(
         &db_path,
         &[
-            "--json",
-            "remind",
-            "--notify",
-            "--soon-days",
-            &MAX_SOON_DAYS.to_string(),
+            "date", "add", &grace_id, "--kind", "birthday", "--on", "12-09",
         ],
     );
-    assert!(!output.status.success());
-    let parsed: Value = serde_json::from_slice(&output.stdout).expect("parse json");
-    let soon = parsed["soon"].as_array().expect("soon array");
-    assert_eq!(soon.len(), 1);
+    run_cmd(&db_path, &["archive-contact", &grace_id]);
+
+    // vcf: active only unless asked, and an explicit filter selector wins.
+    let vcf = run_cmd(&db_path, &["export", "vcf"]);
+    assert!(vcf.contains("FN:Ada Lovelace"));
+    assert!(!vcf.contains("FN:Grace Hopper"));
+    let vcf = run_cmd(&db_path, &["export", "vcf", "--include-archived"]);
+    assert!(vcf.contains("FN:Ada Lovelace"));
+    assert!(vcf.contains("FN:Grace Hopper"));
+    let vcf = run_cmd(
+        &db_path,
+        &[
+            "export",
+            "vcf",
+            "--include-archived",
+            "--filter",
+            "archived:false",
+        ],
+    );
+    assert!(!vcf.contains("FN:Grace Hopper"));
+
+    // ics: archived contacts add birthdays, never touchpoints.
+    let ics = run_cmd(&db_path, &["export", "ics"]);
+    assert!(ics.contains("SUMMARY:Reach out to Ada Lovelace"));
+    assert!(!ics.contains("Grace Hopper"));
+    let ics = run_cmd(&db_path, &["export", "ics", "--include-archived"]);
+    assert!(ics.contains("SUMMARY:Reach out to Ada Lovelace"));
+    assert!(ics.contains("SUMMARY:Grace Hopper's birthday"));
+    assert!(!ics.contains("Reach out to Grace Hopper"));
+
+    // json: archived included unless excluded.
+    let names = |snapshot: serde_json::Value| -> Vec<String> {
+        snapshot["contacts"]
+            .as_array()
+            .expect("contacts")
+            .iter()
+            .map(|contact| contact["display_name"].as_str().expect("name").to_string())
+            .collect()
+    };
+    let all = names(run_cmd_json(&db_path, &["export", "json"]));
+    assert_eq!(all.len(), 2);
+    assert!(all.contains(&"Grace Hopper".to_string()));
+    let active = names(run_cmd_json(
+        &db_path,
+        &["export", "json", "--exclude-archived"],
+    ));
+    assert_eq!(active, vec!["Ada Lovelace".to_string()]);
 }
 
 #[test]
-fn cli_remind_email_backend_fails_without_feature() {
-    if cfg!(feature = "email-notify") {
-        return;
-    }
+fn cli_invalid_filter_returns_exit_code_3() {
+    let temp = TempDir::new().expect("temp dir");
+    let db_path = temp.path().join("knotter.sqlite3");
+
+    let output = run_cmd_output(&db_path, &["list", "--filter", "due:later"]);
+    assert_eq!(output.status.code(), Some(3));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("invalid due selector"));
+}
 
+#[test]
+fn cli_show_pages_and_windows_interactions() {
     let temp = TempDir::new().expect("temp dir");
     let db_path = temp.path().join("knotter.sqlite3");
-    let config_path = temp.path().join("config.toml");
 
-    std::fs::write(
-        &config_path,
-        "due_soon_days = 3650\n[notifications]\nenabled = true\nbackend = \"email\"\n\n[notifications.email]\nfrom = \"Knotter <knotter@example.com>\"\nto = [\"ada@example.com\"]\nsmtp_host = \"smtp.example.com\"\nsmtp_port = 587\nusername = \"user@example.com\"\npassword_env = \"KNOTTER_SMTP_PASSWORD\"\ntls = \"start-tls\"\ntimeout_seconds = 20\n",
-    )
-    .expect("write config");
-    restrict_config_permissions(&config_path);
+    let created = run_cmd_json(&db_path, &["add-contact", "--name", "Ada Lovelace"]);
+    let id = created["id"].as_str().expect("id").to_string();
+    for day in 1..=5 {
+        let when = format!("2024-03-0{day} 12:00");
+        let note = format!("day {day}");
+        run_cmd(
+            &db_path,
+            &["add-note", &id, "--note", &note, "--when", &when],
+        );
+    }
 
-    run_cmd_with_config(
-        &db_path,
-        &config_path,
-        &["add-contact", "--name", "Ada Lovelace"],
-    );
+    let notes = |detail: &Value| -> Vec<String> {
+        detail["recent_interactions"]
+            .as_array()
+            .expect("interactions")
+            .iter()
+            .map(|item| item["note"].as_str().expect("note").to_string())
+            .collect()
+    };
 
-    let list = run_cmd_json_with_config(&db_path, &config_path, &["list"]);
-    let items = list.as_array().expect("array");
-    let id = items[0]["id"].as_str().expect("id").to_string();
+    let page = run_cmd_json(&db_path, &["show", &id, "--limit", "2", "--offset", "1"]);
+    assert_eq!(page["interactions_total"], 5);
+    assert_eq!(notes(&page), vec!["day 4", "day 3"]);
 
-    run_cmd_with_config(
+    let all = run_cmd_json(&db_path, &["show", &id, "--all", "--offset", "3"]);
+    assert_eq!(notes(&all), vec!["day 2", "day 1"]);
+
+    let window = run_cmd_json(
         &db_path,
-        &config_path,
-        &["schedule", &id, "--at", "2030-01-02"],
+        &[
+            "show",
+            &id,
+            "--since",
+            "2024-03-02",
+            "--until",
+            "2024-03-04",
+        ],
     );
+    assert_eq!(window["interactions_total"], 3);
+    assert_eq!(notes(&window), vec!["day 4", "day 3", "day 2"]);
 
-    let output = run_cmd_output_with_config(
+    let text = run_cmd(&db_path, &["show", &id, "--limit", "2"]);
+    assert!(text.contains("interactions (1-2 of 5):"));
+    assert!(text.contains("--offset 2"));
+
+    let output = run_cmd_output(
         &db_path,
-        &config_path,
         &[
-            "--json",
-            "remind",
-            "--notify",
-            "--soon-days",
-            &MAX_SOON_DAYS.to_string(),
+            "show",
+            &id,
+            "--since",
+            "2024-03-04",
+            "--until",
+            "2024-03-02",
         ],
     );
-    assert!(!output.status.success());
-    let parsed: Value = serde_json::from_slice(&output.stdout).expect("parse json");
-    let soon = parsed["soon"].as_array().expect("soon array");
-    assert_eq!(soon.len(), 1);
+    assert_eq!(output.status.code(), Some(3));
 }
 
 #[test]
-fn cli_import_vcf_creates_contact() {
+fn cli_show_missing_contact_returns_exit_code_2() {
     let temp = TempDir::new().expect("temp dir");
     let db_path = temp.path().join("knotter.sqlite3");
-    let vcf_path = temp.path().join("contacts.vcf");
-
-    let vcf = "BEGIN:VCARD\nVERSION:3.0\nFN:Grace Hopper\nEMAIL:grace@example.com\nCATEGORIES:friends\nEND:VCARD\n";
-    std::fs::write(&vcf_path, vcf).expect("write vcf");
-
-    run_cmd(
-        &db_path,
-        &["import", "vcf", vcf_path.to_str().expect("path")],
-    );
+    let missing = ContactId::new().to_string();
 
-    let list = run_cmd_json(&db_path, &["list"]);
-    let items = list.as_array().expect("array");
-    assert_eq!(items.len(), 1);
-    assert_eq!(items[0]["display_name"], "Grace Hopper");
+    let output = run_cmd_output(&db_path, &["show", &missing]);
+    assert_eq!(output.status.code(), Some(2));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("contact not found"));
 }
 
 #[test]
-fn cli_import_vcf_dedupes_by_uid() {
+fn cli_show_and_list_by_handle() {
     let temp = TempDir::new().expect("temp dir");
     let db_path = temp.path().join("knotter.sqlite3");
-    let vcf_path = temp.path().join("contacts.vcf");
-
-    let vcf = "BEGIN:VCARD\nVERSION:3.0\nUID:abc-123\nFN:Grace Hopper\nEND:VCARD\n";
-    std::fs::write(&vcf_path, vcf).expect("write vcf");
 
+    let ada = run_cmd_json(
+        &db_path,
+        &["add-contact", "--name", "Ada Lovelace", "--handle", "@Ada"],
+    );
     run_cmd(
         &db_path,
-        &["import", "vcf", vcf_path.to_str().expect("path")],
+        &[
+            "add-contact",
+            "--name",
+            "Ada Byron",
+            "--email",
+            "ada@example.com",
+        ],
     );
-
-    let list = run_cmd_json(&db_path, &["list"]);
-    let items = list.as_array().expect("array");
-    assert_eq!(items.len(), 1);
-    assert_eq!(items[0]["display_name"], "Grace Hopper");
-
-    let vcf = "BEGIN:VCARD\nVERSION:3.0\nUID:abc-123\nFN:Grace H.\nEND:VCARD\n";
-    std::fs::write(&vcf_path, vcf).expect("write vcf");
-
     run_cmd(
         &db_path,
-        &["import", "vcf", vcf_path.to_str().expect("path")],
+        &["add-contact", "--name", "Grace Hopper", "--handle", "grace"],
+    );
+    run_cmd(
+        &db_path,
+        &[
+            "add-contact",
+            "--name",
+            "Grace Brewster",
+            "--handle",
+            "@grace",
+        ],
     );
 
-    let list = run_cmd_json(&db_path, &["list"]);
-    let items = list.as_array().expect("array");
-    assert_eq!(items.len(), 1);
-    assert_eq!(items[0]["display_name"], "Grace H.");
-}
-
-#[test]
-fn cli_import_vcf_updates_when_emails_match_active_and_archived() {
-    let temp = TempDir::new().expect("temp dir");
-    let db_path = temp.path().join("knotter.sqlite3");
-    let vcf_path = temp.path().join("contacts.vcf");
-    let store = Store::open(&db_path).expect("open store");
-    store.migrate().expect("migrate");
-    let now = 1_700_000_000;
+    let shown = run_cmd_json(&db_path, &["show", "--handle", "ada"]);
+    assert_eq!(shown["id"], ada["id"]);
+    let shown = run_cmd_json(&db_path, &["show", "--handle", "@ADA"]);
+    assert_eq!(shown["id"], ada["id"]);
 
-    store
-        .contacts()
-        .create(
-            now,
-            knotter_store::repo::ContactNew {
-                display_name: "Active".to_string(),
-                email: Some("active@example.com".to_string()),
-                phone: None,
-                handle: None,
-                timezone: None,
-                next_touchpoint_at: None,
-                cadence_days: None,
-                archived_at: None,
-            },
-        )
-        .expect("create active");
-    store
-        .contacts()
-        .create(
-            now,
-            knotter_store::repo::ContactNew {
-                display_name: "Archived".to_string(),
-                email: Some("archived@example.com".to_string()),
-                phone: None,
-                handle: None,
-                timezone: None,
-                next_touchpoint_at: None,
-                cadence_days: None,
-                archived_at: Some(now),
-            },
-        )
-        .expect("create archived");
+    let listed = run_cmd_json(&db_path, &["list", "--filter", "@ada"]);
+    let names: Vec<_> = listed
+        .as_array()
+        .expect("array")
+        .iter()
+        .map(|item| item["display_name"].as_str().expect("name"))
+        .collect();
+    assert_eq!(names, vec!["Ada Lovelace"]);
 
-    let vcf = "BEGIN:VCARD\nVERSION:3.0\nFN:Mixed\nEMAIL:active@example.com\nEMAIL:archived@example.com\nEND:VCARD\n";
-    std::fs::write(&vcf_path, vcf).expect("write vcf");
+    let output = run_cmd_output(&db_path, &["show", "--handle", "grace"]);
+    assert_eq!(output.status.code(), Some(3));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("matches 2 contacts"), "{stderr}");
 
-    let report = run_cmd_json(
-        &db_path,
-        &["import", "vcf", vcf_path.to_str().expect("path")],
-    );
-    assert_eq!(report["created"], 0);
-    assert_eq!(report["updated"], 1);
-    assert_eq!(report["skipped"], 0);
-    assert_eq!(report["merge_candidates_created"], 0);
+    let output = run_cmd_output(&db_path, &["show", "--handle", "nobody"]);
+    assert_eq!(output.status.code(), Some(2));
 
-    let store = Store::open(&db_path).expect("open store");
-    let candidates = store
-        .merge_candidates()
-        .list(None)
-        .expect("list candidates");
-    assert!(candidates.is_empty());
+    let output = run_cmd_output(&db_path, &["show"]);
+    assert!(!output.status.success());
 }
 
 #[test]
-fn cli_export_vcf_writes_file() {
+fn cli_contact_arguments_resolve_names_aliases_emails_and_handles() {
     let temp = TempDir::new().expect("temp dir");
     let db_path = temp.path().join("knotter.sqlite3");
-    let out_path = temp.path().join("export.vcf");
-
-    run_cmd(&db_path, &["add-contact", "--name", "Ada Lovelace"]);
 
-    run_cmd(
+    let bob = run_cmd_json(
         &db_path,
-        &["export", "vcf", "--out", out_path.to_str().expect("path")],
+        &[
+            "add-contact",
+            "--name",
+            "Bob Stone",
+            "--email",
+            "bob@example.com",
+            "--handle",
+            "@bobs",
+        ],
     );
+    let bob_id = bob["id"].as_str().expect("id").to_string();
+    run_cmd(&db_path, &["alias", "add", &bob_id, "Bobby"]);
+    let first_ann = run_cmd_json(&db_path, &["add-contact", "--name", "Ann Lee"]);
+    let second_ann = run_cmd_json(&db_path, &["add-contact", "--name", "ann lee"]);
+    // A contact named after another contact's id: the id still wins.
+    run_cmd(&db_path, &["add-contact", "--name", &bob_id]);
+
+    for arg in [
+        "bob stone",
+        "bobby",
+        "BOB@example.com",
+        "@bobs",
+        "bobs",
+        &bob_id,
+    ] {
+        let shown = run_cmd_json(&db_path, &["show", arg]);
+        assert_eq!(shown["id"], bob["id"], "{arg}");
+    }
+
+    run_cmd(&db_path, &["tag", "add", "Bobby", "friends"]);
+    run_cmd(&db_path, &["touch", "bob stone", "--no-reschedule"]);
+    let shown = run_cmd_json(&db_path, &["show", &bob_id]);
+    assert_eq!(shown["tags"], serde_json::json!(["friends"]));
+    assert_eq!(shown["interactions_total"], 1);
 
-    let contents = std::fs::read_to_string(&out_path).expect("read vcf");
-    assert!(contents.contains("BEGIN:VCARD"));
-    assert!(contents.contains("FN:Ada Lovelace"));
+    let output = run_cmd_output(&db_path, &["schedule", "Ann Lee", "--at", "2099-01-01"]);
+    assert_eq!(output.status.code(), Some(3));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("matches 2 contacts"), "{stderr}");
+    for ann in [&first_ann, &second_ann] {
+        assert!(stderr.contains(ann["id"].as_str().expect("id")), "{stderr}");
+    }
+
+    let output = run_cmd_output(&db_path, &["archive-contact", "Nobody"]);
+    assert_eq!(output.status.code(), Some(2));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("no contact matches"), "{stderr}");
 }
 
 #[test]
-fn cli_export_ics_writes_file() {
+fn cli_email_alias_mute_cadence_merge_and_trash_accept_contact_names() {
     let temp = TempDir::new().expect("temp dir");
     let db_path = temp.path().join("knotter.sqlite3");
-    let out_path = temp.path().join("export.ics");
+    let cleo = run_cmd_json(&db_path, &["add-contact", "--name", "Cleo Park"]);
+    let dana = run_cmd_json(
+        &db_path,
+        &[
+            "add-contact",
+            "--name",
+            "Dana Wu",
+            "--email",
+            "dana@example.com",
+        ],
+    );
 
-    run_cmd(&db_path, &["add-contact", "--name", "Ada Lovelace"]);
-    let list = run_cmd_json(&db_path, &["list"]);
-    let items = list.as_array().expect("array");
-    let id = items[0]["id"].as_str().expect("id").to_string();
-    run_cmd(&db_path, &["schedule", &id, "--at", "2030-01-01"]);
+    run_cmd(&db_path, &["email", "add", "cleo park", "cleo@example.com"]);
+    let emails = run_cmd_json(&db_path, &["email", "ls", "Cleo Park"]);
+    assert!(emails.to_string().contains("cleo@example.com"), "{emails}");
+    run_cmd(&db_path, &["alias", "add", "cleo@example.com", "Clee"]);
+    let aliases = run_cmd_json(&db_path, &["alias", "ls", "clee"]);
+    assert!(aliases.to_string().contains("Clee"), "{aliases}");
+
+    let muted = run_cmd_json(&db_path, &["mute", "Clee", "--for", "3m"]);
+    assert_eq!(muted["id"], cleo["id"]);
+    assert!(muted["muted_until"].is_number(), "{muted}");
+    run_cmd(&db_path, &["unmute", "cleo park"]);
+    let suggestions = run_cmd_json(&db_path, &["suggest-cadence", "cleo park"]);
+    assert_eq!(suggestions[0]["id"], cleo["id"]);
 
     run_cmd(
         &db_path,
-        &["export", "ics", "--out", out_path.to_str().expect("path")],
+        &["edit-contact", "dana@example.com", "--met-via", "Clee"],
     );
+    let shown = run_cmd_json(&db_path, &["show", "Dana Wu"]);
+    assert_eq!(shown["origin"]["met_via"], cleo["id"]);
 
-    let contents = std::fs::read_to_string(&out_path).expect("read ics");
-    assert!(contents.contains("BEGIN:VEVENT"));
-    assert!(contents.contains("SUMMARY:Reach out to Ada Lovelace"));
+    let merged = run_cmd_json(&db_path, &["merge", "contacts", "Dana Wu", "Cleo Park"]);
+    assert_eq!(merged["id"], dana["id"]);
+
+    run_cmd(&db_path, &["delete", "dana wu"]);
+    let restored = run_cmd_json(&db_path, &["trash", "restore", "Dana Wu"]);
+    assert_eq!(restored["id"], dana["id"]);
+    let output = run_cmd_output(&db_path, &["trash", "restore", "Nobody"]);
+    assert_eq!(output.status.code(), Some(2));
 }
 
 #[test]
-fn cli_invalid_filter_returns_exit_code_3() {
+fn cli_export_ics_invalid_window_returns_exit_code_3() {
     let temp = TempDir::new().expect("temp dir");
     let db_path = temp.path().join("knotter.sqlite3");
 
-    let output = run_cmd_output(&db_path, &["list", "--filter", "due:later"]);
+    let output = run_cmd_output(&db_path, &["export", "ics", "--window-days", "0"]);
     assert_eq!(output.status.code(), Some(3));
     let stderr = String::from_utf8_lossy(&output.stderr);
-    assert!(stderr.contains("invalid due selector"));
+    assert!(stderr.contains("--window-days must be positive"));
 }
 
 #[test]
-fn cli_show_missing_contact_returns_exit_code_2() {
+fn cli_export_ics_alarm_from_flag_and_config() {
     let temp = TempDir::new().expect("temp dir");
     let db_path = temp.path().join("knotter.sqlite3");
-    let missing = ContactId::new().to_string();
-
-    let output = run_cmd_output(&db_path, &["show", &missing]);
-    assert_eq!(output.status.code(), Some(2));
-    let stderr = String::from_utf8_lossy(&output.stderr);
-    assert!(stderr.contains("contact not found"));
-}
+    let out_path = temp.path().join("export.ics");
+    let config_path = temp.path().join("config.toml");
 
-#[test]
-fn cli_export_ics_invalid_window_returns_exit_code_3() {
-    let temp = TempDir::new().expect("temp dir");
-    let db_path = temp.path().join("knotter.sqlite3");
+    let created = run_cmd_json(&db_path, &["add-contact", "--name", "Ada Lovelace"]);
+    let id = created["id"].as_str().expect("id").to_string();
+    run_cmd(&db_path, &["schedule", &id, "--at", "2030-01-01"]);
 
-    let output = run_cmd_output(&db_path, &["export", "ics", "--window-days", "0"]);
+    let output = run_cmd_output(
+        &db_path,
+        &[
+            "export",
+            "ics",
+            "--alarm",
+            "9 hours",
+            "--out",
+            out_path.to_str().expect("path"),
+        ],
+    );
     assert_eq!(output.status.code(), Some(3));
-    let stderr = String::from_utf8_lossy(&output.stderr);
-    assert!(stderr.contains("--window-days must be positive"));
+    assert!(!out_path.exists());
+
+    run_cmd(
+        &db_path,
+        &[
+            "export",
+            "ics",
+            "--alarm",
+            "PT9H",
+            "--out",
+            out_path.to_str().expect("path"),
+        ],
+    );
+    let contents = std::fs::read_to_string(&out_path).expect("read ics");
+    assert!(contents.contains("BEGIN:VALARM\r\nACTION:DISPLAY\r\n"));
+    assert!(contents.contains("TRIGGER:-PT9H\r\n"));
+
+    std::fs::write(&config_path, "[exports.ics]\nalarm = \"1d\"\n").expect("write config");
+    restrict_config_permissions(&config_path);
+    let stdout = run_cmd_with_config(&db_path, &config_path, &["export", "ics"]);
+    assert!(stdout.contains("TRIGGER:-P1D\r\n"));
 }
 
 #[test]
@@ -1265,7 +4275,7 @@ fn cli_export_json_outputs_snapshot() {
             "--note",
             "hello",
             "--when",
-            "2030-01-02",
+            "2024-01-02",
         ],
     );
 
@@ -1274,7 +4284,7 @@ fn cli_export_json_outputs_snapshot() {
     let snapshot: Value = serde_json::from_slice(&output.stdout).expect("parse json");
 
     assert!(snapshot["metadata"]["exported_at"].is_number());
-    assert_eq!(snapshot["metadata"]["format_version"], 1);
+    assert_eq!(snapshot["metadata"]["format_version"], 5);
 
     let contacts = snapshot["contacts"].as_array().expect("contacts array");
     assert_eq!(contacts.len(), 1);
@@ -1308,6 +4318,8 @@ fn cli_add_note_reschedule_updates_next_touchpoint() {
         ],
     );
     let id = created["id"].as_str().expect("id").to_string();
+    // Tomorrow, so the touch anchors the reschedule rather than now.
+    let when = tomorrow_local_date();
 
     run_cmd(
         &db_path,
@@ -1319,13 +4331,15 @@ fn cli_add_note_reschedule_updates_next_touchpoint() {
             "--note",
             "hello",
             "--when",
-            "2030-01-02",
+            &when,
             "--reschedule",
         ],
     );
 
     let detail = run_cmd_json(&db_path, &["show", &id]);
-    let occurred_at = parse_local_timestamp("2030-01-02").expect("parse when");
+    let occurred_at = Clock::default()
+        .parse_local_timestamp(&when)
+        .expect("parse when");
     let expected = schedule_next(occurred_at, 7).expect("schedule");
     assert_eq!(detail["next_touchpoint_at"], expected);
 }
@@ -1358,6 +4372,8 @@ auto_reschedule = true
         ],
     );
     let id = created["id"].as_str().expect("id").to_string();
+    // Tomorrow, so the touch anchors the reschedule rather than now.
+    let when = tomorrow_local_date();
 
     run_cmd_with_config(
         &db_path,
@@ -1370,12 +4386,14 @@ auto_reschedule = true
             "--note",
             "follow up",
             "--when",
-            "2030-02-01",
+            &when,
         ],
     );
 
     let detail = run_cmd_json_with_config(&db_path, &config_path, &["show", &id]);
-    let occurred_at = parse_local_timestamp("2030-02-01").expect("parse when");
+    let occurred_at = Clock::default()
+        .parse_local_timestamp(&when)
+        .expect("parse when");
     let expected = schedule_next(occurred_at, 14).expect("schedule");
     assert_eq!(detail["next_touchpoint_at"], expected);
 }
@@ -1420,7 +4438,7 @@ auto_reschedule = true
             "--note",
             "hello",
             "--when",
-            "2030-01-02",
+            "2024-01-02",
             "--no-reschedule",
         ],
     );
@@ -1469,6 +4487,175 @@ auto_reschedule = true
     assert!(next >= expected_min);
 }
 
+#[test]
+fn cli_touch_uses_default_kind_and_note_templates() {
+    let temp = TempDir::new().expect("temp dir");
+    let db_path = temp.path().join("knotter.sqlite3");
+    let config_path = temp.path().join("config.toml");
+
+    std::fs::write(
+        &config_path,
+        r#"
+[interactions]
+default_touch_kind = "call"
+
+[interactions.templates]
+coffee = "Met for coffee at {place}"
+"#,
+    )
+    .expect("write config");
+    restrict_config_permissions(&config_path);
+
+    let created = run_cmd_json_with_config(
+        &db_path,
+        &config_path,
+        &["add-contact", "--name", "Grace Hopper"],
+    );
+    let id = created["id"].as_str().expect("id").to_string();
+
+    let templates = run_cmd_json_with_config(&db_path, &config_path, &["templates", "ls"]);
+    assert_eq!(templates[0]["name"], "coffee");
+    assert_eq!(templates[0]["variables"], serde_json::json!(["place"]));
+
+    let touched = run_cmd_json_with_config(
+        &db_path,
+        &config_path,
+        &[
+            "touch",
+            &id,
+            "--note",
+            "talked shop",
+            "--template",
+            "coffee",
+            "--var",
+            "place=Blue Bottle",
+        ],
+    );
+    assert_eq!(touched["kind"], "call");
+    assert_eq!(
+        touched["note"],
+        "talked shop\nMet for coffee at Blue Bottle"
+    );
+
+    let added = run_cmd_json_with_config(
+        &db_path,
+        &config_path,
+        &[
+            "add-note",
+            &id,
+            "--template",
+            "coffee",
+            "--var",
+            "place=the office",
+        ],
+    );
+    assert_eq!(added["kind"], "other:note");
+    assert_eq!(added["note"], "Met for coffee at the office");
+
+    for (args, expected) in [
+        (
+            vec!["touch", id.as_str(), "--template", "coffee"],
+            "template coffee: missing variable(s): place",
+        ),
+        (
+            vec!["touch", id.as_str(), "--template", "tea"],
+            "unknown template tea (defined: coffee)",
+        ),
+    ] {
+        let output = run_cmd_output_with_config(&db_path, &config_path, &args);
+        assert_eq!(output.status.code(), Some(3), "{args:?}");
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert!(stderr.contains(expected), "{stderr}");
+    }
+}
+
+#[test]
+fn cli_touch_dry_run_reports_reschedule_without_writing() {
+    let temp = TempDir::new().expect("temp dir");
+    let db_path = temp.path().join("knotter.sqlite3");
+
+    let created = run_cmd_json(
+        &db_path,
+        &[
+            "add-contact",
+            "--name",
+            "Grace Hopper",
+            "--cadence-days",
+            "10",
+        ],
+    );
+    let id = created["id"].as_str().expect("id").to_string();
+
+    let before = knotter_core::time::now_utc();
+    let preview = run_cmd_json(&db_path, &["touch", &id, "--reschedule", "--dry-run"]);
+    assert_eq!(preview["dry_run"], true);
+    assert_eq!(preview["kind"], "other:touch");
+    let changes = preview["changes"].as_array().expect("changes");
+    assert_eq!(changes.len(), 1);
+    assert_eq!(changes[0]["field"], "next_touchpoint_at");
+    assert!(changes[0]["old"].is_null());
+    assert!(changes[0]["new"].as_i64().expect("new") >= schedule_next(before, 10).unwrap());
+
+    let output = run_cmd(&db_path, &["touch", &id, "--reschedule", "--dry-run"]);
+    assert!(output.starts_with(&format!("touched {id} (dry run)")));
+    assert!(output.contains("  next_touchpoint_at: - -> "));
+
+    let detail = run_cmd_json(&db_path, &["show", &id]);
+    assert!(detail["next_touchpoint_at"].is_null());
+    assert_eq!(detail["interactions_total"], 0);
+}
+
+#[test]
+fn cli_dry_run_leaves_schedule_tags_archive_and_fields_unchanged() {
+    let temp = TempDir::new().expect("temp dir");
+    let db_path = temp.path().join("knotter.sqlite3");
+
+    let created = run_cmd_json(&db_path, &["add-contact", "--name", "Ada"]);
+    let id = created["id"].as_str().expect("id").to_string();
+    run_cmd(&db_path, &["schedule", &id, "--at", "+2w"]);
+    let original = run_cmd_json(&db_path, &["show", &id]);
+
+    let preview = run_cmd_json(&db_path, &["schedule", &id, "--at", "+4w", "--dry-run"]);
+    assert_eq!(preview["dry_run"], true);
+    assert_eq!(preview["changes"][0]["field"], "next_touchpoint_at");
+    assert_eq!(preview["changes"][0]["old"], original["next_touchpoint_at"]);
+    assert_eq!(preview["next_touchpoint_at"], preview["changes"][0]["new"]);
+
+    let preview = run_cmd_json(&db_path, &["clear-schedule", &id, "--dry-run"]);
+    assert!(preview["changes"][0]["new"].is_null());
+
+    let preview = run_cmd_json(&db_path, &["tag", "add", &id, "Friends", "--dry-run"]);
+    assert_eq!(preview["tag"], "friends");
+    assert_eq!(preview["changes"][0]["field"], "tags");
+    assert_eq!(preview["changes"][0]["new"], serde_json::json!(["friends"]));
+
+    let preview = run_cmd_json(
+        &db_path,
+        &["archive-contact", &id, "--reason", "moved", "--dry-run"],
+    );
+    let fields: Vec<&str> = preview["changes"]
+        .as_array()
+        .expect("changes")
+        .iter()
+        .map(|change| change["field"].as_str().expect("field"))
+        .collect();
+    assert_eq!(fields, vec!["archived_at", "archive_reason"]);
+
+    let preview = run_cmd_json(
+        &db_path,
+        &["edit-contact", &id, "--name", "Ada Lovelace", "--dry-run"],
+    );
+    assert_eq!(preview["display_name"], "Ada Lovelace");
+    assert_eq!(preview["changes"][0]["old"], "Ada");
+
+    let output = run_cmd(&db_path, &["unarchive-contact", &id, "--dry-run"]);
+    assert!(output.contains("(dry run)"));
+    assert!(output.contains("no changes"));
+
+    let after = run_cmd_json(&db_path, &["show", &id]);
+    assert_eq!(after, original);
+}
+
 #[test]
 fn cli_touch_no_reschedule_overrides_config() {
     let temp = TempDir::new().expect("temp dir");
@@ -1504,6 +4691,37 @@ auto_reschedule = true
     assert!(detail["next_touchpoint_at"].is_null());
 }
 
+#[test]
+fn cli_touch_twice_reports_already_recorded_unless_forced() {
+    let temp = TempDir::new().expect("temp dir");
+    let db_path = temp.path().join("knotter.sqlite3");
+
+    let created = run_cmd_json(&db_path, &["add-contact", "--name", "Ada Lovelace"]);
+    let id = created["id"].as_str().expect("id").to_string();
+
+    let first = run_cmd_json(&db_path, &["touch", &id]);
+    assert!(first.get("already_recorded").is_none());
+
+    let output = run_cmd(&db_path, &["touch", &id]);
+    assert!(
+        output.starts_with(&format!("already touched {id}")),
+        "{output}"
+    );
+    let repeat = run_cmd_json(&db_path, &["touch", &id]);
+    assert_eq!(repeat["already_recorded"], true);
+    assert_eq!(repeat["id"], first["id"]);
+
+    run_cmd(&db_path, &["touch", &id, "--note", "called back"]);
+    run_cmd(&db_path, &["touch", &id, "--force"]);
+    let note = run_cmd_json(&db_path, &["add-note", &id, "--note", "lunch"]);
+    let repeat = run_cmd_json(&db_path, &["add-note", &id, "--note", "lunch"]);
+    assert_eq!(repeat["already_recorded"], true);
+    assert_eq!(repeat["id"], note["id"]);
+
+    let detail = run_cmd_json(&db_path, &["show", &id]);
+    assert_eq!(detail["interactions_total"], 4);
+}
+
 #[test]
 fn cli_touch_records_kind_and_reschedules() {
     let temp = TempDir::new().expect("temp dir");
@@ -1520,6 +4738,8 @@ fn cli_touch_records_kind_and_reschedules() {
         ],
     );
     let id = created["id"].as_str().expect("id").to_string();
+    // Tomorrow, so the touch anchors the reschedule rather than now.
+    let when = tomorrow_local_date();
 
     run_cmd(
         &db_path,
@@ -1531,13 +4751,15 @@ fn cli_touch_records_kind_and_reschedules() {
             "--note",
             "sync",
             "--when",
-            "2030-03-01",
+            &when,
             "--reschedule",
         ],
     );
 
     let detail = run_cmd_json(&db_path, &["show", &id]);
-    let occurred_at = parse_local_timestamp("2030-03-01").expect("parse when");
+    let occurred_at = Clock::default()
+        .parse_local_timestamp(&when)
+        .expect("parse when");
     let expected = schedule_next(occurred_at, 10).expect("schedule");
     assert_eq!(detail["next_touchpoint_at"], expected);
 
@@ -1552,6 +4774,169 @@ fn cli_touch_records_kind_and_reschedules() {
     assert_eq!(interactions[0].note, "sync");
 }
 
+#[test]
+fn cli_note_edit_and_rm_reschedule_from_latest_interaction() {
+    let temp = TempDir::new().expect("temp dir");
+    let db_path = temp.path().join("knotter.sqlite3");
+
+    let created = run_cmd_json(
+        &db_path,
+        &[
+            "add-contact",
+            "--name",
+            "Ada Lovelace",
+            "--cadence-days",
+            "7",
+        ],
+    );
+    let id = created["id"].as_str().expect("id").to_string();
+
+    let older = run_cmd_json(
+        &db_path,
+        &["add-note", &id, "--note", "older", "--when", "2020-01-01"],
+    );
+    let latest = run_cmd_json(
+        &db_path,
+        &["add-note", &id, "--note", "latest", "--when", "2020-02-01"],
+    );
+    let older_id = older["id"].as_str().expect("older id").to_string();
+    let latest_id = latest["id"].as_str().expect("latest id").to_string();
+
+    let output = run_cmd_output(
+        &db_path,
+        &["note", "edit", &latest_id, "--when", "2999-01-01"],
+    );
+    assert_eq!(output.status.code(), Some(3));
+
+    let edited = run_cmd_json(
+        &db_path,
+        &[
+            "note",
+            "edit",
+            &latest_id,
+            "--kind",
+            "call",
+            "--note",
+            "fixed typo",
+            "--when",
+            "2020-01-15",
+            "--reschedule",
+        ],
+    );
+    assert_eq!(edited["kind"], "call");
+    assert_eq!(edited["note"], "fixed typo");
+    let detail = run_cmd_json(&db_path, &["show", &id]);
+    let expected = schedule_next(
+        Clock::default()
+            .parse_local_timestamp("2020-01-15")
+            .expect("parse when"),
+        7,
+    )
+    .expect("schedule");
+    assert_eq!(detail["next_touchpoint_at"], expected);
+
+    let removed = run_cmd_json(&db_path, &["note", "rm", &latest_id, "--reschedule"]);
+    assert_eq!(removed["id"], latest_id.as_str());
+    let detail = run_cmd_json(&db_path, &["show", &id]);
+    let expected = schedule_next(
+        Clock::default()
+            .parse_local_timestamp("2020-01-01")
+            .expect("parse when"),
+        7,
+    )
+    .expect("schedule");
+    assert_eq!(detail["next_touchpoint_at"], expected);
+    let interactions = detail["recent_interactions"].as_array().expect("array");
+    assert_eq!(interactions.len(), 1);
+    assert_eq!(interactions[0]["id"], older_id.as_str());
+
+    let output = run_cmd_output(&db_path, &["note", "rm", &latest_id]);
+    assert_eq!(output.status.code(), Some(2));
+}
+
+#[test]
+fn cli_note_attach_links_files_and_urls_to_an_interaction() {
+    let temp = TempDir::new().expect("temp dir");
+    let db_path = temp.path().join("knotter.sqlite3");
+
+    let created = run_cmd_json(&db_path, &["add-contact", "--name", "Ada Lovelace"]);
+    let id = created["id"].as_str().expect("id").to_string();
+    let note = run_cmd_json(&db_path, &["add-note", &id, "--note", "salon"]);
+    let note_id = note["id"].as_str().expect("note id").to_string();
+
+    let attached = run_cmd_json(
+        &db_path,
+        &["note", "attach", &note_id, "notes/salon 2024.md"],
+    );
+    assert_eq!(attached["added"], true);
+    assert_eq!(attached["attachment"]["kind"], "file");
+    assert_eq!(attached["attachment"]["value"], "notes/salon 2024.md");
+    let stdout = run_cmd(
+        &db_path,
+        &["note", "attach", &note_id, "https://photos.example/salon"],
+    );
+    assert!(stdout.contains("attached url https://photos.example/salon"));
+    let again = run_cmd_json(
+        &db_path,
+        &["note", "attach", &note_id, "https://photos.example/salon"],
+    );
+    assert_eq!(again["added"], false);
+
+    let output = run_cmd_output(
+        &db_path,
+        &[
+            "note",
+            "attach",
+            &note_id,
+            "--kind",
+            "url",
+            "photos.example/salon",
+        ],
+    );
+    assert_eq!(output.status.code(), Some(3));
+    let output = run_cmd_output(
+        &db_path,
+        &[
+            "note",
+            "attach",
+            "00000000-0000-0000-0000-000000000000",
+            "notes.md",
+        ],
+    );
+    assert_eq!(output.status.code(), Some(2));
+
+    let listed = run_cmd_json(&db_path, &["note", "attachments", &note_id]);
+    let values: Vec<&str> = listed
+        .as_array()
+        .expect("array")
+        .iter()
+        .map(|attachment| attachment["value"].as_str().expect("value"))
+        .collect();
+    assert_eq!(values.len(), 2);
+    assert!(values.contains(&"notes/salon 2024.md"));
+
+    let detail = run_cmd_json(&db_path, &["show", &id]);
+    let attachments = detail["recent_interactions"][0]["attachments"]
+        .as_array()
+        .expect("attachments");
+    assert_eq!(attachments.len(), 2);
+    let stdout = run_cmd(&db_path, &["show", &id]);
+    assert!(stdout.contains("salon (2 attachments)"));
+    assert!(stdout.contains("url   https://photos.example/salon"));
+
+    let snapshot = run_cmd_json(&db_path, &["export", "json"]);
+    let exported = &snapshot["contacts"][0]["interactions"][0]["attachments"];
+    assert_eq!(exported.as_array().map(Vec::len), Some(2));
+    let v3 = run_cmd_json(&db_path, &["export", "json", "--format-version", "3"]);
+    assert!(v3["contacts"][0]["interactions"][0]
+        .get("attachments")
+        .is_none());
+
+    run_cmd(&db_path, &["note", "rm", &note_id]);
+    let output = run_cmd_output(&db_path, &["note", "attachments", &note_id]);
+    assert_eq!(output.status.code(), Some(2));
+}
+
 #[test]
 fn cli_export_json_excludes_archived_when_requested() {
     let temp = TempDir::new().expect("temp dir");
@@ -1660,39 +5045,660 @@ fn cli_archive_and_list_filters_archived() {
     assert_eq!(items.len(), 1);
     assert_eq!(items[0]["id"], archived["id"]);
 
-    let unarchived_out = run_cmd_json(&db_path, &["unarchive-contact", archived_id]);
-    assert!(unarchived_out["archived_at"].is_null());
+    let unarchived_out = run_cmd_json(&db_path, &["unarchive-contact", archived_id]);
+    assert!(unarchived_out["archived_at"].is_null());
+}
+
+#[test]
+fn cli_archive_reason_round_trips_through_show_list_and_export() {
+    let temp = TempDir::new().expect("temp dir");
+    let db_path = temp.path().join("knotter.sqlite3");
+
+    let first = run_cmd_json(&db_path, &["add-contact", "--name", "First"]);
+    let second = run_cmd_json(&db_path, &["add-contact", "--name", "Second"]);
+    let first_id = first["id"].as_str().expect("first id");
+    let second_id = second["id"].as_str().expect("second id");
+
+    let archived = run_cmd_json(
+        &db_path,
+        &["archive-contact", first_id, "--reason", "moved abroad"],
+    );
+    assert!(archived["archived_at"].is_number());
+    assert_eq!(archived["archive_reason"], "moved abroad");
+
+    // Archive the second contact a bit later so the archive-time sort is observable.
+    run_cmd_json_with_env(
+        &db_path,
+        &["archive-contact", second_id],
+        &[
+            ("KNOTTER_TEST_NOW_UTC", "4102444800"),
+            ("KNOTTER_ALLOW_TEST_NOW_UTC", "1"),
+        ],
+    );
+
+    let detail = run_cmd_json(&db_path, &["show", first_id]);
+    assert_eq!(detail["archive_reason"], "moved abroad");
+    let detail = run_cmd_json(&db_path, &["show", second_id]);
+    assert!(detail.get("archive_reason").is_none());
+
+    let list = run_cmd_json(&db_path, &["list", "--archived-only", "--sort", "archived"]);
+    let items = list.as_array().expect("list array");
+    assert_eq!(items.len(), 2);
+    assert_eq!(items[0]["id"], second["id"]);
+    assert_eq!(items[1]["id"], first["id"]);
+    assert_eq!(items[1]["archive_reason"], "moved abroad");
+
+    let stdout = run_cmd(&db_path, &["list", "--archived-only"]);
+    assert!(stdout.contains(": moved abroad)"), "{stdout}");
+
+    let export = run_cmd_json(&db_path, &["export", "json"]);
+    let contacts = export["contacts"].as_array().expect("contacts");
+    let exported = contacts
+        .iter()
+        .find(|contact| contact["id"] == first["id"])
+        .expect("exported contact");
+    assert_eq!(exported["archive_reason"], "moved abroad");
+
+    run_cmd_json(&db_path, &["unarchive-contact", first_id]);
+    let detail = run_cmd_json(&db_path, &["show", first_id]);
+    assert!(detail.get("archive_reason").is_none());
+}
+
+#[test]
+fn cli_hard_delete_requires_archive_and_confirmation() {
+    let temp = TempDir::new().expect("temp dir");
+    let db_path = temp.path().join("knotter.sqlite3");
+
+    let contact = run_cmd_json(
+        &db_path,
+        &[
+            "add-contact",
+            "--name",
+            "Ada Lovelace",
+            "--email",
+            "ada@example.com",
+        ],
+    );
+    let id = contact["id"].as_str().expect("id");
+    run_cmd(
+        &db_path,
+        &["add-note", id, "--kind", "call", "--note", "hi"],
+    );
+
+    let output = run_cmd_output(&db_path, &["delete", id, "--hard", "--yes"]);
+    assert_eq!(output.status.code(), Some(3));
+    let stderr = String::from_utf8(output.stderr).expect("utf8");
+    assert!(stderr.contains("not archived"), "{stderr}");
+
+    run_cmd(&db_path, &["archive-contact", id]);
+
+    // No confirmation on stdin: refuse.
+    let output = run_cmd_output(&db_path, &["delete", id, "--hard"]);
+    assert_eq!(output.status.code(), Some(3));
+
+    let config_dir = TempDir::new().expect("temp config dir");
+    let output = cargo_bin_cmd!("knotter")
+        .env("XDG_CONFIG_HOME", config_dir.path())
+        .args(["--db-path", db_path.to_str().expect("db path")])
+        .args(["delete", id, "--hard"])
+        .write_stdin("Ada\n")
+        .output()
+        .expect("run delete");
+    assert_eq!(output.status.code(), Some(3));
+    assert!(run_cmd_json(&db_path, &["show", id])["id"].is_string());
+
+    let output = cargo_bin_cmd!("knotter")
+        .env("XDG_CONFIG_HOME", config_dir.path())
+        .args(["--db-path", db_path.to_str().expect("db path")])
+        .args(["--json", "delete", id, "--hard"])
+        .write_stdin("Ada Lovelace\n")
+        .output()
+        .expect("run delete");
+    assert!(output.status.success(), "command failed: {:?}", output);
+    let stderr = String::from_utf8(output.stderr).expect("utf8");
+    assert!(stderr.contains("1 interactions, 1 emails"), "{stderr}");
+    let report: Value = serde_json::from_slice(&output.stdout).expect("parse json");
+    assert_eq!(report["interactions"], 1);
+    assert_eq!(report["emails"], 1);
+    assert_eq!(report["merge_candidates_dismissed"], 0);
+
+    let output = run_cmd_output(&db_path, &["show", id]);
+    assert_eq!(output.status.code(), Some(2));
+
+    let other = run_cmd_json(&db_path, &["add-contact", "--name", "Grace"]);
+    let other_id = other["id"].as_str().expect("id");
+    let report = run_cmd_json(
+        &db_path,
+        &["delete", other_id, "--hard", "--force", "--yes"],
+    );
+    assert_eq!(report["display_name"], "Grace");
+}
+
+#[test]
+fn cli_delete_moves_contact_to_trash_until_restored_or_emptied() {
+    let temp = TempDir::new().expect("temp dir");
+    let db_path = temp.path().join("knotter.sqlite3");
+
+    let contact = run_cmd_json(
+        &db_path,
+        &[
+            "add-contact",
+            "--name",
+            "Ada Lovelace",
+            "--email",
+            "ada@example.com",
+        ],
+    );
+    let id = contact["id"].as_str().expect("id");
+
+    let trashed = run_cmd_json(&db_path, &["delete", id]);
+    assert_eq!(trashed["id"], id);
+    assert!(trashed["deleted_at"].is_i64());
+    let output = run_cmd_output(&db_path, &["show", id]);
+    assert_eq!(output.status.code(), Some(2));
+    let list = run_cmd_json(&db_path, &["list"]);
+    assert!(list.as_array().expect("array").is_empty());
+
+    let trash = run_cmd_json(&db_path, &["trash", "ls"]);
+    let trash = trash.as_array().expect("array");
+    assert_eq!(trash.len(), 1);
+    assert_eq!(trash[0]["display_name"], "Ada Lovelace");
+
+    // The trashed contact's address is free for a new contact.
+    let reused = run_cmd_json(
+        &db_path,
+        &[
+            "add-contact",
+            "--name",
+            "Ada New",
+            "--email",
+            "ada@example.com",
+        ],
+    );
+    assert_eq!(reused["email"], "ada@example.com");
+
+    let restored = run_cmd_json(&db_path, &["trash", "restore", id]);
+    assert_eq!(restored["dropped_emails"][0], "ada@example.com");
+    let shown = run_cmd_json(&db_path, &["show", id]);
+    assert!(shown["email"].is_null());
+
+    run_cmd(&db_path, &["delete", id]);
+    let report = run_cmd_json(&db_path, &["trash", "empty", "--older-than", "30d"]);
+    assert_eq!(report["purged"], 0);
+    let report = run_cmd_json(&db_path, &["trash", "empty"]);
+    assert_eq!(report["purged"], 1);
+    assert!(run_cmd_json(&db_path, &["trash", "ls"])
+        .as_array()
+        .expect("array")
+        .is_empty());
+}
+
+#[test]
+fn cli_list_archived_filter_tokens() {
+    let temp = TempDir::new().expect("temp dir");
+    let db_path = temp.path().join("knotter.sqlite3");
+
+    let active = run_cmd_json(&db_path, &["add-contact", "--name", "Active"]);
+    let archived = run_cmd_json(&db_path, &["add-contact", "--name", "Archived"]);
+    let archived_id = archived["id"].as_str().expect("archived id");
+
+    let archived_out = run_cmd_json(&db_path, &["archive-contact", archived_id]);
+    assert!(archived_out["archived_at"].is_number());
+
+    let list = run_cmd_json(&db_path, &["list", "--filter", "archived:true"]);
+    let items = list.as_array().expect("list array");
+    assert_eq!(items.len(), 1);
+    assert_eq!(items[0]["id"], archived["id"]);
+
+    let list = run_cmd_json(&db_path, &["list", "--filter", "archived:false"]);
+    let items = list.as_array().expect("list array");
+    assert_eq!(items.len(), 1);
+    assert_eq!(items[0]["id"], active["id"]);
+
+    let list = run_cmd_json(
+        &db_path,
+        &["list", "--only-archived", "--filter", "archived:true"],
+    );
+    let items = list.as_array().expect("list array");
+    assert_eq!(items.len(), 1);
+    assert_eq!(items[0]["id"], archived["id"]);
+}
+
+#[test]
+fn cli_list_shows_last_interaction() {
+    let temp = TempDir::new().expect("temp dir");
+    let db_path = temp.path().join("knotter.sqlite3");
+
+    let ada = run_cmd_json(&db_path, &["add-contact", "--name", "Ada"]);
+    let ada_id = ada["id"].as_str().expect("id");
+    run_cmd_json(&db_path, &["add-contact", "--name", "Grace"]);
+    let when = (Local::now().date_naive() - Duration::days(21))
+        .format("%Y-%m-%d")
+        .to_string();
+    run_cmd(
+        &db_path,
+        &[
+            "add-note", ada_id, "--kind", "call", "--note", "hi", "--when", &when,
+        ],
+    );
+
+    let list = run_cmd_json(&db_path, &["list"]);
+    let items = list.as_array().expect("list array");
+    assert_eq!(items[0]["display_name"], "Ada");
+    assert!(items[0]["last_interaction_at"].is_number());
+    assert_eq!(items[0]["last_interaction_kind"], "call");
+    assert!(items[1]["last_interaction_at"].is_null());
+    assert!(items[1]["last_interaction_kind"].is_null());
+
+    let output = run_cmd(&db_path, &["list"]);
+    let lines: Vec<&str> = output.lines().collect();
+    assert!(lines[0].contains("Ada  [unscheduled]  -  3w ago (call)"));
+    assert!(lines[1].ends_with("Grace  [unscheduled]  -  -"));
+}
+
+#[test]
+fn cli_suggest_cadence_reports_and_applies() {
+    let temp = TempDir::new().expect("temp dir");
+    let db_path = temp.path().join("knotter.sqlite3");
+
+    let regular = run_cmd_json(&db_path, &["add-contact", "--name", "Regular"]);
+    let regular_id = regular["id"].as_str().expect("id");
+    let sparse = run_cmd_json(&db_path, &["add-contact", "--name", "Sparse"]);
+    let sparse_id = sparse["id"].as_str().expect("id");
+    let preset = run_cmd_json(
+        &db_path,
+        &["add-contact", "--name", "Preset", "--cadence-days", "90"],
+    );
+    let preset_id = preset["id"].as_str().expect("id");
+
+    let today = Local::now().date_naive();
+    for days_ago in [70, 42, 14] {
+        let when = (today - Duration::days(days_ago))
+            .format("%Y-%m-%d")
+            .to_string();
+        for id in [regular_id, preset_id] {
+            run_cmd(
+                &db_path,
+                &[
+                    "add-note", id, "--kind", "call", "--note", "hi", "--when", &when,
+                ],
+            );
+        }
+    }
+    let when = (today - Duration::days(5)).format("%Y-%m-%d").to_string();
+    run_cmd(
+        &db_path,
+        &[
+            "add-note", sparse_id, "--kind", "call", "--note", "hi", "--when", &when,
+        ],
+    );
+
+    let single = run_cmd_json(&db_path, &["suggest-cadence", sparse_id]);
+    let single = single.as_array().expect("array");
+    assert_eq!(single.len(), 1);
+    assert!(single[0]["suggested_cadence_days"].is_null());
+
+    let all = run_cmd_json(&db_path, &["suggest-cadence", "--all", "--apply"]);
+    let all = all.as_array().expect("array");
+    assert_eq!(all.len(), 2);
+    let regular_item = all
+        .iter()
+        .find(|item| item["id"] == regular["id"])
+        .expect("regular");
+    assert_eq!(regular_item["suggested_cadence_days"], 30);
+    assert_eq!(regular_item["applied"], true);
+    let preset_item = all
+        .iter()
+        .find(|item| item["id"] == preset["id"])
+        .expect("preset");
+    assert_eq!(preset_item["applied"], false);
+
+    let shown = run_cmd_json(&db_path, &["show", regular_id]);
+    assert_eq!(shown["cadence_days"], 30);
+    let shown = run_cmd_json(&db_path, &["show", preset_id]);
+    assert_eq!(shown["cadence_days"], 90);
+
+    run_cmd_json(
+        &db_path,
+        &["suggest-cadence", preset_id, "--apply", "--override"],
+    );
+    let shown = run_cmd_json(&db_path, &["show", preset_id]);
+    assert_eq!(shown["cadence_days"], 30);
+}
+
+#[test]
+fn cli_enrich_timezones_fills_only_empty_unambiguous_timezones() {
+    let temp = TempDir::new().expect("temp dir");
+    let db_path = temp.path().join("knotter.sqlite3");
+
+    let london = run_cmd_json(
+        &db_path,
+        &[
+            "add-contact",
+            "--name",
+            "London",
+            "--phone",
+            "+44 20 7946 0958",
+        ],
+    );
+    let london_id = london["id"].as_str().expect("id");
+    let us = run_cmd_json(
+        &db_path,
+        &[
+            "add-contact",
+            "--name",
+            "Stateside",
+            "--phone",
+            "+1 415 555 1212",
+        ],
+    );
+    let preset = run_cmd_json(
+        &db_path,
+        &[
+            "add-contact",
+            "--name",
+            "Preset",
+            "--phone",
+            "0049 30 901820",
+            "--timezone",
+            "America/New_York",
+        ],
+    );
+    let preset_id = preset["id"].as_str().expect("id");
+
+    let dry_run = run_cmd_json(&db_path, &["enrich", "timezones", "--dry-run"]);
+    assert_eq!(dry_run["dry_run"], true);
+    let changes = dry_run["changes"].as_array().expect("changes");
+    assert_eq!(changes.len(), 1);
+    assert_eq!(changes[0]["id"], london["id"]);
+    assert_eq!(changes[0]["timezone"], "Europe/London");
+    let ambiguous = dry_run["ambiguous"].as_array().expect("ambiguous");
+    assert_eq!(ambiguous.len(), 1);
+    assert_eq!(ambiguous[0]["id"], us["id"]);
+    assert_eq!(ambiguous[0]["region"], "NANP");
+    assert!(run_cmd_json(&db_path, &["show", london_id])["timezone"].is_null());
+
+    run_cmd_json(&db_path, &["enrich", "timezones"]);
+    assert_eq!(
+        run_cmd_json(&db_path, &["show", london_id])["timezone"],
+        "Europe/London"
+    );
+    assert_eq!(
+        run_cmd_json(&db_path, &["show", preset_id])["timezone"],
+        "America/New_York"
+    );
+
+    let rerun = run_cmd_json(&db_path, &["enrich", "timezones"]);
+    assert!(rerun["changes"].as_array().expect("changes").is_empty());
+}
+
+#[test]
+fn cli_phone_normalization_strict_flag_and_maintenance_command() {
+    let temp = TempDir::new().expect("temp dir");
+    let db_path = temp.path().join("knotter.sqlite3");
+
+    let rejected = run_cmd_output(
+        &db_path,
+        &[
+            "add-contact",
+            "--name",
+            "Vague",
+            "--phone",
+            "call me",
+            "--strict-phone",
+        ],
+    );
+    assert_eq!(rejected.status.code(), Some(3));
+    assert!(String::from_utf8_lossy(&rejected.stderr).contains("invalid phone number"));
+
+    let raw = run_cmd_json(
+        &db_path,
+        &["add-contact", "--name", "Raw", "--phone", "415.555.1212"],
+    );
+    assert_eq!(raw["phone"], "415.555.1212");
+    let raw_id = raw["id"].as_str().expect("id");
+    let vague = run_cmd_json(
+        &db_path,
+        &["add-contact", "--name", "Vague", "--phone", "call me"],
+    );
+    assert_eq!(vague["phone"], "call me");
+
+    let config_path = temp.path().join("config.toml");
+    std::fs::write(&config_path, "[contacts]\nnormalize_phones = true\n").expect("write config");
+    restrict_config_permissions(&config_path);
+    let added = run_cmd_with_config(
+        &db_path,
+        &config_path,
+        &[
+            "--json",
+            "add-contact",
+            "--name",
+            "Canonical",
+            "--phone",
+            "+1 (415) 555-1213 ext. 7",
+        ],
+    );
+    let added: Value = serde_json::from_str(&added).expect("parse json");
+    assert_eq!(added["phone"], "+14155551213 x7");
+
+    let preview = run_cmd_json(&db_path, &["normalize", "phones", "--dry-run"]);
+    assert_eq!(preview["dry_run"], true);
+    let changes = preview["changes"].as_array().expect("changes");
+    assert_eq!(changes.len(), 1);
+    assert_eq!(changes[0]["id"], raw["id"]);
+    assert_eq!(changes[0]["normalized"], "4155551212");
+    assert_eq!(preview["unparseable"][0]["phone"], "call me");
+    assert_eq!(preview["unchanged"], 1);
+    assert_eq!(
+        run_cmd_json(&db_path, &["show", raw_id])["phone"],
+        "415.555.1212"
+    );
+
+    let applied = run_cmd_json(&db_path, &["normalize", "phones", "--apply"]);
+    assert_eq!(applied["dry_run"], false);
+    assert_eq!(
+        run_cmd_json(&db_path, &["show", raw_id])["phone"],
+        "4155551212"
+    );
+    let rerun = run_cmd_json(&db_path, &["normalize", "phones", "--apply"]);
+    assert!(rerun["changes"].as_array().expect("changes").is_empty());
+}
+
+#[test]
+fn cli_data_dir_flag_overrides_env_and_xdg() {
+    let temp = TempDir::new().expect("temp dir");
+    let config_dir = temp.path().join("config");
+    let xdg = temp.path().join("xdg");
+    let env_dir = temp.path().join("env-profile");
+    let flag_dir = temp.path().join("flag-profile");
+
+    let list = |extra: &[&str], knotter_data_dir: Option<&Path>| {
+        let mut cmd = cargo_bin_cmd!("knotter");
+        cmd.env("XDG_CONFIG_HOME", &config_dir)
+            .env("XDG_DATA_HOME", &xdg)
+            .env_remove("KNOTTER_DATA_DIR");
+        if let Some(dir) = knotter_data_dir {
+            cmd.env("KNOTTER_DATA_DIR", dir);
+        }
+        let output = cmd.args(extra).arg("list").output().expect("run command");
+        assert!(output.status.success(), "command failed: {:?}", output);
+    };
+
+    list(&[], None);
+    assert!(xdg.join("knotter").join("knotter.sqlite3").is_file());
+
+    list(&[], Some(&env_dir));
+    assert!(env_dir.join("knotter.sqlite3").is_file());
+
+    let flag = flag_dir.to_str().expect("flag dir");
+    list(&["--data-dir", flag], Some(&env_dir));
+    assert!(flag_dir.join("knotter.sqlite3").is_file());
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let mode = std::fs::metadata(&flag_dir)
+            .expect("flag dir metadata")
+            .permissions()
+            .mode();
+        assert_eq!(mode & 0o777, 0o700);
+    }
+}
+
+#[test]
+fn cli_db_stats_and_vacuum_report_sizes() {
+    let temp = TempDir::new().expect("temp dir");
+    let db_path = temp.path().join("knotter.sqlite3");
+
+    let ada = run_cmd_json(
+        &db_path,
+        &["add-contact", "--name", "Ada", "--email", "ada@example.com"],
+    );
+    let ada_id = ada["id"].as_str().expect("id").to_string();
+    run_cmd(&db_path, &["add-note", &ada_id, "--note", "hello"]);
+
+    let stats = run_cmd_json(&db_path, &["db", "stats"]);
+    assert!(stats["size_bytes"].as_u64().expect("size") > 0);
+    assert!(stats["page_size"].as_u64().expect("page size") > 0);
+    assert_eq!(stats["rows"]["contacts"], 1);
+    assert_eq!(stats["rows"]["contact_emails"], 1);
+    assert_eq!(stats["rows"]["interactions"], 1);
+    assert_eq!(stats["rows"]["contact_merge_candidates"], 0);
+
+    let vacuum = run_cmd_json(&db_path, &["db", "vacuum"]);
+    let before = vacuum["before_bytes"].as_u64().expect("before");
+    let after = vacuum["after_bytes"].as_u64().expect("after");
+    assert_eq!(
+        vacuum["reclaimed_bytes"].as_u64(),
+        Some(before.saturating_sub(after))
+    );
+
+    let stats = run_cmd_json(&db_path, &["db", "stats"]);
+    assert_eq!(stats["free_pages"], 0);
+    assert_eq!(stats["wal_size_bytes"], 0);
+    assert_eq!(stats["rows"]["contacts"], 1);
 }
 
 #[test]
-fn cli_list_archived_filter_tokens() {
+fn cli_dedupe_interactions_removes_repeats_without_rescheduling() {
     let temp = TempDir::new().expect("temp dir");
     let db_path = temp.path().join("knotter.sqlite3");
 
-    let active = run_cmd_json(&db_path, &["add-contact", "--name", "Active"]);
-    let archived = run_cmd_json(&db_path, &["add-contact", "--name", "Archived"]);
-    let archived_id = archived["id"].as_str().expect("archived id");
+    let ada = run_cmd_json(
+        &db_path,
+        &["add-contact", "--name", "Ada", "--cadence-days", "7"],
+    );
+    let ada_id = ada["id"].as_str().expect("id").to_string();
+    let grace = run_cmd_json(&db_path, &["add-contact", "--name", "Grace"]);
+    let grace_id = grace["id"].as_str().expect("id").to_string();
+    let touch = |id: &str, when: &str, note: &str| {
+        run_cmd(
+            &db_path,
+            &[
+                "touch", id, "--kind", "call", "--when", when, "--note", note, "--force",
+            ],
+        );
+    };
+    touch(&ada_id, "2026-01-05 10:00", "weekly call");
+    touch(&ada_id, "2026-01-05 10:01", "weekly call");
+    touch(&ada_id, "2026-01-05 10:02", "weekly call");
+    touch(&ada_id, "2026-01-05 12:00", "weekly call");
+    touch(&grace_id, "2026-01-05 10:00", "hello");
+    touch(&grace_id, "2026-01-05 10:00", "hello");
+    touch(&grace_id, "2026-01-05 10:00", "goodbye");
+    let before = run_cmd_json(&db_path, &["show", &ada_id]);
+
+    let dry_run = run_cmd_json(&db_path, &["dedupe", "interactions", "--dry-run"]);
+    assert_eq!(dry_run["window_seconds"], 120);
+    assert_eq!(dry_run["dry_run"], true);
+    assert_eq!(dry_run["removed"], 3);
+    assert!(dry_run["relinked_messages"].is_null());
+    let contacts = dry_run["contacts"].as_array().expect("contacts");
+    assert_eq!(contacts.len(), 2);
+    assert_eq!(contacts[0]["id"], ada_id.as_str());
+    assert_eq!(contacts[0]["removed"], 2);
+    assert_eq!(contacts[1]["display_name"], "Grace");
+    assert_eq!(contacts[1]["removed"], 1);
+
+    let output = run_cmd(&db_path, &["dedupe", "interactions", "--window", "30s"]);
+    assert!(output.contains(&format!("removed 1 from {grace_id} Grace")));
+    assert!(output.contains("removed 1 duplicate interaction(s) across 1 contact(s)"));
+
+    let report = run_cmd_json(&db_path, &["dedupe", "interactions"]);
+    assert_eq!(report["removed"], 2);
+    assert_eq!(report["relinked_messages"], 0);
+    let output = run_cmd(&db_path, &["dedupe", "interactions", "--window", "3h"]);
+    assert!(output.contains(&format!("removed 1 from {ada_id} Ada")));
+    assert_eq!(
+        run_cmd(&db_path, &["dedupe", "interactions"]).trim(),
+        "no duplicate interactions"
+    );
 
-    let archived_out = run_cmd_json(&db_path, &["archive-contact", archived_id]);
-    assert!(archived_out["archived_at"].is_number());
+    let after = run_cmd_json(&db_path, &["show", &ada_id]);
+    assert_eq!(after["next_touchpoint_at"], before["next_touchpoint_at"]);
+    assert_eq!(after["updated_at"], before["updated_at"]);
+    assert_eq!(
+        after["recent_interactions"].as_array().map(Vec::len),
+        Some(1)
+    );
 
-    let list = run_cmd_json(&db_path, &["list", "--filter", "archived:true"]);
-    let items = list.as_array().expect("list array");
-    assert_eq!(items.len(), 1);
-    assert_eq!(items[0]["id"], archived["id"]);
+    let output = run_cmd_output(&db_path, &["dedupe", "interactions", "--window", "soon"]);
+    assert_eq!(output.status.code(), Some(3));
+}
 
-    let list = run_cmd_json(&db_path, &["list", "--filter", "archived:false"]);
-    let items = list.as_array().expect("list array");
-    assert_eq!(items.len(), 1);
-    assert_eq!(items[0]["id"], active["id"]);
+#[test]
+fn cli_log_lists_contact_changes_and_export_can_include_them() {
+    let temp = TempDir::new().expect("temp dir");
+    let db_path = temp.path().join("knotter.sqlite3");
 
-    let list = run_cmd_json(
+    let ada = run_cmd_json(&db_path, &["add-contact", "--name", "Ada"]);
+    let ada_id = ada["id"].as_str().expect("id").to_string();
+    run_cmd(
         &db_path,
-        &["list", "--only-archived", "--filter", "archived:true"],
+        &[
+            "edit-contact",
+            &ada_id,
+            "--name",
+            "Ada Lovelace",
+            "--cadence-days",
+            "14",
+        ],
+    );
+    run_cmd(&db_path, &["edit-contact", &ada_id, "--cadence-days", "14"]);
+    run_cmd(&db_path, &["archive-contact", "Ada Lovelace"]);
+
+    let log = run_cmd_json(&db_path, &["log", "Ada Lovelace"]);
+    let entries = log.as_array().expect("array");
+    assert_eq!(entries.len(), 2);
+    assert_eq!(entries[0]["action"], "archive");
+    assert_eq!(entries[0]["origin"], "manual");
+    assert_eq!(entries[1]["action"], "update");
+    assert_eq!(
+        entries[1]["changes"],
+        serde_json::json!({
+            "cadence_days": [null, 14],
+            "display_name": ["Ada", "Ada Lovelace"],
+        })
+    );
+
+    let limited = run_cmd_json(&db_path, &["log", &ada_id, "--limit", "1"]);
+    assert_eq!(limited.as_array().expect("array").len(), 1);
+
+    let output = run_cmd_output(&db_path, &["log", &ada_id]);
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("display_name: Ada -> Ada Lovelace"));
+
+    let export = run_cmd_json(&db_path, &["export", "json"]);
+    assert!(export["contacts"][0].get("audit").is_none());
+    let export = run_cmd_json(&db_path, &["export", "json", "--include-audit"]);
+    assert_eq!(
+        export["contacts"][0]["audit"]
+            .as_array()
+            .expect("audit")
+            .len(),
+        2
     );
-    let items = list.as_array().expect("list array");
-    assert_eq!(items.len(), 1);
-    assert_eq!(items[0]["id"], archived["id"]);
 }
 
 #[test]
@@ -1916,6 +5922,285 @@ cadence_days = 30
     assert!(grace["next_touchpoint_at"].is_number());
 }
 
+#[test]
+fn cli_contact_priority_filters_orders_remind_and_gates_loops() {
+    let temp = TempDir::new().expect("temp dir");
+    let db_path = temp.path().join("knotter.sqlite3");
+    let config_path = temp.path().join("config.toml");
+    std::fs::write(
+        &config_path,
+        r#"
+[loops]
+schedule_missing = true
+anchor = "created-at"
+
+[[loops.tags]]
+tag = "friend"
+cadence_days = 30
+min_priority = 5
+"#,
+    )
+    .expect("write config");
+    restrict_config_permissions(&config_path);
+    let add = |args: &[&str]| -> String {
+        let created = run_cmd_json_with_config(&db_path, &config_path, args);
+        created["id"].as_str().expect("id").to_string()
+    };
+
+    let tomorrow = tomorrow_local_date();
+    add(&[
+        "add-contact",
+        "--name",
+        "Ada",
+        "--next-touchpoint-at",
+        &tomorrow,
+    ]);
+    let grace_id = add(&[
+        "add-contact",
+        "--name",
+        "Grace",
+        "--priority",
+        "10",
+        "--next-touchpoint-at",
+        &tomorrow,
+    ]);
+    let linus_id = add(&["add-contact", "--name", "Linus", "--tag", "friend"]);
+    let hedy_id = add(&[
+        "add-contact",
+        "--name",
+        "Hedy",
+        "--tag",
+        "friend",
+        "--priority",
+        "7",
+    ]);
+
+    // The friend rule only schedules contacts at priority 5 or more.
+    let linus = run_cmd_json_with_config(&db_path, &config_path, &["show", &linus_id]);
+    assert!(linus["cadence_days"].is_null());
+    assert_eq!(linus["priority"], 0);
+    let hedy = run_cmd_json_with_config(&db_path, &config_path, &["show", &hedy_id]);
+    assert_eq!(hedy["cadence_days"], 30);
+    assert_eq!(hedy["priority"], 7);
+
+    run_cmd_with_config(
+        &db_path,
+        &config_path,
+        &["edit-contact", &linus_id, "--priority", "5"],
+    );
+    run_cmd_with_config(&db_path, &config_path, &["loops", "apply"]);
+    let linus = run_cmd_json_with_config(&db_path, &config_path, &["show", &linus_id]);
+    assert_eq!(linus["cadence_days"], 30);
+
+    let starred = run_cmd_json_with_config(
+        &db_path,
+        &config_path,
+        &["list", "--filter", "priority:high"],
+    );
+    let names = starred
+        .as_array()
+        .expect("array")
+        .iter()
+        .map(|item| item["display_name"].as_str().expect("name"))
+        .collect::<Vec<_>>();
+    assert_eq!(names, ["Grace", "Hedy", "Linus"]);
+    let exact =
+        run_cmd_json_with_config(&db_path, &config_path, &["list", "--filter", "priority:7"]);
+    assert_eq!(exact.as_array().expect("array").len(), 1);
+
+    let human = run_cmd_with_config(&db_path, &config_path, &["list"]);
+    assert!(
+        human.contains(&format!("{grace_id}  ★ Grace  [")),
+        "{human}"
+    );
+    assert!(human.contains("  Ada  ["), "{human}");
+
+    // Same day: the starred contact comes first despite sorting after by name.
+    let remind = run_cmd_json_with_config(&db_path, &config_path, &["remind"]);
+    let soon = remind["soon"]
+        .as_array()
+        .expect("soon")
+        .iter()
+        .map(|item| item["display_name"].as_str().expect("name"))
+        .collect::<Vec<_>>();
+    assert_eq!(soon, ["Grace", "Ada"]);
+
+    let export = run_cmd_json_with_config(&db_path, &config_path, &["export", "json"]);
+    let grace = export["contacts"]
+        .as_array()
+        .expect("contacts")
+        .iter()
+        .find(|contact| contact["display_name"] == "Grace")
+        .expect("grace");
+    assert_eq!(grace["priority"], 10);
+
+    for args in [
+        &["edit-contact", grace_id.as_str(), "--priority", "11"][..],
+        &["list", "--filter", "priority:>=11"][..],
+    ] {
+        let output = run_cmd_output_with_config(&db_path, &config_path, args);
+        assert_eq!(output.status.code(), Some(3), "{args:?}");
+    }
+}
+
+#[test]
+fn cli_tag_rename_merge_and_delete_report_affected_contacts() {
+    let temp = TempDir::new().expect("temp dir");
+    let db_path = temp.path().join("knotter.sqlite3");
+    let config_path = temp.path().join("config.toml");
+    std::fs::write(
+        &config_path,
+        r#"
+[[loops.tags]]
+tag = "friends"
+cadence_days = 30
+"#,
+    )
+    .expect("write config");
+    restrict_config_permissions(&config_path);
+
+    let ada = run_cmd_json_with_config(
+        &db_path,
+        &config_path,
+        &[
+            "add-contact",
+            "--name",
+            "Ada",
+            "--tag",
+            "friends",
+            "--tag",
+            "pals",
+        ],
+    );
+    let ada_id = ada["id"].as_str().expect("id").to_string();
+    run_cmd_json_with_config(
+        &db_path,
+        &config_path,
+        &["add-contact", "--name", "Grace", "--tag", "friend"],
+    );
+
+    let dry = run_cmd_json_with_config(
+        &db_path,
+        &config_path,
+        &["tag", "rename", "friends", "friend", "--dry-run"],
+    );
+    assert_eq!(dry["affected_contacts"], 1);
+    assert_eq!(dry["dry_run"], true);
+
+    let output = run_cmd_output_with_config(
+        &db_path,
+        &config_path,
+        &["--json", "tag", "rename", "friends", "friend"],
+    );
+    assert!(output.status.success(), "rename failed: {:?}", output);
+    let renamed: Value = serde_json::from_slice(&output.stdout).expect("json");
+    assert_eq!(renamed["sources"], serde_json::json!(["friends"]));
+    assert_eq!(renamed["target"], "friend");
+    assert_eq!(renamed["affected_contacts"], 1);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("loops rule for tag \"friends\""));
+
+    let merged = run_cmd_json_with_config(
+        &db_path,
+        &config_path,
+        &["tag", "merge", "pals", "friend", "--into", "close"],
+    );
+    assert_eq!(merged["sources"], serde_json::json!(["pals", "friend"]));
+    assert_eq!(merged["affected_contacts"], 2);
+
+    let tags = run_cmd_json_with_config(&db_path, &config_path, &["tag", "ls"]);
+    assert_eq!(tags, serde_json::json!([{ "name": "close", "count": 2 }]));
+
+    let deleted = run_cmd_json_with_config(&db_path, &config_path, &["tag", "delete", "close"]);
+    assert_eq!(deleted["affected_contacts"], 2);
+    assert!(deleted["target"].is_null());
+    let shown = run_cmd_json_with_config(&db_path, &config_path, &["show", &ada_id]);
+    assert_eq!(shown["tags"], serde_json::json!([]));
+
+    let missing = run_cmd_output_with_config(&db_path, &config_path, &["tag", "delete", "close"]);
+    assert_eq!(missing.status.code(), Some(2));
+}
+
+#[test]
+fn cli_tag_ls_filters_sorts_counts_and_prunes_unused() {
+    let temp = TempDir::new().expect("temp dir");
+    let db_path = temp.path().join("knotter.sqlite3");
+
+    let ada = run_cmd_json(
+        &db_path,
+        &[
+            "add-contact",
+            "--name",
+            "Ada",
+            "--tag",
+            "work",
+            "--tag",
+            "friends",
+        ],
+    );
+    let ada_id = ada["id"].as_str().expect("id").to_string();
+    let grace = run_cmd_json(
+        &db_path,
+        &["add-contact", "--name", "Grace", "--tag", "friends"],
+    );
+    let grace_id = grace["id"].as_str().expect("id").to_string();
+    run_cmd_json(
+        &db_path,
+        &["add-contact", "--name", "Linus", "--tag", "friends"],
+    );
+    run_cmd(&db_path, &["tag", "add", &ada_id, "stale"]);
+    run_cmd(&db_path, &["tag", "rm", &ada_id, "stale"]);
+    run_cmd(&db_path, &["archive-contact", &grace_id]);
+
+    let by_count = run_cmd_json(&db_path, &["tag", "ls", "--sort", "count"]);
+    assert_eq!(
+        by_count,
+        serde_json::json!([
+            { "name": "friends", "count": 3 },
+            { "name": "work", "count": 1 },
+            { "name": "stale", "count": 0 },
+        ])
+    );
+    let active = run_cmd_json(
+        &db_path,
+        &["tag", "ls", "--active-only", "--min-count", "2"],
+    );
+    assert_eq!(
+        active,
+        serde_json::json!([{ "name": "friends", "count": 2 }])
+    );
+    let unused = run_cmd_json(&db_path, &["tag", "ls", "--unused"]);
+    assert_eq!(unused, serde_json::json!([{ "name": "stale", "count": 0 }]));
+    let output = run_cmd(&db_path, &["tag", "ls", "--counts", "--sort", "count"]);
+    assert_eq!(output, "3  friends\n1  work\n0  stale\n");
+
+    // Without --yes the prune asks first and refuses on end of input.
+    let output = run_cmd_output(&db_path, &["tag", "ls", "--prune-unused"]);
+    assert_eq!(output.status.code(), Some(3));
+    let config_dir = TempDir::new().expect("temp config dir");
+    let output = cargo_bin_cmd!("knotter")
+        .env("XDG_CONFIG_HOME", config_dir.path())
+        .args(["--db-path", db_path.to_str().expect("db path")])
+        .args(["--json", "tag", "ls", "--prune-unused"])
+        .write_stdin("y\n")
+        .output()
+        .expect("run prune");
+    assert!(output.status.success(), "prune failed: {:?}", output);
+    let pruned: Value = serde_json::from_slice(&output.stdout).expect("json");
+    assert_eq!(pruned, serde_json::json!({ "pruned": ["stale"] }));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("stale"));
+    assert_eq!(
+        run_cmd(&db_path, &["tag", "ls", "--prune-unused", "--yes"]).trim(),
+        "no unused tags"
+    );
+    assert_eq!(
+        run_cmd_json(&db_path, &["tag", "ls"])
+            .as_array()
+            .map(Vec::len),
+        Some(2)
+    );
+}
+
 #[test]
 fn cli_tag_add_apply_on_tag_change_updates_cadence() {
     let temp = TempDir::new().expect("temp dir");
@@ -1979,16 +6264,101 @@ cadence_days = 90
     let created = run_cmd_json_with_config(
         &db_path,
         &config_path,
-        &["add-contact", "--name", "Ada Lovelace", "--tag", "friend"],
+        &["add-contact", "--name", "Ada Lovelace", "--tag", "friend"],
+    );
+    let id = created["id"].as_str().expect("id").to_string();
+    assert_eq!(created["cadence_days"], 90);
+
+    let detail = run_cmd_json_with_config(&db_path, &config_path, &["show", &id]);
+    let tags = detail["tags"].as_array().expect("tags");
+    assert!(tags.iter().any(|tag| tag == "friend"));
+    assert_eq!(detail["cadence_days"], 90);
+    assert!(detail["next_touchpoint_at"].is_number());
+}
+
+#[test]
+fn cli_add_contact_applies_contact_defaults_unless_overridden() {
+    let temp = TempDir::new().expect("temp dir");
+    let db_path = temp.path().join("knotter.sqlite3");
+    let config_path = temp.path().join("config.toml");
+    let vcf_path = temp.path().join("contacts.vcf");
+
+    std::fs::write(
+        &config_path,
+        r#"
+[contacts.defaults]
+tags = ["friends"]
+cadence_days = 45
+timezone = "Europe/Berlin"
+next_touchpoint = "+7d"
+"#,
+    )
+    .expect("write config");
+    restrict_config_permissions(&config_path);
+
+    let created = run_cmd_json_with_config(
+        &db_path,
+        &config_path,
+        &["add-contact", "--name", "Ada Lovelace"],
+    );
+    let id = created["id"].as_str().expect("id").to_string();
+    let detail = run_cmd_json_with_config(&db_path, &config_path, &["show", &id]);
+    assert_eq!(detail["cadence_days"], 45);
+    assert_eq!(detail["timezone"], "Europe/Berlin");
+    assert_eq!(detail["tags"], serde_json::json!(["friends"]));
+    let next = detail["next_touchpoint_at"]
+        .as_i64()
+        .expect("next touchpoint");
+    let now = Utc::now().timestamp();
+    assert!(next > now + 5 * 86_400 && next < now + 9 * 86_400);
+
+    let created = run_cmd_json_with_config(
+        &db_path,
+        &config_path,
+        &[
+            "add-contact",
+            "--name",
+            "Grace Hopper",
+            "--tag",
+            "work",
+            "--cadence-days",
+            "10",
+        ],
+    );
+    let id = created["id"].as_str().expect("id").to_string();
+    let detail = run_cmd_json_with_config(&db_path, &config_path, &["show", &id]);
+    assert_eq!(detail["cadence_days"], 10);
+    assert_eq!(detail["tags"], serde_json::json!(["work"]));
+    assert_eq!(detail["timezone"], "Europe/Berlin");
+
+    let created = run_cmd_json_with_config(
+        &db_path,
+        &config_path,
+        &["add-contact", "--name", "Linus", "--no-defaults"],
+    );
+    let id = created["id"].as_str().expect("id").to_string();
+    let detail = run_cmd_json_with_config(&db_path, &config_path, &["show", &id]);
+    assert!(detail["cadence_days"].is_null());
+    assert!(detail["timezone"].is_null());
+    assert!(detail["next_touchpoint_at"].is_null());
+    assert_eq!(detail["tags"], serde_json::json!([]));
+
+    std::fs::write(
+        &vcf_path,
+        "BEGIN:VCARD\nVERSION:3.0\nFN:Imported\nEND:VCARD\n",
+    )
+    .expect("write vcf");
+    run_cmd_with_config(
+        &db_path,
+        &config_path,
+        &["import", "vcf", vcf_path.to_str().expect("path")],
     );
-    let id = created["id"].as_str().expect("id").to_string();
-    assert_eq!(created["cadence_days"], 90);
-
-    let detail = run_cmd_json_with_config(&db_path, &config_path, &["show", &id]);
-    let tags = detail["tags"].as_array().expect("tags");
-    assert!(tags.iter().any(|tag| tag == "friend"));
-    assert_eq!(detail["cadence_days"], 90);
-    assert!(detail["next_touchpoint_at"].is_number());
+    let listed =
+        run_cmd_json_with_config(&db_path, &config_path, &["list", "--filter", "Imported"]);
+    let imported = listed.as_array().expect("array");
+    assert_eq!(imported.len(), 1);
+    assert!(imported[0]["cadence_days"].is_null());
+    assert_eq!(imported[0]["tags"], serde_json::json!([]));
 }
 
 #[test]
@@ -2070,6 +6440,7 @@ cadence_days = 7
             kind: knotter_core::domain::InteractionKind::Call,
             note: "hello".to_string(),
             follow_up_at: None,
+            direction: None,
         })
         .expect("add interaction");
 
@@ -2161,6 +6532,125 @@ cadence_days = 90
     assert_eq!(detail["cadence_days"], 90);
 }
 
+#[test]
+fn cli_loops_apply_only_tag_and_skip_buckets() {
+    let temp = TempDir::new().expect("temp dir");
+    let db_path = temp.path().join("knotter.sqlite3");
+    let config_path = temp.path().join("config.toml");
+
+    std::fs::write(
+        &config_path,
+        r#"
+[loops]
+default_cadence_days = 120
+schedule_missing = false
+
+[[loops.tags]]
+tag = "friend"
+cadence_days = 90
+
+[[loops.tags]]
+tag = "family"
+cadence_days = 30
+"#,
+    )
+    .expect("write config");
+    restrict_config_permissions(&config_path);
+    // Seed without loops so add-contact does not pick up the default cadence.
+    let setup_config_path = temp.path().join("setup.toml");
+    std::fs::write(&setup_config_path, "").expect("write setup config");
+    restrict_config_permissions(&setup_config_path);
+
+    let add = |name: &str, extra: &[&str]| {
+        let mut args = vec!["add-contact", "--name", name];
+        args.extend_from_slice(extra);
+        let created = run_cmd_json_with_config(&db_path, &setup_config_path, &args);
+        created["id"].as_str().expect("id").to_string()
+    };
+    let friend = add("Ada Lovelace", &[]);
+    let family = add("Grace Hopper", &[]);
+    let kept = add("Alan Turing", &["--cadence-days", "180"]);
+    let archived = add("Edsger Dijkstra", &[]);
+    let untagged = add("Barbara Liskov", &[]);
+    run_cmd_with_config(
+        &db_path,
+        &setup_config_path,
+        &["tag", "add", &friend, "friend"],
+    );
+    run_cmd_with_config(
+        &db_path,
+        &setup_config_path,
+        &["tag", "add", &family, "family"],
+    );
+    run_cmd_with_config(
+        &db_path,
+        &setup_config_path,
+        &["tag", "add", &kept, "friend"],
+    );
+    run_cmd_with_config(
+        &db_path,
+        &setup_config_path,
+        &["tag", "add", &archived, "friend"],
+    );
+    run_cmd_with_config(
+        &db_path,
+        &setup_config_path,
+        &["archive-contact", &archived],
+    );
+
+    let output = run_cmd_output_with_config(
+        &db_path,
+        &config_path,
+        &["loops", "apply", "--only-tag", "colleague"],
+    );
+    assert_eq!(output.status.code(), Some(3));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("no loop rule for tag colleague"),
+        "{stderr}"
+    );
+    assert!(stderr.contains("friend, family"), "{stderr}");
+
+    let report = run_cmd_json_with_config(
+        &db_path,
+        &config_path,
+        &["loops", "apply", "--only-tag", "Friend"],
+    );
+    assert_eq!(report["matched"], 2);
+    assert_eq!(report["updated"], 1);
+    assert_eq!(report["skipped_archived"], 1);
+    assert_eq!(report["skipped_existing"], 1);
+    // Family and untagged contacts fall outside the selected rules and the
+    // default cadence does not apply to them.
+    assert_eq!(report["skipped_no_loop"], 2);
+    assert_eq!(report["skipped_unchanged"], 0);
+    assert_eq!(report["skipped"], 4);
+    let changes = report["changes"].as_array().expect("changes");
+    assert_eq!(changes.len(), 1);
+    assert_eq!(changes[0]["id"], friend.as_str());
+
+    for (id, cadence) in [
+        (&friend, Value::from(90)),
+        (&family, Value::Null),
+        (&kept, Value::from(180)),
+        (&archived, Value::Null),
+        (&untagged, Value::Null),
+    ] {
+        let detail = run_cmd_json_with_config(&db_path, &config_path, &["show", id]);
+        assert_eq!(detail["cadence_days"], cadence, "{id}");
+    }
+
+    let summary = run_cmd_with_config(&db_path, &config_path, &["loops", "apply"]);
+    assert!(
+        summary.contains(
+            "matched 4 | updated 2 | scheduled 0 | skipped 3 (archived 1, kept existing cadence 1, no loop 0, unchanged 1)"
+        ),
+        "{summary}"
+    );
+    let detail = run_cmd_json_with_config(&db_path, &config_path, &["show", &archived]);
+    assert_eq!(detail["cadence_days"], Value::Null);
+}
+
 #[test]
 fn cli_tag_remove_apply_loop_keeps_command_successful() {
     let temp = TempDir::new().expect("temp dir");
@@ -2374,3 +6864,576 @@ fn cli_sync_errors_without_sources_or_accounts() {
     let stderr = String::from_utf8(output.stderr).expect("utf8");
     assert!(stderr.contains("no contact sources, email accounts, or telegram accounts configured"));
 }
+
+/// A plaintext IMAP server with two fixed mailboxes, enough for the header
+/// sync: LOGIN, SELECT, UID SEARCH, UID FETCH, and LOGOUT. Each connection
+/// gets its own thread, so parallel fetches really run side by side. The
+/// counter tracks how many headers were sent.
+#[cfg(feature = "email-sync")]
+fn serve_fake_imap() -> (u16, std::sync::Arc<std::sync::atomic::AtomicUsize>) {
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    /// UID, From, and To of each message.
+    type Messages = &'static [(u32, &'static str, &'static str)];
+    const MAILBOXES: [(&str, Messages); 2] = [
+        (
+            "INBOX",
+            &[
+                (1, "Ada <ada@example.org>", "me@example.com"),
+                (2, "Grace <grace@example.org>", "me@example.com"),
+                (3, "Linus <linus@example.org>", "me@example.com"),
+            ],
+        ),
+        (
+            "Sent",
+            &[
+                (1, "me@example.com", "Ada <ada@example.org>"),
+                (2, "me@example.com", "Margaret <margaret@example.org>"),
+            ],
+        ),
+    ];
+
+    let listener = TcpListener::bind("127.0.0.1:0").expect("bind");
+    let port = listener.local_addr().expect("addr").port();
+    let served = Arc::new(AtomicUsize::new(0));
+    let counter = Arc::clone(&served);
+    std::thread::spawn(move || {
+        for stream in listener.incoming() {
+            let mut stream = stream.expect("accept");
+            let counter = Arc::clone(&counter);
+            std::thread::spawn(move || {
+                let mut reader = BufReader::new(stream.try_clone().expect("clone stream"));
+                write!(stream, "* OK fake IMAP ready\r\n").expect("greet");
+                let mut messages: Messages = &[];
+                let mut name = "";
+                loop {
+                    let mut line = String::new();
+                    if reader.read_line(&mut line).expect("read command") == 0 {
+                        return;
+                    }
+                    let (tag, command) = line.trim_end().split_once(' ').expect("tagged");
+                    let upper = command.to_ascii_uppercase();
+                    if upper.starts_with("SELECT") {
+                        let mailbox = command[7..].trim_matches('"');
+                        let (found, list) = MAILBOXES
+                            .iter()
+                            .find(|(found, _)| *found == mailbox)
+                            .expect("known mailbox");
+                        (name, messages) = (found, list);
+                        write!(
+                            stream,
+                            "* {} EXISTS\r\n* OK [UIDVALIDITY 7] ok\r\n{tag} OK [READ-WRITE] done\r\n",
+                            messages.len()
+                        )
+                        .expect("select");
+                    } else if let Some(range) = upper.strip_prefix("UID SEARCH UID ") {
+                        let start: u32 = range.trim_end_matches(":*").parse().expect("start");
+                        let uids: Vec<String> = messages
+                            .iter()
+                            .filter(|(uid, _, _)| *uid >= start)
+                            .map(|(uid, _, _)| uid.to_string())
+                            .collect();
+                        write!(stream, "* SEARCH {}\r\n{tag} OK done\r\n", uids.join(" "))
+                            .expect("search");
+                    } else if let Some(rest) = upper.strip_prefix("UID FETCH ") {
+                        let (set, _) = rest.split_once(' ').expect("fetch query");
+                        for uid in set.split(',').map(|uid| uid.parse::<u32>().expect("uid")) {
+                            let (_, from, to) = messages
+                                .iter()
+                                .find(|(found, _, _)| *found == uid)
+                                .expect("known uid");
+                            let header = format!(
+                                "Date: Mon, 02 Jan 2023 10:0{uid}:00 +0000\r\nFrom: {from}\r\nTo: {to}\r\nSubject: hello\r\nMessage-ID: <{name}-{uid}@example.org>\r\n\r\n"
+                            );
+                            write!(
+                                stream,
+                                "* {uid} FETCH (UID {uid} BODY[HEADER.FIELDS (DATE FROM TO CC SUBJECT MESSAGE-ID)] {{{}}}\r\n{header})\r\n",
+                                header.len()
+                            )
+                            .expect("fetch");
+                            counter.fetch_add(1, Ordering::SeqCst);
+                        }
+                        write!(stream, "{tag} OK done\r\n").expect("fetch done");
+                    } else if upper.starts_with("LOGOUT") {
+                        write!(stream, "* BYE\r\n{tag} OK done\r\n").expect("logout");
+                        return;
+                    } else {
+                        write!(stream, "{tag} OK done\r\n").expect("reply");
+                    }
+                }
+            });
+        }
+    });
+    (port, served)
+}
+
+#[cfg(feature = "email-sync")]
+#[test]
+fn cli_import_email_gives_the_same_result_with_one_or_several_jobs() {
+    let (port, served) = serve_fake_imap();
+    let temp = TempDir::new().expect("temp dir");
+    let config_path = temp.path().join("config.toml");
+    std::fs::write(
+        &config_path,
+        format!(
+            "[[contacts.email_accounts]]\nname = \"work\"\nhost = \"127.0.0.1\"\nport = {port}\nusername = \"me@example.com\"\npassword_cmd = \"echo pw\"\nmailboxes = [\"INBOX\", \"Sent\"]\nidentities = [\"me@example.com\"]\ntls = \"none\"\n"
+        ),
+    )
+    .expect("write config");
+    restrict_config_permissions(&config_path);
+
+    // Two runs, so the second picks up where the limit left the first.
+    let import = |db_path: &Path, limit: Option<&str>, jobs: &str| -> Vec<Value> {
+        let mut args = vec!["import", "email", "--jobs", jobs];
+        if let Some(limit) = limit {
+            args.extend(["--limit", limit]);
+        }
+        let mut seen = Vec::new();
+        for _ in 0..2 {
+            let report = run_cmd_json_with_config(db_path, &config_path, &args);
+            let names: Vec<Value> = run_cmd_json_with_config(db_path, &config_path, &["list"])
+                .as_array()
+                .expect("list")
+                .iter()
+                .map(|contact| contact["display_name"].clone())
+                .collect();
+            let states: Vec<Value> =
+                run_cmd_json_with_config(db_path, &config_path, &["sync", "email-state", "ls"])
+                    .as_array()
+                    .expect("states")
+                    .iter()
+                    .map(|state| serde_json::json!([state["mailbox"], state["last_uid"]]))
+                    .collect();
+            seen.push(serde_json::json!({ "report": report, "names": names, "states": states }));
+        }
+        seen
+    };
+
+    for limit in [None, Some("4")] {
+        let runs: Vec<Vec<Value>> = ["1", "4"]
+            .into_iter()
+            .map(|jobs| {
+                let db_path = temp.path().join(format!("jobs-{jobs}-{limit:?}.sqlite3"));
+                let served_before = served.load(std::sync::atomic::Ordering::SeqCst);
+                let run = import(&db_path, limit, jobs);
+                // Five messages in all: nothing is fetched only to be dropped.
+                assert_eq!(
+                    served.load(std::sync::atomic::Ordering::SeqCst) - served_before,
+                    5,
+                    "--jobs {jobs} --limit {limit:?}"
+                );
+                run
+            })
+            .collect();
+        assert_eq!(runs[0], runs[1], "--limit {limit:?}");
+        let expected_seen = if limit.is_some() { [4, 1] } else { [5, 0] };
+        for (run, seen) in runs[0].iter().zip(expected_seen) {
+            assert_eq!(run["report"]["messages_seen"], seen, "--limit {limit:?}");
+        }
+        assert_eq!(runs[0][1]["names"].as_array().map(Vec::len), Some(4));
+    }
+}
+
+#[cfg(feature = "webhook-notify")]
+fn serve_webhook_once(status: &'static str) -> (String, std::thread::JoinHandle<String>) {
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+
+    let listener = TcpListener::bind("127.0.0.1:0").expect("bind");
+    let url = format!("http://{}/hook", listener.local_addr().expect("addr"));
+    let handle = std::thread::spawn(move || {
+        let (mut stream, _) = listener.accept().expect("accept");
+        let mut reader = BufReader::new(stream.try_clone().expect("clone stream"));
+        let mut head = String::new();
+        let mut content_length = 0usize;
+        loop {
+            let mut line = String::new();
+            reader.read_line(&mut line).expect("read header");
+            if line == "\r\n" || line.is_empty() {
+                break;
+            }
+            if let Some(value) = line.to_ascii_lowercase().strip_prefix("content-length:") {
+                content_length = value.trim().parse().expect("content length");
+            }
+            head.push_str(&line);
+        }
+        let mut body = vec![0u8; content_length];
+        reader.read_exact(&mut body).expect("read body");
+        write!(
+            stream,
+            "HTTP/1.1 {status}\r\nContent-Length: 0\r\nConnection: close\r\n\r\n"
+        )
+        .expect("write response");
+        format!("{head}\n{}", String::from_utf8(body).expect("utf8 body"))
+    });
+    (url, handle)
+}
+
+#[cfg(feature = "webhook-notify")]
+#[test]
+fn cli_remind_webhook_posts_json_and_reports_http_errors() {
+    let temp = TempDir::new().expect("temp dir");
+    let db_path = temp.path().join("knotter.sqlite3");
+    let config_path = temp.path().join("config.toml");
+
+    let tomorrow = (Local::now().date_naive() + Duration::days(1))
+        .format("%Y-%m-%d")
+        .to_string();
+    run_cmd(
+        &db_path,
+        &[
+            "add-contact",
+            "--name",
+            "Ada Lovelace",
+            "--next-touchpoint-at",
+            &tomorrow,
+        ],
+    );
+
+    let write_config = |url: &str| {
+        std::fs::write(
+            &config_path,
+            format!(
+                "[notifications]\nenabled = true\nbackend = \"webhook\"\n\n[notifications.webhook]\nurl = \"{url}\"\nformat = \"json\"\ntoken_env = \"KNOTTER_TEST_WEBHOOK_TOKEN\"\nallow_insecure = true\n"
+            ),
+        )
+        .expect("write config");
+        restrict_config_permissions(&config_path);
+    };
+
+    let (url, server) = serve_webhook_once("200 OK");
+    write_config(&url);
+    let output = cargo_bin_cmd!("knotter")
+        .env("KNOTTER_TEST_WEBHOOK_TOKEN", "secret-token")
+        .args([
+            "--db-path",
+            db_path.to_str().expect("db path"),
+            "--config",
+            config_path.to_str().expect("config path"),
+            "remind",
+        ])
+        .output()
+        .expect("run command");
+    assert!(output.status.success(), "command failed: {:?}", output);
+    let request = server.join().expect("server");
+    assert!(
+        request.contains("authorization: Bearer secret-token"),
+        "request: {request}"
+    );
+    let body = request.split_once('\n').map(|(_, rest)| rest).unwrap_or("");
+    let body = &body[body.find("\n{").expect("json body") + 1..];
+    let payload: Value = serde_json::from_str(body).expect("json payload");
+    assert_eq!(payload["title"], "knotter reminders");
+    assert_eq!(
+        payload["data"]["reminders"]["soon"][0]["display_name"],
+        "Ada Lovelace"
+    );
+
+    let (url, server) = serve_webhook_once("500 Internal Server Error");
+    write_config(&url);
+    let output = cargo_bin_cmd!("knotter")
+        .env("KNOTTER_TEST_WEBHOOK_TOKEN", "secret-token")
+        .args([
+            "--db-path",
+            db_path.to_str().expect("db path"),
+            "--config",
+            config_path.to_str().expect("config path"),
+            "remind",
+        ])
+        .output()
+        .expect("run command");
+    server.join().expect("server");
+    assert!(!output.status.success());
+    let stdout = String::from_utf8(output.stdout).expect("utf8");
+    let stderr = String::from_utf8(output.stderr).expect("utf8");
+    assert!(stdout.contains("Ada Lovelace"), "stdout: {stdout}");
+    assert!(
+        stderr.contains("webhook notification failed"),
+        "stderr: {stderr}"
+    );
+    assert!(stderr.contains("500"), "stderr: {stderr}");
+}
+
+#[test]
+fn cli_readonly_allows_reads_and_rejects_writes() {
+    let temp = TempDir::new().expect("temp dir");
+    let db_path = temp.path().join("knotter.sqlite3");
+
+    let missing = run_cmd_output(&db_path, &["--readonly", "list"]);
+    assert_eq!(missing.status.code(), Some(2));
+    assert!(!db_path.exists());
+
+    run_cmd(&db_path, &["add-contact", "--name", "Ada"]);
+
+    let list = run_cmd_json(&db_path, &["--readonly", "list"]);
+    assert_eq!(list.as_array().expect("array").len(), 1);
+
+    let denied = run_cmd_output(&db_path, &["--readonly", "add-contact", "--name", "Grace"]);
+    assert_eq!(denied.status.code(), Some(3));
+    let stderr = String::from_utf8(denied.stderr).expect("utf8");
+    assert!(stderr.contains("read-only mode"), "stderr: {stderr}");
+
+    let dry_run = run_cmd_output(
+        &db_path,
+        &["--readonly", "merge", "scan-same-name", "--dry-run"],
+    );
+    assert!(dry_run.status.success(), "dry run failed: {dry_run:?}");
+
+    let id = list[0]["id"].as_str().expect("id").to_string();
+    let edit = run_cmd_json(
+        &db_path,
+        &[
+            "--readonly",
+            "edit-contact",
+            &id,
+            "--name",
+            "Grace",
+            "--dry-run",
+        ],
+    );
+    assert_eq!(edit["dry_run"], true);
+    assert_eq!(edit["changes"][0]["field"], "name");
+    let schedule = run_cmd_output(
+        &db_path,
+        &[
+            "--readonly",
+            "schedule",
+            &id,
+            "--at",
+            "2030-01-01",
+            "--dry-run",
+        ],
+    );
+    assert!(schedule.status.success(), "dry run failed: {schedule:?}");
+
+    let list = run_cmd_json(&db_path, &["list"]);
+    assert_eq!(list.as_array().expect("array").len(), 1);
+    assert_eq!(list[0]["display_name"], "Ada");
+    assert!(list[0]["next_touchpoint_at"].is_null());
+}
+
+#[test]
+fn cli_followups_list_remind_and_done() {
+    let temp = TempDir::new().expect("temp dir");
+    let db_path = temp.path().join("knotter.sqlite3");
+
+    run_cmd(&db_path, &["add-contact", "--name", "Ada Lovelace"]);
+    run_cmd(&db_path, &["add-contact", "--name", "Grace Hopper"]);
+    let list = run_cmd_json(&db_path, &["list"]);
+    let ada = list[0]["id"].as_str().expect("id").to_string();
+    let grace = list[1]["id"].as_str().expect("id").to_string();
+
+    let pending = run_cmd_json(
+        &db_path,
+        &[
+            "add-note",
+            &ada,
+            "--note",
+            "promised the slides",
+            "--follow-up-at",
+            "2020-01-02",
+        ],
+    );
+    run_cmd(
+        &db_path,
+        &[
+            "add-note",
+            &grace,
+            "--note",
+            "someday",
+            "--follow-up-at",
+            "2099-01-01",
+        ],
+    );
+    let pending_id = pending["id"].as_str().expect("interaction id").to_string();
+
+    let followups = run_cmd_json(&db_path, &["followups"]);
+    let followups = followups.as_array().expect("array");
+    assert_eq!(followups.len(), 1);
+    assert_eq!(followups[0]["interaction_id"], pending_id);
+    assert_eq!(followups[0]["display_name"], "Ada Lovelace");
+
+    let remind = run_cmd_json(&db_path, &["remind"]);
+    let bucket = remind["follow_ups"].as_array().expect("follow_ups array");
+    assert_eq!(bucket.len(), 1);
+    assert_eq!(bucket[0]["note"], "promised the slides");
+
+    let filtered = run_cmd_json(&db_path, &["list", "--filter", "followup:pending"]);
+    assert_eq!(filtered.as_array().expect("array").len(), 2);
+
+    let done = run_cmd_json(
+        &db_path,
+        &[
+            "followups",
+            "done",
+            &pending_id,
+            "--note",
+            "sent the slides",
+        ],
+    );
+    assert_eq!(done["id"], pending_id);
+    assert!(done["logged_interaction_id"].is_string());
+
+    let again = run_cmd_output(&db_path, &["followups", "done", &pending_id]);
+    assert_eq!(again.status.code(), Some(3));
+
+    let followups = run_cmd_json(&db_path, &["followups"]);
+    assert!(followups.as_array().expect("array").is_empty());
+    let show = run_cmd_json(&db_path, &["show", &ada]);
+    assert_eq!(
+        show["recent_interactions"].as_array().expect("array").len(),
+        2
+    );
+}
+
+#[test]
+fn cli_roulette_skips_recent_contacts_and_touch_logs_picks() {
+    let temp = TempDir::new().expect("temp dir");
+    let db_path = temp.path().join("knotter.sqlite3");
+
+    let ada = run_cmd_json(
+        &db_path,
+        &["add-contact", "--name", "Ada", "--tag", "friend"],
+    );
+    let ada_id = ada["id"].as_str().expect("id").to_string();
+    let grace = run_cmd_json(
+        &db_path,
+        &["add-contact", "--name", "Grace", "--tag", "friend"],
+    );
+    let grace_id = grace["id"].as_str().expect("id").to_string();
+    run_cmd_json(&db_path, &["add-contact", "--name", "Linus"]);
+    run_cmd(
+        &db_path,
+        &[
+            "add-note",
+            &ada_id,
+            "--note",
+            "Talked about\nthe engine",
+            "--when",
+            "2020-01-01 12:00",
+        ],
+    );
+    run_cmd(&db_path, &["add-note", &grace_id, "--note", "just now"]);
+
+    let picks = run_cmd_json(&db_path, &["roulette", "-n", "5", "--filter", "#friend"]);
+    let picks = picks.as_array().expect("array");
+    assert_eq!(picks.len(), 1);
+    assert_eq!(picks[0]["id"], ada_id.as_str());
+    assert_eq!(picks[0]["latest_note"], "Talked about the engine");
+    assert!(picks[0]["last_interaction_at"].is_number());
+
+    let picks = run_cmd_json(&db_path, &["pick", "-n", "5", "--touch"]);
+    let picks = picks.as_array().expect("array");
+    assert_eq!(picks.len(), 2);
+    assert!(picks.iter().all(|pick| pick["touched"] == true));
+
+    let shown = run_cmd_json(&db_path, &["show", &ada_id]);
+    assert_eq!(shown["recent_interactions"][0]["kind"], "other:reached-out");
+    let picks = run_cmd_json(&db_path, &["roulette", "--min-gap-days", "1"]);
+    assert_eq!(picks, serde_json::json!([]));
+}
+
+#[test]
+fn cli_export_json_streams_large_databases() {
+    let temp = TempDir::new().expect("temp dir");
+    let db_path = temp.path().join("knotter.sqlite3");
+    let out_path = temp.path().join("export").join("snapshot.json");
+    let store = Store::open(&db_path).expect("open store");
+    store.migrate().expect("migrate");
+    let now = 1_700_000_000;
+
+    // More contacts than one export batch, with a few thousand interactions.
+    let contact_count = 450;
+    let per_contact = 8;
+    let mut ids = Vec::new();
+    for index in 0..contact_count {
+        let contact = store
+            .contacts()
+            .create(
+                now,
+                knotter_store::repo::ContactNew {
+                    display_name: format!("Contact {index:04}"),
+                    email: Some(format!("contact{index}@example.com")),
+                    phone: None,
+                    handle: None,
+                    timezone: None,
+                    next_touchpoint_at: None,
+                    cadence_days: None,
+                    archived_at: None,
+                    organization: None,
+                },
+            )
+            .expect("create contact");
+        store
+            .tags()
+            .add_tag_to_contact(
+                &contact.id.to_string(),
+                knotter_core::domain::TagName::new("bulk").expect("tag"),
+            )
+            .expect("tag contact");
+        ids.push(contact.id);
+    }
+    let tx = store
+        .connection()
+        .unchecked_transaction()
+        .expect("transaction");
+    let interactions = knotter_store::repo::InteractionsRepo::new(&tx);
+    for id in &ids {
+        for step in 0..per_contact {
+            interactions
+                .add(knotter_store::repo::InteractionNew {
+                    contact_id: *id,
+                    occurred_at: now - step * 3600,
+                    created_at: now,
+                    kind: InteractionKind::Email,
+                    note: format!("message {step}"),
+                    follow_up_at: None,
+                    direction: None,
+                })
+                .expect("add interaction");
+        }
+    }
+    tx.commit().expect("commit");
+    drop(store);
+
+    let report = run_cmd_json(
+        &db_path,
+        &[
+            "export",
+            "json",
+            "--out",
+            out_path.to_str().expect("out path"),
+        ],
+    );
+    assert_eq!(report["format"], "json");
+    assert_eq!(report["count"], contact_count);
+
+    let data = std::fs::read_to_string(&out_path).expect("read export");
+    let snapshot: knotter_core::dto::ExportSnapshotDto =
+        serde_json::from_str(&data).expect("parse export");
+    assert_eq!(snapshot.contacts.len(), contact_count as usize);
+    let interactions: usize = snapshot
+        .contacts
+        .iter()
+        .map(|contact| contact.interactions.len())
+        .sum();
+    assert_eq!(interactions, (contact_count * per_contact) as usize);
+    assert!(snapshot
+        .contacts
+        .iter()
+        .all(|contact| contact.tags == ["bulk"] && contact.emails.len() == 1));
+    // Streaming produces the same bytes as serializing the whole snapshot at once.
+    assert_eq!(
+        serde_json::to_string_pretty(&snapshot).expect("serialize"),
+        data
+    );
+
+    let output = run_cmd_output(&db_path, &["export", "json"]);
+    assert!(output.status.success(), "export failed: {:?}", output);
+    let streamed: knotter_core::dto::ExportSnapshotDto =
+        serde_json::from_slice(&output.stdout).expect("parse stdout export");
+    assert_eq!(streamed.contacts.len(), contact_count as usize);
+}
@@ -0,0 +1,191 @@
+//! Starter config written by `knotter init`.
+
+use toml::Value;
+
+/// Answers collected by `knotter init`; [`InitConfig::render`] turns them into
+/// a config file that only sets what was chosen.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InitConfig {
+    pub due_soon_days: i64,
+    pub default_cadence_days: Option<i32>,
+    pub email: Option<InitEmail>,
+    pub source: Option<InitSource>,
+}
+
+/// SMTP settings for the email notification backend.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InitEmail {
+    pub smtp_host: String,
+    pub from: String,
+    pub to: Vec<String>,
+    pub username: Option<String>,
+    /// Env var holding the SMTP password; only written with a username.
+    pub password_env: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InitSource {
+    Carddav {
+        name: String,
+        url: String,
+        username: String,
+        password_env: Option<String>,
+    },
+    Macos {
+        name: String,
+    },
+}
+
+impl InitConfig {
+    pub fn render(&self) -> String {
+        let mut lines = vec![
+            "# knotter configuration written by `knotter init`.".to_string(),
+            "# Keep this file user-readable only (chmod 600). Every option is".to_string(),
+            "# described in docs/configuration.md; validate changes with".to_string(),
+            "# `knotter config check`.".to_string(),
+            String::new(),
+            format!("due_soon_days = {}", self.due_soon_days),
+        ];
+        if let Some(days) = self.default_cadence_days {
+            lines.push(format!("default_cadence_days = {days}"));
+        }
+
+        if let Some(email) = &self.email {
+            lines.push(String::new());
+            lines.push("[notifications]".to_string());
+            lines.push("enabled = true".to_string());
+            lines.push("backend = \"email\"".to_string());
+            lines.push(String::new());
+            lines.push("[notifications.email]".to_string());
+            lines.push(format!("from = {}", quote(&email.from)));
+            let to = email.to.iter().map(|to| quote(to)).collect::<Vec<_>>();
+            lines.push(format!("to = [{}]", to.join(", ")));
+            lines.push(format!("smtp_host = {}", quote(&email.smtp_host)));
+            if let Some(username) = &email.username {
+                lines.push(format!("username = {}", quote(username)));
+                if let Some(var) = &email.password_env {
+                    lines.push(format!("password_env = {}", quote(var)));
+                }
+            }
+        }
+
+        if let Some(source) = &self.source {
+            lines.push(String::new());
+            lines.push("[[contacts.sources]]".to_string());
+            match source {
+                InitSource::Carddav {
+                    name,
+                    url,
+                    username,
+                    password_env,
+                } => {
+                    lines.push(format!("name = {}", quote(name)));
+                    lines.push("type = \"carddav\"".to_string());
+                    lines.push(format!("url = {}", quote(url)));
+                    lines.push(format!("username = {}", quote(username)));
+                    if let Some(var) = password_env {
+                        lines.push(format!("password_env = {}", quote(var)));
+                    }
+                }
+                InitSource::Macos { name } => {
+                    lines.push(format!("name = {}", quote(name)));
+                    lines.push("type = \"macos\"".to_string());
+                }
+            }
+        }
+
+        lines.push(String::new());
+        lines.join("\n")
+    }
+}
+
+fn quote(value: &str) -> String {
+    Value::String(value.to_string()).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{InitConfig, InitEmail, InitSource};
+    use crate::{merge_config, ConfigFile, ContactSourceKind, NotificationBackend, SecretSource};
+
+    fn parse(contents: &str) -> crate::AppConfig {
+        let parsed: ConfigFile = toml::from_str(contents).expect("parse toml");
+        merge_config(parsed).expect("merge")
+    }
+
+    #[test]
+    fn render_minimal_config_sets_only_due_soon_days() {
+        let rendered = InitConfig {
+            due_soon_days: 10,
+            default_cadence_days: None,
+            email: None,
+            source: None,
+        }
+        .render();
+        let config = parse(&rendered);
+        assert_eq!(config.due_soon_days, 10);
+        assert_eq!(config.default_cadence_days, None);
+        assert!(!config.notifications.enabled);
+        assert!(config.contacts.sources.is_empty());
+    }
+
+    #[test]
+    fn render_full_config_round_trips() {
+        let rendered = InitConfig {
+            due_soon_days: 7,
+            default_cadence_days: Some(30),
+            email: Some(InitEmail {
+                smtp_host: "smtp.example.com".to_string(),
+                from: "Knotter <knotter@example.com>".to_string(),
+                to: vec!["you@example.com".to_string(), "me@example.com".to_string()],
+                username: Some("user@example.com".to_string()),
+                password_env: Some("KNOTTER_SMTP_PASSWORD".to_string()),
+            }),
+            source: Some(InitSource::Carddav {
+                name: "gmail".to_string(),
+                url: "https://example.test/carddav/".to_string(),
+                username: "o\"brien\\x".to_string(),
+                password_env: Some("KNOTTER_GMAIL_PASSWORD".to_string()),
+            }),
+        }
+        .render();
+        let config = parse(&rendered);
+        assert_eq!(config.default_cadence_days, Some(30));
+        assert!(config.notifications.enabled);
+        assert_eq!(config.notifications.backend, NotificationBackend::Email);
+        let email = config.notifications.email.expect("email");
+        assert_eq!(email.smtp_host, "smtp.example.com");
+        assert_eq!(email.to.len(), 2);
+        assert!(matches!(
+            email.password,
+            Some(SecretSource::Env(ref var)) if var == "KNOTTER_SMTP_PASSWORD"
+        ));
+        assert_eq!(config.contacts.sources.len(), 1);
+        assert_eq!(config.contacts.sources[0].name, "gmail");
+        match &config.contacts.sources[0].kind {
+            ContactSourceKind::Carddav(source) => {
+                assert_eq!(source.username.as_deref(), Some("o\"brien\\x"));
+            }
+            other => panic!("expected carddav, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn render_macos_source() {
+        let rendered = InitConfig {
+            due_soon_days: 7,
+            default_cadence_days: None,
+            email: None,
+            source: Some(InitSource::Macos {
+                name: "macos".to_string(),
+            }),
+        }
+        .render();
+        assert!(rendered.contains("type = \"macos\""));
+        let config = parse(&rendered);
+        assert!(matches!(
+            config.contacts.sources[0].kind,
+            ContactSourceKind::Macos(_)
+        ));
+    }
+}
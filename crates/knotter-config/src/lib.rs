@@ -1,29 +1,55 @@
-use std::collections::HashSet;
+use std::collections::{BTreeMap, BTreeSet, HashSet};
 use std::env;
 use std::fs;
 use std::path::{Path, PathBuf};
 
-use knotter_core::domain::TagName;
-use knotter_core::rules::cadence::MAX_CADENCE_DAYS;
-use knotter_core::rules::{validate_soon_days, LoopPolicy, LoopRule, LoopStrategy};
+use chrono::{NaiveDate, NaiveTime};
+use knotter_core::domain::{
+    normalize_email, phone_match_key, validate_contact_priority, InteractionKind, TagName,
+    MAX_CONTACT_PRIORITY,
+};
+use knotter_core::rules::cadence::{MAX_CADENCE_DAYS, MAX_JITTER_DAYS};
+use knotter_core::rules::{validate_soon_days, LoopPolicy, LoopRule, LoopStrategy, QuietHours};
+use knotter_core::template::{is_variable_name, NoteTemplate};
+use knotter_core::time::{parse_duration, parse_relative_date, parse_timezone, Tz};
 use serde::Deserialize;
 use thiserror::Error;
 
+mod init;
+
+pub use init::{InitConfig, InitEmail, InitSource};
+
 const APP_DIR: &str = "knotter";
 const CONFIG_FILENAME: &str = "config.toml";
 
 pub const DEFAULT_SOON_DAYS: i64 = 7;
 pub const DEFAULT_TELEGRAM_SNIPPET_LEN: usize = 160;
 pub const MAX_RANDOM_CONTACTS_IF_NO_REMINDERS: usize = 100;
+pub const DEFAULT_NOTIFICATIONS_MAX_PER_RUN: usize = 5;
+pub const MAX_NOTIFICATIONS_MAX_PER_RUN: usize = 50;
+pub const MAX_NOTIFICATIONS_MAX_PER_BUCKET: usize = 1000;
+pub const DEFAULT_SYNC_MAX_FUTURE_SKEW_DAYS: i64 = 2;
+pub const MAX_SYNC_MAX_FUTURE_SKEW_DAYS: i64 = 365;
+pub const DEFAULT_INTERACTIONS_DEDUPE_WINDOW_MINUTES: i64 = 5;
+pub const MAX_INTERACTIONS_DEDUPE_WINDOW_MINUTES: i64 = 1440;
+
+/// Commented config template written by `knotter config init`.
+pub const CONFIG_TEMPLATE: &str = include_str!("template.toml");
 
 #[derive(Debug, Clone)]
 pub struct AppConfig {
     pub due_soon_days: i64,
     pub default_cadence_days: Option<i32>,
+    /// Zone local days are cut in (due buckets, dates, parsing); `None` uses
+    /// the system zone.
+    pub timezone: Option<Tz>,
     pub notifications: NotificationsConfig,
     pub interactions: InteractionsConfig,
     pub loops: LoopConfig,
     pub contacts: ContactsConfig,
+    pub tui: TuiConfig,
+    pub sync: SyncConfig,
+    pub exports: ExportsConfig,
 }
 
 #[derive(Debug, Clone)]
@@ -31,12 +57,72 @@ pub struct NotificationsConfig {
     pub enabled: bool,
     pub backend: NotificationBackend,
     pub email: Option<NotificationsEmailConfig>,
+    pub webhook: Option<NotificationsWebhookConfig>,
     pub random_contacts_if_no_reminders: usize,
+    /// Where reminders go when the email backend fails to send.
+    pub fallback: NotificationFallback,
+    /// Desktop/stdout: one notification per overdue or due-today contact.
+    pub per_contact: bool,
+    /// Cap on per-contact notifications; the rest are summarized in one more.
+    pub max_per_run: usize,
+    /// Most urgent entries shown per due bucket in formatted reminders; the
+    /// rest are counted. `None` shows everything.
+    pub max_per_bucket: Option<usize>,
+    /// Local-time window in which `remind --notify` skips delivery.
+    pub quiet_hours: Option<QuietHours>,
 }
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone)]
 pub struct InteractionsConfig {
     pub auto_reschedule: bool,
+    /// A manual touch or note matching one logged this many minutes earlier
+    /// (same contact, kind, and note) is reported instead of added; 0 disables.
+    pub dedupe_window_minutes: i64,
+    /// Kind `touch` logs without `--kind`; `None` means `other:touch`.
+    pub default_touch_kind: Option<InteractionKind>,
+    /// Note templates for `touch`/`add-note --template`, by name.
+    pub templates: BTreeMap<String, NoteTemplate>,
+}
+
+impl Default for InteractionsConfig {
+    fn default() -> Self {
+        Self {
+            auto_reschedule: false,
+            dedupe_window_minutes: DEFAULT_INTERACTIONS_DEDUPE_WINDOW_MINUTES,
+            default_touch_kind: None,
+            templates: BTreeMap::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct TuiConfig {
+    pub colorize_rows: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct SyncConfig {
+    /// Imported interactions dated more than this many days after now are skipped.
+    pub max_future_skew_days: i64,
+}
+
+impl Default for SyncConfig {
+    fn default() -> Self {
+        Self {
+            max_future_skew_days: DEFAULT_SYNC_MAX_FUTURE_SKEW_DAYS,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ExportsConfig {
+    pub ics: IcsExportConfig,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct IcsExportConfig {
+    /// Seconds before each touchpoint to attach a VALARM; `None` adds no alarms.
+    pub alarm_seconds: Option<i64>,
 }
 
 #[derive(Debug, Clone)]
@@ -47,9 +133,30 @@ pub struct NotificationsEmailConfig {
     pub smtp_host: String,
     pub smtp_port: Option<u16>,
     pub username: Option<String>,
-    pub password_env: Option<String>,
+    pub password: Option<SecretSource>,
     pub tls: EmailTls,
     pub timeout_seconds: Option<u64>,
+    /// Send a styled HTML part next to the plain-text body.
+    pub html: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct NotificationsWebhookConfig {
+    pub url: String,
+    pub format: WebhookFormat,
+    pub token: Option<SecretSource>,
+    pub timeout_seconds: Option<u64>,
+    pub allow_insecure: bool,
+}
+
+/// Where a credential is read from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SecretSource {
+    /// Name of an environment variable (`*_env` fields).
+    Env(String),
+    /// Program and arguments from a `*_cmd` field, split shell-words style and run
+    /// without a shell; its stdout is the secret.
+    Command(Vec<String>),
 }
 
 #[derive(Debug, Clone)]
@@ -59,6 +166,8 @@ pub struct LoopConfig {
     pub schedule_missing: bool,
     pub anchor: LoopAnchor,
     pub override_existing: bool,
+    /// Spread newly scheduled touchpoints over ±N days, per contact.
+    pub jitter_days: i32,
 }
 
 impl Default for LoopConfig {
@@ -69,6 +178,7 @@ impl Default for LoopConfig {
             schedule_missing: false,
             anchor: LoopAnchor::Now,
             override_existing: false,
+            jitter_days: 0,
         }
     }
 }
@@ -86,6 +196,106 @@ pub struct ContactsConfig {
     pub sources: Vec<ContactSourceConfig>,
     pub email_accounts: Vec<EmailAccountConfig>,
     pub telegram_accounts: Vec<TelegramAccountConfig>,
+    pub defaults: ContactDefaultsConfig,
+    /// Give contacts created by email, telegram, and vCard imports the
+    /// top-level `default_cadence_days` and a first touchpoint from it.
+    pub apply_default_cadence_on_import: bool,
+    /// Store phone numbers entered or imported in canonical form (digits with
+    /// an optional leading `+`, plus any extension).
+    pub normalize_phones: bool,
+    /// The user's own addresses, handles, and numbers, which imports never turn
+    /// into contacts.
+    pub ignore_identities: IgnoredIdentities,
+}
+
+/// `contacts.ignore_identities` plus every account's own identities: email
+/// account `identities` (or the username) and telegram account phones.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct IgnoredIdentities {
+    /// Lowercased addresses.
+    pub emails: BTreeSet<String>,
+    /// Lowercased handles without the leading `@`.
+    pub handles: BTreeSet<String>,
+    /// Phone match keys (see `phone_match_key`).
+    pub phones: BTreeSet<String>,
+}
+
+impl IgnoredIdentities {
+    /// Files `raw` as an email (`a@b`), handle (`@name`, or any other text
+    /// with letters), or phone number. Returns false when it is none of them.
+    pub fn insert(&mut self, raw: &str) -> bool {
+        let trimmed = raw.trim();
+        if let Some(handle) = trimmed.strip_prefix('@') {
+            return self.insert_handle(handle);
+        }
+        if trimmed.contains('@') {
+            let Some(email) = normalize_email(trimmed) else {
+                return false;
+            };
+            self.emails.insert(email);
+            return true;
+        }
+        if !trimmed.chars().any(char::is_alphabetic) {
+            if let Some(phone) = phone_match_key(trimmed) {
+                self.phones.insert(phone);
+                return true;
+            }
+        }
+        self.insert_handle(trimmed)
+    }
+
+    fn insert_handle(&mut self, raw: &str) -> bool {
+        let Some(handle) = normalize_ignored_handle(raw) else {
+            return false;
+        };
+        self.handles.insert(handle);
+        true
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.emails.is_empty() && self.handles.is_empty() && self.phones.is_empty()
+    }
+
+    pub fn contains_email(&self, value: &str) -> bool {
+        normalize_email(value).is_some_and(|email| self.emails.contains(&email))
+    }
+
+    /// Matches with or without a leading `@`, ignoring case.
+    pub fn contains_handle(&self, value: &str) -> bool {
+        normalize_ignored_handle(value).is_some_and(|handle| self.handles.contains(&handle))
+    }
+
+    pub fn contains_phone(&self, value: &str) -> bool {
+        phone_match_key(value).is_some_and(|phone| self.phones.contains(&phone))
+    }
+}
+
+fn normalize_ignored_handle(raw: &str) -> Option<String> {
+    let handle = raw.trim().trim_start_matches('@').trim();
+    if handle.is_empty() || handle.chars().any(char::is_whitespace) {
+        return None;
+    }
+    Some(handle.to_lowercase())
+}
+
+/// Prefills for contacts created by `add-contact` and the TUI add form
+/// (`[contacts.defaults]`). Imports never apply them.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ContactDefaultsConfig {
+    pub tags: Vec<String>,
+    pub cadence_days: Option<i32>,
+    pub timezone: Option<String>,
+    /// Relative date expression such as `+7d` or `next-monday`.
+    pub next_touchpoint: Option<String>,
+}
+
+impl ContactDefaultsConfig {
+    pub fn is_empty(&self) -> bool {
+        self.tags.is_empty()
+            && self.cadence_days.is_none()
+            && self.timezone.is_none()
+            && self.next_touchpoint.is_none()
+    }
 }
 
 impl ContactsConfig {
@@ -125,7 +335,7 @@ pub enum ContactSourceKind {
 pub struct CardDavSourceConfig {
     pub url: String,
     pub username: Option<String>,
-    pub password_env: Option<String>,
+    pub password: Option<SecretSource>,
     pub tag: Option<String>,
 }
 
@@ -133,6 +343,7 @@ pub struct CardDavSourceConfig {
 pub struct MacosSourceConfig {
     pub group: Option<String>,
     pub tag: Option<String>,
+    pub groups_as_tags: bool,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
@@ -156,12 +367,31 @@ pub struct EmailAccountConfig {
     pub host: String,
     pub port: u16,
     pub username: String,
-    pub password_env: String,
+    pub password: SecretSource,
+    /// Mailbox names; entries containing `*` are expanded from the server's list.
     pub mailboxes: Vec<String>,
+    /// Glob patterns dropped from wildcard expansions of `mailboxes`.
+    pub exclude_mailboxes: Vec<String>,
     pub identities: Vec<String>,
     pub tag: Option<String>,
     pub merge_policy: EmailMergePolicy,
     pub tls: EmailAccountTls,
+    pub gmail_labels: bool,
+    pub label_tags: Vec<EmailLabelTag>,
+    /// Record "Email (cc)" touches for existing contacts on a message's Cc line.
+    pub include_cc: bool,
+    /// Let Cc addresses create contacts too; requires `include_cc`.
+    pub cc_creates_contacts: bool,
+    /// Replace a matched contact's address-only display name with the
+    /// personal name from a later message header.
+    pub update_names: bool,
+}
+
+/// Maps a Gmail label to a knotter tag; labels match case-insensitively.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EmailLabelTag {
+    pub label: String,
+    pub tag: String,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Default)]
@@ -176,13 +406,16 @@ pub enum TelegramMergePolicy {
 pub struct TelegramAccountConfig {
     pub name: String,
     pub api_id: i32,
-    pub api_hash_env: String,
+    pub api_hash: SecretSource,
     pub phone: String,
     pub session_path: Option<PathBuf>,
     pub tag: Option<String>,
     pub merge_policy: TelegramMergePolicy,
     pub allowlist_user_ids: Vec<i64>,
     pub snippet_len: usize,
+    /// Tag imported contacts `tg-verified`, `tg-premium`, and `tg-mutual`
+    /// from their Telegram profile flags.
+    pub metadata_tags: bool,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
@@ -191,6 +424,26 @@ pub enum NotificationBackend {
     Stdout,
     Desktop,
     Email,
+    Webhook,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum NotificationFallback {
+    #[default]
+    Stdout,
+    Desktop,
+    None,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum WebhookFormat {
+    Slack,
+    Discord,
+    Ntfy,
+    #[default]
+    Json,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Default)]
@@ -207,15 +460,25 @@ impl Default for AppConfig {
         Self {
             due_soon_days: DEFAULT_SOON_DAYS,
             default_cadence_days: None,
+            timezone: None,
             notifications: NotificationsConfig {
                 enabled: false,
                 backend: NotificationBackend::Desktop,
                 email: None,
+                webhook: None,
                 random_contacts_if_no_reminders: 0,
+                fallback: NotificationFallback::default(),
+                per_contact: false,
+                max_per_run: DEFAULT_NOTIFICATIONS_MAX_PER_RUN,
+                max_per_bucket: None,
+                quiet_hours: None,
             },
             interactions: InteractionsConfig::default(),
             loops: LoopConfig::default(),
             contacts: ContactsConfig::default(),
+            tui: TuiConfig::default(),
+            sync: SyncConfig::default(),
+            exports: ExportsConfig::default(),
         }
     }
 }
@@ -234,20 +497,28 @@ pub enum ConfigError {
     InvalidSoonDays(i64),
     #[error("invalid default_cadence_days value: {0}")]
     InvalidCadenceDays(i32),
+    #[error("invalid timezone value: {0} (expected an IANA name such as Asia/Tokyo)")]
+    InvalidTimezone(String),
     #[error("invalid loops.default_cadence_days value: {0}")]
     InvalidLoopDefaultCadence(i32),
     #[error("invalid loops rule cadence_days value: {0}")]
     InvalidLoopCadenceDays(i32),
+    #[error("invalid loops.jitter_days value: {0}")]
+    InvalidLoopJitterDays(i32),
     #[error("invalid loops rule tag: {0}")]
     InvalidLoopTag(String),
     #[error("duplicate loops rule tag: {0}")]
     DuplicateLoopTag(String),
+    #[error("invalid loops rule min_priority value: {0} (expected 0-{max})", max = MAX_CONTACT_PRIORITY)]
+    InvalidLoopMinPriority(i32),
     #[error("invalid contact source name: {0}")]
     InvalidContactSourceName(String),
     #[error("duplicate contact source name: {0}")]
     DuplicateContactSourceName(String),
     #[error("invalid contact source {source_name} field: {field}")]
     InvalidContactSourceField { source_name: String, field: String },
+    #[error("invalid contacts.ignore_identities entry: {0:?}")]
+    InvalidIgnoreIdentity(String),
     #[error("invalid email account name: {0}")]
     InvalidEmailAccountName(String),
     #[error("duplicate email account name: {0}")]
@@ -260,10 +531,32 @@ pub enum ConfigError {
     DuplicateTelegramAccountName(String),
     #[error("invalid telegram account {account_name} field: {field}")]
     InvalidTelegramAccountField { account_name: String, field: String },
+    #[error("invalid contacts.defaults field: {field}")]
+    InvalidContactDefaultsField { field: String },
     #[error("invalid notifications email field: {field}")]
     InvalidNotificationsEmailField { field: String },
+    #[error("invalid notifications webhook field: {field}")]
+    InvalidNotificationsWebhookField { field: String },
     #[error("invalid notifications.random_contacts_if_no_reminders value: {value} (max {max})")]
     InvalidNotificationsRandomContacts { value: usize, max: usize },
+    #[error("invalid notifications.max_per_run value: {value} (expected 1-{max})")]
+    InvalidNotificationsMaxPerRun { value: usize, max: usize },
+    #[error("invalid notifications.max_per_bucket value: {value} (expected 1-{max})")]
+    InvalidNotificationsMaxPerBucket { value: usize, max: usize },
+    #[error("invalid notifications.quiet_hours: {0}")]
+    InvalidNotificationsQuietHours(String),
+    #[error("invalid interactions.dedupe_window_minutes value: {value} (expected 0-{max})")]
+    InvalidInteractionsDedupeWindowMinutes { value: i64, max: i64 },
+    #[error("invalid interactions.default_touch_kind: {0}")]
+    InvalidInteractionsDefaultTouchKind(String),
+    #[error("invalid interactions.templates.{name}: {reason}")]
+    InvalidInteractionsTemplate { name: String, reason: String },
+    #[error("invalid sync.max_future_skew_days value: {value} (expected 0-{max})")]
+    InvalidSyncMaxFutureSkewDays { value: i64, max: i64 },
+    #[error("invalid exports.ics.alarm value: {0}")]
+    InvalidExportsIcsAlarm(String),
+    #[error("config file already exists: {0}")]
+    ConfigFileExists(PathBuf),
     #[error("failed to read config file {path}: {source}")]
     Read {
         path: PathBuf,
@@ -276,6 +569,12 @@ pub enum ConfigError {
         #[source]
         source: toml::de::Error,
     },
+    #[error("failed to write config file {path}: {source}")]
+    Write {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
 }
 
 pub type Result<T> = std::result::Result<T, ConfigError>;
@@ -285,10 +584,14 @@ pub type Result<T> = std::result::Result<T, ConfigError>;
 struct ConfigFile {
     due_soon_days: Option<i64>,
     default_cadence_days: Option<i32>,
+    timezone: Option<String>,
     notifications: Option<NotificationsFile>,
     interactions: Option<InteractionsFile>,
     loops: Option<LoopConfigFile>,
     contacts: Option<ContactsFile>,
+    tui: Option<TuiFile>,
+    sync: Option<SyncFile>,
+    exports: Option<ExportsFile>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -297,8 +600,21 @@ struct NotificationsFile {
     enabled: Option<bool>,
     backend: Option<NotificationBackend>,
     email: Option<NotificationsEmailFile>,
+    webhook: Option<NotificationsWebhookFile>,
     #[serde(alias = "random_contacts_if_no_dates_today")]
     random_contacts_if_no_reminders: Option<usize>,
+    fallback: Option<NotificationFallback>,
+    per_contact: Option<bool>,
+    max_per_run: Option<usize>,
+    max_per_bucket: Option<usize>,
+    quiet_hours: Option<QuietHoursFile>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct QuietHoursFile {
+    start: String,
+    end: String,
 }
 
 #[derive(Debug, Deserialize)]
@@ -311,14 +627,54 @@ struct NotificationsEmailFile {
     smtp_port: Option<u16>,
     username: Option<String>,
     password_env: Option<String>,
+    password_cmd: Option<String>,
     tls: Option<EmailTls>,
     timeout_seconds: Option<u64>,
+    html: Option<bool>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct NotificationsWebhookFile {
+    url: Option<String>,
+    format: Option<WebhookFormat>,
+    token_env: Option<String>,
+    token_cmd: Option<String>,
+    timeout_seconds: Option<u64>,
+    allow_insecure: Option<bool>,
 }
 
 #[derive(Debug, Deserialize)]
 #[serde(deny_unknown_fields)]
 struct InteractionsFile {
     auto_reschedule: Option<bool>,
+    dedupe_window_minutes: Option<i64>,
+    default_touch_kind: Option<String>,
+    templates: Option<BTreeMap<String, String>>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct TuiFile {
+    colorize_rows: Option<bool>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct SyncFile {
+    max_future_skew_days: Option<i64>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ExportsFile {
+    ics: Option<IcsExportFile>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct IcsExportFile {
+    alarm: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -330,6 +686,7 @@ struct LoopConfigFile {
     schedule_missing: Option<bool>,
     anchor: Option<LoopAnchor>,
     override_existing: Option<bool>,
+    jitter_days: Option<i32>,
     tags: Option<Vec<LoopRuleFile>>,
 }
 
@@ -339,6 +696,7 @@ struct LoopRuleFile {
     tag: String,
     cadence_days: i32,
     priority: Option<i32>,
+    min_priority: Option<i32>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -347,6 +705,19 @@ struct ContactsFile {
     sources: Option<Vec<ContactSourceFile>>,
     email_accounts: Option<Vec<EmailAccountFile>>,
     telegram_accounts: Option<Vec<TelegramAccountFile>>,
+    defaults: Option<ContactDefaultsFile>,
+    apply_default_cadence_on_import: Option<bool>,
+    normalize_phones: Option<bool>,
+    ignore_identities: Option<Vec<String>>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ContactDefaultsFile {
+    tags: Option<Vec<String>>,
+    cadence_days: Option<i32>,
+    timezone: Option<String>,
+    next_touchpoint: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -357,12 +728,14 @@ enum ContactSourceFile {
         url: String,
         username: Option<String>,
         password_env: Option<String>,
+        password_cmd: Option<String>,
         tag: Option<String>,
     },
     Macos {
         name: String,
         group: Option<String>,
         tag: Option<String>,
+        groups_as_tags: Option<bool>,
     },
 }
 
@@ -373,12 +746,26 @@ struct EmailAccountFile {
     host: String,
     port: Option<u16>,
     username: String,
-    password_env: String,
+    password_env: Option<String>,
+    password_cmd: Option<String>,
     mailboxes: Option<Vec<String>>,
+    exclude_mailboxes: Option<Vec<String>>,
     identities: Option<Vec<String>>,
     tag: Option<String>,
     merge_policy: Option<EmailMergePolicy>,
     tls: Option<EmailAccountTls>,
+    gmail_labels: Option<bool>,
+    label_tags: Option<Vec<EmailLabelTagFile>>,
+    include_cc: Option<bool>,
+    cc_creates_contacts: Option<bool>,
+    update_names: Option<bool>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct EmailLabelTagFile {
+    label: String,
+    tag: String,
 }
 
 #[derive(Debug, Deserialize)]
@@ -386,13 +773,15 @@ struct EmailAccountFile {
 struct TelegramAccountFile {
     name: String,
     api_id: i32,
-    api_hash_env: String,
+    api_hash_env: Option<String>,
+    api_hash_cmd: Option<String>,
     phone: String,
     session_path: Option<String>,
     tag: Option<String>,
     merge_policy: Option<TelegramMergePolicy>,
     allowlist_user_ids: Option<Vec<i64>>,
     snippet_len: Option<usize>,
+    metadata_tags: Option<bool>,
 }
 
 pub fn load(config_path: Option<PathBuf>) -> Result<AppConfig> {
@@ -433,6 +822,177 @@ pub fn resolve_config_path(custom: Option<PathBuf>) -> Result<PathBuf> {
     }
 }
 
+/// Result of validating a config file without failing fast.
+#[derive(Debug)]
+pub struct ConfigCheck {
+    pub path: PathBuf,
+    pub exists: bool,
+    pub errors: Vec<ConfigError>,
+    pub warnings: Vec<String>,
+}
+
+impl ConfigCheck {
+    pub fn is_valid(&self) -> bool {
+        self.errors.is_empty()
+    }
+}
+
+/// Validates the config file at the resolved path, reporting every problem found.
+pub fn check(config_path: Option<PathBuf>) -> Result<ConfigCheck> {
+    let required = config_path.is_some();
+    let path = resolve_config_path(config_path)?;
+    let mut report = ConfigCheck {
+        exists: path.exists(),
+        path,
+        errors: Vec::new(),
+        warnings: Vec::new(),
+    };
+
+    if !report.exists {
+        if required {
+            report
+                .errors
+                .push(ConfigError::MissingConfigFile(report.path.clone()));
+        } else {
+            report
+                .warnings
+                .push("config file not found; using defaults".to_string());
+        }
+        return Ok(report);
+    }
+
+    if let Err(err) = ensure_permissions(&report.path) {
+        report.errors.push(err);
+    }
+    let contents = match fs::read_to_string(&report.path) {
+        Ok(contents) => contents,
+        Err(source) => {
+            report.errors.push(ConfigError::Read {
+                path: report.path.clone(),
+                source,
+            });
+            return Ok(report);
+        }
+    };
+    let parsed: ConfigFile = match toml::from_str(&contents) {
+        Ok(parsed) => parsed,
+        Err(source) => {
+            report.errors.push(ConfigError::Parse {
+                path: report.path.clone(),
+                source,
+            });
+            return Ok(report);
+        }
+    };
+
+    let (config, errors) = merge_config_collect(parsed);
+    report.errors.extend(errors);
+    report.warnings.extend(config_warnings(&config));
+    Ok(report)
+}
+
+fn config_warnings(config: &AppConfig) -> Vec<String> {
+    let mut warnings = Vec::new();
+
+    if config.notifications.email.is_some()
+        && config.notifications.backend != NotificationBackend::Email
+    {
+        warnings.push(
+            "notifications.email is set but notifications.backend is not \"email\"".to_string(),
+        );
+    }
+
+    if config.notifications.webhook.is_some()
+        && config.notifications.backend != NotificationBackend::Webhook
+    {
+        warnings.push(
+            "notifications.webhook is set but notifications.backend is not \"webhook\"".to_string(),
+        );
+    }
+
+    let mut secrets: Vec<(String, &SecretSource)> = Vec::new();
+    if let Some(email) = &config.notifications.email {
+        if let Some(secret) = &email.password {
+            secrets.push(("notifications.email.password_env".to_string(), secret));
+        }
+    }
+    if let Some(webhook) = &config.notifications.webhook {
+        if let Some(secret) = &webhook.token {
+            secrets.push(("notifications.webhook.token_env".to_string(), secret));
+        }
+    }
+    for source in &config.contacts.sources {
+        if let ContactSourceKind::Carddav(carddav) = &source.kind {
+            if let Some(secret) = &carddav.password {
+                secrets.push((
+                    format!("contact source {} password_env", source.name),
+                    secret,
+                ));
+            }
+        }
+    }
+    for account in &config.contacts.email_accounts {
+        secrets.push((
+            format!("email account {} password_env", account.name),
+            &account.password,
+        ));
+    }
+    for account in &config.contacts.telegram_accounts {
+        secrets.push((
+            format!("telegram account {} api_hash_env", account.name),
+            &account.api_hash,
+        ));
+    }
+    // Commands are only run when the secret is needed, so only env vars are checked.
+    for (field, secret) in secrets {
+        let SecretSource::Env(var) = secret else {
+            continue;
+        };
+        if env::var_os(var).is_none() {
+            warnings.push(format!(
+                "{field} refers to unset environment variable {var}"
+            ));
+        }
+    }
+
+    warnings
+}
+
+/// Writes [`CONFIG_TEMPLATE`] to `path` with user-only permissions.
+pub fn write_template(path: &Path, force: bool) -> Result<()> {
+    write_file(path, CONFIG_TEMPLATE, force)
+}
+
+/// Validates `contents` as a config file, then writes it to `path` with
+/// user-only permissions; nothing is written when validation fails.
+pub fn write_config(path: &Path, contents: &str, force: bool) -> Result<AppConfig> {
+    let parsed: ConfigFile = toml::from_str(contents).map_err(|source| ConfigError::Parse {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    let config = merge_config(parsed)?;
+    write_file(path, contents, force)?;
+    Ok(config)
+}
+
+fn write_file(path: &Path, contents: &str, force: bool) -> Result<()> {
+    if path.exists() && !force {
+        return Err(ConfigError::ConfigFileExists(path.to_path_buf()));
+    }
+    let write_err = |source| ConfigError::Write {
+        path: path.to_path_buf(),
+        source,
+    };
+    if let Some(parent) = path.parent() {
+        if !parent.as_os_str().is_empty() {
+            fs::create_dir_all(parent).map_err(write_err)?;
+        }
+    }
+    fs::write(path, contents).map_err(write_err)?;
+    restrict_permissions(path).map_err(write_err)?;
+    Ok(())
+}
+
 fn load_at_path(path: &Path, required: bool) -> Result<Option<AppConfig>> {
     if !path.exists() {
         if required {
@@ -454,19 +1014,39 @@ fn load_at_path(path: &Path, required: bool) -> Result<Option<AppConfig>> {
 }
 
 fn merge_config(parsed: ConfigFile) -> Result<AppConfig> {
+    let (config, errors) = merge_config_collect(parsed);
+    match errors.into_iter().next() {
+        Some(err) => Err(err),
+        None => Ok(config),
+    }
+}
+
+/// Merges a parsed config file into defaults, collecting every validation error
+/// instead of stopping at the first one. Invalid values and entries are skipped.
+fn merge_config_collect(parsed: ConfigFile) -> (AppConfig, Vec<ConfigError>) {
     let mut config = AppConfig::default();
+    let mut errors = Vec::new();
 
     if let Some(soon_days) = parsed.due_soon_days {
-        let soon_days =
-            validate_soon_days(soon_days).map_err(|_| ConfigError::InvalidSoonDays(soon_days))?;
-        config.due_soon_days = soon_days;
+        match validate_soon_days(soon_days) {
+            Ok(soon_days) => config.due_soon_days = soon_days,
+            Err(_) => errors.push(ConfigError::InvalidSoonDays(soon_days)),
+        }
     }
 
     if let Some(cadence) = parsed.default_cadence_days {
         if cadence <= 0 || cadence > MAX_CADENCE_DAYS {
-            return Err(ConfigError::InvalidCadenceDays(cadence));
+            errors.push(ConfigError::InvalidCadenceDays(cadence));
+        } else {
+            config.default_cadence_days = Some(cadence);
+        }
+    }
+
+    if let Some(timezone) = parsed.timezone {
+        match parse_timezone(&timezone) {
+            Some(zone) => config.timezone = Some(zone),
+            None => errors.push(ConfigError::InvalidTimezone(timezone)),
         }
-        config.default_cadence_days = Some(cadence);
     }
 
     if let Some(notifications) = parsed.notifications {
@@ -477,16 +1057,58 @@ fn merge_config(parsed: ConfigFile) -> Result<AppConfig> {
             config.notifications.backend = backend;
         }
         if let Some(email) = notifications.email {
-            config.notifications.email = Some(merge_notifications_email(email)?);
+            match merge_notifications_email(email) {
+                Ok(email) => config.notifications.email = Some(email),
+                Err(err) => errors.push(err),
+            }
+        }
+        if let Some(webhook) = notifications.webhook {
+            match merge_notifications_webhook(webhook) {
+                Ok(webhook) => config.notifications.webhook = Some(webhook),
+                Err(err) => errors.push(err),
+            }
         }
         if let Some(count) = notifications.random_contacts_if_no_reminders {
             if count > MAX_RANDOM_CONTACTS_IF_NO_REMINDERS {
-                return Err(ConfigError::InvalidNotificationsRandomContacts {
+                errors.push(ConfigError::InvalidNotificationsRandomContacts {
                     value: count,
                     max: MAX_RANDOM_CONTACTS_IF_NO_REMINDERS,
                 });
+            } else {
+                config.notifications.random_contacts_if_no_reminders = count;
+            }
+        }
+        if let Some(fallback) = notifications.fallback {
+            config.notifications.fallback = fallback;
+        }
+        if let Some(per_contact) = notifications.per_contact {
+            config.notifications.per_contact = per_contact;
+        }
+        if let Some(max) = notifications.max_per_run {
+            if max == 0 || max > MAX_NOTIFICATIONS_MAX_PER_RUN {
+                errors.push(ConfigError::InvalidNotificationsMaxPerRun {
+                    value: max,
+                    max: MAX_NOTIFICATIONS_MAX_PER_RUN,
+                });
+            } else {
+                config.notifications.max_per_run = max;
+            }
+        }
+        if let Some(max) = notifications.max_per_bucket {
+            if max == 0 || max > MAX_NOTIFICATIONS_MAX_PER_BUCKET {
+                errors.push(ConfigError::InvalidNotificationsMaxPerBucket {
+                    value: max,
+                    max: MAX_NOTIFICATIONS_MAX_PER_BUCKET,
+                });
+            } else {
+                config.notifications.max_per_bucket = Some(max);
+            }
+        }
+        if let Some(quiet_hours) = notifications.quiet_hours {
+            match merge_quiet_hours(quiet_hours) {
+                Ok(quiet_hours) => config.notifications.quiet_hours = Some(quiet_hours),
+                Err(err) => errors.push(err),
             }
-            config.notifications.random_contacts_if_no_reminders = count;
         }
     }
 
@@ -494,23 +1116,95 @@ fn merge_config(parsed: ConfigFile) -> Result<AppConfig> {
         if let Some(auto_reschedule) = interactions.auto_reschedule {
             config.interactions.auto_reschedule = auto_reschedule;
         }
+        if let Some(minutes) = interactions.dedupe_window_minutes {
+            if !(0..=MAX_INTERACTIONS_DEDUPE_WINDOW_MINUTES).contains(&minutes) {
+                errors.push(ConfigError::InvalidInteractionsDedupeWindowMinutes {
+                    value: minutes,
+                    max: MAX_INTERACTIONS_DEDUPE_WINDOW_MINUTES,
+                });
+            } else {
+                config.interactions.dedupe_window_minutes = minutes;
+            }
+        }
+        if let Some(raw) = interactions.default_touch_kind {
+            match raw.parse::<InteractionKind>() {
+                Ok(kind) => config.interactions.default_touch_kind = Some(kind),
+                Err(err) => errors.push(ConfigError::InvalidInteractionsDefaultTouchKind(
+                    err.to_string(),
+                )),
+            }
+        }
+        for (name, source) in interactions.templates.unwrap_or_default() {
+            if !is_variable_name(&name) {
+                errors.push(ConfigError::InvalidInteractionsTemplate {
+                    name,
+                    reason: "names use letters, digits, '_' or '-'".to_string(),
+                });
+                continue;
+            }
+            match NoteTemplate::parse(&source) {
+                Ok(template) => {
+                    config.interactions.templates.insert(name, template);
+                }
+                Err(err) => errors.push(ConfigError::InvalidInteractionsTemplate {
+                    name,
+                    reason: err.to_string(),
+                }),
+            }
+        }
+    }
+
+    if let Some(tui) = parsed.tui {
+        if let Some(colorize_rows) = tui.colorize_rows {
+            config.tui.colorize_rows = colorize_rows;
+        }
+    }
+
+    if let Some(sync) = parsed.sync {
+        if let Some(days) = sync.max_future_skew_days {
+            if !(0..=MAX_SYNC_MAX_FUTURE_SKEW_DAYS).contains(&days) {
+                errors.push(ConfigError::InvalidSyncMaxFutureSkewDays {
+                    value: days,
+                    max: MAX_SYNC_MAX_FUTURE_SKEW_DAYS,
+                });
+            } else {
+                config.sync.max_future_skew_days = days;
+            }
+        }
+    }
+
+    if let Some(alarm) = parsed.exports.and_then(|exports| exports.ics?.alarm) {
+        match parse_duration(&alarm) {
+            Ok(seconds) => config.exports.ics.alarm_seconds = Some(seconds),
+            Err(_) => errors.push(ConfigError::InvalidExportsIcsAlarm(alarm)),
+        }
     }
 
     if config.notifications.enabled
         && config.notifications.backend == NotificationBackend::Email
         && config.notifications.email.is_none()
     {
-        return Err(ConfigError::InvalidNotificationsEmailField {
+        errors.push(ConfigError::InvalidNotificationsEmailField {
             field: "notifications.email".to_string(),
         });
     }
 
+    if config.notifications.enabled
+        && config.notifications.backend == NotificationBackend::Webhook
+        && config.notifications.webhook.is_none()
+    {
+        errors.push(ConfigError::InvalidNotificationsWebhookField {
+            field: "notifications.webhook".to_string(),
+        });
+    }
+
     if let Some(loops) = parsed.loops {
         if let Some(default_cadence) = loops.default_cadence_days {
             if default_cadence <= 0 || default_cadence > MAX_CADENCE_DAYS {
-                return Err(ConfigError::InvalidLoopDefaultCadence(default_cadence));
+                errors.push(ConfigError::InvalidLoopDefaultCadence(default_cadence));
+            } else {
+                config.loops.policy.default_cadence_days = Some(default_cadence);
             }
-            config.loops.policy.default_cadence_days = Some(default_cadence);
         }
 
         if let Some(strategy) = loops.strategy {
@@ -533,20 +1227,21 @@ fn merge_config(parsed: ConfigFile) -> Result<AppConfig> {
             config.loops.override_existing = override_existing;
         }
 
+        if let Some(jitter_days) = loops.jitter_days {
+            if !(0..=MAX_JITTER_DAYS).contains(&jitter_days) {
+                errors.push(ConfigError::InvalidLoopJitterDays(jitter_days));
+            } else {
+                config.loops.jitter_days = jitter_days;
+            }
+        }
+
         if let Some(rules) = loops.tags {
             let mut seen: HashSet<String> = HashSet::new();
             for rule in rules {
-                let tag = TagName::new(&rule.tag)
-                    .map_err(|_| ConfigError::InvalidLoopTag(rule.tag.clone()))?;
-                let normalized = tag.as_str().to_string();
-                if !seen.insert(normalized.clone()) {
-                    return Err(ConfigError::DuplicateLoopTag(normalized));
+                match merge_loop_rule(rule, &mut seen) {
+                    Ok(loop_rule) => config.loops.policy.rules.push(loop_rule),
+                    Err(err) => errors.push(err),
                 }
-
-                let priority = rule.priority.unwrap_or(0);
-                let loop_rule = LoopRule::new(tag, rule.cadence_days, priority)
-                    .map_err(|_| ConfigError::InvalidLoopCadenceDays(rule.cadence_days))?;
-                config.loops.policy.rules.push(loop_rule);
             }
         }
     }
@@ -555,147 +1250,329 @@ fn merge_config(parsed: ConfigFile) -> Result<AppConfig> {
         if let Some(sources) = contacts.sources {
             let mut seen: HashSet<String> = HashSet::new();
             for source in sources {
-                let (name, kind) = match source {
-                    ContactSourceFile::Carddav {
-                        name,
-                        url,
-                        username,
-                        password_env,
-                        tag,
-                    } => {
-                        let name = normalize_source_name(&name)?;
-                        let url = normalize_required_string(url, &name, "url")?;
-                        let username = normalize_optional_string(username).ok_or_else(|| {
-                            ConfigError::InvalidContactSourceField {
-                                source_name: name.clone(),
-                                field: "username".to_string(),
-                            }
-                        })?;
-                        let password_env = normalize_optional_string(password_env);
-                        let tag = normalize_optional_tag(tag, &name)?;
-                        (
-                            name,
-                            ContactSourceKind::Carddav(CardDavSourceConfig {
-                                url,
-                                username: Some(username),
-                                password_env,
-                                tag,
-                            }),
-                        )
-                    }
-                    ContactSourceFile::Macos { name, group, tag } => {
-                        let name = normalize_source_name(&name)?;
-                        let group = normalize_optional_string(group);
-                        let tag = normalize_optional_tag(tag, &name)?;
-                        (
-                            name,
-                            ContactSourceKind::Macos(MacosSourceConfig { group, tag }),
-                        )
-                    }
-                };
-
-                if !seen.insert(name.clone()) {
-                    return Err(ConfigError::DuplicateContactSourceName(name));
+                match merge_contact_source(source, &mut seen) {
+                    Ok(source) => config.contacts.sources.push(source),
+                    Err(err) => errors.push(err),
                 }
-
-                config
-                    .contacts
-                    .sources
-                    .push(ContactSourceConfig { name, kind });
             }
         }
         if let Some(accounts) = contacts.email_accounts {
             let mut seen: HashSet<String> = HashSet::new();
             for account in accounts {
-                let name = normalize_email_account_name(&account.name)?;
-                if !seen.insert(name.clone()) {
-                    return Err(ConfigError::DuplicateEmailAccountName(name));
+                match merge_email_account(account, &mut seen) {
+                    Ok(account) => config.contacts.email_accounts.push(account),
+                    Err(err) => errors.push(err),
                 }
-                let host = normalize_email_account_field(account.host, &name, "host")?;
-                let port = account.port.unwrap_or(993);
-                if port == 0 {
-                    return Err(ConfigError::InvalidEmailAccountField {
-                        account_name: name.clone(),
-                        field: "port".to_string(),
-                    });
-                }
-                let username = normalize_email_account_field(account.username, &name, "username")?;
-                let password_env =
-                    normalize_email_account_field(account.password_env, &name, "password_env")?;
-                let mailboxes = normalize_mailboxes(account.mailboxes, &name)?;
-                let identities = normalize_identities(account.identities, &username);
-                let tag = normalize_optional_tag_for_email_account(account.tag, &name)?;
-                let merge_policy = account
-                    .merge_policy
-                    .unwrap_or(EmailMergePolicy::NameOrEmail);
-                let tls = account.tls.unwrap_or(EmailAccountTls::Tls);
-
-                config.contacts.email_accounts.push(EmailAccountConfig {
-                    name,
-                    host,
-                    port,
-                    username,
-                    password_env,
-                    mailboxes,
-                    identities,
-                    tag,
-                    merge_policy,
-                    tls,
-                });
             }
         }
         if let Some(accounts) = contacts.telegram_accounts {
             let mut seen: HashSet<String> = HashSet::new();
             for account in accounts {
-                let name = normalize_telegram_account_name(&account.name)?;
-                if !seen.insert(name.clone()) {
-                    return Err(ConfigError::DuplicateTelegramAccountName(name));
-                }
-                if account.api_id <= 0 {
-                    return Err(ConfigError::InvalidTelegramAccountField {
-                        account_name: name.clone(),
-                        field: "api_id".to_string(),
-                    });
+                match merge_telegram_account(account, &mut seen) {
+                    Ok(account) => config.contacts.telegram_accounts.push(account),
+                    Err(err) => errors.push(err),
                 }
-                let api_hash_env =
-                    normalize_telegram_account_field(account.api_hash_env, &name, "api_hash_env")?;
-                let phone = normalize_telegram_account_field(account.phone, &name, "phone")?;
-                let session_path =
-                    normalize_optional_string(account.session_path).map(PathBuf::from);
-                let tag = normalize_optional_tag_for_telegram_account(account.tag, &name)?;
-                let merge_policy = account.merge_policy.unwrap_or_default();
-                let allowlist_user_ids =
-                    normalize_allowlist_user_ids(account.allowlist_user_ids, &name)?;
-                let snippet_len = match account.snippet_len {
-                    Some(0) => {
-                        return Err(ConfigError::InvalidTelegramAccountField {
-                            account_name: name.clone(),
-                            field: "snippet_len".to_string(),
-                        })
-                    }
-                    Some(value) => value,
-                    None => DEFAULT_TELEGRAM_SNIPPET_LEN,
-                };
-
-                config
-                    .contacts
-                    .telegram_accounts
-                    .push(TelegramAccountConfig {
-                        name,
-                        api_id: account.api_id,
-                        api_hash_env,
-                        phone,
-                        session_path,
-                        tag,
-                        merge_policy,
-                        allowlist_user_ids,
-                        snippet_len,
-                    });
             }
         }
+        if let Some(defaults) = contacts.defaults {
+            match merge_contact_defaults(defaults) {
+                Ok(defaults) => config.contacts.defaults = defaults,
+                Err(err) => errors.push(err),
+            }
+        }
+        if let Some(apply) = contacts.apply_default_cadence_on_import {
+            config.contacts.apply_default_cadence_on_import = apply;
+        }
+        if let Some(normalize) = contacts.normalize_phones {
+            config.contacts.normalize_phones = normalize;
+        }
+        for raw in contacts.ignore_identities.unwrap_or_default() {
+            if !config.contacts.ignore_identities.insert(&raw) {
+                errors.push(ConfigError::InvalidIgnoreIdentity(raw));
+            }
+        }
+    }
+    // Every account's own identities are the user's too.
+    for account in &config.contacts.email_accounts {
+        for identity in &account.identities {
+            config.contacts.ignore_identities.insert(identity);
+        }
+    }
+    for account in &config.contacts.telegram_accounts {
+        if let Some(phone) = phone_match_key(&account.phone) {
+            config.contacts.ignore_identities.phones.insert(phone);
+        }
     }
 
-    Ok(config)
+    (config, errors)
+}
+
+fn merge_loop_rule(rule: LoopRuleFile, seen: &mut HashSet<String>) -> Result<LoopRule> {
+    let tag = TagName::new(&rule.tag).map_err(|_| ConfigError::InvalidLoopTag(rule.tag.clone()))?;
+    let normalized = tag.as_str().to_string();
+    if !seen.insert(normalized.clone()) {
+        return Err(ConfigError::DuplicateLoopTag(normalized));
+    }
+
+    if let Some(min_priority) = rule.min_priority {
+        validate_contact_priority(min_priority)
+            .map_err(|_| ConfigError::InvalidLoopMinPriority(min_priority))?;
+    }
+
+    let priority = rule.priority.unwrap_or(0);
+    Ok(LoopRule::new(tag, rule.cadence_days, priority)
+        .map_err(|_| ConfigError::InvalidLoopCadenceDays(rule.cadence_days))?
+        .with_min_priority(rule.min_priority))
+}
+
+fn merge_contact_defaults(defaults: ContactDefaultsFile) -> Result<ContactDefaultsConfig> {
+    let invalid = |field: &str| ConfigError::InvalidContactDefaultsField {
+        field: format!("contacts.defaults.{field}"),
+    };
+
+    let mut tags = Vec::new();
+    for raw in defaults.tags.unwrap_or_default() {
+        let tag = TagName::new(&raw).map_err(|_| invalid("tags"))?;
+        let normalized = tag.as_str().to_string();
+        if !tags.contains(&normalized) {
+            tags.push(normalized);
+        }
+    }
+
+    if let Some(cadence) = defaults.cadence_days {
+        if cadence <= 0 || cadence > MAX_CADENCE_DAYS {
+            return Err(invalid("cadence_days"));
+        }
+    }
+
+    let timezone = match defaults.timezone {
+        Some(value) => {
+            Some(normalize_optional_string(Some(value)).ok_or_else(|| invalid("timezone"))?)
+        }
+        None => None,
+    };
+
+    let next_touchpoint = match defaults.next_touchpoint {
+        Some(value) => {
+            let value =
+                normalize_optional_string(Some(value)).ok_or_else(|| invalid("next_touchpoint"))?;
+            // Only relative expressions make sense as a default; a fixed date
+            // would be in the past soon after it is written.
+            let anchor = NaiveDate::from_ymd_opt(2000, 1, 1).expect("valid anchor date");
+            match parse_relative_date(&value, anchor) {
+                Some(Ok(_)) => Some(value),
+                _ => return Err(invalid("next_touchpoint")),
+            }
+        }
+        None => None,
+    };
+
+    Ok(ContactDefaultsConfig {
+        tags,
+        cadence_days: defaults.cadence_days,
+        timezone,
+        next_touchpoint,
+    })
+}
+
+fn merge_contact_source(
+    source: ContactSourceFile,
+    seen: &mut HashSet<String>,
+) -> Result<ContactSourceConfig> {
+    let (name, kind) = match source {
+        ContactSourceFile::Carddav {
+            name,
+            url,
+            username,
+            password_env,
+            password_cmd,
+            tag,
+        } => {
+            let name = normalize_source_name(&name)?;
+            let url = normalize_required_string(url, &name, "url")?;
+            let username = normalize_optional_string(username).ok_or_else(|| {
+                ConfigError::InvalidContactSourceField {
+                    source_name: name.clone(),
+                    field: "username".to_string(),
+                }
+            })?;
+            let password =
+                secret_source(password_env, password_cmd, "password_env", "password_cmd").map_err(
+                    |field| ConfigError::InvalidContactSourceField {
+                        source_name: name.clone(),
+                        field,
+                    },
+                )?;
+            let tag = normalize_optional_tag(tag, &name)?;
+            (
+                name,
+                ContactSourceKind::Carddav(CardDavSourceConfig {
+                    url,
+                    username: Some(username),
+                    password,
+                    tag,
+                }),
+            )
+        }
+        ContactSourceFile::Macos {
+            name,
+            group,
+            tag,
+            groups_as_tags,
+        } => {
+            let name = normalize_source_name(&name)?;
+            let group = normalize_optional_string(group);
+            let tag = normalize_optional_tag(tag, &name)?;
+            (
+                name,
+                ContactSourceKind::Macos(MacosSourceConfig {
+                    group,
+                    tag,
+                    groups_as_tags: groups_as_tags.unwrap_or(false),
+                }),
+            )
+        }
+    };
+
+    if !seen.insert(name.clone()) {
+        return Err(ConfigError::DuplicateContactSourceName(name));
+    }
+
+    Ok(ContactSourceConfig { name, kind })
+}
+
+fn merge_email_account(
+    account: EmailAccountFile,
+    seen: &mut HashSet<String>,
+) -> Result<EmailAccountConfig> {
+    let name = normalize_email_account_name(&account.name)?;
+    if !seen.insert(name.clone()) {
+        return Err(ConfigError::DuplicateEmailAccountName(name));
+    }
+    let host = normalize_email_account_field(account.host, &name, "host")?;
+    let port = account.port.unwrap_or(993);
+    if port == 0 {
+        return Err(ConfigError::InvalidEmailAccountField {
+            account_name: name.clone(),
+            field: "port".to_string(),
+        });
+    }
+    let username = normalize_email_account_field(account.username, &name, "username")?;
+    let password = secret_source(
+        account.password_env,
+        account.password_cmd,
+        "password_env",
+        "password_cmd",
+    )
+    .and_then(|secret| secret.ok_or_else(|| "password_env".to_string()))
+    .map_err(|field| ConfigError::InvalidEmailAccountField {
+        account_name: name.clone(),
+        field,
+    })?;
+    let mailboxes = normalize_mailboxes(account.mailboxes, &name)?;
+    let exclude_mailboxes = normalize_exclude_mailboxes(account.exclude_mailboxes, &name)?;
+    if !exclude_mailboxes.is_empty() && !mailboxes.iter().any(|mailbox| mailbox.contains('*')) {
+        return Err(ConfigError::InvalidEmailAccountField {
+            account_name: name,
+            field: "exclude_mailboxes (requires a wildcard in mailboxes)".to_string(),
+        });
+    }
+    let identities = normalize_identities(account.identities, &username);
+    let tag = normalize_optional_tag_for_email_account(account.tag, &name)?;
+    let merge_policy = account
+        .merge_policy
+        .unwrap_or(EmailMergePolicy::NameOrEmail);
+    let tls = account.tls.unwrap_or(EmailAccountTls::Tls);
+    let gmail_labels = account.gmail_labels.unwrap_or(false);
+    let label_tags = normalize_label_tags(account.label_tags, &name)?;
+    if !label_tags.is_empty() && !gmail_labels {
+        return Err(ConfigError::InvalidEmailAccountField {
+            account_name: name,
+            field: "label_tags (requires gmail_labels = true)".to_string(),
+        });
+    }
+    let include_cc = account.include_cc.unwrap_or(false);
+    let cc_creates_contacts = account.cc_creates_contacts.unwrap_or(false);
+    if cc_creates_contacts && !include_cc {
+        return Err(ConfigError::InvalidEmailAccountField {
+            account_name: name,
+            field: "cc_creates_contacts (requires include_cc = true)".to_string(),
+        });
+    }
+
+    Ok(EmailAccountConfig {
+        name,
+        host,
+        port,
+        username,
+        password,
+        mailboxes,
+        exclude_mailboxes,
+        identities,
+        tag,
+        merge_policy,
+        tls,
+        gmail_labels,
+        label_tags,
+        include_cc,
+        cc_creates_contacts,
+        update_names: account.update_names.unwrap_or(false),
+    })
+}
+
+fn merge_telegram_account(
+    account: TelegramAccountFile,
+    seen: &mut HashSet<String>,
+) -> Result<TelegramAccountConfig> {
+    let name = normalize_telegram_account_name(&account.name)?;
+    if !seen.insert(name.clone()) {
+        return Err(ConfigError::DuplicateTelegramAccountName(name));
+    }
+    if account.api_id <= 0 {
+        return Err(ConfigError::InvalidTelegramAccountField {
+            account_name: name.clone(),
+            field: "api_id".to_string(),
+        });
+    }
+    let api_hash = secret_source(
+        account.api_hash_env,
+        account.api_hash_cmd,
+        "api_hash_env",
+        "api_hash_cmd",
+    )
+    .and_then(|secret| secret.ok_or_else(|| "api_hash_env".to_string()))
+    .map_err(|field| ConfigError::InvalidTelegramAccountField {
+        account_name: name.clone(),
+        field,
+    })?;
+    let phone = normalize_telegram_account_field(account.phone, &name, "phone")?;
+    let session_path = normalize_optional_string(account.session_path).map(PathBuf::from);
+    let tag = normalize_optional_tag_for_telegram_account(account.tag, &name)?;
+    let merge_policy = account.merge_policy.unwrap_or_default();
+    let allowlist_user_ids = normalize_allowlist_user_ids(account.allowlist_user_ids, &name)?;
+    let snippet_len = match account.snippet_len {
+        Some(0) => {
+            return Err(ConfigError::InvalidTelegramAccountField {
+                account_name: name.clone(),
+                field: "snippet_len".to_string(),
+            })
+        }
+        Some(value) => value,
+        None => DEFAULT_TELEGRAM_SNIPPET_LEN,
+    };
+
+    Ok(TelegramAccountConfig {
+        name,
+        api_id: account.api_id,
+        api_hash,
+        phone,
+        session_path,
+        tag,
+        merge_policy,
+        allowlist_user_ids,
+        snippet_len,
+        metadata_tags: account.metadata_tags.unwrap_or(false),
+    })
 }
 
 fn merge_notifications_email(file: NotificationsEmailFile) -> Result<NotificationsEmailConfig> {
@@ -737,8 +1614,14 @@ fn merge_notifications_email(file: NotificationsEmailFile) -> Result<Notificatio
     let subject_prefix = normalize_optional_string(file.subject_prefix)
         .unwrap_or_else(|| "knotter reminders".to_string());
     let username = normalize_optional_string(file.username);
-    let password_env = normalize_optional_string(file.password_env);
-    if username.is_some() != password_env.is_some() {
+    let password = secret_source(
+        file.password_env,
+        file.password_cmd,
+        "notifications.email.password_env",
+        "notifications.email.password_cmd",
+    )
+    .map_err(|field| ConfigError::InvalidNotificationsEmailField { field })?;
+    if username.is_some() != password.is_some() {
         return Err(ConfigError::InvalidNotificationsEmailField {
             field: "notifications.email.username/password_env".to_string(),
         });
@@ -761,9 +1644,71 @@ fn merge_notifications_email(file: NotificationsEmailFile) -> Result<Notificatio
         smtp_host,
         smtp_port,
         username,
-        password_env,
+        password,
         tls,
         timeout_seconds,
+        html: file.html.unwrap_or(false),
+    })
+}
+
+fn merge_quiet_hours(file: QuietHoursFile) -> Result<QuietHours> {
+    let parse = |field: &str, raw: &str| {
+        NaiveTime::parse_from_str(raw.trim(), "%H:%M").map_err(|_| {
+            ConfigError::InvalidNotificationsQuietHours(format!(
+                "{field} must be HH:MM, got {raw:?}"
+            ))
+        })
+    };
+    let quiet_hours = QuietHours {
+        start: parse("start", &file.start)?,
+        end: parse("end", &file.end)?,
+    };
+    if quiet_hours.start == quiet_hours.end {
+        return Err(ConfigError::InvalidNotificationsQuietHours(
+            "start and end must differ".to_string(),
+        ));
+    }
+    Ok(quiet_hours)
+}
+
+fn merge_notifications_webhook(
+    file: NotificationsWebhookFile,
+) -> Result<NotificationsWebhookConfig> {
+    let invalid = |field: &str| ConfigError::InvalidNotificationsWebhookField {
+        field: format!("notifications.webhook.{field}"),
+    };
+    let url = normalize_optional_string(file.url).ok_or_else(|| invalid("url"))?;
+    let allow_insecure = file.allow_insecure.unwrap_or(false);
+    let lowered = url.to_ascii_lowercase();
+    let rest = if let Some(rest) = lowered.strip_prefix("https://") {
+        rest
+    } else if let Some(rest) = lowered.strip_prefix("http://") {
+        if !allow_insecure {
+            return Err(invalid("url (http requires allow_insecure = true)"));
+        }
+        rest
+    } else {
+        return Err(invalid("url"));
+    };
+    if rest.is_empty() || rest.starts_with('/') || rest.chars().any(char::is_whitespace) {
+        return Err(invalid("url"));
+    }
+
+    let timeout_seconds = match file.timeout_seconds {
+        Some(0) => return Err(invalid("timeout_seconds")),
+        Some(value) => Some(value),
+        None => None,
+    };
+
+    let token = secret_source(file.token_env, file.token_cmd, "token_env", "token_cmd")
+        .map_err(|field| invalid(&field))?;
+
+    Ok(NotificationsWebhookConfig {
+        url,
+        format: file.format.unwrap_or_default(),
+        token,
+        timeout_seconds,
+        allow_insecure,
     })
 }
 
@@ -836,12 +1781,32 @@ fn normalize_mailboxes(value: Option<Vec<String>>, account: &str) -> Result<Vec<
             out.push(trimmed.to_string());
         }
     }
-    if out.is_empty() {
-        return Err(ConfigError::InvalidEmailAccountField {
-            account_name: account.to_string(),
-            field: "mailboxes".to_string(),
-        });
-    }
+    if out.is_empty() {
+        return Err(ConfigError::InvalidEmailAccountField {
+            account_name: account.to_string(),
+            field: "mailboxes".to_string(),
+        });
+    }
+    Ok(out)
+}
+
+fn normalize_exclude_mailboxes(value: Option<Vec<String>>, account: &str) -> Result<Vec<String>> {
+    let mut out: Vec<String> = Vec::new();
+    for raw in value.unwrap_or_default() {
+        let trimmed = raw.trim();
+        if trimmed.is_empty() {
+            return Err(ConfigError::InvalidEmailAccountField {
+                account_name: account.to_string(),
+                field: "exclude_mailboxes".to_string(),
+            });
+        }
+        if !out
+            .iter()
+            .any(|existing| existing.eq_ignore_ascii_case(trimmed))
+        {
+            out.push(trimmed.to_string());
+        }
+    }
     Ok(out)
 }
 
@@ -867,6 +1832,58 @@ fn normalize_identities(value: Option<Vec<String>>, username: &str) -> Vec<Strin
     out
 }
 
+fn normalize_label_tags(
+    value: Option<Vec<EmailLabelTagFile>>,
+    account_name: &str,
+) -> Result<Vec<EmailLabelTag>> {
+    let invalid = |field: &str| ConfigError::InvalidEmailAccountField {
+        account_name: account_name.to_string(),
+        field: field.to_string(),
+    };
+    let mut out: Vec<EmailLabelTag> = Vec::new();
+    for entry in value.unwrap_or_default() {
+        let label = entry.label.trim();
+        if label.is_empty() {
+            return Err(invalid("label_tags.label"));
+        }
+        let tag = knotter_core::domain::TagName::new(entry.tag.trim())
+            .map_err(|_| invalid("label_tags.tag"))?;
+        if out
+            .iter()
+            .any(|existing| existing.label.eq_ignore_ascii_case(label))
+        {
+            return Err(invalid("label_tags.label"));
+        }
+        out.push(EmailLabelTag {
+            label: label.to_string(),
+            tag: tag.as_str().to_string(),
+        });
+    }
+    Ok(out)
+}
+
+/// Builds a secret from an `*_env`/`*_cmd` pair. Errors name the offending field:
+/// both set, or a command that does not split into at least a program name.
+fn secret_source(
+    env: Option<String>,
+    cmd: Option<String>,
+    env_field: &str,
+    cmd_field: &str,
+) -> std::result::Result<Option<SecretSource>, String> {
+    match (
+        normalize_optional_string(env),
+        normalize_optional_string(cmd),
+    ) {
+        (Some(_), Some(_)) => Err(format!("{env_field}/{cmd_field} (set only one)")),
+        (Some(var), None) => Ok(Some(SecretSource::Env(var))),
+        (None, Some(command)) => match shlex::split(&command) {
+            Some(argv) if !argv.is_empty() => Ok(Some(SecretSource::Command(argv))),
+            _ => Err(cmd_field.to_string()),
+        },
+        (None, None) => Ok(None),
+    }
+}
+
 fn normalize_optional_string(value: Option<String>) -> Option<String> {
     value.and_then(|raw| {
         let trimmed = raw.trim();
@@ -1023,15 +2040,34 @@ fn ensure_permissions(_path: &Path) -> Result<()> {
     Ok(())
 }
 
+#[cfg(unix)]
+fn restrict_permissions(path: &Path) -> std::io::Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+
+    fs::set_permissions(path, fs::Permissions::from_mode(0o600))
+}
+
+#[cfg(not(unix))]
+fn restrict_permissions(_path: &Path) -> std::io::Result<()> {
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::{
-        load_at_path, merge_config, CardDavSourceConfig, ConfigFile, ContactSourceFile,
-        ContactSourceKind, ContactsFile, EmailAccountFile, EmailAccountTls, EmailMergePolicy,
-        EmailTls, LoopAnchor, LoopConfigFile, LoopRuleFile, LoopStrategy, MacosSourceConfig,
-        NotificationBackend, NotificationsEmailFile, NotificationsFile, TelegramAccountFile,
-        TelegramMergePolicy, DEFAULT_TELEGRAM_SNIPPET_LEN,
+        check, load_at_path, merge_config, write_template, CardDavSourceConfig, ConfigError,
+        ConfigFile, ContactDefaultsConfig, ContactSourceFile, ContactSourceKind, ContactsFile,
+        EmailAccountFile, EmailAccountTls, EmailLabelTag, EmailLabelTagFile, EmailMergePolicy,
+        EmailTls, InteractionKind, LoopAnchor, LoopConfigFile, LoopRuleFile, LoopStrategy,
+        MacosSourceConfig, NotificationBackend, NotificationFallback, NotificationsEmailFile,
+        NotificationsFile, NotificationsWebhookFile, SecretSource, TelegramAccountFile,
+        TelegramMergePolicy, WebhookFormat, DEFAULT_INTERACTIONS_DEDUPE_WINDOW_MINUTES,
+        DEFAULT_NOTIFICATIONS_MAX_PER_RUN, DEFAULT_SYNC_MAX_FUTURE_SKEW_DAYS,
+        DEFAULT_TELEGRAM_SNIPPET_LEN, MAX_INTERACTIONS_DEDUPE_WINDOW_MINUTES,
+        MAX_NOTIFICATIONS_MAX_PER_BUCKET, MAX_NOTIFICATIONS_MAX_PER_RUN,
+        MAX_SYNC_MAX_FUTURE_SKEW_DAYS,
     };
+    use chrono::NaiveTime;
     use std::fs;
     use std::path::Path;
     use tempfile::TempDir;
@@ -1051,15 +2087,25 @@ mod tests {
         let parsed = ConfigFile {
             due_soon_days: Some(3),
             default_cadence_days: Some(14),
+            timezone: None,
             notifications: Some(NotificationsFile {
                 enabled: Some(true),
                 backend: Some(NotificationBackend::Desktop),
                 email: None,
+                webhook: None,
                 random_contacts_if_no_reminders: None,
+                fallback: None,
+                per_contact: None,
+                max_per_run: None,
+                max_per_bucket: None,
+                quiet_hours: None,
             }),
             interactions: None,
             loops: None,
             contacts: None,
+            tui: None,
+            sync: None,
+            exports: None,
         };
         let merged = merge_config(parsed).expect("merge");
         assert_eq!(merged.due_soon_days, 3);
@@ -1073,6 +2119,7 @@ mod tests {
         let parsed = ConfigFile {
             due_soon_days: None,
             default_cadence_days: None,
+            timezone: None,
             notifications: Some(NotificationsFile {
                 enabled: Some(true),
                 backend: Some(NotificationBackend::Email),
@@ -1087,14 +2134,25 @@ mod tests {
                     smtp_port: Some(587),
                     username: Some("user@example.com".to_string()),
                     password_env: Some("KNOTTER_SMTP_PASSWORD".to_string()),
+                    password_cmd: None,
                     tls: Some(EmailTls::StartTls),
                     timeout_seconds: Some(20),
+                    html: None,
                 }),
+                webhook: None,
                 random_contacts_if_no_reminders: None,
+                fallback: None,
+                per_contact: None,
+                max_per_run: None,
+                max_per_bucket: None,
+                quiet_hours: None,
             }),
             interactions: None,
             loops: None,
             contacts: None,
+            tui: None,
+            sync: None,
+            exports: None,
         };
 
         let merged = merge_config(parsed).expect("merge");
@@ -1107,7 +2165,10 @@ mod tests {
         assert_eq!(email.smtp_host, "smtp.example.com");
         assert_eq!(email.smtp_port, Some(587));
         assert_eq!(email.username.as_deref(), Some("user@example.com"));
-        assert_eq!(email.password_env.as_deref(), Some("KNOTTER_SMTP_PASSWORD"));
+        assert_eq!(
+            email.password,
+            Some(SecretSource::Env("KNOTTER_SMTP_PASSWORD".to_string()))
+        );
         assert_eq!(email.tls, EmailTls::StartTls);
         assert_eq!(email.timeout_seconds, Some(20));
     }
@@ -1117,15 +2178,25 @@ mod tests {
         let parsed = ConfigFile {
             due_soon_days: None,
             default_cadence_days: None,
+            timezone: None,
             notifications: Some(NotificationsFile {
                 enabled: Some(true),
                 backend: Some(NotificationBackend::Email),
                 email: None,
+                webhook: None,
                 random_contacts_if_no_reminders: None,
+                fallback: None,
+                per_contact: None,
+                max_per_run: None,
+                max_per_bucket: None,
+                quiet_hours: None,
             }),
             interactions: None,
             loops: None,
             contacts: None,
+            tui: None,
+            sync: None,
+            exports: None,
         };
 
         let err = merge_config(parsed).unwrap_err();
@@ -1137,15 +2208,25 @@ mod tests {
         let parsed = ConfigFile {
             due_soon_days: None,
             default_cadence_days: None,
+            timezone: None,
             notifications: Some(NotificationsFile {
                 enabled: Some(false),
                 backend: Some(NotificationBackend::Email),
                 email: None,
+                webhook: None,
                 random_contacts_if_no_reminders: None,
+                fallback: None,
+                per_contact: None,
+                max_per_run: None,
+                max_per_bucket: None,
+                quiet_hours: None,
             }),
             interactions: None,
             loops: None,
             contacts: None,
+            tui: None,
+            sync: None,
+            exports: None,
         };
 
         let merged = merge_config(parsed).expect("merge");
@@ -1154,11 +2235,109 @@ mod tests {
         assert!(merged.notifications.email.is_none());
     }
 
+    fn webhook_config(webhook: NotificationsWebhookFile) -> ConfigFile {
+        ConfigFile {
+            due_soon_days: None,
+            default_cadence_days: None,
+            timezone: None,
+            notifications: Some(NotificationsFile {
+                enabled: Some(true),
+                backend: Some(NotificationBackend::Webhook),
+                email: None,
+                webhook: Some(webhook),
+                random_contacts_if_no_reminders: None,
+                fallback: None,
+                per_contact: None,
+                max_per_run: None,
+                max_per_bucket: None,
+                quiet_hours: None,
+            }),
+            interactions: None,
+            loops: None,
+            contacts: None,
+            tui: None,
+            sync: None,
+            exports: None,
+        }
+    }
+
+    #[test]
+    fn merge_config_parses_webhook_notifications() {
+        let merged = merge_config(webhook_config(NotificationsWebhookFile {
+            url: Some(" https://hooks.slack.com/services/T000/B000/XXX ".to_string()),
+            format: Some(WebhookFormat::Slack),
+            token_env: Some("KNOTTER_WEBHOOK_TOKEN".to_string()),
+            token_cmd: None,
+            timeout_seconds: Some(10),
+            allow_insecure: None,
+        }))
+        .expect("merge");
+        let webhook = merged.notifications.webhook.expect("webhook config");
+        assert_eq!(
+            webhook.url,
+            "https://hooks.slack.com/services/T000/B000/XXX"
+        );
+        assert_eq!(webhook.format, WebhookFormat::Slack);
+        assert_eq!(
+            webhook.token,
+            Some(SecretSource::Env("KNOTTER_WEBHOOK_TOKEN".to_string()))
+        );
+        assert_eq!(webhook.timeout_seconds, Some(10));
+        assert!(!webhook.allow_insecure);
+    }
+
+    #[test]
+    fn merge_config_requires_https_webhook_unless_insecure_allowed() {
+        let file = |url: &str, allow_insecure: Option<bool>| NotificationsWebhookFile {
+            url: Some(url.to_string()),
+            format: None,
+            token_env: None,
+            token_cmd: None,
+            timeout_seconds: None,
+            allow_insecure,
+        };
+
+        let err =
+            merge_config(webhook_config(file("http://ntfy.local/knotter", None))).unwrap_err();
+        assert!(err.to_string().contains("allow_insecure"));
+        let err = merge_config(webhook_config(file("ftp://example.com", None))).unwrap_err();
+        assert!(err.to_string().contains("notifications.webhook.url"));
+
+        let merged = merge_config(webhook_config(file(
+            "http://ntfy.local/knotter",
+            Some(true),
+        )))
+        .expect("merge");
+        let webhook = merged.notifications.webhook.expect("webhook config");
+        assert_eq!(webhook.format, WebhookFormat::Json);
+        assert!(webhook.allow_insecure);
+    }
+
+    #[test]
+    fn merge_config_rejects_webhook_backend_without_webhook_config() {
+        let mut parsed = webhook_config(NotificationsWebhookFile {
+            url: None,
+            format: None,
+            token_env: None,
+            token_cmd: None,
+            timeout_seconds: None,
+            allow_insecure: None,
+        });
+        parsed
+            .notifications
+            .as_mut()
+            .expect("notifications")
+            .webhook = None;
+        let err = merge_config(parsed).unwrap_err();
+        assert!(err.to_string().contains("notifications.webhook"));
+    }
+
     #[test]
     fn merge_config_rejects_email_missing_password_env() {
         let parsed = ConfigFile {
             due_soon_days: None,
             default_cadence_days: None,
+            timezone: None,
             notifications: Some(NotificationsFile {
                 enabled: Some(true),
                 backend: Some(NotificationBackend::Email),
@@ -1170,14 +2349,25 @@ mod tests {
                     smtp_port: Some(587),
                     username: Some("user@example.com".to_string()),
                     password_env: None,
+                    password_cmd: None,
                     tls: None,
                     timeout_seconds: None,
+                    html: None,
                 }),
+                webhook: None,
                 random_contacts_if_no_reminders: None,
+                fallback: None,
+                per_contact: None,
+                max_per_run: None,
+                max_per_bucket: None,
+                quiet_hours: None,
             }),
             interactions: None,
             loops: None,
             contacts: None,
+            tui: None,
+            sync: None,
+            exports: None,
         };
 
         let err = merge_config(parsed).unwrap_err();
@@ -1189,6 +2379,7 @@ mod tests {
         let parsed = ConfigFile {
             due_soon_days: None,
             default_cadence_days: None,
+            timezone: None,
             notifications: Some(NotificationsFile {
                 enabled: Some(true),
                 backend: Some(NotificationBackend::Email),
@@ -1200,14 +2391,25 @@ mod tests {
                     smtp_port: Some(587),
                     username: None,
                     password_env: None,
+                    password_cmd: None,
                     tls: None,
                     timeout_seconds: None,
+                    html: None,
                 }),
+                webhook: None,
                 random_contacts_if_no_reminders: None,
+                fallback: None,
+                per_contact: None,
+                max_per_run: None,
+                max_per_bucket: None,
+                quiet_hours: None,
             }),
             interactions: None,
             loops: None,
             contacts: None,
+            tui: None,
+            sync: None,
+            exports: None,
         };
 
         let err = merge_config(parsed).unwrap_err();
@@ -1224,11 +2426,306 @@ mod tests {
         assert_eq!(merged.notifications.random_contacts_if_no_reminders, 10);
     }
 
+    #[test]
+    fn merge_config_parses_per_contact_notifications() {
+        let parsed: ConfigFile = toml::from_str("[notifications]\n").expect("parse toml");
+        let merged = merge_config(parsed).expect("merge");
+        assert!(!merged.notifications.per_contact);
+        assert_eq!(
+            merged.notifications.max_per_run,
+            DEFAULT_NOTIFICATIONS_MAX_PER_RUN
+        );
+
+        let parsed: ConfigFile =
+            toml::from_str("[notifications]\nper_contact = true\nmax_per_run = 3\n")
+                .expect("parse toml");
+        let merged = merge_config(parsed).expect("merge");
+        assert!(merged.notifications.per_contact);
+        assert_eq!(merged.notifications.max_per_run, 3);
+
+        for value in [0, MAX_NOTIFICATIONS_MAX_PER_RUN + 1] {
+            let parsed: ConfigFile =
+                toml::from_str(&format!("[notifications]\nmax_per_run = {value}\n"))
+                    .expect("parse toml");
+            let err = merge_config(parsed).unwrap_err();
+            assert!(err.to_string().contains("notifications.max_per_run"));
+        }
+    }
+
+    #[test]
+    fn merge_config_parses_max_per_bucket() {
+        let parsed: ConfigFile = toml::from_str("[notifications]\n").expect("parse toml");
+        let merged = merge_config(parsed).expect("merge");
+        assert_eq!(merged.notifications.max_per_bucket, None);
+
+        let parsed: ConfigFile =
+            toml::from_str("[notifications]\nmax_per_bucket = 10\n").expect("parse toml");
+        let merged = merge_config(parsed).expect("merge");
+        assert_eq!(merged.notifications.max_per_bucket, Some(10));
+
+        for value in [0, MAX_NOTIFICATIONS_MAX_PER_BUCKET + 1] {
+            let parsed: ConfigFile =
+                toml::from_str(&format!("[notifications]\nmax_per_bucket = {value}\n"))
+                    .expect("parse toml");
+            let err = merge_config(parsed).unwrap_err();
+            assert!(err.to_string().contains("notifications.max_per_bucket"));
+        }
+    }
+
+    #[test]
+    fn merge_config_parses_quiet_hours() {
+        let parsed: ConfigFile = toml::from_str(
+            "[notifications]\nquiet_hours = { start = \"22:00\", end = \"08:00\" }\n",
+        )
+        .expect("parse toml");
+        let merged = merge_config(parsed).expect("merge");
+        let quiet_hours = merged.notifications.quiet_hours.expect("quiet hours");
+        assert_eq!(
+            quiet_hours.start,
+            NaiveTime::from_hms_opt(22, 0, 0).unwrap()
+        );
+        assert_eq!(quiet_hours.end, NaiveTime::from_hms_opt(8, 0, 0).unwrap());
+
+        for body in [
+            "quiet_hours = { start = \"10pm\", end = \"08:00\" }",
+            "quiet_hours = { start = \"08:00\", end = \"08:00\" }",
+        ] {
+            let parsed: ConfigFile =
+                toml::from_str(&format!("[notifications]\n{body}\n")).expect("parse toml");
+            let err = merge_config(parsed).unwrap_err();
+            assert!(err.to_string().contains("notifications.quiet_hours"));
+        }
+    }
+
+    #[test]
+    fn merge_config_parses_sync_max_future_skew_days() {
+        let parsed: ConfigFile = toml::from_str("").expect("parse toml");
+        let merged = merge_config(parsed).expect("merge");
+        assert_eq!(
+            merged.sync.max_future_skew_days,
+            DEFAULT_SYNC_MAX_FUTURE_SKEW_DAYS
+        );
+
+        let parsed: ConfigFile =
+            toml::from_str("[sync]\nmax_future_skew_days = 0\n").expect("parse toml");
+        let merged = merge_config(parsed).expect("merge");
+        assert_eq!(merged.sync.max_future_skew_days, 0);
+
+        for value in [-1, MAX_SYNC_MAX_FUTURE_SKEW_DAYS + 1] {
+            let parsed: ConfigFile =
+                toml::from_str(&format!("[sync]\nmax_future_skew_days = {value}\n"))
+                    .expect("parse toml");
+            let err = merge_config(parsed).unwrap_err();
+            assert!(err.to_string().contains("sync.max_future_skew_days"));
+        }
+    }
+
+    #[test]
+    fn merge_config_reads_touch_kind_and_note_templates() {
+        let parsed: ConfigFile = toml::from_str(
+            "[interactions]\ndefault_touch_kind = \"Call\"\n[interactions.templates]\ncoffee = \"Met for coffee at {place}\"\n",
+        )
+        .expect("parse toml");
+        let merged = merge_config(parsed).expect("merge config");
+        assert_eq!(
+            merged.interactions.default_touch_kind,
+            Some(InteractionKind::Call)
+        );
+        let coffee = &merged.interactions.templates["coffee"];
+        assert_eq!(coffee.source(), "Met for coffee at {place}");
+        assert_eq!(coffee.variables(), vec!["place"]);
+
+        for (toml, expected) in [
+            (
+                "[interactions]\ndefault_touch_kind = \"visit\"\n",
+                "interactions.default_touch_kind",
+            ),
+            (
+                "[interactions.templates]\ncoffee = \"at {place\"\n",
+                "interactions.templates.coffee: unclosed",
+            ),
+            (
+                "[interactions.templates]\n\"two words\" = \"hi\"\n",
+                "interactions.templates.two words",
+            ),
+        ] {
+            let parsed: ConfigFile = toml::from_str(toml).expect("parse toml");
+            let err = merge_config(parsed).expect_err("invalid interactions config");
+            assert!(err.to_string().contains(expected), "{err}");
+        }
+    }
+
+    #[test]
+    fn merge_config_validates_interactions_dedupe_window() {
+        let parsed: ConfigFile = toml::from_str("[interactions]\n").expect("parse toml");
+        let merged = merge_config(parsed).expect("merge");
+        assert_eq!(
+            merged.interactions.dedupe_window_minutes,
+            DEFAULT_INTERACTIONS_DEDUPE_WINDOW_MINUTES
+        );
+
+        let parsed: ConfigFile =
+            toml::from_str("[interactions]\ndedupe_window_minutes = 0\n").expect("parse toml");
+        let merged = merge_config(parsed).expect("merge");
+        assert_eq!(merged.interactions.dedupe_window_minutes, 0);
+
+        for value in [-1, MAX_INTERACTIONS_DEDUPE_WINDOW_MINUTES + 1] {
+            let parsed: ConfigFile = toml::from_str(&format!(
+                "[interactions]\ndedupe_window_minutes = {value}\n"
+            ))
+            .expect("parse toml");
+            let err = merge_config(parsed).unwrap_err();
+            assert!(err
+                .to_string()
+                .contains("interactions.dedupe_window_minutes"));
+        }
+    }
+
+    #[test]
+    fn merge_config_parses_notification_fallback() {
+        let parsed: ConfigFile = toml::from_str("[notifications]\n").expect("parse toml");
+        let merged = merge_config(parsed).expect("merge");
+        assert_eq!(merged.notifications.fallback, NotificationFallback::Stdout);
+
+        let parsed: ConfigFile =
+            toml::from_str("[notifications]\nfallback = \"none\"\n").expect("parse toml");
+        let merged = merge_config(parsed).expect("merge");
+        assert_eq!(merged.notifications.fallback, NotificationFallback::None);
+
+        let err = toml::from_str::<ConfigFile>("[notifications]\nfallback = \"pager\"\n");
+        assert!(err.is_err());
+    }
+
+    #[test]
+    fn merge_config_parses_apply_default_cadence_on_import() {
+        let merged = merge_config(toml::from_str("").expect("parse toml")).expect("merge");
+        assert!(!merged.contacts.apply_default_cadence_on_import);
+
+        let parsed: ConfigFile =
+            toml::from_str("[contacts]\napply_default_cadence_on_import = true\n")
+                .expect("parse toml");
+        let merged = merge_config(parsed).expect("merge");
+        assert!(merged.contacts.apply_default_cadence_on_import);
+    }
+
+    #[test]
+    fn merge_config_parses_normalize_phones() {
+        let merged = merge_config(toml::from_str("").expect("parse toml")).expect("merge");
+        assert!(!merged.contacts.normalize_phones);
+
+        let parsed: ConfigFile =
+            toml::from_str("[contacts]\nnormalize_phones = true\n").expect("parse toml");
+        let merged = merge_config(parsed).expect("merge");
+        assert!(merged.contacts.normalize_phones);
+    }
+
+    #[test]
+    fn merge_config_sorts_ignore_identities_and_adds_account_identities() {
+        let parsed: ConfigFile = toml::from_str(
+            r#"
+[contacts]
+ignore_identities = [" Me@Alt.com ", "@MyBizHandle", "plainhandle", "+49 151 2345 6789"]
+
+[[contacts.email_accounts]]
+name = "work"
+host = "imap.example.com"
+username = "work@example.com"
+password_env = "KNOTTER_TEST_PASSWORD"
+mailboxes = ["INBOX"]
+identities = ["work@example.com", "alias@example.com"]
+"#,
+        )
+        .expect("parse toml");
+        let merged = merge_config(parsed).expect("merge");
+        let ignored = &merged.contacts.ignore_identities;
+        assert!(ignored.contains_email("me@alt.com"));
+        assert!(ignored.contains_email("ALIAS@example.com"));
+        assert!(ignored.contains_email("work@example.com"));
+        assert!(ignored.contains_handle("mybizhandle"));
+        assert!(ignored.contains_handle("@MyBizHandle"));
+        assert!(ignored.contains_handle("@plainhandle"));
+        assert!(ignored.contains_phone("004915123456789"));
+        assert!(!ignored.contains_email("friend@example.com"));
+
+        let parsed: ConfigFile =
+            toml::from_str("[contacts]\nignore_identities = [\"ok@example.com\", \" @ \"]\n")
+                .expect("parse toml");
+        let err = merge_config(parsed).unwrap_err();
+        assert!(err.to_string().contains("contacts.ignore_identities"));
+    }
+
+    #[test]
+    fn merge_config_parses_exports_ics_alarm() {
+        let merged = merge_config(toml::from_str("").expect("parse toml")).expect("merge");
+        assert_eq!(merged.exports.ics.alarm_seconds, None);
+
+        let parsed: ConfigFile =
+            toml::from_str("[exports.ics]\nalarm = \"9h\"\n").expect("parse toml");
+        let merged = merge_config(parsed).expect("merge");
+        assert_eq!(merged.exports.ics.alarm_seconds, Some(9 * 3_600));
+
+        let parsed: ConfigFile =
+            toml::from_str("[exports.ics]\nalarm = \"9 hours\"\n").expect("parse toml");
+        match merge_config(parsed).expect_err("invalid alarm") {
+            ConfigError::InvalidExportsIcsAlarm(value) => assert_eq!(value, "9 hours"),
+            other => panic!("unexpected error: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn merge_config_parses_contact_defaults() {
+        let parsed: ConfigFile = toml::from_str(
+            "[contacts.defaults]\ntags = [\"Friends\", \"friends\", \"work\"]\ncadence_days = 45\ntimezone = \" Europe/Berlin \"\nnext_touchpoint = \"+7d\"\n",
+        )
+        .expect("parse toml");
+        let merged = merge_config(parsed).expect("merge");
+        assert_eq!(
+            merged.contacts.defaults,
+            ContactDefaultsConfig {
+                tags: vec!["friends".to_string(), "work".to_string()],
+                cadence_days: Some(45),
+                timezone: Some("Europe/Berlin".to_string()),
+                next_touchpoint: Some("+7d".to_string()),
+            }
+        );
+
+        for (body, field) in [
+            ("tags = [\" \"]", "tags"),
+            ("cadence_days = 0", "cadence_days"),
+            ("timezone = \" \"", "timezone"),
+            ("next_touchpoint = \"2030-01-01\"", "next_touchpoint"),
+            ("next_touchpoint = \"+7x\"", "next_touchpoint"),
+        ] {
+            let parsed: ConfigFile =
+                toml::from_str(&format!("[contacts.defaults]\n{body}\n")).expect("parse toml");
+            let err = merge_config(parsed).expect_err("invalid defaults");
+            match err {
+                ConfigError::InvalidContactDefaultsField { field: actual } => {
+                    assert_eq!(actual, format!("contacts.defaults.{field}"));
+                }
+                other => panic!("unexpected error: {other:?}"),
+            }
+        }
+    }
+
+    #[test]
+    fn merge_config_parses_timezone() {
+        let parsed: ConfigFile =
+            toml::from_str("timezone = \" Asia/Tokyo \"\n").expect("parse toml");
+        let merged = merge_config(parsed).expect("merge");
+        assert_eq!(merged.timezone, Some(knotter_core::time::Tz::Asia__Tokyo));
+
+        let parsed: ConfigFile = toml::from_str("timezone = \"UTC+9\"\n").expect("parse toml");
+        let err = merge_config(parsed).expect_err("invalid timezone");
+        assert!(matches!(err, ConfigError::InvalidTimezone(value) if value == "UTC+9"));
+    }
+
     #[test]
     fn merge_config_parses_contact_sources() {
         let parsed = ConfigFile {
             due_soon_days: None,
             default_cadence_days: None,
+            timezone: None,
             notifications: None,
             interactions: None,
             loops: None,
@@ -1239,17 +2736,26 @@ mod tests {
                         url: "https://example.test/carddav/".to_string(),
                         username: Some("user@example.com".to_string()),
                         password_env: Some("KNOTTER_GMAIL_PASSWORD".to_string()),
+                        password_cmd: None,
                         tag: Some("gmail".to_string()),
                     },
                     ContactSourceFile::Macos {
                         name: "Local".to_string(),
                         group: Some("Friends".to_string()),
                         tag: None,
+                        groups_as_tags: Some(true),
                     },
                 ]),
                 email_accounts: None,
                 telegram_accounts: None,
+                defaults: None,
+                apply_default_cadence_on_import: None,
+                normalize_phones: None,
+                ignore_identities: None,
             }),
+            tui: None,
+            sync: None,
+            exports: None,
         };
 
         let merged = merge_config(parsed).expect("merge");
@@ -1265,8 +2771,13 @@ mod tests {
         }
         let local = &merged.contacts.sources[1];
         match &local.kind {
-            ContactSourceKind::Macos(MacosSourceConfig { group, .. }) => {
+            ContactSourceKind::Macos(MacosSourceConfig {
+                group,
+                groups_as_tags,
+                ..
+            }) => {
                 assert_eq!(group.as_deref(), Some("Friends"));
+                assert!(groups_as_tags);
             }
             _ => panic!("expected macos"),
         }
@@ -1277,6 +2788,7 @@ mod tests {
         let parsed = ConfigFile {
             due_soon_days: None,
             default_cadence_days: None,
+            timezone: None,
             notifications: None,
             interactions: None,
             loops: None,
@@ -1287,15 +2799,32 @@ mod tests {
                     host: "imap.example.com".to_string(),
                     port: None,
                     username: "user@example.com".to_string(),
-                    password_env: "KNOTTER_GMAIL_PASSWORD".to_string(),
+                    password_env: Some("KNOTTER_GMAIL_PASSWORD".to_string()),
+                    password_cmd: None,
                     mailboxes: Some(vec!["INBOX".to_string(), "Sent".to_string()]),
+                    exclude_mailboxes: None,
                     identities: Some(vec!["user@example.com".to_string()]),
                     tag: Some("friends".to_string()),
                     merge_policy: Some(EmailMergePolicy::NameOrEmail),
                     tls: Some(EmailAccountTls::Tls),
+                    gmail_labels: Some(true),
+                    label_tags: Some(vec![EmailLabelTagFile {
+                        label: " Clients ".to_string(),
+                        tag: "Clients".to_string(),
+                    }]),
+                    include_cc: Some(true),
+                    cc_creates_contacts: None,
+                    update_names: None,
                 }]),
                 telegram_accounts: None,
+                defaults: None,
+                apply_default_cadence_on_import: None,
+                normalize_phones: None,
+                ignore_identities: None,
             }),
+            tui: None,
+            sync: None,
+            exports: None,
         };
 
         let merged = merge_config(parsed).expect("merge");
@@ -1309,6 +2838,181 @@ mod tests {
         assert_eq!(account.tag.as_deref(), Some("friends"));
         assert_eq!(account.merge_policy, EmailMergePolicy::NameOrEmail);
         assert_eq!(account.tls, EmailAccountTls::Tls);
+        assert!(account.gmail_labels);
+        assert_eq!(
+            account.label_tags,
+            vec![EmailLabelTag {
+                label: "Clients".to_string(),
+                tag: "clients".to_string(),
+            }]
+        );
+        assert!(account.include_cc);
+        assert!(!account.cc_creates_contacts);
+        assert!(!account.update_names);
+    }
+
+    #[test]
+    fn merge_config_rejects_label_tags_without_gmail_labels() {
+        let parsed: ConfigFile = toml::from_str(
+            r#"
+[[contacts.email_accounts]]
+name = "gmail"
+host = "imap.gmail.com"
+username = "user@gmail.com"
+password_env = "KNOTTER_GMAIL_PASSWORD"
+label_tags = [{ label = "Clients", tag = "clients" }]
+"#,
+        )
+        .expect("parse toml");
+
+        let err = merge_config(parsed).unwrap_err();
+        assert!(err.to_string().contains("label_tags"));
+    }
+
+    #[test]
+    fn merge_config_rejects_cc_creates_contacts_without_include_cc() {
+        let parsed: ConfigFile = toml::from_str(
+            r#"
+[[contacts.email_accounts]]
+name = "gmail"
+host = "imap.gmail.com"
+username = "user@gmail.com"
+password_env = "KNOTTER_GMAIL_PASSWORD"
+cc_creates_contacts = true
+"#,
+        )
+        .expect("parse toml");
+
+        let err = merge_config(parsed).unwrap_err();
+        assert!(err.to_string().contains("requires include_cc"));
+    }
+
+    #[test]
+    fn merge_config_parses_mailbox_wildcards_and_excludes() {
+        let parsed: ConfigFile = toml::from_str(
+            r#"
+[[contacts.email_accounts]]
+name = "gmail"
+host = "imap.gmail.com"
+username = "user@gmail.com"
+password_env = "KNOTTER_GMAIL_PASSWORD"
+mailboxes = ["*"]
+exclude_mailboxes = [" Spam ", "spam", "Lists/*"]
+"#,
+        )
+        .expect("parse toml");
+        let merged = merge_config(parsed).expect("merge");
+        let account = &merged.contacts.email_accounts[0];
+        assert_eq!(account.mailboxes, vec!["*".to_string()]);
+        assert_eq!(
+            account.exclude_mailboxes,
+            vec!["Spam".to_string(), "Lists/*".to_string()]
+        );
+
+        let parsed: ConfigFile = toml::from_str(
+            r#"
+[[contacts.email_accounts]]
+name = "gmail"
+host = "imap.gmail.com"
+username = "user@gmail.com"
+password_env = "KNOTTER_GMAIL_PASSWORD"
+mailboxes = ["INBOX"]
+exclude_mailboxes = ["Spam"]
+"#,
+        )
+        .expect("parse toml");
+        let err = merge_config(parsed).unwrap_err();
+        assert!(err.to_string().contains("requires a wildcard"));
+    }
+
+    #[test]
+    fn merge_config_parses_secret_commands() {
+        let parsed: ConfigFile = toml::from_str(
+            r#"
+[notifications.email]
+from = "knotter@example.com"
+to = ["me@example.com"]
+smtp_host = "smtp.example.com"
+username = "knotter@example.com"
+password_cmd = "pass show 'mail/smtp relay'"
+html = true
+
+[[contacts.email_accounts]]
+name = "gmail"
+host = "imap.gmail.com"
+username = "user@gmail.com"
+password_cmd = "security find-generic-password -w -s knotter"
+
+[[contacts.telegram_accounts]]
+name = "primary"
+api_id = 1
+api_hash_cmd = "op read op://Private/telegram/hash"
+phone = "+15551234567"
+"#,
+        )
+        .expect("parse toml");
+
+        let merged = merge_config(parsed).expect("merge");
+        let email = merged.notifications.email.expect("email");
+        assert!(email.html);
+        assert_eq!(
+            email.password,
+            Some(SecretSource::Command(vec![
+                "pass".to_string(),
+                "show".to_string(),
+                "mail/smtp relay".to_string(),
+            ]))
+        );
+        let account = &merged.contacts.email_accounts[0];
+        assert!(matches!(
+            &account.password,
+            SecretSource::Command(argv) if argv[0] == "security" && argv.len() == 5
+        ));
+        let telegram = &merged.contacts.telegram_accounts[0];
+        assert!(matches!(
+            &telegram.api_hash,
+            SecretSource::Command(argv) if argv == &["op", "read", "op://Private/telegram/hash"]
+        ));
+    }
+
+    #[test]
+    fn merge_config_rejects_conflicting_or_invalid_secret_commands() {
+        let both: ConfigFile = toml::from_str(
+            r#"
+[[contacts.email_accounts]]
+name = "gmail"
+host = "imap.gmail.com"
+username = "user@gmail.com"
+password_env = "KNOTTER_GMAIL_PASSWORD"
+password_cmd = "pass show gmail"
+"#,
+        )
+        .expect("parse toml");
+        let err = merge_config(both).unwrap_err();
+        assert!(err.to_string().contains("password_env/password_cmd"));
+
+        let neither: ConfigFile = toml::from_str(
+            r#"
+[[contacts.telegram_accounts]]
+name = "primary"
+api_id = 1
+phone = "+15551234567"
+"#,
+        )
+        .expect("parse toml");
+        let err = merge_config(neither).unwrap_err();
+        assert!(err.to_string().contains("api_hash_env"));
+
+        let unterminated: ConfigFile = toml::from_str(
+            r#"
+[notifications.webhook]
+url = "https://example.com/hook"
+token_cmd = "pass show 'webhook"
+"#,
+        )
+        .expect("parse toml");
+        let err = merge_config(unterminated).unwrap_err();
+        assert!(err.to_string().contains("token_cmd"));
     }
 
     #[test]
@@ -1316,6 +3020,7 @@ mod tests {
         let parsed = ConfigFile {
             due_soon_days: None,
             default_cadence_days: None,
+            timezone: None,
             notifications: None,
             interactions: None,
             loops: None,
@@ -1325,15 +3030,24 @@ mod tests {
                 telegram_accounts: Some(vec![TelegramAccountFile {
                     name: "Primary".to_string(),
                     api_id: 123,
-                    api_hash_env: "KNOTTER_TELEGRAM_HASH".to_string(),
+                    api_hash_env: Some("KNOTTER_TELEGRAM_HASH".to_string()),
+                    api_hash_cmd: None,
                     phone: "+15551234567".to_string(),
                     session_path: Some("/tmp/knotter-telegram.session".to_string()),
                     tag: Some("friends".to_string()),
                     merge_policy: Some(TelegramMergePolicy::NameOrUsername),
                     allowlist_user_ids: Some(vec![42, 7, 42]),
                     snippet_len: None,
+                    metadata_tags: None,
                 }]),
+                defaults: None,
+                apply_default_cadence_on_import: None,
+                normalize_phones: None,
+                ignore_identities: None,
             }),
+            tui: None,
+            sync: None,
+            exports: None,
         };
 
         let merged = merge_config(parsed).expect("merge");
@@ -1341,7 +3055,10 @@ mod tests {
         let account = &merged.contacts.telegram_accounts[0];
         assert_eq!(account.name, "primary");
         assert_eq!(account.api_id, 123);
-        assert_eq!(account.api_hash_env, "KNOTTER_TELEGRAM_HASH");
+        assert_eq!(
+            account.api_hash,
+            SecretSource::Env("KNOTTER_TELEGRAM_HASH".to_string())
+        );
         assert_eq!(account.phone, "+15551234567");
         assert_eq!(
             account
@@ -1354,6 +3071,7 @@ mod tests {
         assert_eq!(account.merge_policy, TelegramMergePolicy::NameOrUsername);
         assert_eq!(account.allowlist_user_ids, vec![42, 7]);
         assert_eq!(account.snippet_len, DEFAULT_TELEGRAM_SNIPPET_LEN);
+        assert!(!account.metadata_tags);
     }
 
     #[test]
@@ -1361,6 +3079,7 @@ mod tests {
         let parsed = ConfigFile {
             due_soon_days: None,
             default_cadence_days: None,
+            timezone: None,
             notifications: None,
             interactions: None,
             loops: None,
@@ -1370,15 +3089,24 @@ mod tests {
                 telegram_accounts: Some(vec![TelegramAccountFile {
                     name: "../Primary".to_string(),
                     api_id: 123,
-                    api_hash_env: "KNOTTER_TELEGRAM_HASH".to_string(),
+                    api_hash_env: Some("KNOTTER_TELEGRAM_HASH".to_string()),
+                    api_hash_cmd: None,
                     phone: "+15551234567".to_string(),
                     session_path: None,
                     tag: None,
                     merge_policy: None,
                     allowlist_user_ids: None,
                     snippet_len: None,
+                    metadata_tags: None,
                 }]),
+                defaults: None,
+                apply_default_cadence_on_import: None,
+                normalize_phones: None,
+                ignore_identities: None,
             }),
+            tui: None,
+            sync: None,
+            exports: None,
         };
 
         let err = merge_config(parsed).expect_err("expected invalid name");
@@ -1393,6 +3121,7 @@ mod tests {
         let parsed = ConfigFile {
             due_soon_days: None,
             default_cadence_days: None,
+            timezone: None,
             notifications: None,
             interactions: None,
             loops: None,
@@ -1402,16 +3131,25 @@ mod tests {
                         name: "Primary".to_string(),
                         group: None,
                         tag: None,
+                        groups_as_tags: None,
                     },
                     ContactSourceFile::Macos {
                         name: "primary".to_string(),
                         group: None,
                         tag: None,
+                        groups_as_tags: None,
                     },
                 ]),
                 email_accounts: None,
                 telegram_accounts: None,
+                defaults: None,
+                apply_default_cadence_on_import: None,
+                normalize_phones: None,
+                ignore_identities: None,
             }),
+            tui: None,
+            sync: None,
+            exports: None,
         };
 
         let err = merge_config(parsed).unwrap_err();
@@ -1423,6 +3161,7 @@ mod tests {
         let parsed = ConfigFile {
             due_soon_days: None,
             default_cadence_days: None,
+            timezone: None,
             notifications: None,
             interactions: None,
             loops: None,
@@ -1432,11 +3171,19 @@ mod tests {
                     url: "   ".to_string(),
                     username: Some("user@example.com".to_string()),
                     password_env: Some("KNOTTER_GMAIL_PASSWORD".to_string()),
+                    password_cmd: None,
                     tag: None,
                 }]),
                 email_accounts: None,
                 telegram_accounts: None,
+                defaults: None,
+                apply_default_cadence_on_import: None,
+                normalize_phones: None,
+                ignore_identities: None,
             }),
+            tui: None,
+            sync: None,
+            exports: None,
         };
 
         let err = merge_config(parsed).unwrap_err();
@@ -1448,6 +3195,7 @@ mod tests {
         let parsed = ConfigFile {
             due_soon_days: None,
             default_cadence_days: None,
+            timezone: None,
             notifications: None,
             interactions: None,
             loops: None,
@@ -1457,20 +3205,26 @@ mod tests {
                     url: "https://example.test/carddav/".to_string(),
                     username: Some("user@example.com".to_string()),
                     password_env: Some("".to_string()),
+                    password_cmd: None,
                     tag: Some("friends".to_string()),
                 }]),
                 email_accounts: None,
                 telegram_accounts: None,
+                defaults: None,
+                apply_default_cadence_on_import: None,
+                normalize_phones: None,
+                ignore_identities: None,
             }),
+            tui: None,
+            sync: None,
+            exports: None,
         };
 
         let merged = merge_config(parsed).expect("merge");
         let source = merged.contacts.sources.first().expect("source");
         match &source.kind {
-            ContactSourceKind::Carddav(CardDavSourceConfig {
-                password_env, tag, ..
-            }) => {
-                assert!(password_env.is_none());
+            ContactSourceKind::Carddav(CardDavSourceConfig { password, tag, .. }) => {
+                assert!(password.is_none());
                 assert_eq!(tag.as_deref(), Some("friends"));
             }
             _ => panic!("expected carddav"),
@@ -1482,6 +3236,7 @@ mod tests {
         let parsed = ConfigFile {
             due_soon_days: None,
             default_cadence_days: None,
+            timezone: None,
             notifications: None,
             interactions: None,
             loops: Some(LoopConfigFile {
@@ -1491,20 +3246,26 @@ mod tests {
                 schedule_missing: Some(true),
                 anchor: Some(LoopAnchor::LastInteraction),
                 override_existing: Some(true),
+                jitter_days: Some(5),
                 tags: Some(vec![
                     LoopRuleFile {
                         tag: "friend".to_string(),
                         cadence_days: 90,
                         priority: Some(10),
+                        min_priority: Some(5),
                     },
                     LoopRuleFile {
                         tag: "family".to_string(),
                         cadence_days: 30,
                         priority: None,
+                        min_priority: None,
                     },
                 ]),
             }),
             contacts: None,
+            tui: None,
+            sync: None,
+            exports: None,
         };
 
         let merged = merge_config(parsed).expect("merge");
@@ -1514,10 +3275,33 @@ mod tests {
         assert!(merged.loops.schedule_missing);
         assert_eq!(merged.loops.anchor, LoopAnchor::LastInteraction);
         assert!(merged.loops.override_existing);
+        assert_eq!(merged.loops.jitter_days, 5);
         assert_eq!(merged.loops.policy.rules.len(), 2);
         assert_eq!(merged.loops.policy.rules[0].tag.as_str(), "friend");
         assert_eq!(merged.loops.policy.rules[0].cadence_days, 90);
         assert_eq!(merged.loops.policy.rules[0].priority, 10);
+        assert_eq!(merged.loops.policy.rules[0].min_priority, Some(5));
+        assert_eq!(merged.loops.policy.rules[1].min_priority, None);
+    }
+
+    #[test]
+    fn merge_config_rejects_out_of_range_loop_min_priority() {
+        let parsed: ConfigFile = toml::from_str(
+            "[[loops.tags]]\ntag = \"friend\"\ncadence_days = 30\nmin_priority = 11\n",
+        )
+        .expect("parse toml");
+        let err = merge_config(parsed).unwrap_err();
+        assert!(err.to_string().contains("invalid loops rule min_priority"));
+    }
+
+    #[test]
+    fn merge_config_rejects_out_of_range_loop_jitter() {
+        let parsed: ConfigFile = toml::from_str("[loops]\njitter_days = 31\n").expect("parse toml");
+        let err = merge_config(parsed).unwrap_err();
+        assert!(err.to_string().contains("invalid loops.jitter_days"));
+
+        let parsed: ConfigFile = toml::from_str("[loops]\n").expect("parse toml");
+        assert_eq!(merge_config(parsed).expect("merge").loops.jitter_days, 0);
     }
 
     #[test]
@@ -1525,6 +3309,7 @@ mod tests {
         let parsed = ConfigFile {
             due_soon_days: None,
             default_cadence_days: None,
+            timezone: None,
             notifications: None,
             interactions: None,
             loops: Some(LoopConfigFile {
@@ -1534,20 +3319,26 @@ mod tests {
                 schedule_missing: None,
                 anchor: None,
                 override_existing: None,
+                jitter_days: None,
                 tags: Some(vec![
                     LoopRuleFile {
                         tag: "Friend".to_string(),
                         cadence_days: 90,
                         priority: None,
+                        min_priority: None,
                     },
                     LoopRuleFile {
                         tag: "friend".to_string(),
                         cadence_days: 30,
                         priority: None,
+                        min_priority: None,
                     },
                 ]),
             }),
             contacts: None,
+            tui: None,
+            sync: None,
+            exports: None,
         };
 
         let err = merge_config(parsed).unwrap_err();
@@ -1559,6 +3350,7 @@ mod tests {
         let parsed = ConfigFile {
             due_soon_days: None,
             default_cadence_days: None,
+            timezone: None,
             notifications: None,
             interactions: None,
             loops: Some(LoopConfigFile {
@@ -1568,13 +3360,18 @@ mod tests {
                 schedule_missing: None,
                 anchor: None,
                 override_existing: None,
+                jitter_days: None,
                 tags: Some(vec![LoopRuleFile {
                     tag: "   ".to_string(),
                     cadence_days: 30,
                     priority: None,
+                    min_priority: None,
                 }]),
             }),
             contacts: None,
+            tui: None,
+            sync: None,
+            exports: None,
         };
 
         let err = merge_config(parsed).unwrap_err();
@@ -1586,6 +3383,7 @@ mod tests {
         let parsed = ConfigFile {
             due_soon_days: None,
             default_cadence_days: None,
+            timezone: None,
             notifications: None,
             interactions: None,
             loops: None,
@@ -1595,11 +3393,19 @@ mod tests {
                     url: "https://example.test/carddav/".to_string(),
                     username: Some("   ".to_string()),
                     password_env: None,
+                    password_cmd: None,
                     tag: None,
                 }]),
                 email_accounts: None,
                 telegram_accounts: None,
+                defaults: None,
+                apply_default_cadence_on_import: None,
+                normalize_phones: None,
+                ignore_identities: None,
             }),
+            tui: None,
+            sync: None,
+            exports: None,
         };
 
         let err = merge_config(parsed).unwrap_err();
@@ -1611,6 +3417,7 @@ mod tests {
         let parsed = ConfigFile {
             due_soon_days: None,
             default_cadence_days: None,
+            timezone: None,
             notifications: None,
             interactions: None,
             loops: None,
@@ -1619,10 +3426,18 @@ mod tests {
                     name: "Local".to_string(),
                     group: None,
                     tag: Some("   ".to_string()),
+                    groups_as_tags: None,
                 }]),
                 email_accounts: None,
                 telegram_accounts: None,
+                defaults: None,
+                apply_default_cadence_on_import: None,
+                normalize_phones: None,
+                ignore_identities: None,
             }),
+            tui: None,
+            sync: None,
+            exports: None,
         };
 
         let err = merge_config(parsed).unwrap_err();
@@ -1653,4 +3468,53 @@ mod tests {
         assert_eq!(config.due_soon_days, 5);
         assert!(config.notifications.enabled);
     }
+
+    #[test]
+    fn write_template_creates_private_file_that_loads_as_defaults() {
+        let temp = TempDir::new().expect("tempdir");
+        let path = temp.path().join("knotter").join("config.toml");
+        write_template(&path, false).expect("write template");
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = fs::metadata(&path).expect("metadata").permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+        let config = load_at_path(&path, true).expect("load").expect("config");
+        assert_eq!(config.due_soon_days, super::DEFAULT_SOON_DAYS);
+        assert!(config.contacts.sources.is_empty());
+
+        let err = write_template(&path, false).unwrap_err();
+        assert!(matches!(err, ConfigError::ConfigFileExists(_)));
+        write_template(&path, true).expect("overwrite with force");
+    }
+
+    #[test]
+    fn check_reports_every_validation_error() {
+        let temp = TempDir::new().expect("tempdir");
+        let path = temp.path().join("config.toml");
+        fs::write(
+            &path,
+            "due_soon_days = -1\ndefault_cadence_days = 0\n\n[[loops.tags]]\ntag = \"friends\"\ncadence_days = 30\n\n[[loops.tags]]\ntag = \"Friends\"\ncadence_days = 60\n",
+        )
+        .expect("write config");
+        restrict_permissions(&path);
+
+        let report = check(Some(path.clone())).expect("check");
+        assert!(report.exists);
+        assert!(!report.is_valid());
+        let messages: Vec<String> = report.errors.iter().map(|err| err.to_string()).collect();
+        assert_eq!(messages.len(), 3, "{messages:?}");
+        assert!(messages[0].contains("due_soon_days"));
+        assert!(messages[1].contains("default_cadence_days"));
+        assert!(messages[2].contains("duplicate loops rule tag"));
+
+        let missing = check(Some(temp.path().join("missing.toml"))).expect("check");
+        assert!(!missing.exists);
+        assert!(matches!(
+            missing.errors.as_slice(),
+            [ConfigError::MissingConfigFile(_)]
+        ));
+    }
 }
@@ -1,6 +1,7 @@
 use crate::domain::ids::ContactId;
 use crate::error::CoreError;
 use crate::rules::cadence::MAX_CADENCE_DAYS;
+use chrono::NaiveDate;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -16,6 +17,8 @@ pub struct Contact {
     pub created_at: i64,
     pub updated_at: i64,
     pub archived_at: Option<i64>,
+    /// Company or organization, as typed or imported from vCard `ORG`.
+    pub organization: Option<String>,
 }
 
 impl Contact {
@@ -33,3 +36,85 @@ impl Contact {
         Ok(())
     }
 }
+
+/// Where and how a contact was met, optionally through another contact.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ContactOrigin {
+    pub met_at: Option<String>,
+    pub met_on: Option<NaiveDate>,
+    pub met_via: Option<ContactId>,
+}
+
+impl ContactOrigin {
+    pub fn is_empty(&self) -> bool {
+        self.met_at.is_none() && self.met_on.is_none() && self.met_via.is_none()
+    }
+
+    pub fn validate(&self, contact_id: ContactId) -> Result<(), CoreError> {
+        if self.met_via == Some(contact_id) {
+            return Err(CoreError::SelfIntroduction);
+        }
+        Ok(())
+    }
+}
+
+/// Highest contact priority; `--priority` accepts 0 through this.
+pub const MAX_CONTACT_PRIORITY: i32 = 10;
+/// Contacts at or above this priority are starred and match `priority:high`.
+pub const HIGH_CONTACT_PRIORITY: i32 = 5;
+
+pub fn validate_contact_priority(priority: i32) -> Result<i32, CoreError> {
+    if !(0..=MAX_CONTACT_PRIORITY).contains(&priority) {
+        return Err(CoreError::InvalidPriority(priority));
+    }
+    Ok(priority)
+}
+
+pub fn is_starred(priority: i32) -> bool {
+    priority >= HIGH_CONTACT_PRIORITY
+}
+
+/// The priority after toggling the star: starred contacts drop to 0, others
+/// jump to [`MAX_CONTACT_PRIORITY`].
+pub fn toggle_star(priority: i32) -> i32 {
+    if is_starred(priority) {
+        0
+    } else {
+        MAX_CONTACT_PRIORITY
+    }
+}
+
+/// Trims an alias (nickname) and collapses inner whitespace; `None` when blank.
+pub fn normalize_alias(raw: &str) -> Option<String> {
+    let collapsed = raw.split_whitespace().collect::<Vec<_>>().join(" ");
+    if collapsed.is_empty() {
+        None
+    } else {
+        Some(collapsed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{normalize_alias, toggle_star, validate_contact_priority, MAX_CONTACT_PRIORITY};
+
+    #[test]
+    fn normalize_alias_trims_and_collapses_whitespace() {
+        assert_eq!(normalize_alias("  Bob "), Some("Bob".to_string()));
+        assert_eq!(normalize_alias("Big \t Bob"), Some("Big Bob".to_string()));
+        assert_eq!(normalize_alias("   "), None);
+    }
+
+    #[test]
+    fn priority_range_and_star_toggle() {
+        assert_eq!(validate_contact_priority(0), Ok(0));
+        assert_eq!(validate_contact_priority(10), Ok(10));
+        assert!(validate_contact_priority(-1).is_err());
+        assert!(validate_contact_priority(11).is_err());
+
+        assert_eq!(toggle_star(0), MAX_CONTACT_PRIORITY);
+        assert_eq!(toggle_star(4), MAX_CONTACT_PRIORITY);
+        assert_eq!(toggle_star(5), 0);
+        assert_eq!(toggle_star(10), 0);
+    }
+}
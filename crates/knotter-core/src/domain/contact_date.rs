@@ -48,30 +48,30 @@ pub struct ContactDate {
     pub created_at: i64,
     pub updated_at: i64,
     pub source: Option<String>,
+    /// Start reminding this many days before the date; `None` means day-of only.
+    #[serde(default)]
+    pub remind_days_before: Option<u16>,
 }
 
+/// Longest lead time a contact date reminder may have.
+pub const MAX_REMIND_DAYS_BEFORE: u16 = 365;
+
 impl ContactDate {
     pub fn validate(&self) -> Result<(), CoreError> {
-        let label = self.label.as_deref().map(str::trim);
-        if matches!(self.kind, ContactDateKind::Custom)
-            && label.is_none_or(|value| value.is_empty())
-        {
-            return Err(CoreError::MissingContactDateLabel);
-        }
-        if let Some(value) = label {
-            if value.is_empty() {
-                return Err(CoreError::InvalidContactDateLabel);
-            }
-        }
-
-        if let Some(year) = self.year {
-            if !(1..=9999).contains(&year) {
-                return Err(CoreError::InvalidContactDateYear(year));
+        validate_contact_date_parts(
+            self.kind,
+            self.label.as_deref(),
+            self.month,
+            self.day,
+            self.year,
+        )?;
+
+        if let Some(days) = self.remind_days_before {
+            if days > MAX_REMIND_DAYS_BEFORE {
+                return Err(CoreError::InvalidContactDateLeadDays(days));
             }
         }
 
-        validate_month_day(self.month, self.day, self.year)?;
-
         Ok(())
     }
 }
@@ -87,6 +87,35 @@ pub fn normalize_contact_date_label(label: Option<String>) -> Option<String> {
     })
 }
 
+/// Checks a date before it is stored: custom dates need a label, the year must
+/// be 1-9999, and the month/day must exist (Feb 29 only in leap years, or
+/// without a year).
+pub fn validate_contact_date_parts(
+    kind: ContactDateKind,
+    label: Option<&str>,
+    month: u8,
+    day: u8,
+    year: Option<i32>,
+) -> Result<(), CoreError> {
+    let label = label.map(str::trim);
+    if matches!(kind, ContactDateKind::Custom) && label.is_none_or(|value| value.is_empty()) {
+        return Err(CoreError::MissingContactDateLabel);
+    }
+    if let Some(value) = label {
+        if value.is_empty() {
+            return Err(CoreError::InvalidContactDateLabel);
+        }
+    }
+
+    if let Some(year) = year {
+        if !(1..=9999).contains(&year) {
+            return Err(CoreError::InvalidContactDateYear(year));
+        }
+    }
+
+    validate_month_day(month, day, year)
+}
+
 fn validate_month_day(month: u8, day: u8, year: Option<i32>) -> Result<(), CoreError> {
     if !(1..=12).contains(&month) {
         return Err(CoreError::InvalidContactDateMonth(month));
@@ -121,6 +150,7 @@ mod tests {
             created_at: 0,
             updated_at: 0,
             source: None,
+            remind_days_before: None,
         };
         assert!(date.validate().is_ok());
     }
@@ -138,6 +168,7 @@ mod tests {
             created_at: 0,
             updated_at: 0,
             source: None,
+            remind_days_before: None,
         };
         assert!(date.validate().is_err());
     }
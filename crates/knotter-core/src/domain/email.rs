@@ -6,13 +6,76 @@ pub fn normalize_email(value: &str) -> Option<String> {
     Some(trimmed.to_ascii_lowercase())
 }
 
+/// Whether `name` is just a stand-in for one of `emails` rather than a name a
+/// person chose: blank, one of the addresses (ignoring case), or an address's
+/// lowercase local part with or without its `+tag`. Importers fall back to the
+/// address when a header carries no personal name, so such names are safe to
+/// replace; a capitalized "Bob" for `bob@example.com` is left alone.
+pub fn is_email_placeholder_name<S: AsRef<str>>(name: &str, emails: &[S]) -> bool {
+    let name =
+        name.trim_matches(|ch: char| ch.is_whitespace() || matches!(ch, '"' | '\'' | '<' | '>'));
+    if name.is_empty() {
+        return true;
+    }
+    emails
+        .iter()
+        .filter_map(|email| normalize_email(email.as_ref()))
+        .any(|email| {
+            if name.eq_ignore_ascii_case(&email) {
+                return true;
+            }
+            let Some((local, _)) = email.rsplit_once('@') else {
+                return false;
+            };
+            name == local || local.split_once('+').is_some_and(|(base, _)| name == base)
+        })
+}
+
 #[cfg(test)]
 mod tests {
-    use super::normalize_email;
+    use super::{is_email_placeholder_name, normalize_email};
 
     #[test]
     fn normalize_email_trims_and_lowercases() {
         let value = normalize_email("  Ada@Example.com ");
         assert_eq!(value.as_deref(), Some("ada@example.com"));
     }
+
+    #[test]
+    fn placeholder_names_are_blank_or_an_address() {
+        let emails = ["jsmith@corp.com", "Jane.Smith+news@Example.org"];
+        for name in [
+            "",
+            "   ",
+            "jsmith@corp.com",
+            " JSmith@Corp.com ",
+            "<jsmith@corp.com>",
+            "\"jsmith@corp.com\"",
+            "jsmith",
+            "jane.smith+news@example.org",
+            "jane.smith+news",
+            "jane.smith",
+        ] {
+            assert!(is_email_placeholder_name(name, &emails), "{name:?}");
+        }
+    }
+
+    #[test]
+    fn real_names_are_not_placeholders() {
+        let emails = ["jsmith@corp.com", "jane.smith+news@example.org"];
+        for name in [
+            "Jane Smith",
+            "J Smith",
+            "JSmith",
+            "Jane.Smith",
+            "janesmith",
+            "jane.smith+other",
+            "smith",
+            "jsmith@other.com",
+            "corp.com",
+        ] {
+            assert!(!is_email_placeholder_name(name, &emails), "{name:?}");
+        }
+        assert!(!is_email_placeholder_name::<&str>("jsmith", &[]));
+    }
 }
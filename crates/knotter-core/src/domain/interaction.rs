@@ -1,6 +1,10 @@
 use crate::domain::ids::{ContactId, InteractionId};
 use crate::error::CoreError;
 use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+/// Default age (in days) after which an unanswered inbound message counts as awaiting reply.
+pub const DEFAULT_AWAITING_REPLY_DAYS: i64 = 3;
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
@@ -21,6 +25,67 @@ impl InteractionKind {
         }
         Ok(Self::Other(trimmed.to_ascii_lowercase()))
     }
+
+    /// The kind as the CLI prints and accepts it; parses back with `from_str`.
+    pub fn label(&self) -> String {
+        match self {
+            InteractionKind::Call => "call".to_string(),
+            InteractionKind::Text => "text".to_string(),
+            InteractionKind::Hangout => "hangout".to_string(),
+            InteractionKind::Email => "email".to_string(),
+            InteractionKind::Telegram => "telegram".to_string(),
+            InteractionKind::Other(label) => format!("other:{}", label),
+        }
+    }
+}
+
+impl FromStr for InteractionKind {
+    type Err = CoreError;
+
+    /// `call`, `text`, `hangout`, `email`, `telegram`, or `other:<label>`, in
+    /// any case.
+    fn from_str(raw: &str) -> Result<Self, Self::Err> {
+        let trimmed = raw.trim();
+        let lower = trimmed.to_ascii_lowercase();
+        match lower.as_str() {
+            "call" => Ok(InteractionKind::Call),
+            "text" => Ok(InteractionKind::Text),
+            "hangout" => Ok(InteractionKind::Hangout),
+            "email" => Ok(InteractionKind::Email),
+            "telegram" => Ok(InteractionKind::Telegram),
+            _ if lower.starts_with("other:") => InteractionKind::other(&trimmed[6..]),
+            _ => Err(CoreError::InvalidInteractionKind(trimmed.to_string())),
+        }
+    }
+}
+
+/// Direction of an imported message; manual interactions have no direction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum InteractionDirection {
+    Inbound,
+    Outbound,
+}
+
+impl InteractionDirection {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            InteractionDirection::Inbound => "inbound",
+            InteractionDirection::Outbound => "outbound",
+        }
+    }
+}
+
+impl FromStr for InteractionDirection {
+    type Err = CoreError;
+
+    fn from_str(raw: &str) -> Result<Self, Self::Err> {
+        match raw.trim().to_ascii_lowercase().as_str() {
+            "inbound" => Ok(InteractionDirection::Inbound),
+            "outbound" => Ok(InteractionDirection::Outbound),
+            _ => Err(CoreError::InvalidInteractionDirection(raw.to_string())),
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -32,4 +97,160 @@ pub struct Interaction {
     pub kind: InteractionKind,
     pub note: String,
     pub follow_up_at: Option<i64>,
+    pub direction: Option<InteractionDirection>,
+}
+
+/// What an interaction attachment points at. Files are stored as the path
+/// given; knotter never copies or opens them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AttachmentKind {
+    File,
+    Url,
+}
+
+impl AttachmentKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            AttachmentKind::File => "file",
+            AttachmentKind::Url => "url",
+        }
+    }
+
+    /// `url` for anything written as `scheme://...`, otherwise `file`.
+    pub fn detect(value: &str) -> Self {
+        if value.trim().contains("://") {
+            AttachmentKind::Url
+        } else {
+            AttachmentKind::File
+        }
+    }
+}
+
+impl FromStr for AttachmentKind {
+    type Err = CoreError;
+
+    fn from_str(raw: &str) -> Result<Self, Self::Err> {
+        match raw.trim().to_ascii_lowercase().as_str() {
+            "file" => Ok(AttachmentKind::File),
+            "url" => Ok(AttachmentKind::Url),
+            _ => Err(CoreError::InvalidAttachmentKind(raw.to_string())),
+        }
+    }
+}
+
+/// A file path or URL linked to an interaction.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InteractionAttachment {
+    pub interaction_id: InteractionId,
+    pub kind: AttachmentKind,
+    pub value: String,
+    pub created_at: i64,
+}
+
+/// Checks an attachment value for `kind`, returning it trimmed. URLs need a
+/// scheme (`https:`, `file:`, `mailto:` ...) followed by something; file paths
+/// only need to be non-empty.
+pub fn validate_attachment(kind: AttachmentKind, value: &str) -> Result<String, CoreError> {
+    let value = value.trim();
+    if value.is_empty() {
+        return Err(CoreError::EmptyAttachment);
+    }
+    if kind == AttachmentKind::Url {
+        let has_scheme = value.split_once(':').is_some_and(|(scheme, rest)| {
+            let mut chars = scheme.chars();
+            chars.next().is_some_and(|ch| ch.is_ascii_alphabetic())
+                && chars.all(|ch| ch.is_ascii_alphanumeric() || matches!(ch, '+' | '-' | '.'))
+                && !rest.trim_start_matches('/').is_empty()
+        });
+        if !has_scheme {
+            return Err(CoreError::InvalidAttachmentUrl(value.to_string()));
+        }
+    }
+    Ok(value.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{validate_attachment, AttachmentKind, InteractionDirection, InteractionKind};
+    use crate::error::CoreError;
+    use std::str::FromStr;
+
+    #[test]
+    fn direction_round_trip() {
+        for direction in [
+            InteractionDirection::Inbound,
+            InteractionDirection::Outbound,
+        ] {
+            let parsed = InteractionDirection::from_str(direction.as_str()).expect("parse");
+            assert_eq!(parsed, direction);
+        }
+        assert!(InteractionDirection::from_str("sideways").is_err());
+    }
+
+    #[test]
+    fn kind_parses_names_and_other_labels() {
+        assert_eq!(" Call ".parse(), Ok(InteractionKind::Call));
+        assert_eq!(
+            "OTHER:Coffee".parse(),
+            Ok(InteractionKind::Other("coffee".to_string()))
+        );
+        assert_eq!(
+            "other: ".parse::<InteractionKind>(),
+            Err(CoreError::InvalidInteractionKindLabel)
+        );
+        assert_eq!(
+            "visit".parse::<InteractionKind>(),
+            Err(CoreError::InvalidInteractionKind("visit".to_string()))
+        );
+    }
+
+    #[test]
+    fn kind_label_round_trips() {
+        for kind in [
+            InteractionKind::Call,
+            InteractionKind::Telegram,
+            InteractionKind::Other("coffee".to_string()),
+        ] {
+            assert_eq!(kind.label().parse(), Ok(kind));
+        }
+    }
+
+    #[test]
+    fn attachments_detect_urls_and_require_a_scheme() {
+        assert_eq!(
+            AttachmentKind::detect("https://docs.example/notes"),
+            AttachmentKind::Url
+        );
+        assert_eq!(
+            AttachmentKind::detect("~/notes/ada.md"),
+            AttachmentKind::File
+        );
+        assert_eq!(
+            AttachmentKind::detect(r"C:\notes\ada.md"),
+            AttachmentKind::File
+        );
+        assert_eq!(" URL ".parse(), Ok(AttachmentKind::Url));
+        assert!("photo".parse::<AttachmentKind>().is_err());
+
+        assert_eq!(
+            validate_attachment(AttachmentKind::Url, " https://example.com/a "),
+            Ok("https://example.com/a".to_string())
+        );
+        assert!(validate_attachment(AttachmentKind::Url, "mailto:ada@example.com").is_ok());
+        for bad in ["example.com/doc", "://example.com", "https://", "1http://x"] {
+            assert_eq!(
+                validate_attachment(AttachmentKind::Url, bad),
+                Err(CoreError::InvalidAttachmentUrl(bad.to_string()))
+            );
+        }
+        assert_eq!(
+            validate_attachment(AttachmentKind::File, "notes/ada 2024.md"),
+            Ok("notes/ada 2024.md".to_string())
+        );
+        assert_eq!(
+            validate_attachment(AttachmentKind::File, "  "),
+            Err(CoreError::EmptyAttachment)
+        );
+    }
 }
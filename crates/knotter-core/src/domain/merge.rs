@@ -1,3 +1,6 @@
+/// Archive reason recorded on contacts that imports stage for a merge.
+pub const STAGED_MERGE_ARCHIVE_REASON: &str = "staged for merge";
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum MergeCandidateReason {
     EmailDuplicate,
@@ -4,14 +4,27 @@ pub mod email;
 pub mod ids;
 pub mod interaction;
 pub mod merge;
+pub mod name;
 pub mod phone;
+pub mod phone_region;
 pub mod tag;
 
-pub use contact::Contact;
-pub use contact_date::{normalize_contact_date_label, ContactDate, ContactDateKind};
-pub use email::normalize_email;
+pub use contact::{
+    is_starred, normalize_alias, toggle_star, validate_contact_priority, Contact, ContactOrigin,
+    HIGH_CONTACT_PRIORITY, MAX_CONTACT_PRIORITY,
+};
+pub use contact_date::{
+    normalize_contact_date_label, validate_contact_date_parts, ContactDate, ContactDateKind,
+    MAX_REMIND_DAYS_BEFORE,
+};
+pub use email::{is_email_placeholder_name, normalize_email};
 pub use ids::{ContactDateId, ContactId, InteractionId, MergeCandidateId, TagId};
-pub use interaction::{Interaction, InteractionKind};
-pub use merge::MergeCandidateReason;
-pub use phone::normalize_phone_for_match;
+pub use interaction::{
+    validate_attachment, AttachmentKind, Interaction, InteractionAttachment, InteractionDirection,
+    InteractionKind, DEFAULT_AWAITING_REPLY_DAYS,
+};
+pub use merge::{MergeCandidateReason, STAGED_MERGE_ARCHIVE_REASON};
+pub use name::name_match_key;
+pub use phone::{canonical_phone, normalize_phone_for_match, phone_match_key};
+pub use phone_region::{phone_region, PhoneRegion};
 pub use tag::{normalize_tag_name, Tag, TagName};
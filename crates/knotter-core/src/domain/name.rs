@@ -0,0 +1,95 @@
+/// Key for matching display names across sources: case folded, inner
+/// whitespace collapsed, and Latin diacritics stripped, so "Zoë  Müller" and
+/// "zoe muller" share a key. Display names themselves are never rewritten.
+pub fn name_match_key(raw: &str) -> String {
+    let mut key = String::with_capacity(raw.len());
+    for word in raw.split_whitespace() {
+        if !key.is_empty() {
+            key.push(' ');
+        }
+        for ch in word.chars().flat_map(char::to_lowercase) {
+            if is_combining_mark(ch) {
+                continue;
+            }
+            match fold_latin(ch) {
+                Some(folded) => key.push_str(folded),
+                None => key.push(ch),
+            }
+        }
+    }
+    key
+}
+
+/// Combining diacritical marks, as left behind by decomposed (NFD) input.
+fn is_combining_mark(ch: char) -> bool {
+    ('\u{0300}'..='\u{036f}').contains(&ch)
+}
+
+/// ASCII spelling of a lowercase Latin-1 Supplement / Latin Extended-A letter.
+fn fold_latin(ch: char) -> Option<&'static str> {
+    let folded = match ch {
+        'à' | 'á' | 'â' | 'ã' | 'ä' | 'å' | 'ā' | 'ă' | 'ą' => "a",
+        'æ' => "ae",
+        'ç' | 'ć' | 'ĉ' | 'ċ' | 'č' => "c",
+        'ď' | 'đ' | 'ð' => "d",
+        'è' | 'é' | 'ê' | 'ë' | 'ē' | 'ĕ' | 'ė' | 'ę' | 'ě' => "e",
+        'ĝ' | 'ğ' | 'ġ' | 'ģ' => "g",
+        'ĥ' | 'ħ' => "h",
+        'ì' | 'í' | 'î' | 'ï' | 'ĩ' | 'ī' | 'ĭ' | 'į' | 'ı' => "i",
+        'ĳ' => "ij",
+        'ĵ' => "j",
+        'ķ' => "k",
+        'ĺ' | 'ļ' | 'ľ' | 'ŀ' | 'ł' => "l",
+        'ñ' | 'ń' | 'ņ' | 'ň' | 'ŉ' | 'ŋ' => "n",
+        'ò' | 'ó' | 'ô' | 'õ' | 'ö' | 'ø' | 'ō' | 'ŏ' | 'ő' => "o",
+        'œ' => "oe",
+        'ŕ' | 'ŗ' | 'ř' => "r",
+        'ś' | 'ŝ' | 'ş' | 'š' | 'ſ' => "s",
+        'ß' => "ss",
+        'ţ' | 'ť' | 'ŧ' => "t",
+        'þ' => "th",
+        'ù' | 'ú' | 'û' | 'ü' | 'ũ' | 'ū' | 'ŭ' | 'ů' | 'ű' | 'ų' => "u",
+        'ŵ' => "w",
+        'ý' | 'ÿ' | 'ŷ' => "y",
+        'ź' | 'ż' | 'ž' => "z",
+        _ => return None,
+    };
+    Some(folded)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::name_match_key;
+
+    #[test]
+    fn german_umlauts_and_eszett_fold() {
+        assert_eq!(name_match_key("Zoë Müller"), "zoe muller");
+        assert_eq!(name_match_key("JÜRGEN GRÖẞE"), "jurgen grosse");
+        assert_eq!(
+            name_match_key("Jürgen Straße"),
+            name_match_key("jurgen strasse")
+        );
+    }
+
+    #[test]
+    fn french_accents_fold_in_composed_and_decomposed_forms() {
+        assert_eq!(name_match_key("Hélène Françoise"), "helene francoise");
+        assert_eq!(name_match_key("Ève Lœuvre"), "eve loeuvre");
+        // "é" written as "e" + U+0301 COMBINING ACUTE ACCENT.
+        assert_eq!(name_match_key("Rene\u{301}e"), "renee");
+    }
+
+    #[test]
+    fn ascii_names_only_fold_case_and_whitespace() {
+        assert_eq!(name_match_key("zoe muller"), "zoe muller");
+        assert_eq!(name_match_key("  Ada \t Lovelace "), "ada lovelace");
+        assert_eq!(name_match_key("O'Brien-Smith Jr."), "o'brien-smith jr.");
+        assert_eq!(name_match_key("   "), "");
+    }
+
+    #[test]
+    fn other_scripts_are_only_lowercased() {
+        assert_eq!(name_match_key("Алёна"), "алёна");
+        assert_eq!(name_match_key("山田 太郎"), "山田 太郎");
+    }
+}
@@ -1,3 +1,8 @@
+use crate::error::CoreError;
+
+/// Extension markers accepted after the number, longest first.
+const EXTENSION_MARKERS: &[&str] = &[";ext=", "ext.", "ext=", "ext", "x", "#"];
+
 pub fn normalize_phone_for_match(value: &str) -> Option<String> {
     let trimmed = value.trim();
     if trimmed.is_empty() {
@@ -33,9 +38,58 @@ pub fn normalize_phone_for_match(value: &str) -> Option<String> {
     Some(out)
 }
 
+/// Key for comparing phone numbers: the digits with an optional leading `+`,
+/// ignoring any extension. An international `00` prefix becomes `+`, and an
+/// 11-digit number starting with `1` gets the `+` it implies.
+pub fn phone_match_key(value: &str) -> Option<String> {
+    let digits = normalize_phone_for_match(value)?;
+    if let Some(rest) = digits.strip_prefix("00").filter(|rest| !rest.is_empty()) {
+        return Some(format!("+{rest}"));
+    }
+    if digits.len() == 11 && digits.starts_with('1') {
+        return Some(format!("+{digits}"));
+    }
+    Some(digits)
+}
+
+/// Canonical stored form of a phone number: its `phone_match_key`, followed by
+/// ` x<digits>` when it has an extension (`x12`, `ext. 12`, `#12`).
+///
+/// Rejects values with anything other than digits, spacing, `()-./`
+/// punctuation, a single leading `+`, and that optional extension, so
+/// `555-CALL` or `call me` never get silently truncated.
+pub fn canonical_phone(value: &str) -> Result<String, CoreError> {
+    let invalid = || CoreError::InvalidPhone(value.trim().to_string());
+    let trimmed = value.trim();
+    let number_end = trimmed
+        .char_indices()
+        .find(|&(index, ch)| {
+            !(ch.is_ascii_digit()
+                || ch.is_whitespace()
+                || matches!(ch, '(' | ')' | '-' | '.' | '/')
+                || (ch == '+' && index == 0))
+        })
+        .map_or(trimmed.len(), |(index, _)| index);
+    let (number, tail) = trimmed.split_at(number_end);
+    let key = phone_match_key(number).ok_or_else(invalid)?;
+
+    if tail.is_empty() {
+        return Ok(key);
+    }
+    let lower = tail.to_ascii_lowercase();
+    let extension = EXTENSION_MARKERS
+        .iter()
+        .find_map(|marker| lower.strip_prefix(marker))
+        .map(str::trim)
+        .filter(|digits| !digits.is_empty() && digits.chars().all(|ch| ch.is_ascii_digit()))
+        .ok_or_else(invalid)?;
+    Ok(format!("{key} x{extension}"))
+}
+
 #[cfg(test)]
 mod tests {
-    use super::normalize_phone_for_match;
+    use super::{canonical_phone, normalize_phone_for_match, phone_match_key};
+    use crate::error::CoreError;
 
     #[test]
     fn normalize_phone_trims_and_strips_formatting() {
@@ -65,4 +119,53 @@ mod tests {
     fn normalize_phone_rejects_empty() {
         assert!(normalize_phone_for_match("   ").is_none());
     }
+
+    #[test]
+    fn phone_match_key_expands_international_prefixes() {
+        assert_eq!(
+            phone_match_key("00 1 415 555 1212").unwrap(),
+            "+14155551212"
+        );
+        assert_eq!(phone_match_key("1-415-555-1212").unwrap(), "+14155551212");
+        assert_eq!(phone_match_key("415.555.1212").unwrap(), "4155551212");
+        assert_eq!(phone_match_key("00").unwrap(), "00");
+    }
+
+    #[test]
+    fn canonical_phone_formats_numbers_and_extensions() {
+        assert_eq!(
+            canonical_phone("+1 (415) 555-1212").unwrap(),
+            "+14155551212"
+        );
+        assert_eq!(canonical_phone("00141555512 12").unwrap(), "+14155551212");
+        assert_eq!(canonical_phone(" 415.555.1212 ").unwrap(), "4155551212");
+        assert_eq!(
+            canonical_phone("415-555-1212 x89").unwrap(),
+            "4155551212 x89"
+        );
+        assert_eq!(
+            canonical_phone("+44 20 7946 0958 ext. 7").unwrap(),
+            "+442079460958 x7"
+        );
+        assert_eq!(canonical_phone("030/901820#12").unwrap(), "030901820 x12");
+    }
+
+    #[test]
+    fn canonical_phone_rejects_unparseable_values() {
+        for value in [
+            "",
+            "call me",
+            "555-CALL",
+            "x123",
+            "+",
+            "41+5",
+            "415 x",
+            "415 ext abc",
+        ] {
+            assert!(
+                matches!(canonical_phone(value), Err(CoreError::InvalidPhone(_))),
+                "{value:?} should be rejected"
+            );
+        }
+    }
 }
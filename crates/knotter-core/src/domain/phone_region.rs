@@ -0,0 +1,200 @@
+use super::phone::normalize_phone_for_match;
+
+/// Country (or numbering plan) a phone number's calling code belongs to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PhoneRegion {
+    pub calling_code: &'static str,
+    /// ISO 3166-1 alpha-2 code, or a short label for shared numbering plans.
+    pub region: &'static str,
+    /// IANA zone for single-timezone regions; `None` when the region spans several.
+    pub timezone: Option<&'static str>,
+}
+
+/// Minimum digits after the calling code for a number to be treated as complete.
+const MIN_NATIONAL_DIGITS: usize = 4;
+
+/// Calling codes are prefix-free, so at most one entry matches a number.
+const CALLING_CODES: &[(&str, &str, Option<&str>)] = &[
+    ("1", "NANP", None),
+    ("7", "RU", None),
+    ("20", "EG", Some("Africa/Cairo")),
+    ("27", "ZA", Some("Africa/Johannesburg")),
+    ("30", "GR", Some("Europe/Athens")),
+    ("31", "NL", Some("Europe/Amsterdam")),
+    ("32", "BE", Some("Europe/Brussels")),
+    ("33", "FR", Some("Europe/Paris")),
+    ("34", "ES", None),
+    ("36", "HU", Some("Europe/Budapest")),
+    ("39", "IT", Some("Europe/Rome")),
+    ("40", "RO", Some("Europe/Bucharest")),
+    ("41", "CH", Some("Europe/Zurich")),
+    ("43", "AT", Some("Europe/Vienna")),
+    ("44", "GB", Some("Europe/London")),
+    ("45", "DK", Some("Europe/Copenhagen")),
+    ("46", "SE", Some("Europe/Stockholm")),
+    ("47", "NO", Some("Europe/Oslo")),
+    ("48", "PL", Some("Europe/Warsaw")),
+    ("49", "DE", Some("Europe/Berlin")),
+    ("51", "PE", Some("America/Lima")),
+    ("52", "MX", None),
+    ("53", "CU", Some("America/Havana")),
+    ("54", "AR", Some("America/Argentina/Buenos_Aires")),
+    ("55", "BR", None),
+    ("56", "CL", None),
+    ("57", "CO", Some("America/Bogota")),
+    ("58", "VE", Some("America/Caracas")),
+    ("60", "MY", Some("Asia/Kuala_Lumpur")),
+    ("61", "AU", None),
+    ("62", "ID", None),
+    ("63", "PH", Some("Asia/Manila")),
+    ("64", "NZ", None),
+    ("65", "SG", Some("Asia/Singapore")),
+    ("66", "TH", Some("Asia/Bangkok")),
+    ("81", "JP", Some("Asia/Tokyo")),
+    ("82", "KR", Some("Asia/Seoul")),
+    ("84", "VN", Some("Asia/Ho_Chi_Minh")),
+    ("86", "CN", Some("Asia/Shanghai")),
+    ("90", "TR", Some("Europe/Istanbul")),
+    ("91", "IN", Some("Asia/Kolkata")),
+    ("92", "PK", Some("Asia/Karachi")),
+    ("93", "AF", Some("Asia/Kabul")),
+    ("94", "LK", Some("Asia/Colombo")),
+    ("95", "MM", Some("Asia/Yangon")),
+    ("98", "IR", Some("Asia/Tehran")),
+    ("212", "MA", Some("Africa/Casablanca")),
+    ("213", "DZ", Some("Africa/Algiers")),
+    ("216", "TN", Some("Africa/Tunis")),
+    ("234", "NG", Some("Africa/Lagos")),
+    ("254", "KE", Some("Africa/Nairobi")),
+    ("351", "PT", None),
+    ("352", "LU", Some("Europe/Luxembourg")),
+    ("353", "IE", Some("Europe/Dublin")),
+    ("354", "IS", Some("Atlantic/Reykjavik")),
+    ("358", "FI", Some("Europe/Helsinki")),
+    ("359", "BG", Some("Europe/Sofia")),
+    ("370", "LT", Some("Europe/Vilnius")),
+    ("371", "LV", Some("Europe/Riga")),
+    ("372", "EE", Some("Europe/Tallinn")),
+    ("380", "UA", Some("Europe/Kyiv")),
+    ("385", "HR", Some("Europe/Zagreb")),
+    ("386", "SI", Some("Europe/Ljubljana")),
+    ("420", "CZ", Some("Europe/Prague")),
+    ("421", "SK", Some("Europe/Bratislava")),
+    ("852", "HK", Some("Asia/Hong_Kong")),
+    ("886", "TW", Some("Asia/Taipei")),
+    ("966", "SA", Some("Asia/Riyadh")),
+    ("971", "AE", Some("Asia/Dubai")),
+    ("972", "IL", Some("Asia/Jerusalem")),
+];
+
+/// Looks up the calling-code region of an international phone number.
+///
+/// Only numbers written with a `+` or `00` international prefix are matched;
+/// national numbers (`020 7946 0958`, `415-555-1212`) carry no country code
+/// and return `None`, as do unknown codes. The North American `011` exit code
+/// is not recognized because it collides with national numbers such as
+/// `0113 496 0000`.
+pub fn phone_region(value: &str) -> Option<PhoneRegion> {
+    let normalized = normalize_phone_for_match(value)?;
+    let digits = if let Some(rest) = normalized.strip_prefix('+') {
+        rest
+    } else {
+        normalized.strip_prefix("00")?
+    };
+
+    CALLING_CODES
+        .iter()
+        .find(|(code, _, _)| digits.starts_with(code))
+        .filter(|(code, _, _)| digits.len() >= code.len() + MIN_NATIONAL_DIGITS)
+        .map(|&(calling_code, region, timezone)| PhoneRegion {
+            calling_code,
+            region,
+            timezone,
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{phone_region, CALLING_CODES};
+
+    fn timezone(value: &str) -> Option<&'static str> {
+        phone_region(value).and_then(|region| region.timezone)
+    }
+
+    #[test]
+    fn plus_prefixed_numbers_map_to_single_timezone_countries() {
+        assert_eq!(timezone("+44 20 7946 0958"), Some("Europe/London"));
+        assert_eq!(timezone("+49 30 901820"), Some("Europe/Berlin"));
+        assert_eq!(timezone("+81 3-1234-5678"), Some("Asia/Tokyo"));
+        assert_eq!(timezone("+353 1 234 5678"), Some("Europe/Dublin"));
+    }
+
+    #[test]
+    fn international_dialing_prefixes_are_accepted_without_plus() {
+        assert_eq!(timezone("0044 20 7946 0958"), Some("Europe/London"));
+        assert_eq!(timezone("0033 1 23 45 67 89"), Some("Europe/Paris"));
+        assert_eq!(timezone("00353-1-234-5678"), Some("Europe/Dublin"));
+    }
+
+    #[test]
+    fn national_numbers_without_country_code_are_not_guessed() {
+        assert_eq!(phone_region("020 7946 0958"), None);
+        assert_eq!(phone_region("415-555-1212"), None);
+        assert_eq!(phone_region("4420 7946 0958"), None);
+        assert_eq!(phone_region("0 44 20"), None);
+        assert_eq!(phone_region("0113 496 0000"), None);
+    }
+
+    #[test]
+    fn formatting_noise_and_trunk_zero_are_ignored() {
+        assert_eq!(timezone("  +44 (0) 20-7946.0958 "), Some("Europe/London"));
+        assert_eq!(
+            timezone("+33 (0)1 23 45 67 89 ext. 12"),
+            Some("Europe/Paris")
+        );
+        assert_eq!(timezone("+49/30/901820 x 7"), Some("Europe/Berlin"));
+    }
+
+    #[test]
+    fn multi_timezone_countries_are_ambiguous() {
+        for (value, region) in [
+            ("+1 415 555 1212", "NANP"),
+            ("+7 495 123-45-67", "RU"),
+            ("+61 2 9876 5432", "AU"),
+            ("+55 11 91234 5678", "BR"),
+        ] {
+            let matched = phone_region(value).expect("region");
+            assert_eq!(matched.region, region);
+            assert_eq!(matched.timezone, None);
+        }
+    }
+
+    #[test]
+    fn longest_calling_code_is_not_shadowed() {
+        let matched = phone_region("+380 44 123 4567").expect("region");
+        assert_eq!(matched.calling_code, "380");
+        assert_eq!(matched.timezone, Some("Europe/Kyiv"));
+        let matched = phone_region("+30 21 0123 4567").expect("region");
+        assert_eq!(matched.calling_code, "30");
+    }
+
+    #[test]
+    fn unknown_codes_and_truncated_numbers_return_none() {
+        assert_eq!(phone_region("+999 1234 5678"), None);
+        assert_eq!(phone_region("+44 12"), None);
+        assert_eq!(phone_region("+"), None);
+        assert_eq!(phone_region(""), None);
+    }
+
+    #[test]
+    fn calling_codes_are_prefix_free() {
+        for (code, _, _) in CALLING_CODES {
+            for (other, _, _) in CALLING_CODES {
+                assert!(
+                    code == other || !other.starts_with(code),
+                    "{code} is a prefix of {other}"
+                );
+            }
+        }
+    }
+}
@@ -1,6 +1,12 @@
-use crate::domain::{ContactDateId, ContactDateKind, ContactId, InteractionId};
+use crate::domain::{
+    AttachmentKind, ContactDateId, ContactDateKind, ContactId, ContactOrigin,
+    InteractionAttachment, InteractionDirection, InteractionId, MergeCandidateId,
+};
 use crate::rules::DueState;
+use crate::time::Clock;
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ContactListItemDto {
@@ -9,7 +15,23 @@ pub struct ContactListItemDto {
     pub due_state: DueState,
     pub next_touchpoint_at: Option<i64>,
     pub archived_at: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub archive_reason: Option<String>,
+    /// Set only while the contact is muted.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub muted_until: Option<i64>,
     pub tags: Vec<String>,
+    /// When the most recent interaction happened; `None` if there are none.
+    #[serde(default)]
+    pub last_interaction_at: Option<i64>,
+    /// Kind of the most recent interaction, formatted like `InteractionDto::kind`.
+    #[serde(default)]
+    pub last_interaction_kind: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub organization: Option<String>,
+    /// 0 to 10; 5 and up is starred.
+    #[serde(default)]
+    pub priority: i32,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -19,6 +41,27 @@ pub struct InteractionDto {
     pub kind: String,
     pub note: String,
     pub follow_up_at: Option<i64>,
+    pub direction: Option<InteractionDirection>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub attachments: Vec<AttachmentDto>,
+}
+
+/// A file path or URL linked to an interaction.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AttachmentDto {
+    pub kind: AttachmentKind,
+    pub value: String,
+    pub created_at: i64,
+}
+
+impl From<InteractionAttachment> for AttachmentDto {
+    fn from(attachment: InteractionAttachment) -> Self {
+        Self {
+            kind: attachment.kind,
+            value: attachment.value,
+            created_at: attachment.created_at,
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -29,6 +72,8 @@ pub struct ContactDateDto {
     pub month: u8,
     pub day: u8,
     pub year: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub remind_days_before: Option<u16>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -40,14 +85,161 @@ pub struct ContactDetailDto {
     pub phone: Option<String>,
     pub handle: Option<String>,
     pub timezone: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub organization: Option<String>,
     pub next_touchpoint_at: Option<i64>,
     pub cadence_days: Option<i32>,
     pub created_at: i64,
     pub updated_at: i64,
     pub archived_at: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub archive_reason: Option<String>,
+    /// Set only while the contact is muted.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub muted_until: Option<i64>,
+    #[serde(default)]
+    pub priority: i32,
     pub tags: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub aliases: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub origin: Option<ContactOriginDto>,
     pub dates: Vec<ContactDateDto>,
     pub recent_interactions: Vec<InteractionDto>,
+    /// Interactions matching the requested window; `recent_interactions` may
+    /// hold only a page of them.
+    #[serde(default)]
+    pub interactions_total: usize,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub provenance: Vec<FieldProvenanceDto>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub sync_history: Vec<SyncHistoryDto>,
+    /// Latest synced message per channel, most recent first.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub last_touch_context: Vec<LastTouchContextDto>,
+}
+
+/// Where and how a contact was met.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ContactOriginDto {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub met_at: Option<String>,
+    /// Local date, `YYYY-MM-DD`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub met_date: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub met_via: Option<ContactId>,
+    /// Display name of `met_via`; unset when that contact is trashed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub met_via_name: Option<String>,
+}
+
+impl ContactOriginDto {
+    pub fn from_origin(origin: ContactOrigin, met_via_name: Option<String>) -> Self {
+        Self {
+            met_at: origin.met_at,
+            met_date: origin
+                .met_on
+                .map(|date| date.format("%Y-%m-%d").to_string()),
+            met_via: origin.met_via,
+            met_via_name,
+        }
+    }
+
+    /// "RustConf 2023 on 2023-09-12 via Ada Lovelace"; `None` when nothing is set.
+    pub fn summary(&self) -> Option<String> {
+        let mut parts = Vec::new();
+        if let Some(met_at) = self.met_at.as_deref() {
+            parts.push(met_at.to_string());
+        }
+        if let Some(date) = self.met_date.as_deref() {
+            parts.push(format!("on {date}"));
+        }
+        match (self.met_via_name.as_deref(), self.met_via) {
+            (Some(name), _) => parts.push(format!("via {name}")),
+            (None, Some(id)) => parts.push(format!("via {id}")),
+            (None, None) => {}
+        }
+        (!parts.is_empty()).then(|| parts.join(" "))
+    }
+}
+
+/// Channel a synced message came through.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TouchChannel {
+    Telegram,
+    Email,
+}
+
+impl TouchChannel {
+    pub fn label(self) -> &'static str {
+        match self {
+            TouchChannel::Telegram => "Telegram",
+            TouchChannel::Email => "Email",
+        }
+    }
+}
+
+/// The most recent synced message with a contact on one channel: the Telegram
+/// snippet or the email subject.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LastTouchContextDto {
+    pub channel: TouchChannel,
+    pub occurred_at: i64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub direction: Option<InteractionDirection>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+}
+
+impl LastTouchContextDto {
+    /// "← 'see you thursday!' (2024-05-02)"; the arrow points left for inbound
+    /// messages and right for outbound ones. The date is local to `clock`.
+    pub fn summary(&self, clock: &Clock) -> String {
+        let mut parts = Vec::new();
+        match self.direction {
+            Some(InteractionDirection::Inbound) => parts.push("←".to_string()),
+            Some(InteractionDirection::Outbound) => parts.push("→".to_string()),
+            None => {}
+        }
+        if let Some(text) = self.text.as_deref() {
+            parts.push(format!("'{text}'"));
+        }
+        parts.push(format!(
+            "({})",
+            clock.format_timestamp_date(self.occurred_at)
+        ));
+        parts.join(" ")
+    }
+}
+
+/// Which source last wrote a contact field ("manual" for CLI/TUI edits).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FieldProvenanceDto {
+    pub field: String,
+    pub source: String,
+    pub updated_at: i64,
+}
+
+/// An import that changed a contact, and which fields it changed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SyncHistoryDto {
+    pub source: String,
+    pub fields: Vec<String>,
+    pub synced_at: i64,
+}
+
+/// A recorded change to a contact; `changes` maps each changed field to its
+/// `[old, new]` values.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ContactAuditDto {
+    pub occurred_at: i64,
+    pub action: String,
+    pub origin: String,
+    pub changes: BTreeMap<String, (Value, Value)>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -66,6 +258,9 @@ pub struct ExportInteractionDto {
     pub kind: String,
     pub note: String,
     pub follow_up_at: Option<i64>,
+    pub direction: Option<InteractionDirection>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub attachments: Vec<AttachmentDto>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -82,9 +277,24 @@ pub struct ExportContactDto {
     pub created_at: i64,
     pub updated_at: i64,
     pub archived_at: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub archive_reason: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub muted_until: Option<i64>,
     pub tags: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub aliases: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub origin: Option<ContactOriginDto>,
     pub dates: Vec<ContactDateDto>,
     pub interactions: Vec<ExportInteractionDto>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub organization: Option<String>,
+    #[serde(default)]
+    pub priority: i32,
+    /// Present only when the export was asked to include the audit trail.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub audit: Vec<ContactAuditDto>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -96,12 +306,49 @@ pub struct DateReminderItemDto {
     pub month: u8,
     pub day: u8,
     pub year: Option<i32>,
+    /// Days until the date, for `dates_upcoming` entries.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub days_until: Option<i64>,
+    /// Due bucket the same contact is listed in, when it is also due.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub also_due: Option<DueState>,
+}
+
+/// An interaction whose promised follow-up is still pending.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FollowUpItemDto {
+    pub interaction_id: InteractionId,
+    pub contact_id: ContactId,
+    pub display_name: String,
+    pub follow_up_at: i64,
+    pub kind: String,
+    pub note: String,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ExportSnapshotDto {
     pub metadata: ExportMetadataDto,
     pub contacts: Vec<ExportContactDto>,
+    /// Present only when the export was asked to include merge decisions.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub merge_candidates: Option<Vec<ExportMergeCandidateDto>>,
+}
+
+/// A merge candidate as exported. Contact ids are local to the exporting
+/// database, so the primary emails are carried along for matching elsewhere.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExportMergeCandidateDto {
+    pub id: MergeCandidateId,
+    pub created_at: i64,
+    pub status: String,
+    pub reason: String,
+    pub source: Option<String>,
+    pub contact_a_id: ContactId,
+    pub contact_a_email: Option<String>,
+    pub contact_b_id: ContactId,
+    pub contact_b_email: Option<String>,
+    pub preferred_contact_id: Option<ContactId>,
+    pub resolved_at: Option<i64>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -110,6 +357,29 @@ pub struct ReminderOutputDto {
     pub today: Vec<ContactListItemDto>,
     pub soon: Vec<ContactListItemDto>,
     pub dates_today: Vec<DateReminderItemDto>,
+    /// Dates inside their `remind_days_before` lead window, soonest first.
+    #[serde(default)]
+    pub dates_upcoming: Vec<DateReminderItemDto>,
+    #[serde(default)]
+    pub follow_ups: Vec<FollowUpItemDto>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub week: Vec<ReminderDayDto>,
+}
+
+/// One day of the weekly digest: touchpoints scheduled and dates occurring that day.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReminderDayDto {
+    pub date: String,
+    #[serde(default)]
+    pub contacts: Vec<ContactListItemDto>,
+    #[serde(default)]
+    pub dates: Vec<DateReminderItemDto>,
+}
+
+impl ReminderDayDto {
+    pub fn is_empty(&self) -> bool {
+        self.contacts.is_empty() && self.dates.is_empty()
+    }
 }
 
 impl ReminderOutputDto {
@@ -119,6 +389,9 @@ impl ReminderOutputDto {
             today: Vec::new(),
             soon: Vec::new(),
             dates_today: Vec::new(),
+            dates_upcoming: Vec::new(),
+            follow_ups: Vec::new(),
+            week: Vec::new(),
         };
 
         for item in items {
@@ -138,6 +411,9 @@ impl ReminderOutputDto {
             && self.today.is_empty()
             && self.soon.is_empty()
             && self.dates_today.is_empty()
+            && self.dates_upcoming.is_empty()
+            && self.follow_ups.is_empty()
+            && self.week.iter().all(ReminderDayDto::is_empty)
     }
 }
 
@@ -156,7 +432,13 @@ mod tests {
                 due_state: DueState::Overdue,
                 next_touchpoint_at: Some(1),
                 archived_at: None,
+                archive_reason: None,
+                muted_until: None,
                 tags: vec!["friends".to_string()],
+                last_interaction_at: None,
+                last_interaction_kind: None,
+                organization: None,
+                priority: 0,
             },
             ContactListItemDto {
                 id: ContactId::new(),
@@ -164,7 +446,13 @@ mod tests {
                 due_state: DueState::Today,
                 next_touchpoint_at: Some(2),
                 archived_at: None,
+                archive_reason: None,
+                muted_until: None,
                 tags: Vec::new(),
+                last_interaction_at: None,
+                last_interaction_kind: None,
+                organization: None,
+                priority: 0,
             },
             ContactListItemDto {
                 id: ContactId::new(),
@@ -172,7 +460,13 @@ mod tests {
                 due_state: DueState::Soon,
                 next_touchpoint_at: Some(3),
                 archived_at: None,
+                archive_reason: None,
+                muted_until: None,
                 tags: Vec::new(),
+                last_interaction_at: None,
+                last_interaction_kind: None,
+                organization: None,
+                priority: 0,
             },
             ContactListItemDto {
                 id: ContactId::new(),
@@ -180,7 +474,13 @@ mod tests {
                 due_state: DueState::Scheduled,
                 next_touchpoint_at: Some(4),
                 archived_at: None,
+                archive_reason: None,
+                muted_until: None,
                 tags: Vec::new(),
+                last_interaction_at: None,
+                last_interaction_kind: None,
+                organization: None,
+                priority: 0,
             },
             ContactListItemDto {
                 id: ContactId::new(),
@@ -188,7 +488,13 @@ mod tests {
                 due_state: DueState::Unscheduled,
                 next_touchpoint_at: None,
                 archived_at: None,
+                archive_reason: None,
+                muted_until: None,
                 tags: Vec::new(),
+                last_interaction_at: None,
+                last_interaction_kind: None,
+                organization: None,
+                priority: 0,
             },
         ];
 
@@ -4,14 +4,34 @@ use thiserror::Error;
 pub enum CoreError {
     #[error("display name is required")]
     EmptyDisplayName,
+    #[error("alias cannot be empty")]
+    EmptyAlias,
+    #[error("a contact cannot be introduced by itself")]
+    SelfIntroduction,
     #[error("invalid cadence days: {0}")]
     InvalidCadenceDays(i32),
+    #[error("invalid priority: {0} (expected 0-{max})", max = crate::domain::MAX_CONTACT_PRIORITY)]
+    InvalidPriority(i32),
+    #[error("invalid jitter days: {0}")]
+    InvalidJitterDays(i32),
     #[error("invalid soon days: {0}")]
     InvalidSoonDays(i64),
     #[error("invalid tag name")]
     InvalidTagName,
     #[error("invalid interaction kind label")]
     InvalidInteractionKindLabel,
+    #[error(
+        "invalid interaction kind: {0} (expected call|text|hangout|email|telegram|other:<label>)"
+    )]
+    InvalidInteractionKind(String),
+    #[error("invalid interaction direction: {0}")]
+    InvalidInteractionDirection(String),
+    #[error("invalid attachment kind: {0} (expected file|url)")]
+    InvalidAttachmentKind(String),
+    #[error("attachment cannot be empty")]
+    EmptyAttachment,
+    #[error("invalid attachment URL: {0} (expected a scheme, like https://...)")]
+    InvalidAttachmentUrl(String),
     #[error("invalid contact date kind: {0}")]
     InvalidContactDateKind(String),
     #[error("invalid contact date month: {0}")]
@@ -24,8 +44,24 @@ pub enum CoreError {
     MissingContactDateLabel,
     #[error("invalid contact date label")]
     InvalidContactDateLabel,
+    #[error("invalid contact date lead time: {0} days (max 365)")]
+    InvalidContactDateLeadDays(u16),
+    #[error("invalid phone number: {0}")]
+    InvalidPhone(String),
     #[error("invalid timestamp")]
     InvalidTimestamp,
     #[error("timestamp must be now or later")]
     TimestampInPast,
+    #[error("interaction time is before 1990-01-01")]
+    InteractionTooOld,
+    #[error("interaction time is more than {max_days} day(s) in the future")]
+    InteractionTooFarInFuture { max_days: i64 },
+    #[error("invalid JSON export: {0}")]
+    InvalidExport(String),
+    #[error("unsupported export format version {0} (supported: 1-{max})", max = crate::FORMAT_VERSION)]
+    InvalidExportFormatVersion(u32),
+    #[error(
+        "export format version {found} was produced by a newer knotter (this build reads up to {supported}); upgrade knotter to import it"
+    )]
+    NewerExportFormat { found: u32, supported: u32 },
 }
@@ -0,0 +1,433 @@
+//! Versioning for the `export json` snapshot format.
+//!
+//! Version history:
+//! - 1: contacts with emails, tags, dates, and interactions.
+//! - 2: adds `archive_reason`, `muted_until`, `aliases`, and `origin` on contacts,
+//!   `direction` on interactions, `remind_days_before` on dates, and the
+//!   snapshot-level `merge_candidates`.
+//! - 3: adds `organization` on contacts.
+//! - 4: adds `attachments` on interactions.
+//! - 5: adds `priority` on contacts.
+//!
+//! Readers accept every version up to `FORMAT_VERSION` by upgrading one step at
+//! a time; writers can downgrade contacts to an older version for older installs.
+
+use crate::dto::{ExportContactDto, ExportSnapshotDto};
+use crate::error::CoreError;
+use serde_json::{Map, Value};
+
+/// Snapshot format written by this build.
+pub const FORMAT_VERSION: u32 = 5;
+/// First format version that can carry `merge_candidates`.
+pub const MERGE_CANDIDATES_FORMAT_VERSION: u32 = 2;
+
+const V2_CONTACT_FIELDS: [&str; 4] = ["archive_reason", "muted_until", "aliases", "origin"];
+
+/// Upgrade steps; entry `i` maps version `i + 1` to `i + 2`.
+const UPGRADES: [fn(Value) -> Value; (FORMAT_VERSION - 1) as usize] = [
+    upgrade_v1_to_v2,
+    upgrade_v2_to_v3,
+    upgrade_v3_to_v4,
+    upgrade_v4_to_v5,
+];
+/// Contact downgrade steps; entry `i` maps version `i + 2` to `i + 1`.
+const CONTACT_DOWNGRADES: [fn(Value) -> Value; (FORMAT_VERSION - 1) as usize] = [
+    downgrade_contact_v2_to_v1,
+    downgrade_contact_v3_to_v2,
+    downgrade_contact_v4_to_v3,
+    downgrade_contact_v5_to_v4,
+];
+
+/// Rejects versions this build can neither read nor write.
+pub fn ensure_known_format_version(version: u32) -> Result<(), CoreError> {
+    if version == 0 || version > FORMAT_VERSION {
+        return Err(CoreError::InvalidExportFormatVersion(version));
+    }
+    Ok(())
+}
+
+/// Parses a JSON snapshot of any supported version, upgrading it to the
+/// current one. Snapshots from a newer knotter are rejected rather than
+/// silently misread.
+pub fn parse_export_snapshot(raw: &str) -> Result<ExportSnapshotDto, CoreError> {
+    let value: Value =
+        serde_json::from_str(raw).map_err(|err| CoreError::InvalidExport(err.to_string()))?;
+    let value = upgrade_snapshot(value)?;
+    serde_json::from_value(value).map_err(|err| CoreError::InvalidExport(err.to_string()))
+}
+
+/// Upgrades a raw snapshot to `FORMAT_VERSION` without deserializing it.
+pub fn upgrade_snapshot(mut value: Value) -> Result<Value, CoreError> {
+    let version = snapshot_format_version(&value)?;
+    if version > FORMAT_VERSION {
+        return Err(CoreError::NewerExportFormat {
+            found: version,
+            supported: FORMAT_VERSION,
+        });
+    }
+    ensure_known_format_version(version)?;
+    for (index, step) in UPGRADES.iter().enumerate().skip((version - 1) as usize) {
+        value = step(value);
+        set_format_version(&mut value, index as u32 + 2);
+    }
+    Ok(value)
+}
+
+/// Serializes a contact in the shape of an older (or the current) format.
+pub fn contact_for_format_version(
+    contact: &ExportContactDto,
+    version: u32,
+) -> Result<Value, CoreError> {
+    ensure_known_format_version(version)?;
+    let mut value =
+        serde_json::to_value(contact).map_err(|err| CoreError::InvalidExport(err.to_string()))?;
+    for step in CONTACT_DOWNGRADES.iter().skip((version - 1) as usize).rev() {
+        value = step(value);
+    }
+    Ok(value)
+}
+
+fn snapshot_format_version(value: &Value) -> Result<u32, CoreError> {
+    let raw = value
+        .get("metadata")
+        .and_then(|metadata| metadata.get("format_version"))
+        .ok_or_else(|| CoreError::InvalidExport("missing metadata.format_version".to_string()))?;
+    raw.as_u64()
+        .and_then(|version| u32::try_from(version).ok())
+        .ok_or_else(|| CoreError::InvalidExport(format!("invalid metadata.format_version: {raw}")))
+}
+
+fn set_format_version(value: &mut Value, version: u32) {
+    if let Some(metadata) = value.get_mut("metadata").and_then(Value::as_object_mut) {
+        metadata.insert("format_version".to_string(), Value::from(version));
+    }
+}
+
+/// v1 -> v2: gives every contact, interaction, and date the fields v2 added,
+/// empty. Builds that predate versioning already wrote some of them under
+/// version 1, so values that are present are kept.
+fn upgrade_v1_to_v2(mut value: Value) -> Value {
+    let contacts = value
+        .get_mut("contacts")
+        .and_then(Value::as_array_mut)
+        .into_iter()
+        .flatten()
+        .filter_map(Value::as_object_mut);
+    for contact in contacts {
+        for field in ["archive_reason", "muted_until", "origin"] {
+            contact.entry(field).or_insert(Value::Null);
+        }
+        contact
+            .entry("aliases")
+            .or_insert_with(|| Value::Array(Vec::new()));
+        for_each_child(contact, "interactions", |interaction| {
+            interaction.entry("direction").or_insert(Value::Null);
+        });
+        for_each_child(contact, "dates", |date| {
+            date.entry("remind_days_before").or_insert(Value::Null);
+        });
+    }
+    value
+}
+
+/// v2 -> v3: gives every contact an empty `organization`.
+fn upgrade_v2_to_v3(mut value: Value) -> Value {
+    let contacts = value
+        .get_mut("contacts")
+        .and_then(Value::as_array_mut)
+        .into_iter()
+        .flatten()
+        .filter_map(Value::as_object_mut);
+    for contact in contacts {
+        contact.entry("organization").or_insert(Value::Null);
+    }
+    value
+}
+
+/// v3 -> v4: gives every interaction an empty `attachments` list.
+fn upgrade_v3_to_v4(mut value: Value) -> Value {
+    let contacts = value
+        .get_mut("contacts")
+        .and_then(Value::as_array_mut)
+        .into_iter()
+        .flatten()
+        .filter_map(Value::as_object_mut);
+    for contact in contacts {
+        for_each_child(contact, "interactions", |interaction| {
+            interaction
+                .entry("attachments")
+                .or_insert_with(|| Value::Array(Vec::new()));
+        });
+    }
+    value
+}
+
+/// v4 -> v5: gives every contact priority 0.
+fn upgrade_v4_to_v5(mut value: Value) -> Value {
+    let contacts = value
+        .get_mut("contacts")
+        .and_then(Value::as_array_mut)
+        .into_iter()
+        .flatten()
+        .filter_map(Value::as_object_mut);
+    for contact in contacts {
+        contact.entry("priority").or_insert(Value::from(0));
+    }
+    value
+}
+
+/// v5 -> v4: drops `priority`.
+fn downgrade_contact_v5_to_v4(mut value: Value) -> Value {
+    if let Some(contact) = value.as_object_mut() {
+        contact.remove("priority");
+    }
+    value
+}
+
+/// v4 -> v3: drops interaction `attachments`.
+fn downgrade_contact_v4_to_v3(mut value: Value) -> Value {
+    if let Some(contact) = value.as_object_mut() {
+        for_each_child(contact, "interactions", |interaction| {
+            interaction.remove("attachments");
+        });
+    }
+    value
+}
+
+/// v3 -> v2: drops `organization`.
+fn downgrade_contact_v3_to_v2(mut value: Value) -> Value {
+    if let Some(contact) = value.as_object_mut() {
+        contact.remove("organization");
+    }
+    value
+}
+
+/// v2 -> v1: drops the fields v1 readers do not know about.
+fn downgrade_contact_v2_to_v1(mut value: Value) -> Value {
+    if let Some(contact) = value.as_object_mut() {
+        for field in V2_CONTACT_FIELDS {
+            contact.remove(field);
+        }
+        for_each_child(contact, "interactions", |interaction| {
+            interaction.remove("direction");
+        });
+        for_each_child(contact, "dates", |date| {
+            date.remove("remind_days_before");
+        });
+    }
+    value
+}
+
+fn for_each_child(
+    contact: &mut Map<String, Value>,
+    key: &str,
+    mut apply: impl FnMut(&mut Map<String, Value>),
+) {
+    let children = contact
+        .get_mut(key)
+        .and_then(Value::as_array_mut)
+        .into_iter()
+        .flatten()
+        .filter_map(Value::as_object_mut);
+    for child in children {
+        apply(child);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        contact_for_format_version, downgrade_contact_v2_to_v1, downgrade_contact_v3_to_v2,
+        downgrade_contact_v4_to_v3, downgrade_contact_v5_to_v4, parse_export_snapshot,
+        upgrade_snapshot, upgrade_v1_to_v2, upgrade_v2_to_v3, upgrade_v3_to_v4, upgrade_v4_to_v5,
+        FORMAT_VERSION,
+    };
+    use crate::domain::{AttachmentKind, ContactOrigin};
+    use crate::dto::{AttachmentDto, ContactOriginDto, ExportContactDto};
+    use crate::error::CoreError;
+    use serde_json::{json, Value};
+
+    const CONTACT_ID: &str = "7c9e6679-7425-40de-944b-e07fc1f90ae7";
+    const INTERACTION_ID: &str = "9b2e4c8a-1f3d-4e5b-8a6c-2d7f9e0b1c3a";
+    const DATE_ID: &str = "3f2504e0-4f89-41d3-9a0c-0305e82c3301";
+
+    fn v1_snapshot() -> Value {
+        json!({
+            "metadata": {
+                "exported_at": 1_700_000_000,
+                "app_version": "0.5.0",
+                "schema_version": 1,
+                "format_version": 1
+            },
+            "contacts": [{
+                "id": CONTACT_ID,
+                "display_name": "Ada Lovelace",
+                "email": "ada@example.com",
+                "emails": ["ada@example.com"],
+                "phone": null,
+                "handle": null,
+                "timezone": null,
+                "next_touchpoint_at": null,
+                "cadence_days": 30,
+                "created_at": 1_600_000_000,
+                "updated_at": 1_600_000_000,
+                "archived_at": null,
+                "tags": ["friend"],
+                "dates": [{
+                    "id": DATE_ID,
+                    "kind": "birthday",
+                    "label": null,
+                    "month": 12,
+                    "day": 10,
+                    "year": 1815
+                }],
+                "interactions": [{
+                    "id": INTERACTION_ID,
+                    "occurred_at": 1_650_000_000,
+                    "created_at": 1_650_000_000,
+                    "kind": "call",
+                    "note": "hello",
+                    "follow_up_at": null
+                }]
+            }]
+        })
+    }
+
+    #[test]
+    fn upgrade_v1_to_v2_adds_empty_fields_and_keeps_present_ones() {
+        let mut input = v1_snapshot();
+        input["contacts"][0]["aliases"] = json!(["Countess"]);
+        let upgraded = upgrade_v1_to_v2(input);
+        let contact = &upgraded["contacts"][0];
+        assert_eq!(contact["archive_reason"], Value::Null);
+        assert_eq!(contact["muted_until"], Value::Null);
+        assert_eq!(contact["origin"], Value::Null);
+        assert_eq!(contact["aliases"], json!(["Countess"]));
+        assert_eq!(contact["interactions"][0]["direction"], Value::Null);
+        assert_eq!(contact["dates"][0]["remind_days_before"], Value::Null);
+    }
+
+    #[test]
+    fn downgrade_v2_to_v1_strips_v2_fields() {
+        let mut contact = v1_snapshot()["contacts"][0].clone();
+        contact["archive_reason"] = json!("moved away");
+        contact["muted_until"] = json!(1_800_000_000);
+        contact["aliases"] = json!(["Countess"]);
+        contact["origin"] = json!({ "met_at": "Salon" });
+        contact["interactions"][0]["direction"] = json!("inbound");
+        contact["dates"][0]["remind_days_before"] = json!(7);
+
+        let downgraded = downgrade_contact_v2_to_v1(contact);
+        assert_eq!(downgraded, v1_snapshot()["contacts"][0]);
+    }
+
+    #[test]
+    fn organization_is_added_by_v3_and_dropped_for_v2() {
+        let upgraded = upgrade_v2_to_v3(upgrade_v1_to_v2(v1_snapshot()));
+        assert_eq!(upgraded["contacts"][0]["organization"], Value::Null);
+
+        let mut contact = v1_snapshot()["contacts"][0].clone();
+        contact["organization"] = json!("Analytical Engines");
+        let downgraded = downgrade_contact_v3_to_v2(contact);
+        assert_eq!(downgraded, v1_snapshot()["contacts"][0]);
+    }
+
+    #[test]
+    fn attachments_are_added_by_v4_and_dropped_for_v3() {
+        let upgraded = upgrade_v3_to_v4(v1_snapshot());
+        assert_eq!(
+            upgraded["contacts"][0]["interactions"][0]["attachments"],
+            json!([])
+        );
+
+        let mut contact = v1_snapshot()["contacts"][0].clone();
+        contact["interactions"][0]["attachments"] =
+            json!([{ "kind": "url", "value": "https://example.com", "created_at": 1 }]);
+        let downgraded = downgrade_contact_v4_to_v3(contact);
+        assert_eq!(downgraded, v1_snapshot()["contacts"][0]);
+    }
+
+    #[test]
+    fn priority_is_added_by_v5_and_dropped_for_v4() {
+        let upgraded = upgrade_v4_to_v5(v1_snapshot());
+        assert_eq!(upgraded["contacts"][0]["priority"], json!(0));
+
+        let mut contact = v1_snapshot()["contacts"][0].clone();
+        contact["priority"] = json!(10);
+        let downgraded = downgrade_contact_v5_to_v4(contact);
+        assert_eq!(downgraded, v1_snapshot()["contacts"][0]);
+    }
+
+    #[test]
+    fn parse_upgrades_v1_snapshots() {
+        let snapshot = parse_export_snapshot(&v1_snapshot().to_string()).expect("parse v1");
+        assert_eq!(snapshot.metadata.format_version, FORMAT_VERSION);
+        assert_eq!(snapshot.contacts[0].display_name, "Ada Lovelace");
+        assert!(snapshot.contacts[0].aliases.is_empty());
+        assert_eq!(snapshot.contacts[0].priority, 0);
+        assert!(snapshot.merge_candidates.is_none());
+    }
+
+    #[test]
+    fn parse_rejects_newer_and_unknown_versions() {
+        let mut newer = v1_snapshot();
+        newer["metadata"]["format_version"] = json!(FORMAT_VERSION + 1);
+        let err = parse_export_snapshot(&newer.to_string()).expect_err("newer");
+        assert_eq!(
+            err,
+            CoreError::NewerExportFormat {
+                found: FORMAT_VERSION + 1,
+                supported: FORMAT_VERSION,
+            }
+        );
+        assert!(err.to_string().contains("produced by a newer knotter"));
+
+        let mut zero = v1_snapshot();
+        zero["metadata"]["format_version"] = json!(0);
+        assert_eq!(
+            upgrade_snapshot(zero).expect_err("zero"),
+            CoreError::InvalidExportFormatVersion(0)
+        );
+
+        let mut missing = v1_snapshot();
+        missing["metadata"]
+            .as_object_mut()
+            .expect("metadata")
+            .remove("format_version");
+        assert!(matches!(
+            parse_export_snapshot(&missing.to_string()),
+            Err(CoreError::InvalidExport(_))
+        ));
+    }
+
+    #[test]
+    fn contacts_round_trip_through_older_versions() {
+        let mut snapshot = parse_export_snapshot(&v1_snapshot().to_string()).expect("parse");
+        let mut contact: ExportContactDto = snapshot.contacts.remove(0);
+        contact.aliases = vec!["Countess".to_string()];
+        contact.organization = Some("Analytical Engines".to_string());
+        contact.priority = 10;
+        contact.interactions[0].attachments = vec![AttachmentDto {
+            kind: AttachmentKind::File,
+            value: "notes/salon.md".to_string(),
+            created_at: 1_650_000_000,
+        }];
+        contact.origin = Some(ContactOriginDto::from_origin(
+            ContactOrigin {
+                met_at: Some("Salon".to_string()),
+                ..Default::default()
+            },
+            None,
+        ));
+
+        let current = contact_for_format_version(&contact, FORMAT_VERSION).expect("current");
+        assert_eq!(current, serde_json::to_value(&contact).expect("serialize"));
+
+        let v1 = contact_for_format_version(&contact, 1).expect("v1");
+        assert_eq!(v1, v1_snapshot()["contacts"][0]);
+        assert_eq!(
+            contact_for_format_version(&contact, FORMAT_VERSION + 1).expect_err("newer"),
+            CoreError::InvalidExportFormatVersion(FORMAT_VERSION + 1)
+        );
+    }
+}
@@ -7,12 +7,45 @@ pub enum ArchivedSelector {
     Active,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MutedSelector {
+    Muted,
+    Unmuted,
+}
+
+/// `priority:N` (exact) or `priority:>=N`; `priority:high` is `>=5`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PrioritySelector {
+    Exactly(i32),
+    AtLeast(i32),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReplySelector {
+    Awaiting,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FollowupSelector {
+    Pending,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum FilterExpr {
     Text(String),
     Tag(TagName),
     Due(DueSelector),
     Archived(ArchivedSelector),
+    Muted(MutedSelector),
+    Priority(PrioritySelector),
+    Reply(ReplySelector),
+    Followup(FollowupSelector),
+    /// Substring of where the contact was met (`met:rustconf`).
+    Met(String),
+    /// Substring of the contact's organization (`org:acme`).
+    Org(String),
+    /// Exact handle, stored with or without a leading `@` (`@alice`).
+    Handle(String),
     And(Vec<FilterExpr>),
 }
 
@@ -3,7 +3,10 @@ mod parser;
 
 use thiserror::Error;
 
-pub use ast::{ArchivedSelector, ContactFilter, FilterExpr};
+pub use ast::{
+    ArchivedSelector, ContactFilter, FilterExpr, FollowupSelector, MutedSelector, PrioritySelector,
+    ReplySelector,
+};
 pub use parser::parse_filter;
 
 #[derive(Debug, Error, PartialEq, Eq)]
@@ -14,6 +17,20 @@ pub enum FilterParseError {
     InvalidDueSelector(String),
     #[error("invalid archived selector: {0}")]
     InvalidArchivedSelector(String),
+    #[error("invalid muted selector: {0}")]
+    InvalidMutedSelector(String),
+    #[error("invalid priority selector: {0} (expected N, >=N, or high)")]
+    InvalidPrioritySelector(String),
+    #[error("invalid reply selector: {0}")]
+    InvalidReplySelector(String),
+    #[error("invalid followup selector: {0}")]
+    InvalidFollowupSelector(String),
+    #[error("empty met token")]
+    EmptyMet,
+    #[error("empty org token")]
+    EmptyOrg,
+    #[error("empty handle token")]
+    EmptyHandle,
     #[error("invalid tag: {0}")]
     InvalidTag(String),
 }
@@ -1,5 +1,8 @@
-use crate::domain::TagName;
-use crate::filter::ast::{ArchivedSelector, ContactFilter, FilterExpr};
+use crate::domain::{TagName, HIGH_CONTACT_PRIORITY, MAX_CONTACT_PRIORITY};
+use crate::filter::ast::{
+    ArchivedSelector, ContactFilter, FilterExpr, FollowupSelector, MutedSelector, PrioritySelector,
+    ReplySelector,
+};
 use crate::filter::FilterParseError;
 use crate::rules::DueSelector;
 
@@ -20,6 +23,34 @@ pub fn parse_filter(input: &str) -> Result<ContactFilter, FilterParseError> {
         } else if let Some(selector_raw) = token.strip_prefix("archived:") {
             let selector = parse_archived_selector(selector_raw)?;
             terms.push(FilterExpr::Archived(selector));
+        } else if let Some(selector_raw) = token.strip_prefix("muted:") {
+            let selector = parse_muted_selector(selector_raw)?;
+            terms.push(FilterExpr::Muted(selector));
+        } else if let Some(selector_raw) = token.strip_prefix("priority:") {
+            let selector = parse_priority_selector(selector_raw)?;
+            terms.push(FilterExpr::Priority(selector));
+        } else if let Some(selector_raw) = token.strip_prefix("reply:") {
+            let selector = parse_reply_selector(selector_raw)?;
+            terms.push(FilterExpr::Reply(selector));
+        } else if let Some(selector_raw) = token.strip_prefix("followup:") {
+            let selector = parse_followup_selector(selector_raw)?;
+            terms.push(FilterExpr::Followup(selector));
+        } else if let Some(met_raw) = token.strip_prefix("met:") {
+            if met_raw.is_empty() {
+                return Err(FilterParseError::EmptyMet);
+            }
+            terms.push(FilterExpr::Met(met_raw.to_string()));
+        } else if let Some(org_raw) = token.strip_prefix("org:") {
+            if org_raw.is_empty() {
+                return Err(FilterParseError::EmptyOrg);
+            }
+            terms.push(FilterExpr::Org(org_raw.to_string()));
+        } else if let Some(handle_raw) = token.strip_prefix('@') {
+            let handle = handle_raw.trim_start_matches('@');
+            if handle.is_empty() {
+                return Err(FilterParseError::EmptyHandle);
+            }
+            terms.push(FilterExpr::Handle(handle.to_string()));
         } else {
             terms.push(FilterExpr::Text(token.to_string()));
         }
@@ -33,6 +64,7 @@ fn parse_due_selector(raw: &str) -> Result<DueSelector, FilterParseError> {
         "overdue" => Ok(DueSelector::Overdue),
         "today" => Ok(DueSelector::Today),
         "soon" => Ok(DueSelector::Soon),
+        "scheduled" => Ok(DueSelector::Scheduled),
         "any" => Ok(DueSelector::Any),
         "none" => Ok(DueSelector::None),
         _ => Err(FilterParseError::InvalidDueSelector(raw.to_string())),
@@ -47,11 +79,56 @@ fn parse_archived_selector(raw: &str) -> Result<ArchivedSelector, FilterParseErr
     }
 }
 
+fn parse_muted_selector(raw: &str) -> Result<MutedSelector, FilterParseError> {
+    match raw {
+        "true" | "yes" | "1" | "muted" => Ok(MutedSelector::Muted),
+        "false" | "no" | "0" | "unmuted" => Ok(MutedSelector::Unmuted),
+        _ => Err(FilterParseError::InvalidMutedSelector(raw.to_string())),
+    }
+}
+
+fn parse_priority_selector(raw: &str) -> Result<PrioritySelector, FilterParseError> {
+    if raw == "high" {
+        return Ok(PrioritySelector::AtLeast(HIGH_CONTACT_PRIORITY));
+    }
+    let (at_least, number) = match raw.strip_prefix(">=") {
+        Some(number) => (true, number),
+        None => (false, raw),
+    };
+    let priority = number
+        .parse::<i32>()
+        .ok()
+        .filter(|value| (0..=MAX_CONTACT_PRIORITY).contains(value))
+        .ok_or_else(|| FilterParseError::InvalidPrioritySelector(raw.to_string()))?;
+    Ok(if at_least {
+        PrioritySelector::AtLeast(priority)
+    } else {
+        PrioritySelector::Exactly(priority)
+    })
+}
+
+fn parse_reply_selector(raw: &str) -> Result<ReplySelector, FilterParseError> {
+    match raw {
+        "awaiting" => Ok(ReplySelector::Awaiting),
+        _ => Err(FilterParseError::InvalidReplySelector(raw.to_string())),
+    }
+}
+
+fn parse_followup_selector(raw: &str) -> Result<FollowupSelector, FilterParseError> {
+    match raw {
+        "pending" => Ok(FollowupSelector::Pending),
+        _ => Err(FilterParseError::InvalidFollowupSelector(raw.to_string())),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::parse_filter;
     use crate::domain::TagName;
-    use crate::filter::ast::{ArchivedSelector, FilterExpr};
+    use crate::filter::ast::{
+        ArchivedSelector, FilterExpr, FollowupSelector, MutedSelector, PrioritySelector,
+        ReplySelector,
+    };
     use crate::filter::FilterParseError;
     use crate::rules::DueSelector;
 
@@ -67,6 +144,15 @@ mod tests {
         );
     }
 
+    #[test]
+    fn parse_due_scheduled() {
+        let filter = parse_filter("due:scheduled").unwrap();
+        assert_eq!(
+            filter,
+            FilterExpr::And(vec![FilterExpr::Due(DueSelector::Scheduled)])
+        );
+    }
+
     #[test]
     fn parse_archived_selector() {
         let filter = parse_filter("archived:true").unwrap();
@@ -82,6 +168,134 @@ mod tests {
         );
     }
 
+    #[test]
+    fn parse_muted_selector() {
+        let filter = parse_filter("muted:true").unwrap();
+        assert_eq!(
+            filter,
+            FilterExpr::And(vec![FilterExpr::Muted(MutedSelector::Muted)])
+        );
+        let filter = parse_filter("muted:false").unwrap();
+        assert_eq!(
+            filter,
+            FilterExpr::And(vec![FilterExpr::Muted(MutedSelector::Unmuted)])
+        );
+        let err = parse_filter("muted:later").unwrap_err();
+        assert_eq!(
+            err,
+            FilterParseError::InvalidMutedSelector("later".to_string())
+        );
+    }
+
+    #[test]
+    fn parse_priority_selector() {
+        let filter = parse_filter("priority:3 priority:>=5 priority:high").unwrap();
+        assert_eq!(
+            filter,
+            FilterExpr::And(vec![
+                FilterExpr::Priority(PrioritySelector::Exactly(3)),
+                FilterExpr::Priority(PrioritySelector::AtLeast(5)),
+                FilterExpr::Priority(PrioritySelector::AtLeast(5)),
+            ])
+        );
+        for raw in ["11", ">=-1", "low", ""] {
+            let err = parse_filter(&format!("priority:{raw}")).unwrap_err();
+            assert_eq!(
+                err,
+                FilterParseError::InvalidPrioritySelector(raw.to_string())
+            );
+        }
+    }
+
+    #[test]
+    fn parse_reply_selector() {
+        let filter = parse_filter("reply:awaiting").unwrap();
+        assert_eq!(
+            filter,
+            FilterExpr::And(vec![FilterExpr::Reply(ReplySelector::Awaiting)])
+        );
+        let err = parse_filter("reply:later").unwrap_err();
+        assert_eq!(
+            err,
+            FilterParseError::InvalidReplySelector("later".to_string())
+        );
+    }
+
+    #[test]
+    fn parse_followup_selector() {
+        let filter = parse_filter("followup:pending").unwrap();
+        assert_eq!(
+            filter,
+            FilterExpr::And(vec![FilterExpr::Followup(FollowupSelector::Pending)])
+        );
+        let err = parse_filter("followup:done").unwrap_err();
+        assert_eq!(
+            err,
+            FilterParseError::InvalidFollowupSelector("done".to_string())
+        );
+    }
+
+    #[test]
+    fn parse_met_term() {
+        let filter = parse_filter("met:rustconf #friends").unwrap();
+        assert_eq!(
+            filter,
+            FilterExpr::And(vec![
+                FilterExpr::Met("rustconf".to_string()),
+                FilterExpr::Tag(TagName::new("friends").unwrap())
+            ])
+        );
+        let err = parse_filter("met:").unwrap_err();
+        assert_eq!(err, FilterParseError::EmptyMet);
+    }
+
+    #[test]
+    fn parse_org_term() {
+        let filter = parse_filter("org:acme due:soon").unwrap();
+        assert_eq!(
+            filter,
+            FilterExpr::And(vec![
+                FilterExpr::Org("acme".to_string()),
+                FilterExpr::Due(DueSelector::Soon)
+            ])
+        );
+        let err = parse_filter("org:").unwrap_err();
+        assert_eq!(err, FilterParseError::EmptyOrg);
+    }
+
+    #[test]
+    fn parse_handle_term() {
+        let filter = parse_filter("@alice #friends").unwrap();
+        assert_eq!(
+            filter,
+            FilterExpr::And(vec![
+                FilterExpr::Handle("alice".to_string()),
+                FilterExpr::Tag(TagName::new("friends").unwrap())
+            ])
+        );
+        let filter = parse_filter("@@alice").unwrap();
+        assert_eq!(
+            filter,
+            FilterExpr::And(vec![FilterExpr::Handle("alice".to_string())])
+        );
+        assert_eq!(
+            parse_filter("@").unwrap_err(),
+            FilterParseError::EmptyHandle
+        );
+    }
+
+    #[test]
+    fn email_like_text_is_not_a_handle() {
+        let filter = parse_filter("alice@example.com @alice").unwrap();
+        assert_eq!(
+            filter,
+            FilterExpr::And(vec![
+                FilterExpr::Text("alice@example.com".to_string()),
+                FilterExpr::Handle("alice".to_string())
+            ])
+        );
+    }
+
     #[test]
     fn parse_text_terms() {
         let filter = parse_filter("alice bob").unwrap();
@@ -1,13 +1,19 @@
 pub mod domain;
 pub mod dto;
 pub mod error;
+pub mod export_format;
 pub mod filter;
 pub mod rules;
+pub mod template;
 pub mod time;
 
 pub use domain::*;
 pub use dto::*;
 pub use error::CoreError;
-pub use filter::{parse_filter, ContactFilter, FilterExpr, FilterParseError};
+pub use export_format::FORMAT_VERSION;
+pub use filter::{
+    parse_filter, ContactFilter, FilterExpr, FilterParseError, FollowupSelector, ReplySelector,
+};
 pub use rules::*;
+pub use template::{NoteTemplate, TemplateError};
 pub use time::*;
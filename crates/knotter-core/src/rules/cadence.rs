@@ -1,6 +1,26 @@
+use crate::domain::ContactId;
 use crate::error::CoreError;
+use std::collections::BTreeMap;
 
 pub const MAX_CADENCE_DAYS: i32 = 3650;
+/// Widest spread accepted for `loops.jitter_days` and `--jitter`.
+pub const MAX_JITTER_DAYS: i32 = 30;
+
+/// Cadences offered by `suggest_cadence`, in days.
+pub const SUGGESTED_CADENCE_BUCKETS: [i32; 6] = [7, 14, 30, 60, 90, 180];
+/// Only interactions this recent feed a suggestion.
+pub const SUGGESTION_WINDOW_DAYS: i64 = 365;
+/// Fewer distinct interaction days than this yields no suggestion.
+pub const MIN_SUGGESTION_INTERACTION_DAYS: usize = 3;
+
+const SECONDS_PER_DAY: i64 = 86_400;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CadenceSuggestion {
+    pub cadence_days: i32,
+    pub median_gap_days: f64,
+    pub interaction_days: usize,
+}
 
 pub fn schedule_next(now_utc: i64, cadence_days: i32) -> Result<i64, CoreError> {
     if cadence_days <= 0 || cadence_days > MAX_CADENCE_DAYS {
@@ -27,9 +47,150 @@ pub fn next_touchpoint_after_touch(
     }
 }
 
+/// Moves `timestamp` by a whole number of days in `-jitter_days..=jitter_days`.
+/// The offset is derived from the contact id alone, so a contact lands on the
+/// same day every time while a batch scheduled for one date fans out.
+pub fn jitter_touchpoint(
+    timestamp: i64,
+    jitter_days: i32,
+    contact_id: &ContactId,
+) -> Result<i64, CoreError> {
+    if !(0..=MAX_JITTER_DAYS).contains(&jitter_days) {
+        return Err(CoreError::InvalidJitterDays(jitter_days));
+    }
+    if jitter_days == 0 {
+        return Ok(timestamp);
+    }
+    // FNV-1a keeps the offset stable across runs and builds.
+    let hash = contact_id
+        .as_uuid()
+        .as_bytes()
+        .iter()
+        .fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+            (hash ^ u64::from(*byte)).wrapping_mul(0x0100_0000_01b3)
+        });
+    let span = 2 * u64::from(jitter_days.unsigned_abs()) + 1;
+    let offset = (hash % span) as i64 - i64::from(jitter_days);
+    Ok(timestamp + offset * SECONDS_PER_DAY)
+}
+
+/// A scheduled touchpoint as seen by `rebalance_days`: the day number it falls
+/// on (any consistent count, such as local days since the epoch) and the
+/// contact's cadence.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ScheduledDay {
+    pub day: i64,
+    pub cadence_days: Option<i32>,
+}
+
+/// Moves touchpoints off days holding more than `max_per_day` of them, within
+/// `first_day..first_day + window_days`. Returns `(index into scheduled, new
+/// day)` for each move.
+///
+/// On a crowded day the contacts with the shortest cadence stay put, since a
+/// shift of a few days matters least to those seen rarely (no cadence counts
+/// as the longest). Each one moved goes to the nearest day with room, the
+/// later day on a tie. Touchpoints outside the window are neither moved nor
+/// counted; when the window has no room left the rest stay where they are.
+pub fn rebalance_days(
+    scheduled: &[ScheduledDay],
+    first_day: i64,
+    window_days: i64,
+    max_per_day: usize,
+) -> Vec<(usize, i64)> {
+    let last_day = first_day + window_days - 1;
+    let in_window = |day: i64| (first_day..=last_day).contains(&day);
+    let mut by_day: BTreeMap<i64, Vec<usize>> = BTreeMap::new();
+    for (index, item) in scheduled.iter().enumerate() {
+        if in_window(item.day) {
+            by_day.entry(item.day).or_default().push(index);
+        }
+    }
+    let mut load: BTreeMap<i64, usize> = by_day
+        .iter()
+        .map(|(day, indexes)| (*day, indexes.len()))
+        .collect();
+
+    let mut moves = Vec::new();
+    for (day, mut indexes) in by_day {
+        if indexes.len() <= max_per_day {
+            continue;
+        }
+        indexes.sort_by_key(|index| (scheduled[*index].cadence_days.unwrap_or(i32::MAX), *index));
+        for index in indexes.into_iter().skip(max_per_day) {
+            let has_room = |candidate: i64, load: &BTreeMap<i64, usize>| {
+                in_window(candidate) && load.get(&candidate).copied().unwrap_or(0) < max_per_day
+            };
+            let target = (1..window_days).find_map(|distance| {
+                [day + distance, day - distance]
+                    .into_iter()
+                    .find(|candidate| has_room(*candidate, &load))
+            });
+            let Some(target) = target else {
+                continue;
+            };
+            *load.entry(day).or_default() -= 1;
+            *load.entry(target).or_default() += 1;
+            moves.push((index, target));
+        }
+    }
+    moves
+}
+
+/// Suggests a cadence from interaction timestamps (unix seconds UTC, any order).
+///
+/// Interactions from the last year are collapsed to distinct UTC days, so a burst
+/// of imported emails on one day counts once. The median gap between those days
+/// is rounded to the nearest bucket in `SUGGESTED_CADENCE_BUCKETS` (ties pick the
+/// shorter cadence).
+pub fn suggest_cadence(now_utc: i64, occurred_at: &[i64]) -> Option<CadenceSuggestion> {
+    let window_start = now_utc - SUGGESTION_WINDOW_DAYS * SECONDS_PER_DAY;
+    let mut days: Vec<i64> = occurred_at
+        .iter()
+        .copied()
+        .filter(|ts| *ts >= window_start && *ts <= now_utc)
+        .map(|ts| ts.div_euclid(SECONDS_PER_DAY))
+        .collect();
+    days.sort_unstable();
+    days.dedup();
+    if days.len() < MIN_SUGGESTION_INTERACTION_DAYS {
+        return None;
+    }
+
+    let mut gaps: Vec<i64> = days.windows(2).map(|pair| pair[1] - pair[0]).collect();
+    gaps.sort_unstable();
+    let mid = gaps.len() / 2;
+    let median_gap_days = if gaps.len().is_multiple_of(2) {
+        (gaps[mid - 1] + gaps[mid]) as f64 / 2.0
+    } else {
+        gaps[mid] as f64
+    };
+
+    Some(CadenceSuggestion {
+        cadence_days: nearest_cadence_bucket(median_gap_days),
+        median_gap_days,
+        interaction_days: days.len(),
+    })
+}
+
+fn nearest_cadence_bucket(gap_days: f64) -> i32 {
+    let mut best = SUGGESTED_CADENCE_BUCKETS[0];
+    for bucket in SUGGESTED_CADENCE_BUCKETS {
+        if (f64::from(bucket) - gap_days).abs() < (f64::from(best) - gap_days).abs() {
+            best = bucket;
+        }
+    }
+    best
+}
+
 #[cfg(test)]
 mod tests {
-    use super::{next_touchpoint_after_touch, schedule_next, MAX_CADENCE_DAYS};
+    use super::{
+        jitter_touchpoint, next_touchpoint_after_touch, rebalance_days, schedule_next,
+        suggest_cadence, ScheduledDay, MAX_CADENCE_DAYS, MAX_JITTER_DAYS, SECONDS_PER_DAY,
+    };
+    use crate::domain::ContactId;
+    use std::collections::HashMap;
 
     #[test]
     fn schedule_next_adds_days() {
@@ -52,4 +213,151 @@ mod tests {
         let result = next_touchpoint_after_touch(now, Some(7), false, existing).unwrap();
         assert_eq!(result, existing);
     }
+
+    const NOW: i64 = 1_700_000_000;
+
+    fn days_ago(days: i64) -> i64 {
+        NOW - days * SECONDS_PER_DAY
+    }
+
+    #[test]
+    fn suggest_cadence_needs_three_interaction_days() {
+        assert_eq!(suggest_cadence(NOW, &[]), None);
+        assert_eq!(suggest_cadence(NOW, &[days_ago(30), days_ago(1)]), None);
+        // Same-day duplicates do not count as separate interactions.
+        let same_day = [
+            days_ago(30),
+            days_ago(30) + 60,
+            days_ago(1),
+            days_ago(1) + 60,
+        ];
+        assert_eq!(suggest_cadence(NOW, &same_day), None);
+    }
+
+    #[test]
+    fn suggest_cadence_ignores_interactions_older_than_a_year() {
+        let history = [days_ago(800), days_ago(700), days_ago(60), days_ago(30)];
+        assert_eq!(suggest_cadence(NOW, &history), None);
+    }
+
+    #[test]
+    fn suggest_cadence_collapses_bursty_email_threads() {
+        let mut history = Vec::new();
+        for start in [days_ago(90), days_ago(60), days_ago(30), days_ago(0)] {
+            for minute in 0..6 {
+                history.push(start + minute * 60);
+            }
+        }
+        let suggestion = suggest_cadence(NOW, &history).expect("suggestion");
+        assert_eq!(suggestion.interaction_days, 4);
+        assert_eq!(suggestion.median_gap_days, 30.0);
+        assert_eq!(suggestion.cadence_days, 30);
+    }
+
+    #[test]
+    fn suggest_cadence_uses_median_gap() {
+        // Gaps of 5, 6, and 100 days: the outlier does not drag the median.
+        let history = [days_ago(111), days_ago(11), days_ago(5), days_ago(0)];
+        let suggestion = suggest_cadence(NOW, &history).expect("suggestion");
+        assert_eq!(suggestion.median_gap_days, 6.0);
+        assert_eq!(suggestion.cadence_days, 7);
+
+        // Even number of gaps averages the middle pair.
+        let history = [
+            days_ago(60),
+            days_ago(50),
+            days_ago(30),
+            days_ago(10),
+            days_ago(0),
+        ];
+        let suggestion = suggest_cadence(NOW, &history).expect("suggestion");
+        assert_eq!(suggestion.median_gap_days, 15.0);
+        assert_eq!(suggestion.cadence_days, 14);
+    }
+
+    #[test]
+    fn suggest_cadence_rounds_to_nearest_bucket() {
+        let gap = |days: i64| suggest_cadence(NOW, &[days_ago(2 * days), days_ago(days), NOW]);
+        assert_eq!(gap(1).unwrap().cadence_days, 7);
+        assert_eq!(gap(10).unwrap().cadence_days, 7); // tie between 7 and 14 picks 7
+        assert_eq!(gap(11).unwrap().cadence_days, 14);
+        assert_eq!(gap(22).unwrap().cadence_days, 14); // tie between 14 and 30
+        assert_eq!(gap(23).unwrap().cadence_days, 30);
+        assert_eq!(gap(45).unwrap().cadence_days, 30);
+        assert_eq!(gap(46).unwrap().cadence_days, 60);
+        assert_eq!(gap(76).unwrap().cadence_days, 90);
+        assert_eq!(gap(135).unwrap().cadence_days, 90);
+        assert_eq!(gap(136).unwrap().cadence_days, 180);
+    }
+
+    #[test]
+    fn jitter_is_stable_per_contact_and_stays_in_range() {
+        let base = 1_700_000_000;
+        let ids: Vec<ContactId> = (0..200).map(|_| ContactId::new()).collect();
+        let mut offsets = HashMap::new();
+        for id in &ids {
+            let first = jitter_touchpoint(base, 5, id).unwrap();
+            assert_eq!(jitter_touchpoint(base, 5, id).unwrap(), first);
+            let offset_days = (first - base) / SECONDS_PER_DAY;
+            assert_eq!((first - base) % SECONDS_PER_DAY, 0);
+            assert!((-5..=5).contains(&offset_days));
+            *offsets.entry(offset_days).or_insert(0) += 1;
+        }
+        // 200 contacts over 11 days spread out rather than piling up.
+        assert!(offsets.len() > 5);
+
+        let id = ContactId::new();
+        assert_eq!(jitter_touchpoint(base, 0, &id).unwrap(), base);
+        assert!(jitter_touchpoint(base, -1, &id).is_err());
+        assert!(jitter_touchpoint(base, MAX_JITTER_DAYS + 1, &id).is_err());
+    }
+
+    fn day(day: i64, cadence_days: Option<i32>) -> ScheduledDay {
+        ScheduledDay { day, cadence_days }
+    }
+
+    fn apply(scheduled: &[ScheduledDay], moves: &[(usize, i64)]) -> Vec<i64> {
+        let mut days: Vec<i64> = scheduled.iter().map(|item| item.day).collect();
+        for (index, target) in moves {
+            days[*index] = *target;
+        }
+        days
+    }
+
+    #[test]
+    fn rebalance_caps_each_day_and_moves_longest_cadences_to_nearest_days() {
+        let mut scheduled = vec![day(10, Some(7)), day(10, Some(90)), day(10, None)];
+        scheduled.extend((0..5).map(|_| day(10, Some(30))));
+        scheduled.push(day(11, Some(30)));
+        scheduled.push(day(11, Some(30)));
+
+        let moves = rebalance_days(&scheduled, 5, 30, 3);
+        let days = apply(&scheduled, &moves);
+        let mut load: HashMap<i64, usize> = HashMap::new();
+        for day in &days {
+            *load.entry(*day).or_default() += 1;
+        }
+        assert!(load.values().all(|count| *count <= 3));
+        assert_eq!(days[0], 10, "the 7-day cadence stays");
+        assert_ne!(days[1], 10, "the 90-day cadence moves");
+        assert_ne!(days[2], 10, "no cadence moves");
+        // Day 11 had one free slot and day 9 is as close; later wins the tie.
+        assert_eq!(load[&11], 3);
+        assert_eq!(load[&9], 3);
+        assert!(days.iter().all(|day| (8..=12).contains(day)));
+
+        assert_eq!(rebalance_days(&scheduled, 5, 30, 3), moves);
+    }
+
+    #[test]
+    fn rebalance_leaves_outside_window_and_overflow_alone() {
+        let scheduled = vec![day(1, None), day(1, None), day(50, None), day(50, None)];
+        assert!(rebalance_days(&scheduled, 5, 30, 1).is_empty());
+
+        // Two days of room for five touchpoints: two stay, one moves, two stay put.
+        let scheduled: Vec<ScheduledDay> = (0..5).map(|_| day(5, Some(30))).collect();
+        let moves = rebalance_days(&scheduled, 5, 2, 1);
+        assert_eq!(moves.len(), 1);
+        assert_eq!(moves[0].1, 6);
+    }
 }
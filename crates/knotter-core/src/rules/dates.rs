@@ -24,14 +24,49 @@ pub fn date_occurs_today(
     Ok(false)
 }
 
+/// Days from `today` to the next occurrence of `month`/`day` (0 when it is
+/// today). Feb 29 dates fall on Feb 28 in non-leap years, and dates already
+/// past this year wrap to next year.
+pub fn days_until_next_occurrence(today: NaiveDate, month: u8, day: u8) -> Option<i64> {
+    let this_year = occurrence_in_year(today.year(), month, day)?;
+    let next = if this_year >= today {
+        this_year
+    } else {
+        occurrence_in_year(today.year() + 1, month, day)?
+    };
+    Some((next - today).num_days())
+}
+
+/// How many days ahead the date is when today falls inside its lead window
+/// (`1..=lead_days` days before it). Day-of occurrences are left to
+/// `date_occurs_today`, so this returns `None` for them.
+pub fn date_in_lead_window(
+    now_utc: i64,
+    month: u8,
+    day: u8,
+    lead_days: u16,
+    local_offset: FixedOffset,
+) -> Result<Option<i64>, CoreError> {
+    let today = local_today(now_utc, local_offset)?;
+    Ok(days_until_next_occurrence(today, month, day)
+        .filter(|days| (1..=i64::from(lead_days)).contains(days)))
+}
+
+fn occurrence_in_year(year: i32, month: u8, day: u8) -> Option<NaiveDate> {
+    if month == 2 && day == 29 && !is_leap_year(year) {
+        return NaiveDate::from_ymd_opt(year, 2, 28);
+    }
+    NaiveDate::from_ymd_opt(year, month.into(), day.into())
+}
+
 pub fn is_leap_year(year: i32) -> bool {
     (year % 4 == 0 && year % 100 != 0) || year % 400 == 0
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{date_occurs_today, is_leap_year};
-    use chrono::{FixedOffset, TimeZone, Utc};
+    use super::{date_in_lead_window, date_occurs_today, days_until_next_occurrence, is_leap_year};
+    use chrono::{FixedOffset, NaiveDate, TimeZone, Utc};
 
     #[test]
     fn date_occurs_today_exact_match() {
@@ -72,4 +107,55 @@ mod tests {
         assert!(!is_leap_year(1900));
         assert!(is_leap_year(2000));
     }
+
+    #[test]
+    fn lead_window_wraps_into_next_year() {
+        let offset = FixedOffset::east_opt(0).unwrap();
+        let now = Utc
+            .with_ymd_and_hms(2024, 12, 25, 12, 0, 0)
+            .unwrap()
+            .timestamp();
+        assert_eq!(
+            date_in_lead_window(now, 1, 3, 14, offset).expect("date"),
+            Some(9)
+        );
+        assert_eq!(
+            date_in_lead_window(now, 1, 10, 14, offset).expect("date"),
+            None
+        );
+        assert_eq!(
+            date_in_lead_window(now, 12, 31, 14, offset).expect("date"),
+            Some(6)
+        );
+        // Day-of and already-passed dates are not "upcoming".
+        assert_eq!(
+            date_in_lead_window(now, 12, 25, 14, offset).expect("date"),
+            None
+        );
+        assert_eq!(
+            date_in_lead_window(now, 12, 20, 14, offset).expect("date"),
+            None
+        );
+    }
+
+    #[test]
+    fn lead_window_handles_leap_day() {
+        let feb_20_non_leap = NaiveDate::from_ymd_opt(2023, 2, 20).unwrap();
+        assert_eq!(days_until_next_occurrence(feb_20_non_leap, 2, 29), Some(8));
+        let feb_20_leap = NaiveDate::from_ymd_opt(2024, 2, 20).unwrap();
+        assert_eq!(days_until_next_occurrence(feb_20_leap, 2, 29), Some(9));
+        // From late 2027 the next Feb 29 is in leap year 2028.
+        let dec_31 = NaiveDate::from_ymd_opt(2027, 12, 31).unwrap();
+        assert_eq!(days_until_next_occurrence(dec_31, 2, 29), Some(60));
+
+        let offset = FixedOffset::east_opt(0).unwrap();
+        let now = Utc
+            .with_ymd_and_hms(2023, 2, 21, 12, 0, 0)
+            .unwrap()
+            .timestamp();
+        assert_eq!(
+            date_in_lead_window(now, 2, 29, 7, offset).expect("date"),
+            Some(7)
+        );
+    }
 }
@@ -1,6 +1,7 @@
-use chrono::{DateTime, Duration, FixedOffset, TimeZone, Utc};
+use chrono::{Duration, FixedOffset, NaiveDate, TimeZone, Utc};
 
 use crate::error::CoreError;
+use crate::rules::local_today;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -19,6 +20,8 @@ pub enum DueSelector {
     Overdue,
     Today,
     Soon,
+    /// Scheduled beyond the soon window.
+    Scheduled,
     Any,
     None,
 }
@@ -32,6 +35,48 @@ pub fn validate_soon_days(soon_days: i64) -> Result<i64, CoreError> {
     Ok(soon_days)
 }
 
+/// Where the due buckets split, as UTC timestamps: overdue before `now`,
+/// today until `start_of_tomorrow`, soon until `soon_end`. Every surface that
+/// buckets contacts (list filters, remind, loops) uses these bounds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DueBounds {
+    pub start_of_today: i64,
+    pub start_of_tomorrow: i64,
+    pub soon_end: i64,
+}
+
+impl DueBounds {
+    /// Bounds for the local calendar day `today` at `local_offset`.
+    pub fn for_local_day(today: NaiveDate, soon_days: i64, local_offset: FixedOffset) -> Self {
+        let start_of_today_local = today.and_hms_opt(0, 0, 0).expect("midnight is valid");
+        let start_of_tomorrow_local = start_of_today_local + Duration::days(1);
+        let start_of_today = local_offset
+            .from_local_datetime(&start_of_today_local)
+            .single()
+            .expect("fixed offset conversion")
+            .with_timezone(&Utc)
+            .timestamp();
+        let start_of_tomorrow = local_offset
+            .from_local_datetime(&start_of_tomorrow_local)
+            .single()
+            .expect("fixed offset conversion")
+            .with_timezone(&Utc)
+            .timestamp();
+
+        Self {
+            start_of_today,
+            start_of_tomorrow,
+            soon_end: start_of_tomorrow + Duration::days(soon_days).num_seconds(),
+        }
+    }
+}
+
+/// Bounds for the local day containing `now_utc`.
+pub fn due_bounds(now_utc: i64, soon_days: i64, local_offset: FixedOffset) -> DueBounds {
+    let today = local_today(now_utc, local_offset).expect("valid timestamp");
+    DueBounds::for_local_day(today, soon_days, local_offset)
+}
+
 pub fn compute_due_state(
     now_utc: i64,
     next_touchpoint_at: Option<i64>,
@@ -48,46 +93,23 @@ pub fn compute_due_state(
         return Ok(DueState::Overdue);
     }
 
-    let (start_of_today, start_of_tomorrow) = local_day_bounds(now_utc, local_offset);
-
-    if next >= start_of_today && next < start_of_tomorrow {
+    let bounds = due_bounds(now_utc, soon_days, local_offset);
+    if next >= bounds.start_of_today && next < bounds.start_of_tomorrow {
         return Ok(DueState::Today);
     }
-
-    let soon_end = start_of_tomorrow + Duration::days(soon_days).num_seconds();
-    if next >= start_of_tomorrow && next < soon_end {
+    if next >= bounds.start_of_tomorrow && next < bounds.soon_end {
         return Ok(DueState::Soon);
     }
 
     Ok(DueState::Scheduled)
 }
 
-fn local_day_bounds(now_utc: i64, local_offset: FixedOffset) -> (i64, i64) {
-    let now = DateTime::<Utc>::from_timestamp(now_utc, 0).expect("valid timestamp");
-    let local = now.with_timezone(&local_offset);
-    let local_date = local.date_naive();
-    let start_of_today_local = local_date.and_hms_opt(0, 0, 0).expect("midnight is valid");
-    let start_of_tomorrow_local = start_of_today_local + Duration::days(1);
-    let start_of_today = local_offset
-        .from_local_datetime(&start_of_today_local)
-        .single()
-        .expect("fixed offset conversion")
-        .with_timezone(&Utc)
-        .timestamp();
-    let start_of_tomorrow = local_offset
-        .from_local_datetime(&start_of_tomorrow_local)
-        .single()
-        .expect("fixed offset conversion")
-        .with_timezone(&Utc)
-        .timestamp();
-
-    (start_of_today, start_of_tomorrow)
-}
-
 #[cfg(test)]
 mod tests {
-    use super::{compute_due_state, validate_soon_days, DueState, MAX_SOON_DAYS};
-    use chrono::{FixedOffset, TimeZone, Utc};
+    use super::{
+        compute_due_state, due_bounds, validate_soon_days, DueBounds, DueState, MAX_SOON_DAYS,
+    };
+    use chrono::{FixedOffset, NaiveDate, TimeZone, Utc};
 
     #[test]
     fn due_state_unscheduled() {
@@ -158,4 +180,51 @@ mod tests {
         let result = validate_soon_days(MAX_SOON_DAYS + 1);
         assert!(result.is_err());
     }
+
+    #[test]
+    fn due_today_flips_at_local_midnight_not_utc_midnight() {
+        // A contact due 2024-01-10 09:00 in UTC+9, which is 00:00 UTC.
+        let tokyo = FixedOffset::east_opt(9 * 3600).unwrap();
+        let next = tokyo
+            .with_ymd_and_hms(2024, 1, 10, 9, 0, 0)
+            .unwrap()
+            .timestamp();
+        // 23:59 local on the 9th is still 14:59 UTC on the 9th.
+        let before_midnight = tokyo
+            .with_ymd_and_hms(2024, 1, 9, 23, 59, 0)
+            .unwrap()
+            .timestamp();
+        // 00:01 local on the 10th is 15:01 UTC on the 9th, where a UTC
+        // machine still sees yesterday.
+        let after_midnight = tokyo
+            .with_ymd_and_hms(2024, 1, 10, 0, 1, 0)
+            .unwrap()
+            .timestamp();
+        let utc = FixedOffset::east_opt(0).unwrap();
+
+        assert_eq!(
+            compute_due_state(before_midnight, Some(next), 7, tokyo).unwrap(),
+            DueState::Soon
+        );
+        assert_eq!(
+            compute_due_state(after_midnight, Some(next), 7, tokyo).unwrap(),
+            DueState::Today
+        );
+        assert_eq!(
+            compute_due_state(after_midnight, Some(next), 7, utc).unwrap(),
+            DueState::Soon
+        );
+
+        let bounds = due_bounds(after_midnight, 7, tokyo);
+        assert_eq!(
+            bounds,
+            DueBounds::for_local_day(NaiveDate::from_ymd_opt(2024, 1, 10).unwrap(), 7, tokyo)
+        );
+        assert_eq!(
+            bounds.start_of_today,
+            Utc.with_ymd_and_hms(2024, 1, 9, 15, 0, 0)
+                .unwrap()
+                .timestamp()
+        );
+    }
 }
@@ -16,7 +16,10 @@ pub enum LoopStrategy {
 pub struct LoopRule {
     pub tag: TagName,
     pub cadence_days: i32,
+    /// Rank among rules under [`LoopStrategy::Priority`]; unrelated to contact priority.
     pub priority: i32,
+    /// Contacts whose priority is below this get no cadence from the rule.
+    pub min_priority: Option<i32>,
 }
 
 impl LoopRule {
@@ -29,8 +32,19 @@ impl LoopRule {
             tag,
             cadence_days,
             priority,
+            min_priority: None,
         })
     }
+
+    pub fn with_min_priority(mut self, min_priority: Option<i32>) -> Self {
+        self.min_priority = min_priority;
+        self
+    }
+
+    fn applies_to(&self, contact_priority: i32) -> bool {
+        self.min_priority
+            .is_none_or(|min_priority| contact_priority >= min_priority)
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Default)]
@@ -41,14 +55,21 @@ pub struct LoopPolicy {
 }
 
 impl LoopPolicy {
-    pub fn resolve_cadence<'a, I>(&self, tags: I) -> Option<i32>
+    /// Cadence for a contact with these tags and `contact_priority`. A tag whose
+    /// rule has a `min_priority` above the contact's still counts as matched, so
+    /// such a contact gets neither that rule's cadence nor the default.
+    pub fn resolve_cadence<'a, I>(&self, tags: I, contact_priority: i32) -> Option<i32>
     where
         I: IntoIterator<Item = &'a str>,
     {
-        self.resolve_cadence_with_match(tags).0
+        self.resolve_cadence_with_match(tags, contact_priority).0
     }
 
-    pub fn resolve_cadence_with_match<'a, I>(&self, tags: I) -> (Option<i32>, bool)
+    pub fn resolve_cadence_with_match<'a, I>(
+        &self,
+        tags: I,
+        contact_priority: i32,
+    ) -> (Option<i32>, bool)
     where
         I: IntoIterator<Item = &'a str>,
     {
@@ -70,6 +91,9 @@ impl LoopPolicy {
                     .filter(|rule| tag_set.contains(rule.tag.as_str()))
                 {
                     matched = true;
+                    if !rule.applies_to(contact_priority) {
+                        continue;
+                    }
                     best = Some(match best {
                         None => rule.cadence_days,
                         Some(current) => current.min(rule.cadence_days),
@@ -90,6 +114,9 @@ impl LoopPolicy {
                     .filter(|rule| tag_set.contains(rule.tag.as_str()))
                 {
                     matched = true;
+                    if !rule.applies_to(contact_priority) {
+                        continue;
+                    }
                     best = Some(select_priority(best, rule));
                 }
                 if matched {
@@ -139,7 +166,7 @@ mod tests {
             ],
         };
 
-        let cadence = policy.resolve_cadence(["friend", "family"].iter().copied());
+        let cadence = policy.resolve_cadence(["friend", "family"].iter().copied(), 0);
         assert_eq!(cadence, Some(30));
     }
 
@@ -154,7 +181,7 @@ mod tests {
             ],
         };
 
-        let cadence = policy.resolve_cadence(["friend", "family"].iter().copied());
+        let cadence = policy.resolve_cadence(["friend", "family"].iter().copied(), 0);
         assert_eq!(cadence, Some(90));
     }
 
@@ -169,7 +196,7 @@ mod tests {
             ],
         };
 
-        let cadence = policy.resolve_cadence(["friend", "family"].iter().copied());
+        let cadence = policy.resolve_cadence(["friend", "family"].iter().copied(), 0);
         assert_eq!(cadence, Some(30));
     }
 
@@ -181,7 +208,26 @@ mod tests {
             rules: vec![LoopRule::new(TagName::new("friend").unwrap(), 90, 0).unwrap()],
         };
 
-        let cadence = policy.resolve_cadence(["coworker"].iter().copied());
+        let cadence = policy.resolve_cadence(["coworker"].iter().copied(), 0);
         assert_eq!(cadence, Some(180));
     }
+
+    #[test]
+    fn min_priority_gates_a_rule_without_falling_back_to_the_default() {
+        let policy = LoopPolicy {
+            default_cadence_days: Some(180),
+            strategy: LoopStrategy::Shortest,
+            rules: vec![
+                LoopRule::new(TagName::new("friend").unwrap(), 30, 0)
+                    .unwrap()
+                    .with_min_priority(Some(5)),
+                LoopRule::new(TagName::new("family").unwrap(), 90, 0).unwrap(),
+            ],
+        };
+
+        assert_eq!(policy.resolve_cadence(["friend"], 5), Some(30));
+        assert_eq!(policy.resolve_cadence(["friend"], 4), None);
+        assert_eq!(policy.resolve_cadence(["friend", "family"], 0), Some(90));
+        assert_eq!(policy.resolve_cadence(["coworker"], 0), Some(180));
+    }
 }
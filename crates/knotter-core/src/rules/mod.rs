@@ -2,10 +2,25 @@ pub mod cadence;
 pub mod dates;
 pub mod due;
 pub mod loops;
+pub mod pick;
+pub mod quiet_hours;
 pub mod validation;
 
-pub use cadence::{next_touchpoint_after_touch, schedule_next};
-pub use dates::{date_occurs_today, is_leap_year, local_today};
-pub use due::{compute_due_state, validate_soon_days, DueSelector, DueState, MAX_SOON_DAYS};
+pub use cadence::{
+    jitter_touchpoint, next_touchpoint_after_touch, rebalance_days, schedule_next, suggest_cadence,
+    CadenceSuggestion, ScheduledDay, MAX_JITTER_DAYS, SUGGESTED_CADENCE_BUCKETS,
+};
+pub use dates::{
+    date_in_lead_window, date_occurs_today, days_until_next_occurrence, is_leap_year, local_today,
+};
+pub use due::{
+    compute_due_state, due_bounds, validate_soon_days, DueBounds, DueSelector, DueState,
+    MAX_SOON_DAYS,
+};
 pub use loops::{LoopPolicy, LoopRule, LoopStrategy};
-pub use validation::{ensure_future_timestamp, ensure_future_timestamp_with_precision};
+pub use pick::{pick_weight, weighted_sample, PickRng, MAX_PICK_WEIGHT_DAYS};
+pub use quiet_hours::QuietHours;
+pub use validation::{
+    ensure_future_timestamp, ensure_future_timestamp_with_precision,
+    ensure_sane_interaction_timestamp, MIN_INTERACTION_TIMESTAMP,
+};
@@ -0,0 +1,113 @@
+use uuid::Uuid;
+
+const SECONDS_PER_DAY: i64 = 86_400;
+/// Staleness stops adding weight after a year, so one ancient contact does not
+/// crowd out everyone else.
+pub const MAX_PICK_WEIGHT_DAYS: i64 = 365;
+
+/// Small seeded generator (SplitMix64); picks are reproducible when the seed is.
+#[derive(Debug, Clone)]
+pub struct PickRng {
+    state: u64,
+}
+
+impl PickRng {
+    pub fn seeded(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    /// Seeds from the OS random source (via a v4 UUID).
+    pub fn from_entropy() -> Self {
+        Self::seeded(Uuid::new_v4().as_u64_pair().0)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform in `(0, 1]`.
+    fn next_unit(&mut self) -> f64 {
+        ((self.next_u64() >> 11) + 1) as f64 / (1u64 << 53) as f64
+    }
+}
+
+/// Weight for the random picker: one plus the days since the last interaction
+/// (capped at `MAX_PICK_WEIGHT_DAYS`); never-contacted contacts get the cap.
+pub fn pick_weight(last_interaction_at: Option<i64>, now_utc: i64) -> f64 {
+    let days = match last_interaction_at {
+        Some(ts) => (now_utc - ts).max(0) / SECONDS_PER_DAY,
+        None => MAX_PICK_WEIGHT_DAYS,
+    };
+    1.0 + days.min(MAX_PICK_WEIGHT_DAYS) as f64
+}
+
+/// Draws up to `count` items without replacement, each with probability
+/// proportional to its weight (Efraimidis–Spirakis). Non-positive weights are
+/// never picked.
+pub fn weighted_sample<T>(items: Vec<(T, f64)>, count: usize, rng: &mut PickRng) -> Vec<T> {
+    let mut keyed: Vec<(f64, T)> = items
+        .into_iter()
+        .filter(|(_, weight)| *weight > 0.0)
+        .map(|(item, weight)| (rng.next_unit().ln() / weight, item))
+        .collect();
+    keyed.sort_by(|a, b| b.0.total_cmp(&a.0));
+    keyed.truncate(count);
+    keyed.into_iter().map(|(_, item)| item).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{pick_weight, weighted_sample, PickRng, MAX_PICK_WEIGHT_DAYS, SECONDS_PER_DAY};
+
+    const NOW: i64 = 1_700_000_000;
+
+    #[test]
+    fn weight_grows_with_staleness_and_caps() {
+        assert_eq!(pick_weight(Some(NOW), NOW), 1.0);
+        assert_eq!(pick_weight(Some(NOW - 10 * SECONDS_PER_DAY), NOW), 11.0);
+        assert_eq!(
+            pick_weight(Some(NOW - 5_000 * SECONDS_PER_DAY), NOW),
+            pick_weight(None, NOW)
+        );
+        assert_eq!(pick_weight(None, NOW), 1.0 + MAX_PICK_WEIGHT_DAYS as f64);
+        assert_eq!(pick_weight(Some(NOW + SECONDS_PER_DAY), NOW), 1.0);
+    }
+
+    #[test]
+    fn same_seed_gives_same_picks() {
+        let items = || (0..20).map(|i| (i, 1.0 + i as f64)).collect::<Vec<_>>();
+        let first = weighted_sample(items(), 5, &mut PickRng::seeded(42));
+        let second = weighted_sample(items(), 5, &mut PickRng::seeded(42));
+        assert_eq!(first, second);
+        assert_eq!(first.len(), 5);
+        let mut unique = first.clone();
+        unique.sort_unstable();
+        unique.dedup();
+        assert_eq!(unique.len(), 5);
+    }
+
+    #[test]
+    fn heavier_items_win_more_often() {
+        let mut rng = PickRng::seeded(7);
+        let mut stale_wins = 0;
+        for _ in 0..1_000 {
+            let picked = weighted_sample(vec![("fresh", 1.0), ("stale", 100.0)], 1, &mut rng);
+            if picked == ["stale"] {
+                stale_wins += 1;
+            }
+        }
+        assert!(stale_wins > 950, "stale won {stale_wins} times");
+    }
+
+    #[test]
+    fn zero_weight_and_short_pools() {
+        let mut rng = PickRng::seeded(1);
+        let picked = weighted_sample(vec![("a", 0.0), ("b", 2.0)], 5, &mut rng);
+        assert_eq!(picked, ["b"]);
+        assert!(weighted_sample(vec![("a", 1.0)], 0, &mut rng).is_empty());
+    }
+}
@@ -0,0 +1,68 @@
+use chrono::NaiveTime;
+
+/// A daily local-time window in which notifications are held back. The window
+/// includes `start` and excludes `end`; when `end` is earlier than `start` it
+/// runs past midnight.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct QuietHours {
+    pub start: NaiveTime,
+    pub end: NaiveTime,
+}
+
+impl QuietHours {
+    pub fn contains(&self, time: NaiveTime) -> bool {
+        if self.start <= self.end {
+            self.start <= time && time < self.end
+        } else {
+            time >= self.start || time < self.end
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::QuietHours;
+    use chrono::NaiveTime;
+
+    fn at(hour: u32, minute: u32) -> NaiveTime {
+        NaiveTime::from_hms_opt(hour, minute, 0).expect("time")
+    }
+
+    #[test]
+    fn window_within_a_day() {
+        let quiet = QuietHours {
+            start: at(12, 0),
+            end: at(14, 0),
+        };
+        assert!(!quiet.contains(at(11, 59)));
+        assert!(quiet.contains(at(12, 0)));
+        assert!(quiet.contains(at(13, 59)));
+        assert!(!quiet.contains(at(14, 0)));
+    }
+
+    #[test]
+    fn window_spanning_midnight() {
+        let quiet = QuietHours {
+            start: at(22, 0),
+            end: at(8, 0),
+        };
+        assert!(!quiet.contains(at(21, 59)));
+        assert!(quiet.contains(at(22, 0)));
+        assert!(quiet.contains(at(23, 59)));
+        assert!(quiet.contains(at(0, 0)));
+        assert!(quiet.contains(at(7, 59)));
+        assert!(!quiet.contains(at(8, 0)));
+        assert!(!quiet.contains(at(12, 0)));
+    }
+
+    #[test]
+    fn window_ending_at_midnight() {
+        let quiet = QuietHours {
+            start: at(20, 0),
+            end: at(0, 0),
+        };
+        assert!(quiet.contains(at(23, 30)));
+        assert!(!quiet.contains(at(0, 0)));
+        assert!(!quiet.contains(at(19, 59)));
+    }
+}
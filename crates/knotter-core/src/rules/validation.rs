@@ -1,15 +1,42 @@
 use crate::error::CoreError;
-use crate::time::TimePrecision;
-use chrono::{DateTime, Local, TimeZone, Utc};
+use crate::time::{Clock, TimePrecision};
+use chrono::{TimeZone, Utc};
+
+/// 1990-01-01T00:00:00Z; anything earlier is a broken clock, not history.
+pub const MIN_INTERACTION_TIMESTAMP: i64 = 631_152_000;
+
+/// Rejects interaction times from a skewed clock: before 1990, or more than
+/// `max_future_skew_days` after `now_utc`.
+pub fn ensure_sane_interaction_timestamp(
+    now_utc: i64,
+    timestamp: i64,
+    max_future_skew_days: i64,
+) -> Result<i64, CoreError> {
+    if timestamp < MIN_INTERACTION_TIMESTAMP {
+        return Err(CoreError::InteractionTooOld);
+    }
+    if timestamp > now_utc.saturating_add(max_future_skew_days.saturating_mul(86_400)) {
+        return Err(CoreError::InteractionTooFarInFuture {
+            max_days: max_future_skew_days,
+        });
+    }
+    Ok(timestamp)
+}
 
 pub fn ensure_future_timestamp(now_utc: i64, timestamp: i64) -> Result<i64, CoreError> {
-    ensure_future_timestamp_with_precision(now_utc, timestamp, TimePrecision::Second)
+    ensure_future_timestamp_with_precision(
+        now_utc,
+        timestamp,
+        TimePrecision::Second,
+        &Clock::default(),
+    )
 }
 
 pub fn ensure_future_timestamp_with_precision(
     now_utc: i64,
     timestamp: i64,
     precision: TimePrecision,
+    clock: &Clock,
 ) -> Result<i64, CoreError> {
     match precision {
         TimePrecision::Second => {
@@ -31,39 +58,63 @@ pub fn ensure_future_timestamp_with_precision(
             }
         }
         TimePrecision::Date => {
-            let now_date = local_date(now_utc);
-            let timestamp_date = local_date(timestamp);
+            let now_date = clock.local_naive(now_utc).date();
+            let timestamp_date = clock.local_naive(timestamp).date();
             if timestamp_date < now_date {
                 return Err(CoreError::TimestampInPast);
             }
-            Ok(end_of_day_utc(timestamp_date))
+            Ok(end_of_day_utc(timestamp_date, clock))
         }
     }
 }
 
-fn local_date(timestamp: i64) -> chrono::NaiveDate {
-    DateTime::<Utc>::from_timestamp(timestamp, 0)
-        .unwrap_or_else(|| DateTime::<Utc>::from_timestamp(0, 0).unwrap())
-        .with_timezone(&Local)
-        .date_naive()
-}
-
-fn end_of_day_utc(date: chrono::NaiveDate) -> i64 {
+fn end_of_day_utc(date: chrono::NaiveDate, clock: &Clock) -> i64 {
     let naive = date
         .and_hms_opt(23, 59, 59)
         .unwrap_or_else(|| date.and_hms_opt(23, 59, 0).unwrap());
-    if let Some(local_dt) = Local.from_local_datetime(&naive).single() {
-        return local_dt.with_timezone(&Utc).timestamp();
-    }
-    Utc.from_utc_datetime(&naive).timestamp()
+    clock
+        .local_to_utc_timestamp(naive)
+        .unwrap_or_else(|_| Utc.from_utc_datetime(&naive).timestamp())
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{ensure_future_timestamp, ensure_future_timestamp_with_precision};
-    use crate::time::TimePrecision;
+    use super::{
+        ensure_future_timestamp, ensure_future_timestamp_with_precision,
+        ensure_sane_interaction_timestamp, MIN_INTERACTION_TIMESTAMP,
+    };
+    use crate::error::CoreError;
+    use crate::time::{Clock, TimePrecision};
     use chrono::{Local, TimeZone, Utc};
 
+    #[test]
+    fn sane_interaction_timestamp_bounds() {
+        let now = 1_700_000_000;
+        assert_eq!(
+            Utc.timestamp_opt(MIN_INTERACTION_TIMESTAMP, 0)
+                .unwrap()
+                .to_rfc3339(),
+            "1990-01-01T00:00:00+00:00"
+        );
+        assert_eq!(
+            ensure_sane_interaction_timestamp(now, MIN_INTERACTION_TIMESTAMP, 2),
+            Ok(MIN_INTERACTION_TIMESTAMP)
+        );
+        assert_eq!(
+            ensure_sane_interaction_timestamp(now, 0, 2),
+            Err(CoreError::InteractionTooOld)
+        );
+        assert_eq!(
+            ensure_sane_interaction_timestamp(now, now + 2 * 86_400, 2),
+            Ok(now + 2 * 86_400)
+        );
+        assert_eq!(
+            ensure_sane_interaction_timestamp(now, now + 2 * 86_400 + 1, 2),
+            Err(CoreError::InteractionTooFarInFuture { max_days: 2 })
+        );
+        assert!(ensure_sane_interaction_timestamp(now, now + 1, 0).is_err());
+    }
+
     #[test]
     fn ensure_future_timestamp_rejects_past() {
         let now = 1_700_000_000;
@@ -82,9 +133,13 @@ mod tests {
     fn ensure_future_timestamp_with_precision_allows_same_minute() {
         let now = 1_700_000_045;
         let timestamp = 1_700_000_040;
-        let adjusted =
-            ensure_future_timestamp_with_precision(now, timestamp, TimePrecision::Minute)
-                .expect("minute precision");
+        let adjusted = ensure_future_timestamp_with_precision(
+            now,
+            timestamp,
+            TimePrecision::Minute,
+            &Clock::default(),
+        )
+        .expect("minute precision");
         assert_eq!(adjusted, now);
     }
 
@@ -92,9 +147,13 @@ mod tests {
     fn ensure_future_timestamp_with_precision_rejects_previous_minute() {
         let now = 1_700_000_065;
         let timestamp = 1_700_000_000;
-        assert!(
-            ensure_future_timestamp_with_precision(now, timestamp, TimePrecision::Minute).is_err()
-        );
+        assert!(ensure_future_timestamp_with_precision(
+            now,
+            timestamp,
+            TimePrecision::Minute,
+            &Clock::default()
+        )
+        .is_err());
     }
 
     #[test]
@@ -103,8 +162,13 @@ mod tests {
         let ts_local = Local.with_ymd_and_hms(2030, 1, 15, 0, 0, 0).unwrap();
         let now = now_local.with_timezone(&Utc).timestamp();
         let timestamp = ts_local.with_timezone(&Utc).timestamp();
-        let adjusted = ensure_future_timestamp_with_precision(now, timestamp, TimePrecision::Date)
-            .expect("date precision");
+        let adjusted = ensure_future_timestamp_with_precision(
+            now,
+            timestamp,
+            TimePrecision::Date,
+            &Clock::default(),
+        )
+        .expect("date precision");
         let expected = Local
             .with_ymd_and_hms(2030, 1, 15, 23, 59, 59)
             .unwrap()
@@ -119,8 +183,12 @@ mod tests {
         let ts_local = Local.with_ymd_and_hms(2030, 1, 14, 23, 59, 59).unwrap();
         let now = now_local.with_timezone(&Utc).timestamp();
         let timestamp = ts_local.with_timezone(&Utc).timestamp();
-        assert!(
-            ensure_future_timestamp_with_precision(now, timestamp, TimePrecision::Date).is_err()
-        );
+        assert!(ensure_future_timestamp_with_precision(
+            now,
+            timestamp,
+            TimePrecision::Date,
+            &Clock::default()
+        )
+        .is_err());
     }
 }
@@ -0,0 +1,239 @@
+use std::collections::BTreeMap;
+use thiserror::Error;
+
+/// A note template with `{name}` placeholders. `{{` and `}}` stand for literal
+/// braces.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NoteTemplate {
+    source: String,
+    parts: Vec<Part>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Part {
+    Text(String),
+    Variable(String),
+}
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum TemplateError {
+    #[error("unclosed '{{' (write '{{{{' for a literal brace)")]
+    UnclosedPlaceholder,
+    #[error("unmatched '}}' (write '}}}}' for a literal brace)")]
+    UnmatchedClosingBrace,
+    #[error("invalid variable name {0:?} (use letters, digits, '_' or '-')")]
+    InvalidVariableName(String),
+    #[error("missing variable(s): {}", .0.join(", "))]
+    MissingVariables(Vec<String>),
+    #[error("unknown variable(s): {} (expected: {})", .unknown.join(", "), expected_list(.expected))]
+    UnknownVariables {
+        unknown: Vec<String>,
+        expected: Vec<String>,
+    },
+}
+
+fn expected_list(expected: &[String]) -> String {
+    if expected.is_empty() {
+        "none".to_string()
+    } else {
+        expected.join(", ")
+    }
+}
+
+impl NoteTemplate {
+    pub fn parse(source: &str) -> Result<Self, TemplateError> {
+        let mut parts = Vec::new();
+        let mut text = String::new();
+        let mut chars = source.chars().peekable();
+        while let Some(ch) = chars.next() {
+            match ch {
+                '{' if chars.peek() == Some(&'{') => {
+                    chars.next();
+                    text.push('{');
+                }
+                '}' if chars.peek() == Some(&'}') => {
+                    chars.next();
+                    text.push('}');
+                }
+                '}' => return Err(TemplateError::UnmatchedClosingBrace),
+                '{' => {
+                    let mut name = String::new();
+                    loop {
+                        match chars.next() {
+                            Some('}') => break,
+                            Some(ch) => name.push(ch),
+                            None => return Err(TemplateError::UnclosedPlaceholder),
+                        }
+                    }
+                    let name = name.trim();
+                    if !is_variable_name(name) {
+                        return Err(TemplateError::InvalidVariableName(name.to_string()));
+                    }
+                    if !text.is_empty() {
+                        parts.push(Part::Text(std::mem::take(&mut text)));
+                    }
+                    parts.push(Part::Variable(name.to_string()));
+                }
+                _ => text.push(ch),
+            }
+        }
+        if !text.is_empty() {
+            parts.push(Part::Text(text));
+        }
+        Ok(Self {
+            source: source.to_string(),
+            parts,
+        })
+    }
+
+    /// The template as written, braces and all.
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    /// Variable names in order of first use.
+    pub fn variables(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = Vec::new();
+        for part in &self.parts {
+            if let Part::Variable(name) = part {
+                if !names.contains(&name.as_str()) {
+                    names.push(name);
+                }
+            }
+        }
+        names
+    }
+
+    /// Fills every placeholder from `vars`. Every variable the template uses
+    /// must be given, and every given variable must be used, so a misspelled
+    /// name is caught instead of silently dropped.
+    pub fn render(&self, vars: &BTreeMap<String, String>) -> Result<String, TemplateError> {
+        let expected = self.variables();
+        let unknown: Vec<String> = vars
+            .keys()
+            .filter(|name| !expected.contains(&name.as_str()))
+            .cloned()
+            .collect();
+        if !unknown.is_empty() {
+            return Err(TemplateError::UnknownVariables {
+                unknown,
+                expected: expected.iter().map(|name| name.to_string()).collect(),
+            });
+        }
+        let missing: Vec<String> = expected
+            .iter()
+            .filter(|name| !vars.contains_key(**name))
+            .map(|name| name.to_string())
+            .collect();
+        if !missing.is_empty() {
+            return Err(TemplateError::MissingVariables(missing));
+        }
+
+        let mut out = String::new();
+        for part in &self.parts {
+            match part {
+                Part::Text(text) => out.push_str(text),
+                Part::Variable(name) => out.push_str(&vars[name]),
+            }
+        }
+        Ok(out)
+    }
+}
+
+/// Template and variable names: letters, digits, `_`, and `-`.
+pub fn is_variable_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|ch| ch.is_alphanumeric() || ch == '_' || ch == '-')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vars(pairs: &[(&str, &str)]) -> BTreeMap<String, String> {
+        pairs
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn renders_variables_in_place() {
+        let template = NoteTemplate::parse("Met {who} for coffee at {place}; {who} paid").unwrap();
+        assert_eq!(template.variables(), vec!["who", "place"]);
+        assert_eq!(
+            template
+                .render(&vars(&[("place", "Blue Bottle"), ("who", "Ada")]))
+                .unwrap(),
+            "Met Ada for coffee at Blue Bottle; Ada paid"
+        );
+        assert_eq!(
+            NoteTemplate::parse("{ place }")
+                .unwrap()
+                .render(&vars(&[("place", "home")]))
+                .unwrap(),
+            "home"
+        );
+    }
+
+    #[test]
+    fn doubled_braces_are_literal() {
+        let template = NoteTemplate::parse("{{not a var}} and {{{name}}}").unwrap();
+        assert_eq!(template.variables(), vec!["name"]);
+        assert_eq!(
+            template.render(&vars(&[("name", "x")])).unwrap(),
+            "{not a var} and {x}"
+        );
+        // Values are inserted as-is, braces included.
+        let template = NoteTemplate::parse("{a}").unwrap();
+        assert_eq!(template.render(&vars(&[("a", "{b}")])).unwrap(), "{b}");
+    }
+
+    #[test]
+    fn malformed_templates_are_rejected() {
+        assert_eq!(
+            NoteTemplate::parse("at {place"),
+            Err(TemplateError::UnclosedPlaceholder)
+        );
+        assert_eq!(
+            NoteTemplate::parse("smile :}"),
+            Err(TemplateError::UnmatchedClosingBrace)
+        );
+        assert_eq!(
+            NoteTemplate::parse("{}"),
+            Err(TemplateError::InvalidVariableName(String::new()))
+        );
+        assert_eq!(
+            NoteTemplate::parse("{two words}"),
+            Err(TemplateError::InvalidVariableName("two words".to_string()))
+        );
+    }
+
+    #[test]
+    fn missing_and_unknown_variables_are_listed() {
+        let template = NoteTemplate::parse("{a} {b} {c}").unwrap();
+        let err = template.render(&vars(&[("b", "1")])).unwrap_err();
+        assert_eq!(
+            err,
+            TemplateError::MissingVariables(vec!["a".to_string(), "c".to_string()])
+        );
+        assert_eq!(err.to_string(), "missing variable(s): a, c");
+
+        let err = template
+            .render(&vars(&[("a", "1"), ("b", "2"), ("c", "3"), ("plce", "x")]))
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "unknown variable(s): plce (expected: a, b, c)"
+        );
+
+        let plain = NoteTemplate::parse("no variables").unwrap();
+        assert_eq!(
+            plain.render(&vars(&[("x", "1")])).unwrap_err().to_string(),
+            "unknown variable(s): x (expected: none)"
+        );
+        assert_eq!(plain.render(&BTreeMap::new()).unwrap(), "no variables");
+    }
+}
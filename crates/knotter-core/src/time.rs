@@ -1,9 +1,19 @@
 use chrono::{
-    DateTime, Datelike, FixedOffset, Local, NaiveDate, NaiveDateTime, NaiveTime, Offset, TimeZone,
-    Timelike, Utc,
+    DateTime, Datelike, Duration, FixedOffset, Local, NaiveDate, NaiveDateTime, NaiveTime, Offset,
+    TimeZone, Timelike, Utc,
 };
 use thiserror::Error;
 
+pub mod duration;
+pub mod relative;
+
+pub use chrono_tz::Tz;
+pub use duration::parse_duration;
+pub use relative::parse_relative_date;
+
+/// Environment variable that pins "today" like the CLI's `--today-is`.
+pub const TODAY_ENV: &str = "KNOTTER_TODAY";
+
 const DATETIME_FORMATS_MINUTES: [&str; 2] = ["%Y-%m-%d %H:%M", "%Y-%m-%dT%H:%M"];
 const DATETIME_FORMATS_SECONDS: [&str; 2] = ["%Y-%m-%d %H:%M:%S", "%Y-%m-%dT%H:%M:%S"];
 
@@ -28,12 +38,41 @@ pub enum TimeParseError {
     InvalidDateFormat,
     #[error("invalid date format: expected YYYY-MM-DD, YYYYMMDD, MM-DD, --MMDD, or --MM-DD")]
     InvalidDatePartsFormat,
+    #[error(
+        "invalid relative date '{0}': expected today, tomorrow, +N[d|w|m|y], in N days|weeks|months|years, or next-<weekday>"
+    )]
+    InvalidRelativeDate(String),
+    #[error(
+        "invalid duration '{0}': expected N[w|d|h|m|s] parts such as 9h or 1h30m, or ISO 8601 such as PT9H"
+    )]
+    InvalidDuration(String),
     #[error("invalid time format: expected HH:MM")]
     InvalidTimeFormat,
     #[error("ambiguous local time: {0}")]
     AmbiguousLocalTime(String),
 }
 
+pub fn parse_timezone(input: &str) -> Option<Tz> {
+    input.trim().parse().ok()
+}
+
+/// The pinned day from `flag`, else from [`TODAY_ENV`]; blank means unpinned.
+pub fn today_override(flag: Option<&str>) -> Result<Option<NaiveDate>, TimeParseError> {
+    let raw = match flag {
+        Some(value) => value.to_string(),
+        None => std::env::var(TODAY_ENV).unwrap_or_default(),
+    };
+    if raw.trim().is_empty() {
+        return Ok(None);
+    }
+    NaiveDate::parse_from_str(raw.trim(), "%Y-%m-%d")
+        .map(Some)
+        .map_err(|_| TimeParseError::InvalidDateFormat)
+}
+
+/// Current UTC time in seconds; debug builds honour `KNOTTER_TEST_NOW_UTC`
+/// when `KNOTTER_ALLOW_TEST_NOW_UTC=1`. Ignores any pinned day, see
+/// [`Clock::now_utc`].
 pub fn now_utc() -> i64 {
     if cfg!(debug_assertions) {
         if let Ok(allow) = std::env::var("KNOTTER_ALLOW_TEST_NOW_UTC") {
@@ -49,68 +88,215 @@ pub fn now_utc() -> i64 {
     Utc::now().timestamp()
 }
 
-pub fn local_offset() -> FixedOffset {
-    Local::now().offset().fix()
+/// The zone local dates are computed in (`None` is the system zone) and an
+/// optional pinned "today". Resolved once at startup from config and
+/// `--today-is`/[`TODAY_ENV`], then passed to everything that cuts, parses,
+/// or prints local days.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Clock {
+    zone: Option<Tz>,
+    today: Option<NaiveDate>,
 }
 
-pub fn parse_local_timestamp(input: &str) -> Result<i64, TimeParseError> {
-    parse_local_timestamp_with_precision(input).map(|(timestamp, _)| timestamp)
-}
+impl Clock {
+    pub fn new(zone: Option<Tz>, today: Option<NaiveDate>) -> Self {
+        Self { zone, today }
+    }
 
-pub fn parse_local_timestamp_with_precision(
-    input: &str,
-) -> Result<(i64, TimePrecision), TimeParseError> {
-    let trimmed = input.trim();
-    if trimmed.is_empty() {
-        return Err(TimeParseError::Empty);
+    /// Start of the pinned day in the zone, else [`now_utc`].
+    pub fn now_utc(&self) -> i64 {
+        match self.today {
+            Some(today) => self.local_start_of_day(today),
+            None => now_utc(),
+        }
     }
 
-    if let Ok(date) = NaiveDate::parse_from_str(trimmed, "%Y-%m-%d") {
-        let naive = date
-            .and_hms_opt(0, 0, 0)
-            .ok_or(TimeParseError::InvalidDate)?;
-        return Ok((local_to_utc_timestamp(naive)?, TimePrecision::Date));
+    /// Offset of the zone at [`Clock::now_utc`].
+    pub fn local_offset(&self) -> FixedOffset {
+        let now = DateTime::<Utc>::from_timestamp(self.now_utc(), 0).unwrap_or_else(Utc::now);
+        match self.zone {
+            Some(zone) => now.with_timezone(&zone).offset().fix(),
+            None => now.with_timezone(&Local).offset().fix(),
+        }
     }
 
-    for fmt in DATETIME_FORMATS_SECONDS {
-        if let Ok(dt) = NaiveDateTime::parse_from_str(trimmed, fmt) {
-            return Ok((local_to_utc_timestamp(dt)?, TimePrecision::Second));
+    /// Wall-clock time of `ts` in the zone.
+    pub fn local_naive(&self, ts: i64) -> NaiveDateTime {
+        let dt = DateTime::<Utc>::from_timestamp(ts, 0)
+            .unwrap_or_else(|| DateTime::<Utc>::from_timestamp(0, 0).unwrap());
+        match self.zone {
+            Some(zone) => dt.with_timezone(&zone).naive_local(),
+            None => dt.with_timezone(&Local).naive_local(),
         }
     }
 
-    for fmt in DATETIME_FORMATS_MINUTES {
-        if let Ok(dt) = NaiveDateTime::parse_from_str(trimmed, fmt) {
-            return Ok((local_to_utc_timestamp(dt)?, TimePrecision::Minute));
+    /// First instant of `date` in the zone; days starting inside a DST gap
+    /// begin at the first valid hour.
+    pub fn local_start_of_day(&self, date: NaiveDate) -> i64 {
+        let midnight = date.and_hms_opt(0, 0, 0).expect("midnight is valid");
+        (0..=3)
+            .find_map(|hours| self.local_to_utc_earliest(midnight + Duration::hours(hours)))
+            .unwrap_or_else(|| Utc.from_utc_datetime(&midnight).timestamp())
+    }
+
+    fn local_to_utc_earliest(&self, naive: NaiveDateTime) -> Option<i64> {
+        match self.zone {
+            Some(zone) => zone
+                .from_local_datetime(&naive)
+                .earliest()
+                .map(|dt| dt.timestamp()),
+            None => Local
+                .from_local_datetime(&naive)
+                .earliest()
+                .map(|dt| dt.timestamp()),
         }
     }
 
-    Err(TimeParseError::InvalidDateTime)
-}
+    pub(crate) fn local_to_utc_timestamp(
+        &self,
+        naive: NaiveDateTime,
+    ) -> Result<i64, TimeParseError> {
+        let single = match self.zone {
+            Some(zone) => zone
+                .from_local_datetime(&naive)
+                .single()
+                .map(|dt| dt.timestamp()),
+            None => Local
+                .from_local_datetime(&naive)
+                .single()
+                .map(|dt| dt.timestamp()),
+        };
+        single.ok_or_else(|| TimeParseError::AmbiguousLocalTime(naive.to_string()))
+    }
 
-pub fn parse_local_date_time(date: &str, time: Option<&str>) -> Result<i64, TimeParseError> {
-    parse_local_date_time_with_precision(date, time).map(|(timestamp, _)| timestamp)
-}
+    pub fn parse_local_timestamp(&self, input: &str) -> Result<i64, TimeParseError> {
+        self.parse_local_timestamp_with_precision(input)
+            .map(|(timestamp, _)| timestamp)
+    }
 
-pub fn parse_local_date_time_with_precision(
-    date: &str,
-    time: Option<&str>,
-) -> Result<(i64, TimePrecision), TimeParseError> {
-    let date = NaiveDate::parse_from_str(date.trim(), "%Y-%m-%d")
-        .map_err(|_| TimeParseError::InvalidDateFormat)?;
-    let (time, precision) = match time {
-        Some(raw) => (
-            NaiveTime::parse_from_str(raw.trim(), "%H:%M")
-                .map_err(|_| TimeParseError::InvalidTimeFormat)?,
-            TimePrecision::Minute,
-        ),
-        None => (
-            NaiveTime::from_hms_opt(0, 0, 0).ok_or(TimeParseError::InvalidDate)?,
-            TimePrecision::Date,
-        ),
-    };
+    pub fn parse_local_timestamp_with_precision(
+        &self,
+        input: &str,
+    ) -> Result<(i64, TimePrecision), TimeParseError> {
+        let trimmed = input.trim();
+        if trimmed.is_empty() {
+            return Err(TimeParseError::Empty);
+        }
+
+        if let Ok(date) = NaiveDate::parse_from_str(trimmed, "%Y-%m-%d") {
+            let naive = date
+                .and_hms_opt(0, 0, 0)
+                .ok_or(TimeParseError::InvalidDate)?;
+            return Ok((self.local_to_utc_timestamp(naive)?, TimePrecision::Date));
+        }
+
+        for fmt in DATETIME_FORMATS_SECONDS {
+            if let Ok(dt) = NaiveDateTime::parse_from_str(trimmed, fmt) {
+                return Ok((self.local_to_utc_timestamp(dt)?, TimePrecision::Second));
+            }
+        }
+
+        for fmt in DATETIME_FORMATS_MINUTES {
+            if let Ok(dt) = NaiveDateTime::parse_from_str(trimmed, fmt) {
+                return Ok((self.local_to_utc_timestamp(dt)?, TimePrecision::Minute));
+            }
+        }
+
+        Err(TimeParseError::InvalidDateTime)
+    }
+
+    pub fn parse_local_date_time(
+        &self,
+        date: &str,
+        time: Option<&str>,
+    ) -> Result<i64, TimeParseError> {
+        self.parse_local_date_time_with_precision(date, time)
+            .map(|(timestamp, _)| timestamp)
+    }
+
+    pub fn parse_local_date_time_with_precision(
+        &self,
+        date: &str,
+        time: Option<&str>,
+    ) -> Result<(i64, TimePrecision), TimeParseError> {
+        let date = NaiveDate::parse_from_str(date.trim(), "%Y-%m-%d")
+            .map_err(|_| TimeParseError::InvalidDateFormat)?;
+        let (time, precision) = match time {
+            Some(raw) => (
+                NaiveTime::parse_from_str(raw.trim(), "%H:%M")
+                    .map_err(|_| TimeParseError::InvalidTimeFormat)?,
+                TimePrecision::Minute,
+            ),
+            None => (
+                NaiveTime::from_hms_opt(0, 0, 0).ok_or(TimeParseError::InvalidDate)?,
+                TimePrecision::Date,
+            ),
+        };
+
+        let naive = date.and_time(time);
+        Ok((self.local_to_utc_timestamp(naive)?, precision))
+    }
+
+    /// Like [`Clock::parse_local_date_time_with_precision`], but `date` may
+    /// also be a relative expression (`+2w`, `tomorrow`, `next-monday`, ...)
+    /// resolved against the local date of `now_utc`. Returns the resolved date
+    /// alongside the timestamp so callers can echo what was understood.
+    pub fn parse_local_date_time_relative(
+        &self,
+        date: &str,
+        time: Option<&str>,
+        now_utc: i64,
+    ) -> Result<(i64, TimePrecision, NaiveDate), TimeParseError> {
+        if DateTime::<Utc>::from_timestamp(now_utc, 0).is_none() {
+            return Err(TimeParseError::InvalidDate);
+        }
+        let today = self.local_naive(now_utc).date();
+        let resolved = match parse_relative_date(date, today) {
+            Some(result) => result?,
+            None => NaiveDate::parse_from_str(date.trim(), "%Y-%m-%d")
+                .map_err(|_| TimeParseError::InvalidDateFormat)?,
+        };
+        let (timestamp, precision) = self
+            .parse_local_date_time_with_precision(&resolved.format("%Y-%m-%d").to_string(), time)?;
+        Ok((timestamp, precision, resolved))
+    }
 
-    let naive = date.and_time(time);
-    Ok((local_to_utc_timestamp(naive)?, precision))
+    pub fn format_timestamp_date(&self, ts: i64) -> String {
+        self.local_naive(ts).format("%Y-%m-%d").to_string()
+    }
+
+    pub fn format_timestamp_datetime(&self, ts: i64) -> String {
+        self.local_naive(ts).format("%Y-%m-%d %H:%M").to_string()
+    }
+
+    pub fn format_timestamp_time(&self, ts: i64) -> String {
+        self.local_naive(ts).format("%H:%M").to_string()
+    }
+
+    pub fn format_timestamp_date_or_datetime(&self, ts: i64) -> String {
+        let dt = self.local_naive(ts);
+        if dt.hour() == 0 && dt.minute() == 0 && dt.second() == 0 {
+            dt.format("%Y-%m-%d").to_string()
+        } else {
+            dt.format("%Y-%m-%d %H:%M").to_string()
+        }
+    }
+
+    /// Coarse age of a past timestamp for narrow columns: "today", "3d ago",
+    /// "3w ago", "5mo ago", "2y ago". Future timestamps fall back to the date.
+    pub fn format_relative_past(&self, ts: i64, now_utc: i64) -> String {
+        if ts > now_utc {
+            return self.format_timestamp_date(ts);
+        }
+        let days = (now_utc - ts) / 86_400;
+        match days {
+            0 => "today".to_string(),
+            1..=13 => format!("{days}d ago"),
+            14..=59 => format!("{}w ago", days / 7),
+            60..=364 => format!("{}mo ago", days / 30),
+            _ => format!("{}y ago", days / 365),
+        }
+    }
 }
 
 pub fn parse_date_parts(input: &str) -> Result<(u8, u8, Option<i32>), TimeParseError> {
@@ -190,66 +376,25 @@ pub fn format_date_parts(month: u8, day: u8, year: Option<i32>) -> String {
     }
 }
 
-pub fn format_timestamp_date(ts: i64) -> String {
-    let dt = DateTime::<Utc>::from_timestamp(ts, 0)
-        .unwrap_or_else(|| DateTime::<Utc>::from_timestamp(0, 0).unwrap())
-        .with_timezone(&Local);
-    dt.format("%Y-%m-%d").to_string()
-}
-
-pub fn format_timestamp_datetime(ts: i64) -> String {
-    let dt = DateTime::<Utc>::from_timestamp(ts, 0)
-        .unwrap_or_else(|| DateTime::<Utc>::from_timestamp(0, 0).unwrap())
-        .with_timezone(&Local);
-    dt.format("%Y-%m-%d %H:%M").to_string()
-}
-
-pub fn format_timestamp_time(ts: i64) -> String {
-    let dt = DateTime::<Utc>::from_timestamp(ts, 0)
-        .unwrap_or_else(|| DateTime::<Utc>::from_timestamp(0, 0).unwrap())
-        .with_timezone(&Local);
-    dt.format("%H:%M").to_string()
-}
-
-pub fn format_timestamp_date_or_datetime(ts: i64) -> String {
-    let dt = DateTime::<Utc>::from_timestamp(ts, 0)
-        .unwrap_or_else(|| DateTime::<Utc>::from_timestamp(0, 0).unwrap())
-        .with_timezone(&Local);
-    if dt.hour() == 0 && dt.minute() == 0 && dt.second() == 0 {
-        dt.format("%Y-%m-%d").to_string()
-    } else {
-        dt.format("%Y-%m-%d %H:%M").to_string()
-    }
-}
-
-fn local_to_utc_timestamp(naive: NaiveDateTime) -> Result<i64, TimeParseError> {
-    let local = Local
-        .from_local_datetime(&naive)
-        .single()
-        .ok_or_else(|| TimeParseError::AmbiguousLocalTime(naive.to_string()))?;
-    Ok(local.with_timezone(&Utc).timestamp())
-}
-
 #[cfg(test)]
 mod tests {
-    use super::{
-        format_date_parts, format_timestamp_date, format_timestamp_date_or_datetime,
-        format_timestamp_datetime, format_timestamp_time, parse_date_parts, parse_local_date_time,
-        parse_local_date_time_with_precision, parse_local_timestamp,
-        parse_local_timestamp_with_precision, TimeParseError, TimePrecision,
-    };
-    use chrono::{Local, TimeZone, Utc};
+    use super::{format_date_parts, parse_date_parts, Clock, TimeParseError, TimePrecision, Tz};
+    use chrono::{Local, NaiveDate, TimeZone, Utc};
 
     #[test]
     fn parse_local_timestamp_accepts_date_only() {
-        let ts = parse_local_timestamp("2030-01-15").unwrap();
+        let ts = Clock::default()
+            .parse_local_timestamp("2030-01-15")
+            .unwrap();
         let local = Utc.timestamp_opt(ts, 0).unwrap().with_timezone(&Local);
         assert_eq!(local.format("%Y-%m-%d").to_string(), "2030-01-15");
     }
 
     #[test]
     fn parse_local_timestamp_accepts_datetime() {
-        let ts = parse_local_timestamp("2030-01-15 13:45").unwrap();
+        let ts = Clock::default()
+            .parse_local_timestamp("2030-01-15 13:45")
+            .unwrap();
         let local = Utc.timestamp_opt(ts, 0).unwrap().with_timezone(&Local);
         assert_eq!(
             local.format("%Y-%m-%d %H:%M").to_string(),
@@ -259,13 +404,15 @@ mod tests {
 
     #[test]
     fn parse_local_timestamp_rejects_empty() {
-        let err = parse_local_timestamp("").unwrap_err();
+        let err = Clock::default().parse_local_timestamp("").unwrap_err();
         assert!(matches!(err, TimeParseError::Empty));
     }
 
     #[test]
     fn parse_local_date_time_accepts_date_and_time() {
-        let ts = parse_local_date_time("2030-01-15", Some("13:45")).unwrap();
+        let ts = Clock::default()
+            .parse_local_date_time("2030-01-15", Some("13:45"))
+            .unwrap();
         let local = Utc.timestamp_opt(ts, 0).unwrap().with_timezone(&Local);
         assert_eq!(
             local.format("%Y-%m-%d %H:%M").to_string(),
@@ -275,11 +422,15 @@ mod tests {
 
     #[test]
     fn parse_local_timestamp_infers_precision() {
-        let (date_ts, date_precision) = parse_local_timestamp_with_precision("2030-01-15").unwrap();
-        let (minute_ts, minute_precision) =
-            parse_local_timestamp_with_precision("2030-01-15 13:45").unwrap();
-        let (second_ts, second_precision) =
-            parse_local_timestamp_with_precision("2030-01-15 13:45:30").unwrap();
+        let (date_ts, date_precision) = Clock::default()
+            .parse_local_timestamp_with_precision("2030-01-15")
+            .unwrap();
+        let (minute_ts, minute_precision) = Clock::default()
+            .parse_local_timestamp_with_precision("2030-01-15 13:45")
+            .unwrap();
+        let (second_ts, second_precision) = Clock::default()
+            .parse_local_timestamp_with_precision("2030-01-15 13:45:30")
+            .unwrap();
 
         assert_eq!(date_precision, TimePrecision::Date);
         assert_eq!(minute_precision, TimePrecision::Minute);
@@ -290,11 +441,14 @@ mod tests {
 
     #[test]
     fn parse_local_date_time_infers_precision() {
-        let (_ts, precision) = parse_local_date_time_with_precision("2030-01-15", None).unwrap();
+        let (_ts, precision) = Clock::default()
+            .parse_local_date_time_with_precision("2030-01-15", None)
+            .unwrap();
         assert_eq!(precision, TimePrecision::Date);
 
-        let (_ts, precision) =
-            parse_local_date_time_with_precision("2030-01-15", Some("13:45")).unwrap();
+        let (_ts, precision) = Clock::default()
+            .parse_local_date_time_with_precision("2030-01-15", Some("13:45"))
+            .unwrap();
         assert_eq!(precision, TimePrecision::Minute);
     }
 
@@ -303,16 +457,19 @@ mod tests {
         let local = Local.with_ymd_and_hms(2030, 1, 15, 13, 45, 0).unwrap();
         let ts = local.with_timezone(&Utc).timestamp();
         assert_eq!(
-            format_timestamp_date(ts),
+            Clock::default().format_timestamp_date(ts),
             local.format("%Y-%m-%d").to_string()
         );
         assert_eq!(
-            format_timestamp_datetime(ts),
+            Clock::default().format_timestamp_datetime(ts),
             local.format("%Y-%m-%d %H:%M").to_string()
         );
-        assert_eq!(format_timestamp_time(ts), local.format("%H:%M").to_string());
         assert_eq!(
-            format_timestamp_date_or_datetime(ts),
+            Clock::default().format_timestamp_time(ts),
+            local.format("%H:%M").to_string()
+        );
+        assert_eq!(
+            Clock::default().format_timestamp_date_or_datetime(ts),
             local.format("%Y-%m-%d %H:%M").to_string()
         );
     }
@@ -331,4 +488,63 @@ mod tests {
         assert_eq!(format_date_parts(1, 5, Some(2030)), "2030-01-05");
         assert_eq!(format_date_parts(1, 5, None), "01-05");
     }
+
+    #[test]
+    fn format_relative_past_uses_coarse_units() {
+        let now = 1_700_000_000;
+        let day = 86_400;
+        assert_eq!(
+            Clock::default().format_relative_past(now - 60, now),
+            "today"
+        );
+        assert_eq!(
+            Clock::default().format_relative_past(now - 3 * day, now),
+            "3d ago"
+        );
+        assert_eq!(
+            Clock::default().format_relative_past(now - 21 * day, now),
+            "3w ago"
+        );
+        assert_eq!(
+            Clock::default().format_relative_past(now - 150 * day, now),
+            "5mo ago"
+        );
+        assert_eq!(
+            Clock::default().format_relative_past(now - 800 * day, now),
+            "2y ago"
+        );
+        assert_eq!(
+            Clock::default().format_relative_past(now + day, now),
+            Clock::default().format_timestamp_date(now + day)
+        );
+    }
+
+    #[test]
+    fn clocks_in_different_zones_disagree_on_today() {
+        let ts = Utc
+            .with_ymd_and_hms(2024, 1, 10, 15, 30, 0)
+            .unwrap()
+            .timestamp();
+        let tokyo = Clock::new(Some(Tz::Asia__Tokyo), None);
+        let utc = Clock::new(Some(Tz::UTC), None);
+        assert_eq!(tokyo.format_timestamp_date(ts), "2024-01-11");
+        assert_eq!(utc.format_timestamp_date(ts), "2024-01-10");
+        assert_eq!(
+            tokyo.parse_local_timestamp("2024-01-11").unwrap(),
+            utc.parse_local_timestamp("2024-01-10 15:00").unwrap()
+        );
+    }
+
+    #[test]
+    fn pinned_day_starts_at_local_midnight() {
+        let day = NaiveDate::from_ymd_opt(2024, 1, 11).unwrap();
+        let clock = Clock::new(Some(Tz::Asia__Tokyo), Some(day));
+        assert_eq!(
+            clock.now_utc(),
+            Utc.with_ymd_and_hms(2024, 1, 10, 15, 0, 0)
+                .unwrap()
+                .timestamp()
+        );
+        assert_eq!(clock.local_offset().local_minus_utc(), 9 * 3600);
+    }
 }
@@ -0,0 +1,126 @@
+use super::TimeParseError;
+
+const SECONDS_PER_MINUTE: i64 = 60;
+const SECONDS_PER_HOUR: i64 = 60 * SECONDS_PER_MINUTE;
+const SECONDS_PER_DAY: i64 = 24 * SECONDS_PER_HOUR;
+const SECONDS_PER_WEEK: i64 = 7 * SECONDS_PER_DAY;
+
+/// Longest duration accepted, in seconds (a year); anything bigger is almost
+/// certainly a typo.
+pub const MAX_DURATION_SECONDS: i64 = 366 * SECONDS_PER_DAY;
+
+/// Parses a non-negative duration into seconds.
+///
+/// Accepted forms (case-insensitive):
+/// - one or more `N<unit>` parts with units `w`, `d`, `h`, `m`, `s`, such as
+///   `9h`, `1d`, or `1h30m` (`m` is minutes here, unlike relative dates where
+///   it means months)
+/// - ISO 8601 durations such as `PT9H`, `P1D`, `P1DT12H`, or `P2W`
+pub fn parse_duration(input: &str) -> Result<i64, TimeParseError> {
+    let normalized = input.trim().to_ascii_lowercase();
+    let invalid = || TimeParseError::InvalidDuration(input.trim().to_string());
+    if normalized.is_empty() {
+        return Err(invalid());
+    }
+    let seconds = match normalized.strip_prefix('p') {
+        Some(iso) => parse_iso(iso),
+        None => parse_parts(&normalized, unit_seconds),
+    }
+    .ok_or_else(invalid)?;
+    if seconds > MAX_DURATION_SECONDS {
+        return Err(invalid());
+    }
+    Ok(seconds)
+}
+
+/// Splits the leading run of ASCII digits off `raw`.
+pub(super) fn leading_amount(raw: &str) -> Option<(u32, &str)> {
+    let digits_end = raw
+        .find(|ch: char| !ch.is_ascii_digit())
+        .unwrap_or(raw.len());
+    let amount = raw[..digits_end].parse().ok()?;
+    Some((amount, &raw[digits_end..]))
+}
+
+fn unit_seconds(unit: &str) -> Option<i64> {
+    match unit {
+        "w" => Some(SECONDS_PER_WEEK),
+        "d" => Some(SECONDS_PER_DAY),
+        "h" => Some(SECONDS_PER_HOUR),
+        "m" => Some(SECONDS_PER_MINUTE),
+        "s" => Some(1),
+        _ => None,
+    }
+}
+
+/// Sums `N<unit>` parts; each part needs an amount and a single-letter unit.
+fn parse_parts(raw: &str, unit: fn(&str) -> Option<i64>) -> Option<i64> {
+    let mut rest = raw;
+    let mut total: i64 = 0;
+    while !rest.is_empty() {
+        let (amount, after) = leading_amount(rest)?;
+        let letter = after.get(..1)?;
+        total = total.checked_add(i64::from(amount).checked_mul(unit(letter)?)?)?;
+        rest = &after[1..];
+    }
+    Some(total)
+}
+
+fn parse_iso(raw: &str) -> Option<i64> {
+    let (date, time) = match raw.split_once('t') {
+        Some((date, time)) if !time.is_empty() => (date, Some(time)),
+        Some(_) => return None,
+        None => (raw, None),
+    };
+    if date.is_empty() && time.is_none() {
+        return None;
+    }
+    let date_seconds = parse_parts(date, |unit| match unit {
+        "w" => Some(SECONDS_PER_WEEK),
+        "d" => Some(SECONDS_PER_DAY),
+        _ => None,
+    })?;
+    let time_seconds = match time {
+        Some(time) => parse_parts(time, |unit| match unit {
+            "h" => Some(SECONDS_PER_HOUR),
+            "m" => Some(SECONDS_PER_MINUTE),
+            "s" => Some(1),
+            _ => None,
+        })?,
+        None => 0,
+    };
+    date_seconds.checked_add(time_seconds)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_friendly_and_iso_forms() {
+        assert_eq!(parse_duration("9h").unwrap(), 9 * 3_600);
+        assert_eq!(parse_duration("1d").unwrap(), 86_400);
+        assert_eq!(parse_duration("1H30m").unwrap(), 5_400);
+        assert_eq!(parse_duration("2w").unwrap(), 14 * 86_400);
+        assert_eq!(parse_duration("PT9H").unwrap(), 9 * 3_600);
+        assert_eq!(parse_duration("p1dt12h").unwrap(), 36 * 3_600);
+        assert_eq!(parse_duration("PT15M").unwrap(), 900);
+        assert_eq!(parse_duration("P2W").unwrap(), 14 * 86_400);
+        assert_eq!(parse_duration("0m").unwrap(), 0);
+    }
+
+    #[test]
+    fn rejects_malformed_durations() {
+        for input in [
+            "", "9", "h", "9x", "-9h", "1.5h", "P", "PT", "P9H", "PT1D", "9 h", "400d",
+        ] {
+            assert!(
+                matches!(
+                    parse_duration(input),
+                    Err(TimeParseError::InvalidDuration(_))
+                ),
+                "{input:?} should be rejected"
+            );
+        }
+    }
+}
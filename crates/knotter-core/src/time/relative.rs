@@ -0,0 +1,223 @@
+use super::duration::leading_amount;
+use super::TimeParseError;
+use chrono::{Datelike, Days, Months, NaiveDate, Weekday};
+
+/// Largest amount accepted in `+N<unit>` / `in N <unit>`; anything bigger is
+/// almost certainly a typo.
+const MAX_AMOUNT: u32 = 9999;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Unit {
+    Day,
+    Week,
+    Month,
+    Year,
+}
+
+/// Resolves a relative date expression against `today`.
+///
+/// Accepted forms (case-insensitive):
+/// - `today`, `tomorrow`
+/// - `+N<unit>` such as `+10d`, `+2w`, `+3m`, `+1y`
+/// - `in N <unit>` such as `in 2 weeks` or `in 1 month`
+/// - `next-<weekday>` / `next <weekday>` (`next-monday`, `next fri`), always
+///   strictly after `today`
+///
+/// Month and year steps clamp to the end of shorter months (`2025-01-31 +1m`
+/// is `2025-02-28`). Returns `None` when `input` does not look relative, so
+/// callers can fall back to absolute date parsing.
+pub fn parse_relative_date(
+    input: &str,
+    today: NaiveDate,
+) -> Option<Result<NaiveDate, TimeParseError>> {
+    let normalized = input.trim().to_ascii_lowercase();
+    let words: Vec<&str> = normalized.split_whitespace().collect();
+    let invalid = || {
+        Some(Err(TimeParseError::InvalidRelativeDate(
+            input.trim().to_string(),
+        )))
+    };
+
+    match words.as_slice() {
+        ["today"] => Some(Ok(today)),
+        ["tomorrow"] => Some(Ok(today + Days::new(1))),
+        [single] if single.starts_with('+') => {
+            let Some((amount, unit)) = split_amount_unit(&single[1..]) else {
+                return invalid();
+            };
+            apply(today, amount, unit).map_or_else(invalid, |date| Some(Ok(date)))
+        }
+        ["in", rest @ ..] => {
+            let Some((amount, unit)) = split_amount_unit(&rest.concat()) else {
+                return invalid();
+            };
+            apply(today, amount, unit).map_or_else(invalid, |date| Some(Ok(date)))
+        }
+        ["next", weekday] => next_weekday(today, weekday).map_or_else(invalid, |d| Some(Ok(d))),
+        [single] if single.starts_with("next-") => {
+            next_weekday(today, &single[5..]).map_or_else(invalid, |d| Some(Ok(d)))
+        }
+        _ => None,
+    }
+}
+
+fn split_amount_unit(raw: &str) -> Option<(u32, Unit)> {
+    let (amount, unit) = leading_amount(raw)?;
+    if amount > MAX_AMOUNT {
+        return None;
+    }
+    let unit = match unit {
+        "d" | "day" | "days" => Unit::Day,
+        "w" | "week" | "weeks" => Unit::Week,
+        "m" | "month" | "months" => Unit::Month,
+        "y" | "year" | "years" => Unit::Year,
+        _ => return None,
+    };
+    Some((amount, unit))
+}
+
+fn apply(today: NaiveDate, amount: u32, unit: Unit) -> Option<NaiveDate> {
+    match unit {
+        Unit::Day => today.checked_add_days(Days::new(u64::from(amount))),
+        Unit::Week => today.checked_add_days(Days::new(u64::from(amount) * 7)),
+        Unit::Month => today.checked_add_months(Months::new(amount)),
+        Unit::Year => today.checked_add_months(Months::new(amount.checked_mul(12)?)),
+    }
+}
+
+fn next_weekday(today: NaiveDate, raw: &str) -> Option<NaiveDate> {
+    let target = parse_weekday(raw)?;
+    let ahead = (7 + target.num_days_from_monday() - today.weekday().num_days_from_monday()) % 7;
+    let ahead = if ahead == 0 { 7 } else { ahead };
+    today.checked_add_days(Days::new(u64::from(ahead)))
+}
+
+fn parse_weekday(raw: &str) -> Option<Weekday> {
+    let weekday = match raw {
+        "monday" | "mon" => Weekday::Mon,
+        "tuesday" | "tue" | "tues" => Weekday::Tue,
+        "wednesday" | "wed" => Weekday::Wed,
+        "thursday" | "thu" | "thurs" => Weekday::Thu,
+        "friday" | "fri" => Weekday::Fri,
+        "saturday" | "sat" => Weekday::Sat,
+        "sunday" | "sun" => Weekday::Sun,
+        _ => return None,
+    };
+    Some(weekday)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_relative_date;
+    use crate::time::TimeParseError;
+    use chrono::NaiveDate;
+
+    fn date(value: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(value, "%Y-%m-%d").unwrap()
+    }
+
+    fn resolve(input: &str, today: &str) -> String {
+        parse_relative_date(input, date(today))
+            .expect("relative")
+            .expect("valid")
+            .format("%Y-%m-%d")
+            .to_string()
+    }
+
+    #[test]
+    fn keywords_resolve_against_today() {
+        assert_eq!(resolve("today", "2025-03-01"), "2025-03-01");
+        assert_eq!(resolve("tomorrow", "2025-03-01"), "2025-03-02");
+        assert_eq!(resolve("  Tomorrow ", "2025-12-31"), "2026-01-01");
+    }
+
+    #[test]
+    fn plus_offsets_cover_every_unit() {
+        assert_eq!(resolve("+10d", "2025-03-01"), "2025-03-11");
+        assert_eq!(resolve("+2w", "2025-03-01"), "2025-03-15");
+        assert_eq!(resolve("+3m", "2025-03-01"), "2025-06-01");
+        assert_eq!(resolve("+1y", "2025-03-01"), "2026-03-01");
+        assert_eq!(resolve("+0d", "2025-03-01"), "2025-03-01");
+        assert_eq!(resolve("+2W", "2025-03-01"), "2025-03-15");
+    }
+
+    #[test]
+    fn in_phrases_accept_long_and_short_units() {
+        assert_eq!(resolve("in 2 weeks", "2025-03-01"), "2025-03-15");
+        assert_eq!(resolve("in 1 week", "2025-03-01"), "2025-03-08");
+        assert_eq!(resolve("in 10 days", "2025-03-01"), "2025-03-11");
+        assert_eq!(resolve("in 1 month", "2025-03-01"), "2025-04-01");
+        assert_eq!(resolve("in 2 years", "2025-03-01"), "2027-03-01");
+        assert_eq!(resolve("in 3d", "2025-03-01"), "2025-03-04");
+        assert_eq!(resolve("IN  2   Weeks", "2025-03-01"), "2025-03-15");
+    }
+
+    #[test]
+    fn month_steps_clamp_to_month_end() {
+        assert_eq!(resolve("+1m", "2025-01-31"), "2025-02-28");
+        assert_eq!(resolve("+1m", "2024-01-31"), "2024-02-29");
+        assert_eq!(resolve("+1m", "2025-03-31"), "2025-04-30");
+        assert_eq!(resolve("+2m", "2025-12-31"), "2026-02-28");
+        assert_eq!(resolve("+12m", "2025-01-31"), "2026-01-31");
+        assert_eq!(resolve("+1y", "2024-02-29"), "2025-02-28");
+        assert_eq!(resolve("+4y", "2024-02-29"), "2028-02-29");
+        assert_eq!(resolve("+1d", "2025-02-28"), "2025-03-01");
+        assert_eq!(resolve("+1d", "2024-02-28"), "2024-02-29");
+    }
+
+    #[test]
+    fn day_steps_are_calendar_days_across_dst_transitions() {
+        // EU and US spring-forward / fall-back days: resolution is by calendar
+        // date, so the 23- or 25-hour day never shifts the result.
+        assert_eq!(resolve("+1d", "2025-03-29"), "2025-03-30");
+        assert_eq!(resolve("+1d", "2025-03-30"), "2025-03-31");
+        assert_eq!(resolve("+1w", "2025-03-26"), "2025-04-02");
+        assert_eq!(resolve("+1d", "2025-10-25"), "2025-10-26");
+        assert_eq!(resolve("+1d", "2025-10-26"), "2025-10-27");
+        assert_eq!(resolve("+1d", "2025-03-09"), "2025-03-10");
+        assert_eq!(resolve("+1d", "2025-11-02"), "2025-11-03");
+        assert_eq!(resolve("tomorrow", "2025-03-08"), "2025-03-09");
+    }
+
+    #[test]
+    fn next_weekday_is_strictly_after_today() {
+        // 2025-03-03 is a Monday.
+        assert_eq!(resolve("next-monday", "2025-03-03"), "2025-03-10");
+        assert_eq!(resolve("next-tuesday", "2025-03-03"), "2025-03-04");
+        assert_eq!(resolve("next sunday", "2025-03-03"), "2025-03-09");
+        assert_eq!(resolve("next-fri", "2025-03-08"), "2025-03-14");
+        assert_eq!(resolve("next-monday", "2025-12-29"), "2026-01-05");
+        assert_eq!(resolve("next-Wed", "2024-02-28"), "2024-03-06");
+    }
+
+    #[test]
+    fn absolute_dates_are_not_relative() {
+        assert!(parse_relative_date("2025-07-01", date("2025-03-01")).is_none());
+        assert!(parse_relative_date("", date("2025-03-01")).is_none());
+        assert!(parse_relative_date("someday", date("2025-03-01")).is_none());
+    }
+
+    #[test]
+    fn malformed_relative_expressions_are_rejected() {
+        for input in [
+            "+",
+            "+2",
+            "+2x",
+            "+-2d",
+            "+d",
+            "in",
+            "in two weeks",
+            "in 2 fortnights",
+            "next-moon",
+            "+99999d",
+        ] {
+            let result = parse_relative_date(input, date("2025-03-01"));
+            match result {
+                Some(Err(TimeParseError::InvalidRelativeDate(raw))) => {
+                    assert_eq!(raw, input.trim())
+                }
+                other => panic!("{input}: unexpected {other:?}"),
+            }
+        }
+    }
+}
@@ -0,0 +1,30 @@
+-- 012_interaction_direction.sql
+-- Track inbound/outbound direction for imported message interactions.
+
+ALTER TABLE interactions ADD COLUMN direction TEXT;   -- "inbound"|"outbound" (NULL for manual notes)
+
+UPDATE interactions
+   SET direction = (
+       SELECT em.direction
+         FROM email_messages em
+        WHERE em.contact_id = interactions.contact_id
+          AND em.occurred_at = interactions.occurred_at
+        LIMIT 1
+   )
+ WHERE kind = 'email'
+   AND direction IS NULL;
+
+UPDATE interactions
+   SET direction = (
+       SELECT tm.direction
+         FROM telegram_messages tm
+        WHERE tm.contact_id = interactions.contact_id
+          AND tm.occurred_at = interactions.occurred_at
+        LIMIT 1
+   )
+ WHERE kind = 'telegram'
+   AND direction IS NULL;
+
+CREATE INDEX IF NOT EXISTS idx_interactions_directed_contact_occurred
+  ON interactions(contact_id, occurred_at DESC)
+ WHERE direction IS NOT NULL;
@@ -0,0 +1,16 @@
+-- 013_contact_archive_reason.sql
+-- Optional free-text reason recorded when a contact is archived.
+
+ALTER TABLE contacts ADD COLUMN archive_reason TEXT;   -- NULL when active or archived without a reason
+
+-- Contacts staged by imports are archived and listed as an open merge candidate.
+UPDATE contacts
+   SET archive_reason = 'staged for merge'
+ WHERE archived_at IS NOT NULL
+   AND archive_reason IS NULL
+   AND EXISTS (
+       SELECT 1
+         FROM contact_merge_candidates mc
+        WHERE mc.status = 'open'
+          AND mc.contact_a_id = contacts.id
+   );
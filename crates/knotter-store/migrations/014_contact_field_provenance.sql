@@ -0,0 +1,12 @@
+-- 014_contact_field_provenance.sql
+-- Last writer of each contact field: "manual" for CLI/TUI edits, otherwise the import source.
+
+CREATE TABLE IF NOT EXISTS contact_field_provenance (
+  contact_id TEXT NOT NULL,
+  field TEXT NOT NULL,                          -- display_name, email, phone, ...
+  source TEXT NOT NULL,
+  updated_at INTEGER NOT NULL,
+
+  PRIMARY KEY (contact_id, field),
+  FOREIGN KEY(contact_id) REFERENCES contacts(id) ON DELETE CASCADE
+);
@@ -0,0 +1,19 @@
+-- 015_contact_trash.sql
+-- Soft delete: trashed contacts stay in the table but are hidden until restored or purged.
+
+ALTER TABLE contacts ADD COLUMN deleted_at INTEGER;    -- unix seconds UTC; NULL unless trashed
+
+CREATE INDEX IF NOT EXISTS idx_contacts_deleted_at
+  ON contacts(deleted_at);
+
+-- Addresses of trashed contacts, set aside so other contacts can use them meanwhile.
+CREATE TABLE IF NOT EXISTS trashed_contact_emails (
+  contact_id TEXT NOT NULL,
+  email TEXT NOT NULL,
+  is_primary INTEGER NOT NULL DEFAULT 0,
+  created_at INTEGER NOT NULL,
+  source TEXT,
+
+  PRIMARY KEY (contact_id, email),
+  FOREIGN KEY(contact_id) REFERENCES contacts(id) ON DELETE CASCADE
+);
@@ -0,0 +1,13 @@
+-- 016_contact_aliases.sql
+-- Alternate names (nicknames) used for text filters and name-based import matching.
+
+CREATE TABLE IF NOT EXISTS contact_aliases (
+  contact_id TEXT NOT NULL,
+  alias TEXT NOT NULL COLLATE NOCASE,
+  created_at INTEGER NOT NULL,
+
+  PRIMARY KEY (contact_id, alias),
+  FOREIGN KEY(contact_id) REFERENCES contacts(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_contact_aliases_alias ON contact_aliases(alias);
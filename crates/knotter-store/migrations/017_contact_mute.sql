@@ -0,0 +1,4 @@
+-- 017_contact_mute.sql
+-- Temporarily pause reminders for a contact without archiving it.
+
+ALTER TABLE contacts ADD COLUMN muted_until INTEGER;  -- unix seconds UTC; muted while in the future
@@ -0,0 +1,4 @@
+-- 018_contact_date_lead.sql
+-- Remind about a contact date some days ahead (e.g. to mail a birthday gift).
+
+ALTER TABLE contact_dates ADD COLUMN remind_days_before INTEGER;  -- NULL = day-of only
@@ -0,0 +1,15 @@
+-- 019_contact_origins.sql
+-- Where and how a contact was met, optionally introduced by another contact.
+
+CREATE TABLE IF NOT EXISTS contact_origins (
+  contact_id TEXT PRIMARY KEY NOT NULL,
+  met_at TEXT,                  -- free text such as "RustConf 2023"
+  met_on TEXT,                  -- local date, YYYY-MM-DD
+  met_via_contact_id TEXT,      -- introducing contact; cleared when it is deleted
+  updated_at INTEGER NOT NULL,
+
+  FOREIGN KEY(contact_id) REFERENCES contacts(id) ON DELETE CASCADE,
+  FOREIGN KEY(met_via_contact_id) REFERENCES contacts(id) ON DELETE SET NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_contact_origins_met_via ON contact_origins(met_via_contact_id);
@@ -0,0 +1,7 @@
+-- 020_contact_name_key.sql
+-- Folded display name (case, whitespace, Latin diacritics) used to match
+-- contacts by name across sources. Filled in by knotter, not by SQL.
+
+ALTER TABLE contacts ADD COLUMN name_key TEXT;
+
+CREATE INDEX IF NOT EXISTS idx_contacts_name_key ON contacts(name_key);
@@ -0,0 +1,14 @@
+-- 021_contact_sync_log.sql
+-- One row per import that actually changed a contact: which source, which fields.
+
+CREATE TABLE IF NOT EXISTS contact_sync_log (
+  id INTEGER PRIMARY KEY AUTOINCREMENT,
+  contact_id TEXT NOT NULL,
+  source TEXT NOT NULL,
+  fields TEXT NOT NULL,         -- comma-separated field names, e.g. "name,emails"
+  synced_at INTEGER NOT NULL,
+
+  FOREIGN KEY(contact_id) REFERENCES contacts(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_contact_sync_log_contact ON contact_sync_log(contact_id, synced_at);
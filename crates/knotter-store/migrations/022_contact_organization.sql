@@ -0,0 +1,4 @@
+-- 022_contact_organization.sql
+-- Company or organization a contact works for (vCard ORG).
+
+ALTER TABLE contacts ADD COLUMN organization TEXT;
@@ -0,0 +1,8 @@
+-- 023_settings.sql
+-- Single values changed from the CLI or TUI rather than the config file.
+
+CREATE TABLE IF NOT EXISTS settings (
+  key TEXT PRIMARY KEY,
+  value TEXT NOT NULL,
+  updated_at INTEGER NOT NULL
+);
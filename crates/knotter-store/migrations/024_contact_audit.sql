@@ -0,0 +1,15 @@
+-- 024_contact_audit.sql
+-- Append-only history of changes to contacts. Rows outlive the contact so a
+-- delete stays on record; a merge moves the absorbed contact's rows over.
+
+CREATE TABLE IF NOT EXISTS contact_audit (
+  id INTEGER PRIMARY KEY AUTOINCREMENT,
+  contact_id TEXT NOT NULL,
+  action TEXT NOT NULL,         -- update, archive, unarchive, merge, trash, restore, delete
+  origin TEXT NOT NULL,         -- manual, tui, or the import source
+  changes TEXT NOT NULL,        -- JSON object: field -> [old, new]
+  detail TEXT,
+  occurred_at INTEGER NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_contact_audit_contact ON contact_audit(contact_id, occurred_at);
@@ -0,0 +1,13 @@
+-- 025_interaction_attachments.sql
+-- File paths and URLs linked to an interaction. Paths are stored as given;
+-- the files themselves are never copied.
+
+CREATE TABLE IF NOT EXISTS interaction_attachments (
+  interaction_id TEXT NOT NULL,
+  kind TEXT NOT NULL,           -- "file"|"url"
+  value TEXT NOT NULL,
+  created_at INTEGER NOT NULL,
+
+  PRIMARY KEY (interaction_id, value),
+  FOREIGN KEY(interaction_id) REFERENCES interactions(id) ON DELETE CASCADE
+);
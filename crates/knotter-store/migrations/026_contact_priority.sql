@@ -0,0 +1,4 @@
+-- 026_contact_priority.sql
+-- How much a contact matters: 0 (default) to 10; 5 and up counts as starred.
+
+ALTER TABLE contacts ADD COLUMN priority INTEGER NOT NULL DEFAULT 0;
@@ -32,6 +32,13 @@ pub fn backup_to(conn: &Connection, path: &Path) -> Result<()> {
     Ok(())
 }
 
+/// Copies the whole database into a fresh in-memory connection.
+pub fn copy_to_memory(conn: &Connection) -> Result<Connection> {
+    let mut dest = db::open_in_memory()?;
+    Backup::new(conn, &mut dest)?.run_to_completion(PAGES_PER_STEP, Duration::ZERO, None)?;
+    Ok(dest)
+}
+
 fn canonicalize_path(path: &Path) -> Result<PathBuf> {
     if path.exists() {
         return Ok(fs::canonicalize(path)?);
@@ -47,7 +54,7 @@ fn canonicalize_path(path: &Path) -> Result<PathBuf> {
     Ok(parent.join(file_name))
 }
 
-fn main_db_path(conn: &Connection) -> Result<Option<PathBuf>> {
+pub(crate) fn main_db_path(conn: &Connection) -> Result<Option<PathBuf>> {
     let mut stmt = conn.prepare("PRAGMA database_list;")?;
     let mut rows = stmt.query([])?;
     while let Some(row) = rows.next()? {
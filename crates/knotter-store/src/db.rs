@@ -1,5 +1,5 @@
-use crate::error::Result;
-use rusqlite::Connection;
+use crate::error::{Result, StoreError};
+use rusqlite::{Connection, OpenFlags};
 use std::fs;
 use std::path::Path;
 
@@ -10,6 +10,23 @@ pub fn open(path: &Path) -> Result<Connection> {
     Ok(conn)
 }
 
+/// Opens an existing database without write access. Nothing on disk is touched:
+/// no file is created, permissions are left alone, and the journal mode is kept.
+pub fn open_readonly(path: &Path) -> Result<Connection> {
+    if !path.is_file() {
+        return Err(StoreError::NotFound(path.display().to_string()));
+    }
+    let conn = Connection::open_with_flags(
+        path,
+        OpenFlags::SQLITE_OPEN_READ_ONLY
+            | OpenFlags::SQLITE_OPEN_NO_MUTEX
+            | OpenFlags::SQLITE_OPEN_URI,
+    )?;
+    conn.pragma_update(None, "foreign_keys", "ON")?;
+    conn.pragma_update(None, "busy_timeout", 2000)?;
+    Ok(conn)
+}
+
 pub fn open_in_memory() -> Result<Connection> {
     let conn = Connection::open_in_memory()?;
     apply_pragmas(&conn)?;
@@ -0,0 +1,265 @@
+//! Consistency checks behind `knotter doctor`.
+
+use crate::error::{Result, StoreError};
+use crate::repo::merge_candidates::MergeCandidateStatus;
+use knotter_core::domain::{ContactId, InteractionId};
+use rusqlite::{params, Connection};
+use std::str::FromStr;
+
+/// Tables whose `contact_id` must reference an existing contact.
+const CONTACT_CHILD_TABLES: &[&str] = &[
+    "contact_emails",
+    "contact_tags",
+    "interactions",
+    "contact_dates",
+    "contact_telegram_accounts",
+    "contact_sources",
+    "contact_field_provenance",
+    "contact_sync_log",
+    "email_messages",
+    "telegram_messages",
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OrphanRows {
+    pub table: &'static str,
+    pub rows: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DuplicateEmail {
+    pub email: String,
+    pub contact_ids: Vec<ContactId>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SyncStateAccounts {
+    pub email: Vec<String>,
+    pub telegram: Vec<String>,
+}
+
+/// Problems reported by `PRAGMA integrity_check`; empty when the file is healthy.
+pub fn integrity_check(conn: &Connection) -> Result<Vec<String>> {
+    let mut stmt = conn.prepare("PRAGMA integrity_check;")?;
+    let rows = stmt.query_map([], |row| row.get::<_, String>(0))?;
+    let mut problems = Vec::new();
+    for row in rows {
+        let message = row?;
+        if message != "ok" {
+            problems.push(message);
+        }
+    }
+    Ok(problems)
+}
+
+/// Rows whose contact (or, for `contact_tags`, tag) no longer exists.
+pub fn orphan_rows(conn: &Connection) -> Result<Vec<OrphanRows>> {
+    let mut out = Vec::new();
+    for table in CONTACT_CHILD_TABLES {
+        let rows: i64 = conn.query_row(&count_orphans_sql(table), [], |row| row.get(0))?;
+        if rows > 0 {
+            out.push(OrphanRows {
+                table,
+                rows: rows as usize,
+            });
+        }
+    }
+    Ok(out)
+}
+
+pub fn delete_orphan_rows(conn: &Connection) -> Result<Vec<OrphanRows>> {
+    let mut out = Vec::new();
+    for table in CONTACT_CHILD_TABLES {
+        let rows = conn.execute(&delete_orphans_sql(table), [])?;
+        if rows > 0 {
+            out.push(OrphanRows { table, rows });
+        }
+    }
+    Ok(out)
+}
+
+fn orphan_predicate(table: &str) -> String {
+    let missing_contact =
+        format!("NOT EXISTS (SELECT 1 FROM contacts c WHERE c.id = {table}.contact_id)");
+    if table == "contact_tags" {
+        format!(
+            "{missing_contact} OR NOT EXISTS (SELECT 1 FROM tags t WHERE t.id = contact_tags.tag_id)"
+        )
+    } else {
+        missing_contact
+    }
+}
+
+fn count_orphans_sql(table: &str) -> String {
+    format!(
+        "SELECT COUNT(*) FROM {table} WHERE {};",
+        orphan_predicate(table)
+    )
+}
+
+fn delete_orphans_sql(table: &str) -> String {
+    format!("DELETE FROM {table} WHERE {};", orphan_predicate(table))
+}
+
+const DANGLING_CANDIDATE_PREDICATE: &str = "status = ?1
+   AND (NOT EXISTS (SELECT 1 FROM contacts c WHERE c.id = contact_merge_candidates.contact_a_id)
+        OR NOT EXISTS (SELECT 1 FROM contacts c WHERE c.id = contact_merge_candidates.contact_b_id))";
+
+/// Open merge candidates that reference a contact that no longer exists.
+pub fn dangling_merge_candidates(conn: &Connection) -> Result<usize> {
+    let count: i64 = conn.query_row(
+        &format!(
+            "SELECT COUNT(*) FROM contact_merge_candidates WHERE {DANGLING_CANDIDATE_PREDICATE};"
+        ),
+        [MergeCandidateStatus::Open.as_str()],
+        |row| row.get(0),
+    )?;
+    Ok(count as usize)
+}
+
+pub fn dismiss_dangling_merge_candidates(conn: &Connection, now_utc: i64) -> Result<usize> {
+    Ok(conn.execute(
+        &format!(
+            "UPDATE contact_merge_candidates SET status = ?2, resolved_at = ?3
+             WHERE {DANGLING_CANDIDATE_PREDICATE};"
+        ),
+        params![
+            MergeCandidateStatus::Open.as_str(),
+            MergeCandidateStatus::Dismissed.as_str(),
+            now_utc
+        ],
+    )?)
+}
+
+/// Contacts whose `archived_at` lies after `now_utc`.
+pub fn archived_in_future(conn: &Connection, now_utc: i64) -> Result<Vec<ContactId>> {
+    let mut stmt = conn.prepare(
+        "SELECT id FROM contacts WHERE archived_at > ?1 AND deleted_at IS NULL ORDER BY display_name COLLATE NOCASE;",
+    )?;
+    let rows = stmt.query_map([now_utc], |row| row.get::<_, String>(0))?;
+    let mut ids = Vec::new();
+    for row in rows {
+        ids.push(parse_contact_id(row?)?);
+    }
+    Ok(ids)
+}
+
+/// Interactions dated before `min_occurred_at` or after `max_occurred_at`,
+/// typically imported from a device with a wrong clock. Oldest first.
+pub fn interactions_out_of_range(
+    conn: &Connection,
+    min_occurred_at: i64,
+    max_occurred_at: i64,
+) -> Result<Vec<InteractionId>> {
+    let mut stmt = conn.prepare(
+        "SELECT interactions.id FROM interactions
+         INNER JOIN contacts ON contacts.id = interactions.contact_id
+         WHERE contacts.deleted_at IS NULL
+           AND (interactions.occurred_at < ?1 OR interactions.occurred_at > ?2)
+         ORDER BY interactions.occurred_at, interactions.id;",
+    )?;
+    let rows = stmt.query_map([min_occurred_at, max_occurred_at], |row| {
+        row.get::<_, String>(0)
+    })?;
+    let mut ids = Vec::new();
+    for row in rows {
+        let value = row?;
+        ids.push(InteractionId::from_str(&value).map_err(|_| StoreError::InvalidId(value))?);
+    }
+    Ok(ids)
+}
+
+/// Active contacts whose scheduled touchpoint has already passed.
+pub fn overdue_active_contacts(conn: &Connection, now_utc: i64) -> Result<usize> {
+    let count: i64 = conn.query_row(
+        "SELECT COUNT(*) FROM contacts
+         WHERE archived_at IS NULL AND deleted_at IS NULL
+           AND next_touchpoint_at IS NOT NULL AND next_touchpoint_at < ?1;",
+        [now_utc],
+        |row| row.get(0),
+    )?;
+    Ok(count as usize)
+}
+
+/// Emails that normalize to the same address on different contacts, where no
+/// open merge candidate already links those contacts.
+pub fn duplicate_emails_without_candidate(conn: &Connection) -> Result<Vec<DuplicateEmail>> {
+    let mut stmt = conn.prepare(
+        "WITH addresses AS (
+           SELECT contact_id, lower(trim(email)) AS email FROM contact_emails
+           UNION
+           SELECT id, lower(trim(email)) FROM contacts
+           WHERE email IS NOT NULL AND trim(email) <> '' AND deleted_at IS NULL
+         )
+         SELECT email, group_concat(contact_id, ',')
+         FROM (SELECT DISTINCT email, contact_id FROM addresses ORDER BY contact_id)
+         GROUP BY email
+         HAVING COUNT(*) > 1
+         ORDER BY email;",
+    )?;
+    let rows = stmt.query_map([], |row| {
+        Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+    })?;
+
+    let mut out = Vec::new();
+    for row in rows {
+        let (email, ids) = row?;
+        let contact_ids = ids
+            .split(',')
+            .map(|id| parse_contact_id(id.to_string()))
+            .collect::<Result<Vec<_>>>()?;
+        if !all_pairs_have_open_candidate(conn, &contact_ids)? {
+            out.push(DuplicateEmail { email, contact_ids });
+        }
+    }
+    Ok(out)
+}
+
+fn all_pairs_have_open_candidate(conn: &Connection, ids: &[ContactId]) -> Result<bool> {
+    for (index, left) in ids.iter().enumerate() {
+        for right in &ids[index + 1..] {
+            let exists: i64 = conn.query_row(
+                "SELECT EXISTS (
+                   SELECT 1 FROM contact_merge_candidates
+                   WHERE status = ?1
+                     AND ((contact_a_id = ?2 AND contact_b_id = ?3)
+                       OR (contact_a_id = ?3 AND contact_b_id = ?2))
+                 );",
+                params![
+                    MergeCandidateStatus::Open.as_str(),
+                    left.to_string(),
+                    right.to_string()
+                ],
+                |row| row.get(0),
+            )?;
+            if exists == 0 {
+                return Ok(false);
+            }
+        }
+    }
+    Ok(true)
+}
+
+/// Account names that have stored email or Telegram sync state.
+pub fn sync_state_accounts(conn: &Connection) -> Result<SyncStateAccounts> {
+    Ok(SyncStateAccounts {
+        email: distinct_accounts(conn, "email_sync_state")?,
+        telegram: distinct_accounts(conn, "telegram_sync_state")?,
+    })
+}
+
+fn distinct_accounts(conn: &Connection, table: &str) -> Result<Vec<String>> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT DISTINCT account FROM {table} ORDER BY account;"
+    ))?;
+    let rows = stmt.query_map([], |row| row.get::<_, String>(0))?;
+    let mut accounts = Vec::new();
+    for row in rows {
+        accounts.push(row?);
+    }
+    Ok(accounts)
+}
+
+fn parse_contact_id(value: String) -> Result<ContactId> {
+    ContactId::from_str(&value).map_err(|_| StoreError::InvalidId(value))
+}
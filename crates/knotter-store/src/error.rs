@@ -24,6 +24,8 @@ pub enum StoreError {
     InvalidBackupPath(PathBuf),
     #[error("unsupported interaction kind: {0}")]
     InvalidInteractionKind(String),
+    #[error("invalid interaction: {0}")]
+    InvalidInteraction(String),
     #[error("invalid filter: {0}")]
     InvalidFilter(String),
     #[error("duplicate email: {0}")]
@@ -34,6 +36,8 @@ pub enum StoreError {
     DuplicateContactSource(String, String),
     #[error("invalid merge: {0}")]
     InvalidMerge(String),
+    #[error("not enough free disk space: need {needed} bytes, {available} available")]
+    InsufficientSpace { needed: u64, available: u64 },
 }
 
 pub type Result<T> = std::result::Result<T, StoreError>;
@@ -50,11 +54,13 @@ pub enum StoreErrorKind {
     InvalidDataPath,
     InvalidBackupPath,
     InvalidInteractionKind,
+    InvalidInteraction,
     InvalidFilter,
     DuplicateEmail,
     DuplicateTelegramUser,
     DuplicateContactSource,
     InvalidMerge,
+    InsufficientSpace,
 }
 
 impl StoreError {
@@ -70,11 +76,13 @@ impl StoreError {
             StoreError::InvalidDataPath(_) => StoreErrorKind::InvalidDataPath,
             StoreError::InvalidBackupPath(_) => StoreErrorKind::InvalidBackupPath,
             StoreError::InvalidInteractionKind(_) => StoreErrorKind::InvalidInteractionKind,
+            StoreError::InvalidInteraction(_) => StoreErrorKind::InvalidInteraction,
             StoreError::InvalidFilter(_) => StoreErrorKind::InvalidFilter,
             StoreError::DuplicateEmail(_) => StoreErrorKind::DuplicateEmail,
             StoreError::DuplicateTelegramUser(_) => StoreErrorKind::DuplicateTelegramUser,
             StoreError::DuplicateContactSource(_, _) => StoreErrorKind::DuplicateContactSource,
             StoreError::InvalidMerge(_) => StoreErrorKind::InvalidMerge,
+            StoreError::InsufficientSpace { .. } => StoreErrorKind::InsufficientSpace,
         }
     }
 }
@@ -1,6 +1,8 @@
 pub mod backup;
 pub mod db;
+pub mod doctor;
 pub mod error;
+pub mod maintenance;
 pub mod migrate;
 pub mod paths;
 pub mod query;
@@ -13,23 +15,49 @@ use std::path::Path;
 
 pub struct Store {
     conn: Connection,
+    readonly: bool,
 }
 
 impl Store {
     pub fn open(path: &Path) -> Result<Self> {
         let conn = db::open(path)?;
-        Ok(Self { conn })
+        Ok(Self {
+            conn,
+            readonly: false,
+        })
+    }
+
+    /// Opens an existing database read-only; pair with `check_schema` instead of
+    /// `migrate`.
+    pub fn open_readonly(path: &Path) -> Result<Self> {
+        let conn = db::open_readonly(path)?;
+        Ok(Self {
+            conn,
+            readonly: true,
+        })
     }
 
     pub fn open_in_memory() -> Result<Self> {
         let conn = db::open_in_memory()?;
-        Ok(Self { conn })
+        Ok(Self {
+            conn,
+            readonly: false,
+        })
+    }
+
+    pub fn is_readonly(&self) -> bool {
+        self.readonly
     }
 
     pub fn migrate(&self) -> Result<()> {
         migrate::run_migrations(&self.conn)
     }
 
+    /// Errors unless the schema is exactly the version this build migrates to.
+    pub fn check_schema(&self) -> Result<()> {
+        migrate::check_schema(&self.conn)
+    }
+
     pub fn schema_version(&self) -> Result<i64> {
         migrate::schema_version(&self.conn)
     }
@@ -38,6 +66,23 @@ impl Store {
         backup::backup_to(&self.conn, path)
     }
 
+    /// A writable in-memory copy, for dry runs that apply their changes and roll
+    /// back on a database opened read-only.
+    pub fn copy_to_memory(&self) -> Result<Self> {
+        Ok(Self {
+            conn: backup::copy_to_memory(&self.conn)?,
+            readonly: false,
+        })
+    }
+
+    pub fn stats(&self) -> Result<maintenance::DbStats> {
+        maintenance::stats(&self.conn)
+    }
+
+    pub fn vacuum(&self) -> Result<maintenance::VacuumReport> {
+        maintenance::vacuum(&self.conn)
+    }
+
     pub fn connection(&self) -> &Connection {
         &self.conn
     }
@@ -62,6 +107,10 @@ impl Store {
         repo::TelegramSyncRepo::new(&self.conn)
     }
 
+    pub fn last_touch(&self) -> repo::LastTouchRepo<'_> {
+        repo::LastTouchRepo::new(&self.conn)
+    }
+
     pub fn tags(&self) -> repo::TagsRepo<'_> {
         repo::TagsRepo::new(&self.conn)
     }
@@ -70,15 +119,43 @@ impl Store {
         repo::InteractionsRepo::new(&self.conn)
     }
 
+    pub fn interaction_attachments(&self) -> repo::InteractionAttachmentsRepo<'_> {
+        repo::InteractionAttachmentsRepo::new(&self.conn)
+    }
+
+    pub fn contact_aliases(&self) -> repo::ContactAliasesRepo<'_> {
+        repo::ContactAliasesRepo::new(&self.conn)
+    }
+
+    pub fn contact_audit(&self) -> repo::ContactAuditRepo<'_> {
+        repo::ContactAuditRepo::new(&self.conn)
+    }
+
     pub fn contact_dates(&self) -> repo::ContactDatesRepo<'_> {
         repo::ContactDatesRepo::new(&self.conn)
     }
 
+    pub fn contact_origins(&self) -> repo::ContactOriginsRepo<'_> {
+        repo::ContactOriginsRepo::new(&self.conn)
+    }
+
     pub fn contact_sources(&self) -> repo::ContactSourcesRepo<'_> {
         repo::ContactSourcesRepo::new(&self.conn)
     }
 
+    pub fn field_provenance(&self) -> repo::FieldProvenanceRepo<'_> {
+        repo::FieldProvenanceRepo::new(&self.conn)
+    }
+
     pub fn merge_candidates(&self) -> repo::MergeCandidatesRepo<'_> {
         repo::MergeCandidatesRepo::new(&self.conn)
     }
+
+    pub fn settings(&self) -> repo::SettingsRepo<'_> {
+        repo::SettingsRepo::new(&self.conn)
+    }
+
+    pub fn sync_log(&self) -> repo::SyncLogRepo<'_> {
+        repo::SyncLogRepo::new(&self.conn)
+    }
 }
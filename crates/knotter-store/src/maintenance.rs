@@ -0,0 +1,173 @@
+//! Size reporting and compaction behind `knotter db stats` and `db vacuum`.
+
+use crate::backup::main_db_path;
+use crate::error::{Result, StoreError};
+use rusqlite::Connection;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Tables counted by `stats`, in report order.
+const COUNTED_TABLES: &[&str] = &[
+    "contacts",
+    "contact_emails",
+    "interactions",
+    "interaction_attachments",
+    "tags",
+    "contact_merge_candidates",
+    "contact_audit",
+    "email_sync_state",
+    "telegram_sync_state",
+    "email_messages",
+    "telegram_messages",
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TableRows {
+    pub table: &'static str,
+    pub rows: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DbStats {
+    /// `None` for an in-memory database.
+    pub path: Option<PathBuf>,
+    /// Size of the main file; pages times page size when there is no file.
+    pub file_size_bytes: u64,
+    pub page_count: u64,
+    pub page_size: u64,
+    /// Unused pages that `vacuum` would give back.
+    pub freelist_count: u64,
+    /// Size of the `-wal` file, when there is one.
+    pub wal_size_bytes: Option<u64>,
+    pub tables: Vec<TableRows>,
+}
+
+impl DbStats {
+    /// Main file plus write-ahead log.
+    pub fn total_bytes(&self) -> u64 {
+        self.file_size_bytes + self.wal_size_bytes.unwrap_or(0)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VacuumReport {
+    pub before: DbStats,
+    pub after: DbStats,
+}
+
+impl VacuumReport {
+    pub fn reclaimed_bytes(&self) -> u64 {
+        self.before
+            .total_bytes()
+            .saturating_sub(self.after.total_bytes())
+    }
+}
+
+pub fn stats(conn: &Connection) -> Result<DbStats> {
+    let page_count = count(conn, "PRAGMA page_count;")?;
+    let page_size = count(conn, "PRAGMA page_size;")?;
+    let freelist_count = count(conn, "PRAGMA freelist_count;")?;
+    let path = main_db_path(conn)?;
+    let (file_size_bytes, wal_size_bytes) = match &path {
+        Some(path) => (fs::metadata(path)?.len(), file_len(&wal_path(path))?),
+        None => (page_count * page_size, None),
+    };
+
+    let mut tables = Vec::with_capacity(COUNTED_TABLES.len());
+    for table in COUNTED_TABLES {
+        let rows = count(conn, &format!("SELECT COUNT(*) FROM {table};"))?;
+        tables.push(TableRows { table, rows });
+    }
+
+    Ok(DbStats {
+        path,
+        file_size_bytes,
+        page_count,
+        page_size,
+        freelist_count,
+        wal_size_bytes,
+        tables,
+    })
+}
+
+/// Rebuilds the database file, refreshes query planner statistics, and folds
+/// the WAL back in. On a database with nothing to reclaim the file can end up
+/// slightly larger, since `optimize` may add planner statistics.
+///
+/// VACUUM builds a full copy in SQLite's temp directory and then writes it
+/// back through the WAL next to the database, so it is refused when either
+/// disk has less free space than the database takes up. The temp directory
+/// is skipped when `temp_store` keeps temp files in memory.
+pub fn vacuum(conn: &Connection) -> Result<VacuumReport> {
+    let before = stats(conn)?;
+    if let Some(path) = &before.path {
+        let dir = path
+            .parent()
+            .filter(|dir| !dir.as_os_str().is_empty())
+            .unwrap_or_else(|| Path::new("."));
+        ensure_vacuum_space(before.total_bytes(), fs4::available_space(dir)?)?;
+        let temp_store: i64 = conn.query_row("PRAGMA temp_store;", [], |row| row.get(0))?;
+        if temp_store != TEMP_STORE_MEMORY {
+            let temp_dir = sqlite_temp_dir();
+            ensure_vacuum_space(before.total_bytes(), fs4::available_space(&temp_dir)?)?;
+        }
+    }
+
+    conn.execute_batch("VACUUM;")?;
+    conn.execute_batch("PRAGMA optimize;")?;
+    conn.query_row("PRAGMA wal_checkpoint(TRUNCATE);", [], |_| Ok(()))?;
+
+    let after = stats(conn)?;
+    Ok(VacuumReport { before, after })
+}
+
+/// Errors unless `available` bytes can hold a copy of a `needed`-byte database.
+pub fn ensure_vacuum_space(needed: u64, available: u64) -> Result<()> {
+    if available < needed {
+        return Err(StoreError::InsufficientSpace { needed, available });
+    }
+    Ok(())
+}
+
+/// `PRAGMA temp_store` value for keeping temp files in memory.
+const TEMP_STORE_MEMORY: i64 = 2;
+
+/// Where SQLite puts temp files: the first existing directory it would try.
+#[cfg(unix)]
+fn sqlite_temp_dir() -> PathBuf {
+    let from_env = ["SQLITE_TMPDIR", "TMPDIR"]
+        .into_iter()
+        .filter_map(std::env::var_os)
+        .map(PathBuf::from);
+    let fixed = ["/var/tmp", "/usr/tmp", "/tmp"]
+        .into_iter()
+        .map(PathBuf::from);
+    from_env
+        .chain(fixed)
+        .find(|dir| dir.is_dir())
+        .unwrap_or_else(|| PathBuf::from("."))
+}
+
+#[cfg(not(unix))]
+fn sqlite_temp_dir() -> PathBuf {
+    std::env::temp_dir()
+}
+
+fn count(conn: &Connection, sql: &str) -> Result<u64> {
+    let value: i64 = conn.query_row(sql, [], |row| row.get(0))?;
+    Ok(value.max(0) as u64)
+}
+
+fn wal_path(path: &Path) -> PathBuf {
+    let mut wal = path.as_os_str().to_owned();
+    wal.push("-wal");
+    PathBuf::from(wal)
+}
+
+fn file_len(path: &Path) -> Result<Option<u64>> {
+    match fs::metadata(path) {
+        Ok(meta) => Ok(Some(meta.len())),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(err) => Err(err.into()),
+    }
+}
@@ -1,5 +1,6 @@
 use crate::error::{Result, StoreError};
-use rusqlite::{Connection, OptionalExtension, Transaction};
+use knotter_core::domain::name_match_key;
+use rusqlite::{params, Connection, OptionalExtension, Transaction};
 
 const MIGRATIONS: &[(&str, &str)] = &[
     ("001_init.sql", include_str!("../migrations/001_init.sql")),
@@ -43,6 +44,66 @@ const MIGRATIONS: &[(&str, &str)] = &[
         "011_contact_sources_external_id_norm.sql",
         include_str!("../migrations/011_contact_sources_external_id_norm.sql"),
     ),
+    (
+        "012_interaction_direction.sql",
+        include_str!("../migrations/012_interaction_direction.sql"),
+    ),
+    (
+        "013_contact_archive_reason.sql",
+        include_str!("../migrations/013_contact_archive_reason.sql"),
+    ),
+    (
+        "014_contact_field_provenance.sql",
+        include_str!("../migrations/014_contact_field_provenance.sql"),
+    ),
+    (
+        "015_contact_trash.sql",
+        include_str!("../migrations/015_contact_trash.sql"),
+    ),
+    (
+        "016_contact_aliases.sql",
+        include_str!("../migrations/016_contact_aliases.sql"),
+    ),
+    (
+        "017_contact_mute.sql",
+        include_str!("../migrations/017_contact_mute.sql"),
+    ),
+    (
+        "018_contact_date_lead.sql",
+        include_str!("../migrations/018_contact_date_lead.sql"),
+    ),
+    (
+        "019_contact_origins.sql",
+        include_str!("../migrations/019_contact_origins.sql"),
+    ),
+    (
+        "020_contact_name_key.sql",
+        include_str!("../migrations/020_contact_name_key.sql"),
+    ),
+    (
+        "021_contact_sync_log.sql",
+        include_str!("../migrations/021_contact_sync_log.sql"),
+    ),
+    (
+        "022_contact_organization.sql",
+        include_str!("../migrations/022_contact_organization.sql"),
+    ),
+    (
+        "023_settings.sql",
+        include_str!("../migrations/023_settings.sql"),
+    ),
+    (
+        "024_contact_audit.sql",
+        include_str!("../migrations/024_contact_audit.sql"),
+    ),
+    (
+        "025_interaction_attachments.sql",
+        include_str!("../migrations/025_interaction_attachments.sql"),
+    ),
+    (
+        "026_contact_priority.sql",
+        include_str!("../migrations/026_contact_priority.sql"),
+    ),
 ];
 
 pub fn run_migrations(conn: &Connection) -> Result<()> {
@@ -58,12 +119,13 @@ pub fn run_migrations(conn: &Connection) -> Result<()> {
         )));
     }
 
-    for (index, (_name, sql)) in MIGRATIONS.iter().enumerate() {
+    for (index, (name, sql)) in MIGRATIONS.iter().enumerate() {
         let version = (index + 1) as i64;
         if current >= version {
             continue;
         }
         tx.execute_batch(sql)?;
+        backfill(&tx, name)?;
         set_version(&tx, version)?;
     }
 
@@ -71,6 +133,38 @@ pub fn run_migrations(conn: &Connection) -> Result<()> {
     Ok(())
 }
 
+/// Verifies the schema is current without migrating, for read-only connections.
+pub fn check_schema(conn: &Connection) -> Result<()> {
+    let supported = MIGRATIONS.len() as i64;
+    let has_schema_table: i64 = conn.query_row(
+        "SELECT COUNT(*) FROM sqlite_master WHERE type = 'table' AND name = 'knotter_schema';",
+        [],
+        |row| row.get(0),
+    )?;
+    let current = if has_schema_table == 0 {
+        0
+    } else {
+        conn.query_row("SELECT version FROM knotter_schema LIMIT 1;", [], |row| {
+            row.get::<_, i64>(0)
+        })
+        .optional()?
+        .unwrap_or(0)
+    };
+    if current < supported {
+        return Err(StoreError::Migration(format!(
+            "db version {} older than supported {}; open it once without --readonly to migrate",
+            current, supported
+        )));
+    }
+    if current > supported {
+        return Err(StoreError::Migration(format!(
+            "db version {} newer than available migrations {}",
+            current, supported
+        )));
+    }
+    Ok(())
+}
+
 pub fn schema_version(conn: &Connection) -> Result<i64> {
     let version: i64 =
         conn.query_row("SELECT version FROM knotter_schema LIMIT 1;", [], |row| {
@@ -79,6 +173,23 @@ pub fn schema_version(conn: &Connection) -> Result<i64> {
     Ok(version)
 }
 
+/// Data fills that SQL alone cannot compute, run right after their migration.
+fn backfill(tx: &Transaction<'_>, migration: &str) -> Result<()> {
+    if migration == "020_contact_name_key.sql" {
+        let mut stmt = tx.prepare("SELECT id, display_name FROM contacts;")?;
+        let rows = stmt
+            .query_map([], |row| {
+                Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        let mut update = tx.prepare("UPDATE contacts SET name_key = ?2 WHERE id = ?1;")?;
+        for (id, display_name) in rows {
+            update.execute(params![id, name_match_key(&display_name)])?;
+        }
+    }
+    Ok(())
+}
+
 fn ensure_schema_table(tx: &Transaction<'_>) -> Result<()> {
     tx.execute_batch("CREATE TABLE IF NOT EXISTS knotter_schema (version INTEGER NOT NULL);")?;
 
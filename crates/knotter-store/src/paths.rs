@@ -3,12 +3,31 @@ use chrono::Local;
 use std::env;
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
 use std::time::{SystemTime, UNIX_EPOCH};
 
 const APP_DIR: &str = "knotter";
 const DB_FILENAME: &str = "knotter.sqlite3";
+/// Environment variable naming the knotter data directory itself (no `knotter/` suffix).
+pub const DATA_DIR_ENV: &str = "KNOTTER_DATA_DIR";
 
+static DATA_DIR_OVERRIDE: OnceLock<PathBuf> = OnceLock::new();
+
+/// Points every data path at `dir` for the rest of the process (the `--data-dir`
+/// flag). Only the first call takes effect.
+pub fn set_data_dir_override(dir: PathBuf) {
+    let _ = DATA_DIR_OVERRIDE.set(dir);
+}
+
+/// Base data directory. Precedence: `--data-dir` override, `KNOTTER_DATA_DIR`,
+/// `$XDG_DATA_HOME/knotter`, then `~/.local/share/knotter`.
 pub fn data_dir() -> Result<PathBuf> {
+    if let Some(dir) = DATA_DIR_OVERRIDE.get() {
+        return non_empty_dir(dir.clone());
+    }
+    if let Some(dir) = env::var_os(DATA_DIR_ENV) {
+        return non_empty_dir(PathBuf::from(dir));
+    }
     if let Some(dir) = env::var_os("XDG_DATA_HOME") {
         let path = PathBuf::from(dir);
         if path.as_os_str().is_empty() {
@@ -21,6 +40,13 @@ pub fn data_dir() -> Result<PathBuf> {
     Ok(home.join(".local").join("share").join(APP_DIR))
 }
 
+fn non_empty_dir(path: PathBuf) -> Result<PathBuf> {
+    if path.as_os_str().is_empty() {
+        return Err(StoreError::InvalidDataPath(path));
+    }
+    Ok(path)
+}
+
 pub fn ensure_data_dir() -> Result<PathBuf> {
     let dir = data_dir()?;
     if !dir.exists() {
@@ -54,6 +80,14 @@ pub fn resolve_db_path(custom: Option<PathBuf>) -> Result<PathBuf> {
     }
 }
 
+/// Like `resolve_db_path`, but never creates directories (for `--readonly`).
+pub fn resolve_db_path_readonly(custom: Option<PathBuf>) -> Result<PathBuf> {
+    match custom {
+        Some(path) => Ok(path),
+        None => Ok(db_path_in(&data_dir()?)),
+    }
+}
+
 pub fn db_path_in(dir: &Path) -> PathBuf {
     dir.join(DB_FILENAME)
 }
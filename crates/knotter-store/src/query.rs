@@ -1,7 +1,11 @@
 use crate::error::{Result, StoreError};
-use chrono::{DateTime, Duration, FixedOffset, TimeZone, Utc};
-use knotter_core::domain::TagName;
-use knotter_core::filter::{ArchivedSelector, ContactFilter, FilterExpr};
+use chrono::FixedOffset;
+use knotter_core::domain::{TagName, DEFAULT_AWAITING_REPLY_DAYS};
+use knotter_core::filter::{
+    ArchivedSelector, ContactFilter, FilterExpr, FollowupSelector, MutedSelector, PrioritySelector,
+    ReplySelector,
+};
+pub use knotter_core::rules::{due_bounds, DueBounds};
 use knotter_core::rules::{validate_soon_days, DueSelector};
 use rusqlite::types::Value;
 
@@ -11,6 +15,29 @@ pub struct ContactQuery {
     pub tags: Vec<TagName>,
     pub due: Option<DueSelector>,
     pub archived: Option<ArchivedSelector>,
+    pub muted: Option<MutedSelector>,
+    /// Every selector must hold, so `priority:>=3 priority:5` means exactly 5.
+    pub priorities: Vec<PrioritySelector>,
+    pub reply: Option<ReplySelector>,
+    /// Minimum age of the unanswered inbound message for `reply:awaiting`.
+    pub awaiting_reply_days: Option<i64>,
+    pub followup: Option<FollowupSelector>,
+    /// Substrings matched against where the contact was met.
+    pub met_terms: Vec<String>,
+    /// Substrings matched against the contact's organization.
+    pub org_terms: Vec<String>,
+    /// Handles matched exactly (case-insensitive), ignoring a leading `@`.
+    pub handles: Vec<String>,
+    pub sort: ContactSort,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ContactSort {
+    /// Active first, then by due bucket and name.
+    #[default]
+    Due,
+    /// Most recently archived first; active contacts last.
+    Archived,
 }
 
 pub struct SqlQuery {
@@ -18,6 +45,100 @@ pub struct SqlQuery {
     pub params: Vec<Value>,
 }
 
+/// A window into an ordered contact listing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ListPage {
+    pub limit: usize,
+    pub offset: usize,
+}
+
+/// Everything a contact listing needs; `list`, `remind`, and the TUI all go
+/// through [`ContactsRepo::list_view`](crate::repo::ContactsRepo::list_view) with
+/// one of these so due buckets share a single definition.
+#[derive(Debug, Clone)]
+pub struct ContactListQuery {
+    /// Parsed filter expression; `None` matches every contact.
+    pub filter: Option<ContactFilter>,
+    /// Used when the filter has no `archived:` term.
+    pub archived: Option<ArchivedSelector>,
+    /// Used when the filter has no `muted:` term.
+    pub muted: Option<MutedSelector>,
+    /// Combined with the filter's `due:` term; the two must agree.
+    pub due: Option<DueSelector>,
+    pub reply: Option<ReplySelector>,
+    pub awaiting_reply_days: Option<i64>,
+    /// Width of the "soon" window after today, in days.
+    pub soon_days: i64,
+    pub sort: ContactSort,
+    pub page: Option<ListPage>,
+}
+
+impl ContactListQuery {
+    pub fn new(soon_days: i64) -> Self {
+        Self {
+            filter: None,
+            archived: None,
+            muted: None,
+            due: None,
+            reply: None,
+            awaiting_reply_days: None,
+            soon_days,
+            sort: ContactSort::default(),
+            page: None,
+        }
+    }
+
+    /// Resolves the filter and selectors into the underlying [`ContactQuery`].
+    pub fn to_contact_query(&self) -> Result<ContactQuery> {
+        let mut query = match &self.filter {
+            Some(filter) => ContactQuery::from_filter(filter)?,
+            None => ContactQuery::default(),
+        };
+        if query.archived.is_none() {
+            query.archived = self.archived;
+        }
+        if query.muted.is_none() {
+            query.muted = self.muted;
+        }
+        match (query.due, self.due) {
+            (Some(existing), Some(due)) if existing != due => {
+                return Err(StoreError::InvalidFilter(
+                    "multiple due filters are not supported".to_string(),
+                ));
+            }
+            (None, due) => query.due = due,
+            _ => {}
+        }
+        if self.reply.is_some() {
+            query.reply = self.reply;
+        }
+        if self.awaiting_reply_days.is_some() {
+            query.awaiting_reply_days = self.awaiting_reply_days;
+        }
+        query.sort = self.sort;
+        Ok(query)
+    }
+}
+
+/// Separator used when aggregating tag names into a single column.
+pub(crate) const TAG_SEPARATOR: char = '\u{1f}';
+
+/// Due bucket rank: overdue, today, soon, scheduled, unscheduled. Same order as
+/// `DueState` sorting in list output.
+const DUE_RANK_SQL: &str = "CASE
+                WHEN contacts.next_touchpoint_at IS NULL THEN 4
+                WHEN contacts.next_touchpoint_at < ? THEN 0
+                WHEN contacts.next_touchpoint_at >= ? AND contacts.next_touchpoint_at < ? THEN 1
+                WHEN contacts.next_touchpoint_at >= ? AND contacts.next_touchpoint_at < ? THEN 2
+                ELSE 3
+            END";
+
+struct WhereClause {
+    clauses: Vec<String>,
+    params: Vec<Value>,
+    bounds: DueBounds,
+}
+
 impl ContactQuery {
     pub fn from_filter(filter: &ContactFilter) -> Result<Self> {
         let mut query = ContactQuery::default();
@@ -52,6 +173,24 @@ impl ContactQuery {
                 }
                 self.archived = Some(*selector);
             }
+            FilterExpr::Muted(selector) => {
+                if self.muted.is_some() {
+                    return Err(StoreError::InvalidFilter(
+                        "multiple muted filters are not supported".to_string(),
+                    ));
+                }
+                self.muted = Some(*selector);
+            }
+            FilterExpr::Priority(selector) => self.priorities.push(*selector),
+            FilterExpr::Reply(selector) => {
+                self.reply = Some(*selector);
+            }
+            FilterExpr::Followup(selector) => {
+                self.followup = Some(*selector);
+            }
+            FilterExpr::Met(text) => self.met_terms.push(text.to_string()),
+            FilterExpr::Org(text) => self.org_terms.push(text.to_string()),
+            FilterExpr::Handle(handle) => self.handles.push(handle.to_string()),
             FilterExpr::And(terms) => {
                 for term in terms {
                     self.push_expr(term)?;
@@ -61,28 +200,49 @@ impl ContactQuery {
         Ok(())
     }
 
-    pub fn to_sql(
+    fn where_clause(
         &self,
         now_utc: i64,
         soon_days: i64,
         local_offset: FixedOffset,
-    ) -> Result<SqlQuery> {
+    ) -> Result<WhereClause> {
         let soon_days = validate_soon_days(soon_days).map_err(StoreError::Core)?;
-        let mut clauses: Vec<String> = Vec::new();
+        // Trashed contacts are invisible to every contact query.
+        let mut clauses: Vec<String> = vec!["deleted_at IS NULL".to_string()];
         let mut params: Vec<Value> = Vec::new();
 
         for term in &self.text_terms {
             clauses.push(
-                "(display_name LIKE ? OR phone LIKE ? OR handle LIKE ? OR EXISTS (SELECT 1 FROM contact_emails ce WHERE ce.contact_id = contacts.id AND ce.email LIKE ?))"
+                "(display_name LIKE ? OR phone LIKE ? OR handle LIKE ? OR EXISTS (SELECT 1 FROM contact_emails ce WHERE ce.contact_id = contacts.id AND ce.email LIKE ?) OR EXISTS (SELECT 1 FROM contact_aliases ca WHERE ca.contact_id = contacts.id AND ca.alias LIKE ?))"
                     .to_string(),
             );
             let like = format!("%{}%", term);
             params.push(Value::from(like.clone()));
             params.push(Value::from(like.clone()));
             params.push(Value::from(like.clone()));
+            params.push(Value::from(like.clone()));
             params.push(Value::from(like));
         }
 
+        for term in &self.met_terms {
+            clauses.push(
+                "EXISTS (SELECT 1 FROM contact_origins co WHERE co.contact_id = contacts.id AND co.met_at LIKE ?)"
+                    .to_string(),
+            );
+            params.push(Value::from(format!("%{}%", term)));
+        }
+
+        for term in &self.org_terms {
+            clauses.push("organization LIKE ?".to_string());
+            params.push(Value::from(format!("%{}%", term)));
+        }
+
+        for handle in &self.handles {
+            clauses.push("(handle = ? COLLATE NOCASE OR handle = ? COLLATE NOCASE)".to_string());
+            params.push(Value::from(handle.clone()));
+            params.push(Value::from(format!("@{handle}")));
+        }
+
         for tag in &self.tags {
             clauses.push(
                 "EXISTS (SELECT 1 FROM contact_tags ct INNER JOIN tags t ON t.id = ct.tag_id WHERE ct.contact_id = contacts.id AND t.name = ?)"
@@ -111,6 +271,10 @@ impl ContactQuery {
                     params.push(Value::from(bounds.start_of_tomorrow));
                     params.push(Value::from(bounds.soon_end));
                 }
+                DueSelector::Scheduled => {
+                    clauses.push("next_touchpoint_at >= ?".to_string());
+                    params.push(Value::from(bounds.soon_end));
+                }
                 DueSelector::Any => {
                     clauses.push("next_touchpoint_at IS NOT NULL".to_string());
                 }
@@ -127,8 +291,80 @@ impl ContactQuery {
             }
         }
 
+        // A mute ends on its own once `muted_until` passes.
+        if let Some(selector) = self.muted {
+            match selector {
+                MutedSelector::Muted => clauses.push("muted_until > ?".to_string()),
+                MutedSelector::Unmuted => {
+                    clauses.push("(muted_until IS NULL OR muted_until <= ?)".to_string())
+                }
+            }
+            params.push(Value::from(now_utc));
+        }
+
+        for selector in &self.priorities {
+            let (clause, priority) = match *selector {
+                PrioritySelector::Exactly(priority) => ("priority = ?", priority),
+                PrioritySelector::AtLeast(priority) => ("priority >= ?", priority),
+            };
+            clauses.push(clause.to_string());
+            params.push(Value::from(priority));
+        }
+
+        if let Some(ReplySelector::Awaiting) = self.reply {
+            let days = self
+                .awaiting_reply_days
+                .unwrap_or(DEFAULT_AWAITING_REPLY_DAYS);
+            if days < 0 {
+                return Err(StoreError::InvalidFilter(format!(
+                    "awaiting reply days must be non-negative: {days}"
+                )));
+            }
+            // Latest directed (imported) interaction per contact; ties count as replied.
+            clauses.push(
+                "EXISTS (
+                    SELECT 1 FROM (
+                        SELECT i.direction, i.occurred_at
+                        FROM interactions i
+                        WHERE i.contact_id = contacts.id AND i.direction IS NOT NULL
+                        ORDER BY i.occurred_at DESC, (i.direction = 'outbound') DESC
+                        LIMIT 1
+                    ) latest
+                    WHERE latest.direction = 'inbound' AND latest.occurred_at < ?
+                )"
+                .to_string(),
+            );
+            params.push(Value::from(now_utc - days * 86_400));
+        }
+
+        if let Some(FollowupSelector::Pending) = self.followup {
+            clauses.push(
+                "EXISTS (SELECT 1 FROM interactions fi WHERE fi.contact_id = contacts.id AND fi.follow_up_at IS NOT NULL)"
+                    .to_string(),
+            );
+        }
+
+        Ok(WhereClause {
+            clauses,
+            params,
+            bounds,
+        })
+    }
+
+    pub fn to_sql(
+        &self,
+        now_utc: i64,
+        soon_days: i64,
+        local_offset: FixedOffset,
+    ) -> Result<SqlQuery> {
+        let WhereClause {
+            clauses,
+            mut params,
+            bounds,
+        } = self.where_clause(now_utc, soon_days, local_offset)?;
+
         let mut sql = String::from(
-            "SELECT id, display_name, email, phone, handle, timezone, next_touchpoint_at, cadence_days, created_at, updated_at, archived_at FROM contacts",
+            "SELECT id, display_name, email, phone, handle, timezone, next_touchpoint_at, cadence_days, created_at, updated_at, archived_at, organization FROM contacts",
         );
 
         if !clauses.is_empty() {
@@ -136,6 +372,13 @@ impl ContactQuery {
             sql.push_str(&clauses.join(" AND "));
         }
 
+        if self.sort == ContactSort::Archived {
+            sql.push_str(
+                " ORDER BY (archived_at IS NULL) ASC, archived_at DESC, display_name COLLATE NOCASE ASC",
+            );
+            return Ok(SqlQuery { sql, params });
+        }
+
         sql.push_str(
             " ORDER BY (archived_at IS NOT NULL) ASC,
             CASE
@@ -156,40 +399,111 @@ impl ContactQuery {
 
         Ok(SqlQuery { sql, params })
     }
-}
 
-#[derive(Debug, Clone, Copy)]
-pub struct DueBounds {
-    pub start_of_today: i64,
-    pub start_of_tomorrow: i64,
-    pub soon_end: i64,
-}
+    /// Builds the list view query: contact columns, the due bucket rank, the
+    /// contact's tag names joined by `TAG_SEPARATOR`, and the time and kind of
+    /// the latest interaction (read through `idx_interactions_contact_occurred`),
+    /// all in one statement.
+    pub fn to_list_sql(
+        &self,
+        now_utc: i64,
+        soon_days: i64,
+        local_offset: FixedOffset,
+        page: Option<ListPage>,
+    ) -> Result<SqlQuery> {
+        let WhereClause {
+            clauses,
+            params: where_params,
+            bounds,
+        } = self.where_clause(now_utc, soon_days, local_offset)?;
 
-pub fn due_bounds(now_utc: i64, soon_days: i64, local_offset: FixedOffset) -> DueBounds {
-    let now = DateTime::<Utc>::from_timestamp(now_utc, 0).expect("valid timestamp");
-    let local = now.with_timezone(&local_offset);
-    let local_date = local.date_naive();
-    let start_of_today_local = local_date.and_hms_opt(0, 0, 0).expect("midnight is valid");
-    let start_of_tomorrow_local = start_of_today_local + Duration::days(1);
-
-    let start_of_today = local_offset
-        .from_local_datetime(&start_of_today_local)
-        .single()
-        .expect("fixed offset conversion")
-        .with_timezone(&Utc)
-        .timestamp();
-    let start_of_tomorrow = local_offset
-        .from_local_datetime(&start_of_tomorrow_local)
-        .single()
-        .expect("fixed offset conversion")
-        .with_timezone(&Utc)
-        .timestamp();
-
-    let soon_end = start_of_tomorrow + Duration::days(soon_days).num_seconds();
-
-    DueBounds {
-        start_of_today,
-        start_of_tomorrow,
-        soon_end,
+        let mut params: Vec<Value> = Vec::new();
+        let mut sql = format!(
+            "SELECT contacts.id, contacts.display_name, contacts.email, contacts.phone, contacts.handle,
+                contacts.timezone, contacts.next_touchpoint_at, contacts.cadence_days,
+                contacts.created_at, contacts.updated_at, contacts.archived_at,
+                contacts.organization,
+                {DUE_RANK_SQL} AS due_rank,
+                group_concat(tags.name, char(31) ORDER BY tags.name) AS tag_names,
+                contacts.archive_reason,
+                CASE WHEN contacts.muted_until > ? THEN contacts.muted_until END AS muted_until,
+                (SELECT MAX(interactions.occurred_at) FROM interactions
+                 WHERE interactions.contact_id = contacts.id) AS last_interaction_at,
+                (SELECT interactions.kind FROM interactions
+                 WHERE interactions.contact_id = contacts.id
+                 ORDER BY interactions.occurred_at DESC, interactions.created_at DESC
+                 LIMIT 1) AS last_interaction_kind,
+                contacts.priority
+             FROM contacts
+             LEFT JOIN contact_tags ON contact_tags.contact_id = contacts.id
+             LEFT JOIN tags ON tags.id = contact_tags.tag_id"
+        );
+        params.push(Value::from(now_utc));
+        params.push(Value::from(bounds.start_of_today));
+        params.push(Value::from(bounds.start_of_tomorrow));
+        params.push(Value::from(bounds.start_of_tomorrow));
+        params.push(Value::from(bounds.soon_end));
+        params.push(Value::from(now_utc));
+
+        if !clauses.is_empty() {
+            sql.push_str(" WHERE ");
+            sql.push_str(&clauses.join(" AND "));
+        }
+        params.extend(where_params);
+
+        sql.push_str(" GROUP BY contacts.id");
+        match self.sort {
+            ContactSort::Due => sql.push_str(
+                " ORDER BY (contacts.archived_at IS NOT NULL) ASC,
+                 due_rank ASC,
+                 contacts.display_name COLLATE NOCASE ASC,
+                 contacts.id ASC",
+            ),
+            ContactSort::Archived => sql.push_str(
+                " ORDER BY (contacts.archived_at IS NULL) ASC,
+                 contacts.archived_at DESC,
+                 contacts.display_name COLLATE NOCASE ASC,
+                 contacts.id ASC",
+            ),
+        }
+
+        if let Some(page) = page {
+            sql.push_str(" LIMIT ? OFFSET ?");
+            params.push(Value::from(page.limit as i64));
+            params.push(Value::from(page.offset as i64));
+        }
+
+        Ok(SqlQuery { sql, params })
+    }
+
+    /// Counts matching contacts per due bucket; rows are `(due_rank, count)`.
+    pub fn to_due_counts_sql(
+        &self,
+        now_utc: i64,
+        soon_days: i64,
+        local_offset: FixedOffset,
+    ) -> Result<SqlQuery> {
+        let WhereClause {
+            clauses,
+            params: where_params,
+            bounds,
+        } = self.where_clause(now_utc, soon_days, local_offset)?;
+
+        let mut sql = format!("SELECT {DUE_RANK_SQL} AS due_rank, COUNT(*) FROM contacts");
+        let mut params = vec![
+            Value::from(now_utc),
+            Value::from(bounds.start_of_today),
+            Value::from(bounds.start_of_tomorrow),
+            Value::from(bounds.start_of_tomorrow),
+            Value::from(bounds.soon_end),
+        ];
+        if !clauses.is_empty() {
+            sql.push_str(" WHERE ");
+            sql.push_str(&clauses.join(" AND "));
+        }
+        params.extend(where_params);
+        sql.push_str(" GROUP BY due_rank");
+
+        Ok(SqlQuery { sql, params })
     }
 }
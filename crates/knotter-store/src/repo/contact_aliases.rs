@@ -0,0 +1,91 @@
+use crate::error::{Result, StoreError};
+use crate::temp_table::TempContactIdTable;
+use knotter_core::domain::{normalize_alias, ContactId};
+use knotter_core::CoreError;
+use rusqlite::{params, Connection};
+use std::collections::HashMap;
+use std::str::FromStr;
+
+pub struct ContactAliasesRepo<'a> {
+    conn: &'a Connection,
+}
+
+impl<'a> ContactAliasesRepo<'a> {
+    pub fn new(conn: &'a Connection) -> Self {
+        Self { conn }
+    }
+
+    /// Adds an alias; returns `false` when the contact already has it (ignoring case).
+    pub fn add(&self, now_utc: i64, contact_id: ContactId, alias: &str) -> Result<bool> {
+        add_inner(self.conn, now_utc, contact_id, alias)
+    }
+
+    /// Removes an alias (matched ignoring case); returns `false` when it was not set.
+    pub fn remove(&self, contact_id: ContactId, alias: &str) -> Result<bool> {
+        let Some(alias) = normalize_alias(alias) else {
+            return Ok(false);
+        };
+        let removed = self.conn.execute(
+            "DELETE FROM contact_aliases WHERE contact_id = ?1 AND alias = ?2;",
+            params![contact_id.to_string(), alias],
+        )?;
+        Ok(removed > 0)
+    }
+
+    pub fn list_for_contact(&self, contact_id: ContactId) -> Result<Vec<String>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT alias FROM contact_aliases
+             WHERE contact_id = ?1
+             ORDER BY alias ASC;",
+        )?;
+        let rows = stmt.query_map([contact_id.to_string()], |row| row.get(0))?;
+        let mut aliases = Vec::new();
+        for row in rows {
+            aliases.push(row?);
+        }
+        Ok(aliases)
+    }
+
+    pub fn list_for_contacts(
+        &self,
+        contact_ids: &[ContactId],
+    ) -> Result<HashMap<ContactId, Vec<String>>> {
+        let mut map: HashMap<ContactId, Vec<String>> = HashMap::new();
+        if contact_ids.is_empty() {
+            return Ok(map);
+        }
+
+        let temp_table = TempContactIdTable::create(self.conn, contact_ids)?;
+        let temp_table_name = temp_table.name();
+
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT ca.contact_id, ca.alias
+             FROM contact_aliases ca
+             INNER JOIN {temp_table_name} tmp ON tmp.id = ca.contact_id
+             ORDER BY ca.alias ASC;"
+        ))?;
+        let mut rows = stmt.query([])?;
+        while let Some(row) = rows.next()? {
+            let contact_id_raw: String = row.get(0)?;
+            let contact_id = ContactId::from_str(&contact_id_raw)
+                .map_err(|_| StoreError::InvalidId(contact_id_raw.clone()))?;
+            map.entry(contact_id).or_default().push(row.get(1)?);
+        }
+        Ok(map)
+    }
+}
+
+pub(crate) fn add_inner(
+    conn: &Connection,
+    now_utc: i64,
+    contact_id: ContactId,
+    alias: &str,
+) -> Result<bool> {
+    let alias = normalize_alias(alias).ok_or(StoreError::Core(CoreError::EmptyAlias))?;
+    let inserted = conn.execute(
+        "INSERT OR IGNORE INTO contact_aliases (contact_id, alias, created_at)
+         VALUES (?1, ?2, ?3);",
+        params![contact_id.to_string(), alias, now_utc],
+    )?;
+    Ok(inserted > 0)
+}
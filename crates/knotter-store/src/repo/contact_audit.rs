@@ -0,0 +1,251 @@
+use crate::error::{Result, StoreError};
+use crate::temp_table::TempContactIdTable;
+use knotter_core::domain::{Contact, ContactId};
+use rusqlite::{params, Connection};
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+use std::str::FromStr;
+
+/// What an audit entry records.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AuditAction {
+    Update,
+    Archive,
+    Unarchive,
+    /// Another contact was merged into this one.
+    Merge,
+    Trash,
+    Restore,
+    Delete,
+}
+
+impl AuditAction {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AuditAction::Update => "update",
+            AuditAction::Archive => "archive",
+            AuditAction::Unarchive => "unarchive",
+            AuditAction::Merge => "merge",
+            AuditAction::Trash => "trash",
+            AuditAction::Restore => "restore",
+            AuditAction::Delete => "delete",
+        }
+    }
+}
+
+/// A field's value before and after a change; `Null` when it was unset.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldChange {
+    pub field: String,
+    pub old: Value,
+    pub new: Value,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct AuditEntry {
+    pub contact_id: ContactId,
+    pub action: String,
+    pub origin: String,
+    /// Changed fields in name order.
+    pub changes: Vec<FieldChange>,
+    pub detail: Option<String>,
+    pub occurred_at: i64,
+}
+
+pub struct ContactAuditRepo<'a> {
+    conn: &'a Connection,
+}
+
+impl<'a> ContactAuditRepo<'a> {
+    pub fn new(conn: &'a Connection) -> Self {
+        Self { conn }
+    }
+
+    /// Entries for the contact, newest first. Entries of a deleted contact are
+    /// kept, so this works for ids that no longer resolve.
+    pub fn list_for_contact(
+        &self,
+        contact_id: ContactId,
+        limit: Option<usize>,
+    ) -> Result<Vec<AuditEntry>> {
+        let limit = limit.map(|limit| limit as i64).unwrap_or(-1);
+        let mut stmt = self.conn.prepare(
+            "SELECT contact_id, action, origin, changes, detail, occurred_at
+             FROM contact_audit
+             WHERE contact_id = ?1
+             ORDER BY occurred_at DESC, id DESC
+             LIMIT ?2;",
+        )?;
+        let mut rows = stmt.query(params![contact_id.to_string(), limit])?;
+        let mut entries = Vec::new();
+        while let Some(row) = rows.next()? {
+            entries.push(entry_from_row(row)?);
+        }
+        Ok(entries)
+    }
+
+    /// Entries for each contact, newest first.
+    pub fn list_for_contacts(
+        &self,
+        contact_ids: &[ContactId],
+    ) -> Result<HashMap<ContactId, Vec<AuditEntry>>> {
+        let mut map: HashMap<ContactId, Vec<AuditEntry>> = HashMap::new();
+        if contact_ids.is_empty() {
+            return Ok(map);
+        }
+
+        let temp_table = TempContactIdTable::create(self.conn, contact_ids)?;
+        let temp_table_name = temp_table.name();
+
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT a.contact_id, a.action, a.origin, a.changes, a.detail, a.occurred_at
+             FROM contact_audit a
+             INNER JOIN {temp_table_name} tmp ON tmp.id = a.contact_id
+             ORDER BY a.occurred_at DESC, a.id DESC;"
+        ))?;
+        let mut rows = stmt.query([])?;
+        while let Some(row) = rows.next()? {
+            let entry = entry_from_row(row)?;
+            map.entry(entry.contact_id).or_default().push(entry);
+        }
+        Ok(map)
+    }
+}
+
+/// Fields that differ between two versions of a contact, in name order.
+/// `updated_at` is bookkeeping and never counts as a change.
+pub(crate) fn diff_contacts(before: &Contact, after: &Contact) -> Vec<FieldChange> {
+    let mut changes = Vec::new();
+    let mut compare = |field: &str, old: Value, new: Value| {
+        if old != new {
+            changes.push(FieldChange {
+                field: field.to_string(),
+                old,
+                new,
+            });
+        }
+    };
+    compare(
+        "archived_at",
+        before.archived_at.into(),
+        after.archived_at.into(),
+    );
+    compare(
+        "cadence_days",
+        before.cadence_days.into(),
+        after.cadence_days.into(),
+    );
+    compare(
+        "display_name",
+        before.display_name.as_str().into(),
+        after.display_name.as_str().into(),
+    );
+    compare(
+        "email",
+        before.email.as_deref().into(),
+        after.email.as_deref().into(),
+    );
+    compare(
+        "handle",
+        before.handle.as_deref().into(),
+        after.handle.as_deref().into(),
+    );
+    compare(
+        "next_touchpoint_at",
+        before.next_touchpoint_at.into(),
+        after.next_touchpoint_at.into(),
+    );
+    compare(
+        "organization",
+        before.organization.as_deref().into(),
+        after.organization.as_deref().into(),
+    );
+    compare(
+        "phone",
+        before.phone.as_deref().into(),
+        after.phone.as_deref().into(),
+    );
+    compare(
+        "timezone",
+        before.timezone.as_deref().into(),
+        after.timezone.as_deref().into(),
+    );
+    changes
+}
+
+/// How an update reads in the log: flipping `archived_at` is an archive or
+/// unarchive, anything else an update.
+pub(crate) fn update_action(before: &Contact, after: &Contact) -> AuditAction {
+    match (before.archived_at, after.archived_at) {
+        (None, Some(_)) => AuditAction::Archive,
+        (Some(_), None) => AuditAction::Unarchive,
+        _ => AuditAction::Update,
+    }
+}
+
+/// Appends an entry. Updates that changed nothing are not recorded, so
+/// imports that rewrite identical values leave no trace.
+pub(crate) fn record_inner(
+    conn: &Connection,
+    now_utc: i64,
+    contact_id: ContactId,
+    action: AuditAction,
+    origin: &str,
+    changes: &[FieldChange],
+    detail: Option<&str>,
+) -> Result<()> {
+    if action == AuditAction::Update && changes.is_empty() {
+        return Ok(());
+    }
+    let mut diff = Map::new();
+    for change in changes {
+        diff.insert(
+            change.field.clone(),
+            Value::Array(vec![change.old.clone(), change.new.clone()]),
+        );
+    }
+    conn.execute(
+        "INSERT INTO contact_audit (contact_id, action, origin, changes, detail, occurred_at)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6);",
+        params![
+            contact_id.to_string(),
+            action.as_str(),
+            origin,
+            Value::Object(diff).to_string(),
+            detail,
+            now_utc
+        ],
+    )?;
+    Ok(())
+}
+
+fn entry_from_row(row: &rusqlite::Row<'_>) -> Result<AuditEntry> {
+    let contact_id_raw: String = row.get(0)?;
+    let contact_id = ContactId::from_str(&contact_id_raw)
+        .map_err(|_| StoreError::InvalidId(contact_id_raw.clone()))?;
+    let changes_raw: String = row.get(3)?;
+    let changes = match serde_json::from_str::<Value>(&changes_raw) {
+        Ok(Value::Object(diff)) => diff
+            .into_iter()
+            .map(|(field, values)| {
+                let (old, new) = match values {
+                    Value::Array(mut pair) if pair.len() == 2 => {
+                        let new = pair.pop().unwrap_or(Value::Null);
+                        (pair.pop().unwrap_or(Value::Null), new)
+                    }
+                    other => (Value::Null, other),
+                };
+                FieldChange { field, old, new }
+            })
+            .collect(),
+        _ => Vec::new(),
+    };
+    Ok(AuditEntry {
+        contact_id,
+        action: row.get(1)?,
+        origin: row.get(2)?,
+        changes,
+        detail: row.get(4)?,
+        occurred_at: row.get(5)?,
+    })
+}
@@ -1,10 +1,10 @@
 use crate::error::{Result, StoreError};
 use crate::temp_table::TempContactIdTable;
-use chrono::{Datelike, FixedOffset};
+use chrono::{Datelike, FixedOffset, NaiveDate};
 use knotter_core::domain::{
     normalize_contact_date_label, ContactDate, ContactDateId, ContactDateKind, ContactId,
 };
-use knotter_core::rules::{is_leap_year, local_today};
+use knotter_core::rules::{date_in_lead_window, is_leap_year, local_today};
 use rusqlite::{params, Connection, Row};
 use std::collections::HashMap;
 use std::str::FromStr;
@@ -18,6 +18,7 @@ pub struct ContactDateNew {
     pub day: u8,
     pub year: Option<i32>,
     pub source: Option<String>,
+    pub remind_days_before: Option<u16>,
 }
 
 #[derive(Debug, Clone)]
@@ -31,6 +32,13 @@ pub struct ContactDateOccurrence {
     pub year: Option<i32>,
 }
 
+/// A date whose lead window includes today, `days_until` days before it occurs.
+#[derive(Debug, Clone)]
+pub struct UpcomingContactDate {
+    pub occurrence: ContactDateOccurrence,
+    pub days_until: i64,
+}
+
 #[derive(Debug, Clone, Copy)]
 pub enum ContactDateUpsertPolicy {
     OverwriteYear,
@@ -73,27 +81,38 @@ impl<'a> ContactDatesRepo<'a> {
             created_at: now_utc,
             updated_at: now_utc,
             source: input.source.clone(),
+            remind_days_before: input.remind_days_before,
         };
         contact_date.validate()?;
 
         let sql = match policy {
             ContactDateUpsertPolicy::OverwriteYear => {
                 "INSERT INTO contact_dates
-                 (id, contact_id, kind, label, month, day, year, created_at, updated_at, source)
-                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)
+                 (id, contact_id, kind, label, month, day, year, created_at, updated_at, source,
+                  remind_days_before)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)
                  ON CONFLICT(contact_id, kind, label, month, day) DO UPDATE SET
                     year = excluded.year,
                     updated_at = excluded.updated_at,
-                    source = COALESCE(excluded.source, contact_dates.source);"
+                    source = COALESCE(excluded.source, contact_dates.source),
+                    remind_days_before = COALESCE(
+                        excluded.remind_days_before,
+                        contact_dates.remind_days_before
+                    );"
             }
             ContactDateUpsertPolicy::PreserveYear => {
                 "INSERT INTO contact_dates
-                 (id, contact_id, kind, label, month, day, year, created_at, updated_at, source)
-                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)
+                 (id, contact_id, kind, label, month, day, year, created_at, updated_at, source,
+                  remind_days_before)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)
                  ON CONFLICT(contact_id, kind, label, month, day) DO UPDATE SET
                     year = COALESCE(excluded.year, contact_dates.year),
                     updated_at = excluded.updated_at,
-                    source = COALESCE(excluded.source, contact_dates.source);"
+                    source = COALESCE(excluded.source, contact_dates.source),
+                    remind_days_before = COALESCE(
+                        excluded.remind_days_before,
+                        contact_dates.remind_days_before
+                    );"
             }
         };
 
@@ -110,6 +129,7 @@ impl<'a> ContactDatesRepo<'a> {
                 contact_date.created_at,
                 contact_date.updated_at,
                 contact_date.source,
+                contact_date.remind_days_before,
             ],
         )?;
 
@@ -125,7 +145,8 @@ impl<'a> ContactDatesRepo<'a> {
 
     pub fn list_for_contact(&self, contact_id: ContactId) -> Result<Vec<ContactDate>> {
         let mut stmt = self.conn.prepare(
-            "SELECT id, contact_id, kind, label, month, day, year, created_at, updated_at, source
+            "SELECT id, contact_id, kind, label, month, day, year, created_at, updated_at, source,
+                    remind_days_before
              FROM contact_dates
              WHERE contact_id = ?1
              ORDER BY kind ASC, month ASC, day ASC, label ASC;",
@@ -160,7 +181,8 @@ impl<'a> ContactDatesRepo<'a> {
                     d.year,
                     d.created_at,
                     d.updated_at,
-                    d.source
+                    d.source,
+                    d.remind_days_before
              FROM contact_dates d
              INNER JOIN {temp_table_name} tmp ON tmp.id = d.contact_id
              ORDER BY d.contact_id ASC,
@@ -185,15 +207,22 @@ impl<'a> ContactDatesRepo<'a> {
         local_offset: FixedOffset,
     ) -> Result<Vec<ContactDateOccurrence>> {
         let today = local_today(now_utc, local_offset)?;
-        let month = today.month() as u8;
-        let day = today.day() as u8;
-        let include_feb_29 = month == 2 && day == 28 && !is_leap_year(today.year());
+        self.list_for_day(today)
+    }
+
+    /// Dates (birthdays, name days, custom) that occur on `date`, with Feb 29
+    /// dates falling on Feb 28 in non-leap years.
+    pub fn list_for_day(&self, date: NaiveDate) -> Result<Vec<ContactDateOccurrence>> {
+        let month = date.month() as u8;
+        let day = date.day() as u8;
+        let include_feb_29 = month == 2 && day == 28 && !is_leap_year(date.year());
 
         let sql = if include_feb_29 {
             "SELECT d.contact_id, c.display_name, d.kind, d.label, d.month, d.day, d.year
              FROM contact_dates d
              JOIN contacts c ON c.id = d.contact_id
              WHERE c.archived_at IS NULL
+               AND c.deleted_at IS NULL
                AND ((d.month = ?1 AND d.day = ?2) OR (d.month = 2 AND d.day = 29))
              ORDER BY c.display_name COLLATE NOCASE ASC;"
         } else {
@@ -201,6 +230,7 @@ impl<'a> ContactDatesRepo<'a> {
              FROM contact_dates d
              JOIN contacts c ON c.id = d.contact_id
              WHERE c.archived_at IS NULL
+               AND c.deleted_at IS NULL
                AND d.month = ?1
                AND d.day = ?2
              ORDER BY c.display_name COLLATE NOCASE ASC;"
@@ -215,6 +245,45 @@ impl<'a> ContactDatesRepo<'a> {
         Ok(items)
     }
 
+    /// Dates with a lead time whose window includes today (not counting the
+    /// day itself, which `list_today` covers), soonest first.
+    pub fn list_upcoming(
+        &self,
+        now_utc: i64,
+        local_offset: FixedOffset,
+    ) -> Result<Vec<UpcomingContactDate>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT d.contact_id, c.display_name, d.kind, d.label, d.month, d.day, d.year,
+                    d.remind_days_before
+             FROM contact_dates d
+             JOIN contacts c ON c.id = d.contact_id
+             WHERE c.archived_at IS NULL
+               AND c.deleted_at IS NULL
+               AND d.remind_days_before > 0
+             ORDER BY c.display_name COLLATE NOCASE ASC;",
+        )?;
+        let mut rows = stmt.query([])?;
+        let mut items = Vec::new();
+        while let Some(row) = rows.next()? {
+            let occurrence = contact_date_occurrence_from_row(row)?;
+            let lead_days: u16 = row.get(7)?;
+            if let Some(days_until) = date_in_lead_window(
+                now_utc,
+                occurrence.month,
+                occurrence.day,
+                lead_days,
+                local_offset,
+            )? {
+                items.push(UpcomingContactDate {
+                    occurrence,
+                    days_until,
+                });
+            }
+        }
+        items.sort_by_key(|item| item.days_until);
+        Ok(items)
+    }
+
     pub fn delete(&self, id: ContactDateId) -> Result<()> {
         let updated = self
             .conn
@@ -234,7 +303,8 @@ impl<'a> ContactDatesRepo<'a> {
         day: u8,
     ) -> Result<Option<ContactDate>> {
         let mut stmt = self.conn.prepare(
-            "SELECT id, contact_id, kind, label, month, day, year, created_at, updated_at, source
+            "SELECT id, contact_id, kind, label, month, day, year, created_at, updated_at, source,
+                    remind_days_before
              FROM contact_dates
              WHERE contact_id = ?1 AND kind = ?2 AND label = ?3 AND month = ?4 AND day = ?5
              LIMIT 1;",
@@ -265,6 +335,7 @@ fn contact_date_from_row(row: &Row<'_>) -> Result<ContactDate> {
     let created_at: i64 = row.get(7)?;
     let updated_at: i64 = row.get(8)?;
     let source: Option<String> = row.get(9)?;
+    let remind_days_before: Option<u16> = row.get(10)?;
 
     let id = ContactDateId::from_str(&id).map_err(|_| StoreError::InvalidId(id))?;
     let contact_id =
@@ -287,6 +358,7 @@ fn contact_date_from_row(row: &Row<'_>) -> Result<ContactDate> {
         created_at,
         updated_at,
         source,
+        remind_days_before,
     })
 }
 
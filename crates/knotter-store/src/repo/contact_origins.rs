@@ -0,0 +1,178 @@
+use crate::error::{Result, StoreError};
+use crate::temp_table::TempContactIdTable;
+use chrono::NaiveDate;
+use knotter_core::domain::{ContactId, ContactOrigin};
+use knotter_core::CoreError;
+use rusqlite::{params, Connection, OptionalExtension, Row};
+use std::collections::HashMap;
+use std::str::FromStr;
+
+const MET_ON_FORMAT: &str = "%Y-%m-%d";
+
+/// Partial change to a contact's origin; `Some(None)` clears a field.
+#[derive(Debug, Clone, Default)]
+pub struct ContactOriginUpdate {
+    pub met_at: Option<Option<String>>,
+    pub met_on: Option<Option<NaiveDate>>,
+    pub met_via: Option<Option<ContactId>>,
+}
+
+impl ContactOriginUpdate {
+    pub fn is_empty(&self) -> bool {
+        self.met_at.is_none() && self.met_on.is_none() && self.met_via.is_none()
+    }
+}
+
+pub struct ContactOriginsRepo<'a> {
+    conn: &'a Connection,
+}
+
+impl<'a> ContactOriginsRepo<'a> {
+    pub fn new(conn: &'a Connection) -> Self {
+        Self { conn }
+    }
+
+    pub fn get(&self, contact_id: ContactId) -> Result<Option<ContactOrigin>> {
+        get_inner(self.conn, contact_id)
+    }
+
+    /// Applies `update` and returns the resulting origin (`None` once every
+    /// field is cleared). A newly set introducer must exist and not be trashed.
+    pub fn update(
+        &self,
+        now_utc: i64,
+        contact_id: ContactId,
+        update: ContactOriginUpdate,
+    ) -> Result<Option<ContactOrigin>> {
+        update_inner(self.conn, now_utc, contact_id, update)
+    }
+
+    pub fn list_for_contacts(
+        &self,
+        contact_ids: &[ContactId],
+    ) -> Result<HashMap<ContactId, ContactOrigin>> {
+        let mut map = HashMap::new();
+        if contact_ids.is_empty() {
+            return Ok(map);
+        }
+
+        let temp_table = TempContactIdTable::create(self.conn, contact_ids)?;
+        let temp_table_name = temp_table.name();
+
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT co.contact_id, co.met_at, co.met_on, co.met_via_contact_id
+             FROM contact_origins co
+             INNER JOIN {temp_table_name} tmp ON tmp.id = co.contact_id;"
+        ))?;
+        let mut rows = stmt.query([])?;
+        while let Some(row) = rows.next()? {
+            let contact_id = parse_contact_id(row.get(0)?)?;
+            let origin = origin_from_row(row, 1)?;
+            if !origin.is_empty() {
+                map.insert(contact_id, origin);
+            }
+        }
+        Ok(map)
+    }
+}
+
+pub(crate) fn update_inner(
+    conn: &Connection,
+    now_utc: i64,
+    contact_id: ContactId,
+    update: ContactOriginUpdate,
+) -> Result<Option<ContactOrigin>> {
+    let mut origin = get_inner(conn, contact_id)?.unwrap_or_default();
+    let new_via = update.met_via.flatten();
+    if let Some(met_at) = update.met_at {
+        origin.met_at = met_at.and_then(|value| {
+            let trimmed = value.trim();
+            (!trimmed.is_empty()).then(|| trimmed.to_string())
+        });
+    }
+    if let Some(met_on) = update.met_on {
+        origin.met_on = met_on;
+    }
+    if let Some(met_via) = update.met_via {
+        origin.met_via = met_via;
+    }
+    origin.validate(contact_id)?;
+    if let Some(via) = new_via {
+        let exists = conn
+            .query_row(
+                "SELECT 1 FROM contacts WHERE id = ?1 AND deleted_at IS NULL;",
+                [via.to_string()],
+                |_| Ok(()),
+            )
+            .optional()?
+            .is_some();
+        if !exists {
+            return Err(StoreError::NotFound(via.to_string()));
+        }
+    }
+
+    if origin.is_empty() {
+        conn.execute(
+            "DELETE FROM contact_origins WHERE contact_id = ?1;",
+            [contact_id.to_string()],
+        )?;
+        return Ok(None);
+    }
+    conn.execute(
+        "INSERT INTO contact_origins (contact_id, met_at, met_on, met_via_contact_id, updated_at)
+         VALUES (?1, ?2, ?3, ?4, ?5)
+         ON CONFLICT(contact_id) DO UPDATE SET
+             met_at = excluded.met_at,
+             met_on = excluded.met_on,
+             met_via_contact_id = excluded.met_via_contact_id,
+             updated_at = excluded.updated_at;",
+        params![
+            contact_id.to_string(),
+            origin.met_at,
+            origin
+                .met_on
+                .map(|date| date.format(MET_ON_FORMAT).to_string()),
+            origin.met_via.map(|id| id.to_string()),
+            now_utc,
+        ],
+    )?;
+    Ok(Some(origin))
+}
+
+fn get_inner(conn: &Connection, contact_id: ContactId) -> Result<Option<ContactOrigin>> {
+    let mut stmt = conn.prepare(
+        "SELECT met_at, met_on, met_via_contact_id
+         FROM contact_origins WHERE contact_id = ?1;",
+    )?;
+    let mut rows = stmt.query([contact_id.to_string()])?;
+    match rows.next()? {
+        Some(row) => {
+            let origin = origin_from_row(row, 0)?;
+            Ok((!origin.is_empty()).then_some(origin))
+        }
+        None => Ok(None),
+    }
+}
+
+fn origin_from_row(row: &Row<'_>, start: usize) -> Result<ContactOrigin> {
+    let met_on = row
+        .get::<_, Option<String>>(start + 1)?
+        .map(|raw| {
+            NaiveDate::parse_from_str(&raw, MET_ON_FORMAT)
+                .map_err(|_| StoreError::Core(CoreError::InvalidTimestamp))
+        })
+        .transpose()?;
+    let met_via = row
+        .get::<_, Option<String>>(start + 2)?
+        .map(parse_contact_id)
+        .transpose()?;
+    Ok(ContactOrigin {
+        met_at: row.get(start)?,
+        met_on,
+        met_via,
+    })
+}
+
+fn parse_contact_id(raw: String) -> Result<ContactId> {
+    ContactId::from_str(&raw).map_err(|_| StoreError::InvalidId(raw))
+}
@@ -1,14 +1,22 @@
 use crate::error::{Result, StoreError};
-use crate::query::{due_bounds, ContactQuery};
+use crate::query::{due_bounds, ContactListQuery, ContactQuery, ListPage, TAG_SEPARATOR};
+use crate::repo::contact_audit::{self, AuditAction};
+use crate::repo::emails::ContactEmail;
+use crate::repo::field_provenance::{ContactField, MANUAL_SOURCE};
 use crate::repo::merge_candidates::MergeCandidateStatus;
 use crate::temp_table::TempContactIdTable;
 use chrono::FixedOffset;
-use knotter_core::domain::{normalize_email, Contact, ContactId, TagName};
-use knotter_core::rules::validate_soon_days;
-use rusqlite::{params, params_from_iter, Connection};
+use knotter_core::domain::{
+    name_match_key, normalize_alias, normalize_email, validate_contact_priority, Contact,
+    ContactId, TagName,
+};
+use knotter_core::dto::ContactListItemDto;
+use knotter_core::rules::{validate_soon_days, DueState};
+use rusqlite::{params, params_from_iter, Connection, OptionalExtension};
+use std::collections::HashMap;
 use std::str::FromStr;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct ContactNew {
     pub display_name: String,
     pub email: Option<String>,
@@ -18,6 +26,47 @@ pub struct ContactNew {
     pub next_touchpoint_at: Option<i64>,
     pub cadence_days: Option<i32>,
     pub archived_at: Option<i64>,
+    pub organization: Option<String>,
+}
+
+/// A contact as shown in list views, with its due bucket and tag names.
+#[derive(Debug, Clone)]
+pub struct ContactListRow {
+    pub contact: Contact,
+    pub due_state: DueState,
+    pub tags: Vec<String>,
+    pub archive_reason: Option<String>,
+    /// End of the contact's mute; `None` unless the mute is still in effect.
+    pub muted_until: Option<i64>,
+    pub last_interaction_at: Option<i64>,
+    /// Stored kind of the latest interaction (`call`, `other:<label>`, ...).
+    pub last_interaction_kind: Option<String>,
+    pub priority: i32,
+}
+
+/// Rows that go away (or get dismissed) when a contact is deleted.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ContactDeleteImpact {
+    pub interactions: usize,
+    pub emails: usize,
+    pub dates: usize,
+    pub tags: usize,
+    pub open_merge_candidates: usize,
+}
+
+/// A soft-deleted contact waiting in the trash.
+#[derive(Debug, Clone)]
+pub struct TrashedContact {
+    pub contact: Contact,
+    pub deleted_at: i64,
+}
+
+/// Result of restoring a contact from the trash.
+#[derive(Debug, Clone)]
+pub struct ContactRestore {
+    pub contact: Contact,
+    /// Emails claimed by another contact while this one was trashed; they are not restored.
+    pub dropped_emails: Vec<String>,
 }
 
 #[derive(Debug, Clone, Default)]
@@ -31,6 +80,9 @@ pub struct ContactUpdate {
     pub next_touchpoint_at: Option<Option<i64>>,
     pub cadence_days: Option<Option<i32>>,
     pub archived_at: Option<Option<i64>>,
+    pub organization: Option<Option<String>>,
+    /// Provenance recorded for changed fields; `None` records a manual edit.
+    pub source: Option<String>,
 }
 
 #[derive(Debug, Clone)]
@@ -61,6 +113,16 @@ pub enum MergeTouchpointPreference {
     Secondary,
     Earliest,
     Latest,
+    /// Keep the touchpoint of whichever contact supplied the merged cadence.
+    Cadence,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MergeCadencePreference {
+    /// Follow `prefer`, falling back to the other contact's cadence.
+    Preferred,
+    Shortest,
+    Longest,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -70,11 +132,27 @@ pub enum MergeArchivedPreference {
     Secondary,
 }
 
+/// Per-field winners that take precedence over `prefer` and the cadence and
+/// touchpoint rules. A chosen side without a value falls back to the other side.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MergeFieldOverrides {
+    pub display_name: Option<MergePreference>,
+    /// Which contact's primary email stays primary; all emails are kept.
+    pub email: Option<MergePreference>,
+    pub phone: Option<MergePreference>,
+    pub handle: Option<MergePreference>,
+    pub timezone: Option<MergePreference>,
+    pub cadence_days: Option<MergePreference>,
+    pub next_touchpoint_at: Option<MergePreference>,
+}
+
 #[derive(Debug, Clone)]
 pub struct ContactMergeOptions {
     pub prefer: MergePreference,
     pub touchpoint: MergeTouchpointPreference,
+    pub cadence: MergeCadencePreference,
     pub archived: MergeArchivedPreference,
+    pub fields: MergeFieldOverrides,
 }
 
 impl Default for ContactMergeOptions {
@@ -82,18 +160,49 @@ impl Default for ContactMergeOptions {
         Self {
             prefer: MergePreference::Primary,
             touchpoint: MergeTouchpointPreference::Earliest,
+            cadence: MergeCadencePreference::Preferred,
             archived: MergeArchivedPreference::ActiveIfAny,
+            fields: MergeFieldOverrides::default(),
         }
     }
 }
 
+/// What `merge_contacts` would produce for the same contacts and options,
+/// computed without writing anything.
+#[derive(Debug, Clone)]
+pub struct MergedContactPreview {
+    /// The primary contact as it would look after the merge; `email` is the
+    /// primary email it would keep.
+    pub contact: Contact,
+    /// Every email the merged contact would have, primary first.
+    pub emails: Vec<String>,
+    /// Union of both contacts' tags, sorted.
+    pub tags: Vec<String>,
+    /// Display names that lose and would be kept as aliases.
+    pub new_aliases: Vec<String>,
+    pub interactions_moved: usize,
+    pub dates_moved: usize,
+}
+
 pub struct ContactsRepo<'a> {
     conn: &'a Connection,
+    /// Audit origin for changes that do not name their own source.
+    origin: &'a str,
 }
 
 impl<'a> ContactsRepo<'a> {
     pub fn new(conn: &'a Connection) -> Self {
-        Self { conn }
+        Self {
+            conn,
+            origin: MANUAL_SOURCE,
+        }
+    }
+
+    /// Records changes made through this repo under `origin` in the audit log
+    /// (`manual` by default). An update's own `source` still takes precedence.
+    pub fn with_origin(mut self, origin: &'a str) -> Self {
+        self.origin = origin;
+        self
     }
 
     pub fn create(&self, now_utc: i64, input: ContactNew) -> Result<Contact> {
@@ -149,8 +258,8 @@ impl<'a> ContactsRepo<'a> {
 
     pub fn get(&self, id: ContactId) -> Result<Option<Contact>> {
         let mut stmt = self.conn.prepare(
-            "SELECT id, display_name, email, phone, handle, timezone, next_touchpoint_at, cadence_days, created_at, updated_at, archived_at
-             FROM contacts WHERE id = ?1;",
+            "SELECT id, display_name, email, phone, handle, timezone, next_touchpoint_at, cadence_days, created_at, updated_at, archived_at, organization
+             FROM contacts WHERE id = ?1 AND deleted_at IS NULL;",
         )?;
         let mut rows = stmt.query([id.to_string()])?;
         if let Some(row) = rows.next()? {
@@ -162,10 +271,10 @@ impl<'a> ContactsRepo<'a> {
 
     pub fn list_by_email(&self, email: &str) -> Result<Vec<Contact>> {
         let mut stmt = self.conn.prepare(
-            "SELECT c.id, c.display_name, c.email, c.phone, c.handle, c.timezone, c.next_touchpoint_at, c.cadence_days, c.created_at, c.updated_at, c.archived_at
+            "SELECT c.id, c.display_name, c.email, c.phone, c.handle, c.timezone, c.next_touchpoint_at, c.cadence_days, c.created_at, c.updated_at, c.archived_at, c.organization
              FROM contacts c
              INNER JOIN contact_emails ce ON ce.contact_id = c.id
-             WHERE ce.email = ?1
+             WHERE ce.email = ?1 AND c.deleted_at IS NULL
              ORDER BY (c.archived_at IS NOT NULL) ASC, c.updated_at DESC;",
         )?;
         let mut rows = stmt.query([normalize_email(email).unwrap_or_else(|| email.to_string())])?;
@@ -176,18 +285,20 @@ impl<'a> ContactsRepo<'a> {
         Ok(contacts)
     }
 
+    /// Contacts whose display name matches `name` ignoring case, extra
+    /// whitespace, and diacritics (see `name_match_key`).
     pub fn list_by_display_name(&self, name: &str) -> Result<Vec<Contact>> {
-        let trimmed = name.trim();
-        if trimmed.is_empty() {
+        let key = name_match_key(name);
+        if key.is_empty() {
             return Ok(Vec::new());
         }
         let mut stmt = self.conn.prepare(
-            "SELECT id, display_name, email, phone, handle, timezone, next_touchpoint_at, cadence_days, created_at, updated_at, archived_at
+            "SELECT id, display_name, email, phone, handle, timezone, next_touchpoint_at, cadence_days, created_at, updated_at, archived_at, organization
              FROM contacts
-             WHERE display_name = ?1 COLLATE NOCASE
+             WHERE name_key = ?1 AND deleted_at IS NULL
              ORDER BY (archived_at IS NOT NULL) ASC, updated_at DESC;",
         )?;
-        let mut rows = stmt.query([trimmed])?;
+        let mut rows = stmt.query([key])?;
         let mut contacts = Vec::new();
         while let Some(row) = rows.next()? {
             contacts.push(contact_from_row(row)?);
@@ -195,15 +306,45 @@ impl<'a> ContactsRepo<'a> {
         Ok(contacts)
     }
 
+    /// Contacts with `alias` as a nickname (case-insensitive).
+    pub fn list_by_alias(&self, alias: &str) -> Result<Vec<Contact>> {
+        let Some(alias) = normalize_alias(alias) else {
+            return Ok(Vec::new());
+        };
+        let mut stmt = self.conn.prepare(
+            "SELECT c.id, c.display_name, c.email, c.phone, c.handle, c.timezone, c.next_touchpoint_at, c.cadence_days, c.created_at, c.updated_at, c.archived_at, c.organization
+             FROM contacts c
+             INNER JOIN contact_aliases ca ON ca.contact_id = c.id
+             WHERE ca.alias = ?1 AND c.deleted_at IS NULL
+             ORDER BY (c.archived_at IS NOT NULL) ASC, c.updated_at DESC;",
+        )?;
+        let mut rows = stmt.query([alias])?;
+        let mut contacts = Vec::new();
+        while let Some(row) = rows.next()? {
+            contacts.push(contact_from_row(row)?);
+        }
+        Ok(contacts)
+    }
+
+    /// Display-name matches, or alias matches when no display name matches, so
+    /// an exact name always wins over a nickname.
+    pub fn list_by_name_or_alias(&self, name: &str) -> Result<Vec<Contact>> {
+        let by_name = self.list_by_display_name(name)?;
+        if !by_name.is_empty() {
+            return Ok(by_name);
+        }
+        self.list_by_alias(name)
+    }
+
     pub fn list_by_handle(&self, handle: &str) -> Result<Vec<Contact>> {
         let trimmed = handle.trim();
         if trimmed.is_empty() {
             return Ok(Vec::new());
         }
         let mut stmt = self.conn.prepare(
-            "SELECT id, display_name, email, phone, handle, timezone, next_touchpoint_at, cadence_days, created_at, updated_at, archived_at
+            "SELECT id, display_name, email, phone, handle, timezone, next_touchpoint_at, cadence_days, created_at, updated_at, archived_at, organization
              FROM contacts
-             WHERE handle = ?1 COLLATE NOCASE
+             WHERE handle = ?1 COLLATE NOCASE AND deleted_at IS NULL
              ORDER BY (archived_at IS NOT NULL) ASC, updated_at DESC;",
         )?;
         let mut rows = stmt.query([trimmed])?;
@@ -217,11 +358,11 @@ impl<'a> ContactsRepo<'a> {
     pub fn update(&self, now_utc: i64, id: ContactId, update: ContactUpdate) -> Result<Contact> {
         if self.conn.is_autocommit() {
             let tx = self.conn.unchecked_transaction()?;
-            let contact = update_inner(&tx, now_utc, id, update)?;
+            let contact = update_inner(&tx, self.origin, now_utc, id, update)?;
             tx.commit()?;
             Ok(contact)
         } else {
-            update_inner(self.conn, now_utc, id, update)
+            update_inner(self.conn, self.origin, now_utc, id, update)
         }
     }
 
@@ -234,22 +375,101 @@ impl<'a> ContactsRepo<'a> {
     ) -> Result<Contact> {
         if self.conn.is_autocommit() {
             let tx = self.conn.unchecked_transaction()?;
-            let contact = update_with_email_ops_inner(&tx, now_utc, id, update, email_ops)?;
+            let contact =
+                update_with_email_ops_inner(&tx, self.origin, now_utc, id, update, email_ops)?;
             tx.commit()?;
             Ok(contact)
         } else {
-            update_with_email_ops_inner(self.conn, now_utc, id, update, email_ops)
+            update_with_email_ops_inner(self.conn, self.origin, now_utc, id, update, email_ops)
         }
     }
 
     pub fn delete(&self, now_utc: i64, id: ContactId) -> Result<()> {
         if self.conn.is_autocommit() {
             let tx = self.conn.unchecked_transaction()?;
-            delete_inner(&tx, now_utc, id)?;
+            delete_inner(&tx, self.origin, now_utc, id)?;
             tx.commit()?;
             Ok(())
         } else {
-            delete_inner(self.conn, now_utc, id)
+            delete_inner(self.conn, self.origin, now_utc, id)
+        }
+    }
+
+    pub fn delete_impact(&self, id: ContactId) -> Result<ContactDeleteImpact> {
+        let id_key = id.to_string();
+        let count = |sql: &str| -> Result<usize> {
+            let value: i64 = self.conn.query_row(sql, [&id_key], |row| row.get(0))?;
+            Ok(value as usize)
+        };
+        Ok(ContactDeleteImpact {
+            interactions: count("SELECT COUNT(*) FROM interactions WHERE contact_id = ?1;")?,
+            emails: count("SELECT COUNT(*) FROM contact_emails WHERE contact_id = ?1;")?,
+            dates: count("SELECT COUNT(*) FROM contact_dates WHERE contact_id = ?1;")?,
+            tags: count("SELECT COUNT(*) FROM contact_tags WHERE contact_id = ?1;")?,
+            open_merge_candidates: self.conn.query_row(
+                "SELECT COUNT(*) FROM contact_merge_candidates
+                 WHERE status = ?2
+                   AND (contact_a_id = ?1 OR contact_b_id = ?1);",
+                params![id_key, MergeCandidateStatus::Open.as_str()],
+                |row| row.get::<_, i64>(0).map(|value| value as usize),
+            )?,
+        })
+    }
+
+    /// Moves a contact to the trash. Its emails are set aside so other contacts
+    /// can claim them, and its import and Telegram mappings are dropped so syncs
+    /// treat it as gone.
+    pub fn trash(&self, now_utc: i64, id: ContactId) -> Result<Contact> {
+        if self.conn.is_autocommit() {
+            let tx = self.conn.unchecked_transaction()?;
+            let contact = trash_inner(&tx, self.origin, now_utc, id)?;
+            tx.commit()?;
+            Ok(contact)
+        } else {
+            trash_inner(self.conn, self.origin, now_utc, id)
+        }
+    }
+
+    /// Trashed contacts, most recently deleted first.
+    pub fn list_trashed(&self) -> Result<Vec<TrashedContact>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, display_name, email, phone, handle, timezone, next_touchpoint_at, cadence_days, created_at, updated_at, archived_at, organization, deleted_at
+             FROM contacts
+             WHERE deleted_at IS NOT NULL
+             ORDER BY deleted_at DESC, display_name COLLATE NOCASE ASC, id ASC;",
+        )?;
+        let mut rows = stmt.query([])?;
+        let mut items = Vec::new();
+        while let Some(row) = rows.next()? {
+            items.push(TrashedContact {
+                contact: contact_from_row(row)?,
+                deleted_at: row.get(12)?,
+            });
+        }
+        Ok(items)
+    }
+
+    pub fn restore(&self, now_utc: i64, id: ContactId) -> Result<ContactRestore> {
+        if self.conn.is_autocommit() {
+            let tx = self.conn.unchecked_transaction()?;
+            let restored = restore_inner(&tx, self.origin, now_utc, id)?;
+            tx.commit()?;
+            Ok(restored)
+        } else {
+            restore_inner(self.conn, self.origin, now_utc, id)
+        }
+    }
+
+    /// Permanently deletes trashed contacts, optionally only those trashed at or
+    /// before `deleted_before`. Returns the number of contacts removed.
+    pub fn empty_trash(&self, now_utc: i64, deleted_before: Option<i64>) -> Result<usize> {
+        if self.conn.is_autocommit() {
+            let tx = self.conn.unchecked_transaction()?;
+            let removed = empty_trash_inner(&tx, self.origin, now_utc, deleted_before)?;
+            tx.commit()?;
+            Ok(removed)
+        } else {
+            empty_trash_inner(self.conn, self.origin, now_utc, deleted_before)
         }
     }
 
@@ -261,6 +481,163 @@ impl<'a> ContactsRepo<'a> {
         self.update(now_utc, id, update)
     }
 
+    /// Archives a contact and records why. A `None` reason clears any earlier one.
+    pub fn archive_with_reason(
+        &self,
+        now_utc: i64,
+        id: ContactId,
+        reason: Option<&str>,
+    ) -> Result<Contact> {
+        if self.conn.is_autocommit() {
+            let tx = self.conn.unchecked_transaction()?;
+            let contact = archive_with_reason_inner(&tx, self.origin, now_utc, id, reason)?;
+            tx.commit()?;
+            Ok(contact)
+        } else {
+            archive_with_reason_inner(self.conn, self.origin, now_utc, id, reason)
+        }
+    }
+
+    /// Sets the archive reason without touching `archived_at`; ignored for active contacts.
+    pub fn set_archive_reason(
+        &self,
+        now_utc: i64,
+        id: ContactId,
+        reason: Option<&str>,
+    ) -> Result<()> {
+        if self.conn.is_autocommit() {
+            let tx = self.conn.unchecked_transaction()?;
+            set_archive_reason_audited(&tx, self.origin, now_utc, id, reason)?;
+            tx.commit()?;
+            Ok(())
+        } else {
+            set_archive_reason_audited(self.conn, self.origin, now_utc, id, reason)
+        }
+    }
+
+    pub fn archive_reason(&self, id: ContactId) -> Result<Option<String>> {
+        let reason = self
+            .conn
+            .query_row(
+                "SELECT archive_reason FROM contacts WHERE id = ?1;",
+                [id.to_string()],
+                |row| row.get::<_, Option<String>>(0),
+            )
+            .optional()?;
+        Ok(reason.flatten())
+    }
+
+    pub fn list_archive_reasons(&self) -> Result<HashMap<ContactId, String>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, archive_reason FROM contacts
+                 WHERE archive_reason IS NOT NULL AND deleted_at IS NULL;",
+        )?;
+        let mut rows = stmt.query([])?;
+        let mut reasons = HashMap::new();
+        while let Some(row) = rows.next()? {
+            let id_str: String = row.get(0)?;
+            let id = ContactId::from_str(&id_str).map_err(|_| StoreError::InvalidId(id_str))?;
+            reasons.insert(id, row.get(1)?);
+        }
+        Ok(reasons)
+    }
+
+    /// Pauses reminders and loop scheduling for a contact until `until`
+    /// (unix seconds UTC). The mute lapses on its own; no cleanup is needed.
+    pub fn mute(&self, now_utc: i64, id: ContactId, until: i64) -> Result<Contact> {
+        self.set_muted_until(now_utc, id, Some(until))
+    }
+
+    pub fn unmute(&self, now_utc: i64, id: ContactId) -> Result<Contact> {
+        self.set_muted_until(now_utc, id, None)
+    }
+
+    fn set_muted_until(
+        &self,
+        now_utc: i64,
+        id: ContactId,
+        muted_until: Option<i64>,
+    ) -> Result<Contact> {
+        if self.conn.is_autocommit() {
+            let tx = self.conn.unchecked_transaction()?;
+            let contact = set_muted_until_inner(&tx, self.origin, now_utc, id, muted_until)?;
+            tx.commit()?;
+            Ok(contact)
+        } else {
+            set_muted_until_inner(self.conn, self.origin, now_utc, id, muted_until)
+        }
+    }
+
+    /// End of the contact's mute, or `None` when it is not muted at `now_utc`.
+    pub fn active_mute(&self, id: ContactId, now_utc: i64) -> Result<Option<i64>> {
+        let muted_until = self
+            .conn
+            .query_row(
+                "SELECT muted_until FROM contacts WHERE id = ?1 AND deleted_at IS NULL;",
+                [id.to_string()],
+                |row| row.get::<_, Option<i64>>(0),
+            )
+            .optional()?;
+        Ok(muted_until.flatten().filter(|until| *until > now_utc))
+    }
+
+    /// Stored `muted_until` values, expired ones included.
+    pub fn list_muted_until(&self) -> Result<HashMap<ContactId, i64>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, muted_until FROM contacts
+                 WHERE muted_until IS NOT NULL AND deleted_at IS NULL;",
+        )?;
+        let mut rows = stmt.query([])?;
+        let mut muted = HashMap::new();
+        while let Some(row) = rows.next()? {
+            let id_str: String = row.get(0)?;
+            let id = ContactId::from_str(&id_str).map_err(|_| StoreError::InvalidId(id_str))?;
+            muted.insert(id, row.get(1)?);
+        }
+        Ok(muted)
+    }
+
+    /// Sets how much the contact matters (0 to `MAX_CONTACT_PRIORITY`); the
+    /// change is recorded in the audit log like any other edit.
+    pub fn set_priority(&self, now_utc: i64, id: ContactId, priority: i32) -> Result<Contact> {
+        let priority = validate_contact_priority(priority)?;
+        if self.conn.is_autocommit() {
+            let tx = self.conn.unchecked_transaction()?;
+            let contact = set_priority_inner(&tx, self.origin, now_utc, id, priority)?;
+            tx.commit()?;
+            Ok(contact)
+        } else {
+            set_priority_inner(self.conn, self.origin, now_utc, id, priority)
+        }
+    }
+
+    pub fn priority(&self, id: ContactId) -> Result<i32> {
+        self.conn
+            .query_row(
+                "SELECT priority FROM contacts WHERE id = ?1 AND deleted_at IS NULL;",
+                [id.to_string()],
+                |row| row.get(0),
+            )
+            .optional()?
+            .ok_or_else(|| StoreError::NotFound(id.to_string()))
+    }
+
+    /// Priorities above 0; contacts missing from the map have priority 0.
+    pub fn list_priorities(&self) -> Result<HashMap<ContactId, i32>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, priority FROM contacts
+                 WHERE priority <> 0 AND deleted_at IS NULL;",
+        )?;
+        let mut rows = stmt.query([])?;
+        let mut priorities = HashMap::new();
+        while let Some(row) = rows.next()? {
+            let id_str: String = row.get(0)?;
+            let id = ContactId::from_str(&id_str).map_err(|_| StoreError::InvalidId(id_str))?;
+            priorities.insert(id, row.get(1)?);
+        }
+        Ok(priorities)
+    }
+
     pub fn unarchive(&self, now_utc: i64, id: ContactId) -> Result<Contact> {
         let update = ContactUpdate {
             archived_at: Some(None),
@@ -278,19 +655,64 @@ impl<'a> ContactsRepo<'a> {
     ) -> Result<Contact> {
         if self.conn.is_autocommit() {
             let tx = self.conn.unchecked_transaction()?;
-            let contact = merge_contacts_inner(&tx, now_utc, primary_id, secondary_id, options)?;
+            let contact =
+                merge_contacts_inner(&tx, self.origin, now_utc, primary_id, secondary_id, options)?;
             tx.commit()?;
             Ok(contact)
         } else {
-            merge_contacts_inner(self.conn, now_utc, primary_id, secondary_id, options)
+            merge_contacts_inner(
+                self.conn,
+                self.origin,
+                now_utc,
+                primary_id,
+                secondary_id,
+                options,
+            )
         }
     }
 
+    /// Computes what `merge_contacts` would do with the same arguments, without
+    /// writing. `merge_contacts` applies exactly this result.
+    pub fn preview_merge(
+        &self,
+        now_utc: i64,
+        primary_id: ContactId,
+        secondary_id: ContactId,
+        options: ContactMergeOptions,
+    ) -> Result<MergedContactPreview> {
+        preview_merge_inner(self.conn, now_utc, primary_id, secondary_id, options)
+    }
+
     pub fn list_all(&self) -> Result<Vec<Contact>> {
         let query = ContactQuery::default();
         self.list_contacts(&query, 0, 7, FixedOffset::east_opt(0).expect("utc offset"))
     }
 
+    /// Contacts whose display name or id starts with `prefix` (case-insensitive),
+    /// active contacts first. Used for shell completion.
+    pub fn list_id_names_with_prefix(
+        &self,
+        prefix: &str,
+        limit: usize,
+    ) -> Result<Vec<(ContactId, String)>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, display_name FROM contacts
+             WHERE (instr(lower(display_name), lower(?1)) = 1
+                    OR instr(lower(id), lower(?1)) = 1)
+               AND deleted_at IS NULL
+             ORDER BY (archived_at IS NOT NULL) ASC, display_name COLLATE NOCASE ASC, id ASC
+             LIMIT ?2;",
+        )?;
+        let mut rows = stmt.query(params![prefix, limit as i64])?;
+        let mut items = Vec::new();
+        while let Some(row) = rows.next()? {
+            let id_raw: String = row.get(0)?;
+            let id = ContactId::from_str(&id_raw).map_err(|_| StoreError::InvalidId(id_raw))?;
+            items.push((id, row.get(1)?));
+        }
+        Ok(items)
+    }
+
     pub fn list_contacts(
         &self,
         query: &ContactQuery,
@@ -308,6 +730,100 @@ impl<'a> ContactsRepo<'a> {
         Ok(contacts)
     }
 
+    /// Lists contacts with due state and tags resolved in a single SQL statement,
+    /// optionally restricted to one page of the ordered result.
+    pub fn list_rows(
+        &self,
+        query: &ContactQuery,
+        now_utc: i64,
+        soon_days: i64,
+        local_offset: FixedOffset,
+        page: Option<ListPage>,
+    ) -> Result<Vec<ContactListRow>> {
+        let compiled = query.to_list_sql(now_utc, soon_days, local_offset, page)?;
+        let mut stmt = self.conn.prepare(&compiled.sql)?;
+        let mut rows = stmt.query(params_from_iter(compiled.params))?;
+        let mut items = Vec::new();
+        while let Some(row) = rows.next()? {
+            let contact = contact_from_row(row)?;
+            let due_rank: i64 = row.get(12)?;
+            let tag_names: Option<String> = row.get(13)?;
+            let tags = tag_names
+                .map(|names| names.split(TAG_SEPARATOR).map(str::to_string).collect())
+                .unwrap_or_default();
+            items.push(ContactListRow {
+                contact,
+                due_state: due_state_from_rank(due_rank),
+                tags,
+                archive_reason: row.get(14)?,
+                muted_until: row.get(15)?,
+                last_interaction_at: row.get(16)?,
+                last_interaction_kind: row.get(17)?,
+                priority: row.get(18)?,
+            });
+        }
+        Ok(items)
+    }
+
+    /// The shared contact listing: rows for `query` with the due bucket computed
+    /// in SQL against `now_utc` and local midnight in `local_offset`.
+    pub fn list_view(
+        &self,
+        query: &ContactListQuery,
+        now_utc: i64,
+        local_offset: FixedOffset,
+    ) -> Result<Vec<ContactListItemDto>> {
+        let contact_query = query.to_contact_query()?;
+        Ok(self
+            .list_rows(
+                &contact_query,
+                now_utc,
+                query.soon_days,
+                local_offset,
+                query.page,
+            )?
+            .into_iter()
+            .map(|row| ContactListItemDto {
+                id: row.contact.id,
+                display_name: row.contact.display_name,
+                due_state: row.due_state,
+                next_touchpoint_at: row.contact.next_touchpoint_at,
+                archived_at: row.contact.archived_at,
+                archive_reason: row.archive_reason,
+                muted_until: row.muted_until,
+                tags: row.tags,
+                last_interaction_at: row.last_interaction_at,
+                last_interaction_kind: row.last_interaction_kind,
+                organization: row.contact.organization,
+                priority: row.priority,
+            })
+            .collect())
+    }
+
+    /// Number of contacts matching `query` in each due bucket, most urgent first.
+    pub fn count_by_due_state(
+        &self,
+        query: &ContactQuery,
+        now_utc: i64,
+        soon_days: i64,
+        local_offset: FixedOffset,
+    ) -> Result<Vec<(DueState, usize)>> {
+        let compiled = query.to_due_counts_sql(now_utc, soon_days, local_offset)?;
+        let mut counts = [0usize; 5];
+        let mut stmt = self.conn.prepare(&compiled.sql)?;
+        let mut rows = stmt.query(params_from_iter(compiled.params))?;
+        while let Some(row) = rows.next()? {
+            let rank: i64 = row.get(0)?;
+            let count: i64 = row.get(1)?;
+            counts[rank.clamp(0, 4) as usize] += count as usize;
+        }
+        Ok(counts
+            .into_iter()
+            .enumerate()
+            .map(|(rank, count)| (due_state_from_rank(rank as i64), count))
+            .collect())
+    }
+
     pub fn list_due_contacts(
         &self,
         now_utc: i64,
@@ -317,9 +833,11 @@ impl<'a> ContactsRepo<'a> {
         let soon_days = validate_soon_days(soon_days).map_err(StoreError::Core)?;
         let bounds = due_bounds(now_utc, soon_days, local_offset);
         let mut stmt = self.conn.prepare(
-            "SELECT id, display_name, email, phone, handle, timezone, next_touchpoint_at, cadence_days, created_at, updated_at, archived_at
+            "SELECT id, display_name, email, phone, handle, timezone, next_touchpoint_at, cadence_days, created_at, updated_at, archived_at, organization
              FROM contacts
              WHERE archived_at IS NULL
+               AND deleted_at IS NULL
+               AND (muted_until IS NULL OR muted_until <= ?2)
                AND next_touchpoint_at IS NOT NULL
                AND next_touchpoint_at < ?1
              ORDER BY CASE
@@ -344,8 +862,10 @@ impl<'a> ContactsRepo<'a> {
         Ok(contacts)
     }
 
+    /// Random active, unmuted contacts, skipping `exclude_ids`.
     pub fn list_random_active(
         &self,
+        now_utc: i64,
         limit: usize,
         exclude_ids: &[ContactId],
     ) -> Result<Vec<Contact>> {
@@ -360,25 +880,29 @@ impl<'a> ContactsRepo<'a> {
 
         let sql = if let Some(table) = exclude_table.as_ref() {
             format!(
-                "SELECT id, display_name, email, phone, handle, timezone, next_touchpoint_at, cadence_days, created_at, updated_at, archived_at
+                "SELECT id, display_name, email, phone, handle, timezone, next_touchpoint_at, cadence_days, created_at, updated_at, archived_at, organization
                  FROM contacts
                  WHERE archived_at IS NULL
+                   AND deleted_at IS NULL
+                   AND (muted_until IS NULL OR muted_until <= ?2)
                    AND NOT EXISTS (SELECT 1 FROM {} WHERE id = contacts.id)
                  ORDER BY RANDOM()
                  LIMIT ?1;",
                 table.name()
             )
         } else {
-            "SELECT id, display_name, email, phone, handle, timezone, next_touchpoint_at, cadence_days, created_at, updated_at, archived_at
+            "SELECT id, display_name, email, phone, handle, timezone, next_touchpoint_at, cadence_days, created_at, updated_at, archived_at, organization
              FROM contacts
              WHERE archived_at IS NULL
+               AND deleted_at IS NULL
+               AND (muted_until IS NULL OR muted_until <= ?2)
              ORDER BY RANDOM()
              LIMIT ?1;"
                 .to_string()
         };
 
         let mut stmt = self.conn.prepare(&sql)?;
-        let mut rows = stmt.query(params_from_iter([limit as i64]))?;
+        let mut rows = stmt.query(params![limit as i64, now_utc])?;
         let mut contacts = Vec::new();
         while let Some(row) = rows.next()? {
             contacts.push(contact_from_row(row)?);
@@ -387,6 +911,158 @@ impl<'a> ContactsRepo<'a> {
     }
 }
 
+fn set_muted_until_inner(
+    conn: &Connection,
+    origin: &str,
+    now_utc: i64,
+    id: ContactId,
+    muted_until: Option<i64>,
+) -> Result<Contact> {
+    let before: Option<i64> = conn
+        .query_row(
+            "SELECT muted_until FROM contacts WHERE id = ?1 AND deleted_at IS NULL;",
+            [id.to_string()],
+            |row| row.get(0),
+        )
+        .optional()?
+        .ok_or_else(|| StoreError::NotFound(id.to_string()))?;
+    conn.execute(
+        "UPDATE contacts SET muted_until = ?2, updated_at = ?3 WHERE id = ?1;",
+        params![id.to_string(), muted_until, now_utc],
+    )?;
+    if before != muted_until {
+        let change = contact_audit::FieldChange {
+            field: "muted_until".to_string(),
+            old: before.into(),
+            new: muted_until.into(),
+        };
+        contact_audit::record_inner(
+            conn,
+            now_utc,
+            id,
+            AuditAction::Update,
+            origin,
+            &[change],
+            None,
+        )?;
+    }
+    get_inner(conn, id)?.ok_or_else(|| StoreError::NotFound(id.to_string()))
+}
+
+fn set_priority_inner(
+    conn: &Connection,
+    origin: &str,
+    now_utc: i64,
+    id: ContactId,
+    priority: i32,
+) -> Result<Contact> {
+    let before: i32 = conn
+        .query_row(
+            "SELECT priority FROM contacts WHERE id = ?1 AND deleted_at IS NULL;",
+            [id.to_string()],
+            |row| row.get(0),
+        )
+        .optional()?
+        .ok_or_else(|| StoreError::NotFound(id.to_string()))?;
+    if before != priority {
+        conn.execute(
+            "UPDATE contacts SET priority = ?2, updated_at = ?3 WHERE id = ?1;",
+            params![id.to_string(), priority, now_utc],
+        )?;
+        let change = contact_audit::FieldChange {
+            field: "priority".to_string(),
+            old: before.into(),
+            new: priority.into(),
+        };
+        contact_audit::record_inner(
+            conn,
+            now_utc,
+            id,
+            AuditAction::Update,
+            origin,
+            &[change],
+            None,
+        )?;
+    }
+    get_inner(conn, id)?.ok_or_else(|| StoreError::NotFound(id.to_string()))
+}
+
+/// Archives the contact and stores the reason, logged as one audit entry
+/// so the reason shows up next to the archive that it explains.
+fn archive_with_reason_inner(
+    conn: &Connection,
+    origin: &str,
+    now_utc: i64,
+    id: ContactId,
+    reason: Option<&str>,
+) -> Result<Contact> {
+    let before = get_inner(conn, id)?.ok_or_else(|| StoreError::NotFound(id.to_string()))?;
+    let update = ContactUpdate {
+        archived_at: Some(Some(now_utc)),
+        ..Default::default()
+    };
+    let contact = apply_update_inner(conn, now_utc, id, update)?;
+    let mut changes = contact_audit::diff_contacts(&before, &contact);
+    changes.extend(set_archive_reason_inner(conn, id, reason)?);
+    changes.sort_by(|a, b| a.field.cmp(&b.field));
+    let action = contact_audit::update_action(&before, &contact);
+    contact_audit::record_inner(conn, now_utc, id, action, origin, &changes, None)?;
+    Ok(contact)
+}
+
+fn set_archive_reason_audited(
+    conn: &Connection,
+    origin: &str,
+    now_utc: i64,
+    id: ContactId,
+    reason: Option<&str>,
+) -> Result<()> {
+    let changes: Vec<_> = set_archive_reason_inner(conn, id, reason)?
+        .into_iter()
+        .collect();
+    contact_audit::record_inner(
+        conn,
+        now_utc,
+        id,
+        AuditAction::Update,
+        origin,
+        &changes,
+        None,
+    )
+}
+
+/// Writes the reason and returns the change, if any. Active contacts keep
+/// no reason, so for them nothing is written.
+fn set_archive_reason_inner(
+    conn: &Connection,
+    id: ContactId,
+    reason: Option<&str>,
+) -> Result<Option<contact_audit::FieldChange>> {
+    let reason = reason.map(str::trim).filter(|reason| !reason.is_empty());
+    let before: Option<Option<String>> = conn
+        .query_row(
+            "SELECT archive_reason FROM contacts WHERE id = ?1 AND archived_at IS NOT NULL;",
+            [id.to_string()],
+            |row| row.get(0),
+        )
+        .optional()?;
+    let Some(before) = before else {
+        return Ok(None);
+    };
+    if before.as_deref() == reason {
+        return Ok(None);
+    }
+    conn.execute(
+        "UPDATE contacts SET archive_reason = ?2 WHERE id = ?1;",
+        params![id.to_string(), reason],
+    )?;
+    Ok(Some(contact_audit::FieldChange {
+        field: "archive_reason".to_string(),
+        old: before.into(),
+        new: reason.into(),
+    }))
+}
+
 fn create_inner(conn: &Connection, now_utc: i64, input: ContactNew) -> Result<Contact> {
     let contact = Contact {
         id: ContactId::new(),
@@ -400,13 +1076,14 @@ fn create_inner(conn: &Connection, now_utc: i64, input: ContactNew) -> Result<Co
         created_at: now_utc,
         updated_at: now_utc,
         archived_at: input.archived_at,
+        organization: input.organization,
     };
 
     contact.validate()?;
 
     conn.execute(
-        "INSERT INTO contacts (id, display_name, email, phone, handle, timezone, next_touchpoint_at, cadence_days, created_at, updated_at, archived_at)
-         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11);",
+        "INSERT INTO contacts (id, display_name, email, phone, handle, timezone, next_touchpoint_at, cadence_days, created_at, updated_at, archived_at, name_key, organization)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13);",
         params![
             contact.id.to_string(),
             contact.display_name,
@@ -419,6 +1096,8 @@ fn create_inner(conn: &Connection, now_utc: i64, input: ContactNew) -> Result<Co
             contact.created_at,
             contact.updated_at,
             contact.archived_at,
+            name_match_key(&contact.display_name),
+            contact.organization,
         ],
     )?;
 
@@ -448,12 +1127,39 @@ fn create_with_tags_inner(
 }
 
 fn update_inner(
+    conn: &Connection,
+    origin: &str,
+    now_utc: i64,
+    id: ContactId,
+    update: ContactUpdate,
+) -> Result<Contact> {
+    let before = get_inner(conn, id)?.ok_or_else(|| StoreError::NotFound(id.to_string()))?;
+    let origin = update.source.clone().unwrap_or_else(|| origin.to_string());
+    let contact = apply_update_inner(conn, now_utc, id, update)?;
+    record_update_audit(conn, now_utc, &origin, &before, &contact)?;
+    Ok(contact)
+}
+
+fn record_update_audit(
+    conn: &Connection,
+    now_utc: i64,
+    origin: &str,
+    before: &Contact,
+    after: &Contact,
+) -> Result<()> {
+    let changes = contact_audit::diff_contacts(before, after);
+    let action = contact_audit::update_action(before, after);
+    contact_audit::record_inner(conn, now_utc, after.id, action, origin, &changes, None)
+}
+
+fn apply_update_inner(
     conn: &Connection,
     now_utc: i64,
     id: ContactId,
     update: ContactUpdate,
 ) -> Result<Contact> {
     let mut contact = get_inner(conn, id)?.ok_or_else(|| StoreError::NotFound(id.to_string()))?;
+    let before = contact.clone();
 
     if let Some(value) = update.display_name {
         contact.display_name = value;
@@ -494,12 +1200,18 @@ fn update_inner(
     if let Some(value) = update.archived_at {
         contact.archived_at = value;
     }
+    if let Some(value) = update.organization {
+        contact.organization = value;
+    }
 
     contact.updated_at = now_utc;
     contact.validate()?;
 
     conn.execute(
-        "UPDATE contacts SET display_name = ?2, email = ?3, phone = ?4, handle = ?5, timezone = ?6, next_touchpoint_at = ?7, cadence_days = ?8, updated_at = ?9, archived_at = ?10
+        "UPDATE contacts SET display_name = ?2, email = ?3, phone = ?4, handle = ?5, timezone = ?6, next_touchpoint_at = ?7, cadence_days = ?8, updated_at = ?9, archived_at = ?10,
+             archive_reason = CASE WHEN ?10 IS NULL THEN NULL ELSE archive_reason END,
+             name_key = ?11,
+             organization = ?12
          WHERE id = ?1;",
         params![
             contact.id.to_string(),
@@ -512,6 +1224,8 @@ fn update_inner(
             contact.cadence_days,
             contact.updated_at,
             contact.archived_at,
+            name_match_key(&contact.display_name),
+            contact.organization,
         ],
     )?;
 
@@ -529,21 +1243,57 @@ fn update_inner(
         }
     }
 
+    let changed = changed_fields(&before, &contact);
+    let source = update.source.as_deref().unwrap_or(MANUAL_SOURCE);
+    crate::repo::field_provenance::record_inner(conn, now_utc, contact.id, &changed, source)?;
+
     Ok(contact)
 }
 
+fn changed_fields(before: &Contact, after: &Contact) -> Vec<ContactField> {
+    let mut changed = Vec::new();
+    if before.display_name != after.display_name {
+        changed.push(ContactField::DisplayName);
+    }
+    if before.email != after.email {
+        changed.push(ContactField::Email);
+    }
+    if before.phone != after.phone {
+        changed.push(ContactField::Phone);
+    }
+    if before.handle != after.handle {
+        changed.push(ContactField::Handle);
+    }
+    if before.timezone != after.timezone {
+        changed.push(ContactField::Timezone);
+    }
+    if before.organization != after.organization {
+        changed.push(ContactField::Organization);
+    }
+    if before.next_touchpoint_at != after.next_touchpoint_at {
+        changed.push(ContactField::NextTouchpointAt);
+    }
+    if before.cadence_days != after.cadence_days {
+        changed.push(ContactField::CadenceDays);
+    }
+    changed
+}
+
 fn update_with_email_ops_inner(
     conn: &Connection,
+    origin: &str,
     now_utc: i64,
     id: ContactId,
     update: ContactUpdate,
     email_ops: EmailOps,
 ) -> Result<Contact> {
     let update_empty = update_is_empty(&update);
+    let source = update.source.clone();
+    let before = get_inner(conn, id)?.ok_or_else(|| StoreError::NotFound(id.to_string()))?;
     let mut contact = if update_empty {
-        get_inner(conn, id)?.ok_or_else(|| StoreError::NotFound(id.to_string()))?
+        before.clone()
     } else {
-        update_inner(conn, now_utc, id, update)?
+        apply_update_inner(conn, now_utc, id, update)?
     };
 
     let emails_repo = crate::repo::emails::EmailsRepo::new(conn);
@@ -588,15 +1338,26 @@ fn update_with_email_ops_inner(
     }
 
     if let Some(updated) = get_inner(conn, id)? {
+        if updated.email != contact.email {
+            crate::repo::field_provenance::record_inner(
+                conn,
+                now_utc,
+                updated.id,
+                &[ContactField::Email],
+                source.as_deref().unwrap_or(MANUAL_SOURCE),
+            )?;
+        }
         contact = updated;
     }
+    let origin = source.as_deref().unwrap_or(origin);
+    record_update_audit(conn, now_utc, origin, &before, &contact)?;
     Ok(contact)
 }
 
 fn get_inner(conn: &Connection, id: ContactId) -> Result<Option<Contact>> {
     let mut stmt = conn.prepare(
-        "SELECT id, display_name, email, phone, handle, timezone, next_touchpoint_at, cadence_days, created_at, updated_at, archived_at
-         FROM contacts WHERE id = ?1;",
+        "SELECT id, display_name, email, phone, handle, timezone, next_touchpoint_at, cadence_days, created_at, updated_at, archived_at, organization
+         FROM contacts WHERE id = ?1 AND deleted_at IS NULL;",
     )?;
     let mut rows = stmt.query([id.to_string()])?;
     if let Some(row) = rows.next()? {
@@ -615,9 +1376,20 @@ fn update_is_empty(update: &ContactUpdate) -> bool {
         && update.next_touchpoint_at.is_none()
         && update.cadence_days.is_none()
         && update.archived_at.is_none()
+        && update.organization.is_none()
 }
 
-fn delete_inner(conn: &Connection, now_utc: i64, id: ContactId) -> Result<()> {
+fn delete_inner(conn: &Connection, origin: &str, now_utc: i64, id: ContactId) -> Result<()> {
+    let display_name: Option<String> = conn
+        .query_row(
+            "SELECT display_name FROM contacts WHERE id = ?1;",
+            [id.to_string()],
+            |row| row.get(0),
+        )
+        .optional()?;
+    let Some(display_name) = display_name else {
+        return Ok(());
+    };
     let open_status = MergeCandidateStatus::Open.as_str();
     let dismissed_status = MergeCandidateStatus::Dismissed.as_str();
     let id_key = id.to_string();
@@ -629,16 +1401,181 @@ fn delete_inner(conn: &Connection, now_utc: i64, id: ContactId) -> Result<()> {
         params![open_status, dismissed_status, now_utc, id_key],
     )?;
     conn.execute("DELETE FROM contacts WHERE id = ?1;", [id.to_string()])?;
-    Ok(())
+    contact_audit::record_inner(
+        conn,
+        now_utc,
+        id,
+        AuditAction::Delete,
+        origin,
+        &[],
+        Some(&display_name),
+    )
 }
 
-fn merge_contacts_inner(
+fn trash_inner(conn: &Connection, origin: &str, now_utc: i64, id: ContactId) -> Result<Contact> {
+    let contact = get_inner(conn, id)?.ok_or_else(|| StoreError::NotFound(id.to_string()))?;
+    let id_key = id.to_string();
+    conn.execute(
+        "UPDATE contact_merge_candidates
+         SET status = ?2, resolved_at = ?3
+         WHERE status = ?1
+           AND (contact_a_id = ?4 OR contact_b_id = ?4);",
+        params![
+            MergeCandidateStatus::Open.as_str(),
+            MergeCandidateStatus::Dismissed.as_str(),
+            now_utc,
+            id_key
+        ],
+    )?;
+    conn.execute(
+        "INSERT OR REPLACE INTO trashed_contact_emails (contact_id, email, is_primary, created_at, source)
+         SELECT contact_id, email, is_primary, created_at, source
+         FROM contact_emails WHERE contact_id = ?1;",
+        [&id_key],
+    )?;
+    conn.execute(
+        "DELETE FROM contact_emails WHERE contact_id = ?1;",
+        [&id_key],
+    )?;
+    conn.execute(
+        "DELETE FROM contact_sources WHERE contact_id = ?1;",
+        [&id_key],
+    )?;
+    conn.execute(
+        "DELETE FROM contact_telegram_accounts WHERE contact_id = ?1;",
+        [&id_key],
+    )?;
+    conn.execute(
+        "UPDATE contacts SET deleted_at = ?2 WHERE id = ?1;",
+        params![id_key, now_utc],
+    )?;
+    contact_audit::record_inner(conn, now_utc, id, AuditAction::Trash, origin, &[], None)?;
+    Ok(contact)
+}
+
+fn restore_inner(
+    conn: &Connection,
+    origin: &str,
+    now_utc: i64,
+    id: ContactId,
+) -> Result<ContactRestore> {
+    let id_key = id.to_string();
+    let trashed: Option<String> = conn
+        .query_row(
+            "SELECT email FROM contacts WHERE id = ?1 AND deleted_at IS NOT NULL;",
+            [&id_key],
+            |row| row.get(0),
+        )
+        .optional()?
+        .ok_or_else(|| StoreError::NotFound(id.to_string()))?;
+
+    let mut stashed = Vec::new();
+    {
+        let mut stmt = conn.prepare(
+            "SELECT email, is_primary, created_at, source
+             FROM trashed_contact_emails
+             WHERE contact_id = ?1
+             ORDER BY is_primary DESC, email ASC;",
+        )?;
+        let mut rows = stmt.query([&id_key])?;
+        while let Some(row) = rows.next()? {
+            stashed.push((
+                row.get::<_, String>(0)?,
+                row.get::<_, i64>(1)? != 0,
+                row.get::<_, i64>(2)?,
+                row.get::<_, Option<String>>(3)?,
+            ));
+        }
+    }
+
+    let mut restored = Vec::new();
+    let mut dropped_emails = Vec::new();
+    for (email, is_primary, created_at, source) in stashed {
+        let inserted = conn.execute(
+            "INSERT OR IGNORE INTO contact_emails (contact_id, email, is_primary, created_at, source)
+             VALUES (?1, ?2, ?3, ?4, ?5);",
+            params![id_key, email, is_primary as i64, created_at, source],
+        )?;
+        if inserted > 0 {
+            restored.push((email, is_primary));
+        } else {
+            dropped_emails.push(email);
+        }
+    }
+    conn.execute(
+        "DELETE FROM trashed_contact_emails WHERE contact_id = ?1;",
+        [&id_key],
+    )?;
+
+    // Keep the primary column pointing at an address this contact still owns.
+    let primary_kept = restored.iter().any(|(_, is_primary)| *is_primary);
+    let email = if primary_kept {
+        trashed
+    } else if let Some((fallback, _)) = restored.first() {
+        conn.execute(
+            "UPDATE contact_emails SET is_primary = 1 WHERE contact_id = ?1 AND email = ?2;",
+            params![id_key, fallback],
+        )?;
+        Some(fallback.clone())
+    } else {
+        None
+    };
+    conn.execute(
+        "UPDATE contacts SET deleted_at = NULL, email = ?2, updated_at = ?3 WHERE id = ?1;",
+        params![id_key, email, now_utc],
+    )?;
+
+    let detail = (!dropped_emails.is_empty())
+        .then(|| format!("emails not restored: {}", dropped_emails.join(", ")));
+    contact_audit::record_inner(
+        conn,
+        now_utc,
+        id,
+        AuditAction::Restore,
+        origin,
+        &[],
+        detail.as_deref(),
+    )?;
+
+    let contact = get_inner(conn, id)?.ok_or_else(|| StoreError::NotFound(id.to_string()))?;
+    Ok(ContactRestore {
+        contact,
+        dropped_emails,
+    })
+}
+
+fn empty_trash_inner(
+    conn: &Connection,
+    origin: &str,
+    now_utc: i64,
+    deleted_before: Option<i64>,
+) -> Result<usize> {
+    let mut ids = Vec::new();
+    {
+        let mut stmt = conn.prepare(
+            "SELECT id FROM contacts
+             WHERE deleted_at IS NOT NULL
+               AND (?1 IS NULL OR deleted_at <= ?1);",
+        )?;
+        let mut rows = stmt.query([deleted_before])?;
+        while let Some(row) = rows.next()? {
+            let id_raw: String = row.get(0)?;
+            ids.push(ContactId::from_str(&id_raw).map_err(|_| StoreError::InvalidId(id_raw))?);
+        }
+    }
+    for id in &ids {
+        delete_inner(conn, origin, now_utc, *id)?;
+    }
+    Ok(ids.len())
+}
+
+fn preview_merge_inner(
     conn: &Connection,
     now_utc: i64,
     primary_id: ContactId,
     secondary_id: ContactId,
     options: ContactMergeOptions,
-) -> Result<Contact> {
+) -> Result<MergedContactPreview> {
     if primary_id == secondary_id {
         return Err(StoreError::InvalidMerge(
             "merge requires distinct contact IDs".to_string(),
@@ -650,8 +1587,69 @@ fn merge_contacts_inner(
     let secondary = get_inner(conn, secondary_id)?
         .ok_or_else(|| StoreError::NotFound(secondary_id.to_string()))?;
 
-    let prefer_secondary = matches!(options.prefer, MergePreference::Secondary);
-    let merged = merge_contact_fields(now_utc, &primary, &secondary, options);
+    let emails_repo = crate::repo::emails::EmailsRepo::new(conn);
+    let primary_emails = emails_repo.list_for_contact(&primary_id)?;
+    let secondary_emails = emails_repo.list_for_contact(&secondary_id)?;
+    let contact = merge_contact_fields(
+        now_utc,
+        &primary,
+        &secondary,
+        &primary_emails,
+        &secondary_emails,
+        options,
+    );
+
+    let mut emails: Vec<String> = contact.email.iter().cloned().collect();
+    for email in primary_emails.iter().chain(&secondary_emails) {
+        if !emails.contains(&email.email) {
+            emails.push(email.email.clone());
+        }
+    }
+
+    let tags_repo = crate::repo::tags::TagsRepo::new(conn);
+    let mut tags: Vec<String> = tags_repo
+        .list_for_contact(&primary_id.to_string())?
+        .into_iter()
+        .chain(tags_repo.list_for_contact(&secondary_id.to_string())?)
+        .map(|tag| tag.name.as_str().to_string())
+        .collect();
+    tags.sort();
+    tags.dedup();
+
+    let new_aliases = [&primary.display_name, &secondary.display_name]
+        .into_iter()
+        .filter(|name| !name.eq_ignore_ascii_case(&contact.display_name))
+        .cloned()
+        .collect();
+
+    let count = |sql: &str| -> Result<usize> {
+        let value: i64 = conn.query_row(sql, [secondary_id.to_string()], |row| row.get(0))?;
+        Ok(value as usize)
+    };
+    Ok(MergedContactPreview {
+        contact,
+        emails,
+        tags,
+        new_aliases,
+        interactions_moved: count("SELECT COUNT(*) FROM interactions WHERE contact_id = ?1;")?,
+        dates_moved: count("SELECT COUNT(*) FROM contact_dates WHERE contact_id = ?1;")?,
+    })
+}
+
+fn merge_contacts_inner(
+    conn: &Connection,
+    origin: &str,
+    now_utc: i64,
+    primary_id: ContactId,
+    secondary_id: ContactId,
+    options: ContactMergeOptions,
+) -> Result<Contact> {
+    let preview = preview_merge_inner(conn, now_utc, primary_id, secondary_id, options)?;
+    let primary =
+        get_inner(conn, primary_id)?.ok_or_else(|| StoreError::NotFound(primary_id.to_string()))?;
+    let secondary = get_inner(conn, secondary_id)?
+        .ok_or_else(|| StoreError::NotFound(secondary_id.to_string()))?;
+    let merged = preview.contact;
     merged.validate()?;
 
     conn.execute(
@@ -663,7 +1661,10 @@ fn merge_contacts_inner(
              next_touchpoint_at = ?6,
              cadence_days = ?7,
              updated_at = ?8,
-             archived_at = ?9
+             archived_at = ?9,
+             archive_reason = CASE WHEN ?9 IS NULL THEN NULL ELSE archive_reason END,
+             name_key = ?10,
+             organization = ?11
          WHERE id = ?1;",
         params![
             primary_id.to_string(),
@@ -675,9 +1676,19 @@ fn merge_contacts_inner(
             merged.cadence_days,
             merged.updated_at,
             merged.archived_at,
+            name_match_key(&merged.display_name),
+            merged.organization,
         ],
     )?;
 
+    // The merged contact matters at least as much as either half did.
+    conn.execute(
+        "UPDATE contacts
+         SET priority = MAX(priority, (SELECT priority FROM contacts WHERE id = ?2))
+         WHERE id = ?1;",
+        params![primary_id.to_string(), secondary_id.to_string()],
+    )?;
+
     conn.execute(
         "INSERT OR IGNORE INTO contact_tags (contact_id, tag_id)\n         SELECT ?1, tag_id FROM contact_tags WHERE contact_id = ?2;",
         params![primary_id.to_string(), secondary_id.to_string()],
@@ -688,6 +1699,22 @@ fn merge_contacts_inner(
         params![primary_id.to_string(), secondary_id.to_string()],
     )?;
 
+    conn.execute(
+        "INSERT OR IGNORE INTO contact_aliases (contact_id, alias, created_at)
+         SELECT ?1, alias, created_at FROM contact_aliases WHERE contact_id = ?2;",
+        params![primary_id.to_string(), secondary_id.to_string()],
+    )?;
+
+    conn.execute(
+        "UPDATE contact_sync_log SET contact_id = ?1 WHERE contact_id = ?2;",
+        params![primary_id.to_string(), secondary_id.to_string()],
+    )?;
+
+    conn.execute(
+        "UPDATE contact_audit SET contact_id = ?1 WHERE contact_id = ?2;",
+        params![primary_id.to_string(), secondary_id.to_string()],
+    )?;
+
     conn.execute(
         "UPDATE email_messages SET contact_id = ?1 WHERE contact_id = ?2;",
         params![primary_id.to_string(), secondary_id.to_string()],
@@ -752,6 +1779,31 @@ fn merge_contacts_inner(
         params![primary_id.to_string(), secondary_id.to_string(), now_utc],
     )?;
 
+    conn.execute(
+        "UPDATE contact_dates
+         SET remind_days_before = (
+             SELECT d2.remind_days_before FROM contact_dates d2
+             WHERE d2.contact_id = ?2
+               AND d2.kind = contact_dates.kind
+               AND d2.label = contact_dates.label
+               AND d2.month = contact_dates.month
+               AND d2.day = contact_dates.day
+         ),
+             updated_at = ?3
+         WHERE contact_id = ?1
+           AND remind_days_before IS NULL
+           AND EXISTS (
+             SELECT 1 FROM contact_dates d2
+             WHERE d2.contact_id = ?2
+               AND d2.kind = contact_dates.kind
+               AND d2.label = contact_dates.label
+               AND d2.month = contact_dates.month
+               AND d2.day = contact_dates.day
+               AND d2.remind_days_before IS NOT NULL
+           );",
+        params![primary_id.to_string(), secondary_id.to_string(), now_utc],
+    )?;
+
     conn.execute(
         "DELETE FROM contact_dates
          WHERE contact_id = ?1
@@ -770,8 +1822,41 @@ fn merge_contacts_inner(
         params![primary_id.to_string(), secondary_id.to_string()],
     )?;
 
-    let primary_email =
-        merge_contact_emails(conn, now_utc, &primary_id, &secondary_id, prefer_secondary)?;
+    // Fill the primary's missing origin fields from the secondary, then point
+    // introductions made by the secondary at the primary.
+    conn.execute(
+        "UPDATE contact_origins
+         SET met_at = COALESCE(met_at, (SELECT o2.met_at FROM contact_origins o2 WHERE o2.contact_id = ?2)),
+             met_on = COALESCE(met_on, (SELECT o2.met_on FROM contact_origins o2 WHERE o2.contact_id = ?2)),
+             met_via_contact_id = COALESCE(met_via_contact_id, (SELECT o2.met_via_contact_id FROM contact_origins o2 WHERE o2.contact_id = ?2)),
+             updated_at = ?3
+         WHERE contact_id = ?1
+           AND EXISTS (SELECT 1 FROM contact_origins o2 WHERE o2.contact_id = ?2);",
+        params![primary_id.to_string(), secondary_id.to_string(), now_utc],
+    )?;
+    conn.execute(
+        "INSERT OR IGNORE INTO contact_origins (contact_id, met_at, met_on, met_via_contact_id, updated_at)
+         SELECT ?1, met_at, met_on, met_via_contact_id, ?3 FROM contact_origins WHERE contact_id = ?2;",
+        params![primary_id.to_string(), secondary_id.to_string(), now_utc],
+    )?;
+    conn.execute(
+        "UPDATE contact_origins SET met_via_contact_id = ?1 WHERE met_via_contact_id = ?2;",
+        params![primary_id.to_string(), secondary_id.to_string()],
+    )?;
+    conn.execute(
+        "UPDATE contact_origins SET met_via_contact_id = NULL
+         WHERE contact_id = ?1 AND met_via_contact_id = ?1;",
+        [primary_id.to_string()],
+    )?;
+
+    let primary_email = merged.email.clone();
+    merge_contact_emails(
+        conn,
+        now_utc,
+        &primary_id,
+        &secondary_id,
+        primary_email.as_deref(),
+    )?;
     crate::repo::emails::EmailsRepo::new(conn)
         .set_primary(&primary_id, primary_email.as_deref())?;
     conn.execute(
@@ -806,22 +1891,61 @@ fn merge_contacts_inner(
         params![open_status, dismissed_status, now_utc, secondary_key],
     )?;
 
+    // Fields whose merged value came from the secondary carry its provenance too.
+    let merged =
+        get_inner(conn, primary_id)?.ok_or_else(|| StoreError::NotFound(primary_id.to_string()))?;
+    let from_secondary = changed_fields(&primary, &merged);
+    // The name that lost keeps matching imports and filters as an alias.
+    for name in &preview.new_aliases {
+        crate::repo::contact_aliases::add_inner(conn, now_utc, primary_id, name)?;
+    }
+    crate::repo::field_provenance::copy_fields_inner(
+        conn,
+        secondary_id,
+        primary_id,
+        &from_secondary,
+    )?;
+
     conn.execute(
         "DELETE FROM contacts WHERE id = ?1;",
         [secondary_id.to_string()],
     )?;
 
-    get_inner(conn, primary_id)?.ok_or_else(|| StoreError::NotFound(primary_id.to_string()))
+    contact_audit::record_inner(
+        conn,
+        now_utc,
+        primary_id,
+        AuditAction::Merge,
+        origin,
+        &contact_audit::diff_contacts(&primary, &merged),
+        Some(&format!(
+            "merged {} ({secondary_id})",
+            secondary.display_name
+        )),
+    )?;
+
+    Ok(merged)
 }
 
 fn merge_contact_fields(
     now_utc: i64,
     primary: &Contact,
     secondary: &Contact,
+    primary_emails: &[ContactEmail],
+    secondary_emails: &[ContactEmail],
     options: ContactMergeOptions,
 ) -> Contact {
     let prefer_secondary = matches!(options.prefer, MergePreference::Secondary);
-    let display_name = if prefer_secondary {
+    let fields = options.fields;
+    let wins_secondary = |field: Option<MergePreference>| {
+        field.map_or(prefer_secondary, |side| side == MergePreference::Secondary)
+    };
+    let email = choose_primary_email(
+        primary_emails,
+        secondary_emails,
+        wins_secondary(fields.email),
+    );
+    let display_name = if wins_secondary(fields.display_name) {
         secondary.display_name.clone()
     } else {
         primary.display_name.clone()
@@ -829,41 +1953,83 @@ fn merge_contact_fields(
     let phone = choose_optional(
         primary.phone.clone(),
         secondary.phone.clone(),
-        prefer_secondary,
+        wins_secondary(fields.phone),
     );
     let handle = choose_optional(
         primary.handle.clone(),
         secondary.handle.clone(),
-        prefer_secondary,
+        wins_secondary(fields.handle),
     );
     let timezone = choose_optional(
         primary.timezone.clone(),
         secondary.timezone.clone(),
-        prefer_secondary,
+        wins_secondary(fields.timezone),
     );
-    let cadence_days = choose_optional(
-        primary.cadence_days,
-        secondary.cadence_days,
+    let organization = choose_optional(
+        primary.organization.clone(),
+        secondary.organization.clone(),
         prefer_secondary,
     );
+    let cadence_days = match fields.cadence_days {
+        Some(side) => choose_optional(
+            primary.cadence_days,
+            secondary.cadence_days,
+            side == MergePreference::Secondary,
+        ),
+        None => match options.cadence {
+            MergeCadencePreference::Preferred => choose_optional(
+                primary.cadence_days,
+                secondary.cadence_days,
+                prefer_secondary,
+            ),
+            MergeCadencePreference::Shortest => {
+                match (primary.cadence_days, secondary.cadence_days) {
+                    (Some(a), Some(b)) => Some(a.min(b)),
+                    (value, None) | (None, value) => value,
+                }
+            }
+            MergeCadencePreference::Longest => {
+                match (primary.cadence_days, secondary.cadence_days) {
+                    (Some(a), Some(b)) => Some(a.max(b)),
+                    (value, None) | (None, value) => value,
+                }
+            }
+        },
+    };
 
-    let next_touchpoint_at = match options.touchpoint {
-        MergeTouchpointPreference::Primary => primary.next_touchpoint_at,
-        MergeTouchpointPreference::Secondary => secondary.next_touchpoint_at,
-        MergeTouchpointPreference::Earliest => {
-            match (primary.next_touchpoint_at, secondary.next_touchpoint_at) {
-                (Some(a), Some(b)) => Some(a.min(b)),
-                (Some(value), None) | (None, Some(value)) => Some(value),
-                (None, None) => None,
+    let next_touchpoint_at = match fields.next_touchpoint_at {
+        Some(side) => choose_optional(
+            primary.next_touchpoint_at,
+            secondary.next_touchpoint_at,
+            side == MergePreference::Secondary,
+        ),
+        None => match options.touchpoint {
+            MergeTouchpointPreference::Primary => primary.next_touchpoint_at,
+            MergeTouchpointPreference::Secondary => secondary.next_touchpoint_at,
+            MergeTouchpointPreference::Earliest => {
+                match (primary.next_touchpoint_at, secondary.next_touchpoint_at) {
+                    (Some(a), Some(b)) => Some(a.min(b)),
+                    (Some(value), None) | (None, Some(value)) => Some(value),
+                    (None, None) => None,
+                }
             }
-        }
-        MergeTouchpointPreference::Latest => {
-            match (primary.next_touchpoint_at, secondary.next_touchpoint_at) {
-                (Some(a), Some(b)) => Some(a.max(b)),
-                (Some(value), None) | (None, Some(value)) => Some(value),
-                (None, None) => None,
+            MergeTouchpointPreference::Latest => {
+                match (primary.next_touchpoint_at, secondary.next_touchpoint_at) {
+                    (Some(a), Some(b)) => Some(a.max(b)),
+                    (Some(value), None) | (None, Some(value)) => Some(value),
+                    (None, None) => None,
+                }
             }
-        }
+            MergeTouchpointPreference::Cadence => {
+                let cadence_from_secondary = cadence_days != primary.cadence_days
+                    || (primary.cadence_days == secondary.cadence_days && prefer_secondary);
+                if cadence_from_secondary {
+                    secondary.next_touchpoint_at.or(primary.next_touchpoint_at)
+                } else {
+                    primary.next_touchpoint_at.or(secondary.next_touchpoint_at)
+                }
+            }
+        },
     };
 
     let archived_at = match options.archived {
@@ -880,7 +2046,7 @@ fn merge_contact_fields(
     Contact {
         id: primary.id,
         display_name,
-        email: primary.email.clone(),
+        email,
         phone,
         handle,
         timezone,
@@ -889,6 +2055,7 @@ fn merge_contact_fields(
         created_at: primary.created_at,
         updated_at: now_utc,
         archived_at,
+        organization,
     }
 }
 
@@ -904,40 +2071,38 @@ fn choose_optional<T: Clone>(
     }
 }
 
+/// The primary email a merge keeps: the winning side's primary, then the other
+/// side's, then the first listed email on either side.
+fn choose_primary_email(
+    primary_emails: &[ContactEmail],
+    secondary_emails: &[ContactEmail],
+    prefer_secondary: bool,
+) -> Option<String> {
+    let (first, second) = if prefer_secondary {
+        (secondary_emails, primary_emails)
+    } else {
+        (primary_emails, secondary_emails)
+    };
+    first
+        .iter()
+        .find(|email| email.is_primary)
+        .or_else(|| second.iter().find(|email| email.is_primary))
+        .or_else(|| first.first())
+        .or_else(|| second.first())
+        .map(|email| email.email.clone())
+}
+
 fn merge_contact_emails(
     conn: &Connection,
     now_utc: i64,
     primary_id: &ContactId,
     secondary_id: &ContactId,
-    prefer_secondary: bool,
-) -> Result<Option<String>> {
+    primary_email: Option<&str>,
+) -> Result<()> {
     let emails_repo = crate::repo::emails::EmailsRepo::new(conn);
     let primary_emails = emails_repo.list_for_contact(primary_id)?;
     let secondary_emails = emails_repo.list_for_contact(secondary_id)?;
 
-    let primary_primary = primary_emails
-        .iter()
-        .find(|email| email.is_primary)
-        .map(|email| email.email.clone());
-    let secondary_primary = secondary_emails
-        .iter()
-        .find(|email| email.is_primary)
-        .map(|email| email.email.clone());
-
-    let primary_email = if prefer_secondary {
-        secondary_primary
-            .clone()
-            .or_else(|| primary_primary.clone())
-            .or_else(|| secondary_emails.first().map(|email| email.email.clone()))
-            .or_else(|| primary_emails.first().map(|email| email.email.clone()))
-    } else {
-        primary_primary
-            .clone()
-            .or_else(|| secondary_primary.clone())
-            .or_else(|| primary_emails.first().map(|email| email.email.clone()))
-            .or_else(|| secondary_emails.first().map(|email| email.email.clone()))
-    };
-
     let mut primary_map = std::collections::HashMap::new();
     for email in &primary_emails {
         primary_map.insert(email.email.clone(), email.clone());
@@ -977,15 +2142,11 @@ fn merge_contact_emails(
         }
     }
 
-    if primary_emails.is_empty() && secondary_emails.is_empty() {
-        return Ok(None);
-    }
-
-    if let Some(primary_email) = primary_email.as_deref() {
+    if let Some(primary_email) = primary_email {
         emails_repo.add_email(now_utc, primary_id, primary_email, Some("primary"), true)?;
     }
 
-    Ok(primary_email)
+    Ok(())
 }
 
 fn create_with_emails_and_tags_inner(
@@ -1051,6 +2212,16 @@ fn normalize_emails(emails: Vec<String>) -> Vec<String> {
     normalized
 }
 
+fn due_state_from_rank(rank: i64) -> DueState {
+    match rank {
+        0 => DueState::Overdue,
+        1 => DueState::Today,
+        2 => DueState::Soon,
+        3 => DueState::Scheduled,
+        _ => DueState::Unscheduled,
+    }
+}
+
 fn contact_from_row(row: &rusqlite::Row<'_>) -> Result<Contact> {
     let id_str: String = row.get(0)?;
     let id = ContactId::from_str(&id_str).map_err(|_| StoreError::InvalidId(id_str.clone()))?;
@@ -1066,5 +2237,6 @@ fn contact_from_row(row: &rusqlite::Row<'_>) -> Result<Contact> {
         created_at: row.get(8)?,
         updated_at: row.get(9)?,
         archived_at: row.get(10)?,
+        organization: row.get(11)?,
     })
 }
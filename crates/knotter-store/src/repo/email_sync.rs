@@ -67,6 +67,38 @@ impl<'a> EmailSyncRepo<'a> {
         ))
     }
 
+    /// Every stored account/mailbox position, ordered by account then mailbox.
+    pub fn list_states(&self) -> Result<Vec<EmailSyncState>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT account, mailbox, uidvalidity, last_uid, last_seen_at
+             FROM email_sync_state
+             ORDER BY account, mailbox;",
+        )?;
+        let rows = stmt.query_map([], |row| {
+            Ok(EmailSyncState {
+                account: row.get(0)?,
+                mailbox: row.get(1)?,
+                uidvalidity: row.get(2)?,
+                last_uid: row.get(3)?,
+                last_seen_at: row.get(4)?,
+            })
+        })?;
+        Ok(rows.collect::<rusqlite::Result<_>>()?)
+    }
+
+    /// Zeroes the stored position of one mailbox, or of every mailbox of
+    /// `account`, so the next import refetches from the first UID. Imported
+    /// message records are kept for dedupe. Returns the number of rows reset.
+    pub fn reset_state(&self, account: &str, mailbox: Option<&str>) -> Result<usize> {
+        let reset = self.conn.execute(
+            "UPDATE email_sync_state
+             SET uidvalidity = NULL, last_uid = 0, last_seen_at = NULL
+             WHERE account = ?1 AND (?2 IS NULL OR mailbox = ?2);",
+            params![account, mailbox],
+        )?;
+        Ok(reset)
+    }
+
     pub fn upsert_state(&self, state: &EmailSyncState) -> Result<()> {
         self.conn.execute(
             "INSERT INTO email_sync_state (account, mailbox, uidvalidity, last_uid, last_seen_at)
@@ -0,0 +1,122 @@
+use crate::error::Result;
+use knotter_core::domain::ContactId;
+use rusqlite::{params, Connection};
+
+/// Provenance source recorded for CLI and TUI edits.
+pub const MANUAL_SOURCE: &str = "manual";
+
+/// Contact fields whose last writer is tracked.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ContactField {
+    DisplayName,
+    Email,
+    Phone,
+    Handle,
+    Timezone,
+    Organization,
+    NextTouchpointAt,
+    CadenceDays,
+}
+
+impl ContactField {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ContactField::DisplayName => "display_name",
+            ContactField::Email => "email",
+            ContactField::Phone => "phone",
+            ContactField::Handle => "handle",
+            ContactField::Timezone => "timezone",
+            ContactField::Organization => "organization",
+            ContactField::NextTouchpointAt => "next_touchpoint_at",
+            ContactField::CadenceDays => "cadence_days",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldProvenance {
+    pub field: String,
+    pub source: String,
+    pub updated_at: i64,
+}
+
+impl FieldProvenance {
+    pub fn is_manual(&self) -> bool {
+        self.source == MANUAL_SOURCE
+    }
+}
+
+pub struct FieldProvenanceRepo<'a> {
+    conn: &'a Connection,
+}
+
+impl<'a> FieldProvenanceRepo<'a> {
+    pub fn new(conn: &'a Connection) -> Self {
+        Self { conn }
+    }
+
+    pub fn list_for_contact(&self, contact_id: ContactId) -> Result<Vec<FieldProvenance>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT field, source, updated_at
+             FROM contact_field_provenance
+             WHERE contact_id = ?1
+             ORDER BY field ASC;",
+        )?;
+        let rows = stmt.query_map([contact_id.to_string()], |row| {
+            Ok(FieldProvenance {
+                field: row.get(0)?,
+                source: row.get(1)?,
+                updated_at: row.get(2)?,
+            })
+        })?;
+        let mut provenance = Vec::new();
+        for row in rows {
+            provenance.push(row?);
+        }
+        Ok(provenance)
+    }
+}
+
+pub(crate) fn record_inner(
+    conn: &Connection,
+    now_utc: i64,
+    contact_id: ContactId,
+    fields: &[ContactField],
+    source: &str,
+) -> Result<()> {
+    for field in fields {
+        conn.execute(
+            "INSERT INTO contact_field_provenance (contact_id, field, source, updated_at)
+             VALUES (?1, ?2, ?3, ?4)
+             ON CONFLICT(contact_id, field) DO UPDATE SET
+               source = excluded.source,
+               updated_at = excluded.updated_at;",
+            params![contact_id.to_string(), field.as_str(), source, now_utc],
+        )?;
+    }
+    Ok(())
+}
+
+/// Replaces `to`'s provenance for `fields` with `from`'s (dropping it when `from`
+/// has none), for merges where those values came from `from`.
+pub(crate) fn copy_fields_inner(
+    conn: &Connection,
+    from: ContactId,
+    to: ContactId,
+    fields: &[ContactField],
+) -> Result<()> {
+    for field in fields {
+        conn.execute(
+            "DELETE FROM contact_field_provenance WHERE contact_id = ?1 AND field = ?2;",
+            params![to.to_string(), field.as_str()],
+        )?;
+        conn.execute(
+            "INSERT INTO contact_field_provenance (contact_id, field, source, updated_at)
+             SELECT ?1, field, source, updated_at
+             FROM contact_field_provenance
+             WHERE contact_id = ?2 AND field = ?3;",
+            params![to.to_string(), from.to_string(), field.as_str()],
+        )?;
+    }
+    Ok(())
+}
@@ -0,0 +1,127 @@
+use crate::error::{Result, StoreError};
+use crate::temp_table::TempContactIdTable;
+use knotter_core::domain::{
+    validate_attachment, AttachmentKind, ContactId, InteractionAttachment, InteractionId,
+};
+use rusqlite::{params, Connection, Row};
+use std::collections::HashMap;
+use std::str::FromStr;
+
+pub struct InteractionAttachmentsRepo<'a> {
+    conn: &'a Connection,
+}
+
+impl<'a> InteractionAttachmentsRepo<'a> {
+    pub fn new(conn: &'a Connection) -> Self {
+        Self { conn }
+    }
+
+    /// Links a file path or URL to an interaction; returns `false` when the
+    /// interaction already has it. URLs must have a scheme.
+    pub fn add(
+        &self,
+        now_utc: i64,
+        interaction_id: InteractionId,
+        kind: AttachmentKind,
+        value: &str,
+    ) -> Result<bool> {
+        let value = validate_attachment(kind, value)?;
+        let exists = self.conn.query_row(
+            "SELECT EXISTS(SELECT 1 FROM interactions WHERE id = ?1);",
+            [interaction_id.to_string()],
+            |row| row.get::<_, bool>(0),
+        )?;
+        if !exists {
+            return Err(StoreError::NotFound(interaction_id.to_string()));
+        }
+        let inserted = self.conn.execute(
+            "INSERT OR IGNORE INTO interaction_attachments (interaction_id, kind, value, created_at)
+             VALUES (?1, ?2, ?3, ?4);",
+            params![interaction_id.to_string(), kind.as_str(), value, now_utc],
+        )?;
+        Ok(inserted > 0)
+    }
+
+    /// Oldest first.
+    pub fn list_for_interaction(
+        &self,
+        interaction_id: InteractionId,
+    ) -> Result<Vec<InteractionAttachment>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT interaction_id, kind, value, created_at
+             FROM interaction_attachments
+             WHERE interaction_id = ?1
+             ORDER BY created_at ASC, value ASC;",
+        )?;
+        let mut rows = stmt.query([interaction_id.to_string()])?;
+        let mut attachments = Vec::new();
+        while let Some(row) = rows.next()? {
+            attachments.push(attachment_from_row(row)?);
+        }
+        Ok(attachments)
+    }
+
+    /// Attachments of every interaction the contact has, keyed by interaction.
+    pub fn list_for_contact(
+        &self,
+        contact_id: ContactId,
+    ) -> Result<HashMap<InteractionId, Vec<InteractionAttachment>>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT a.interaction_id, a.kind, a.value, a.created_at
+             FROM interaction_attachments a
+             INNER JOIN interactions i ON i.id = a.interaction_id
+             WHERE i.contact_id = ?1
+             ORDER BY a.created_at ASC, a.value ASC;",
+        )?;
+        let rows = stmt.query([contact_id.to_string()])?;
+        group_by_interaction(rows)
+    }
+
+    pub fn list_for_contacts(
+        &self,
+        contact_ids: &[ContactId],
+    ) -> Result<HashMap<InteractionId, Vec<InteractionAttachment>>> {
+        if contact_ids.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        let temp_table = TempContactIdTable::create(self.conn, contact_ids)?;
+        let temp_table_name = temp_table.name();
+
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT a.interaction_id, a.kind, a.value, a.created_at
+             FROM interaction_attachments a
+             INNER JOIN interactions i ON i.id = a.interaction_id
+             INNER JOIN {temp_table_name} tmp ON tmp.id = i.contact_id
+             ORDER BY a.created_at ASC, a.value ASC;"
+        ))?;
+        let rows = stmt.query([])?;
+        group_by_interaction(rows)
+    }
+}
+
+fn group_by_interaction(
+    mut rows: rusqlite::Rows<'_>,
+) -> Result<HashMap<InteractionId, Vec<InteractionAttachment>>> {
+    let mut map: HashMap<InteractionId, Vec<InteractionAttachment>> = HashMap::new();
+    while let Some(row) = rows.next()? {
+        let attachment = attachment_from_row(row)?;
+        map.entry(attachment.interaction_id)
+            .or_default()
+            .push(attachment);
+    }
+    Ok(map)
+}
+
+fn attachment_from_row(row: &Row<'_>) -> Result<InteractionAttachment> {
+    let interaction_id_raw: String = row.get(0)?;
+    let interaction_id = InteractionId::from_str(&interaction_id_raw)
+        .map_err(|_| StoreError::InvalidId(interaction_id_raw.clone()))?;
+    let kind_raw: String = row.get(1)?;
+    Ok(InteractionAttachment {
+        interaction_id,
+        kind: AttachmentKind::from_str(&kind_raw)?,
+        value: row.get(2)?,
+        created_at: row.get(3)?,
+    })
+}
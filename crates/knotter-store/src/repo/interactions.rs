@@ -1,6 +1,8 @@
 use crate::error::{Result, StoreError};
 use crate::temp_table::TempContactIdTable;
-use knotter_core::domain::{ContactId, Interaction, InteractionId, InteractionKind};
+use knotter_core::domain::{
+    ContactId, Interaction, InteractionDirection, InteractionId, InteractionKind,
+};
 use knotter_core::rules::next_touchpoint_after_touch;
 use rusqlite::{params, Connection, OptionalExtension};
 use std::collections::HashMap;
@@ -14,6 +16,70 @@ pub struct InteractionNew {
     pub kind: InteractionKind,
     pub note: String,
     pub follow_up_at: Option<i64>,
+    pub direction: Option<InteractionDirection>,
+}
+
+/// Fields to change on an existing interaction; `None` leaves the value as is.
+#[derive(Debug, Clone, Default)]
+pub struct InteractionUpdate {
+    pub occurred_at: Option<i64>,
+    pub kind: Option<InteractionKind>,
+    pub note: Option<String>,
+}
+
+/// `occurred_at` window for paging a contact's history: `since` is inclusive,
+/// `until` exclusive; `None` leaves that side open.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct InteractionRange {
+    pub since: Option<i64>,
+    pub until: Option<i64>,
+}
+
+/// An interaction with a pending follow-up, joined with its contact's name.
+#[derive(Debug, Clone)]
+pub struct FollowUp {
+    pub interaction: Interaction,
+    pub display_name: String,
+}
+
+/// What [`InteractionsRepo::add_unless_recent`] did with the new interaction.
+#[derive(Debug, Clone)]
+pub enum InteractionAdd {
+    Added(Interaction),
+    /// A matching interaction was already logged inside the window; it is
+    /// returned and nothing was inserted or rescheduled.
+    AlreadyRecorded(Interaction),
+}
+
+/// An interaction found by [`InteractionsRepo::find_duplicates`], with the
+/// earlier one it repeats.
+#[derive(Debug, Clone)]
+pub struct DuplicateInteraction {
+    pub interaction: Interaction,
+    pub kept_id: InteractionId,
+    pub kept_occurred_at: i64,
+}
+
+/// What [`InteractionsRepo::delete_duplicates`] removed.
+#[derive(Debug, Clone, Default)]
+pub struct InteractionDedupe {
+    pub removed: Vec<DuplicateInteraction>,
+    /// Email and telegram sync rows moved onto a kept interaction's time.
+    pub relinked_messages: usize,
+}
+
+impl InteractionAdd {
+    pub fn interaction(&self) -> &Interaction {
+        match self {
+            Self::Added(interaction) | Self::AlreadyRecorded(interaction) => interaction,
+        }
+    }
+
+    pub fn into_interaction(self) -> Interaction {
+        match self {
+            Self::Added(interaction) | Self::AlreadyRecorded(interaction) => interaction,
+        }
+    }
 }
 
 pub struct InteractionsRepo<'a> {
@@ -58,20 +124,329 @@ impl<'a> InteractionsRepo<'a> {
         add_with_reschedule_inner(self.conn, now_utc, input, reschedule)
     }
 
+    /// Guard for manually logged interactions: when the contact already has
+    /// one of the same kind and note whose `occurred_at` is less than
+    /// `window_seconds` away from the new one, returns it instead of adding
+    /// another. A window of 0 always adds.
+    pub fn add_unless_recent(
+        &self,
+        now_utc: i64,
+        input: InteractionNew,
+        reschedule: bool,
+        window_seconds: i64,
+    ) -> Result<InteractionAdd> {
+        let tx = self.conn.unchecked_transaction()?;
+        let outcome = add_unless_recent_inner(&tx, now_utc, input, reschedule, window_seconds)?;
+        tx.commit()?;
+        Ok(outcome)
+    }
+
+    pub fn add_unless_recent_in_tx(
+        &self,
+        now_utc: i64,
+        input: InteractionNew,
+        reschedule: bool,
+        window_seconds: i64,
+    ) -> Result<InteractionAdd> {
+        add_unless_recent_inner(self.conn, now_utc, input, reschedule, window_seconds)
+    }
+
+    pub fn get(&self, id: InteractionId) -> Result<Option<Interaction>> {
+        get_inner(self.conn, id)
+    }
+
+    /// Edits an interaction. `occurred_at` may not move past `now_utc`.
+    ///
+    /// With `reschedule`, moving the contact's most recent interaction re-anchors
+    /// `next_touchpoint_at` on whichever interaction is latest afterwards.
+    pub fn update(
+        &self,
+        now_utc: i64,
+        id: InteractionId,
+        update: InteractionUpdate,
+        reschedule: bool,
+    ) -> Result<Interaction> {
+        if let Some(occurred_at) = update.occurred_at {
+            if occurred_at > now_utc {
+                return Err(StoreError::InvalidInteraction(
+                    "occurred_at cannot be in the future".to_string(),
+                ));
+            }
+        }
+
+        let tx = self.conn.unchecked_transaction()?;
+        let existing = get_inner(&tx, id)?.ok_or_else(|| StoreError::NotFound(id.to_string()))?;
+        let latest_before = latest_occurred_at_inner(&tx, existing.contact_id)?;
+
+        let updated = Interaction {
+            occurred_at: update.occurred_at.unwrap_or(existing.occurred_at),
+            kind: update.kind.unwrap_or_else(|| existing.kind.clone()),
+            note: update.note.unwrap_or_else(|| existing.note.clone()),
+            ..existing.clone()
+        };
+        tx.execute(
+            "UPDATE interactions SET occurred_at = ?2, kind = ?3, note = ?4 WHERE id = ?1;",
+            params![
+                id.to_string(),
+                updated.occurred_at,
+                serialize_kind(&updated.kind)?,
+                updated.note,
+            ],
+        )?;
+
+        if reschedule && updated.occurred_at != existing.occurred_at {
+            let latest_after = latest_occurred_at_inner(&tx, existing.contact_id)?;
+            if latest_before == Some(existing.occurred_at)
+                || latest_after == Some(updated.occurred_at)
+            {
+                reschedule_from_latest_inner(&tx, now_utc, existing.contact_id, latest_after)?;
+            }
+        }
+
+        tx.commit()?;
+        Ok(updated)
+    }
+
+    /// Deletes an interaction and returns it.
+    ///
+    /// With `reschedule`, deleting the contact's most recent interaction re-anchors
+    /// `next_touchpoint_at` on the latest remaining one.
+    pub fn delete(&self, now_utc: i64, id: InteractionId, reschedule: bool) -> Result<Interaction> {
+        let tx = self.conn.unchecked_transaction()?;
+        let existing = get_inner(&tx, id)?.ok_or_else(|| StoreError::NotFound(id.to_string()))?;
+        let latest_before = latest_occurred_at_inner(&tx, existing.contact_id)?;
+
+        tx.execute("DELETE FROM interactions WHERE id = ?1;", [id.to_string()])?;
+
+        if reschedule && latest_before == Some(existing.occurred_at) {
+            let latest_after = latest_occurred_at_inner(&tx, existing.contact_id)?;
+            reschedule_from_latest_inner(&tx, now_utc, existing.contact_id, latest_after)?;
+        }
+
+        tx.commit()?;
+        Ok(existing)
+    }
+
+    /// Interactions that repeat an earlier one for the same contact with the
+    /// same kind and note, at most `window_seconds` after the previous
+    /// occurrence. Runs of such repeats are chained, so each run keeps its
+    /// earliest interaction and every later one is listed. Ordered by contact,
+    /// then time.
+    pub fn find_duplicates(&self, window_seconds: i64) -> Result<Vec<DuplicateInteraction>> {
+        find_duplicates_inner(self.conn, window_seconds)
+    }
+
+    /// Deletes what [`Self::find_duplicates`] finds, in one transaction.
+    ///
+    /// A follow-up or direction only the duplicate had moves to the kept
+    /// interaction, and so do its attachments. Email and telegram sync rows,
+    /// which are matched to their interaction by contact and time, are moved
+    /// to the kept interaction's time when nothing else of that kind is left
+    /// at theirs. Schedules are left alone.
+    pub fn delete_duplicates(&self, window_seconds: i64) -> Result<InteractionDedupe> {
+        let tx = self.conn.unchecked_transaction()?;
+        let removed = find_duplicates_inner(&tx, window_seconds)?;
+        for duplicate in &removed {
+            let interaction = &duplicate.interaction;
+            tx.execute(
+                "UPDATE interactions
+                 SET follow_up_at = COALESCE(follow_up_at, ?2),
+                     direction = COALESCE(direction, ?3)
+                 WHERE id = ?1;",
+                params![
+                    duplicate.kept_id.to_string(),
+                    interaction.follow_up_at,
+                    interaction.direction.map(InteractionDirection::as_str),
+                ],
+            )?;
+            tx.execute(
+                "UPDATE OR IGNORE interaction_attachments SET interaction_id = ?1
+                 WHERE interaction_id = ?2;",
+                params![duplicate.kept_id.to_string(), interaction.id.to_string()],
+            )?;
+            tx.execute(
+                "DELETE FROM interactions WHERE id = ?1;",
+                [interaction.id.to_string()],
+            )?;
+        }
+
+        let mut relinked_messages = 0;
+        for duplicate in &removed {
+            let interaction = &duplicate.interaction;
+            let table = match interaction.kind {
+                InteractionKind::Email => "email_messages",
+                InteractionKind::Telegram => "telegram_messages",
+                _ => continue,
+            };
+            if interaction.occurred_at == duplicate.kept_occurred_at {
+                continue;
+            }
+            relinked_messages += tx.execute(
+                &format!(
+                    "UPDATE {table} SET occurred_at = ?3
+                     WHERE contact_id = ?1 AND occurred_at = ?2
+                       AND NOT EXISTS (
+                         SELECT 1 FROM interactions
+                         WHERE contact_id = ?1 AND occurred_at = ?2 AND kind = ?4
+                       );"
+                ),
+                params![
+                    interaction.contact_id.to_string(),
+                    interaction.occurred_at,
+                    duplicate.kept_occurred_at,
+                    serialize_kind(&interaction.kind)?,
+                ],
+            )?;
+        }
+
+        tx.commit()?;
+        Ok(InteractionDedupe {
+            removed,
+            relinked_messages,
+        })
+    }
+
+    /// Pending follow-ups due at or before `until` on active contacts, earliest first.
+    pub fn list_follow_ups(&self, until: i64) -> Result<Vec<FollowUp>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT interactions.id,
+                    interactions.contact_id,
+                    interactions.occurred_at,
+                    interactions.created_at,
+                    interactions.kind,
+                    interactions.note,
+                    interactions.follow_up_at,
+                    interactions.direction,
+                    contacts.display_name
+             FROM interactions
+             INNER JOIN contacts ON contacts.id = interactions.contact_id
+             WHERE interactions.follow_up_at IS NOT NULL
+               AND interactions.follow_up_at <= ?1
+               AND contacts.archived_at IS NULL
+               AND contacts.deleted_at IS NULL
+             ORDER BY interactions.follow_up_at ASC,
+                      contacts.display_name COLLATE NOCASE ASC,
+                      interactions.id ASC;",
+        )?;
+        let mut rows = stmt.query([until])?;
+        let mut items = Vec::new();
+        while let Some(row) = rows.next()? {
+            items.push(FollowUp {
+                interaction: interaction_from_row(row)?,
+                display_name: row.get(8)?,
+            });
+        }
+        Ok(items)
+    }
+
+    /// Marks a follow-up as done by clearing `follow_up_at`.
+    pub fn clear_follow_up(&self, id: InteractionId) -> Result<Interaction> {
+        clear_follow_up_inner(self.conn, id)
+    }
+
+    /// Clears a follow-up and logs a new interaction for the same contact in one
+    /// transaction. Returns the cleared interaction and the new one.
+    pub fn complete_follow_up(
+        &self,
+        now_utc: i64,
+        id: InteractionId,
+        kind: InteractionKind,
+        note: String,
+        reschedule: bool,
+    ) -> Result<(Interaction, Interaction)> {
+        let tx = self.conn.unchecked_transaction()?;
+        let cleared = clear_follow_up_inner(&tx, id)?;
+        let input = InteractionNew {
+            contact_id: cleared.contact_id,
+            occurred_at: now_utc,
+            created_at: now_utc,
+            kind,
+            note,
+            follow_up_at: None,
+            direction: None,
+        };
+        let added = if reschedule {
+            add_with_reschedule_inner(&tx, now_utc, input, true)?
+        } else {
+            add_inner(&tx, input)?
+        };
+        tx.commit()?;
+        Ok((cleared, added))
+    }
+
+    /// Whether the contact already has an interaction of `kind` with exactly this note.
+    pub fn exists_with_note(
+        &self,
+        contact_id: ContactId,
+        kind: &InteractionKind,
+        note: &str,
+    ) -> Result<bool> {
+        let count: i64 = self.conn.query_row(
+            "SELECT COUNT(*) FROM interactions
+             WHERE contact_id = ?1 AND kind = ?2 AND note = ?3;",
+            params![contact_id.to_string(), serialize_kind(kind)?, note],
+            |row| row.get(0),
+        )?;
+        Ok(count > 0)
+    }
+
+    /// Whether an identical interaction (same contact, time, kind, and note)
+    /// is already logged; lets repeated bulk imports skip rows they added before.
+    pub fn exists_at(
+        &self,
+        contact_id: ContactId,
+        occurred_at: i64,
+        kind: &InteractionKind,
+        note: &str,
+    ) -> Result<bool> {
+        let count: i64 = self.conn.query_row(
+            "SELECT COUNT(*) FROM interactions
+             WHERE contact_id = ?1 AND occurred_at = ?2 AND kind = ?3 AND note = ?4;",
+            params![
+                contact_id.to_string(),
+                occurred_at,
+                serialize_kind(kind)?,
+                note
+            ],
+            |row| row.get(0),
+        )?;
+        Ok(count > 0)
+    }
+
     pub fn list_for_contact(
         &self,
         contact_id: ContactId,
         limit: i64,
         offset: i64,
+    ) -> Result<Vec<Interaction>> {
+        self.list_for_contact_in_range(contact_id, InteractionRange::default(), Some(limit), offset)
+    }
+
+    /// Newest-first page of a contact's interactions within `range`; `limit: None`
+    /// returns every remaining row.
+    pub fn list_for_contact_in_range(
+        &self,
+        contact_id: ContactId,
+        range: InteractionRange,
+        limit: Option<i64>,
+        offset: i64,
     ) -> Result<Vec<Interaction>> {
         let mut stmt = self.conn.prepare(
-            "SELECT id, contact_id, occurred_at, created_at, kind, note, follow_up_at
+            "SELECT id, contact_id, occurred_at, created_at, kind, note, follow_up_at, direction
              FROM interactions
              WHERE contact_id = ?1
-             ORDER BY occurred_at DESC
-             LIMIT ?2 OFFSET ?3;",
+               AND (?2 IS NULL OR occurred_at >= ?2)
+               AND (?3 IS NULL OR occurred_at < ?3)
+             ORDER BY occurred_at DESC, created_at DESC, id ASC
+             LIMIT ?4 OFFSET ?5;",
         )?;
-        let mut rows = stmt.query(params![contact_id.to_string(), limit, offset])?;
+        let mut rows = stmt.query(params![
+            contact_id.to_string(),
+            range.since,
+            range.until,
+            limit.unwrap_or(-1),
+            offset
+        ])?;
         let mut items = Vec::new();
         while let Some(row) = rows.next()? {
             items.push(interaction_from_row(row)?);
@@ -79,6 +454,22 @@ impl<'a> InteractionsRepo<'a> {
         Ok(items)
     }
 
+    pub fn count_for_contact_in_range(
+        &self,
+        contact_id: ContactId,
+        range: InteractionRange,
+    ) -> Result<usize> {
+        let count: i64 = self.conn.query_row(
+            "SELECT COUNT(*) FROM interactions
+             WHERE contact_id = ?1
+               AND (?2 IS NULL OR occurred_at >= ?2)
+               AND (?3 IS NULL OR occurred_at < ?3);",
+            params![contact_id.to_string(), range.since, range.until],
+            |row| row.get(0),
+        )?;
+        Ok(count as usize)
+    }
+
     pub fn list_for_contacts(
         &self,
         contact_ids: &[ContactId],
@@ -98,7 +489,8 @@ impl<'a> InteractionsRepo<'a> {
                     interactions.created_at,
                     interactions.kind,
                     interactions.note,
-                    interactions.follow_up_at
+                    interactions.follow_up_at,
+                    interactions.direction
              FROM interactions
              INNER JOIN {temp_table_name} tmp ON tmp.id = interactions.contact_id
              ORDER BY interactions.contact_id ASC,
@@ -148,6 +540,39 @@ impl<'a> InteractionsRepo<'a> {
         Ok(map)
     }
 
+    /// Occurrence timestamps at or after `since`, oldest first, keyed by contact.
+    pub fn occurred_at_since_for_contacts(
+        &self,
+        contact_ids: &[ContactId],
+        since: i64,
+    ) -> Result<HashMap<ContactId, Vec<i64>>> {
+        let mut map: HashMap<ContactId, Vec<i64>> = HashMap::new();
+        if contact_ids.is_empty() {
+            return Ok(map);
+        }
+
+        let temp_table = TempContactIdTable::create(self.conn, contact_ids)?;
+        let temp_table_name = temp_table.name();
+
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT interactions.contact_id, interactions.occurred_at
+             FROM interactions
+             INNER JOIN {temp_table_name} tmp ON tmp.id = interactions.contact_id
+             WHERE interactions.occurred_at >= ?1
+             ORDER BY interactions.contact_id ASC, interactions.occurred_at ASC;"
+        ))?;
+        let mut rows = stmt.query([since])?;
+        while let Some(row) = rows.next()? {
+            let contact_id_raw: String = row.get(0)?;
+            let contact_id = ContactId::from_str(&contact_id_raw)
+                .map_err(|_| StoreError::InvalidId(contact_id_raw.clone()))?;
+            let occurred_at: i64 = row.get(1)?;
+            map.entry(contact_id).or_default().push(occurred_at);
+        }
+
+        Ok(map)
+    }
+
     pub fn touch_contact(
         &self,
         now_utc: i64,
@@ -207,10 +632,52 @@ impl<'a> InteractionsRepo<'a> {
             kind,
             note: String::new(),
             follow_up_at: None,
+            direction: None,
         })
     }
 }
 
+fn add_unless_recent_inner(
+    conn: &Connection,
+    now_utc: i64,
+    input: InteractionNew,
+    reschedule: bool,
+    window_seconds: i64,
+) -> Result<InteractionAdd> {
+    if window_seconds > 0 {
+        let existing: Option<String> = conn
+            .query_row(
+                "SELECT id FROM interactions
+                 WHERE contact_id = ?1 AND kind = ?2 AND note = ?3
+                   AND ABS(occurred_at - ?4) < ?5
+                 ORDER BY ABS(occurred_at - ?4), id
+                 LIMIT 1;",
+                params![
+                    input.contact_id.to_string(),
+                    serialize_kind(&input.kind)?,
+                    input.note,
+                    input.occurred_at,
+                    window_seconds
+                ],
+                |row| row.get(0),
+            )
+            .optional()?;
+        if let Some(id) = existing {
+            let id = InteractionId::from_str(&id).map_err(|_| StoreError::InvalidId(id.clone()))?;
+            if let Some(interaction) = get_inner(conn, id)? {
+                return Ok(InteractionAdd::AlreadyRecorded(interaction));
+            }
+        }
+    }
+
+    let interaction = if reschedule {
+        add_with_reschedule_inner(conn, now_utc, input, reschedule)?
+    } else {
+        add_inner(conn, input)?
+    };
+    Ok(InteractionAdd::Added(interaction))
+}
+
 fn add_with_reschedule_inner(
     conn: &Connection,
     now_utc: i64,
@@ -244,13 +711,128 @@ fn add_with_reschedule_inner(
     add_inner(conn, input)
 }
 
+/// Gaps and islands: a row more than the window after the previous one with
+/// the same contact, kind, and note starts a new run, and each run keeps its
+/// first row.
+fn find_duplicates_inner(
+    conn: &Connection,
+    window_seconds: i64,
+) -> Result<Vec<DuplicateInteraction>> {
+    let mut stmt = conn.prepare(
+        "WITH stepped AS (
+           SELECT *,
+                  CASE WHEN occurred_at - LAG(occurred_at) OVER same <= ?1 THEN 0 ELSE 1 END
+                    AS starts_run
+           FROM interactions
+           WINDOW same AS (PARTITION BY contact_id, kind, note
+                           ORDER BY occurred_at, created_at, id)
+         ),
+         runs AS (
+           SELECT *,
+                  SUM(starts_run) OVER (PARTITION BY contact_id, kind, note
+                                        ORDER BY occurred_at, created_at, id
+                                        ROWS UNBOUNDED PRECEDING) AS run
+           FROM stepped
+         ),
+         kept AS (
+           SELECT *,
+                  FIRST_VALUE(id) OVER run_rows AS kept_id,
+                  FIRST_VALUE(occurred_at) OVER run_rows AS kept_occurred_at
+           FROM runs
+           WINDOW run_rows AS (PARTITION BY contact_id, kind, note, run
+                               ORDER BY occurred_at, created_at, id)
+         )
+         SELECT id, contact_id, occurred_at, created_at, kind, note, follow_up_at, direction,
+                kept_id, kept_occurred_at
+         FROM kept
+         WHERE id <> kept_id
+         ORDER BY contact_id, occurred_at, created_at, id;",
+    )?;
+    let mut rows = stmt.query([window_seconds])?;
+    let mut duplicates = Vec::new();
+    while let Some(row) = rows.next()? {
+        let kept_id: String = row.get(8)?;
+        duplicates.push(DuplicateInteraction {
+            interaction: interaction_from_row(row)?,
+            kept_id: InteractionId::from_str(&kept_id)
+                .map_err(|_| StoreError::InvalidId(kept_id.clone()))?,
+            kept_occurred_at: row.get(9)?,
+        });
+    }
+    Ok(duplicates)
+}
+
+fn get_inner(conn: &Connection, id: InteractionId) -> Result<Option<Interaction>> {
+    let mut stmt = conn.prepare(
+        "SELECT id, contact_id, occurred_at, created_at, kind, note, follow_up_at, direction
+         FROM interactions
+         WHERE id = ?1;",
+    )?;
+    let mut rows = stmt.query([id.to_string()])?;
+    match rows.next()? {
+        Some(row) => Ok(Some(interaction_from_row(row)?)),
+        None => Ok(None),
+    }
+}
+
+fn clear_follow_up_inner(conn: &Connection, id: InteractionId) -> Result<Interaction> {
+    let existing = get_inner(conn, id)?.ok_or_else(|| StoreError::NotFound(id.to_string()))?;
+    if existing.follow_up_at.is_none() {
+        return Err(StoreError::InvalidInteraction(format!(
+            "interaction {id} has no pending follow-up"
+        )));
+    }
+    conn.execute(
+        "UPDATE interactions SET follow_up_at = NULL WHERE id = ?1;",
+        [id.to_string()],
+    )?;
+    Ok(Interaction {
+        follow_up_at: None,
+        ..existing
+    })
+}
+
+fn latest_occurred_at_inner(conn: &Connection, contact_id: ContactId) -> Result<Option<i64>> {
+    Ok(conn.query_row(
+        "SELECT MAX(occurred_at) FROM interactions WHERE contact_id = ?1;",
+        [contact_id.to_string()],
+        |row| row.get(0),
+    )?)
+}
+
+/// Recomputes `next_touchpoint_at` from the latest interaction. Contacts without a
+/// cadence or without any remaining interactions keep their current schedule.
+fn reschedule_from_latest_inner(
+    conn: &Connection,
+    now_utc: i64,
+    contact_id: ContactId,
+    latest: Option<i64>,
+) -> Result<()> {
+    let Some(latest) = latest else {
+        return Ok(());
+    };
+    let (cadence_days, existing_next): (Option<i32>, Option<i64>) = conn.query_row(
+        "SELECT cadence_days, next_touchpoint_at FROM contacts WHERE id = ?1;",
+        [contact_id.to_string()],
+        |row| Ok((row.get(0)?, row.get(1)?)),
+    )?;
+    let next_touchpoint = next_touchpoint_after_touch(latest, cadence_days, true, existing_next)?;
+    if next_touchpoint != existing_next {
+        conn.execute(
+            "UPDATE contacts SET next_touchpoint_at = ?2, updated_at = ?3 WHERE id = ?1;",
+            params![contact_id.to_string(), next_touchpoint, now_utc],
+        )?;
+    }
+    Ok(())
+}
+
 fn add_inner(conn: &Connection, input: InteractionNew) -> Result<Interaction> {
     let id = InteractionId::new();
     let kind = serialize_kind(&input.kind)?;
 
     conn.execute(
-        "INSERT INTO interactions (id, contact_id, occurred_at, created_at, kind, note, follow_up_at)
-         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7);",
+        "INSERT INTO interactions (id, contact_id, occurred_at, created_at, kind, note, follow_up_at, direction)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8);",
         params![
             id.to_string(),
             input.contact_id.to_string(),
@@ -259,6 +841,7 @@ fn add_inner(conn: &Connection, input: InteractionNew) -> Result<Interaction> {
             kind,
             input.note,
             input.follow_up_at,
+            input.direction.map(InteractionDirection::as_str),
         ],
     )?;
 
@@ -270,6 +853,7 @@ fn add_inner(conn: &Connection, input: InteractionNew) -> Result<Interaction> {
         kind: input.kind,
         note: input.note,
         follow_up_at: input.follow_up_at,
+        direction: input.direction,
     })
 }
 
@@ -317,6 +901,11 @@ fn interaction_from_row(row: &rusqlite::Row<'_>) -> Result<Interaction> {
         .map_err(|_| StoreError::InvalidId(contact_id_str.clone()))?;
     let kind_raw: String = row.get(4)?;
     let kind = parse_kind(&kind_raw)?;
+    let direction_raw: Option<String> = row.get(7)?;
+    let direction = direction_raw
+        .as_deref()
+        .map(InteractionDirection::from_str)
+        .transpose()?;
     Ok(Interaction {
         id,
         contact_id,
@@ -325,5 +914,6 @@ fn interaction_from_row(row: &rusqlite::Row<'_>) -> Result<Interaction> {
         kind,
         note: row.get(5)?,
         follow_up_at: row.get(6)?,
+        direction,
     })
 }
@@ -0,0 +1,56 @@
+use crate::error::Result;
+use knotter_core::domain::{ContactId, InteractionDirection};
+use knotter_core::dto::{LastTouchContextDto, TouchChannel};
+use rusqlite::{Connection, OptionalExtension};
+
+/// Reads the latest synced message per channel straight from the message
+/// tables the syncs already maintain, so it follows merges and mailbox resets.
+pub struct LastTouchRepo<'a> {
+    conn: &'a Connection,
+}
+
+impl<'a> LastTouchRepo<'a> {
+    pub fn new(conn: &'a Connection) -> Self {
+        Self { conn }
+    }
+
+    /// One entry per channel with synced messages, most recent first.
+    pub fn list_for_contact(&self, contact_id: ContactId) -> Result<Vec<LastTouchContextDto>> {
+        let mut touches = Vec::new();
+        for (channel, sql) in [
+            (
+                TouchChannel::Telegram,
+                "SELECT occurred_at, direction, snippet FROM telegram_messages
+                 WHERE contact_id = ?1
+                 ORDER BY occurred_at DESC, message_id DESC
+                 LIMIT 1;",
+            ),
+            (
+                TouchChannel::Email,
+                "SELECT occurred_at, direction, subject FROM email_messages
+                 WHERE contact_id = ?1
+                 ORDER BY occurred_at DESC, uid DESC
+                 LIMIT 1;",
+            ),
+        ] {
+            let row: Option<(i64, String, Option<String>)> = self
+                .conn
+                .query_row(sql, [contact_id.to_string()], |row| {
+                    Ok((row.get(0)?, row.get(1)?, row.get(2)?))
+                })
+                .optional()?;
+            if let Some((occurred_at, direction, text)) = row {
+                touches.push(LastTouchContextDto {
+                    channel,
+                    occurred_at,
+                    direction: direction.parse::<InteractionDirection>().ok(),
+                    text: text
+                        .map(|value| value.trim().to_string())
+                        .filter(|value| !value.is_empty()),
+                });
+            }
+        }
+        touches.sort_by_key(|touch| std::cmp::Reverse(touch.occurred_at));
+        Ok(touches)
+    }
+}
@@ -1,6 +1,8 @@
 use crate::error::{Result, StoreError};
+use crate::query::ListPage;
 use knotter_core::domain::{ContactId, MergeCandidateId, MergeCandidateReason};
-use rusqlite::{params, Connection, ErrorCode};
+use rusqlite::types::Value;
+use rusqlite::{params, params_from_iter, Connection, ErrorCode};
 use std::str::FromStr;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -68,11 +70,81 @@ pub struct MergeCandidateCreate {
     pub preferred_contact_id: Option<ContactId>,
 }
 
+/// Filters and paging for [`MergeCandidatesRepo::list`]; the default lists
+/// every candidate, newest first.
+#[derive(Debug, Clone, Default)]
+pub struct MergeCandidateQuery {
+    pub status: Option<MergeCandidateStatus>,
+    /// Matches any of these reasons; empty matches every reason.
+    pub reasons: Vec<String>,
+    pub source: Option<String>,
+    /// Only candidates created before this timestamp.
+    pub created_before: Option<i64>,
+    pub page: Option<ListPage>,
+}
+
+impl MergeCandidateQuery {
+    pub fn status(status: MergeCandidateStatus) -> Self {
+        Self {
+            status: Some(status),
+            ..Self::default()
+        }
+    }
+
+    fn where_clause(&self) -> (String, Vec<Value>) {
+        let mut clauses = Vec::new();
+        let mut values = Vec::new();
+        if let Some(status) = self.status {
+            values.push(Value::from(status.as_str().to_string()));
+            clauses.push(format!("status = ?{}", values.len()));
+        }
+        if !self.reasons.is_empty() {
+            let start = values.len() + 1;
+            values.extend(self.reasons.iter().cloned().map(Value::from));
+            let placeholders: Vec<String> = (start..=values.len())
+                .map(|index| format!("?{index}"))
+                .collect();
+            clauses.push(format!("reason IN ({})", placeholders.join(", ")));
+        }
+        if let Some(source) = &self.source {
+            values.push(Value::from(source.clone()));
+            clauses.push(format!("source = ?{}", values.len()));
+        }
+        if let Some(before) = self.created_before {
+            values.push(Value::from(before));
+            clauses.push(format!("created_at < ?{}", values.len()));
+        }
+        if clauses.is_empty() {
+            (String::new(), values)
+        } else {
+            (format!("WHERE {}", clauses.join(" AND ")), values)
+        }
+    }
+}
+
+/// What `prune_open` does with the candidates it selects.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MergePruneAction {
+    Dismiss,
+    Delete,
+}
+
 pub struct MergeCandidateCreateResult {
     pub candidate: MergeCandidate,
     pub created: bool,
 }
 
+/// What happened when a merge decision from another database was applied.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MergeDecisionOutcome {
+    /// No candidate existed for the pair, so one was recorded with the incoming status.
+    Created,
+    /// A local open candidate for the pair was dismissed.
+    Dismissed,
+    /// The pair already carried an equivalent or final local decision.
+    Unchanged,
+}
+
 pub struct MergeCandidatesRepo<'a> {
     conn: &'a Connection,
 }
@@ -82,35 +154,75 @@ impl<'a> MergeCandidatesRepo<'a> {
         Self { conn }
     }
 
-    pub fn list(&self, status: Option<MergeCandidateStatus>) -> Result<Vec<MergeCandidate>> {
+    pub fn list(&self, query: &MergeCandidateQuery) -> Result<Vec<MergeCandidate>> {
+        let (where_clause, mut values) = query.where_clause();
+        let mut sql = format!(
+            "SELECT id, created_at, status, reason, source, contact_a_id, contact_b_id, preferred_contact_id, resolved_at
+             FROM contact_merge_candidates
+             {where_clause}
+             ORDER BY created_at DESC, id ASC"
+        );
+        if let Some(page) = query.page {
+            values.push(Value::from(page.limit.min(i64::MAX as usize) as i64));
+            values.push(Value::from(page.offset as i64));
+            sql.push_str(&format!(
+                " LIMIT ?{} OFFSET ?{}",
+                values.len() - 1,
+                values.len()
+            ));
+        }
+        let mut stmt = self.conn.prepare(&sql)?;
+        let mut rows = stmt.query(params_from_iter(values))?;
         let mut candidates = Vec::new();
-        let mut stmt = match status {
-            Some(_) => self.conn.prepare(
-                "SELECT id, created_at, status, reason, source, contact_a_id, contact_b_id, preferred_contact_id, resolved_at
-                 FROM contact_merge_candidates
-                 WHERE status = ?1
-                 ORDER BY created_at DESC;",
-            )?,
-            None => self.conn.prepare(
-                "SELECT id, created_at, status, reason, source, contact_a_id, contact_b_id, preferred_contact_id, resolved_at
-                 FROM contact_merge_candidates
-                 ORDER BY created_at DESC;",
-            )?,
-        };
-
-        let mut rows = match status {
-            Some(status) => stmt.query([status.as_str()])?,
-            None => stmt.query([])?,
-        };
-
         while let Some(row) = rows.next()? {
             candidates.push(merge_candidate_from_row(row)?);
         }
         Ok(candidates)
     }
 
+    /// Candidates matching `query`, ignoring its page.
+    pub fn count(&self, query: &MergeCandidateQuery) -> Result<usize> {
+        let (where_clause, values) = query.where_clause();
+        let count: i64 = self.conn.query_row(
+            &format!("SELECT COUNT(*) FROM contact_merge_candidates {where_clause}"),
+            params_from_iter(values),
+            |row| row.get(0),
+        )?;
+        Ok(count as usize)
+    }
+
     pub fn list_open(&self) -> Result<Vec<MergeCandidate>> {
-        self.list(Some(MergeCandidateStatus::Open))
+        self.list(&MergeCandidateQuery::status(MergeCandidateStatus::Open))
+    }
+
+    /// Dismisses or deletes open candidates created before `created_before` in
+    /// one statement, returning how many were affected.
+    pub fn prune_open(
+        &self,
+        now_utc: i64,
+        created_before: i64,
+        action: MergePruneAction,
+    ) -> Result<usize> {
+        let open = MergeCandidateStatus::Open.as_str();
+        let affected = match action {
+            MergePruneAction::Dismiss => self.conn.execute(
+                "UPDATE contact_merge_candidates
+                 SET status = ?3, resolved_at = ?4
+                 WHERE status = ?1 AND created_at < ?2;",
+                params![
+                    open,
+                    created_before,
+                    MergeCandidateStatus::Dismissed.as_str(),
+                    now_utc
+                ],
+            )?,
+            MergePruneAction::Delete => self.conn.execute(
+                "DELETE FROM contact_merge_candidates
+                 WHERE status = ?1 AND created_at < ?2;",
+                params![open, created_before],
+            )?,
+        };
+        Ok(affected)
     }
 
     pub fn has_open_for_contact(&self, contact_id: ContactId) -> Result<bool> {
@@ -258,6 +370,60 @@ impl<'a> MergeCandidatesRepo<'a> {
             .ok_or_else(|| StoreError::NotFound(id.to_string()))
     }
 
+    /// Reconciles an `open` or `dismissed` decision for a contact pair with the
+    /// local candidates: an incoming dismissal closes a local open candidate, and a
+    /// pair with no local history is recorded as-is. Local dismissals always win.
+    pub fn apply_decision(
+        &self,
+        now_utc: i64,
+        contact_a_id: ContactId,
+        contact_b_id: ContactId,
+        status: MergeCandidateStatus,
+        create: MergeCandidateCreate,
+    ) -> Result<MergeDecisionOutcome> {
+        if status == MergeCandidateStatus::Merged {
+            return Err(StoreError::InvalidMerge(
+                "merged decisions cannot be replayed".to_string(),
+            ));
+        }
+        let (a, b) = ordered_pair(contact_a_id, contact_b_id);
+        let open = self.find_by_pair_and_status(a, b, MergeCandidateStatus::Open)?;
+        let dismissed = self.find_by_pair_and_status(a, b, MergeCandidateStatus::Dismissed)?;
+
+        match (status, open) {
+            (MergeCandidateStatus::Dismissed, Some(open)) => {
+                self.update_status(open.id, MergeCandidateStatus::Dismissed, Some(now_utc))?;
+                Ok(MergeDecisionOutcome::Dismissed)
+            }
+            (_, Some(_)) => Ok(MergeDecisionOutcome::Unchanged),
+            (_, None) if dismissed.is_some() => Ok(MergeDecisionOutcome::Unchanged),
+            (MergeCandidateStatus::Dismissed, None) => {
+                let preferred = create
+                    .preferred_contact_id
+                    .filter(|id| *id == a || *id == b);
+                self.conn.execute(
+                    "INSERT INTO contact_merge_candidates (id, created_at, status, reason, source, contact_a_id, contact_b_id, preferred_contact_id, resolved_at)
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?2);",
+                    params![
+                        MergeCandidateId::new().to_string(),
+                        now_utc,
+                        MergeCandidateStatus::Dismissed.as_str(),
+                        create.reason,
+                        create.source,
+                        a.to_string(),
+                        b.to_string(),
+                        preferred.map(|id| id.to_string()),
+                    ],
+                )?;
+                Ok(MergeDecisionOutcome::Created)
+            }
+            (_, None) => {
+                self.create(now_utc, a, b, create)?;
+                Ok(MergeDecisionOutcome::Created)
+            }
+        }
+    }
+
     fn update_status(
         &self,
         id: MergeCandidateId,
@@ -1,29 +1,52 @@
+pub mod contact_aliases;
+pub mod contact_audit;
 pub mod contact_dates;
+pub mod contact_origins;
 pub mod contact_sources;
 pub mod contacts;
 pub mod email_sync;
 pub mod emails;
+pub mod field_provenance;
+pub mod interaction_attachments;
 pub mod interactions;
+pub mod last_touch;
 pub mod merge_candidates;
+pub mod settings;
+pub mod sync_log;
 pub mod tags;
 pub mod telegram_accounts;
 pub mod telegram_sync;
 
-pub use contact_dates::{ContactDateNew, ContactDateOccurrence, ContactDatesRepo};
+pub use contact_aliases::ContactAliasesRepo;
+pub use contact_audit::{AuditAction, AuditEntry, ContactAuditRepo, FieldChange};
+pub use contact_dates::{
+    ContactDateNew, ContactDateOccurrence, ContactDatesRepo, UpcomingContactDate,
+};
+pub use contact_origins::{ContactOriginUpdate, ContactOriginsRepo};
 pub use contact_sources::{
     ContactSource, ContactSourceMatch, ContactSourceNew, ContactSourcesRepo,
 };
 pub use contacts::{
-    ContactMergeOptions, ContactNew, ContactUpdate, ContactsRepo, EmailOps,
-    MergeArchivedPreference, MergePreference, MergeTouchpointPreference,
+    ContactDeleteImpact, ContactListRow, ContactMergeOptions, ContactNew, ContactRestore,
+    ContactUpdate, ContactsRepo, EmailOps, MergeArchivedPreference, MergeCadencePreference,
+    MergeFieldOverrides, MergePreference, MergeTouchpointPreference, MergedContactPreview,
+    TrashedContact,
 };
 pub use email_sync::{EmailMessageRecord, EmailSyncRepo, EmailSyncState};
 pub use emails::{ContactEmail, EmailsRepo};
-pub use interactions::{InteractionNew, InteractionsRepo};
+pub use field_provenance::{ContactField, FieldProvenance, FieldProvenanceRepo, MANUAL_SOURCE};
+pub use interaction_attachments::InteractionAttachmentsRepo;
+pub use interactions::{
+    DuplicateInteraction, FollowUp, InteractionAdd, InteractionDedupe, InteractionNew,
+    InteractionRange, InteractionUpdate, InteractionsRepo,
+};
+pub use last_touch::LastTouchRepo;
 pub use merge_candidates::{
-    MergeCandidate, MergeCandidateCreate, MergeCandidateCreateResult, MergeCandidateStatus,
-    MergeCandidatesRepo,
+    MergeCandidate, MergeCandidateCreate, MergeCandidateCreateResult, MergeCandidateQuery,
+    MergeCandidateStatus, MergeCandidatesRepo, MergeDecisionOutcome, MergePruneAction,
 };
+pub use settings::SettingsRepo;
+pub use sync_log::{SyncLogEntry, SyncLogRepo};
 pub use tags::TagsRepo;
 pub use telegram_accounts::{TelegramAccount, TelegramAccountNew, TelegramAccountsRepo};
 pub use telegram_sync::{TelegramMessageRecord, TelegramSyncRepo, TelegramSyncState};
@@ -0,0 +1,66 @@
+use crate::error::Result;
+use rusqlite::{params, Connection, OptionalExtension};
+
+/// Unix seconds UTC at which notifications resume.
+const VACATION_UNTIL_KEY: &str = "vacation_until";
+
+pub struct SettingsRepo<'a> {
+    conn: &'a Connection,
+}
+
+impl<'a> SettingsRepo<'a> {
+    pub fn new(conn: &'a Connection) -> Self {
+        Self { conn }
+    }
+
+    pub fn get(&self, key: &str) -> Result<Option<String>> {
+        Ok(self
+            .conn
+            .query_row("SELECT value FROM settings WHERE key = ?1;", [key], |row| {
+                row.get(0)
+            })
+            .optional()?)
+    }
+
+    pub fn set(&self, now_utc: i64, key: &str, value: &str) -> Result<()> {
+        self.conn.execute(
+            "INSERT INTO settings (key, value, updated_at) VALUES (?1, ?2, ?3)
+             ON CONFLICT(key) DO UPDATE SET value = excluded.value, updated_at = excluded.updated_at;",
+            params![key, value, now_utc],
+        )?;
+        Ok(())
+    }
+
+    /// Whether there was a value to remove.
+    pub fn remove(&self, key: &str) -> Result<bool> {
+        Ok(self
+            .conn
+            .execute("DELETE FROM settings WHERE key = ?1;", [key])?
+            > 0)
+    }
+
+    /// When vacation mode ends; the stored value is kept after it passes.
+    pub fn vacation_until(&self) -> Result<Option<i64>> {
+        Ok(self
+            .conn
+            .query_row(
+                "SELECT CAST(value AS INTEGER) FROM settings WHERE key = ?1;",
+                [VACATION_UNTIL_KEY],
+                |row| row.get(0),
+            )
+            .optional()?)
+    }
+
+    /// The vacation end if it is still ahead of `now_utc`.
+    pub fn active_vacation_until(&self, now_utc: i64) -> Result<Option<i64>> {
+        Ok(self.vacation_until()?.filter(|until| *until > now_utc))
+    }
+
+    pub fn set_vacation_until(&self, now_utc: i64, until: i64) -> Result<()> {
+        self.set(now_utc, VACATION_UNTIL_KEY, &until.to_string())
+    }
+
+    pub fn clear_vacation(&self) -> Result<bool> {
+        self.remove(VACATION_UNTIL_KEY)
+    }
+}
@@ -0,0 +1,64 @@
+use crate::error::Result;
+use knotter_core::domain::ContactId;
+use rusqlite::{params, Connection};
+
+/// One import that changed a contact.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SyncLogEntry {
+    pub source: String,
+    pub fields: Vec<String>,
+    pub synced_at: i64,
+}
+
+pub struct SyncLogRepo<'a> {
+    conn: &'a Connection,
+}
+
+impl<'a> SyncLogRepo<'a> {
+    pub fn new(conn: &'a Connection) -> Self {
+        Self { conn }
+    }
+
+    /// Records that `source` changed `fields` on the contact; a call with no
+    /// fields records nothing.
+    pub fn record(
+        &self,
+        now_utc: i64,
+        contact_id: ContactId,
+        source: &str,
+        fields: &[&str],
+    ) -> Result<()> {
+        if fields.is_empty() {
+            return Ok(());
+        }
+        self.conn.execute(
+            "INSERT INTO contact_sync_log (contact_id, source, fields, synced_at)
+             VALUES (?1, ?2, ?3, ?4);",
+            params![contact_id.to_string(), source, fields.join(","), now_utc],
+        )?;
+        Ok(())
+    }
+
+    /// Entries for the contact, newest first.
+    pub fn list_for_contact(&self, contact_id: ContactId) -> Result<Vec<SyncLogEntry>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT source, fields, synced_at
+             FROM contact_sync_log
+             WHERE contact_id = ?1
+             ORDER BY synced_at DESC, id DESC;",
+        )?;
+        let rows = stmt.query_map([contact_id.to_string()], |row| {
+            let fields: String = row.get(1)?;
+            Ok(SyncLogEntry {
+                source: row.get(0)?,
+                fields: fields.split(',').map(str::to_string).collect(),
+                synced_at: row.get(2)?,
+            })
+        })?;
+        let mut entries = Vec::new();
+        for row in rows {
+            entries.push(row?);
+        }
+        Ok(entries)
+    }
+}
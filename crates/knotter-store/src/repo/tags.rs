@@ -19,23 +19,61 @@ impl<'a> TagsRepo<'a> {
     }
 
     pub fn list_with_counts(&self) -> Result<Vec<(Tag, i64)>> {
+        list_with_counts_inner(self.conn, false)
+    }
+
+    /// Like [`Self::list_with_counts`], but archived contacts are not counted.
+    pub fn list_with_active_counts(&self) -> Result<Vec<(Tag, i64)>> {
+        list_with_counts_inner(self.conn, true)
+    }
+
+    /// Tags no contact carries, not even an archived or trashed one.
+    pub fn list_unused(&self) -> Result<Vec<Tag>> {
         let mut stmt = self.conn.prepare(
-            "SELECT tags.id, tags.name, COUNT(contact_tags.contact_id) AS cnt
+            "SELECT tags.id, tags.name
              FROM tags
-             LEFT JOIN contact_tags ON tags.id = contact_tags.tag_id
-             GROUP BY tags.id, tags.name
+             WHERE NOT EXISTS (SELECT 1 FROM contact_tags WHERE contact_tags.tag_id = tags.id)
              ORDER BY tags.name ASC;",
         )?;
         let mut rows = stmt.query([])?;
         let mut items = Vec::new();
         while let Some(row) = rows.next()? {
-            let tag = tag_from_row(row)?;
-            let count: i64 = row.get(2)?;
-            items.push((tag, count));
+            items.push(tag_from_row(row)?);
         }
         Ok(items)
     }
 
+    /// Deletes every tag [`Self::list_unused`] reports, in one transaction, and
+    /// returns their names.
+    pub fn delete_unused(&self) -> Result<Vec<String>> {
+        let tx = self.conn.unchecked_transaction()?;
+        let names = {
+            let mut stmt = tx.prepare(
+                "DELETE FROM tags
+                 WHERE NOT EXISTS (SELECT 1 FROM contact_tags WHERE contact_tags.tag_id = tags.id)
+                 RETURNING name;",
+            )?;
+            let rows = stmt.query_map([], |row| row.get(0))?;
+            let mut names = rows.collect::<rusqlite::Result<Vec<String>>>()?;
+            names.sort();
+            names
+        };
+        tx.commit()?;
+        Ok(names)
+    }
+
+    /// Tag names starting with `prefix` (case-insensitive), for shell completion.
+    pub fn list_names_with_prefix(&self, prefix: &str, limit: usize) -> Result<Vec<String>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT name FROM tags
+             WHERE instr(lower(name), lower(?1)) = 1
+             ORDER BY name ASC
+             LIMIT ?2;",
+        )?;
+        let rows = stmt.query_map(params![prefix, limit as i64], |row| row.get(0))?;
+        Ok(rows.collect::<rusqlite::Result<Vec<String>>>()?)
+    }
+
     pub fn list_for_contact(&self, contact_id: &str) -> Result<Vec<Tag>> {
         let mut stmt = self.conn.prepare(
             "SELECT tags.id, tags.name
@@ -113,11 +151,85 @@ impl<'a> TagsRepo<'a> {
         Ok(())
     }
 
-    pub fn set_contact_tags(&self, contact_id: &str, tags: Vec<TagName>) -> Result<()> {
+    pub fn get_by_name(&self, name: &TagName) -> Result<Option<Tag>> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT id, name FROM tags WHERE name = ?1;")?;
+        let mut rows = stmt.query([name.as_str()])?;
+        match rows.next()? {
+            Some(row) => Ok(Some(tag_from_row(row)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Counts active (non-deleted) contacts carrying any of `names`.
+    pub fn count_contacts_with_any(&self, names: &[TagName]) -> Result<usize> {
+        count_contacts_with_any_inner(self.conn, names)
+    }
+
+    /// Renames `old` to `new`, merging into `new` when it already exists.
+    /// Returns how many active contacts carried `old`.
+    pub fn rename(&self, old: &TagName, new: TagName) -> Result<usize> {
+        self.merge_into(std::slice::from_ref(old), new)
+    }
+
+    /// Moves every contact tagged with one of `sources` onto `target` and drops
+    /// the source tags, in one transaction. Returns how many active contacts
+    /// carried a source tag.
+    pub fn merge_into(&self, sources: &[TagName], target: TagName) -> Result<usize> {
         let tx = self.conn.unchecked_transaction()?;
-        set_contact_tags_inner(&tx, contact_id, tags)?;
+        let affected = count_contacts_with_any_inner(&tx, sources)?;
+        let target = upsert_inner(&tx, target)?;
+        for source in sources {
+            if source == &target.name {
+                continue;
+            }
+            let source_id: Option<String> = tx
+                .query_row(
+                    "SELECT id FROM tags WHERE name = ?1;",
+                    [source.as_str()],
+                    |row| row.get(0),
+                )
+                .optional()?;
+            let Some(source_id) = source_id else {
+                continue;
+            };
+            tx.execute(
+                "INSERT OR IGNORE INTO contact_tags (contact_id, tag_id)
+                 SELECT contact_id, ?1 FROM contact_tags WHERE tag_id = ?2;",
+                params![target.id.to_string(), source_id],
+            )?;
+            tx.execute("DELETE FROM contact_tags WHERE tag_id = ?1;", [&source_id])?;
+            tx.execute("DELETE FROM tags WHERE id = ?1;", [&source_id])?;
+        }
         tx.commit()?;
-        Ok(())
+        Ok(affected)
+    }
+
+    /// Removes `name` from every contact and deletes the tag, in one transaction.
+    /// Returns how many active contacts carried it.
+    pub fn delete(&self, name: &TagName) -> Result<usize> {
+        let tx = self.conn.unchecked_transaction()?;
+        let affected = count_contacts_with_any_inner(&tx, std::slice::from_ref(name))?;
+        tx.execute(
+            "DELETE FROM contact_tags
+             WHERE tag_id IN (SELECT id FROM tags WHERE name = ?1);",
+            [name.as_str()],
+        )?;
+        tx.execute("DELETE FROM tags WHERE name = ?1;", [name.as_str()])?;
+        tx.commit()?;
+        Ok(affected)
+    }
+
+    pub fn set_contact_tags(&self, contact_id: &str, tags: Vec<TagName>) -> Result<()> {
+        if self.conn.is_autocommit() {
+            let tx = self.conn.unchecked_transaction()?;
+            set_contact_tags_inner(&tx, contact_id, tags)?;
+            tx.commit()?;
+            Ok(())
+        } else {
+            set_contact_tags_inner(self.conn, contact_id, tags)
+        }
     }
 }
 
@@ -142,6 +254,49 @@ pub(crate) fn set_contact_tags_inner(
     Ok(())
 }
 
+/// Archived contacts are left out in the join itself, so a tag only they carry
+/// still shows up with a count of 0.
+fn list_with_counts_inner(conn: &Connection, active_only: bool) -> Result<Vec<(Tag, i64)>> {
+    let mut stmt = conn.prepare(
+        "SELECT tags.id, tags.name, COUNT(contacts.id) AS cnt
+         FROM tags
+         LEFT JOIN contact_tags ON tags.id = contact_tags.tag_id
+         LEFT JOIN contacts ON contacts.id = contact_tags.contact_id
+                           AND contacts.deleted_at IS NULL
+                           AND (?1 = 0 OR contacts.archived_at IS NULL)
+         GROUP BY tags.id, tags.name
+         ORDER BY tags.name ASC;",
+    )?;
+    let mut rows = stmt.query([active_only])?;
+    let mut items = Vec::new();
+    while let Some(row) = rows.next()? {
+        let tag = tag_from_row(row)?;
+        let count: i64 = row.get(2)?;
+        items.push((tag, count));
+    }
+    Ok(items)
+}
+
+fn count_contacts_with_any_inner(conn: &Connection, names: &[TagName]) -> Result<usize> {
+    if names.is_empty() {
+        return Ok(0);
+    }
+    let placeholders = vec!["?"; names.len()].join(", ");
+    let count: i64 = conn.query_row(
+        &format!(
+            "SELECT COUNT(DISTINCT contact_tags.contact_id)
+             FROM contact_tags
+             INNER JOIN tags ON tags.id = contact_tags.tag_id
+             INNER JOIN contacts ON contacts.id = contact_tags.contact_id
+             WHERE contacts.deleted_at IS NULL
+               AND tags.name IN ({placeholders});"
+        ),
+        rusqlite::params_from_iter(names.iter().map(TagName::as_str)),
+        |row| row.get(0),
+    )?;
+    Ok(count as usize)
+}
+
 fn upsert_inner(conn: &Connection, name: TagName) -> Result<Tag> {
     let tag_name = name.as_str().to_string();
     let new_id = TagId::new();
@@ -54,6 +54,36 @@ impl<'a> TelegramSyncRepo<'a> {
         ))
     }
 
+    /// Every stored account/peer position, ordered by account then peer.
+    pub fn list_states(&self) -> Result<Vec<TelegramSyncState>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT account, peer_id, last_message_id, last_seen_at
+             FROM telegram_sync_state
+             ORDER BY account, peer_id;",
+        )?;
+        let rows = stmt.query_map([], |row| {
+            Ok(TelegramSyncState {
+                account: row.get(0)?,
+                peer_id: row.get(1)?,
+                last_message_id: row.get(2)?,
+                last_seen_at: row.get(3)?,
+            })
+        })?;
+        Ok(rows.collect::<rusqlite::Result<_>>()?)
+    }
+
+    /// Zeroes the stored position of one peer, or of every peer of `account`,
+    /// so the next import refetches their history. Returns the rows reset.
+    pub fn reset_state(&self, account: &str, peer_id: Option<i64>) -> Result<usize> {
+        let reset = self.conn.execute(
+            "UPDATE telegram_sync_state
+             SET last_message_id = 0, last_seen_at = NULL
+             WHERE account = ?1 AND (?2 IS NULL OR peer_id = ?2);",
+            params![account, peer_id],
+        )?;
+        Ok(reset)
+    }
+
     pub fn upsert_state(&self, state: &TelegramSyncState) -> Result<()> {
         self.conn.execute(
             "INSERT INTO telegram_sync_state (account, peer_id, last_message_id, last_seen_at)
@@ -0,0 +1,108 @@
+mod common;
+
+use chrono::FixedOffset;
+use common::new_contact;
+use knotter_core::parse_filter;
+use knotter_store::query::ContactQuery;
+use knotter_store::repo::ContactMergeOptions;
+use knotter_store::Store;
+
+#[test]
+fn aliases_add_list_and_remove_ignoring_case() {
+    let store = Store::open_in_memory().expect("open in memory");
+    store.migrate().expect("migrate");
+    let now = 1_700_000_000;
+    let robert = store
+        .contacts()
+        .create(now, new_contact("Robert Smith"))
+        .expect("create");
+
+    let aliases = store.contact_aliases();
+    assert!(aliases.add(now, robert.id, "  Bob ").expect("add"));
+    assert!(!aliases.add(now, robert.id, "BOB").expect("add duplicate"));
+    assert!(aliases.add(now, robert.id, "Rob").expect("add"));
+    assert!(aliases.add(now, robert.id, "   ").is_err());
+    assert_eq!(
+        aliases.list_for_contact(robert.id).expect("list"),
+        vec!["Bob", "Rob"]
+    );
+
+    assert!(aliases.remove(robert.id, "rob").expect("remove"));
+    assert!(!aliases.remove(robert.id, "rob").expect("remove again"));
+    let map = aliases.list_for_contacts(&[robert.id]).expect("map");
+    assert_eq!(map.get(&robert.id), Some(&vec!["Bob".to_string()]));
+}
+
+#[test]
+fn name_lookup_prefers_display_names_over_aliases() {
+    let store = Store::open_in_memory().expect("open in memory");
+    store.migrate().expect("migrate");
+    let now = 1_700_000_000;
+    let robert = store
+        .contacts()
+        .create(now, new_contact("Robert Smith"))
+        .expect("create");
+    store
+        .contact_aliases()
+        .add(now, robert.id, "Bob")
+        .expect("alias");
+
+    let contacts = store.contacts();
+    let matches = contacts.list_by_name_or_alias("bob").expect("lookup");
+    assert_eq!(matches.len(), 1);
+    assert_eq!(matches[0].id, robert.id);
+
+    let bob = contacts.create(now, new_contact("Bob")).expect("create");
+    let matches = contacts.list_by_name_or_alias("Bob").expect("lookup");
+    assert_eq!(matches.len(), 1);
+    assert_eq!(matches[0].id, bob.id);
+    assert_eq!(
+        contacts.list_by_alias("bob").expect("alias")[0].id,
+        robert.id
+    );
+
+    let filter = parse_filter("bob").expect("parse filter");
+    let query = ContactQuery::from_filter(&filter).expect("build query");
+    let offset = FixedOffset::east_opt(0).expect("offset");
+    let listed = contacts
+        .list_contacts(&query, now, 7, offset)
+        .expect("list contacts");
+    assert_eq!(listed.len(), 2);
+}
+
+#[test]
+fn merge_moves_aliases_and_keeps_the_losing_name() {
+    let store = Store::open_in_memory().expect("open in memory");
+    store.migrate().expect("migrate");
+    let now = 1_700_000_000;
+    let primary = store
+        .contacts()
+        .create(now, new_contact("Robert Smith"))
+        .expect("create primary");
+    let secondary = store
+        .contacts()
+        .create(now, new_contact("Bobby"))
+        .expect("create secondary");
+    store
+        .contact_aliases()
+        .add(now, secondary.id, "Bob")
+        .expect("alias");
+
+    store
+        .contacts()
+        .merge_contacts(
+            now + 10,
+            primary.id,
+            secondary.id,
+            ContactMergeOptions::default(),
+        )
+        .expect("merge");
+
+    assert_eq!(
+        store
+            .contact_aliases()
+            .list_for_contact(primary.id)
+            .expect("aliases"),
+        vec!["Bob", "Bobby"]
+    );
+}
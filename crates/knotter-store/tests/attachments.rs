@@ -0,0 +1,180 @@
+mod common;
+
+use common::new_contact;
+use knotter_core::domain::{AttachmentKind, ContactId, Interaction, InteractionKind};
+use knotter_core::CoreError;
+use knotter_store::error::{StoreError, StoreErrorKind};
+use knotter_store::repo::InteractionNew;
+use knotter_store::Store;
+
+const NOW: i64 = 1_700_000_000;
+
+fn store() -> Store {
+    let store = Store::open_in_memory().expect("open in memory");
+    store.migrate().expect("migrate");
+    store
+}
+
+fn contact(store: &Store, name: &str) -> ContactId {
+    store
+        .contacts()
+        .create(NOW, new_contact(name))
+        .expect("create contact")
+        .id
+}
+
+fn interaction(store: &Store, contact_id: ContactId, occurred_at: i64) -> Interaction {
+    store
+        .interactions()
+        .add(InteractionNew {
+            contact_id,
+            occurred_at,
+            created_at: NOW,
+            kind: InteractionKind::Hangout,
+            note: "coffee".to_string(),
+            follow_up_at: None,
+            direction: None,
+        })
+        .expect("add interaction")
+}
+
+#[test]
+fn attachments_are_added_once_and_listed_oldest_first() {
+    let store = store();
+    let ada = contact(&store, "Ada");
+    let meeting = interaction(&store, ada, NOW - 3600);
+    let repo = store.interaction_attachments();
+
+    assert!(repo
+        .add(NOW, meeting.id, AttachmentKind::File, "~/notes/ada.md")
+        .expect("add file"));
+    assert!(repo
+        .add(
+            NOW + 1,
+            meeting.id,
+            AttachmentKind::Url,
+            " https://docs.example/ada "
+        )
+        .expect("add url"));
+    assert!(!repo
+        .add(NOW + 2, meeting.id, AttachmentKind::File, "~/notes/ada.md")
+        .expect("add again"));
+
+    let attachments = repo.list_for_interaction(meeting.id).expect("list");
+    assert_eq!(attachments.len(), 2);
+    assert_eq!(attachments[0].kind, AttachmentKind::File);
+    assert_eq!(attachments[0].value, "~/notes/ada.md");
+    assert_eq!(attachments[0].created_at, NOW);
+    assert_eq!(attachments[1].kind, AttachmentKind::Url);
+    assert_eq!(attachments[1].value, "https://docs.example/ada");
+
+    let by_interaction = repo.list_for_contact(ada).expect("list for contact");
+    assert_eq!(by_interaction.len(), 1);
+    assert_eq!(by_interaction[&meeting.id], attachments);
+}
+
+#[test]
+fn attachments_reject_urls_without_a_scheme_and_unknown_interactions() {
+    let store = store();
+    let ada = contact(&store, "Ada");
+    let meeting = interaction(&store, ada, NOW - 3600);
+    let repo = store.interaction_attachments();
+
+    let err = repo
+        .add(NOW, meeting.id, AttachmentKind::Url, "docs.example/ada")
+        .expect_err("no scheme");
+    assert!(matches!(
+        err,
+        StoreError::Core(CoreError::InvalidAttachmentUrl(_))
+    ));
+    let err = repo
+        .add(NOW, meeting.id, AttachmentKind::File, " ")
+        .expect_err("empty");
+    assert!(matches!(err, StoreError::Core(CoreError::EmptyAttachment)));
+
+    let other = interaction(&store, ada, NOW - 7200);
+    store
+        .interactions()
+        .delete(NOW, other.id, false)
+        .expect("delete");
+    let err = repo
+        .add(NOW, other.id, AttachmentKind::File, "notes.md")
+        .expect_err("missing interaction");
+    assert_eq!(err.kind(), StoreErrorKind::NotFound);
+    assert!(repo
+        .list_for_interaction(meeting.id)
+        .expect("list")
+        .is_empty());
+}
+
+#[test]
+fn deleting_an_interaction_or_contact_removes_its_attachments() {
+    let store = store();
+    let ada = contact(&store, "Ada");
+    let grace = contact(&store, "Grace");
+    let first = interaction(&store, ada, NOW - 3600);
+    let second = interaction(&store, ada, NOW - 86_400);
+    let third = interaction(&store, grace, NOW - 3600);
+    let repo = store.interaction_attachments();
+    for (id, value) in [(first.id, "a.md"), (second.id, "b.md"), (third.id, "c.md")] {
+        repo.add(NOW, id, AttachmentKind::File, value)
+            .expect("add attachment");
+    }
+
+    let all = repo.list_for_contacts(&[ada, grace]).expect("list all");
+    assert_eq!(all.len(), 3);
+
+    store
+        .interactions()
+        .delete(NOW, first.id, false)
+        .expect("delete interaction");
+    assert!(repo
+        .list_for_interaction(first.id)
+        .expect("list")
+        .is_empty());
+    assert_eq!(repo.list_for_interaction(second.id).expect("list").len(), 1);
+
+    store.contacts().delete(NOW, grace).expect("delete contact");
+    let remaining = repo.list_for_contacts(&[ada, grace]).expect("list all");
+    assert_eq!(remaining.len(), 1);
+    assert!(remaining.contains_key(&second.id));
+
+    let rows: i64 = store
+        .connection()
+        .query_row("SELECT COUNT(*) FROM interaction_attachments;", [], |row| {
+            row.get(0)
+        })
+        .expect("count");
+    assert_eq!(rows, 1);
+}
+
+#[test]
+fn dedupe_moves_attachments_to_the_kept_interaction() {
+    let store = store();
+    let ada = contact(&store, "Ada");
+    let kept = interaction(&store, ada, NOW - 3600);
+    let repeat = interaction(&store, ada, NOW - 3600 + 30);
+    let repo = store.interaction_attachments();
+    repo.add(NOW, kept.id, AttachmentKind::File, "shared.md")
+        .expect("add kept");
+    repo.add(NOW, repeat.id, AttachmentKind::File, "shared.md")
+        .expect("add repeat");
+    repo.add(
+        NOW,
+        repeat.id,
+        AttachmentKind::Url,
+        "https://example.com/photo",
+    )
+    .expect("add repeat url");
+
+    let dedupe = store.interactions().delete_duplicates(120).expect("dedupe");
+    assert_eq!(dedupe.removed.len(), 1);
+
+    let values: Vec<String> = repo
+        .list_for_interaction(kept.id)
+        .expect("list")
+        .into_iter()
+        .map(|attachment| attachment.value)
+        .collect();
+    assert_eq!(values, vec!["https://example.com/photo", "shared.md"]);
+}
@@ -27,6 +27,7 @@ fn backup_creates_readable_snapshot() {
                 next_touchpoint_at: None,
                 cadence_days: None,
                 archived_at: None,
+                organization: None,
             },
         )
         .expect("create contact");
@@ -0,0 +1,9 @@
+use knotter_store::repo::ContactNew;
+
+/// A contact with only a display name set.
+pub fn new_contact(name: &str) -> ContactNew {
+    ContactNew {
+        display_name: name.to_string(),
+        ..Default::default()
+    }
+}
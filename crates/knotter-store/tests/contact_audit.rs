@@ -0,0 +1,203 @@
+mod common;
+
+use common::new_contact;
+use knotter_store::repo::{ContactMergeOptions, ContactUpdate};
+use knotter_store::Store;
+use serde_json::json;
+
+#[test]
+fn audit_records_changes_with_origin_and_skips_no_op_updates() {
+    let store = Store::open_in_memory().expect("open in memory");
+    store.migrate().expect("migrate");
+    let now = 1_700_000_000;
+    let ada = store
+        .contacts()
+        .create(now, new_contact("Ada"))
+        .expect("create");
+
+    let rename = ContactUpdate {
+        display_name: Some("Ada Lovelace".to_string()),
+        cadence_days: Some(Some(14)),
+        source: Some("vcf".to_string()),
+        ..Default::default()
+    };
+    store
+        .contacts()
+        .update(now + 10, ada.id, rename.clone())
+        .expect("rename");
+    store
+        .contacts()
+        .update(now + 20, ada.id, rename)
+        .expect("same values again");
+    store
+        .contacts()
+        .with_origin("tui")
+        .archive_with_reason(now + 30, ada.id, Some("moved"))
+        .expect("archive");
+
+    let audit = store.contact_audit();
+    let entries = audit.list_for_contact(ada.id, None).expect("list");
+    assert_eq!(entries.len(), 2);
+    assert_eq!(entries[0].action, "archive");
+    assert_eq!(entries[0].origin, "tui");
+    assert_eq!(entries[0].occurred_at, now + 30);
+    assert_eq!(entries[0].changes[0].field, "archive_reason");
+    assert_eq!(entries[0].changes[0].new, json!("moved"));
+    assert_eq!(entries[0].changes[1].field, "archived_at");
+    assert_eq!(entries[0].changes[1].new, json!(now + 30));
+
+    assert_eq!(entries[1].action, "update");
+    assert_eq!(entries[1].origin, "vcf");
+    let fields: Vec<(&str, &serde_json::Value, &serde_json::Value)> = entries[1]
+        .changes
+        .iter()
+        .map(|change| (change.field.as_str(), &change.old, &change.new))
+        .collect();
+    assert_eq!(
+        fields,
+        vec![
+            ("cadence_days", &json!(null), &json!(14)),
+            ("display_name", &json!("Ada"), &json!("Ada Lovelace")),
+        ]
+    );
+
+    let limited = audit.list_for_contact(ada.id, Some(1)).expect("limit");
+    assert_eq!(limited.len(), 1);
+    assert_eq!(limited[0].action, "archive");
+}
+
+#[test]
+fn audit_follows_merges_and_outlives_deletes() {
+    let store = Store::open_in_memory().expect("open in memory");
+    store.migrate().expect("migrate");
+    let now = 1_700_000_000;
+    let ada = store
+        .contacts()
+        .create(now, new_contact("Ada"))
+        .expect("create");
+    let dup = store
+        .contacts()
+        .create(now, new_contact("Ada L"))
+        .expect("create dup");
+    store
+        .contacts()
+        .update(
+            now + 10,
+            dup.id,
+            ContactUpdate {
+                phone: Some(Some("555".to_string())),
+                ..Default::default()
+            },
+        )
+        .expect("edit dup");
+
+    store
+        .contacts()
+        .merge_contacts(now + 20, ada.id, dup.id, ContactMergeOptions::default())
+        .expect("merge");
+    let entries = store
+        .contact_audit()
+        .list_for_contact(ada.id, None)
+        .expect("list merged");
+    let actions: Vec<&str> = entries.iter().map(|entry| entry.action.as_str()).collect();
+    assert_eq!(actions, vec!["merge", "update"]);
+    assert_eq!(entries[0].origin, "manual");
+    assert_eq!(
+        entries[0].detail.as_deref(),
+        Some(format!("merged Ada L ({})", dup.id).as_str())
+    );
+    assert_eq!(entries[0].changes[0].field, "phone");
+
+    store.contacts().trash(now + 30, ada.id).expect("trash");
+    store.contacts().restore(now + 40, ada.id).expect("restore");
+    store.contacts().delete(now + 50, ada.id).expect("delete");
+    let entries = store
+        .contact_audit()
+        .list_for_contact(ada.id, None)
+        .expect("list deleted");
+    let actions: Vec<&str> = entries.iter().map(|entry| entry.action.as_str()).collect();
+    assert_eq!(
+        actions,
+        vec!["delete", "restore", "trash", "merge", "update"]
+    );
+    assert_eq!(entries[0].detail.as_deref(), Some("Ada"));
+    assert!(entries[0].changes.is_empty());
+}
+
+#[test]
+fn audit_records_mutes_and_archive_reasons() {
+    let store = Store::open_in_memory().expect("open in memory");
+    store.migrate().expect("migrate");
+    let now = 1_700_000_000;
+    let ada = store
+        .contacts()
+        .create(now, new_contact("Ada"))
+        .expect("create");
+
+    let contacts = store.contacts().with_origin("cli");
+    contacts.mute(now + 10, ada.id, now + 1_000).expect("mute");
+    contacts.unmute(now + 20, ada.id).expect("unmute");
+    contacts.unmute(now + 30, ada.id).expect("unmute again");
+    contacts.archive(now + 40, ada.id).expect("archive");
+    contacts
+        .set_archive_reason(now + 50, ada.id, Some("moved"))
+        .expect("set reason");
+    contacts
+        .set_archive_reason(now + 60, ada.id, Some(" moved "))
+        .expect("same reason again");
+
+    let entries = store
+        .contact_audit()
+        .list_for_contact(ada.id, None)
+        .expect("list");
+    let summary: Vec<(&str, &str, &serde_json::Value, &serde_json::Value)> = entries
+        .iter()
+        .flat_map(|entry| {
+            entry.changes.iter().map(move |change| {
+                (
+                    entry.action.as_str(),
+                    change.field.as_str(),
+                    &change.old,
+                    &change.new,
+                )
+            })
+        })
+        .collect();
+    assert_eq!(
+        summary,
+        vec![
+            ("update", "archive_reason", &json!(null), &json!("moved")),
+            ("archive", "archived_at", &json!(null), &json!(now + 40)),
+            ("update", "muted_until", &json!(now + 1_000), &json!(null)),
+            ("update", "muted_until", &json!(null), &json!(now + 1_000)),
+        ]
+    );
+    assert!(entries.iter().all(|entry| entry.origin == "cli"));
+}
+
+#[test]
+fn audit_rolls_back_with_the_change() {
+    let store = Store::open_in_memory().expect("open in memory");
+    store.migrate().expect("migrate");
+    let now = 1_700_000_000;
+    let ada = store
+        .contacts()
+        .create(now, new_contact("Ada"))
+        .expect("create");
+
+    {
+        let tx = store
+            .connection()
+            .unchecked_transaction()
+            .expect("transaction");
+        knotter_store::repo::ContactsRepo::new(&tx)
+            .archive(now + 10, ada.id)
+            .expect("archive");
+    }
+
+    assert!(store
+        .contact_audit()
+        .list_for_contact(ada.id, None)
+        .expect("list")
+        .is_empty());
+}
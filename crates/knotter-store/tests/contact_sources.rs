@@ -22,6 +22,7 @@ fn contact_sources_upsert_and_find() {
                 next_touchpoint_at: None,
                 cadence_days: None,
                 archived_at: None,
+                organization: None,
             },
         )
         .expect("create contact");
@@ -81,6 +82,7 @@ fn contact_sources_rejects_duplicate_external_id() {
                 next_touchpoint_at: None,
                 cadence_days: None,
                 archived_at: None,
+                organization: None,
             },
         )
         .expect("create contact");
@@ -97,6 +99,7 @@ fn contact_sources_rejects_duplicate_external_id() {
                 next_touchpoint_at: None,
                 cadence_days: None,
                 archived_at: None,
+                organization: None,
             },
         )
         .expect("create contact");
@@ -147,6 +150,7 @@ fn contact_sources_rejects_case_insensitive_duplicate_external_id() {
                 next_touchpoint_at: None,
                 cadence_days: None,
                 archived_at: None,
+                organization: None,
             },
         )
         .expect("create contact");
@@ -163,6 +167,7 @@ fn contact_sources_rejects_case_insensitive_duplicate_external_id() {
                 next_touchpoint_at: None,
                 cadence_days: None,
                 archived_at: None,
+                organization: None,
             },
         )
         .expect("create contact");
@@ -213,6 +218,7 @@ fn contact_sources_case_insensitive_matches() {
                 next_touchpoint_at: None,
                 cadence_days: None,
                 archived_at: None,
+                organization: None,
             },
         )
         .expect("create contact");
@@ -258,6 +264,7 @@ fn contact_sources_list_contact_ids_for_source_returns_distinct_sorted_ids() {
                 next_touchpoint_at: None,
                 cadence_days: None,
                 archived_at: None,
+                organization: None,
             },
         )
         .expect("create contact a");
@@ -274,6 +281,7 @@ fn contact_sources_list_contact_ids_for_source_returns_distinct_sorted_ids() {
                 next_touchpoint_at: None,
                 cadence_days: None,
                 archived_at: None,
+                organization: None,
             },
         )
         .expect("create contact b");
@@ -355,6 +363,7 @@ fn contact_sources_case_insensitive_returns_multiple_matches() {
                 next_touchpoint_at: None,
                 cadence_days: None,
                 archived_at: None,
+                organization: None,
             },
         )
         .expect("create contact");
@@ -371,6 +380,7 @@ fn contact_sources_case_insensitive_returns_multiple_matches() {
                 next_touchpoint_at: None,
                 cadence_days: None,
                 archived_at: None,
+                organization: None,
             },
         )
         .expect("create contact");
@@ -413,6 +423,7 @@ fn contact_sources_case_insensitive_matches_trimmed_external_id() {
                 next_touchpoint_at: None,
                 cadence_days: None,
                 archived_at: None,
+                organization: None,
             },
         )
         .expect("create contact");
@@ -1,5 +1,9 @@
-use knotter_core::domain::{ContactId, TagName};
-use knotter_store::repo::{ContactNew, ContactUpdate, ContactsRepo, EmailOps};
+use knotter_core::domain::{ContactDateKind, ContactId, InteractionKind, TagName};
+use knotter_store::error::StoreError;
+use knotter_store::repo::{
+    ContactDateNew, ContactDeleteImpact, ContactField, ContactNew, ContactUpdate, ContactsRepo,
+    EmailOps, InteractionNew, MergeCandidateCreate, MergeCandidateStatus,
+};
 use knotter_store::Store;
 use tempfile::TempDir;
 
@@ -22,6 +26,7 @@ fn contact_crud_roundtrip() {
                 next_touchpoint_at: None,
                 cadence_days: Some(30),
                 archived_at: None,
+                organization: None,
             },
         )
         .expect("create contact");
@@ -61,6 +66,437 @@ fn contact_crud_roundtrip() {
     assert!(missing.is_none());
 }
 
+#[test]
+fn updates_record_field_provenance_for_changed_fields() {
+    let store = Store::open_in_memory().expect("open in memory");
+    store.migrate().expect("migrate");
+
+    let now = 1_700_000_000;
+    let contact = store
+        .contacts()
+        .create(
+            now,
+            ContactNew {
+                display_name: "Ada Lovelace".to_string(),
+                email: None,
+                phone: Some("111".to_string()),
+                handle: None,
+                timezone: None,
+                next_touchpoint_at: None,
+                cadence_days: None,
+                archived_at: None,
+                organization: None,
+            },
+        )
+        .expect("create contact");
+
+    // Unchanged values record nothing.
+    store
+        .contacts()
+        .update(
+            now + 10,
+            contact.id,
+            ContactUpdate {
+                phone: Some(Some("111".to_string())),
+                ..Default::default()
+            },
+        )
+        .expect("no-op update");
+    let provenance = store.field_provenance();
+    assert!(provenance
+        .list_for_contact(contact.id)
+        .expect("list")
+        .is_empty());
+
+    store
+        .contacts()
+        .update(
+            now + 20,
+            contact.id,
+            ContactUpdate {
+                phone: Some(Some("222".to_string())),
+                ..Default::default()
+            },
+        )
+        .expect("manual update");
+    assert!(provenance
+        .list_for_contact(contact.id)
+        .expect("list")
+        .iter()
+        .any(|entry| entry.field == ContactField::Phone.as_str() && entry.is_manual()));
+
+    store
+        .contacts()
+        .update_with_email_ops(
+            now + 30,
+            contact.id,
+            ContactUpdate {
+                display_name: Some("Ada Byron".to_string()),
+                phone: Some(Some("222".to_string())),
+                source: Some("gmail".to_string()),
+                ..Default::default()
+            },
+            EmailOps::Mutate {
+                clear: false,
+                add: vec!["ada@example.com".to_string()],
+                remove: Vec::new(),
+                source: Some("vcf".to_string()),
+            },
+        )
+        .expect("import update");
+
+    let entries = provenance.list_for_contact(contact.id).expect("list");
+    let summary: Vec<(&str, &str, i64)> = entries
+        .iter()
+        .map(|entry| {
+            (
+                entry.field.as_str(),
+                entry.source.as_str(),
+                entry.updated_at,
+            )
+        })
+        .collect();
+    assert_eq!(
+        summary,
+        vec![
+            ("display_name", "gmail", now + 30),
+            ("email", "gmail", now + 30),
+            ("phone", "manual", now + 20),
+        ]
+    );
+
+    store
+        .contacts()
+        .delete(now + 40, contact.id)
+        .expect("delete contact");
+    assert!(provenance
+        .list_for_contact(contact.id)
+        .expect("list")
+        .is_empty());
+}
+
+#[test]
+fn delete_impact_counts_cascade_and_delete_dismisses_merge_candidates() {
+    let store = Store::open_in_memory().expect("open in memory");
+    store.migrate().expect("migrate");
+
+    let now = 1_700_000_000;
+    let new_contact = |name: &str, email: Option<&str>| ContactNew {
+        display_name: name.to_string(),
+        email: email.map(str::to_string),
+        phone: None,
+        handle: None,
+        timezone: None,
+        next_touchpoint_at: None,
+        cadence_days: None,
+        archived_at: None,
+        organization: None,
+    };
+    let doomed = store
+        .contacts()
+        .create(now, new_contact("Ada Lovelace", Some("ada@example.com")))
+        .expect("create doomed");
+    let other = store
+        .contacts()
+        .create(now, new_contact("Ada L.", None))
+        .expect("create other");
+
+    store
+        .interactions()
+        .add(InteractionNew {
+            contact_id: doomed.id,
+            occurred_at: now,
+            created_at: now,
+            kind: InteractionKind::Call,
+            note: "hi".to_string(),
+            follow_up_at: None,
+            direction: None,
+        })
+        .expect("add interaction");
+    store
+        .contact_dates()
+        .upsert(
+            now,
+            ContactDateNew {
+                contact_id: doomed.id,
+                kind: ContactDateKind::Birthday,
+                label: None,
+                month: 12,
+                day: 10,
+                year: None,
+                source: None,
+                remind_days_before: None,
+            },
+        )
+        .expect("add date");
+    store
+        .tags()
+        .add_tag_to_contact(&doomed.id.to_string(), TagName::new("friend").unwrap())
+        .expect("add tag");
+    let candidate = store
+        .merge_candidates()
+        .create(
+            now,
+            doomed.id,
+            other.id,
+            MergeCandidateCreate {
+                reason: "test".to_string(),
+                source: None,
+                preferred_contact_id: None,
+            },
+        )
+        .expect("create candidate")
+        .candidate;
+
+    let impact = store.contacts().delete_impact(doomed.id).expect("impact");
+    assert_eq!(
+        impact,
+        ContactDeleteImpact {
+            interactions: 1,
+            emails: 1,
+            dates: 1,
+            tags: 1,
+            open_merge_candidates: 1,
+        }
+    );
+
+    store
+        .contacts()
+        .delete(now + 10, doomed.id)
+        .expect("delete contact");
+    assert!(store.merge_candidates().list_open().unwrap().is_empty());
+    let dismissed = store
+        .merge_candidates()
+        .get(candidate.id)
+        .unwrap()
+        .expect("candidate kept");
+    assert_eq!(dismissed.status, MergeCandidateStatus::Dismissed);
+}
+
+#[test]
+fn trashed_contact_is_hidden_and_frees_its_email() {
+    let store = Store::open_in_memory().expect("open in memory");
+    store.migrate().expect("migrate");
+
+    let now = 1_700_000_000;
+    let new_contact = |name: &str, email: Option<&str>| ContactNew {
+        display_name: name.to_string(),
+        email: email.map(str::to_string),
+        phone: None,
+        handle: None,
+        timezone: None,
+        next_touchpoint_at: Some(now - 10),
+        cadence_days: None,
+        archived_at: None,
+        organization: None,
+    };
+    let trashed = store
+        .contacts()
+        .create(now, new_contact("Ada Lovelace", Some("ada@example.com")))
+        .expect("create trashed");
+    let other = store
+        .contacts()
+        .create(now, new_contact("Ada L.", None))
+        .expect("create other");
+    store
+        .tags()
+        .add_tag_to_contact(&trashed.id.to_string(), TagName::new("friend").unwrap())
+        .expect("add tag");
+    let candidate = store
+        .merge_candidates()
+        .create(
+            now,
+            trashed.id,
+            other.id,
+            MergeCandidateCreate {
+                reason: "test".to_string(),
+                source: None,
+                preferred_contact_id: None,
+            },
+        )
+        .expect("create candidate")
+        .candidate;
+
+    store
+        .contacts()
+        .trash(now + 10, trashed.id)
+        .expect("trash contact");
+
+    assert!(store.contacts().get(trashed.id).unwrap().is_none());
+    assert!(store
+        .contacts()
+        .list_by_email("ada@example.com")
+        .unwrap()
+        .is_empty());
+    let remaining: Vec<_> = store
+        .contacts()
+        .list_all()
+        .unwrap()
+        .into_iter()
+        .map(|contact| contact.id)
+        .collect();
+    assert_eq!(remaining, vec![other.id]);
+    let tags = store.tags().list_with_counts().unwrap();
+    assert_eq!(tags[0].1, 0);
+    let dismissed = store
+        .merge_candidates()
+        .get(candidate.id)
+        .unwrap()
+        .expect("candidate kept");
+    assert_eq!(dismissed.status, MergeCandidateStatus::Dismissed);
+    assert!(matches!(
+        store.contacts().trash(now + 20, trashed.id),
+        Err(StoreError::NotFound(_))
+    ));
+
+    let listed = store.contacts().list_trashed().expect("list trash");
+    assert_eq!(listed.len(), 1);
+    assert_eq!(listed[0].contact.id, trashed.id);
+    assert_eq!(listed[0].deleted_at, now + 10);
+
+    let reuse = store
+        .contacts()
+        .create(now + 30, new_contact("Ada New", Some("ada@example.com")))
+        .expect("create with reused email");
+    store
+        .emails()
+        .add_email(now + 30, &reuse.id, "ada@example.com", None, true)
+        .expect("reuse trashed email");
+}
+
+#[test]
+fn restore_returns_contact_and_drops_emails_claimed_meanwhile() {
+    let store = Store::open_in_memory().expect("open in memory");
+    store.migrate().expect("migrate");
+
+    let now = 1_700_000_000;
+    let contact = store
+        .contacts()
+        .create_with_emails_and_tags(
+            now,
+            ContactNew {
+                display_name: "Ada Lovelace".to_string(),
+                email: Some("ada@example.com".to_string()),
+                phone: None,
+                handle: None,
+                timezone: None,
+                next_touchpoint_at: None,
+                cadence_days: None,
+                archived_at: None,
+                organization: None,
+            },
+            Vec::new(),
+            vec![
+                "ada@example.com".to_string(),
+                "ada@work.example".to_string(),
+            ],
+            None,
+        )
+        .expect("create contact");
+    store.contacts().trash(now + 10, contact.id).expect("trash");
+
+    let claimer = store
+        .contacts()
+        .create(
+            now + 20,
+            ContactNew {
+                display_name: "Someone Else".to_string(),
+                email: None,
+                phone: None,
+                handle: None,
+                timezone: None,
+                next_touchpoint_at: None,
+                cadence_days: None,
+                archived_at: None,
+                organization: None,
+            },
+        )
+        .expect("create claimer");
+    store
+        .emails()
+        .add_email(now + 20, &claimer.id, "ada@example.com", None, true)
+        .expect("claim email");
+
+    let restored = store
+        .contacts()
+        .restore(now + 30, contact.id)
+        .expect("restore");
+    assert_eq!(restored.dropped_emails, vec!["ada@example.com".to_string()]);
+    assert_eq!(restored.contact.email.as_deref(), Some("ada@work.example"));
+    assert_eq!(
+        store.emails().list_emails_for_contact(&contact.id).unwrap(),
+        vec!["ada@work.example".to_string()]
+    );
+    assert!(store.contacts().list_trashed().unwrap().is_empty());
+    assert!(matches!(
+        store.contacts().restore(now + 40, contact.id),
+        Err(StoreError::NotFound(_))
+    ));
+}
+
+#[test]
+fn empty_trash_purges_only_contacts_trashed_before_cutoff() {
+    let store = Store::open_in_memory().expect("open in memory");
+    store.migrate().expect("migrate");
+
+    let now = 1_700_000_000;
+    let create = |name: &str| {
+        store
+            .contacts()
+            .create(
+                now,
+                ContactNew {
+                    display_name: name.to_string(),
+                    email: None,
+                    phone: None,
+                    handle: None,
+                    timezone: None,
+                    next_touchpoint_at: None,
+                    cadence_days: None,
+                    archived_at: None,
+                    organization: None,
+                },
+            )
+            .expect("create contact")
+    };
+    let old = create("Old");
+    let recent = create("Recent");
+    store
+        .interactions()
+        .add(InteractionNew {
+            contact_id: old.id,
+            occurred_at: now,
+            created_at: now,
+            kind: InteractionKind::Call,
+            note: "hi".to_string(),
+            follow_up_at: None,
+            direction: None,
+        })
+        .expect("add interaction");
+    store.contacts().trash(now + 10, old.id).expect("trash old");
+    store
+        .contacts()
+        .trash(now + 1_000, recent.id)
+        .expect("trash recent");
+
+    let removed = store
+        .contacts()
+        .empty_trash(now + 2_000, Some(now + 500))
+        .expect("empty old trash");
+    assert_eq!(removed, 1);
+    let listed = store.contacts().list_trashed().unwrap();
+    assert_eq!(listed.len(), 1);
+    assert_eq!(listed[0].contact.id, recent.id);
+    assert!(store
+        .interactions()
+        .list_for_contact(old.id, 10, 0)
+        .unwrap()
+        .is_empty());
+
+    assert_eq!(store.contacts().empty_trash(now + 2_000, None).unwrap(), 1);
+    assert!(store.contacts().list_trashed().unwrap().is_empty());
+}
+
 #[test]
 fn list_by_email_is_case_insensitive_and_prefers_active() {
     let store = Store::open_in_memory().expect("open in memory");
@@ -80,6 +516,7 @@ fn list_by_email_is_case_insensitive_and_prefers_active() {
                 next_touchpoint_at: None,
                 cadence_days: None,
                 archived_at: None,
+                organization: None,
             },
         )
         .expect("create contact");
@@ -96,6 +533,7 @@ fn list_by_email_is_case_insensitive_and_prefers_active() {
                 next_touchpoint_at: None,
                 cadence_days: None,
                 archived_at: Some(now + 20),
+                organization: None,
             },
         )
         .expect("create archived contact");
@@ -128,6 +566,7 @@ fn list_by_handle_is_case_insensitive_and_prefers_active() {
                 next_touchpoint_at: None,
                 cadence_days: None,
                 archived_at: None,
+                organization: None,
             },
         )
         .expect("create contact");
@@ -144,6 +583,7 @@ fn list_by_handle_is_case_insensitive_and_prefers_active() {
                 next_touchpoint_at: None,
                 cadence_days: None,
                 archived_at: Some(now + 20),
+                organization: None,
             },
         )
         .expect("create archived contact");
@@ -173,6 +613,7 @@ fn tags_attach_and_list() {
                 next_touchpoint_at: None,
                 cadence_days: None,
                 archived_at: None,
+                organization: None,
             },
         )
         .expect("create contact");
@@ -226,6 +667,7 @@ fn archive_and_unarchive_contact() {
                 next_touchpoint_at: None,
                 cadence_days: None,
                 archived_at: None,
+                organization: None,
             },
         )
         .expect("create contact");
@@ -243,6 +685,64 @@ fn archive_and_unarchive_contact() {
     assert!(unarchived.archived_at.is_none());
 }
 
+#[test]
+fn archive_reason_is_recorded_and_cleared_on_unarchive() {
+    let store = Store::open_in_memory().expect("open in memory");
+    store.migrate().expect("migrate");
+
+    let now = 1_700_000_000;
+    let contact = store
+        .contacts()
+        .create(
+            now,
+            ContactNew {
+                display_name: "Ada Lovelace".to_string(),
+                email: None,
+                phone: None,
+                handle: None,
+                timezone: None,
+                next_touchpoint_at: None,
+                cadence_days: None,
+                archived_at: None,
+                organization: None,
+            },
+        )
+        .expect("create contact");
+
+    // Reasons only stick to archived contacts.
+    store
+        .contacts()
+        .set_archive_reason(now, contact.id, Some("ignored"))
+        .expect("set reason on active contact");
+    assert_eq!(store.contacts().archive_reason(contact.id).unwrap(), None);
+
+    store
+        .contacts()
+        .archive_with_reason(now + 10, contact.id, Some("  moved abroad  "))
+        .expect("archive contact");
+    assert_eq!(
+        store.contacts().archive_reason(contact.id).unwrap(),
+        Some("moved abroad".to_string())
+    );
+    let reasons = store.contacts().list_archive_reasons().expect("reasons");
+    assert_eq!(
+        reasons.get(&contact.id).map(String::as_str),
+        Some("moved abroad")
+    );
+
+    store
+        .contacts()
+        .unarchive(now + 20, contact.id)
+        .expect("unarchive contact");
+    assert_eq!(store.contacts().archive_reason(contact.id).unwrap(), None);
+
+    store
+        .contacts()
+        .archive_with_reason(now + 30, contact.id, None)
+        .expect("archive without reason");
+    assert_eq!(store.contacts().archive_reason(contact.id).unwrap(), None);
+}
+
 #[test]
 fn list_names_for_contacts_handles_large_inputs() {
     let store = Store::open_in_memory().expect("open in memory");
@@ -262,6 +762,7 @@ fn list_names_for_contacts_handles_large_inputs() {
                 next_touchpoint_at: None,
                 cadence_days: None,
                 archived_at: None,
+                organization: None,
             },
         )
         .expect("create contact");
@@ -304,6 +805,7 @@ fn update_with_email_ops_updates_timestamp() {
                 next_touchpoint_at: None,
                 cadence_days: None,
                 archived_at: None,
+                organization: None,
             },
         )
         .expect("create contact");
@@ -347,6 +849,7 @@ fn list_random_active_handles_large_exclude_lists() {
                 next_touchpoint_at: None,
                 cadence_days: None,
                 archived_at: None,
+                organization: None,
             },
         )
         .expect("create contact");
@@ -356,7 +859,7 @@ fn list_random_active_handles_large_exclude_lists() {
 
     let picks = store
         .contacts()
-        .list_random_active(1, &exclude_ids)
+        .list_random_active(now, 1, &exclude_ids)
         .expect("list random active");
     assert!(picks.len() <= 1);
 }
@@ -380,6 +883,7 @@ fn list_names_for_contacts_does_not_touch_main_temp_contact_ids_table() {
                 next_touchpoint_at: None,
                 cadence_days: None,
                 archived_at: None,
+                organization: None,
             },
         )
         .expect("create contact");
@@ -435,6 +939,7 @@ fn create_with_tags_in_tx_commits_with_outer_scope() {
                 next_touchpoint_at: None,
                 cadence_days: None,
                 archived_at: None,
+                organization: None,
             },
             vec![TagName::new("friends").expect("tag")],
         )
@@ -475,6 +980,7 @@ fn create_with_tags_in_tx_rolls_back_with_outer_scope() {
                     next_touchpoint_at: None,
                     cadence_days: None,
                     archived_at: None,
+                    organization: None,
                 },
                 vec![TagName::new("friends").expect("tag")],
             )
@@ -485,3 +991,59 @@ fn create_with_tags_in_tx_rolls_back_with_outer_scope() {
     let missing = store.contacts().get(contact_id).expect("get contact");
     assert!(missing.is_none());
 }
+
+#[test]
+fn display_name_lookup_ignores_case_whitespace_and_diacritics() {
+    let store = Store::open_in_memory().expect("open in memory");
+    store.migrate().expect("migrate");
+    let now = 1_700_000_000;
+    let contacts = store.contacts();
+    let new_contact = |name: &str| ContactNew {
+        display_name: name.to_string(),
+        email: None,
+        phone: None,
+        handle: None,
+        timezone: None,
+        next_touchpoint_at: None,
+        cadence_days: None,
+        archived_at: None,
+        organization: None,
+    };
+    let zoe = contacts
+        .create(now, new_contact("Zoë Müller"))
+        .expect("create");
+    let helene = contacts
+        .create(now, new_contact("Hélène Dupont"))
+        .expect("create");
+
+    let found = contacts
+        .list_by_display_name("zoe  MULLER")
+        .expect("lookup");
+    assert_eq!(found.len(), 1);
+    assert_eq!(found[0].id, zoe.id);
+    assert_eq!(found[0].display_name, "Zoë Müller");
+    assert!(contacts
+        .list_by_display_name("Zoe Mueller")
+        .expect("lookup")
+        .is_empty());
+
+    contacts
+        .update(
+            now + 1,
+            helene.id,
+            ContactUpdate {
+                display_name: Some("Hélène Françoise Dupont".to_string()),
+                ..Default::default()
+            },
+        )
+        .expect("rename");
+    assert!(contacts
+        .list_by_display_name("Helene Dupont")
+        .expect("lookup")
+        .is_empty());
+    let found = contacts
+        .list_by_display_name("helene francoise dupont")
+        .expect("lookup");
+    assert_eq!(found.len(), 1);
+    assert_eq!(found[0].id, helene.id);
+}
@@ -27,6 +27,7 @@ fn contact_dates_upsert_updates_year() {
                 next_touchpoint_at: None,
                 cadence_days: None,
                 archived_at: None,
+                organization: None,
             },
         )
         .expect("create contact");
@@ -43,6 +44,7 @@ fn contact_dates_upsert_updates_year() {
                 day: 3,
                 year: None,
                 source: Some("test".to_string()),
+                remind_days_before: None,
             },
         )
         .expect("upsert date");
@@ -59,6 +61,7 @@ fn contact_dates_upsert_updates_year() {
                 day: 3,
                 year: Some(1990),
                 source: Some("test".to_string()),
+                remind_days_before: None,
             },
         )
         .expect("upsert date");
@@ -94,6 +97,7 @@ fn contact_dates_upsert_clears_year_when_missing() {
                 next_touchpoint_at: None,
                 cadence_days: None,
                 archived_at: None,
+                organization: None,
             },
         )
         .expect("create contact");
@@ -110,6 +114,7 @@ fn contact_dates_upsert_clears_year_when_missing() {
                 day: 3,
                 year: Some(1990),
                 source: Some("test".to_string()),
+                remind_days_before: None,
             },
         )
         .expect("upsert date");
@@ -126,6 +131,7 @@ fn contact_dates_upsert_clears_year_when_missing() {
                 day: 3,
                 year: None,
                 source: Some("test".to_string()),
+                remind_days_before: None,
             },
         )
         .expect("upsert date");
@@ -161,6 +167,7 @@ fn contact_dates_upsert_preserve_year_keeps_existing() {
                 next_touchpoint_at: None,
                 cadence_days: None,
                 archived_at: None,
+                organization: None,
             },
         )
         .expect("create contact");
@@ -177,6 +184,7 @@ fn contact_dates_upsert_preserve_year_keeps_existing() {
                 day: 3,
                 year: Some(1990),
                 source: Some("test".to_string()),
+                remind_days_before: None,
             },
         )
         .expect("upsert date");
@@ -193,6 +201,7 @@ fn contact_dates_upsert_preserve_year_keeps_existing() {
                 day: 3,
                 year: None,
                 source: Some("test".to_string()),
+                remind_days_before: None,
             },
         )
         .expect("upsert date");
@@ -229,6 +238,7 @@ fn list_today_includes_leap_day_on_feb_28_non_leap_year() {
                 next_touchpoint_at: None,
                 cadence_days: None,
                 archived_at: None,
+                organization: None,
             },
         )
         .expect("create contact");
@@ -245,6 +255,7 @@ fn list_today_includes_leap_day_on_feb_28_non_leap_year() {
                 day: 29,
                 year: None,
                 source: None,
+                remind_days_before: None,
             },
         )
         .expect("upsert date");
@@ -280,6 +291,7 @@ fn contact_dates_custom_label_trigger_rejects_empty_label() {
                 next_touchpoint_at: None,
                 cadence_days: None,
                 archived_at: None,
+                organization: None,
             },
         )
         .expect("create contact");
@@ -308,6 +320,7 @@ fn contact_dates_custom_label_trigger_rejects_empty_label() {
                 day: 1,
                 year: None,
                 source: None,
+                remind_days_before: None,
             },
         )
         .expect("create date");
@@ -321,3 +334,68 @@ fn contact_dates_custom_label_trigger_rejects_empty_label() {
         .expect_err("update should fail");
     assert!(err.to_string().contains("custom date label required"));
 }
+
+#[test]
+fn list_upcoming_respects_lead_time_across_year_end() {
+    let store = Store::open_in_memory().expect("open");
+    store.migrate().expect("migrate");
+
+    let now = Utc
+        .with_ymd_and_hms(2024, 12, 25, 12, 0, 0)
+        .unwrap()
+        .timestamp();
+    let offset = FixedOffset::east_opt(0).expect("offset");
+
+    let mut ids = Vec::new();
+    for name in ["Ada", "Grace", "Linus"] {
+        let contact = store
+            .contacts()
+            .create(
+                now,
+                ContactNew {
+                    display_name: name.to_string(),
+                    email: None,
+                    phone: None,
+                    handle: None,
+                    timezone: None,
+                    next_touchpoint_at: None,
+                    cadence_days: None,
+                    archived_at: None,
+                    organization: None,
+                },
+            )
+            .expect("create contact");
+        ids.push(contact.id);
+    }
+    let birthday = |contact_id, month, day, remind_days_before| ContactDateNew {
+        contact_id,
+        kind: ContactDateKind::Birthday,
+        label: None,
+        month,
+        day,
+        year: None,
+        source: None,
+        remind_days_before,
+    };
+    let dates = store.contact_dates();
+    dates
+        .upsert(now, birthday(ids[0], 1, 3, Some(14)))
+        .expect("ada");
+    dates
+        .upsert(now, birthday(ids[1], 1, 20, Some(14)))
+        .expect("grace");
+    dates
+        .upsert(now, birthday(ids[2], 1, 1, None))
+        .expect("linus");
+
+    let upcoming = dates.list_upcoming(now, offset).expect("upcoming");
+    assert_eq!(upcoming.len(), 1);
+    assert_eq!(upcoming[0].occurrence.display_name, "Ada");
+    assert_eq!(upcoming[0].days_until, 9);
+
+    // Re-importing without a lead time keeps the one set by hand.
+    let kept = dates
+        .upsert_preserve_year(now, birthday(ids[0], 1, 3, None))
+        .expect("reimport");
+    assert_eq!(kept.remind_days_before, Some(14));
+}
@@ -0,0 +1,184 @@
+mod common;
+
+use common::new_contact;
+use knotter_core::domain::{ContactId, InteractionKind};
+use knotter_core::rules::MIN_INTERACTION_TIMESTAMP;
+use knotter_store::doctor::{self, OrphanRows};
+use knotter_store::repo::{ContactNew, InteractionNew, MergeCandidateCreate, MergeCandidateStatus};
+use knotter_store::Store;
+
+fn contact(name: &str, email: Option<&str>) -> ContactNew {
+    ContactNew {
+        email: email.map(|value| value.to_string()),
+        ..new_contact(name)
+    }
+}
+
+#[test]
+fn doctor_finds_and_repairs_orphans_and_dangling_candidates() {
+    let store = Store::open_in_memory().expect("open in memory");
+    store.migrate().expect("migrate");
+    let conn = store.connection();
+    let now = 1_700_000_000;
+
+    let ada = store
+        .contacts()
+        .create(now, contact("Ada", Some("ada@example.com")))
+        .expect("create ada");
+    let grace = store
+        .contacts()
+        .create(now, contact("Grace", None))
+        .expect("create grace");
+    store
+        .merge_candidates()
+        .create(
+            now,
+            ada.id,
+            grace.id,
+            MergeCandidateCreate {
+                reason: "manual".to_string(),
+                source: None,
+                preferred_contact_id: None,
+            },
+        )
+        .expect("create candidate");
+    assert!(doctor::integrity_check(conn).expect("integrity").is_empty());
+    assert!(doctor::orphan_rows(conn).expect("orphans").is_empty());
+    assert_eq!(
+        doctor::dangling_merge_candidates(conn).expect("dangling"),
+        0
+    );
+
+    // Simulate a crash that removed a contact without its cascade.
+    conn.pragma_update(None, "foreign_keys", "OFF")
+        .expect("disable fks");
+    conn.execute("DELETE FROM contacts WHERE id = ?1;", [ada.id.to_string()])
+        .expect("delete contact");
+    conn.pragma_update(None, "foreign_keys", "ON")
+        .expect("enable fks");
+
+    assert_eq!(
+        doctor::orphan_rows(conn).expect("orphans"),
+        vec![OrphanRows {
+            table: "contact_emails",
+            rows: 1,
+        }]
+    );
+    assert_eq!(
+        doctor::dangling_merge_candidates(conn).expect("dangling"),
+        1
+    );
+
+    assert_eq!(
+        doctor::delete_orphan_rows(conn).expect("delete orphans"),
+        vec![OrphanRows {
+            table: "contact_emails",
+            rows: 1,
+        }]
+    );
+    assert_eq!(
+        doctor::dismiss_dangling_merge_candidates(conn, now + 10).expect("dismiss"),
+        1
+    );
+    assert!(doctor::orphan_rows(conn).expect("orphans").is_empty());
+    let candidates = store
+        .merge_candidates()
+        .list(&knotter_store::repo::MergeCandidateQuery::default())
+        .expect("list candidates");
+    assert_eq!(candidates[0].status, MergeCandidateStatus::Dismissed);
+    assert_eq!(candidates[0].resolved_at, Some(now + 10));
+}
+
+#[test]
+fn doctor_reports_duplicate_emails_future_archives_and_overdue() {
+    let store = Store::open_in_memory().expect("open in memory");
+    store.migrate().expect("migrate");
+    let conn = store.connection();
+    let now = 1_700_000_000;
+
+    let ada = store
+        .contacts()
+        .create(now, contact("Ada", Some("ada@example.com")))
+        .expect("create ada");
+    let mut other = contact("Ada L", None);
+    other.archived_at = Some(now + 86_400);
+    other.next_touchpoint_at = Some(now - 10);
+    let other = store.contacts().create(now, other).expect("create other");
+    let mut overdue = contact("Grace", None);
+    overdue.next_touchpoint_at = Some(now - 10);
+    store.contacts().create(now, overdue).expect("create grace");
+
+    // A legacy primary column that differs only by case from another contact's email.
+    conn.execute(
+        "UPDATE contacts SET email = 'ADA@example.com ' WHERE id = ?1;",
+        [other.id.to_string()],
+    )
+    .expect("set legacy email");
+
+    let duplicates = doctor::duplicate_emails_without_candidate(conn).expect("duplicates");
+    assert_eq!(duplicates.len(), 1);
+    assert_eq!(duplicates[0].email, "ada@example.com");
+    let mut expected: Vec<ContactId> = vec![ada.id, other.id];
+    expected.sort_by_key(|id| id.to_string());
+    assert_eq!(duplicates[0].contact_ids, expected);
+
+    store
+        .merge_candidates()
+        .create(
+            now,
+            other.id,
+            ada.id,
+            MergeCandidateCreate {
+                reason: "manual".to_string(),
+                source: None,
+                preferred_contact_id: None,
+            },
+        )
+        .expect("create candidate");
+    assert!(doctor::duplicate_emails_without_candidate(conn)
+        .expect("duplicates")
+        .is_empty());
+
+    assert_eq!(
+        doctor::archived_in_future(conn, now).expect("future"),
+        vec![other.id]
+    );
+    assert_eq!(
+        doctor::overdue_active_contacts(conn, now).expect("overdue"),
+        1
+    );
+}
+
+#[test]
+fn doctor_finds_interactions_outside_the_sane_window() {
+    let store = Store::open_in_memory().expect("open in memory");
+    store.migrate().expect("migrate");
+    let conn = store.connection();
+    let now = 1_700_000_000;
+    let ada = store
+        .contacts()
+        .create(now, contact("Ada", None))
+        .expect("create ada");
+
+    let mut ids = Vec::new();
+    for occurred_at in [0, now - 60, now + 400 * 86_400] {
+        let interaction = store
+            .interactions()
+            .add(InteractionNew {
+                contact_id: ada.id,
+                occurred_at,
+                created_at: now,
+                kind: InteractionKind::Email,
+                note: String::new(),
+                follow_up_at: None,
+                direction: None,
+            })
+            .expect("add interaction");
+        ids.push(interaction.id);
+    }
+
+    let found =
+        doctor::interactions_out_of_range(conn, MIN_INTERACTION_TIMESTAMP, now + 2 * 86_400)
+            .expect("out of range");
+    assert_eq!(found, vec![ids[0], ids[2]]);
+}
@@ -1,4 +1,4 @@
-use knotter_store::repo::{EmailMessageRecord, EmailSyncRepo};
+use knotter_store::repo::{EmailMessageRecord, EmailSyncRepo, EmailSyncState};
 use knotter_store::Store;
 
 #[test]
@@ -19,6 +19,7 @@ fn email_sync_dedupes_null_message_id_by_account_mailbox_uid() {
                 next_touchpoint_at: None,
                 cadence_days: None,
                 archived_at: None,
+                organization: None,
             },
         )
         .expect("create contact");
@@ -65,6 +66,7 @@ fn email_sync_dedupes_message_id_per_account() {
                 next_touchpoint_at: None,
                 cadence_days: None,
                 archived_at: None,
+                organization: None,
             },
         )
         .expect("create contact");
@@ -95,3 +97,49 @@ fn email_sync_dedupes_message_id_per_account() {
     third.uid = 99;
     assert!(repo.record_message(&third).expect("different account"));
 }
+
+#[test]
+fn email_sync_state_lists_and_resets_per_account_or_mailbox() {
+    let store = Store::open_in_memory().expect("open in memory");
+    store.migrate().expect("migrate");
+    let repo = EmailSyncRepo::new(store.connection());
+    for (account, mailbox) in [("work", "Sent"), ("work", "INBOX"), ("home", "INBOX")] {
+        repo.upsert_state(&EmailSyncState {
+            account: account.to_string(),
+            mailbox: mailbox.to_string(),
+            uidvalidity: Some(9),
+            last_uid: 120,
+            last_seen_at: Some(1_700_000_000),
+        })
+        .expect("upsert state");
+    }
+
+    let states = repo.list_states().expect("list states");
+    let keys: Vec<_> = states
+        .iter()
+        .map(|state| format!("{}/{}", state.account, state.mailbox))
+        .collect();
+    assert_eq!(keys, vec!["home/INBOX", "work/INBOX", "work/Sent"]);
+
+    assert_eq!(repo.reset_state("work", Some("Sent")).expect("reset"), 1);
+    let sent = repo
+        .load_state("work", "Sent")
+        .expect("load")
+        .expect("state");
+    assert_eq!(sent.last_uid, 0);
+    assert_eq!(sent.uidvalidity, None);
+    assert_eq!(sent.last_seen_at, None);
+    let inbox = repo
+        .load_state("work", "INBOX")
+        .expect("load")
+        .expect("state");
+    assert_eq!(inbox.last_uid, 120);
+
+    assert_eq!(repo.reset_state("work", None).expect("reset"), 2);
+    let home = repo
+        .load_state("home", "INBOX")
+        .expect("load")
+        .expect("state");
+    assert_eq!(home.last_uid, 120);
+    assert_eq!(repo.reset_state("missing", None).expect("reset"), 0);
+}
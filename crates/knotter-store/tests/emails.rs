@@ -21,6 +21,7 @@ fn contact_emails_track_primary_and_secondary() {
                 next_touchpoint_at: None,
                 cadence_days: None,
                 archived_at: None,
+                organization: None,
             },
         )
         .expect("create contact");
@@ -64,6 +65,7 @@ fn contact_emails_enforces_global_uniqueness() {
                 next_touchpoint_at: None,
                 cadence_days: None,
                 archived_at: None,
+                organization: None,
             },
         )
         .expect("create contact a");
@@ -81,6 +83,7 @@ fn contact_emails_enforces_global_uniqueness() {
                 next_touchpoint_at: None,
                 cadence_days: None,
                 archived_at: None,
+                organization: None,
             },
         )
         .expect("create contact b");
@@ -117,6 +120,7 @@ fn replace_emails_rejects_duplicates_without_partial_update() {
                 next_touchpoint_at: None,
                 cadence_days: None,
                 archived_at: None,
+                organization: None,
             },
         )
         .expect("create contact a");
@@ -134,6 +138,7 @@ fn replace_emails_rejects_duplicates_without_partial_update() {
                 next_touchpoint_at: None,
                 cadence_days: None,
                 archived_at: None,
+                organization: None,
             },
         )
         .expect("create contact b");
@@ -187,6 +192,7 @@ fn add_email_sets_primary_when_missing() {
                 next_touchpoint_at: None,
                 cadence_days: None,
                 archived_at: None,
+                organization: None,
             },
         )
         .expect("create contact");
@@ -227,6 +233,7 @@ fn replace_emails_includes_primary_when_missing() {
                 next_touchpoint_at: None,
                 cadence_days: None,
                 archived_at: None,
+                organization: None,
             },
         )
         .expect("create contact");
@@ -276,6 +283,7 @@ fn replace_emails_preserves_metadata_for_existing_entries() {
                 next_touchpoint_at: None,
                 cadence_days: None,
                 archived_at: None,
+                organization: None,
             },
             Vec::new(),
             vec![
@@ -22,6 +22,7 @@ fn list_interactions_for_contacts_groups_and_orders() {
                 next_touchpoint_at: None,
                 cadence_days: None,
                 archived_at: None,
+                organization: None,
             },
         )
         .expect("create contact one");
@@ -39,6 +40,7 @@ fn list_interactions_for_contacts_groups_and_orders() {
                 next_touchpoint_at: None,
                 cadence_days: None,
                 archived_at: None,
+                organization: None,
             },
         )
         .expect("create contact two");
@@ -52,6 +54,7 @@ fn list_interactions_for_contacts_groups_and_orders() {
             kind: InteractionKind::Call,
             note: "First".to_string(),
             follow_up_at: None,
+            direction: None,
         })
         .expect("add interaction 1");
 
@@ -64,6 +67,7 @@ fn list_interactions_for_contacts_groups_and_orders() {
             kind: InteractionKind::Email,
             note: "Second".to_string(),
             follow_up_at: None,
+            direction: None,
         })
         .expect("add interaction 2");
 
@@ -76,6 +80,7 @@ fn list_interactions_for_contacts_groups_and_orders() {
             kind: InteractionKind::Text,
             note: "Third".to_string(),
             follow_up_at: None,
+            direction: None,
         })
         .expect("add interaction 3");
 
@@ -1,6 +1,9 @@
+mod common;
+
 use chrono::{FixedOffset, TimeZone, Utc};
 use knotter_core::parse_filter;
-use knotter_store::query::ContactQuery;
+use knotter_core::rules::DueState;
+use knotter_store::query::{ContactQuery, ListPage};
 use knotter_store::repo::ContactNew;
 use knotter_store::Store;
 
@@ -28,6 +31,7 @@ fn filter_tags_and_due() {
                 next_touchpoint_at: Some(now - 3600),
                 cadence_days: None,
                 archived_at: None,
+                organization: None,
             },
         )
         .expect("create contact");
@@ -45,6 +49,7 @@ fn filter_tags_and_due() {
                 next_touchpoint_at: Some(now + 3600),
                 cadence_days: None,
                 archived_at: None,
+                organization: None,
             },
         )
         .expect("create contact");
@@ -62,6 +67,7 @@ fn filter_tags_and_due() {
                 next_touchpoint_at: Some(now + 2 * 86_400),
                 cadence_days: None,
                 archived_at: None,
+                organization: None,
             },
         )
         .expect("create contact");
@@ -79,6 +85,7 @@ fn filter_tags_and_due() {
                 next_touchpoint_at: None,
                 cadence_days: None,
                 archived_at: None,
+                organization: None,
             },
         )
         .expect("create contact");
@@ -96,6 +103,7 @@ fn filter_tags_and_due() {
                 next_touchpoint_at: None,
                 cadence_days: None,
                 archived_at: Some(now - 60),
+                organization: None,
             },
         )
         .expect("create archived contact");
@@ -192,3 +200,391 @@ fn filter_tags_and_due() {
     assert_eq!(results.len(), 1);
     assert_eq!(results[0].display_name, "Archived");
 }
+
+#[test]
+fn filter_reply_awaiting_uses_latest_directed_interaction() {
+    use knotter_core::domain::{InteractionDirection, InteractionKind};
+    use knotter_store::repo::InteractionNew;
+
+    let store = Store::open_in_memory().expect("open");
+    store.migrate().expect("migrate");
+
+    let now = Utc
+        .with_ymd_and_hms(2024, 1, 10, 12, 0, 0)
+        .unwrap()
+        .timestamp();
+    let offset = FixedOffset::east_opt(0).unwrap();
+
+    let mut contact_ids = Vec::new();
+    for name in ["Waiting", "Replied", "Recent", "Manual"] {
+        let contact = store
+            .contacts()
+            .create(
+                now,
+                ContactNew {
+                    display_name: name.to_string(),
+                    email: None,
+                    phone: None,
+                    handle: None,
+                    timezone: None,
+                    next_touchpoint_at: None,
+                    cadence_days: None,
+                    archived_at: None,
+                    organization: None,
+                },
+            )
+            .expect("create contact");
+        contact_ids.push(contact.id);
+    }
+
+    let add = |contact_index: usize, occurred_at: i64, direction: Option<InteractionDirection>| {
+        store
+            .interactions()
+            .add(InteractionNew {
+                contact_id: contact_ids[contact_index],
+                occurred_at,
+                created_at: now,
+                kind: InteractionKind::Email,
+                note: String::new(),
+                follow_up_at: None,
+                direction,
+            })
+            .expect("add interaction");
+    };
+    add(0, now - 10 * 86_400, Some(InteractionDirection::Outbound));
+    add(0, now - 5 * 86_400, Some(InteractionDirection::Inbound));
+    add(0, now - 86_400, None);
+    add(1, now - 5 * 86_400, Some(InteractionDirection::Inbound));
+    add(1, now - 4 * 86_400, Some(InteractionDirection::Outbound));
+    add(2, now - 86_400, Some(InteractionDirection::Inbound));
+    add(3, now - 5 * 86_400, None);
+
+    let filter = parse_filter("reply:awaiting").expect("parse filter");
+    let query = ContactQuery::from_filter(&filter).expect("query");
+    let results = store
+        .contacts()
+        .list_contacts(&query, now, 7, offset)
+        .expect("list");
+    let names: Vec<_> = results.iter().map(|c| c.display_name.as_str()).collect();
+    assert_eq!(names, vec!["Waiting"]);
+
+    let mut query = ContactQuery::from_filter(&filter).expect("query");
+    query.awaiting_reply_days = Some(0);
+    let results = store
+        .contacts()
+        .list_contacts(&query, now, 7, offset)
+        .expect("list");
+    let names: Vec<_> = results.iter().map(|c| c.display_name.as_str()).collect();
+    assert_eq!(names, vec!["Recent", "Waiting"]);
+}
+
+#[test]
+fn filter_followup_pending_matches_contacts_with_open_follow_ups() {
+    use knotter_core::domain::InteractionKind;
+    use knotter_store::repo::InteractionNew;
+
+    let store = Store::open_in_memory().expect("open");
+    store.migrate().expect("migrate");
+    let now = 1_700_000_000;
+    let offset = FixedOffset::east_opt(0).unwrap();
+
+    let pending = store
+        .contacts()
+        .create(now, new_contact("Pending", None))
+        .expect("create pending");
+    let done = store
+        .contacts()
+        .create(now, new_contact("Done", None))
+        .expect("create done");
+    for (contact_id, follow_up_at) in [(pending.id, Some(now + 86_400)), (done.id, None)] {
+        store
+            .interactions()
+            .add(InteractionNew {
+                contact_id,
+                occurred_at: now,
+                created_at: now,
+                kind: InteractionKind::Call,
+                note: String::new(),
+                follow_up_at,
+                direction: None,
+            })
+            .expect("add interaction");
+    }
+
+    let filter = parse_filter("followup:pending").expect("parse filter");
+    let query = ContactQuery::from_filter(&filter).expect("query");
+    let results = store
+        .contacts()
+        .list_contacts(&query, now, 7, offset)
+        .expect("list");
+    let names: Vec<_> = results.iter().map(|c| c.display_name.as_str()).collect();
+    assert_eq!(names, vec!["Pending"]);
+}
+
+#[test]
+fn filter_handle_matches_exactly_with_or_without_stored_at() {
+    let store = Store::open_in_memory().expect("open");
+    store.migrate().expect("migrate");
+    let now = 1_700_000_000;
+    let offset = FixedOffset::east_opt(0).unwrap();
+
+    for (name, handle) in [
+        ("Bare", Some("Alice")),
+        ("Prefixed", Some("@alice")),
+        ("Longer", Some("alice2")),
+        ("Email", None),
+    ] {
+        let mut contact = new_contact(name, None);
+        contact.handle = handle.map(str::to_string);
+        if handle.is_none() {
+            contact.email = Some("alice@example.com".to_string());
+        }
+        store.contacts().create(now, contact).expect("create");
+    }
+
+    let names = |raw: &str| {
+        let filter = parse_filter(raw).expect("parse filter");
+        let query = ContactQuery::from_filter(&filter).expect("query");
+        let mut names: Vec<_> = store
+            .contacts()
+            .list_contacts(&query, now, 7, offset)
+            .expect("list")
+            .into_iter()
+            .map(|contact| contact.display_name)
+            .collect();
+        names.sort();
+        names
+    };
+    assert_eq!(names("@alice"), vec!["Bare", "Prefixed"]);
+    assert_eq!(names("@ALICE2"), vec!["Longer"]);
+    // Free text still matches handles and emails by substring.
+    assert_eq!(names("alice"), vec!["Bare", "Email", "Longer", "Prefixed"]);
+}
+
+#[test]
+fn filter_org_matches_substring_case_insensitively() {
+    let store = Store::open_in_memory().expect("open");
+    store.migrate().expect("migrate");
+    let now = 1_700_000_000;
+    let offset = FixedOffset::east_opt(0).unwrap();
+
+    for (name, organization) in [
+        ("Ada", Some("Acme Corp")),
+        ("Grace", Some("ACME Labs")),
+        ("Linus", Some("Initech")),
+        ("Solo", None),
+    ] {
+        let mut contact = new_contact(name, None);
+        contact.organization = organization.map(str::to_string);
+        store.contacts().create(now, contact).expect("create");
+    }
+
+    let names = |raw: &str| {
+        let filter = parse_filter(raw).expect("parse filter");
+        let query = ContactQuery::from_filter(&filter).expect("query");
+        let mut names: Vec<_> = store
+            .contacts()
+            .list_contacts(&query, now, 7, offset)
+            .expect("list")
+            .into_iter()
+            .map(|contact| contact.display_name)
+            .collect();
+        names.sort();
+        names
+    };
+    assert_eq!(names("org:acme"), vec!["Ada", "Grace"]);
+    assert_eq!(names("org:tech"), vec!["Linus"]);
+    assert!(names("org:globex").is_empty());
+}
+
+fn new_contact(name: &str, next_touchpoint_at: Option<i64>) -> ContactNew {
+    ContactNew {
+        next_touchpoint_at,
+        ..common::new_contact(name)
+    }
+}
+
+#[test]
+fn list_rows_resolves_due_state_tags_and_pages() {
+    let store = Store::open_in_memory().expect("open");
+    store.migrate().expect("migrate");
+
+    let now = Utc
+        .with_ymd_and_hms(2024, 1, 10, 12, 0, 0)
+        .unwrap()
+        .timestamp();
+    let offset = FixedOffset::east_opt(0).unwrap();
+
+    let ada = store
+        .contacts()
+        .create(now, new_contact("Ada", Some(now - 3600)))
+        .expect("create");
+    store
+        .contacts()
+        .create(now, new_contact("Grace", Some(now + 3600)))
+        .expect("create");
+    store
+        .contacts()
+        .create(now, new_contact("Tim", Some(now + 2 * 86_400)))
+        .expect("create");
+    store
+        .contacts()
+        .create(now, new_contact("Linus", Some(now + 30 * 86_400)))
+        .expect("create");
+    store
+        .contacts()
+        .create(now, new_contact("Ken", None))
+        .expect("create");
+    for tag in ["work", "friends"] {
+        store
+            .tags()
+            .add_tag_to_contact(
+                &ada.id.to_string(),
+                knotter_core::TagName::new(tag).unwrap(),
+            )
+            .expect("tag");
+    }
+
+    let rows = store
+        .contacts()
+        .list_rows(&ContactQuery::default(), now, 7, offset, None)
+        .expect("list rows");
+    let summary: Vec<_> = rows
+        .iter()
+        .map(|row| (row.contact.display_name.as_str(), row.due_state))
+        .collect();
+    assert_eq!(
+        summary,
+        vec![
+            ("Ada", DueState::Overdue),
+            ("Grace", DueState::Today),
+            ("Tim", DueState::Soon),
+            ("Linus", DueState::Scheduled),
+            ("Ken", DueState::Unscheduled),
+        ]
+    );
+    assert_eq!(rows[0].tags, vec!["friends", "work"]);
+    assert!(rows[1].tags.is_empty());
+
+    let page = store
+        .contacts()
+        .list_rows(
+            &ContactQuery::default(),
+            now,
+            7,
+            offset,
+            Some(ListPage {
+                limit: 2,
+                offset: 2,
+            }),
+        )
+        .expect("list page");
+    let names: Vec<_> = page
+        .iter()
+        .map(|row| row.contact.display_name.as_str())
+        .collect();
+    assert_eq!(names, vec!["Tim", "Linus"]);
+
+    let filter = parse_filter("#work due:overdue").expect("parse filter");
+    let query = ContactQuery::from_filter(&filter).expect("build query");
+    let rows = store
+        .contacts()
+        .list_rows(&query, now, 7, offset, None)
+        .expect("list rows");
+    assert_eq!(rows.len(), 1);
+    assert_eq!(rows[0].contact.id, ada.id);
+
+    let filter = parse_filter("due:scheduled").expect("parse filter");
+    let query = ContactQuery::from_filter(&filter).expect("build query");
+    let rows = store
+        .contacts()
+        .list_rows(&query, now, 7, offset, None)
+        .expect("list rows");
+    let names: Vec<_> = rows
+        .iter()
+        .map(|row| row.contact.display_name.as_str())
+        .collect();
+    assert_eq!(names, vec!["Linus"]);
+
+    let counts = store
+        .contacts()
+        .count_by_due_state(&ContactQuery::default(), now, 7, offset)
+        .expect("count by due state");
+    assert_eq!(
+        counts,
+        vec![
+            (DueState::Overdue, 1),
+            (DueState::Today, 1),
+            (DueState::Soon, 1),
+            (DueState::Scheduled, 1),
+            (DueState::Unscheduled, 1),
+        ]
+    );
+    let filter = parse_filter("#work").expect("parse filter");
+    let query = ContactQuery::from_filter(&filter).expect("build query");
+    let counts = store
+        .contacts()
+        .count_by_due_state(&query, now, 7, offset)
+        .expect("count tagged");
+    assert_eq!(counts[0], (DueState::Overdue, 1));
+    assert_eq!(counts.iter().map(|(_, count)| count).sum::<usize>(), 1);
+}
+
+thread_local! {
+    static STATEMENTS: std::cell::Cell<usize> = const { std::cell::Cell::new(0) };
+}
+
+fn count_statement(event: rusqlite::trace::TraceEvent<'_>) {
+    if let rusqlite::trace::TraceEvent::Stmt(..) = event {
+        STATEMENTS.with(|count| count.set(count.get() + 1));
+    }
+}
+
+#[test]
+fn list_rows_uses_constant_statement_count() {
+    use rusqlite::trace::TraceEventCodes;
+
+    let store = Store::open_in_memory().expect("open");
+    store.migrate().expect("migrate");
+    let now = Utc
+        .with_ymd_and_hms(2024, 1, 10, 12, 0, 0)
+        .unwrap()
+        .timestamp();
+    let offset = FixedOffset::east_opt(0).unwrap();
+
+    let statements_for = |count: usize| {
+        let existing = store.contacts().list_all().expect("list").len();
+        for index in existing..count {
+            let contact = store
+                .contacts()
+                .create(now, new_contact(&format!("Contact {index}"), Some(now)))
+                .expect("create");
+            store
+                .tags()
+                .add_tag_to_contact(
+                    &contact.id.to_string(),
+                    knotter_core::TagName::new("bulk").unwrap(),
+                )
+                .expect("tag");
+        }
+
+        let filter = parse_filter("#bulk archived:active").expect("parse filter");
+        let query = ContactQuery::from_filter(&filter).expect("build query");
+        STATEMENTS.with(|count| count.set(0));
+        store
+            .connection()
+            .trace_v2(TraceEventCodes::SQLITE_TRACE_STMT, Some(count_statement));
+        let rows = store
+            .contacts()
+            .list_rows(&query, now, 7, offset, None)
+            .expect("list rows");
+        store.connection().trace_v2(TraceEventCodes::empty(), None);
+        assert_eq!(rows.len(), count);
+        STATEMENTS.with(|count| count.get())
+    };
+
+    let small = statements_for(5);
+    let large = statements_for(500);
+    assert_eq!(small, 1);
+    assert_eq!(small, large);
+}
@@ -1,6 +1,10 @@
-use knotter_core::domain::InteractionKind;
+use knotter_core::domain::{InteractionDirection, InteractionKind};
 use knotter_core::rules::schedule_next;
-use knotter_store::repo::{ContactNew, InteractionNew};
+use knotter_store::error::StoreErrorKind;
+use knotter_store::repo::{
+    ContactNew, EmailMessageRecord, InteractionAdd, InteractionNew, InteractionRange,
+    InteractionUpdate, TelegramMessageRecord,
+};
 use knotter_store::Store;
 
 #[test]
@@ -22,6 +26,7 @@ fn interactions_add_and_list() {
                 next_touchpoint_at: None,
                 cadence_days: None,
                 archived_at: None,
+                organization: None,
             },
         )
         .expect("create contact");
@@ -35,6 +40,7 @@ fn interactions_add_and_list() {
             kind: InteractionKind::Email,
             note: "Sent a follow-up.".to_string(),
             follow_up_at: None,
+            direction: None,
         })
         .expect("add interaction");
 
@@ -47,6 +53,7 @@ fn interactions_add_and_list() {
             kind: InteractionKind::Call,
             note: "Quick call.".to_string(),
             follow_up_at: None,
+            direction: None,
         })
         .expect("add interaction");
 
@@ -57,6 +64,99 @@ fn interactions_add_and_list() {
     assert_eq!(list.len(), 2);
     assert_eq!(list[0].note, "Quick call.");
     assert_eq!(list[1].note, "Sent a follow-up.");
+
+    let interactions = store.interactions();
+    assert!(interactions
+        .exists_with_note(contact.id, &InteractionKind::Call, "Quick call.")
+        .expect("exists"));
+    assert!(!interactions
+        .exists_with_note(contact.id, &InteractionKind::Email, "Quick call.")
+        .expect("exists"));
+    assert!(!interactions
+        .exists_with_note(contact.id, &InteractionKind::Call, "Quick call")
+        .expect("exists"));
+    assert!(interactions
+        .exists_at(contact.id, now - 50, &InteractionKind::Call, "Quick call.")
+        .expect("exists"));
+    assert!(!interactions
+        .exists_at(contact.id, now - 49, &InteractionKind::Call, "Quick call.")
+        .expect("exists"));
+}
+
+#[test]
+fn interactions_page_and_window_in_sql() {
+    let store = Store::open_in_memory().expect("open in memory");
+    store.migrate().expect("migrate");
+
+    let now = 1_700_000_000;
+    let contact = store
+        .contacts()
+        .create(
+            now,
+            ContactNew {
+                display_name: "Ada Lovelace".to_string(),
+                email: None,
+                phone: None,
+                handle: None,
+                timezone: None,
+                next_touchpoint_at: None,
+                cadence_days: None,
+                archived_at: None,
+                organization: None,
+            },
+        )
+        .expect("create contact");
+
+    for day in 0..10 {
+        store
+            .interactions()
+            .add(InteractionNew {
+                contact_id: contact.id,
+                occurred_at: now - day * 86_400,
+                created_at: now,
+                kind: InteractionKind::Call,
+                note: format!("day {day}"),
+                follow_up_at: None,
+                direction: None,
+            })
+            .expect("add interaction");
+    }
+
+    let interactions = store.interactions();
+    let all = InteractionRange::default();
+    assert_eq!(
+        interactions
+            .count_for_contact_in_range(contact.id, all)
+            .expect("count"),
+        10
+    );
+
+    let page = interactions
+        .list_for_contact_in_range(contact.id, all, Some(3), 3)
+        .expect("page");
+    let notes: Vec<&str> = page.iter().map(|item| item.note.as_str()).collect();
+    assert_eq!(notes, vec!["day 3", "day 4", "day 5"]);
+
+    let rest = interactions
+        .list_for_contact_in_range(contact.id, all, None, 8)
+        .expect("rest");
+    assert_eq!(rest.len(), 2);
+
+    let window = InteractionRange {
+        since: Some(now - 5 * 86_400),
+        until: Some(now - 2 * 86_400),
+    };
+    assert_eq!(
+        interactions
+            .count_for_contact_in_range(contact.id, window)
+            .expect("count"),
+        3
+    );
+    let windowed = interactions
+        .list_for_contact_in_range(contact.id, window, None, 0)
+        .expect("window");
+    let notes: Vec<&str> = windowed.iter().map(|item| item.note.as_str()).collect();
+    assert_eq!(notes, vec!["day 3", "day 4", "day 5"]);
 }
 
 #[test]
@@ -78,6 +178,7 @@ fn touch_contact_inserts_interaction_and_reschedules_when_requested() {
                 next_touchpoint_at: Some(now + 123),
                 cadence_days: Some(7),
                 archived_at: None,
+                organization: None,
             },
         )
         .expect("create contact");
@@ -128,6 +229,7 @@ fn add_with_reschedule_updates_next_touchpoint() {
                 next_touchpoint_at: None,
                 cadence_days: Some(14),
                 archived_at: None,
+                organization: None,
             },
         )
         .expect("create contact");
@@ -144,6 +246,7 @@ fn add_with_reschedule_updates_next_touchpoint() {
                 kind: InteractionKind::Call,
                 note: "catch-up".to_string(),
                 follow_up_at: None,
+                direction: None,
             },
             true,
         )
@@ -177,6 +280,7 @@ fn interactions_latest_occurred_at_for_contacts() {
                 next_touchpoint_at: None,
                 cadence_days: None,
                 archived_at: None,
+                organization: None,
             },
         )
         .expect("create contact");
@@ -194,6 +298,7 @@ fn interactions_latest_occurred_at_for_contacts() {
                 next_touchpoint_at: None,
                 cadence_days: None,
                 archived_at: None,
+                organization: None,
             },
         )
         .expect("create contact");
@@ -207,6 +312,7 @@ fn interactions_latest_occurred_at_for_contacts() {
             kind: InteractionKind::Call,
             note: "first early".to_string(),
             follow_up_at: None,
+            direction: None,
         })
         .expect("add interaction");
     store
@@ -218,6 +324,7 @@ fn interactions_latest_occurred_at_for_contacts() {
             kind: InteractionKind::Email,
             note: "first latest".to_string(),
             follow_up_at: None,
+            direction: None,
         })
         .expect("add interaction");
 
@@ -230,6 +337,7 @@ fn interactions_latest_occurred_at_for_contacts() {
             kind: InteractionKind::Text,
             note: "second latest".to_string(),
             follow_up_at: None,
+            direction: None,
         })
         .expect("add interaction");
 
@@ -240,3 +348,472 @@ fn interactions_latest_occurred_at_for_contacts() {
     assert_eq!(latest.get(&first.id), Some(&(now - 50)));
     assert_eq!(latest.get(&second.id), Some(&(now - 10)));
 }
+
+#[test]
+fn update_and_delete_interactions_reschedule_from_latest() {
+    let store = Store::open_in_memory().expect("open in memory");
+    store.migrate().expect("migrate");
+
+    let now = 1_700_000_000;
+    let day = 86_400;
+    let contact = store
+        .contacts()
+        .create(
+            now,
+            ContactNew {
+                display_name: "Grace Hopper".to_string(),
+                email: None,
+                phone: None,
+                handle: None,
+                timezone: None,
+                next_touchpoint_at: Some(now + 7 * day),
+                cadence_days: Some(7),
+                archived_at: None,
+                organization: None,
+            },
+        )
+        .expect("create contact");
+
+    let add = |occurred_at: i64, note: &str| {
+        store
+            .interactions()
+            .add(InteractionNew {
+                contact_id: contact.id,
+                occurred_at,
+                created_at: now,
+                kind: InteractionKind::Call,
+                note: note.to_string(),
+                follow_up_at: None,
+                direction: None,
+            })
+            .expect("add interaction")
+    };
+    let older = add(now - 10 * day, "older");
+    let latest = add(now, "latest");
+
+    let err = store
+        .interactions()
+        .update(
+            now,
+            latest.id,
+            InteractionUpdate {
+                occurred_at: Some(now + 60),
+                ..Default::default()
+            },
+            true,
+        )
+        .expect_err("future edit");
+    assert_eq!(err.kind(), StoreErrorKind::InvalidInteraction);
+
+    let updated = store
+        .interactions()
+        .update(
+            now,
+            latest.id,
+            InteractionUpdate {
+                occurred_at: Some(now - 2 * day),
+                kind: Some(InteractionKind::Text),
+                note: Some("back-dated".to_string()),
+            },
+            true,
+        )
+        .expect("update");
+    assert_eq!(updated.kind, InteractionKind::Text);
+    assert_eq!(updated.note, "back-dated");
+    let stored = store
+        .interactions()
+        .get(latest.id)
+        .expect("get")
+        .expect("interaction");
+    assert_eq!(stored.occurred_at, now - 2 * day);
+    let contact_now = store
+        .contacts()
+        .get(contact.id)
+        .expect("get contact")
+        .expect("contact");
+    assert_eq!(
+        contact_now.next_touchpoint_at,
+        Some(schedule_next(now - 2 * day, 7).expect("schedule"))
+    );
+
+    // Editing only the note of an older interaction leaves the schedule alone.
+    store
+        .interactions()
+        .update(
+            now,
+            older.id,
+            InteractionUpdate {
+                note: Some("edited".to_string()),
+                ..Default::default()
+            },
+            true,
+        )
+        .expect("update older");
+
+    let removed = store
+        .interactions()
+        .delete(now, latest.id, true)
+        .expect("delete latest");
+    assert_eq!(removed.id, latest.id);
+    assert!(store.interactions().get(latest.id).expect("get").is_none());
+    let contact_now = store
+        .contacts()
+        .get(contact.id)
+        .expect("get contact")
+        .expect("contact");
+    assert_eq!(
+        contact_now.next_touchpoint_at,
+        Some(schedule_next(now - 10 * day, 7).expect("schedule"))
+    );
+
+    let err = store
+        .interactions()
+        .delete(now, latest.id, false)
+        .expect_err("already deleted");
+    assert_eq!(err.kind(), StoreErrorKind::NotFound);
+}
+
+#[test]
+fn follow_ups_list_clear_and_complete() {
+    let store = Store::open_in_memory().expect("open in memory");
+    store.migrate().expect("migrate");
+    let now = 1_700_000_000;
+    let day = 86_400;
+
+    let contact = |name: &str, archived_at: Option<i64>| ContactNew {
+        display_name: name.to_string(),
+        email: None,
+        phone: None,
+        handle: None,
+        timezone: None,
+        next_touchpoint_at: None,
+        cadence_days: Some(7),
+        archived_at,
+        organization: None,
+    };
+    let ada = store
+        .contacts()
+        .create(now, contact("Ada", None))
+        .expect("create ada");
+    let old = store
+        .contacts()
+        .create(now, contact("Old", Some(now - day)))
+        .expect("create archived");
+
+    let add = |contact_id, follow_up_at| {
+        store
+            .interactions()
+            .add(InteractionNew {
+                contact_id,
+                occurred_at: now - day,
+                created_at: now - day,
+                kind: InteractionKind::Call,
+                note: "promised to send notes".to_string(),
+                follow_up_at,
+                direction: None,
+            })
+            .expect("add interaction")
+    };
+    let later = add(ada.id, Some(now + 10 * day));
+    let soon = add(ada.id, Some(now + day));
+    let overdue = add(ada.id, Some(now - 2 * day));
+    add(ada.id, None);
+    add(old.id, Some(now - day));
+
+    let pending = store
+        .interactions()
+        .list_follow_ups(now + 3 * day)
+        .expect("list follow-ups");
+    let ids: Vec<_> = pending.iter().map(|item| item.interaction.id).collect();
+    assert_eq!(ids, vec![overdue.id, soon.id]);
+    assert_eq!(pending[0].display_name, "Ada");
+
+    let cleared = store
+        .interactions()
+        .clear_follow_up(overdue.id)
+        .expect("clear follow-up");
+    assert_eq!(cleared.follow_up_at, None);
+    let err = store
+        .interactions()
+        .clear_follow_up(overdue.id)
+        .expect_err("already cleared");
+    assert_eq!(err.kind(), StoreErrorKind::InvalidInteraction);
+
+    let (cleared, logged) = store
+        .interactions()
+        .complete_follow_up(
+            now,
+            soon.id,
+            InteractionKind::Email,
+            "sent the notes".to_string(),
+            true,
+        )
+        .expect("complete follow-up");
+    assert_eq!(cleared.follow_up_at, None);
+    assert_eq!(logged.contact_id, ada.id);
+    assert_eq!(logged.occurred_at, now);
+    let ada = store.contacts().get(ada.id).expect("get").expect("ada");
+    assert_eq!(
+        ada.next_touchpoint_at,
+        Some(schedule_next(now, 7).expect("next"))
+    );
+
+    let pending = store
+        .interactions()
+        .list_follow_ups(now + 30 * day)
+        .expect("list follow-ups");
+    let ids: Vec<_> = pending.iter().map(|item| item.interaction.id).collect();
+    assert_eq!(ids, vec![later.id]);
+}
+
+#[test]
+fn add_unless_recent_reports_repeats_inside_the_window() {
+    let store = Store::open_in_memory().expect("open in memory");
+    store.migrate().expect("migrate");
+
+    let now = 1_700_000_000;
+    let contact = store
+        .contacts()
+        .create(
+            now,
+            ContactNew {
+                display_name: "Ada Lovelace".to_string(),
+                email: None,
+                phone: None,
+                handle: None,
+                timezone: None,
+                next_touchpoint_at: None,
+                cadence_days: Some(7),
+                archived_at: None,
+                organization: None,
+            },
+        )
+        .expect("create contact");
+    let touch = |occurred_at: i64, kind: InteractionKind, note: &str| InteractionNew {
+        contact_id: contact.id,
+        occurred_at,
+        created_at: occurred_at,
+        kind,
+        note: note.to_string(),
+        follow_up_at: None,
+        direction: None,
+    };
+    let touch_kind = || InteractionKind::other("touch").expect("kind");
+    let window = 300;
+    let repo = store.interactions();
+
+    let first = match repo
+        .add_unless_recent(now, touch(now, touch_kind(), ""), true, window)
+        .expect("first touch")
+    {
+        InteractionAdd::Added(interaction) => interaction,
+        other => panic!("expected added, got {other:?}"),
+    };
+    let next_after_first = store
+        .contacts()
+        .get(contact.id)
+        .expect("get contact")
+        .expect("contact exists")
+        .next_touchpoint_at;
+
+    let later = now + window - 1;
+    match repo
+        .add_unless_recent(later, touch(later, touch_kind(), ""), true, window)
+        .expect("repeat touch")
+    {
+        InteractionAdd::AlreadyRecorded(interaction) => assert_eq!(interaction.id, first.id),
+        other => panic!("expected already recorded, got {other:?}"),
+    }
+    let after_repeat = store
+        .contacts()
+        .get(contact.id)
+        .expect("get contact")
+        .expect("contact exists");
+    assert_eq!(after_repeat.next_touchpoint_at, next_after_first);
+
+    let outcome = repo
+        .add_unless_recent(
+            later,
+            touch(later, touch_kind(), "called back"),
+            true,
+            window,
+        )
+        .expect("different note");
+    assert!(matches!(outcome, InteractionAdd::Added(_)));
+
+    let outcome = repo
+        .add_unless_recent(later, touch(later, InteractionKind::Call, ""), true, window)
+        .expect("different kind");
+    assert!(matches!(outcome, InteractionAdd::Added(_)));
+
+    let boundary = now + window;
+    let outcome = repo
+        .add_unless_recent(boundary, touch(boundary, touch_kind(), ""), true, window)
+        .expect("touch at the window edge");
+    assert!(matches!(outcome, InteractionAdd::Added(_)));
+
+    let outcome = repo
+        .add_unless_recent(boundary, touch(boundary, touch_kind(), ""), true, 0)
+        .expect("touch with the guard disabled");
+    assert!(matches!(outcome, InteractionAdd::Added(_)));
+
+    let list = repo
+        .list_for_contact(contact.id, 10, 0)
+        .expect("list interactions");
+    assert_eq!(list.len(), 5);
+}
+
+#[test]
+fn delete_duplicates_keeps_the_earliest_and_relinks_sync_rows() {
+    let store = Store::open_in_memory().expect("open in memory");
+    store.migrate().expect("migrate");
+
+    let now = 1_700_000_000;
+    let contact = store
+        .contacts()
+        .create(
+            now,
+            ContactNew {
+                display_name: "Ada Lovelace".to_string(),
+                email: None,
+                phone: None,
+                handle: None,
+                timezone: None,
+                next_touchpoint_at: Some(now + 86_400),
+                cadence_days: Some(7),
+                archived_at: None,
+                organization: None,
+            },
+        )
+        .expect("create contact");
+    let repo = store.interactions();
+    let add = |occurred_at: i64, kind: InteractionKind, note: &str| {
+        repo.add(InteractionNew {
+            contact_id: contact.id,
+            occurred_at,
+            created_at: now,
+            kind,
+            note: note.to_string(),
+            follow_up_at: None,
+            direction: None,
+        })
+        .expect("add interaction")
+    };
+
+    // A run chained 60s apart, then a repeat after a long gap.
+    let kept_email = add(now, InteractionKind::Email, "Re: plans");
+    add(now + 60, InteractionKind::Email, "Re: plans");
+    add(now + 120, InteractionKind::Email, "Re: plans");
+    add(now + 3_600, InteractionKind::Email, "Re: plans");
+    add(now + 30, InteractionKind::Email, "Other thread");
+    add(now + 30, InteractionKind::Call, "Re: plans");
+    // Same second twice, as after a telegram resync.
+    add(now + 10, InteractionKind::Telegram, "hi");
+    let duplicate_telegram = repo
+        .add(InteractionNew {
+            contact_id: contact.id,
+            occurred_at: now + 10,
+            created_at: now + 5,
+            kind: InteractionKind::Telegram,
+            note: "hi".to_string(),
+            follow_up_at: Some(now + 7 * 86_400),
+            direction: Some(InteractionDirection::Inbound),
+        })
+        .expect("add interaction");
+
+    let email = |uid: i64, occurred_at: i64| EmailMessageRecord {
+        account: "work".to_string(),
+        mailbox: "INBOX".to_string(),
+        uidvalidity: 1,
+        uid,
+        message_id: Some(format!("<{uid}@example.com>")),
+        contact_id: contact.id,
+        occurred_at,
+        direction: "inbound".to_string(),
+        subject: Some("Re: plans".to_string()),
+        created_at: now,
+    };
+    for (uid, at) in [(1, now), (2, now + 60), (3, now + 120), (4, now + 3_600)] {
+        assert!(store
+            .email_sync()
+            .record_message(&email(uid, at))
+            .expect("record email"));
+    }
+    for message_id in [1, 2] {
+        assert!(store
+            .telegram_sync()
+            .record_message(&TelegramMessageRecord {
+                account: "primary".to_string(),
+                peer_id: 42,
+                message_id,
+                contact_id: contact.id,
+                occurred_at: now + 10,
+                direction: "inbound".to_string(),
+                snippet: Some("hi".to_string()),
+                created_at: now,
+            })
+            .expect("record telegram"));
+    }
+
+    let found = repo.find_duplicates(120).expect("find duplicates");
+    let found_at: Vec<i64> = found
+        .iter()
+        .map(|duplicate| duplicate.interaction.occurred_at)
+        .collect();
+    assert_eq!(found_at, vec![now + 10, now + 60, now + 120]);
+    assert_eq!(found[0].interaction.id, duplicate_telegram.id);
+    assert!(found[1..]
+        .iter()
+        .all(|duplicate| duplicate.kept_id == kept_email.id && duplicate.kept_occurred_at == now));
+    assert_eq!(repo.find_duplicates(59).expect("narrow window").len(), 1);
+    assert_eq!(
+        repo.list_for_contact(contact.id, 20, 0)
+            .expect("list")
+            .len(),
+        8,
+        "finding deletes nothing"
+    );
+
+    let dedupe = repo.delete_duplicates(120).expect("delete duplicates");
+    assert_eq!(dedupe.removed.len(), 3);
+    assert_eq!(dedupe.relinked_messages, 2);
+    assert!(repo.find_duplicates(120).expect("find again").is_empty());
+
+    let left = repo.list_for_contact(contact.id, 20, 0).expect("list");
+    assert_eq!(left.len(), 5);
+    let telegram = left
+        .iter()
+        .find(|interaction| interaction.kind == InteractionKind::Telegram)
+        .expect("kept telegram");
+    assert_eq!(telegram.follow_up_at, Some(now + 7 * 86_400));
+    assert_eq!(telegram.direction, Some(InteractionDirection::Inbound));
+
+    let email_times: Vec<i64> = {
+        let mut stmt = store
+            .connection()
+            .prepare("SELECT occurred_at FROM email_messages ORDER BY uid;")
+            .expect("prepare");
+        stmt.query_map([], |row| row.get(0))
+            .expect("query")
+            .collect::<Result<_, _>>()
+            .expect("rows")
+    };
+    assert_eq!(email_times, vec![now, now, now, now + 3_600]);
+    let telegram_rows: i64 = store
+        .connection()
+        .query_row(
+            "SELECT COUNT(*) FROM telegram_messages WHERE occurred_at = ?1;",
+            [now + 10],
+            |row| row.get(0),
+        )
+        .expect("count telegram rows");
+    assert_eq!(telegram_rows, 2);
+
+    let after = store
+        .contacts()
+        .get(contact.id)
+        .expect("get contact")
+        .expect("contact");
+    assert_eq!(after.next_touchpoint_at, Some(now + 86_400));
+    assert_eq!(after.updated_at, contact.updated_at);
+}
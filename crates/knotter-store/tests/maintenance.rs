@@ -0,0 +1,102 @@
+mod common;
+
+use common::new_contact;
+use knotter_core::domain::InteractionKind;
+use knotter_store::error::StoreError;
+use knotter_store::maintenance::ensure_vacuum_space;
+use knotter_store::repo::contacts::ContactNew;
+use knotter_store::repo::InteractionNew;
+use knotter_store::Store;
+use tempfile::TempDir;
+
+fn contact(name: &str) -> ContactNew {
+    ContactNew {
+        email: Some(format!("{}@example.com", name.to_lowercase())),
+        ..new_contact(name)
+    }
+}
+
+fn rows(stats: &knotter_store::maintenance::DbStats, table: &str) -> u64 {
+    stats
+        .tables
+        .iter()
+        .find(|entry| entry.table == table)
+        .map(|entry| entry.rows)
+        .expect("table counted")
+}
+
+#[test]
+fn stats_count_rows_for_an_in_memory_database() {
+    let store = Store::open_in_memory().expect("open");
+    store.migrate().expect("migrate");
+    store
+        .contacts()
+        .create(1_700_000_000, contact("Ada"))
+        .expect("create");
+
+    let stats = store.stats().expect("stats");
+    assert!(stats.path.is_none());
+    assert_eq!(stats.file_size_bytes, stats.page_count * stats.page_size);
+    assert_eq!(stats.wal_size_bytes, None);
+    assert_eq!(rows(&stats, "contacts"), 1);
+    assert_eq!(rows(&stats, "contact_emails"), 1);
+    assert_eq!(rows(&stats, "interactions"), 0);
+}
+
+#[test]
+fn vacuum_gives_back_space_left_by_deleted_rows() {
+    let temp = TempDir::new().expect("temp dir");
+    let db_path = temp.path().join("knotter.sqlite3");
+    let store = Store::open(&db_path).expect("open store");
+    store.migrate().expect("migrate");
+
+    let now = 1_700_000_000;
+    let ada = store
+        .contacts()
+        .create(now, contact("Ada"))
+        .expect("create");
+    let note = "x".repeat(2_000);
+    let mut ids = Vec::new();
+    for offset in 0..500 {
+        let interaction = store
+            .interactions()
+            .add(InteractionNew {
+                contact_id: ada.id,
+                occurred_at: now - offset,
+                created_at: now,
+                kind: InteractionKind::Call,
+                note: note.clone(),
+                follow_up_at: None,
+                direction: None,
+            })
+            .expect("add interaction");
+        ids.push(interaction.id);
+    }
+    for id in ids {
+        store.interactions().delete(now, id, false).expect("delete");
+    }
+
+    let before = store.stats().expect("stats");
+    assert!(before.path.is_some());
+    assert!(before.freelist_count > 0 || before.wal_size_bytes.is_some());
+    assert_eq!(rows(&before, "interactions"), 0);
+
+    let report = store.vacuum().expect("vacuum");
+    assert!(report.reclaimed_bytes() > 0, "{report:?}");
+    assert_eq!(report.after.freelist_count, 0);
+    assert!(report.after.total_bytes() < 1_000_000);
+    assert_eq!(rows(&report.after, "contacts"), 1);
+}
+
+#[test]
+fn vacuum_space_guard_needs_room_for_a_copy() {
+    ensure_vacuum_space(1_000, 1_000).expect("exactly enough");
+    let err = ensure_vacuum_space(1_000, 999).expect_err("too little");
+    assert!(matches!(
+        err,
+        StoreError::InsufficientSpace {
+            needed: 1_000,
+            available: 999
+        }
+    ));
+}
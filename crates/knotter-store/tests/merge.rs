@@ -1,10 +1,21 @@
 use knotter_core::domain::ContactDateKind;
+use knotter_store::doctor;
+use knotter_store::query::ListPage;
 use knotter_store::repo::{
-    ContactDateNew, ContactMergeOptions, ContactNew, ContactSourceNew, InteractionNew,
-    MergeCandidateCreate, MergeCandidateStatus, TelegramAccountNew, TelegramMessageRecord,
+    ContactDateNew, ContactMergeOptions, ContactNew, ContactSourceNew, ContactUpdate,
+    InteractionNew, MergeArchivedPreference, MergeCadencePreference, MergeCandidateCreate,
+    MergeCandidateQuery, MergeCandidateStatus, MergeDecisionOutcome, MergeFieldOverrides,
+    MergePreference, MergePruneAction, MergeTouchpointPreference, TelegramAccountNew,
+    TelegramMessageRecord,
 };
 use knotter_store::Store;
 
+fn assert_no_orphans(store: &Store) {
+    assert!(doctor::orphan_rows(store.connection())
+        .expect("orphan rows")
+        .is_empty());
+}
+
 #[test]
 fn merge_candidates_dedupe_open_pairs() {
     let store = Store::open_in_memory().expect("open store");
@@ -24,6 +35,7 @@ fn merge_candidates_dedupe_open_pairs() {
                 next_touchpoint_at: None,
                 cadence_days: None,
                 archived_at: None,
+                organization: None,
             },
         )
         .expect("create contact a");
@@ -41,6 +53,7 @@ fn merge_candidates_dedupe_open_pairs() {
                 next_touchpoint_at: None,
                 cadence_days: None,
                 archived_at: None,
+                organization: None,
             },
         )
         .expect("create contact b");
@@ -80,6 +93,127 @@ fn merge_candidates_dedupe_open_pairs() {
     assert_eq!(created.candidate.id, deduped.candidate.id);
 }
 
+#[test]
+fn apply_decision_dismisses_open_pairs_and_keeps_local_dismissals() {
+    let store = Store::open_in_memory().expect("open store");
+    store.migrate().expect("migrate");
+    let now = 1_700_000_000;
+    let create_contact = |name: &str| {
+        store
+            .contacts()
+            .create(
+                now,
+                ContactNew {
+                    display_name: name.to_string(),
+                    email: None,
+                    phone: None,
+                    handle: None,
+                    timezone: None,
+                    next_touchpoint_at: None,
+                    cadence_days: None,
+                    archived_at: None,
+                    organization: None,
+                },
+            )
+            .expect("create contact")
+    };
+    let ada = create_contact("Ada");
+    let ada_l = create_contact("Ada L");
+    let grace = create_contact("Grace");
+    let decision = || MergeCandidateCreate {
+        reason: "name-duplicate".to_string(),
+        source: Some("other-machine".to_string()),
+        preferred_contact_id: None,
+    };
+
+    let open = store
+        .merge_candidates()
+        .create(now, ada.id, ada_l.id, decision())
+        .expect("create open")
+        .candidate;
+    let outcome = store
+        .merge_candidates()
+        .apply_decision(
+            now + 10,
+            ada_l.id,
+            ada.id,
+            MergeCandidateStatus::Dismissed,
+            decision(),
+        )
+        .expect("dismiss");
+    assert_eq!(outcome, MergeDecisionOutcome::Dismissed);
+    let dismissed = store.merge_candidates().get(open.id).unwrap().unwrap();
+    assert_eq!(dismissed.status, MergeCandidateStatus::Dismissed);
+    assert_eq!(dismissed.resolved_at, Some(now + 10));
+
+    // A local dismissal is not reopened by an incoming open candidate.
+    let outcome = store
+        .merge_candidates()
+        .apply_decision(
+            now + 20,
+            ada.id,
+            ada_l.id,
+            MergeCandidateStatus::Open,
+            decision(),
+        )
+        .expect("open after dismissal");
+    assert_eq!(outcome, MergeDecisionOutcome::Unchanged);
+    assert!(store.merge_candidates().list_open().unwrap().is_empty());
+
+    let outcome = store
+        .merge_candidates()
+        .apply_decision(
+            now + 30,
+            ada.id,
+            grace.id,
+            MergeCandidateStatus::Dismissed,
+            decision(),
+        )
+        .expect("record dismissal");
+    assert_eq!(outcome, MergeDecisionOutcome::Created);
+    let outcome = store
+        .merge_candidates()
+        .apply_decision(
+            now + 40,
+            grace.id,
+            ada.id,
+            MergeCandidateStatus::Dismissed,
+            decision(),
+        )
+        .expect("repeat dismissal");
+    assert_eq!(outcome, MergeDecisionOutcome::Unchanged);
+    let dismissed = store
+        .merge_candidates()
+        .list(&MergeCandidateQuery::status(
+            MergeCandidateStatus::Dismissed,
+        ))
+        .unwrap();
+    assert_eq!(dismissed.len(), 2);
+
+    let outcome = store
+        .merge_candidates()
+        .apply_decision(
+            now + 50,
+            ada_l.id,
+            grace.id,
+            MergeCandidateStatus::Open,
+            decision(),
+        )
+        .expect("record open");
+    assert_eq!(outcome, MergeDecisionOutcome::Created);
+    assert_eq!(store.merge_candidates().list_open().unwrap().len(), 1);
+    assert!(store
+        .merge_candidates()
+        .apply_decision(
+            now + 60,
+            ada_l.id,
+            grace.id,
+            MergeCandidateStatus::Merged,
+            decision(),
+        )
+        .is_err());
+}
+
 #[test]
 fn merge_contacts_unifies_emails_tags_and_interactions() {
     let store = Store::open_in_memory().expect("open store");
@@ -99,6 +233,7 @@ fn merge_contacts_unifies_emails_tags_and_interactions() {
                 next_touchpoint_at: Some(2_000),
                 cadence_days: Some(30),
                 archived_at: None,
+                organization: None,
             },
         )
         .expect("create primary");
@@ -116,6 +251,7 @@ fn merge_contacts_unifies_emails_tags_and_interactions() {
                 next_touchpoint_at: Some(1_000),
                 cadence_days: None,
                 archived_at: Some(now),
+                organization: None,
             },
         )
         .expect("create secondary");
@@ -149,6 +285,7 @@ fn merge_contacts_unifies_emails_tags_and_interactions() {
             kind: knotter_core::domain::InteractionKind::Call,
             note: "Call".to_string(),
             follow_up_at: None,
+            direction: None,
         })
         .expect("add interaction primary");
     store
@@ -160,6 +297,7 @@ fn merge_contacts_unifies_emails_tags_and_interactions() {
             kind: knotter_core::domain::InteractionKind::Email,
             note: "Email".to_string(),
             follow_up_at: None,
+            direction: None,
         })
         .expect("add interaction secondary");
 
@@ -205,6 +343,7 @@ fn merge_contacts_unifies_emails_tags_and_interactions() {
 
     let missing = store.contacts().get(secondary.id).expect("get secondary");
     assert!(missing.is_none());
+    assert_no_orphans(&store);
 }
 
 #[test]
@@ -226,6 +365,7 @@ fn merge_contacts_moves_telegram_accounts_and_messages() {
                 next_touchpoint_at: None,
                 cadence_days: None,
                 archived_at: None,
+                organization: None,
             },
         )
         .expect("create primary");
@@ -243,6 +383,7 @@ fn merge_contacts_moves_telegram_accounts_and_messages() {
                 next_touchpoint_at: None,
                 cadence_days: None,
                 archived_at: None,
+                organization: None,
             },
         )
         .expect("create secondary");
@@ -309,6 +450,7 @@ fn merge_contacts_moves_telegram_accounts_and_messages() {
         )
         .expect("query telegram message");
     assert_eq!(contact_id, primary.id.to_string());
+    assert_no_orphans(&store);
 }
 
 #[test]
@@ -330,6 +472,7 @@ fn merge_contacts_moves_contact_sources() {
                 next_touchpoint_at: None,
                 cadence_days: None,
                 archived_at: None,
+                organization: None,
             },
         )
         .expect("create primary");
@@ -347,6 +490,7 @@ fn merge_contacts_moves_contact_sources() {
                 next_touchpoint_at: None,
                 cadence_days: None,
                 archived_at: None,
+                organization: None,
             },
         )
         .expect("create secondary");
@@ -395,6 +539,7 @@ fn merge_contacts_resolves_open_merge_candidates_for_secondary() {
                 next_touchpoint_at: None,
                 cadence_days: None,
                 archived_at: None,
+                organization: None,
             },
         )
         .expect("create primary");
@@ -412,6 +557,7 @@ fn merge_contacts_resolves_open_merge_candidates_for_secondary() {
                 next_touchpoint_at: None,
                 cadence_days: None,
                 archived_at: None,
+                organization: None,
             },
         )
         .expect("create secondary");
@@ -429,6 +575,7 @@ fn merge_contacts_resolves_open_merge_candidates_for_secondary() {
                 next_touchpoint_at: None,
                 cadence_days: None,
                 archived_at: None,
+                organization: None,
             },
         )
         .expect("create other");
@@ -505,6 +652,7 @@ fn merge_contacts_dedupes_contact_dates() {
                 next_touchpoint_at: None,
                 cadence_days: None,
                 archived_at: None,
+                organization: None,
             },
         )
         .expect("create primary");
@@ -521,6 +669,7 @@ fn merge_contacts_dedupes_contact_dates() {
                 next_touchpoint_at: None,
                 cadence_days: None,
                 archived_at: None,
+                organization: None,
             },
         )
         .expect("create secondary");
@@ -537,6 +686,7 @@ fn merge_contacts_dedupes_contact_dates() {
                 day: 1,
                 year: None,
                 source: None,
+                remind_days_before: None,
             },
         )
         .expect("add primary date");
@@ -552,9 +702,26 @@ fn merge_contacts_dedupes_contact_dates() {
                 day: 1,
                 year: Some(1990),
                 source: None,
+                remind_days_before: None,
             },
         )
         .expect("add secondary date");
+    store
+        .contact_dates()
+        .upsert(
+            now,
+            ContactDateNew {
+                contact_id: secondary.id,
+                kind: ContactDateKind::NameDay,
+                label: None,
+                month: 3,
+                day: 9,
+                year: None,
+                source: None,
+                remind_days_before: None,
+            },
+        )
+        .expect("add secondary name day");
 
     store
         .contacts()
@@ -570,10 +737,169 @@ fn merge_contacts_dedupes_contact_dates() {
         .contact_dates()
         .list_for_contact(primary.id)
         .expect("list dates");
-    assert_eq!(dates.len(), 1);
-    assert_eq!(dates[0].month, 1);
-    assert_eq!(dates[0].day, 1);
-    assert_eq!(dates[0].year, Some(1990));
+    assert_eq!(dates.len(), 2);
+    let birthday = dates
+        .iter()
+        .find(|date| date.kind == ContactDateKind::Birthday)
+        .expect("birthday");
+    assert_eq!(birthday.month, 1);
+    assert_eq!(birthday.day, 1);
+    assert_eq!(birthday.year, Some(1990));
+    assert!(dates
+        .iter()
+        .any(|date| date.kind == ContactDateKind::NameDay && date.month == 3 && date.day == 9));
+    assert_no_orphans(&store);
+}
+
+#[test]
+fn merge_contacts_keeps_provenance_and_cadence_preference() {
+    let store = Store::open_in_memory().expect("open");
+    store.migrate().expect("migrate");
+    let now = 1_700_000_000;
+
+    let contact = |name: &str, cadence_days: i32, next_touchpoint_at: i64| ContactNew {
+        display_name: name.to_string(),
+        email: None,
+        phone: None,
+        handle: None,
+        timezone: None,
+        next_touchpoint_at: Some(next_touchpoint_at),
+        cadence_days: Some(cadence_days),
+        archived_at: None,
+        organization: None,
+    };
+    let primary = store
+        .contacts()
+        .create(now, contact("Primary", 30, now + 30 * 86_400))
+        .expect("create primary");
+    let secondary = store
+        .contacts()
+        .create(now, contact("Secondary", 7, now + 40 * 86_400))
+        .expect("create secondary");
+    store
+        .contacts()
+        .update(
+            now,
+            secondary.id,
+            ContactUpdate {
+                phone: Some(Some("+1 555 0100".to_string())),
+                source: Some("carddav:work".to_string()),
+                ..Default::default()
+            },
+        )
+        .expect("import phone");
+    store
+        .contacts()
+        .update(
+            now,
+            secondary.id,
+            ContactUpdate {
+                cadence_days: Some(Some(7)),
+                handle: Some(Some("@sec".to_string())),
+                ..Default::default()
+            },
+        )
+        .expect("manual handle");
+
+    let options = ContactMergeOptions {
+        touchpoint: MergeTouchpointPreference::Cadence,
+        cadence: MergeCadencePreference::Shortest,
+        ..ContactMergeOptions::default()
+    };
+    let merged = store
+        .contacts()
+        .merge_contacts(now + 10, primary.id, secondary.id, options)
+        .expect("merge contacts");
+
+    assert_eq!(merged.display_name, "Primary");
+    assert_eq!(merged.cadence_days, Some(7));
+    assert_eq!(merged.next_touchpoint_at, Some(now + 40 * 86_400));
+    assert_eq!(merged.phone.as_deref(), Some("+1 555 0100"));
+
+    let provenance = store
+        .field_provenance()
+        .list_for_contact(primary.id)
+        .expect("provenance");
+    let source_of = |field: &str| {
+        provenance
+            .iter()
+            .find(|row| row.field == field)
+            .map(|row| row.source.as_str())
+    };
+    assert_eq!(source_of("phone"), Some("carddav:work"));
+    assert_eq!(source_of("handle"), Some("manual"));
+    assert_eq!(source_of("display_name"), None);
+    assert_no_orphans(&store);
+}
+
+#[test]
+fn merge_contacts_applies_per_field_overrides() {
+    let store = Store::open_in_memory().expect("open store");
+    store.migrate().expect("migrate");
+    let now = 1_700_000_000;
+
+    let primary = store
+        .contacts()
+        .create(
+            now,
+            ContactNew {
+                display_name: "Primary".to_string(),
+                email: Some("primary@example.com".to_string()),
+                phone: Some("+1 555 0100".to_string()),
+                handle: Some("@primary".to_string()),
+                timezone: None,
+                next_touchpoint_at: Some(now + 5 * 86_400),
+                cadence_days: Some(30),
+                archived_at: None,
+                organization: None,
+            },
+        )
+        .expect("create primary");
+    let secondary = store
+        .contacts()
+        .create(
+            now,
+            ContactNew {
+                display_name: "Secondary".to_string(),
+                email: Some("secondary@example.com".to_string()),
+                phone: Some("+1 555 0199".to_string()),
+                handle: None,
+                timezone: Some("Europe/Berlin".to_string()),
+                next_touchpoint_at: Some(now + 20 * 86_400),
+                cadence_days: Some(7),
+                archived_at: None,
+                organization: None,
+            },
+        )
+        .expect("create secondary");
+
+    let options = ContactMergeOptions {
+        fields: MergeFieldOverrides {
+            display_name: Some(MergePreference::Secondary),
+            email: Some(MergePreference::Secondary),
+            handle: Some(MergePreference::Secondary),
+            cadence_days: Some(MergePreference::Secondary),
+            next_touchpoint_at: Some(MergePreference::Secondary),
+            ..MergeFieldOverrides::default()
+        },
+        ..ContactMergeOptions::default()
+    };
+    let merged = store
+        .contacts()
+        .merge_contacts(now + 10, primary.id, secondary.id, options)
+        .expect("merge contacts");
+
+    assert_eq!(merged.id, primary.id);
+    assert_eq!(merged.display_name, "Secondary");
+    assert_eq!(merged.email.as_deref(), Some("secondary@example.com"));
+    // Fields without an override still follow `prefer`.
+    assert_eq!(merged.phone.as_deref(), Some("+1 555 0100"));
+    // A chosen side without a value falls back to the other contact.
+    assert_eq!(merged.handle.as_deref(), Some("@primary"));
+    assert_eq!(merged.timezone.as_deref(), Some("Europe/Berlin"));
+    assert_eq!(merged.cadence_days, Some(7));
+    assert_eq!(merged.next_touchpoint_at, Some(now + 20 * 86_400));
+    assert_no_orphans(&store);
 }
 
 #[test]
@@ -595,6 +921,7 @@ fn merge_contacts_prefers_secondary_primary_email() {
                 next_touchpoint_at: None,
                 cadence_days: None,
                 archived_at: None,
+                organization: None,
             },
         )
         .expect("create primary");
@@ -612,6 +939,7 @@ fn merge_contacts_prefers_secondary_primary_email() {
                 next_touchpoint_at: None,
                 cadence_days: None,
                 archived_at: None,
+                organization: None,
             },
         )
         .expect("create secondary");
@@ -637,3 +965,316 @@ fn merge_contacts_prefers_secondary_primary_email() {
         .map(|email| email.email.clone());
     assert_eq!(primary_email, Some("secondary@example.com".to_string()));
 }
+
+#[test]
+fn preview_merge_matches_merge_for_conflicting_fields() {
+    let store = Store::open_in_memory().expect("open store");
+    store.migrate().expect("migrate");
+    let now = 1_700_000_000;
+
+    let primary = store
+        .contacts()
+        .create(
+            now,
+            ContactNew {
+                display_name: "Ada".to_string(),
+                email: Some("ada@example.com".to_string()),
+                phone: Some("+1 555 0100".to_string()),
+                handle: None,
+                timezone: Some("Europe/London".to_string()),
+                next_touchpoint_at: Some(now + 20 * 86_400),
+                cadence_days: Some(30),
+                archived_at: None,
+                organization: None,
+            },
+        )
+        .expect("create primary");
+    let secondary = store
+        .contacts()
+        .create(
+            now,
+            ContactNew {
+                display_name: "Ada Lovelace".to_string(),
+                email: Some("ada@work.test".to_string()),
+                phone: Some("+1 555 0199".to_string()),
+                handle: Some("@ada".to_string()),
+                timezone: Some("Europe/Berlin".to_string()),
+                next_touchpoint_at: Some(now + 5 * 86_400),
+                cadence_days: Some(7),
+                archived_at: None,
+                organization: None,
+            },
+        )
+        .expect("create secondary");
+    store
+        .tags()
+        .add_tag_to_contact(
+            &primary.id.to_string(),
+            knotter_core::domain::TagName::new("friends").expect("tag"),
+        )
+        .expect("tag primary");
+    store
+        .tags()
+        .add_tag_to_contact(
+            &secondary.id.to_string(),
+            knotter_core::domain::TagName::new("work").expect("tag"),
+        )
+        .expect("tag secondary");
+    store
+        .interactions()
+        .add(InteractionNew {
+            contact_id: secondary.id,
+            occurred_at: now - 10,
+            created_at: now - 10,
+            kind: knotter_core::domain::InteractionKind::Call,
+            note: "Call".to_string(),
+            follow_up_at: None,
+            direction: None,
+        })
+        .expect("add interaction");
+    store
+        .contact_dates()
+        .upsert(
+            now,
+            ContactDateNew {
+                contact_id: secondary.id,
+                kind: ContactDateKind::Birthday,
+                label: None,
+                month: 12,
+                day: 10,
+                year: Some(1815),
+                source: None,
+                remind_days_before: None,
+            },
+        )
+        .expect("add date");
+
+    let options = ContactMergeOptions {
+        touchpoint: MergeTouchpointPreference::Latest,
+        fields: MergeFieldOverrides {
+            display_name: Some(MergePreference::Secondary),
+            timezone: Some(MergePreference::Secondary),
+            ..MergeFieldOverrides::default()
+        },
+        ..ContactMergeOptions::default()
+    };
+    let preview = store
+        .contacts()
+        .preview_merge(now + 10, primary.id, secondary.id, options.clone())
+        .expect("preview merge");
+
+    assert_eq!(preview.contact.display_name, "Ada Lovelace");
+    assert_eq!(preview.contact.email.as_deref(), Some("ada@example.com"));
+    assert_eq!(preview.contact.phone.as_deref(), Some("+1 555 0100"));
+    assert_eq!(preview.contact.handle.as_deref(), Some("@ada"));
+    assert_eq!(preview.contact.timezone.as_deref(), Some("Europe/Berlin"));
+    assert_eq!(preview.contact.cadence_days, Some(30));
+    assert_eq!(preview.contact.next_touchpoint_at, Some(now + 20 * 86_400));
+    assert_eq!(preview.emails, vec!["ada@example.com", "ada@work.test"]);
+    assert_eq!(preview.tags, vec!["friends", "work"]);
+    assert_eq!(preview.new_aliases, vec!["Ada"]);
+    assert_eq!(preview.interactions_moved, 1);
+    assert_eq!(preview.dates_moved, 1);
+
+    // Previewing writes nothing.
+    assert!(store
+        .contacts()
+        .get(secondary.id)
+        .expect("get secondary")
+        .is_some());
+
+    let merged = store
+        .contacts()
+        .merge_contacts(now + 10, primary.id, secondary.id, options)
+        .expect("merge contacts");
+    assert_eq!(merged.display_name, preview.contact.display_name);
+    assert_eq!(merged.email, preview.contact.email);
+    assert_eq!(merged.phone, preview.contact.phone);
+    assert_eq!(merged.handle, preview.contact.handle);
+    assert_eq!(merged.timezone, preview.contact.timezone);
+    assert_eq!(merged.cadence_days, preview.contact.cadence_days);
+    assert_eq!(
+        merged.next_touchpoint_at,
+        preview.contact.next_touchpoint_at
+    );
+    assert_eq!(
+        store
+            .emails()
+            .list_emails_for_contact(&primary.id)
+            .expect("list emails"),
+        preview.emails
+    );
+}
+
+#[test]
+fn preview_merge_resolves_archived_survivor() {
+    let store = Store::open_in_memory().expect("open store");
+    store.migrate().expect("migrate");
+    let now = 1_700_000_000;
+
+    let create = |name: &str, archived_at: Option<i64>| {
+        store
+            .contacts()
+            .create(
+                now,
+                ContactNew {
+                    display_name: name.to_string(),
+                    email: None,
+                    phone: None,
+                    handle: None,
+                    timezone: None,
+                    next_touchpoint_at: None,
+                    cadence_days: None,
+                    archived_at,
+                    organization: None,
+                },
+            )
+            .expect("create contact")
+    };
+    let archived = create("Archived", Some(now - 100));
+    let active = create("Active", None);
+
+    let preview = |archived_pref: MergeArchivedPreference| {
+        store
+            .contacts()
+            .preview_merge(
+                now,
+                archived.id,
+                active.id,
+                ContactMergeOptions {
+                    archived: archived_pref,
+                    ..ContactMergeOptions::default()
+                },
+            )
+            .expect("preview merge")
+    };
+    assert_eq!(
+        preview(MergeArchivedPreference::ActiveIfAny)
+            .contact
+            .archived_at,
+        None
+    );
+    assert_eq!(
+        preview(MergeArchivedPreference::Primary)
+            .contact
+            .archived_at,
+        Some(now - 100)
+    );
+    assert_eq!(
+        preview(MergeArchivedPreference::Secondary)
+            .contact
+            .archived_at,
+        None
+    );
+    assert!(preview(MergeArchivedPreference::Primary).emails.is_empty());
+
+    let merged = store
+        .contacts()
+        .merge_contacts(now, archived.id, active.id, ContactMergeOptions::default())
+        .expect("merge contacts");
+    assert_eq!(merged.archived_at, None);
+    assert_no_orphans(&store);
+}
+
+#[test]
+fn merge_candidates_list_filters_pages_and_prunes() {
+    let store = Store::open_in_memory().expect("open store");
+    store.migrate().expect("migrate");
+    let now = 1_700_000_000;
+
+    let contacts: Vec<_> = (0..5)
+        .map(|index| {
+            store
+                .contacts()
+                .create(
+                    now,
+                    ContactNew {
+                        display_name: format!("Contact {index}"),
+                        email: None,
+                        phone: None,
+                        handle: None,
+                        timezone: None,
+                        next_touchpoint_at: None,
+                        cadence_days: None,
+                        archived_at: None,
+                        organization: None,
+                    },
+                )
+                .expect("create contact")
+        })
+        .collect();
+    let candidate = |index: usize, created_at: i64, reason: &str, source: &str| {
+        store
+            .merge_candidates()
+            .create(
+                created_at,
+                contacts[0].id,
+                contacts[index].id,
+                MergeCandidateCreate {
+                    reason: reason.to_string(),
+                    source: Some(source.to_string()),
+                    preferred_contact_id: None,
+                },
+            )
+            .expect("create candidate")
+            .candidate
+    };
+    let old_email = candidate(1, now - 200 * 86_400, "email-duplicate", "vcf");
+    let old_name = candidate(2, now - 100 * 86_400, "name-duplicate", "scan:same-name");
+    let recent = candidate(3, now - 86_400, "email-duplicate", "vcf");
+    let dismissed = candidate(4, now - 300 * 86_400, "email-duplicate", "vcf");
+    store
+        .merge_candidates()
+        .dismiss(now, dismissed.id)
+        .expect("dismiss");
+
+    let repo = store.merge_candidates();
+    let ids = |query: &MergeCandidateQuery| -> Vec<_> {
+        repo.list(query)
+            .expect("list")
+            .into_iter()
+            .map(|candidate| candidate.id)
+            .collect()
+    };
+    let open = MergeCandidateQuery::status(MergeCandidateStatus::Open);
+    assert_eq!(ids(&open), vec![recent.id, old_name.id, old_email.id]);
+    assert_eq!(
+        ids(&MergeCandidateQuery {
+            reasons: vec!["email-duplicate".to_string()],
+            source: Some("vcf".to_string()),
+            ..open.clone()
+        }),
+        vec![recent.id, old_email.id]
+    );
+    let older = MergeCandidateQuery {
+        created_before: Some(now - 90 * 86_400),
+        ..open.clone()
+    };
+    assert_eq!(ids(&older), vec![old_name.id, old_email.id]);
+    let page = MergeCandidateQuery {
+        page: Some(ListPage {
+            limit: 1,
+            offset: 1,
+        }),
+        ..open.clone()
+    };
+    assert_eq!(ids(&page), vec![old_name.id]);
+    assert_eq!(repo.count(&page).expect("count"), 3);
+
+    let dismissed_count = repo
+        .prune_open(now, now - 150 * 86_400, MergePruneAction::Dismiss)
+        .expect("prune dismiss");
+    assert_eq!(dismissed_count, 1);
+    assert_eq!(
+        repo.get(old_email.id).expect("get").expect("kept").status,
+        MergeCandidateStatus::Dismissed
+    );
+    let deleted = repo
+        .prune_open(now, now - 90 * 86_400, MergePruneAction::Delete)
+        .expect("prune delete");
+    assert_eq!(deleted, 1);
+    assert!(repo.get(old_name.id).expect("get").is_none());
+    assert_eq!(ids(&open), vec![recent.id]);
+    // Candidates that were already resolved are never pruned.
+    assert!(repo.get(dismissed.id).expect("get").is_some());
+}
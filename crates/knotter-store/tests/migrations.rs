@@ -1,3 +1,4 @@
+use knotter_store::error::StoreError;
 use knotter_store::Store;
 
 #[test]
@@ -12,5 +13,65 @@ fn migrations_apply_once() {
             row.get(0)
         })
         .expect("schema version");
-    assert_eq!(version, 11);
+    assert_eq!(version, 26);
+}
+
+#[test]
+fn readonly_open_requires_current_schema_and_rejects_writes() {
+    let temp = tempfile::TempDir::new().expect("temp dir");
+    let db_path = temp.path().join("knotter.sqlite3");
+
+    assert!(matches!(
+        Store::open_readonly(&db_path),
+        Err(StoreError::NotFound(_))
+    ));
+
+    let store = Store::open(&db_path).expect("open");
+    drop(store);
+    let readonly = Store::open_readonly(&db_path).expect("open read-only");
+    let err = readonly.check_schema().expect_err("unmigrated db");
+    assert!(err.to_string().contains("older than supported"));
+    drop(readonly);
+
+    let store = Store::open(&db_path).expect("open");
+    store.migrate().expect("migrate");
+    drop(store);
+
+    let readonly = Store::open_readonly(&db_path).expect("open read-only");
+    assert!(readonly.is_readonly());
+    readonly.check_schema().expect("current schema");
+    assert!(readonly
+        .connection()
+        .execute("DELETE FROM contacts;", [])
+        .is_err());
+}
+
+#[test]
+fn name_key_migration_backfills_existing_contacts() {
+    let store = Store::open_in_memory().expect("open in memory");
+    store.migrate().expect("migrate");
+    let conn = store.connection();
+    conn.execute(
+        "INSERT INTO contacts (id, display_name, created_at, updated_at)
+         VALUES ('7c9e6679-7425-40de-944b-e07fc1f90ae7', 'Zoë  Müller', 0, 0);",
+        [],
+    )
+    .expect("insert legacy row");
+    // Rewind to just before 020 so it runs again over the legacy row.
+    conn.execute_batch(
+        "DROP INDEX idx_contacts_name_key;
+         ALTER TABLE contacts DROP COLUMN name_key;
+         ALTER TABLE contacts DROP COLUMN organization;
+         ALTER TABLE contacts DROP COLUMN priority;
+         UPDATE knotter_schema SET version = 19;",
+    )
+    .expect("rewind");
+
+    store.migrate().expect("migrate");
+    let found = store
+        .contacts()
+        .list_by_display_name("Zoe Muller")
+        .expect("lookup");
+    assert_eq!(found.len(), 1);
+    assert_eq!(found[0].display_name, "Zoë  Müller");
 }
@@ -0,0 +1,130 @@
+mod common;
+
+use chrono::{FixedOffset, NaiveDate};
+use common::new_contact;
+use knotter_core::parse_filter;
+use knotter_store::error::StoreError;
+use knotter_store::query::ContactQuery;
+use knotter_store::repo::{ContactMergeOptions, ContactOriginUpdate};
+use knotter_store::Store;
+
+#[test]
+fn origin_update_sets_clears_and_filters() {
+    let store = Store::open_in_memory().expect("open in memory");
+    store.migrate().expect("migrate");
+    let now = 1_700_000_000;
+    let ada = store
+        .contacts()
+        .create(now, new_contact("Ada"))
+        .expect("create");
+    let grace = store
+        .contacts()
+        .create(now, new_contact("Grace"))
+        .expect("create");
+
+    let origins = store.contact_origins();
+    let origin = origins
+        .update(
+            now,
+            ada.id,
+            ContactOriginUpdate {
+                met_at: Some(Some("  RustConf 2023 ".to_string())),
+                met_on: Some(NaiveDate::from_ymd_opt(2023, 9, 12)),
+                met_via: Some(Some(grace.id)),
+            },
+        )
+        .expect("update")
+        .expect("origin");
+    assert_eq!(origin.met_at.as_deref(), Some("RustConf 2023"));
+    assert_eq!(origin.met_via, Some(grace.id));
+
+    let err = origins
+        .update(
+            now,
+            ada.id,
+            ContactOriginUpdate {
+                met_via: Some(Some(ada.id)),
+                ..Default::default()
+            },
+        )
+        .expect_err("self introduction");
+    assert!(matches!(err, StoreError::Core(_)));
+
+    let filter = parse_filter("met:rustconf").expect("parse filter");
+    let query = ContactQuery::from_filter(&filter).expect("build query");
+    let offset = FixedOffset::east_opt(0).expect("offset");
+    let listed = store
+        .contacts()
+        .list_contacts(&query, now, 7, offset)
+        .expect("list contacts");
+    assert_eq!(listed.len(), 1);
+    assert_eq!(listed[0].id, ada.id);
+
+    let cleared = origins
+        .update(
+            now,
+            ada.id,
+            ContactOriginUpdate {
+                met_at: Some(None),
+                met_on: Some(None),
+                met_via: Some(None),
+            },
+        )
+        .expect("clear");
+    assert!(cleared.is_none());
+    assert!(origins.get(ada.id).expect("get").is_none());
+}
+
+#[test]
+fn deleting_introducer_clears_link_and_merges_repoint_it() {
+    let store = Store::open_in_memory().expect("open in memory");
+    store.migrate().expect("migrate");
+    let now = 1_700_000_000;
+    let contacts = store.contacts();
+    let ada = contacts.create(now, new_contact("Ada")).expect("create");
+    let grace = contacts.create(now, new_contact("Grace")).expect("create");
+    let grace_dup = contacts
+        .create(now, new_contact("Grace H."))
+        .expect("create");
+    let linus = contacts.create(now, new_contact("Linus")).expect("create");
+
+    let origins = store.contact_origins();
+    let via = |id| ContactOriginUpdate {
+        met_via: Some(Some(id)),
+        ..Default::default()
+    };
+    origins
+        .update(now, ada.id, via(grace_dup.id))
+        .expect("link");
+    origins
+        .update(
+            now,
+            grace_dup.id,
+            ContactOriginUpdate {
+                met_at: Some(Some("Meetup".to_string())),
+                met_via: Some(Some(grace.id)),
+                ..Default::default()
+            },
+        )
+        .expect("link");
+    origins.update(now, grace.id, via(linus.id)).expect("link");
+
+    contacts
+        .merge_contacts(
+            now + 10,
+            grace.id,
+            grace_dup.id,
+            ContactMergeOptions::default(),
+        )
+        .expect("merge");
+    assert_eq!(
+        origins.get(ada.id).expect("get").expect("origin").met_via,
+        Some(grace.id)
+    );
+    let merged = origins.get(grace.id).expect("get").expect("origin");
+    assert_eq!(merged.met_at.as_deref(), Some("Meetup"));
+    assert_eq!(merged.met_via, Some(linus.id));
+
+    contacts.delete(now + 20, grace.id).expect("delete");
+    assert!(origins.get(ada.id).expect("get").is_none());
+}
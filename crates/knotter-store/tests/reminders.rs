@@ -26,6 +26,7 @@ fn list_due_contacts_only_includes_overdue_today_soon() {
                 next_touchpoint_at: Some(now - 3600),
                 cadence_days: None,
                 archived_at: None,
+                organization: None,
             },
         )
         .expect("create overdue");
@@ -43,6 +44,7 @@ fn list_due_contacts_only_includes_overdue_today_soon() {
                 next_touchpoint_at: Some(now + 3600),
                 cadence_days: None,
                 archived_at: None,
+                organization: None,
             },
         )
         .expect("create today");
@@ -60,6 +62,7 @@ fn list_due_contacts_only_includes_overdue_today_soon() {
                 next_touchpoint_at: Some(now + 2 * 86_400),
                 cadence_days: None,
                 archived_at: None,
+                organization: None,
             },
         )
         .expect("create soon");
@@ -77,6 +80,7 @@ fn list_due_contacts_only_includes_overdue_today_soon() {
                 next_touchpoint_at: Some(now + 30 * 86_400),
                 cadence_days: None,
                 archived_at: None,
+                organization: None,
             },
         )
         .expect("create scheduled");
@@ -94,6 +98,7 @@ fn list_due_contacts_only_includes_overdue_today_soon() {
                 next_touchpoint_at: None,
                 cadence_days: None,
                 archived_at: None,
+                organization: None,
             },
         )
         .expect("create unscheduled");
@@ -111,6 +116,7 @@ fn list_due_contacts_only_includes_overdue_today_soon() {
                 next_touchpoint_at: Some(now - 7200),
                 cadence_days: None,
                 archived_at: Some(now - 60),
+                organization: None,
             },
         )
         .expect("create archived");
@@ -123,3 +129,257 @@ fn list_due_contacts_only_includes_overdue_today_soon() {
     let names: Vec<String> = results.into_iter().map(|c| c.display_name).collect();
     assert_eq!(names, vec!["Overdue", "Today", "Soon"]);
 }
+
+#[test]
+fn muted_contacts_skip_reminders_until_the_mute_lapses() {
+    let store = Store::open_in_memory().expect("open");
+    store.migrate().expect("migrate");
+
+    let now = Utc
+        .with_ymd_and_hms(2024, 1, 10, 12, 0, 0)
+        .unwrap()
+        .timestamp();
+    let offset = FixedOffset::east_opt(0).unwrap();
+    let contacts = store.contacts();
+    let mut ids = Vec::new();
+    for name in ["Muted", "Loud"] {
+        let contact = contacts
+            .create(
+                now,
+                ContactNew {
+                    display_name: name.to_string(),
+                    email: None,
+                    phone: None,
+                    handle: None,
+                    timezone: None,
+                    next_touchpoint_at: Some(now - 3600),
+                    cadence_days: None,
+                    archived_at: None,
+                    organization: None,
+                },
+            )
+            .expect("create");
+        ids.push(contact.id);
+    }
+    let until = now + 3 * 86_400;
+    contacts.mute(now, ids[0], until).expect("mute");
+
+    let due = |at: i64| -> Vec<String> {
+        contacts
+            .list_due_contacts(at, 7, offset)
+            .expect("list due")
+            .into_iter()
+            .map(|contact| contact.display_name)
+            .collect()
+    };
+    assert_eq!(due(now), vec!["Loud"]);
+    assert_eq!(due(until), vec!["Loud", "Muted"]);
+    assert_eq!(
+        contacts.active_mute(ids[0], now).expect("mute"),
+        Some(until)
+    );
+    assert_eq!(contacts.active_mute(ids[0], until).expect("mute"), None);
+
+    let picks = contacts
+        .list_random_active(now, 5, &[])
+        .expect("random picks");
+    assert_eq!(picks.len(), 1);
+
+    let filter = knotter_core::parse_filter("muted:true").expect("filter");
+    let query = knotter_store::query::ContactQuery::from_filter(&filter).expect("query");
+    let rows = contacts
+        .list_rows(&query, now, 7, offset, None)
+        .expect("rows");
+    assert_eq!(rows.len(), 1);
+    assert_eq!(rows[0].muted_until, Some(until));
+    let rows = contacts
+        .list_rows(&query, until, 7, offset, None)
+        .expect("rows");
+    assert!(rows.is_empty());
+
+    contacts.unmute(now, ids[0]).expect("unmute");
+    assert_eq!(due(now), vec!["Loud", "Muted"]);
+}
+
+#[test]
+fn list_view_due_buckets_match_core_at_midnight_and_at_now() {
+    use knotter_core::rules::{compute_due_state, DueSelector, DueState};
+    use knotter_store::query::ContactListQuery;
+
+    let store = Store::open_in_memory().expect("open");
+    store.migrate().expect("migrate");
+    let contacts = store.contacts();
+
+    // Local midnight in UTC+2 is 22:00 UTC the day before.
+    let offset = FixedOffset::east_opt(2 * 3600).unwrap();
+    let midnight = offset
+        .with_ymd_and_hms(2024, 1, 10, 0, 0, 0)
+        .unwrap()
+        .timestamp();
+    let tomorrow = midnight + 86_400;
+    let soon_end = tomorrow + 7 * 86_400;
+    let cases = [
+        ("a-just-missed", midnight - 1, DueState::Overdue),
+        ("b-at-now", midnight, DueState::Today),
+        ("c-end-of-today", tomorrow - 1, DueState::Today),
+        ("d-tomorrow-midnight", tomorrow, DueState::Soon),
+        ("e-end-of-soon", soon_end - 1, DueState::Soon),
+        ("f-after-soon", soon_end, DueState::Scheduled),
+    ];
+    for (name, next, _) in cases {
+        contacts
+            .create(
+                midnight - 86_400,
+                ContactNew {
+                    display_name: name.to_string(),
+                    email: None,
+                    phone: None,
+                    handle: None,
+                    timezone: None,
+                    next_touchpoint_at: Some(next),
+                    cadence_days: None,
+                    archived_at: None,
+                    organization: None,
+                },
+            )
+            .expect("create");
+    }
+
+    let states = |now: i64| -> Vec<(String, DueState)> {
+        let mut rows: Vec<_> = contacts
+            .list_view(&ContactListQuery::new(7), now, offset)
+            .expect("list view")
+            .into_iter()
+            .map(|item| (item.display_name, item.due_state))
+            .collect();
+        rows.sort_by(|a, b| a.0.cmp(&b.0));
+        rows
+    };
+
+    // Now is exactly local midnight: a touchpoint at `now` is due today, not overdue.
+    let expected: Vec<(String, DueState)> = cases
+        .iter()
+        .map(|(name, _, state)| (name.to_string(), *state))
+        .collect();
+    assert_eq!(states(midnight), expected);
+    for (_, next, state) in cases {
+        assert_eq!(
+            compute_due_state(midnight, Some(next), 7, offset).unwrap(),
+            state
+        );
+    }
+
+    // Later the same day the touchpoint at midnight is overdue; one at `now` is today.
+    let noon = midnight + 12 * 3600;
+    let at_noon = states(noon);
+    assert!(at_noon.contains(&("b-at-now".to_string(), DueState::Overdue)));
+    assert!(at_noon.contains(&("c-end-of-today".to_string(), DueState::Today)));
+    assert_eq!(
+        compute_due_state(noon, Some(noon), 7, offset).unwrap(),
+        DueState::Today
+    );
+
+    // The due selector and the computed bucket agree on the same boundaries.
+    let today = ContactListQuery {
+        due: Some(DueSelector::Today),
+        ..ContactListQuery::new(7)
+    };
+    let names: Vec<String> = contacts
+        .list_view(&today, midnight, offset)
+        .expect("today")
+        .into_iter()
+        .map(|item| item.display_name)
+        .collect();
+    assert_eq!(names, ["b-at-now", "c-end-of-today"]);
+
+    let conflicting = ContactListQuery {
+        filter: Some(knotter_core::parse_filter("due:overdue").expect("filter")),
+        ..today
+    };
+    assert!(contacts.list_view(&conflicting, midnight, offset).is_err());
+}
+
+#[test]
+fn list_view_reports_latest_interaction_per_contact() {
+    use knotter_core::domain::{InteractionKind, TagName};
+    use knotter_store::query::ContactListQuery;
+    use knotter_store::repo::InteractionNew;
+
+    let store = Store::open_in_memory().expect("open");
+    store.migrate().expect("migrate");
+    let now = Utc
+        .with_ymd_and_hms(2024, 1, 10, 12, 0, 0)
+        .unwrap()
+        .timestamp();
+    let offset = FixedOffset::east_opt(0).unwrap();
+
+    let create = |name: &str| {
+        store
+            .contacts()
+            .create(
+                now,
+                ContactNew {
+                    display_name: name.to_string(),
+                    email: None,
+                    phone: None,
+                    handle: None,
+                    timezone: None,
+                    next_touchpoint_at: None,
+                    cadence_days: None,
+                    archived_at: None,
+                    organization: None,
+                },
+            )
+            .expect("create")
+    };
+    let ada = create("Ada");
+    create("Grace");
+    // Tags fan out the list join; the latest interaction must not repeat per tag.
+    for tag in ["friends", "work"] {
+        store
+            .tags()
+            .add_tag_to_contact(&ada.id.to_string(), TagName::new(tag).expect("tag"))
+            .expect("tag");
+    }
+    for (offset_days, kind) in [
+        (30, InteractionKind::Call),
+        (3, InteractionKind::Email),
+        (10, InteractionKind::other("coffee").expect("kind")),
+    ] {
+        store
+            .interactions()
+            .add(InteractionNew {
+                contact_id: ada.id,
+                occurred_at: now - offset_days * 86_400,
+                created_at: now,
+                kind,
+                note: String::new(),
+                follow_up_at: None,
+                direction: None,
+            })
+            .expect("add interaction");
+    }
+
+    let rows = store
+        .contacts()
+        .list_view(&ContactListQuery::new(7), now, offset)
+        .expect("list");
+    let summary: Vec<_> = rows
+        .iter()
+        .map(|row| {
+            (
+                row.display_name.as_str(),
+                row.last_interaction_at,
+                row.last_interaction_kind.as_deref(),
+                row.tags.len(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        summary,
+        vec![
+            ("Ada", Some(now - 3 * 86_400), Some("email"), 2),
+            ("Grace", None, None, 0),
+        ]
+    );
+}
@@ -0,0 +1,36 @@
+use knotter_store::Store;
+
+#[test]
+fn vacation_window_is_stored_replaced_and_cleared() {
+    let store = Store::open_in_memory().expect("open in memory");
+    store.migrate().expect("migrate");
+    let now = 1_700_000_000;
+    let settings = store.settings();
+
+    assert_eq!(settings.vacation_until().expect("read"), None);
+    assert!(!settings.clear_vacation().expect("clear"));
+
+    settings
+        .set_vacation_until(now, now + 86_400)
+        .expect("set vacation");
+    settings
+        .set_vacation_until(now, now + 2 * 86_400)
+        .expect("replace vacation");
+    assert_eq!(
+        settings.vacation_until().expect("read"),
+        Some(now + 2 * 86_400)
+    );
+    assert_eq!(
+        settings.active_vacation_until(now).expect("active"),
+        Some(now + 2 * 86_400)
+    );
+    assert_eq!(
+        settings
+            .active_vacation_until(now + 2 * 86_400)
+            .expect("ended"),
+        None
+    );
+
+    assert!(settings.clear_vacation().expect("clear"));
+    assert_eq!(settings.vacation_until().expect("read"), None);
+}
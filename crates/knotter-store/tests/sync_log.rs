@@ -0,0 +1,44 @@
+mod common;
+
+use common::new_contact;
+use knotter_store::repo::ContactMergeOptions;
+use knotter_store::Store;
+
+#[test]
+fn sync_log_records_changes_newest_first_and_follows_merges() {
+    let store = Store::open_in_memory().expect("open in memory");
+    store.migrate().expect("migrate");
+    let now = 1_700_000_000;
+    let ada = store
+        .contacts()
+        .create(now, new_contact("Ada"))
+        .expect("create");
+    let ada_dup = store
+        .contacts()
+        .create(now, new_contact("Ada L"))
+        .expect("create");
+
+    let log = store.sync_log();
+    log.record(now, ada.id, "carddav", &[])
+        .expect("record none");
+    assert!(log.list_for_contact(ada.id).expect("list").is_empty());
+
+    log.record(now + 10, ada.id, "carddav", &["name", "emails"])
+        .expect("record");
+    log.record(now + 20, ada_dup.id, "macos", &["phone"])
+        .expect("record dup");
+
+    let entries = log.list_for_contact(ada.id).expect("list");
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].source, "carddav");
+    assert_eq!(entries[0].fields, vec!["name", "emails"]);
+    assert_eq!(entries[0].synced_at, now + 10);
+
+    store
+        .contacts()
+        .merge_contacts(now + 30, ada.id, ada_dup.id, ContactMergeOptions::default())
+        .expect("merge");
+    let entries = log.list_for_contact(ada.id).expect("list merged");
+    let sources: Vec<&str> = entries.iter().map(|entry| entry.source.as_str()).collect();
+    assert_eq!(sources, vec!["macos", "carddav"]);
+}
@@ -0,0 +1,146 @@
+mod common;
+
+use common::new_contact;
+use knotter_core::domain::{Tag, TagName};
+use knotter_store::Store;
+
+fn tag(name: &str) -> TagName {
+    TagName::new(name).expect("tag name")
+}
+
+fn tag_names(store: &Store, contact_id: &str) -> Vec<String> {
+    store
+        .tags()
+        .list_for_contact(contact_id)
+        .expect("list tags")
+        .into_iter()
+        .map(|tag| tag.name.as_str().to_string())
+        .collect()
+}
+
+#[test]
+fn rename_merges_into_an_existing_tag_without_duplicates() {
+    let store = Store::open_in_memory().expect("open in memory");
+    store.migrate().expect("migrate");
+    let now = 1_700_000_000;
+    let ada = store
+        .contacts()
+        .create(now, new_contact("Ada"))
+        .expect("ada");
+    let grace = store
+        .contacts()
+        .create(now, new_contact("Grace"))
+        .expect("grace");
+    let tags = store.tags();
+    tags.add_tag_to_contact(&ada.id.to_string(), tag("friends"))
+        .expect("tag ada");
+    tags.add_tag_to_contact(&ada.id.to_string(), tag("friend"))
+        .expect("tag ada");
+    tags.add_tag_to_contact(&grace.id.to_string(), tag("friends"))
+        .expect("tag grace");
+
+    assert_eq!(
+        tags.count_contacts_with_any(&[tag("friends")])
+            .expect("count"),
+        2
+    );
+    assert_eq!(
+        tags.rename(&tag("friends"), tag("friend")).expect("rename"),
+        2
+    );
+
+    assert_eq!(tag_names(&store, &ada.id.to_string()), vec!["friend"]);
+    assert_eq!(tag_names(&store, &grace.id.to_string()), vec!["friend"]);
+    assert!(tags.get_by_name(&tag("friends")).expect("get").is_none());
+}
+
+#[test]
+fn merge_into_and_delete_cover_every_contact() {
+    let store = Store::open_in_memory().expect("open in memory");
+    store.migrate().expect("migrate");
+    let now = 1_700_000_000;
+    let ada = store
+        .contacts()
+        .create(now, new_contact("Ada"))
+        .expect("ada");
+    let grace = store
+        .contacts()
+        .create(now, new_contact("Grace"))
+        .expect("grace");
+    let tags = store.tags();
+    tags.add_tag_to_contact(&ada.id.to_string(), tag("pals"))
+        .expect("tag ada");
+    tags.add_tag_to_contact(&grace.id.to_string(), tag("mates"))
+        .expect("tag grace");
+
+    let affected = tags
+        .merge_into(&[tag("pals"), tag("mates")], tag("close"))
+        .expect("merge");
+    assert_eq!(affected, 2);
+    let listed = tags.list_with_counts().expect("list");
+    assert_eq!(listed.len(), 1);
+    assert_eq!(listed[0].0.name.as_str(), "close");
+    assert_eq!(listed[0].1, 2);
+
+    assert_eq!(tags.delete(&tag("close")).expect("delete"), 2);
+    assert!(tags.list_with_counts().expect("list").is_empty());
+    assert!(tag_names(&store, &ada.id.to_string()).is_empty());
+}
+
+#[test]
+fn counts_can_skip_archived_contacts_and_unused_tags_are_pruned() {
+    let store = Store::open_in_memory().expect("open in memory");
+    store.migrate().expect("migrate");
+    let now = 1_700_000_000;
+    let contacts = store.contacts();
+    let ada = contacts.create(now, new_contact("Ada")).expect("ada");
+    let grace = contacts.create(now, new_contact("Grace")).expect("grace");
+    let linus = contacts.create(now, new_contact("Linus")).expect("linus");
+    let tags = store.tags();
+    for (contact, name) in [
+        (&ada, "friends"),
+        (&grace, "friends"),
+        (&grace, "alumni"),
+        (&linus, "kernel"),
+    ] {
+        tags.add_tag_to_contact(&contact.id.to_string(), tag(name))
+            .expect("tag contact");
+    }
+    tags.upsert(tag("stale")).expect("upsert");
+    contacts.archive(now, grace.id).expect("archive grace");
+    contacts.trash(now, linus.id).expect("trash linus");
+
+    let counts = |listed: Vec<(Tag, i64)>| {
+        listed
+            .into_iter()
+            .map(|(tag, count)| (tag.name.as_str().to_string(), count))
+            .collect::<Vec<_>>()
+    };
+    let all = |pairs: &[(&str, i64)]| {
+        pairs
+            .iter()
+            .map(|(name, count)| (name.to_string(), *count))
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(
+        counts(tags.list_with_counts().expect("list")),
+        all(&[("alumni", 1), ("friends", 2), ("kernel", 0), ("stale", 0)])
+    );
+    assert_eq!(
+        counts(tags.list_with_active_counts().expect("list active")),
+        all(&[("alumni", 0), ("friends", 1), ("kernel", 0), ("stale", 0)])
+    );
+
+    // Only tags with no contact at all go; archived and trashed contacts keep theirs.
+    let unused: Vec<String> = tags
+        .list_unused()
+        .expect("unused")
+        .into_iter()
+        .map(|tag| tag.name.as_str().to_string())
+        .collect();
+    assert_eq!(unused, vec!["stale"]);
+    assert_eq!(tags.delete_unused().expect("prune"), vec!["stale"]);
+    assert!(tags.delete_unused().expect("prune again").is_empty());
+    assert_eq!(tag_names(&store, &linus.id.to_string()), vec!["kernel"]);
+    assert_eq!(tags.list_with_counts().expect("list").len(), 3);
+}
@@ -1,3 +1,4 @@
+use knotter_core::time::Clock;
 use knotter_store::repo::{
     ContactNew, TelegramAccountNew, TelegramAccountsRepo, TelegramMessageRecord, TelegramSyncRepo,
     TelegramSyncState,
@@ -23,6 +24,7 @@ fn telegram_accounts_upsert_and_lookup() {
                 next_touchpoint_at: None,
                 cadence_days: None,
                 archived_at: None,
+                organization: None,
             },
         )
         .expect("create contact");
@@ -81,6 +83,7 @@ fn telegram_sync_records_messages_and_state() {
                 next_touchpoint_at: None,
                 cadence_days: None,
                 archived_at: None,
+                organization: None,
             },
         )
         .expect("create contact");
@@ -127,4 +130,108 @@ fn telegram_sync_records_messages_and_state() {
         .expect("load state")
         .expect("state exists");
     assert_eq!(loaded.last_message_id, 100);
+
+    repo.upsert_state(&TelegramSyncState {
+        peer_id: 7,
+        ..state.clone()
+    })
+    .expect("upsert state");
+    let states = repo.list_states().expect("list states");
+    assert_eq!(
+        states.iter().map(|s| s.peer_id).collect::<Vec<_>>(),
+        vec![7, 42]
+    );
+    assert_eq!(repo.reset_state("primary", Some(42)).expect("reset"), 1);
+    let states = repo.list_states().expect("list states");
+    assert_eq!(states[0].last_message_id, 100);
+    assert_eq!(states[1].last_message_id, 0);
+    assert_eq!(states[1].last_seen_at, None);
+    assert_eq!(repo.reset_state("primary", None).expect("reset"), 2);
+    assert_eq!(repo.reset_state("other", None).expect("reset"), 0);
+}
+
+#[test]
+fn last_touch_context_reports_latest_message_per_channel() {
+    use knotter_core::domain::InteractionDirection;
+    use knotter_core::dto::TouchChannel;
+    use knotter_store::repo::EmailMessageRecord;
+
+    let store = Store::open_in_memory().expect("open in memory");
+    store.migrate().expect("migrate");
+
+    let now = 1_700_000_000;
+    let contact = store
+        .contacts()
+        .create(
+            now,
+            ContactNew {
+                display_name: "Ada Lovelace".to_string(),
+                email: None,
+                phone: None,
+                handle: None,
+                timezone: None,
+                next_touchpoint_at: None,
+                cadence_days: None,
+                archived_at: None,
+                organization: None,
+            },
+        )
+        .expect("create contact");
+    assert!(store
+        .last_touch()
+        .list_for_contact(contact.id)
+        .expect("empty")
+        .is_empty());
+
+    let telegram = store.telegram_sync();
+    for (message_id, occurred_at, direction, snippet) in [
+        (1, now - 300, "outbound", "lunch?"),
+        (2, now - 100, "inbound", " see you thursday! "),
+    ] {
+        telegram
+            .record_message(&TelegramMessageRecord {
+                account: "primary".to_string(),
+                peer_id: 42,
+                message_id,
+                contact_id: contact.id,
+                occurred_at,
+                direction: direction.to_string(),
+                snippet: Some(snippet.to_string()),
+                created_at: now,
+            })
+            .expect("record telegram");
+    }
+    store
+        .email_sync()
+        .record_message(&EmailMessageRecord {
+            account: "work".to_string(),
+            mailbox: "INBOX".to_string(),
+            uidvalidity: 1,
+            uid: 7,
+            message_id: Some("<a@example.com>".to_string()),
+            contact_id: contact.id,
+            occurred_at: now - 200,
+            direction: "outbound".to_string(),
+            subject: Some("Slides".to_string()),
+            created_at: now,
+        })
+        .expect("record email");
+
+    let touches = store
+        .last_touch()
+        .list_for_contact(contact.id)
+        .expect("last touch");
+    assert_eq!(touches.len(), 2);
+    assert_eq!(touches[0].channel, TouchChannel::Telegram);
+    assert_eq!(touches[0].occurred_at, now - 100);
+    assert_eq!(touches[0].direction, Some(InteractionDirection::Inbound));
+    assert_eq!(touches[0].text.as_deref(), Some("see you thursday!"));
+    assert!(touches[0]
+        .summary(&Clock::default())
+        .starts_with("← 'see you thursday!' ("));
+    assert_eq!(touches[1].channel, TouchChannel::Email);
+    assert_eq!(touches[1].text.as_deref(), Some("Slides"));
+    assert!(touches[1]
+        .summary(&Clock::default())
+        .starts_with("→ 'Slides' ("));
 }
@@ -6,6 +6,8 @@ pub struct EmailAccount {
     pub password: String,
     pub tls: EmailTls,
     pub mailboxes: Vec<String>,
+    /// Request Gmail `X-GM-LABELS` when the server advertises `X-GM-EXT-1`.
+    pub gmail_labels: bool,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -29,7 +31,11 @@ pub struct EmailHeader {
     pub occurred_at: i64,
     pub from: Vec<EmailAddress>,
     pub to: Vec<EmailAddress>,
+    /// Cc addresses, kept apart from `to`.
+    pub cc: Vec<EmailAddress>,
     pub subject: Option<String>,
+    /// Gmail labels; empty unless the account requested them and the server supports them.
+    pub labels: Vec<String>,
 }
 
 #[derive(Debug, Clone)]
@@ -40,19 +46,126 @@ pub struct MailboxSyncResult {
     pub headers: Vec<EmailHeader>,
 }
 
+/// Mailboxes an account imports once wildcard entries in its `mailboxes`
+/// list are expanded against the server's mailbox list.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MailboxSelection {
+    /// Explicit mailboxes followed by wildcard matches, in configured order.
+    pub mailboxes: Vec<String>,
+    /// Server mailboxes a wildcard matched and that were kept.
+    pub expanded: Vec<String>,
+    /// Server mailboxes a wildcard matched but an exclude pattern dropped.
+    pub excluded: Vec<String>,
+}
+
+/// Whether any configured mailbox is a wildcard pattern that needs a
+/// server `LIST` to resolve.
+pub fn has_mailbox_wildcard(mailboxes: &[String]) -> bool {
+    mailboxes.iter().any(|mailbox| mailbox.contains('*'))
+}
+
+/// Case-insensitive glob match where `*` matches any run of characters
+/// (including `/`); everything else must match literally.
+pub fn mailbox_matches(pattern: &str, mailbox: &str) -> bool {
+    let pattern = pattern.to_lowercase();
+    let mailbox = mailbox.to_lowercase();
+    let mut parts = pattern.split('*');
+    let first = parts.next().unwrap_or_default();
+    let Some(mut rest) = mailbox.strip_prefix(first) else {
+        return false;
+    };
+    let parts: Vec<&str> = parts.collect();
+    let Some((last, middle)) = parts.split_last() else {
+        // No `*`: the whole name must match.
+        return rest.is_empty();
+    };
+    for part in middle {
+        match rest.find(part) {
+            Some(index) => rest = &rest[index + part.len()..],
+            None => return false,
+        }
+    }
+    rest.ends_with(last)
+}
+
+/// Expands wildcard entries in `configured` against `available` (the server's
+/// mailboxes) and drops wildcard matches that hit an `exclude` pattern.
+/// Explicit entries are kept as configured, even when an exclude matches them.
+pub fn select_mailboxes(
+    configured: &[String],
+    available: &[String],
+    exclude: &[String],
+) -> MailboxSelection {
+    let mut selection = MailboxSelection::default();
+    let contains = |list: &[String], name: &str| {
+        list.iter()
+            .any(|existing| existing.eq_ignore_ascii_case(name))
+    };
+    for entry in configured {
+        if !entry.contains('*') {
+            if !contains(&selection.mailboxes, entry) {
+                selection.mailboxes.push(entry.clone());
+            }
+            continue;
+        }
+        for mailbox in available {
+            if !mailbox_matches(entry, mailbox)
+                || contains(&selection.mailboxes, mailbox)
+                || contains(&selection.excluded, mailbox)
+            {
+                continue;
+            }
+            if exclude
+                .iter()
+                .any(|pattern| mailbox_matches(pattern, mailbox))
+            {
+                selection.excluded.push(mailbox.clone());
+            } else {
+                selection.mailboxes.push(mailbox.clone());
+                selection.expanded.push(mailbox.clone());
+            }
+        }
+    }
+    selection
+}
+
 #[cfg(feature = "email-sync")]
 mod imp {
     use super::{EmailAccount, EmailAddress, EmailHeader, EmailTls, MailboxSyncResult};
     use crate::error::{Result, SyncError};
     use mailparse::{addrparse, dateparse, MailHeaderMap};
 
+    const HEADER_QUERY: &str = "BODY.PEEK[HEADER.FIELDS (DATE FROM TO CC SUBJECT MESSAGE-ID)]";
+    const LABELS_QUERY: &str =
+        "(X-GM-LABELS BODY.PEEK[HEADER.FIELDS (DATE FROM TO CC SUBJECT MESSAGE-ID)])";
+    const GMAIL_CAPABILITY: &str = "X-GM-EXT-1";
+    /// UIDs per `UID FETCH`, so a large first sync reports progress as it goes.
+    const FETCH_CHUNK: usize = 500;
+
     pub fn fetch_mailbox_headers(
         account: &EmailAccount,
         mailbox: &str,
         last_uid: i64,
         limit: Option<usize>,
+    ) -> Result<MailboxSyncResult> {
+        fetch_mailbox_headers_with_progress(account, mailbox, last_uid, limit, &mut |_, _| {})
+    }
+
+    /// Like [`fetch_mailbox_headers`], calling `on_progress(fetched, total)` as
+    /// header batches arrive.
+    pub fn fetch_mailbox_headers_with_progress(
+        account: &EmailAccount,
+        mailbox: &str,
+        last_uid: i64,
+        limit: Option<usize>,
+        on_progress: &mut dyn FnMut(usize, usize),
     ) -> Result<MailboxSyncResult> {
         let mut session = connect(account)?;
+        let mut with_labels = account.gmail_labels
+            && session
+                .capabilities()
+                .map(|caps| caps.has_str(GMAIL_CAPABILITY))
+                .unwrap_or(false);
         let mailbox_info = session
             .select(mailbox)
             .map_err(|err| SyncError::Command(err.to_string()))?;
@@ -72,19 +185,25 @@ mod imp {
         }
         let mut headers = Vec::new();
         let mut max_uid = last_uid;
+        on_progress(0, uids.len());
 
-        if !uids.is_empty() {
-            let sequence = uids
+        for (chunk_index, chunk) in uids.chunks(FETCH_CHUNK).enumerate() {
+            let sequence = chunk
                 .iter()
                 .map(|uid| uid.to_string())
                 .collect::<Vec<_>>()
                 .join(",");
-            let fetches = session
-                .uid_fetch(
-                    sequence,
-                    "BODY.PEEK[HEADER.FIELDS (DATE FROM TO CC SUBJECT MESSAGE-ID)]",
-                )
-                .map_err(|err| SyncError::Command(err.to_string()))?;
+            // Servers that reject X-GM-LABELS still get a plain header fetch,
+            // and later chunks skip straight to it.
+            let fetches = if with_labels {
+                session.uid_fetch(&sequence, LABELS_QUERY).or_else(|_| {
+                    with_labels = false;
+                    session.uid_fetch(&sequence, HEADER_QUERY)
+                })
+            } else {
+                session.uid_fetch(&sequence, HEADER_QUERY)
+            }
+            .map_err(|err| SyncError::Command(err.to_string()))?;
             for fetch in fetches.iter() {
                 let uid = fetch.uid.unwrap_or_default();
                 max_uid = max_uid.max(uid as i64);
@@ -97,16 +216,17 @@ mod imp {
                 let message_id = normalize_message_id(parsed_headers.get_first_value("Message-ID"));
                 let subject = parsed_headers.get_first_value("Subject");
                 let from = parse_addresses(parsed_headers.get_first_value("From").as_deref());
-                let mut to = parse_addresses(parsed_headers.get_first_value("To").as_deref());
+                let to = parse_addresses(parsed_headers.get_first_value("To").as_deref());
                 let cc = parse_addresses(parsed_headers.get_first_value("Cc").as_deref());
-                if !cc.is_empty() {
-                    to.extend(cc);
-                }
                 let occurred_at = parsed_headers
                     .get_first_value("Date")
                     .as_deref()
                     .and_then(|value| dateparse(value).ok())
                     .unwrap_or_else(|| chrono::Utc::now().timestamp());
+                let labels = fetch
+                    .gmail_labels()
+                    .map(|labels| labels.map(|label| label.to_string()).collect())
+                    .unwrap_or_default();
 
                 headers.push(EmailHeader {
                     mailbox: mailbox.to_string(),
@@ -115,9 +235,13 @@ mod imp {
                     occurred_at,
                     from,
                     to,
+                    cc,
                     subject,
+                    labels,
                 });
             }
+            let fetched = (chunk_index * FETCH_CHUNK + chunk.len()).min(uids.len());
+            on_progress(fetched, uids.len());
         }
 
         session
@@ -132,6 +256,28 @@ mod imp {
         })
     }
 
+    pub fn list_mailboxes(account: &EmailAccount) -> Result<Vec<String>> {
+        let mut session = connect(account)?;
+        let names = session
+            .list(None, Some("*"))
+            .map_err(|err| SyncError::Command(err.to_string()))?;
+        // `imap` does not re-export `NameAttribute`, so match on its name.
+        let mailboxes = names
+            .iter()
+            .filter(|name| {
+                !name
+                    .attributes()
+                    .iter()
+                    .any(|attribute| format!("{attribute:?}") == "NoSelect")
+            })
+            .map(|name| name.name().to_string())
+            .collect();
+        session
+            .logout()
+            .map_err(|err| SyncError::Command(err.to_string()))?;
+        Ok(mailboxes)
+    }
+
     fn connect(account: &EmailAccount) -> Result<imap::Session<imap::Connection>> {
         let mode = match account.tls {
             EmailTls::Tls => imap::ConnectionMode::Tls,
@@ -196,7 +342,7 @@ mod imp {
 }
 
 #[cfg(feature = "email-sync")]
-pub use imp::fetch_mailbox_headers;
+pub use imp::{fetch_mailbox_headers, fetch_mailbox_headers_with_progress, list_mailboxes};
 
 #[cfg(not(feature = "email-sync"))]
 pub fn fetch_mailbox_headers(
@@ -209,3 +355,90 @@ pub fn fetch_mailbox_headers(
         "email sync requires the email-sync feature".to_string(),
     ))
 }
+
+/// Like [`fetch_mailbox_headers`], calling `on_progress(fetched, total)` as
+/// header batches arrive.
+#[cfg(not(feature = "email-sync"))]
+pub fn fetch_mailbox_headers_with_progress(
+    _account: &EmailAccount,
+    _mailbox: &str,
+    _last_uid: i64,
+    _limit: Option<usize>,
+    _on_progress: &mut dyn FnMut(usize, usize),
+) -> crate::error::Result<MailboxSyncResult> {
+    Err(crate::error::SyncError::Unavailable(
+        "email sync requires the email-sync feature".to_string(),
+    ))
+}
+
+/// Names of every selectable mailbox on the server.
+#[cfg(not(feature = "email-sync"))]
+pub fn list_mailboxes(_account: &EmailAccount) -> crate::error::Result<Vec<String>> {
+    Err(crate::error::SyncError::Unavailable(
+        "email sync requires the email-sync feature".to_string(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(values: &[&str]) -> Vec<String> {
+        values.iter().map(|value| value.to_string()).collect()
+    }
+
+    #[test]
+    fn mailbox_matches_prefix_suffix_and_literal_patterns() {
+        assert!(mailbox_matches("*", "INBOX"));
+        assert!(mailbox_matches("lists/*", "Lists/Rust"));
+        assert!(mailbox_matches("lists/*", "Lists/Rust/Announce"));
+        assert!(!mailbox_matches("lists/*", "Lists"));
+        assert!(mailbox_matches("*/archive", "Work/Archive"));
+        assert!(mailbox_matches("*news*", "Updates/Newsletters"));
+        assert!(mailbox_matches("spam", "SPAM"));
+        assert!(!mailbox_matches("spam", "Spam/Old"));
+        assert!(mailbox_matches("a*b*c", "axxbyyc"));
+        assert!(!mailbox_matches("a*b*c", "axxcyyb"));
+    }
+
+    #[test]
+    fn select_mailboxes_expands_wildcards_and_applies_excludes() {
+        let available = names(&[
+            "INBOX",
+            "Sent",
+            "Spam",
+            "Trash",
+            "Lists/Rust",
+            "Lists/News",
+            "Work",
+        ]);
+        let selection = select_mailboxes(
+            &names(&["Trash", "*"]),
+            &available,
+            &names(&["spam", "TRASH", "lists/*"]),
+        );
+        assert_eq!(
+            selection.mailboxes,
+            names(&["Trash", "INBOX", "Sent", "Work"])
+        );
+        assert_eq!(selection.expanded, names(&["INBOX", "Sent", "Work"]));
+        assert_eq!(
+            selection.excluded,
+            names(&["Spam", "Lists/Rust", "Lists/News"])
+        );
+    }
+
+    #[test]
+    fn select_mailboxes_keeps_explicit_lists_untouched() {
+        let selection = select_mailboxes(
+            &names(&["INBOX", "[Gmail]/Sent Mail"]),
+            &[],
+            &names(&["inbox"]),
+        );
+        assert_eq!(selection.mailboxes, names(&["INBOX", "[Gmail]/Sent Mail"]));
+        assert!(selection.expanded.is_empty());
+        assert!(selection.excluded.is_empty());
+        assert!(!has_mailbox_wildcard(&selection.mailboxes));
+        assert!(has_mailbox_wildcard(&names(&["INBOX", "Lists/*"])));
+    }
+}
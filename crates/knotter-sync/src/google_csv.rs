@@ -0,0 +1,206 @@
+//! Google Contacts CSV exports (Takeout and contacts.google.com "Google CSV").
+//! Rows become the same [`VcfContact`]s a vCard import produces, so both go
+//! through one import pipeline.
+
+use crate::vcf::{normalize_date_year, parse_vcard_date, ContactDateInput, ParsedVcf, VcfContact};
+use crate::{Result, SyncError};
+use knotter_core::domain::{ContactDateKind, TagName};
+
+/// Separator Google uses for several values in one cell.
+const MULTI_VALUE_SEPARATOR: &str = ":::";
+
+/// Group every Google contact belongs to; never a useful tag.
+const MY_CONTACTS_GROUP: &str = "* myContacts";
+
+/// Older Takeout exports use the first spelling, newer ones the second.
+const GIVEN_NAME_COLUMNS: [&str; 2] = ["Given Name", "First Name"];
+const MIDDLE_NAME_COLUMNS: [&str; 2] = ["Additional Name", "Middle Name"];
+const FAMILY_NAME_COLUMNS: [&str; 2] = ["Family Name", "Last Name"];
+const ORGANIZATION_COLUMNS: [&str; 2] = ["Organization 1 - Name", "Organization Name"];
+const GROUP_COLUMNS: [&str; 2] = ["Group Membership", "Labels"];
+
+struct Columns {
+    headers: Vec<String>,
+}
+
+impl Columns {
+    fn index(&self, names: &[&str]) -> Option<usize> {
+        names
+            .iter()
+            .find_map(|name| self.headers.iter().position(|header| header == name))
+    }
+
+    /// Indexes of numbered columns such as `E-mail 2 - Value`, in file order.
+    fn numbered(&self, prefix: &str, suffix: &str) -> Vec<usize> {
+        self.headers
+            .iter()
+            .enumerate()
+            .filter(|(_, header)| {
+                header
+                    .strip_prefix(prefix)
+                    .and_then(|rest| rest.strip_suffix(suffix))
+                    .is_some_and(|number| {
+                        !number.is_empty() && number.chars().all(|ch| ch.is_ascii_digit())
+                    })
+            })
+            .map(|(index, _)| index)
+            .collect()
+    }
+}
+
+/// Parses a Google contacts CSV. With `groups_as_tags`, group memberships
+/// (minus `* myContacts`) become group tags; blank rows are ignored.
+pub fn parse_google_csv(data: &str, groups_as_tags: bool) -> Result<ParsedVcf> {
+    let data = data.strip_prefix('\u{feff}').unwrap_or(data);
+    let mut reader = csv::ReaderBuilder::new()
+        .flexible(true)
+        .from_reader(data.as_bytes());
+    let columns = Columns {
+        headers: reader
+            .headers()
+            .map_err(|err| SyncError::Parse(format!("invalid CSV header: {err}")))?
+            .iter()
+            .map(|header| header.trim().to_string())
+            .collect(),
+    };
+    let name = columns.index(&["Name"]);
+    let given = columns.index(&GIVEN_NAME_COLUMNS);
+    let middle = columns.index(&MIDDLE_NAME_COLUMNS);
+    let family = columns.index(&FAMILY_NAME_COLUMNS);
+    if name.is_none() && given.is_none() && family.is_none() {
+        return Err(SyncError::Parse(
+            "not a Google contacts CSV: no Name, Given Name, or First Name column".to_string(),
+        ));
+    }
+    let nickname = columns.index(&["Nickname"]);
+    let organization = columns.index(&ORGANIZATION_COLUMNS);
+    let birthday = columns.index(&["Birthday"]);
+    let notes = columns.index(&["Notes"]);
+    let groups = columns.index(&GROUP_COLUMNS);
+    let emails = columns.numbered("E-mail ", " - Value");
+    let phones = columns.numbered("Phone ", " - Value");
+
+    let mut contacts = Vec::new();
+    let mut warnings = Vec::new();
+    let mut skipped = 0;
+    for (index, record) in reader.records().enumerate() {
+        // Spreadsheet row numbers: the header is row 1.
+        let row = index + 2;
+        let record = record.map_err(|err| SyncError::Parse(format!("row {row}: {err}")))?;
+        if record.iter().all(|field| field.trim().is_empty()) {
+            continue;
+        }
+        let cell = |index: Option<usize>| {
+            index
+                .and_then(|index| record.get(index))
+                .map(str::trim)
+                .filter(|value| !value.is_empty())
+        };
+        let values = |indexes: &[usize]| -> Vec<String> {
+            indexes
+                .iter()
+                .filter_map(|index| cell(Some(*index)))
+                .flat_map(split_values)
+                .collect()
+        };
+
+        let organization = cell(organization).map(str::to_string);
+        let composed = [given, middle, family]
+            .into_iter()
+            .filter_map(cell)
+            .collect::<Vec<_>>()
+            .join(" ");
+        let display_name = cell(name)
+            .map(str::to_string)
+            .or_else(|| (!composed.is_empty()).then_some(composed))
+            .or_else(|| organization.clone());
+        let Some(display_name) = display_name else {
+            warnings.push(format!("row {row}: missing name; skipping"));
+            skipped += 1;
+            continue;
+        };
+
+        let mut emails_seen: Vec<String> = Vec::new();
+        for email in values(&emails) {
+            if !emails_seen
+                .iter()
+                .any(|seen| seen.eq_ignore_ascii_case(&email))
+            {
+                emails_seen.push(email);
+            }
+        }
+
+        let mut dates = Vec::new();
+        if let Some(raw) = cell(birthday) {
+            match parse_vcard_date(raw) {
+                Ok((month, day, year)) => dates.push(ContactDateInput {
+                    kind: ContactDateKind::Birthday,
+                    label: None,
+                    month,
+                    day,
+                    year: normalize_date_year(year, &mut warnings, "Birthday"),
+                }),
+                Err(message) => {
+                    warnings.push(format!("row {row}: invalid Birthday: {message}"));
+                }
+            }
+        }
+
+        let mut aliases: Vec<String> = Vec::new();
+        for alias in cell(nickname).map(split_values).unwrap_or_default() {
+            if !alias.eq_ignore_ascii_case(&display_name)
+                && !aliases.iter().any(|seen| seen.eq_ignore_ascii_case(&alias))
+            {
+                aliases.push(alias);
+            }
+        }
+
+        let mut group_tags: Vec<TagName> = Vec::new();
+        if groups_as_tags {
+            for raw in cell(groups).map(split_values).unwrap_or_default() {
+                if raw == MY_CONTACTS_GROUP {
+                    continue;
+                }
+                // Other system groups ("* starred") keep their name.
+                let group = raw.strip_prefix("* ").unwrap_or(&raw);
+                match TagName::new(group) {
+                    Ok(tag) if !group_tags.contains(&tag) => group_tags.push(tag),
+                    Ok(_) => {}
+                    Err(_) => warnings.push(format!(
+                        "invalid group tag for {display_name}: {raw}; skipping"
+                    )),
+                }
+            }
+            group_tags.sort_by(|a, b| a.as_str().cmp(b.as_str()));
+        }
+
+        contacts.push(VcfContact {
+            display_name,
+            emails: emails_seen,
+            phone: values(&phones).into_iter().next(),
+            tags: Vec::new(),
+            next_touchpoint_at: None,
+            cadence_days: None,
+            dates,
+            external_id: None,
+            group_tags,
+            note: cell(notes).map(str::to_string),
+            aliases,
+            organization,
+        });
+    }
+
+    Ok(ParsedVcf {
+        contacts,
+        warnings,
+        skipped,
+    })
+}
+
+fn split_values(cell: &str) -> Vec<String> {
+    cell.split(MULTI_VALUE_SEPARATOR)
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .map(str::to_string)
+        .collect()
+}
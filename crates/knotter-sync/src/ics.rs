@@ -1,12 +1,20 @@
 use crate::error::{Result, SyncError};
-use chrono::{DateTime, Utc};
-use knotter_core::domain::{Contact, ContactId};
+use chrono::{DateTime, Days, NaiveDate, NaiveTime, Utc};
+use knotter_core::domain::{Contact, ContactDate, ContactDateKind, ContactId};
+use knotter_core::rules::days_until_next_occurrence;
 use std::collections::HashMap;
 
+/// Upper bound on projected events per contact when expanding cadences.
+pub const MAX_OCCURRENCES_PER_CONTACT: usize = 26;
+
 #[derive(Debug, Clone, Copy)]
 pub struct IcsExportOptions {
     pub now_utc: i64,
     pub window_days: Option<i64>,
+    /// Project repeated touchpoints every `cadence_days` after the next one.
+    pub expand_cadence: bool,
+    /// Attach a display alarm this many seconds before each event.
+    pub alarm_seconds: Option<i64>,
 }
 
 #[derive(Debug, Clone)]
@@ -15,22 +23,14 @@ pub struct IcsExport {
     pub count: usize,
 }
 
+/// Touchpoint events for active contacts plus a yearly all-day event for every
+/// birthday in `dates`; archived contacts only contribute their birthdays.
 pub fn export_ics(
     contacts: &[Contact],
     tags: &HashMap<ContactId, Vec<String>>,
+    dates: &HashMap<ContactId, Vec<ContactDate>>,
     options: IcsExportOptions,
 ) -> Result<IcsExport> {
-    let mut events: Vec<&Contact> = contacts
-        .iter()
-        .filter(|contact| contact.next_touchpoint_at.is_some())
-        .collect();
-    events.sort_by_key(|contact| {
-        (
-            contact.next_touchpoint_at.unwrap_or(i64::MAX),
-            contact.display_name.to_ascii_lowercase(),
-        )
-    });
-
     let window_end = if let Some(days) = options.window_days {
         let seconds = days
             .checked_mul(86_400)
@@ -44,9 +44,76 @@ pub fn export_ics(
     } else {
         None
     };
+    let today = DateTime::<Utc>::from_timestamp(options.now_utc, 0)
+        .ok_or_else(|| SyncError::Parse(format!("invalid timestamp: {}", options.now_utc)))?
+        .date_naive();
+
+    let mut events = Vec::new();
+    for contact in contacts {
+        for date in dates.get(&contact.id).into_iter().flatten() {
+            if date.kind != ContactDateKind::Birthday {
+                continue;
+            }
+            let Some(day) = next_occurrence(today, date.month, date.day) else {
+                continue;
+            };
+            let at = day.and_time(NaiveTime::MIN).and_utc().timestamp();
+            if window_end.is_some_and(|end| at > end) {
+                continue;
+            }
+            events.push(Occurrence {
+                contact,
+                kind: OccurrenceKind::Birthday(date),
+                at,
+            });
+        }
+
+        if contact.archived_at.is_some() {
+            continue;
+        }
+        let Some(next_touchpoint_at) = contact.next_touchpoint_at else {
+            continue;
+        };
+        let step = match contact.cadence_days {
+            Some(days) if options.expand_cadence && days > 0 => i64::from(days) * 86_400,
+            _ => 0,
+        };
+        let mut emitted = 0usize;
+        for index in 0.. {
+            let Some(at) = (index as i64)
+                .checked_mul(step)
+                .and_then(|offset| next_touchpoint_at.checked_add(offset))
+            else {
+                break;
+            };
+            if window_end.is_some_and(|end| at > end) {
+                break;
+            }
+            if window_end.is_none() || at >= options.now_utc {
+                events.push(Occurrence {
+                    contact,
+                    kind: OccurrenceKind::Touchpoint(index),
+                    at,
+                });
+                emitted += 1;
+            }
+            if step == 0 || emitted >= MAX_OCCURRENCES_PER_CONTACT {
+                break;
+            }
+        }
+    }
+    events.sort_by_key(|event| {
+        (
+            event.at,
+            event.contact.display_name.to_ascii_lowercase(),
+            match event.kind {
+                OccurrenceKind::Birthday(_) => 0,
+                OccurrenceKind::Touchpoint(index) => index + 1,
+            },
+        )
+    });
 
     let mut out = String::new();
-    let mut count = 0usize;
     out.push_str("BEGIN:VCALENDAR\r\n");
     out.push_str("VERSION:2.0\r\n");
     out.push_str("PRODID:-//knotter//EN\r\n");
@@ -54,26 +121,34 @@ pub fn export_ics(
 
     let dtstamp = format_ics_timestamp(options.now_utc)?;
 
-    for contact in events {
-        let Some(next_touchpoint_at) = contact.next_touchpoint_at else {
-            continue;
-        };
-
-        if let Some(end) = window_end {
-            if next_touchpoint_at < options.now_utc || next_touchpoint_at > end {
-                continue;
-            }
-        }
-
-        let dtstart = format_ics_timestamp(next_touchpoint_at)?;
+    for event in &events {
+        let contact = event.contact;
         out.push_str("BEGIN:VEVENT\r\n");
-        out.push_str(&format!("UID:{}\r\n", uid_for_contact(&contact.id)));
-        out.push_str(&format!("DTSTAMP:{}\r\n", dtstamp));
-        out.push_str(&format!("DTSTART:{}\r\n", dtstart));
-        out.push_str(&format!(
-            "SUMMARY:{}\r\n",
-            escape_ics_value(&format!("Reach out to {}", contact.display_name))
-        ));
+        let summary = match event.kind {
+            OccurrenceKind::Touchpoint(index) => {
+                out.push_str(&format!(
+                    "UID:{}\r\n",
+                    uid_for_occurrence(&contact.id, index)
+                ));
+                out.push_str(&format!("DTSTAMP:{}\r\n", dtstamp));
+                out.push_str(&format!("DTSTART:{}\r\n", format_ics_timestamp(event.at)?));
+                format!("Reach out to {}", contact.display_name)
+            }
+            OccurrenceKind::Birthday(date) => {
+                out.push_str(&format!("UID:knotter-date-{}@knotter.local\r\n", date.id));
+                out.push_str(&format!("DTSTAMP:{}\r\n", dtstamp));
+                out.push_str(&format!(
+                    "DTSTART;VALUE=DATE:{}\r\n",
+                    format_ics_date(event.at)?
+                ));
+                out.push_str("RRULE:FREQ=YEARLY\r\n");
+                match &date.label {
+                    Some(label) => format!("{}'s birthday ({label})", contact.display_name),
+                    None => format!("{}'s birthday", contact.display_name),
+                }
+            }
+        };
+        out.push_str(&format!("SUMMARY:{}\r\n", escape_ics_value(&summary)));
 
         let description = build_description(contact, tags);
         if !description.is_empty() {
@@ -83,12 +158,40 @@ pub fn export_ics(
             ));
         }
 
+        if let Some(seconds) = options.alarm_seconds {
+            out.push_str("BEGIN:VALARM\r\n");
+            out.push_str("ACTION:DISPLAY\r\n");
+            out.push_str(&format!("DESCRIPTION:{}\r\n", escape_ics_value(&summary)));
+            out.push_str(&format!("TRIGGER:-{}\r\n", format_ics_duration(seconds)));
+            out.push_str("END:VALARM\r\n");
+        }
+
         out.push_str("END:VEVENT\r\n");
-        count += 1;
     }
 
     out.push_str("END:VCALENDAR\r\n");
-    Ok(IcsExport { data: out, count })
+    Ok(IcsExport {
+        data: out,
+        count: events.len(),
+    })
+}
+
+struct Occurrence<'a> {
+    contact: &'a Contact,
+    kind: OccurrenceKind<'a>,
+    at: i64,
+}
+
+enum OccurrenceKind<'a> {
+    /// Cadence steps after `next_touchpoint_at`; 0 is the scheduled touchpoint itself.
+    Touchpoint(usize),
+    /// Starts on the next occurrence (midnight UTC) and repeats yearly.
+    Birthday(&'a ContactDate),
+}
+
+fn next_occurrence(today: NaiveDate, month: u8, day: u8) -> Option<NaiveDate> {
+    let days = days_until_next_occurrence(today, month, day)?;
+    today.checked_add_days(Days::new(u64::try_from(days).ok()?))
 }
 
 fn build_description(contact: &Contact, tags: &HashMap<ContactId, Vec<String>>) -> String {
@@ -109,10 +212,51 @@ fn format_ics_timestamp(ts: i64) -> Result<String> {
     Ok(dt.format("%Y%m%dT%H%M%SZ").to_string())
 }
 
+fn format_ics_date(ts: i64) -> Result<String> {
+    let dt = DateTime::<Utc>::from_timestamp(ts, 0)
+        .ok_or_else(|| SyncError::Parse(format!("invalid timestamp: {ts}")))?;
+    Ok(dt.format("%Y%m%d").to_string())
+}
+
+/// RFC 5545 duration value such as `P1D`, `PT9H`, or `P1DT2H30M`.
+fn format_ics_duration(seconds: i64) -> String {
+    let days = seconds / 86_400;
+    let hours = seconds % 86_400 / 3_600;
+    let minutes = seconds % 3_600 / 60;
+    let secs = seconds % 60;
+    let mut out = String::from("P");
+    if days > 0 {
+        out.push_str(&format!("{days}D"));
+    }
+    if hours > 0 || minutes > 0 || secs > 0 || days == 0 {
+        out.push('T');
+        if hours > 0 {
+            out.push_str(&format!("{hours}H"));
+        }
+        if minutes > 0 {
+            out.push_str(&format!("{minutes}M"));
+        }
+        if secs > 0 || (hours == 0 && minutes == 0) {
+            out.push_str(&format!("{secs}S"));
+        }
+    }
+    out
+}
+
 fn uid_for_contact(id: &ContactId) -> String {
     format!("knotter-{}@knotter.local", id)
 }
 
+/// The first occurrence keeps the plain contact UID so expanding does not
+/// duplicate events already imported from a non-expanded export.
+fn uid_for_occurrence(id: &ContactId, index: usize) -> String {
+    if index == 0 {
+        uid_for_contact(id)
+    } else {
+        format!("knotter-{}-{}@knotter.local", id, index)
+    }
+}
+
 fn escape_ics_value(value: &str) -> String {
     let mut out = String::with_capacity(value.len());
     for ch in value.chars() {
@@ -131,7 +275,7 @@ fn escape_ics_value(value: &str) -> String {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use knotter_core::domain::{Contact, ContactId};
+    use knotter_core::domain::{Contact, ContactDateId, ContactId};
     use std::str::FromStr;
 
     fn contact_with_id(id: &str, name: &str, next_touchpoint_at: i64) -> Contact {
@@ -147,6 +291,7 @@ mod tests {
             created_at: 0,
             updated_at: 0,
             archived_at: None,
+            organization: None,
         }
     }
 
@@ -165,9 +310,12 @@ mod tests {
         let export = export_ics(
             &[contact],
             &HashMap::new(),
+            &HashMap::new(),
             IcsExportOptions {
                 now_utc: 1_699_000_000,
                 window_days: Some(365),
+                expand_cadence: false,
+                alarm_seconds: None,
             },
         )
         .expect("export");
@@ -178,4 +326,151 @@ mod tests {
             .data
             .contains("UID:knotter-2d8b83e0-1b7c-4f28-9e1a-1a2d5b1e5e2d@knotter.local"));
     }
+
+    #[test]
+    fn expand_cadence_projects_occurrences_within_window() {
+        let now = 1_700_000_000;
+        let mut ada = contact_with_id("2d8b83e0-1b7c-4f28-9e1a-1a2d5b1e5e2d", "Ada", now + 86_400);
+        ada.cadence_days = Some(14);
+        let grace = contact_with_id(
+            "8a0c4f6e-3c1d-4b6a-9f2e-5d7c8b9a0e1f",
+            "Grace",
+            now + 2 * 86_400,
+        );
+        let options = IcsExportOptions {
+            now_utc: now,
+            window_days: Some(30),
+            expand_cadence: true,
+            alarm_seconds: None,
+        };
+
+        let export = export_ics(
+            &[ada.clone(), grace],
+            &HashMap::new(),
+            &HashMap::new(),
+            options,
+        )
+        .expect("export");
+        // Ada: day 1, 15, 29; Grace has no cadence and keeps one event.
+        assert_eq!(export.count, 4);
+        assert_eq!(export.data.matches("BEGIN:VEVENT").count(), 4);
+        assert!(export
+            .data
+            .contains("UID:knotter-2d8b83e0-1b7c-4f28-9e1a-1a2d5b1e5e2d@knotter.local"));
+        assert!(export
+            .data
+            .contains("UID:knotter-2d8b83e0-1b7c-4f28-9e1a-1a2d5b1e5e2d-2@knotter.local"));
+
+        let unbounded = export_ics(
+            &[ada],
+            &HashMap::new(),
+            &HashMap::new(),
+            IcsExportOptions {
+                window_days: None,
+                ..options
+            },
+        )
+        .expect("export");
+        assert_eq!(unbounded.count, MAX_OCCURRENCES_PER_CONTACT);
+    }
+
+    #[test]
+    fn expand_cadence_skips_overdue_occurrences_but_keeps_indexes() {
+        let now = 1_700_000_000;
+        let mut ada = contact_with_id("2d8b83e0-1b7c-4f28-9e1a-1a2d5b1e5e2d", "Ada", now - 86_400);
+        ada.cadence_days = Some(7);
+        let export = export_ics(
+            &[ada],
+            &HashMap::new(),
+            &HashMap::new(),
+            IcsExportOptions {
+                now_utc: now,
+                window_days: Some(10),
+                expand_cadence: true,
+                alarm_seconds: None,
+            },
+        )
+        .expect("export");
+        assert_eq!(export.count, 1);
+        assert!(export
+            .data
+            .contains("UID:knotter-2d8b83e0-1b7c-4f28-9e1a-1a2d5b1e5e2d-1@knotter.local"));
+    }
+
+    #[test]
+    fn alarm_adds_valarm_block_to_each_event() {
+        let contact = contact_with_id("2d8b83e0-1b7c-4f28-9e1a-1a2d5b1e5e2d", "Ada", 1_700_000_000);
+        let export_with = |alarm_seconds| {
+            export_ics(
+                std::slice::from_ref(&contact),
+                &HashMap::new(),
+                &HashMap::new(),
+                IcsExportOptions {
+                    now_utc: 1_699_000_000,
+                    window_days: None,
+                    expand_cadence: false,
+                    alarm_seconds,
+                },
+            )
+            .expect("export")
+            .data
+        };
+
+        assert!(!export_with(None).contains("BEGIN:VALARM"));
+        let hours = export_with(Some(9 * 3_600));
+        assert!(hours.contains(
+            "BEGIN:VALARM\r\nACTION:DISPLAY\r\nDESCRIPTION:Reach out to Ada\r\nTRIGGER:-PT9H\r\nEND:VALARM\r\nEND:VEVENT\r\n"
+        ));
+        assert!(export_with(Some(86_400)).contains("TRIGGER:-P1D\r\n"));
+        assert!(export_with(Some(86_400 + 5_400)).contains("TRIGGER:-P1DT1H30M\r\n"));
+        assert!(export_with(Some(0)).contains("TRIGGER:-PT0S\r\n"));
+    }
+
+    #[test]
+    fn archived_contacts_contribute_birthdays_but_not_touchpoints() {
+        // 2023-11-14T22:13:20Z
+        let now = 1_700_000_000;
+        let ada = contact_with_id("2d8b83e0-1b7c-4f28-9e1a-1a2d5b1e5e2d", "Ada", now + 86_400);
+        let mut grace = contact_with_id(
+            "8a0c4f6e-3c1d-4b6a-9f2e-5d7c8b9a0e1f",
+            "Grace",
+            now + 86_400,
+        );
+        grace.archived_at = Some(now - 86_400);
+        let birthday = |contact: &Contact, month, day| ContactDate {
+            id: ContactDateId::new(),
+            contact_id: contact.id,
+            kind: ContactDateKind::Birthday,
+            label: None,
+            month,
+            day,
+            year: Some(1906),
+            created_at: 0,
+            updated_at: 0,
+            source: None,
+            remind_days_before: None,
+        };
+        let dates = HashMap::from([
+            (ada.id, vec![birthday(&ada, 12, 10)]),
+            (grace.id, vec![birthday(&grace, 11, 20)]),
+        ]);
+        let options = IcsExportOptions {
+            now_utc: now,
+            window_days: Some(30),
+            expand_cadence: false,
+            alarm_seconds: None,
+        };
+
+        let export = export_ics(&[ada, grace], &HashMap::new(), &dates, options).expect("export");
+        assert_eq!(export.count, 3);
+        assert!(export.data.contains("SUMMARY:Reach out to Ada\r\n"));
+        assert!(!export.data.contains("Reach out to Grace"));
+        assert!(export.data.contains(
+            "DTSTART;VALUE=DATE:20231120\r\nRRULE:FREQ=YEARLY\r\nSUMMARY:Grace's birthday\r\n"
+        ));
+        assert!(export.data.contains("DTSTART;VALUE=DATE:20231210\r\n"));
+        let grace_birthday = export.data.find("Grace's birthday").expect("grace");
+        let ada_birthday = export.data.find("Ada's birthday").expect("ada");
+        assert!(grace_birthday < ada_birthday);
+    }
 }
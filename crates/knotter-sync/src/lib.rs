@@ -1,8 +1,10 @@
 pub mod carddav;
 pub mod email;
 pub mod error;
+pub mod google_csv;
 pub mod ics;
 pub mod macos;
+pub mod parallel;
 pub mod source;
 pub mod telegram;
 pub mod vcf;
@@ -1,14 +1,29 @@
 use crate::source::VcfSource;
+use crate::vcf::escape_vcard_value;
 use crate::{Result, SyncError};
+use std::collections::HashMap;
+
+/// Separates the vCards from the group membership lines in the script output.
+const GROUPS_MARKER: &str = "X-KNOTTER-GROUPS-BEGIN";
 
 #[derive(Debug, Clone)]
 pub struct MacosContactsSource {
     pub group: Option<String>,
+    /// Also fetch every group each contact belongs to, as `X-KNOTTER-GROUP` lines.
+    pub groups_as_tags: bool,
 }
 
 impl MacosContactsSource {
     pub fn new(group: Option<String>) -> Self {
-        Self { group }
+        Self {
+            group,
+            groups_as_tags: false,
+        }
+    }
+
+    pub fn with_groups_as_tags(mut self, groups_as_tags: bool) -> Self {
+        self.groups_as_tags = groups_as_tags;
+        self
     }
 }
 
@@ -18,27 +33,74 @@ impl VcfSource for MacosContactsSource {
     }
 
     fn fetch_vcf(&self) -> Result<String> {
-        fetch_contacts_vcf(self.group.as_deref())
+        let output = fetch_contacts_vcf(self.group.as_deref(), self.groups_as_tags)?;
+        Ok(attach_group_memberships(&output))
     }
 }
 
+/// Moves the `<person id>\t<group name>` lines that follow [`GROUPS_MARKER`]
+/// into their cards as `X-KNOTTER-GROUP` properties, matching on `X-ABUID`.
+fn attach_group_memberships(output: &str) -> String {
+    let Some((cards, memberships)) = output.split_once(GROUPS_MARKER) else {
+        return output.to_string();
+    };
+    let mut groups_by_person: HashMap<&str, Vec<&str>> = HashMap::new();
+    for line in memberships.lines() {
+        if let Some((person_id, group)) = line.split_once('\t') {
+            let group = group.trim();
+            if !group.is_empty() {
+                groups_by_person
+                    .entry(person_id.trim())
+                    .or_default()
+                    .push(group);
+            }
+        }
+    }
+
+    let mut out = String::with_capacity(cards.len());
+    let mut person_id: Option<&str> = None;
+    for line in cards.lines() {
+        let trimmed = line.trim();
+        if let Some(value) = trimmed.strip_prefix("X-ABUID:") {
+            person_id = Some(value.trim());
+        }
+        if trimmed.eq_ignore_ascii_case("END:VCARD") {
+            for group in person_id
+                .and_then(|id| groups_by_person.get(id))
+                .into_iter()
+                .flatten()
+            {
+                out.push_str("X-KNOTTER-GROUP:");
+                out.push_str(&escape_vcard_value(group));
+                out.push('\n');
+            }
+            person_id = None;
+        }
+        out.push_str(line);
+        out.push('\n');
+    }
+    out
+}
+
 #[cfg(target_os = "macos")]
-fn fetch_contacts_vcf(group: Option<&str>) -> Result<String> {
+fn fetch_contacts_vcf(group: Option<&str>, groups_as_tags: bool) -> Result<String> {
     use std::process::Command;
 
     let script = r#"
 on run argv
     set oldDelimiters to AppleScript's text item delimiters
     set AppleScript's text item delimiters to linefeed
+    set includeGroups to (item 1 of argv) is "groups"
     set cards to {}
+    set memberships to {}
     set succeeded to false
     repeat 5 times
         try
             tell application "Contacts"
-                if (count of argv) is 0 then
+                if (count of argv) is 1 then
                     set cards to vcard of people
                 else
-                    set targetGroup to item 1 of argv
+                    set targetGroup to item 2 of argv
                     set matchingGroups to groups whose name is targetGroup
                     if (count of matchingGroups) is 0 then
                         error "Contacts group \"" & targetGroup & "\" not found. Create it in Contacts or omit group to import all contacts." number -1719
@@ -46,6 +108,15 @@ on run argv
                     set targetGroupRef to item 1 of matchingGroups
                     set cards to vcard of people of targetGroupRef
                 end if
+                if includeGroups then
+                    set memberships to {}
+                    repeat with aGroup in groups
+                        set groupName to name of aGroup
+                        repeat with personId in (id of people of aGroup)
+                            set end of memberships to (personId as text) & tab & groupName
+                        end repeat
+                    end repeat
+                end if
             end tell
             set succeeded to true
             exit repeat
@@ -66,6 +137,9 @@ on run argv
     else
         set joined to cards as text
     end if
+    if (count of memberships) > 0 then
+        set joined to joined & linefeed & "X-KNOTTER-GROUPS-BEGIN" & linefeed & (memberships as text)
+    end if
     set AppleScript's text item delimiters to oldDelimiters
     return joined
 end run
@@ -73,6 +147,7 @@ end run
 
     let mut cmd = Command::new("osascript");
     cmd.arg("-e").arg(script);
+    cmd.arg(if groups_as_tags { "groups" } else { "nogroups" });
     if let Some(group) = group {
         if !group.trim().is_empty() {
             cmd.arg(group);
@@ -95,8 +170,36 @@ end run
 }
 
 #[cfg(not(target_os = "macos"))]
-fn fetch_contacts_vcf(_group: Option<&str>) -> Result<String> {
+fn fetch_contacts_vcf(_group: Option<&str>, _groups_as_tags: bool) -> Result<String> {
     Err(SyncError::Unavailable(
         "macOS Contacts import is only available on macOS".to_string(),
     ))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::attach_group_memberships;
+    use crate::vcf::parse_vcf;
+
+    #[test]
+    fn group_memberships_become_group_tags() {
+        let output = "BEGIN:VCARD\nVERSION:3.0\nFN:Jane Doe\nNOTE:Met at RustConf\nX-ABUID:AAA:ABPerson\nEND:VCARD\n\
+BEGIN:VCARD\nVERSION:3.0\nFN:John Roe\nX-ABUID:BBB:ABPerson\nEND:VCARD\n\
+X-KNOTTER-GROUPS-BEGIN\nAAA:ABPerson\tFriends\nAAA:ABPerson\tBook  Club\nBBB:ABPerson\t   \n";
+
+        let parsed = parse_vcf(&attach_group_memberships(output)).expect("parse");
+        assert_eq!(parsed.contacts.len(), 2);
+        let jane = &parsed.contacts[0];
+        let tags: Vec<&str> = jane.group_tags.iter().map(|tag| tag.as_str()).collect();
+        assert_eq!(tags, vec!["book-club", "friends"]);
+        assert!(jane.tags.is_empty());
+        assert_eq!(jane.note.as_deref(), Some("Met at RustConf"));
+        assert!(parsed.contacts[1].group_tags.is_empty());
+    }
+
+    #[test]
+    fn output_without_memberships_is_unchanged() {
+        let output = "BEGIN:VCARD\nFN:Jane Doe\nEND:VCARD\n";
+        assert_eq!(attach_group_memberships(output), output);
+    }
+}
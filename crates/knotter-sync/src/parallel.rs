@@ -0,0 +1,83 @@
+//! Bounded fan-out for network fetches. Callers keep database writes on their
+//! own thread and apply the results afterwards, in input order.
+
+use std::collections::VecDeque;
+use std::sync::Mutex;
+use std::thread;
+
+/// Runs `fetch` over `items` on at most `jobs` worker threads and returns the
+/// results in the same order as `items`. With `jobs <= 1` (or a single item)
+/// everything runs on the calling thread, one item after another.
+pub fn map_bounded<T, R, F>(jobs: usize, items: Vec<T>, fetch: F) -> Vec<R>
+where
+    T: Send,
+    R: Send,
+    F: Fn(T) -> R + Sync,
+{
+    let workers = jobs.min(items.len());
+    if workers <= 1 {
+        return items.into_iter().map(fetch).collect();
+    }
+
+    let count = items.len();
+    let queue = Mutex::new(items.into_iter().enumerate().collect::<VecDeque<_>>());
+    let results = Mutex::new((0..count).map(|_| None).collect::<Vec<Option<R>>>());
+    thread::scope(|scope| {
+        for _ in 0..workers {
+            scope.spawn(|| loop {
+                let next = queue
+                    .lock()
+                    .unwrap_or_else(|err| err.into_inner())
+                    .pop_front();
+                let Some((index, item)) = next else {
+                    break;
+                };
+                let result = fetch(item);
+                results.lock().unwrap_or_else(|err| err.into_inner())[index] = Some(result);
+            });
+        }
+    });
+    results
+        .into_inner()
+        .unwrap_or_else(|err| err.into_inner())
+        .into_iter()
+        .map(|result| result.expect("every queued item produces a result"))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::map_bounded;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    #[test]
+    fn results_keep_input_order() {
+        let out = map_bounded(3, (0..10).collect(), |value: u64| {
+            std::thread::sleep(Duration::from_millis(10 - value));
+            value * 2
+        });
+        assert_eq!(out, (0..10).map(|value| value * 2).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn never_exceeds_the_job_limit() {
+        let in_flight = AtomicUsize::new(0);
+        let peak = AtomicUsize::new(0);
+        map_bounded(2, vec![(); 6], |_| {
+            let now = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+            peak.fetch_max(now, Ordering::SeqCst);
+            std::thread::sleep(Duration::from_millis(20));
+            in_flight.fetch_sub(1, Ordering::SeqCst);
+        });
+        assert_eq!(peak.load(Ordering::SeqCst), 2);
+
+        peak.store(0, Ordering::SeqCst);
+        map_bounded(1, vec![(); 3], |_| {
+            let now = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+            peak.fetch_max(now, Ordering::SeqCst);
+            in_flight.fetch_sub(1, Ordering::SeqCst);
+        });
+        assert_eq!(peak.load(Ordering::SeqCst), 1);
+    }
+}
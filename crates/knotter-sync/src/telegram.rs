@@ -18,6 +18,10 @@ pub struct TelegramUser {
     pub first_name: Option<String>,
     pub last_name: Option<String>,
     pub is_bot: bool,
+    pub verified: bool,
+    pub premium: bool,
+    /// Mutual contact: each side has the other in their address book.
+    pub contact: bool,
 }
 
 impl TelegramUser {
@@ -142,6 +146,9 @@ mod imp {
                         first_name,
                         last_name,
                         is_bot: user.is_bot(),
+                        verified: user.verified(),
+                        premium: user.raw.premium,
+                        contact: user.mutual_contact(),
                     });
                 }
                 Ok::<(Vec<TelegramUser>, HashMap<i64, PackedChat>), SyncError>((out, peers))
@@ -1,6 +1,7 @@
 use crate::error::Result;
 use knotter_core::domain::{
-    normalize_contact_date_label, Contact, ContactDate, ContactDateKind, ContactId, TagName,
+    normalize_alias, normalize_contact_date_label, Contact, ContactDate, ContactDateKind,
+    ContactId, ContactOrigin, TagName,
 };
 use knotter_core::time::parse_date_parts;
 use serde::Serialize;
@@ -10,11 +11,40 @@ use std::str::FromStr;
 #[derive(Debug, Clone, Serialize)]
 pub struct ImportReport {
     pub created: usize,
+    /// Matched contacts the import changed.
     pub updated: usize,
+    /// Matched contacts whose values already equalled the import.
+    pub unchanged: usize,
     pub skipped: usize,
     pub merge_candidates_created: usize,
+    /// Contacts group memberships applied as tags (`groups_as_tags`).
+    pub group_tags_attached: usize,
+    /// Contact notes stored as interactions (macOS Contacts imports).
+    pub notes_imported: usize,
+    /// New contacts given the configured default cadence
+    /// (`contacts.apply_default_cadence_on_import`).
+    pub default_cadence_applied: usize,
+    /// Cards carrying one of the user's own emails or phones
+    /// (`contacts.ignore_identities`), left out without a warning.
+    pub identities_skipped: usize,
     pub warnings: Vec<String>,
     pub dry_run: bool,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub files: Vec<ImportFileReport>,
+}
+
+/// Per-file counts for multi-file vCard imports; `error` is set when the file
+/// could not be read or parsed and was skipped.
+#[derive(Debug, Clone, Serialize)]
+pub struct ImportFileReport {
+    pub path: String,
+    pub created: usize,
+    pub updated: usize,
+    pub unchanged: usize,
+    pub skipped: usize,
+    pub merge_candidates_created: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
 }
 
 #[derive(Debug, Clone)]
@@ -27,6 +57,15 @@ pub struct VcfContact {
     pub cadence_days: Option<i32>,
     pub dates: Vec<ContactDateInput>,
     pub external_id: Option<String>,
+    /// Tags from Contacts group memberships (`X-KNOTTER-GROUP`), kept apart from
+    /// `tags` so imports can opt in and count them.
+    pub group_tags: Vec<TagName>,
+    /// Free-text `NOTE` property.
+    pub note: Option<String>,
+    /// `NICKNAME` values, stored as contact aliases.
+    pub aliases: Vec<String>,
+    /// Company name from the first `ORG` component.
+    pub organization: Option<String>,
 }
 
 #[derive(Debug, Clone)]
@@ -76,34 +115,39 @@ pub fn parse_vcf(data: &str) -> Result<ParsedVcf> {
             continue;
         };
 
-        let Some((key, raw_value)) = split_property(trimmed) else {
+        let Some(property) = parse_property(trimmed) else {
             continue;
         };
+        let raw_value = &property.value;
 
-        match key.as_str() {
+        match property.name.as_str() {
             "FN" => {
-                let value = unescape_vcard_value(&raw_value);
+                let value = unescape_vcard_value(raw_value);
                 if card.fn_name.is_none() && !value.trim().is_empty() {
                     card.fn_name = Some(value.trim().to_string());
                 }
             }
+            "N" if card.structured_name.is_none() => {
+                card.structured_name = format_structured_name(raw_value);
+            }
             "EMAIL" => {
-                let value = unescape_vcard_value(&raw_value);
+                let value = unescape_vcard_value(raw_value);
                 let trimmed = value.trim();
                 if !trimmed.is_empty() {
-                    card.emails.push(trimmed.to_string());
+                    card.emails.push(property.typed_value(trimmed));
                 }
             }
             "TEL" => {
-                let value = unescape_vcard_value(&raw_value);
-                if card.phone.is_none() && !value.trim().is_empty() {
-                    card.phone = Some(value.trim().to_string());
+                let value = unescape_vcard_value(raw_value);
+                let value = strip_tel_uri(value.trim());
+                if !value.is_empty() {
+                    card.phones.push(property.typed_value(&value));
                 }
             }
             "CATEGORIES" => {
                 let raw = raw_value.trim();
                 if !raw.is_empty() {
-                    for item in split_escaped_commas(raw) {
+                    for item in split_escaped(raw, ',') {
                         let item = unescape_vcard_value(&item);
                         let item = item.trim();
                         if !item.is_empty() {
@@ -112,38 +156,72 @@ pub fn parse_vcf(data: &str) -> Result<ParsedVcf> {
                     }
                 }
             }
+            "NICKNAME" => {
+                for item in split_escaped(raw_value.trim(), ',') {
+                    if let Some(alias) = normalize_alias(&unescape_vcard_value(&item)) {
+                        card.nicknames.push(alias);
+                    }
+                }
+            }
+            "X-KNOTTER-GROUP" => {
+                let value = unescape_vcard_value(raw_value);
+                let value = value.trim();
+                if !value.is_empty() {
+                    card.groups.push(value.to_string());
+                }
+            }
+            "NOTE" => {
+                let value = unescape_vcard_value(raw_value);
+                if card.note.is_none() && !value.trim().is_empty() {
+                    card.note = Some(value.trim().to_string());
+                }
+            }
+            "ORG" if card.organization.is_none() => {
+                // Later components are departments; only the company is kept.
+                let company = split_escaped(raw_value.trim(), ';')
+                    .into_iter()
+                    .next()
+                    .unwrap_or_default();
+                let company = unescape_vcard_value(&company);
+                if !company.trim().is_empty() {
+                    card.organization = Some(company.trim().to_string());
+                }
+            }
             "X-KNOTTER-NEXT-TOUCHPOINT" => {
-                let value = unescape_vcard_value(&raw_value);
+                let value = unescape_vcard_value(raw_value);
                 if card.next_touchpoint_at.is_none() && !value.trim().is_empty() {
                     card.next_touchpoint_at = Some(value.trim().to_string());
                 }
             }
             "X-KNOTTER-CADENCE-DAYS" => {
-                let value = unescape_vcard_value(&raw_value);
+                let value = unescape_vcard_value(raw_value);
                 if card.cadence_days.is_none() && !value.trim().is_empty() {
                     card.cadence_days = Some(value.trim().to_string());
                 }
             }
             "BDAY" => {
-                let value = unescape_vcard_value(&raw_value);
+                let value = unescape_vcard_value(raw_value);
                 if card.birthday.is_none() && !value.trim().is_empty() {
                     card.birthday = Some(value.trim().to_string());
+                    card.birthday_omit_year = property
+                        .param("X-APPLE-OMIT-YEAR")
+                        .and_then(|year| year.trim().parse().ok());
                 }
             }
             "X-KNOTTER-DATE" => {
-                let value = unescape_vcard_value(&raw_value);
+                let value = unescape_vcard_value(raw_value);
                 if !value.trim().is_empty() {
                     card.date_fields.push(value.trim().to_string());
                 }
             }
             "UID" => {
-                let value = unescape_vcard_value(&raw_value);
+                let value = unescape_vcard_value(raw_value);
                 if card.uid.is_none() && !value.trim().is_empty() {
                     card.uid = Some(value.trim().to_string());
                 }
             }
             "X-ABUID" => {
-                let value = unescape_vcard_value(&raw_value);
+                let value = unescape_vcard_value(raw_value);
                 if card.ab_uid.is_none() && !value.trim().is_empty() {
                     card.ab_uid = Some(value.trim().to_string());
                 }
@@ -173,6 +251,8 @@ pub fn export_vcf(
     tags: &HashMap<ContactId, Vec<String>>,
     emails: &HashMap<ContactId, Vec<String>>,
     dates: &HashMap<ContactId, Vec<ContactDate>>,
+    aliases: &HashMap<ContactId, Vec<String>>,
+    origins: &HashMap<ContactId, ContactOrigin>,
 ) -> Result<String> {
     let mut entries: Vec<&Contact> = contacts.iter().collect();
     entries.sort_by_key(|contact| contact.display_name.to_ascii_lowercase());
@@ -198,6 +278,17 @@ pub fn export_vcf(
         if let Some(phone) = &contact.phone {
             out.push_str(&format!("TEL:{}\r\n", escape_vcard_value(phone)));
         }
+        if let Some(organization) = &contact.organization {
+            out.push_str(&format!("ORG:{}\r\n", escape_vcard_value(organization)));
+        }
+        if let Some(nicknames) = aliases.get(&contact.id).filter(|list| !list.is_empty()) {
+            let joined = nicknames
+                .iter()
+                .map(|alias| escape_vcard_value(alias))
+                .collect::<Vec<_>>()
+                .join(",");
+            out.push_str(&format!("NICKNAME:{}\r\n", joined));
+        }
         if let Some(names) = tags.get(&contact.id) {
             if !names.is_empty() {
                 let mut sorted = names.clone();
@@ -219,6 +310,20 @@ pub fn export_vcf(
         if let Some(cadence_days) = contact.cadence_days {
             out.push_str(&format!("X-KNOTTER-CADENCE-DAYS:{}\r\n", cadence_days));
         }
+        if let Some(origin) = origins.get(&contact.id) {
+            if let Some(met_at) = &origin.met_at {
+                out.push_str(&format!("X-KNOTTER-MET:{}\r\n", escape_vcard_value(met_at)));
+            }
+            if let Some(met_on) = origin.met_on {
+                out.push_str(&format!(
+                    "X-KNOTTER-MET-DATE:{}\r\n",
+                    met_on.format("%Y-%m-%d")
+                ));
+            }
+            if let Some(met_via) = origin.met_via {
+                out.push_str(&format!("X-KNOTTER-MET-VIA:{}\r\n", met_via));
+            }
+        }
         if let Some(contact_dates) = dates.get(&contact.id) {
             let primary_birthday = contact_dates
                 .iter()
@@ -261,12 +366,19 @@ pub fn export_vcf(
 #[derive(Default)]
 struct RawCard {
     fn_name: Option<String>,
-    emails: Vec<String>,
-    phone: Option<String>,
+    structured_name: Option<String>,
+    emails: Vec<TypedValue>,
+    phones: Vec<TypedValue>,
     categories: Vec<String>,
+    nicknames: Vec<String>,
+    groups: Vec<String>,
+    note: Option<String>,
+    organization: Option<String>,
     next_touchpoint_at: Option<String>,
     cadence_days: Option<String>,
     birthday: Option<String>,
+    /// Placeholder year Apple Contacts writes for year-less birthdays.
+    birthday_omit_year: Option<i32>,
     date_fields: Vec<String>,
     uid: Option<String>,
     ab_uid: Option<String>,
@@ -274,7 +386,7 @@ struct RawCard {
 
 impl RawCard {
     fn into_contact(self, warnings: &mut Vec<String>, skipped: &mut usize) -> Option<VcfContact> {
-        let display_name = match self.fn_name {
+        let display_name = match self.fn_name.or(self.structured_name) {
             Some(value) if !value.trim().is_empty() => value,
             _ => {
                 warnings.push("missing FN; skipping vCard".to_string());
@@ -295,6 +407,18 @@ impl RawCard {
         let mut tags: Vec<TagName> = tag_set.into_iter().collect();
         tags.sort_by(|a, b| a.as_str().cmp(b.as_str()));
 
+        let mut group_tags: Vec<TagName> = Vec::new();
+        for raw in self.groups {
+            match TagName::new(&raw) {
+                Ok(tag) if !group_tags.contains(&tag) => group_tags.push(tag),
+                Ok(_) => {}
+                Err(_) => warnings.push(format!(
+                    "invalid group tag for {display_name}: {raw}; skipping"
+                )),
+            }
+        }
+        group_tags.sort_by(|a, b| a.as_str().cmp(b.as_str()));
+
         let cadence_days = match self.cadence_days {
             Some(raw) => match raw.parse::<i32>() {
                 Ok(value) if value > 0 => Some(value),
@@ -326,7 +450,7 @@ impl RawCard {
         };
 
         let mut emails = Vec::new();
-        for raw in self.emails {
+        for raw in by_preference(self.emails) {
             let trimmed = raw.trim();
             if trimmed.is_empty() {
                 continue;
@@ -339,6 +463,15 @@ impl RawCard {
             }
         }
 
+        let mut aliases: Vec<String> = Vec::new();
+        for alias in self.nicknames {
+            if !alias.eq_ignore_ascii_case(&display_name)
+                && !aliases.iter().any(|seen| seen.eq_ignore_ascii_case(&alias))
+            {
+                aliases.push(alias);
+            }
+        }
+
         let mut dates: Vec<ContactDateInput> = Vec::new();
         let mut date_index: HashMap<String, usize> = HashMap::new();
 
@@ -346,6 +479,7 @@ impl RawCard {
         if let Some(raw) = self.birthday {
             match parse_vcard_date(&raw) {
                 Ok((month, day, year)) => {
+                    let year = year.filter(|year| Some(*year) != self.birthday_omit_year);
                     let year = normalize_date_year(year, warnings, "BDAY");
                     birthday_date = Some(ContactDateInput {
                         kind: ContactDateKind::Birthday,
@@ -400,12 +534,16 @@ impl RawCard {
         Some(VcfContact {
             display_name,
             emails,
-            phone: self.phone,
+            phone: by_preference(self.phones).into_iter().next(),
             tags,
             next_touchpoint_at,
             cadence_days,
             dates,
             external_id: normalize_external_id(self.uid.as_deref(), self.ab_uid.as_deref()),
+            group_tags,
+            note: self.note,
+            aliases,
+            organization: self.organization,
         })
     }
 }
@@ -448,21 +586,163 @@ fn normalize_line_endings(input: &str) -> std::borrow::Cow<'_, str> {
     std::borrow::Cow::Owned(out)
 }
 
-fn split_property(line: &str) -> Option<(String, String)> {
-    let mut parts = line.splitn(2, ':');
-    let left = parts.next()?;
-    let value = parts.next()?.to_string();
-    let mut name = left.split(';').next()?.trim();
-    if let Some((_, group)) = name.rsplit_once('.') {
-        name = group;
+/// A content line split as `[group.]NAME[;PARAM=VALUE...]:VALUE`. Groups
+/// (`item1.` in Apple and Google exports) only tie properties to their
+/// `X-ABLabel` and are dropped.
+struct VcardProperty {
+    name: String,
+    /// Upper-cased parameter names with unquoted values; bare vCard 2.1
+    /// parameters (`TEL;CELL:`) are stored as `TYPE`.
+    params: Vec<(String, String)>,
+    value: String,
+}
+
+impl VcardProperty {
+    fn param(&self, name: &str) -> Option<&str> {
+        self.params
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    fn typed_value(&self, value: &str) -> TypedValue {
+        let types = self
+            .params
+            .iter()
+            .filter(|(key, _)| key == "TYPE")
+            .flat_map(|(_, value)| value.split(','))
+            .map(|item| item.trim().to_ascii_lowercase())
+            .filter(|item| !item.is_empty())
+            .collect();
+        TypedValue {
+            value: value.to_string(),
+            types,
+            pref: self.param("PREF").and_then(|pref| pref.trim().parse().ok()),
+        }
+    }
+}
+
+/// An `EMAIL` or `TEL` value with its `TYPE` list and vCard 4.0 `PREF` rank.
+struct TypedValue {
+    value: String,
+    types: Vec<String>,
+    pref: Option<u8>,
+}
+
+impl TypedValue {
+    /// Lower ranks are preferred; `TYPE=pref` (vCard 3.0) counts as `PREF=1`.
+    fn rank(&self) -> u8 {
+        match self.pref {
+            Some(pref) => pref,
+            None if self.types.iter().any(|item| item == "pref") => 1,
+            None => u8::MAX,
+        }
+    }
+}
+
+/// Orders values by preference, keeping file order among equals.
+fn by_preference(mut values: Vec<TypedValue>) -> Vec<String> {
+    values.sort_by_key(TypedValue::rank);
+    values.into_iter().map(|value| value.value).collect()
+}
+
+fn parse_property(line: &str) -> Option<VcardProperty> {
+    let mut segments = Vec::new();
+    let mut current = String::new();
+    let mut in_quotes = false;
+    let mut value = None;
+    for (idx, ch) in line.char_indices() {
+        match ch {
+            '"' => in_quotes = !in_quotes,
+            ';' if !in_quotes => segments.push(std::mem::take(&mut current)),
+            ':' if !in_quotes => {
+                segments.push(std::mem::take(&mut current));
+                value = Some(line[idx + 1..].to_string());
+                break;
+            }
+            _ => current.push(ch),
+        }
+    }
+    let value = value?;
+
+    let mut segments = segments.into_iter();
+    let left = segments.next()?;
+    let mut name = left.trim();
+    if let Some((_, ungrouped)) = name.rsplit_once('.') {
+        name = ungrouped;
     }
     if name.is_empty() {
         return None;
     }
-    Some((name.to_ascii_uppercase(), value))
+
+    let params = segments
+        .filter_map(|segment| {
+            let segment = segment.trim();
+            if segment.is_empty() {
+                return None;
+            }
+            Some(match segment.split_once('=') {
+                Some((key, value)) => (key.trim().to_ascii_uppercase(), value.trim().to_string()),
+                None => ("TYPE".to_string(), segment.to_string()),
+            })
+        })
+        .collect();
+
+    Some(VcardProperty {
+        name: name.to_ascii_uppercase(),
+        params,
+        value,
+    })
+}
+
+/// Strips the `tel:` scheme from vCard 4.0 `VALUE=uri` phones and turns a
+/// `;ext=` parameter into a plain extension.
+fn strip_tel_uri(value: &str) -> String {
+    let Some(head) = value.get(..4) else {
+        return value.to_string();
+    };
+    if !head.eq_ignore_ascii_case("tel:") {
+        return value.to_string();
+    }
+    let mut parts = value[4..].split(';');
+    let number = parts.next().unwrap_or_default().trim().to_string();
+    let extension = parts.find_map(|param| {
+        let (key, value) = param.split_once('=')?;
+        key.trim().eq_ignore_ascii_case("ext").then(|| value.trim())
+    });
+    match extension {
+        Some(ext) if !ext.is_empty() => format!("{number} ext. {ext}"),
+        _ => number,
+    }
+}
+
+/// Builds a display name from `N` (`family;given;additional;prefix;suffix`),
+/// used when a card has no `FN`.
+fn format_structured_name(raw: &str) -> Option<String> {
+    let components = split_escaped(raw, ';');
+    let component = |idx: usize| -> String {
+        components
+            .get(idx)
+            .map(|value| {
+                split_escaped(value, ',')
+                    .iter()
+                    .map(|item| unescape_vcard_value(item).trim().to_string())
+                    .filter(|item| !item.is_empty())
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            })
+            .unwrap_or_default()
+    };
+    let name = [3, 1, 2, 0, 4]
+        .into_iter()
+        .map(component)
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ");
+    (!name.is_empty()).then_some(name)
 }
 
-fn split_escaped_commas(value: &str) -> Vec<String> {
+fn split_escaped(value: &str, separator: char) -> Vec<String> {
     let mut items = Vec::new();
     let mut current = String::new();
     let mut escape = false;
@@ -480,7 +760,7 @@ fn split_escaped_commas(value: &str) -> Vec<String> {
             continue;
         }
 
-        if ch == ',' {
+        if ch == separator {
             items.push(current);
             current = String::new();
         } else {
@@ -492,7 +772,7 @@ fn split_escaped_commas(value: &str) -> Vec<String> {
     items
 }
 
-fn escape_vcard_value(value: &str) -> String {
+pub(crate) fn escape_vcard_value(value: &str) -> String {
     let mut out = String::with_capacity(value.len());
     for ch in value.chars() {
         match ch {
@@ -577,7 +857,7 @@ fn format_vcard_date(month: u8, day: u8, year: Option<i32>) -> String {
     }
 }
 
-fn parse_vcard_date(raw: &str) -> std::result::Result<(u8, u8, Option<i32>), String> {
+pub(crate) fn parse_vcard_date(raw: &str) -> std::result::Result<(u8, u8, Option<i32>), String> {
     let trimmed = raw.trim();
     if trimmed.is_empty() {
         return Err("empty date".to_string());
@@ -615,7 +895,7 @@ fn parse_knotter_date_field(raw: &str) -> std::result::Result<ContactDateInput,
     })
 }
 
-fn normalize_date_year(
+pub(crate) fn normalize_date_year(
     year: Option<i32>,
     warnings: &mut Vec<String>,
     context: &str,
@@ -691,6 +971,17 @@ mod tests {
         );
         assert_eq!(contact.phone.as_deref(), Some("555-1234"));
         assert_eq!(contact.tags.len(), 2);
+        assert_eq!(contact.organization, None);
+    }
+
+    #[test]
+    fn parse_vcf_keeps_company_from_org() {
+        let data = "BEGIN:VCARD\nVERSION:3.0\nFN:Jane Doe\nORG:Acme\\, Inc.;Research\nEND:VCARD\n";
+        let parsed = parse_vcf(data).expect("parse");
+        assert_eq!(
+            parsed.contacts[0].organization.as_deref(),
+            Some("Acme, Inc.")
+        );
     }
 
     #[test]
@@ -808,6 +1099,7 @@ mod tests {
             created_at: 0,
             updated_at: 0,
             archived_at: None,
+            organization: Some("Analytical Engines; Ltd".to_string()),
         };
 
         let mut tag_map = HashMap::new();
@@ -815,14 +1107,94 @@ mod tests {
         let mut email_map = HashMap::new();
         email_map.insert(contact.id, vec!["ada@example.com".to_string()]);
         let date_map: HashMap<ContactId, Vec<ContactDate>> = HashMap::new();
-        let output = export_vcf(&[contact], &tag_map, &email_map, &date_map).expect("export");
+        let introducer = ContactId::from_str("6f1c2a9e-0d4b-4c1e-8f3a-2b7d9e4c5a10").unwrap();
+        let mut origin_map = HashMap::new();
+        origin_map.insert(
+            contact.id,
+            ContactOrigin {
+                met_at: Some("RustConf, 2023".to_string()),
+                met_on: chrono::NaiveDate::from_ymd_opt(2023, 9, 12),
+                met_via: Some(introducer),
+            },
+        );
+        let output = export_vcf(
+            &[contact],
+            &tag_map,
+            &email_map,
+            &date_map,
+            &HashMap::new(),
+            &origin_map,
+        )
+        .expect("export");
         assert!(output.contains("BEGIN:VCARD"));
         assert!(output.contains("FN:Ada Lovelace"));
         assert!(output.contains("EMAIL:ada@example.com"));
         assert!(output.contains("TEL:555-0101"));
+        assert!(output.contains("ORG:Analytical Engines\\; Ltd\r\n"));
         assert!(output.contains("CATEGORIES:friends,work"));
         assert!(output.contains("X-KNOTTER-NEXT-TOUCHPOINT:1700000000"));
         assert!(output.contains("X-KNOTTER-CADENCE-DAYS:30"));
+        assert!(output.contains("X-KNOTTER-MET:RustConf\\, 2023\r\n"));
+        assert!(output.contains("X-KNOTTER-MET-DATE:2023-09-12\r\n"));
+        assert!(output.contains(&format!("X-KNOTTER-MET-VIA:{introducer}\r\n")));
+    }
+
+    #[test]
+    fn parse_vcf_falls_back_to_structured_name() {
+        let data = "BEGIN:VCARD\nVERSION:4.0\nN:O'Brien\\, Jr.;Sean;Patrick,Michael;;\nEND:VCARD\n";
+        let parsed = parse_vcf(data).expect("parse");
+        assert_eq!(parsed.contacts.len(), 1);
+        assert_eq!(
+            parsed.contacts[0].display_name,
+            "Sean Patrick Michael O'Brien, Jr."
+        );
+    }
+
+    #[test]
+    fn parse_property_handles_groups_and_quoted_params() {
+        let property =
+            parse_property("item2.TEL;TYPE=\"work,voice\";X-LABEL=\"a:b\":tel:+1-555").unwrap();
+        assert_eq!(property.name, "TEL");
+        assert_eq!(property.param("X-LABEL"), Some("a:b"));
+        assert_eq!(property.value, "tel:+1-555");
+        assert_eq!(property.typed_value("x").types, vec!["work", "voice"]);
+
+        let legacy = parse_property("TEL;CELL;PREF:555").unwrap();
+        assert_eq!(legacy.typed_value("555").rank(), 1);
+        assert_eq!(strip_tel_uri("TEL:+15550100;ext=7"), "+15550100 ext. 7");
+        assert_eq!(strip_tel_uri("+1 555 0100"), "+1 555 0100");
+    }
+
+    #[test]
+    fn export_vcf_stays_vcard_3_0() {
+        let contact = Contact {
+            id: ContactId::from_str("2d8b83e0-1b7c-4f28-9e1a-1a2d5b1e5e2d").unwrap(),
+            display_name: "Müller, Hans".to_string(),
+            email: Some("hans@example.org".to_string()),
+            phone: Some("+49-30-5550199 ext. 12".to_string()),
+            handle: None,
+            timezone: None,
+            next_touchpoint_at: None,
+            cadence_days: None,
+            created_at: 0,
+            updated_at: 0,
+            archived_at: None,
+            organization: None,
+        };
+        let output = export_vcf(
+            &[contact],
+            &HashMap::new(),
+            &HashMap::new(),
+            &HashMap::new(),
+            &HashMap::new(),
+            &HashMap::new(),
+        )
+        .expect("export");
+        assert!(output.contains("VERSION:3.0\r\n"));
+        assert!(!output.contains("VERSION:4.0"));
+        assert!(output.contains("FN:Müller\\, Hans\r\n"));
+        assert!(output.contains("TEL:+49-30-5550199 ext. 12\r\n"));
+        assert!(!output.contains("VALUE=uri"));
     }
 
     #[test]
@@ -839,6 +1211,7 @@ mod tests {
             created_at: 0,
             updated_at: 0,
             archived_at: None,
+            organization: None,
         };
         let mut tag_map = HashMap::new();
         tag_map.insert(contact.id, vec!["pioneers".to_string()]);
@@ -846,11 +1219,26 @@ mod tests {
         email_map.insert(contact.id, vec!["grace@example.com".to_string()]);
 
         let date_map: HashMap<ContactId, Vec<ContactDate>> = HashMap::new();
-        let output = export_vcf(&[contact], &tag_map, &email_map, &date_map).expect("export");
+        let mut alias_map = HashMap::new();
+        alias_map.insert(
+            contact.id,
+            vec!["Amazing Grace".to_string(), "Hopper, G.".to_string()],
+        );
+        let output = export_vcf(
+            &[contact],
+            &tag_map,
+            &email_map,
+            &date_map,
+            &alias_map,
+            &HashMap::new(),
+        )
+        .expect("export");
+        assert!(output.contains("NICKNAME:Amazing Grace,Hopper\\, G.\r\n"));
         let parsed = parse_vcf(&output).expect("parse");
         assert_eq!(parsed.contacts.len(), 1);
         let round = &parsed.contacts[0];
         assert_eq!(round.display_name, "Grace Hopper");
+        assert_eq!(round.aliases, vec!["Amazing Grace", "Hopper, G."]);
         assert_eq!(
             round.emails.first().map(String::as_str),
             Some("grace@example.com")
@@ -875,6 +1263,7 @@ mod tests {
             created_at: 0,
             updated_at: 0,
             archived_at: None,
+            organization: None,
         };
         let mut tag_map = HashMap::new();
         tag_map.insert(contact.id, vec!["friends".to_string()]);
@@ -891,6 +1280,7 @@ mod tests {
             created_at: 0,
             updated_at: 0,
             source: None,
+            remind_days_before: None,
         };
         let extra_birthday = ContactDate {
             id: ContactDateId::new(),
@@ -903,6 +1293,7 @@ mod tests {
             created_at: 0,
             updated_at: 0,
             source: None,
+            remind_days_before: None,
         };
         let custom = ContactDate {
             id: ContactDateId::new(),
@@ -915,6 +1306,7 @@ mod tests {
             created_at: 0,
             updated_at: 0,
             source: None,
+            remind_days_before: None,
         };
         let mut date_map = HashMap::new();
         date_map.insert(
@@ -922,7 +1314,15 @@ mod tests {
             vec![birthday.clone(), extra_birthday.clone(), custom.clone()],
         );
 
-        let output = export_vcf(&[contact], &tag_map, &email_map, &date_map).expect("export");
+        let output = export_vcf(
+            &[contact],
+            &tag_map,
+            &email_map,
+            &date_map,
+            &HashMap::new(),
+            &HashMap::new(),
+        )
+        .expect("export");
         assert!(output.contains("BDAY:1990-02-14"));
         assert!(output.contains("X-KNOTTER-DATE:birthday|--0301"));
         assert!(output.contains("X-KNOTTER-DATE:custom|--0214|Wife birthday"));
@@ -976,6 +1376,7 @@ mod tests {
             created_at: 0,
             updated_at: 0,
             archived_at: None,
+            organization: None,
         };
         let mut tag_map = HashMap::new();
         tag_map.insert(contact.id, vec!["friends".to_string()]);
@@ -992,11 +1393,20 @@ mod tests {
             created_at: 0,
             updated_at: 0,
             source: None,
+            remind_days_before: None,
         };
         let mut date_map = HashMap::new();
         date_map.insert(contact.id, vec![birthday.clone()]);
 
-        let output = export_vcf(&[contact], &tag_map, &email_map, &date_map).expect("export");
+        let output = export_vcf(
+            &[contact],
+            &tag_map,
+            &email_map,
+            &date_map,
+            &HashMap::new(),
+            &HashMap::new(),
+        )
+        .expect("export");
         assert!(output.contains("BDAY:1906-07-04"));
         assert!(output.contains("X-KNOTTER-DATE:birthday|1906-07-04|Legal"));
 
@@ -1026,6 +1436,7 @@ mod tests {
             created_at: 0,
             updated_at: 0,
             archived_at: None,
+            organization: None,
         };
         let mut tag_map = HashMap::new();
         tag_map.insert(contact.id, vec!["friends".to_string()]);
@@ -1042,6 +1453,7 @@ mod tests {
             created_at: 0,
             updated_at: 0,
             source: None,
+            remind_days_before: None,
         };
         let labeled = ContactDate {
             id: ContactDateId::new(),
@@ -1054,11 +1466,20 @@ mod tests {
             created_at: 0,
             updated_at: 0,
             source: None,
+            remind_days_before: None,
         };
         let mut date_map = HashMap::new();
         date_map.insert(contact.id, vec![unlabeled.clone(), labeled.clone()]);
 
-        let output = export_vcf(&[contact], &tag_map, &email_map, &date_map).expect("export");
+        let output = export_vcf(
+            &[contact],
+            &tag_map,
+            &email_map,
+            &date_map,
+            &HashMap::new(),
+            &HashMap::new(),
+        )
+        .expect("export");
         assert!(output.contains("BDAY:1906-07-04"));
         assert!(output.contains("X-KNOTTER-DATE:birthday|--0704"));
         assert!(output.contains("X-KNOTTER-DATE:birthday|1906-07-04|Legal"));
@@ -0,0 +1,94 @@
+use knotter_core::domain::ContactDateKind;
+use knotter_sync::google_csv::parse_google_csv;
+use knotter_sync::vcf::VcfContact;
+
+const TAKEOUT: &str = include_str!("fixtures/google-takeout.csv");
+
+fn birthday(contact: &VcfContact) -> Option<(u8, u8, Option<i32>)> {
+    contact
+        .dates
+        .iter()
+        .find(|date| date.kind == ContactDateKind::Birthday)
+        .map(|date| (date.month, date.day, date.year))
+}
+
+fn group_tags(contact: &VcfContact) -> Vec<&str> {
+    contact.group_tags.iter().map(|tag| tag.as_str()).collect()
+}
+
+#[test]
+fn takeout_rows_map_to_import_contacts() {
+    let parsed = parse_google_csv(TAKEOUT, true).expect("parse");
+    assert_eq!(parsed.skipped, 1, "{:?}", parsed.warnings);
+    assert_eq!(parsed.warnings, vec!["row 5: missing name; skipping"]);
+    assert_eq!(parsed.contacts.len(), 3);
+
+    let hans = &parsed.contacts[0];
+    assert_eq!(hans.display_name, "Hans Müller");
+    assert_eq!(
+        hans.emails,
+        vec!["hans@example.org", "hans.mueller@example.de"]
+    );
+    assert_eq!(hans.phone.as_deref(), Some("+49 30 5550199"));
+    assert_eq!(birthday(hans), Some((4, 12, None)));
+    assert_eq!(hans.aliases, vec!["Hansi"]);
+    assert_eq!(group_tags(hans), vec!["freunde", "starred"]);
+    assert!(hans.tags.is_empty());
+    assert_eq!(hans.organization.as_deref(), Some("Acme GmbH"));
+    assert_eq!(
+        hans.note.as_deref(),
+        Some("Met at the Berlin meetup, likes climbing")
+    );
+    assert_eq!(hans.external_id, None);
+
+    let yuki = &parsed.contacts[1];
+    assert_eq!(yuki.display_name, "Yuki Nakamura");
+    assert_eq!(yuki.emails, vec!["yuki@example.jp"]);
+    assert_eq!(birthday(yuki), Some((8, 25, Some(1990))));
+    assert!(group_tags(yuki).is_empty());
+
+    let grace = &parsed.contacts[2];
+    assert_eq!(grace.display_name, "Grace Brewster Hopper");
+    assert!(grace.emails.is_empty());
+    assert_eq!(grace.phone.as_deref(), Some("+1 555 0100"));
+    assert_eq!(group_tags(grace), vec!["work"]);
+}
+
+#[test]
+fn groups_stay_off_without_groups_as_tags() {
+    let parsed = parse_google_csv(TAKEOUT, false).expect("parse");
+    assert!(parsed
+        .contacts
+        .iter()
+        .all(|contact| contact.group_tags.is_empty()));
+}
+
+#[test]
+fn newer_google_csv_headers_are_understood() {
+    let data = "First Name,Middle Name,Last Name,Nickname,Organization Name,Birthday,Labels,E-mail 1 - Label,E-mail 1 - Value,Phone 1 - Label,Phone 1 - Value\n\
+                Ada,,Lovelace,,Analytical Engines,1815-12-10,* myContacts ::: Math,Home,ada@example.com,Mobile,+44 20 5550100\n\
+                ,,,,Acme Corp,,,,info@acme.example,,\n";
+    let parsed = parse_google_csv(data, true).expect("parse");
+    assert!(parsed.warnings.is_empty(), "{:?}", parsed.warnings);
+    assert_eq!(parsed.contacts.len(), 2);
+
+    let ada = &parsed.contacts[0];
+    assert_eq!(ada.display_name, "Ada Lovelace");
+    assert_eq!(ada.emails, vec!["ada@example.com"]);
+    assert_eq!(ada.phone.as_deref(), Some("+44 20 5550100"));
+    assert_eq!(birthday(ada), Some((12, 10, Some(1815))));
+    assert_eq!(group_tags(ada), vec!["math"]);
+
+    let acme = &parsed.contacts[1];
+    assert_eq!(acme.display_name, "Acme Corp");
+    assert_eq!(acme.organization.as_deref(), Some("Acme Corp"));
+}
+
+#[test]
+fn files_without_name_columns_are_rejected() {
+    let err = parse_google_csv("Foo,Bar\n1,2\n", false).expect_err("not a Google CSV");
+    assert!(
+        err.to_string().contains("not a Google contacts CSV"),
+        "{err}"
+    );
+}
@@ -0,0 +1,81 @@
+use knotter_core::domain::ContactDateKind;
+use knotter_sync::vcf::{parse_vcf, VcfContact};
+
+const NEXTCLOUD_4_0: &str = include_str!("fixtures/nextcloud-4.0.vcf");
+const APPLE_3_0: &str = include_str!("fixtures/apple-3.0.vcf");
+
+fn birthday(contact: &VcfContact) -> (u8, u8, Option<i32>) {
+    let date = contact
+        .dates
+        .iter()
+        .find(|date| date.kind == ContactDateKind::Birthday)
+        .expect("birthday");
+    (date.month, date.day, date.year)
+}
+
+fn tags(contact: &VcfContact) -> Vec<&str> {
+    contact.tags.iter().map(|tag| tag.as_str()).collect()
+}
+
+#[test]
+fn nextcloud_vcard_4_0_cards_parse_without_warnings() {
+    let parsed = parse_vcf(NEXTCLOUD_4_0).expect("parse");
+    assert!(parsed.warnings.is_empty(), "{:?}", parsed.warnings);
+    assert_eq!(parsed.skipped, 0);
+    assert_eq!(parsed.contacts.len(), 2);
+
+    let hans = &parsed.contacts[0];
+    assert_eq!(hans.display_name, "Müller, Hans");
+    assert_eq!(
+        hans.emails,
+        vec!["hans@example.org", "hans.mueller@example.de"]
+    );
+    assert_eq!(hans.phone.as_deref(), Some("+49-30-5550199 ext. 12"));
+    assert_eq!(birthday(hans), (4, 12, None));
+    assert_eq!(hans.aliases, vec!["Hansi", "Der Kletterer, Berlin"]);
+    assert_eq!(tags(hans), vec!["berlin", "freunde"]);
+    assert_eq!(
+        hans.note.as_deref(),
+        Some("Met at the Berlin meetup; likes climbing")
+    );
+    assert_eq!(
+        hans.external_id.as_deref(),
+        Some("6f1c2a9e-3b4d-4e5f-8a7b-9c0d1e2f3a4b")
+    );
+
+    let yuki = &parsed.contacts[1];
+    assert_eq!(yuki.display_name, "Yuki Nakamura");
+    assert_eq!(yuki.emails, vec!["yuki@example.jp"]);
+    assert_eq!(birthday(yuki), (8, 25, Some(1990)));
+    assert_eq!(
+        yuki.external_id.as_deref(),
+        Some("0a1b2c3d-4e5f-4061-8293-a4b5c6d7e8f9")
+    );
+}
+
+#[test]
+fn apple_vcard_3_0_cards_honor_groups_pref_and_omitted_years() {
+    let parsed = parse_vcf(APPLE_3_0).expect("parse");
+    assert!(parsed.warnings.is_empty(), "{:?}", parsed.warnings);
+    assert_eq!(parsed.contacts.len(), 2);
+
+    let jane = &parsed.contacts[0];
+    assert_eq!(jane.display_name, "Jane Q. Doe");
+    assert_eq!(
+        jane.emails,
+        vec!["jane@example.net", "jane.doe@example.com"]
+    );
+    assert_eq!(jane.phone.as_deref(), Some("+1 (415) 555-0111"));
+    assert_eq!(birthday(jane), (3, 9, None));
+    assert_eq!(tags(jane), vec!["friends,family", "work"]);
+    assert_eq!(
+        jane.external_id.as_deref(),
+        Some("5B1D7F2C-4E8A-4C21-9A6B-2F3E4D5C6B7A:ABPerson")
+    );
+
+    let wei = &parsed.contacts[1];
+    assert_eq!(wei.display_name, "Li, Wei");
+    assert_eq!(wei.emails, vec!["wei.li@example.cn"]);
+    assert_eq!(wei.phone.as_deref(), Some("+86 138 0000 0000"));
+    assert_eq!(birthday(wei), (11, 30, Some(1985)));
+}
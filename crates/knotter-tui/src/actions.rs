@@ -1,36 +1,69 @@
+use anyhow::Result;
+use knotter_core::domain::{
+    is_starred, ContactDateId, ContactId, InteractionAttachment, InteractionId, TagName,
+};
+use knotter_core::dto::{
+    AttachmentDto, ContactDateDto, ContactDetailDto, ContactListItemDto, ContactOriginDto,
+    InteractionDto, SyncHistoryDto,
+};
+use knotter_core::rules::DueState;
+use knotter_core::time::format_date_parts;
+use knotter_store::query::{ContactListQuery, ContactSort, ListPage};
+use knotter_store::repo::{
+    ContactDateNew, ContactNew, ContactUpdate, EmailOps, InteractionAdd, InteractionNew,
+    InteractionUpdate, MergeCandidateQuery, MergeCandidateStatus, MergeFieldOverrides,
+};
+use knotter_store::Store;
 use std::collections::HashMap;
 
-use anyhow::Result;
-use chrono::FixedOffset;
-use knotter_core::domain::{ContactId, TagName};
-use knotter_core::dto::{ContactDateDto, ContactDetailDto, ContactListItemDto, InteractionDto};
-use knotter_core::filter::ArchivedSelector;
-use knotter_core::rules::compute_due_state;
-use knotter_core::time::{local_offset, now_utc};
-use knotter_store::repo::{ContactNew, ContactUpdate, EmailOps, InteractionNew};
-use knotter_store::{query::ContactQuery, Store};
+use crate::app::{
+    App, ArchiveView, MergePickerItem, MergeSide, Mode, TagChoice, INTERACTION_PAGE_SIZE,
+    LIST_PAGE_SIZE, MERGE_PAGE_SIZE, READONLY_ERROR,
+};
 
-use crate::app::{App, MergePickerItem, Mode, TagChoice};
-use crate::util::format_interaction_kind;
+/// Audit-log origin for contact changes made in the TUI.
+const AUDIT_ORIGIN: &str = "tui";
 
 #[derive(Debug, Clone)]
 pub enum Action {
     LoadList,
+    LoadMoreList,
     LoadDetail(ContactId),
+    /// Fetches the next page of older interactions for the open detail view.
+    LoadMoreInteractions(ContactId),
     LoadTags(ContactId),
     LoadMerges,
+    /// Fetches the next page of open merge candidates.
+    LoadMoreMerges,
+    LoadMergeDetail(knotter_core::domain::MergeCandidateId),
     LoadMergePicker(ContactId),
-    CreateContact(ContactNew, Vec<String>),
+    CreateContact(ContactNew, Vec<String>, Vec<TagName>),
     UpdateContact(ContactId, ContactUpdate, Vec<String>),
     AddInteraction(InteractionNew),
+    UpdateInteraction {
+        contact_id: ContactId,
+        interaction_id: InteractionId,
+        update: InteractionUpdate,
+    },
+    DeleteInteraction {
+        contact_id: ContactId,
+        interaction_id: InteractionId,
+    },
     SetTags(ContactId, Vec<TagName>),
+    AddContactDate(ContactDateNew),
+    DeleteContactDate {
+        contact_id: ContactId,
+        date_id: ContactDateId,
+    },
     ScheduleContact(ContactId, i64),
     ClearSchedule(ContactId),
-    ArchiveContact(ContactId),
+    ArchiveContact(ContactId, Option<String>),
     UnarchiveContact(ContactId),
+    SetPriority(ContactId, i32),
     ApplyMerge {
         primary_id: ContactId,
         secondary_id: ContactId,
+        fields: MergeFieldOverrides,
     },
     ApplyAllMerges {
         candidate_ids: Vec<knotter_core::domain::MergeCandidateId>,
@@ -42,31 +75,85 @@ pub enum Action {
     DismissMerge(knotter_core::domain::MergeCandidateId),
 }
 
+impl Action {
+    /// Short name for the action log.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Action::LoadList => "load list",
+            Action::LoadMoreList => "load more contacts",
+            Action::LoadDetail(_) => "load contact",
+            Action::LoadMoreInteractions(_) => "load interactions",
+            Action::LoadTags(_) => "load tags",
+            Action::LoadMerges => "load merges",
+            Action::LoadMoreMerges => "load more merges",
+            Action::LoadMergeDetail(_) => "load merge",
+            Action::LoadMergePicker(_) => "load merge picker",
+            Action::CreateContact(..) => "create contact",
+            Action::UpdateContact(..) => "update contact",
+            Action::AddInteraction(_) => "add note",
+            Action::UpdateInteraction { .. } => "update note",
+            Action::DeleteInteraction { .. } => "delete note",
+            Action::SetTags(..) => "set tags",
+            Action::AddContactDate(_) => "add date",
+            Action::DeleteContactDate { .. } => "delete date",
+            Action::ScheduleContact(..) => "schedule",
+            Action::ClearSchedule(_) => "clear schedule",
+            Action::ArchiveContact(..) => "archive",
+            Action::UnarchiveContact(_) => "unarchive",
+            Action::SetPriority(..) => "set priority",
+            Action::ApplyMerge { .. } => "apply merge",
+            Action::ApplyAllMerges { .. } => "apply all merges",
+            Action::SetMergePreferred { .. } => "set merge preference",
+            Action::DismissMerge(_) => "dismiss merge",
+        }
+    }
+
+    fn is_mutating(&self) -> bool {
+        !matches!(
+            self,
+            Action::LoadList
+                | Action::LoadMoreList
+                | Action::LoadDetail(_)
+                | Action::LoadMoreInteractions(_)
+                | Action::LoadTags(_)
+                | Action::LoadMerges
+                | Action::LoadMoreMerges
+                | Action::LoadMergeDetail(_)
+                | Action::LoadMergePicker(_)
+        )
+    }
+}
+
 pub fn execute_action(app: &mut App, store: &Store, action: Action) -> Result<()> {
+    if app.readonly && action.is_mutating() {
+        anyhow::bail!(READONLY_ERROR);
+    }
     match action {
         Action::LoadList => {
-            let now = now_utc();
-            let offset = local_offset();
-            let query = if let Some(filter) = &app.filter {
-                ContactQuery::from_filter(filter)?
-            } else {
-                ContactQuery::default()
-            };
-            let mut query = query;
-            if !app.show_archived && query.archived.is_none() {
-                query.archived = Some(ArchivedSelector::Active);
-            }
-            let contacts = store
-                .contacts()
-                .list_contacts(&query, now, app.soon_days, offset)?;
-            let ids: Vec<ContactId> = contacts.iter().map(|c| c.id).collect();
-            let tag_map = store.tags().list_names_for_contacts(&ids)?;
-            let items = build_list_items(contacts, tag_map, now, app.soon_days, offset)?;
-            app.apply_list(items);
+            // Reload at least as many rows as are already on screen so a refresh
+            // after an edit keeps the scroll position intact.
+            let limit = app.contacts.len().max(LIST_PAGE_SIZE);
+            let (items, has_more) = load_list_page(app, store, 0, limit)?;
+            app.apply_list(items, has_more);
+            app.due_counts = load_due_counts(app, store)?;
+            app.clear_error();
+        }
+        Action::LoadMoreList => {
+            let (items, has_more) = load_list_page(app, store, app.contacts.len(), LIST_PAGE_SIZE)?;
+            app.append_list(items, has_more);
             app.clear_error();
         }
         Action::LoadDetail(contact_id) => {
-            if let Some(detail) = load_detail(store, contact_id)? {
+            // Keep already-fetched older interactions so a refresh after an edit
+            // does not jump the interactions pane back to the first page.
+            let limit = app
+                .detail
+                .as_ref()
+                .filter(|detail| detail.id == contact_id)
+                .map(|detail| detail.recent_interactions.len())
+                .unwrap_or(0)
+                .max(INTERACTION_PAGE_SIZE);
+            if let Some(detail) = load_detail(store, contact_id, limit, app.clock.now_utc())? {
                 app.apply_detail(detail);
                 app.clear_error();
             } else {
@@ -77,33 +164,65 @@ pub fn execute_action(app: &mut App, store: &Store, action: Action) -> Result<()
                 }
             }
         }
+        Action::LoadMoreInteractions(contact_id) => {
+            let offset = app
+                .detail
+                .as_ref()
+                .filter(|detail| detail.id == contact_id)
+                .map(|detail| detail.recent_interactions.len())
+                .unwrap_or(0);
+            let mut attachments = store
+                .interaction_attachments()
+                .list_for_contact(contact_id)?;
+            let items = store
+                .interactions()
+                .list_for_contact(contact_id, INTERACTION_PAGE_SIZE as i64, offset as i64)?
+                .into_iter()
+                .map(|interaction| interaction_dto(interaction, &mut attachments))
+                .collect();
+            app.append_interactions(contact_id, items);
+            app.clear_error();
+        }
         Action::LoadMerges => {
-            let candidates = store.merge_candidates().list_open()?;
-            let mut items = Vec::new();
-            for candidate in candidates {
-                let auto_merge_safe = candidate.auto_merge_safe();
-                let contact_a_name = store
-                    .contacts()
-                    .get(candidate.contact_a_id)?
-                    .map(|contact| contact.display_name)
-                    .unwrap_or_else(|| "<missing contact>".to_string());
-                let contact_b_name = store
-                    .contacts()
-                    .get(candidate.contact_b_id)?
-                    .map(|contact| contact.display_name)
-                    .unwrap_or_else(|| "<missing contact>".to_string());
-                items.push(crate::app::MergeCandidateView {
-                    id: candidate.id,
-                    reason: candidate.reason,
-                    auto_merge_safe,
-                    contact_a_id: candidate.contact_a_id,
-                    contact_b_id: candidate.contact_b_id,
-                    preferred_contact_id: candidate.preferred_contact_id,
-                    contact_a_name,
-                    contact_b_name,
-                });
+            // Like the contact list, a refresh keeps every page already loaded.
+            let limit = app.merge_candidates.len().max(MERGE_PAGE_SIZE);
+            let (items, has_more) = load_merge_page(store, 0, limit)?;
+            app.apply_merge_candidates(items, has_more);
+            app.clear_error();
+        }
+        Action::LoadMoreMerges => {
+            let (items, has_more) =
+                load_merge_page(store, app.merge_candidates.len(), MERGE_PAGE_SIZE)?;
+            app.append_merge_candidates(items, has_more);
+            app.clear_error();
+        }
+        Action::LoadMergeDetail(candidate_id) => {
+            let Some(candidate) = store.merge_candidates().get(candidate_id)? else {
+                app.set_error("merge candidate not found");
+                app.mode = Mode::MergeList;
+                app.enqueue(Action::LoadMerges);
+                return Ok(());
+            };
+            let primary_id = candidate
+                .preferred_contact_id
+                .unwrap_or(candidate.contact_a_id);
+            let secondary_id = if primary_id == candidate.contact_a_id {
+                candidate.contact_b_id
+            } else {
+                candidate.contact_a_id
+            };
+            let (Some(primary), Some(secondary)) = (
+                load_merge_side(store, primary_id)?,
+                load_merge_side(store, secondary_id)?,
+            ) else {
+                app.set_error("merge candidate references a missing contact");
+                return Ok(());
+            };
+            if let Mode::MergeDetail(detail) = &mut app.mode {
+                if detail.candidate_id == candidate_id {
+                    detail.set_sides(primary_id, &primary, secondary_id, &secondary, &app.clock);
+                }
             }
-            app.apply_merge_candidates(items);
             app.clear_error();
         }
         Action::LoadMergePicker(primary_id) => {
@@ -150,12 +269,12 @@ pub fn execute_action(app: &mut App, store: &Store, action: Action) -> Result<()
             }
             app.clear_error();
         }
-        Action::CreateContact(input, emails) => {
-            let now = now_utc();
+        Action::CreateContact(input, emails, tags) => {
+            let now = app.clock.now_utc();
             let contact = store.contacts().create_with_emails_and_tags(
                 now,
                 input,
-                Vec::new(),
+                tags,
                 emails,
                 Some("tui"),
             )?;
@@ -164,7 +283,7 @@ pub fn execute_action(app: &mut App, store: &Store, action: Action) -> Result<()
             app.enqueue(Action::LoadList);
         }
         Action::UpdateContact(id, update, emails) => {
-            let now = now_utc();
+            let now = app.clock.now_utc();
             let existing = store.emails().list_emails_for_contact(&id)?;
             let normalized_existing = normalize_email_list(existing);
             let normalized_next = normalize_email_list(emails.clone());
@@ -179,6 +298,7 @@ pub fn execute_action(app: &mut App, store: &Store, action: Action) -> Result<()
             };
             let contact = store
                 .contacts()
+                .with_origin(AUDIT_ORIGIN)
                 .update_with_email_ops(now, id, update, email_ops)?;
             app.set_status(format!("Updated {}", contact.display_name));
             app.pending_select = Some(contact.id);
@@ -187,15 +307,54 @@ pub fn execute_action(app: &mut App, store: &Store, action: Action) -> Result<()
         }
         Action::AddInteraction(input) => {
             let contact_id = input.contact_id;
-            let now = now_utc();
-            let interaction = if app.auto_reschedule_interactions {
-                store.interactions().add_with_reschedule(now, input, true)?
-            } else {
-                store.interactions().add(input)?
+            let now = app.clock.now_utc();
+            let outcome = store.interactions().add_unless_recent(
+                now,
+                input,
+                app.auto_reschedule_interactions,
+                app.interaction_dedupe_window_minutes * 60,
+            )?;
+            let verb = match outcome {
+                InteractionAdd::Added(_) => "Added",
+                InteractionAdd::AlreadyRecorded(_) => "Already recorded",
             };
             app.set_status(format!(
-                "Added interaction ({})",
-                format_interaction_kind(&interaction.kind)
+                "{verb} interaction ({})",
+                outcome.interaction().kind.label()
+            ));
+            app.enqueue(Action::LoadDetail(contact_id));
+            app.enqueue(Action::LoadList);
+        }
+        Action::UpdateInteraction {
+            contact_id,
+            interaction_id,
+            update,
+        } => {
+            let interaction = store.interactions().update(
+                app.clock.now_utc(),
+                interaction_id,
+                update,
+                app.auto_reschedule_interactions,
+            )?;
+            app.set_status(format!(
+                "Updated interaction ({})",
+                interaction.kind.label()
+            ));
+            app.enqueue(Action::LoadDetail(contact_id));
+            app.enqueue(Action::LoadList);
+        }
+        Action::DeleteInteraction {
+            contact_id,
+            interaction_id,
+        } => {
+            let interaction = store.interactions().delete(
+                app.clock.now_utc(),
+                interaction_id,
+                app.auto_reschedule_interactions,
+            )?;
+            app.set_status(format!(
+                "Deleted interaction ({})",
+                interaction.kind.label()
             ));
             app.enqueue(Action::LoadDetail(contact_id));
             app.enqueue(Action::LoadList);
@@ -209,6 +368,24 @@ pub fn execute_action(app: &mut App, store: &Store, action: Action) -> Result<()
             app.enqueue(Action::LoadDetail(contact_id));
             app.enqueue(Action::LoadList);
         }
+        Action::AddContactDate(input) => {
+            let contact_id = input.contact_id;
+            let date = store.contact_dates().upsert(app.clock.now_utc(), input)?;
+            app.set_status(format!(
+                "Saved {} {}",
+                date.kind.as_str(),
+                format_date_parts(date.month, date.day, date.year)
+            ));
+            app.enqueue(Action::LoadDetail(contact_id));
+        }
+        Action::DeleteContactDate {
+            contact_id,
+            date_id,
+        } => {
+            store.contact_dates().delete(date_id)?;
+            app.set_status("Removed date".to_string());
+            app.enqueue(Action::LoadDetail(contact_id));
+        }
         Action::ScheduleContact(contact_id, timestamp) => {
             let update = ContactUpdate {
                 display_name: None,
@@ -220,9 +397,14 @@ pub fn execute_action(app: &mut App, store: &Store, action: Action) -> Result<()
                 next_touchpoint_at: Some(Some(timestamp)),
                 cadence_days: None,
                 archived_at: None,
+                organization: None,
+                source: None,
             };
-            let now = now_utc();
-            store.contacts().update(now, contact_id, update)?;
+            let now = app.clock.now_utc();
+            store
+                .contacts()
+                .with_origin(AUDIT_ORIGIN)
+                .update(now, contact_id, update)?;
             app.set_status("Scheduled touchpoint".to_string());
             app.enqueue(Action::LoadDetail(contact_id));
             app.enqueue(Action::LoadList);
@@ -238,40 +420,72 @@ pub fn execute_action(app: &mut App, store: &Store, action: Action) -> Result<()
                 next_touchpoint_at: Some(None),
                 cadence_days: None,
                 archived_at: None,
+                organization: None,
+                source: None,
             };
-            let now = now_utc();
-            store.contacts().update(now, contact_id, update)?;
+            let now = app.clock.now_utc();
+            store
+                .contacts()
+                .with_origin(AUDIT_ORIGIN)
+                .update(now, contact_id, update)?;
             app.set_status("Cleared schedule".to_string());
             app.enqueue(Action::LoadDetail(contact_id));
             app.enqueue(Action::LoadList);
         }
-        Action::ArchiveContact(contact_id) => {
-            let now = now_utc();
-            let contact = store.contacts().archive(now, contact_id)?;
+        Action::ArchiveContact(contact_id, reason) => {
+            let now = app.clock.now_utc();
+            let contact = store
+                .contacts()
+                .with_origin(AUDIT_ORIGIN)
+                .archive_with_reason(now, contact_id, reason.as_deref())?;
             app.set_status(format!("Archived {}", contact.display_name));
             app.enqueue(Action::LoadDetail(contact_id));
             app.enqueue(Action::LoadList);
         }
         Action::UnarchiveContact(contact_id) => {
-            let now = now_utc();
-            let contact = store.contacts().unarchive(now, contact_id)?;
+            let now = app.clock.now_utc();
+            let contact = store
+                .contacts()
+                .with_origin(AUDIT_ORIGIN)
+                .unarchive(now, contact_id)?;
             app.set_status(format!("Unarchived {}", contact.display_name));
             app.enqueue(Action::LoadDetail(contact_id));
             app.enqueue(Action::LoadList);
         }
+        Action::SetPriority(contact_id, priority) => {
+            let now = app.clock.now_utc();
+            let contact = store
+                .contacts()
+                .with_origin(AUDIT_ORIGIN)
+                .set_priority(now, contact_id, priority)?;
+            let verb = if is_starred(priority) {
+                "Starred"
+            } else {
+                "Unstarred"
+            };
+            app.set_status(format!("{verb} {}", contact.display_name));
+            app.enqueue(Action::LoadDetail(contact_id));
+            app.enqueue(Action::LoadList);
+        }
         Action::ApplyMerge {
             primary_id,
             secondary_id,
+            fields,
         } => {
-            let now = now_utc();
+            let now = app.clock.now_utc();
             let refresh_merges = matches!(app.mode, Mode::MergeList);
             let tx = store.connection().unchecked_transaction()?;
-            let merged = knotter_store::repo::ContactsRepo::new(&tx).merge_contacts(
-                now,
-                primary_id,
-                secondary_id,
-                knotter_store::repo::ContactMergeOptions::default(),
-            )?;
+            let merged = knotter_store::repo::ContactsRepo::new(&tx)
+                .with_origin(AUDIT_ORIGIN)
+                .merge_contacts(
+                    now,
+                    primary_id,
+                    secondary_id,
+                    knotter_store::repo::ContactMergeOptions {
+                        fields,
+                        ..Default::default()
+                    },
+                )?;
             tx.commit()?;
             app.set_status(format!("Merged {} into {}", secondary_id, primary_id));
             app.pending_select = Some(merged.id);
@@ -282,7 +496,7 @@ pub fn execute_action(app: &mut App, store: &Store, action: Action) -> Result<()
             app.enqueue(Action::LoadDetail(merged.id));
         }
         Action::ApplyAllMerges { candidate_ids } => {
-            let now = now_utc();
+            let now = app.clock.now_utc();
             let mut applied = 0;
             let mut skipped = 0;
             let mut failed = 0;
@@ -306,12 +520,14 @@ pub fn execute_action(app: &mut App, store: &Store, action: Action) -> Result<()
                 } else {
                     candidate.contact_a_id
                 };
-                let merged = knotter_store::repo::ContactsRepo::new(&tx).merge_contacts(
-                    now,
-                    primary_id,
-                    secondary_id,
-                    knotter_store::repo::ContactMergeOptions::default(),
-                );
+                let merged = knotter_store::repo::ContactsRepo::new(&tx)
+                    .with_origin(AUDIT_ORIGIN)
+                    .merge_contacts(
+                        now,
+                        primary_id,
+                        secondary_id,
+                        knotter_store::repo::ContactMergeOptions::default(),
+                    );
                 match merged {
                     Ok(merged) => {
                         tx.commit()?;
@@ -347,7 +563,7 @@ pub fn execute_action(app: &mut App, store: &Store, action: Action) -> Result<()
             app.enqueue(Action::LoadMerges);
         }
         Action::DismissMerge(candidate_id) => {
-            let now = now_utc();
+            let now = app.clock.now_utc();
             store.merge_candidates().dismiss(now, candidate_id)?;
             app.set_status("Dismissed merge candidate".to_string());
             app.enqueue(Action::LoadMerges);
@@ -369,47 +585,184 @@ fn normalize_email_list(emails: Vec<String>) -> Vec<String> {
     normalized
 }
 
-fn build_list_items(
-    contacts: Vec<knotter_core::domain::Contact>,
-    tags: HashMap<ContactId, Vec<String>>,
-    now: i64,
-    soon_days: i64,
-    offset: FixedOffset,
-) -> Result<Vec<ContactListItemDto>> {
-    let mut items = Vec::with_capacity(contacts.len());
-    for contact in contacts {
-        let due_state = compute_due_state(now, contact.next_touchpoint_at, soon_days, offset)?;
-        let tags = tags.get(&contact.id).cloned().unwrap_or_default();
-        items.push(ContactListItemDto {
-            id: contact.id,
-            display_name: contact.display_name,
-            due_state,
-            next_touchpoint_at: contact.next_touchpoint_at,
-            archived_at: contact.archived_at,
-            tags,
+fn list_query(app: &App) -> ContactListQuery {
+    ContactListQuery {
+        filter: app.filter.clone(),
+        archived: app.archive_view.selector(),
+        sort: match app.effective_archive_view() {
+            ArchiveView::Archived => ContactSort::Archived,
+            ArchiveView::Active | ArchiveView::All => ContactSort::Due,
+        },
+        ..ContactListQuery::new(app.soon_days)
+    }
+}
+
+/// Due bucket counts for the header, ignoring any `due:` term so every bucket stays visible.
+fn load_due_counts(app: &App, store: &Store) -> Result<Vec<(DueState, usize)>> {
+    let mut query = list_query(app).to_contact_query()?;
+    query.due = None;
+    Ok(store.contacts().count_by_due_state(
+        &query,
+        app.clock.now_utc(),
+        app.soon_days,
+        app.clock.local_offset(),
+    )?)
+}
+
+/// Fetches one page of the contact list; fetches one extra row to learn whether
+/// another page exists.
+fn load_list_page(
+    app: &App,
+    store: &Store,
+    offset: usize,
+    limit: usize,
+) -> Result<(Vec<ContactListItemDto>, bool)> {
+    let query = ContactListQuery {
+        page: Some(ListPage {
+            limit: limit + 1,
+            offset,
+        }),
+        ..list_query(app)
+    };
+    let mut items =
+        store
+            .contacts()
+            .list_view(&query, app.clock.now_utc(), app.clock.local_offset())?;
+    let has_more = items.len() > limit;
+    items.truncate(limit);
+    Ok((items, has_more))
+}
+
+fn load_merge_page(
+    store: &Store,
+    offset: usize,
+    limit: usize,
+) -> Result<(Vec<crate::app::MergeCandidateView>, bool)> {
+    let query = MergeCandidateQuery {
+        page: Some(ListPage {
+            limit: limit + 1,
+            offset,
+        }),
+        ..MergeCandidateQuery::status(MergeCandidateStatus::Open)
+    };
+    let mut candidates = store.merge_candidates().list(&query)?;
+    let has_more = candidates.len() > limit;
+    candidates.truncate(limit);
+    let mut items = Vec::with_capacity(candidates.len());
+    for candidate in candidates {
+        let auto_merge_safe = candidate.auto_merge_safe();
+        let contact_a_name = store
+            .contacts()
+            .get(candidate.contact_a_id)?
+            .map(|contact| contact.display_name)
+            .unwrap_or_else(|| "<missing contact>".to_string());
+        let contact_b_name = store
+            .contacts()
+            .get(candidate.contact_b_id)?
+            .map(|contact| contact.display_name)
+            .unwrap_or_else(|| "<missing contact>".to_string());
+        items.push(crate::app::MergeCandidateView {
+            id: candidate.id,
+            reason: candidate.reason,
+            auto_merge_safe,
+            contact_a_id: candidate.contact_a_id,
+            contact_b_id: candidate.contact_b_id,
+            preferred_contact_id: candidate.preferred_contact_id,
+            contact_a_name,
+            contact_b_name,
         });
     }
-    Ok(items)
+    Ok((items, has_more))
 }
 
-fn load_detail(store: &Store, contact_id: ContactId) -> Result<Option<ContactDetailDto>> {
+fn load_merge_side(store: &Store, contact_id: ContactId) -> Result<Option<MergeSide>> {
+    let Some(contact) = store.contacts().get(contact_id)? else {
+        return Ok(None);
+    };
+    let mut emails = store.emails().list_emails_for_contact(&contact_id)?;
+    if let Some(primary) = contact.email.as_deref() {
+        if let Some(pos) = emails.iter().position(|email| email == primary) {
+            let primary = emails.remove(pos);
+            emails.insert(0, primary);
+        }
+    }
+    let tags = store
+        .tags()
+        .list_for_contact(&contact_id.to_string())?
+        .into_iter()
+        .map(|tag| tag.name.as_str().to_string())
+        .collect();
+    Ok(Some(MergeSide {
+        display_name: contact.display_name,
+        emails,
+        phone: contact.phone,
+        handle: contact.handle,
+        timezone: contact.timezone,
+        cadence_days: contact.cadence_days,
+        next_touchpoint_at: contact.next_touchpoint_at,
+        tags,
+    }))
+}
+
+/// Takes the interaction's attachments out of `attachments`, as loaded by
+/// `InteractionAttachmentsRepo::list_for_contact`.
+fn interaction_dto(
+    interaction: knotter_core::domain::Interaction,
+    attachments: &mut HashMap<InteractionId, Vec<InteractionAttachment>>,
+) -> InteractionDto {
+    InteractionDto {
+        id: interaction.id,
+        occurred_at: interaction.occurred_at,
+        kind: interaction.kind.label(),
+        note: interaction.note,
+        follow_up_at: interaction.follow_up_at,
+        direction: interaction.direction,
+        attachments: attachments
+            .remove(&interaction.id)
+            .unwrap_or_default()
+            .into_iter()
+            .map(AttachmentDto::from)
+            .collect(),
+    }
+}
+
+fn load_detail(
+    store: &Store,
+    contact_id: ContactId,
+    interaction_limit: usize,
+    now_utc: i64,
+) -> Result<Option<ContactDetailDto>> {
     let contact = match store.contacts().get(contact_id)? {
         Some(contact) => contact,
         None => return Ok(None),
     };
     let tags = store.tags().list_for_contact(&contact_id.to_string())?;
     let emails = store.emails().list_emails_for_contact(&contact_id)?;
-    let interactions = store.interactions().list_for_contact(contact_id, 50, 0)?;
-    let recent_interactions = interactions
+    let interactions_repo = store.interactions();
+    let mut attachments = store
+        .interaction_attachments()
+        .list_for_contact(contact_id)?;
+    let recent_interactions = interactions_repo
+        .list_for_contact(contact_id, interaction_limit as i64, 0)?
         .into_iter()
-        .map(|interaction| InteractionDto {
-            id: interaction.id,
-            occurred_at: interaction.occurred_at,
-            kind: format_interaction_kind(&interaction.kind),
-            note: interaction.note,
-            follow_up_at: interaction.follow_up_at,
-        })
+        .map(|interaction| interaction_dto(interaction, &mut attachments))
         .collect();
+    let interactions_total =
+        interactions_repo.count_for_contact_in_range(contact_id, Default::default())?;
+    let archive_reason = store.contacts().archive_reason(contact_id)?;
+    let origin = match store.contact_origins().get(contact_id)? {
+        Some(origin) => {
+            let met_via_name = match origin.met_via {
+                Some(via) => store
+                    .contacts()
+                    .get(via)?
+                    .map(|contact| contact.display_name),
+                None => None,
+            };
+            Some(ContactOriginDto::from_origin(origin, met_via_name))
+        }
+        None => None,
+    };
     let dates = store.contact_dates().list_for_contact(contact_id)?;
     let date_dtos = dates
         .into_iter()
@@ -420,6 +773,7 @@ fn load_detail(store: &Store, contact_id: ContactId) -> Result<Option<ContactDet
             month: date.month,
             day: date.day,
             year: date.year,
+            remind_days_before: date.remind_days_before,
         })
         .collect();
     let tags = tags
@@ -434,13 +788,32 @@ fn load_detail(store: &Store, contact_id: ContactId) -> Result<Option<ContactDet
         phone: contact.phone,
         handle: contact.handle,
         timezone: contact.timezone,
+        organization: contact.organization,
         next_touchpoint_at: contact.next_touchpoint_at,
         cadence_days: contact.cadence_days,
         created_at: contact.created_at,
         updated_at: contact.updated_at,
         archived_at: contact.archived_at,
+        archive_reason,
+        muted_until: store.contacts().active_mute(contact_id, now_utc)?,
+        priority: store.contacts().priority(contact_id)?,
         tags,
+        aliases: store.contact_aliases().list_for_contact(contact_id)?,
+        origin,
         dates: date_dtos,
         recent_interactions,
+        interactions_total,
+        provenance: Vec::new(),
+        sync_history: store
+            .sync_log()
+            .list_for_contact(contact_id)?
+            .into_iter()
+            .map(|entry| SyncHistoryDto {
+                source: entry.source,
+                fields: entry.fields,
+                synced_at: entry.synced_at,
+            })
+            .collect(),
+        last_touch_context: store.last_touch().list_for_contact(contact_id)?,
     }))
 }
@@ -1,13 +1,94 @@
 use std::collections::VecDeque;
+use std::time::{Duration, Instant};
 
 use crossterm::event::{KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
-use knotter_core::domain::{ContactId, TagName};
-use knotter_core::filter::{parse_filter, ContactFilter};
-use knotter_core::rules::ensure_future_timestamp_with_precision;
+use knotter_config::ContactDefaultsConfig;
+use knotter_core::domain::{
+    canonical_phone, normalize_contact_date_label, toggle_star, validate_contact_date_parts,
+    ContactDateKind, ContactId, InteractionId, TagName,
+};
+use knotter_core::dto::ContactDateDto;
+use knotter_core::filter::{parse_filter, ArchivedSelector, ContactFilter, FilterExpr};
+use knotter_core::rules::{ensure_future_timestamp_with_precision, DueState};
+use knotter_core::time::Clock;
+use knotter_store::repo::{ContactDateNew, MergeFieldOverrides, MergePreference};
 
 use crate::actions::Action;
 
 const LIST_EMPTY: &str = "No contacts. Press 'a' to add one.";
+/// Rows fetched per list page.
+pub const LIST_PAGE_SIZE: usize = 200;
+pub const READONLY_ERROR: &str = "read-only mode: editing is disabled";
+/// Fetch the next page once the selection is this close to the loaded end.
+const LIST_PREFETCH_MARGIN: usize = 20;
+/// Interactions fetched per detail page; older ones load on demand.
+pub const INTERACTION_PAGE_SIZE: usize = 50;
+/// Fetch the next interaction page once the selection is this close to the end.
+const INTERACTION_PREFETCH_MARGIN: usize = 5;
+/// Pause in filter typing after which the list is re-queried.
+pub const FILTER_DEBOUNCE: Duration = Duration::from_millis(150);
+/// Open merge candidates fetched per page.
+pub const MERGE_PAGE_SIZE: usize = 100;
+/// Fetch the next merge page once the selection is this close to the loaded end.
+const MERGE_PREFETCH_MARGIN: usize = 10;
+/// Status and error messages kept for the `!` log overlay; older ones are dropped.
+pub const ACTION_LOG_CAPACITY: usize = 200;
+
+/// Which contacts the list shows, cycled with `v`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ArchiveView {
+    #[default]
+    Active,
+    Archived,
+    All,
+}
+
+impl ArchiveView {
+    pub fn next(self) -> Self {
+        match self {
+            ArchiveView::Active => ArchiveView::Archived,
+            ArchiveView::Archived => ArchiveView::All,
+            ArchiveView::All => ArchiveView::Active,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ArchiveView::Active => "active",
+            ArchiveView::Archived => "archived",
+            ArchiveView::All => "all",
+        }
+    }
+
+    pub fn selector(self) -> Option<ArchivedSelector> {
+        match self {
+            ArchiveView::Active => Some(ArchivedSelector::Active),
+            ArchiveView::Archived => Some(ArchivedSelector::Archived),
+            ArchiveView::All => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogSeverity {
+    Info,
+    Error,
+}
+
+/// A status or error message as it was shown in the footer.
+#[derive(Debug, Clone)]
+pub struct LogEntry {
+    pub at: i64,
+    pub severity: LogSeverity,
+    pub message: String,
+}
+
+/// The `!` overlay; `scroll` counts entries from the newest.
+#[derive(Debug, Clone)]
+pub struct ActionLogView {
+    pub scroll: usize,
+    pub previous: Box<Mode>,
+}
 
 #[derive(Debug, Clone)]
 pub enum Mode {
@@ -15,13 +96,18 @@ pub enum Mode {
     FilterEditing,
     Detail(ContactId),
     MergeList,
+    MergeDetail(MergeDetail),
     ModalMergePicker(MergePicker),
     ModalAddContact(ContactForm),
     ModalEditContact(ContactForm),
     ModalAddNote(NoteForm),
+    ModalEditNote(NoteForm),
     ModalEditTags(TagEditor),
+    ModalEditDates(DateEditor),
     ModalSchedule(ScheduleForm),
+    ModalArchive(ArchiveForm),
     Confirm(ConfirmState),
+    ActionLog(ActionLogView),
 }
 
 #[derive(Debug, Clone)]
@@ -32,19 +118,48 @@ pub struct App {
     pub filter_input: String,
     pub filter: Option<ContactFilter>,
     pub filter_error: Option<String>,
+    /// When the filter being typed is re-queried; pushed back by each keystroke.
+    pub filter_requery_at: Option<Instant>,
     pub contacts: Vec<knotter_core::dto::ContactListItemDto>,
     pub selected: usize,
+    pub list_has_more: bool,
+    list_loading_more: bool,
+    /// Contacts per due bucket under the current filter, ignoring its `due:` term.
+    pub due_counts: Vec<(DueState, usize)>,
+    /// Filter text to restore with `0` while a number-key quick filter is active.
+    quick_filter_base: Option<String>,
     pub detail: Option<knotter_core::dto::ContactDetailDto>,
     pub detail_scroll: usize,
+    /// Whether j/k, `e`, and `d` act on the interactions pane in the detail view.
+    pub interactions_focused: bool,
+    pub interaction_selected: usize,
+    interactions_loading_more: bool,
+    /// Whether the detail view lists sync history entries or only counts them.
+    pub sync_history_expanded: bool,
     pub status: Option<String>,
     pub error: Option<String>,
+    /// Every status and error set, oldest first, capped at `ACTION_LOG_CAPACITY`.
+    pub action_log: VecDeque<LogEntry>,
     pub soon_days: i64,
     pub default_cadence_days: Option<i32>,
+    pub contact_defaults: ContactDefaultsConfig,
+    /// Rewrite parseable phones into canonical form when a contact form is saved.
+    pub normalize_phones: bool,
     pub auto_reschedule_interactions: bool,
-    pub show_archived: bool,
+    /// Window for reporting a repeated interaction instead of adding it; 0 disables.
+    pub interaction_dedupe_window_minutes: i64,
+    pub colorize_rows: bool,
+    /// Set with `v`; an `archived:` filter term takes precedence.
+    pub archive_view: ArchiveView,
+    /// Set by `--readonly`; mutating keybindings are refused instead of opening forms.
+    pub readonly: bool,
+    /// Zone and pinned day for due buckets, typed dates, and displayed dates.
+    pub clock: Clock,
     pub empty_hint: &'static str,
     pub merge_candidates: Vec<MergeCandidateView>,
     pub merge_selected: usize,
+    pub merge_has_more: bool,
+    merge_loading_more: bool,
     actions: VecDeque<Action>,
     pub(crate) pending_select: Option<ContactId>,
 }
@@ -66,6 +181,7 @@ impl App {
         soon_days: i64,
         default_cadence_days: Option<i32>,
         auto_reschedule_interactions: bool,
+        colorize_rows: bool,
     ) -> Self {
         let mut app = Self {
             mode: Mode::List,
@@ -74,19 +190,37 @@ impl App {
             filter_input: String::new(),
             filter: None,
             filter_error: None,
+            filter_requery_at: None,
             contacts: Vec::new(),
             selected: 0,
+            list_has_more: false,
+            list_loading_more: false,
+            due_counts: Vec::new(),
+            quick_filter_base: None,
             detail: None,
             detail_scroll: 0,
+            interactions_focused: false,
+            interaction_selected: 0,
+            interactions_loading_more: false,
+            sync_history_expanded: false,
             status: None,
             error: None,
+            action_log: VecDeque::new(),
             soon_days,
             default_cadence_days,
+            contact_defaults: ContactDefaultsConfig::default(),
+            normalize_phones: false,
             auto_reschedule_interactions,
-            show_archived: false,
+            interaction_dedupe_window_minutes: 0,
+            colorize_rows,
+            archive_view: ArchiveView::Active,
+            readonly: false,
+            clock: Clock::default(),
             empty_hint: LIST_EMPTY,
             merge_candidates: Vec::new(),
             merge_selected: 0,
+            merge_has_more: false,
+            merge_loading_more: false,
             actions: VecDeque::new(),
             pending_select: None,
         };
@@ -94,6 +228,31 @@ impl App {
         app
     }
 
+    pub fn with_readonly(mut self, readonly: bool) -> Self {
+        self.readonly = readonly;
+        self
+    }
+
+    pub fn with_clock(mut self, clock: Clock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    pub fn with_contact_defaults(mut self, defaults: ContactDefaultsConfig) -> Self {
+        self.contact_defaults = defaults;
+        self
+    }
+
+    pub fn with_normalize_phones(mut self, normalize_phones: bool) -> Self {
+        self.normalize_phones = normalize_phones;
+        self
+    }
+
+    pub fn with_interaction_dedupe_window(mut self, minutes: i64) -> Self {
+        self.interaction_dedupe_window_minutes = minutes;
+        self
+    }
+
     pub fn enqueue(&mut self, action: Action) {
         self.actions.push_back(action);
     }
@@ -102,8 +261,30 @@ impl App {
         self.actions.pop_front()
     }
 
+    /// Runs the debounced filter re-query once typing has paused.
+    pub fn tick(&mut self, now: Instant) {
+        if self.filter_requery_at.is_some_and(|due| now >= due) {
+            self.filter_requery_at = None;
+            self.apply_filter_input();
+        }
+    }
+
+    /// The next time `tick` has work to do, so the event loop can wake for it.
+    pub fn next_deadline(&self) -> Option<Instant> {
+        self.filter_requery_at
+    }
+
     pub fn set_error(&mut self, message: impl Into<String>) {
-        self.error = Some(message.into());
+        let message = message.into();
+        self.log(LogSeverity::Error, message.clone());
+        self.error = Some(message);
+    }
+
+    /// Shows a failed action's error; the log entry also names the action.
+    pub fn set_action_error(&mut self, action: &str, message: impl Into<String>) {
+        let message = message.into();
+        self.log(LogSeverity::Error, format!("{action}: {message}"));
+        self.error = Some(message);
     }
 
     pub fn clear_error(&mut self) {
@@ -111,16 +292,41 @@ impl App {
     }
 
     pub fn set_status(&mut self, message: impl Into<String>) {
-        self.status = Some(message.into());
+        let message = message.into();
+        self.log(LogSeverity::Info, message.clone());
+        self.status = Some(message);
+    }
+
+    fn log(&mut self, severity: LogSeverity, message: String) {
+        if self.action_log.len() == ACTION_LOG_CAPACITY {
+            self.action_log.pop_front();
+        }
+        self.action_log.push_back(LogEntry {
+            at: knotter_core::time::now_utc(),
+            severity,
+            message,
+        });
     }
 
     pub fn selected_contact_id(&self) -> Option<ContactId> {
         self.contacts.get(self.selected).map(|c| c.id)
     }
 
-    pub fn apply_list(&mut self, items: Vec<knotter_core::dto::ContactListItemDto>) {
+    pub fn apply_list(
+        &mut self,
+        items: Vec<knotter_core::dto::ContactListItemDto>,
+        has_more: bool,
+    ) {
+        // Follow the selected contact to its new row; if it is gone, the index
+        // stays put (clamped) so the cursor does not jump to the top.
+        let target = self
+            .pending_select
+            .take()
+            .or_else(|| self.selected_contact_id());
         self.contacts = items;
-        if let Some(target) = self.pending_select.take() {
+        self.list_has_more = has_more;
+        self.list_loading_more = false;
+        if let Some(target) = target {
             if let Some(pos) = self.contacts.iter().position(|item| item.id == target) {
                 self.selected = pos;
             }
@@ -130,18 +336,148 @@ impl App {
         }
     }
 
+    pub fn append_list(
+        &mut self,
+        items: Vec<knotter_core::dto::ContactListItemDto>,
+        has_more: bool,
+    ) {
+        self.contacts.extend(items);
+        self.list_has_more = has_more;
+        self.list_loading_more = false;
+    }
+
     pub fn apply_detail(&mut self, detail: knotter_core::dto::ContactDetailDto) {
-        self.detail_scroll = 0;
+        let same_contact = self.detail.as_ref().map(|current| current.id) == Some(detail.id);
+        if !same_contact {
+            self.interactions_focused = false;
+            self.interaction_selected = 0;
+            self.sync_history_expanded = false;
+        }
+        let count = detail.recent_interactions.len();
+        if count == 0 {
+            self.interactions_focused = false;
+        }
+        self.interaction_selected = self.interaction_selected.min(count.saturating_sub(1));
+        self.interactions_loading_more = false;
+        if let Mode::ModalEditDates(editor) = &mut self.mode {
+            if editor.contact_id == detail.id {
+                editor.set_dates(detail.dates.clone());
+            }
+        }
         self.detail = Some(detail);
+        self.detail_scroll = if self.interactions_focused {
+            self.selected_interaction_line()
+        } else {
+            0
+        };
+    }
+
+    /// Appends an older page of interactions fetched by `LoadMoreInteractions`.
+    pub fn append_interactions(
+        &mut self,
+        contact_id: ContactId,
+        items: Vec<knotter_core::dto::InteractionDto>,
+    ) {
+        self.interactions_loading_more = false;
+        if let Some(detail) = self
+            .detail
+            .as_mut()
+            .filter(|detail| detail.id == contact_id)
+        {
+            if items.is_empty() {
+                // History shrank since the first page; stop asking for more.
+                detail.interactions_total = detail.recent_interactions.len();
+            }
+            detail.recent_interactions.extend(items);
+        }
     }
 
-    pub fn apply_merge_candidates(&mut self, items: Vec<MergeCandidateView>) {
+    /// Loaded interaction count for the open detail, when older ones remain unfetched.
+    pub fn interactions_loaded_partially(&self) -> Option<(usize, usize)> {
+        let detail = self.detail.as_ref()?;
+        let loaded = detail.recent_interactions.len();
+        (loaded < detail.interactions_total).then_some((loaded, detail.interactions_total))
+    }
+
+    fn maybe_load_more_interactions(&mut self) {
+        if self.interactions_loading_more {
+            return;
+        }
+        let Some(detail) = &self.detail else {
+            return;
+        };
+        let loaded = detail.recent_interactions.len();
+        if loaded >= detail.interactions_total {
+            return;
+        }
+        let near_end = if self.interactions_focused {
+            self.interaction_selected + INTERACTION_PREFETCH_MARGIN >= loaded
+        } else {
+            let total_lines: usize = detail
+                .recent_interactions
+                .iter()
+                .map(|interaction| 2 + usize::from(!interaction.note.trim().is_empty()))
+                .sum();
+            self.detail_scroll + INTERACTION_PREFETCH_MARGIN * 3 >= total_lines
+        };
+        if near_end {
+            let contact_id = detail.id;
+            self.interactions_loading_more = true;
+            self.enqueue(Action::LoadMoreInteractions(contact_id));
+        }
+    }
+
+    pub fn selected_interaction(&self) -> Option<&knotter_core::dto::InteractionDto> {
+        self.detail
+            .as_ref()
+            .and_then(|detail| detail.recent_interactions.get(self.interaction_selected))
+    }
+
+    /// First line of the selected interaction in the interactions pane; each entry
+    /// renders as a header, an optional note line, and a blank separator.
+    fn selected_interaction_line(&self) -> usize {
+        let Some(detail) = &self.detail else {
+            return 0;
+        };
+        detail
+            .recent_interactions
+            .iter()
+            .take(self.interaction_selected)
+            .map(|interaction| 2 + usize::from(!interaction.note.trim().is_empty()))
+            .sum()
+    }
+
+    fn move_interaction_selection(&mut self, delta: i32) {
+        let count = self
+            .detail
+            .as_ref()
+            .map(|detail| detail.recent_interactions.len())
+            .unwrap_or(0);
+        if count == 0 {
+            return;
+        }
+        let next = (self.interaction_selected as i64 + i64::from(delta)).clamp(0, count as i64 - 1);
+        self.interaction_selected = next as usize;
+        self.detail_scroll = self.selected_interaction_line();
+        self.maybe_load_more_interactions();
+    }
+
+    pub fn apply_merge_candidates(&mut self, items: Vec<MergeCandidateView>, has_more: bool) {
         self.merge_candidates = items;
+        self.merge_has_more = has_more;
+        self.merge_loading_more = false;
         if self.merge_selected >= self.merge_candidates.len() {
             self.merge_selected = self.merge_candidates.len().saturating_sub(1);
         }
     }
 
+    /// Appends the next page of open candidates fetched by `LoadMoreMerges`.
+    pub fn append_merge_candidates(&mut self, items: Vec<MergeCandidateView>, has_more: bool) {
+        self.merge_candidates.extend(items);
+        self.merge_has_more = has_more;
+        self.merge_loading_more = false;
+    }
+
     pub fn empty_hint(&self) -> String {
         self.empty_hint.to_string()
     }
@@ -180,6 +516,25 @@ impl App {
             return;
         }
 
+        if key.code == KeyCode::Char('!')
+            && matches!(
+                self.mode,
+                Mode::List | Mode::Detail(_) | Mode::MergeList | Mode::MergeDetail(_)
+            )
+        {
+            let previous = std::mem::replace(&mut self.mode, Mode::List);
+            self.mode = Mode::ActionLog(ActionLogView {
+                scroll: 0,
+                previous: Box::new(previous),
+            });
+            return;
+        }
+
+        if self.readonly && self.is_mutating_key(key) {
+            self.set_error(READONLY_ERROR);
+            return;
+        }
+
         let mut mode = std::mem::replace(&mut self.mode, Mode::List);
         match &mut mode {
             Mode::List => {
@@ -202,6 +557,11 @@ impl App {
                     mode = next;
                 }
             }
+            Mode::MergeDetail(detail) => {
+                if let Some(next) = self.handle_merge_detail_key(detail, key) {
+                    mode = next;
+                }
+            }
             Mode::ModalMergePicker(picker) => {
                 if let Some(next) = self.handle_merge_picker_key(picker, key) {
                     mode = next;
@@ -212,7 +572,7 @@ impl App {
                     mode = next;
                 }
             }
-            Mode::ModalAddNote(form) => {
+            Mode::ModalAddNote(form) | Mode::ModalEditNote(form) => {
                 if let Some(next) = self.handle_note_form_key(form, key) {
                     mode = next;
                 }
@@ -222,20 +582,69 @@ impl App {
                     mode = next;
                 }
             }
+            Mode::ModalEditDates(editor) => {
+                if let Some(next) = self.handle_date_editor_key(editor, key) {
+                    mode = next;
+                }
+            }
             Mode::ModalSchedule(form) => {
                 if let Some(next) = self.handle_schedule_form_key(form, key) {
                     mode = next;
                 }
             }
+            Mode::ModalArchive(form) => {
+                if let Some(next) = self.handle_archive_form_key(form, key) {
+                    mode = next;
+                }
+            }
             Mode::Confirm(state) => {
                 if let Some(next) = self.handle_confirm_key(state, key) {
                     mode = next;
                 }
             }
+            Mode::ActionLog(view) => {
+                if let Some(next) = self.handle_action_log_key(view, key) {
+                    mode = next;
+                }
+            }
         }
         self.mode = mode;
     }
 
+    fn handle_action_log_key(&mut self, view: &mut ActionLogView, key: KeyEvent) -> Option<Mode> {
+        let last = self.action_log.len().saturating_sub(1);
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('!') => {
+                return Some(std::mem::replace(&mut *view.previous, Mode::List));
+            }
+            KeyCode::Down | KeyCode::Char('j') => view.scroll = (view.scroll + 1).min(last),
+            KeyCode::Up | KeyCode::Char('k') => view.scroll = view.scroll.saturating_sub(1),
+            KeyCode::PageDown => view.scroll = (view.scroll + 10).min(last),
+            KeyCode::PageUp => view.scroll = view.scroll.saturating_sub(10),
+            KeyCode::Home | KeyCode::Char('g') => view.scroll = 0,
+            KeyCode::End | KeyCode::Char('G') => view.scroll = last,
+            _ => {}
+        }
+        None
+    }
+
+    /// Keys that open an editing form or confirm a write in the current mode.
+    fn is_mutating_key(&self, key: KeyEvent) -> bool {
+        let KeyCode::Char(ch) = key.code else {
+            return matches!(self.mode, Mode::MergeDetail(_)) && key.code == KeyCode::Enter;
+        };
+        match self.mode {
+            Mode::List => matches!(ch, 'a' | 'e' | 'n' | 't' | 's' | 'x' | 'A' | 'M' | '*'),
+            Mode::Detail(_) if self.interactions_focused => {
+                matches!(ch, 'e' | 'd' | 'n' | 't' | 'b' | 's' | 'x' | 'A' | 'M')
+            }
+            Mode::Detail(_) => matches!(ch, 'e' | 'n' | 't' | 'b' | 's' | 'x' | 'A' | 'M'),
+            Mode::MergeList => matches!(ch, 'p' | 'd' | 'a' | 'A'),
+            Mode::MergeDetail(_) => matches!(ch, 'p' | 'd'),
+            _ => false,
+        }
+    }
+
     fn handle_list_key(&mut self, key: KeyEvent) -> Option<Mode> {
         match key.code {
             KeyCode::Down | KeyCode::Char('j') => self.move_selection(1),
@@ -244,9 +653,8 @@ impl App {
             KeyCode::PageUp => self.move_selection(-5),
             KeyCode::Home | KeyCode::Char('g') => self.selected = 0,
             KeyCode::End | KeyCode::Char('G') => {
-                if !self.contacts.is_empty() {
-                    self.selected = self.contacts.len() - 1;
-                }
+                self.selected = self.contacts.len().saturating_sub(1);
+                self.maybe_load_more();
             }
             KeyCode::Enter => {
                 if let Some(id) = self.selected_contact_id() {
@@ -262,16 +670,22 @@ impl App {
                 self.filter_input.clear();
                 self.filter = None;
                 self.filter_error = None;
+                self.quick_filter_base = None;
                 self.enqueue(Action::LoadList);
             }
+            KeyCode::Char(digit @ '0'..='5') => self.apply_quick_filter(digit),
             KeyCode::Char('a') => {
-                return Some(Mode::ModalAddContact(ContactForm::new(
-                    self.default_cadence_days,
-                )));
+                return Some(Mode::ModalAddContact(
+                    ContactForm::new(self.default_cadence_days)
+                        .with_defaults(&self.contact_defaults, &self.clock),
+                ));
             }
             KeyCode::Char('e') => {
                 if let Some(detail) = self.detail_for_selected() {
-                    return Some(Mode::ModalEditContact(ContactForm::from_detail(&detail)));
+                    return Some(Mode::ModalEditContact(ContactForm::from_detail(
+                        &detail,
+                        &self.clock,
+                    )));
                 } else if let Some(id) = self.selected_contact_id() {
                     self.enqueue(Action::LoadDetail(id));
                     return Some(Mode::Detail(id));
@@ -293,16 +707,7 @@ impl App {
                     return Some(Mode::ModalSchedule(ScheduleForm::new(id)));
                 }
             }
-            KeyCode::Char('v') => {
-                self.show_archived = !self.show_archived;
-                let status = if self.show_archived {
-                    "Showing archived contacts"
-                } else {
-                    "Hiding archived contacts"
-                };
-                self.set_status(status.to_string());
-                self.enqueue(Action::LoadList);
-            }
+            KeyCode::Char('v') => self.cycle_archive_view(),
             KeyCode::Char('x') => {
                 if let Some(id) = self.selected_contact_id() {
                     let message = "Clear scheduled touchpoint? (y/n)".to_string();
@@ -314,18 +719,22 @@ impl App {
             }
             KeyCode::Char('A') => {
                 if let Some(item) = self.contacts.get(self.selected) {
-                    let (message, action) = if item.archived_at.is_some() {
-                        (
-                            format!("Unarchive {}? (y/n)", item.display_name),
-                            ConfirmAction::UnarchiveContact(item.id),
-                        )
-                    } else {
-                        (
-                            format!("Archive {}? (y/n)", item.display_name),
-                            ConfirmAction::ArchiveContact(item.id),
-                        )
-                    };
-                    return Some(Mode::Confirm(ConfirmState::new(message, action)));
+                    if item.archived_at.is_none() {
+                        return Some(Mode::ModalArchive(ArchiveForm::new(
+                            item.id,
+                            item.display_name.clone(),
+                        )));
+                    }
+                    let message = format!("Unarchive {}? (y/n)", item.display_name);
+                    return Some(Mode::Confirm(ConfirmState::new(
+                        message,
+                        ConfirmAction::UnarchiveContact(item.id),
+                    )));
+                }
+            }
+            KeyCode::Char('*') => {
+                if let Some(item) = self.contacts.get(self.selected) {
+                    self.enqueue(Action::SetPriority(item.id, toggle_star(item.priority)));
                 }
             }
             KeyCode::Char('m') => {
@@ -350,59 +759,202 @@ impl App {
         None
     }
 
+    /// Which contacts are listed: the filter's `archived:` term if it has
+    /// one, otherwise the `v` toggle.
+    pub fn effective_archive_view(&self) -> ArchiveView {
+        match self.filter.as_ref().and_then(filter_archived_selector) {
+            Some(ArchivedSelector::Active) => ArchiveView::Active,
+            Some(ArchivedSelector::Archived) => ArchiveView::Archived,
+            None => self.archive_view,
+        }
+    }
+
+    /// Moves to the next view after the one on screen. Any `archived:` term is
+    /// dropped from the filter so the toggle and the filter never disagree.
+    fn cycle_archive_view(&mut self) {
+        self.archive_view = self.effective_archive_view().next();
+        self.selected = 0;
+        self.set_status(format!("Showing {} contacts", self.archive_view.label()));
+        if let Some(base) = self.quick_filter_base.as_mut() {
+            *base = without_archived_terms(base);
+        }
+        if self
+            .filter_input
+            .split_whitespace()
+            .any(|term| term.starts_with("archived:"))
+        {
+            self.set_filter_text(without_archived_terms(&self.filter_input));
+        } else {
+            self.enqueue(Action::LoadList);
+        }
+    }
+
+    /// `1`-`5` narrow the current filter to one due bucket; `0` restores the
+    /// filter text from before the first quick filter.
+    fn apply_quick_filter(&mut self, digit: char) {
+        let selector = match digit {
+            '1' => "due:overdue",
+            '2' => "due:today",
+            '3' => "due:soon",
+            '4' => "due:scheduled",
+            '5' => "due:none",
+            _ => {
+                if let Some(base) = self.quick_filter_base.take() {
+                    self.set_filter_text(base);
+                }
+                return;
+            }
+        };
+        let base = self
+            .quick_filter_base
+            .get_or_insert_with(|| self.filter_input.clone());
+        let mut terms: Vec<&str> = base
+            .split_whitespace()
+            .filter(|term| !term.starts_with("due:"))
+            .collect();
+        terms.push(selector);
+        let text = terms.join(" ");
+        self.set_filter_text(text);
+    }
+
+    /// Replaces the filter text and applies it through the same parser as `/`.
+    fn set_filter_text(&mut self, text: String) {
+        self.filter_input = text;
+        if self.filter_input.trim().is_empty() {
+            self.filter = None;
+            self.filter_error = None;
+        } else {
+            match parse_filter(&self.filter_input) {
+                Ok(parsed) => {
+                    self.filter = Some(parsed);
+                    self.filter_error = None;
+                }
+                Err(err) => {
+                    self.filter_error = Some(err.to_string());
+                    return;
+                }
+            }
+        }
+        self.enqueue(Action::LoadList);
+    }
+
     fn handle_filter_key(&mut self, key: KeyEvent) -> Option<Mode> {
         match key.code {
             KeyCode::Esc => {
+                self.filter_requery_at = None;
                 self.filter_error = None;
                 return Some(Mode::List);
             }
             KeyCode::Enter => {
-                if self.filter_input.trim().is_empty() {
-                    self.filter = None;
-                    self.filter_error = None;
-                    self.enqueue(Action::LoadList);
+                self.filter_requery_at = None;
+                if self.apply_filter_input() {
                     return Some(Mode::List);
                 }
-                match parse_filter(&self.filter_input) {
-                    Ok(parsed) => {
-                        self.filter = Some(parsed);
-                        self.filter_error = None;
-                        self.enqueue(Action::LoadList);
-                        return Some(Mode::List);
-                    }
-                    Err(err) => {
-                        self.filter_error = Some(err.to_string());
-                    }
-                }
             }
             _ => {
+                let before = self.filter_input.clone();
                 apply_text_input(&mut self.filter_input, key);
+                if self.filter_input != before {
+                    self.filter_requery_at = Some(Instant::now() + FILTER_DEBOUNCE);
+                }
             }
         }
         None
     }
 
+    /// Parses the typed filter and reloads the list with it; a parse error is
+    /// shown instead and the current list is kept.
+    fn apply_filter_input(&mut self) -> bool {
+        if self.filter_input.trim().is_empty() {
+            self.filter = None;
+            self.filter_error = None;
+            self.enqueue(Action::LoadList);
+            return true;
+        }
+        match parse_filter(&self.filter_input) {
+            Ok(parsed) => {
+                self.filter = Some(parsed);
+                self.filter_error = None;
+                self.quick_filter_base = None;
+                self.enqueue(Action::LoadList);
+                true
+            }
+            Err(err) => {
+                self.filter_error = Some(err.to_string());
+                false
+            }
+        }
+    }
+
     fn handle_detail_key(&mut self, key: KeyEvent, contact_id: ContactId) -> Option<Mode> {
         match key.code {
             KeyCode::Esc | KeyCode::Backspace => {
                 self.detail = None;
                 return Some(Mode::List);
             }
+            KeyCode::Tab if self.selected_interaction().is_some() => {
+                self.interactions_focused = !self.interactions_focused;
+                if self.interactions_focused {
+                    self.detail_scroll = self.selected_interaction_line();
+                }
+            }
+            KeyCode::Down | KeyCode::Char('j') if self.interactions_focused => {
+                self.move_interaction_selection(1);
+            }
+            KeyCode::Up | KeyCode::Char('k') if self.interactions_focused => {
+                self.move_interaction_selection(-1);
+            }
             KeyCode::Down | KeyCode::Char('j') => {
                 self.detail_scroll = self.detail_scroll.saturating_add(1);
+                self.maybe_load_more_interactions();
             }
             KeyCode::Up | KeyCode::Char('k') => {
                 self.detail_scroll = self.detail_scroll.saturating_sub(1);
             }
             KeyCode::PageDown => {
                 self.detail_scroll = self.detail_scroll.saturating_add(5);
+                self.maybe_load_more_interactions();
             }
             KeyCode::PageUp => {
                 self.detail_scroll = self.detail_scroll.saturating_sub(5);
             }
+            KeyCode::Char('e') if self.interactions_focused => {
+                if let Some(interaction) = self.selected_interaction() {
+                    return Some(Mode::ModalEditNote(NoteForm::for_interaction(
+                        contact_id,
+                        interaction,
+                        &self.clock,
+                    )));
+                }
+            }
+            KeyCode::Char('d') if self.interactions_focused => {
+                if let Some(interaction) = self.selected_interaction() {
+                    let message = format!(
+                        "Delete {} interaction from {}? (y/n)",
+                        interaction.kind,
+                        self.clock
+                            .format_timestamp_datetime(interaction.occurred_at)
+                    );
+                    let state = ConfirmState::new(
+                        message,
+                        ConfirmAction::DeleteInteraction {
+                            contact_id,
+                            interaction_id: interaction.id,
+                        },
+                    )
+                    .with_return_modes(
+                        ConfirmReturn::Detail(contact_id),
+                        ConfirmReturn::Detail(contact_id),
+                    );
+                    return Some(Mode::Confirm(state));
+                }
+            }
             KeyCode::Char('e') => {
                 if let Some(detail) = self.detail.clone() {
-                    return Some(Mode::ModalEditContact(ContactForm::from_detail(&detail)));
+                    return Some(Mode::ModalEditContact(ContactForm::from_detail(
+                        &detail,
+                        &self.clock,
+                    )));
                 }
             }
             KeyCode::Char('n') => {
@@ -412,6 +964,11 @@ impl App {
                 self.enqueue(Action::LoadTags(contact_id));
                 return Some(Mode::ModalEditTags(TagEditor::new(contact_id)));
             }
+            KeyCode::Char('b') => {
+                if let Some(detail) = &self.detail {
+                    return Some(Mode::ModalEditDates(DateEditor::from_detail(detail)));
+                }
+            }
             KeyCode::Char('s') => {
                 return Some(Mode::ModalSchedule(ScheduleForm::new(contact_id)));
             }
@@ -424,18 +981,17 @@ impl App {
             }
             KeyCode::Char('A') => {
                 if let Some(detail) = &self.detail {
-                    let (message, action) = if detail.archived_at.is_some() {
-                        (
-                            format!("Unarchive {}? (y/n)", detail.display_name),
-                            ConfirmAction::UnarchiveContact(contact_id),
-                        )
-                    } else {
-                        (
-                            format!("Archive {}? (y/n)", detail.display_name),
-                            ConfirmAction::ArchiveContact(contact_id),
-                        )
-                    };
-                    return Some(Mode::Confirm(ConfirmState::new(message, action)));
+                    if detail.archived_at.is_none() {
+                        return Some(Mode::ModalArchive(ArchiveForm::new(
+                            contact_id,
+                            detail.display_name.clone(),
+                        )));
+                    }
+                    let message = format!("Unarchive {}? (y/n)", detail.display_name);
+                    return Some(Mode::Confirm(ConfirmState::new(
+                        message,
+                        ConfirmAction::UnarchiveContact(contact_id),
+                    )));
                 }
             }
             KeyCode::Char('m') => {
@@ -453,6 +1009,9 @@ impl App {
                     )));
                 }
             }
+            KeyCode::Char('H') => {
+                self.sync_history_expanded = !self.sync_history_expanded;
+            }
             KeyCode::Char('r') => {
                 self.enqueue(Action::LoadDetail(contact_id));
             }
@@ -470,9 +1029,8 @@ impl App {
             KeyCode::PageUp => self.move_merge_selection(-5),
             KeyCode::Home | KeyCode::Char('g') => self.merge_selected = 0,
             KeyCode::End | KeyCode::Char('G') => {
-                if !self.merge_candidates.is_empty() {
-                    self.merge_selected = self.merge_candidates.len() - 1;
-                }
+                self.merge_selected = self.merge_candidates.len().saturating_sub(1);
+                self.maybe_load_more_merges();
             }
             KeyCode::Char('p') => {
                 if let Some(candidate) = self.merge_candidates.get(self.merge_selected) {
@@ -521,32 +1079,9 @@ impl App {
             }
             KeyCode::Enter => {
                 if let Some(candidate) = self.merge_candidates.get(self.merge_selected) {
-                    let primary_id = candidate
-                        .preferred_contact_id
-                        .unwrap_or(candidate.contact_a_id);
-                    let secondary_id = if primary_id == candidate.contact_a_id {
-                        candidate.contact_b_id
-                    } else {
-                        candidate.contact_a_id
-                    };
-                    let primary_name = if primary_id == candidate.contact_a_id {
-                        &candidate.contact_a_name
-                    } else {
-                        &candidate.contact_b_name
-                    };
-                    let secondary_name = if secondary_id == candidate.contact_a_id {
-                        &candidate.contact_a_name
-                    } else {
-                        &candidate.contact_b_name
-                    };
-                    let message = format!("Merge {} into {}? (y/n)", secondary_name, primary_name);
-                    return Some(Mode::Confirm(ConfirmState::new(
-                        message,
-                        ConfirmAction::ApplyMerge {
-                            primary_id,
-                            secondary_id,
-                        },
-                    )));
+                    let detail = MergeDetail::new(candidate);
+                    self.enqueue(Action::LoadMergeDetail(detail.candidate_id));
+                    return Some(Mode::MergeDetail(detail));
                 }
             }
             KeyCode::Char('r') => self.enqueue(Action::LoadMerges),
@@ -555,6 +1090,62 @@ impl App {
         None
     }
 
+    fn handle_merge_detail_key(&mut self, detail: &mut MergeDetail, key: KeyEvent) -> Option<Mode> {
+        match key.code {
+            KeyCode::Esc => return Some(Mode::MergeList),
+            KeyCode::Down | KeyCode::Char('j') => detail.move_selection(1),
+            KeyCode::Up | KeyCode::Char('k') => detail.move_selection(-1),
+            KeyCode::Char(' ') => detail.toggle_selected(),
+            KeyCode::Left | KeyCode::Char('h') => detail.choose_selected(MergePreference::Primary),
+            KeyCode::Right | KeyCode::Char('l') => {
+                detail.choose_selected(MergePreference::Secondary)
+            }
+            KeyCode::Char('p') => {
+                self.enqueue(Action::SetMergePreferred {
+                    candidate_id: detail.candidate_id,
+                    preferred_contact_id: detail.secondary_id,
+                });
+                self.enqueue(Action::LoadMergeDetail(detail.candidate_id));
+            }
+            KeyCode::Char('d') => {
+                let message = format!("Dismiss merge candidate {}? (y/n)", detail.candidate_id);
+                let confirm =
+                    ConfirmState::new(message, ConfirmAction::DismissMerge(detail.candidate_id))
+                        .with_return_modes(
+                            ConfirmReturn::MergeList,
+                            ConfirmReturn::MergeDetail(detail.clone()),
+                        );
+                return Some(Mode::Confirm(confirm));
+            }
+            KeyCode::Enter => {
+                if detail.rows.is_empty() {
+                    self.set_error("merge details are still loading");
+                    return None;
+                }
+                let message = format!(
+                    "Merge {} into {}? (y/n)",
+                    detail.secondary_name, detail.primary_name
+                );
+                let confirm = ConfirmState::new(
+                    message,
+                    ConfirmAction::ApplyMerge {
+                        primary_id: detail.primary_id,
+                        secondary_id: detail.secondary_id,
+                        fields: detail.overrides(),
+                    },
+                )
+                .with_return_modes(
+                    ConfirmReturn::MergeList,
+                    ConfirmReturn::MergeDetail(detail.clone()),
+                );
+                return Some(Mode::Confirm(confirm));
+            }
+            KeyCode::Char('r') => self.enqueue(Action::LoadMergeDetail(detail.candidate_id)),
+            _ => {}
+        }
+        None
+    }
+
     fn handle_merge_picker_key(&mut self, picker: &mut MergePicker, key: KeyEvent) -> Option<Mode> {
         if key.modifiers.contains(KeyModifiers::CONTROL) && matches!(key.code, KeyCode::Char('r')) {
             self.enqueue(Action::LoadMergePicker(picker.primary_id));
@@ -622,6 +1213,7 @@ impl App {
                         let confirm = ConfirmState::new(
                             message,
                             ConfirmAction::ApplyMerge {
+                                fields: MergeFieldOverrides::default(),
                                 primary_id: picker.primary_id,
                                 secondary_id: target.id,
                             },
@@ -653,8 +1245,7 @@ impl App {
             }
             KeyCode::Char('n') if key.modifiers.contains(KeyModifiers::CONTROL) => {
                 if form.focus == 6 {
-                    let now = knotter_core::time::now_utc();
-                    form.set_next_touchpoint_now(now);
+                    form.set_next_touchpoint_now(&self.clock);
                     self.set_status("Next touchpoint set to now".to_string());
                 }
             }
@@ -662,7 +1253,10 @@ impl App {
             KeyCode::BackTab => form.focus_prev(),
             KeyCode::Enter => {
                 if form.is_save_focus() {
-                    match form.to_action() {
+                    if self.normalize_phones {
+                        form.normalize_phone();
+                    }
+                    match form.to_action(&self.clock) {
                         Ok(action) => {
                             self.enqueue(action);
                             return Some(Mode::List);
@@ -686,20 +1280,20 @@ impl App {
 
     fn handle_note_form_key(&mut self, form: &mut NoteForm, key: KeyEvent) -> Option<Mode> {
         match key.code {
-            KeyCode::Esc => return Some(Mode::List),
+            KeyCode::Esc => return Some(form.exit_mode()),
             KeyCode::Tab => form.focus_next(),
             KeyCode::BackTab => form.focus_prev(),
             KeyCode::Enter => {
                 if form.is_save_focus() {
-                    match form.to_action() {
+                    match form.to_action(&self.clock) {
                         Ok(action) => {
                             self.enqueue(action);
-                            return Some(Mode::List);
+                            return Some(form.exit_mode());
                         }
                         Err(err) => self.set_error(err),
                     }
                 } else if form.is_cancel_focus() {
-                    return Some(Mode::List);
+                    return Some(form.exit_mode());
                 } else if form.is_note_focus() {
                     form.note.push('\n');
                 } else {
@@ -757,22 +1351,60 @@ impl App {
         None
     }
 
-    fn handle_schedule_form_key(&mut self, form: &mut ScheduleForm, key: KeyEvent) -> Option<Mode> {
+    fn handle_date_editor_key(&mut self, editor: &mut DateEditor, key: KeyEvent) -> Option<Mode> {
         match key.code {
-            KeyCode::Esc => return Some(Mode::List),
-            KeyCode::Char('n') if key.modifiers.contains(KeyModifiers::CONTROL) => {
-                if form.focus == 0 || form.focus == 1 {
-                    let now = knotter_core::time::now_utc();
-                    form.set_now(now);
-                    self.set_status("Schedule set to now".to_string());
+            KeyCode::Esc => return Some(Mode::Detail(editor.contact_id)),
+            KeyCode::Tab => editor.focus_next(),
+            KeyCode::BackTab => editor.focus_prev(),
+            KeyCode::Enter if editor.focus == DateEditorFocus::Close => {
+                return Some(Mode::Detail(editor.contact_id));
+            }
+            KeyCode::Enter if editor.focus != DateEditorFocus::List => match editor.to_action() {
+                Ok(action) => {
+                    self.enqueue(action);
+                    editor.clear_inputs();
                 }
+                Err(err) => self.set_error(err),
+            },
+            KeyCode::Up | KeyCode::Char('k') if editor.focus == DateEditorFocus::List => {
+                editor.move_selection(-1);
             }
-            KeyCode::Tab => form.focus_next(),
-            KeyCode::BackTab => form.focus_prev(),
-            KeyCode::Enter => {
-                if form.is_save_focus() {
-                    match form.to_action() {
-                        Ok(action) => {
+            KeyCode::Down | KeyCode::Char('j') if editor.focus == DateEditorFocus::List => {
+                editor.move_selection(1);
+            }
+            KeyCode::Char('d') | KeyCode::Delete if editor.focus == DateEditorFocus::List => {
+                if let Some(action) = editor.remove_action() {
+                    self.enqueue(action);
+                }
+            }
+            KeyCode::Left if editor.focus == DateEditorFocus::Kind => editor.cycle_kind(-1),
+            KeyCode::Right | KeyCode::Char(' ') if editor.focus == DateEditorFocus::Kind => {
+                editor.cycle_kind(1);
+            }
+            _ => {
+                if let Some(target) = editor.active_field_mut() {
+                    apply_text_input(target, key);
+                }
+            }
+        }
+        None
+    }
+
+    fn handle_schedule_form_key(&mut self, form: &mut ScheduleForm, key: KeyEvent) -> Option<Mode> {
+        match key.code {
+            KeyCode::Esc => return Some(Mode::List),
+            KeyCode::Char('n') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                if form.focus == 0 || form.focus == 1 {
+                    form.set_now(&self.clock);
+                    self.set_status("Schedule set to now".to_string());
+                }
+            }
+            KeyCode::Tab => form.focus_next(),
+            KeyCode::BackTab => form.focus_prev(),
+            KeyCode::Enter => {
+                if form.is_save_focus() {
+                    match form.to_action(&self.clock) {
+                        Ok(action) => {
                             self.enqueue(action);
                             return Some(Mode::List);
                         }
@@ -793,6 +1425,28 @@ impl App {
         None
     }
 
+    fn handle_archive_form_key(&mut self, form: &mut ArchiveForm, key: KeyEvent) -> Option<Mode> {
+        match key.code {
+            KeyCode::Esc => return Some(Mode::List),
+            KeyCode::Tab => form.focus_next(),
+            KeyCode::BackTab => form.focus_prev(),
+            KeyCode::Enter => {
+                if form.is_cancel_focus() {
+                    return Some(Mode::List);
+                }
+                // Enter in the reason field submits, so "A, Enter" stays a quick archive.
+                self.enqueue(form.to_action());
+                return Some(Mode::List);
+            }
+            _ => {
+                if let Some(target) = form.active_field_mut() {
+                    apply_text_input(target, key);
+                }
+            }
+        }
+        None
+    }
+
     fn handle_confirm_key(&mut self, state: &mut ConfirmState, key: KeyEvent) -> Option<Mode> {
         match key.code {
             KeyCode::Char('y') | KeyCode::Char('Y') => {
@@ -831,6 +1485,17 @@ impl App {
             next = len - 1;
         }
         self.selected = next as usize;
+        self.maybe_load_more();
+    }
+
+    fn maybe_load_more(&mut self) {
+        if !self.list_has_more || self.list_loading_more {
+            return;
+        }
+        if self.selected + LIST_PREFETCH_MARGIN >= self.contacts.len() {
+            self.list_loading_more = true;
+            self.enqueue(Action::LoadMoreList);
+        }
     }
 
     fn move_merge_selection(&mut self, delta: i32) {
@@ -847,6 +1512,17 @@ impl App {
             next = len - 1;
         }
         self.merge_selected = next as usize;
+        self.maybe_load_more_merges();
+    }
+
+    fn maybe_load_more_merges(&mut self) {
+        if !self.merge_has_more || self.merge_loading_more {
+            return;
+        }
+        if self.merge_selected + MERGE_PREFETCH_MARGIN >= self.merge_candidates.len() {
+            self.merge_loading_more = true;
+            self.enqueue(Action::LoadMoreMerges);
+        }
     }
 
     fn detail_for_selected(&self) -> Option<knotter_core::dto::ContactDetailDto> {
@@ -868,10 +1544,8 @@ fn apply_text_input(target: &mut String, key: KeyEvent) {
         KeyCode::Char('w') if key.modifiers.contains(KeyModifiers::CONTROL) => {
             delete_last_word(target);
         }
-        KeyCode::Char(ch) => {
-            if !key.modifiers.contains(KeyModifiers::CONTROL) {
-                target.push(ch);
-            }
+        KeyCode::Char(ch) if !key.modifiers.contains(KeyModifiers::CONTROL) => {
+            target.push(ch);
         }
         KeyCode::Backspace => {
             target.pop();
@@ -900,6 +1574,8 @@ pub struct ContactForm {
     pub timezone: String,
     pub cadence_days: String,
     pub next_touchpoint_at: String,
+    /// Comma-separated tags; only shown when adding a contact.
+    pub tags: String,
     pub original_next_touchpoint_at: Option<i64>,
     pub original_next_touchpoint_display: String,
 }
@@ -920,15 +1596,36 @@ impl ContactForm {
                 .map(|value| value.to_string())
                 .unwrap_or_default(),
             next_touchpoint_at: String::new(),
+            tags: String::new(),
             original_next_touchpoint_at: None,
             original_next_touchpoint_display: String::new(),
         }
     }
 
-    pub fn from_detail(detail: &knotter_core::dto::ContactDetailDto) -> Self {
+    /// Prefills empty fields from `[contacts.defaults]`; the relative next
+    /// touchpoint is resolved to a date so it can be edited like any other.
+    pub fn with_defaults(mut self, defaults: &ContactDefaultsConfig, clock: &Clock) -> Self {
+        if let Some(cadence) = defaults.cadence_days {
+            self.cadence_days = cadence.to_string();
+        }
+        if let Some(timezone) = defaults.timezone.as_deref() {
+            self.timezone = timezone.to_string();
+        }
+        if let Some(offset) = defaults.next_touchpoint.as_deref() {
+            if let Ok((_, _, date)) =
+                clock.parse_local_date_time_relative(offset, None, clock.now_utc())
+            {
+                self.next_touchpoint_at = date.format("%Y-%m-%d").to_string();
+            }
+        }
+        self.tags = defaults.tags.join(", ");
+        self
+    }
+
+    pub fn from_detail(detail: &knotter_core::dto::ContactDetailDto, clock: &Clock) -> Self {
         let next_touchpoint_display = detail
             .next_touchpoint_at
-            .map(knotter_core::time::format_timestamp_date_or_datetime)
+            .map(|ts| clock.format_timestamp_date_or_datetime(ts))
             .unwrap_or_default();
         let mut emails = detail.emails.clone();
         if emails.is_empty() {
@@ -950,18 +1647,28 @@ impl ContactForm {
                 .map(|value| value.to_string())
                 .unwrap_or_default(),
             next_touchpoint_at: next_touchpoint_display.clone(),
+            tags: String::new(),
             original_next_touchpoint_at: detail.next_touchpoint_at,
             original_next_touchpoint_display: next_touchpoint_display,
         }
     }
 
+    /// Tags are only editable here when adding; existing contacts use the tag editor.
+    fn field_count(&self) -> usize {
+        if self.contact_id.is_none() {
+            Self::FIELD_COUNT + 1
+        } else {
+            Self::FIELD_COUNT
+        }
+    }
+
     pub fn focus_next(&mut self) {
-        let total = Self::FIELD_COUNT + 2;
+        let total = self.field_count() + 2;
         self.focus = (self.focus + 1) % total;
     }
 
     pub fn focus_prev(&mut self) {
-        let total = Self::FIELD_COUNT + 2;
+        let total = self.field_count() + 2;
         if self.focus == 0 {
             self.focus = total - 1;
         } else {
@@ -970,11 +1677,11 @@ impl ContactForm {
     }
 
     pub fn is_save_focus(&self) -> bool {
-        self.focus == Self::FIELD_COUNT
+        self.focus == self.field_count()
     }
 
     pub fn is_cancel_focus(&self) -> bool {
-        self.focus == Self::FIELD_COUNT + 1
+        self.focus == self.field_count() + 1
     }
 
     pub fn active_field_mut(&mut self) -> Option<&mut String> {
@@ -986,15 +1693,23 @@ impl ContactForm {
             4 => Some(&mut self.timezone),
             5 => Some(&mut self.cadence_days),
             6 => Some(&mut self.next_touchpoint_at),
+            7 if self.contact_id.is_none() => Some(&mut self.tags),
             _ => None,
         }
     }
 
-    pub fn set_next_touchpoint_now(&mut self, now_utc: i64) {
-        self.next_touchpoint_at = knotter_core::time::format_timestamp_datetime(now_utc);
+    /// Replaces the phone with its canonical form; unparseable values are kept.
+    pub fn normalize_phone(&mut self) {
+        if let Ok(phone) = canonical_phone(&self.phone) {
+            self.phone = phone;
+        }
+    }
+
+    pub fn set_next_touchpoint_now(&mut self, clock: &Clock) {
+        self.next_touchpoint_at = clock.format_timestamp_datetime(clock.now_utc());
     }
 
-    pub fn to_action(&self) -> Result<Action, String> {
+    pub fn to_action(&self, clock: &Clock) -> Result<Action, String> {
         let name = self.name.trim();
         if name.is_empty() {
             return Err("name is required".to_string());
@@ -1018,19 +1733,19 @@ impl ContactForm {
         {
             self.original_next_touchpoint_at
         } else {
-            let (parsed, precision) =
-                knotter_core::time::parse_local_timestamp_with_precision(&self.next_touchpoint_at)
-                    .map_err(|err| err.to_string())?;
-            let now = knotter_core::time::now_utc();
+            let (parsed, precision) = clock
+                .parse_local_timestamp_with_precision(&self.next_touchpoint_at)
+                .map_err(|err| err.to_string())?;
+            let now = clock.now_utc();
             Some(
-                ensure_future_timestamp_with_precision(now, parsed, precision).map_err(|err| {
-                    match err {
+                ensure_future_timestamp_with_precision(now, parsed, precision, clock).map_err(
+                    |err| match err {
                         knotter_core::CoreError::TimestampInPast => {
                             "next touchpoint must be now or later".to_string()
                         }
                         _ => err.to_string(),
-                    }
-                })?,
+                    },
+                )?,
             )
         };
 
@@ -1051,9 +1766,18 @@ impl ContactForm {
                 next_touchpoint_at: Some(next_touchpoint_at),
                 cadence_days: Some(cadence),
                 archived_at: None,
+                organization: None,
+                source: None,
             };
             Ok(Action::UpdateContact(contact_id, update, emails))
         } else {
+            let tags = self
+                .tags
+                .split(',')
+                .map(str::trim)
+                .filter(|tag| !tag.is_empty())
+                .map(|tag| TagName::new(tag).map_err(|_| format!("invalid tag: {tag}")))
+                .collect::<Result<Vec<_>, _>>()?;
             let input = knotter_store::repo::ContactNew {
                 display_name: name.to_string(),
                 email: primary_email,
@@ -1063,8 +1787,9 @@ impl ContactForm {
                 next_touchpoint_at,
                 cadence_days: cadence,
                 archived_at: None,
+                organization: None,
             };
-            Ok(Action::CreateContact(input, emails))
+            Ok(Action::CreateContact(input, emails, tags))
         }
     }
 }
@@ -1073,9 +1798,12 @@ impl ContactForm {
 pub struct NoteForm {
     pub(crate) focus: usize,
     pub contact_id: ContactId,
+    /// Set when editing an existing interaction instead of adding one.
+    pub interaction_id: Option<InteractionId>,
     pub kind: String,
     pub when: String,
     pub note: String,
+    original_when: String,
 }
 
 impl NoteForm {
@@ -1085,9 +1813,40 @@ impl NoteForm {
         Self {
             focus: 0,
             contact_id,
+            interaction_id: None,
             kind: "other:note".to_string(),
             when: String::new(),
             note: String::new(),
+            original_when: String::new(),
+        }
+    }
+
+    pub fn for_interaction(
+        contact_id: ContactId,
+        interaction: &knotter_core::dto::InteractionDto,
+        clock: &Clock,
+    ) -> Self {
+        let when = clock.format_timestamp_datetime(interaction.occurred_at);
+        Self {
+            focus: 0,
+            contact_id,
+            interaction_id: Some(interaction.id),
+            kind: interaction.kind.clone(),
+            when: when.clone(),
+            note: interaction.note.clone(),
+            original_when: when,
+        }
+    }
+
+    pub fn is_edit(&self) -> bool {
+        self.interaction_id.is_some()
+    }
+
+    pub fn exit_mode(&self) -> Mode {
+        if self.is_edit() {
+            Mode::Detail(self.contact_id)
+        } else {
+            Mode::List
         }
     }
 
@@ -1126,26 +1885,64 @@ impl NoteForm {
         }
     }
 
-    pub fn to_action(&self) -> Result<Action, String> {
+    pub fn to_action(&self, clock: &Clock) -> Result<Action, String> {
         let kind =
             crate::util::parse_interaction_kind(&self.kind).map_err(|err| err.to_string())?;
+        if let Some(interaction_id) = self.interaction_id {
+            return self.to_update_action(interaction_id, kind, clock);
+        }
         let occurred_at = if self.when.trim().is_empty() {
-            knotter_core::time::now_utc()
+            clock.now_utc()
         } else {
-            knotter_core::time::parse_local_timestamp(&self.when).map_err(|err| err.to_string())?
+            clock
+                .parse_local_timestamp(&self.when)
+                .map_err(|err| err.to_string())?
         };
 
         let input = knotter_store::repo::InteractionNew {
             contact_id: self.contact_id,
             occurred_at,
-            created_at: knotter_core::time::now_utc(),
+            created_at: clock.now_utc(),
             kind,
             note: self.note.clone(),
             follow_up_at: None,
+            direction: None,
         };
 
         Ok(Action::AddInteraction(input))
     }
+
+    fn to_update_action(
+        &self,
+        interaction_id: InteractionId,
+        kind: knotter_core::domain::InteractionKind,
+        clock: &Clock,
+    ) -> Result<Action, String> {
+        // The form shows minutes only; an untouched time keeps its original seconds.
+        let occurred_at = if self.when.trim() == self.original_when {
+            None
+        } else if self.when.trim().is_empty() {
+            return Err("when is required".to_string());
+        } else {
+            let occurred_at = clock
+                .parse_local_timestamp(&self.when)
+                .map_err(|err| err.to_string())?;
+            if occurred_at > clock.now_utc() {
+                return Err("when cannot be in the future".to_string());
+            }
+            Some(occurred_at)
+        };
+
+        Ok(Action::UpdateInteraction {
+            contact_id: self.contact_id,
+            interaction_id,
+            update: knotter_store::repo::InteractionUpdate {
+                occurred_at,
+                kind: Some(kind),
+                note: Some(self.note.clone()),
+            },
+        })
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -1435,6 +2232,247 @@ impl MergePicker {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MergeField {
+    Name,
+    Email,
+    Phone,
+    Handle,
+    Timezone,
+    Cadence,
+    NextTouchpoint,
+    Tags,
+}
+
+impl MergeField {
+    pub fn label(self) -> &'static str {
+        match self {
+            MergeField::Name => "Name",
+            MergeField::Email => "Email",
+            MergeField::Phone => "Phone",
+            MergeField::Handle => "Handle",
+            MergeField::Timezone => "Timezone",
+            MergeField::Cadence => "Cadence",
+            MergeField::NextTouchpoint => "Next touchpoint",
+            MergeField::Tags => "Tags",
+        }
+    }
+}
+
+/// One contact's side of a merge, as shown in the merge detail view.
+#[derive(Debug, Clone, Default)]
+pub struct MergeSide {
+    pub display_name: String,
+    /// Primary email first.
+    pub emails: Vec<String>,
+    pub phone: Option<String>,
+    pub handle: Option<String>,
+    pub timezone: Option<String>,
+    pub cadence_days: Option<i32>,
+    pub next_touchpoint_at: Option<i64>,
+    pub tags: Vec<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct MergeFieldRow {
+    pub field: MergeField,
+    pub primary: String,
+    pub secondary: String,
+    /// Side whose value is kept; `None` when the values do not conflict.
+    pub winner: Option<MergePreference>,
+}
+
+#[derive(Debug, Clone)]
+pub struct MergeDetail {
+    pub candidate_id: knotter_core::domain::MergeCandidateId,
+    pub primary_id: ContactId,
+    pub secondary_id: ContactId,
+    pub primary_name: String,
+    pub secondary_name: String,
+    pub rows: Vec<MergeFieldRow>,
+    pub selected: usize,
+}
+
+impl MergeDetail {
+    pub fn new(candidate: &MergeCandidateView) -> Self {
+        let primary_id = candidate
+            .preferred_contact_id
+            .unwrap_or(candidate.contact_a_id);
+        let (secondary_id, primary_name, secondary_name) = if primary_id == candidate.contact_a_id {
+            (
+                candidate.contact_b_id,
+                candidate.contact_a_name.clone(),
+                candidate.contact_b_name.clone(),
+            )
+        } else {
+            (
+                candidate.contact_a_id,
+                candidate.contact_b_name.clone(),
+                candidate.contact_a_name.clone(),
+            )
+        };
+        Self {
+            candidate_id: candidate.id,
+            primary_id,
+            secondary_id,
+            primary_name,
+            secondary_name,
+            rows: Vec::new(),
+            selected: 0,
+        }
+    }
+
+    /// Rebuilds the field rows; conflicting fields start out matching the
+    /// default merge (primary wins, earliest touchpoint).
+    pub fn set_sides(
+        &mut self,
+        primary_id: ContactId,
+        primary: &MergeSide,
+        secondary_id: ContactId,
+        secondary: &MergeSide,
+        clock: &Clock,
+    ) {
+        self.primary_id = primary_id;
+        self.secondary_id = secondary_id;
+        self.primary_name = primary.display_name.clone();
+        self.secondary_name = secondary.display_name.clone();
+
+        let text = |value: &Option<String>| value.clone().unwrap_or_default();
+        let cadence = |value: Option<i32>| value.map(|days| format!("{days}d")).unwrap_or_default();
+        let touchpoint = |value: Option<i64>| {
+            value
+                .map(|ts| clock.format_timestamp_datetime(ts))
+                .unwrap_or_default()
+        };
+        let earliest = match (primary.next_touchpoint_at, secondary.next_touchpoint_at) {
+            (Some(a), Some(b)) if b < a => MergePreference::Secondary,
+            _ => MergePreference::Primary,
+        };
+        self.rows = vec![
+            row(
+                MergeField::Name,
+                primary.display_name.clone(),
+                secondary.display_name.clone(),
+                MergePreference::Primary,
+            ),
+            row(
+                MergeField::Email,
+                primary.emails.first().cloned().unwrap_or_default(),
+                secondary.emails.first().cloned().unwrap_or_default(),
+                MergePreference::Primary,
+            ),
+            row(
+                MergeField::Phone,
+                text(&primary.phone),
+                text(&secondary.phone),
+                MergePreference::Primary,
+            ),
+            row(
+                MergeField::Handle,
+                text(&primary.handle),
+                text(&secondary.handle),
+                MergePreference::Primary,
+            ),
+            row(
+                MergeField::Timezone,
+                text(&primary.timezone),
+                text(&secondary.timezone),
+                MergePreference::Primary,
+            ),
+            row(
+                MergeField::Cadence,
+                cadence(primary.cadence_days),
+                cadence(secondary.cadence_days),
+                MergePreference::Primary,
+            ),
+            row(
+                MergeField::NextTouchpoint,
+                touchpoint(primary.next_touchpoint_at),
+                touchpoint(secondary.next_touchpoint_at),
+                earliest,
+            ),
+            // Tags are always combined, so they never conflict.
+            MergeFieldRow {
+                field: MergeField::Tags,
+                primary: primary.tags.join(", "),
+                secondary: secondary.tags.join(", "),
+                winner: None,
+            },
+        ];
+        self.selected = self.selected.min(self.rows.len() - 1);
+    }
+
+    pub fn move_selection(&mut self, delta: i32) {
+        if self.rows.is_empty() {
+            self.selected = 0;
+            return;
+        }
+        let next = (self.selected as i64 + i64::from(delta)).clamp(0, self.rows.len() as i64 - 1);
+        self.selected = next as usize;
+    }
+
+    pub fn toggle_selected(&mut self) {
+        if let Some(winner) = self
+            .rows
+            .get_mut(self.selected)
+            .and_then(|row| row.winner.as_mut())
+        {
+            *winner = match winner {
+                MergePreference::Primary => MergePreference::Secondary,
+                MergePreference::Secondary => MergePreference::Primary,
+            };
+        }
+    }
+
+    pub fn choose_selected(&mut self, side: MergePreference) {
+        if let Some(winner) = self
+            .rows
+            .get_mut(self.selected)
+            .and_then(|row| row.winner.as_mut())
+        {
+            *winner = side;
+        }
+    }
+
+    pub fn overrides(&self) -> MergeFieldOverrides {
+        let mut fields = MergeFieldOverrides::default();
+        for row in &self.rows {
+            let Some(winner) = row.winner else {
+                continue;
+            };
+            let slot = match row.field {
+                MergeField::Name => &mut fields.display_name,
+                MergeField::Email => &mut fields.email,
+                MergeField::Phone => &mut fields.phone,
+                MergeField::Handle => &mut fields.handle,
+                MergeField::Timezone => &mut fields.timezone,
+                MergeField::Cadence => &mut fields.cadence_days,
+                MergeField::NextTouchpoint => &mut fields.next_touchpoint_at,
+                MergeField::Tags => continue,
+            };
+            *slot = Some(winner);
+        }
+        fields
+    }
+}
+
+/// A field conflicts only when both sides have a value and the values differ.
+fn row(
+    field: MergeField,
+    primary: String,
+    secondary: String,
+    default: MergePreference,
+) -> MergeFieldRow {
+    let winner =
+        (!primary.is_empty() && !secondary.is_empty() && primary != secondary).then_some(default);
+    MergeFieldRow {
+        field,
+        primary,
+        secondary,
+        winner,
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct ScheduleForm {
     pub(crate) focus: usize,
@@ -1485,12 +2523,13 @@ impl ScheduleForm {
         }
     }
 
-    pub fn set_now(&mut self, now_utc: i64) {
-        self.date = knotter_core::time::format_timestamp_date(now_utc);
-        self.time = knotter_core::time::format_timestamp_time(now_utc);
+    pub fn set_now(&mut self, clock: &Clock) {
+        let now = clock.now_utc();
+        self.date = clock.format_timestamp_date(now);
+        self.time = clock.format_timestamp_time(now);
     }
 
-    pub fn to_action(&self) -> Result<Action, String> {
+    pub fn to_action(&self, clock: &Clock) -> Result<Action, String> {
         let date = self.date.trim();
         if date.is_empty() {
             return Err("date is required".to_string());
@@ -1500,34 +2539,265 @@ impl ScheduleForm {
         } else {
             Some(self.time.trim())
         };
-        let (timestamp, precision) =
-            knotter_core::time::parse_local_date_time_with_precision(date, time)
-                .map_err(|err| err.to_string())?;
-        let now = knotter_core::time::now_utc();
-        let timestamp =
-            ensure_future_timestamp_with_precision(now, timestamp, precision).map_err(|err| {
-                match err {
+        let now = clock.now_utc();
+        let (timestamp, precision, resolved) = clock
+            .parse_local_date_time_relative(date, time, now)
+            .map_err(|err| err.to_string())?;
+        let timestamp = ensure_future_timestamp_with_precision(now, timestamp, precision, clock)
+            .map_err(|err| {
+                let message = match err {
                     knotter_core::CoreError::TimestampInPast => {
                         "scheduled time must be now or later".to_string()
                     }
                     _ => err.to_string(),
-                }
+                };
+                let understood = match time {
+                    Some(time) => format!("{} {time}", resolved.format("%Y-%m-%d")),
+                    None => resolved.format("%Y-%m-%d").to_string(),
+                };
+                format!("{message} (parsed as {understood})")
             })?;
         Ok(Action::ScheduleContact(self.contact_id, timestamp))
     }
 }
 
+#[derive(Debug, Clone)]
+pub struct ArchiveForm {
+    pub(crate) focus: usize,
+    pub contact_id: ContactId,
+    pub display_name: String,
+    pub reason: String,
+}
+
+impl ArchiveForm {
+    const FIELD_COUNT: usize = 1;
+
+    pub fn new(contact_id: ContactId, display_name: String) -> Self {
+        Self {
+            focus: 0,
+            contact_id,
+            display_name,
+            reason: String::new(),
+        }
+    }
+
+    pub fn focus_next(&mut self) {
+        let total = Self::FIELD_COUNT + 2;
+        self.focus = (self.focus + 1) % total;
+    }
+
+    pub fn focus_prev(&mut self) {
+        let total = Self::FIELD_COUNT + 2;
+        if self.focus == 0 {
+            self.focus = total - 1;
+        } else {
+            self.focus -= 1;
+        }
+    }
+
+    pub fn is_save_focus(&self) -> bool {
+        self.focus == Self::FIELD_COUNT
+    }
+
+    pub fn is_cancel_focus(&self) -> bool {
+        self.focus == Self::FIELD_COUNT + 1
+    }
+
+    pub fn active_field_mut(&mut self) -> Option<&mut String> {
+        match self.focus {
+            0 => Some(&mut self.reason),
+            _ => None,
+        }
+    }
+
+    pub fn to_action(&self) -> Action {
+        let reason = self.reason.trim();
+        let reason = (!reason.is_empty()).then(|| reason.to_string());
+        Action::ArchiveContact(self.contact_id, reason)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DateEditorFocus {
+    List,
+    Kind,
+    Label,
+    Month,
+    Day,
+    Year,
+    Add,
+    Close,
+}
+
+const DATE_EDITOR_FOCUS_ORDER: [DateEditorFocus; 8] = [
+    DateEditorFocus::List,
+    DateEditorFocus::Kind,
+    DateEditorFocus::Label,
+    DateEditorFocus::Month,
+    DateEditorFocus::Day,
+    DateEditorFocus::Year,
+    DateEditorFocus::Add,
+    DateEditorFocus::Close,
+];
+
+const DATE_KINDS: [ContactDateKind; 3] = [
+    ContactDateKind::Birthday,
+    ContactDateKind::NameDay,
+    ContactDateKind::Custom,
+];
+
+/// Lists a contact's dates with remove, plus a form to add one.
+#[derive(Debug, Clone)]
+pub struct DateEditor {
+    pub contact_id: ContactId,
+    pub display_name: String,
+    pub dates: Vec<ContactDateDto>,
+    pub selected_index: usize,
+    pub focus: DateEditorFocus,
+    pub kind: ContactDateKind,
+    pub label: String,
+    pub month: String,
+    pub day: String,
+    pub year: String,
+}
+
+impl DateEditor {
+    pub fn from_detail(detail: &knotter_core::dto::ContactDetailDto) -> Self {
+        let focus = if detail.dates.is_empty() {
+            DateEditorFocus::Kind
+        } else {
+            DateEditorFocus::List
+        };
+        Self {
+            contact_id: detail.id,
+            display_name: detail.display_name.clone(),
+            dates: detail.dates.clone(),
+            selected_index: 0,
+            focus,
+            kind: ContactDateKind::Birthday,
+            label: String::new(),
+            month: String::new(),
+            day: String::new(),
+            year: String::new(),
+        }
+    }
+
+    pub fn set_dates(&mut self, dates: Vec<ContactDateDto>) {
+        self.dates = dates;
+        self.selected_index = self.selected_index.min(self.dates.len().saturating_sub(1));
+    }
+
+    pub fn focus_next(&mut self) {
+        let index = self.focus_index();
+        self.focus = DATE_EDITOR_FOCUS_ORDER[(index + 1) % DATE_EDITOR_FOCUS_ORDER.len()];
+    }
+
+    pub fn focus_prev(&mut self) {
+        let index = self.focus_index();
+        let len = DATE_EDITOR_FOCUS_ORDER.len();
+        self.focus = DATE_EDITOR_FOCUS_ORDER[(index + len - 1) % len];
+    }
+
+    fn focus_index(&self) -> usize {
+        DATE_EDITOR_FOCUS_ORDER
+            .iter()
+            .position(|focus| *focus == self.focus)
+            .unwrap_or(0)
+    }
+
+    pub fn move_selection(&mut self, delta: i32) {
+        if self.dates.is_empty() {
+            self.selected_index = 0;
+            return;
+        }
+        let last = self.dates.len() as i32 - 1;
+        self.selected_index = (self.selected_index as i32 + delta).clamp(0, last) as usize;
+    }
+
+    pub fn cycle_kind(&mut self, delta: i32) {
+        let len = DATE_KINDS.len() as i32;
+        let index = DATE_KINDS
+            .iter()
+            .position(|kind| *kind == self.kind)
+            .unwrap_or(0) as i32;
+        self.kind = DATE_KINDS[(index + delta).rem_euclid(len) as usize];
+    }
+
+    pub fn active_field_mut(&mut self) -> Option<&mut String> {
+        match self.focus {
+            DateEditorFocus::Label => Some(&mut self.label),
+            DateEditorFocus::Month => Some(&mut self.month),
+            DateEditorFocus::Day => Some(&mut self.day),
+            DateEditorFocus::Year => Some(&mut self.year),
+            _ => None,
+        }
+    }
+
+    pub fn clear_inputs(&mut self) {
+        self.label.clear();
+        self.month.clear();
+        self.day.clear();
+        self.year.clear();
+    }
+
+    pub fn remove_action(&self) -> Option<Action> {
+        let date = self.dates.get(self.selected_index)?;
+        Some(Action::DeleteContactDate {
+            contact_id: self.contact_id,
+            date_id: date.id,
+        })
+    }
+
+    pub fn to_action(&self) -> Result<Action, String> {
+        let month = parse_date_field(&self.month, "month")?;
+        let day = parse_date_field(&self.day, "day")?;
+        let year = match self.year.trim() {
+            "" => None,
+            raw => Some(
+                raw.parse::<i32>()
+                    .map_err(|_| "year must be a number".to_string())?,
+            ),
+        };
+        let label = normalize_contact_date_label(Some(self.label.clone()));
+        validate_contact_date_parts(self.kind, label.as_deref(), month, day, year)
+            .map_err(|err| err.to_string())?;
+        Ok(Action::AddContactDate(ContactDateNew {
+            contact_id: self.contact_id,
+            kind: self.kind,
+            label,
+            month,
+            day,
+            year,
+            source: Some("tui".to_string()),
+            remind_days_before: None,
+        }))
+    }
+}
+
+fn parse_date_field(raw: &str, name: &str) -> Result<u8, String> {
+    let raw = raw.trim();
+    if raw.is_empty() {
+        return Err(format!("{name} is required"));
+    }
+    raw.parse::<u8>()
+        .map_err(|_| format!("{name} must be a number"))
+}
+
 #[derive(Debug, Clone)]
 pub enum ConfirmAction {
     ClearSchedule(ContactId),
-    ArchiveContact(ContactId),
     UnarchiveContact(ContactId),
     ApplyMerge {
         primary_id: ContactId,
         secondary_id: ContactId,
+        fields: MergeFieldOverrides,
     },
     DismissMerge(knotter_core::domain::MergeCandidateId),
     ApplyAllMerges(Vec<knotter_core::domain::MergeCandidateId>),
+    DeleteInteraction {
+        contact_id: ContactId,
+        interaction_id: InteractionId,
+    },
 }
 
 #[derive(Debug, Clone)]
@@ -1557,19 +2827,27 @@ impl ConfirmState {
     pub fn to_action(&self) -> Option<Action> {
         match &self.action {
             ConfirmAction::ClearSchedule(id) => Some(Action::ClearSchedule(*id)),
-            ConfirmAction::ArchiveContact(id) => Some(Action::ArchiveContact(*id)),
             ConfirmAction::UnarchiveContact(id) => Some(Action::UnarchiveContact(*id)),
             ConfirmAction::ApplyMerge {
                 primary_id,
                 secondary_id,
+                fields,
             } => Some(Action::ApplyMerge {
                 primary_id: *primary_id,
                 secondary_id: *secondary_id,
+                fields: *fields,
             }),
             ConfirmAction::DismissMerge(id) => Some(Action::DismissMerge(*id)),
             ConfirmAction::ApplyAllMerges(candidate_ids) => Some(Action::ApplyAllMerges {
                 candidate_ids: candidate_ids.clone(),
             }),
+            ConfirmAction::DeleteInteraction {
+                contact_id,
+                interaction_id,
+            } => Some(Action::DeleteInteraction {
+                contact_id: *contact_id,
+                interaction_id: *interaction_id,
+            }),
         }
     }
 }
@@ -1587,6 +2865,7 @@ fn default_confirm_return_mode(action: &ConfirmAction) -> ConfirmReturn {
 pub enum ConfirmReturn {
     List,
     MergeList,
+    MergeDetail(MergeDetail),
     Detail(ContactId),
     MergePicker(MergePicker),
 }
@@ -1596,6 +2875,7 @@ impl ConfirmReturn {
         match self {
             ConfirmReturn::List => Mode::List,
             ConfirmReturn::MergeList => Mode::MergeList,
+            ConfirmReturn::MergeDetail(detail) => Mode::MergeDetail(detail),
             ConfirmReturn::Detail(contact_id) => Mode::Detail(contact_id),
             ConfirmReturn::MergePicker(picker) => Mode::ModalMergePicker(picker),
         }
@@ -1626,10 +2906,36 @@ fn parse_emails(raw: &str) -> Vec<String> {
     out
 }
 
+fn filter_archived_selector(filter: &ContactFilter) -> Option<ArchivedSelector> {
+    match filter {
+        FilterExpr::Archived(selector) => Some(*selector),
+        FilterExpr::And(terms) => terms.iter().find_map(filter_archived_selector),
+        _ => None,
+    }
+}
+
+fn without_archived_terms(text: &str) -> String {
+    text.split_whitespace()
+        .filter(|term| !term.starts_with("archived:"))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
 #[cfg(test)]
 mod tests {
-    use super::{MergePicker, MergePickerItem, MergePickerReturn};
-    use knotter_core::domain::ContactId;
+    use super::{
+        App, ArchiveView, LogSeverity, MergeCandidateView, MergeField, MergePicker,
+        MergePickerItem, MergePickerReturn, MergeSide, Mode, ScheduleForm, ACTION_LOG_CAPACITY,
+        FILTER_DEBOUNCE, LIST_PAGE_SIZE, READONLY_ERROR,
+    };
+    use crate::actions::Action;
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+    use knotter_config::ContactDefaultsConfig;
+    use knotter_core::domain::{ContactDateId, ContactDateKind, ContactId, InteractionId};
+    use knotter_core::dto::{ContactDateDto, ContactDetailDto, ContactListItemDto, InteractionDto};
+    use knotter_core::rules::DueState;
+    use knotter_core::time::Clock;
+    use knotter_store::repo::MergePreference;
 
     fn item(name: &str, email: Option<&str>) -> MergePickerItem {
         MergePickerItem {
@@ -1756,4 +3062,718 @@ mod tests {
         picker.move_selection(5);
         assert_eq!(picker.selected_index, 1);
     }
+
+    fn list_items(count: usize) -> Vec<ContactListItemDto> {
+        (0..count)
+            .map(|index| ContactListItemDto {
+                id: ContactId::new(),
+                display_name: format!("Contact {index}"),
+                due_state: DueState::Unscheduled,
+                next_touchpoint_at: None,
+                archived_at: None,
+                archive_reason: None,
+                muted_until: None,
+                tags: Vec::new(),
+                last_interaction_at: None,
+                last_interaction_kind: None,
+                organization: None,
+                priority: 0,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn list_requests_next_page_once_near_loaded_end() {
+        let mut app = App::new(7, None, false, false);
+        while app.next_action().is_some() {}
+        app.apply_list(list_items(LIST_PAGE_SIZE), true);
+
+        app.move_selection(10);
+        assert!(app.next_action().is_none());
+
+        app.move_selection(LIST_PAGE_SIZE as i32);
+        assert!(matches!(app.next_action(), Some(Action::LoadMoreList)));
+        app.move_selection(1);
+        assert!(app.next_action().is_none());
+
+        app.append_list(list_items(5), false);
+        assert_eq!(app.contacts.len(), LIST_PAGE_SIZE + 5);
+        app.move_selection(5);
+        assert!(app.next_action().is_none());
+    }
+
+    #[test]
+    fn filter_typing_requeries_after_debounce_and_enter_applies_now() {
+        let mut app = App::new(7, None, false, false);
+        while app.next_action().is_some() {}
+        app.mode = Mode::FilterEditing;
+
+        for ch in "#fr".chars() {
+            app.handle_key(KeyEvent::new(KeyCode::Char(ch), KeyModifiers::NONE));
+        }
+        let due = app.next_deadline().expect("requery scheduled");
+        app.tick(due - FILTER_DEBOUNCE / 2);
+        assert!(app.next_action().is_none());
+        assert!(app.filter.is_none());
+
+        // Another keystroke pushes the deadline back.
+        app.handle_key(KeyEvent::new(KeyCode::Char('i'), KeyModifiers::NONE));
+        let later = app.next_deadline().expect("requery rescheduled");
+        assert!(later >= due);
+        app.tick(due);
+        assert!(app.next_action().is_none());
+
+        app.tick(later);
+        assert!(matches!(app.next_action(), Some(Action::LoadList)));
+        assert!(app.filter.is_some());
+        assert!(matches!(app.mode, Mode::FilterEditing));
+        assert!(app.next_deadline().is_none());
+
+        // Keys that leave the text alone schedule nothing.
+        app.handle_key(KeyEvent::new(KeyCode::Left, KeyModifiers::NONE));
+        assert!(app.next_deadline().is_none());
+
+        // A pending requery that does not parse shows the error, not a reload.
+        app.handle_key(KeyEvent::new(KeyCode::Char(' '), KeyModifiers::NONE));
+        for ch in "due:".chars() {
+            app.handle_key(KeyEvent::new(KeyCode::Char(ch), KeyModifiers::NONE));
+        }
+        app.tick(app.next_deadline().expect("requery scheduled"));
+        assert!(app.next_action().is_none());
+        assert!(app.filter_error.is_some());
+
+        for ch in "soon".chars() {
+            app.handle_key(KeyEvent::new(KeyCode::Char(ch), KeyModifiers::NONE));
+        }
+        app.handle_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+        assert!(matches!(app.mode, Mode::List));
+        assert!(app.next_deadline().is_none());
+        assert!(app.filter_error.is_none());
+        assert!(matches!(app.next_action(), Some(Action::LoadList)));
+        assert!(app.next_action().is_none());
+    }
+
+    #[test]
+    fn list_refresh_keeps_selected_contact() {
+        let mut app = App::new(7, None, false, false);
+        while app.next_action().is_some() {}
+        let mut items = list_items(10);
+        app.apply_list(items.clone(), false);
+        app.selected = 6;
+        let selected_id = items[6].id;
+
+        items.remove(2);
+        app.apply_list(items.clone(), false);
+        assert_eq!(app.selected_contact_id(), Some(selected_id));
+        assert_eq!(app.selected, 5);
+
+        items.retain(|item| item.id != selected_id);
+        app.apply_list(items, false);
+        assert_eq!(app.selected, 5);
+    }
+
+    #[test]
+    fn number_keys_apply_due_quick_filters_and_zero_restores() {
+        let mut app = App::new(7, None, false, false);
+        while app.next_action().is_some() {}
+        app.mode = Mode::FilterEditing;
+        app.filter_input = "#friends due:any".to_string();
+        app.handle_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+        assert!(matches!(app.mode, Mode::List));
+        while app.next_action().is_some() {}
+
+        app.handle_key(KeyEvent::new(KeyCode::Char('1'), KeyModifiers::NONE));
+        assert_eq!(app.filter_input, "#friends due:overdue");
+        assert!(app.filter.is_some());
+        assert!(matches!(app.next_action(), Some(Action::LoadList)));
+
+        app.handle_key(KeyEvent::new(KeyCode::Char('4'), KeyModifiers::NONE));
+        assert_eq!(app.filter_input, "#friends due:scheduled");
+        app.handle_key(KeyEvent::new(KeyCode::Char('5'), KeyModifiers::NONE));
+        assert_eq!(app.filter_input, "#friends due:none");
+
+        app.handle_key(KeyEvent::new(KeyCode::Char('0'), KeyModifiers::NONE));
+        assert_eq!(app.filter_input, "#friends due:any");
+        while app.next_action().is_some() {}
+
+        // With no quick filter active, 0 leaves the filter alone.
+        app.handle_key(KeyEvent::new(KeyCode::Char('0'), KeyModifiers::NONE));
+        assert_eq!(app.filter_input, "#friends due:any");
+        assert!(app.next_action().is_none());
+
+        app.handle_key(KeyEvent::new(KeyCode::Char('c'), KeyModifiers::NONE));
+        app.handle_key(KeyEvent::new(KeyCode::Char('2'), KeyModifiers::NONE));
+        assert_eq!(app.filter_input, "due:today");
+        app.handle_key(KeyEvent::new(KeyCode::Char('0'), KeyModifiers::NONE));
+        assert!(app.filter_input.is_empty());
+        assert!(app.filter.is_none());
+    }
+
+    #[test]
+    fn v_cycles_archive_views_and_drops_filter_archived_term() {
+        let mut app = App::new(7, None, false, false);
+        while app.next_action().is_some() {}
+        assert_eq!(app.effective_archive_view(), ArchiveView::Active);
+
+        app.handle_key(KeyEvent::new(KeyCode::Char('v'), KeyModifiers::NONE));
+        assert_eq!(app.archive_view, ArchiveView::Archived);
+        assert!(matches!(app.next_action(), Some(Action::LoadList)));
+        app.handle_key(KeyEvent::new(KeyCode::Char('v'), KeyModifiers::NONE));
+        assert_eq!(app.archive_view, ArchiveView::All);
+        app.handle_key(KeyEvent::new(KeyCode::Char('v'), KeyModifiers::NONE));
+        assert_eq!(app.archive_view, ArchiveView::Active);
+        while app.next_action().is_some() {}
+
+        // A filter term wins over the toggle, and `v` continues from it.
+        app.mode = Mode::FilterEditing;
+        app.filter_input = "#friends archived:true".to_string();
+        app.handle_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+        assert_eq!(app.effective_archive_view(), ArchiveView::Archived);
+        while app.next_action().is_some() {}
+
+        app.handle_key(KeyEvent::new(KeyCode::Char('v'), KeyModifiers::NONE));
+        assert_eq!(app.archive_view, ArchiveView::All);
+        assert_eq!(app.filter_input, "#friends");
+        assert_eq!(app.effective_archive_view(), ArchiveView::All);
+        assert!(matches!(app.next_action(), Some(Action::LoadList)));
+    }
+
+    #[test]
+    fn schedule_form_accepts_relative_dates_and_echoes_parsed_date() {
+        let id = ContactId::new();
+        let mut form = ScheduleForm::new(id);
+        form.date = "+2w".to_string();
+        match form.to_action(&Clock::default()) {
+            Ok(Action::ScheduleContact(contact_id, timestamp)) => {
+                assert_eq!(contact_id, id);
+                assert!(timestamp > knotter_core::time::now_utc() + 13 * 86_400);
+            }
+            other => panic!("unexpected action: {other:?}"),
+        }
+
+        form.date = "2000-01-01".to_string();
+        let err = form.to_action(&Clock::default()).unwrap_err();
+        assert!(err.contains("parsed as 2000-01-01"), "{err}");
+
+        form.date = "in two weeks".to_string();
+        let err = form.to_action(&Clock::default()).unwrap_err();
+        assert!(err.contains("invalid relative date"), "{err}");
+    }
+
+    #[test]
+    fn archive_key_opens_reason_form_and_enter_archives() {
+        let mut app = App::new(7, None, false, false);
+        while app.next_action().is_some() {}
+        let items = list_items(1);
+        let id = items[0].id;
+        app.apply_list(items, false);
+
+        app.handle_key(KeyEvent::new(KeyCode::Char('A'), KeyModifiers::SHIFT));
+        assert!(matches!(app.mode, Mode::ModalArchive(_)));
+        for ch in "moved".chars() {
+            app.handle_key(KeyEvent::new(KeyCode::Char(ch), KeyModifiers::NONE));
+        }
+        app.handle_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+
+        assert!(matches!(app.mode, Mode::List));
+        match app.next_action() {
+            Some(Action::ArchiveContact(contact_id, reason)) => {
+                assert_eq!(contact_id, id);
+                assert_eq!(reason.as_deref(), Some("moved"));
+            }
+            other => panic!("unexpected action: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn merge_list_requests_next_page_near_loaded_end() {
+        let mut app = App::new(7, None, false, false);
+        while app.next_action().is_some() {}
+        let view = || MergeCandidateView {
+            id: knotter_core::domain::MergeCandidateId::new(),
+            reason: "email".to_string(),
+            auto_merge_safe: false,
+            contact_a_id: ContactId::new(),
+            contact_b_id: ContactId::new(),
+            preferred_contact_id: None,
+            contact_a_name: "Ada".to_string(),
+            contact_b_name: "Ada L".to_string(),
+        };
+        app.apply_merge_candidates((0..12).map(|_| view()).collect(), true);
+        app.mode = Mode::MergeList;
+
+        app.handle_key(KeyEvent::new(KeyCode::Char('j'), KeyModifiers::NONE));
+        assert!(app.next_action().is_none());
+        app.handle_key(KeyEvent::new(KeyCode::Char('j'), KeyModifiers::NONE));
+        assert!(matches!(app.next_action(), Some(Action::LoadMoreMerges)));
+        app.handle_key(KeyEvent::new(KeyCode::Char('j'), KeyModifiers::NONE));
+        assert!(
+            app.next_action().is_none(),
+            "only one page request in flight"
+        );
+
+        app.append_merge_candidates((0..3).map(|_| view()).collect(), false);
+        assert_eq!(app.merge_candidates.len(), 15);
+        app.handle_key(KeyEvent::new(KeyCode::End, KeyModifiers::NONE));
+        assert_eq!(app.merge_selected, 14);
+        assert!(app.next_action().is_none());
+    }
+
+    #[test]
+    fn merge_detail_toggles_field_winners_and_confirms_with_overrides() {
+        let mut app = App::new(7, None, false, false);
+        while app.next_action().is_some() {}
+        let (ada, ada_l) = (ContactId::new(), ContactId::new());
+        let candidate_id = knotter_core::domain::MergeCandidateId::new();
+        app.apply_merge_candidates(
+            vec![MergeCandidateView {
+                id: candidate_id,
+                reason: "email".to_string(),
+                auto_merge_safe: false,
+                contact_a_id: ada,
+                contact_b_id: ada_l,
+                preferred_contact_id: None,
+                contact_a_name: "Ada".to_string(),
+                contact_b_name: "Ada L".to_string(),
+            }],
+            false,
+        );
+        app.mode = Mode::MergeList;
+
+        app.handle_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+        assert!(matches!(app.mode, Mode::MergeDetail(_)));
+        assert!(matches!(
+            app.next_action(),
+            Some(Action::LoadMergeDetail(id)) if id == candidate_id
+        ));
+        let Mode::MergeDetail(detail) = &mut app.mode else {
+            unreachable!();
+        };
+        let primary = MergeSide {
+            display_name: "Ada".to_string(),
+            phone: Some("+1 555 0100".to_string()),
+            next_touchpoint_at: Some(2_000),
+            tags: vec!["friends".to_string()],
+            ..MergeSide::default()
+        };
+        let secondary = MergeSide {
+            display_name: "Ada Lovelace".to_string(),
+            phone: Some("+1 555 0100".to_string()),
+            handle: Some("@ada".to_string()),
+            next_touchpoint_at: Some(1_000),
+            tags: vec!["work".to_string()],
+            ..MergeSide::default()
+        };
+        detail.set_sides(ada, &primary, ada_l, &secondary, &Clock::default());
+        let winner = |detail: &super::MergeDetail, field: MergeField| {
+            detail
+                .rows
+                .iter()
+                .find(|row| row.field == field)
+                .and_then(|row| row.winner)
+        };
+        assert_eq!(
+            winner(detail, MergeField::Name),
+            Some(MergePreference::Primary)
+        );
+        assert_eq!(winner(detail, MergeField::Phone), None);
+        assert_eq!(winner(detail, MergeField::Handle), None);
+        assert_eq!(winner(detail, MergeField::Tags), None);
+        assert_eq!(
+            winner(detail, MergeField::NextTouchpoint),
+            Some(MergePreference::Secondary)
+        );
+
+        // Name is the first row; space flips it to the secondary contact.
+        app.handle_key(KeyEvent::new(KeyCode::Char(' '), KeyModifiers::NONE));
+        // Space on a non-conflicting row (phone) does nothing.
+        app.handle_key(KeyEvent::new(KeyCode::Char('j'), KeyModifiers::NONE));
+        app.handle_key(KeyEvent::new(KeyCode::Char('j'), KeyModifiers::NONE));
+        app.handle_key(KeyEvent::new(KeyCode::Char(' '), KeyModifiers::NONE));
+
+        app.handle_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+        assert!(matches!(app.mode, Mode::Confirm(_)));
+        app.handle_key(KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE));
+        assert!(matches!(app.mode, Mode::MergeDetail(_)));
+
+        app.handle_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+        app.handle_key(KeyEvent::new(KeyCode::Char('y'), KeyModifiers::NONE));
+        assert!(matches!(app.mode, Mode::MergeList));
+        match app.next_action() {
+            Some(Action::ApplyMerge {
+                primary_id,
+                secondary_id,
+                fields,
+            }) => {
+                assert_eq!((primary_id, secondary_id), (ada, ada_l));
+                assert_eq!(fields.display_name, Some(MergePreference::Secondary));
+                assert_eq!(fields.phone, None);
+                assert_eq!(fields.next_touchpoint_at, Some(MergePreference::Secondary));
+            }
+            other => panic!("unexpected action: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn readonly_blocks_mutating_keys_but_allows_navigation() {
+        let mut app = App::new(7, None, false, false).with_readonly(true);
+        while app.next_action().is_some() {}
+        let items = list_items(1);
+        let id = items[0].id;
+        app.apply_list(items, false);
+
+        for ch in ['a', 'e', 'n', 't', 's', 'x', 'A', 'M'] {
+            app.handle_key(KeyEvent::new(KeyCode::Char(ch), KeyModifiers::NONE));
+            assert!(matches!(app.mode, Mode::List), "key {ch} left list mode");
+            assert!(app.next_action().is_none(), "key {ch} queued an action");
+        }
+        assert_eq!(app.error.as_deref(), Some(READONLY_ERROR));
+
+        app.handle_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+        assert!(matches!(app.mode, Mode::Detail(contact_id) if contact_id == id));
+        assert!(matches!(app.next_action(), Some(Action::LoadDetail(_))));
+
+        app.handle_key(KeyEvent::new(KeyCode::Char('m'), KeyModifiers::NONE));
+        assert!(matches!(app.mode, Mode::MergeList));
+        assert!(matches!(app.next_action(), Some(Action::LoadMerges)));
+        app.handle_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+        assert!(matches!(app.mode, Mode::MergeList));
+    }
+
+    #[test]
+    fn add_form_prefills_contact_defaults_and_saves_tags() {
+        let defaults = ContactDefaultsConfig {
+            tags: vec!["friends".to_string(), "work".to_string()],
+            cadence_days: Some(45),
+            timezone: Some("Europe/Berlin".to_string()),
+            next_touchpoint: Some("+7d".to_string()),
+        };
+        let mut app = App::new(7, Some(30), false, false).with_contact_defaults(defaults);
+        while app.next_action().is_some() {}
+
+        app.handle_key(KeyEvent::new(KeyCode::Char('a'), KeyModifiers::NONE));
+        let Mode::ModalAddContact(form) = &mut app.mode else {
+            panic!("add form not opened");
+        };
+        assert_eq!(form.cadence_days, "45");
+        assert_eq!(form.timezone, "Europe/Berlin");
+        assert_eq!(form.tags, "friends, work");
+        assert_eq!(form.next_touchpoint_at.len(), "YYYY-MM-DD".len());
+
+        form.name = "Ada".to_string();
+        form.tags = "friends".to_string();
+        match form.to_action(&Clock::default()) {
+            Ok(Action::CreateContact(input, _, tags)) => {
+                assert_eq!(input.cadence_days, Some(45));
+                assert_eq!(input.timezone.as_deref(), Some("Europe/Berlin"));
+                assert!(input.next_touchpoint_at.is_some());
+                let tags: Vec<&str> = tags.iter().map(|tag| tag.as_str()).collect();
+                assert_eq!(tags, ["friends"]);
+            }
+            other => panic!("unexpected action: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn saving_contact_form_normalizes_phone_when_enabled() {
+        let mut app = App::new(7, None, false, false).with_normalize_phones(true);
+        while app.next_action().is_some() {}
+
+        app.handle_key(KeyEvent::new(KeyCode::Char('a'), KeyModifiers::NONE));
+        let Mode::ModalAddContact(form) = &mut app.mode else {
+            panic!("add form not opened");
+        };
+        form.name = "Ada".to_string();
+        form.phone = "+1 (415) 555-1212".to_string();
+        form.focus = form.field_count();
+        app.handle_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+
+        match app.next_action() {
+            Some(Action::CreateContact(input, _, _)) => {
+                assert_eq!(input.phone.as_deref(), Some("+14155551212"));
+            }
+            other => panic!("unexpected action: {other:?}"),
+        }
+    }
+
+    fn detail_with_interactions(id: ContactId, notes: &[&str]) -> ContactDetailDto {
+        ContactDetailDto {
+            id,
+            display_name: "Ada".to_string(),
+            email: None,
+            emails: Vec::new(),
+            phone: None,
+            handle: None,
+            timezone: None,
+            organization: None,
+            next_touchpoint_at: None,
+            cadence_days: None,
+            created_at: 0,
+            updated_at: 0,
+            archived_at: None,
+            archive_reason: None,
+            muted_until: None,
+            priority: 0,
+            tags: Vec::new(),
+            aliases: Vec::new(),
+            origin: None,
+            dates: Vec::new(),
+            interactions_total: notes.len(),
+            recent_interactions: notes
+                .iter()
+                .enumerate()
+                .map(|(index, note)| InteractionDto {
+                    id: InteractionId::new(),
+                    occurred_at: 1_700_000_000 - index as i64 * 86_400,
+                    kind: "call".to_string(),
+                    note: note.to_string(),
+                    follow_up_at: None,
+                    direction: None,
+                    attachments: Vec::new(),
+                })
+                .collect(),
+            provenance: Vec::new(),
+            sync_history: Vec::new(),
+            last_touch_context: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn detail_fetches_older_interactions_when_selection_nears_the_end() {
+        let mut app = App::new(7, None, false, false);
+        while app.next_action().is_some() {}
+        let contact_id = ContactId::new();
+        app.mode = Mode::Detail(contact_id);
+        let notes: Vec<String> = (0..8).map(|index| format!("note {index}")).collect();
+        let notes: Vec<&str> = notes.iter().map(String::as_str).collect();
+        let mut detail = detail_with_interactions(contact_id, &notes);
+        detail.interactions_total = 12;
+        app.apply_detail(detail);
+        assert_eq!(app.interactions_loaded_partially(), Some((8, 12)));
+
+        app.handle_key(KeyEvent::new(KeyCode::Tab, KeyModifiers::NONE));
+        app.handle_key(KeyEvent::new(KeyCode::Char('j'), KeyModifiers::NONE));
+        assert!(app.next_action().is_none());
+        app.handle_key(KeyEvent::new(KeyCode::Char('j'), KeyModifiers::NONE));
+        app.handle_key(KeyEvent::new(KeyCode::Char('j'), KeyModifiers::NONE));
+        assert!(matches!(
+            app.next_action(),
+            Some(Action::LoadMoreInteractions(id)) if id == contact_id
+        ));
+        app.handle_key(KeyEvent::new(KeyCode::Char('j'), KeyModifiers::NONE));
+        assert!(
+            app.next_action().is_none(),
+            "only one page request in flight"
+        );
+
+        let older = detail_with_interactions(contact_id, &["a", "b", "c", "d"]);
+        app.append_interactions(contact_id, older.recent_interactions);
+        assert_eq!(app.interactions_loaded_partially(), None);
+        assert_eq!(
+            app.detail
+                .as_ref()
+                .expect("detail")
+                .recent_interactions
+                .len(),
+            12
+        );
+        app.handle_key(KeyEvent::new(KeyCode::Char('j'), KeyModifiers::NONE));
+        assert!(app.next_action().is_none());
+    }
+
+    #[test]
+    fn detail_sync_history_toggles_and_collapses_for_other_contacts() {
+        let mut app = App::new(7, None, false, false);
+        while app.next_action().is_some() {}
+        let contact_id = ContactId::new();
+        app.mode = Mode::Detail(contact_id);
+        app.apply_detail(detail_with_interactions(contact_id, &[]));
+
+        app.handle_key(KeyEvent::new(KeyCode::Char('H'), KeyModifiers::NONE));
+        assert!(app.sync_history_expanded);
+        app.apply_detail(detail_with_interactions(contact_id, &[]));
+        assert!(app.sync_history_expanded);
+
+        let other_id = ContactId::new();
+        app.mode = Mode::Detail(other_id);
+        app.apply_detail(detail_with_interactions(other_id, &[]));
+        assert!(!app.sync_history_expanded);
+    }
+
+    #[test]
+    fn detail_interactions_pane_selects_edits_and_deletes() {
+        let mut app = App::new(7, None, false, false);
+        while app.next_action().is_some() {}
+        let contact_id = ContactId::new();
+        app.mode = Mode::Detail(contact_id);
+        app.apply_detail(detail_with_interactions(contact_id, &["first", "second"]));
+        let second_id = app.detail.as_ref().expect("detail").recent_interactions[1].id;
+
+        app.handle_key(KeyEvent::new(KeyCode::Tab, KeyModifiers::NONE));
+        assert!(app.interactions_focused);
+        app.handle_key(KeyEvent::new(KeyCode::Char('j'), KeyModifiers::NONE));
+        assert_eq!(app.interaction_selected, 1);
+        assert_eq!(app.detail_scroll, 3);
+
+        app.handle_key(KeyEvent::new(KeyCode::Char('e'), KeyModifiers::NONE));
+        match &app.mode {
+            Mode::ModalEditNote(form) => {
+                assert_eq!(form.interaction_id, Some(second_id));
+                assert_eq!(form.note, "second");
+            }
+            other => panic!("unexpected mode: {other:?}"),
+        }
+        // Tab to the note field and append, then save.
+        app.handle_key(KeyEvent::new(KeyCode::Tab, KeyModifiers::NONE));
+        app.handle_key(KeyEvent::new(KeyCode::Tab, KeyModifiers::NONE));
+        app.handle_key(KeyEvent::new(KeyCode::Char('!'), KeyModifiers::NONE));
+        app.handle_key(KeyEvent::new(KeyCode::Tab, KeyModifiers::NONE));
+        app.handle_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+        assert!(matches!(app.mode, Mode::Detail(id) if id == contact_id));
+        match app.next_action() {
+            Some(Action::UpdateInteraction {
+                interaction_id,
+                update,
+                ..
+            }) => {
+                assert_eq!(interaction_id, second_id);
+                assert_eq!(update.note.as_deref(), Some("second!"));
+                assert_eq!(update.occurred_at, None);
+            }
+            other => panic!("unexpected action: {other:?}"),
+        }
+
+        app.handle_key(KeyEvent::new(KeyCode::Char('d'), KeyModifiers::NONE));
+        assert!(matches!(app.mode, Mode::Confirm(_)));
+        app.handle_key(KeyEvent::new(KeyCode::Char('y'), KeyModifiers::NONE));
+        assert!(matches!(app.mode, Mode::Detail(id) if id == contact_id));
+        match app.next_action() {
+            Some(Action::DeleteInteraction { interaction_id, .. }) => {
+                assert_eq!(interaction_id, second_id);
+            }
+            other => panic!("unexpected action: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn date_editor_validates_adds_and_removes_dates() {
+        let mut app = App::new(7, None, false, false);
+        while app.next_action().is_some() {}
+        let contact_id = ContactId::new();
+        app.mode = Mode::Detail(contact_id);
+        let mut detail = detail_with_interactions(contact_id, &[]);
+        let birthday = ContactDateDto {
+            id: ContactDateId::new(),
+            kind: ContactDateKind::Birthday,
+            label: None,
+            month: 12,
+            day: 10,
+            year: Some(1815),
+            remind_days_before: None,
+        };
+        detail.dates = vec![birthday.clone()];
+        app.apply_detail(detail.clone());
+
+        let key = |code| KeyEvent::new(code, KeyModifiers::NONE);
+        let type_text = |app: &mut App, text: &str| {
+            for ch in text.chars() {
+                app.handle_key(KeyEvent::new(KeyCode::Char(ch), KeyModifiers::NONE));
+            }
+        };
+
+        app.handle_key(key(KeyCode::Char('b')));
+        assert!(matches!(app.mode, Mode::ModalEditDates(_)));
+        app.handle_key(key(KeyCode::Char('d')));
+        match app.next_action() {
+            Some(Action::DeleteContactDate { date_id, .. }) => assert_eq!(date_id, birthday.id),
+            other => panic!("unexpected action: {other:?}"),
+        }
+
+        // Kind -> custom, then month/day/year for a leap day in a non-leap year.
+        app.handle_key(key(KeyCode::Tab));
+        app.handle_key(key(KeyCode::Left));
+        app.handle_key(key(KeyCode::Tab));
+        app.handle_key(key(KeyCode::Tab));
+        type_text(&mut app, "2");
+        app.handle_key(key(KeyCode::Tab));
+        type_text(&mut app, "29");
+        app.handle_key(key(KeyCode::Tab));
+        type_text(&mut app, "2023");
+        app.handle_key(key(KeyCode::Enter));
+        assert!(app
+            .error
+            .as_deref()
+            .is_some_and(|err| err.contains("label")));
+        assert!(app.next_action().is_none());
+
+        for _ in 0..3 {
+            app.handle_key(key(KeyCode::BackTab));
+        }
+        type_text(&mut app, "Leap");
+        app.handle_key(key(KeyCode::Enter));
+        assert!(app.error.as_deref().is_some_and(|err| err.contains("day")));
+        assert!(app.next_action().is_none());
+
+        for _ in 0..3 {
+            app.handle_key(key(KeyCode::Tab));
+        }
+        for _ in 0..4 {
+            app.handle_key(key(KeyCode::Backspace));
+        }
+        app.handle_key(key(KeyCode::Enter));
+        match app.next_action() {
+            Some(Action::AddContactDate(input)) => {
+                assert_eq!(input.contact_id, contact_id);
+                assert_eq!(input.kind, ContactDateKind::Custom);
+                assert_eq!(input.label.as_deref(), Some("Leap"));
+                assert_eq!((input.month, input.day, input.year), (2, 29, None));
+            }
+            other => panic!("unexpected action: {other:?}"),
+        }
+        match &app.mode {
+            Mode::ModalEditDates(editor) => assert!(editor.month.is_empty()),
+            other => panic!("unexpected mode: {other:?}"),
+        }
+
+        detail.dates.clear();
+        app.apply_detail(detail);
+        match &app.mode {
+            Mode::ModalEditDates(editor) => assert!(editor.dates.is_empty()),
+            other => panic!("unexpected mode: {other:?}"),
+        }
+        app.handle_key(key(KeyCode::Esc));
+        assert!(matches!(app.mode, Mode::Detail(id) if id == contact_id));
+    }
+
+    #[test]
+    fn action_log_keeps_recent_messages_and_opens_over_the_current_screen() {
+        let mut app = App::new(7, None, false, false);
+        while app.next_action().is_some() {}
+        app.apply_list(list_items(1), false);
+
+        for index in 0..ACTION_LOG_CAPACITY + 5 {
+            app.set_status(format!("status {index}"));
+        }
+        app.set_action_error("set tags", "tag is invalid");
+        assert_eq!(app.action_log.len(), ACTION_LOG_CAPACITY);
+        assert_eq!(app.action_log[0].message, "status 6");
+        let newest = app.action_log.back().expect("entry");
+        assert_eq!(newest.severity, LogSeverity::Error);
+        assert_eq!(newest.message, "set tags: tag is invalid");
+        assert_eq!(app.error.as_deref(), Some("tag is invalid"));
+
+        app.handle_key(KeyEvent::new(KeyCode::Char('!'), KeyModifiers::NONE));
+        app.handle_key(KeyEvent::new(KeyCode::Char('G'), KeyModifiers::NONE));
+        match &app.mode {
+            Mode::ActionLog(view) => {
+                assert_eq!(view.scroll, ACTION_LOG_CAPACITY - 1);
+                assert!(matches!(*view.previous, Mode::List));
+            }
+            other => panic!("unexpected mode: {other:?}"),
+        }
+        app.handle_key(KeyEvent::new(KeyCode::Char('a'), KeyModifiers::NONE));
+        assert!(app.next_action().is_none());
+        app.handle_key(KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE));
+        assert!(matches!(app.mode, Mode::List));
+    }
 }
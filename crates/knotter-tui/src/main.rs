@@ -7,7 +7,7 @@ use std::io::{self, Stdout};
 use std::path::PathBuf;
 use std::time::{Duration, Instant};
 
-use anyhow::{Context as _, Result};
+use anyhow::{anyhow, Context as _, Result};
 use clap::Parser;
 use crossterm::event::{self, Event};
 use crossterm::execute;
@@ -21,6 +21,7 @@ use crate::actions::execute_action;
 use crate::app::App;
 use knotter_config as config;
 use knotter_core::rules::validate_soon_days;
+use knotter_core::time::{today_override, Clock, TODAY_ENV};
 use knotter_store::{paths, Store};
 
 #[derive(Debug, Parser)]
@@ -28,27 +29,57 @@ use knotter_store::{paths, Store};
 struct Args {
     #[arg(long)]
     db_path: Option<PathBuf>,
+    /// Base directory for the database (overrides KNOTTER_DATA_DIR)
+    #[arg(long, value_name = "DIR")]
+    data_dir: Option<PathBuf>,
     #[arg(long)]
     config: Option<PathBuf>,
     #[arg(long)]
     soon_days: Option<i64>,
+    /// Open the database read-only and disable editing
+    #[arg(long)]
+    readonly: bool,
 }
 
 fn main() -> Result<()> {
     let args = Args::parse();
+    if let Some(dir) = args.data_dir.clone() {
+        paths::set_data_dir_override(dir);
+    }
     let app_config = config::load(args.config.clone()).with_context(|| "load config")?;
-
-    let db_path = paths::resolve_db_path(args.db_path).with_context(|| "resolve database path")?;
-
-    let store = Store::open(&db_path)?;
-    store.migrate()?;
+    let today =
+        today_override(None).map_err(|_| anyhow!("invalid {TODAY_ENV}: expected YYYY-MM-DD"))?;
+    let clock = Clock::new(app_config.timezone, today);
+
+    let db_path = if args.readonly {
+        paths::resolve_db_path_readonly(args.db_path)
+    } else {
+        paths::resolve_db_path(args.db_path)
+    }
+    .with_context(|| "resolve database path")?;
+
+    let store = if args.readonly {
+        let store = Store::open_readonly(&db_path)?;
+        store.check_schema()?;
+        store
+    } else {
+        let store = Store::open(&db_path)?;
+        store.migrate()?;
+        store
+    };
 
     let soon_days = validate_soon_days(args.soon_days.unwrap_or(app_config.due_soon_days))?;
     let mut app = App::new(
         soon_days,
         app_config.default_cadence_days,
         app_config.interactions.auto_reschedule,
-    );
+        app_config.tui.colorize_rows,
+    )
+    .with_readonly(args.readonly)
+    .with_clock(clock)
+    .with_contact_defaults(app_config.contacts.defaults.clone())
+    .with_normalize_phones(app_config.contacts.normalize_phones)
+    .with_interaction_dedupe_window(app_config.interactions.dedupe_window_minutes);
 
     let mut terminal = TerminalGuard::new()?;
     run_app(&mut terminal, &store, &mut app)
@@ -59,9 +90,11 @@ fn run_app(terminal: &mut TerminalGuard, store: &Store, app: &mut App) -> Result
     let mut last_tick = Instant::now();
 
     loop {
+        app.tick(Instant::now());
         while let Some(action) = app.next_action() {
+            let name = action.name();
             if let Err(err) = execute_action(app, store, action) {
-                app.set_error(err.to_string());
+                app.set_action_error(name, err.to_string());
             }
         }
 
@@ -71,9 +104,12 @@ fn run_app(terminal: &mut TerminalGuard, store: &Store, app: &mut App) -> Result
             break;
         }
 
-        let timeout = tick_rate
+        let mut timeout = tick_rate
             .checked_sub(last_tick.elapsed())
             .unwrap_or(Duration::from_secs(0));
+        if let Some(deadline) = app.next_deadline() {
+            timeout = timeout.min(deadline.saturating_duration_since(Instant::now()));
+        }
         if event::poll(timeout)? {
             match event::read()? {
                 Event::Key(key) => app.handle_key(key),
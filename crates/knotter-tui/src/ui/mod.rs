@@ -4,17 +4,24 @@ use ratatui::text::{Line, Span, Text};
 use ratatui::widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph, Wrap};
 use ratatui::Frame;
 
-use knotter_core::rules::DueState;
-use knotter_core::time::{format_date_parts, format_timestamp_date, format_timestamp_datetime};
+use chrono::NaiveDate;
+use knotter_core::domain::{is_starred, ContactId};
+use knotter_core::dto::{ContactDateDto, ContactListItemDto};
+use knotter_core::rules::{days_until_next_occurrence, local_today, DueState};
+use knotter_core::time::{format_date_parts, Clock};
 
 use crate::app::{
-    App, ConfirmState, ContactForm, MergePicker, MergePickerFocus, Mode, NoteForm, ScheduleForm,
-    TagEditor, TagEditorFocus,
+    ActionLogView, App, ArchiveForm, ConfirmState, ContactForm, DateEditor, DateEditorFocus,
+    LogSeverity, MergeDetail, MergeFieldRow, MergePicker, MergePickerFocus, Mode, NoteForm,
+    ScheduleForm, TagEditor, TagEditorFocus,
 };
+use crate::util::initials;
+use knotter_store::repo::MergePreference;
 
 pub fn draw(frame: &mut Frame<'_>, app: &App) {
     let size = frame.area();
-    let header_lines = 1 + usize::from(app.filter_error.is_some());
+    let header_lines =
+        1 + usize::from(!app.due_counts.is_empty()) + usize::from(app.filter_error.is_some());
     let footer_lines = 1 + usize::from(app.error.is_some()) + usize::from(app.status.is_some());
     let header_height = (header_lines + 2) as u16;
     let footer_height = (footer_lines + 2) as u16;
@@ -29,9 +36,15 @@ pub fn draw(frame: &mut Frame<'_>, app: &App) {
 
     render_header(frame, chunks[0], app);
 
-    match &app.mode {
+    // The log overlay keeps the screen it was opened from underneath.
+    let screen = match &app.mode {
+        Mode::ActionLog(view) => view.previous.as_ref(),
+        mode => mode,
+    };
+    match screen {
         Mode::Detail(_) => render_detail(frame, chunks[1], app),
         Mode::MergeList => render_merge_list(frame, chunks[1], app),
+        Mode::MergeDetail(detail) => render_merge_detail(frame, chunks[1], detail),
         _ => render_list(frame, chunks[1], app),
     }
 
@@ -44,11 +57,14 @@ pub fn draw(frame: &mut Frame<'_>, app: &App) {
     match &app.mode {
         Mode::ModalAddContact(form) => render_contact_form(frame, size, "Add Contact", form),
         Mode::ModalEditContact(form) => render_contact_form(frame, size, "Edit Contact", form),
-        Mode::ModalAddNote(form) => render_note_form(frame, size, form),
+        Mode::ModalAddNote(form) | Mode::ModalEditNote(form) => render_note_form(frame, size, form),
         Mode::ModalEditTags(editor) => render_tag_editor(frame, size, editor),
+        Mode::ModalEditDates(editor) => render_date_editor(frame, size, editor, &app.clock),
         Mode::ModalSchedule(form) => render_schedule_form(frame, size, form),
+        Mode::ModalArchive(form) => render_archive_form(frame, size, form),
         Mode::ModalMergePicker(picker) => render_merge_picker(frame, size, picker),
         Mode::Confirm(state) => render_confirm(frame, size, state),
+        Mode::ActionLog(view) => render_action_log(frame, size, app, view),
         _ => {}
     }
 }
@@ -59,12 +75,27 @@ fn render_header(frame: &mut Frame<'_>, area: Rect, app: &App) {
     } else {
         app.filter_input.clone()
     };
+    let more = if app.list_has_more { "+" } else { "" };
     let title = format!(
-        "knotter  contacts: {}  filter: {}",
+        "knotter  contacts: {}{}  view: {}  filter: {}",
         app.contacts.len(),
+        more,
+        app.effective_archive_view().label(),
         filter_display
     );
-    let mut lines = vec![Line::from(title)];
+    let mut spans = vec![Span::raw(title)];
+    if app.readonly {
+        spans.push(Span::styled(
+            "  [read-only]",
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        ));
+    }
+    let mut lines = vec![Line::from(spans)];
+    if !app.due_counts.is_empty() {
+        lines.push(due_summary_line(&app.due_counts));
+    }
     if let Some(err) = &app.filter_error {
         lines.push(Line::from(Span::styled(
             err.clone(),
@@ -77,18 +108,53 @@ fn render_header(frame: &mut Frame<'_>, area: Rect, app: &App) {
     frame.render_widget(paragraph, area);
 }
 
+/// Per-bucket counts, each prefixed with the number key that filters to it.
+fn due_summary_line(counts: &[(DueState, usize)]) -> Line<'static> {
+    let mut spans = Vec::new();
+    for (index, (state, count)) in counts.iter().enumerate() {
+        if index > 0 {
+            spans.push(Span::raw("  "));
+        }
+        let (label, style) = due_badge(*state);
+        spans.push(Span::styled(
+            format!("{}", index + 1),
+            Style::default().add_modifier(Modifier::DIM),
+        ));
+        spans.push(Span::raw(" "));
+        spans.push(Span::styled(format!("{label} {count}"), style));
+    }
+    Line::from(spans)
+}
+
 fn render_footer(frame: &mut Frame<'_>, area: Rect, app: &App) {
     let hint = match app.mode {
-        Mode::List => "j/k move  enter detail  / filter  a add  e edit  n note  t tags  s schedule  x clear  A archive  v archived  m merges  M merge-with  ? help",
-        Mode::Detail(_) => "esc back  j/k scroll  e edit  n note  t tags  s schedule  x clear  A archive  m merges  M merge-with  ? help",
+        Mode::List if app.readonly => {
+            "j/k move  enter detail  / filter  1-5 due  v archived  m merges  ! log  ? help"
+        }
+        Mode::Detail(_) if app.readonly => "esc back  j/k scroll  tab interactions  H history  m merges  ! log  ? help",
+        Mode::MergeList if app.readonly => "j/k move  enter details  r refresh  esc back",
+        Mode::MergeDetail(_) if app.readonly => "j/k move  r refresh  esc back",
+        Mode::List => "j/k move  enter detail  / filter  1-5 due  a add  e edit  n note  t tags  s schedule  x clear  A archive  * star  v archived  m merges  M merge-with  ! log  ? help",
+        Mode::Detail(_) if app.interactions_focused => {
+            "esc back  tab contact  j/k select  e edit note  d delete note  n note  ! log  ? help"
+        }
+        Mode::Detail(_) => "esc back  j/k scroll  tab interactions  e edit  n note  t tags  b dates  s schedule  x clear  A archive  H history  m merges  M merge-with  ! log  ? help",
         Mode::MergeList => {
-            "j/k move  enter merge  p prefer  d dismiss  a/A apply-all  r refresh  esc back"
+            "j/k move  enter details  p prefer  d dismiss  a/A apply-all  r refresh  esc back"
         }
-        Mode::FilterEditing => "enter apply  esc cancel",
+        Mode::MergeDetail(_) => {
+            "j/k move  space toggle  h/l keep left/right  enter merge  p prefer  d dismiss  r refresh  esc back"
+        }
+        Mode::FilterEditing => "type to filter  enter apply  esc close",
+        Mode::ActionLog(_) => "j/k scroll  g/G newest/oldest  esc close",
         Mode::ModalAddContact(_) | Mode::ModalEditContact(_) => {
             "tab next  shift+tab prev  enter select  ctrl+n set now  esc cancel"
         }
         Mode::ModalSchedule(_) => "tab next  shift+tab prev  enter select  ctrl+n set now  esc cancel",
+        Mode::ModalArchive(_) => "type reason  tab next  shift+tab prev  enter archive  esc cancel",
+        Mode::ModalEditDates(_) => {
+            "tab next  shift+tab prev  j/k move  d remove  left/right kind  enter add  esc back"
+        }
         Mode::ModalMergePicker(_) => {
             "tab next  shift+tab prev  enter select  ctrl+r refresh  esc cancel"
         }
@@ -126,59 +192,13 @@ fn render_list(frame: &mut Frame<'_>, area: Rect, app: &App) {
         return;
     }
 
-    let items: Vec<ListItem> = app
-        .contacts
-        .iter()
-        .map(|contact| {
-            let (label, style) = due_badge(contact.due_state);
-            let due_span = Span::styled(format!("[{}]", label), style);
-            let next = contact
-                .next_touchpoint_at
-                .map(format_timestamp_date)
-                .unwrap_or_else(|| "-".to_string());
-            let tags = if contact.tags.is_empty() {
-                "".to_string()
-            } else {
-                contact
-                    .tags
-                    .iter()
-                    .map(|tag| format!("#{}", tag))
-                    .collect::<Vec<_>>()
-                    .join(" ")
-            };
-            let name_style = if contact.archived_at.is_some() {
-                Style::default().fg(Color::DarkGray)
-            } else {
-                Style::default().add_modifier(Modifier::BOLD)
-            };
-            let archived_badge = if contact.archived_at.is_some() {
-                Some(Span::styled(
-                    "[archived]",
-                    Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
-                ))
-            } else {
-                None
-            };
-            let mut spans = vec![
-                Span::styled(contact.display_name.clone(), name_style),
-                Span::raw(" "),
-            ];
-            if let Some(badge) = archived_badge {
-                spans.push(badge);
-                spans.push(Span::raw(" "));
-            }
-            spans.push(due_span);
-            spans.push(Span::raw("  "));
-            spans.push(Span::raw(next));
-            spans.push(Span::raw("  "));
-            spans.push(Span::styled(tags, Style::default().fg(Color::DarkGray)));
-            let line = Line::from(spans);
-            ListItem::new(line)
-        })
-        .collect();
+    // Build rows only for the window inside the borders; the list can hold
+    // thousands of loaded contacts.
+    let height = usize::from(area.height.saturating_sub(2));
+    let (items, selected) = visible_contact_items(app, height, app.clock.now_utc());
 
     let mut state = ListState::default();
-    state.select(Some(app.selected));
+    state.select(Some(selected));
 
     let list = List::new(items)
         .block(Block::default().borders(Borders::ALL).title("Contacts"))
@@ -193,6 +213,141 @@ fn render_list(frame: &mut Frame<'_>, area: Rect, app: &App) {
     frame.render_stateful_widget(list, area, &mut state);
 }
 
+/// List items for the `height` rows around the selection, plus the selection's
+/// index within them. The window scrolls the way a fresh `ListState` would:
+/// the selected row sits at the bottom once it is past the first screen.
+fn visible_contact_items(app: &App, height: usize, now: i64) -> (Vec<ListItem<'static>>, usize) {
+    let height = height.max(1);
+    let selected = app.selected.min(app.contacts.len().saturating_sub(1));
+    let start = (selected + 1).saturating_sub(height);
+    let items = app
+        .contacts
+        .iter()
+        .skip(start)
+        .take(height)
+        .map(|contact| {
+            ListItem::new(contact_list_line(
+                contact,
+                app.colorize_rows,
+                now,
+                &app.clock,
+            ))
+        })
+        .collect();
+    (items, selected - start)
+}
+
+fn contact_list_line(
+    contact: &ContactListItemDto,
+    colorize_rows: bool,
+    now: i64,
+    clock: &Clock,
+) -> Line<'static> {
+    let archived = contact.archived_at.is_some();
+    let (label, style) = due_badge(contact.due_state);
+    let due_span = Span::styled(format!("[{}]", label), style);
+    let next = contact
+        .next_touchpoint_at
+        .map(|ts| clock.format_timestamp_date(ts))
+        .unwrap_or_else(|| "-".to_string());
+    let last = match (&contact.last_interaction_at, &contact.last_interaction_kind) {
+        (Some(at), Some(kind)) => format!("{} ({kind})", clock.format_relative_past(*at, now)),
+        (Some(at), None) => clock.format_relative_past(*at, now),
+        (None, _) => "-".to_string(),
+    };
+    let tags = if contact.tags.is_empty() {
+        "".to_string()
+    } else {
+        contact
+            .tags
+            .iter()
+            .map(|tag| format!("#{}", tag))
+            .collect::<Vec<_>>()
+            .join(" ")
+    };
+    let name_style = if archived {
+        Style::default().fg(Color::DarkGray)
+    } else {
+        Style::default().add_modifier(Modifier::BOLD)
+    };
+    let initials_style = if archived {
+        Style::default().fg(Color::DarkGray)
+    } else {
+        Style::default()
+            .fg(Color::Black)
+            .bg(initials_color(&contact.id))
+    };
+    let mut spans = vec![
+        Span::styled(
+            format!(" {:<2} ", initials(&contact.display_name)),
+            initials_style,
+        ),
+        Span::raw(" "),
+        Span::styled(contact.display_name.clone(), name_style),
+        Span::raw(" "),
+    ];
+    if is_starred(contact.priority) {
+        spans.push(Span::styled("★", Style::default().fg(Color::Yellow)));
+        spans.push(Span::raw(" "));
+    }
+    if archived {
+        spans.push(Span::styled(
+            "[archived]",
+            Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+        ));
+        spans.push(Span::raw(" "));
+    }
+    if contact.muted_until.is_some() {
+        spans.push(Span::styled("[muted]", Style::default().fg(Color::Magenta)));
+        spans.push(Span::raw(" "));
+    }
+    spans.push(due_span);
+    spans.push(Span::raw("  "));
+    spans.push(Span::raw(next));
+    spans.push(Span::raw("  "));
+    spans.push(Span::raw(last));
+    spans.push(Span::raw("  "));
+    spans.push(Span::styled(tags, Style::default().fg(Color::DarkGray)));
+
+    let line = Line::from(spans);
+    match due_row_color(contact.due_state) {
+        Some(color) if colorize_rows && !archived => line.style(Style::default().fg(color)),
+        _ => line,
+    }
+}
+
+const INITIALS_PALETTE: [Color; 8] = [
+    Color::Cyan,
+    Color::Green,
+    Color::Blue,
+    Color::Magenta,
+    Color::Yellow,
+    Color::LightBlue,
+    Color::LightGreen,
+    Color::LightMagenta,
+];
+
+fn initials_color(id: &ContactId) -> Color {
+    // FNV-1a keeps the color stable across runs for the same contact.
+    let hash = id
+        .as_uuid()
+        .as_bytes()
+        .iter()
+        .fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+            (hash ^ u64::from(*byte)).wrapping_mul(0x0100_0000_01b3)
+        });
+    INITIALS_PALETTE[(hash % INITIALS_PALETTE.len() as u64) as usize]
+}
+
+fn due_row_color(state: DueState) -> Option<Color> {
+    match state {
+        DueState::Overdue => Some(Color::Red),
+        DueState::Today => Some(Color::Yellow),
+        DueState::Soon => Some(Color::Magenta),
+        DueState::Scheduled | DueState::Unscheduled => None,
+    }
+}
+
 fn render_merge_list(frame: &mut Frame<'_>, area: Rect, app: &App) {
     if app.merge_candidates.is_empty() {
         let paragraph = Paragraph::new("No merge candidates.")
@@ -271,6 +426,90 @@ fn render_merge_list(frame: &mut Frame<'_>, area: Rect, app: &App) {
     frame.render_stateful_widget(list, area, &mut state);
 }
 
+fn render_merge_detail(frame: &mut Frame<'_>, area: Rect, detail: &MergeDetail) {
+    let title = format!(
+        "Merge {} into {}",
+        detail.secondary_name, detail.primary_name
+    );
+    let block = Block::default().borders(Borders::ALL).title(title);
+    if detail.rows.is_empty() {
+        let paragraph = Paragraph::new("Loading...")
+            .block(block)
+            .alignment(Alignment::Center);
+        frame.render_widget(paragraph, area);
+        return;
+    }
+
+    let value_width = detail
+        .rows
+        .iter()
+        .map(|row| row.primary.chars().count())
+        .chain(std::iter::once(detail.primary_name.chars().count() + 6))
+        .max()
+        .unwrap_or(0)
+        .min(40);
+    let keep = format!("keep: {}", detail.primary_name);
+    let header = Line::from(Span::styled(
+        format!(
+            "  {:<16}{keep:<value_width$}  merge: {}",
+            "", detail.secondary_name
+        ),
+        Style::default().fg(Color::DarkGray),
+    ));
+    let items: Vec<ListItem> = detail
+        .rows
+        .iter()
+        .map(|row| ListItem::new(merge_field_line(row, value_width)))
+        .collect();
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(2), Constraint::Min(1)])
+        .split(block.inner(area));
+    frame.render_widget(block, area);
+    frame.render_widget(Paragraph::new(header), chunks[0]);
+
+    let mut state = ListState::default();
+    state.select(Some(detail.selected));
+    let list = List::new(items)
+        .highlight_style(Style::default().add_modifier(Modifier::REVERSED))
+        .highlight_symbol("➤ ");
+    frame.render_stateful_widget(list, chunks[1], &mut state);
+}
+
+/// Conflicting fields highlight the winning side; other fields show both values.
+fn merge_field_line(row: &MergeFieldRow, value_width: usize) -> Line<'static> {
+    let side_style = |side: MergePreference| match row.winner {
+        Some(winner) if winner == side => Style::default()
+            .fg(Color::Green)
+            .add_modifier(Modifier::BOLD),
+        Some(_) => Style::default().fg(Color::DarkGray),
+        None => Style::default(),
+    };
+    let value = |value: &str| {
+        if value.is_empty() {
+            "-".to_string()
+        } else {
+            value.chars().take(value_width).collect()
+        }
+    };
+    Line::from(vec![
+        Span::styled(
+            format!("{:<16}", row.field.label()),
+            Style::default().add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(
+            format!("{:<width$}", value(&row.primary), width = value_width),
+            side_style(MergePreference::Primary),
+        ),
+        Span::raw("  "),
+        Span::styled(
+            value(&row.secondary),
+            side_style(MergePreference::Secondary),
+        ),
+    ])
+}
+
 fn render_detail(frame: &mut Frame<'_>, area: Rect, app: &App) {
     let Some(detail) = &app.detail else {
         let paragraph = Paragraph::new("Loading...")
@@ -302,6 +541,13 @@ fn render_detail(frame: &mut Frame<'_>, area: Rect, app: &App) {
             "Timezone: {}",
             detail.timezone.clone().unwrap_or_else(|| "-".to_string())
         )),
+        Line::from(format!(
+            "Organization: {}",
+            detail
+                .organization
+                .clone()
+                .unwrap_or_else(|| "-".to_string())
+        )),
         Line::from(format!(
             "Cadence: {}",
             detail
@@ -313,18 +559,32 @@ fn render_detail(frame: &mut Frame<'_>, area: Rect, app: &App) {
             "Next touchpoint: {}",
             detail
                 .next_touchpoint_at
-                .map(format_timestamp_date)
+                .map(|ts| app.clock.format_timestamp_date(ts))
                 .unwrap_or_else(|| "-".to_string())
         )),
         Line::from(format!(
             "Archived: {}",
             detail
                 .archived_at
-                .map(format_timestamp_date)
+                .map(|ts| app.clock.format_timestamp_date(ts))
                 .unwrap_or_else(|| "-".to_string())
         )),
     ];
 
+    if let Some(reason) = &detail.archive_reason {
+        info_lines.push(Line::from(format!("Archive reason: {}", reason)));
+    }
+
+    if let Some(muted_until) = detail.muted_until {
+        info_lines.push(Line::from(vec![
+            Span::styled("[muted]", Style::default().fg(Color::Magenta)),
+            Span::raw(format!(
+                " until {}",
+                app.clock.format_timestamp_date(muted_until)
+            )),
+        ]));
+    }
+
     if !detail.tags.is_empty() {
         info_lines.push(Line::from(format!(
             "Tags: {}",
@@ -337,12 +597,50 @@ fn render_detail(frame: &mut Frame<'_>, area: Rect, app: &App) {
         )));
     }
 
+    if !detail.aliases.is_empty() {
+        info_lines.push(Line::from(format!(
+            "Aliases: {}",
+            detail.aliases.join(", ")
+        )));
+    }
+
+    if let Some(met) = detail.origin.as_ref().and_then(|origin| origin.summary()) {
+        info_lines.push(Line::from(format!("Met: {}", met)));
+    }
+
+    for touch in &detail.last_touch_context {
+        info_lines.push(Line::from(format!(
+            "Last {}: {}",
+            touch.channel.label(),
+            touch.summary(&app.clock)
+        )));
+    }
+
     if !detail.dates.is_empty() {
         info_lines.push(Line::from("Dates:"));
+        let today = local_today(app.clock.now_utc(), app.clock.local_offset()).ok();
         for date in &detail.dates {
-            let label = format_contact_date_label(date.kind, date.label.as_deref());
-            let date_str = format_date_parts(date.month, date.day, date.year);
-            info_lines.push(Line::from(format!("  {}  {}", label, date_str)));
+            info_lines.push(Line::from(format!(
+                "  {}",
+                format_contact_date(date, today)
+            )));
+        }
+    }
+
+    if !detail.sync_history.is_empty() {
+        let count = detail.sync_history.len();
+        if app.sync_history_expanded {
+            info_lines.push(Line::from(format!("Sync history ({count}):")));
+            for entry in &detail.sync_history {
+                info_lines.push(Line::from(format!(
+                    "  {}  {}  {}",
+                    app.clock.format_timestamp_datetime(entry.synced_at),
+                    entry.source,
+                    entry.fields.join(", ")
+                )));
+            }
+        } else {
+            info_lines.push(Line::from(format!("Sync history ({count}, H to expand)")));
         }
     }
 
@@ -378,28 +676,82 @@ fn render_detail(frame: &mut Frame<'_>, area: Rect, app: &App) {
     if detail.recent_interactions.is_empty() {
         interaction_lines.push(Line::from("No interactions yet."));
     } else {
-        for interaction in &detail.recent_interactions {
-            let when = format_timestamp_datetime(interaction.occurred_at);
-            let header = Line::from(vec![
+        for (index, interaction) in detail.recent_interactions.iter().enumerate() {
+            let when = app.clock.format_timestamp_datetime(interaction.occurred_at);
+            let marker = if app.interactions_focused && index == app.interaction_selected {
+                Span::styled("> ", Style::default().add_modifier(Modifier::BOLD))
+            } else {
+                Span::raw("")
+            };
+            let mut header = vec![
+                marker,
                 Span::styled(when, Style::default().fg(Color::Yellow)),
                 Span::raw(" "),
                 Span::styled(interaction.kind.clone(), Style::default().fg(Color::Cyan)),
-            ]);
-            interaction_lines.push(header);
+            ];
+            let attached = match interaction.attachments.len() {
+                0 => None,
+                1 => Some(" (1 attachment)".to_string()),
+                count => Some(format!(" ({count} attachments)")),
+            };
+            if let Some(attached) = attached {
+                header.push(Span::styled(attached, Style::default().fg(Color::DarkGray)));
+            }
+            interaction_lines.push(Line::from(header));
             if !interaction.note.trim().is_empty() {
                 interaction_lines.push(Line::from(Span::raw(interaction.note.clone())));
             }
+            for attachment in &interaction.attachments {
+                interaction_lines.push(Line::from(vec![
+                    Span::styled(
+                        format!("{} ", attachment.kind.as_str()),
+                        Style::default().fg(Color::DarkGray),
+                    ),
+                    Span::raw(attachment.value.clone()),
+                ]));
+            }
             interaction_lines.push(Line::from(""));
         }
     }
 
+    let title = match app.interactions_loaded_partially() {
+        Some((loaded, total)) => format!("Interactions ({loaded} of {total})"),
+        None => "Interactions".to_string(),
+    };
     let interactions = Paragraph::new(Text::from(interaction_lines))
-        .block(Block::default().borders(Borders::ALL).title("Interactions"))
+        .block(interactions_block(title, app.interactions_focused))
         .scroll((app.detail_scroll as u16, 0))
         .wrap(Wrap { trim: true });
     frame.render_widget(interactions, chunks[1]);
 }
 
+fn interactions_block(title: String, focused: bool) -> Block<'static> {
+    let block = Block::default().borders(Borders::ALL).title(title);
+    if focused {
+        block.border_style(Style::default().fg(Color::Yellow))
+    } else {
+        block
+    }
+}
+
+/// `Birthday  1815-12-10  in 12 days`, plus the reminder lead when set.
+fn format_contact_date(date: &ContactDateDto, today: Option<NaiveDate>) -> String {
+    let label = format_contact_date_label(date.kind, date.label.as_deref());
+    let date_str = format_date_parts(date.month, date.day, date.year);
+    let until =
+        match today.and_then(|today| days_until_next_occurrence(today, date.month, date.day)) {
+            Some(0) => "  today".to_string(),
+            Some(1) => "  tomorrow".to_string(),
+            Some(days) => format!("  in {days} days"),
+            None => String::new(),
+        };
+    let lead = match date.remind_days_before {
+        Some(days) if days > 0 => format!("  (remind {days}d before)"),
+        _ => String::new(),
+    };
+    format!("{label}  {date_str}{until}{lead}")
+}
+
 fn format_contact_date_label(
     kind: knotter_core::domain::ContactDateKind,
     label: Option<&str>,
@@ -436,8 +788,15 @@ fn render_contact_form(frame: &mut Frame<'_>, area: Rect, title: &str, form: &Co
             "Must be now or later. Ctrl+N sets to now.",
             Style::default().fg(Color::DarkGray),
         )),
-        Line::from(""),
     ];
+    if form.contact_id.is_none() {
+        lines.push(field_line(
+            "Tags (comma separated)",
+            &form.tags,
+            form.focus == 7,
+        ));
+    }
+    lines.push(Line::from(""));
 
     let save_style = if form.is_save_focus() {
         Style::default().fg(Color::Black).bg(Color::LightGreen)
@@ -464,7 +823,12 @@ fn render_note_form(frame: &mut Frame<'_>, area: Rect, form: &NoteForm) {
     let modal = centered_rect(70, 70, area);
     frame.render_widget(Clear, modal);
 
-    let block = Block::default().borders(Borders::ALL).title("Add Note");
+    let title = if form.is_edit() {
+        "Edit Note"
+    } else {
+        "Add Note"
+    };
+    let block = Block::default().borders(Borders::ALL).title(title);
     let mut lines = Vec::new();
     lines.push(field_line("Kind", &form.kind, form.focus == 0));
     lines.push(field_line("When (optional)", &form.when, form.focus == 1));
@@ -581,6 +945,95 @@ fn render_tag_editor(frame: &mut Frame<'_>, area: Rect, editor: &TagEditor) {
     frame.render_widget(buttons, chunks[2]);
 }
 
+fn render_date_editor(frame: &mut Frame<'_>, area: Rect, editor: &DateEditor, clock: &Clock) {
+    let modal = centered_rect(70, 70, area);
+    frame.render_widget(Clear, modal);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(4), Constraint::Length(9)])
+        .split(modal);
+
+    let today = local_today(clock.now_utc(), clock.local_offset()).ok();
+    let items: Vec<ListItem> = if editor.dates.is_empty() {
+        vec![ListItem::new(Line::from("No dates yet."))]
+    } else {
+        editor
+            .dates
+            .iter()
+            .map(|date| ListItem::new(Line::from(format_contact_date(date, today))))
+            .collect()
+    };
+    let mut state = ListState::default();
+    if editor.focus == DateEditorFocus::List && !editor.dates.is_empty() {
+        state.select(Some(editor.selected_index));
+    }
+    let list_block = Block::default()
+        .borders(Borders::ALL)
+        .title(format!("Dates for {}", editor.display_name));
+    let list_block = if editor.focus == DateEditorFocus::List {
+        list_block.border_style(Style::default().fg(Color::Yellow))
+    } else {
+        list_block
+    };
+    let list = List::new(items)
+        .block(list_block)
+        .highlight_style(
+            Style::default()
+                .fg(Color::Black)
+                .bg(Color::LightGreen)
+                .add_modifier(Modifier::BOLD),
+        )
+        .highlight_symbol("➤ ");
+    frame.render_stateful_widget(list, chunks[0], &mut state);
+
+    let add_style = if editor.focus == DateEditorFocus::Add {
+        Style::default().fg(Color::Black).bg(Color::LightGreen)
+    } else {
+        Style::default().fg(Color::Green)
+    };
+    let close_style = if editor.focus == DateEditorFocus::Close {
+        Style::default().fg(Color::Black).bg(Color::LightRed)
+    } else {
+        Style::default().fg(Color::Red)
+    };
+    let lines = vec![
+        field_line(
+            "Kind",
+            &format!("< {} >", editor.kind.as_str()),
+            editor.focus == DateEditorFocus::Kind,
+        ),
+        field_line(
+            "Label (required for custom)",
+            &editor.label,
+            editor.focus == DateEditorFocus::Label,
+        ),
+        field_line(
+            "Month",
+            &editor.month,
+            editor.focus == DateEditorFocus::Month,
+        ),
+        field_line("Day", &editor.day, editor.focus == DateEditorFocus::Day),
+        field_line(
+            "Year (optional)",
+            &editor.year,
+            editor.focus == DateEditorFocus::Year,
+        ),
+        Line::from(Span::styled(
+            "Feb 29 without a year falls on Feb 28 in non-leap years.",
+            Style::default().fg(Color::DarkGray),
+        )),
+        Line::from(vec![
+            Span::styled("[Add]", add_style),
+            Span::raw("  "),
+            Span::styled("[Close]", close_style),
+        ]),
+    ];
+    let form =
+        Paragraph::new(lines).block(Block::default().borders(Borders::ALL).title("Add date"));
+    frame.render_widget(form, chunks[1]);
+}
+
 fn render_merge_picker(frame: &mut Frame<'_>, area: Rect, picker: &MergePicker) {
     let modal = centered_rect(70, 70, area);
     frame.render_widget(Clear, modal);
@@ -694,7 +1147,11 @@ fn render_schedule_form(frame: &mut Frame<'_>, area: Rect, form: &ScheduleForm)
         .borders(Borders::ALL)
         .title("Schedule Touchpoint");
     let mut lines = vec![
-        field_line("Date (YYYY-MM-DD)", &form.date, form.focus == 0),
+        field_line(
+            "Date (YYYY-MM-DD, +2w, next-mon)",
+            &form.date,
+            form.focus == 0,
+        ),
         field_line("Time (HH:MM)", &form.time, form.focus == 1),
         Line::from(Span::styled(
             "Must be now or later. Ctrl+N sets to now.",
@@ -724,6 +1181,43 @@ fn render_schedule_form(frame: &mut Frame<'_>, area: Rect, form: &ScheduleForm)
     frame.render_widget(paragraph, modal);
 }
 
+fn render_archive_form(frame: &mut Frame<'_>, area: Rect, form: &ArchiveForm) {
+    let modal = centered_rect(60, 40, area);
+    frame.render_widget(Clear, modal);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(format!("Archive {}", form.display_name));
+    let mut lines = vec![
+        field_line("Reason (optional)", &form.reason, form.focus == 0),
+        Line::from(Span::styled(
+            "Shown in detail view and `knotter show`.",
+            Style::default().fg(Color::DarkGray),
+        )),
+        Line::from(""),
+    ];
+
+    let save_style = if form.is_save_focus() {
+        Style::default().fg(Color::Black).bg(Color::LightGreen)
+    } else {
+        Style::default().fg(Color::Green)
+    };
+    let cancel_style = if form.is_cancel_focus() {
+        Style::default().fg(Color::Black).bg(Color::LightRed)
+    } else {
+        Style::default().fg(Color::Red)
+    };
+
+    lines.push(Line::from(vec![
+        Span::styled("[Archive]", save_style),
+        Span::raw("  "),
+        Span::styled("[Cancel]", cancel_style),
+    ]));
+
+    let paragraph = Paragraph::new(lines).block(block);
+    frame.render_widget(paragraph, modal);
+}
+
 fn render_confirm(frame: &mut Frame<'_>, area: Rect, state: &ConfirmState) {
     let modal = centered_rect(50, 30, area);
     frame.render_widget(Clear, modal);
@@ -733,22 +1227,68 @@ fn render_confirm(frame: &mut Frame<'_>, area: Rect, state: &ConfirmState) {
     frame.render_widget(paragraph, modal);
 }
 
+fn render_action_log(frame: &mut Frame<'_>, area: Rect, app: &App, view: &ActionLogView) {
+    let modal = centered_rect(80, 70, area);
+    frame.render_widget(Clear, modal);
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(format!("Log ({}, newest first)", app.action_log.len()));
+
+    if app.action_log.is_empty() {
+        let paragraph = Paragraph::new("Nothing logged yet.")
+            .block(block)
+            .alignment(Alignment::Center);
+        frame.render_widget(paragraph, modal);
+        return;
+    }
+
+    let lines: Vec<Line> = app
+        .action_log
+        .iter()
+        .rev()
+        .skip(view.scroll)
+        .map(|entry| {
+            let (label, style) = match entry.severity {
+                LogSeverity::Info => ("info ", Style::default().fg(Color::Green)),
+                LogSeverity::Error => ("error", Style::default().fg(Color::Red)),
+            };
+            let at = app
+                .clock
+                .local_naive(entry.at)
+                .format("%H:%M:%S")
+                .to_string();
+            Line::from(vec![
+                Span::styled(format!("{at} "), Style::default().fg(Color::DarkGray)),
+                Span::styled(format!("{label} "), style.add_modifier(Modifier::BOLD)),
+                Span::styled(entry.message.clone(), style),
+            ])
+        })
+        .collect();
+    let paragraph = Paragraph::new(lines)
+        .block(block)
+        .wrap(Wrap { trim: false });
+    frame.render_widget(paragraph, modal);
+}
+
 fn render_help(frame: &mut Frame<'_>, area: Rect) {
     let modal = centered_rect(70, 80, area);
     frame.render_widget(Clear, modal);
 
     let text = vec![
-        Line::from("Global: q quit, Ctrl+C quit, ? help"),
-        Line::from("List: j/k move, enter detail, / filter, a add, e edit, n note, t tags, s schedule, x clear, A archive, v archived, m merges, M merge-with"),
+        Line::from("Global: q quit, Ctrl+C quit, ? help, ! message log (list, detail, merge screens)"),
+        Line::from("Message log: j/k scroll, g/G newest/oldest, PgUp/PgDn page, esc or ! close"),
+        Line::from("List: j/k move, enter detail, / filter, 1-5 due filter (0 restores), a add, e edit, n note, t tags, s schedule, x clear, A archive/unarchive, * star/unstar, v active/archived/all, m merges, M merge-with"),
         Line::from("Filter: enter apply, esc cancel"),
-        Line::from("Detail: esc back, j/k scroll, e edit, n note, t tags, s schedule, x clear, A archive, m merges, M merge-with"),
+        Line::from("Detail: esc back, j/k scroll, e edit, n note, t tags, b dates, s schedule, x clear, A archive, H sync history, m merges, M merge-with"),
+        Line::from("Detail interactions: tab focus, j/k select, e edit note, d delete note"),
         Line::from(
-            "Merge: j/k move, enter merge, p prefer, d dismiss, a/A apply-all, r refresh, esc back",
+            "Merge: j/k move, enter details, p prefer, d dismiss, a/A apply-all, r refresh, esc back",
         ),
+        Line::from("Merge details: j/k move, space toggle winner, h/l keep left/right, enter merge, p prefer, d dismiss, esc back"),
         Line::from("Merge picker: tab to list, j/k move, enter merge, ctrl+r refresh, esc back"),
         Line::from("Modals: tab/shift+tab move, enter activate, esc cancel, Ctrl+N set now (contact/schedule)"),
         Line::from(""),
-        Line::from("Filter syntax: #tag, due:overdue|today|soon|any|none, archived:true|false, text matches name/email/phone/handle"),
+        Line::from("Filter syntax: #tag, @handle (exact), due:overdue|today|soon|any|none, archived:true|false, text matches name/email/phone/handle"),
     ];
 
     let paragraph = Paragraph::new(text)
@@ -809,3 +1349,178 @@ fn centered_rect(percent_x: u16, percent_y: u16, rect: Rect) -> Rect {
         ])
         .split(popup_layout[1])[1]
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{contact_list_line, due_summary_line, initials_color, visible_contact_items};
+    use crate::app::App;
+    use knotter_core::domain::ContactId;
+    use knotter_core::dto::ContactListItemDto;
+    use knotter_core::rules::DueState;
+    use knotter_core::time::Clock;
+    use ratatui::style::{Color, Modifier};
+
+    const NOW: i64 = 1_700_000_000;
+
+    fn contact(name: &str, due_state: DueState, archived: bool) -> ContactListItemDto {
+        ContactListItemDto {
+            id: ContactId::new(),
+            display_name: name.to_string(),
+            due_state,
+            next_touchpoint_at: None,
+            archived_at: archived.then_some(1_700_000_000),
+            archive_reason: None,
+            muted_until: None,
+            tags: vec!["friends".to_string()],
+            last_interaction_at: None,
+            last_interaction_kind: None,
+            organization: None,
+            priority: 0,
+        }
+    }
+
+    fn text(line: &ratatui::text::Line<'_>) -> String {
+        line.spans
+            .iter()
+            .map(|span| span.content.as_ref())
+            .collect()
+    }
+
+    #[test]
+    fn contact_line_renders_initials_badge() {
+        let item = contact("Ada Lovelace", DueState::Overdue, false);
+        let line = contact_list_line(&item, false, NOW, &Clock::default());
+        assert_eq!(text(&line), " AL  Ada Lovelace [overdue]  -  -  #friends");
+        assert_eq!(line.spans[0].style.bg, Some(initials_color(&item.id)));
+        assert_eq!(line.style.fg, None);
+
+        let single = contact("Cher", DueState::Unscheduled, false);
+        let line = contact_list_line(&single, false, NOW, &Clock::default());
+        assert_eq!(text(&line), " CH  Cher [unscheduled]  -  -  #friends");
+    }
+
+    #[test]
+    fn contact_line_colorizes_rows_by_due_state() {
+        let line = contact_list_line(
+            &contact("Ada", DueState::Overdue, false),
+            true,
+            NOW,
+            &Clock::default(),
+        );
+        assert_eq!(line.style.fg, Some(Color::Red));
+        assert!(line.spans[2].style.add_modifier.contains(Modifier::BOLD));
+
+        let line = contact_list_line(
+            &contact("Ada", DueState::Today, false),
+            true,
+            NOW,
+            &Clock::default(),
+        );
+        assert_eq!(line.style.fg, Some(Color::Yellow));
+
+        let line = contact_list_line(
+            &contact("Ada", DueState::Scheduled, false),
+            true,
+            NOW,
+            &Clock::default(),
+        );
+        assert_eq!(line.style.fg, None);
+    }
+
+    #[test]
+    fn contact_line_keeps_archived_rows_dim() {
+        let line = contact_list_line(
+            &contact("Émile Zola", DueState::Overdue, true),
+            true,
+            NOW,
+            &Clock::default(),
+        );
+        assert_eq!(
+            text(&line),
+            " ÉZ  Émile Zola [archived] [overdue]  -  -  #friends"
+        );
+        assert_eq!(line.style.fg, None);
+        assert_eq!(line.spans[0].style.fg, Some(Color::DarkGray));
+        assert_eq!(line.spans[0].style.bg, None);
+        assert_eq!(line.spans[2].style.fg, Some(Color::DarkGray));
+    }
+
+    #[test]
+    fn contact_line_flags_muted_contacts() {
+        let mut item = contact("Ada", DueState::Overdue, false);
+        item.muted_until = Some(1_800_000_000);
+        let line = contact_list_line(&item, false, NOW, &Clock::default());
+        assert_eq!(text(&line), " AD  Ada [muted] [overdue]  -  -  #friends");
+        assert_eq!(line.spans[4].style.fg, Some(Color::Magenta));
+    }
+
+    #[test]
+    fn contact_line_stars_high_priority_contacts() {
+        let mut item = contact("Ada", DueState::Overdue, false);
+        item.priority = 4;
+        let line = contact_list_line(&item, false, NOW, &Clock::default());
+        assert_eq!(text(&line), " AD  Ada [overdue]  -  -  #friends");
+
+        item.priority = 5;
+        let line = contact_list_line(&item, false, NOW, &Clock::default());
+        assert_eq!(text(&line), " AD  Ada ★ [overdue]  -  -  #friends");
+        assert_eq!(line.spans[4].style.fg, Some(Color::Yellow));
+    }
+
+    #[test]
+    fn contact_line_shows_last_interaction_relative_to_now() {
+        let mut item = contact("Ada", DueState::Overdue, false);
+        item.last_interaction_at = Some(NOW - 21 * 86_400);
+        item.last_interaction_kind = Some("call".to_string());
+        let line = contact_list_line(&item, false, NOW, &Clock::default());
+        assert_eq!(
+            text(&line),
+            " AD  Ada [overdue]  -  3w ago (call)  #friends"
+        );
+    }
+
+    #[test]
+    fn due_summary_line_lists_counts_with_quick_filter_keys() {
+        let line = due_summary_line(&[
+            (DueState::Overdue, 3),
+            (DueState::Today, 1),
+            (DueState::Soon, 0),
+            (DueState::Scheduled, 12),
+            (DueState::Unscheduled, 4),
+        ]);
+        assert_eq!(
+            text(&line),
+            "1 overdue 3  2 today 1  3 soon 0  4 scheduled 12  5 unscheduled 4"
+        );
+        assert_eq!(line.spans[2].style.fg, Some(Color::Red));
+    }
+
+    #[test]
+    fn visible_items_are_bounded_by_viewport_height() {
+        let mut app = App::new(7, None, false, false);
+        app.contacts = (0..3_000)
+            .map(|idx| contact(&format!("Contact {idx}"), DueState::Scheduled, false))
+            .collect();
+
+        let (items, selected) = visible_contact_items(&app, 20, NOW);
+        assert_eq!(items.len(), 20);
+        assert_eq!(selected, 0);
+
+        app.selected = 2_500;
+        let (items, selected) = visible_contact_items(&app, 20, NOW);
+        assert_eq!(items.len(), 20);
+        assert_eq!(selected, 19);
+
+        app.contacts.truncate(5);
+        app.selected = 4;
+        let (items, selected) = visible_contact_items(&app, 20, NOW);
+        assert_eq!(items.len(), 5);
+        assert_eq!(selected, 4);
+    }
+
+    #[test]
+    fn initials_color_is_deterministic_per_contact() {
+        let id = ContactId::new();
+        assert_eq!(initials_color(&id), initials_color(&id));
+    }
+}
@@ -25,13 +25,45 @@ pub fn parse_interaction_kind(raw: &str) -> Result<InteractionKind> {
     }
 }
 
-pub fn format_interaction_kind(kind: &InteractionKind) -> String {
-    match kind {
-        InteractionKind::Call => "call".to_string(),
-        InteractionKind::Text => "text".to_string(),
-        InteractionKind::Hangout => "hangout".to_string(),
-        InteractionKind::Email => "email".to_string(),
-        InteractionKind::Telegram => "telegram".to_string(),
-        InteractionKind::Other(label) => format!("other:{}", label),
+/// Two-letter initials for a display name: first letters of the first and last
+/// words, or the first two letters of a single word.
+pub fn initials(name: &str) -> String {
+    let words: Vec<&str> = name
+        .split_whitespace()
+        .filter(|word| word.chars().any(char::is_alphanumeric))
+        .collect();
+    let first_letter = |word: &str| word.chars().find(|ch| ch.is_alphanumeric());
+    let letters: Vec<char> = match words.as_slice() {
+        [] => Vec::new(),
+        [word] => word
+            .chars()
+            .filter(|ch| ch.is_alphanumeric())
+            .take(2)
+            .collect(),
+        [first, .., last] => first_letter(first)
+            .into_iter()
+            .chain(first_letter(last))
+            .collect(),
+    };
+    if letters.is_empty() {
+        return "?".to_string();
+    }
+    letters.into_iter().flat_map(char::to_uppercase).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::initials;
+
+    #[test]
+    fn initials_handles_single_multi_word_and_non_ascii_names() {
+        assert_eq!(initials("Ada Lovelace"), "AL");
+        assert_eq!(initials("Grace Brewster Hopper"), "GH");
+        assert_eq!(initials("cher"), "CH");
+        assert_eq!(initials("Ö"), "Ö");
+        assert_eq!(initials("élodie  (work) Dupré"), "ÉD");
+        assert_eq!(initials("李 小龍"), "李小");
+        assert_eq!(initials("  "), "?");
+        assert_eq!(initials("--- ???"), "?");
     }
 }